@@ -15,7 +15,7 @@
  * 3. Eventually consolidate to a fully grouped AppState
  */
 
-use crate::database::Database;
+use crate::database::AsyncDatabase;
 use crate::services::screen::ScreenCaptureService;
 use crate::services::llava::LlavaService;
 use crate::services::model_installer::ModelInstallerService;
@@ -51,7 +51,7 @@ use tokio::sync::Mutex as TokioMutex;
 /// Core infrastructure services
 pub struct CoreServices {
     /// Main database connection
-    pub db: Mutex<Database>,
+    pub db: AsyncDatabase,  // v3.9.0: queries run on the blocking pool
     /// Screen capture service
     pub screen: ScreenCaptureService,
     /// LLaVA vision model service
@@ -160,7 +160,7 @@ impl AppState {
     // === Convenience accessors for backwards compatibility ===
 
     /// Get database reference
-    pub fn db(&self) -> &Mutex<Database> {
+    pub fn db(&self) -> &AsyncDatabase {
         &self.core.db
     }
 
@@ -197,7 +197,7 @@ impl AppState {
 /// Legacy AppState structure for backwards compatibility
 /// TODO: Migrate commands to use new grouped AppState, then remove this
 pub struct LegacyAppState {
-    pub db: Mutex<Database>,
+    pub db: AsyncDatabase,  // v3.9.0: queries run on the blocking pool
     pub screen_service: ScreenCaptureService,
     pub llava_service: Mutex<LlavaService>,
     pub model_installer: Arc<ModelInstallerService>,
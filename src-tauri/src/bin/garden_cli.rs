@@ -0,0 +1,272 @@
+// garden-cli (v3.9.0)
+// Headless access to the Garden of Eden data directory: chat, RAG search,
+// memory export and diagnostics without the Tauri UI
+
+use garden_of_eden_v3::headless::{Headless, HeadlessOptions};
+use garden_of_eden_v3::services::memory_browser::{MemoryFilter, MemorySort};
+use serde::Serialize;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: garden-cli [--data-dir DIR] [--profile ID] [--json] <command> [options]
+
+Commands:
+  chat <message>        Send a message through the persona + RAG pipeline
+      --conversation ID   Continue a conversation (default: start a new one)
+      --no-remember       Don't store the exchange in episodic memory
+  search <query>        Search episodic memory
+      --top-k N           Number of results (default: 5)
+  export-memories       Export episodic memories
+      --output FILE       Write to a file instead of stdout
+      --format FORMAT     json (default) or jsonl
+      --pinned            Only pinned memories
+      --asserted          Only memories written or corrected by the user
+      --query TEXT        Only memories containing TEXT
+      --since UNIX        Only memories created at or after UNIX seconds
+  diagnostics           Storage, data and Ollama health
+      --embedding         Also load the embedding model
+  help                  Show this message
+
+Encrypted storage is unlocked from the OS keychain, or with the passphrase in
+GARDEN_PASSPHRASE. Set RUST_LOG for logs.
+";
+
+/// A usage error (exit code 2) or a failure (exit code 1)
+enum CliError {
+    Usage(String),
+    Failed(anyhow::Error),
+}
+
+impl From<anyhow::Error> for CliError {
+    fn from(e: anyhow::Error) -> Self {
+        CliError::Failed(e)
+    }
+}
+
+impl From<std::io::Error> for CliError {
+    fn from(e: std::io::Error) -> Self {
+        CliError::Failed(e.into())
+    }
+}
+
+impl From<serde_json::Error> for CliError {
+    fn from(e: serde_json::Error) -> Self {
+        CliError::Failed(e.into())
+    }
+}
+
+type CliResult<T> = Result<T, CliError>;
+
+/// Remove `--flag` from the arguments, true if it was there
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    match args.iter().position(|a| a == flag) {
+        Some(index) => {
+            args.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Remove `--flag VALUE` from the arguments
+fn take_value(args: &mut Vec<String>, flag: &str) -> CliResult<Option<String>> {
+    let Some(index) = args.iter().position(|a| a == flag) else {
+        return Ok(None);
+    };
+    if index + 1 >= args.len() {
+        return Err(CliError::Usage(format!("{} needs a value", flag)));
+    }
+    let value = args.remove(index + 1);
+    args.remove(index);
+    Ok(Some(value))
+}
+
+fn take_parsed<T: std::str::FromStr>(args: &mut Vec<String>, flag: &str) -> CliResult<Option<T>> {
+    match take_value(args, flag)? {
+        Some(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|_| CliError::Usage(format!("Invalid value for {}: {}", flag, value))),
+        None => Ok(None),
+    }
+}
+
+/// The single positional argument left after the options were taken
+fn positional(args: Vec<String>, name: &str) -> CliResult<String> {
+    if let Some(unknown) = args.iter().find(|a| a.starts_with("--")) {
+        return Err(CliError::Usage(format!("Unknown option: {}", unknown)));
+    }
+    let text = args.join(" ");
+    if text.trim().is_empty() {
+        return Err(CliError::Usage(format!("Missing <{}>", name)));
+    }
+    Ok(text)
+}
+
+fn no_more_args(args: &[String]) -> CliResult<()> {
+    match args.first() {
+        Some(extra) => Err(CliError::Usage(format!("Unexpected argument: {}", extra))),
+        None => Ok(()),
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> CliResult<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn one_line(text: &str, max_chars: usize) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() <= max_chars {
+        flat
+    } else {
+        format!("{}…", flat.chars().take(max_chars).collect::<String>())
+    }
+}
+
+async fn chat(headless: &Headless, mut args: Vec<String>, json: bool) -> CliResult<()> {
+    let conversation = take_value(&mut args, "--conversation")?;
+    let remember = !take_flag(&mut args, "--no-remember");
+    let message = positional(args, "message")?;
+
+    let reply = headless.chat(&message, conversation.as_deref(), remember).await?;
+    if json {
+        return print_json(&reply);
+    }
+    println!("{}", reply.response);
+    if !reply.citations.is_empty() {
+        println!();
+        for citation in &reply.citations {
+            println!("[{}] {}: {}", citation.index, citation.label, one_line(&citation.snippet, 80));
+        }
+    }
+    eprintln!("conversation: {}", reply.conversation_id);
+    Ok(())
+}
+
+async fn search(headless: &Headless, mut args: Vec<String>, json: bool) -> CliResult<()> {
+    let top_k = take_parsed::<usize>(&mut args, "--top-k")?.unwrap_or(5).max(1);
+    let query = positional(args, "query")?;
+
+    let hits = headless.search(&query, top_k).await?;
+    if json {
+        return print_json(&hits);
+    }
+    if hits.is_empty() {
+        println!("No matching memories");
+    }
+    for hit in &hits {
+        let date = chrono::DateTime::from_timestamp(hit.created_at, 0)
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        println!("{:.3}  {}  {}", hit.score, date, one_line(&hit.user_message, 70));
+        if !hit.ai_response.is_empty() {
+            println!("              {}", one_line(&hit.ai_response, 70));
+        }
+    }
+    Ok(())
+}
+
+fn export_memories(headless: &Headless, mut args: Vec<String>) -> CliResult<()> {
+    let output = take_value(&mut args, "--output")?.map(PathBuf::from);
+    let format = take_value(&mut args, "--format")?.unwrap_or_else(|| "json".to_string());
+    let filter = MemoryFilter {
+        pinned_only: take_flag(&mut args, "--pinned"),
+        user_asserted_only: take_flag(&mut args, "--asserted"),
+        query: take_value(&mut args, "--query")?,
+        created_after: take_parsed(&mut args, "--since")?,
+        sort: MemorySort::Oldest,
+        ..Default::default()
+    };
+    no_more_args(&args)?;
+
+    let items = headless.export_memories(&filter)?;
+    let mut text = match format.as_str() {
+        "json" => serde_json::to_string_pretty(&items)?,
+        "jsonl" => items
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<Vec<_>, _>>()?
+            .join("\n"),
+        other => return Err(CliError::Usage(format!("Unknown format: {} (json or jsonl)", other))),
+    };
+    text.push('\n');
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, text)?;
+            eprintln!("Exported {} memories to {}", items.len(), path.display());
+        }
+        None => std::io::stdout().write_all(text.as_bytes())?,
+    }
+    Ok(())
+}
+
+async fn diagnostics(headless: &Headless, mut args: Vec<String>, json: bool) -> CliResult<()> {
+    let check_embedding = take_flag(&mut args, "--embedding");
+    no_more_args(&args)?;
+
+    let report = headless.diagnostics(check_embedding).await?;
+    if json {
+        return print_json(&report);
+    }
+    println!("version:       {}", report.version);
+    println!("data dir:      {}", report.data_dir.display());
+    println!("database:      {} ({} KB)", report.db_path.display(), report.db_size_bytes / 1024);
+    println!("encrypted:     {}", report.encrypted);
+    println!("integrity:     {}", report.integrity);
+    for (table, count) in &report.counts {
+        println!("{:<15}{}", format!("{}:", table), count);
+    }
+    println!("chat model:    {}", report.chat_model);
+    println!("ollama:        {}", if report.ollama_reachable { "reachable" } else { "unreachable" });
+    if let Some(mode) = &report.embedding_mode {
+        println!("embedding:     {}", mode);
+    }
+    Ok(())
+}
+
+async fn run(mut args: Vec<String>) -> CliResult<()> {
+    let options = HeadlessOptions {
+        data_dir: take_value(&mut args, "--data-dir")?.map(PathBuf::from),
+        profile: take_value(&mut args, "--profile")?,
+        passphrase: std::env::var("GARDEN_PASSPHRASE").ok().filter(|p| !p.is_empty()),
+    };
+    let json = take_flag(&mut args, "--json");
+
+    if args.is_empty() || matches!(args[0].as_str(), "help" | "--help" | "-h") {
+        print!("{}", USAGE);
+        return Ok(());
+    }
+    let command = args.remove(0);
+    if !matches!(command.as_str(), "chat" | "search" | "export-memories" | "diagnostics") {
+        return Err(CliError::Usage(format!("Unknown command: {}", command)));
+    }
+
+    let headless = Headless::open(options)?;
+    match command.as_str() {
+        "chat" => chat(&headless, args, json).await,
+        "search" => search(&headless, args, json).await,
+        "export-memories" => export_memories(&headless, args),
+        _ => diagnostics(&headless, args, json).await,
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+
+    match run(std::env::args().skip(1).collect()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(CliError::Usage(message)) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            ExitCode::from(2)
+        }
+        Err(CliError::Failed(e)) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,36 @@
+/**
+ * Activity Timeline Commands (v3.9.0)
+ */
+
+use crate::services::activity_timeline::{ActivityTimelineService, DailySummary, DayTimeline};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Get the activity timeline for a day (YYYY-MM-DD, defaults to today)
+#[tauri::command]
+pub async fn timeline_get_day(
+    date: Option<String>,
+    service: State<'_, Arc<ActivityTimelineService>>,
+) -> AppResult<DayTimeline> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .get_day(date.as_deref())
+            .map_err(|e| format!("Failed to get timeline: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Generate an LLM summary of what the user worked on during a day
+#[tauri::command]
+pub async fn timeline_generate_summary(
+    date: Option<String>,
+    service: State<'_, Arc<ActivityTimelineService>>,
+) -> AppResult<DailySummary> {
+    Ok(service
+        .generate_summary(date.as_deref())
+        .await
+        .map_err(|e| format!("Failed to generate daily summary: {}", e))?)
+}
@@ -0,0 +1,77 @@
+/**
+ * Agent Undo Commands (v3.9.0)
+ *
+ * Undo file and git changes made by agent tools, one at a time or per plan
+ */
+
+use crate::services::agent_actions::{AgentAction, AgentActionLog, RollbackReport};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Undo the most recent agent action that hasn't been undone yet.
+/// `force` restores even if the file was changed after the agent's action.
+#[tauri::command]
+pub async fn agent_undo_last_action(
+    force: Option<bool>,
+    log: State<'_, Arc<AgentActionLog>>,
+) -> AppResult<AgentAction> {
+    let log_clone = Arc::clone(&log.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        log_clone
+            .undo_last(force.unwrap_or(false))
+            .map_err(|e| format!("Failed to undo agent action: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn agent_undo_action(
+    action_id: String,
+    force: Option<bool>,
+    log: State<'_, Arc<AgentActionLog>>,
+) -> AppResult<AgentAction> {
+    let log_clone = Arc::clone(&log.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        log_clone
+            .undo_action(&action_id, force.unwrap_or(false))
+            .map_err(|e| format!("Failed to undo agent action: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Undo every file and git change made while executing a plan
+#[tauri::command]
+pub async fn agent_rollback_plan(
+    plan_id: String,
+    force: Option<bool>,
+    log: State<'_, Arc<AgentActionLog>>,
+) -> AppResult<RollbackReport> {
+    let log_clone = Arc::clone(&log.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        log_clone
+            .rollback_group(&plan_id, force.unwrap_or(false))
+            .map_err(|e| format!("Failed to roll back plan: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// List logged agent actions, newest first (optionally only one plan's)
+#[tauri::command]
+pub async fn agent_list_actions(
+    plan_id: Option<String>,
+    limit: Option<usize>,
+    log: State<'_, Arc<AgentActionLog>>,
+) -> AppResult<Vec<AgentAction>> {
+    let log_clone = Arc::clone(&log.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        log_clone
+            .list_actions(plan_id.as_deref(), limit.unwrap_or(50))
+            .map_err(|e| format!("Failed to list agent actions: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
@@ -1,15 +1,54 @@
+use crate::AppResult;
 use crate::AppState;
-use crate::services::ollama;
+use crate::database::AsyncDatabase;
+use crate::services::charts::{self, ChartFormat, ChartImage, ChartService};
+use crate::services::chat_attachments::{self, DescribedImage, ImageAttachment};
+use crate::services::context_inspector::{self, ContextInspection};
+use crate::services::conversation_documents::{self, ConversationDocumentService, DocumentChunk};
+use crate::services::conversation_language::{ConversationLanguage, ConversationLanguageService};
+use crate::services::learning_style_adapter::LearningStyleAdapterService;
+use crate::services::localization;
+use crate::services::model_context;
+use crate::services::model_router::ModelRouterService;
+use crate::services::ollama::{self, GenerationOverrides};
+use crate::services::provenance::Citation;
+use crate::services::rag_v2::RagServiceV2;
+use crate::services::response_variants::{ResponseVariant, ResponseVariantService};
+use crate::services::response_verifier::ResponseVerifierService;
+use crate::services::response_formatter::{self, FormattedResponse, ResponseSegment};
+use crate::services::sentiment::SentimentService;
+use crate::services::structured_output::{self, StructuredOptions, StructuredOutput};
+use crate::services::tool_sets;
+use crate::services::visual_analyzer::VisualAnalyzerService;
 use crate::services::webhook_triggers::WebhookTriggerEvent;
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter, State};
 use std::sync::Arc;
+use tokio::sync::Mutex as TokioMutex;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ChatRequest {
     pub message: String,
     pub conversation_id: Option<String>,
     pub context_level: Option<i32>,
+    /// Images to describe and answer about (v3.9.0)
+    #[serde(default)]
+    pub images: Vec<ImageAttachment>,
+    /// Local files to attach to the conversation for document Q&A (v3.9.0)
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+/// A steered regeneration of an assistant reply (v3.9.0)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegenerateResponse {
+    pub variant: ResponseVariant,
+    #[serde(default)]
+    pub segments: Vec<ResponseSegment>,
+    #[serde(default)]
+    pub citations: Vec<Citation>,
+    #[serde(default)]
+    pub groundedness: Option<f32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -17,27 +56,41 @@ pub struct ChatResponse {
     pub conversation_id: String,
     pub message_id: String,
     pub response: String,
+    /// Structured segments (text, code, math, table) for rich rendering (v3.9.0)
+    #[serde(default)]
+    pub segments: Vec<ResponseSegment>,
+    /// Memories the response was based on (v3.9.0)
+    #[serde(default)]
+    pub citations: Vec<Citation>,
+    /// Share of the response supported by the cited memories (v3.9.0)
+    #[serde(default)]
+    pub groundedness: Option<f32>,
+    /// Attached images as the vision model described them (v3.9.0)
+    #[serde(default)]
+    pub images: Vec<DescribedImage>,
+    /// Passages of the conversation's attached files sent with the message (v3.9.0)
+    #[serde(default)]
+    pub document_chunks: Vec<DocumentChunk>,
+    /// Charts drawn for the response by the create_chart tool, as SVG (v3.9.0)
+    #[serde(default)]
+    pub charts: Vec<ChartImage>,
 }
 
-/// Chat command - main AI interaction
-#[tauri::command]
-pub async fn chat(
-    state: State<'_, AppState>,
-    request: ChatRequest,
-) -> Result<ChatResponse, String> {
-    log::info!("Chat command called with message: {}", request.message);
-    let start_time = std::time::Instant::now();
-
-    // Generate IDs
-    let conversation_id = request.conversation_id.unwrap_or_else(|| {
-        format!("conv_{}", chrono::Utc::now().timestamp_millis())
-    });
-    let message_id = format!("msg_{}", chrono::Utc::now().timestamp_millis());
-
-    // Block 1: Save user message to database (scoped to release lock)
-    let is_new_conversation;
-    {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+/// Save a user message, creating the conversation if it doesn't exist
+///
+/// Returns whether the conversation is new. Runs on the blocking pool (v3.9.0).
+async fn save_user_message(
+    db: &AsyncDatabase,
+    conversation_id: &str,
+    message_id: &str,
+    content: &str,
+    context_level: i32,
+    title: &'static str,
+) -> Result<bool, String> {
+    let conversation_id = conversation_id.to_string();
+    let message_id = message_id.to_string();
+    let content = content.to_string();
+    db.call(move |db| {
         let conn = db.conn();
 
         // Create conversation if it doesn't exist
@@ -49,8 +102,6 @@ pub async fn chat(
             )
             .map_err(|e| e.to_string())?;
 
-        is_new_conversation = !conv_exists;
-
         if !conv_exists {
             let now = chrono::Utc::now().timestamp_millis();
             conn.execute(
@@ -58,7 +109,7 @@ pub async fn chat(
                  VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
                 rusqlite::params![
                     &conversation_id,
-                    "New Chat",
+                    title,
                     "user-led",
                     now,
                     now,
@@ -77,15 +128,194 @@ pub async fn chat(
                 &message_id,
                 &conversation_id,
                 "user",
-                &request.message,
+                &content,
                 now,
-                request.context_level.unwrap_or(1)
+                context_level
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+
+        Ok(!conv_exists)
+    }).await
+}
+
+/// Save an AI response and bump the conversation (runs on the blocking pool, v3.9.0)
+async fn save_ai_message(
+    db: &AsyncDatabase,
+    conversation_id: &str,
+    message_id: &str,
+    content: &str,
+) -> Result<(), String> {
+    let conversation_id = conversation_id.to_string();
+    let message_id = message_id.to_string();
+    let content = content.to_string();
+    db.call(move |db| {
+        let conn = db.conn();
+
+        // Save AI message
+        conn.execute(
+            "INSERT INTO messages (id, conversation_id, role, content, timestamp)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![
+                &message_id,
+                &conversation_id,
+                "assistant",
+                &content,
+                chrono::Utc::now().timestamp_millis()
             ],
         )
         .map_err(|e| e.to_string())?;
-    } // db lock is released here
+
+        // Update conversation
+        conn.execute(
+            "UPDATE conversations SET updated_at = ?1, message_count = message_count + 2 WHERE id = ?2",
+            rusqlite::params![chrono::Utc::now().timestamp_millis(), &conversation_id],
+        )
+        .map_err(|e| e.to_string())?;
+
+        Ok(())
+    }).await
+}
+
+/// Score the user's mood for the timeline and session empathy, and resolve the
+/// conversation language lock (runs on the blocking pool, v3.9.0)
+///
+/// Failures are logged; the chat goes on without them.
+async fn record_message_signals(
+    sentiment_service: &Arc<SentimentService>,
+    language_service: &Arc<ConversationLanguageService>,
+    conversation_id: &str,
+    message_id: &str,
+    message: &str,
+) -> Option<ConversationLanguage> {
+    let sentiment_service = Arc::clone(sentiment_service);
+    let language_service = Arc::clone(language_service);
+    let (conversation_id, message_id, message) = (conversation_id.to_string(), message_id.to_string(), message.to_string());
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = sentiment_service.record_message(&conversation_id, &message_id, &message) {
+            log::warn!("Failed to record message sentiment: {}", e);
+        }
+        language_service
+            .resolve_for_message(&conversation_id, &message)
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to resolve conversation language: {}", e);
+                None
+            })
+    })
+    .await
+    .unwrap_or_else(|e| {
+        log::warn!("Task join error: {}", e);
+        None
+    })
+}
+
+/// Index newly attached files, then find the conversation's chunks relevant to
+/// `message` within the prompt budget (runs on the blocking pool, v3.9.0)
+async fn attach_and_search_documents(
+    documents: &Arc<ConversationDocumentService>,
+    rag: &Arc<RagServiceV2>,
+    conversation_id: &str,
+    files: &[String],
+    message: &str,
+) -> Result<Vec<DocumentChunk>, String> {
+    let documents = Arc::clone(documents);
+    let rag = Arc::clone(rag);
+    let (conversation_id, files, message) = (conversation_id.to_string(), files.to_vec(), message.to_string());
+    tokio::task::spawn_blocking(move || -> Result<Vec<DocumentChunk>, String> {
+        for path in &files {
+            documents
+                .attach_file(&conversation_id, path, |text, source| rag.chunk(text, source))
+                .map_err(|e| format!("Failed to attach document: {}", e))?;
+        }
+        Ok(documents
+            .search(&conversation_id, &message, conversation_documents::TOP_K)
+            .map(|hits| conversation_documents::chunks_within_budget(hits, model_context::prompt_budget() / 3))
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to search attached documents: {}", e);
+                Vec::new()
+            }))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// Chat command - main AI interaction
+#[tauri::command]
+pub async fn chat(
+    state: State<'_, AppState>,
+    language_service: State<'_, Arc<ConversationLanguageService>>,
+    sentiment_service: State<'_, Arc<SentimentService>>,
+    router: State<'_, Arc<ModelRouterService>>,
+    verifier: State<'_, Arc<ResponseVerifierService>>,
+    learning_style: State<'_, Arc<LearningStyleAdapterService>>,
+    visual_analyzer: State<'_, Arc<TokioMutex<VisualAnalyzerService>>>,
+    documents: State<'_, Arc<ConversationDocumentService>>,
+    request: ChatRequest,
+) -> AppResult<ChatResponse> {
+    log::info!("Chat command called with message: {}", request.message);
+    let start_time = std::time::Instant::now();
+
+    // v3.9.0: Describe attached images first; the model answers from the descriptions
+    let images = if request.images.is_empty() {
+        Vec::new()
+    } else {
+        chat_attachments::analyze_all(&visual_analyzer, &request.images, &request.message)
+            .await
+            .map_err(|e| format!("Failed to analyze attached image: {}", e))?
+    };
+
+    // Generate IDs
+    let conversation_id = request.conversation_id.unwrap_or_else(|| {
+        format!("conv_{}", chrono::Utc::now().timestamp_millis())
+    });
+    let message_id = format!("msg_{}", chrono::Utc::now().timestamp_millis());
+
+    // Block 1: Save user message to database
+    let is_new_conversation = save_user_message(
+        &state.db,
+        &conversation_id,
+        &message_id,
+        &request.message,
+        request.context_level.unwrap_or(1),
+        "New Chat",
+    ).await?;
     log::info!("⏱️  [PERF] DB Save (user message): {:?}", start_time.elapsed());
 
+    // Score the user's mood and resolve the conversation language lock (v3.9.0)
+    let expected_language = record_message_signals(
+        &sentiment_service,
+        &language_service,
+        &conversation_id,
+        &message_id,
+        &request.message,
+    ).await;
+
+    // v3.9.0: Index attached files for this conversation, then answer from its files
+    let document_chunks = attach_and_search_documents(
+        &documents,
+        &state.rag,
+        &conversation_id,
+        &request.files,
+        &request.message,
+    ).await?;
+    let prompt_message = conversation_documents::augment_message(
+        &chat_attachments::augment_message(&request.message, &images),
+        &document_chunks,
+    );
+
+    // v3.9.0: Remember each image so it can be recalled in later conversations
+    for image in &images {
+        let rag = state.rag.clone();
+        let label = chat_attachments::memory_label(&image.name, &request.message);
+        let description = chat_attachments::describe(image);
+        let (conv_id, msg_id) = (conversation_id.clone(), message_id.clone());
+        tokio::spawn(async move {
+            if let Err(e) = ollama::store_conversation_in_rag(rag, &label, &description, 0.5, Some(&conv_id), Some(&msg_id)).await {
+                log::warn!("Failed to remember attached image: {}", e);
+            }
+        });
+    }
+
     // Trigger webhooks for conversation start (if new)
     if is_new_conversation {
         let trigger_manager = state.webhook_trigger_manager.clone();
@@ -114,37 +344,33 @@ pub async fn chat(
     // Generate AI response using Ollama with RAG v2 (LanceDB) and Persona (v3.4.0)
     // Note: Pass database reference without cloning Mutex
     // v3.4.0: RAG v2 with LanceDB for 10-100x faster retrieval (100ms → 30ms)
+    // v3.9.0: Trivial messages are answered by the fast model, the rest escalate
     let llm_start = std::time::Instant::now();
-    let ai_response = ollama::generate_response_with_rag_and_persona_ref(&request.message, Some(state.rag.clone()), Some(&state.db)).await?;
+    let triage = router.triage(&prompt_message, Some(&conversation_id), Some(state.db.as_mutex())).await;
+    let ollama::CitedResponse { response: ai_response, citations, grounding } = match triage.answer.clone() {
+        Some(response) => ollama::CitedResponse { response, citations: Vec::new(), grounding: None },
+        None => ollama::generate_cited_response_for_conversation(&prompt_message, Some(&conversation_id), Some(state.rag.clone()), Some(state.db.as_mutex())).await?,
+    };
+    router.record(Some(&conversation_id), &triage, llm_start.elapsed());
+    let ai_response = language_service
+        .enforce(expected_language, &request.message, ai_response)
+        .await
+        .response;
+    // v3.9.0: Self-check the reply and regenerate once if it fails
+    let ai_response = verifier
+        .verify(Some(&conversation_id), &prompt_message, ai_response, expected_language)
+        .await
+        .response;
+    // v3.9.0: Learning style transforms, when switched on for this conversation
+    let ai_response = learning_style
+        .transform_response(&conversation_id, &request.message, ai_response)
+        .await
+        .response;
     log::info!("⏱️  [PERF] LLM Response (RAG + Persona + Inference): {:?}", llm_start.elapsed());
     let ai_message_id = format!("msg_{}", chrono::Utc::now().timestamp_millis());
 
     // Block 2: Save AI response to database
-    {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
-        let conn = db.conn();
-
-        // Save AI message
-        conn.execute(
-            "INSERT INTO messages (id, conversation_id, role, content, timestamp)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
-            rusqlite::params![
-                &ai_message_id,
-                &conversation_id,
-                "assistant",
-                &ai_response,
-                chrono::Utc::now().timestamp_millis()
-            ],
-        )
-        .map_err(|e| e.to_string())?;
-
-        // Update conversation
-        conn.execute(
-            "UPDATE conversations SET updated_at = ?1, message_count = message_count + 2 WHERE id = ?2",
-            rusqlite::params![chrono::Utc::now().timestamp_millis(), &conversation_id],
-        )
-        .map_err(|e| e.to_string())?;
-    } // db lock is released here
+    save_ai_message(&state.db, &conversation_id, &ai_message_id, &ai_response).await?;
 
     let total_time = start_time.elapsed();
     log::info!("⏱️  [PERF] TOTAL Chat Response Time: {:?}", total_time);
@@ -169,20 +395,26 @@ pub async fn chat(
     Ok(ChatResponse {
         conversation_id,
         message_id: ai_message_id,
+        segments: response_formatter::parse_segments(&ai_response),
         response: ai_response,
+        citations,
+        groundedness: grounding.map(|report| report.score),
+        images,
+        document_chunks,
+        charts: Vec::new(),
     })
 }
 
 /// Voice input start command (DEPRECATED - removed to reduce latency)
 #[tauri::command]
-pub async fn voice_input_start() -> Result<bool, String> {
-    Err("Voice input has been removed to reduce latency and improve performance".to_string())
+pub async fn voice_input_start() -> AppResult<bool> {
+    Err("Voice input has been removed to reduce latency and improve performance".into())
 }
 
 /// Voice input stop command (DEPRECATED - removed to reduce latency)
 #[tauri::command]
-pub async fn voice_input_stop() -> Result<String, String> {
-    Err("Voice input has been removed to reduce latency and improve performance".to_string())
+pub async fn voice_input_stop() -> AppResult<String> {
+    Err("Voice input has been removed to reduce latency and improve performance".into())
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -194,9 +426,14 @@ struct StreamChunk {
 #[tauri::command]
 pub async fn chat_stream(
     state: State<'_, AppState>,
+    language_service: State<'_, Arc<ConversationLanguageService>>,
+    sentiment_service: State<'_, Arc<SentimentService>>,
+    router: State<'_, Arc<ModelRouterService>>,
+    verifier: State<'_, Arc<ResponseVerifierService>>,
+    learning_style: State<'_, Arc<LearningStyleAdapterService>>,
     app: AppHandle,
     request: ChatRequest,
-) -> Result<ChatResponse, String> {
+) -> AppResult<ChatResponse> {
     log::info!("Streaming chat command called with message: {}", request.message);
 
     // Generate IDs
@@ -206,97 +443,79 @@ pub async fn chat_stream(
     let message_id = format!("msg_{}", chrono::Utc::now().timestamp_millis());
 
     // Block 1: Save user message to database
-    {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
-        let conn = db.conn();
-
-        // Create conversation if it doesn't exist
-        let conv_exists: bool = conn
-            .query_row(
-                "SELECT EXISTS(SELECT 1 FROM conversations WHERE id = ?1)",
-                [&conversation_id],
-                |row| row.get(0),
-            )
-            .map_err(|e| e.to_string())?;
-
-        if !conv_exists {
-            let now = chrono::Utc::now().timestamp_millis();
-            conn.execute(
-                "INSERT INTO conversations (id, title, mode, created_at, updated_at, message_count)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                rusqlite::params![
-                    &conversation_id,
-                    "New Chat",
-                    "user-led",
-                    now,
-                    now,
-                    0
-                ],
-            )
-            .map_err(|e| e.to_string())?;
-        }
+    save_user_message(
+        &state.db,
+        &conversation_id,
+        &message_id,
+        &request.message,
+        request.context_level.unwrap_or(1),
+        "New Chat",
+    ).await?;
 
-        // Save user message
-        let now = chrono::Utc::now().timestamp_millis();
-        conn.execute(
-            "INSERT INTO messages (id, conversation_id, role, content, timestamp, context_level)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            rusqlite::params![
-                &message_id,
-                &conversation_id,
-                "user",
-                &request.message,
-                now,
-                request.context_level.unwrap_or(1)
-            ],
-        )
-        .map_err(|e| e.to_string())?;
-    } // db lock is released here
+    // Score the user's mood and resolve the conversation language lock (v3.9.0)
+    let expected_language = record_message_signals(
+        &sentiment_service,
+        &language_service,
+        &conversation_id,
+        &message_id,
+        &request.message,
+    ).await;
 
     // Generate AI response using streaming
     let ai_message_id = format!("msg_{}", chrono::Utc::now().timestamp_millis());
     let app_clone = app.clone();
 
-    let ai_response = ollama::generate_response_stream(&request.message, move |chunk| {
-        // Emit chunk to frontend via Tauri event
-        app_clone.emit("chat-stream-chunk", StreamChunk { chunk }).map_err(|e| e.to_string())?;
-        Ok(())
-    }).await?;
+    // v3.9.0: A fast-model answer is sent as a single chunk
+    let llm_start = std::time::Instant::now();
+    let triage = router.triage(&request.message, Some(&conversation_id), Some(state.db.as_mutex())).await;
+    let ai_response = match triage.answer.clone() {
+        Some(response) => {
+            app.emit("chat-stream-chunk", StreamChunk { chunk: response.clone() }).map_err(|e| e.to_string())?;
+            response
+        }
+        None => ollama::generate_response_stream(&request.message, move |chunk| {
+            // Emit chunk to frontend via Tauri event
+            app_clone.emit("chat-stream-chunk", StreamChunk { chunk }).map_err(|e| e.to_string())?;
+            Ok(())
+        }).await?,
+    };
+    router.record(Some(&conversation_id), &triage, llm_start.elapsed());
+
+    // Replace the streamed text if it had to be regenerated in the locked language (v3.9.0)
+    let language_check = language_service
+        .enforce(expected_language, &request.message, ai_response)
+        .await;
+
+    // v3.9.0: Self-check the reply; a kept retry replaces the streamed text too
+    let verified = verifier
+        .verify(Some(&conversation_id), &request.message, language_check.response, expected_language)
+        .await;
+    // v3.9.0: Learning style transforms, when switched on for this conversation
+    let transformed = learning_style
+        .transform_response(&conversation_id, &request.message, verified.response)
+        .await;
+    if language_check.regenerated || verified.improved || !transformed.applied.is_empty() {
+        app.emit("chat-stream-replace", StreamChunk { chunk: transformed.response.clone() })
+            .map_err(|e| e.to_string())?;
+    }
+    let ai_response = transformed.response;
 
     // Emit completion event
     app.emit("chat-stream-complete", ()).map_err(|e| e.to_string())?;
 
     // Block 2: Save AI response to database
-    {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
-        let conn = db.conn();
-
-        // Save AI message
-        conn.execute(
-            "INSERT INTO messages (id, conversation_id, role, content, timestamp)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
-            rusqlite::params![
-                &ai_message_id,
-                &conversation_id,
-                "assistant",
-                &ai_response,
-                chrono::Utc::now().timestamp_millis()
-            ],
-        )
-        .map_err(|e| e.to_string())?;
-
-        // Update conversation
-        conn.execute(
-            "UPDATE conversations SET updated_at = ?1, message_count = message_count + 2 WHERE id = ?2",
-            rusqlite::params![chrono::Utc::now().timestamp_millis(), &conversation_id],
-        )
-        .map_err(|e| e.to_string())?;
-    } // db lock is released here
+    save_ai_message(&state.db, &conversation_id, &ai_message_id, &ai_response).await?;
 
     Ok(ChatResponse {
         conversation_id,
         message_id: ai_message_id,
+        segments: response_formatter::parse_segments(&ai_response),
         response: ai_response,
+        citations: Vec::new(),  // Streaming path has no RAG context
+        groundedness: None,
+        images: Vec::new(),
+        document_chunks: Vec::new(),
+        charts: Vec::new(),
     })
 }
 
@@ -304,9 +523,13 @@ pub async fn chat_stream(
 #[tauri::command]
 pub async fn chat_with_tools(
     state: State<'_, AppState>,
+    language_service: State<'_, Arc<ConversationLanguageService>>,
+    sentiment_service: State<'_, Arc<SentimentService>>,
+    learning_style: State<'_, Arc<LearningStyleAdapterService>>,
+    chart_service: State<'_, Arc<ChartService>>,
     app: AppHandle,
     request: ChatRequest,
-) -> Result<ChatResponse, String> {
+) -> AppResult<ChatResponse> {
     log::info!("Chat with tools command called with message: {}", request.message);
 
     // Generate IDs
@@ -316,52 +539,14 @@ pub async fn chat_with_tools(
     let message_id = format!("msg_{}", chrono::Utc::now().timestamp_millis());
 
     // Block 1: Save user message to database
-    {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
-        let conn = db.conn();
-
-        // Create conversation if it doesn't exist
-        let conv_exists: bool = conn
-            .query_row(
-                "SELECT EXISTS(SELECT 1 FROM conversations WHERE id = ?1)",
-                [&conversation_id],
-                |row| row.get(0),
-            )
-            .map_err(|e| e.to_string())?;
-
-        if !conv_exists {
-            let now = chrono::Utc::now().timestamp_millis();
-            conn.execute(
-                "INSERT INTO conversations (id, title, mode, created_at, updated_at, message_count)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                rusqlite::params![
-                    &conversation_id,
-                    "New Chat (Tools)",
-                    "user-led",
-                    now,
-                    now,
-                    0
-                ],
-            )
-            .map_err(|e| e.to_string())?;
-        }
-
-        // Save user message
-        let now = chrono::Utc::now().timestamp_millis();
-        conn.execute(
-            "INSERT INTO messages (id, conversation_id, role, content, timestamp, context_level)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            rusqlite::params![
-                &message_id,
-                &conversation_id,
-                "user",
-                &request.message,
-                now,
-                request.context_level.unwrap_or(1)
-            ],
-        )
-        .map_err(|e| e.to_string())?;
-    } // db lock is released here
+    save_user_message(
+        &state.db,
+        &conversation_id,
+        &message_id,
+        &request.message,
+        request.context_level.unwrap_or(1),
+        "New Chat (Tools)",
+    ).await?;
 
     // Trigger webhook for message sent
     {
@@ -376,9 +561,45 @@ pub async fn chat_with_tools(
         });
     }
 
+    // Score the user's mood and resolve the conversation language lock (v3.9.0)
+    let expected_language = record_message_signals(
+        &sentiment_service,
+        &language_service,
+        &conversation_id,
+        &message_id,
+        &request.message,
+    ).await;
+
+    // v3.9.0: Tool prompt and descriptions in the conversation's (or user's primary) language
+    let prompt_language = match expected_language {
+        Some(language) => language,
+        None => state
+            .db
+            .call(|db| Ok::<_, String>(localization::primary_language(db.conn())))
+            .await
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to read primary language: {}", e);
+                ConversationLanguage::English
+            }),
+    };
+
+    // v3.9.0: Only the tools of the conversation's tool set, if it has one
+    let allowed_tools = {
+        let conversation_id = conversation_id.clone();
+        state
+            .db
+            .call(move |db| tool_sets::allowed_tools(db.conn(), &conversation_id).map_err(|e| e.to_string()))
+            .await
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to resolve conversation tool set: {}", e);
+                None
+            })
+    };
+
     // Generate AI response using tool calling (no lock held during async operation)
     let tool_service = Arc::clone(&state.tool_service);
     let ai_message_id = format!("msg_{}", chrono::Utc::now().timestamp_millis());
+    let generation_started = chrono::Utc::now().timestamp_millis();
     let ai_response = ollama::generate_response_with_tools(
         &request.message,
         tool_service,
@@ -386,34 +607,43 @@ pub async fn chat_with_tools(
         5,     // Max 5 tool calling iterations
         Some(app),  // v3.7.0: Pass AppHandle for tool events
         Some(ai_message_id.clone()),  // v3.7.0: Pass message ID for events
+        prompt_language,
+        allowed_tools,
     ).await?;
+    let ai_response = language_service
+        .enforce(expected_language, &request.message, ai_response)
+        .await
+        .response;
+    let ai_response = learning_style
+        .transform_response(&conversation_id, &request.message, ai_response)
+        .await
+        .response;
 
     // Block 2: Save AI response to database
-    {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
-        let conn = db.conn();
-
-        // Save AI message
-        conn.execute(
-            "INSERT INTO messages (id, conversation_id, role, content, timestamp)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
-            rusqlite::params![
-                &ai_message_id,
-                &conversation_id,
-                "assistant",
-                &ai_response,
-                chrono::Utc::now().timestamp_millis()
-            ],
-        )
-        .map_err(|e| e.to_string())?;
-
-        // Update conversation
-        conn.execute(
-            "UPDATE conversations SET updated_at = ?1, message_count = message_count + 2 WHERE id = ?2",
-            rusqlite::params![chrono::Utc::now().timestamp_millis(), &conversation_id],
-        )
-        .map_err(|e| e.to_string())?;
-    } // db lock is released here
+    save_ai_message(&state.db, &conversation_id, &ai_message_id, &ai_response).await?;
+
+    // v3.9.0: Charts the create_chart tool drew for this reply
+    let charts = {
+        let chart_service = Arc::clone(&chart_service.inner());
+        let (conversation_id, message_id) = (conversation_id.clone(), ai_message_id.clone());
+        tokio::task::spawn_blocking(move || {
+            chart_service
+                .attach_unassigned(generation_started, &conversation_id, &message_id)?
+                .into_iter()
+                .map(|chart| {
+                    let mut image = charts::render(&chart.spec, ChartFormat::Svg, None, None)?;
+                    image.chart_id = Some(chart.id);
+                    Ok(image)
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        })
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to render response charts: {}", e);
+            Vec::new()
+        })
+    };
 
     // Trigger webhook for message received (AI response)
     {
@@ -432,6 +662,129 @@ pub async fn chat_with_tools(
     Ok(ChatResponse {
         conversation_id,
         message_id: ai_message_id,
+        segments: response_formatter::parse_segments(&ai_response),
         response: ai_response,
+        citations: Vec::new(),  // Tool path has no RAG context yet
+        groundedness: None,
+        images: Vec::new(),
+        document_chunks: Vec::new(),
+        charts,
     })
 }
+
+/// Split a stored response into structured segments (v3.9.0)
+///
+/// Used by the frontend to render messages loaded from history, which only store raw markdown.
+#[tauri::command]
+pub async fn chat_format_response(text: String) -> AppResult<FormattedResponse> {
+    Ok(response_formatter::format_response(&text))
+}
+
+/// Generate JSON matching a JSON schema (v3.9.0)
+///
+/// The schema constrains decoding where Ollama supports it; the reply is validated
+/// and retried with the errors fed back (`options.max_retries`).
+#[tauri::command]
+pub async fn chat_structured(
+    prompt: String,
+    schema: serde_json::Value,
+    options: Option<StructuredOptions>,
+) -> AppResult<StructuredOutput> {
+    Ok(structured_output::generate(&prompt, &schema, &options.unwrap_or_default())
+        .await
+        .map_err(|e| format!("Structured output failed: {}", e))?)
+}
+
+/// Exactly what the next turn of a conversation would send to the model (v3.9.0)
+///
+/// Built for `message` (a draft) or, without one, the conversation's last user
+/// message. Nothing is generated and no memory access is recorded.
+#[tauri::command]
+pub async fn chat_inspect_context(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    message: Option<String>,
+) -> AppResult<ContextInspection> {
+    Ok(context_inspector::inspect(&conversation_id, message.as_deref(), Some(&state.rag), state.db.as_mutex())
+        .await
+        .map_err(|e| format!("Failed to inspect context: {}", e))?)
+}
+
+/// Regenerate an assistant reply, optionally steered (v3.9.0)
+///
+/// The new reply replaces the message's content; the original and every
+/// regeneration are kept as variants with the overrides they used.
+#[tauri::command]
+pub async fn chat_regenerate(
+    state: State<'_, AppState>,
+    language_service: State<'_, Arc<ConversationLanguageService>>,
+    variants: State<'_, Arc<ResponseVariantService>>,
+    message_id: String,
+    overrides: Option<GenerationOverrides>,
+) -> AppResult<RegenerateResponse> {
+    let overrides = overrides.unwrap_or_default();
+    log::info!("Regenerating message {} with {:?}", message_id, overrides);
+
+    let source = variants.source(&message_id)
+        .map_err(|e| format!("Failed to regenerate: {}", e))?;
+    let expected_language = language_service
+        .resolve_for_message(&source.conversation_id, &source.user_message)
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to resolve conversation language: {}", e);
+            None
+        });
+
+    let ollama::CitedResponse { response, citations, grounding } = ollama::generate_cited_response_with_overrides(
+        &source.user_message,
+        Some(&source.conversation_id),
+        Some(state.rag.clone()),
+        Some(state.db.as_mutex()),
+        &overrides,
+    ).await?;
+    let response = language_service
+        .enforce(expected_language, &source.user_message, response)
+        .await
+        .response;
+
+    let model = overrides.model.clone().unwrap_or_else(ollama::chat_model);
+    let variant = variants.record(&message_id, &response, &overrides, &model)
+        .map_err(|e| format!("Failed to save regenerated reply: {}", e))?;
+
+    Ok(RegenerateResponse {
+        segments: response_formatter::parse_segments(&variant.content),
+        variant,
+        citations,
+        groundedness: grounding.map(|report| report.score),
+    })
+}
+
+/// Original reply and regenerations of a message (v3.9.0)
+#[tauri::command]
+pub async fn chat_list_variants(
+    variants: State<'_, Arc<ResponseVariantService>>,
+    message_id: String,
+) -> AppResult<Vec<ResponseVariant>> {
+    Ok(variants.list(&message_id)
+        .map_err(|e| format!("Failed to list variants: {}", e))?)
+}
+
+/// Show a variant as the message's reply (v3.9.0)
+#[tauri::command]
+pub async fn chat_select_variant(
+    variants: State<'_, Arc<ResponseVariantService>>,
+    variant_id: String,
+) -> AppResult<ResponseVariant> {
+    Ok(variants.select(&variant_id)
+        .map_err(|e| format!("Failed to select variant: {}", e))?)
+}
+
+/// Rate a variant so regeneration settings can be compared (v3.9.0)
+#[tauri::command]
+pub async fn chat_rate_variant(
+    variants: State<'_, Arc<ResponseVariantService>>,
+    variant_id: String,
+    satisfaction: f32,
+) -> AppResult<()> {
+    Ok(variants.rate(&variant_id, satisfaction)
+        .map_err(|e| format!("Failed to rate variant: {}", e))?)
+}
@@ -0,0 +1,34 @@
+/**
+ * Analytics Commands (v3.9.0)
+ *
+ * Local usage metrics for the dashboard view
+ */
+
+use crate::services::analytics::{AnalyticsService, AnalyticsSummary, SeriesMetric, SeriesPoint};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Default dashboard window
+const DEFAULT_DAYS: u32 = 30;
+
+/// Get usage totals, RAG hit rate, latency percentiles, and tool/model breakdowns
+#[tauri::command]
+pub async fn analytics_get_summary(
+    days: Option<u32>,
+    service: State<'_, Arc<AnalyticsService>>,
+) -> AppResult<AnalyticsSummary> {
+    Ok(service.get_summary(days.unwrap_or(DEFAULT_DAYS))
+        .map_err(|e| format!("Failed to get analytics summary: {}", e))?)
+}
+
+/// Get one metric per day (oldest first, days without data are 0)
+#[tauri::command]
+pub async fn analytics_get_series(
+    metric: SeriesMetric,
+    days: Option<u32>,
+    service: State<'_, Arc<AnalyticsService>>,
+) -> AppResult<Vec<SeriesPoint>> {
+    Ok(service.get_series(metric, days.unwrap_or(DEFAULT_DAYS))
+        .map_err(|e| format!("Failed to get analytics series: {}", e))?)
+}
@@ -1,5 +1,7 @@
+use crate::AppResult;
 use crate::AppState;
 use log::info;
+use std::sync::Arc;
 use tauri::{command, State};
 
 /// Manage context with attention sink pattern
@@ -9,7 +11,7 @@ pub fn attention_sink_manage_context(
     context: String,
     _sink_size: Option<usize>,   // Reserved for future per-call configuration
     _window_size: Option<usize>, // Reserved for future per-call configuration
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: attention_sink_manage_context");
 
     // Use the global attention sink manager from AppState
@@ -24,9 +26,36 @@ pub fn attention_sink_manage_context(
         "compressed_tokens": managed.compressed_tokens,
         "compression_ratio_achieved": managed.compression_ratio_achieved,
         "requires_compression": managed.requires_compression,
+        "summary_segments": managed.summary_segments,
     }))
 }
 
+/// Build the attention-sink prompt for a stored conversation (v3.9.0)
+///
+/// Evicted middle turns are written to the conversation's Summary Buffer summary.
+#[command]
+pub async fn attention_sink_manage_conversation(
+    state: State<'_, AppState>,
+    conversation_id: String,
+) -> AppResult<serde_json::Value> {
+    info!("Command: attention_sink_manage_conversation - {}", conversation_id);
+
+    let manager = Arc::clone(&state.attention_sink);
+    state.db.call(move |db| {
+        let managed = crate::services::conversation_memory::managed_context(db.conn(), &conversation_id, &manager)
+            .map_err(|e| format!("Failed to manage conversation context: {}", e))?;
+
+        Ok(serde_json::json!({
+            "prompt": manager.format_for_prompt(&managed),
+            "summary_segments": managed.summary_segments,
+            "evicted_messages": managed.evicted_messages,
+            "total_original_tokens": managed.total_original_tokens,
+            "compressed_tokens": managed.compressed_tokens,
+            "requires_compression": managed.requires_compression,
+        }))
+    }).await
+}
+
 /// Format managed context for LLM prompt
 #[command]
 pub fn attention_sink_format_prompt(
@@ -34,7 +63,7 @@ pub fn attention_sink_format_prompt(
     attention_sink: String,
     compressed_middle: String,
     recent_window: String,
-) -> Result<String, String> {
+) -> AppResult<String> {
     info!("Command: attention_sink_format_prompt");
 
     let manager = &*state.attention_sink;
@@ -47,6 +76,8 @@ pub fn attention_sink_format_prompt(
         compressed_tokens: 0,
         compression_ratio_achieved: 0.0,
         requires_compression: false,
+        summary_segments: Vec::new(),
+        evicted_messages: 0,
     };
 
     Ok(manager.format_for_prompt(&context))
@@ -58,13 +89,13 @@ pub fn attention_sink_needs_compression(
     state: State<'_, AppState>,
     context: String,
     max_tokens: Option<usize>,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: attention_sink_needs_compression");
 
     let manager = &*state.attention_sink;
     let estimated_tokens = manager.estimate_tokens(&context);
     let needs_compression = manager.needs_compression(estimated_tokens);
-    let max = max_tokens.unwrap_or(manager.config().max_context_tokens);
+    let max = max_tokens.unwrap_or(manager.max_context_tokens());
 
     Ok(serde_json::json!({
         "needs_compression": needs_compression,
@@ -76,14 +107,14 @@ pub fn attention_sink_needs_compression(
 
 /// Estimate token count for text
 #[command]
-pub fn attention_sink_estimate_tokens(state: State<'_, AppState>, text: String) -> Result<usize, String> {
+pub fn attention_sink_estimate_tokens(state: State<'_, AppState>, text: String) -> AppResult<usize> {
     let manager = &*state.attention_sink;
     Ok(manager.estimate_tokens(&text))
 }
 
 /// Get Attention Sink configuration
 #[command]
-pub fn attention_sink_get_config(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+pub fn attention_sink_get_config(state: State<'_, AppState>) -> AppResult<serde_json::Value> {
     info!("Command: attention_sink_get_config");
 
     let manager = &*state.attention_sink;
@@ -95,12 +126,13 @@ pub fn attention_sink_get_config(state: State<'_, AppState>) -> Result<serde_jso
         "compression_ratio": stats.compression_ratio,
         "chunk_size": stats.chunk_size,
         "max_context_tokens": stats.max_context_tokens,
+        "context_window": stats.context_window,
     }))
 }
 
 /// Get Attention Sink statistics
 #[command]
-pub fn attention_sink_stats(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+pub fn attention_sink_stats(state: State<'_, AppState>) -> AppResult<serde_json::Value> {
     info!("Command: attention_sink_stats");
 
     let manager = &*state.attention_sink;
@@ -112,5 +144,6 @@ pub fn attention_sink_stats(state: State<'_, AppState>) -> Result<serde_json::Va
         "compression_ratio": stats.compression_ratio,
         "chunk_size": stats.chunk_size,
         "max_context_tokens": stats.max_context_tokens,
+        "context_window": stats.context_window,
     }))
 }
@@ -0,0 +1,62 @@
+/**
+ * Background Jobs Commands (v3.9.0)
+ *
+ * Inspect, pause, resume, reschedule and trigger the maintenance jobs
+ */
+
+use crate::services::background_jobs::{BackgroundJobsService, JobSchedule, JobStatus};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// List jobs with their schedules, last results and next run times
+#[tauri::command]
+pub async fn background_jobs_list(
+    service: State<'_, Arc<BackgroundJobsService>>,
+) -> AppResult<Vec<JobStatus>> {
+    Ok(service.list_jobs()
+        .map_err(|e| format!("Failed to list background jobs: {}", e))?)
+}
+
+/// Pause a job until it is resumed (persists across restarts)
+#[tauri::command]
+pub async fn background_jobs_pause(
+    job_id: String,
+    service: State<'_, Arc<BackgroundJobsService>>,
+) -> AppResult<JobStatus> {
+    Ok(service.pause(&job_id)
+        .map_err(|e| format!("Failed to pause background job: {}", e))?)
+}
+
+/// Resume a paused job
+#[tauri::command]
+pub async fn background_jobs_resume(
+    job_id: String,
+    service: State<'_, Arc<BackgroundJobsService>>,
+) -> AppResult<JobStatus> {
+    Ok(service.resume(&job_id)
+        .map_err(|e| format!("Failed to resume background job: {}", e))?)
+}
+
+/// Run a job immediately (also when paused)
+#[tauri::command]
+pub async fn background_jobs_run_now(
+    job_id: String,
+    service: State<'_, Arc<BackgroundJobsService>>,
+) -> AppResult<JobStatus> {
+    log::info!("Running background job {} on request", job_id);
+
+    Ok(service.run_now(&job_id)
+        .map_err(|e| format!("Failed to run background job: {}", e))?)
+}
+
+/// Change how often a job runs
+#[tauri::command]
+pub async fn background_jobs_update_schedule(
+    job_id: String,
+    schedule: JobSchedule,
+    service: State<'_, Arc<BackgroundJobsService>>,
+) -> AppResult<JobStatus> {
+    Ok(service.update_schedule(&job_id, schedule)
+        .map_err(|e| format!("Failed to update background job schedule: {}", e))?)
+}
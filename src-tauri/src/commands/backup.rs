@@ -0,0 +1,75 @@
+/**
+ * Backup Commands (v3.9.0)
+ *
+ * Manual backups, backup listing, and verified restore of the active profile
+ */
+
+use crate::commands::profile::rebind_app_state;
+use crate::services::backup::{BackupInfo, BackupKind, BackupService};
+use crate::services::degradation::ServiceSlot;
+use crate::services::profile::ProfileService;
+use crate::AppResult;
+use crate::AppState;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+
+/// Back up the active profile now
+#[tauri::command]
+pub async fn backup_create_now(
+    service: State<'_, Arc<ServiceSlot<BackupService>>>,
+) -> AppResult<BackupInfo> {
+    let service = service.get()?;
+    log::info!("Creating manual backup");
+
+    let manifest = tokio::task::spawn_blocking(move || service.create_backup(BackupKind::Manual))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to create backup: {}", e))?;
+    Ok(BackupInfo::from(&manifest))
+}
+
+/// List backups of the active profile (newest first)
+#[tauri::command]
+pub async fn backup_list(
+    service: State<'_, Arc<ServiceSlot<BackupService>>>,
+) -> AppResult<Vec<BackupInfo>> {
+    let service = service.get()?;
+    Ok(service.list_backups()
+        .map_err(|e| format!("Failed to list backups: {}", e))?)
+}
+
+/// Verify a backup and restore it over the active profile
+///
+/// The current data is backed up first, then every service is rebound to the
+/// restored files.
+#[tauri::command]
+pub async fn backup_restore(
+    backup_id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    service: State<'_, Arc<ServiceSlot<BackupService>>>,
+    profiles: State<'_, Arc<ProfileService>>,
+) -> AppResult<BackupInfo> {
+    let service = service.get()?;
+    log::info!("Restoring backup: {}", backup_id);
+
+    // Checksums, integrity checks and file copies are disk heavy
+    let backups = Arc::clone(&service);
+    let manifest = tokio::task::spawn_blocking(move || backups.restore_backup(&backup_id))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to restore backup: {}", e))?;
+
+    // Old handles still point at the replaced files
+    let paths = profiles.reload_active()
+        .map_err(|e| format!("Failed to reopen restored databases: {}", e))?;
+    rebind_app_state(&state, &paths).await?;
+
+    let info = BackupInfo::from(&manifest);
+    if let Err(e) = app.emit("backup-restored", &info) {
+        log::warn!("Failed to emit backup-restored: {}", e);
+    }
+
+    log::info!("✓ Backup {} restored", info.id);
+    Ok(info)
+}
@@ -0,0 +1,130 @@
+/**
+ * System Benchmark Commands (v3.9.0)
+ *
+ * Measure end-to-end latency budgets and compare with earlier runs
+ */
+
+use crate::services::benchmark::{BenchmarkResult, BenchmarkService, LatencySummary};
+use crate::services::degradation::ServiceSlot;
+use crate::services::ollama;
+use crate::services::tool_calling::ToolCall;
+use crate::AppResult;
+use crate::AppState;
+use std::sync::Arc;
+use std::time::Instant;
+use tauri::State;
+
+/// Default samples per stage
+const DEFAULT_ITERATIONS: usize = 20;
+
+/// Queries used for retrieval timing
+const RAG_QUERIES: &[&str] = &[
+    "What did we talk about yesterday?",
+    "my favorite programming language",
+    "저번에 추천해준 책",
+    "project deadline next week",
+    "how do I sort a vector in Rust",
+];
+
+/// Measure cold start, embedding throughput, RAG retrieval, first-token
+/// latency and tool round-trips, then store the run
+///
+/// Stages that fail are reported in `errors` instead of failing the run.
+/// `include_llm: false` skips the first-token measurement.
+#[tauri::command]
+pub async fn system_benchmark(
+    iterations: Option<usize>,
+    include_llm: Option<bool>,
+    state: State<'_, AppState>,
+    service: State<'_, Arc<ServiceSlot<BenchmarkService>>>,
+) -> AppResult<BenchmarkResult> {
+    let service = service.get()?;
+    let iterations = iterations.unwrap_or(DEFAULT_ITERATIONS).clamp(1, 200);
+    log::info!("Command: system_benchmark - {} iterations", iterations);
+
+    let mut result = BenchmarkResult::new();
+
+    // Embedding throughput
+    let embedding = Arc::clone(&state.embedding);
+    let embedded = tokio::task::spawn_blocking(move || {
+        let start = Instant::now();
+        for i in 0..iterations {
+            embedding.embed(&format!("Benchmark sentence {} about memory retrieval latency", i))?;
+        }
+        Ok::<_, anyhow::Error>(start.elapsed())
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?;
+    match embedded {
+        Ok(elapsed) => result.embedding_throughput = Some(iterations as f64 / elapsed.as_secs_f64().max(1e-6)),
+        Err(e) => result.errors.push(format!("embedding: {}", e)),
+    }
+
+    // RAG retrieval
+    let mut samples = Vec::with_capacity(iterations);
+    for i in 0..iterations {
+        let start = Instant::now();
+        if let Err(e) = state.rag.search_with_scores(RAG_QUERIES[i % RAG_QUERIES.len()], 5).await {
+            result.errors.push(format!("rag: {}", e));
+            break;
+        }
+        samples.push(start.elapsed().as_millis() as u64);
+    }
+    if !samples.is_empty() {
+        result.rag_retrieval = Some(LatencySummary::from_samples(&samples));
+    }
+
+    // First token of the chat model
+    if include_llm.unwrap_or(true) {
+        let start = Instant::now();
+        let mut first_token = None;
+        let response = ollama::generate_response_stream("Reply with one short sentence: are you ready?", |_| {
+            first_token.get_or_insert_with(|| start.elapsed().as_millis() as u64);
+            Ok(())
+        })
+        .await;
+        match response {
+            Ok(_) => {
+                result.first_token_ms = first_token;
+                result.full_response_ms = Some(start.elapsed().as_millis() as u64);
+            }
+            Err(e) => result.errors.push(format!("llm: {}", e)),
+        }
+    }
+
+    // Tool round-trip (side-effect free calculator)
+    let call = ToolCall {
+        tool_name: "calculate".to_string(),
+        arguments: serde_json::json!({ "expression": "1 + 1" }),
+    };
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let tool_result = state.tool_service.execute_tool(&call).await;
+        if !tool_result.success {
+            result.errors.push(format!("tools: {}", tool_result.error.unwrap_or_default()));
+            break;
+        }
+        samples.push(start.elapsed().as_millis() as u64);
+    }
+    if !samples.is_empty() {
+        result.tool_round_trip = Some(LatencySummary::from_samples(&samples));
+    }
+
+    let service = Arc::clone(&service);
+    Ok(tokio::task::spawn_blocking(move || service.record(result))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to record benchmark: {}", e))?)
+}
+
+/// Past benchmark runs, newest first
+#[tauri::command]
+pub async fn system_benchmark_history(
+    limit: Option<usize>,
+    service: State<'_, Arc<ServiceSlot<BenchmarkService>>>,
+) -> AppResult<Vec<BenchmarkResult>> {
+    let service = service.get()?;
+    Ok(service.history(limit.unwrap_or(20))
+        .map_err(|e| format!("Failed to load benchmark history: {}", e))?)
+}
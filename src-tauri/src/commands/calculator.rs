@@ -0,0 +1,54 @@
+/**
+ * Calculator Commands (v3.9.0)
+ *
+ * Evaluating expressions and configuring exchange rates for the calculate tool
+ */
+
+use crate::services::calculator::{Calculation, CalculatorConfig, CalculatorService, ExchangeRates};
+use crate::AppResult;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::State;
+
+/// Evaluate an expression the way the calculate tool does
+#[tauri::command]
+pub async fn calculator_evaluate(
+    expression: String,
+    variables: Option<HashMap<String, serde_json::Value>>,
+    service: State<'_, Arc<CalculatorService>>,
+) -> AppResult<Calculation> {
+    Ok(service
+        .calculate(&expression, &variables.unwrap_or_default())
+        .await
+        .map_err(|e| format!("Calculation failed: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn calculator_get_config(service: State<'_, Arc<CalculatorService>>) -> AppResult<CalculatorConfig> {
+    Ok(service.get_config())
+}
+
+/// Save exchange rate settings; returns the config as stored
+#[tauri::command]
+pub async fn calculator_update_config(
+    config: CalculatorConfig,
+    service: State<'_, Arc<CalculatorService>>,
+) -> AppResult<CalculatorConfig> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .update_config(config)
+            .map_err(|e| format!("Failed to update calculator settings: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Fetch exchange rates now (they're otherwise fetched when a conversion needs them)
+#[tauri::command]
+pub async fn calculator_refresh_rates(service: State<'_, Arc<CalculatorService>>) -> AppResult<ExchangeRates> {
+    Ok(service
+        .refresh_rates()
+        .await
+        .map_err(|e| format!("Failed to fetch exchange rates: {}", e))?)
+}
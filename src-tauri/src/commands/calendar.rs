@@ -1,7 +1,10 @@
+use crate::database::AsyncDatabase;
 use crate::services::calendar::{
     Calendar, CalendarEvent, CalendarService, CalendarToken,
 };
+use crate::services::secrets::{self, SecretsService};
 use crate::services::webhook_triggers::WebhookTriggerEvent;
+use crate::AppResult;
 use crate::AppState;
 use chrono::{DateTime, Utc};
 use log::{error, info};
@@ -38,23 +41,71 @@ impl CalendarServiceWrapper {
 #[tauri::command]
 pub async fn calendar_initialize(
     state: State<'_, AppState>,
+    secrets: State<'_, Arc<SecretsService>>,
     client_id: String,
     client_secret: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     info!("Initializing calendar service");
 
     let service = CalendarService::new(client_id, client_secret)
         .map_err(|e| format!("Failed to initialize calendar service: {}", e))?;
 
+    // Restore the saved token from the vault (v3.9.0)
+    let db = state.db.clone();
+    let secrets = Arc::clone(&secrets);
+    let saved_token = tokio::task::spawn_blocking(move || load_saved_token(&db, &secrets))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?;
+    match saved_token {
+        Ok(Some(token)) => service.set_token(token),
+        Ok(None) => {}
+        Err(e) => error!("Failed to restore calendar token: {}", e),
+    }
+
     *state.calendar_service.service.lock().unwrap() = Some(service);
 
     info!("Calendar service initialized successfully");
     Ok(())
 }
 
+/// Load the calendar token from the vault, moving a legacy plaintext token out of the database
+///
+/// Blocking; run on the blocking pool.
+fn load_saved_token(
+    db: &AsyncDatabase,
+    secrets: &SecretsService,
+) -> anyhow::Result<Option<CalendarToken>> {
+    let legacy_json: Option<String> = {
+        let db = db.lock()?;
+        db.conn()
+            .query_row(
+                "SELECT token_json FROM oauth_tokens WHERE service = ?1",
+                ["google_calendar"],
+                |row| row.get(0),
+            )
+            .ok()
+            .filter(|json: &String| !json.is_empty())
+    };
+
+    if let Some(json) = legacy_json {
+        info!("Moving calendar token from database to keychain");
+        secrets.set(secrets::CALENDAR_TOKEN, &json)?;
+        let db = db.lock()?;
+        db.conn().execute(
+            "UPDATE oauth_tokens SET token_json = '', updated_at = ?1 WHERE service = ?2",
+            rusqlite::params![chrono::Utc::now().timestamp(), "google_calendar"],
+        )?;
+    }
+
+    match secrets.get(secrets::CALENDAR_TOKEN)? {
+        Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+        None => Ok(None),
+    }
+}
+
 /// Start OAuth flow
 #[tauri::command]
-pub async fn calendar_start_oauth(state: State<'_, AppState>) -> Result<String, String> {
+pub async fn calendar_start_oauth(state: State<'_, AppState>) -> AppResult<String> {
     info!("Starting calendar OAuth flow");
 
     let service = {
@@ -81,9 +132,10 @@ pub async fn calendar_start_oauth(state: State<'_, AppState>) -> Result<String,
 #[tauri::command]
 pub async fn calendar_complete_oauth(
     state: State<'_, AppState>,
+    secrets: State<'_, Arc<SecretsService>>,
     code: String,
     received_state: String,
-) -> Result<CalendarToken, String> {
+) -> AppResult<CalendarToken> {
     info!("Completing calendar OAuth flow");
 
     // Verify CSRF token
@@ -96,7 +148,7 @@ pub async fn calendar_complete_oauth(
 
         if csrf_token.secret() != &received_state {
             error!("CSRF token mismatch");
-            return Err("CSRF token mismatch, possible security issue".to_string());
+            return Err("CSRF token mismatch, possible security issue".into());
         }
 
         oauth_state
@@ -117,25 +169,28 @@ pub async fn calendar_complete_oauth(
         .await
         .map_err(|e| format!("OAuth completion failed: {}", e))?;
 
-    // Save token to database
-    {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+    // Save token to the keychain vault; the database only keeps expiry bookkeeping (v3.9.0)
+    let token_json = serde_json::to_string(&token).map_err(|e| e.to_string())?;
+    secrets
+        .set(secrets::CALENDAR_TOKEN, &token_json)
+        .map_err(|e| format!("Failed to save token: {}", e))?;
+    state.db.call(move |db| {
         let conn = db.conn();
 
-        let token_json = serde_json::to_string(&token).map_err(|e| e.to_string())?;
-
         conn.execute(
             "INSERT OR REPLACE INTO oauth_tokens (service, token_json, expires_at, created_at)
              VALUES (?1, ?2, ?3, ?4)",
             rusqlite::params![
                 "google_calendar",
-                token_json,
+                "",
                 token.expires_at,
                 chrono::Utc::now().timestamp()
             ],
         )
         .map_err(|e| format!("Failed to save token: {}", e))?;
-    }
+
+        Ok::<_, String>(())
+    }).await?;
 
     info!("Calendar OAuth completed and token saved");
     Ok(token)
@@ -143,7 +198,7 @@ pub async fn calendar_complete_oauth(
 
 /// Check if calendar is authenticated
 #[tauri::command]
-pub async fn calendar_is_authenticated(state: State<'_, AppState>) -> Result<bool, String> {
+pub async fn calendar_is_authenticated(state: State<'_, AppState>) -> AppResult<bool> {
     let service_guard = state.calendar_service.service.lock().unwrap();
     match service_guard.as_ref() {
         Some(service) => Ok(service.is_authenticated()),
@@ -153,9 +208,16 @@ pub async fn calendar_is_authenticated(state: State<'_, AppState>) -> Result<boo
 
 /// Sign out from calendar
 #[tauri::command]
-pub async fn calendar_sign_out(state: State<'_, AppState>) -> Result<(), String> {
+pub async fn calendar_sign_out(
+    state: State<'_, AppState>,
+    secrets: State<'_, Arc<SecretsService>>,
+) -> AppResult<()> {
     info!("Signing out from calendar");
 
+    secrets
+        .delete(secrets::CALENDAR_TOKEN)
+        .map_err(|e| format!("Failed to delete token: {}", e))?;
+
     {
         let service_guard = state.calendar_service.service.lock().unwrap();
         if let Some(service) = service_guard.as_ref() {
@@ -164,13 +226,14 @@ pub async fn calendar_sign_out(state: State<'_, AppState>) -> Result<(), String>
     }
 
     // Remove token from database
-    {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+    state.db.call(move |db| {
         let conn = db.conn();
 
         conn.execute("DELETE FROM oauth_tokens WHERE service = ?1", ["google_calendar"])
             .map_err(|e| format!("Failed to delete token: {}", e))?;
-    }
+
+        Ok::<_, String>(())
+    }).await?;
 
     info!("Calendar signed out successfully");
     Ok(())
@@ -178,7 +241,7 @@ pub async fn calendar_sign_out(state: State<'_, AppState>) -> Result<(), String>
 
 /// List all calendars
 #[tauri::command]
-pub async fn calendar_list(state: State<'_, AppState>) -> Result<Vec<Calendar>, String> {
+pub async fn calendar_list(state: State<'_, AppState>) -> AppResult<Vec<Calendar>> {
     info!("Listing calendars");
 
     let service = {
@@ -199,7 +262,7 @@ pub async fn calendar_list(state: State<'_, AppState>) -> Result<Vec<Calendar>,
 
 /// Get primary calendar ID
 #[tauri::command]
-pub async fn calendar_get_primary_id(state: State<'_, AppState>) -> Result<String, String> {
+pub async fn calendar_get_primary_id(state: State<'_, AppState>) -> AppResult<String> {
     info!("Getting primary calendar ID");
 
     let service = {
@@ -231,7 +294,7 @@ pub struct ListEventsParams {
 pub async fn calendar_list_events(
     state: State<'_, AppState>,
     params: ListEventsParams,
-) -> Result<Vec<CalendarEvent>, String> {
+) -> AppResult<Vec<CalendarEvent>> {
     info!("Listing events for calendar: {}", params.calendar_id);
 
     let service = {
@@ -271,7 +334,7 @@ pub async fn calendar_get_upcoming(
     state: State<'_, AppState>,
     calendar_id: String,
     days: i64,
-) -> Result<Vec<CalendarEvent>, String> {
+) -> AppResult<Vec<CalendarEvent>> {
     info!("Getting upcoming events for {} days", days);
 
     let service = {
@@ -296,7 +359,7 @@ pub async fn calendar_create_event(
     state: State<'_, AppState>,
     calendar_id: String,
     event: CalendarEvent,
-) -> Result<CalendarEvent, String> {
+) -> AppResult<CalendarEvent> {
     info!("Creating event: {}", event.summary);
 
     let service = {
@@ -337,7 +400,7 @@ pub async fn calendar_update_event(
     calendar_id: String,
     event_id: String,
     event: CalendarEvent,
-) -> Result<CalendarEvent, String> {
+) -> AppResult<CalendarEvent> {
     info!("Updating event: {}", event_id);
 
     let service = {
@@ -362,7 +425,7 @@ pub async fn calendar_delete_event(
     state: State<'_, AppState>,
     calendar_id: String,
     event_id: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     info!("Deleting event: {}", event_id);
 
     let service = {
@@ -387,7 +450,7 @@ pub async fn calendar_search_events(
     state: State<'_, AppState>,
     calendar_id: String,
     query: String,
-) -> Result<Vec<CalendarEvent>, String> {
+) -> AppResult<Vec<CalendarEvent>> {
     info!("Searching events with query: {}", query);
 
     let service = {
@@ -412,7 +475,7 @@ pub async fn calendar_quick_add(
     state: State<'_, AppState>,
     calendar_id: String,
     text: String,
-) -> Result<CalendarEvent, String> {
+) -> AppResult<CalendarEvent> {
     info!("Quick adding event: {}", text);
 
     let service = {
@@ -448,27 +511,26 @@ pub async fn calendar_quick_add(
 
 /// Load saved token from database
 #[tauri::command]
-pub async fn calendar_load_saved_token(state: State<'_, AppState>) -> Result<bool, String> {
+pub async fn calendar_load_saved_token(state: State<'_, AppState>) -> AppResult<bool> {
     info!("Loading saved calendar token");
 
-    let token_json = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
-        let conn = db.conn();
-
-        let result: Result<String, rusqlite::Error> = conn.query_row(
+    let token_json = state.db.call(|db| {
+        let result: Result<String, rusqlite::Error> = db.conn().query_row(
             "SELECT token_json FROM oauth_tokens WHERE service = ?1 AND expires_at > ?2",
             rusqlite::params!["google_calendar", chrono::Utc::now().timestamp()],
             |row| row.get(0),
         );
 
         match result {
-            Ok(json) => json,
-            Err(rusqlite::Error::QueryReturnedNoRows) => {
-                info!("No valid saved token found");
-                return Ok(false);
-            }
-            Err(e) => return Err(format!("Failed to load token: {}", e)),
+            Ok(json) => Ok(Some(json)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(format!("Failed to load token: {}", e)),
         }
+    }).await?;
+
+    let Some(token_json) = token_json else {
+        info!("No valid saved token found");
+        return Ok(false);
     };
 
     let token: CalendarToken =
@@ -480,6 +542,6 @@ pub async fn calendar_load_saved_token(state: State<'_, AppState>) -> Result<boo
         info!("Saved token loaded and set");
         Ok(true)
     } else {
-        Err("Calendar service not initialized".to_string())
+        Err("Calendar service not initialized".into())
     }
 }
@@ -5,24 +5,50 @@
  */
 
 use crate::services::chain_of_thought::{
-    CacheStats, ChainOfThoughtEngine, CoTConfig, Reasoning,
+    self, CacheStats, ChainOfThoughtEngine, CoTConfig, Reasoning, THINKING_COMPLETE_EVENT, THINKING_EVENT,
 };
+use crate::AppResult;
 use std::sync::Arc;
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 
 /// Perform chain-of-thought reasoning
+///
+/// With `show_reasoning` (or the config flag) each step is sent as a
+/// `cot-thinking` event. Visible steps, streamed or returned, are sanitized
+/// of private context; the raw trace is only logged.
 #[tauri::command]
 pub async fn cot_reason(
+    app: AppHandle,
     query: String,
     context: Option<String>,
+    show_reasoning: Option<bool>,
     service: State<'_, Arc<ChainOfThoughtEngine>>,
-) -> Result<Reasoning, String> {
+) -> AppResult<Reasoning> {
     log::info!("CoT reasoning request for query: {}", &query[..query.len().min(50)]);
 
-    service
-        .reason(&query, context.as_deref())
+    let stream = show_reasoning.unwrap_or_else(|| service.get_config().show_reasoning);
+    let mut reasoning = service
+        .reason_observed(&query, context.as_deref(), |step| {
+            if stream {
+                let visible = chain_of_thought::visible_step(step, &query, context.as_deref());
+                if let Err(e) = app.emit(THINKING_EVENT, visible) {
+                    log::warn!("Failed to emit reasoning step: {}", e);
+                }
+            }
+        })
         .await
-        .map_err(|e| format!("Failed to perform reasoning: {}", e))
+        .map_err(|e| format!("Failed to perform reasoning: {}", e))?;
+
+    if stream {
+        app.emit(THINKING_COMPLETE_EVENT, ()).map_err(|e| e.to_string())?;
+    }
+
+    for step in &mut reasoning.steps {
+        let visible = chain_of_thought::visible_step(step, &query, context.as_deref());
+        step.understanding = visible.understanding;
+        step.next_question = visible.next_question;
+    }
+    Ok(reasoning)
 }
 
 /// Update CoT configuration
@@ -30,7 +56,7 @@ pub async fn cot_reason(
 pub async fn cot_update_config(
     config: CoTConfig,
     service: State<'_, Arc<ChainOfThoughtEngine>>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     service.update_config(config);
     Ok(())
 }
@@ -39,7 +65,7 @@ pub async fn cot_update_config(
 #[tauri::command]
 pub async fn cot_get_config(
     service: State<'_, Arc<ChainOfThoughtEngine>>,
-) -> Result<CoTConfig, String> {
+) -> AppResult<CoTConfig> {
     Ok(service.get_config())
 }
 
@@ -47,7 +73,7 @@ pub async fn cot_get_config(
 #[tauri::command]
 pub async fn cot_clear_cache(
     service: State<'_, Arc<ChainOfThoughtEngine>>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     service.clear_cache();
     Ok(())
 }
@@ -56,6 +82,6 @@ pub async fn cot_clear_cache(
 #[tauri::command]
 pub async fn cot_get_cache_stats(
     service: State<'_, Arc<ChainOfThoughtEngine>>,
-) -> Result<CacheStats, String> {
+) -> AppResult<CacheStats> {
     Ok(service.get_cache_stats())
 }
@@ -0,0 +1,128 @@
+/**
+ * Chart Commands (v3.9.0)
+ *
+ * Creating, re-rendering and exporting charts shown with chat responses
+ */
+
+use crate::services::charts::{self, ChartFormat, ChartImage, ChartService, ChartSpec, StoredChart};
+use crate::AppResult;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::State;
+
+/// Store a chart (optionally for a message) and render it
+#[tauri::command]
+pub async fn chart_create(
+    spec: ChartSpec,
+    conversation_id: Option<String>,
+    message_id: Option<String>,
+    format: Option<ChartFormat>,
+    service: State<'_, Arc<ChartService>>,
+) -> AppResult<ChartImage> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        let chart = service_clone.create(spec, conversation_id.as_deref(), message_id.as_deref())?;
+        service_clone.render(&chart.id, format.unwrap_or_default(), None, None)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Failed to create chart: {}", e))?)
+}
+
+/// Render a spec without storing it (previews)
+#[tauri::command]
+pub async fn chart_preview(
+    spec: ChartSpec,
+    format: Option<ChartFormat>,
+    width: Option<u32>,
+    height: Option<u32>,
+) -> AppResult<ChartImage> {
+    Ok(tokio::task::spawn_blocking(move || {
+        charts::render(&spec, format.unwrap_or_default(), width, height)
+            .map_err(|e| format!("Failed to render chart: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// A stored chart's spec
+#[tauri::command]
+pub async fn chart_get(chart_id: String, service: State<'_, Arc<ChartService>>) -> AppResult<StoredChart> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone.get(&chart_id).map_err(|e| format!("Failed to load chart: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Charts of a conversation, or of one message, oldest first
+#[tauri::command]
+pub async fn chart_list(
+    conversation_id: Option<String>,
+    message_id: Option<String>,
+    service: State<'_, Arc<ChartService>>,
+) -> AppResult<Vec<StoredChart>> {
+    if conversation_id.is_none() && message_id.is_none() {
+        return Err("Pass a conversation or message id".into());
+    }
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        match (message_id, conversation_id) {
+            (Some(message_id), _) => service_clone.list_for_message(&message_id),
+            (None, conversation_id) => service_clone.list_for_conversation(&conversation_id.unwrap_or_default()),
+        }
+        .map_err(|e| format!("Failed to list charts: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Render a stored chart again, e.g. larger or as PNG
+#[tauri::command]
+pub async fn chart_render(
+    chart_id: String,
+    format: Option<ChartFormat>,
+    width: Option<u32>,
+    height: Option<u32>,
+    service: State<'_, Arc<ChartService>>,
+) -> AppResult<ChartImage> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .render(&chart_id, format.unwrap_or_default(), width, height)
+            .map_err(|e| format!("Failed to render chart: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Save a chart as .svg or .png
+#[tauri::command]
+pub async fn chart_export(
+    chart_id: String,
+    path: String,
+    width: Option<u32>,
+    height: Option<u32>,
+    service: State<'_, Arc<ChartService>>,
+) -> AppResult<String> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .export(&chart_id, &PathBuf::from(path), width, height)
+            .map(|path| path.to_string_lossy().into_owned())
+            .map_err(|e| format!("Failed to export chart: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn chart_delete(chart_id: String, service: State<'_, Arc<ChartService>>) -> AppResult<()> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone.delete(&chart_id).map_err(|e| format!("Failed to delete chart: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
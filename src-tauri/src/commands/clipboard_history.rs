@@ -0,0 +1,118 @@
+/**
+ * Clipboard History Commands (v3.9.0)
+ */
+
+use crate::services::clipboard_history::{
+    ClipboardEntry, ClipboardHistoryConfig, ClipboardHistoryService, ClipboardSearchResult,
+    ClipboardTransform,
+};
+use crate::services::degradation::ServiceSlot;
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Start recording clipboard history
+#[tauri::command]
+pub async fn clipboard_history_start(
+    service: State<'_, Arc<ServiceSlot<ClipboardHistoryService>>>,
+) -> AppResult<bool> {
+    let service = service.get()?;
+    service.start();
+    Ok(service.is_running())
+}
+
+/// Stop recording clipboard history
+#[tauri::command]
+pub async fn clipboard_history_stop(
+    service: State<'_, Arc<ServiceSlot<ClipboardHistoryService>>>,
+) -> AppResult<bool> {
+    let service = service.get()?;
+    service.stop();
+    Ok(false)
+}
+
+/// Get the most recent clipboard entries
+#[tauri::command]
+pub async fn clipboard_history_get_recent(
+    limit: Option<usize>,
+    service: State<'_, Arc<ServiceSlot<ClipboardHistoryService>>>,
+) -> AppResult<Vec<ClipboardEntry>> {
+    let service = service.get()?;
+    Ok(service
+        .get_recent(limit.unwrap_or(50))
+        .map_err(|e| format!("Failed to get clipboard history: {}", e))?)
+}
+
+/// Search clipboard history by meaning
+#[tauri::command]
+pub async fn clipboard_history_search(
+    query: String,
+    limit: Option<usize>,
+    service: State<'_, Arc<ServiceSlot<ClipboardHistoryService>>>,
+) -> AppResult<Vec<ClipboardSearchResult>> {
+    let service = service.get()?;
+    Ok(service
+        .search(&query, limit.unwrap_or(10))
+        .await
+        .map_err(|e| format!("Failed to search clipboard history: {}", e))?)
+}
+
+/// Summarize, translate, or reformat a selection with the LLM
+#[tauri::command]
+pub async fn clipboard_transform(
+    text: String,
+    transform: ClipboardTransform,
+    copy_result: Option<bool>,
+    service: State<'_, Arc<ServiceSlot<ClipboardHistoryService>>>,
+) -> AppResult<String> {
+    let service = service.get()?;
+    Ok(service
+        .transform(&text, &transform, copy_result.unwrap_or(false))
+        .await
+        .map_err(|e| format!("Failed to transform text: {}", e))?)
+}
+
+/// Delete a clipboard entry
+#[tauri::command]
+pub async fn clipboard_history_delete(
+    id: String,
+    service: State<'_, Arc<ServiceSlot<ClipboardHistoryService>>>,
+) -> AppResult<bool> {
+    let service = service.get()?;
+    Ok(service
+        .delete(&id)
+        .await
+        .map_err(|e| format!("Failed to delete clipboard entry: {}", e))?)
+}
+
+/// Clear all clipboard history (for privacy)
+#[tauri::command]
+pub async fn clipboard_history_clear(
+    service: State<'_, Arc<ServiceSlot<ClipboardHistoryService>>>,
+) -> AppResult<usize> {
+    let service = service.get()?;
+    Ok(service
+        .clear()
+        .await
+        .map_err(|e| format!("Failed to clear clipboard history: {}", e))?)
+}
+
+/// Get clipboard history configuration
+#[tauri::command]
+pub async fn clipboard_history_get_config(
+    service: State<'_, Arc<ServiceSlot<ClipboardHistoryService>>>,
+) -> AppResult<ClipboardHistoryConfig> {
+    let service = service.get()?;
+    Ok(service.get_config())
+}
+
+/// Update clipboard history configuration
+#[tauri::command]
+pub async fn clipboard_history_update_config(
+    config: ClipboardHistoryConfig,
+    service: State<'_, Arc<ServiceSlot<ClipboardHistoryService>>>,
+) -> AppResult<()> {
+    let service = service.get()?;
+    service.update_config(config);
+    Ok(())
+}
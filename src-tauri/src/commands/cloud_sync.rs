@@ -6,6 +6,7 @@ use crate::services::cloud_sync::{
     BackupData, BackupMetadata, CloudSyncService, CloudToken, MemoriesBackup, PersonaBackup,
     SettingsBackup, SyncStatus,
 };
+use crate::AppResult;
 use chrono::Utc;
 use log::{error, info};
 use oauth2::{CsrfToken, PkceCodeVerifier};
@@ -64,7 +65,7 @@ pub async fn cloud_sync_initialize(
     state: State<'_, crate::AppState>,
     client_id: String,
     client_secret: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     info!("Initializing cloud sync service");
 
     let service = CloudSyncService::with_credentials(client_id, client_secret)
@@ -82,7 +83,7 @@ pub async fn cloud_sync_initialize(
 
 /// Start OAuth flow for Google Drive access
 #[tauri::command]
-pub async fn cloud_sync_start_oauth(state: State<'_, crate::AppState>) -> Result<String, String> {
+pub async fn cloud_sync_start_oauth(state: State<'_, crate::AppState>) -> AppResult<String> {
     info!("Starting cloud sync OAuth flow");
 
     let service = {
@@ -119,7 +120,7 @@ pub async fn cloud_sync_complete_oauth(
     state: State<'_, crate::AppState>,
     code: String,
     received_state: String,
-) -> Result<CloudToken, String> {
+) -> AppResult<CloudToken> {
     info!("Completing cloud sync OAuth flow");
 
     // Verify CSRF token and get PKCE verifier
@@ -136,7 +137,7 @@ pub async fn cloud_sync_complete_oauth(
 
         if csrf_token.secret() != &received_state {
             error!("CSRF token mismatch");
-            return Err("CSRF token mismatch, possible security issue".to_string());
+            return Err("CSRF token mismatch, possible security issue".into());
         }
 
         oauth_state
@@ -167,7 +168,7 @@ pub async fn cloud_sync_complete_oauth(
 
 /// Check if authenticated with Google Drive
 #[tauri::command]
-pub async fn cloud_sync_is_authenticated(state: State<'_, crate::AppState>) -> Result<bool, String> {
+pub async fn cloud_sync_is_authenticated(state: State<'_, crate::AppState>) -> AppResult<bool> {
     let service_guard = state
         .cloud_sync_service
         .service
@@ -182,7 +183,7 @@ pub async fn cloud_sync_is_authenticated(state: State<'_, crate::AppState>) -> R
 
 /// Sign out from Google Drive
 #[tauri::command]
-pub async fn cloud_sync_sign_out(state: State<'_, crate::AppState>) -> Result<(), String> {
+pub async fn cloud_sync_sign_out(state: State<'_, crate::AppState>) -> AppResult<()> {
     info!("Signing out from cloud sync");
 
     let service_guard = state
@@ -203,7 +204,7 @@ pub async fn cloud_sync_sign_out(state: State<'_, crate::AppState>) -> Result<()
 
 /// Get sync status
 #[tauri::command]
-pub async fn cloud_sync_get_status(state: State<'_, crate::AppState>) -> Result<SyncStatus, String> {
+pub async fn cloud_sync_get_status(state: State<'_, crate::AppState>) -> AppResult<SyncStatus> {
     let service_guard = state
         .cloud_sync_service
         .service
@@ -227,7 +228,7 @@ pub async fn cloud_sync_get_status(state: State<'_, crate::AppState>) -> Result<
 pub async fn cloud_sync_upload_backup(
     state: State<'_, crate::AppState>,
     request: BackupRequest,
-) -> Result<BackupMetadata, String> {
+) -> AppResult<BackupMetadata> {
     info!("Uploading backup to cloud");
 
     let service = {
@@ -282,7 +283,7 @@ pub async fn cloud_sync_upload_backup(
 #[tauri::command]
 pub async fn cloud_sync_list_backups(
     state: State<'_, crate::AppState>,
-) -> Result<Vec<BackupMetadata>, String> {
+) -> AppResult<Vec<BackupMetadata>> {
     info!("Listing cloud backups");
 
     let service = {
@@ -310,7 +311,7 @@ pub async fn cloud_sync_list_backups(
 pub async fn cloud_sync_download_backup(
     state: State<'_, crate::AppState>,
     file_id: String,
-) -> Result<BackupData, String> {
+) -> AppResult<BackupData> {
     info!("Downloading backup: {}", file_id);
 
     let service = {
@@ -338,7 +339,7 @@ pub async fn cloud_sync_download_backup(
 pub async fn cloud_sync_delete_backup(
     state: State<'_, crate::AppState>,
     file_id: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     info!("Deleting backup: {}", file_id);
 
     let service = {
@@ -365,7 +366,7 @@ pub async fn cloud_sync_delete_backup(
 #[tauri::command]
 pub async fn cloud_sync_refresh_token(
     state: State<'_, crate::AppState>,
-) -> Result<CloudToken, String> {
+) -> AppResult<CloudToken> {
     info!("Refreshing cloud sync token");
 
     let service = {
@@ -393,7 +394,7 @@ pub async fn cloud_sync_refresh_token(
 pub async fn cloud_sync_set_token(
     state: State<'_, crate::AppState>,
     token: CloudToken,
-) -> Result<(), String> {
+) -> AppResult<()> {
     info!("Setting cloud sync token manually");
 
     let service = {
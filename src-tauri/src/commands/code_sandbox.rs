@@ -0,0 +1,48 @@
+/**
+ * Code Sandbox Commands (v3.9.0)
+ *
+ * Running JavaScript in the agent's sandbox and configuring its limits
+ */
+
+use crate::services::code_sandbox::{CodeRunResult, CodeSandboxConfig, CodeSandboxService};
+use crate::services::degradation::ServiceSlot;
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Run a script the way the run_code tool does (e.g. to re-run the agent's code)
+#[tauri::command]
+pub async fn sandbox_run_code(
+    code: String,
+    input: Option<serde_json::Value>,
+    timeout_secs: Option<u64>,
+    service: State<'_, Arc<ServiceSlot<CodeSandboxService>>>,
+) -> AppResult<CodeRunResult> {
+    let service = service.get()?;
+    Ok(service
+        .run(&code, input, timeout_secs)
+        .await
+        .map_err(|e| format!("Failed to run code: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn sandbox_get_config(service: State<'_, Arc<ServiceSlot<CodeSandboxService>>>) -> AppResult<CodeSandboxConfig> {
+    let service = service.get()?;
+    Ok(service.get_config())
+}
+
+/// Save limits and readable folders; returns the config as stored (limits clamped)
+#[tauri::command]
+pub async fn sandbox_update_config(
+    config: CodeSandboxConfig,
+    service: State<'_, Arc<ServiceSlot<CodeSandboxService>>>,
+) -> AppResult<CodeSandboxConfig> {
+    let service_clone = service.get()?;
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .update_config(config)
+            .map_err(|e| format!("Failed to update sandbox settings: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
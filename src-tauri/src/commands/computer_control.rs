@@ -1,6 +1,7 @@
 use crate::services::computer_control::{
     ActionResult, ComputerControlService, RestrictedZone,
 };
+use crate::AppResult;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tauri::State;
@@ -10,11 +11,11 @@ use tauri::State;
 pub async fn computer_click_element(
     description: String,
     service: State<'_, Arc<ComputerControlService>>,
-) -> Result<ActionResult, String> {
-    service
+) -> AppResult<ActionResult> {
+    Ok(service
         .click_element(&description)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
 
 /// Type text at current cursor position
@@ -22,11 +23,11 @@ pub async fn computer_click_element(
 pub async fn computer_type_text(
     text: String,
     service: State<'_, Arc<ComputerControlService>>,
-) -> Result<ActionResult, String> {
-    service
+) -> AppResult<ActionResult> {
+    Ok(service
         .type_text(&text)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
 
 /// Press a keyboard key
@@ -34,11 +35,11 @@ pub async fn computer_type_text(
 pub async fn computer_press_key(
     key: String,
     service: State<'_, Arc<ComputerControlService>>,
-) -> Result<ActionResult, String> {
-    service
+) -> AppResult<ActionResult> {
+    Ok(service
         .press_key(&key)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
 
 /// Scroll in a direction
@@ -47,11 +48,11 @@ pub async fn computer_scroll(
     direction: String,
     amount: i32,
     service: State<'_, Arc<ComputerControlService>>,
-) -> Result<ActionResult, String> {
-    service
+) -> AppResult<ActionResult> {
+    Ok(service
         .scroll(&direction, amount)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
 
 /// Move mouse to coordinates
@@ -60,11 +61,11 @@ pub async fn computer_move_mouse(
     x: i32,
     y: i32,
     service: State<'_, Arc<ComputerControlService>>,
-) -> Result<ActionResult, String> {
-    service
+) -> AppResult<ActionResult> {
+    Ok(service
         .move_mouse(x, y)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
 
 /// Wait for specified milliseconds
@@ -72,11 +73,11 @@ pub async fn computer_move_mouse(
 pub async fn computer_wait(
     milliseconds: u64,
     service: State<'_, Arc<ComputerControlService>>,
-) -> Result<ActionResult, String> {
-    service
+) -> AppResult<ActionResult> {
+    Ok(service
         .wait(milliseconds)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
 
 /// Execute AppleScript (macOS only)
@@ -85,11 +86,11 @@ pub async fn computer_wait(
 pub async fn computer_execute_applescript(
     script: String,
     service: State<'_, Arc<ComputerControlService>>,
-) -> Result<ActionResult, String> {
-    service
+) -> AppResult<ActionResult> {
+    Ok(service
         .execute_applescript(&script)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
 
 /// Get action history
@@ -97,20 +98,20 @@ pub async fn computer_execute_applescript(
 pub fn computer_get_action_history(
     limit: usize,
     service: State<'_, Arc<ComputerControlService>>,
-) -> Result<Vec<ActionResult>, String> {
-    service
+) -> AppResult<Vec<ActionResult>> {
+    Ok(service
         .get_action_history(limit)
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
 
 /// Clear action history
 #[tauri::command]
 pub fn computer_clear_action_history(
     service: State<'_, Arc<ComputerControlService>>,
-) -> Result<usize, String> {
-    service
+) -> AppResult<usize> {
+    Ok(service
         .clear_action_history()
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
 
 /// Response for getting safety config
@@ -126,7 +127,7 @@ pub struct SafetyConfigResponse {
 #[tauri::command]
 pub fn computer_get_safety_config(
     service: State<'_, Arc<ComputerControlService>>,
-) -> Result<SafetyConfigResponse, String> {
+) -> AppResult<SafetyConfigResponse> {
     let config = service.get_safety_config();
     Ok(SafetyConfigResponse {
         restricted_zones: config.restricted_zones.clone(),
@@ -153,7 +154,7 @@ pub struct ComputerControlStats {
 #[tauri::command]
 pub fn computer_get_stats(
     service: State<'_, Arc<ComputerControlService>>,
-) -> Result<ComputerControlStats, String> {
+) -> AppResult<ComputerControlStats> {
     let history = service
         .get_action_history(1000)
         .map_err(|e| e.to_string())?;
@@ -181,7 +182,7 @@ pub async fn computer_click_and_type(
     element_description: String,
     text: String,
     service: State<'_, Arc<ComputerControlService>>,
-) -> Result<Vec<ActionResult>, String> {
+) -> AppResult<Vec<ActionResult>> {
     let mut results = Vec::new();
 
     // Click the element
@@ -213,7 +214,7 @@ pub async fn computer_click_and_type(
 pub async fn computer_type_and_submit(
     text: String,
     service: State<'_, Arc<ComputerControlService>>,
-) -> Result<Vec<ActionResult>, String> {
+) -> AppResult<Vec<ActionResult>> {
     let mut results = Vec::new();
 
     // Type the text
@@ -237,7 +238,7 @@ pub async fn computer_type_and_submit(
 #[tauri::command]
 pub async fn computer_test_connection(
     service: State<'_, Arc<ComputerControlService>>,
-) -> Result<String, String> {
+) -> AppResult<String> {
     // Test by moving mouse slightly and back
     service
         .wait(100)
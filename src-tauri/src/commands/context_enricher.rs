@@ -12,6 +12,7 @@
 use crate::services::context_enricher::{
     ContextEnricherConfig, ContextEnricherService, EnrichedContext,
 };
+use crate::AppResult;
 use std::sync::Arc;
 use tauri::State;
 
@@ -21,13 +22,13 @@ pub async fn context_enrich(
     query: String,
     conversation_id: Option<String>,
     service: State<'_, Arc<ContextEnricherService>>,
-) -> Result<EnrichedContext, String> {
+) -> AppResult<EnrichedContext> {
     log::info!("Context enrichment request for query: {}", &query[..query.len().min(50)]);
 
-    service
+    Ok(service
         .enrich(&query, conversation_id.as_deref())
         .await
-        .map_err(|e| format!("Failed to enrich context: {}", e))
+        .map_err(|e| format!("Failed to enrich context: {}", e))?)
 }
 
 /// Update context enricher configuration
@@ -35,7 +36,7 @@ pub async fn context_enrich(
 pub async fn context_update_config(
     config: ContextEnricherConfig,
     service: State<'_, Arc<ContextEnricherService>>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     service.update_config(config);
     Ok(())
 }
@@ -44,6 +45,6 @@ pub async fn context_update_config(
 #[tauri::command]
 pub async fn context_get_config(
     service: State<'_, Arc<ContextEnricherService>>,
-) -> Result<ContextEnricherConfig, String> {
+) -> AppResult<ContextEnricherConfig> {
     Ok(service.get_config())
 }
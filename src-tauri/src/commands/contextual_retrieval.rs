@@ -12,6 +12,7 @@
 use crate::services::contextual_retrieval::{
     ContextualBoost, ContextualRetrievalConfig, ContextualRetrievalService, BoostStats,
 };
+use crate::AppResult;
 use std::sync::Arc;
 use tauri::State;
 
@@ -20,36 +21,36 @@ use tauri::State;
 pub async fn contextual_boost_memories(
     conversation_text: String,
     service: State<'_, Arc<ContextualRetrievalService>>,
-) -> Result<Vec<ContextualBoost>, String> {
+) -> AppResult<Vec<ContextualBoost>> {
     log::info!(
         "Boosting contextual memories for conversation (length: {})",
         conversation_text.len()
     );
 
-    service
+    Ok(service
         .boost_contextual_memories(&conversation_text)
         .await
-        .map_err(|e| format!("Failed to boost contextual memories: {}", e))
+        .map_err(|e| format!("Failed to boost contextual memories: {}", e))?)
 }
 
 /// Decay old boosts (called by decay worker)
 #[tauri::command]
 pub async fn contextual_decay_old_boosts(
     service: State<'_, Arc<ContextualRetrievalService>>,
-) -> Result<usize, String> {
-    service
+) -> AppResult<usize> {
+    Ok(service
         .decay_old_boosts()
-        .map_err(|e| format!("Failed to decay old boosts: {}", e))
+        .map_err(|e| format!("Failed to decay old boosts: {}", e))?)
 }
 
 /// Get boost statistics
 #[tauri::command]
 pub async fn contextual_get_boost_stats(
     service: State<'_, Arc<ContextualRetrievalService>>,
-) -> Result<BoostStats, String> {
-    service
+) -> AppResult<BoostStats> {
+    Ok(service
         .get_boost_stats()
-        .map_err(|e| format!("Failed to get boost stats: {}", e))
+        .map_err(|e| format!("Failed to get boost stats: {}", e))?)
 }
 
 /// Update contextual retrieval configuration
@@ -57,16 +58,16 @@ pub async fn contextual_get_boost_stats(
 pub async fn contextual_update_config(
     config: ContextualRetrievalConfig,
     service: State<'_, Arc<ContextualRetrievalService>>,
-) -> Result<(), String> {
-    service
+) -> AppResult<()> {
+    Ok(service
         .update_config(config)
-        .map_err(|e| format!("Failed to update config: {}", e))
+        .map_err(|e| format!("Failed to update config: {}", e))?)
 }
 
 /// Get contextual retrieval configuration
 #[tauri::command]
 pub async fn contextual_get_config(
     service: State<'_, Arc<ContextualRetrievalService>>,
-) -> Result<ContextualRetrievalConfig, String> {
+) -> AppResult<ContextualRetrievalConfig> {
     Ok(service.get_config())
 }
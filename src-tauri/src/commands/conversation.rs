@@ -1,135 +1,315 @@
+use crate::AppError;
+use crate::AppResult;
 use crate::AppState;
 use crate::database::models::Message;
+use crate::services::conversation_documents::{ConversationDocument, ConversationDocumentService};
+use crate::services::conversation_language::ConversationLanguageService;
+use crate::services::conversation_organizer::{
+    self, ConversationFilter, ConversationFolder, ConversationSummary, TagCount,
+};
+use crate::services::memory_scope;
+use crate::services::ollama;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use tauri::State;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ConversationSummary {
-    pub id: String,
-    pub title: String,
-    pub mode: String,
-    pub created_at: i64,
-    pub updated_at: i64,
-    pub message_count: i32,
-    pub last_message_preview: Option<String>,
-}
-
-/// Get all conversations ordered by most recent
+/// Get conversations, pinned first, then most recent
 ///
-/// Performance optimized: Uses a single query with LEFT JOIN instead of N+1 subqueries.
-/// The ROW_NUMBER() window function finds the most recent message per conversation efficiently.
-#[tauri::command]
-pub async fn get_conversations(state: State<'_, AppState>) -> Result<Vec<ConversationSummary>, String> {
-    log::info!("Getting all conversations");
-
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
-
-    // Optimized query: Single pass using window function instead of N+1 subqueries
-    // ROW_NUMBER() ranks messages per conversation by timestamp DESC
-    // We then filter to get only the most recent message (rn = 1)
-    let mut stmt = conn
-        .prepare(
-            "WITH ranked_messages AS (
-                SELECT
-                    conversation_id,
-                    content,
-                    ROW_NUMBER() OVER (PARTITION BY conversation_id ORDER BY timestamp DESC) as rn
-                FROM messages
-            )
-            SELECT
-                c.id,
-                c.title,
-                c.mode,
-                c.created_at,
-                c.updated_at,
-                c.message_count,
-                rm.content as last_message
-            FROM conversations c
-            LEFT JOIN ranked_messages rm ON c.id = rm.conversation_id AND rm.rn = 1
-            ORDER BY c.updated_at DESC
-            LIMIT 100",
-        )
-        .map_err(|e| e.to_string())?;
+/// Without a filter, archived conversations are hidden and at most 100 are returned (v3.9.0).
+#[tauri::command]
+pub async fn get_conversations(
+    state: State<'_, AppState>,
+    filter: Option<ConversationFilter>,
+) -> AppResult<Vec<ConversationSummary>> {
+    log::info!("Getting conversations (filter: {:?})", filter);
 
-    let conversations: Vec<ConversationSummary> = stmt
-        .query_map([], |row| {
-            Ok(ConversationSummary {
-                id: row.get(0)?,
-                title: row.get(1)?,
-                mode: row.get(2)?,
-                created_at: row.get(3)?,
-                updated_at: row.get(4)?,
-                message_count: row.get(5)?,
-                last_message_preview: row.get(6).ok(),
-            })
-        })
-        .map_err(|e| e.to_string())?
-        .filter_map(|r| r.ok())
-        .collect();
+    state.db.call(move |db| {
+        let conversations = conversation_organizer::list_conversations(db.conn(), &filter.unwrap_or_default())
+            .map_err(|e| e.to_string())?;
 
-    log::info!("Found {} conversations", conversations.len());
-    Ok(conversations)
+        log::info!("Found {} conversations", conversations.len());
+        Ok(conversations)
+    }).await
 }
 
 /// Get messages for a specific conversation
+///
+/// Stale messages (superseded by an edit) are hidden unless `include_stale` is set (v3.9.0)
 #[tauri::command]
 pub async fn get_conversation_messages(
     state: State<'_, AppState>,
     conversation_id: String,
-) -> Result<Vec<Message>, String> {
+    include_stale: Option<bool>,
+) -> AppResult<Vec<Message>> {
     log::info!("Getting messages for conversation: {}", conversation_id);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
+    state.db.call(move |db| {
+        let conn = db.conn();
 
-    let mut stmt = conn
-        .prepare(
-            "SELECT id, conversation_id, role, content, timestamp, tokens, response_time, context_level, satisfaction
-             FROM messages
-             WHERE conversation_id = ?1
-             ORDER BY timestamp ASC",
-        )
-        .map_err(|e| e.to_string())?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, conversation_id, role, content, timestamp, tokens, response_time, context_level, satisfaction, is_stale, edited_at
+                 FROM messages
+                 WHERE conversation_id = ?1 AND (?2 = 1 OR is_stale = 0)
+                 ORDER BY timestamp ASC",
+            )
+            .map_err(|e| e.to_string())?;
+
+        let messages: Vec<Message> = stmt
+            .query_map(rusqlite::params![&conversation_id, include_stale.unwrap_or(false)], |row| {
+                Ok(Message {
+                    id: row.get(0)?,
+                    conversation_id: row.get(1)?,
+                    role: row.get(2)?,
+                    content: row.get(3)?,
+                    timestamp: row.get(4)?,
+                    tokens: row.get(5).ok(),
+                    response_time: row.get(6).ok(),
+                    context_level: row.get(7).ok(),
+                    satisfaction: row.get(8).ok(),
+                    is_stale: row.get::<_, i64>(9).map(|v| v != 0).unwrap_or(false),
+                    edited_at: row.get(10).ok(),
+                })
+            })
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        log::info!("Found {} messages for conversation {}", messages.len(), conversation_id);
+        Ok(messages)
+    }).await
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MessageRevision {
+    pub id: i64,
+    pub message_id: String,
+    pub conversation_id: String,
+    pub role: String,
+    pub content: String,
+    pub timestamp: i64,
+    pub reason: String, // "edited" | "superseded"
+    pub superseded_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EditMessageResult {
+    pub conversation_id: String,
+    pub edited_message_id: String,
+    pub response_message_id: String,
+    pub response: String,
+    pub stale_message_ids: Vec<String>,
+}
+
+/// Edit a past user message and regenerate the reply from that point (v3.9.0)
+///
+/// Messages after the edited one are marked stale rather than deleted, and both the
+/// original text and the superseded messages are kept in `message_revisions`.
+#[tauri::command]
+pub async fn conversation_edit_message(
+    state: State<'_, AppState>,
+    language_service: State<'_, Arc<ConversationLanguageService>>,
+    message_id: String,
+    new_content: String,
+) -> AppResult<EditMessageResult> {
+    log::info!("Editing message: {}", message_id);
+
+    if new_content.trim().is_empty() {
+        return Err(AppError::missing_field("Message content cannot be empty"));
+    }
+
+    // Block 1: Archive old content, apply the edit, invalidate downstream messages
+    let (conversation_id, edited_timestamp, stale_message_ids) = {
+        let message_id = message_id.clone();
+        let new_content = new_content.clone();
+        state.db.call(move |db| {
+            let tx = db.conn().unchecked_transaction().map_err(|e| e.to_string())?;
+
+            let (conversation_id, role, old_content, edited_timestamp): (String, String, String, i64) = tx
+                .query_row(
+                    "SELECT conversation_id, role, content, timestamp FROM messages WHERE id = ?1 AND is_stale = 0",
+                    [&message_id],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+                )
+                .map_err(|e| format!("Message not found: {}", e))?;
 
-    let messages: Vec<Message> = stmt
-        .query_map([&conversation_id], |row| {
-            Ok(Message {
-                id: row.get(0)?,
-                conversation_id: row.get(1)?,
-                role: row.get(2)?,
-                content: row.get(3)?,
-                timestamp: row.get(4)?,
-                tokens: row.get(5).ok(),
-                response_time: row.get(6).ok(),
-                context_level: row.get(7).ok(),
-                satisfaction: row.get(8).ok(),
+            if role != "user" {
+                return Err("Only user messages can be edited".to_string());
+            }
+
+            let now = chrono::Utc::now().timestamp_millis();
+
+            tx.execute(
+                "INSERT INTO message_revisions (message_id, conversation_id, role, content, timestamp, reason, superseded_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 'edited', ?6)",
+                rusqlite::params![&message_id, &conversation_id, &role, &old_content, edited_timestamp, now],
+            )
+            .map_err(|e| e.to_string())?;
+
+            tx.execute(
+                "UPDATE messages SET content = ?1, edited_at = ?2 WHERE id = ?3",
+                rusqlite::params![&new_content, now, &message_id],
+            )
+            .map_err(|e| e.to_string())?;
+
+            // Everything after the edit point was produced from the old text
+            tx.execute(
+                "INSERT INTO message_revisions (message_id, conversation_id, role, content, timestamp, reason, superseded_at)
+                 SELECT id, conversation_id, role, content, timestamp, 'superseded', ?3
+                 FROM messages
+                 WHERE conversation_id = ?1 AND timestamp > ?2 AND is_stale = 0",
+                rusqlite::params![&conversation_id, edited_timestamp, now],
+            )
+            .map_err(|e| e.to_string())?;
+
+            let stale_message_ids: Vec<String> = {
+                let mut stmt = tx
+                    .prepare(
+                        "SELECT id FROM messages
+                         WHERE conversation_id = ?1 AND timestamp > ?2 AND is_stale = 0
+                         ORDER BY timestamp ASC",
+                    )
+                    .map_err(|e| e.to_string())?;
+                let ids = stmt
+                    .query_map(rusqlite::params![&conversation_id, edited_timestamp], |row| row.get(0))
+                    .map_err(|e| e.to_string())?
+                    .filter_map(|r| r.ok())
+                    .collect();
+                ids
+            };
+
+            tx.execute(
+                "UPDATE messages SET is_stale = 1
+                 WHERE conversation_id = ?1 AND timestamp > ?2 AND is_stale = 0",
+                rusqlite::params![&conversation_id, edited_timestamp],
+            )
+            .map_err(|e| e.to_string())?;
+
+            tx.commit().map_err(|e| e.to_string())?;
+            Ok::<_, String>((conversation_id, edited_timestamp, stale_message_ids))
+        }).await?
+    };
+
+    log::info!("Marked {} downstream messages as stale", stale_message_ids.len());
+
+    let expected_language = language_service
+        .resolve_for_message(&conversation_id, &new_content)
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to resolve conversation language: {}", e);
+            None
+        });
+
+    let ai_response = ollama::generate_response_with_rag_and_persona_ref(&new_content, Some(state.rag.clone()), Some(state.db.as_mutex())).await?;
+    let ai_response = language_service
+        .enforce(expected_language, &new_content, ai_response)
+        .await
+        .response;
+    let response_message_id = format!("msg_{}", chrono::Utc::now().timestamp_millis());
+
+    // Block 2: Save the regenerated reply right after the edited message
+    {
+        let response_message_id = response_message_id.clone();
+        let conversation_id = conversation_id.clone();
+        let ai_response = ai_response.clone();
+        state.db.call(move |db| {
+            let conn = db.conn();
+
+            conn.execute(
+                "INSERT INTO messages (id, conversation_id, role, content, timestamp)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                rusqlite::params![
+                    &response_message_id,
+                    &conversation_id,
+                    "assistant",
+                    &ai_response,
+                    edited_timestamp + 1
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+
+            conn.execute(
+                "UPDATE conversations SET updated_at = ?1, message_count = message_count + 1 WHERE id = ?2",
+                rusqlite::params![chrono::Utc::now().timestamp_millis(), &conversation_id],
+            )
+            .map_err(|e| e.to_string())?;
+
+            Ok::<_, String>(())
+        }).await?;
+    }
+
+    log::info!("Regenerated reply {} for edited message {}", response_message_id, message_id);
+    Ok(EditMessageResult {
+        conversation_id,
+        edited_message_id: message_id,
+        response_message_id,
+        response: ai_response,
+        stale_message_ids,
+    })
+}
+
+/// Get the audit trail of edited / superseded messages for a conversation (v3.9.0)
+#[tauri::command]
+pub async fn conversation_get_message_revisions(
+    state: State<'_, AppState>,
+    conversation_id: String,
+) -> AppResult<Vec<MessageRevision>> {
+    state.db.call(move |db| {
+        let conn = db.conn();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, message_id, conversation_id, role, content, timestamp, reason, superseded_at
+                 FROM message_revisions
+                 WHERE conversation_id = ?1
+                 ORDER BY superseded_at ASC, id ASC",
+            )
+            .map_err(|e| e.to_string())?;
+
+        let revisions: Vec<MessageRevision> = stmt
+            .query_map([&conversation_id], |row| {
+                Ok(MessageRevision {
+                    id: row.get(0)?,
+                    message_id: row.get(1)?,
+                    conversation_id: row.get(2)?,
+                    role: row.get(3)?,
+                    content: row.get(4)?,
+                    timestamp: row.get(5)?,
+                    reason: row.get(6)?,
+                    superseded_at: row.get(7)?,
+                })
             })
-        })
-        .map_err(|e| e.to_string())?
-        .filter_map(|r| r.ok())
-        .collect();
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
 
-    log::info!("Found {} messages for conversation {}", messages.len(), conversation_id);
-    Ok(messages)
+        Ok(revisions)
+    }).await
 }
 
-/// Delete a conversation and all its messages
+/// Delete a conversation, all its messages and the memories scoped to it
 #[tauri::command]
 pub async fn delete_conversation(
     state: State<'_, AppState>,
     conversation_id: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     log::info!("Deleting conversation: {}", conversation_id);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
+    let memory_ids = state.db.call(move |db| {
+        let conn = db.conn();
+        let memory_ids = memory_scope::conversation_memory_ids(conn, &conversation_id)
+            .map_err(|e| e.to_string())?;
 
-    // Delete conversation (CASCADE will delete messages)
-    conn.execute("DELETE FROM conversations WHERE id = ?1", [&conversation_id])
-        .map_err(|e| e.to_string())?;
+        // Delete conversation (CASCADE will delete messages and attached document chunks)
+        conn.execute("DELETE FROM conversations WHERE id = ?1", [&conversation_id])
+            .map_err(|e| e.to_string())?;
 
-    log::info!("Successfully deleted conversation: {}", conversation_id);
+        log::info!("Successfully deleted conversation: {}", conversation_id);
+        Ok::<_, String>(memory_ids)
+    }).await?;
+
+    // Only retrievable in this conversation, so nowhere once it's gone
+    state.rag.delete_memories(&memory_ids).await
+        .map_err(|e| format!("Failed to delete conversation memories: {}", e))?;
     Ok(())
 }
 
@@ -139,18 +319,161 @@ pub async fn update_conversation_title(
     state: State<'_, AppState>,
     conversation_id: String,
     new_title: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     log::info!("Updating conversation {} title to: {}", conversation_id, new_title);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
+    state.db.call(move |db| {
+        let conn = db.conn();
+
+        conn.execute(
+            "UPDATE conversations SET title = ?1, updated_at = ?2 WHERE id = ?3",
+            rusqlite::params![&new_title, chrono::Utc::now().timestamp_millis(), &conversation_id],
+        )
+        .map_err(|e| e.to_string())?;
+
+        log::info!("Successfully updated conversation title");
+        Ok(())
+    }).await
+}
+
+/// Pin a conversation to the top of the list (v3.9.0)
+#[tauri::command]
+pub async fn conversation_set_pinned(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    pinned: bool,
+) -> AppResult<()> {
+    Ok(state.db.call(move |db| {
+        conversation_organizer::set_pinned(db.conn(), &conversation_id, pinned).map_err(|e| e.to_string())
+    }).await?)
+}
+
+#[tauri::command]
+pub async fn conversation_set_favorite(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    favorite: bool,
+) -> AppResult<()> {
+    Ok(state.db.call(move |db| {
+        conversation_organizer::set_favorite(db.conn(), &conversation_id, favorite).map_err(|e| e.to_string())
+    }).await?)
+}
+
+/// Archive (hide from the default list) or restore a conversation (v3.9.0)
+#[tauri::command]
+pub async fn conversation_set_archived(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    archived: bool,
+) -> AppResult<()> {
+    Ok(state.db.call(move |db| {
+        conversation_organizer::set_archived(db.conn(), &conversation_id, archived).map_err(|e| e.to_string())
+    }).await?)
+}
+
+/// Replace the tags of a conversation; returns the stored tags (v3.9.0)
+#[tauri::command]
+pub async fn conversation_set_tags(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    tags: Vec<String>,
+) -> AppResult<Vec<String>> {
+    Ok(state.db.call(move |db| {
+        conversation_organizer::set_tags(db.conn(), &conversation_id, &tags).map_err(|e| e.to_string())
+    }).await?)
+}
+
+/// All tags in use with their conversation counts (v3.9.0)
+#[tauri::command]
+pub async fn conversation_list_tags(state: State<'_, AppState>) -> AppResult<Vec<TagCount>> {
+    Ok(state.db.call(move |db| {
+        conversation_organizer::list_tags(db.conn()).map_err(|e| e.to_string())
+    }).await?)
+}
+
+/// Move a conversation into a folder, or out of any folder with null (v3.9.0)
+#[tauri::command]
+pub async fn conversation_move_to_folder(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    folder_id: Option<String>,
+) -> AppResult<()> {
+    Ok(state.db.call(move |db| {
+        conversation_organizer::move_to_folder(db.conn(), &conversation_id, folder_id.as_deref())
+            .map_err(|e| e.to_string())
+    }).await?)
+}
+
+#[tauri::command]
+pub async fn conversation_list_folders(state: State<'_, AppState>) -> AppResult<Vec<ConversationFolder>> {
+    Ok(state.db.call(move |db| {
+        conversation_organizer::list_folders(db.conn()).map_err(|e| e.to_string())
+    }).await?)
+}
+
+#[tauri::command]
+pub async fn conversation_create_folder(
+    state: State<'_, AppState>,
+    name: String,
+) -> AppResult<ConversationFolder> {
+    Ok(state.db.call(move |db| {
+        conversation_organizer::create_folder(db.conn(), &name).map_err(|e| e.to_string())
+    }).await?)
+}
+
+#[tauri::command]
+pub async fn conversation_rename_folder(
+    state: State<'_, AppState>,
+    folder_id: String,
+    name: String,
+) -> AppResult<()> {
+    Ok(state.db.call(move |db| {
+        conversation_organizer::rename_folder(db.conn(), &folder_id, &name).map_err(|e| e.to_string())
+    }).await?)
+}
 
-    conn.execute(
-        "UPDATE conversations SET title = ?1, updated_at = ?2 WHERE id = ?3",
-        rusqlite::params![&new_title, chrono::Utc::now().timestamp_millis(), &conversation_id],
-    )
-    .map_err(|e| e.to_string())?;
+/// Delete a folder; its conversations stay, outside any folder (v3.9.0)
+#[tauri::command]
+pub async fn conversation_delete_folder(
+    state: State<'_, AppState>,
+    folder_id: String,
+) -> AppResult<()> {
+    Ok(state.db.call(move |db| {
+        conversation_organizer::delete_folder(db.conn(), &folder_id).map_err(|e| e.to_string())
+    }).await?)
+}
 
-    log::info!("Successfully updated conversation title");
+/// Attach a local file to a conversation for document Q&A (v3.9.0)
+///
+/// The file is indexed for this conversation only and removed with it.
+#[tauri::command]
+pub async fn conversation_attach_document(
+    state: State<'_, AppState>,
+    documents: State<'_, Arc<ConversationDocumentService>>,
+    conversation_id: String,
+    path: String,
+) -> AppResult<ConversationDocument> {
+    Ok(documents
+        .attach_file(&conversation_id, &path, |text, source| state.rag.chunk(text, source))
+        .map_err(|e| format!("Failed to attach document: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn conversation_list_documents(
+    documents: State<'_, Arc<ConversationDocumentService>>,
+    conversation_id: String,
+) -> AppResult<Vec<ConversationDocument>> {
+    Ok(documents.list(&conversation_id)
+        .map_err(|e| format!("Failed to list documents: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn conversation_detach_document(
+    documents: State<'_, Arc<ConversationDocumentService>>,
+    conversation_id: String,
+    path: String,
+) -> AppResult<()> {
+    documents.detach(&conversation_id, &path)
+        .map_err(|e| format!("Failed to detach document: {}", e))?;
     Ok(())
 }
@@ -0,0 +1,38 @@
+/**
+ * Conversation Digest Commands (v3.9.0)
+ *
+ * End-of-conversation summaries into durable memories
+ */
+
+use crate::services::conversation_digest::{ConversationDigest, ConversationDigestService};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Digest of a conversation, if it has one yet
+#[tauri::command]
+pub async fn conversation_digest_get(
+    conversation_id: String,
+    service: State<'_, Arc<ConversationDigestService>>,
+) -> AppResult<Option<ConversationDigest>> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .get_digest(&conversation_id)
+            .map_err(|e| format!("Failed to get conversation digest: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Summarize a conversation the user ended instead of waiting for it to go idle
+#[tauri::command]
+pub async fn conversation_digest_now(
+    conversation_id: String,
+    service: State<'_, Arc<ConversationDigestService>>,
+) -> AppResult<ConversationDigest> {
+    Ok(service
+        .digest_conversation(&conversation_id)
+        .await
+        .map_err(|e| format!("Failed to digest conversation: {}", e))?)
+}
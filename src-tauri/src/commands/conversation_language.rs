@@ -0,0 +1,51 @@
+/**
+ * Conversation Language Commands (v3.9.0)
+ */
+
+use crate::services::conversation_language::{
+    ConversationLanguage, ConversationLanguageService, ConversationLanguageState,
+};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Get the detected/locked language of a conversation
+#[tauri::command]
+pub async fn conversation_get_language(
+    conversation_id: String,
+    service: State<'_, Arc<ConversationLanguageService>>,
+) -> AppResult<ConversationLanguageState> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .get_state(&conversation_id)
+            .map_err(|e| format!("Failed to get conversation language: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Lock a conversation to a language ("ko" / "en"), or clear the lock with null
+#[tauri::command]
+pub async fn conversation_set_language(
+    conversation_id: String,
+    language: Option<String>,
+    service: State<'_, Arc<ConversationLanguageService>>,
+) -> AppResult<ConversationLanguageState> {
+    let language = match language.as_deref() {
+        Some(code) => Some(
+            ConversationLanguage::from_code(code)
+                .ok_or_else(|| format!("Unsupported language: {}", code))?,
+        ),
+        None => None,
+    };
+
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .set_override(&conversation_id, language)
+            .map_err(|e| format!("Failed to set conversation language: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
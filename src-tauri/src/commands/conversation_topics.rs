@@ -0,0 +1,69 @@
+/**
+ * Conversation Topics Commands (v3.9.0)
+ *
+ * Topic taxonomy, per-conversation topics and on-demand relabeling
+ */
+
+use crate::services::conversation_topics::{ConversationLabels, ConversationTopicsService, TopicCount};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// List the learned topics with their conversation counts
+#[tauri::command]
+pub async fn conversation_topics_list(
+    service: State<'_, Arc<ConversationTopicsService>>,
+) -> AppResult<Vec<TopicCount>> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .list_topics()
+            .map_err(|e| format!("Failed to list conversation topics: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn conversation_topics_get(
+    conversation_id: String,
+    service: State<'_, Arc<ConversationTopicsService>>,
+) -> AppResult<Vec<String>> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .get_topics(&conversation_id)
+            .map_err(|e| format!("Failed to get conversation topics: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Set a conversation's topics by hand; automatic labeling leaves them alone afterwards
+#[tauri::command]
+pub async fn conversation_topics_set(
+    conversation_id: String,
+    topics: Vec<String>,
+    service: State<'_, Arc<ConversationTopicsService>>,
+) -> AppResult<Vec<String>> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .set_topics(&conversation_id, &topics)
+            .map_err(|e| format!("Failed to set conversation topics: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Title (if untitled) and label one conversation now instead of waiting for the background job
+#[tauri::command]
+pub async fn conversation_topics_relabel(
+    conversation_id: String,
+    service: State<'_, Arc<ConversationTopicsService>>,
+) -> AppResult<ConversationLabels> {
+    Ok(service
+        .label_conversation(&conversation_id)
+        .await
+        .map_err(|e| format!("Failed to label conversation: {}", e))?)
+}
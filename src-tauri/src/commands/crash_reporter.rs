@@ -2,9 +2,13 @@
  * Crash Reporter Commands for Tauri (v3.4.0 - Enhanced)
  *
  * Exposes crash reporting controls to the frontend
+ * - Review-before-send queue for remote submission (v3.9.0)
  */
 
-use crate::services::crash_reporter::{CrashReport, CrashReporterService, CrashReportingSettings};
+use crate::services::crash_reporter::{
+    send_pending_report, CrashReport, CrashReporterService, CrashReportingSettings, PendingCrashReport,
+};
+use crate::AppResult;
 use log::{error, info};
 use std::sync::{Arc, Mutex};
 use tauri::State;
@@ -18,7 +22,7 @@ pub struct CrashReporterState {
 #[tauri::command]
 pub async fn crash_reporter_is_enabled(
     state: State<'_, CrashReporterState>,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     info!("Command: crash_reporter_is_enabled");
 
     let service = state.service.lock().unwrap();
@@ -29,35 +33,35 @@ pub async fn crash_reporter_is_enabled(
 #[tauri::command]
 pub async fn crash_reporter_enable(
     state: State<'_, CrashReporterState>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     info!("Command: crash_reporter_enable");
 
     let service = state.service.lock().unwrap();
-    service.enable().map_err(|e| {
+    Ok(service.enable().map_err(|e| {
         error!("Failed to enable crash reporting: {}", e);
         format!("Failed to enable crash reporting: {}", e)
-    })
+    })?)
 }
 
 /// Disable crash reporting (user opt-out)
 #[tauri::command]
 pub async fn crash_reporter_disable(
     state: State<'_, CrashReporterState>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     info!("Command: crash_reporter_disable");
 
     let service = state.service.lock().unwrap();
-    service.disable().map_err(|e| {
+    Ok(service.disable().map_err(|e| {
         error!("Failed to disable crash reporting: {}", e);
         format!("Failed to disable crash reporting: {}", e)
-    })
+    })?)
 }
 
 /// Get current crash reporting settings
 #[tauri::command]
 pub async fn crash_reporter_get_settings(
     state: State<'_, CrashReporterState>,
-) -> Result<CrashReportingSettings, String> {
+) -> AppResult<CrashReportingSettings> {
     info!("Command: crash_reporter_get_settings");
 
     let service = state.service.lock().unwrap();
@@ -69,60 +73,75 @@ pub async fn crash_reporter_get_settings(
 pub async fn crash_reporter_update_settings(
     state: State<'_, CrashReporterState>,
     settings: CrashReportingSettings,
-) -> Result<(), String> {
+) -> AppResult<()> {
     info!("Command: crash_reporter_update_settings");
 
     let service = state.service.lock().unwrap();
-    service.update_settings(settings).map_err(|e| {
+    Ok(service.update_settings(settings).map_err(|e| {
         error!("Failed to update crash reporting settings: {}", e);
         format!("Failed to update settings: {}", e)
-    })
+    })?)
 }
 
 /// Report an error manually
+///
+/// Returns the pending report id. The report waits for review unless the user
+/// turned `review_before_send` off, in which case it is sent right away.
 #[tauri::command]
 pub async fn crash_reporter_report_error(
     state: State<'_, CrashReporterState>,
     error_message: String,
     context: Option<String>,
-) -> Result<(), String> {
+) -> AppResult<Option<String>> {
     info!("Command: crash_reporter_report_error");
 
-    let service = state.service.lock().unwrap();
-    service
-        .report_error(&error_message, context.as_deref())
-        .map_err(|e| {
-            error!("Failed to report error: {}", e);
-            format!("Failed to report error: {}", e)
-        })
+    let (pending_id, review_before_send) = {
+        let service = state.service.lock().unwrap();
+        let pending_id = service
+            .report_error(&error_message, context.as_deref())
+            .map_err(|e| {
+                error!("Failed to report error: {}", e);
+                format!("Failed to report error: {}", e)
+            })?;
+        (pending_id, service.get_settings().review_before_send)
+    };
+
+    if let Some(id) = pending_id.as_deref().filter(|_| !review_before_send) {
+        send_pending_report(&state.service, id).await.map_err(|e| {
+            error!("Failed to send crash report: {}", e);
+            format!("Failed to send crash report: {}", e)
+        })?;
+    }
+
+    Ok(pending_id)
 }
 
 /// Test crash reporting (for debugging)
 #[tauri::command]
 pub async fn crash_reporter_test(
     state: State<'_, CrashReporterState>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     info!("Command: crash_reporter_test");
 
     let service = state.service.lock().unwrap();
-    service.test_crash_report().map_err(|e| {
+    Ok(service.test_crash_report().map_err(|e| {
         error!("Failed to test crash reporting: {}", e);
         format!("Failed to test crash reporting: {}", e)
-    })
+    })?)
 }
 
 /// Get all local crash reports (v3.4.0)
 #[tauri::command]
 pub async fn crash_reporter_get_local_reports(
     state: State<'_, CrashReporterState>,
-) -> Result<Vec<CrashReport>, String> {
+) -> AppResult<Vec<CrashReport>> {
     info!("Command: crash_reporter_get_local_reports");
 
     let service = state.service.lock().unwrap();
-    service.get_local_crash_reports().map_err(|e| {
+    Ok(service.get_local_crash_reports().map_err(|e| {
         error!("Failed to get local crash reports: {}", e);
         format!("Failed to get crash reports: {}", e)
-    })
+    })?)
 }
 
 /// Cleanup old crash reports (v3.4.0)
@@ -130,12 +149,55 @@ pub async fn crash_reporter_get_local_reports(
 pub async fn crash_reporter_cleanup_old_reports(
     state: State<'_, CrashReporterState>,
     retention_days: i64,
-) -> Result<usize, String> {
+) -> AppResult<usize> {
     info!("Command: crash_reporter_cleanup_old_reports - retention: {} days", retention_days);
 
     let service = state.service.lock().unwrap();
-    service.cleanup_old_crash_reports(retention_days).map_err(|e| {
+    Ok(service.cleanup_old_crash_reports(retention_days).map_err(|e| {
         error!("Failed to cleanup old crash reports: {}", e);
         format!("Failed to cleanup crash reports: {}", e)
-    })
+    })?)
+}
+
+/// Get reports waiting for review, with the exact payload that would be sent (v3.9.0)
+#[tauri::command]
+pub async fn crash_reporter_get_pending_reports(
+    state: State<'_, CrashReporterState>,
+) -> AppResult<Vec<PendingCrashReport>> {
+    info!("Command: crash_reporter_get_pending_reports");
+
+    let service = state.service.lock().unwrap();
+    Ok(service.get_pending_reports().map_err(|e| {
+        error!("Failed to get pending crash reports: {}", e);
+        format!("Failed to get pending crash reports: {}", e)
+    })?)
+}
+
+/// Send a reviewed crash report to the configured endpoint (v3.9.0)
+#[tauri::command]
+pub async fn crash_reporter_send_report(
+    state: State<'_, CrashReporterState>,
+    id: String,
+) -> AppResult<()> {
+    info!("Command: crash_reporter_send_report - {}", id);
+
+    Ok(send_pending_report(&state.service, &id).await.map_err(|e| {
+        error!("Failed to send crash report: {}", e);
+        format!("Failed to send crash report: {}", e)
+    })?)
+}
+
+/// Discard a pending crash report without sending it (v3.9.0)
+#[tauri::command]
+pub async fn crash_reporter_discard_report(
+    state: State<'_, CrashReporterState>,
+    id: String,
+) -> AppResult<()> {
+    info!("Command: crash_reporter_discard_report - {}", id);
+
+    let service = state.service.lock().unwrap();
+    Ok(service.remove_pending_report(&id).map_err(|e| {
+        error!("Failed to discard crash report: {}", e);
+        format!("Failed to discard crash report: {}", e)
+    })?)
 }
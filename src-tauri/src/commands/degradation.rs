@@ -0,0 +1,26 @@
+/**
+ * Service Degradation Commands (v3.9.0)
+ *
+ * Which services are available, degraded or unavailable, and retrying failed ones
+ */
+
+use crate::services::degradation::{self, ServiceStatus};
+use crate::AppResult;
+
+/// Status of every non-critical service, with reasons for failures
+#[tauri::command]
+pub async fn services_get_status() -> AppResult<Vec<ServiceStatus>> {
+    Ok(degradation::status())
+}
+
+/// Re-run the initialization of an unavailable service
+///
+/// Returns the new status; a failed retry is reported in it rather than as an error.
+#[tauri::command]
+pub async fn services_retry_init(name: String) -> AppResult<ServiceStatus> {
+    log::info!("Command: services_retry_init - {}", name);
+
+    Ok(tokio::task::spawn_blocking(move || degradation::retry(&name))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))??)
+}
@@ -0,0 +1,92 @@
+/**
+ * Device Sync Commands (v3.9.0)
+ *
+ * Pair devices, choose a folder or relay transport, and sync on demand
+ */
+
+use crate::services::degradation::ServiceSlot;
+use crate::services::device_sync::{DeviceSyncService, SyncConfig, SyncReport, SyncStatus, SyncTransport};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+#[tauri::command]
+pub async fn sync_status(
+    service: State<'_, Arc<ServiceSlot<DeviceSyncService>>>,
+) -> AppResult<SyncStatus> {
+    let service = service.get()?;
+    Ok(service.status()
+        .map_err(|e| format!("Failed to get sync status: {}", e))?)
+}
+
+/// Start a sync group on this device; returns the pairing code for other devices
+#[tauri::command]
+pub async fn sync_enable(
+    transport: SyncTransport,
+    interval_minutes: Option<u64>,
+    service: State<'_, Arc<ServiceSlot<DeviceSyncService>>>,
+) -> AppResult<String> {
+    let service = service.get()?;
+    let config = SyncConfig { transport, interval_minutes: interval_minutes.unwrap_or(15) };
+    Ok(service.enable(config)
+        .map_err(|e| format!("Failed to enable sync: {}", e))?)
+}
+
+/// Join the sync group of another device with its pairing code
+#[tauri::command]
+pub async fn sync_join(
+    pairing_code: String,
+    transport: SyncTransport,
+    interval_minutes: Option<u64>,
+    service: State<'_, Arc<ServiceSlot<DeviceSyncService>>>,
+) -> AppResult<SyncStatus> {
+    let service = service.get()?;
+    let config = SyncConfig { transport, interval_minutes: interval_minutes.unwrap_or(15) };
+    Ok(service.join(&pairing_code, config)
+        .map_err(|e| format!("Failed to join sync: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn sync_update_config(
+    transport: SyncTransport,
+    interval_minutes: u64,
+    service: State<'_, Arc<ServiceSlot<DeviceSyncService>>>,
+) -> AppResult<SyncStatus> {
+    let service = service.get()?;
+    Ok(service.update_config(SyncConfig { transport, interval_minutes })
+        .map_err(|e| format!("Failed to update sync settings: {}", e))?)
+}
+
+/// Pairing code for adding another device to the group
+#[tauri::command]
+pub async fn sync_pairing_code(
+    service: State<'_, Arc<ServiceSlot<DeviceSyncService>>>,
+) -> AppResult<String> {
+    let service = service.get()?;
+    Ok(service.pairing_code()
+        .map_err(|e| format!("Failed to get pairing code: {}", e))?)
+}
+
+/// Sync with the other devices now
+#[tauri::command]
+pub async fn sync_now(
+    service: State<'_, Arc<ServiceSlot<DeviceSyncService>>>,
+) -> AppResult<SyncReport> {
+    let service = service.get()?;
+    log::info!("Running device sync");
+
+    // Folder and relay I/O plus the merge are blocking
+    Ok(tokio::task::spawn_blocking(move || service.sync_now().map_err(|e| format!("Sync failed: {}", e)))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Stop syncing this device and forget the sync key
+#[tauri::command]
+pub async fn sync_disable(
+    service: State<'_, Arc<ServiceSlot<DeviceSyncService>>>,
+) -> AppResult<()> {
+    let service = service.get()?;
+    Ok(service.disable()
+        .map_err(|e| format!("Failed to disable sync: {}", e))?)
+}
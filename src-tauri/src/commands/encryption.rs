@@ -0,0 +1,81 @@
+/**
+ * Encryption Commands (v3.9.0)
+ *
+ * Enable encryption at rest and unlock storage at startup
+ */
+
+use crate::commands::profile::rebind_app_state;
+use crate::services::encryption::{EncryptionService, EncryptionStatus};
+use crate::services::profile::ProfileService;
+use crate::AppResult;
+use crate::AppState;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+
+/// Get encryption status (enabled / unlocked / key source)
+#[tauri::command]
+pub async fn encryption_status(
+    service: State<'_, Arc<EncryptionService>>,
+) -> AppResult<EncryptionStatus> {
+    Ok(service.status()
+        .map_err(|e| format!("Failed to get encryption status: {}", e))?)
+}
+
+/// Enable encryption at rest
+///
+/// With a passphrase the key is derived from it and asked for at every startup;
+/// without one a random key is kept in the OS keychain. Existing data is
+/// encrypted on the next startup.
+#[tauri::command]
+pub async fn encryption_enable(
+    passphrase: Option<String>,
+    service: State<'_, Arc<EncryptionService>>,
+) -> AppResult<EncryptionStatus> {
+    log::info!("Enabling encryption at rest (keychain: {})", passphrase.is_none());
+
+    let service = Arc::clone(&service);
+    Ok(tokio::task::spawn_blocking(move || service.enable(passphrase.as_deref()))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to enable encryption: {}", e))?)
+}
+
+/// Unlock storage with the passphrase and bind services to the real databases
+#[tauri::command]
+pub async fn encryption_unlock(
+    passphrase: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    service: State<'_, Arc<EncryptionService>>,
+    profiles: State<'_, Arc<ProfileService>>,
+) -> AppResult<EncryptionStatus> {
+    log::info!("Unlocking encrypted storage");
+
+    // Key derivation and first-time migration are CPU/disk heavy
+    let encryption = Arc::clone(&service);
+    let migrated = tokio::task::spawn_blocking(move || encryption.unlock(&passphrase))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to unlock: {}", e))?;
+
+    // Swap the locked in-memory placeholders for the profile's encrypted stores
+    let paths = profiles.reload_active()
+        .map_err(|e| format!("Failed to open encrypted databases: {}", e))?;
+    rebind_app_state(&state, &paths).await?;
+
+    if migrated > 0 {
+        match state.rag.seal_payloads().await {
+            Ok(count) => log::info!("Encrypted {} vector store payloads", count),
+            Err(e) => log::warn!("Failed to encrypt vector store payloads: {}", e),
+        }
+    }
+
+    let status = service.status()
+        .map_err(|e| format!("Failed to get encryption status: {}", e))?;
+    if let Err(e) = app.emit("storage-unlocked", &status) {
+        log::warn!("Failed to emit storage-unlocked: {}", e);
+    }
+
+    log::info!("✓ Storage unlocked");
+    Ok(status)
+}
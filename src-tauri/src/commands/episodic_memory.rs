@@ -7,10 +7,20 @@
  * - Search memories
  * - Export/Import memories
  * - Delete episodes
+ * - Browse with filters / pagination and timeline aggregation (v3.9.0)
+ * - Write, correct and annotate memories by hand (v3.9.0)
+ * - Scope memories to a conversation or project (v3.9.0)
  */
 
 use crate::app_state::AppState;
+use crate::services::memory_browser::{self, MemoryFilter, MemoryItem, MemoryPage, MemoryTimeline, TimelineBucket};
+use crate::services::memory_scope::{self, MemoryScope};
+use crate::services::query_expansion::QueryExpansionOptions;
+use crate::services::temporal_memory::TemporalMemoryService;
+use crate::AppError;
+use crate::AppResult;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use tauri::{command, State};
 
 /// Episode response for frontend
@@ -38,88 +48,88 @@ pub struct MemoryStatsResponse {
 pub async fn episodic_get_recent(
     state: State<'_, AppState>,
     limit: Option<usize>,
-) -> Result<Vec<EpisodeResponse>, String> {
+) -> AppResult<Vec<EpisodeResponse>> {
     let limit = limit.unwrap_or(50);
     log::info!("Command: episodic_get_recent (limit: {})", limit);
 
-    let db = state.db().lock()
-        .map_err(|e| format!("Failed to lock database: {}", e))?;
-    let conn = db.conn();
-
-    let mut stmt = conn.prepare(
-        "SELECT id, user_message, ai_response, satisfaction, created_at, access_count, importance
-         FROM episodic_memory
-         ORDER BY created_at DESC
-         LIMIT ?1"
-    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
-
-    let episodes: Vec<EpisodeResponse> = stmt.query_map(
-        [limit as i64],
-        |row| {
-            Ok(EpisodeResponse {
-                id: row.get(0)?,
-                user_message: row.get(1)?,
-                ai_response: row.get(2)?,
-                satisfaction: row.get(3)?,
-                created_at: row.get(4)?,
-                access_count: row.get(5)?,
-                importance: row.get(6)?,
-            })
-        }
-    )
-    .map_err(|e| format!("Failed to query: {}", e))?
-    .filter_map(|r| r.ok())
-    .collect();
-
-    Ok(episodes)
+    state.db().call(move |db| {
+        let conn = db.conn();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, user_message, ai_response, satisfaction, created_at, access_count, importance
+             FROM episodic_memory
+             ORDER BY created_at DESC
+             LIMIT ?1"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let episodes: Vec<EpisodeResponse> = stmt.query_map(
+            [limit as i64],
+            |row| {
+                Ok(EpisodeResponse {
+                    id: row.get(0)?,
+                    user_message: row.get(1)?,
+                    ai_response: row.get(2)?,
+                    satisfaction: row.get(3)?,
+                    created_at: row.get(4)?,
+                    access_count: row.get(5)?,
+                    importance: row.get(6)?,
+                })
+            }
+        )
+        .map_err(|e| format!("Failed to query: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+        Ok(episodes)
+    }).await
 }
 
 /// Get memory statistics
 #[command]
 pub async fn episodic_get_stats(
     state: State<'_, AppState>,
-) -> Result<MemoryStatsResponse, String> {
+) -> AppResult<MemoryStatsResponse> {
     log::info!("Command: episodic_get_stats");
 
-    let db = state.db().lock()
-        .map_err(|e| format!("Failed to lock database: {}", e))?;
-    let conn = db.conn();
-
-    // Get total count
-    let total_memories: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM episodic_memory",
-        [],
-        |row| row.get(0),
-    ).unwrap_or(0);
-
-    // Get average satisfaction
-    let average_satisfaction: f64 = conn.query_row(
-        "SELECT AVG(satisfaction) FROM episodic_memory",
-        [],
-        |row| row.get::<_, Option<f64>>(0),
-    ).unwrap_or(None).unwrap_or(0.0);
-
-    // Get most accessed topic (based on user_message with highest access_count)
-    let most_accessed_topic: Option<String> = conn.query_row(
-        "SELECT user_message FROM episodic_memory ORDER BY access_count DESC LIMIT 1",
-        [],
-        |row| row.get(0),
-    ).ok();
-
-    // Truncate topic if too long
-    let most_accessed_topic = most_accessed_topic.map(|t| {
-        if t.len() > 50 {
-            format!("{}...", &t[..47])
-        } else {
-            t
-        }
-    });
-
-    Ok(MemoryStatsResponse {
-        total_memories,
-        average_satisfaction,
-        most_accessed_topic,
-    })
+    state.db().call(move |db| {
+        let conn = db.conn();
+
+        // Get total count
+        let total_memories: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM episodic_memory",
+            [],
+            |row| row.get(0),
+        ).unwrap_or(0);
+
+        // Get average satisfaction
+        let average_satisfaction: f64 = conn.query_row(
+            "SELECT AVG(satisfaction) FROM episodic_memory",
+            [],
+            |row| row.get::<_, Option<f64>>(0),
+        ).unwrap_or(None).unwrap_or(0.0);
+
+        // Get most accessed topic (based on user_message with highest access_count)
+        let most_accessed_topic: Option<String> = conn.query_row(
+            "SELECT user_message FROM episodic_memory ORDER BY access_count DESC LIMIT 1",
+            [],
+            |row| row.get(0),
+        ).ok();
+
+        // Truncate topic if too long
+        let most_accessed_topic = most_accessed_topic.map(|t| {
+            if t.len() > 50 {
+                format!("{}...", &t[..47])
+            } else {
+                t
+            }
+        });
+
+        Ok(MemoryStatsResponse {
+            total_memories,
+            average_satisfaction,
+            most_accessed_topic,
+        })
+    }).await
 }
 
 /// Search memories by query
@@ -128,43 +138,77 @@ pub async fn episodic_search(
     state: State<'_, AppState>,
     query: String,
     limit: Option<usize>,
-) -> Result<Vec<EpisodeResponse>, String> {
+) -> AppResult<Vec<EpisodeResponse>> {
     let limit = limit.unwrap_or(20);
     log::info!("Command: episodic_search (query: {}, limit: {})", query, limit);
 
     // Simple text search (semantic search requires embedding service)
-    let db = state.db().lock()
-        .map_err(|e| format!("Failed to lock database: {}", e))?;
-    let conn = db.conn();
-
-    let pattern = format!("%{}%", query);
-    let mut stmt = conn.prepare(
-        "SELECT id, user_message, ai_response, satisfaction, created_at, access_count, importance
-         FROM episodic_memory
-         WHERE user_message LIKE ?1 OR ai_response LIKE ?1
-         ORDER BY created_at DESC
-         LIMIT ?2"
-    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
-
-    let episodes: Vec<EpisodeResponse> = stmt.query_map(
-        rusqlite::params![pattern, limit as i64],
-        |row| {
-            Ok(EpisodeResponse {
-                id: row.get(0)?,
-                user_message: row.get(1)?,
-                ai_response: row.get(2)?,
-                satisfaction: row.get(3)?,
-                created_at: row.get(4)?,
-                access_count: row.get(5)?,
-                importance: row.get(6)?,
-            })
-        }
-    )
-    .map_err(|e| format!("Failed to query: {}", e))?
-    .filter_map(|r| r.ok())
-    .collect();
+    state.db().call(move |db| {
+        let conn = db.conn();
+
+        let pattern = format!("%{}%", query);
+        let mut stmt = conn.prepare(
+            "SELECT id, user_message, ai_response, satisfaction, created_at, access_count, importance
+             FROM episodic_memory
+             WHERE user_message LIKE ?1 OR ai_response LIKE ?1
+             ORDER BY created_at DESC
+             LIMIT ?2"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let episodes: Vec<EpisodeResponse> = stmt.query_map(
+            rusqlite::params![pattern, limit as i64],
+            |row| {
+                Ok(EpisodeResponse {
+                    id: row.get(0)?,
+                    user_message: row.get(1)?,
+                    ai_response: row.get(2)?,
+                    satisfaction: row.get(3)?,
+                    created_at: row.get(4)?,
+                    access_count: row.get(5)?,
+                    importance: row.get(6)?,
+                })
+            }
+        )
+        .map_err(|e| format!("Failed to query: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+        Ok(episodes)
+    }).await
+}
 
-    Ok(episodes)
+/// Search memories semantically, with optional HyDE / multi-query expansion
+#[command]
+pub async fn episodic_semantic_search(
+    state: State<'_, AppState>,
+    query: String,
+    limit: Option<usize>,
+    expansion: Option<QueryExpansionOptions>,
+) -> AppResult<Vec<EpisodeResponse>> {
+    let limit = limit.unwrap_or(20);
+    let expansion = expansion.unwrap_or_default();
+    log::info!(
+        "Command: episodic_semantic_search (query: {}, limit: {}, transform: {:?})",
+        query, limit, expansion.transform
+    );
+
+    let episodes = state.rag()
+        .retrieve_relevant_expanded(&query, limit, &expansion)
+        .await
+        .map_err(|e| format!("Failed to search memories: {}", e))?;
+
+    Ok(episodes
+        .into_iter()
+        .map(|e| EpisodeResponse {
+            id: e.id,
+            user_message: e.user_message,
+            ai_response: e.ai_response,
+            satisfaction: e.satisfaction,
+            created_at: e.created_at,
+            access_count: e.access_count,
+            importance: e.importance,
+        })
+        .collect())
 }
 
 /// Export memories to JSON
@@ -172,40 +216,40 @@ pub async fn episodic_search(
 pub async fn episodic_export(
     state: State<'_, AppState>,
     limit: Option<usize>,
-) -> Result<Vec<EpisodeResponse>, String> {
+) -> AppResult<Vec<EpisodeResponse>> {
     let limit = limit.unwrap_or(1000);
     log::info!("Command: episodic_export (limit: {})", limit);
 
-    let db = state.db().lock()
-        .map_err(|e| format!("Failed to lock database: {}", e))?;
-    let conn = db.conn();
-
-    let mut stmt = conn.prepare(
-        "SELECT id, user_message, ai_response, satisfaction, created_at, access_count, importance
-         FROM episodic_memory
-         ORDER BY created_at DESC
-         LIMIT ?1"
-    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
-
-    let episodes: Vec<EpisodeResponse> = stmt.query_map(
-        [limit as i64],
-        |row| {
-            Ok(EpisodeResponse {
-                id: row.get(0)?,
-                user_message: row.get(1)?,
-                ai_response: row.get(2)?,
-                satisfaction: row.get(3)?,
-                created_at: row.get(4)?,
-                access_count: row.get(5)?,
-                importance: row.get(6)?,
-            })
-        }
-    )
-    .map_err(|e| format!("Failed to query: {}", e))?
-    .filter_map(|r| r.ok())
-    .collect();
-
-    Ok(episodes)
+    state.db().call(move |db| {
+        let conn = db.conn();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, user_message, ai_response, satisfaction, created_at, access_count, importance
+             FROM episodic_memory
+             ORDER BY created_at DESC
+             LIMIT ?1"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let episodes: Vec<EpisodeResponse> = stmt.query_map(
+            [limit as i64],
+            |row| {
+                Ok(EpisodeResponse {
+                    id: row.get(0)?,
+                    user_message: row.get(1)?,
+                    ai_response: row.get(2)?,
+                    satisfaction: row.get(3)?,
+                    created_at: row.get(4)?,
+                    access_count: row.get(5)?,
+                    importance: row.get(6)?,
+                })
+            }
+        )
+        .map_err(|e| format!("Failed to query: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+        Ok(episodes)
+    }).await
 }
 
 /// Import memories from JSON
@@ -213,42 +257,42 @@ pub async fn episodic_export(
 pub async fn episodic_import(
     state: State<'_, AppState>,
     episodes: Vec<EpisodeResponse>,
-) -> Result<usize, String> {
+) -> AppResult<usize> {
     log::info!("Command: episodic_import ({} episodes)", episodes.len());
 
-    let db = state.db().lock()
-        .map_err(|e| format!("Failed to lock database: {}", e))?;
-    let conn = db.conn();
-
-    let mut imported = 0;
-    for episode in episodes {
-        // Check if episode already exists
-        let exists: bool = conn.query_row(
-            "SELECT 1 FROM episodic_memory WHERE id = ?1",
-            [&episode.id],
-            |_| Ok(true),
-        ).unwrap_or(false);
-
-        if !exists {
-            conn.execute(
-                "INSERT INTO episodic_memory (id, user_message, ai_response, satisfaction, created_at, access_count, importance)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                rusqlite::params![
-                    episode.id,
-                    episode.user_message,
-                    episode.ai_response,
-                    episode.satisfaction,
-                    episode.created_at,
-                    episode.access_count,
-                    episode.importance,
-                ],
-            ).map_err(|e| format!("Failed to import episode: {}", e))?;
-            imported += 1;
+    state.db().call(move |db| {
+        let conn = db.conn();
+
+        let mut imported = 0;
+        for episode in episodes {
+            // Check if episode already exists
+            let exists: bool = conn.query_row(
+                "SELECT 1 FROM episodic_memory WHERE id = ?1",
+                [&episode.id],
+                |_| Ok(true),
+            ).unwrap_or(false);
+
+            if !exists {
+                conn.execute(
+                    "INSERT INTO episodic_memory (id, user_message, ai_response, satisfaction, created_at, access_count, importance)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    rusqlite::params![
+                        episode.id,
+                        episode.user_message,
+                        episode.ai_response,
+                        episode.satisfaction,
+                        episode.created_at,
+                        episode.access_count,
+                        episode.importance,
+                    ],
+                ).map_err(|e| format!("Failed to import episode: {}", e))?;
+                imported += 1;
+            }
         }
-    }
 
-    log::info!("Imported {} new episodes", imported);
-    Ok(imported)
+        log::info!("Imported {} new episodes", imported);
+        Ok(imported)
+    }).await
 }
 
 /// Delete an episode
@@ -256,17 +300,157 @@ pub async fn episodic_import(
 pub async fn episodic_delete(
     state: State<'_, AppState>,
     episode_id: String,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     log::info!("Command: episodic_delete (id: {})", episode_id);
 
-    let db = state.db().lock()
-        .map_err(|e| format!("Failed to lock database: {}", e))?;
-    let conn = db.conn();
+    state.db().call(move |db| {
+        let conn = db.conn();
+
+        let deleted = conn.execute(
+            "DELETE FROM episodic_memory WHERE id = ?1",
+            [&episode_id],
+        ).map_err(|e| format!("Failed to delete episode: {}", e))?;
+
+        Ok(deleted > 0)
+    }).await
+}
+
+/// Browse memories with filters (date range, retention band, memory type,
+/// satisfaction, conversation, topic) and pagination
+#[command]
+pub async fn episodic_browse(
+    state: State<'_, AppState>,
+    filter: Option<MemoryFilter>,
+) -> AppResult<MemoryPage> {
+    let filter = filter.unwrap_or_default();
+    log::info!("Command: episodic_browse ({:?})", filter);
+
+    state.db().call(move |db| {
+        Ok(memory_browser::browse_memories(db.conn(), &filter)
+            .map_err(|e| format!("Failed to browse memories: {}", e))?)
+    }).await
+}
+
+/// Memory counts and averages per day / week / month for the timeline view
+#[command]
+pub async fn memory_get_timeline(
+    state: State<'_, AppState>,
+    filter: Option<MemoryFilter>,
+    bucket: Option<TimelineBucket>,
+    utc_offset_minutes: Option<i32>,
+) -> AppResult<MemoryTimeline> {
+    let filter = filter.unwrap_or_default();
+    let bucket = bucket.unwrap_or_default();
+    log::info!("Command: memory_get_timeline (bucket: {:?})", bucket);
+
+    state.db().call(move |db| {
+        Ok(memory_browser::memory_timeline(db.conn(), &filter, bucket, utc_offset_minutes.unwrap_or(0))
+            .map_err(|e| format!("Failed to build memory timeline: {}", e))?)
+    }).await
+}
+
+/// Load one memory for the UI after a manual change
+async fn load_memory(state: &AppState, memory_id: String) -> AppResult<MemoryItem> {
+    state.db().call(move |db| {
+        Ok(memory_browser::get_memory(db.conn(), &memory_id)
+            .map_err(|e| format!("Failed to load memory: {}", e))?
+            .ok_or_else(|| format!("Memory not found: {}", memory_id))?)
+    }).await
+}
+
+/// Write a memory by hand ("my dog is named Toto")
+///
+/// Stored as user-asserted and embedded immediately; pinned unless `pin` is false.
+/// `scope` limits where it is recalled (default: every conversation).
+#[command]
+pub async fn episodic_create(
+    state: State<'_, AppState>,
+    temporal: State<'_, Arc<TemporalMemoryService>>,
+    content: String,
+    note: Option<String>,
+    pin: Option<bool>,
+    scope: Option<MemoryScope>,
+) -> AppResult<MemoryItem> {
+    let content = content.trim().to_string();
+    if content.is_empty() {
+        return Err("Memory content is empty".into());
+    }
+    log::info!("Command: episodic_create");
+
+    let note = note.filter(|n| !n.trim().is_empty());
+    let memory_id = state.rag()
+        .store_user_memory(&content, note.as_deref(), &scope.unwrap_or_default())
+        .await
+        .map_err(|e| format!("Failed to store memory: {}", e))?;
+
+    if pin.unwrap_or(true) {
+        temporal.pin_memory(&memory_id)
+            .map_err(|e| format!("Failed to pin memory: {}", e))?;
+    }
+
+    load_memory(&state, memory_id).await
+}
+
+/// Correct a memory's text; it is re-embedded and becomes user-asserted
+#[command]
+pub async fn episodic_edit(
+    state: State<'_, AppState>,
+    episode_id: String,
+    user_message: String,
+    ai_response: Option<String>,
+) -> AppResult<MemoryItem> {
+    log::info!("Command: episodic_edit (id: {})", episode_id);
+
+    let user_message = user_message.trim().to_string();
+    if user_message.is_empty() {
+        return Err("Memory content is empty".into());
+    }
+    state.rag()
+        .edit_memory(&episode_id, &user_message, ai_response.as_deref().unwrap_or(""))
+        .await
+        .map_err(|e| format!("Failed to edit memory: {}", e))?;
+
+    load_memory(&state, episode_id).await
+}
+
+/// Set or clear the user's note on a memory
+#[command]
+pub async fn episodic_annotate(
+    state: State<'_, AppState>,
+    episode_id: String,
+    note: Option<String>,
+) -> AppResult<MemoryItem> {
+    log::info!("Command: episodic_annotate (id: {})", episode_id);
+
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    let id = episode_id.clone();
+    let updated = state.db().call(move |db| {
+        Ok(db.conn().execute(
+            "UPDATE episodic_memory SET user_note = ?1 WHERE id = ?2",
+            rusqlite::params![note, id],
+        ).map_err(|e| format!("Failed to annotate memory: {}", e))?)
+    }).await?;
+    if updated == 0 {
+        return Err(AppError::not_found(format!("Memory not found: {}", episode_id)));
+    }
+
+    load_memory(&state, episode_id).await
+}
+
+/// Make a memory global, or recallable only in one conversation or project (folder)
+#[command]
+pub async fn episodic_set_scope(
+    state: State<'_, AppState>,
+    episode_id: String,
+    scope: MemoryScope,
+) -> AppResult<MemoryItem> {
+    log::info!("Command: episodic_set_scope (id: {}, scope: {:?})", episode_id, scope);
 
-    let deleted = conn.execute(
-        "DELETE FROM episodic_memory WHERE id = ?1",
-        [&episode_id],
-    ).map_err(|e| format!("Failed to delete episode: {}", e))?;
+    let id = episode_id.clone();
+    state.db().call(move |db| {
+        Ok(memory_scope::set_scope(db.conn(), &id, &scope)
+            .map_err(|e| format!("Failed to set memory scope: {}", e))?)
+    }).await?;
 
-    Ok(deleted > 0)
+    load_memory(&state, episode_id).await
 }
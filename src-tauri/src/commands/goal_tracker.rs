@@ -3,8 +3,10 @@
  */
 
 use crate::services::goal_tracker::{
-    Achievement, Goal, GoalReminder, GoalTrackerService,
+    Achievement, Goal, GoalEvidence, GoalInferenceConfig, GoalReminder, GoalTrackerService,
+    InferenceReport,
 };
+use crate::AppResult;
 use std::sync::Arc;
 use tauri::State;
 
@@ -12,30 +14,30 @@ use tauri::State;
 pub async fn goal_create(
     goal: Goal,
     service: State<'_, Arc<GoalTrackerService>>,
-) -> Result<String, String> {
+) -> AppResult<String> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .create_goal(goal)
             .map_err(|e| format!("Failed to create goal: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
 pub async fn goal_get(
     goal_id: String,
     service: State<'_, Arc<GoalTrackerService>>,
-) -> Result<Goal, String> {
+) -> AppResult<Goal> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .get_goal(&goal_id)
             .map_err(|e| format!("Failed to get goal: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
@@ -44,59 +46,59 @@ pub async fn goal_update_progress(
     progress_delta: f32,
     description: String,
     service: State<'_, Arc<GoalTrackerService>>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .update_progress(&goal_id, progress_delta, &description)
             .map_err(|e| format!("Failed to update progress: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
 pub async fn goal_complete_milestone(
     milestone_id: String,
     service: State<'_, Arc<GoalTrackerService>>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .complete_milestone(&milestone_id)
             .map_err(|e| format!("Failed to complete milestone: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
 pub async fn goal_get_active(
     service: State<'_, Arc<GoalTrackerService>>,
-) -> Result<Vec<Goal>, String> {
+) -> AppResult<Vec<Goal>> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .get_active_goals()
             .map_err(|e| format!("Failed to get active goals: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
 pub async fn goal_get_stale(
     days_threshold: i64,
     service: State<'_, Arc<GoalTrackerService>>,
-) -> Result<Vec<GoalReminder>, String> {
+) -> AppResult<Vec<GoalReminder>> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .get_stale_goals(days_threshold)
             .map_err(|e| format!("Failed to get stale goals: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
@@ -104,9 +106,9 @@ pub async fn goal_detect_progress(
     conversation: String,
     goal_id: String,
     service: State<'_, Arc<GoalTrackerService>>,
-) -> Result<Option<f32>, String> {
+) -> AppResult<Option<f32>> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         let rt = tokio::runtime::Runtime::new().unwrap();
         rt.block_on(async move {
             service_clone
@@ -116,35 +118,102 @@ pub async fn goal_detect_progress(
         })
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
 pub async fn goal_get_achievements(
     goal_id: String,
     service: State<'_, Arc<GoalTrackerService>>,
-) -> Result<Vec<Achievement>, String> {
+) -> AppResult<Vec<Achievement>> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .get_achievements(&goal_id)
             .map_err(|e| format!("Failed to get achievements: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
 pub async fn goal_delete(
     goal_id: String,
     service: State<'_, Arc<GoalTrackerService>>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .delete_goal(&goal_id)
             .map_err(|e| format!("Failed to delete goal: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn goal_get_evidence(
+    goal_id: String,
+    service: State<'_, Arc<GoalTrackerService>>,
+) -> AppResult<Vec<GoalEvidence>> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .get_evidence(&goal_id)
+            .map_err(|e| format!("Failed to get goal evidence: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Take back the progress an evidence entry added
+#[tauri::command]
+pub async fn goal_reject_evidence(
+    evidence_id: String,
+    service: State<'_, Arc<GoalTrackerService>>,
+) -> AppResult<bool> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .reject_evidence(&evidence_id)
+            .map_err(|e| format!("Failed to reject goal evidence: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Check activity since `since` (Unix millis, default: the last day) right away
+#[tauri::command]
+pub async fn goal_infer_progress(
+    since: Option<i64>,
+    service: State<'_, Arc<GoalTrackerService>>,
+) -> AppResult<InferenceReport> {
+    let since = since.unwrap_or_else(|| chrono::Utc::now().timestamp_millis() - 24 * 60 * 60 * 1000);
+    Ok(service
+        .inner()
+        .infer_progress(since)
+        .await
+        .map_err(|e| format!("Failed to infer goal progress: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn goal_get_inference_config(
+    service: State<'_, Arc<GoalTrackerService>>,
+) -> AppResult<GoalInferenceConfig> {
+    Ok(service.get_inference_config())
+}
+
+#[tauri::command]
+pub async fn goal_update_inference_config(
+    config: GoalInferenceConfig,
+    service: State<'_, Arc<GoalTrackerService>>,
+) -> AppResult<()> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .update_inference_config(config)
+            .map_err(|e| format!("Failed to update goal inference config: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
@@ -1,5 +1,11 @@
+use crate::services::entity_extractor::ExtractionResult;
+use crate::services::graph_retrieval::GraphAnswer;
+use crate::services::semantic_wiki::SemanticWikiService;
+use crate::AppError;
+use crate::AppResult;
 use crate::AppState;
 use log::info;
+use std::sync::Arc;
 use tauri::{command, State};
 
 /// Extract entities and relationships from text
@@ -7,13 +13,48 @@ use tauri::{command, State};
 pub async fn graphrag_extract_entities(
     state: State<'_, AppState>,
     text: String,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: graphrag_extract_entities");
 
     let extractor = &*state.entity_extractor;
     let result = extractor.extract(&text).await?;
 
-    Ok(serde_json::json!({
+    Ok(extraction_json(&result))
+}
+
+/// Extract entities from a whole conversation, resolving coreferences across messages (v3.9.0)
+#[command]
+pub async fn graphrag_extract_conversation(
+    state: State<'_, AppState>,
+    conversation_id: String,
+) -> AppResult<serde_json::Value> {
+    info!("Command: graphrag_extract_conversation ({})", conversation_id);
+
+    let messages: Vec<String> = state.db.call(move |db| {
+        let conn = db.conn();
+        let mut stmt = conn
+            .prepare(
+                "SELECT content FROM messages
+                 WHERE conversation_id = ?1 AND role != 'system' AND is_stale = 0
+                 ORDER BY timestamp ASC",
+            )
+            .map_err(|e| e.to_string())?;
+        let messages = stmt
+            .query_map([&conversation_id], |row| row.get(0))
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(messages)
+    }).await?;
+
+    let extractor = &*state.entity_extractor;
+    let result = extractor.extract_conversation(&messages).await?;
+
+    Ok(extraction_json(&result))
+}
+
+fn extraction_json(result: &ExtractionResult) -> serde_json::Value {
+    serde_json::json!({
         "entities": result.entities.iter().map(|e| {
             serde_json::json!({
                 "name": e.name,
@@ -31,7 +72,8 @@ pub async fn graphrag_extract_entities(
                 "properties": r.properties,
             })
         }).collect::<Vec<_>>(),
-    }))
+        "used_llm": result.used_llm,
+    })
 }
 
 /// Build knowledge graph from text
@@ -39,7 +81,7 @@ pub async fn graphrag_extract_entities(
 pub async fn graphrag_build_graph(
     state: State<'_, AppState>,
     text: String,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: graphrag_build_graph");
 
     let mut builder = state.graph_builder.lock().await;
@@ -57,7 +99,7 @@ pub async fn graphrag_build_graph(
 
 /// Save knowledge graph to database
 #[command]
-pub async fn graphrag_save_graph(state: State<'_, AppState>) -> Result<String, String> {
+pub async fn graphrag_save_graph(state: State<'_, AppState>) -> AppResult<String> {
     info!("Command: graphrag_save_graph");
 
     let builder = state.graph_builder.lock().await;
@@ -73,7 +115,7 @@ pub async fn graphrag_save_graph(state: State<'_, AppState>) -> Result<String, S
 pub fn graphrag_load_entity(
     state: State<'_, AppState>,
     entity_id: String,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: graphrag_load_entity ({})", entity_id);
 
     let storage = &*state.graph_storage;
@@ -89,7 +131,7 @@ pub fn graphrag_load_entity(
             "degree": node.degree,
         }))
     } else {
-        Err(format!("Entity not found: {}", entity_id))
+        Err(AppError::not_found(format!("Entity not found: {}", entity_id)))
     }
 }
 
@@ -99,7 +141,7 @@ pub fn graphrag_search_entities(
     state: State<'_, AppState>,
     query: String,
     limit: Option<usize>,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: graphrag_search_entities ({})", query);
 
     let storage = &*state.graph_storage;
@@ -123,7 +165,7 @@ pub fn graphrag_search_entities(
 pub fn graphrag_get_neighbors(
     state: State<'_, AppState>,
     entity_id: String,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: graphrag_get_neighbors ({})", entity_id);
 
     let storage = &*state.graph_storage;
@@ -146,7 +188,7 @@ pub fn graphrag_get_neighbors(
 pub fn graphrag_get_community(
     state: State<'_, AppState>,
     community_id: i32,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: graphrag_get_community ({})", community_id);
 
     let storage = &*state.graph_storage;
@@ -170,13 +212,14 @@ pub fn graphrag_get_community(
 pub fn graphrag_retrieve(
     state: State<'_, AppState>,
     query: String,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: graphrag_retrieve ({})", query);
 
     let engine = &*state.graph_retrieval;
     let results = engine.retrieve(&query)?;
 
     Ok(serde_json::json!({
+        "time_scope": engine.time_scope(&query),
         "results": results.iter().map(|r| {
             serde_json::json!({
                 "entity": {
@@ -193,6 +236,21 @@ pub fn graphrag_retrieve(
     }))
 }
 
+/// Answer a question through multi-hop graph traversal (v3.9.0)
+///
+/// Returns the answer with the entity/relationship paths it used, so the UI
+/// can show how the assistant connected the dots.
+#[command]
+pub async fn graphrag_answer(
+    state: State<'_, AppState>,
+    question: String,
+) -> AppResult<GraphAnswer> {
+    info!("Command: graphrag_answer ({})", question);
+
+    let engine = state.graph_retrieval.clone();
+    Ok(engine.answer(&question).await?)
+}
+
 /// Find path between two entities
 #[command]
 pub fn graphrag_find_path(
@@ -200,7 +258,7 @@ pub fn graphrag_find_path(
     source_id: String,
     target_id: String,
     max_depth: Option<usize>,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: graphrag_find_path ({} -> {})", source_id, target_id);
 
     let engine = &*state.graph_retrieval;
@@ -214,7 +272,7 @@ pub fn graphrag_find_path(
 
 /// Get graph statistics
 #[command]
-pub fn graphrag_stats(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+pub fn graphrag_stats(state: State<'_, AppState>) -> AppResult<serde_json::Value> {
     info!("Command: graphrag_stats");
 
     let storage = &*state.graph_storage;
@@ -232,7 +290,7 @@ pub fn graphrag_stats(state: State<'_, AppState>) -> Result<serde_json::Value, S
 pub fn graphrag_delete_entity(
     state: State<'_, AppState>,
     entity_id: String,
-) -> Result<String, String> {
+) -> AppResult<String> {
     info!("Command: graphrag_delete_entity ({})", entity_id);
 
     let storage = &*state.graph_storage;
@@ -241,9 +299,53 @@ pub fn graphrag_delete_entity(
     Ok(format!("Entity {} deleted successfully", entity_id))
 }
 
+/// List likely duplicate entities ("Bob" / "Bob Smith" / "bob@x.com")
+#[command]
+pub fn graphrag_find_merge_candidates(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+) -> AppResult<serde_json::Value> {
+    info!("Command: graphrag_find_merge_candidates");
+
+    let storage = &*state.graph_storage;
+    let candidates = storage.find_merge_candidates(limit.unwrap_or(20))?;
+
+    Ok(serde_json::json!({ "candidates": candidates }))
+}
+
+/// Merge duplicate entities into a primary entity
+///
+/// Relationships follow the primary entity, and wiki facts about the merged
+/// names move to the primary's name.
+#[command]
+pub fn graphrag_merge_entities(
+    state: State<'_, AppState>,
+    wiki: State<'_, Arc<SemanticWikiService>>,
+    primary_id: String,
+    duplicate_ids: Vec<String>,
+) -> AppResult<serde_json::Value> {
+    info!("Command: graphrag_merge_entities ({} <- {:?})", primary_id, duplicate_ids);
+
+    let storage = &*state.graph_storage;
+    let report = storage.merge_entities(&primary_id, &duplicate_ids)?;
+
+    let facts_updated = wiki
+        .rename_entity(&report.merged_names, &report.primary_name)
+        .map_err(|e| format!("Failed to update wiki facts: {}", e))?;
+
+    Ok(serde_json::json!({
+        "primary_id": report.primary_id,
+        "merged_ids": report.merged_ids,
+        "relationships_redirected": report.relationships_redirected,
+        "relationships_deduplicated": report.relationships_deduplicated,
+        "aliases": storage.get_aliases(&report.primary_id)?,
+        "facts_updated": facts_updated,
+    }))
+}
+
 /// Clear all graph data
 #[command]
-pub fn graphrag_clear_all(state: State<'_, AppState>) -> Result<String, String> {
+pub fn graphrag_clear_all(state: State<'_, AppState>) -> AppResult<String> {
     info!("Command: graphrag_clear_all");
 
     let storage = &*state.graph_storage;
@@ -256,7 +358,7 @@ pub fn graphrag_clear_all(state: State<'_, AppState>) -> Result<String, String>
 #[command]
 pub fn graphrag_get_extractor_config(
     state: State<'_, AppState>,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: graphrag_get_extractor_config");
 
     let extractor = &*state.entity_extractor;
@@ -266,6 +368,8 @@ pub fn graphrag_get_extractor_config(
         "min_confidence": config.min_confidence,
         "max_entities_per_text": config.max_entities_per_text,
         "enable_coreference_resolution": config.enable_coreference_resolution,
+        "mode": config.mode,
+        "batch_chars": config.batch_chars,
     }))
 }
 
@@ -273,7 +377,7 @@ pub fn graphrag_get_extractor_config(
 #[command]
 pub fn graphrag_get_retrieval_config(
     state: State<'_, AppState>,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: graphrag_get_retrieval_config");
 
     let engine = &*state.graph_retrieval;
@@ -7,32 +7,28 @@
 
 #![cfg(feature = "lancedb-support")]
 
-use crate::services::hybrid_search::FusionWeights;
+use crate::services::bm25::TokenizerKind;
+use crate::services::fusion::{self, FusionFeatures, FusionStrategy, FusionWeights, QueryType, ScoreNormalization};
+use crate::services::query_expansion::QueryExpansionOptions;
+use crate::services::reranker::{RerankerModel, RerankerPreset, RerankerStatus};
+use crate::AppError;
+use crate::AppResult;
 use crate::AppState;
-use log::{error, info};
+use log::info;
+use std::sync::Arc;
 use tauri::State;
 
 /// Initialize hybrid search engine and build BM25 index
 #[tauri::command]
-pub async fn hybrid_search_init(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+pub async fn hybrid_search_init(state: State<'_, AppState>) -> AppResult<serde_json::Value> {
     info!("Command: hybrid_search_init");
 
-    // Get or create hybrid search engine
-    let mut hybrid_search = state.hybrid_search.lock().await;
-
-    // Build BM25 index - acquire DB lock only when needed
-    let result = {
-        let db = state.db.lock().map_err(|e| {
-            error!("Failed to lock database: {}", e);
-            format!("Database lock error: {}", e)
-        })?;
-        let conn = db.conn();
-        hybrid_search.build_index(conn)
-    }; // DB lock is dropped here
-
-    result?;
+    // Build BM25 index on the blocking pool (v3.9.0)
+    let engine = Arc::clone(&state.hybrid_search);
+    state.db.call(move |db| engine.blocking_lock().build_index(db.conn())).await?;
 
     // Get statistics
+    let hybrid_search = state.hybrid_search.lock().await;
     let stats = hybrid_search.stats();
 
     info!(
@@ -43,34 +39,31 @@ pub async fn hybrid_search_init(state: State<'_, AppState>) -> Result<serde_json
     Ok(serde_json::json!({
         "bm25_documents": stats.bm25_documents,
         "bm25_terms": stats.bm25_terms,
+        "bm25_tokenizer": stats.bm25_tokenizer,
         "fusion_weights": {
             "bm25_weight": stats.fusion_weights.bm25_weight,
             "semantic_weight": stats.fusion_weights.semantic_weight,
         },
+        "fusion": stats.fusion,
+        "learned_fusion_samples": stats.learned_fusion_samples,
         "rrf_k": stats.rrf_k,
         "reranking_enabled": stats.reranking_enabled,
+        "reranker_model": stats.reranker_model,
     }))
 }
 
-/// Rebuild BM25 index (after new episodes are added)
+/// Rebuild BM25 index from scratch (queries already pick up new episodes)
 #[tauri::command]
 pub async fn hybrid_search_rebuild_index(
     state: State<'_, AppState>,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: hybrid_search_rebuild_index");
 
-    let mut hybrid_search = state.hybrid_search.lock().await;
-
-    // Rebuild index - acquire DB lock only when needed
-    let result = {
-        let db = state.db.lock().map_err(|e| format!("Database lock error: {}", e))?;
-        let conn = db.conn();
-        hybrid_search.rebuild_index(conn)
-    }; // DB lock is dropped here
+    // Rebuild index on the blocking pool (v3.9.0)
+    let engine = Arc::clone(&state.hybrid_search);
+    state.db.call(move |db| engine.blocking_lock().rebuild_index(db.conn())).await?;
 
-    result?;
-
-    let stats = hybrid_search.stats();
+    let stats = state.hybrid_search.lock().await.stats();
 
     info!("BM25 index rebuilt: {} documents", stats.bm25_documents);
 
@@ -86,16 +79,36 @@ pub async fn hybrid_search_query(
     state: State<'_, AppState>,
     query: String,
     top_k: Option<usize>,
-) -> Result<serde_json::Value, String> {
+    expansion: Option<QueryExpansionOptions>,
+) -> AppResult<serde_json::Value> {
     info!("Command: hybrid_search_query - '{}' (top_k: {:?})", query, top_k);
 
     let k = top_k.unwrap_or(5);
+    let expansion = expansion.unwrap_or_default();
+
+    // Index episodes stored since the last query (v3.9.0)
+    let engine = Arc::clone(&state.hybrid_search);
+    state.db.call(move |db| engine.blocking_lock().sync_index(db.conn())).await?;
 
     let hybrid_search = state.hybrid_search.lock().await;
-    let results = hybrid_search.search(&query, k).await?;
+    let results = hybrid_search.search_with_expansion(&query, k, &expansion).await?;
+    drop(hybrid_search);
 
     info!("Hybrid search returned {} results", results.len());
 
+    // Log what was shown so clicks can train learned fusion (v3.9.0)
+    let query_type = QueryType::classify(&query);
+    let search_id = if results.is_empty() {
+        None
+    } else {
+        let hits: Vec<(String, FusionFeatures)> = results.iter().map(|r| (r.episode_id.clone(), r.features)).collect();
+        let logged = state.db.call(move |db| {
+            fusion::log_impressions(db.conn(), query_type, &hits)
+                .map_err(|e| format!("Failed to log impressions: {}", e))
+        }).await?;
+        Some(logged)
+    };
+
     let json_results: Vec<serde_json::Value> = results
         .into_iter()
         .map(|r| {
@@ -115,51 +128,129 @@ pub async fn hybrid_search_query(
     Ok(serde_json::json!({
         "query": query,
         "top_k": k,
+        "transform": expansion.effective_transform(&query),
+        "search_id": search_id,
+        "query_type": query_type,
         "results": json_results,
     }))
 }
 
-/// Update fusion weights
+/// Update fusion weights, strategy and per-query-type profiles
+///
+/// v3.9.0: Every argument is optional; with `query_type` the weights update that
+/// profile instead of the default. The configuration is persisted per profile.
 #[tauri::command]
 pub async fn hybrid_search_set_weights(
     state: State<'_, AppState>,
-    bm25_weight: f32,
-    semantic_weight: f32,
-) -> Result<(), String> {
+    bm25_weight: Option<f32>,
+    semantic_weight: Option<f32>,
+    query_type: Option<QueryType>,
+    strategy: Option<FusionStrategy>,
+    normalization: Option<ScoreNormalization>,
+    query_profiles: Option<bool>,
+) -> AppResult<serde_json::Value> {
     info!(
-        "Command: hybrid_search_set_weights - BM25: {}, Semantic: {}",
-        bm25_weight, semantic_weight
+        "Command: hybrid_search_set_weights - BM25: {:?}, Semantic: {:?}, query type: {:?}, strategy: {:?}",
+        bm25_weight, semantic_weight, query_type, strategy
     );
 
-    // Validate weights
-    if bm25_weight < 0.0 || bm25_weight > 1.0 {
-        return Err("bm25_weight must be between 0.0 and 1.0".to_string());
+    let mut config = state.hybrid_search.lock().await.fusion_config().clone();
+
+    if bm25_weight.is_some() || semantic_weight.is_some() {
+        let current = match query_type {
+            Some(query_type) => config.profiles.get(query_type).clone(),
+            None => config.weights.clone(),
+        };
+        let weights = FusionWeights {
+            bm25_weight: bm25_weight.unwrap_or(current.bm25_weight),
+            semantic_weight: semantic_weight.unwrap_or(current.semantic_weight),
+        };
+        weights.validate().map_err(|e| e.to_string())?;
+        match query_type {
+            Some(query_type) => config.profiles.set(query_type, weights),
+            None => config.weights = weights,
+        }
+    }
+    if let Some(strategy) = strategy {
+        config.strategy = strategy;
+    }
+    if let Some(normalization) = normalization {
+        config.normalization = normalization;
     }
-    if semantic_weight < 0.0 || semantic_weight > 1.0 {
-        return Err("semantic_weight must be between 0.0 and 1.0".to_string());
+    if let Some(query_profiles) = query_profiles {
+        config.query_profiles = query_profiles;
     }
 
-    let mut hybrid_search = state.hybrid_search.lock().await;
+    let saved = config.clone();
+    state.db.call(move |db| {
+        saved.save(db.conn()).map_err(|e| format!("Failed to save fusion config: {}", e))
+    }).await?;
 
-    hybrid_search.set_fusion_weights(FusionWeights {
-        bm25_weight,
-        semantic_weight,
-    });
+    state.hybrid_search.lock().await.set_fusion_config(config.clone());
 
     info!("Fusion weights updated successfully");
+    serde_json::to_value(config).map_err(|e| e.to_string().into())
+}
+
+/// Mark a result of `hybrid_search_query` as useful (v3.9.0)
+#[tauri::command]
+pub async fn hybrid_search_record_click(
+    state: State<'_, AppState>,
+    search_id: String,
+    episode_id: String,
+) -> AppResult<()> {
+    info!("Command: hybrid_search_record_click - {} in {}", episode_id, search_id);
+
+    state.db.call(move |db| {
+        fusion::record_click(db.conn(), &search_id, &episode_id)
+            .map_err(|e| format!("Failed to record click: {}", e))
+    }).await?;
+
     Ok(())
 }
 
+/// Train learned fusion on recorded clicks (v3.9.0)
+///
+/// Takes effect for searches using the `learned` strategy.
+#[tauri::command]
+pub async fn hybrid_search_train_fusion(state: State<'_, AppState>) -> AppResult<serde_json::Value> {
+    info!("Command: hybrid_search_train_fusion");
+
+    let engine = Arc::clone(&state.hybrid_search);
+    let model = state.db.call(move |db| engine.blocking_lock().train_fusion(db.conn())).await?;
+
+    serde_json::to_value(model).map_err(|e| e.to_string().into())
+}
+
+/// Select the BM25 tokenizer (`unicode` or `korean`) and re-index (v3.9.0)
+#[tauri::command]
+pub async fn hybrid_search_set_tokenizer(
+    state: State<'_, AppState>,
+    tokenizer: TokenizerKind,
+) -> AppResult<serde_json::Value> {
+    info!("Command: hybrid_search_set_tokenizer - {:?}", tokenizer);
+
+    let engine = Arc::clone(&state.hybrid_search);
+    state.db.call(move |db| engine.blocking_lock().set_tokenizer(db.conn(), tokenizer)).await?;
+
+    let stats = state.hybrid_search.lock().await.stats();
+    Ok(serde_json::json!({
+        "bm25_documents": stats.bm25_documents,
+        "bm25_terms": stats.bm25_terms,
+        "bm25_tokenizer": stats.bm25_tokenizer,
+    }))
+}
+
 /// Update RRF constant
 #[tauri::command]
 pub async fn hybrid_search_set_rrf_k(
     state: State<'_, AppState>,
     k: f32,
-) -> Result<(), String> {
+) -> AppResult<()> {
     info!("Command: hybrid_search_set_rrf_k - k: {}", k);
 
     if k <= 0.0 {
-        return Err("RRF constant k must be positive".to_string());
+        return Err(AppError::invalid_input("RRF constant k must be positive"));
     }
 
     let mut hybrid_search = state.hybrid_search.lock().await;
@@ -172,7 +263,7 @@ pub async fn hybrid_search_set_rrf_k(
 
 /// Get hybrid search statistics
 #[tauri::command]
-pub async fn hybrid_search_stats(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+pub async fn hybrid_search_stats(state: State<'_, AppState>) -> AppResult<serde_json::Value> {
     info!("Command: hybrid_search_stats");
 
     let hybrid_search = state.hybrid_search.lock().await;
@@ -182,12 +273,16 @@ pub async fn hybrid_search_stats(state: State<'_, AppState>) -> Result<serde_jso
     Ok(serde_json::json!({
         "bm25_documents": stats.bm25_documents,
         "bm25_terms": stats.bm25_terms,
+        "bm25_tokenizer": stats.bm25_tokenizer,
         "fusion_weights": {
             "bm25_weight": stats.fusion_weights.bm25_weight,
             "semantic_weight": stats.fusion_weights.semantic_weight,
         },
+        "fusion": stats.fusion,
+        "learned_fusion_samples": stats.learned_fusion_samples,
         "rrf_k": stats.rrf_k,
         "reranking_enabled": stats.reranking_enabled,
+        "reranker_model": stats.reranker_model,
     }))
 }
 
@@ -197,7 +292,7 @@ pub async fn hybrid_search_compare(
     state: State<'_, AppState>,
     query: String,
     top_k: Option<usize>,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: hybrid_search_compare - '{}'", query);
 
     let k = top_k.unwrap_or(5);
@@ -244,7 +339,7 @@ pub async fn hybrid_search_compare(
 pub async fn hybrid_search_toggle_reranking(
     state: State<'_, AppState>,
     enabled: bool,
-) -> Result<(), String> {
+) -> AppResult<()> {
     info!("Command: hybrid_search_toggle_reranking - enabled: {}", enabled);
 
     let mut hybrid_search = state.hybrid_search.lock().await;
@@ -254,3 +349,97 @@ pub async fn hybrid_search_toggle_reranking(
     info!("Re-ranking toggled successfully");
     Ok(())
 }
+
+fn reranker_status_json(status: RerankerStatus) -> serde_json::Value {
+    serde_json::json!({
+        "model": status.model.as_str(),
+        "candidates": status.candidates,
+        "loaded": status.loaded,
+        "downloaded": status.downloaded,
+        "size_mb": status.model.size_mb(),
+        "last_error": status.last_error,
+    })
+}
+
+/// Select the re-ranking model, by id or by preset (latency / balanced / quality)
+///
+/// The model is downloaded and loaded on the next query, not here.
+#[tauri::command]
+pub async fn hybrid_search_set_reranker_model(
+    state: State<'_, AppState>,
+    model: Option<String>,
+    preset: Option<String>,
+    candidates: Option<usize>,
+) -> AppResult<serde_json::Value> {
+    info!(
+        "Command: hybrid_search_set_reranker_model - model: {:?}, preset: {:?}",
+        model, preset
+    );
+
+    let hybrid_search = state.hybrid_search.lock().await;
+    let reranker = hybrid_search.reranker();
+
+    match (model, preset) {
+        (_, Some(preset)) => {
+            let preset = RerankerPreset::parse(&preset)
+                .ok_or_else(|| AppError::invalid_input(format!("Unknown reranker preset: {}", preset)))?;
+            reranker.set_model(preset.model(), candidates.or(Some(preset.candidates())));
+        }
+        (Some(model), None) => {
+            let model = RerankerModel::parse(&model)
+                .ok_or_else(|| AppError::invalid_input(format!("Unknown reranker model: {}", model)))?;
+            reranker.set_model(model, candidates);
+        }
+        (None, None) => return Err(AppError::missing_field("Either model or preset is required")),
+    }
+
+    let status = reranker.status();
+    info!("Reranker model set to {}", status.model.as_str());
+    Ok(reranker_status_json(status))
+}
+
+/// List available re-ranking models and the current selection
+#[tauri::command]
+pub async fn hybrid_search_list_reranker_models(
+    state: State<'_, AppState>,
+) -> AppResult<serde_json::Value> {
+    info!("Command: hybrid_search_list_reranker_models");
+
+    let hybrid_search = state.hybrid_search.lock().await;
+    let status = hybrid_search.reranker().status();
+    let active = status.model;
+
+    let models: Vec<serde_json::Value> = RerankerModel::ALL
+        .into_iter()
+        .map(|model| {
+            serde_json::json!({
+                "id": model.as_str(),
+                "size_mb": model.size_mb(),
+                "quantized": model.is_quantized(),
+                "downloaded": model.is_downloaded(),
+                "active": model == active,
+            })
+        })
+        .collect();
+
+    let presets: Vec<serde_json::Value> = [
+        ("latency", RerankerPreset::Latency),
+        ("balanced", RerankerPreset::Balanced),
+        ("quality", RerankerPreset::Quality),
+    ]
+    .into_iter()
+    .map(|(name, preset)| {
+        serde_json::json!({
+            "id": name,
+            "model": preset.model().as_str(),
+            "candidates": preset.candidates(),
+        })
+    })
+    .collect();
+
+    Ok(serde_json::json!({
+        "models": models,
+        "presets": presets,
+        "current": reranker_status_json(status),
+    }))
+}
@@ -0,0 +1,39 @@
+/**
+ * Knowledge Export Commands (v3.9.0)
+ *
+ * Wiki and knowledge graph as an Obsidian-style Markdown vault
+ */
+
+use crate::services::knowledge_export::{KnowledgeExportService, VaultExportReport};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Export (or incrementally re-sync) the knowledge vault
+///
+/// `vault_path` is remembered for later syncs; without it the saved folder is used.
+/// Notes edited in the vault are only replaced with `overwrite`.
+#[tauri::command]
+pub async fn knowledge_export_vault(
+    vault_path: Option<String>,
+    overwrite: Option<bool>,
+    service: State<'_, Arc<KnowledgeExportService>>,
+) -> AppResult<VaultExportReport> {
+    let service = Arc::clone(&service);
+    Ok(tokio::task::spawn_blocking(move || {
+        service.export_vault(vault_path.as_deref(), overwrite.unwrap_or(false))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Failed to export knowledge vault: {}", e))?)
+}
+
+/// Folder the vault is synced to, if one was chosen
+#[tauri::command]
+pub async fn knowledge_get_vault_path(
+    service: State<'_, Arc<KnowledgeExportService>>,
+) -> AppResult<Option<String>> {
+    Ok(service
+        .vault_path()
+        .map_err(|e| format!("Failed to get vault path: {}", e))?)
+}
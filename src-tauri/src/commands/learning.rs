@@ -1,5 +1,7 @@
+use crate::AppResult;
 use crate::AppState;
-use crate::services::learning::{Feedback, PersonaParameters, LearningStats};
+use crate::services::learning::{AbExperimentConfig, AbExperimentStatus, Feedback, PersonaParameters, LearningStats};
+use crate::services::retrieval_feedback;
 use tauri::State;
 
 /// Record user feedback for learning system
@@ -7,12 +9,20 @@ use tauri::State;
 pub async fn learning_record_feedback(
     state: State<'_, AppState>,
     feedback: Feedback,
-) -> Result<(), String> {
+) -> AppResult<()> {
     log::info!("Recording feedback: satisfaction={:.2}", feedback.satisfaction);
 
+    let (conversation_id, satisfaction) = (feedback.conversation_id.clone(), feedback.satisfaction);
     state.learning_service
         .record_feedback(feedback)
-        .map_err(|e| format!("Failed to record feedback: {}", e))
+        .map_err(|e| format!("Failed to record feedback: {}", e))?;
+
+    // v3.9.0: The rating also labels the memories retrieved in this conversation
+    state.db.call(move |db| {
+        retrieval_feedback::apply_rating(db.conn(), &conversation_id, satisfaction)
+            .map_err(|e| format!("Failed to label retrieved memories: {}", e))
+    }).await?;
+    Ok(())
 }
 
 /// Optimize persona parameters based on feedback history
@@ -20,29 +30,29 @@ pub async fn learning_record_feedback(
 pub async fn learning_optimize_persona(
     state: State<'_, AppState>,
     current_persona: PersonaParameters,
-) -> Result<PersonaParameters, String> {
+) -> AppResult<PersonaParameters> {
     log::info!("Optimizing persona parameters");
 
-    state.learning_service
+    Ok(state.learning_service
         .optimize_persona(current_persona)
-        .map_err(|e| format!("Failed to optimize persona: {}", e))
+        .map_err(|e| format!("Failed to optimize persona: {}", e))?)
 }
 
 /// Get learning statistics
 #[tauri::command]
 pub async fn learning_get_stats(
     state: State<'_, AppState>,
-) -> Result<LearningStats, String> {
-    state.learning_service
+) -> AppResult<LearningStats> {
+    Ok(state.learning_service
         .get_stats()
-        .map_err(|e| format!("Failed to get learning stats: {}", e))
+        .map_err(|e| format!("Failed to get learning stats: {}", e))?)
 }
 
 /// Generate system prompt from persona parameters
 #[tauri::command]
 pub async fn learning_generate_system_prompt(
     persona: PersonaParameters,
-) -> Result<String, String> {
+) -> AppResult<String> {
     Ok(crate::services::learning::LearningService::generate_system_prompt(&persona))
 }
 
@@ -51,90 +61,92 @@ pub async fn learning_generate_system_prompt(
 pub async fn learning_save_persona(
     state: State<'_, AppState>,
     persona: PersonaParameters,
-) -> Result<(), String> {
+) -> AppResult<()> {
     log::info!("Saving persona parameters to local database");
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
-    let now = chrono::Utc::now().timestamp_millis();
-
-    // Save to database
-    conn.execute(
-        "INSERT INTO persona_parameters (
-            formality, verbosity, humor, emoji_usage, proactiveness,
-            technical_depth, empathy, code_examples, questioning, creativity,
-            created_at
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
-        rusqlite::params![
-            persona.formality,
-            persona.verbosity,
-            persona.humor,
-            persona.emoji_usage,
-            persona.proactiveness,
-            persona.technical_depth,
-            persona.empathy,
-            persona.code_examples,
-            persona.questioning,
-            persona.creativity,
-            now,
-        ],
-    )
-    .map_err(|e| e.to_string())?;
+    state.db.call(move |db| {
+        let conn = db.conn();
+        let now = chrono::Utc::now().timestamp_millis();
+
+        // Save to database
+        conn.execute(
+            "INSERT INTO persona_parameters (
+                formality, verbosity, humor, emoji_usage, proactiveness,
+                technical_depth, empathy, code_examples, questioning, creativity,
+                created_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            rusqlite::params![
+                persona.formality,
+                persona.verbosity,
+                persona.humor,
+                persona.emoji_usage,
+                persona.proactiveness,
+                persona.technical_depth,
+                persona.empathy,
+                persona.code_examples,
+                persona.questioning,
+                persona.creativity,
+                now,
+            ],
+        )
+        .map_err(|e| e.to_string())?;
 
-    Ok(())
+        Ok(())
+    }).await
 }
 
 /// Load persona parameters from local database
 #[tauri::command]
 pub async fn learning_load_persona(
     state: State<'_, AppState>,
-) -> Result<PersonaParameters, String> {
+) -> AppResult<PersonaParameters> {
     log::info!("Loading persona parameters from local database");
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
-
-    // Get latest persona
-    let persona = conn
-        .query_row(
-            "SELECT formality, verbosity, humor, emoji_usage, proactiveness,
-                    technical_depth, empathy, code_examples, questioning, creativity
-             FROM persona_parameters
-             ORDER BY id DESC
-             LIMIT 1",
-            [],
-            |row| {
-                Ok(PersonaParameters {
-                    formality: row.get(0)?,
-                    verbosity: row.get(1)?,
-                    humor: row.get(2)?,
-                    emoji_usage: row.get(3)?,
-                    proactiveness: row.get(4)?,
-                    technical_depth: row.get(5)?,
-                    empathy: row.get(6)?,
-                    code_examples: row.get(7)?,
-                    questioning: row.get(8)?,
-                    creativity: row.get(9)?,
-                })
-            },
-        )
-        .unwrap_or_else(|_| {
-            log::warn!("No persona found in database, using defaults");
-            PersonaParameters {
-                formality: 0.3,
-                verbosity: 0.5,
-                humor: 0.2,
-                emoji_usage: 0.1,
-                proactiveness: 0.4,
-                technical_depth: 0.6,
-                empathy: 0.5,
-                code_examples: 0.7,
-                questioning: 0.5,
-                creativity: 0.4,
-            }
-        });
-
-    Ok(persona)
+    state.db.call(move |db| {
+        let conn = db.conn();
+
+        // Get latest persona
+        let persona = conn
+            .query_row(
+                "SELECT formality, verbosity, humor, emoji_usage, proactiveness,
+                        technical_depth, empathy, code_examples, questioning, creativity
+                 FROM persona_parameters
+                 ORDER BY id DESC
+                 LIMIT 1",
+                [],
+                |row| {
+                    Ok(PersonaParameters {
+                        formality: row.get(0)?,
+                        verbosity: row.get(1)?,
+                        humor: row.get(2)?,
+                        emoji_usage: row.get(3)?,
+                        proactiveness: row.get(4)?,
+                        technical_depth: row.get(5)?,
+                        empathy: row.get(6)?,
+                        code_examples: row.get(7)?,
+                        questioning: row.get(8)?,
+                        creativity: row.get(9)?,
+                    })
+                },
+            )
+            .unwrap_or_else(|_| {
+                log::warn!("No persona found in database, using defaults");
+                PersonaParameters {
+                    formality: 0.3,
+                    verbosity: 0.5,
+                    humor: 0.2,
+                    emoji_usage: 0.1,
+                    proactiveness: 0.4,
+                    technical_depth: 0.6,
+                    empathy: 0.5,
+                    code_examples: 0.7,
+                    questioning: 0.5,
+                    creativity: 0.4,
+                }
+            });
+
+        Ok(persona)
+    }).await
 }
 
 /// Evolve full persona from temporal memories using ML-based trait analysis (Phase 4)
@@ -145,14 +157,14 @@ pub async fn learning_evolve_full_persona_from_temporal(
     retention_threshold: f32,
     learning_rate: f32,
     pattern_detector: State<'_, std::sync::Arc<crate::services::pattern_detector::LlmPatternDetector>>,
-) -> Result<PersonaParameters, String> {
+) -> AppResult<PersonaParameters> {
     log::info!(
         "Evolving full persona from temporal memories (threshold={}, rate={})",
         retention_threshold,
         learning_rate
     );
 
-    state.learning_service
+    Ok(state.learning_service
         .evolve_full_persona_from_temporal(
             current_persona,
             retention_threshold,
@@ -160,5 +172,41 @@ pub async fn learning_evolve_full_persona_from_temporal(
             &pattern_detector,
         )
         .await
-        .map_err(|e| format!("Failed to evolve full persona: {}", e))
+        .map_err(|e| format!("Failed to evolve full persona: {}", e))?)
+}
+
+/// Start a persona A/B experiment (v3.9.0)
+#[tauri::command]
+pub async fn learning_ab_start(
+    state: State<'_, AppState>,
+    config: AbExperimentConfig,
+) -> AppResult<AbExperimentStatus> {
+    log::info!("Starting persona experiment '{}'", config.name);
+
+    Ok(state.learning_service
+        .ab_start(config)
+        .map_err(|e| format!("Failed to start persona experiment: {}", e))?)
+}
+
+/// Running (or most recent) persona experiment with per-variant results (v3.9.0)
+#[tauri::command]
+pub async fn learning_ab_status(
+    state: State<'_, AppState>,
+) -> AppResult<Option<AbExperimentStatus>> {
+    Ok(state.learning_service
+        .ab_status()
+        .map_err(|e| format!("Failed to get persona experiment status: {}", e))?)
+}
+
+/// Stop the running persona experiment, optionally adopting the leader (v3.9.0)
+#[tauri::command]
+pub async fn learning_ab_stop(
+    state: State<'_, AppState>,
+    adopt_leader: Option<bool>,
+) -> AppResult<AbExperimentStatus> {
+    log::info!("Stopping persona experiment");
+
+    Ok(state.learning_service
+        .ab_stop(adopt_leader.unwrap_or(false))
+        .map_err(|e| format!("Failed to stop persona experiment: {}", e))?)
 }
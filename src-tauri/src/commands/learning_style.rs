@@ -4,8 +4,9 @@
 
 use crate::services::learning_style_adapter::{
     AdaptationResult, ComplexityLevel, ExplanationStyle, InteractionData, LearningModality,
-    LearningStyleAdapterService, LearningStyleProfile,
+    LearningStyleAdapterService, LearningStyleProfile, ResponseTransform, TransformOutcome,
 };
+use crate::AppResult;
 use std::sync::Arc;
 use tauri::State;
 
@@ -13,15 +14,15 @@ use tauri::State;
 pub async fn learning_style_get_profile(
     user_id: String,
     service: State<'_, Arc<LearningStyleAdapterService>>,
-) -> Result<LearningStyleProfile, String> {
+) -> AppResult<LearningStyleProfile> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .get_profile(&user_id)
             .map_err(|e| format!("Failed to get learning style profile: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
@@ -29,24 +30,24 @@ pub async fn learning_style_record_interaction(
     user_id: String,
     data: InteractionData,
     service: State<'_, Arc<LearningStyleAdapterService>>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .record_interaction(&user_id, data)
             .map_err(|e| format!("Failed to record interaction: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
 pub async fn learning_style_update_profile(
     user_id: String,
     service: State<'_, Arc<LearningStyleAdapterService>>,
-) -> Result<LearningStyleProfile, String> {
+) -> AppResult<LearningStyleProfile> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         let rt = tokio::runtime::Runtime::new().unwrap();
         rt.block_on(async move {
             service_clone
@@ -56,7 +57,7 @@ pub async fn learning_style_update_profile(
         })
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
@@ -64,9 +65,9 @@ pub async fn learning_style_adapt_response(
     user_id: String,
     original_response: String,
     service: State<'_, Arc<LearningStyleAdapterService>>,
-) -> Result<AdaptationResult, String> {
+) -> AppResult<AdaptationResult> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         let rt = tokio::runtime::Runtime::new().unwrap();
         rt.block_on(async move {
             service_clone
@@ -76,7 +77,7 @@ pub async fn learning_style_adapt_response(
         })
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
@@ -86,13 +87,71 @@ pub async fn learning_style_update_manual(
     complexity_level: Option<ComplexityLevel>,
     explanation_style: Option<ExplanationStyle>,
     service: State<'_, Arc<LearningStyleAdapterService>>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .update_profile_manually(&user_id, primary_modality, complexity_level, explanation_style)
             .map_err(|e| format!("Failed to update profile manually: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn learning_style_get_conversation_transforms(
+    conversation_id: String,
+    service: State<'_, Arc<LearningStyleAdapterService>>,
+) -> AppResult<bool> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .transforms_enabled(&conversation_id)
+            .map_err(|e| format!("Failed to get learning style transforms: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Switch the post-generation transforms on or off for one conversation
+#[tauri::command]
+pub async fn learning_style_set_conversation_transforms(
+    conversation_id: String,
+    enabled: bool,
+    service: State<'_, Arc<LearningStyleAdapterService>>,
+) -> AppResult<()> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .set_transforms_enabled(&conversation_id, enabled)
+            .map_err(|e| format!("Failed to set learning style transforms: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Preview the transforms on a response; without `transforms`, the profile decides
+#[tauri::command]
+pub async fn learning_style_transform_response(
+    user_id: String,
+    user_message: String,
+    response: String,
+    transforms: Option<Vec<ResponseTransform>>,
+    service: State<'_, Arc<LearningStyleAdapterService>>,
+) -> AppResult<TransformOutcome> {
+    let transforms = match transforms {
+        Some(transforms) => transforms,
+        None => {
+            let service_clone = Arc::clone(&service.inner());
+            let profile = tokio::task::spawn_blocking(move || {
+                service_clone
+                    .get_profile(&user_id)
+                    .map_err(|e| format!("Failed to get learning style profile: {}", e))
+            })
+            .await
+            .map_err(|e| format!("Task join error: {}", e))??;
+            LearningStyleAdapterService::transforms_for(&profile)
+        }
+    };
+    Ok(service.apply_transforms(&transforms, &user_message, response).await)
 }
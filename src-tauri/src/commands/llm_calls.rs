@@ -0,0 +1,20 @@
+/**
+ * LLM Call Tracing Commands (v3.9.0)
+ *
+ * Query persisted Ollama / embedding / vision call spans for performance debugging
+ */
+
+use crate::services::structured_logging::{LlmCallLog, LlmCallQuery, LlmCallRecord};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// List recent model calls (newest first), optionally filtered by kind, model, time, or duration
+#[tauri::command]
+pub async fn llm_calls_query(
+    filter: Option<LlmCallQuery>,
+    service: State<'_, Arc<LlmCallLog>>,
+) -> AppResult<Vec<LlmCallRecord>> {
+    Ok(service.query(&filter.unwrap_or_default())
+        .map_err(|e| format!("Failed to query LLM calls: {}", e))?)
+}
@@ -0,0 +1,53 @@
+/**
+ * Localization Commands (v3.9.0)
+ */
+
+use crate::services::localization::{LanguageStatus, LocalizationConfig, LocalizationService};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Primary language used for generated prompts and notifications
+#[tauri::command]
+pub async fn localization_get_language(
+    service: State<'_, Arc<LocalizationService>>,
+) -> AppResult<LanguageStatus> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .status()
+            .map_err(|e| format!("Failed to get primary language: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn localization_get_config(
+    service: State<'_, Arc<LocalizationService>>,
+) -> AppResult<LocalizationConfig> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .get_config()
+            .map_err(|e| format!("Failed to get localization config: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Set the primary language ("ko" / "en"), or null to detect it from recent messages
+#[tauri::command]
+pub async fn localization_update_config(
+    config: LocalizationConfig,
+    service: State<'_, Arc<LocalizationService>>,
+) -> AppResult<()> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .update_config(config)
+            .map_err(|e| format!("Failed to update localization config: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
@@ -13,6 +13,8 @@ use crate::services::lora_data_collector::{
 use crate::services::lora_adapter_manager::{
     LoRAAdapterManager, LoRAAdapter
 };
+use crate::AppError;
+use crate::AppResult;
 use log::info;
 use std::sync::{Arc, Mutex};
 use tauri::{command, State};
@@ -29,14 +31,14 @@ pub async fn lora_collect_data(
     state: State<'_, LoRAState>,
     format: String,
     limit: Option<usize>,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: lora_collect_data (format: {}, limit: {:?})", format, limit);
 
     let format = match format.as_str() {
         "alpaca" => TrainingFormat::Alpaca,
         "sharegpt" => TrainingFormat::ShareGPT,
         "jsonl" => TrainingFormat::JSONL,
-        _ => return Err(format!("Unknown format: {}. Use alpaca, sharegpt, or jsonl", format)),
+        _ => return Err(AppError::invalid_input(format!("Unknown format: {}. Use alpaca, sharegpt, or jsonl", format))),
     };
 
     let collector = state.data_collector.lock()
@@ -57,7 +59,7 @@ pub async fn lora_collect_data(
 #[command]
 pub async fn lora_get_filter(
     state: State<'_, LoRAState>,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: lora_get_filter");
 
     let collector = state.data_collector.lock()
@@ -85,7 +87,7 @@ pub async fn lora_update_filter(
     positive_only: Option<bool>,
     exclude_negative: Option<bool>,
     min_turns: Option<usize>,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     info!("Command: lora_update_filter");
 
     let mut collector = state.data_collector.lock()
@@ -111,14 +113,14 @@ pub async fn lora_update_filter(
 #[command]
 pub async fn lora_list_adapters(
     state: State<'_, LoRAState>,
-) -> Result<Vec<LoRAAdapter>, String> {
+) -> AppResult<Vec<LoRAAdapter>> {
     info!("Command: lora_list_adapters");
 
     let manager = state.adapter_manager.lock()
         .map_err(|e| format!("Failed to lock adapter manager: {}", e))?;
 
-    manager.list_adapters()
-        .map_err(|e| format!("Failed to list adapters: {}", e))
+    Ok(manager.list_adapters()
+        .map_err(|e| format!("Failed to list adapters: {}", e))?)
 }
 
 /// Get a specific LoRA adapter by ID
@@ -126,14 +128,14 @@ pub async fn lora_list_adapters(
 pub async fn lora_get_adapter(
     state: State<'_, LoRAState>,
     adapter_id: String,
-) -> Result<Option<LoRAAdapter>, String> {
+) -> AppResult<Option<LoRAAdapter>> {
     info!("Command: lora_get_adapter - {}", adapter_id);
 
     let manager = state.adapter_manager.lock()
         .map_err(|e| format!("Failed to lock adapter manager: {}", e))?;
 
-    manager.load_adapter(&adapter_id)
-        .map_err(|e| format!("Failed to get adapter: {}", e))
+    Ok(manager.load_adapter(&adapter_id)
+        .map_err(|e| format!("Failed to get adapter: {}", e))?)
 }
 
 /// Register a new LoRA adapter
@@ -145,14 +147,14 @@ pub async fn lora_register_adapter(
     base_model: String,
     adapter_path: String,
     version: String,
-) -> Result<LoRAAdapter, String> {
+) -> AppResult<LoRAAdapter> {
     info!("Command: lora_register_adapter - {}", name);
 
     let manager = state.adapter_manager.lock()
         .map_err(|e| format!("Failed to lock adapter manager: {}", e))?;
 
-    manager.register_adapter(name, description, base_model, adapter_path, version, None)
-        .map_err(|e| format!("Failed to register adapter: {}", e))
+    Ok(manager.register_adapter(name, description, base_model, adapter_path, version, None)
+        .map_err(|e| format!("Failed to register adapter: {}", e))?)
 }
 
 /// Delete a LoRA adapter
@@ -160,15 +162,15 @@ pub async fn lora_register_adapter(
 pub async fn lora_delete_adapter(
     state: State<'_, LoRAState>,
     adapter_id: String,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     info!("Command: lora_delete_adapter - {}", adapter_id);
 
     let manager = state.adapter_manager.lock()
         .map_err(|e| format!("Failed to lock adapter manager: {}", e))?;
 
-    manager.delete_adapter(&adapter_id)
+    Ok(manager.delete_adapter(&adapter_id)
         .map(|_| true)
-        .map_err(|e| format!("Failed to delete adapter: {}", e))
+        .map_err(|e| format!("Failed to delete adapter: {}", e))?)
 }
 
 /// Activate a LoRA adapter (set as active)
@@ -176,29 +178,29 @@ pub async fn lora_delete_adapter(
 pub async fn lora_activate_adapter(
     state: State<'_, LoRAState>,
     adapter_id: String,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     info!("Command: lora_activate_adapter - {}", adapter_id);
 
     let manager = state.adapter_manager.lock()
         .map_err(|e| format!("Failed to lock adapter manager: {}", e))?;
 
-    manager.set_active_adapter(&adapter_id)
+    Ok(manager.set_active_adapter(&adapter_id)
         .map(|_| true)
-        .map_err(|e| format!("Failed to activate adapter: {}", e))
+        .map_err(|e| format!("Failed to activate adapter: {}", e))?)
 }
 
 /// Get currently active LoRA adapter
 #[command]
 pub async fn lora_get_active_adapter(
     state: State<'_, LoRAState>,
-) -> Result<Option<LoRAAdapter>, String> {
+) -> AppResult<Option<LoRAAdapter>> {
     info!("Command: lora_get_active_adapter");
 
     let manager = state.adapter_manager.lock()
         .map_err(|e| format!("Failed to lock adapter manager: {}", e))?;
 
-    manager.get_active_adapter()
-        .map_err(|e| format!("Failed to get active adapter: {}", e))
+    Ok(manager.get_active_adapter()
+        .map_err(|e| format!("Failed to get active adapter: {}", e))?)
 }
 
 /// Generate Modelfile for a LoRA adapter
@@ -207,14 +209,14 @@ pub async fn lora_generate_modelfile(
     state: State<'_, LoRAState>,
     adapter_id: String,
     system_prompt: Option<String>,
-) -> Result<String, String> {
+) -> AppResult<String> {
     info!("Command: lora_generate_modelfile - {}", adapter_id);
 
     let manager = state.adapter_manager.lock()
         .map_err(|e| format!("Failed to lock adapter manager: {}", e))?;
 
-    manager.generate_modelfile(&adapter_id, system_prompt)
-        .map_err(|e| format!("Failed to generate modelfile: {}", e))
+    Ok(manager.generate_modelfile(&adapter_id, system_prompt)
+        .map_err(|e| format!("Failed to generate modelfile: {}", e))?)
 }
 
 /// Export training data to file
@@ -224,14 +226,14 @@ pub async fn lora_export_data(
     format: String,
     output_path: String,
     limit: Option<usize>,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: lora_export_data (format: {}, path: {})", format, output_path);
 
     let format = match format.as_str() {
         "alpaca" => TrainingFormat::Alpaca,
         "sharegpt" => TrainingFormat::ShareGPT,
         "jsonl" => TrainingFormat::JSONL,
-        _ => return Err(format!("Unknown format: {}. Use alpaca, sharegpt, or jsonl", format)),
+        _ => return Err(AppError::invalid_input(format!("Unknown format: {}. Use alpaca, sharegpt, or jsonl", format))),
     };
 
     let collector = state.data_collector.lock()
@@ -265,7 +267,7 @@ pub async fn lora_create_ollama_model(
     adapter_id: String,
     model_name: String,
     system_prompt: Option<String>,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: lora_create_ollama_model - adapter: {}, model: {}", adapter_id, model_name);
 
     // Get the adapter manager (need to clone to avoid holding lock across await)
@@ -292,7 +294,7 @@ pub async fn lora_create_ollama_model(
 
     if !response.status().is_success() {
         let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("Ollama create failed: {}", error_text));
+        return Err(format!("Ollama create failed: {}", error_text).into());
     }
 
     let response_text = response.text().await
@@ -300,11 +302,17 @@ pub async fn lora_create_ollama_model(
 
     // Check for errors in response
     if response_text.contains("error") && !response_text.contains("\"status\":\"success\"") {
-        return Err(format!("Ollama create failed: {}", response_text));
+        return Err(format!("Ollama create failed: {}", response_text).into());
     }
 
     info!("Successfully created Ollama model: {}", model_name);
 
+    // v3.9.0: Remember the model so chat can switch to this adapter
+    state.adapter_manager.lock()
+        .map_err(|e| format!("Failed to lock adapter manager: {}", e))?
+        .set_ollama_model(&adapter_id, &model_name)
+        .map_err(|e| format!("Failed to record adapter model: {}", e))?;
+
     Ok(serde_json::json!({
         "success": true,
         "model_name": model_name,
@@ -316,7 +324,7 @@ pub async fn lora_create_ollama_model(
 #[command]
 pub async fn lora_delete_ollama_model(
     model_name: String,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     info!("Command: lora_delete_ollama_model - {}", model_name);
 
     let client = reqwest::Client::new();
@@ -331,7 +339,7 @@ pub async fn lora_delete_ollama_model(
 
     if !response.status().is_success() {
         let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("Failed to delete model: {}", error_text));
+        return Err(format!("Failed to delete model: {}", error_text).into());
     }
 
     info!("Successfully deleted Ollama model: {}", model_name);
@@ -340,7 +348,7 @@ pub async fn lora_delete_ollama_model(
 
 /// List all Ollama models (v3.6.0)
 #[command]
-pub async fn lora_list_ollama_models() -> Result<Vec<String>, String> {
+pub async fn lora_list_ollama_models() -> AppResult<Vec<String>> {
     info!("Command: lora_list_ollama_models");
 
     let client = reqwest::Client::new();
@@ -351,7 +359,7 @@ pub async fn lora_list_ollama_models() -> Result<Vec<String>, String> {
         .map_err(|e| format!("Failed to connect to Ollama: {}", e))?;
 
     if !response.status().is_success() {
-        return Err("Failed to list Ollama models".to_string());
+        return Err("Failed to list Ollama models".into());
     }
 
     let json: serde_json::Value = response.json().await
@@ -373,7 +381,7 @@ pub async fn lora_list_ollama_models() -> Result<Vec<String>, String> {
 #[command]
 pub async fn lora_get_stats(
     state: State<'_, LoRAState>,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: lora_get_stats");
 
     let collector = state.data_collector.lock()
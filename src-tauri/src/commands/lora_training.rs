@@ -0,0 +1,92 @@
+/**
+ * LoRA Training Commands (v3.9.0)
+ *
+ * Run local fine-tune jobs and pick the chat model
+ */
+
+use crate::services::degradation::ServiceSlot;
+use crate::services::lora_training::{LoRATrainingService, TrainingConfig, TrainingJob, TrainingPreflight};
+use crate::services::ollama;
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+
+/// Check training data, disk space and trainer availability
+#[tauri::command]
+pub async fn lora_training_check(
+    config: Option<TrainingConfig>,
+    service: State<'_, Arc<ServiceSlot<LoRATrainingService>>>,
+) -> AppResult<TrainingPreflight> {
+    let service = service.get()?;
+    Ok(service.preflight(&config.unwrap_or_default())
+        .map_err(|e| format!("Failed to check training readiness: {}", e))?)
+}
+
+/// Start a fine-tune job; progress arrives as `lora-training-progress` events
+#[tauri::command]
+pub async fn lora_training_start(
+    app: AppHandle,
+    config: Option<TrainingConfig>,
+    service: State<'_, Arc<ServiceSlot<LoRATrainingService>>>,
+) -> AppResult<TrainingJob> {
+    let service = service.get()?;
+    log::info!("Command: lora_training_start");
+
+    Ok(service.start(config.unwrap_or_default(), Some(app))
+        .await
+        .map_err(|e| format!("Failed to start LoRA training: {}", e))?)
+}
+
+/// Get a training job
+#[tauri::command]
+pub async fn lora_training_status(
+    job_id: String,
+    service: State<'_, Arc<ServiceSlot<LoRATrainingService>>>,
+) -> AppResult<Option<TrainingJob>> {
+    let service = service.get()?;
+    Ok(service.get_job(&job_id)
+        .map_err(|e| format!("Failed to get training job: {}", e))?)
+}
+
+/// List training jobs, newest first
+#[tauri::command]
+pub async fn lora_training_list(
+    limit: Option<usize>,
+    service: State<'_, Arc<ServiceSlot<LoRATrainingService>>>,
+) -> AppResult<Vec<TrainingJob>> {
+    let service = service.get()?;
+    Ok(service.list_jobs(limit.unwrap_or(20))
+        .map_err(|e| format!("Failed to list training jobs: {}", e))?)
+}
+
+/// Cancel a running job
+#[tauri::command]
+pub async fn lora_training_cancel(
+    job_id: String,
+    service: State<'_, Arc<ServiceSlot<LoRATrainingService>>>,
+) -> AppResult<bool> {
+    let service = service.get()?;
+    log::info!("Command: lora_training_cancel - {}", job_id);
+
+    Ok(service.cancel(&job_id)
+        .map_err(|e| format!("Failed to cancel training job: {}", e))?)
+}
+
+/// Chat with an adapter's model, or the default model when `adapter_id` is omitted
+#[tauri::command]
+pub async fn lora_training_use_adapter(
+    adapter_id: Option<String>,
+    service: State<'_, Arc<ServiceSlot<LoRATrainingService>>>,
+) -> AppResult<String> {
+    let service = service.get()?;
+    log::info!("Command: lora_training_use_adapter - {:?}", adapter_id);
+
+    Ok(service.use_adapter(adapter_id.as_deref())
+        .map_err(|e| format!("Failed to select chat model: {}", e))?)
+}
+
+/// Model currently used for chat
+#[tauri::command]
+pub async fn lora_training_get_chat_model() -> AppResult<String> {
+    Ok(ollama::chat_model())
+}
@@ -2,6 +2,7 @@
  * Phase 4: Memory Consolidation Commands (v3.8.0)
  *
  * Tauri commands for intelligent memory merging.
+ * v3.9.0: Review queue of merge proposals (list / accept / reject).
  *
  * NOTE: This module is only compiled when Phase 4 features are enabled.
  * To enable: cargo build --features phase4
@@ -11,8 +12,10 @@
 
 use crate::services::memory_consolidation::{
     ConsolidationConfig, ConsolidationResult, ConsolidationStats,
-    MemoryConsolidationService,
+    MemoryConsolidationService, MergeProposal, ProposalDecision, ProposalStatus,
 };
+use crate::AppError;
+use crate::AppResult;
 use std::sync::Arc;
 use tauri::State;
 
@@ -20,23 +23,23 @@ use tauri::State;
 #[tauri::command]
 pub async fn consolidation_run(
     service: State<'_, Arc<MemoryConsolidationService>>,
-) -> Result<Vec<ConsolidationResult>, String> {
+) -> AppResult<Vec<ConsolidationResult>> {
     log::info!("Running memory consolidation...");
 
-    service
+    Ok(service
         .consolidate_memories()
         .await
-        .map_err(|e| format!("Failed to consolidate memories: {}", e))
+        .map_err(|e| format!("Failed to consolidate memories: {}", e))?)
 }
 
 /// Get consolidation statistics
 #[tauri::command]
 pub async fn consolidation_get_stats(
     service: State<'_, Arc<MemoryConsolidationService>>,
-) -> Result<ConsolidationStats, String> {
-    service
+) -> AppResult<ConsolidationStats> {
+    Ok(service
         .get_stats()
-        .map_err(|e| format!("Failed to get consolidation stats: {}", e))
+        .map_err(|e| format!("Failed to get consolidation stats: {}", e))?)
 }
 
 /// Update consolidation configuration
@@ -44,16 +47,83 @@ pub async fn consolidation_get_stats(
 pub async fn consolidation_update_config(
     config: ConsolidationConfig,
     service: State<'_, Arc<MemoryConsolidationService>>,
-) -> Result<(), String> {
-    service
+) -> AppResult<()> {
+    Ok(service
         .update_config(config)
-        .map_err(|e| format!("Failed to update config: {}", e))
+        .map_err(|e| format!("Failed to update config: {}", e))?)
 }
 
 /// Get consolidation configuration
 #[tauri::command]
 pub async fn consolidation_get_config(
     service: State<'_, Arc<MemoryConsolidationService>>,
-) -> Result<ConsolidationConfig, String> {
+) -> AppResult<ConsolidationConfig> {
     Ok(service.get_config())
 }
+
+/// Queue merge proposals now instead of waiting for the background job (v3.9.0)
+#[tauri::command]
+pub async fn consolidation_propose(
+    service: State<'_, Arc<MemoryConsolidationService>>,
+) -> AppResult<Vec<MergeProposal>> {
+    Ok(service
+        .propose_merges()
+        .await
+        .map_err(|e| format!("Failed to propose memory merges: {}", e))?)
+}
+
+/// List merge proposals with their similarity scores and proposed merged memory (v3.9.0)
+///
+/// `status` is "pending" (default), "accepted", "rejected" or "all".
+#[tauri::command]
+pub async fn consolidation_list_proposals(
+    status: Option<String>,
+    limit: Option<usize>,
+    service: State<'_, Arc<MemoryConsolidationService>>,
+) -> AppResult<Vec<MergeProposal>> {
+    let status = match status.as_deref().unwrap_or("pending") {
+        "all" => None,
+        other => Some(
+            ProposalStatus::from_str(other)
+                .ok_or_else(|| AppError::invalid_input(format!("Unknown proposal status: {}", other)))?,
+        ),
+    };
+
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .list_proposals(status, limit.unwrap_or(50))
+            .map_err(|e| format!("Failed to list merge proposals: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Merge a proposal's memories; the merged memory can be edited first (v3.9.0)
+#[tauri::command]
+pub async fn consolidation_accept_proposal(
+    proposal_id: String,
+    user_message: Option<String>,
+    ai_response: Option<String>,
+    service: State<'_, Arc<MemoryConsolidationService>>,
+) -> AppResult<ProposalDecision> {
+    Ok(service
+        .accept_proposal(&proposal_id, user_message, ai_response)
+        .await
+        .map_err(|e| format!("Failed to accept merge proposal: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn consolidation_reject_proposal(
+    proposal_id: String,
+    service: State<'_, Arc<MemoryConsolidationService>>,
+) -> AppResult<ProposalDecision> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .reject_proposal(&proposal_id)
+            .map_err(|e| format!("Failed to reject merge proposal: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
@@ -7,6 +7,7 @@
 use crate::services::memory_enhancer::{
     EnhancedMemory, EnhancementStats, MemoryEnhancerConfig, MemoryEnhancerService, QualityMetrics,
 };
+use crate::AppResult;
 use std::sync::Arc;
 use tauri::State;
 
@@ -15,11 +16,11 @@ use tauri::State;
 pub async fn memory_analyze_quality(
     memory_content: String,
     service: State<'_, Arc<MemoryEnhancerService>>,
-) -> Result<QualityMetrics, String> {
-    service
+) -> AppResult<QualityMetrics> {
+    Ok(service
         .analyze_quality(&memory_content)
         .await
-        .map_err(|e| format!("Failed to analyze memory quality: {}", e))
+        .map_err(|e| format!("Failed to analyze memory quality: {}", e))?)
 }
 
 /// Enhance a single memory
@@ -28,11 +29,11 @@ pub async fn memory_enhance(
     memory_content: String,
     quality_metrics: QualityMetrics,
     service: State<'_, Arc<MemoryEnhancerService>>,
-) -> Result<String, String> {
-    service
+) -> AppResult<String> {
+    Ok(service
         .enhance_memory(&memory_content, &quality_metrics)
         .await
-        .map_err(|e| format!("Failed to enhance memory: {}", e))
+        .map_err(|e| format!("Failed to enhance memory: {}", e))?)
 }
 
 /// Process a memory (analyze + enhance if needed)
@@ -41,11 +42,11 @@ pub async fn memory_process(
     memory_id: String,
     memory_content: String,
     service: State<'_, Arc<MemoryEnhancerService>>,
-) -> Result<EnhancedMemory, String> {
-    service
+) -> AppResult<EnhancedMemory> {
+    Ok(service
         .process_memory(&memory_id, &memory_content)
         .await
-        .map_err(|e| format!("Failed to process memory: {}", e))
+        .map_err(|e| format!("Failed to process memory: {}", e))?)
 }
 
 /// Batch enhance multiple memories
@@ -53,26 +54,26 @@ pub async fn memory_process(
 pub async fn memory_batch_enhance(
     memory_ids: Vec<String>,
     service: State<'_, Arc<MemoryEnhancerService>>,
-) -> Result<Vec<EnhancedMemory>, String> {
-    service
+) -> AppResult<Vec<EnhancedMemory>> {
+    Ok(service
         .batch_enhance(memory_ids)
         .await
-        .map_err(|e| format!("Failed to batch enhance memories: {}", e))
+        .map_err(|e| format!("Failed to batch enhance memories: {}", e))?)
 }
 
 /// Get enhancement statistics
 #[tauri::command]
 pub async fn memory_get_enhancement_stats(
     service: State<'_, Arc<MemoryEnhancerService>>,
-) -> Result<EnhancementStats, String> {
+) -> AppResult<EnhancementStats> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .get_stats()
             .map_err(|e| format!("Failed to get enhancement stats: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 /// Get enhanced memory by ID
@@ -80,15 +81,15 @@ pub async fn memory_get_enhancement_stats(
 pub async fn memory_get_enhancement(
     memory_id: String,
     service: State<'_, Arc<MemoryEnhancerService>>,
-) -> Result<Option<EnhancedMemory>, String> {
+) -> AppResult<Option<EnhancedMemory>> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .get_enhancement(&memory_id)
             .map_err(|e| format!("Failed to get enhancement: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 /// Update memory enhancer configuration
@@ -96,23 +97,23 @@ pub async fn memory_get_enhancement(
 pub async fn memory_update_config(
     config: MemoryEnhancerConfig,
     service: State<'_, Arc<MemoryEnhancerService>>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone.update_config(config);
-        Ok(())
+        Ok::<_, String>(())
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 /// Get current configuration
 #[tauri::command]
 pub async fn memory_get_config(
     service: State<'_, Arc<MemoryEnhancerService>>,
-) -> Result<MemoryEnhancerConfig, String> {
+) -> AppResult<MemoryEnhancerConfig> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || Ok(service_clone.get_config()))
+    Ok(tokio::task::spawn_blocking(move || Ok::<_, String>(service_clone.get_config()))
         .await
-        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Task join error: {}", e))??)
 }
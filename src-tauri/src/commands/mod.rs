@@ -45,3 +45,57 @@ pub mod proactive;  // v3.6.0: Proactive AI monitoring
 pub mod lora;  // v3.6.0: LoRA training data collection and adapter management
 pub mod plugin;  // v3.6.0: Plugin system management
 pub mod episodic_memory;  // v3.6.0: Episodic memory visualization commands
+pub mod activity_timeline;  // v3.9.0: Activity timeline and daily summaries
+pub mod ollama_supervisor;  // v3.9.0: Ollama process supervision
+pub mod conversation_language;  // v3.9.0: Conversation language lock commands
+pub mod clipboard_history;  // v3.9.0: Clipboard history and AI transforms
+pub mod quick_ask;  // v3.9.0: Global hotkey quick ask commands
+pub mod scripting;  // v3.9.0: User automation scripts
+pub mod prompt_templates;  // v3.9.0: Prompt template library
+pub mod notification;  // v3.9.0: Notification action routing
+pub mod profile;  // v3.9.0: Profile / workspace switching
+pub mod encryption;  // v3.9.0: Encryption at rest unlock/enable
+pub mod secrets;  // v3.9.0: Secrets vault
+pub mod privacy;  // v3.9.0: Topic export / forget
+pub mod knowledge_export;  // v3.9.0: Markdown vault export
+pub mod note_import;  // v3.9.0: Obsidian / Notion import
+pub mod analytics;  // v3.9.0: Usage analytics dashboard
+pub mod llm_calls;  // v3.9.0: LLM call tracing
+pub mod backup;  // v3.9.0: Local backup and restore
+pub mod device_sync;  // v3.9.0: Multi-device sync
+pub mod background_jobs;  // v3.9.0: Background job scheduler
+pub mod review_queue;  // v3.9.0: Spaced-repetition memory review
+pub mod rag_eval;  // v3.9.0: Retrieval evaluation against golden datasets
+pub mod retrieval_feedback;  // v3.9.0: Feedback-driven retrieval tuning
+pub mod rag;  // v3.9.0: Document ingestion and chunking config
+pub mod persona_presets;  // v3.9.0: Persona presets
+pub mod persona_changes;  // v3.9.0: Persona change log and rollback
+pub mod mood;  // v3.9.0: Conversation mood timeline
+pub mod lora_training;  // v3.9.0: LoRA fine-tune jobs and chat model selection
+pub mod model_router;  // v3.9.0: Dual-model routing config and metrics
+pub mod response_verifier;  // v3.9.0: Response self-check config and stats
+pub mod benchmark;  // v3.9.0: End-to-end latency benchmark
+pub mod startup;  // v3.9.0: Startup timing report
+pub mod degradation;  // v3.9.0: Service status matrix and retry
+pub mod tool_cache;  // v3.9.0: Tool result cache
+pub mod tool_runtime;  // v3.9.0: Tool cancellation and timeouts
+pub mod tool_sets;  // v3.9.0: Tool sets and per-conversation bindings
+pub mod openapi_tools;  // v3.9.0: Importing HTTP APIs as tools
+pub mod sql_query;  // v3.9.0: Databases for the sql_query tool
+pub mod charts;  // v3.9.0: Chart rendering and export
+pub mod calculator;  // v3.9.0: Calculator evaluation, settings and exchange rates
+pub mod code_sandbox;  // v3.9.0: Code sandbox runs and settings
+pub mod window_manager;  // v3.9.0: Window listing, focus, app launching and placement
+pub mod os_automation;  // v3.9.0: Shortcuts, approved PowerShell scripts and their settings
+pub mod reports;  // v3.9.0: Report generation (HTML / PDF)
+pub mod network;  // v3.9.0: Offline mode and web tool politeness
+pub mod search_history;  // v3.9.0: Persisted web search history
+pub mod workspace;  // v3.9.0: Active project awareness
+pub mod terminal_capture;  // v3.9.0: Terminal session capture
+pub mod weekly_review;  // v3.9.0: Weekly review reports
+pub mod localization;  // v3.9.0: Primary language settings
+pub mod translation;  // v3.9.0: Local-model translation and glossary
+pub mod conversation_topics;  // v3.9.0: Automatic titles and topic labels
+pub mod conversation_digest;  // v3.9.0: Conversation summaries into durable memories
+pub mod session_context;  // v3.9.0: Chat ↔ computer control context sessions
+pub mod agent_actions;  // v3.9.0: Undo / rollback of agent file and git changes
@@ -0,0 +1,49 @@
+/**
+ * Model Router Commands (v3.9.0)
+ *
+ * Configure fast-model triage and inspect per-route metrics
+ */
+
+use crate::services::model_router::{ModelRouterService, RouteDecision, RouterConfig, RouterMetrics};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Get the router config
+#[tauri::command]
+pub async fn router_get_config(
+    service: State<'_, Arc<ModelRouterService>>,
+) -> AppResult<RouterConfig> {
+    Ok(service.config())
+}
+
+/// Update the router config
+#[tauri::command]
+pub async fn router_set_config(
+    config: RouterConfig,
+    service: State<'_, Arc<ModelRouterService>>,
+) -> AppResult<RouterConfig> {
+    log::info!("Command: router_set_config - enabled: {}", config.enabled);
+
+    Ok(service.set_config(config)
+        .map_err(|e| format!("Failed to update router config: {}", e))?)
+}
+
+/// Per-route requests, escalations, latency and confidence
+#[tauri::command]
+pub async fn router_get_metrics(
+    days: Option<u32>,
+    service: State<'_, Arc<ModelRouterService>>,
+) -> AppResult<RouterMetrics> {
+    Ok(service.metrics(days.unwrap_or(7))
+        .map_err(|e| format!("Failed to get router metrics: {}", e))?)
+}
+
+/// Preview the route of a message without answering it
+#[tauri::command]
+pub async fn router_classify(
+    message: String,
+    service: State<'_, Arc<ModelRouterService>>,
+) -> AppResult<RouteDecision> {
+    Ok(service.classify(&message))
+}
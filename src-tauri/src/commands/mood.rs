@@ -0,0 +1,20 @@
+/**
+ * Mood Commands (v3.9.0)
+ *
+ * Per-conversation sentiment timeline
+ */
+
+use crate::services::sentiment::{MoodTimeline, SentimentService};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Mood timeline of a conversation (oldest message first)
+#[tauri::command]
+pub async fn mood_get_timeline(
+    conversation_id: String,
+    service: State<'_, Arc<SentimentService>>,
+) -> AppResult<MoodTimeline> {
+    Ok(service.timeline(&conversation_id)
+        .map_err(|e| format!("Failed to load mood timeline: {}", e))?)
+}
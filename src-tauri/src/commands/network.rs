@@ -0,0 +1,38 @@
+/**
+ * Network Policy Commands (v3.9.0)
+ *
+ * Offline mode and politeness settings for the web search / URL fetch tools
+ */
+
+use crate::services::network_policy::{self, NetworkPolicyConfig, NetworkStatus};
+use crate::AppResult;
+
+/// Turn offline mode on or off for this session
+///
+/// While offline, web search and URL fetch fail without sending any request.
+#[tauri::command]
+pub async fn network_set_offline(offline: bool) -> AppResult<NetworkStatus> {
+    log::info!("Command: network_set_offline - {}", offline);
+
+    let policy = network_policy::global();
+    policy.set_offline(offline);
+    Ok(policy.status())
+}
+
+/// Offline flag, in-flight requests and politeness settings
+#[tauri::command]
+pub async fn network_get_status() -> AppResult<NetworkStatus> {
+    Ok(network_policy::global().status())
+}
+
+/// Update user agent, per-domain interval, concurrency cap and robots.txt handling
+#[tauri::command]
+pub async fn network_set_config(config: NetworkPolicyConfig) -> AppResult<NetworkStatus> {
+    log::info!("Command: network_set_config");
+
+    let policy = network_policy::global();
+    policy
+        .set_config(config)
+        .map_err(|e| format!("Failed to update network policy: {}", e))?;
+    Ok(policy.status())
+}
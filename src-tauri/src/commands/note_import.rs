@@ -0,0 +1,29 @@
+/**
+ * Note Import Commands (v3.9.0)
+ *
+ * Obsidian vaults and Notion exports into RAG, the wiki and the knowledge graph
+ */
+
+use crate::services::degradation::ServiceSlot;
+use crate::services::note_import::{NoteImportReport, NoteImportService};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Import a folder of Markdown notes (Obsidian vault or unzipped Notion export)
+///
+/// Importing the same folder again only processes notes that changed and
+/// removes the memories of notes that were deleted.
+#[tauri::command]
+pub async fn notes_import_vault(
+    vault_path: String,
+    service: State<'_, Arc<ServiceSlot<NoteImportService>>>,
+) -> AppResult<NoteImportReport> {
+    let service = service.get()?;
+    log::info!("Command: notes_import_vault - {}", vault_path);
+
+    Ok(service
+        .import_vault(&vault_path)
+        .await
+        .map_err(|e| format!("Failed to import notes: {}", e))?)
+}
@@ -0,0 +1,23 @@
+/**
+ * Notification Commands (v3.9.0)
+ *
+ * Routes notification button clicks mirrored in the frontend
+ */
+
+use crate::services::notification::{NotificationRoute, NotificationService};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Handle a notification button click ("snooze", "open_chat", "run_plan")
+#[tauri::command]
+pub async fn notification_handle_action(
+    notification_id: String,
+    action: String,
+    service: State<'_, Arc<NotificationService>>,
+) -> AppResult<NotificationRoute> {
+    Ok(service
+        .handle_action(&notification_id, &action)
+        .await
+        .map_err(|e| format!("Failed to handle notification action: {}", e))?)
+}
@@ -0,0 +1,46 @@
+/**
+ * Ollama Supervisor Commands (v3.9.0)
+ *
+ * Status and control for the Ollama process supervisor. Status changes are also
+ * pushed to the frontend via the `ollama-status` event.
+ */
+
+use crate::services::ollama_supervisor::{OllamaSupervisor, SupervisorConfig, SupervisorState};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Get current Ollama supervisor state
+#[tauri::command]
+pub async fn ollama_get_status(
+    supervisor: State<'_, Arc<OllamaSupervisor>>,
+) -> AppResult<SupervisorState> {
+    Ok(supervisor.get_state())
+}
+
+/// Restart the Ollama server immediately
+#[tauri::command]
+pub async fn ollama_restart(
+    supervisor: State<'_, Arc<OllamaSupervisor>>,
+) -> AppResult<()> {
+    log::info!("Command: ollama_restart");
+    Ok(supervisor.restart_now().await?)
+}
+
+/// Get supervisor configuration
+#[tauri::command]
+pub async fn ollama_get_supervisor_config(
+    supervisor: State<'_, Arc<OllamaSupervisor>>,
+) -> AppResult<SupervisorConfig> {
+    Ok(supervisor.config())
+}
+
+/// Update supervisor configuration
+#[tauri::command]
+pub async fn ollama_update_supervisor_config(
+    config: SupervisorConfig,
+    supervisor: State<'_, Arc<OllamaSupervisor>>,
+) -> AppResult<()> {
+    supervisor.update_config(config);
+    Ok(())
+}
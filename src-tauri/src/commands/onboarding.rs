@@ -1,3 +1,5 @@
+use crate::AppError;
+use crate::AppResult;
 use crate::AppState;
 use crate::database::models::UserProfile;
 use crate::services::system_info::{SystemInfoService, SystemSpecs};
@@ -27,107 +29,14 @@ pub struct OnboardingStatus {
 #[tauri::command]
 pub async fn check_onboarding_status(
     state: State<'_, AppState>,
-) -> Result<OnboardingStatus, String> {
+) -> AppResult<OnboardingStatus> {
     log::info!("Checking onboarding status...");
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
+    state.db.call(move |db| {
+        let conn = db.conn();
 
-    // Check if user_profile exists
-    let profile_result = conn.query_row(
-        "SELECT id, name, display_name, age_group, occupation, interests,
-                tone_preference, proactive_frequency, selected_persona,
-                onboarding_completed_at, created_at, updated_at
-         FROM user_profile WHERE id = 1",
-        [],
-        |row| {
-            Ok(UserProfile {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                display_name: row.get(2)?,
-                age_group: row.get(3)?,
-                occupation: row.get(4)?,
-                interests: row.get(5)?,
-                tone_preference: row.get(6)?,
-                proactive_frequency: row.get(7)?,
-                selected_persona: row.get(8)?,
-                onboarding_completed_at: row.get(9)?,
-                created_at: row.get(10)?,
-                updated_at: row.get(11)?,
-            })
-        },
-    );
-
-    match profile_result {
-        Ok(profile) => {
-            log::info!("User profile found: {}", profile.name);
-            Ok(OnboardingStatus {
-                completed: true,
-                profile: Some(profile),
-            })
-        }
-        Err(rusqlite::Error::QueryReturnedNoRows) => {
-            log::info!("No user profile found - onboarding not completed");
-            Ok(OnboardingStatus {
-                completed: false,
-                profile: None,
-            })
-        }
-        Err(e) => {
-            log::error!("Error checking onboarding status: {}", e);
-            Err(e.to_string())
-        }
-    }
-}
-
-/// Complete onboarding and save user profile
-#[tauri::command]
-pub async fn complete_onboarding(
-    state: State<'_, AppState>,
-    answers: OnboardingAnswers,
-) -> Result<UserProfile, String> {
-    log::info!("Completing onboarding for user: {}", answers.name);
-
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
-
-    let now = chrono::Utc::now().timestamp_millis();
-
-    // Extract display name (Korean name: use full name, English name: first name)
-    let display_name = extract_display_name(&answers.name);
-
-    // Insert user profile
-    conn.execute(
-        "INSERT INTO user_profile (
-            id, name, display_name, age_group, occupation, interests,
-            tone_preference, proactive_frequency, selected_persona,
-            onboarding_completed_at, created_at, updated_at
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
-        rusqlite::params![
-            1, // Only one profile allowed
-            &answers.name,
-            &display_name,
-            Option::<String>::None, // age_group not collected in onboarding
-            &answers.occupation,
-            &answers.interests,
-            &answers.tone_preference,
-            &answers.proactive_frequency,
-            &answers.selected_persona,
-            now,
-            now,
-            now,
-        ],
-    )
-    .map_err(|e| format!("Failed to save user profile: {}", e))?;
-
-    // Apply persona settings based on onboarding answers
-    apply_persona_from_onboarding(&conn, &answers)?;
-
-    log::info!("Onboarding completed successfully for {}", answers.name);
-
-    // Return the created profile
-    let profile = conn
-        .query_row(
+        // Check if user_profile exists
+        let profile_result = conn.query_row(
             "SELECT id, name, display_name, age_group, occupation, interests,
                     tone_preference, proactive_frequency, selected_persona,
                     onboarding_completed_at, created_at, updated_at
@@ -149,10 +58,105 @@ pub async fn complete_onboarding(
                     updated_at: row.get(11)?,
                 })
             },
-        )
-        .map_err(|e| e.to_string())?;
+        );
+
+        match profile_result {
+            Ok(profile) => {
+                log::info!("User profile found: {}", profile.name);
+                Ok(OnboardingStatus {
+                    completed: true,
+                    profile: Some(profile),
+                })
+            }
+            Err(rusqlite::Error::QueryReturnedNoRows) => {
+                log::info!("No user profile found - onboarding not completed");
+                Ok(OnboardingStatus {
+                    completed: false,
+                    profile: None,
+                })
+            }
+            Err(e) => {
+                log::error!("Error checking onboarding status: {}", e);
+                Err(e.to_string().into())
+            }
+        }
+    }).await
+}
+
+/// Complete onboarding and save user profile
+#[tauri::command]
+pub async fn complete_onboarding(
+    state: State<'_, AppState>,
+    answers: OnboardingAnswers,
+) -> AppResult<UserProfile> {
+    log::info!("Completing onboarding for user: {}", answers.name);
+
+    state.db.call(move |db| {
+        let conn = db.conn();
+
+        let now = chrono::Utc::now().timestamp_millis();
+
+        // Extract display name (Korean name: use full name, English name: first name)
+        let display_name = extract_display_name(&answers.name);
 
-    Ok(profile)
+        // Insert user profile
+        conn.execute(
+            "INSERT INTO user_profile (
+                id, name, display_name, age_group, occupation, interests,
+                tone_preference, proactive_frequency, selected_persona,
+                onboarding_completed_at, created_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            rusqlite::params![
+                1, // Only one profile allowed
+                &answers.name,
+                &display_name,
+                Option::<String>::None, // age_group not collected in onboarding
+                &answers.occupation,
+                &answers.interests,
+                &answers.tone_preference,
+                &answers.proactive_frequency,
+                &answers.selected_persona,
+                now,
+                now,
+                now,
+            ],
+        )
+        .map_err(|e| format!("Failed to save user profile: {}", e))?;
+
+        // Apply persona settings based on onboarding answers
+        apply_persona_from_onboarding(&conn, &answers)?;
+
+        log::info!("Onboarding completed successfully for {}", answers.name);
+
+        // Return the created profile
+        let profile = conn
+            .query_row(
+                "SELECT id, name, display_name, age_group, occupation, interests,
+                        tone_preference, proactive_frequency, selected_persona,
+                        onboarding_completed_at, created_at, updated_at
+                 FROM user_profile WHERE id = 1",
+                [],
+                |row| {
+                    Ok(UserProfile {
+                        id: row.get(0)?,
+                        name: row.get(1)?,
+                        display_name: row.get(2)?,
+                        age_group: row.get(3)?,
+                        occupation: row.get(4)?,
+                        interests: row.get(5)?,
+                        tone_preference: row.get(6)?,
+                        proactive_frequency: row.get(7)?,
+                        selected_persona: row.get(8)?,
+                        onboarding_completed_at: row.get(9)?,
+                        created_at: row.get(10)?,
+                        updated_at: row.get(11)?,
+                    })
+                },
+            )
+            .map_err(|e| e.to_string())?;
+
+        Ok(profile)
+    }).await
 }
 
 /// Extract display name from full name
@@ -307,7 +311,7 @@ fn apply_persona_from_onboarding(
 
 /// Detect system specifications
 #[tauri::command]
-pub async fn detect_system_specs() -> Result<SystemSpecs, String> {
+pub async fn detect_system_specs() -> AppResult<SystemSpecs> {
     log::info!("Detecting system specifications...");
 
     let mut service = SystemInfoService::new();
@@ -318,7 +322,7 @@ pub async fn detect_system_specs() -> Result<SystemSpecs, String> {
 
 /// Get model recommendation based on system specs
 #[tauri::command]
-pub async fn get_model_recommendation(specs: SystemSpecs) -> Result<ModelRecommendation, String> {
+pub async fn get_model_recommendation(specs: SystemSpecs) -> AppResult<ModelRecommendation> {
     log::info!("Getting model recommendation for system specs");
 
     let recommendation = ModelRecommenderService::recommend(&specs)
@@ -329,7 +333,7 @@ pub async fn get_model_recommendation(specs: SystemSpecs) -> Result<ModelRecomme
 
 /// Get required models list
 #[tauri::command]
-pub async fn get_required_models(llm_model: String) -> Result<RequiredModels, String> {
+pub async fn get_required_models(llm_model: String) -> AppResult<RequiredModels> {
     log::info!("Getting required models for LLM: {}", llm_model);
 
     let models = ModelRecommenderService::get_required_models(&llm_model)
@@ -340,7 +344,7 @@ pub async fn get_required_models(llm_model: String) -> Result<RequiredModels, St
 
 /// Check if Ollama is installed
 #[tauri::command]
-pub async fn check_ollama_installed(state: State<'_, AppState>) -> Result<bool, String> {
+pub async fn check_ollama_installed(state: State<'_, AppState>) -> AppResult<bool> {
     log::info!("Checking Ollama installation...");
 
     let is_installed = state.model_installer.check_ollama_installed().await
@@ -351,7 +355,7 @@ pub async fn check_ollama_installed(state: State<'_, AppState>) -> Result<bool,
 
 /// Install Ollama (auto-install via Homebrew on macOS or installer on Windows)
 #[tauri::command]
-pub async fn install_ollama(state: State<'_, AppState>) -> Result<(), String> {
+pub async fn install_ollama(state: State<'_, AppState>) -> AppResult<()> {
     log::info!("Installing Ollama...");
 
     state.model_installer.install_ollama().await
@@ -365,7 +369,7 @@ pub async fn install_ollama(state: State<'_, AppState>) -> Result<(), String> {
 pub async fn check_model_exists(
     state: State<'_, AppState>,
     model_name: String,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     log::info!("Checking if model exists: {}", model_name);
 
     let exists = state.model_installer.check_model_exists(&model_name).await
@@ -380,13 +384,13 @@ pub async fn start_model_download(
     state: State<'_, AppState>,
     model_name: String,
     model_type: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     log::info!("Starting model download: {} ({})", model_name, model_type);
 
     let model_type_enum = match model_type.as_str() {
         "llm" => crate::services::model_installer::ModelType::LLM,
         "llava" => crate::services::model_installer::ModelType::LLaVA,
-        _ => return Err(format!("Invalid model type: {}", model_type)),
+        _ => return Err(AppError::invalid_input(format!("Invalid model type: {}", model_type))),
     };
 
     state.model_installer.start_model_download(model_name, model_type_enum).await
@@ -399,7 +403,7 @@ pub async fn start_model_download(
 #[tauri::command]
 pub async fn get_download_progress(
     state: State<'_, AppState>,
-) -> Result<ModelDownloadState, String> {
+) -> AppResult<ModelDownloadState> {
     let download_state = state.model_installer.get_download_state();
 
     Ok(download_state)
@@ -407,7 +411,7 @@ pub async fn get_download_progress(
 
 /// Generate custom prompt from survey results
 #[tauri::command]
-pub async fn generate_custom_prompt(survey: SurveyResults) -> Result<String, String> {
+pub async fn generate_custom_prompt(survey: SurveyResults) -> AppResult<String> {
     log::info!("Generating custom prompt from survey results");
 
     let prompt = PromptCustomizerService::generate_custom_prompt(&survey)
@@ -421,7 +425,7 @@ pub async fn generate_custom_prompt(survey: SurveyResults) -> Result<String, Str
 pub async fn generate_model_config(
     survey: SurveyResults,
     model_name: String,
-) -> Result<ModelConfig, String> {
+) -> AppResult<ModelConfig> {
     log::info!("Generating model config for: {}", model_name);
 
     let config = PromptCustomizerService::generate_model_config(&survey, &model_name)
@@ -437,31 +441,32 @@ pub async fn save_onboarding_state(
     system_specs_json: String,
     recommended_model: String,
     selected_model: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     log::info!("Saving onboarding state to database");
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
-
-    let now = chrono::Utc::now().timestamp_millis();
-
-    conn.execute(
-        "INSERT OR REPLACE INTO onboarding_state (
-            id, completed, system_specs_json, recommended_model, selected_model, created_at
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        rusqlite::params![
-            1, // Only one onboarding state
-            false,
-            &system_specs_json,
-            &recommended_model,
-            &selected_model,
-            now,
-        ],
-    )
-    .map_err(|e| format!("Failed to save onboarding state: {}", e))?;
+    state.db.call(move |db| {
+        let conn = db.conn();
+
+        let now = chrono::Utc::now().timestamp_millis();
+
+        conn.execute(
+            "INSERT OR REPLACE INTO onboarding_state (
+                id, completed, system_specs_json, recommended_model, selected_model, created_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                1, // Only one onboarding state
+                false,
+                &system_specs_json,
+                &recommended_model,
+                &selected_model,
+                now,
+            ],
+        )
+        .map_err(|e| format!("Failed to save onboarding state: {}", e))?;
 
-    log::info!("Onboarding state saved successfully");
-    Ok(())
+        log::info!("Onboarding state saved successfully");
+        Ok(())
+    }).await
 }
 
 /// Save survey results and custom prompt
@@ -470,44 +475,46 @@ pub async fn save_survey_results(
     state: State<'_, AppState>,
     survey_json: String,
     custom_prompt: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     log::info!("Saving survey results and custom prompt");
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
+    state.db.call(move |db| {
+        let conn = db.conn();
 
-    conn.execute(
-        "UPDATE onboarding_state SET
-            survey_results_json = ?1,
-            custom_prompt = ?2
-         WHERE id = 1",
-        rusqlite::params![&survey_json, &custom_prompt],
-    )
-    .map_err(|e| format!("Failed to save survey results: {}", e))?;
+        conn.execute(
+            "UPDATE onboarding_state SET
+                survey_results_json = ?1,
+                custom_prompt = ?2
+             WHERE id = 1",
+            rusqlite::params![&survey_json, &custom_prompt],
+        )
+        .map_err(|e| format!("Failed to save survey results: {}", e))?;
 
-    log::info!("Survey results saved successfully");
-    Ok(())
+        log::info!("Survey results saved successfully");
+        Ok(())
+    }).await
 }
 
 /// Mark onboarding as completed
 #[tauri::command]
-pub async fn mark_onboarding_completed(state: State<'_, AppState>) -> Result<(), String> {
+pub async fn mark_onboarding_completed(state: State<'_, AppState>) -> AppResult<()> {
     log::info!("Marking onboarding as completed");
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
+    state.db.call(move |db| {
+        let conn = db.conn();
 
-    let now = chrono::Utc::now().timestamp_millis();
+        let now = chrono::Utc::now().timestamp_millis();
 
-    conn.execute(
-        "UPDATE onboarding_state SET
-            completed = 1,
-            completed_at = ?1
-         WHERE id = 1",
-        rusqlite::params![now],
-    )
-    .map_err(|e| format!("Failed to mark onboarding as completed: {}", e))?;
+        conn.execute(
+            "UPDATE onboarding_state SET
+                completed = 1,
+                completed_at = ?1
+             WHERE id = 1",
+            rusqlite::params![now],
+        )
+        .map_err(|e| format!("Failed to mark onboarding as completed: {}", e))?;
 
-    log::info!("Onboarding marked as completed");
-    Ok(())
+        log::info!("Onboarding marked as completed");
+        Ok(())
+    }).await
 }
@@ -0,0 +1,74 @@
+/**
+ * OpenAPI Tool Commands (v3.9.0)
+ *
+ * Importing HTTP APIs from OpenAPI / Swagger documents as agent tools
+ */
+
+use crate::services::degradation::ServiceSlot;
+use crate::services::openapi_tools::{ApiSpec, OpenApiToolService};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Import an API from its JSON OpenAPI / Swagger document
+///
+/// `base_url` overrides the document's servers; `credential` (bearer token,
+/// API key or "user:password") is stored in the secrets vault.
+#[tauri::command]
+pub async fn api_tools_import(
+    name: String,
+    document: String,
+    base_url: Option<String>,
+    credential: Option<String>,
+    service: State<'_, Arc<ServiceSlot<OpenApiToolService>>>,
+) -> AppResult<ApiSpec> {
+    let service_clone = service.get()?;
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .import(&name, &document, base_url.as_deref(), credential.as_deref())
+            .map_err(|e| format!("Failed to import API: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Imported APIs with their operations
+#[tauri::command]
+pub async fn api_tools_list(service: State<'_, Arc<ServiceSlot<OpenApiToolService>>>) -> AppResult<Vec<ApiSpec>> {
+    let service_clone = service.get()?;
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone.list().map_err(|e| format!("Failed to list APIs: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Remove an imported API, its tools and its credential
+#[tauri::command]
+pub async fn api_tools_remove(api_id: String, service: State<'_, Arc<ServiceSlot<OpenApiToolService>>>) -> AppResult<()> {
+    let service_clone = service.get()?;
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .remove(&api_id)
+            .map_err(|e| format!("Failed to remove API: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Replace an API's credential, or delete it with null
+#[tauri::command]
+pub async fn api_tools_set_credential(
+    api_id: String,
+    credential: Option<String>,
+    service: State<'_, Arc<ServiceSlot<OpenApiToolService>>>,
+) -> AppResult<()> {
+    let service_clone = service.get()?;
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .set_credential(&api_id, credential.as_deref())
+            .map_err(|e| format!("Failed to store API credential: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
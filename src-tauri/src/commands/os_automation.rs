@@ -0,0 +1,101 @@
+/**
+ * OS Automation Commands (v3.9.0)
+ *
+ * macOS Shortcuts, approved PowerShell scripts and their settings
+ */
+
+use crate::services::degradation::ServiceSlot;
+use crate::services::os_automation::{ApprovedScript, AutomationRunResult, OsAutomationConfig, OsAutomationService};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+#[tauri::command]
+pub async fn os_automation_get_config(service: State<'_, Arc<ServiceSlot<OsAutomationService>>>) -> AppResult<OsAutomationConfig> {
+    let service = service.get()?;
+    Ok(service.get_config())
+}
+
+/// Save Shortcut access and limits; approved scripts aren't changed here
+#[tauri::command]
+pub async fn os_automation_update_config(
+    config: OsAutomationConfig,
+    service: State<'_, Arc<ServiceSlot<OsAutomationService>>>,
+) -> AppResult<OsAutomationConfig> {
+    let service_clone = service.get()?;
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .update_config(config)
+            .map_err(|e| format!("Failed to update automation settings: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// The Shortcuts the agent may run
+#[tauri::command]
+pub async fn os_automation_list_shortcuts(service: State<'_, Arc<ServiceSlot<OsAutomationService>>>) -> AppResult<Vec<String>> {
+    let service = service.get()?;
+    Ok(service
+        .list_shortcuts()
+        .await
+        .map_err(|e| format!("Failed to list Shortcuts: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn os_automation_run_shortcut(
+    name: String,
+    input: Option<String>,
+    service: State<'_, Arc<ServiceSlot<OsAutomationService>>>,
+) -> AppResult<AutomationRunResult> {
+    let service = service.get()?;
+    Ok(service
+        .run_shortcut(&name, input.as_deref())
+        .await
+        .map_err(|e| format!("Failed to run Shortcut: {}", e))?)
+}
+
+/// Approve a PowerShell script (again, after it was edited)
+#[tauri::command]
+pub async fn os_automation_approve_script(
+    name: String,
+    path: String,
+    description: Option<String>,
+    parameters: Option<Vec<String>>,
+    service: State<'_, Arc<ServiceSlot<OsAutomationService>>>,
+) -> AppResult<ApprovedScript> {
+    let service_clone = service.get()?;
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .approve_script(&name, &path, &description.unwrap_or_default(), parameters.unwrap_or_default())
+            .map_err(|e| format!("Failed to approve script: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn os_automation_remove_script(name: String, service: State<'_, Arc<ServiceSlot<OsAutomationService>>>) -> AppResult<bool> {
+    let service_clone = service.get()?;
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .remove_script(&name)
+            .map_err(|e| format!("Failed to remove script: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Run an approved script with `{ "Name": value }` parameters
+#[tauri::command]
+pub async fn os_automation_run_script(
+    name: String,
+    parameters: Option<serde_json::Map<String, serde_json::Value>>,
+    service: State<'_, Arc<ServiceSlot<OsAutomationService>>>,
+) -> AppResult<AutomationRunResult> {
+    let service = service.get()?;
+    Ok(service
+        .run_script(&name, &parameters.unwrap_or_default())
+        .await
+        .map_err(|e| format!("Failed to run script: {}", e))?)
+}
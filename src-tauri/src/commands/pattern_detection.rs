@@ -5,6 +5,7 @@
  */
 
 use crate::services::pattern_detector::{LlmPatternDetector, TraitAnalysis};
+use crate::AppResult;
 use std::sync::Arc;
 use tauri::State;
 
@@ -13,11 +14,11 @@ use tauri::State;
 pub async fn pattern_analyze_traits(
     text: String,
     service: State<'_, Arc<LlmPatternDetector>>,
-) -> Result<TraitAnalysis, String> {
-    service
+) -> AppResult<TraitAnalysis> {
+    Ok(service
         .analyze_traits(&text)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
 
 /// Analyze single trait
@@ -26,9 +27,9 @@ pub async fn pattern_analyze_single_trait(
     text: String,
     trait_name: String,
     service: State<'_, Arc<LlmPatternDetector>>,
-) -> Result<f32, String> {
-    service
+) -> AppResult<f32> {
+    Ok(service
         .analyze_single_trait(&text, &trait_name)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
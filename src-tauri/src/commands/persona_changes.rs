@@ -0,0 +1,37 @@
+/**
+ * Persona Change Log Commands (v3.9.0)
+ *
+ * Explain persona changes and roll back to earlier snapshots
+ */
+
+use crate::services::persona_changes::{self, PersonaChangeEntry};
+use crate::AppResult;
+use crate::AppState;
+use tauri::State;
+
+/// Persona changes with explanations, newest first
+#[tauri::command]
+pub async fn persona_get_change_log(
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<PersonaChangeEntry>> {
+    state.db.call(move |db| {
+        Ok(persona_changes::change_log(db, limit.unwrap_or(50))
+            .map_err(|e| format!("Failed to load persona change log: {}", e))?)
+    }).await
+}
+
+/// Restore the persona after `change_id` (or before it with `before`)
+#[tauri::command]
+pub async fn persona_rollback_to(
+    change_id: String,
+    before: Option<bool>,
+    state: State<'_, AppState>,
+) -> AppResult<PersonaChangeEntry> {
+    log::info!("Command: persona_rollback_to - {}", change_id);
+
+    state.db.call(move |db| {
+        Ok(persona_changes::rollback_to(db, &change_id, before.unwrap_or(false))
+            .map_err(|e| format!("Failed to roll back persona: {}", e))?)
+    }).await
+}
@@ -0,0 +1,67 @@
+/**
+ * Persona Preset Commands (v3.9.0)
+ *
+ * Save, apply and list named persona profiles
+ */
+
+use crate::database::models::PersonaParameters;
+use crate::services::degradation::ServiceSlot;
+use crate::services::persona_presets::{PersonaPreset, PersonaPresetService};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Save a preset; snapshots the current persona when `parameters` is omitted
+#[tauri::command]
+pub async fn persona_save_preset(
+    name: String,
+    description: Option<String>,
+    parameters: Option<PersonaParameters>,
+    custom_instructions: Option<String>,
+    service: State<'_, Arc<ServiceSlot<PersonaPresetService>>>,
+) -> AppResult<PersonaPreset> {
+    let service = service.get()?;
+    log::info!("Command: persona_save_preset - {}", name);
+
+    Ok(service.save(
+        &name,
+        description.as_deref(),
+        parameters,
+        custom_instructions.as_deref().unwrap_or(""),
+    )
+    .map_err(|e| format!("Failed to save persona preset: {}", e))?)
+}
+
+/// Apply a preset (by id or name) to the chat persona
+#[tauri::command]
+pub async fn persona_apply_preset(
+    preset: String,
+    service: State<'_, Arc<ServiceSlot<PersonaPresetService>>>,
+) -> AppResult<PersonaPreset> {
+    let service = service.get()?;
+    log::info!("Command: persona_apply_preset - {}", preset);
+
+    Ok(service.apply(&preset)
+        .map_err(|e| format!("Failed to apply persona preset: {}", e))?)
+}
+
+/// List built-in and custom presets
+#[tauri::command]
+pub async fn persona_list_presets(
+    service: State<'_, Arc<ServiceSlot<PersonaPresetService>>>,
+) -> AppResult<Vec<PersonaPreset>> {
+    let service = service.get()?;
+    Ok(service.list()
+        .map_err(|e| format!("Failed to list persona presets: {}", e))?)
+}
+
+/// Delete a custom preset
+#[tauri::command]
+pub async fn persona_delete_preset(
+    preset: String,
+    service: State<'_, Arc<ServiceSlot<PersonaPresetService>>>,
+) -> AppResult<bool> {
+    let service = service.get()?;
+    Ok(service.delete(&preset)
+        .map_err(|e| format!("Failed to delete persona preset: {}", e))?)
+}
@@ -5,6 +5,8 @@
  */
 
 use crate::services::planner::Plan;
+use crate::AppError;
+use crate::AppResult;
 use crate::AppState;
 use log::info;
 use tauri::{command, State};
@@ -14,7 +16,7 @@ use tauri::{command, State};
 pub async fn planner_generate(
     state: State<'_, AppState>,
     goal: String,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: planner_generate");
 
     let planner = &*state.planner;
@@ -28,7 +30,7 @@ pub async fn planner_generate(
 pub async fn planner_approve(
     state: State<'_, AppState>,
     plan_json: String,
-) -> Result<String, String> {
+) -> AppResult<String> {
     info!("Command: planner_approve");
 
     let mut plan: Plan =
@@ -48,14 +50,14 @@ pub async fn planner_approve(
 pub async fn planner_execute(
     state: State<'_, AppState>,
     plan_id: String,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: planner_execute for plan: {}", plan_id);
 
     // Retrieve approved plan
     let mut approved_plans = state.approved_plans.lock().await;
     let mut plan = approved_plans
         .remove(&plan_id)
-        .ok_or_else(|| format!("Plan {} not found or not approved", plan_id))?;
+        .ok_or_else(|| AppError::not_found(format!("Plan {} not found or not approved", plan_id)))?;
 
     // Execute plan
     let planner = &*state.planner;
@@ -73,7 +75,7 @@ pub async fn planner_execute(
 pub async fn planner_reject(
     state: State<'_, AppState>,
     plan_id: String,
-) -> Result<String, String> {
+) -> AppResult<String> {
     info!("Command: planner_reject for plan: {}", plan_id);
 
     let mut approved_plans = state.approved_plans.lock().await;
@@ -87,7 +89,7 @@ pub async fn planner_reject(
 pub async fn planner_get_plan(
     state: State<'_, AppState>,
     plan_id: String,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: planner_get_plan for: {}", plan_id);
 
     // Check approved plans first
@@ -102,14 +104,14 @@ pub async fn planner_get_plan(
         return Ok(serde_json::to_value(plan).map_err(|e| e.to_string())?);
     }
 
-    Err(format!("Plan {} not found", plan_id))
+    Err(AppError::not_found(format!("Plan {} not found", plan_id)))
 }
 
 /// List all approved plans
 #[command]
 pub async fn planner_list_approved(
     state: State<'_, AppState>,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: planner_list_approved");
 
     let approved_plans = state.approved_plans.lock().await;
@@ -126,7 +128,7 @@ pub async fn planner_list_approved(
 pub async fn planner_list_history(
     state: State<'_, AppState>,
     limit: Option<usize>,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: planner_list_history");
 
     let plan_history = state.plan_history.lock().await;
@@ -160,7 +162,7 @@ pub async fn planner_list_history(
 
 /// Clear plan history
 #[command]
-pub async fn planner_clear_history(state: State<'_, AppState>) -> Result<String, String> {
+pub async fn planner_clear_history(state: State<'_, AppState>) -> AppResult<String> {
     info!("Command: planner_clear_history");
 
     let mut plan_history = state.plan_history.lock().await;
@@ -172,7 +174,7 @@ pub async fn planner_clear_history(state: State<'_, AppState>) -> Result<String,
 
 /// Get planner configuration
 #[command]
-pub fn planner_get_config(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+pub fn planner_get_config(state: State<'_, AppState>) -> AppResult<serde_json::Value> {
     info!("Command: planner_get_config");
 
     let planner = &*state.planner;
@@ -196,7 +198,7 @@ pub fn planner_set_config(
     max_steps: Option<usize>,
     enable_auto_recovery: Option<bool>,
     max_retry_attempts: Option<usize>,
-) -> Result<String, String> {
+) -> AppResult<String> {
     info!("Command: planner_set_config");
 
     let planner = &*state.planner;
@@ -231,16 +233,14 @@ pub async fn planner_generate_and_execute(
     state: State<'_, AppState>,
     goal: String,
     auto_approve: bool,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: planner_generate_and_execute");
 
     let planner = &*state.planner;
     let mut plan = planner.generate_plan(&goal).await?;
 
     if !auto_approve {
-        return Err(
-            "Plan generated but not approved. Use planner_approve to approve it.".to_string(),
-        );
+        return Err("Plan generated but not approved. Use planner_approve to approve it.".into());
     }
 
     // Auto-approve for testing/automation
@@ -257,7 +257,7 @@ pub async fn planner_generate_and_execute(
 
 /// Get statistics about planning
 #[command]
-pub async fn planner_stats(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+pub async fn planner_stats(state: State<'_, AppState>) -> AppResult<serde_json::Value> {
     info!("Command: planner_stats");
 
     let approved_plans = state.approved_plans.lock().await;
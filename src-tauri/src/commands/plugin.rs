@@ -6,9 +6,13 @@
  * - Load, unload, enable, and disable plugins
  * - Execute plugin functions
  * - Manage plugin permissions
+ * - Structured LLM output for plugins with the `llm` permission (v3.9.0)
  */
 
 use crate::services::plugin::{PluginManifest, PluginService, PluginResult, Permission};
+use crate::services::structured_output::{self, StructuredOptions, StructuredOutput};
+use crate::AppError;
+use crate::AppResult;
 use log::info;
 use std::sync::{Arc, Mutex};
 use std::path::PathBuf;
@@ -23,21 +27,21 @@ pub struct PluginState {
 #[command]
 pub async fn plugin_discover(
     state: State<'_, PluginState>,
-) -> Result<Vec<PluginManifest>, String> {
+) -> AppResult<Vec<PluginManifest>> {
     info!("Command: plugin_discover");
 
     let mut service = state.service.lock()
         .map_err(|e| format!("Failed to lock plugin service: {}", e))?;
 
-    service.discover_plugins()
-        .map_err(|e| format!("Failed to discover plugins: {}", e))
+    Ok(service.discover_plugins()
+        .map_err(|e| format!("Failed to discover plugins: {}", e))?)
 }
 
 /// List loaded plugins
 #[command]
 pub async fn plugin_list(
     state: State<'_, PluginState>,
-) -> Result<Vec<PluginManifest>, String> {
+) -> AppResult<Vec<PluginManifest>> {
     info!("Command: plugin_list");
 
     let service = state.service.lock()
@@ -51,15 +55,15 @@ pub async fn plugin_list(
 pub async fn plugin_load(
     state: State<'_, PluginState>,
     plugin_id: String,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     info!("Command: plugin_load - {}", plugin_id);
 
     let mut service = state.service.lock()
         .map_err(|e| format!("Failed to lock plugin service: {}", e))?;
 
-    service.load_plugin(&plugin_id)
+    Ok(service.load_plugin(&plugin_id)
         .map(|_| true)
-        .map_err(|e| format!("Failed to load plugin: {}", e))
+        .map_err(|e| format!("Failed to load plugin: {}", e))?)
 }
 
 /// Unload a plugin by ID
@@ -67,15 +71,15 @@ pub async fn plugin_load(
 pub async fn plugin_unload(
     state: State<'_, PluginState>,
     plugin_id: String,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     info!("Command: plugin_unload - {}", plugin_id);
 
     let mut service = state.service.lock()
         .map_err(|e| format!("Failed to lock plugin service: {}", e))?;
 
-    service.unload_plugin(&plugin_id)
+    Ok(service.unload_plugin(&plugin_id)
         .map(|_| true)
-        .map_err(|e| format!("Failed to unload plugin: {}", e))
+        .map_err(|e| format!("Failed to unload plugin: {}", e))?)
 }
 
 /// Enable a plugin
@@ -83,15 +87,15 @@ pub async fn plugin_unload(
 pub async fn plugin_enable(
     state: State<'_, PluginState>,
     plugin_id: String,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     info!("Command: plugin_enable - {}", plugin_id);
 
     let mut service = state.service.lock()
         .map_err(|e| format!("Failed to lock plugin service: {}", e))?;
 
-    service.enable_plugin(&plugin_id)
+    Ok(service.enable_plugin(&plugin_id)
         .map(|_| true)
-        .map_err(|e| format!("Failed to enable plugin: {}", e))
+        .map_err(|e| format!("Failed to enable plugin: {}", e))?)
 }
 
 /// Disable a plugin
@@ -99,15 +103,15 @@ pub async fn plugin_enable(
 pub async fn plugin_disable(
     state: State<'_, PluginState>,
     plugin_id: String,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     info!("Command: plugin_disable - {}", plugin_id);
 
     let mut service = state.service.lock()
         .map_err(|e| format!("Failed to lock plugin service: {}", e))?;
 
-    service.disable_plugin(&plugin_id)
+    Ok(service.disable_plugin(&plugin_id)
         .map(|_| true)
-        .map_err(|e| format!("Failed to disable plugin: {}", e))
+        .map_err(|e| format!("Failed to disable plugin: {}", e))?)
 }
 
 /// Execute a plugin function
@@ -117,14 +121,14 @@ pub async fn plugin_execute(
     plugin_id: String,
     function_name: String,
     args: Vec<serde_json::Value>,
-) -> Result<PluginResult, String> {
+) -> AppResult<PluginResult> {
     info!("Command: plugin_execute - {}:{}", plugin_id, function_name);
 
     let mut service = state.service.lock()
         .map_err(|e| format!("Failed to lock plugin service: {}", e))?;
 
-    service.execute_plugin(&plugin_id, &function_name, args)
-        .map_err(|e| format!("Failed to execute plugin function: {}", e))
+    Ok(service.execute_plugin(&plugin_id, &function_name, args)
+        .map_err(|e| format!("Failed to execute plugin function: {}", e))?)
 }
 
 /// Install a plugin from a path
@@ -132,15 +136,15 @@ pub async fn plugin_execute(
 pub async fn plugin_install(
     state: State<'_, PluginState>,
     source_path: String,
-) -> Result<String, String> {
+) -> AppResult<String> {
     info!("Command: plugin_install - {}", source_path);
 
     let mut service = state.service.lock()
         .map_err(|e| format!("Failed to lock plugin service: {}", e))?;
 
     let path = PathBuf::from(&source_path);
-    service.install_plugin(&path)
-        .map_err(|e| format!("Failed to install plugin: {}", e))
+    Ok(service.install_plugin(&path)
+        .map_err(|e| format!("Failed to install plugin: {}", e))?)
 }
 
 /// Uninstall a plugin
@@ -148,15 +152,15 @@ pub async fn plugin_install(
 pub async fn plugin_uninstall(
     state: State<'_, PluginState>,
     plugin_id: String,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     info!("Command: plugin_uninstall - {}", plugin_id);
 
     let mut service = state.service.lock()
         .map_err(|e| format!("Failed to lock plugin service: {}", e))?;
 
-    service.uninstall_plugin(&plugin_id)
+    Ok(service.uninstall_plugin(&plugin_id)
         .map(|_| true)
-        .map_err(|e| format!("Failed to uninstall plugin: {}", e))
+        .map_err(|e| format!("Failed to uninstall plugin: {}", e))?)
 }
 
 /// Check if a plugin has a specific permission
@@ -165,7 +169,7 @@ pub async fn plugin_has_permission(
     state: State<'_, PluginState>,
     plugin_id: String,
     permission: String,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     info!("Command: plugin_has_permission - {}:{}", plugin_id, permission);
 
     let service = state.service.lock()
@@ -179,17 +183,44 @@ pub async fn plugin_has_permission(
         "notification" => Permission::Notification,
         "clipboard" => Permission::Clipboard,
         "shell" => Permission::Shell,
-        _ => return Err(format!("Unknown permission: {}", permission)),
+        "llm" => Permission::Llm,
+        _ => return Err(AppError::invalid_input(format!("Unknown permission: {}", permission))),
     };
 
     Ok(service.has_permission(&plugin_id, &perm))
 }
 
+/// Generate JSON matching `schema` on behalf of a plugin (v3.9.0)
+///
+/// Requires the `llm` permission in the plugin's manifest.
+#[command]
+pub async fn plugin_chat_structured(
+    state: State<'_, PluginState>,
+    plugin_id: String,
+    prompt: String,
+    schema: serde_json::Value,
+    options: Option<StructuredOptions>,
+) -> AppResult<StructuredOutput> {
+    info!("Command: plugin_chat_structured - {}", plugin_id);
+
+    {
+        let service = state.service.lock()
+            .map_err(|e| format!("Failed to lock plugin service: {}", e))?;
+        if !service.has_permission(&plugin_id, &Permission::Llm) {
+            return Err(format!("Plugin {} does not have the llm permission", plugin_id).into());
+        }
+    }
+
+    Ok(structured_output::generate(&prompt, &schema, &options.unwrap_or_default())
+        .await
+        .map_err(|e| format!("Structured output failed: {}", e))?)
+}
+
 /// Get plugins directory path
 #[command]
 pub async fn plugin_get_directory(
     state: State<'_, PluginState>,
-) -> Result<String, String> {
+) -> AppResult<String> {
     info!("Command: plugin_get_directory");
 
     let service = state.service.lock()
@@ -202,7 +233,7 @@ pub async fn plugin_get_directory(
 #[command]
 pub async fn plugin_get_stats(
     state: State<'_, PluginState>,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: plugin_get_stats");
 
     let mut service = state.service.lock()
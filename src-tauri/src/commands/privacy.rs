@@ -0,0 +1,48 @@
+/**
+ * Privacy Commands (v3.9.0)
+ *
+ * Export and forget everything stored about a topic
+ */
+
+use crate::services::privacy::{ForgetReport, PrivacyService, TopicExport};
+use crate::{AppResult, AppState};
+use std::sync::Arc;
+use tauri::State;
+
+/// Export everything stored about a topic (episodes, wiki facts, graph entities)
+#[tauri::command]
+pub async fn memory_export_topic(
+    query: String,
+    service: State<'_, Arc<PrivacyService>>,
+) -> AppResult<TopicExport> {
+    let service = Arc::clone(&service);
+    Ok(tokio::task::spawn_blocking(move || service.export_topic(&query))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to export topic: {}", e))?)
+}
+
+/// Delete everything stored about a topic across all memory stores
+///
+/// The report carries an export of the deleted data.
+#[tauri::command]
+pub async fn memory_forget_topic(
+    query: String,
+    service: State<'_, Arc<PrivacyService>>,
+    state: State<'_, AppState>,
+) -> AppResult<ForgetReport> {
+    log::info!("Forget topic requested");
+
+    let report = service.forget_topic(&query)
+        .await
+        .map_err(|e| format!("Failed to forget topic: {}", e))?;
+
+    // Drop the deleted episodes from the in-memory BM25 index right away
+    #[cfg(feature = "lancedb-support")]
+    {
+        let engine = Arc::clone(&state.hybrid_search);
+        state.db.call(move |db| engine.blocking_lock().sync_index(db.conn())).await?;
+    }
+
+    Ok(report)
+}
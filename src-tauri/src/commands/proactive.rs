@@ -5,11 +5,17 @@
  * - Start/stop background screen monitoring
  * - Configure trigger thresholds and intervals
  * - Get proactive suggestions
+ * - Context-driven suggestion engine and accept/dismiss feedback (v3.9.0)
  */
 
+use crate::AppResult;
 use crate::AppState;
+use crate::services::proactive_engine::{
+    ProactiveEngineConfig, ProactiveEngineStats, ProactiveSuggestionEngine, SuggestionFeedback,
+};
 use crate::services::proactive_manager::{ProactiveConfig, ProactiveSuggestion};
 use log::info;
+use std::sync::Arc;
 use tauri::{command, State, AppHandle, Emitter};
 
 /// Start proactive monitoring
@@ -17,7 +23,7 @@ use tauri::{command, State, AppHandle, Emitter};
 pub async fn proactive_start(
     state: State<'_, AppState>,
     app_handle: AppHandle,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     info!("Command: proactive_start");
 
     let manager = state.proactive_manager.lock().await;
@@ -25,22 +31,22 @@ pub async fn proactive_start(
     // Set app_handle for event emission
     manager.set_app_handle(app_handle).await;
 
-    manager.start().await
+    Ok(manager.start().await
         .map(|_| true)
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
 
 /// Stop proactive monitoring
 #[command]
 pub async fn proactive_stop(
     state: State<'_, AppState>,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     info!("Command: proactive_stop");
 
     let manager = state.proactive_manager.lock().await;
-    manager.stop()
+    Ok(manager.stop()
         .map(|_| false)
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
 
 /// Toggle proactive monitoring
@@ -48,7 +54,7 @@ pub async fn proactive_stop(
 pub async fn proactive_toggle(
     state: State<'_, AppState>,
     app_handle: AppHandle,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     info!("Command: proactive_toggle");
 
     let manager = state.proactive_manager.lock().await;
@@ -56,15 +62,15 @@ pub async fn proactive_toggle(
     // Set app_handle for event emission
     manager.set_app_handle(app_handle).await;
 
-    manager.toggle().await
-        .map_err(|e| e.to_string())
+    Ok(manager.toggle().await
+        .map_err(|e| e.to_string())?)
 }
 
 /// Get proactive monitoring status
 #[command]
 pub async fn proactive_status(
     state: State<'_, AppState>,
-) -> Result<serde_json::Value, String> {
+) -> AppResult<serde_json::Value> {
     info!("Command: proactive_status");
 
     let manager = state.proactive_manager.lock().await;
@@ -82,20 +88,20 @@ pub async fn proactive_status(
 pub async fn proactive_update_config(
     state: State<'_, AppState>,
     config: ProactiveConfig,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     info!("Command: proactive_update_config");
 
     let manager = state.proactive_manager.lock().await;
-    manager.update_config(config)
+    Ok(manager.update_config(config)
         .map(|_| true)
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
 
 /// Get current proactive configuration
 #[command]
 pub async fn proactive_get_config(
     state: State<'_, AppState>,
-) -> Result<ProactiveConfig, String> {
+) -> AppResult<ProactiveConfig> {
     info!("Command: proactive_get_config");
 
     let manager = state.proactive_manager.lock().await;
@@ -107,11 +113,14 @@ pub async fn proactive_get_config(
 pub async fn proactive_dismiss_suggestion(
     _state: State<'_, AppState>,
     suggestion_id: String,
-) -> Result<bool, String> {
+    engine: State<'_, Arc<ProactiveSuggestionEngine>>,
+) -> AppResult<bool> {
     info!("Command: proactive_dismiss_suggestion - {}", suggestion_id);
 
-    // For now, just acknowledge the dismissal
-    // In the future, this could update analytics or learn from user preferences
+    // Dismissals lower proactiveness (v3.9.0)
+    engine
+        .record_feedback(&suggestion_id, SuggestionFeedback::Dismissed)
+        .map_err(|e| format!("Failed to record feedback: {}", e))?;
     Ok(true)
 }
 
@@ -120,9 +129,14 @@ pub async fn proactive_dismiss_suggestion(
 pub async fn proactive_accept_suggestion(
     _state: State<'_, AppState>,
     suggestion_id: String,
-) -> Result<serde_json::Value, String> {
+    engine: State<'_, Arc<ProactiveSuggestionEngine>>,
+) -> AppResult<serde_json::Value> {
     info!("Command: proactive_accept_suggestion - {}", suggestion_id);
 
+    engine
+        .record_feedback(&suggestion_id, SuggestionFeedback::Accepted)
+        .map_err(|e| format!("Failed to record feedback: {}", e))?;
+
     // Return the suggestion details for the frontend to handle
     // The frontend will then initiate the appropriate action
     Ok(serde_json::json!({
@@ -132,6 +146,72 @@ pub async fn proactive_accept_suggestion(
     }))
 }
 
+/// Start the context-driven suggestion engine (v3.9.0)
+#[command]
+pub async fn proactive_engine_start(
+    engine: State<'_, Arc<ProactiveSuggestionEngine>>,
+    app_handle: AppHandle,
+) -> AppResult<bool> {
+    info!("Command: proactive_engine_start");
+
+    engine.set_app_handle(app_handle).await;
+    engine.start();
+    Ok(engine.is_running())
+}
+
+/// Stop the context-driven suggestion engine (v3.9.0)
+#[command]
+pub async fn proactive_engine_stop(
+    engine: State<'_, Arc<ProactiveSuggestionEngine>>,
+) -> AppResult<bool> {
+    info!("Command: proactive_engine_stop");
+
+    engine.stop();
+    Ok(false)
+}
+
+/// Run one suggestion cycle immediately (v3.9.0)
+#[command]
+pub async fn proactive_engine_run_now(
+    engine: State<'_, Arc<ProactiveSuggestionEngine>>,
+    app_handle: AppHandle,
+) -> AppResult<Vec<ProactiveSuggestion>> {
+    info!("Command: proactive_engine_run_now");
+
+    engine.set_app_handle(app_handle).await;
+    Ok(engine.run_cycle().await
+        .map_err(|e| format!("Failed to generate suggestions: {}", e))?)
+}
+
+/// Get suggestion engine configuration (v3.9.0)
+#[command]
+pub async fn proactive_engine_get_config(
+    engine: State<'_, Arc<ProactiveSuggestionEngine>>,
+) -> AppResult<ProactiveEngineConfig> {
+    Ok(engine.get_config())
+}
+
+/// Update suggestion engine configuration (v3.9.0)
+#[command]
+pub async fn proactive_engine_update_config(
+    config: ProactiveEngineConfig,
+    engine: State<'_, Arc<ProactiveSuggestionEngine>>,
+) -> AppResult<bool> {
+    info!("Command: proactive_engine_update_config");
+
+    engine.update_config(config);
+    Ok(true)
+}
+
+/// Get suggestion engine statistics (budget, acceptance rate) (v3.9.0)
+#[command]
+pub async fn proactive_engine_get_stats(
+    engine: State<'_, Arc<ProactiveSuggestionEngine>>,
+) -> AppResult<ProactiveEngineStats> {
+    Ok(engine.get_stats()
+        .map_err(|e| format!("Failed to get proactive stats: {}", e))?)
+}
+
 /// Emit a proactive suggestion event to the frontend
 /// This is called internally by the ProactiveManager
 #[allow(dead_code)]
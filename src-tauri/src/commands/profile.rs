@@ -0,0 +1,121 @@
+/**
+ * Profile Commands (v3.9.0)
+ *
+ * Create, rename, delete, and switch isolated workspaces without restarting
+ */
+
+use crate::database::Database;
+use crate::services::profile::{Profile, ProfilePaths, ProfileService};
+use crate::AppResult;
+use crate::AppState;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+
+/// List all profiles
+#[tauri::command]
+pub async fn profile_list(
+    service: State<'_, Arc<ProfileService>>,
+) -> AppResult<Vec<Profile>> {
+    Ok(service.list_profiles()
+        .map_err(|e| format!("Failed to list profiles: {}", e))?)
+}
+
+/// Get the active profile
+#[tauri::command]
+pub async fn profile_get_active(
+    service: State<'_, Arc<ProfileService>>,
+) -> AppResult<Profile> {
+    Ok(service.active_profile()
+        .map_err(|e| format!("Failed to get active profile: {}", e))?)
+}
+
+/// Create a new profile (stays on the current one)
+#[tauri::command]
+pub async fn profile_create(
+    name: String,
+    service: State<'_, Arc<ProfileService>>,
+) -> AppResult<Profile> {
+    log::info!("Creating profile: {}", name);
+
+    let service = Arc::clone(&service);
+    Ok(tokio::task::spawn_blocking(move || service.create_profile(&name))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to create profile: {}", e))?)
+}
+
+/// Rename a profile
+#[tauri::command]
+pub async fn profile_rename(
+    profile_id: String,
+    name: String,
+    service: State<'_, Arc<ProfileService>>,
+) -> AppResult<Profile> {
+    Ok(service.rename_profile(&profile_id, &name)
+        .map_err(|e| format!("Failed to rename profile: {}", e))?)
+}
+
+/// Delete a profile and its data (must not be active)
+#[tauri::command]
+pub async fn profile_delete(
+    profile_id: String,
+    service: State<'_, Arc<ProfileService>>,
+) -> AppResult<()> {
+    log::info!("Deleting profile: {}", profile_id);
+
+    Ok(service.delete_profile(&profile_id)
+        .map_err(|e| format!("Failed to delete profile: {}", e))?)
+}
+
+/// Switch to another profile and re-bind AppState services to its stores
+#[tauri::command]
+pub async fn profile_switch(
+    profile_id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    service: State<'_, Arc<ProfileService>>,
+) -> AppResult<Profile> {
+    log::info!("Switching to profile: {}", profile_id);
+
+    // Shared database handle (used by most services) + raw connections
+    let paths = service.switch_profile(&profile_id)
+        .map_err(|e| format!("Failed to switch profile: {}", e))?;
+
+    rebind_app_state(&state, &paths).await?;
+
+    let profile = service.active_profile()
+        .map_err(|e| format!("Failed to get active profile: {}", e))?;
+    if let Err(e) = app.emit("profile-switched", &profile) {
+        log::warn!("Failed to emit profile-switched: {}", e);
+    }
+
+    log::info!("✓ Switched to profile '{}'", profile.name);
+    Ok(profile)
+}
+
+/// Point AppState's own stores at a profile's files
+///
+/// Shared with encryption unlock, which swaps the locked placeholders for the real stores.
+pub(crate) async fn rebind_app_state(state: &AppState, paths: &ProfilePaths) -> Result<(), String> {
+    // AppState's own database handle (chat, conversations, persona)
+    let db_path = paths.db.clone();
+    state.db.call_mut(move |db| {
+        *db = Database::open(&db_path)
+            .map_err(|e| format!("Failed to open profile database: {}", e))?;
+        Ok::<_, String>(())
+    }).await?;
+
+    // Episodic memory and knowledge graph
+    state.rag.rebind(paths.lance_db.clone()).await
+        .map_err(|e| format!("Failed to switch RAG store: {}", e))?;
+    state.graph_storage
+        .reopen(&paths.knowledge_graph.to_string_lossy())
+        .map_err(|e| format!("Failed to switch knowledge graph: {}", e))?;
+
+    // Cached prompts belong to the previous persona
+    if let Ok(cache) = state.prompt_cache.lock() {
+        cache.clear_all();
+    }
+
+    Ok(())
+}
@@ -1,17 +1,18 @@
+use crate::AppResult;
 use crate::AppState;
 use log::info;
 use tauri::{command, State};
 
 /// Check if prompt is in cache
 #[command]
-pub fn prompt_cache_contains(state: State<'_, AppState>, prompt: String) -> Result<bool, String> {
+pub fn prompt_cache_contains(state: State<'_, AppState>, prompt: String) -> AppResult<bool> {
     let cache = state.prompt_cache.lock().unwrap();
     Ok(cache.contains(&prompt))
 }
 
 /// Get cached prompt
 #[command]
-pub fn prompt_cache_get(state: State<'_, AppState>, prompt: String) -> Result<serde_json::Value, String> {
+pub fn prompt_cache_get(state: State<'_, AppState>, prompt: String) -> AppResult<serde_json::Value> {
     let cache = state.prompt_cache.lock().unwrap();
 
     match cache.get(&prompt) {
@@ -31,7 +32,7 @@ pub fn prompt_cache_get(state: State<'_, AppState>, prompt: String) -> Result<se
 
 /// Put prompt in cache
 #[command]
-pub fn prompt_cache_put(state: State<'_, AppState>, prompt: String) -> Result<String, String> {
+pub fn prompt_cache_put(state: State<'_, AppState>, prompt: String) -> AppResult<String> {
     info!("Command: prompt_cache_put");
     let cache = state.prompt_cache.lock().unwrap();
     let hash = cache.put(&prompt);
@@ -40,7 +41,7 @@ pub fn prompt_cache_put(state: State<'_, AppState>, prompt: String) -> Result<St
 
 /// Clear expired cache entries
 #[command]
-pub fn prompt_cache_clear_expired(state: State<'_, AppState>) -> Result<usize, String> {
+pub fn prompt_cache_clear_expired(state: State<'_, AppState>) -> AppResult<usize> {
     info!("Command: prompt_cache_clear_expired");
     let cache = state.prompt_cache.lock().unwrap();
     let removed = cache.clear_expired();
@@ -49,7 +50,7 @@ pub fn prompt_cache_clear_expired(state: State<'_, AppState>) -> Result<usize, S
 
 /// Clear all cache entries
 #[command]
-pub fn prompt_cache_clear_all(state: State<'_, AppState>) -> Result<(), String> {
+pub fn prompt_cache_clear_all(state: State<'_, AppState>) -> AppResult<()> {
     info!("Command: prompt_cache_clear_all");
     let cache = state.prompt_cache.lock().unwrap();
     cache.clear_all();
@@ -58,7 +59,7 @@ pub fn prompt_cache_clear_all(state: State<'_, AppState>) -> Result<(), String>
 
 /// Get cache statistics
 #[command]
-pub fn prompt_cache_stats(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+pub fn prompt_cache_stats(state: State<'_, AppState>) -> AppResult<serde_json::Value> {
     info!("Command: prompt_cache_stats");
     let cache = state.prompt_cache.lock().unwrap();
     let stats = cache.stats();
@@ -75,14 +76,14 @@ pub fn prompt_cache_stats(state: State<'_, AppState>) -> Result<serde_json::Valu
 
 /// Get cache hit rate
 #[command]
-pub fn prompt_cache_hit_rate(state: State<'_, AppState>) -> Result<f32, String> {
+pub fn prompt_cache_hit_rate(state: State<'_, AppState>) -> AppResult<f32> {
     let cache = state.prompt_cache.lock().unwrap();
     Ok(cache.hit_rate())
 }
 
 /// Get all cached entries (for debugging)
 #[command]
-pub fn prompt_cache_get_all(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+pub fn prompt_cache_get_all(state: State<'_, AppState>) -> AppResult<serde_json::Value> {
     info!("Command: prompt_cache_get_all");
     let cache = state.prompt_cache.lock().unwrap();
     let entries = cache.get_all_entries();
@@ -109,7 +110,7 @@ pub fn prompt_cache_get_all(state: State<'_, AppState>) -> Result<serde_json::Va
 
 /// Get cache configuration
 #[command]
-pub fn prompt_cache_get_config(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+pub fn prompt_cache_get_config(state: State<'_, AppState>) -> AppResult<serde_json::Value> {
     info!("Command: prompt_cache_get_config");
     let cache = state.prompt_cache.lock().unwrap();
     let config = cache.config();
@@ -123,7 +124,7 @@ pub fn prompt_cache_get_config(state: State<'_, AppState>) -> Result<serde_json:
 
 /// Manually trigger LRU eviction
 #[command]
-pub fn prompt_cache_evict_lru(state: State<'_, AppState>) -> Result<(), String> {
+pub fn prompt_cache_evict_lru(state: State<'_, AppState>) -> AppResult<()> {
     info!("Command: prompt_cache_evict_lru");
     let cache = state.prompt_cache.lock().unwrap();
     cache.evict_lru();
@@ -0,0 +1,175 @@
+/**
+ * Prompt Template Commands (v3.9.0)
+ *
+ * Template library CRUD, variable resolution, and template hotkeys
+ */
+
+use crate::commands::quick_ask::refresh_hotkeys;
+use crate::services::prompt_templates::{
+    PromptTemplate, PromptTemplateInput, PromptTemplateService, RenderRequest, RenderedPrompt, TemplateCategory,
+};
+use crate::services::quick_ask::{normalize_accelerator, QuickAskService};
+use crate::AppResult;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+/// Emitted when a template hotkey fires; the chat input inserts `text`
+pub const TEMPLATE_INSERT_EVENT: &str = "prompt-template-insert";
+
+/// Resolve a template from its hotkey and hand it to the main window
+async fn insert_from_hotkey(app: AppHandle, template_id: String) -> Result<(), String> {
+    let templates = Arc::clone(app.state::<Arc<PromptTemplateService>>().inner());
+    let quick_ask = Arc::clone(app.state::<Arc<QuickAskService>>().inner());
+    let rendered = templates
+        .resolve(&template_id, RenderRequest::default(), &quick_ask)
+        .await
+        .map_err(|e| format!("Failed to resolve template: {}", e))?;
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    app.emit(TEMPLATE_INSERT_EVENT, rendered)
+        .map_err(|e| format!("Failed to emit {}: {}", TEMPLATE_INSERT_EVENT, e))
+}
+
+/// Bind template hotkeys, skipping ones already `taken`
+///
+/// Called from `quick_ask::register_hotkeys`, which clears all shortcuts first.
+pub fn register_template_hotkeys(app: &AppHandle, taken: &mut HashSet<String>) {
+    let Some(service) = app.try_state::<Arc<PromptTemplateService>>() else {
+        return;
+    };
+    let hotkeys = match service.hotkeys() {
+        Ok(hotkeys) => hotkeys,
+        Err(e) => {
+            log::warn!("Failed to load template hotkeys: {}", e);
+            return;
+        }
+    };
+
+    let shortcuts = app.global_shortcut();
+    for (template_id, accelerator) in hotkeys {
+        if taken.contains(&normalize_accelerator(&accelerator)) {
+            log::warn!("Template hotkey {} is already in use", accelerator);
+            continue;
+        }
+
+        let id = template_id.clone();
+        let result = shortcuts.on_shortcut(accelerator.as_str(), move |app, _shortcut, event| {
+            if event.state() != ShortcutState::Pressed {
+                return;
+            }
+            let (app, id) = (app.clone(), id.clone());
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = insert_from_hotkey(app, id).await {
+                    log::warn!("{}", e);
+                }
+            });
+        });
+
+        match result {
+            Ok(()) => {
+                taken.insert(normalize_accelerator(&accelerator));
+                log::info!("Registered template hotkey {} ({})", accelerator, template_id)
+            }
+            Err(e) => log::warn!("Failed to register hotkey {}: {}", accelerator, e),
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn prompt_template_list(
+    category: Option<String>,
+    service: State<'_, Arc<PromptTemplateService>>,
+) -> AppResult<Vec<PromptTemplate>> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .list(category.as_deref())
+            .map_err(|e| format!("Failed to list templates: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn prompt_template_get(
+    id: String,
+    service: State<'_, Arc<PromptTemplateService>>,
+) -> AppResult<PromptTemplate> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .get(&id)
+            .map_err(|e| format!("Failed to get template: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn prompt_template_categories(
+    service: State<'_, Arc<PromptTemplateService>>,
+) -> AppResult<Vec<TemplateCategory>> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .categories()
+            .map_err(|e| format!("Failed to list template categories: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Create or update a template and re-register hotkeys
+#[tauri::command]
+pub async fn prompt_template_save(
+    template: PromptTemplateInput,
+    app: AppHandle,
+    service: State<'_, Arc<PromptTemplateService>>,
+) -> AppResult<PromptTemplate> {
+    let service_clone = Arc::clone(&service.inner());
+    let saved = tokio::task::spawn_blocking(move || {
+        service_clone
+            .save(template)
+            .map_err(|e| format!("Failed to save template: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+    refresh_hotkeys(&app)?;
+    Ok(saved)
+}
+
+#[tauri::command]
+pub async fn prompt_template_delete(
+    id: String,
+    app: AppHandle,
+    service: State<'_, Arc<PromptTemplateService>>,
+) -> AppResult<()> {
+    let service_clone = Arc::clone(&service.inner());
+    tokio::task::spawn_blocking(move || {
+        service_clone
+            .delete(&id)
+            .map_err(|e| format!("Failed to delete template: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+    Ok(refresh_hotkeys(&app)?)
+}
+
+/// Fill in a template's variables for insertion into chat
+#[tauri::command]
+pub async fn prompt_template_render(
+    id: String,
+    request: Option<RenderRequest>,
+    service: State<'_, Arc<PromptTemplateService>>,
+    quick_ask: State<'_, Arc<QuickAskService>>,
+) -> AppResult<RenderedPrompt> {
+    Ok(service
+        .resolve(&id, request.unwrap_or_default(), &quick_ask)
+        .await
+        .map_err(|e| format!("Failed to render template: {}", e))?)
+}
@@ -0,0 +1,93 @@
+/**
+ * Quick Ask Commands (v3.9.0)
+ *
+ * Global hotkeys + a single-turn "quick ask" popup
+ */
+
+use crate::services::quick_ask::{normalize_accelerator, QuickAskConfig, QuickAskResult, QuickAskService, QuickAskSource};
+use crate::AppResult;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+/// (Re-)register the global shortcuts from the current quick ask config,
+/// then script and prompt template hotkeys (v3.9.0)
+pub fn register_hotkeys(app: &AppHandle, service: Arc<QuickAskService>) -> Result<(), String> {
+    let shortcuts = app.global_shortcut();
+    shortcuts
+        .unregister_all()
+        .map_err(|e| format!("Failed to unregister hotkeys: {}", e))?;
+
+    let mut taken = HashSet::new();
+    for binding in service.get_config().bindings.into_iter().filter(|b| b.enabled) {
+        let service_clone = Arc::clone(&service);
+        let source = binding.source;
+
+        let result = shortcuts.on_shortcut(binding.accelerator.as_str(), move |_app, _shortcut, event| {
+            if event.state() != ShortcutState::Pressed {
+                return;
+            }
+            let service = Arc::clone(&service_clone);
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = service.ask(source, None).await {
+                    log::warn!("Quick ask failed: {}", e);
+                }
+            });
+        });
+
+        // One bad or already-taken hotkey must not disable the others
+        match result {
+            Ok(()) => {
+                taken.insert(normalize_accelerator(&binding.accelerator));
+                log::info!("Registered quick ask hotkey {} ({:?})", binding.accelerator, source)
+            }
+            Err(e) => log::warn!("Failed to register hotkey {}: {}", binding.accelerator, e),
+        }
+    }
+
+    crate::commands::scripting::register_script_hotkeys(app, &mut taken);
+    crate::commands::prompt_templates::register_template_hotkeys(app, &mut taken);
+    Ok(())
+}
+
+/// Re-register every global shortcut after a script or template hotkey changed (v3.9.0)
+pub fn refresh_hotkeys(app: &AppHandle) -> Result<(), String> {
+    let service = app.state::<Arc<QuickAskService>>();
+    register_hotkeys(app, Arc::clone(service.inner()))
+}
+
+/// Run a quick ask turn (used by the popup for follow-up questions)
+#[tauri::command]
+pub async fn quick_ask_run(
+    source: QuickAskSource,
+    question: Option<String>,
+    service: State<'_, Arc<QuickAskService>>,
+) -> AppResult<QuickAskResult> {
+    Ok(service
+        .ask(source, question)
+        .await
+        .map_err(|e| format!("Quick ask failed: {}", e))?)
+}
+
+/// Get quick ask configuration (hotkeys)
+#[tauri::command]
+pub async fn quick_ask_get_config(
+    service: State<'_, Arc<QuickAskService>>,
+) -> AppResult<QuickAskConfig> {
+    Ok(service.get_config())
+}
+
+/// Update quick ask configuration and re-register hotkeys
+#[tauri::command]
+pub async fn quick_ask_update_config(
+    config: QuickAskConfig,
+    app: AppHandle,
+    service: State<'_, Arc<QuickAskService>>,
+) -> AppResult<()> {
+    service
+        .update_config(config)
+        .map_err(|e| format!("Failed to update quick ask config: {}", e))?;
+
+    Ok(register_hotkeys(&app, Arc::clone(&service.inner()))?)
+}
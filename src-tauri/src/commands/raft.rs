@@ -6,6 +6,8 @@
 
 use tauri::State;
 use serde::{Serialize, Deserialize};
+use crate::AppError;
+use crate::AppResult;
 use crate::AppState;
 use crate::services::raft::RaftConfig;
 
@@ -17,6 +19,13 @@ pub struct RaftConfigDto {
     pub num_distractors: usize,
     pub confidence_threshold: f32,
     pub use_chain_of_thought: bool,
+    /// v3.9.0: Minimum groundedness of answers to memory questions
+    #[serde(default = "default_min_groundedness")]
+    pub min_groundedness: f32,
+}
+
+fn default_min_groundedness() -> f32 {
+    RaftConfig::default().min_groundedness
 }
 
 impl From<RaftConfig> for RaftConfigDto {
@@ -26,6 +35,7 @@ impl From<RaftConfig> for RaftConfigDto {
             num_distractors: config.num_distractors,
             confidence_threshold: config.confidence_threshold,
             use_chain_of_thought: config.use_chain_of_thought,
+            min_groundedness: config.min_groundedness,
         }
     }
 }
@@ -37,13 +47,14 @@ impl From<RaftConfigDto> for RaftConfig {
             num_distractors: dto.num_distractors,
             confidence_threshold: dto.confidence_threshold,
             use_chain_of_thought: dto.use_chain_of_thought,
+            min_groundedness: dto.min_groundedness,
         }
     }
 }
 
 /// Get current RAFT configuration
 #[tauri::command]
-pub async fn get_raft_config(state: State<'_, AppState>) -> Result<RaftConfigDto, String> {
+pub async fn get_raft_config(state: State<'_, AppState>) -> AppResult<RaftConfigDto> {
     let rag_service = &state.rag;
 
     let config = rag_service
@@ -58,18 +69,21 @@ pub async fn get_raft_config(state: State<'_, AppState>) -> Result<RaftConfigDto
 pub async fn update_raft_config(
     state: State<'_, AppState>,
     config: RaftConfigDto,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let rag_service = &state.rag;
 
     // Validate configuration
     if config.relevance_threshold < 0.0 || config.relevance_threshold > 1.0 {
-        return Err("Relevance threshold must be between 0.0 and 1.0".to_string());
+        return Err(AppError::invalid_input("Relevance threshold must be between 0.0 and 1.0"));
     }
     if config.confidence_threshold < 0.0 || config.confidence_threshold > 1.0 {
-        return Err("Confidence threshold must be between 0.0 and 1.0".to_string());
+        return Err(AppError::invalid_input("Confidence threshold must be between 0.0 and 1.0"));
+    }
+    if config.min_groundedness < 0.0 || config.min_groundedness > 1.0 {
+        return Err(AppError::invalid_input("Minimum groundedness must be between 0.0 and 1.0"));
     }
     if config.num_distractors > 10 {
-        return Err("Number of distractors must be <= 10".to_string());
+        return Err(AppError::invalid_input("Number of distractors must be <= 10"));
     }
 
     rag_service
@@ -82,7 +96,7 @@ pub async fn update_raft_config(
 
 /// Reset RAFT configuration to defaults
 #[tauri::command]
-pub async fn reset_raft_config(state: State<'_, AppState>) -> Result<RaftConfigDto, String> {
+pub async fn reset_raft_config(state: State<'_, AppState>) -> AppResult<RaftConfigDto> {
     let rag_service = &state.rag;
 
     let default_config = RaftConfig {
@@ -90,6 +104,7 @@ pub async fn reset_raft_config(state: State<'_, AppState>) -> Result<RaftConfigD
         num_distractors: 2,
         confidence_threshold: 0.6,
         use_chain_of_thought: true,
+        min_groundedness: 0.5,
     };
 
     rag_service
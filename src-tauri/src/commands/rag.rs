@@ -0,0 +1,150 @@
+/**
+ * RAG Ingestion Commands (v3.9.0)
+ *
+ * Document ingestion, per-source chunking configuration, ANN index tuning
+ * and memory readiness
+ */
+
+use crate::services::chunker::{Chunk, ChunkingSettings, SourceKind};
+use crate::services::degradation::{self, ServiceState};
+use crate::services::index_tuning::{IndexStatus, IndexTuning, RecallPreference};
+use crate::services::memory_scope::MemoryScope;
+use crate::services::startup;
+use crate::AppResult;
+use crate::AppState;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Emitted once the vector store finished opening (or failed to)
+pub const MEMORY_READY_EVENT: &str = "memory-ready";
+
+/// Whether memory features (retrieval, storing episodes) are available
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryStatus {
+    pub ready: bool,
+    pub error: Option<String>,
+}
+
+/// Open the vector store in the background after the window is up
+///
+/// Retrieval calls made before this finishes wait for it instead of failing.
+pub async fn warm_up_memory(app: AppHandle) {
+    let rag = Arc::clone(&app.state::<AppState>().rag);
+    let start = Instant::now();
+    let status = match rag.warm_up().await {
+        Ok(()) => {
+            startup::record_lazy("rag", start.elapsed());
+            degradation::report("RAG Service", ServiceState::Available, None);
+            log::info!("✓ Memory ready after {}ms", start.elapsed().as_millis());
+            MemoryStatus { ready: true, error: None }
+        }
+        Err(e) => {
+            // Retrieval retries the open on its next call
+            degradation::report("RAG Service", ServiceState::Unavailable, Some(e.to_string()));
+            MemoryStatus { ready: false, error: Some(e.to_string()) }
+        }
+    };
+    if let Err(e) = app.emit(MEMORY_READY_EVENT, status) {
+        log::warn!("Failed to emit {}: {}", MEMORY_READY_EVENT, e);
+    }
+}
+
+/// Current memory readiness, for views mounted after `memory-ready` was emitted
+#[tauri::command]
+pub async fn rag_get_status(
+    state: State<'_, AppState>,
+) -> AppResult<MemoryStatus> {
+    Ok(MemoryStatus {
+        ready: state.rag.is_ready(),
+        error: state.rag.init_error(),
+    })
+}
+
+/// Chunk a document and store it in episodic memory
+///
+/// `source_kind` is detected from the name and content when omitted;
+/// `scope` limits where the chunks are recalled (default: every conversation).
+#[tauri::command]
+pub async fn rag_ingest_document(
+    name: String,
+    content: String,
+    source_kind: Option<SourceKind>,
+    scope: Option<MemoryScope>,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<String>> {
+    log::info!("Command: rag_ingest_document - name: {}, length: {}", name, content.len());
+
+    Ok(state.rag.ingest_document_in_scope(&name, &content, source_kind, &scope.unwrap_or_default())
+        .await
+        .map_err(|e| format!("Failed to ingest document: {}", e))?)
+}
+
+/// Get per-source chunking settings
+#[tauri::command]
+pub async fn rag_get_chunking_config(
+    state: State<'_, AppState>,
+) -> AppResult<ChunkingSettings> {
+    Ok(state.rag.get_chunking_settings())
+}
+
+/// Update per-source chunking settings
+#[tauri::command]
+pub async fn rag_update_chunking_config(
+    settings: ChunkingSettings,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    Ok(state.rag.update_chunking_settings(settings)
+        .map_err(|e| format!("Failed to update chunking config: {}", e))?)
+}
+
+/// Show how a text would be chunked without storing it
+#[tauri::command]
+pub async fn rag_preview_chunks(
+    text: String,
+    source_kind: Option<SourceKind>,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<Chunk>> {
+    let source = source_kind.unwrap_or_else(|| SourceKind::detect("", &text));
+    Ok(state.rag.chunk(&text, source))
+}
+
+/// ANN index tuning in effect and the corpus it applies to
+#[tauri::command]
+pub async fn rag_get_index_config(
+    state: State<'_, AppState>,
+) -> AppResult<IndexStatus> {
+    Ok(state.rag.index_status()
+        .await
+        .map_err(|e| format!("Failed to get index config: {}", e))?)
+}
+
+/// Tune the vector index (IVF partitions, PQ bits, refine factor, SQLite batch sizes)
+///
+/// With `auto_tune`, parameters are derived from the corpus size for that recall
+/// preference and `tuning` is ignored; with neither, the saved tuning is re-applied.
+/// `rebuild` (default: true) rebuilds the index with the new build parameters.
+#[tauri::command]
+pub async fn rag_configure_index(
+    tuning: Option<IndexTuning>,
+    auto_tune: Option<RecallPreference>,
+    rebuild: Option<bool>,
+    state: State<'_, AppState>,
+) -> AppResult<IndexStatus> {
+    log::info!("Command: rag_configure_index - auto_tune: {:?}", auto_tune);
+
+    let tuning = match (auto_tune, tuning) {
+        (Some(preference), _) => state.rag.auto_tune_index(preference)
+            .await
+            .map_err(|e| format!("Failed to auto-tune index: {}", e))?,
+        (None, Some(tuning)) => tuning,
+        (None, None) => state.rag.index_status()
+            .await
+            .map_err(|e| format!("Failed to get index config: {}", e))?
+            .tuning,
+    };
+    Ok(state.rag.configure_index(tuning, rebuild.unwrap_or(true))
+        .await
+        .map_err(|e| format!("Failed to configure index: {}", e))?)
+}
@@ -0,0 +1,140 @@
+/**
+ * RAG Eval Commands (v3.9.0)
+ *
+ * Golden datasets and offline retrieval evaluation
+ */
+
+use crate::services::degradation::ServiceSlot;
+use crate::services::rag_eval::{
+    CaseResult, EvalCase, EvalHistoryEntry, NewEvalCase, RagEvalService, RetrievalMode,
+};
+#[cfg(feature = "lancedb-support")]
+use crate::services::rag_eval::{build_report, score_case, EvalRun};
+#[cfg(feature = "lancedb-support")]
+use crate::AppResult;
+use crate::AppState;
+use std::sync::Arc;
+use tauri::State;
+
+/// Default cutoff for recall@k
+#[cfg(feature = "lancedb-support")]
+const DEFAULT_K: usize = 5;
+
+/// Add query → expected-memory pairs to a dataset
+#[tauri::command]
+pub async fn rag_eval_add_cases(
+    dataset: String,
+    cases: Vec<NewEvalCase>,
+    service: State<'_, Arc<ServiceSlot<RagEvalService>>>,
+) -> AppResult<Vec<EvalCase>> {
+    let service = service.get()?;
+    Ok(service.add_cases(&dataset, cases)
+        .map_err(|e| format!("Failed to add eval cases: {}", e))?)
+}
+
+/// List the cases of a dataset
+#[tauri::command]
+pub async fn rag_eval_list_cases(
+    dataset: String,
+    service: State<'_, Arc<ServiceSlot<RagEvalService>>>,
+) -> AppResult<Vec<EvalCase>> {
+    let service = service.get()?;
+    Ok(service.list_cases(&dataset)
+        .map_err(|e| format!("Failed to list eval cases: {}", e))?)
+}
+
+/// List datasets with their case counts
+#[tauri::command]
+pub async fn rag_eval_list_datasets(
+    service: State<'_, Arc<ServiceSlot<RagEvalService>>>,
+) -> AppResult<Vec<(String, usize)>> {
+    let service = service.get()?;
+    Ok(service.list_datasets()
+        .map_err(|e| format!("Failed to list eval datasets: {}", e))?)
+}
+
+/// Delete one case
+#[tauri::command]
+pub async fn rag_eval_delete_case(
+    case_id: String,
+    service: State<'_, Arc<ServiceSlot<RagEvalService>>>,
+) -> AppResult<bool> {
+    let service = service.get()?;
+    Ok(service.delete_case(&case_id)
+        .map_err(|e| format!("Failed to delete eval case: {}", e))?)
+}
+
+/// Run a dataset against each retrieval configuration and persist the results
+///
+/// `modes` defaults to BM25-only, dense-only, hybrid and hybrid + rerank.
+#[cfg(feature = "lancedb-support")]
+#[tauri::command]
+pub async fn rag_eval_run(
+    dataset: String,
+    k: Option<usize>,
+    modes: Option<Vec<RetrievalMode>>,
+    state: State<'_, AppState>,
+    service: State<'_, Arc<ServiceSlot<RagEvalService>>>,
+) -> AppResult<EvalRun> {
+    let service = service.get()?;
+    let k = k.unwrap_or(DEFAULT_K).max(1);
+    let modes = modes.unwrap_or_else(|| RetrievalMode::ALL.to_vec());
+    log::info!("Command: rag_eval_run - dataset: {}, k: {}, modes: {:?}", dataset, k, modes);
+
+    let cases = service.list_cases(&dataset)
+        .map_err(|e| format!("Failed to list eval cases: {}", e))?;
+    if cases.is_empty() {
+        return Err(format!("Dataset '{}' has no cases", dataset).into());
+    }
+
+    let started_at = chrono::Utc::now().timestamp_millis();
+    let hybrid_search = state.hybrid_search.lock().await;
+
+    let mut reports = Vec::with_capacity(modes.len());
+    for mode in modes {
+        let mut results = Vec::with_capacity(cases.len());
+        for case in &cases {
+            let start = std::time::Instant::now();
+            let retrieved = hybrid_search.search_ids(&case.query, k, mode).await;
+            let latency_ms = start.elapsed().as_millis() as u64;
+            results.push(score_case(case, retrieved, k, latency_ms));
+        }
+        let report = build_report(mode, results);
+        log::info!(
+            "Eval {} ({}): recall@{} {:.3}, MRR {:.3}, p50 {}ms",
+            dataset, mode.as_str(), k, report.recall_at_k, report.mrr, report.latency.p50
+        );
+        reports.push(report);
+    }
+    drop(hybrid_search);
+
+    let case_count = cases.len();
+    Ok(tokio::task::spawn_blocking(move || service.record_run(&dataset, k, started_at, case_count, reports))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to record eval run: {}", e))?)
+}
+
+/// Past eval results, newest first
+#[tauri::command]
+pub async fn rag_eval_history(
+    dataset: Option<String>,
+    limit: Option<usize>,
+    service: State<'_, Arc<ServiceSlot<RagEvalService>>>,
+) -> AppResult<Vec<EvalHistoryEntry>> {
+    let service = service.get()?;
+    Ok(service.history(dataset.as_deref(), limit.unwrap_or(50))
+        .map_err(|e| format!("Failed to load eval history: {}", e))?)
+}
+
+/// Per-case results of one configuration of a run
+#[tauri::command]
+pub async fn rag_eval_run_details(
+    run_id: String,
+    mode: RetrievalMode,
+    service: State<'_, Arc<ServiceSlot<RagEvalService>>>,
+) -> AppResult<Vec<CaseResult>> {
+    let service = service.get()?;
+    Ok(service.run_details(&run_id, mode)
+        .map_err(|e| format!("Failed to load eval run details: {}", e))?)
+}
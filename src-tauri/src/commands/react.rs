@@ -1,17 +1,36 @@
+use crate::services::tool_sets;
+use crate::AppResult;
 use crate::AppState;
 use log::info;
 use tauri::{command, State};
 
 /// Execute ReAct loop for a user query
+///
+/// `tool_set` limits the run to one tool set's tools (v3.9.0)
 #[command]
 pub async fn react_execute(
     state: State<'_, AppState>,
     query: String,
-) -> Result<serde_json::Value, String> {
+    tool_set: Option<String>,
+) -> AppResult<serde_json::Value> {
     info!("Command: react_execute");
 
+    let allowed_tools = match tool_set {
+        Some(set_id) => Some(
+            state
+                .db
+                .call(move |db| match tool_sets::get_tool_set(db.conn(), &set_id) {
+                    Ok(Some(set)) => Ok(set.allowed()),
+                    Ok(None) => Err(format!("Tool set not found: {}", set_id)),
+                    Err(e) => Err(e.to_string()),
+                })
+                .await?,
+        ),
+        None => None,
+    };
+
     let agent = &*state.react_agent;
-    let execution = agent.execute(&query).await?;
+    let execution = agent.execute_with_tools(&query, allowed_tools.as_ref()).await?;
 
     Ok(serde_json::json!({
         "steps": execution.steps.iter().map(|step| {
@@ -29,7 +48,7 @@ pub async fn react_execute(
 
 /// Get ReAct configuration
 #[command]
-pub fn react_get_config(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+pub fn react_get_config(state: State<'_, AppState>) -> AppResult<serde_json::Value> {
     info!("Command: react_get_config");
 
     let agent = &*state.react_agent;
@@ -51,7 +70,7 @@ pub fn react_set_config(
     model: Option<String>,
     temperature: Option<f32>,
     enable_verbose: Option<bool>,
-) -> Result<String, String> {
+) -> AppResult<String> {
     info!("Command: react_set_config");
 
     let agent = &*state.react_agent;
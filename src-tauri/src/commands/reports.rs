@@ -0,0 +1,101 @@
+/**
+ * Report Commands (v3.9.0)
+ *
+ * Exporting conversations, weekly reviews, plans and wiki pages as HTML or PDF
+ */
+
+use crate::services::charts::ChartService;
+use crate::services::reports::{self, GeneratedReport, ReportDocument, ReportFormat, ReportSource};
+use crate::services::semantic_wiki::SemanticWikiService;
+use crate::services::weekly_review::WeeklyReviewService;
+use crate::AppError;
+use crate::AppResult;
+use crate::AppState;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::State;
+
+/// Turn a conversation, weekly review, plan or wiki page into a standalone
+/// HTML page (default) or a PDF.
+///
+/// With `output_path` the file is written there; otherwise the report is
+/// returned inline (PDFs as base64).
+#[tauri::command]
+pub async fn report_generate(
+    source: ReportSource,
+    format: Option<ReportFormat>,
+    output_path: Option<String>,
+    state: State<'_, AppState>,
+    weekly_review: State<'_, Arc<WeeklyReviewService>>,
+    wiki: State<'_, Arc<SemanticWikiService>>,
+    charts: State<'_, Arc<ChartService>>,
+) -> AppResult<GeneratedReport> {
+    log::info!("Generating report from {:?}", source);
+
+    let document: ReportDocument = match source {
+        ReportSource::Conversation { conversation_id } => {
+            let charts_clone = Arc::clone(&charts.inner());
+            let id = conversation_id.clone();
+            let conversation_charts = tokio::task::spawn_blocking(move || {
+                charts_clone
+                    .list_for_conversation(&id)
+                    .map_err(|e| format!("Failed to load charts: {}", e))
+            })
+            .await
+            .map_err(|e| format!("Task join error: {}", e))??;
+
+            state
+                .db
+                .call(move |db| {
+                    reports::conversation_document(db.conn(), &conversation_id, &conversation_charts)
+                        .map_err(|e| e.to_string())
+                })
+                .await?
+        }
+        ReportSource::WeeklyReview { week_start } => {
+            let service_clone = Arc::clone(&weekly_review.inner());
+            tokio::task::spawn_blocking(move || {
+                let reviews = service_clone
+                    .list(52)
+                    .map_err(|e| format!("Failed to load weekly reviews: {}", e))?;
+                let review = match &week_start {
+                    Some(week_start) => reviews.iter().find(|r| &r.week_start == week_start),
+                    None => reviews.first(),
+                }
+                .ok_or_else(|| match &week_start {
+                    Some(week_start) => format!("No weekly review for the week of {}", week_start),
+                    None => "No weekly review generated yet".to_string(),
+                })?;
+                Ok::<_, String>(reports::weekly_review_document(review))
+            })
+            .await
+            .map_err(|e| format!("Task join error: {}", e))??
+        }
+        ReportSource::Plan { plan_id } => {
+            // Plans still being executed first, like planner_get_plan
+            let approved = state.approved_plans.lock().await.get(&plan_id).cloned();
+            let plan = match approved {
+                Some(plan) => Some(plan),
+                None => state.plan_history.lock().await.get(&plan_id).cloned(),
+            };
+            let plan = plan.ok_or_else(|| AppError::not_found(format!("Plan {} not found", plan_id)))?;
+            reports::plan_document(&plan)
+        }
+        ReportSource::WikiPage { entity } => {
+            let page = wiki
+                .get_entity_page(&entity, false)
+                .await
+                .map_err(|e| format!("Failed to get entity page: {}", e))?;
+            reports::wiki_document(&page)
+        }
+    };
+
+    let format = format.unwrap_or_default();
+    Ok(tokio::task::spawn_blocking(move || {
+        let output = output_path.map(PathBuf::from);
+        reports::generate(&document, format, output.as_deref())
+            .map_err(|e| format!("Failed to generate report: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
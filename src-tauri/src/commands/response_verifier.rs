@@ -0,0 +1,52 @@
+/**
+ * Response Verifier Commands (v3.9.0)
+ *
+ * Configure the reply self-check and inspect retry statistics
+ */
+
+use crate::services::conversation_language::ConversationLanguage;
+use crate::services::response_verifier::{self, ResponseVerifierService, VerificationIssue, VerifierConfig, VerifierStats};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Get the verifier config
+#[tauri::command]
+pub async fn verifier_get_config(
+    service: State<'_, Arc<ResponseVerifierService>>,
+) -> AppResult<VerifierConfig> {
+    Ok(service.config())
+}
+
+/// Update the verifier config
+#[tauri::command]
+pub async fn verifier_set_config(
+    config: VerifierConfig,
+    service: State<'_, Arc<ResponseVerifierService>>,
+) -> AppResult<VerifierConfig> {
+    log::info!("Command: verifier_set_config - enabled: {}, auto_retry: {}", config.enabled, config.auto_retry);
+
+    Ok(service.set_config(config)
+        .map_err(|e| format!("Failed to update verifier config: {}", e))?)
+}
+
+/// How often replies were flagged and regenerated
+#[tauri::command]
+pub async fn verifier_get_stats(
+    days: Option<u32>,
+    service: State<'_, Arc<ResponseVerifierService>>,
+) -> AppResult<VerifierStats> {
+    Ok(service.stats(days.unwrap_or(7))
+        .map_err(|e| format!("Failed to get verifier stats: {}", e))?)
+}
+
+/// Run the checks on a message/reply pair without regenerating
+#[tauri::command]
+pub async fn verifier_check(
+    message: String,
+    response: String,
+    language: Option<String>,
+) -> AppResult<Vec<VerificationIssue>> {
+    let expected = language.as_deref().and_then(ConversationLanguage::from_code);
+    Ok(response_verifier::check_response(&message, &response, expected))
+}
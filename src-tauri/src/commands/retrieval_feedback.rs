@@ -0,0 +1,41 @@
+/**
+ * Retrieval Feedback Commands (v3.9.0)
+ *
+ * Retrieval quality trend and retraining from rated responses
+ */
+
+use crate::services::retrieval_feedback::{self, RetrievalQualityReport};
+#[cfg(feature = "lancedb-support")]
+use crate::services::retrieval_feedback::TuningRun;
+use crate::AppResult;
+use crate::AppState;
+use tauri::State;
+
+/// Weekly retrieval quality and past retraining runs
+#[tauri::command]
+pub async fn retrieval_feedback_report(
+    state: State<'_, AppState>,
+    weeks: Option<usize>,
+) -> AppResult<RetrievalQualityReport> {
+    let weeks = weeks.unwrap_or(12).max(1);
+    log::info!("Command: retrieval_feedback_report - {} weeks", weeks);
+
+    Ok(state.db.call(move |db| {
+        let trend = retrieval_feedback::quality_trend(db.conn(), weeks)
+            .map_err(|e| format!("Failed to load retrieval quality: {}", e))?;
+        let runs = retrieval_feedback::tuning_runs(db.conn(), 20)
+            .map_err(|e| format!("Failed to load tuning runs: {}", e))?;
+        Ok::<_, String>(RetrievalQualityReport { trend, runs })
+    }).await?)
+}
+
+/// Retrain fusion weights and the relevance threshold now (also runs daily)
+#[cfg(feature = "lancedb-support")]
+#[tauri::command]
+pub async fn retrieval_feedback_retrain(state: State<'_, AppState>) -> AppResult<TuningRun> {
+    log::info!("Command: retrieval_feedback_retrain");
+
+    Ok(retrieval_feedback::retrain(state.db.as_mutex(), &state.rag, &state.hybrid_search)
+        .await
+        .map_err(|e| format!("Failed to retrain retrieval: {}", e))?)
+}
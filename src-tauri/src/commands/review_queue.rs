@@ -0,0 +1,62 @@
+/**
+ * Review Queue Commands (v3.9.0)
+ *
+ * Spaced-repetition review of at-risk memories
+ */
+
+use crate::services::review_queue::{
+    ReviewItem, ReviewOutcome, ReviewQueueConfig, ReviewQueueService, ReviewResult, ReviewStats,
+};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Memories due for review, highest priority first
+#[tauri::command]
+pub async fn review_queue_get(
+    limit: Option<usize>,
+    service: State<'_, Arc<ReviewQueueService>>,
+) -> AppResult<Vec<ReviewItem>> {
+    Ok(service.get_queue(limit)
+        .map_err(|e| format!("Failed to load review queue: {}", e))?)
+}
+
+/// Confirm, correct, or dismiss a memory
+#[tauri::command]
+pub async fn review_queue_submit(
+    memory_id: String,
+    outcome: ReviewOutcome,
+    service: State<'_, Arc<ReviewQueueService>>,
+) -> AppResult<ReviewResult> {
+    let service = Arc::clone(&service);
+    Ok(tokio::task::spawn_blocking(move || service.review(&memory_id, outcome))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to review memory: {}", e))?)
+}
+
+/// Due, recent, and total review counts
+#[tauri::command]
+pub async fn review_queue_get_stats(
+    service: State<'_, Arc<ReviewQueueService>>,
+) -> AppResult<ReviewStats> {
+    Ok(service.get_stats()
+        .map_err(|e| format!("Failed to get review stats: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn review_queue_get_config(
+    service: State<'_, Arc<ReviewQueueService>>,
+) -> AppResult<ReviewQueueConfig> {
+    Ok(service.get_config()
+        .map_err(|e| format!("Failed to get review queue config: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn review_queue_update_config(
+    config: ReviewQueueConfig,
+    service: State<'_, Arc<ReviewQueueService>>,
+) -> AppResult<()> {
+    Ok(service.update_config(config)
+        .map_err(|e| format!("Failed to update review queue config: {}", e))?)
+}
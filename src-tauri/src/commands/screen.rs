@@ -1,7 +1,10 @@
+use crate::AppResult;
 use crate::AppState;
-use crate::services::screen::{ScreenTrackingState, ScreenCapture, EnhancedScreenCapture};
+use crate::services::screen::{ScreenTrackingState, ScreenCapture, EnhancedScreenCapture, PrivacyConfig};
 use crate::services::active_window::ActiveWindow;
 use crate::services::llava::ScreenAnalysis;
+use crate::services::screen_history::{ScreenHistoryMatch, ScreenHistoryService};
+use std::sync::Arc;
 use tauri::State;
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -28,7 +31,7 @@ impl From<ScreenTrackingState> for ScreenStatus {
 pub async fn screen_start_tracking(
     state: State<'_, AppState>,
     interval: Option<u64>,
-) -> Result<ScreenStatus, String> {
+) -> AppResult<ScreenStatus> {
     log::info!("Starting screen tracking with interval: {:?}", interval);
 
     let interval_seconds = interval.unwrap_or(30);
@@ -43,7 +46,7 @@ pub async fn screen_start_tracking(
 
 /// Stop screen tracking
 #[tauri::command]
-pub async fn screen_stop_tracking(state: State<'_, AppState>) -> Result<ScreenStatus, String> {
+pub async fn screen_stop_tracking(state: State<'_, AppState>) -> AppResult<ScreenStatus> {
     log::info!("Stopping screen tracking");
 
     state.screen_service.stop_tracking()?;
@@ -57,7 +60,7 @@ pub async fn screen_stop_tracking(state: State<'_, AppState>) -> Result<ScreenSt
 pub async fn screen_toggle_tracking(
     state: State<'_, AppState>,
     interval: Option<u64>,
-) -> Result<ScreenStatus, String> {
+) -> AppResult<ScreenStatus> {
     log::info!("Toggling screen tracking");
 
     state.screen_service.toggle_tracking(interval).await?;
@@ -68,7 +71,7 @@ pub async fn screen_toggle_tracking(
 
 /// Get current screen tracking status
 #[tauri::command]
-pub async fn screen_get_status(state: State<'_, AppState>) -> Result<ScreenStatus, String> {
+pub async fn screen_get_status(state: State<'_, AppState>) -> AppResult<ScreenStatus> {
     let status = state.screen_service.get_status()?;
     Ok(status.into())
 }
@@ -78,16 +81,16 @@ pub async fn screen_get_status(state: State<'_, AppState>) -> Result<ScreenStatu
 pub async fn screen_get_recent(
     state: State<'_, AppState>,
     limit: Option<usize>,
-) -> Result<Vec<ScreenCapture>, String> {
+) -> AppResult<Vec<ScreenCapture>> {
     let limit = limit.unwrap_or(10);
-    state.screen_service.get_recent_captures(limit)
+    Ok(state.screen_service.get_recent_captures(limit)?)
 }
 
 /// Clear all screen captures (for privacy)
 #[tauri::command]
-pub async fn screen_clear_all(state: State<'_, AppState>) -> Result<usize, String> {
+pub async fn screen_clear_all(state: State<'_, AppState>) -> AppResult<usize> {
     log::info!("Clearing all screen captures");
-    state.screen_service.clear_all_captures()
+    Ok(state.screen_service.clear_all_captures()?)
 }
 
 /// Capture screen with active window detection and vision analysis
@@ -95,18 +98,18 @@ pub async fn screen_clear_all(state: State<'_, AppState>) -> Result<usize, Strin
 pub async fn screen_capture_with_context(
     state: State<'_, AppState>,
     context_level: Option<u8>,
-) -> Result<EnhancedScreenCapture, String> {
+) -> AppResult<EnhancedScreenCapture> {
     let level = context_level.unwrap_or(1); // Default to Level 1 (quick)
     log::info!("Capturing screen with context level {}", level);
 
-    state.screen_service.capture_with_context(level).await
+    Ok(state.screen_service.capture_with_context(level).await?)
 }
 
 /// Get currently active window information
 #[tauri::command]
-pub async fn screen_get_active_window(state: State<'_, AppState>) -> Result<ActiveWindow, String> {
+pub async fn screen_get_active_window(state: State<'_, AppState>) -> AppResult<ActiveWindow> {
     log::info!("Getting active window");
-    state.screen_service.get_active_window()
+    Ok(state.screen_service.get_active_window()?)
 }
 
 /// Analyze current screen with LLaVA vision model
@@ -114,9 +117,52 @@ pub async fn screen_get_active_window(state: State<'_, AppState>) -> Result<Acti
 pub async fn screen_analyze_current(
     state: State<'_, AppState>,
     context_level: Option<u8>,
-) -> Result<ScreenAnalysis, String> {
+) -> AppResult<ScreenAnalysis> {
     let level = context_level.unwrap_or(2); // Default to Level 2 (detailed)
     log::info!("Analyzing current screen with context level {}", level);
 
-    state.screen_service.analyze_current_screen(level).await
+    Ok(state.screen_service.analyze_current_screen(level).await?)
+}
+
+/// Configure privacy zones and sensitive-content redaction (v3.9.0)
+#[tauri::command]
+pub async fn screen_configure_privacy(
+    state: State<'_, AppState>,
+    config: PrivacyConfig,
+) -> AppResult<PrivacyConfig> {
+    log::info!("Configuring screen privacy");
+    Ok(state.screen_service.configure_privacy(config)?)
+}
+
+/// Get current privacy configuration (v3.9.0)
+#[tauri::command]
+pub async fn screen_get_privacy_config(state: State<'_, AppState>) -> AppResult<PrivacyConfig> {
+    Ok(state.screen_service.get_privacy_config())
+}
+
+/// Search screenshot history by meaning, e.g. "the error dialog from yesterday" (v3.9.0)
+#[tauri::command]
+pub async fn screen_search_history(
+    query: String,
+    limit: Option<usize>,
+    history: State<'_, Arc<ScreenHistoryService>>,
+) -> AppResult<Vec<ScreenHistoryMatch>> {
+    log::info!("Searching screen history: {}", query);
+
+    Ok(history
+        .search(&query, limit.unwrap_or(10))
+        .await
+        .map_err(|e| format!("Failed to search screen history: {}", e))?)
+}
+
+/// Clear all screenshot history frames (for privacy) (v3.9.0)
+#[tauri::command]
+pub async fn screen_clear_history(
+    history: State<'_, Arc<ScreenHistoryService>>,
+) -> AppResult<usize> {
+    log::info!("Clearing screen history");
+    Ok(history
+        .clear()
+        .await
+        .map_err(|e| format!("Failed to clear screen history: {}", e))?)
 }
@@ -0,0 +1,125 @@
+/**
+ * Scripting Commands (v3.9.0)
+ *
+ * Script editor: save, check, run, and (re-)bind script hotkeys
+ */
+
+use crate::commands::quick_ask::refresh_hotkeys;
+use crate::services::degradation::ServiceSlot;
+use crate::services::quick_ask::normalize_accelerator;
+use crate::services::scripting::{self, Script, ScriptInput, ScriptRun, ScriptingService};
+use crate::services::webhook_triggers::EVENT_NAMES;
+use crate::AppResult;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+/// Bind the hotkeys of enabled scripts, skipping ones already `taken`
+///
+/// Called from `quick_ask::register_hotkeys`, which clears all shortcuts first.
+pub fn register_script_hotkeys(app: &AppHandle, taken: &mut HashSet<String>) {
+    let Some(slot) = app.try_state::<Arc<ServiceSlot<ScriptingService>>>() else {
+        return;
+    };
+    let Ok(service) = slot.get() else {
+        return;
+    };
+    let hotkeys = match service.hotkeys() {
+        Ok(hotkeys) => hotkeys,
+        Err(e) => {
+            log::warn!("Failed to load script hotkeys: {}", e);
+            return;
+        }
+    };
+
+    let shortcuts = app.global_shortcut();
+    for (script_id, accelerator) in hotkeys {
+        if taken.contains(&normalize_accelerator(&accelerator)) {
+            log::warn!("Script hotkey {} is already in use", accelerator);
+            continue;
+        }
+
+        let service_clone = Arc::clone(&service);
+        let id = script_id.clone();
+        let result = shortcuts.on_shortcut(accelerator.as_str(), move |_app, _shortcut, event| {
+            if event.state() != ShortcutState::Pressed {
+                return;
+            }
+            let (service, id) = (Arc::clone(&service_clone), id.clone());
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = service.run_async(id, None).await {
+                    log::warn!("Hotkey script failed: {}", e);
+                }
+            });
+        });
+
+        match result {
+            Ok(()) => {
+                taken.insert(normalize_accelerator(&accelerator));
+                log::info!("Registered script hotkey {} ({})", accelerator, script_id)
+            }
+            Err(e) => log::warn!("Failed to register hotkey {}: {}", accelerator, e),
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn scripts_list(
+    service: State<'_, Arc<ServiceSlot<ScriptingService>>>,
+) -> AppResult<Vec<Script>> {
+    let service = service.get()?;
+    Ok(service.list()
+        .map_err(|e| format!("Failed to list scripts: {}", e))?)
+}
+
+/// Create or update a script (it must compile)
+#[tauri::command]
+pub async fn scripts_save(
+    script: ScriptInput,
+    app: AppHandle,
+    service: State<'_, Arc<ServiceSlot<ScriptingService>>>,
+) -> AppResult<Script> {
+    let service = service.get()?;
+    let saved = service.save(script)
+        .map_err(|e| format!("Failed to save script: {}", e))?;
+    refresh_hotkeys(&app)?;
+    Ok(saved)
+}
+
+#[tauri::command]
+pub async fn scripts_delete(
+    id: String,
+    app: AppHandle,
+    service: State<'_, Arc<ServiceSlot<ScriptingService>>>,
+) -> AppResult<()> {
+    let service = service.get()?;
+    service.delete(&id)
+        .map_err(|e| format!("Failed to delete script: {}", e))?;
+    Ok(refresh_hotkeys(&app)?)
+}
+
+/// Run a saved script now
+#[tauri::command]
+pub async fn scripts_run(
+    id: String,
+    service: State<'_, Arc<ServiceSlot<ScriptingService>>>,
+) -> AppResult<ScriptRun> {
+    Ok(service.get()?
+        .run_async(id, None)
+        .await
+        .map_err(|e| format!("Failed to run script: {}", e))?)
+}
+
+/// Syntax check for the editor
+#[tauri::command]
+pub async fn scripts_check_syntax(source: String) -> AppResult<()> {
+    Ok(scripting::check_syntax(&source)
+        .map_err(|e| format!("{}", e))?)
+}
+
+/// Event names a webhook-triggered script can listen to
+#[tauri::command]
+pub async fn scripts_event_names() -> AppResult<Vec<String>> {
+    Ok(EVENT_NAMES.iter().map(|name| name.to_string()).collect())
+}
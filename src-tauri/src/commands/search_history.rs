@@ -0,0 +1,72 @@
+/**
+ * Search History Commands (v3.9.0)
+ *
+ * Browse, search and clear the web searches made by the web search tool
+ */
+
+use crate::services::search_history::{SearchHistoryEntry, SearchHistoryService};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Default number of entries returned
+const DEFAULT_LIMIT: usize = 50;
+
+/// Most recent searches first
+#[tauri::command]
+pub async fn search_history_get_recent(
+    limit: Option<usize>,
+    service: State<'_, Arc<SearchHistoryService>>,
+) -> AppResult<Vec<SearchHistoryEntry>> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .get_recent(limit.unwrap_or(DEFAULT_LIMIT))
+            .map_err(|e| format!("Failed to load search history: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Searches whose query or results mention `query`
+#[tauri::command]
+pub async fn search_history_search(
+    query: String,
+    limit: Option<usize>,
+    service: State<'_, Arc<SearchHistoryService>>,
+) -> AppResult<Vec<SearchHistoryEntry>> {
+    log::info!("Command: search_history_search");
+
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .search(&query, limit.unwrap_or(DEFAULT_LIMIT))
+            .map_err(|e| format!("Failed to search history: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn search_history_delete(
+    id: String,
+    service: State<'_, Arc<SearchHistoryService>>,
+) -> AppResult<bool> {
+    Ok(service
+        .delete(&id)
+        .await
+        .map_err(|e| format!("Failed to delete search: {}", e))?)
+}
+
+/// Remove every saved search, returning how many were removed
+#[tauri::command]
+pub async fn search_history_clear(
+    service: State<'_, Arc<SearchHistoryService>>,
+) -> AppResult<usize> {
+    log::info!("Command: search_history_clear");
+
+    Ok(service
+        .clear()
+        .await
+        .map_err(|e| format!("Failed to clear search history: {}", e))?)
+}
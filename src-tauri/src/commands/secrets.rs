@@ -0,0 +1,50 @@
+/**
+ * Secrets Commands (v3.9.0)
+ *
+ * OS keychain vault for API keys and OAuth tokens
+ */
+
+use crate::services::secrets::SecretsService;
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Store (or replace) a secret
+#[tauri::command]
+pub async fn secrets_set(
+    name: String,
+    value: String,
+    service: State<'_, Arc<SecretsService>>,
+) -> AppResult<()> {
+    let service = Arc::clone(&service);
+    Ok(tokio::task::spawn_blocking(move || service.set(&name, &value))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to store secret: {}", e))?)
+}
+
+/// Read a secret (`null` if not set)
+#[tauri::command]
+pub async fn secrets_get(
+    name: String,
+    service: State<'_, Arc<SecretsService>>,
+) -> AppResult<Option<String>> {
+    let service = Arc::clone(&service);
+    Ok(tokio::task::spawn_blocking(move || service.get(&name))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to read secret: {}", e))?)
+}
+
+/// Delete a secret
+#[tauri::command]
+pub async fn secrets_delete(
+    name: String,
+    service: State<'_, Arc<SecretsService>>,
+) -> AppResult<()> {
+    let service = Arc::clone(&service);
+    Ok(tokio::task::spawn_blocking(move || service.delete(&name))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to delete secret: {}", e))?)
+}
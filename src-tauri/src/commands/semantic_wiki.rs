@@ -5,8 +5,9 @@
  */
 
 use crate::services::semantic_wiki::{
-    Fact, FactCategory, SemanticWikiConfig, SemanticWikiService, WikiStats,
+    EntityPage, Fact, FactCategory, SemanticWikiConfig, SemanticWikiService, WikiConflict, WikiStats,
 };
+use crate::AppResult;
 use std::sync::Arc;
 use tauri::State;
 
@@ -18,10 +19,10 @@ pub async fn wiki_extract_facts(
     conversation_id: String,
     message_id: Option<String>,
     service: State<'_, Arc<SemanticWikiService>>,
-) -> Result<Vec<Fact>, String> {
+) -> AppResult<Vec<Fact>> {
     log::info!("Wiki fact extraction request");
 
-    service
+    Ok(service
         .extract_facts(
             &user_message,
             &ai_response,
@@ -29,7 +30,7 @@ pub async fn wiki_extract_facts(
             message_id.as_deref(),
         )
         .await
-        .map_err(|e| format!("Failed to extract facts: {}", e))
+        .map_err(|e| format!("Failed to extract facts: {}", e))?)
 }
 
 /// Store facts in the wiki
@@ -37,11 +38,11 @@ pub async fn wiki_extract_facts(
 pub async fn wiki_store_facts(
     facts: Vec<Fact>,
     service: State<'_, Arc<SemanticWikiService>>,
-) -> Result<usize, String> {
-    service
+) -> AppResult<usize> {
+    Ok(service
         .store_facts(facts)
         .await
-        .map_err(|e| format!("Failed to store facts: {}", e))
+        .map_err(|e| format!("Failed to store facts: {}", e))?)
 }
 
 /// Search for facts
@@ -51,11 +52,65 @@ pub async fn wiki_search(
     limit: usize,
     category_filter: Option<FactCategory>,
     service: State<'_, Arc<SemanticWikiService>>,
-) -> Result<Vec<(Fact, f32)>, String> {
-    service
+) -> AppResult<Vec<(Fact, f32)>> {
+    Ok(service
         .search(&query, limit, category_filter)
         .await
-        .map_err(|e| format!("Failed to search wiki: {}", e))
+        .map_err(|e| format!("Failed to search wiki: {}", e))?)
+}
+
+/// Write a fact by hand, optionally correcting an existing one
+///
+/// User-asserted facts never decay and win conflicts with inferred facts.
+#[tauri::command]
+pub async fn wiki_create_fact(
+    statement: String,
+    entity: String,
+    category: Option<FactCategory>,
+    replaces: Option<String>,
+    service: State<'_, Arc<SemanticWikiService>>,
+) -> AppResult<Fact> {
+    Ok(service
+        .assert_fact(
+            &statement,
+            &entity,
+            category.unwrap_or(FactCategory::Other),
+            replaces.as_deref(),
+        )
+        .await
+        .map_err(|e| format!("Failed to create fact: {}", e))?)
+}
+
+/// Edit a fact by hand; it becomes user-asserted
+#[tauri::command]
+pub async fn wiki_update_fact(
+    fact_id: String,
+    statement: Option<String>,
+    entity: Option<String>,
+    category: Option<FactCategory>,
+    service: State<'_, Arc<SemanticWikiService>>,
+) -> AppResult<Fact> {
+    Ok(service
+        .update_fact(&fact_id, statement.as_deref(), entity.as_deref(), category)
+        .await
+        .map_err(|e| format!("Failed to update fact: {}", e))?)
+}
+
+/// Set or clear the user's note on a fact
+#[tauri::command]
+pub async fn wiki_annotate_fact(
+    fact_id: String,
+    note: Option<String>,
+    service: State<'_, Arc<SemanticWikiService>>,
+) -> AppResult<Fact> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .annotate_fact(&fact_id, note.as_deref())
+            .map_err(|e| format!("Failed to annotate fact: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 /// Get facts by entity
@@ -64,30 +119,43 @@ pub async fn wiki_get_by_entity(
     entity: String,
     limit: usize,
     service: State<'_, Arc<SemanticWikiService>>,
-) -> Result<Vec<Fact>, String> {
+) -> AppResult<Vec<Fact>> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .get_facts_by_entity(&entity, limit)
             .map_err(|e| format!("Failed to get facts by entity: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Get the wiki page of an entity, summarizing facts learned since the last visit
+#[tauri::command]
+pub async fn wiki_get_entity_page(
+    entity: String,
+    regenerate: Option<bool>,
+    service: State<'_, Arc<SemanticWikiService>>,
+) -> AppResult<EntityPage> {
+    Ok(service
+        .get_entity_page(&entity, regenerate.unwrap_or(false))
+        .await
+        .map_err(|e| format!("Failed to get entity page: {}", e))?)
 }
 
 /// Get wiki statistics
 #[tauri::command]
 pub async fn wiki_get_stats(
     service: State<'_, Arc<SemanticWikiService>>,
-) -> Result<WikiStats, String> {
+) -> AppResult<WikiStats> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .get_stats()
             .map_err(|e| format!("Failed to get wiki stats: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 /// Update wiki configuration
@@ -95,25 +163,71 @@ pub async fn wiki_get_stats(
 pub async fn wiki_update_config(
     config: SemanticWikiConfig,
     service: State<'_, Arc<SemanticWikiService>>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone.update_config(config);
-        Ok(())
+        Ok::<_, String>(())
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 /// Get current configuration
 #[tauri::command]
 pub async fn wiki_get_config(
     service: State<'_, Arc<SemanticWikiService>>,
-) -> Result<SemanticWikiConfig, String> {
+) -> AppResult<SemanticWikiConfig> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        Ok::<_, String>(service_clone.get_config())
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// List contradicting facts flagged on store
+#[tauri::command]
+pub async fn wiki_list_conflicts(
+    include_resolved: Option<bool>,
+    service: State<'_, Arc<SemanticWikiService>>,
+) -> AppResult<Vec<WikiConflict>> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
-        Ok(service_clone.get_config())
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .list_conflicts(include_resolved.unwrap_or(false))
+            .map_err(|e| format!("Failed to list wiki conflicts: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Pick the winning fact of a conflict (None keeps both)
+#[tauri::command]
+pub async fn wiki_resolve_conflict(
+    conflict_id: String,
+    winner_fact_id: Option<String>,
+    note: Option<String>,
+    service: State<'_, Arc<SemanticWikiService>>,
+) -> AppResult<WikiConflict> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .resolve_conflict(&conflict_id, winner_fact_id.as_deref(), note.as_deref())
+            .map_err(|e| format!("Failed to resolve wiki conflict: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Let the LLM adjudicate a conflict
+#[tauri::command]
+pub async fn wiki_adjudicate_conflict(
+    conflict_id: String,
+    service: State<'_, Arc<SemanticWikiService>>,
+) -> AppResult<WikiConflict> {
+    Ok(service
+        .adjudicate_conflict(&conflict_id)
+        .await
+        .map_err(|e| format!("Failed to adjudicate wiki conflict: {}", e))?)
 }
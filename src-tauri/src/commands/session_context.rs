@@ -0,0 +1,80 @@
+/**
+ * Session Context Commands (v3.9.0)
+ *
+ * Share chat memories with computer control, fill forms from them, and
+ * review which memories were used
+ */
+
+use crate::services::computer_control::ComputerControlService;
+use crate::services::session_context::{
+    ContextAuditEntry, FormField, FormFillResult, SessionContextBridge, SharedContext,
+};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Retrieve memories for a chat request and open a context session with them
+#[tauri::command]
+pub async fn computer_context_share(
+    conversation_id: String,
+    request: String,
+    limit: Option<usize>,
+    bridge: State<'_, Arc<SessionContextBridge>>,
+) -> AppResult<SharedContext> {
+    Ok(bridge
+        .share_from_chat(&conversation_id, &request, limit)
+        .await
+        .map_err(|e| format!("Failed to share chat context: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn computer_context_get(
+    session_id: String,
+    bridge: State<'_, Arc<SessionContextBridge>>,
+) -> AppResult<SharedContext> {
+    Ok(bridge
+        .get_session(&session_id)
+        .map_err(|e| format!("Failed to get context session: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn computer_context_close(
+    session_id: String,
+    bridge: State<'_, Arc<SessionContextBridge>>,
+) -> AppResult<bool> {
+    Ok(bridge
+        .close_session(&session_id)
+        .map_err(|e| format!("Failed to close context session: {}", e))?)
+}
+
+/// Fill form fields with values from the session's memories, optionally pressing Enter afterwards
+#[tauri::command]
+pub async fn computer_context_fill_form(
+    session_id: String,
+    fields: Vec<FormField>,
+    submit: Option<bool>,
+    bridge: State<'_, Arc<SessionContextBridge>>,
+    computer: State<'_, Arc<ComputerControlService>>,
+) -> AppResult<FormFillResult> {
+    Ok(bridge
+        .fill_form(&computer, &session_id, fields, submit.unwrap_or(false))
+        .await
+        .map_err(|e| format!("Failed to fill form: {}", e))?)
+}
+
+/// Audit of memories shared with and used by computer control, newest first
+#[tauri::command]
+pub async fn computer_context_audit(
+    conversation_id: Option<String>,
+    limit: Option<usize>,
+    bridge: State<'_, Arc<SessionContextBridge>>,
+) -> AppResult<Vec<ContextAuditEntry>> {
+    let bridge_clone = Arc::clone(&bridge.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        bridge_clone
+            .list_audit(conversation_id.as_deref(), limit.unwrap_or(50))
+            .map_err(|e| format!("Failed to get context audit: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
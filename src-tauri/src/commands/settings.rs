@@ -1,7 +1,11 @@
+use crate::AppError;
+use crate::AppResult;
 use crate::AppState;
 use crate::database::models::PersonaSettings;
 use crate::services::model_recommender::{ModelOption, ModelInfo, ModelRecommenderService};
 use crate::services::system_info::SystemInfoService;
+use crate::services::vector_backend::VectorBackendConfig;
+use crate::services::vision_backend::{self, VisionModelConfig, VISION_CONFIG_KEY};
 use serde::{Deserialize, Serialize};
 use tauri::State;
 
@@ -14,66 +18,67 @@ pub struct Settings {
 
 /// Get current settings
 #[tauri::command]
-pub async fn get_settings(state: State<'_, AppState>) -> Result<Settings, String> {
+pub async fn get_settings(state: State<'_, AppState>) -> AppResult<Settings> {
     log::info!("Getting settings");
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
-
-    // Get persona settings (v3.8.0: 10 standardized parameters)
-    let persona: PersonaSettings = conn
-        .query_row(
-            "SELECT id, formality, verbosity, humor, emoji_usage,
-                    empathy, creativity, proactiveness,
-                    technical_depth, code_examples, questioning,
-                    created_at, updated_at
-             FROM persona_settings
-             ORDER BY id DESC
-             LIMIT 1",
-            [],
-            |row| {
-                Ok(PersonaSettings {
-                    id: Some(row.get(0)?),
-                    formality: row.get(1)?,
-                    verbosity: row.get(2)?,
-                    humor: row.get(3)?,
-                    emoji_usage: row.get(4)?,
-                    empathy: row.get(5)?,
-                    creativity: row.get(6)?,
-                    proactiveness: row.get(7)?,
-                    technical_depth: row.get(8)?,
-                    code_examples: row.get(9)?,
-                    questioning: row.get(10)?,
-                    created_at: row.get(11)?,
-                    updated_at: row.get(12)?,
-                })
-            },
-        )
-        .map_err(|e| e.to_string())?;
-
-    // Get theme preference
-    let theme: String = conn
-        .query_row(
-            "SELECT value FROM user_preferences WHERE key = 'theme'",
-            [],
-            |row| row.get(0),
-        )
-        .unwrap_or_else(|_| "system".to_string());
-
-    // Get language preference
-    let language: String = conn
-        .query_row(
-            "SELECT value FROM user_preferences WHERE key = 'language'",
-            [],
-            |row| row.get(0),
-        )
-        .unwrap_or_else(|_| "auto".to_string());
-
-    Ok(Settings {
-        persona,
-        theme,
-        language,
-    })
+    state.db.call(move |db| {
+        let conn = db.conn();
+
+        // Get persona settings (v3.8.0: 10 standardized parameters)
+        let persona: PersonaSettings = conn
+            .query_row(
+                "SELECT id, formality, verbosity, humor, emoji_usage,
+                        empathy, creativity, proactiveness,
+                        technical_depth, code_examples, questioning,
+                        created_at, updated_at
+                 FROM persona_settings
+                 ORDER BY id DESC
+                 LIMIT 1",
+                [],
+                |row| {
+                    Ok(PersonaSettings {
+                        id: Some(row.get(0)?),
+                        formality: row.get(1)?,
+                        verbosity: row.get(2)?,
+                        humor: row.get(3)?,
+                        emoji_usage: row.get(4)?,
+                        empathy: row.get(5)?,
+                        creativity: row.get(6)?,
+                        proactiveness: row.get(7)?,
+                        technical_depth: row.get(8)?,
+                        code_examples: row.get(9)?,
+                        questioning: row.get(10)?,
+                        created_at: row.get(11)?,
+                        updated_at: row.get(12)?,
+                    })
+                },
+            )
+            .map_err(|e| e.to_string())?;
+
+        // Get theme preference
+        let theme: String = conn
+            .query_row(
+                "SELECT value FROM user_preferences WHERE key = 'theme'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or_else(|_| "system".to_string());
+
+        // Get language preference
+        let language: String = conn
+            .query_row(
+                "SELECT value FROM user_preferences WHERE key = 'language'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or_else(|_| "auto".to_string());
+
+        Ok(Settings {
+            persona,
+            theme,
+            language,
+        })
+    }).await
 }
 
 /// Update settings
@@ -81,58 +86,59 @@ pub async fn get_settings(state: State<'_, AppState>) -> Result<Settings, String
 pub async fn update_settings(
     state: State<'_, AppState>,
     settings: Settings,
-) -> Result<(), String> {
+) -> AppResult<()> {
     log::info!("Updating settings");
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
-    let now = chrono::Utc::now().timestamp_millis();
+    state.db.call(move |db| {
+        let conn = db.conn();
+        let now = chrono::Utc::now().timestamp_millis();
+
+        // Update persona settings (v3.8.0: 10 standardized parameters)
+        if let Some(_id) = settings.persona.id {
+            conn.execute(
+                "UPDATE persona_settings SET
+                    formality = ?1, verbosity = ?2, humor = ?3, emoji_usage = ?4,
+                    empathy = ?5, creativity = ?6, proactiveness = ?7,
+                    technical_depth = ?8, code_examples = ?9, questioning = ?10,
+                    updated_at = ?11
+                 WHERE id = (SELECT id FROM persona_settings ORDER BY id DESC LIMIT 1)",
+                rusqlite::params![
+                    settings.persona.formality,
+                    settings.persona.verbosity,
+                    settings.persona.humor,
+                    settings.persona.emoji_usage,
+                    settings.persona.empathy,
+                    settings.persona.creativity,
+                    settings.persona.proactiveness,
+                    settings.persona.technical_depth,
+                    settings.persona.code_examples,
+                    settings.persona.questioning,
+                    now,
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+
+            log::info!("Persona settings updated via settings panel");
+        }
 
-    // Update persona settings (v3.8.0: 10 standardized parameters)
-    if let Some(_id) = settings.persona.id {
+        // Update theme
         conn.execute(
-            "UPDATE persona_settings SET
-                formality = ?1, verbosity = ?2, humor = ?3, emoji_usage = ?4,
-                empathy = ?5, creativity = ?6, proactiveness = ?7,
-                technical_depth = ?8, code_examples = ?9, questioning = ?10,
-                updated_at = ?11
-             WHERE id = (SELECT id FROM persona_settings ORDER BY id DESC LIMIT 1)",
-            rusqlite::params![
-                settings.persona.formality,
-                settings.persona.verbosity,
-                settings.persona.humor,
-                settings.persona.emoji_usage,
-                settings.persona.empathy,
-                settings.persona.creativity,
-                settings.persona.proactiveness,
-                settings.persona.technical_depth,
-                settings.persona.code_examples,
-                settings.persona.questioning,
-                now,
-            ],
+            "INSERT OR REPLACE INTO user_preferences (key, value, updated_at)
+             VALUES ('theme', ?1, ?2)",
+            rusqlite::params![settings.theme, now],
         )
         .map_err(|e| e.to_string())?;
 
-        log::info!("Persona settings updated via settings panel");
-    }
-
-    // Update theme
-    conn.execute(
-        "INSERT OR REPLACE INTO user_preferences (key, value, updated_at)
-         VALUES ('theme', ?1, ?2)",
-        rusqlite::params![settings.theme, now],
-    )
-    .map_err(|e| e.to_string())?;
-
-    // Update language
-    conn.execute(
-        "INSERT OR REPLACE INTO user_preferences (key, value, updated_at)
-         VALUES ('language', ?1, ?2)",
-        rusqlite::params![settings.language, now],
-    )
-    .map_err(|e| e.to_string())?;
+        // Update language
+        conn.execute(
+            "INSERT OR REPLACE INTO user_preferences (key, value, updated_at)
+             VALUES ('language', ?1, ?2)",
+            rusqlite::params![settings.language, now],
+        )
+        .map_err(|e| e.to_string())?;
 
-    Ok(())
+        Ok(())
+    }).await
 }
 
 // ============================================================================
@@ -143,7 +149,7 @@ pub async fn update_settings(
 #[tauri::command]
 pub fn get_available_models_for_system(
     language_preference: String,
-) -> Result<Vec<ModelOption>, String> {
+) -> AppResult<Vec<ModelOption>> {
     log::info!("Getting available models for language: {}", language_preference);
 
     // Detect system specs
@@ -160,7 +166,7 @@ pub fn get_available_models_for_system(
 
 /// Get currently active LLM model from Ollama
 #[tauri::command]
-pub async fn get_current_llm_model() -> Result<String, String> {
+pub async fn get_current_llm_model() -> AppResult<String> {
     log::info!("Getting current LLM model");
 
     let model = ModelRecommenderService::get_current_model()
@@ -175,32 +181,33 @@ pub async fn get_current_llm_model() -> Result<String, String> {
 pub async fn switch_llm_model(
     state: State<'_, AppState>,
     model_name: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     log::info!("Switching to model: {}", model_name);
 
     // Validate model name
     if !ModelRecommenderService::is_valid_model(&model_name) {
-        return Err(format!("Invalid model name: {}", model_name));
+        return Err(AppError::invalid_input(format!("Invalid model name: {}", model_name)));
     }
 
     // Save selected model to database
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
-    let now = chrono::Utc::now().timestamp_millis();
+    state.db.call(move |db| {
+        let conn = db.conn();
+        let now = chrono::Utc::now().timestamp_millis();
 
-    conn.execute(
-        "INSERT OR REPLACE INTO user_preferences (key, value, updated_at)
-         VALUES ('active_llm_model', ?1, ?2)",
-        rusqlite::params![model_name, now],
-    )
-    .map_err(|e| e.to_string())?;
+        conn.execute(
+            "INSERT OR REPLACE INTO user_preferences (key, value, updated_at)
+             VALUES ('active_llm_model', ?1, ?2)",
+            rusqlite::params![model_name, now],
+        )
+        .map_err(|e| e.to_string())?;
 
-    Ok(())
+        Ok(())
+    }).await
 }
 
 /// List all downloaded Ollama models with sizes
 #[tauri::command]
-pub async fn list_ollama_models() -> Result<Vec<ModelInfo>, String> {
+pub async fn list_ollama_models() -> AppResult<Vec<ModelInfo>> {
     log::info!("Listing all downloaded Ollama models");
 
     let models = ModelRecommenderService::list_downloaded_models()
@@ -212,7 +219,7 @@ pub async fn list_ollama_models() -> Result<Vec<ModelInfo>, String> {
 
 /// Delete an Ollama model
 #[tauri::command]
-pub async fn delete_ollama_model(model_name: String) -> Result<(), String> {
+pub async fn delete_ollama_model(model_name: String) -> AppResult<()> {
     log::info!("Deleting model: {}", model_name);
 
     ModelRecommenderService::delete_model(&model_name)
@@ -224,7 +231,7 @@ pub async fn delete_ollama_model(model_name: String) -> Result<(), String> {
 
 /// Get size of a specific Ollama model
 #[tauri::command]
-pub async fn get_ollama_model_size(model_name: String) -> Result<f32, String> {
+pub async fn get_ollama_model_size(model_name: String) -> AppResult<f32> {
     log::info!("Getting size of model: {}", model_name);
 
     let models = ModelRecommenderService::list_downloaded_models()
@@ -234,7 +241,7 @@ pub async fn get_ollama_model_size(model_name: String) -> Result<f32, String> {
     let model = models
         .iter()
         .find(|m| m.name == model_name)
-        .ok_or_else(|| format!("Model not found: {}", model_name))?;
+        .ok_or_else(|| AppError::not_found(format!("Model not found: {}", model_name)))?;
 
     Ok(model.size_gb)
 }
@@ -337,31 +344,32 @@ impl Default for Phase5Settings {
 
 /// Get Phase 5 settings
 #[tauri::command]
-pub async fn get_phase5_settings(state: State<'_, AppState>) -> Result<Phase5Settings, String> {
+pub async fn get_phase5_settings(state: State<'_, AppState>) -> AppResult<Phase5Settings> {
     log::info!("Getting Phase 5 settings");
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
-
-    // Try to load from database, use defaults if not found
-    let settings_json: Result<String, _> = conn.query_row(
-        "SELECT value FROM user_preferences WHERE key = 'phase5_settings'",
-        [],
-        |row| row.get(0),
-    );
-
-    match settings_json {
-        Ok(json) => {
-            serde_json::from_str(&json).map_err(|e| {
-                log::warn!("Failed to parse Phase5 settings, using defaults: {}", e);
-                e.to_string()
-            })
-        }
-        Err(_) => {
-            log::info!("No Phase5 settings found, returning defaults");
-            Ok(Phase5Settings::default())
+    state.db.call(move |db| {
+        let conn = db.conn();
+
+        // Try to load from database, use defaults if not found
+        let settings_json: Result<String, _> = conn.query_row(
+            "SELECT value FROM user_preferences WHERE key = 'phase5_settings'",
+            [],
+            |row| row.get(0),
+        );
+
+        match settings_json {
+            Ok(json) => {
+                Ok(serde_json::from_str(&json).map_err(|e| {
+                    log::warn!("Failed to parse Phase5 settings, using defaults: {}", e);
+                    e.to_string()
+                })?)
+            }
+            Err(_) => {
+                log::info!("No Phase5 settings found, returning defaults");
+                Ok(Phase5Settings::default())
+            }
         }
-    }
+    }).await
 }
 
 /// Update Phase 5 settings
@@ -369,22 +377,88 @@ pub async fn get_phase5_settings(state: State<'_, AppState>) -> Result<Phase5Set
 pub async fn update_phase5_settings(
     state: State<'_, AppState>,
     settings: Phase5Settings,
-) -> Result<(), String> {
+) -> AppResult<()> {
     log::info!("Updating Phase 5 settings");
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
-    let now = chrono::Utc::now().timestamp_millis();
+    state.db.call(move |db| {
+        let conn = db.conn();
+        let now = chrono::Utc::now().timestamp_millis();
 
-    let settings_json = serde_json::to_string(&settings).map_err(|e| e.to_string())?;
+        let settings_json = serde_json::to_string(&settings).map_err(|e| e.to_string())?;
 
-    conn.execute(
-        "INSERT OR REPLACE INTO user_preferences (key, value, updated_at)
-         VALUES ('phase5_settings', ?1, ?2)",
-        rusqlite::params![settings_json, now],
-    )
-    .map_err(|e| e.to_string())?;
+        conn.execute(
+            "INSERT OR REPLACE INTO user_preferences (key, value, updated_at)
+             VALUES ('phase5_settings', ?1, ?2)",
+            rusqlite::params![settings_json, now],
+        )
+        .map_err(|e| e.to_string())?;
 
-    log::info!("Phase 5 settings saved successfully");
-    Ok(())
+        log::info!("Phase 5 settings saved successfully");
+        Ok(())
+    }).await
+}
+
+// ============================================================================
+// Vision Model Settings (v3.9.0)
+// ============================================================================
+
+/// Get the vision models used for fast and detailed image tasks
+#[tauri::command]
+pub async fn get_vision_model_config() -> AppResult<VisionModelConfig> {
+    Ok(vision_backend::current_config())
+}
+
+/// Update the vision models (llava variants, qwen2-vl, moondream) per task
+#[tauri::command]
+pub async fn update_vision_model_config(
+    state: State<'_, AppState>,
+    config: VisionModelConfig,
+) -> AppResult<()> {
+    log::info!("Updating vision model config: {:?}", config);
+
+    vision_backend::set_config(config.clone()).map_err(|e| e.to_string())?;
+
+    state.db.call(move |db| {
+        let conn = db.conn();
+        let now = chrono::Utc::now().timestamp_millis();
+
+        let config_json = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+
+        conn.execute(
+            "INSERT OR REPLACE INTO user_preferences (key, value, updated_at)
+             VALUES (?1, ?2, ?3)",
+            rusqlite::params![VISION_CONFIG_KEY, config_json, now],
+        )
+        .map_err(|e| e.to_string())?;
+
+        log::info!("Vision model config saved successfully");
+        Ok(())
+    }).await
+}
+
+// ============================================================================
+// Vector Store Backend (v3.9.0)
+// ============================================================================
+
+/// Get the vector store episodic memory is searched in
+#[tauri::command]
+pub async fn get_vector_backend_config(state: State<'_, AppState>) -> AppResult<VectorBackendConfig> {
+    Ok(state.rag.vector_backend_config().map_err(|e| e.to_string())?)
+}
+
+/// Switch episodic memory to LanceDB, SQLite or a Qdrant server
+///
+/// With `reindex`, existing memories are re-embedded into the new store;
+/// returns the number of vectors written.
+#[tauri::command]
+pub async fn update_vector_backend_config(
+    state: State<'_, AppState>,
+    config: VectorBackendConfig,
+    reindex: Option<bool>,
+) -> AppResult<usize> {
+    log::info!("Updating vector backend: {:?}", config.backend);
+
+    Ok(state.rag.switch_vector_backend(config, reindex.unwrap_or(true))
+        .await
+        .map_err(|e| format!("Failed to switch vector store: {}", e))?)
 }
@@ -0,0 +1,116 @@
+/**
+ * SQL Query Commands (v3.9.0)
+ *
+ * Configuring databases for the read-only sql_query tool and running queries
+ */
+
+use crate::services::degradation::ServiceSlot;
+use crate::services::sql_query::{
+    SqlConnectionInfo, SqlEngine, SqlQueryHistoryEntry, SqlQueryResult, SqlQueryService, TableSchema,
+};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Add a database: a file path for SQLite, a connection URL for Postgres / MySQL
+///
+/// URLs are stored in the secrets vault; the schema is read right away.
+/// Server URLs should log in as a role with SELECT-only grants: the query
+/// checks can't rule out every server function with side effects.
+#[tauri::command]
+pub async fn sql_add_connection(
+    name: String,
+    engine: SqlEngine,
+    target: String,
+    service: State<'_, Arc<ServiceSlot<SqlQueryService>>>,
+) -> AppResult<SqlConnectionInfo> {
+    let service = service.get()?;
+    let service_clone = Arc::clone(&service);
+    let mut info = tokio::task::spawn_blocking(move || {
+        service_clone
+            .add_connection(&name, engine, &target)
+            .map_err(|e| format!("Failed to add database: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+
+    match service.refresh_schema(&info.id).await {
+        Ok(tables) => {
+            info.tables = tables;
+            info.schema_refreshed_at = Some(chrono::Utc::now().timestamp_millis());
+        }
+        Err(e) => log::warn!("Added database '{}' but couldn't read its schema: {}", info.name, e),
+    }
+    Ok(info)
+}
+
+/// Configured databases with their cached schemas
+#[tauri::command]
+pub async fn sql_list_connections(service: State<'_, Arc<ServiceSlot<SqlQueryService>>>) -> AppResult<Vec<SqlConnectionInfo>> {
+    let service_clone = service.get()?;
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .list_connections()
+            .map_err(|e| format!("Failed to list databases: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Remove a database, its stored URL and its query history
+#[tauri::command]
+pub async fn sql_remove_connection(connection_id: String, service: State<'_, Arc<ServiceSlot<SqlQueryService>>>) -> AppResult<()> {
+    let service_clone = service.get()?;
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .remove_connection(&connection_id)
+            .map_err(|e| format!("Failed to remove database: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Read a database's tables and columns again
+#[tauri::command]
+pub async fn sql_refresh_schema(
+    connection_id: String,
+    service: State<'_, Arc<ServiceSlot<SqlQueryService>>>,
+) -> AppResult<Vec<TableSchema>> {
+    let service = service.get()?;
+    Ok(service
+        .refresh_schema(&connection_id)
+        .await
+        .map_err(|e| format!("Failed to read schema: {}", e))?)
+}
+
+/// Run a read-only query from the UI (recorded in the history like tool calls)
+#[tauri::command]
+pub async fn sql_run_query(
+    connection_id: String,
+    query: String,
+    limit: Option<usize>,
+    service: State<'_, Arc<ServiceSlot<SqlQueryService>>>,
+) -> AppResult<SqlQueryResult> {
+    let service = service.get()?;
+    Ok(service
+        .run_query(&connection_id, &query, limit)
+        .await
+        .map_err(|e| format!("Query failed: {}", e))?)
+}
+
+/// Recent queries, newest first, optionally for one database
+#[tauri::command]
+pub async fn sql_query_history(
+    connection_id: Option<String>,
+    limit: Option<usize>,
+    service: State<'_, Arc<ServiceSlot<SqlQueryService>>>,
+) -> AppResult<Vec<SqlQueryHistoryEntry>> {
+    let service_clone = service.get()?;
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .history(connection_id.as_deref(), limit.unwrap_or(50).min(500))
+            .map_err(|e| format!("Failed to load query history: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
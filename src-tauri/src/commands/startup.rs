@@ -0,0 +1,15 @@
+/**
+ * Startup Report Commands (v3.9.0)
+ *
+ * Per-service initialization timings of the current launch
+ */
+
+use crate::services::startup::{self, StartupReport};
+use crate::AppResult;
+
+/// Per-service timings of this launch, including lazy services initialized since
+#[tauri::command]
+pub async fn startup_report() -> AppResult<StartupReport> {
+    log::info!("Command: startup_report");
+    Ok(startup::report())
+}
@@ -1,3 +1,4 @@
+use crate::AppResult;
 use serde::{Deserialize, Serialize};
 use std::env;
 
@@ -11,7 +12,7 @@ pub struct SystemInfo {
 
 /// Get system information
 #[tauri::command]
-pub async fn get_system_info() -> Result<SystemInfo, String> {
+pub async fn get_system_info() -> AppResult<SystemInfo> {
     log::info!("Getting system info");
 
     let platform = env::consts::OS.to_string();
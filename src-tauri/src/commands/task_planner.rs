@@ -2,20 +2,29 @@
  * Phase 5 Stage 4: Task Planner Commands (v3.9.0)
  */
 
+use crate::services::calendar::{CalendarEvent, EventDateTime};
 use crate::services::task_planner::{
-    ExecutionPlan, Task, TaskBreakdown, TaskPlannerService, TaskStatus,
+    DependencyGraph, ExecutionPlan, NewRecurringTask, RecurringTask, ScheduleEvent, Task,
+    TaskBreakdown, TaskPlannerService, TaskSchedule, TaskStatus,
 };
+use crate::AppResult;
+use crate::AppState;
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
 use std::sync::Arc;
 use tauri::State;
 
+/// Schedule view length when the frontend doesn't ask for one
+const DEFAULT_SCHEDULE_DAYS: u32 = 7;
+const MAX_SCHEDULE_DAYS: u32 = 31;
+
 #[tauri::command]
 pub async fn task_decompose(
     description: String,
     context: Option<String>,
     service: State<'_, Arc<TaskPlannerService>>,
-) -> Result<TaskBreakdown, String> {
+) -> AppResult<TaskBreakdown> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         let rt = tokio::runtime::Runtime::new().unwrap();
         rt.block_on(async move {
             service_clone
@@ -25,37 +34,37 @@ pub async fn task_decompose(
         })
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
 pub async fn task_create(
     task: Task,
     service: State<'_, Arc<TaskPlannerService>>,
-) -> Result<String, String> {
+) -> AppResult<String> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .create_task(task)
             .map_err(|e| format!("Failed to create task: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
 pub async fn task_get(
     task_id: String,
     service: State<'_, Arc<TaskPlannerService>>,
-) -> Result<Task, String> {
+) -> AppResult<Task> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .get_task(&task_id)
             .map_err(|e| format!("Failed to get task: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
@@ -63,15 +72,15 @@ pub async fn task_update_status(
     task_id: String,
     status: TaskStatus,
     service: State<'_, Arc<TaskPlannerService>>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .update_task_status(&task_id, status)
             .map_err(|e| format!("Failed to update task status: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
@@ -79,73 +88,244 @@ pub async fn task_update_progress(
     task_id: String,
     progress: f32,
     service: State<'_, Arc<TaskPlannerService>>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .update_task_progress(&task_id, progress)
             .map_err(|e| format!("Failed to update task progress: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
 pub async fn task_generate_execution_plan(
     root_task_id: String,
     service: State<'_, Arc<TaskPlannerService>>,
-) -> Result<ExecutionPlan, String> {
+) -> AppResult<ExecutionPlan> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .generate_execution_plan(&root_task_id)
             .map_err(|e| format!("Failed to generate execution plan: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
 pub async fn task_get_subtasks(
     parent_id: String,
     service: State<'_, Arc<TaskPlannerService>>,
-) -> Result<Vec<Task>, String> {
+) -> AppResult<Vec<Task>> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .get_subtasks(&parent_id)
             .map_err(|e| format!("Failed to get subtasks: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
 pub async fn task_get_all(
     status_filter: Option<TaskStatus>,
     service: State<'_, Arc<TaskPlannerService>>,
-) -> Result<Vec<Task>, String> {
+) -> AppResult<Vec<Task>> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .get_all_tasks(status_filter)
             .map_err(|e| format!("Failed to get tasks: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
 }
 
 #[tauri::command]
 pub async fn task_delete(
     task_id: String,
     service: State<'_, Arc<TaskPlannerService>>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let service_clone = Arc::clone(&service.inner());
-    tokio::task::spawn_blocking(move || {
+    Ok(tokio::task::spawn_blocking(move || {
         service_clone
             .delete_task(&task_id)
             .map_err(|e| format!("Failed to delete task: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn task_add_dependency(
+    task_id: String,
+    depends_on: String,
+    service: State<'_, Arc<TaskPlannerService>>,
+) -> AppResult<Task> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .add_dependency(&task_id, &depends_on)
+            .map_err(|e| format!("Failed to add dependency: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn task_remove_dependency(
+    task_id: String,
+    depends_on: String,
+    service: State<'_, Arc<TaskPlannerService>>,
+) -> AppResult<Task> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .remove_dependency(&task_id, &depends_on)
+            .map_err(|e| format!("Failed to remove dependency: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn task_get_dependency_graph(
+    root_task_id: Option<String>,
+    service: State<'_, Arc<TaskPlannerService>>,
+) -> AppResult<DependencyGraph> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .get_dependency_graph(root_task_id.as_deref())
+            .map_err(|e| format!("Failed to get dependency graph: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Create a recurring task; an instance due within a day is generated right away
+#[tauri::command]
+pub async fn task_create_recurring(
+    recurring: NewRecurringTask,
+    service: State<'_, Arc<TaskPlannerService>>,
+) -> AppResult<RecurringTask> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        let created = service_clone
+            .create_recurring_task(recurring)
+            .map_err(|e| format!("Failed to create recurring task: {}", e))?;
+        service_clone
+            .generate_recurring_tasks(Utc::now().timestamp())
+            .map_err(|e| format!("Failed to generate recurring tasks: {}", e))?;
+        Ok::<_, String>(created)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn task_list_recurring(
+    service: State<'_, Arc<TaskPlannerService>>,
+) -> AppResult<Vec<RecurringTask>> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .list_recurring_tasks()
+            .map_err(|e| format!("Failed to list recurring tasks: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn task_set_recurring_active(
+    recurrence_id: String,
+    active: bool,
+    service: State<'_, Arc<TaskPlannerService>>,
+) -> AppResult<bool> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .set_recurring_task_active(&recurrence_id, active)
+            .map_err(|e| format!("Failed to update recurring task: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn task_delete_recurring(
+    recurrence_id: String,
+    service: State<'_, Arc<TaskPlannerService>>,
+) -> AppResult<bool> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .delete_recurring_task(&recurrence_id)
+            .map_err(|e| format!("Failed to delete recurring task: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Due dates for the coming days laid out around calendar events.
+/// Works without a connected calendar (all working hours count as free).
+#[tauri::command]
+pub async fn task_get_schedule(
+    days: Option<u32>,
+    service: State<'_, Arc<TaskPlannerService>>,
+    state: State<'_, AppState>,
+) -> AppResult<TaskSchedule> {
+    let days = days.unwrap_or(DEFAULT_SCHEDULE_DAYS).clamp(1, MAX_SCHEDULE_DAYS);
+    let now = Utc::now();
+
+    // Clone out of the lock: the calendar API is async
+    let calendar = state.calendar_service.service.lock().ok().and_then(|guard| guard.clone());
+    let (events, calendar_connected) = match calendar {
+        Some(calendar) => {
+            let time_max = now + chrono::Duration::days(days as i64 + 1);
+            match calendar.list_events("primary", Some(now), Some(time_max), Some(250)).await {
+                Ok(events) => (events.iter().filter_map(schedule_event).collect(), true),
+                Err(e) => {
+                    log::warn!("Schedule built without calendar events: {}", e);
+                    (Vec::new(), false)
+                }
+            }
+        }
+        None => (Vec::new(), false),
+    };
+
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .build_schedule(events, calendar_connected, now.timestamp(), days)
+            .map_err(|e| format!("Failed to build schedule: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Calendar event times as Unix seconds; all-day events use local midnight
+fn schedule_event(event: &CalendarEvent) -> Option<ScheduleEvent> {
+    let all_day = event.start.date_time.is_none();
+    Some(ScheduleEvent {
+        title: event.summary.clone(),
+        start: event_timestamp(&event.start)?,
+        end: event_timestamp(&event.end)?,
+        all_day,
+    })
+}
+
+fn event_timestamp(time: &EventDateTime) -> Option<i64> {
+    if let Some(date_time) = &time.date_time {
+        return DateTime::parse_from_rfc3339(date_time).ok().map(|t| t.timestamp());
+    }
+    let date = NaiveDate::parse_from_str(time.date.as_deref()?, "%Y-%m-%d").ok()?;
+    Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()
+        .map(|t| t.timestamp())
 }
@@ -7,6 +7,7 @@
 use crate::services::temporal_memory::{
     TemporalMemoryService, DecayConfig, RetentionStats, RetentionForecast,
 };
+use crate::AppResult;
 use std::sync::Arc;
 use tauri::State;
 
@@ -15,8 +16,8 @@ use tauri::State;
 pub async fn temporal_pin_memory(
     memory_id: String,
     service: State<'_, Arc<TemporalMemoryService>>,
-) -> Result<(), String> {
-    service.pin_memory(&memory_id).map_err(|e| e.to_string())
+) -> AppResult<()> {
+    Ok(service.pin_memory(&memory_id).map_err(|e| e.to_string())?)
 }
 
 /// Unpin a memory (resume normal decay)
@@ -24,31 +25,31 @@ pub async fn temporal_pin_memory(
 pub async fn temporal_unpin_memory(
     memory_id: String,
     service: State<'_, Arc<TemporalMemoryService>>,
-) -> Result<(), String> {
-    service.unpin_memory(&memory_id).map_err(|e| e.to_string())
+) -> AppResult<()> {
+    Ok(service.unpin_memory(&memory_id).map_err(|e| e.to_string())?)
 }
 
 /// Get retention statistics
 #[tauri::command]
 pub async fn temporal_get_retention_stats(
     service: State<'_, Arc<TemporalMemoryService>>,
-) -> Result<RetentionStats, String> {
-    service.get_retention_stats().map_err(|e| e.to_string())
+) -> AppResult<RetentionStats> {
+    Ok(service.get_retention_stats().map_err(|e| e.to_string())?)
 }
 
 /// Manually trigger decay update (for testing or admin)
 #[tauri::command]
 pub async fn temporal_trigger_decay_update(
     service: State<'_, Arc<TemporalMemoryService>>,
-) -> Result<usize, String> {
-    service.update_all_retention_scores().map_err(|e| e.to_string())
+) -> AppResult<usize> {
+    Ok(service.update_all_retention_scores().map_err(|e| e.to_string())?)
 }
 
 /// Get current decay configuration
 #[tauri::command]
 pub async fn temporal_get_config(
     service: State<'_, Arc<TemporalMemoryService>>,
-) -> Result<DecayConfig, String> {
+) -> AppResult<DecayConfig> {
     Ok(service.get_config())
 }
 
@@ -57,8 +58,8 @@ pub async fn temporal_get_config(
 pub async fn temporal_update_config(
     config: DecayConfig,
     service: State<'_, Arc<TemporalMemoryService>>,
-) -> Result<(), String> {
-    service.update_config(config).map_err(|e| e.to_string())
+) -> AppResult<()> {
+    Ok(service.update_config(config).map_err(|e| e.to_string())?)
 }
 
 /// Prune low retention memories below threshold
@@ -66,8 +67,8 @@ pub async fn temporal_update_config(
 pub async fn temporal_prune_memories(
     threshold: f64,
     service: State<'_, Arc<TemporalMemoryService>>,
-) -> Result<usize, String> {
-    service.prune_low_retention_memories(threshold).map_err(|e| e.to_string())
+) -> AppResult<usize> {
+    Ok(service.prune_low_retention_memories(threshold).map_err(|e| e.to_string())?)
 }
 
 /// Calculate retention score for given parameters (for preview/testing)
@@ -78,7 +79,7 @@ pub async fn temporal_calculate_retention(
     access_count: i32,
     is_pinned: bool,
     service: State<'_, Arc<TemporalMemoryService>>,
-) -> Result<f64, String> {
+) -> AppResult<f64> {
     Ok(service.calculate_retention(days_elapsed, decay_strength, access_count, is_pinned))
 }
 
@@ -88,11 +89,11 @@ pub async fn temporal_set_memory_type(
     memory_id: String,
     memory_type: String,
     service: State<'_, Arc<TemporalMemoryService>>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     use crate::services::temporal_memory::MemoryType;
 
     let mem_type = MemoryType::from_str(&memory_type);
-    service.set_memory_type(&memory_id, mem_type).map_err(|e| e.to_string())
+    Ok(service.set_memory_type(&memory_id, mem_type).map_err(|e| e.to_string())?)
 }
 
 /// Get memory type
@@ -100,7 +101,7 @@ pub async fn temporal_set_memory_type(
 pub async fn temporal_get_memory_type(
     memory_id: String,
     service: State<'_, Arc<TemporalMemoryService>>,
-) -> Result<String, String> {
+) -> AppResult<String> {
     let mem_type = service.get_memory_type(&memory_id).map_err(|e| e.to_string())?;
     Ok(mem_type.as_str().to_string())
 }
@@ -112,7 +113,7 @@ pub async fn temporal_classify_memory(
     user_message: String,
     ai_response: String,
     service: State<'_, Arc<TemporalMemoryService>>,
-) -> Result<String, String> {
+) -> AppResult<String> {
     let mem_type = service
         .classify_and_set_memory_type(&memory_id, &user_message, &ai_response)
         .map_err(|e| e.to_string())?;
@@ -125,10 +126,10 @@ pub async fn temporal_forecast_retention(
     memory_id: String,
     days_ahead: f64,
     service: State<'_, Arc<TemporalMemoryService>>,
-) -> Result<RetentionForecast, String> {
-    service
+) -> AppResult<RetentionForecast> {
+    Ok(service
         .forecast_retention(&memory_id, days_ahead)
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
 
 /// Find memories at risk of decay
@@ -137,8 +138,8 @@ pub async fn temporal_find_at_risk_memories(
     days_ahead: f64,
     threshold: f64,
     service: State<'_, Arc<TemporalMemoryService>>,
-) -> Result<Vec<RetentionForecast>, String> {
-    service
+) -> AppResult<Vec<RetentionForecast>> {
+    Ok(service
         .find_at_risk_memories(days_ahead, threshold)
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
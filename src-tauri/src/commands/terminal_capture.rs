@@ -0,0 +1,115 @@
+/**
+ * Terminal Capture Commands (v3.9.0)
+ *
+ * Install the shell hook, browse captured commands and manage capture settings
+ */
+
+use crate::services::degradation::ServiceSlot;
+use crate::services::terminal_capture::{
+    Shell, ShellHook, TerminalCaptureConfig, TerminalCaptureService, TerminalCommand, TerminalQuery,
+    TerminalSession,
+};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Write the hook for `shell` (zsh, bash or fish) and return the line to add to the rc file
+#[tauri::command]
+pub async fn terminal_capture_install_hook(
+    shell: String,
+    service: State<'_, Arc<ServiceSlot<TerminalCaptureService>>>,
+) -> AppResult<ShellHook> {
+    let service = service.get()?;
+    log::info!("Command: terminal_capture_install_hook ({})", shell);
+
+    let shell = Shell::parse(&shell).map_err(|e| e.to_string())?;
+    Ok(tokio::task::spawn_blocking(move || {
+        service
+            .install_hook(shell)
+            .map_err(|e| format!("Failed to install shell hook: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn terminal_capture_list_sessions(
+    service: State<'_, Arc<ServiceSlot<TerminalCaptureService>>>,
+) -> AppResult<Vec<TerminalSession>> {
+    let service = service.get()?;
+    Ok(tokio::task::spawn_blocking(move || {
+        service
+            .list_sessions()
+            .map_err(|e| format!("Failed to list terminal sessions: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Recorded commands, most recent first
+#[tauri::command]
+pub async fn terminal_capture_get_commands(
+    query: Option<TerminalQuery>,
+    service: State<'_, Arc<ServiceSlot<TerminalCaptureService>>>,
+) -> AppResult<Vec<TerminalCommand>> {
+    let service = service.get()?;
+    Ok(tokio::task::spawn_blocking(move || {
+        service
+            .find(&query.unwrap_or_default())
+            .map_err(|e| format!("Failed to load terminal commands: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn terminal_capture_delete(
+    id: String,
+    service: State<'_, Arc<ServiceSlot<TerminalCaptureService>>>,
+) -> AppResult<bool> {
+    let service = service.get()?;
+    log::info!("Command: terminal_capture_delete");
+
+    Ok(service
+        .delete(&id)
+        .await
+        .map_err(|e| format!("Failed to delete terminal command: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn terminal_capture_clear(
+    service: State<'_, Arc<ServiceSlot<TerminalCaptureService>>>,
+) -> AppResult<usize> {
+    let service = service.get()?;
+    log::info!("Command: terminal_capture_clear");
+
+    Ok(service
+        .clear()
+        .await
+        .map_err(|e| format!("Failed to clear terminal history: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn terminal_capture_get_config(
+    service: State<'_, Arc<ServiceSlot<TerminalCaptureService>>>,
+) -> AppResult<TerminalCaptureConfig> {
+    let service = service.get()?;
+    Ok(service.get_config())
+}
+
+#[tauri::command]
+pub async fn terminal_capture_update_config(
+    config: TerminalCaptureConfig,
+    service: State<'_, Arc<ServiceSlot<TerminalCaptureService>>>,
+) -> AppResult<()> {
+    let service = service.get()?;
+    log::info!("Command: terminal_capture_update_config");
+
+    Ok(tokio::task::spawn_blocking(move || {
+        service
+            .update_config(config)
+            .map_err(|e| format!("Failed to update terminal capture config: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
@@ -0,0 +1,23 @@
+/**
+ * Tool Cache Commands (v3.9.0)
+ *
+ * Inspecting and clearing cached results of deterministic tools
+ */
+
+use crate::services::tool_cache::ToolCacheStats;
+use crate::AppResult;
+use crate::AppState;
+use tauri::State;
+
+/// Drop every cached tool result, returning how many were removed
+#[tauri::command]
+pub async fn tool_cache_clear(state: State<'_, AppState>) -> AppResult<usize> {
+    log::info!("Command: tool_cache_clear");
+    Ok(state.tool_service.clear_cache())
+}
+
+/// Cached entries and hit/miss counters
+#[tauri::command]
+pub async fn tool_cache_stats(state: State<'_, AppState>) -> AppResult<ToolCacheStats> {
+    Ok(state.tool_service.cache_stats())
+}
@@ -7,6 +7,7 @@ use tauri::State;
 use crate::services::tool_history::{
     ToolCallRecord, ToolHistoryFilters, ToolUsageStats,
 };
+use crate::AppResult;
 use crate::AppState;
 
 /// Get tool call history with optional filters
@@ -20,10 +21,10 @@ use crate::AppState;
 pub async fn get_tool_history(
     state: State<'_, AppState>,
     filters: Option<ToolHistoryFilters>,
-) -> Result<Vec<ToolCallRecord>, String> {
+) -> AppResult<Vec<ToolCallRecord>> {
     let service = state.tool_history_service.lock().await;
-    service.get_history(filters.unwrap_or_default())
-        .map_err(|e| format!("Failed to get tool history: {}", e))
+    Ok(service.get_history(filters.unwrap_or_default())
+        .map_err(|e| format!("Failed to get tool history: {}", e))?)
 }
 
 /// Get tool usage statistics
@@ -33,10 +34,10 @@ pub async fn get_tool_history(
 #[tauri::command]
 pub async fn get_tool_statistics(
     state: State<'_, AppState>,
-) -> Result<ToolUsageStats, String> {
+) -> AppResult<ToolUsageStats> {
     let service = state.tool_history_service.lock().await;
-    service.get_statistics()
-        .map_err(|e| format!("Failed to get tool statistics: {}", e))
+    Ok(service.get_statistics()
+        .map_err(|e| format!("Failed to get tool statistics: {}", e))?)
 }
 
 /// Export tool history to JSON or CSV string
@@ -52,7 +53,7 @@ pub async fn export_tool_history(
     state: State<'_, AppState>,
     format: String,
     filters: Option<ToolHistoryFilters>,
-) -> Result<String, String> {
+) -> AppResult<String> {
     let service = state.tool_history_service.lock().await;
 
     // Get records with filters
@@ -62,8 +63,8 @@ pub async fn export_tool_history(
     // Convert to string based on format
     match format.to_lowercase().as_str() {
         "json" => {
-            serde_json::to_string_pretty(&records)
-                .map_err(|e| format!("Failed to serialize to JSON: {}", e))
+            Ok(serde_json::to_string_pretty(&records)
+                .map_err(|e| format!("Failed to serialize to JSON: {}", e))?)
         }
         "csv" => {
             let mut csv_content = String::from("id,conversation_id,message_id,tool_name,execution_time_ms,status,created_at,error_message\n");
@@ -84,7 +85,7 @@ pub async fn export_tool_history(
 
             Ok(csv_content)
         }
-        _ => Err(format!("Unsupported export format: {}. Use 'json' or 'csv'", format))
+        _ => Err(format!("Unsupported export format: {}. Use 'json' or 'csv'", format).into())
     }
 }
 
@@ -99,10 +100,10 @@ pub async fn export_tool_history(
 pub async fn delete_tool_history(
     state: State<'_, AppState>,
     before_timestamp: i64,
-) -> Result<usize, String> {
+) -> AppResult<usize> {
     let service = state.tool_history_service.lock().await;
-    service.delete_history_before(before_timestamp)
-        .map_err(|e| format!("Failed to delete tool history: {}", e))
+    Ok(service.delete_history_before(before_timestamp)
+        .map_err(|e| format!("Failed to delete tool history: {}", e))?)
 }
 
 /// Get the most recently used tools
@@ -116,10 +117,10 @@ pub async fn delete_tool_history(
 pub async fn get_recent_tools(
     state: State<'_, AppState>,
     limit: usize,
-) -> Result<Vec<String>, String> {
+) -> AppResult<Vec<String>> {
     let service = state.tool_history_service.lock().await;
-    service.get_recent_tools(limit)
-        .map_err(|e| format!("Failed to get recent tools: {}", e))
+    Ok(service.get_recent_tools(limit)
+        .map_err(|e| format!("Failed to get recent tools: {}", e))?)
 }
 
 /// Get error rate for a specific tool
@@ -133,8 +134,8 @@ pub async fn get_recent_tools(
 pub async fn get_tool_error_rate(
     state: State<'_, AppState>,
     tool_name: String,
-) -> Result<f32, String> {
+) -> AppResult<f32> {
     let service = state.tool_history_service.lock().await;
-    service.get_tool_error_rate(&tool_name)
-        .map_err(|e| format!("Failed to get error rate for '{}': {}", tool_name, e))
+    Ok(service.get_tool_error_rate(&tool_name)
+        .map_err(|e| format!("Failed to get error rate for '{}': {}", tool_name, e))?)
 }
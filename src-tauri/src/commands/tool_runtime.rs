@@ -0,0 +1,62 @@
+/**
+ * Tool Runtime Commands (v3.9.0)
+ *
+ * Cancelling running tool calls and per-tool timeouts
+ */
+
+use crate::services::tool_runtime::{self, RunningToolCall, ToolTimeout};
+use crate::AppResult;
+use crate::AppState;
+use tauri::State;
+
+/// Cancel a running tool call by the `call_id` of its start event
+#[tauri::command]
+pub async fn tool_cancel(state: State<'_, AppState>, call_id: String) -> AppResult<bool> {
+    log::info!("Command: tool_cancel ({})", call_id);
+    Ok(state.tool_service.cancel_call(&call_id))
+}
+
+/// Cancel every running tool call, returning how many were cancelled
+#[tauri::command]
+pub async fn tool_cancel_all(state: State<'_, AppState>) -> AppResult<usize> {
+    log::info!("Command: tool_cancel_all");
+    Ok(state.tool_service.cancel_all_calls())
+}
+
+/// Tool calls in progress
+#[tauri::command]
+pub async fn tool_list_running(state: State<'_, AppState>) -> AppResult<Vec<RunningToolCall>> {
+    Ok(state.tool_service.running_calls())
+}
+
+/// Effective timeout of every tool
+#[tauri::command]
+pub async fn tool_get_timeouts(state: State<'_, AppState>) -> AppResult<Vec<ToolTimeout>> {
+    Ok(state.tool_service.timeouts())
+}
+
+/// Override a tool's timeout in seconds; `None` restores the tool's default
+#[tauri::command]
+pub async fn tool_set_timeout(
+    state: State<'_, AppState>,
+    tool_name: String,
+    timeout_secs: Option<u64>,
+) -> AppResult<Vec<ToolTimeout>> {
+    log::info!("Command: tool_set_timeout ({} -> {:?})", tool_name, timeout_secs);
+
+    state
+        .tool_service
+        .set_timeout(&tool_name, timeout_secs)
+        .map_err(|e| format!("Failed to set tool timeout: {}", e))?;
+
+    let overrides = state.tool_service.timeout_overrides();
+    state
+        .db
+        .call(move |db| {
+            tool_runtime::save_overrides(db.conn(), &overrides)
+                .map_err(|e| format!("Failed to save tool timeouts: {}", e))
+        })
+        .await?;
+
+    Ok(state.tool_service.timeouts())
+}
@@ -0,0 +1,91 @@
+/**
+ * Tool Set Commands (v3.9.0)
+ *
+ * Named tool sets and the tool set of each conversation
+ */
+
+use crate::services::tool_sets::{self, ToolSet};
+use crate::AppResult;
+use crate::AppState;
+use tauri::State;
+
+/// Built-in and custom tool sets
+#[tauri::command]
+pub async fn tool_set_list(state: State<'_, AppState>) -> AppResult<Vec<ToolSet>> {
+    Ok(state.db.call(|db| tool_sets::list_tool_sets(db.conn()).map_err(|e| e.to_string())).await?)
+}
+
+/// Create a tool set from registered tools
+#[tauri::command]
+pub async fn tool_set_create(
+    state: State<'_, AppState>,
+    name: String,
+    description: Option<String>,
+    tools: Vec<String>,
+) -> AppResult<ToolSet> {
+    let known_tools = state.tool_service.list_tools();
+    Ok(state
+        .db
+        .call(move |db| {
+            tool_sets::create_tool_set(db.conn(), &name, &description.unwrap_or_default(), &tools, &known_tools)
+                .map_err(|e| e.to_string())
+        })
+        .await?)
+}
+
+/// Rename a custom tool set or change its tools
+#[tauri::command]
+pub async fn tool_set_update(
+    state: State<'_, AppState>,
+    set_id: String,
+    name: String,
+    description: Option<String>,
+    tools: Vec<String>,
+) -> AppResult<ToolSet> {
+    let known_tools = state.tool_service.list_tools();
+    Ok(state
+        .db
+        .call(move |db| {
+            tool_sets::update_tool_set(db.conn(), &set_id, &name, &description.unwrap_or_default(), &tools, &known_tools)
+                .map_err(|e| e.to_string())
+        })
+        .await?)
+}
+
+/// Delete a custom tool set; its conversations get every tool again
+#[tauri::command]
+pub async fn tool_set_delete(state: State<'_, AppState>, set_id: String) -> AppResult<()> {
+    Ok(state
+        .db
+        .call(move |db| tool_sets::delete_tool_set(db.conn(), &set_id).map_err(|e| e.to_string()))
+        .await?)
+}
+
+/// Tool set of a conversation; null when it may use every tool
+#[tauri::command]
+pub async fn conversation_get_tool_set(
+    state: State<'_, AppState>,
+    conversation_id: String,
+) -> AppResult<Option<ToolSet>> {
+    Ok(state
+        .db
+        .call(move |db| tool_sets::conversation_tool_set(db.conn(), &conversation_id).map_err(|e| e.to_string()))
+        .await?)
+}
+
+/// Bind a conversation to a tool set, or back to every tool with null
+#[tauri::command]
+pub async fn conversation_set_tool_set(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    set_id: Option<String>,
+) -> AppResult<()> {
+    log::info!("Command: conversation_set_tool_set ({}, {:?})", conversation_id, set_id);
+    Ok(state
+        .db
+        .call(move |db| {
+            tool_sets::set_conversation_tool_set(db.conn(), &conversation_id, set_id.as_deref())
+                .map_err(|e| e.to_string())
+        })
+        .await?)
+}
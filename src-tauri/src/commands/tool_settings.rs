@@ -7,6 +7,7 @@ use tauri::State;
 use serde_json::Value;
 use std::collections::HashMap;
 use crate::services::tool_settings::ToolSettings;
+use crate::AppResult;
 use crate::AppState;
 
 /// Get all tool settings
@@ -16,10 +17,10 @@ use crate::AppState;
 #[tauri::command]
 pub async fn get_all_tool_settings(
     state: State<'_, AppState>,
-) -> Result<HashMap<String, ToolSettings>, String> {
+) -> AppResult<HashMap<String, ToolSettings>> {
     let service = state.tool_settings_service.lock().await;
-    service.get_all_settings()
-        .map_err(|e| format!("Failed to get all tool settings: {}", e))
+    Ok(service.get_all_settings()
+        .map_err(|e| format!("Failed to get all tool settings: {}", e))?)
 }
 
 /// Get settings for a specific tool
@@ -33,10 +34,10 @@ pub async fn get_all_tool_settings(
 pub async fn get_tool_setting(
     state: State<'_, AppState>,
     tool_name: String,
-) -> Result<ToolSettings, String> {
+) -> AppResult<ToolSettings> {
     let service = state.tool_settings_service.lock().await;
-    service.get_settings(&tool_name)
-        .map_err(|e| format!("Failed to get settings for '{}': {}", tool_name, e))
+    Ok(service.get_settings(&tool_name)
+        .map_err(|e| format!("Failed to get settings for '{}': {}", tool_name, e))?)
 }
 
 /// Update settings for a specific tool
@@ -54,10 +55,10 @@ pub async fn update_tool_setting(
     tool_name: String,
     enabled: bool,
     config: Value,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let service = state.tool_settings_service.lock().await;
-    service.update_settings(&tool_name, enabled, config)
-        .map_err(|e| format!("Failed to update settings for '{}': {}", tool_name, e))
+    Ok(service.update_settings(&tool_name, enabled, config)
+        .map_err(|e| format!("Failed to update settings for '{}': {}", tool_name, e))?)
 }
 
 /// Bulk update settings for multiple tools
@@ -71,7 +72,7 @@ pub async fn update_tool_setting(
 pub async fn update_all_tool_settings(
     state: State<'_, AppState>,
     settings_map: HashMap<String, ToolSettings>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let service = state.tool_settings_service.lock().await;
 
     // Update each tool setting
@@ -94,10 +95,10 @@ pub async fn update_all_tool_settings(
 pub async fn enable_tool(
     state: State<'_, AppState>,
     tool_name: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let service = state.tool_settings_service.lock().await;
-    service.enable_tool(&tool_name)
-        .map_err(|e| format!("Failed to enable tool '{}': {}", tool_name, e))
+    Ok(service.enable_tool(&tool_name)
+        .map_err(|e| format!("Failed to enable tool '{}': {}", tool_name, e))?)
 }
 
 /// Disable a specific tool
@@ -111,10 +112,10 @@ pub async fn enable_tool(
 pub async fn disable_tool(
     state: State<'_, AppState>,
     tool_name: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let service = state.tool_settings_service.lock().await;
-    service.disable_tool(&tool_name)
-        .map_err(|e| format!("Failed to disable tool '{}': {}", tool_name, e))
+    Ok(service.disable_tool(&tool_name)
+        .map_err(|e| format!("Failed to disable tool '{}': {}", tool_name, e))?)
 }
 
 /// Reset all tool settings to defaults
@@ -124,10 +125,10 @@ pub async fn disable_tool(
 #[tauri::command]
 pub async fn reset_tool_settings(
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let service = state.tool_settings_service.lock().await;
-    service.reset_all_to_defaults()
-        .map_err(|e| format!("Failed to reset tool settings: {}", e))
+    Ok(service.reset_all_to_defaults()
+        .map_err(|e| format!("Failed to reset tool settings: {}", e))?)
 }
 
 /// Get default configuration for a specific tool
@@ -141,8 +142,8 @@ pub async fn reset_tool_settings(
 pub async fn get_tool_default_config(
     state: State<'_, AppState>,
     tool_name: String,
-) -> Result<Value, String> {
+) -> AppResult<Value> {
     let service = state.tool_settings_service.lock().await;
-    service.get_default_config(&tool_name)
-        .map_err(|e| format!("Failed to get default config for '{}': {}", tool_name, e))
+    Ok(service.get_default_config(&tool_name)
+        .map_err(|e| format!("Failed to get default config for '{}': {}", tool_name, e))?)
 }
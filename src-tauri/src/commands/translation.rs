@@ -0,0 +1,52 @@
+/**
+ * Translation Commands (v3.9.0)
+ */
+
+use crate::services::translation::{GlossaryEntry, Translation, TranslationService};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Translate text into `target_language` (code or name) with the local model
+#[tauri::command]
+pub async fn translate_text(
+    text: String,
+    target_language: String,
+    source_language: Option<String>,
+    service: State<'_, Arc<TranslationService>>,
+) -> AppResult<Translation> {
+    Ok(service
+        .translate(&text, &target_language, source_language.as_deref())
+        .await
+        .map_err(|e| format!("Failed to translate text: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn translation_get_glossary(
+    service: State<'_, Arc<TranslationService>>,
+) -> AppResult<Vec<GlossaryEntry>> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .get_glossary()
+            .map_err(|e| format!("Failed to get translation glossary: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+/// Replace the glossary; returns the stored (normalized) entries
+#[tauri::command]
+pub async fn translation_update_glossary(
+    entries: Vec<GlossaryEntry>,
+    service: State<'_, Arc<TranslationService>>,
+) -> AppResult<Vec<GlossaryEntry>> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .update_glossary(entries)
+            .map_err(|e| format!("Failed to update translation glossary: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
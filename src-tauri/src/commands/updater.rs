@@ -5,6 +5,8 @@
  */
 
 use crate::services::updater::{UpdateChannel, UpdateCheckResult, UpdaterService};
+use crate::AppError;
+use crate::AppResult;
 use crate::AppState;
 use log::{error, info, warn};
 use tauri::{AppHandle, Emitter, State};
@@ -12,14 +14,14 @@ use tauri_plugin_updater::UpdaterExt;
 
 /// Get current application version
 #[tauri::command]
-pub async fn updater_get_version() -> Result<String, String> {
+pub async fn updater_get_version() -> AppResult<String> {
     info!("Command: updater_get_version");
     Ok(UpdaterService::get_current_version())
 }
 
 /// Check for available updates (v3.4.0 - Full Implementation)
 #[tauri::command]
-pub async fn updater_check_for_updates(app: AppHandle) -> Result<UpdateCheckResult, String> {
+pub async fn updater_check_for_updates(app: AppHandle) -> AppResult<UpdateCheckResult> {
     info!("Command: updater_check_for_updates");
 
     let current_version = UpdaterService::get_current_version();
@@ -50,20 +52,20 @@ pub async fn updater_check_for_updates(app: AppHandle) -> Result<UpdateCheckResu
                 }
                 Err(e) => {
                     error!("Failed to check for updates: {}", e);
-                    Err(format!("Failed to check for updates: {}", e))
+                    Err(format!("Failed to check for updates: {}", e).into())
                 }
             }
         }
         Err(e) => {
             error!("Failed to initialize updater: {}", e);
-            Err(format!("Updater not available: {}", e))
+            Err(format!("Updater not available: {}", e).into())
         }
     }
 }
 
 /// Install available update (v3.4.0 - Full Implementation)
 #[tauri::command]
-pub async fn updater_install_update(app: AppHandle) -> Result<(), String> {
+pub async fn updater_install_update(app: AppHandle) -> AppResult<()> {
     info!("Command: updater_install_update");
 
     match app.updater() {
@@ -94,37 +96,37 @@ pub async fn updater_install_update(app: AppHandle) -> Result<(), String> {
                         }
                         Err(e) => {
                             error!("Failed to download/install update: {}", e);
-                            Err(format!("Failed to install update: {}", e))
+                            Err(format!("Failed to install update: {}", e).into())
                         }
                     }
                 }
                 Ok(None) => {
                     warn!("No update available to install");
-                    Err("No update available".to_string())
+                    Err("No update available".into())
                 }
                 Err(e) => {
                     error!("Failed to check for updates before install: {}", e);
-                    Err(format!("Failed to check for updates: {}", e))
+                    Err(format!("Failed to check for updates: {}", e).into())
                 }
             }
         }
         Err(e) => {
             error!("Failed to initialize updater: {}", e);
-            Err(format!("Updater not available: {}", e))
+            Err(format!("Updater not available: {}", e).into())
         }
     }
 }
 
 /// Set auto-update check interval (in hours)
 #[tauri::command]
-pub async fn updater_set_check_interval(hours: u64) -> Result<(), String> {
+pub async fn updater_set_check_interval(hours: u64) -> AppResult<()> {
     info!("Command: updater_set_check_interval - {} hours", hours);
 
     // This would update the service's check interval
     // For now, just validate the input
     if hours == 0 || hours > 168 {
         // Max 1 week
-        return Err("Invalid interval: must be between 1 and 168 hours".to_string());
+        return Err(AppError::invalid_input("Invalid interval: must be between 1 and 168 hours"));
     }
 
     Ok(())
@@ -132,57 +134,55 @@ pub async fn updater_set_check_interval(hours: u64) -> Result<(), String> {
 
 /// Get update endpoint URL
 #[tauri::command]
-pub async fn updater_get_endpoint() -> Result<String, String> {
+pub async fn updater_get_endpoint() -> AppResult<String> {
     info!("Command: updater_get_endpoint");
     Ok(UpdaterService::get_update_endpoint())
 }
 
 /// Compare two versions
 #[tauri::command]
-pub async fn updater_is_newer_version(current: String, latest: String) -> Result<bool, String> {
+pub async fn updater_is_newer_version(current: String, latest: String) -> AppResult<bool> {
     info!("Command: updater_is_newer_version - {} vs {}", current, latest);
 
-    UpdaterService::is_newer_version(&current, &latest)
+    Ok(UpdaterService::is_newer_version(&current, &latest)
         .map_err(|e| {
             error!("Failed to compare versions: {}", e);
             format!("Failed to compare versions: {}", e)
-        })
+        })?)
 }
 
 /// Get current update channel (v3.5.0)
 #[tauri::command]
-pub async fn updater_get_channel(state: State<'_, AppState>) -> Result<String, String> {
+pub async fn updater_get_channel(state: State<'_, AppState>) -> AppResult<String> {
     info!("Command: updater_get_channel");
 
-    let db = state.db.lock().map_err(|e| {
-        error!("Failed to lock database: {}", e);
-        format!("Database lock error: {}", e)
-    })?;
-    let conn = db.conn();
+    state.db.call(move |db| {
+        let conn = db.conn();
 
-    // Get channel from database (default to 'stable' if not found)
-    let channel: String = conn
-        .query_row(
-            "SELECT channel FROM update_settings WHERE id = 1",
-            [],
-            |row| row.get(0),
-        )
-        .unwrap_or_else(|_| {
-            // Initialize default settings if not exists
-            let _ = conn.execute(
-                "INSERT OR IGNORE INTO update_settings (id, channel) VALUES (1, 'stable')",
+        // Get channel from database (default to 'stable' if not found)
+        let channel: String = conn
+            .query_row(
+                "SELECT channel FROM update_settings WHERE id = 1",
                 [],
-            );
-            "stable".to_string()
-        });
-
-    info!("Current update channel: {}", channel);
-    Ok(channel)
+                |row| row.get(0),
+            )
+            .unwrap_or_else(|_| {
+                // Initialize default settings if not exists
+                let _ = conn.execute(
+                    "INSERT OR IGNORE INTO update_settings (id, channel) VALUES (1, 'stable')",
+                    [],
+                );
+                "stable".to_string()
+            });
+
+        info!("Current update channel: {}", channel);
+        Ok(channel)
+    }).await
 }
 
 /// Set update channel (v3.5.0)
 #[tauri::command]
-pub async fn updater_set_channel(state: State<'_, AppState>, channel: String) -> Result<(), String> {
+pub async fn updater_set_channel(state: State<'_, AppState>, channel: String) -> AppResult<()> {
     info!("Command: updater_set_channel - {}", channel);
 
     // Validate channel
@@ -192,76 +192,72 @@ pub async fn updater_set_channel(state: State<'_, AppState>, channel: String) ->
             format!("Invalid channel: {}", e)
         })?;
 
-    let db = state.db.lock().map_err(|e| {
-        error!("Failed to lock database: {}", e);
-        format!("Database lock error: {}", e)
-    })?;
-    let conn = db.conn();
-
-    // Initialize settings row if not exists
-    conn.execute(
-        "INSERT OR IGNORE INTO update_settings (id, channel) VALUES (1, 'stable')",
-        [],
-    )
-    .map_err(|e| {
-        error!("Failed to initialize update settings: {}", e);
-        format!("Database error: {}", e)
-    })?;
-
-    // Update channel
-    conn.execute(
-        "UPDATE update_settings SET channel = ?1 WHERE id = 1",
-        [update_channel.as_str()],
-    )
-    .map_err(|e| {
-        error!("Failed to update channel: {}", e);
-        format!("Failed to set update channel: {}", e)
-    })?;
-
-    info!("Update channel set to: {}", update_channel.as_str());
-    Ok(())
+    state.db.call(move |db| {
+        let conn = db.conn();
+
+        // Initialize settings row if not exists
+        conn.execute(
+            "INSERT OR IGNORE INTO update_settings (id, channel) VALUES (1, 'stable')",
+            [],
+        )
+        .map_err(|e| {
+            error!("Failed to initialize update settings: {}", e);
+            format!("Database error: {}", e)
+        })?;
+
+        // Update channel
+        conn.execute(
+            "UPDATE update_settings SET channel = ?1 WHERE id = 1",
+            [update_channel.as_str()],
+        )
+        .map_err(|e| {
+            error!("Failed to update channel: {}", e);
+            format!("Failed to set update channel: {}", e)
+        })?;
+
+        info!("Update channel set to: {}", update_channel.as_str());
+        Ok(())
+    }).await
 }
 
 /// Get update scheduling settings (v3.5.0)
 #[tauri::command]
-pub async fn updater_get_schedule_settings(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+pub async fn updater_get_schedule_settings(state: State<'_, AppState>) -> AppResult<serde_json::Value> {
     info!("Command: updater_get_schedule_settings");
 
-    let db = state.db.lock().map_err(|e| {
-        error!("Failed to lock database: {}", e);
-        format!("Database lock error: {}", e)
-    })?;
-    let conn = db.conn();
-
-    // Get settings from database
-    let settings = conn
-        .query_row(
-            "SELECT auto_check, check_interval, download_in_background, bandwidth_limit, last_check
-             FROM update_settings WHERE id = 1",
-            [],
-            |row| {
-                Ok(serde_json::json!({
-                    "auto_check": row.get::<_, bool>(0)?,
-                    "check_interval": row.get::<_, i64>(1)?,
-                    "download_in_background": row.get::<_, bool>(2)?,
-                    "bandwidth_limit": row.get::<_, Option<i64>>(3)?,
-                    "last_check": row.get::<_, Option<i64>>(4)?
-                }))
-            },
-        )
-        .unwrap_or_else(|_| {
-            // Return defaults if not found
-            serde_json::json!({
-                "auto_check": true,
-                "check_interval": 3600,
-                "download_in_background": false,
-                "bandwidth_limit": null,
-                "last_check": null
-            })
-        });
+    state.db.call(move |db| {
+        let conn = db.conn();
 
-    info!("Update schedule settings retrieved");
-    Ok(settings)
+        // Get settings from database
+        let settings = conn
+            .query_row(
+                "SELECT auto_check, check_interval, download_in_background, bandwidth_limit, last_check
+                 FROM update_settings WHERE id = 1",
+                [],
+                |row| {
+                    Ok(serde_json::json!({
+                        "auto_check": row.get::<_, bool>(0)?,
+                        "check_interval": row.get::<_, i64>(1)?,
+                        "download_in_background": row.get::<_, bool>(2)?,
+                        "bandwidth_limit": row.get::<_, Option<i64>>(3)?,
+                        "last_check": row.get::<_, Option<i64>>(4)?
+                    }))
+                },
+            )
+            .unwrap_or_else(|_| {
+                // Return defaults if not found
+                serde_json::json!({
+                    "auto_check": true,
+                    "check_interval": 3600,
+                    "download_in_background": false,
+                    "bandwidth_limit": null,
+                    "last_check": null
+                })
+            });
+
+        info!("Update schedule settings retrieved");
+        Ok(settings)
+    }).await
 }
 
 /// Update scheduling settings (v3.5.0)
@@ -272,111 +268,107 @@ pub async fn updater_update_schedule_settings(
     check_interval: Option<i64>,
     download_in_background: Option<bool>,
     bandwidth_limit: Option<i64>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     info!("Command: updater_update_schedule_settings");
 
     // Validate check_interval if provided
     if let Some(interval) = check_interval {
         if interval < 60 || interval > 604800 {
             // Min 1 minute, Max 1 week
-            return Err("Invalid interval: must be between 60 and 604800 seconds".to_string());
+            return Err(AppError::invalid_input("Invalid interval: must be between 60 and 604800 seconds"));
         }
     }
 
     // Validate bandwidth_limit if provided
     if let Some(limit) = bandwidth_limit {
         if limit < 0 {
-            return Err("Bandwidth limit must be non-negative".to_string());
+            return Err(AppError::invalid_input("Bandwidth limit must be non-negative"));
         }
     }
 
-    let db = state.db.lock().map_err(|e| {
-        error!("Failed to lock database: {}", e);
-        format!("Database lock error: {}", e)
-    })?;
-    let conn = db.conn();
-
-    // Initialize settings row if not exists
-    conn.execute(
-        "INSERT OR IGNORE INTO update_settings (id, channel) VALUES (1, 'stable')",
-        [],
-    )
-    .map_err(|e| {
-        error!("Failed to initialize update settings: {}", e);
-        format!("Database error: {}", e)
-    })?;
-
-    // Update each field if provided
-    if let Some(enabled) = auto_check {
-        conn.execute(
-            "UPDATE update_settings SET auto_check = ?1 WHERE id = 1",
-            [enabled],
-        )
-        .map_err(|e| format!("Failed to update auto_check: {}", e))?;
-        info!("Auto-check set to: {}", enabled);
-    }
+    state.db.call(move |db| {
+        let conn = db.conn();
 
-    if let Some(interval) = check_interval {
+        // Initialize settings row if not exists
         conn.execute(
-            "UPDATE update_settings SET check_interval = ?1 WHERE id = 1",
-            [interval],
+            "INSERT OR IGNORE INTO update_settings (id, channel) VALUES (1, 'stable')",
+            [],
         )
-        .map_err(|e| format!("Failed to update check_interval: {}", e))?;
-        info!("Check interval set to: {} seconds", interval);
-    }
+        .map_err(|e| {
+            error!("Failed to initialize update settings: {}", e);
+            format!("Database error: {}", e)
+        })?;
 
-    if let Some(background) = download_in_background {
-        conn.execute(
-            "UPDATE update_settings SET download_in_background = ?1 WHERE id = 1",
-            [background],
-        )
-        .map_err(|e| format!("Failed to update download_in_background: {}", e))?;
-        info!("Download in background set to: {}", background);
-    }
+        // Update each field if provided
+        if let Some(enabled) = auto_check {
+            conn.execute(
+                "UPDATE update_settings SET auto_check = ?1 WHERE id = 1",
+                [enabled],
+            )
+            .map_err(|e| format!("Failed to update auto_check: {}", e))?;
+            info!("Auto-check set to: {}", enabled);
+        }
 
-    if let Some(limit) = bandwidth_limit {
-        conn.execute(
-            "UPDATE update_settings SET bandwidth_limit = ?1 WHERE id = 1",
-            [limit],
-        )
-        .map_err(|e| format!("Failed to update bandwidth_limit: {}", e))?;
-        info!("Bandwidth limit set to: {} KB/s", limit);
-    }
+        if let Some(interval) = check_interval {
+            conn.execute(
+                "UPDATE update_settings SET check_interval = ?1 WHERE id = 1",
+                [interval],
+            )
+            .map_err(|e| format!("Failed to update check_interval: {}", e))?;
+            info!("Check interval set to: {} seconds", interval);
+        }
 
-    info!("Update schedule settings updated successfully");
-    Ok(())
+        if let Some(background) = download_in_background {
+            conn.execute(
+                "UPDATE update_settings SET download_in_background = ?1 WHERE id = 1",
+                [background],
+            )
+            .map_err(|e| format!("Failed to update download_in_background: {}", e))?;
+            info!("Download in background set to: {}", background);
+        }
+
+        if let Some(limit) = bandwidth_limit {
+            conn.execute(
+                "UPDATE update_settings SET bandwidth_limit = ?1 WHERE id = 1",
+                [limit],
+            )
+            .map_err(|e| format!("Failed to update bandwidth_limit: {}", e))?;
+            info!("Bandwidth limit set to: {} KB/s", limit);
+        }
+
+        info!("Update schedule settings updated successfully");
+        Ok(())
+    }).await
 }
 
 /// Mark last update check timestamp (v3.5.0)
 #[tauri::command]
-pub async fn updater_mark_last_check(state: State<'_, AppState>) -> Result<(), String> {
+pub async fn updater_mark_last_check(state: State<'_, AppState>) -> AppResult<()> {
     info!("Command: updater_mark_last_check");
 
-    let db = state.db.lock().map_err(|e| {
-        error!("Failed to lock database: {}", e);
-        format!("Database lock error: {}", e)
-    })?;
-    let conn = db.conn();
-
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs() as i64;
-
-    conn.execute(
-        "INSERT OR IGNORE INTO update_settings (id, channel) VALUES (1, 'stable')",
-        [],
-    )
-    .map_err(|e| format!("Database error: {}", e))?;
-
-    conn.execute(
-        "UPDATE update_settings SET last_check = ?1 WHERE id = 1",
-        [now],
-    )
-    .map_err(|e| format!("Failed to update last_check: {}", e))?;
-
-    info!("Last check timestamp updated to: {}", now);
-    Ok(())
+    state.db.call(move |db| {
+        let conn = db.conn();
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        conn.execute(
+            "INSERT OR IGNORE INTO update_settings (id, channel) VALUES (1, 'stable')",
+            [],
+        )
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        conn.execute(
+            "UPDATE update_settings SET last_check = ?1 WHERE id = 1",
+            [now],
+        )
+        .map_err(|e| format!("Failed to update last_check: {}", e))?;
+
+        info!("Last check timestamp updated to: {}", now);
+        Ok(())
+    }).await
 }
 
 /// Add update history entry (v3.5.0)
@@ -389,88 +381,84 @@ pub async fn updater_add_history_entry(
     error_message: Option<String>,
     download_size: Option<i64>,
     install_duration: Option<i64>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     info!("Command: updater_add_history_entry - {} -> {} (success: {})", from_version, to_version, success);
 
-    let db = state.db.lock().map_err(|e| {
-        error!("Failed to lock database: {}", e);
-        format!("Database lock error: {}", e)
-    })?;
-    let conn = db.conn();
-
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs() as i64;
-
-    conn.execute(
-        "INSERT INTO update_history (from_version, to_version, update_date, success, error_message, download_size, install_duration, created_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-        (
-            &from_version,
-            &to_version,
-            now,
-            success,
-            error_message.as_deref(),
-            download_size,
-            install_duration,
-            now,
-        ),
-    )
-    .map_err(|e| {
-        error!("Failed to insert update history: {}", e);
-        format!("Failed to record update history: {}", e)
-    })?;
-
-    info!("Update history entry added successfully");
-    Ok(())
-}
+    state.db.call(move |db| {
+        let conn = db.conn();
 
-/// Get update history (v3.5.0)
-#[tauri::command]
-pub async fn updater_get_history(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
-    info!("Command: updater_get_history");
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
 
-    let db = state.db.lock().map_err(|e| {
-        error!("Failed to lock database: {}", e);
-        format!("Database lock error: {}", e)
-    })?;
-    let conn = db.conn();
-
-    let mut stmt = conn
-        .prepare(
-            "SELECT id, from_version, to_version, update_date, success, error_message, download_size, install_duration
-             FROM update_history
-             ORDER BY update_date DESC"
+        conn.execute(
+            "INSERT INTO update_history (from_version, to_version, update_date, success, error_message, download_size, install_duration, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            (
+                &from_version,
+                &to_version,
+                now,
+                success,
+                error_message.as_deref(),
+                download_size,
+                install_duration,
+                now,
+            ),
         )
         .map_err(|e| {
-            error!("Failed to prepare statement: {}", e);
-            format!("Database error: {}", e)
+            error!("Failed to insert update history: {}", e);
+            format!("Failed to record update history: {}", e)
         })?;
 
-    let history_iter = stmt
-        .query_map([], |row| {
-            Ok(serde_json::json!({
-                "id": row.get::<_, i64>(0)?,
-                "from_version": row.get::<_, String>(1)?,
-                "to_version": row.get::<_, String>(2)?,
-                "update_date": row.get::<_, i64>(3)?,
-                "success": row.get::<_, bool>(4)?,
-                "error_message": row.get::<_, Option<String>>(5)?,
-                "download_size": row.get::<_, Option<i64>>(6)?,
-                "install_duration": row.get::<_, Option<i64>>(7)?
-            }))
-        })
-        .map_err(|e| {
-            error!("Failed to query update history: {}", e);
-            format!("Database error: {}", e)
-        })?;
+        info!("Update history entry added successfully");
+        Ok(())
+    }).await
+}
 
-    let mut history = Vec::new();
-    for entry in history_iter {
-        history.push(entry.map_err(|e| format!("Failed to parse history entry: {}", e))?);
-    }
+/// Get update history (v3.5.0)
+#[tauri::command]
+pub async fn updater_get_history(state: State<'_, AppState>) -> AppResult<serde_json::Value> {
+    info!("Command: updater_get_history");
+
+    state.db.call(move |db| {
+        let conn = db.conn();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, from_version, to_version, update_date, success, error_message, download_size, install_duration
+                 FROM update_history
+                 ORDER BY update_date DESC"
+            )
+            .map_err(|e| {
+                error!("Failed to prepare statement: {}", e);
+                format!("Database error: {}", e)
+            })?;
+
+        let history_iter = stmt
+            .query_map([], |row| {
+                Ok(serde_json::json!({
+                    "id": row.get::<_, i64>(0)?,
+                    "from_version": row.get::<_, String>(1)?,
+                    "to_version": row.get::<_, String>(2)?,
+                    "update_date": row.get::<_, i64>(3)?,
+                    "success": row.get::<_, bool>(4)?,
+                    "error_message": row.get::<_, Option<String>>(5)?,
+                    "download_size": row.get::<_, Option<i64>>(6)?,
+                    "install_duration": row.get::<_, Option<i64>>(7)?
+                }))
+            })
+            .map_err(|e| {
+                error!("Failed to query update history: {}", e);
+                format!("Database error: {}", e)
+            })?;
+
+        let mut history = Vec::new();
+        for entry in history_iter {
+            history.push(entry.map_err(|e| format!("Failed to parse history entry: {}", e))?);
+        }
 
-    info!("Retrieved {} update history entries", history.len());
-    Ok(serde_json::json!(history))
+        info!("Retrieved {} update history entries", history.len());
+        Ok(serde_json::json!(history))
+    }).await
 }
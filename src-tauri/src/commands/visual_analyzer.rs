@@ -7,6 +7,7 @@
 use crate::services::visual_analyzer::{
     VisualAnalysis, VisualAnalyzerConfig, VisualAnalyzerService,
 };
+use crate::AppResult;
 use std::sync::Arc;
 use tauri::State;
 use tokio::sync::Mutex as TokioMutex;
@@ -17,14 +18,14 @@ pub async fn visual_analyze_image(
     image_path: String,
     user_question: Option<String>,
     service: State<'_, Arc<TokioMutex<VisualAnalyzerService>>>,
-) -> Result<VisualAnalysis, String> {
+) -> AppResult<VisualAnalysis> {
     log::info!("Visual analysis request for image: {}", &image_path);
 
     let service_guard = service.lock().await;
-    service_guard
+    Ok(service_guard
         .analyze(&image_path, user_question.as_deref())
         .await
-        .map_err(|e| format!("Failed to analyze image: {}", e))
+        .map_err(|e| format!("Failed to analyze image: {}", e))?)
 }
 
 /// Analyze current screen capture
@@ -32,14 +33,14 @@ pub async fn visual_analyze_image(
 pub async fn visual_analyze_screen(
     user_question: Option<String>,
     service: State<'_, Arc<TokioMutex<VisualAnalyzerService>>>,
-) -> Result<VisualAnalysis, String> {
+) -> AppResult<VisualAnalysis> {
     log::info!("Visual analysis request for current screen");
 
     let service_guard = service.lock().await;
-    service_guard
+    Ok(service_guard
         .analyze_current_screen(user_question.as_deref())
         .await
-        .map_err(|e| format!("Failed to analyze screen: {}", e))
+        .map_err(|e| format!("Failed to analyze screen: {}", e))?)
 }
 
 /// Update visual analyzer configuration
@@ -47,7 +48,7 @@ pub async fn visual_analyze_screen(
 pub async fn visual_update_config(
     config: VisualAnalyzerConfig,
     service: State<'_, Arc<TokioMutex<VisualAnalyzerService>>>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let service_guard = service.lock().await;
     service_guard.update_config(config);
     Ok(())
@@ -57,7 +58,7 @@ pub async fn visual_update_config(
 #[tauri::command]
 pub async fn visual_get_config(
     service: State<'_, Arc<TokioMutex<VisualAnalyzerService>>>,
-) -> Result<VisualAnalyzerConfig, String> {
+) -> AppResult<VisualAnalyzerConfig> {
     let service_guard = service.lock().await;
     Ok(service_guard.get_config())
 }
@@ -66,7 +67,7 @@ pub async fn visual_get_config(
 #[tauri::command]
 pub async fn visual_is_loaded(
     service: State<'_, Arc<TokioMutex<VisualAnalyzerService>>>,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     let service_guard = service.lock().await;
     Ok(service_guard.is_loaded())
 }
@@ -76,9 +77,9 @@ pub async fn visual_is_loaded(
 pub async fn visual_get_recent(
     limit: usize,
     service: State<'_, Arc<TokioMutex<VisualAnalyzerService>>>,
-) -> Result<Vec<VisualAnalysis>, String> {
+) -> AppResult<Vec<VisualAnalysis>> {
     let service_guard = service.lock().await;
-    service_guard
+    Ok(service_guard
         .get_recent(limit)
-        .map_err(|e| format!("Failed to get recent analyses: {}", e))
+        .map_err(|e| format!("Failed to get recent analyses: {}", e))?)
 }
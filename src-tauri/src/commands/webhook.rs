@@ -1,7 +1,11 @@
+use crate::services::secrets::{self, SecretsService};
 use crate::services::webhook::{WebhookConfig, WebhookPayload, WebhookPreset, WebhookService};
+use crate::AppError;
+use crate::AppResult;
 use crate::AppState;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use tauri::State;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -42,12 +46,23 @@ impl WebhookRecord {
             retries: self.retries as u32,
         })
     }
+
+    /// Config with the sensitive headers from the secrets vault merged in (v3.9.0)
+    fn to_config_with_secrets(&self, secrets: &SecretsService) -> Result<WebhookConfig, String> {
+        let mut config = self.to_config()?;
+        let sensitive = secrets
+            .get_map(&secrets::webhook_headers_key(&self.name))
+            .map_err(|e| format!("Failed to read webhook secrets: {}", e))?;
+        config.headers.extend(sensitive);
+        Ok(config)
+    }
 }
 
 /// Register or update a webhook
 #[tauri::command]
 pub async fn register_webhook(
     state: State<'_, AppState>,
+    secrets: State<'_, Arc<SecretsService>>,
     name: String,
     preset: Option<String>,
     url: String,
@@ -56,15 +71,10 @@ pub async fn register_webhook(
     enabled: Option<bool>,
     timeout: Option<i64>,
     retries: Option<i64>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     log::info!("Registering webhook: {}", name);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
-    let now = chrono::Utc::now().timestamp();
-
     let method = method.unwrap_or_else(|| "POST".to_string());
-    let headers = headers.unwrap_or_else(|| "{}".to_string());
     let enabled = enabled.unwrap_or(true);
     let timeout = timeout.unwrap_or(5000);
     let retries = retries.unwrap_or(3);
@@ -72,90 +82,67 @@ pub async fn register_webhook(
     // Validate preset if provided
     if let Some(ref p) = preset {
         if !matches!(p.as_str(), "slack" | "discord" | "notion" | "custom") {
-            return Err(format!("Invalid preset: {}", p));
+            return Err(AppError::invalid_input(format!("Invalid preset: {}", p)));
         }
     }
 
-    conn.execute(
-        "INSERT OR REPLACE INTO webhooks
-         (name, preset, url, method, headers, enabled, timeout, retries, created_at, last_used_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
-        rusqlite::params![
-            name,
-            preset,
-            url,
-            method,
-            headers,
-            enabled,
-            timeout,
-            retries,
-            now,
-            None::<i64>,
-        ],
-    )
-    .map_err(|e| format!("Failed to register webhook: {}", e))?;
-
-    log::info!("Webhook {} registered successfully", name);
-    Ok(())
-}
-
-/// List all webhooks
-#[tauri::command]
-pub async fn list_webhooks(state: State<'_, AppState>) -> Result<Vec<WebhookRecord>, String> {
-    log::info!("Listing all webhooks");
+    // Credentials (Authorization, API keys, ...) go to the vault; the rest stays in the database
+    let headers: HashMap<String, String> = match headers {
+        Some(h) => serde_json::from_str(&h).map_err(|e| format!("Invalid headers JSON: {}", e))?,
+        None => HashMap::new(),
+    };
+    let (plain_headers, sensitive_headers) = secrets::split_sensitive_headers(headers);
+    secrets
+        .set_map(&secrets::webhook_headers_key(&name), &sensitive_headers)
+        .map_err(|e| format!("Failed to store webhook secrets: {}", e))?;
+    let headers = serde_json::to_string(&plain_headers).map_err(|e| e.to_string())?;
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
+    state.db.call(move |db| {
+        let conn = db.conn();
+        let now = chrono::Utc::now().timestamp();
 
-    let mut stmt = conn
-        .prepare(
-            "SELECT name, preset, url, method, headers, enabled, timeout, retries, created_at, last_used_at
-             FROM webhooks
-             ORDER BY created_at DESC",
+        conn.execute(
+            "INSERT OR REPLACE INTO webhooks
+             (name, preset, url, method, headers, enabled, timeout, retries, created_at, last_used_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            rusqlite::params![
+                name,
+                preset,
+                url,
+                method,
+                headers,
+                enabled,
+                timeout,
+                retries,
+                now,
+                None::<i64>,
+            ],
         )
-        .map_err(|e| e.to_string())?;
-
-    let webhooks = stmt
-        .query_map([], |row| {
-            Ok(WebhookRecord {
-                name: row.get(0)?,
-                preset: row.get(1)?,
-                url: row.get(2)?,
-                method: row.get(3)?,
-                headers: row.get(4)?,
-                enabled: row.get(5)?,
-                timeout: row.get(6)?,
-                retries: row.get(7)?,
-                created_at: row.get(8)?,
-                last_used_at: row.get(9)?,
-            })
-        })
-        .map_err(|e| e.to_string())?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| format!("Failed to register webhook: {}", e))?;
 
-    log::info!("Found {} webhooks", webhooks.len());
-    Ok(webhooks)
+        log::info!("Webhook {} registered successfully", name);
+        Ok(())
+    }).await
 }
 
-/// Get a specific webhook by name
+/// List all webhooks
 #[tauri::command]
-pub async fn get_webhook(
-    state: State<'_, AppState>,
-    name: String,
-) -> Result<WebhookRecord, String> {
-    log::info!("Getting webhook: {}", name);
+pub async fn list_webhooks(state: State<'_, AppState>) -> AppResult<Vec<WebhookRecord>> {
+    log::info!("Listing all webhooks");
+
+    state.db.call(move |db| {
+        let conn = db.conn();
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
+        let mut stmt = conn
+            .prepare(
+                "SELECT name, preset, url, method, headers, enabled, timeout, retries, created_at, last_used_at
+                 FROM webhooks
+                 ORDER BY created_at DESC",
+            )
+            .map_err(|e| e.to_string())?;
 
-    let webhook = conn
-        .query_row(
-            "SELECT name, preset, url, method, headers, enabled, timeout, retries, created_at, last_used_at
-             FROM webhooks
-             WHERE name = ?1",
-            [&name],
-            |row| {
+        let webhooks = stmt
+            .query_map([], |row| {
                 Ok(WebhookRecord {
                     name: row.get(0)?,
                     preset: row.get(1)?,
@@ -168,29 +155,84 @@ pub async fn get_webhook(
                     created_at: row.get(8)?,
                     last_used_at: row.get(9)?,
                 })
-            },
-        )
-        .map_err(|e| format!("Webhook not found: {}", e))?;
+            })
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
 
-    Ok(webhook)
+        log::info!("Found {} webhooks", webhooks.len());
+        Ok(webhooks)
+    }).await
+}
+
+/// Get a specific webhook by name
+#[tauri::command]
+pub async fn get_webhook(
+    state: State<'_, AppState>,
+    name: String,
+) -> AppResult<WebhookRecord> {
+    log::info!("Getting webhook: {}", name);
+
+    state.db.call(move |db| {
+        let conn = db.conn();
+
+        let webhook = conn
+            .query_row(
+                "SELECT name, preset, url, method, headers, enabled, timeout, retries, created_at, last_used_at
+                 FROM webhooks
+                 WHERE name = ?1",
+                [&name],
+                |row| {
+                    Ok(WebhookRecord {
+                        name: row.get(0)?,
+                        preset: row.get(1)?,
+                        url: row.get(2)?,
+                        method: row.get(3)?,
+                        headers: row.get(4)?,
+                        enabled: row.get(5)?,
+                        timeout: row.get(6)?,
+                        retries: row.get(7)?,
+                        created_at: row.get(8)?,
+                        last_used_at: row.get(9)?,
+                    })
+                },
+            )
+            .map_err(|e| format!("Webhook not found: {}", e))?;
+
+        Ok(webhook)
+    }).await
 }
 
 /// Delete a webhook
 #[tauri::command]
-pub async fn delete_webhook(state: State<'_, AppState>, name: String) -> Result<(), String> {
+pub async fn delete_webhook(
+    state: State<'_, AppState>,
+    secrets: State<'_, Arc<SecretsService>>,
+    name: String,
+) -> AppResult<()> {
     log::info!("Deleting webhook: {}", name);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
+    {
+        let name = name.clone();
+        state.db.call(move |db| {
+            let conn = db.conn();
 
-    let deleted = conn
-        .execute("DELETE FROM webhooks WHERE name = ?1", [&name])
-        .map_err(|e| e.to_string())?;
+            let deleted = conn
+                .execute("DELETE FROM webhooks WHERE name = ?1", [&name])
+                .map_err(|e| e.to_string())?;
 
-    if deleted == 0 {
-        return Err(format!("Webhook not found: {}", name));
+            if deleted == 0 {
+                return Err(format!("Webhook not found: {}", name));
+            }
+
+            Ok::<_, String>(())
+        }).await?;
     }
 
+    secrets
+        .delete(&secrets::webhook_headers_key(&name))
+        .map_err(|e| format!("Failed to delete webhook secrets: {}", e))?;
+
     log::info!("Webhook {} deleted", name);
     Ok(())
 }
@@ -201,61 +243,64 @@ pub async fn toggle_webhook(
     state: State<'_, AppState>,
     name: String,
     enabled: bool,
-) -> Result<(), String> {
+) -> AppResult<()> {
     log::info!("Toggling webhook {}: {}", name, enabled);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let conn = db.conn();
+    state.db.call(move |db| {
+        let conn = db.conn();
 
-    conn.execute(
-        "UPDATE webhooks SET enabled = ?1 WHERE name = ?2",
-        rusqlite::params![enabled, name],
-    )
-    .map_err(|e| e.to_string())?;
+        conn.execute(
+            "UPDATE webhooks SET enabled = ?1 WHERE name = ?2",
+            rusqlite::params![enabled, name],
+        )
+        .map_err(|e| e.to_string())?;
 
-    Ok(())
+        Ok(())
+    }).await
 }
 
 /// Trigger a webhook manually
 #[tauri::command]
 pub async fn trigger_webhook(
     state: State<'_, AppState>,
+    secrets: State<'_, Arc<SecretsService>>,
     name: String,
     event: String,
     data: serde_json::Value,
-) -> Result<(), String> {
+) -> AppResult<()> {
     log::info!("Triggering webhook: {} for event: {}", name, event);
 
-    // Get webhook config from database (scoped to release lock)
-    let config = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
-        let conn = db.conn();
-
-        let record = conn
-            .query_row(
-                "SELECT name, preset, url, method, headers, enabled, timeout, retries, created_at, last_used_at
-                 FROM webhooks
-                 WHERE name = ?1",
-                [&name],
-                |row| {
-                    Ok(WebhookRecord {
-                        name: row.get(0)?,
-                        preset: row.get(1)?,
-                        url: row.get(2)?,
-                        method: row.get(3)?,
-                        headers: row.get(4)?,
-                        enabled: row.get(5)?,
-                        timeout: row.get(6)?,
-                        retries: row.get(7)?,
-                        created_at: row.get(8)?,
-                        last_used_at: row.get(9)?,
-                    })
-                },
-            )
-            .map_err(|e| format!("Webhook not found: {}", e))?;
-
-        record.to_config()?
-    }; // db lock is released here
+    // Get webhook config from database
+    let record = {
+        let name = name.clone();
+        state.db.call(move |db| {
+            let conn = db.conn();
+
+            conn
+                .query_row(
+                    "SELECT name, preset, url, method, headers, enabled, timeout, retries, created_at, last_used_at
+                     FROM webhooks
+                     WHERE name = ?1",
+                    [&name],
+                    |row| {
+                        Ok(WebhookRecord {
+                            name: row.get(0)?,
+                            preset: row.get(1)?,
+                            url: row.get(2)?,
+                            method: row.get(3)?,
+                            headers: row.get(4)?,
+                            enabled: row.get(5)?,
+                            timeout: row.get(6)?,
+                            retries: row.get(7)?,
+                            created_at: row.get(8)?,
+                            last_used_at: row.get(9)?,
+                        })
+                    },
+                )
+                .map_err(|e| format!("Webhook not found: {}", e))
+        }).await?
+    };
+    let config = record.to_config_with_secrets(&secrets)?;
 
     // Create payload
     let payload = WebhookPayload {
@@ -268,17 +313,21 @@ pub async fn trigger_webhook(
     let webhook_service = WebhookService::new();
     webhook_service.trigger(&config, payload).await?;
 
-    // Update last_used_at (scoped to release lock)
+    // Update last_used_at
     {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
-        let conn = db.conn();
-        let now = chrono::Utc::now().timestamp();
+        let name = name.clone();
+        state.db.call(move |db| {
+            let conn = db.conn();
+            let now = chrono::Utc::now().timestamp();
+
+            conn.execute(
+                "UPDATE webhooks SET last_used_at = ?1 WHERE name = ?2",
+                rusqlite::params![now, name],
+            )
+            .map_err(|e| e.to_string())?;
 
-        conn.execute(
-            "UPDATE webhooks SET last_used_at = ?1 WHERE name = ?2",
-            rusqlite::params![now, name],
-        )
-        .map_err(|e| e.to_string())?;
+            Ok::<_, String>(())
+        }).await?;
     }
 
     log::info!("Webhook {} triggered successfully", name);
@@ -287,41 +336,45 @@ pub async fn trigger_webhook(
 
 /// Test webhook connection
 #[tauri::command]
-pub async fn test_webhook(state: State<'_, AppState>, name: String) -> Result<String, String> {
+pub async fn test_webhook(
+    state: State<'_, AppState>,
+    secrets: State<'_, Arc<SecretsService>>,
+    name: String,
+) -> AppResult<String> {
     log::info!("Testing webhook: {}", name);
 
-    // Get webhook config (scoped to release lock)
-    let config = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
-        let conn = db.conn();
-
-        let record = conn
-            .query_row(
-                "SELECT name, preset, url, method, headers, enabled, timeout, retries, created_at, last_used_at
-                 FROM webhooks
-                 WHERE name = ?1",
-                [&name],
-                |row| {
-                    Ok(WebhookRecord {
-                        name: row.get(0)?,
-                        preset: row.get(1)?,
-                        url: row.get(2)?,
-                        method: row.get(3)?,
-                        headers: row.get(4)?,
-                        enabled: row.get(5)?,
-                        timeout: row.get(6)?,
-                        retries: row.get(7)?,
-                        created_at: row.get(8)?,
-                        last_used_at: row.get(9)?,
-                    })
-                },
-            )
-            .map_err(|e| format!("Webhook not found: {}", e))?;
-
-        record.to_config()?
-    }; // db lock is released here
+    // Get webhook config
+    let record = {
+        state.db.call(move |db| {
+            let conn = db.conn();
+
+            conn
+                .query_row(
+                    "SELECT name, preset, url, method, headers, enabled, timeout, retries, created_at, last_used_at
+                     FROM webhooks
+                     WHERE name = ?1",
+                    [&name],
+                    |row| {
+                        Ok(WebhookRecord {
+                            name: row.get(0)?,
+                            preset: row.get(1)?,
+                            url: row.get(2)?,
+                            method: row.get(3)?,
+                            headers: row.get(4)?,
+                            enabled: row.get(5)?,
+                            timeout: row.get(6)?,
+                            retries: row.get(7)?,
+                            created_at: row.get(8)?,
+                            last_used_at: row.get(9)?,
+                        })
+                    },
+                )
+                .map_err(|e| format!("Webhook not found: {}", e))
+        }).await?
+    };
+    let config = record.to_config_with_secrets(&secrets)?;
 
     // Test webhook
     let webhook_service = WebhookService::new();
-    webhook_service.test(&config).await
+    Ok(webhook_service.test(&config).await?)
 }
@@ -0,0 +1,71 @@
+/**
+ * Weekly Review Commands (v3.9.0)
+ */
+
+use crate::services::weekly_review::{WeeklyReport, WeeklyReviewConfig, WeeklyReviewService};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Report for the week containing `date` (YYYY-MM-DD, default: this week).
+/// With `deliver`, it's also sent by notification / webhook as configured.
+#[tauri::command]
+pub async fn review_generate_weekly(
+    date: Option<String>,
+    deliver: Option<bool>,
+    service: State<'_, Arc<WeeklyReviewService>>,
+) -> AppResult<WeeklyReport> {
+    let service_clone = Arc::clone(&service.inner());
+    let mut report = tokio::task::spawn_blocking(move || {
+        service_clone
+            .generate(date.as_deref())
+            .map_err(|e| format!("Failed to generate weekly review: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+
+    if deliver.unwrap_or(false) {
+        service
+            .deliver(&mut report)
+            .await
+            .map_err(|e| format!("Failed to deliver weekly review: {}", e))?;
+    }
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn review_list_weekly(
+    limit: Option<usize>,
+    service: State<'_, Arc<WeeklyReviewService>>,
+) -> AppResult<Vec<WeeklyReport>> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .list(limit.unwrap_or(12))
+            .map_err(|e| format!("Failed to list weekly reviews: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn review_get_weekly_config(
+    service: State<'_, Arc<WeeklyReviewService>>,
+) -> AppResult<WeeklyReviewConfig> {
+    Ok(service.get_config())
+}
+
+#[tauri::command]
+pub async fn review_update_weekly_config(
+    config: WeeklyReviewConfig,
+    service: State<'_, Arc<WeeklyReviewService>>,
+) -> AppResult<()> {
+    let service_clone = Arc::clone(&service.inner());
+    Ok(tokio::task::spawn_blocking(move || {
+        service_clone
+            .update_config(config)
+            .map_err(|e| format!("Failed to update weekly review config: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??)
+}
@@ -0,0 +1,60 @@
+/**
+ * Window Manager Commands (v3.9.0)
+ *
+ * Listing, focusing, opening and arranging app windows, as the manage_windows tool does
+ */
+
+use crate::services::window_manager::{WindowInfo, WindowManagerService, WindowPlacement};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Run a window manager call off the async runtime (it shells out / waits on the OS)
+async fn blocking<T, F>(service: &State<'_, Arc<WindowManagerService>>, context: &'static str, f: F) -> AppResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&WindowManagerService) -> anyhow::Result<T> + Send + 'static,
+{
+    let service_clone = Arc::clone(service.inner());
+    Ok(tokio::task::spawn_blocking(move || f(&service_clone).map_err(|e| format!("{}: {}", context, e)))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))??)
+}
+
+#[tauri::command]
+pub async fn window_list(
+    filter: Option<String>,
+    service: State<'_, Arc<WindowManagerService>>,
+) -> AppResult<Vec<WindowInfo>> {
+    blocking(&service, "Failed to list windows", move |s| s.list_windows(filter.as_deref())).await
+}
+
+/// Focus a window by id, app name or title
+#[tauri::command]
+pub async fn window_focus(target: String, service: State<'_, Arc<WindowManagerService>>) -> AppResult<WindowInfo> {
+    blocking(&service, "Failed to focus window", move |s| s.focus(&target)).await
+}
+
+/// Launch an app, optionally with files or folders; returns the resolved paths
+#[tauri::command]
+pub async fn window_open_app(
+    app: String,
+    paths: Option<Vec<String>>,
+    service: State<'_, Arc<WindowManagerService>>,
+) -> AppResult<Vec<String>> {
+    blocking(&service, "Failed to open app", move |s| s.open_app(&app, &paths.unwrap_or_default())).await
+}
+
+#[tauri::command]
+pub async fn window_open_url(url: String, service: State<'_, Arc<WindowManagerService>>) -> AppResult<String> {
+    blocking(&service, "Failed to open URL", move |s| s.open_url(&url)).await
+}
+
+#[tauri::command]
+pub async fn window_move_resize(
+    target: String,
+    placement: WindowPlacement,
+    service: State<'_, Arc<WindowManagerService>>,
+) -> AppResult<WindowInfo> {
+    blocking(&service, "Failed to move window", move |s| s.move_resize(&target, placement)).await
+}
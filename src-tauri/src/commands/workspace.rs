@@ -0,0 +1,55 @@
+/**
+ * Workspace Commands (v3.9.0)
+ *
+ * Show, pin and configure the active project used for context and file tools
+ */
+
+use crate::services::workspace::{Workspace, WorkspaceConfig, WorkspaceService};
+use crate::AppResult;
+use std::sync::Arc;
+use tauri::State;
+
+/// Active project, if one is pinned or detected
+#[tauri::command]
+pub async fn workspace_get_active(
+    service: State<'_, Arc<WorkspaceService>>,
+) -> AppResult<Option<Workspace>> {
+    let service = Arc::clone(&service);
+    Ok(tokio::task::spawn_blocking(move || service.current())
+        .await
+        .map_err(|e| format!("Workspace task failed: {}", e))?
+        .map_err(|e| format!("Failed to detect workspace: {}", e))?)
+}
+
+/// Pin the project containing `path`; `None` goes back to automatic detection
+#[tauri::command]
+pub async fn workspace_set_active(
+    path: Option<String>,
+    service: State<'_, Arc<WorkspaceService>>,
+) -> AppResult<Option<Workspace>> {
+    log::info!("Command: workspace_set_active ({:?})", path);
+
+    let service = Arc::clone(&service);
+    Ok(tokio::task::spawn_blocking(move || service.set_active(path.as_deref()))
+        .await
+        .map_err(|e| format!("Workspace task failed: {}", e))?
+        .map_err(|e| format!("Failed to set workspace: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn workspace_get_config(
+    service: State<'_, Arc<WorkspaceService>>,
+) -> AppResult<WorkspaceConfig> {
+    Ok(service.get_config())
+}
+
+#[tauri::command]
+pub async fn workspace_update_config(
+    config: WorkspaceConfig,
+    service: State<'_, Arc<WorkspaceService>>,
+) -> AppResult<()> {
+    log::info!("Command: workspace_update_config");
+
+    Ok(service.update_config(config)
+        .map_err(|e| format!("Failed to update workspace config: {}", e))?)
+}
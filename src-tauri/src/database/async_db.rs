@@ -0,0 +1,193 @@
+//! Async Database Handle (v3.9.0)
+//!
+//! Async commands used to lock the shared `std::sync::Mutex<Database>` on a
+//! Tokio worker, so one slow query stalled every other command scheduled on
+//! that worker. `AsyncDatabase` runs the closure on the blocking pool instead.
+//!
+//! Features:
+//! - `call` for async code: lock + query on `spawn_blocking`
+//! - `call_mut` for async code that replaces the connection (profile switch, unlock)
+//! - `lock` for code that is already synchronous (service threads)
+//! - Cheap `Clone`, so background tasks can own a handle
+//!
+//! Never hold the guard from `lock` across an `.await`; clippy's
+//! `await_holding_lock` (on by default) rejects it.
+
+use super::Database;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Error from `AsyncDatabase::call` itself (poisoned lock, panicked task)
+///
+/// Converts into `String`, `anyhow::Error` and `AppError` (poisoned locks
+/// aren't retryable).
+#[derive(Debug, Clone)]
+pub enum DatabaseTaskError {
+    /// A thread panicked while holding the database lock
+    Poisoned,
+    /// The blocking task panicked or was cancelled
+    Join(String),
+}
+
+impl fmt::Display for DatabaseTaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DatabaseTaskError::Poisoned => f.write_str("Database lock poisoned"),
+            DatabaseTaskError::Join(e) => write!(f, "Database task failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for DatabaseTaskError {}
+
+impl From<DatabaseTaskError> for String {
+    fn from(error: DatabaseTaskError) -> Self {
+        error.to_string()
+    }
+}
+
+/// Shared database connection usable from async commands
+#[derive(Clone)]
+pub struct AsyncDatabase {
+    inner: Arc<Mutex<Database>>,
+}
+
+impl AsyncDatabase {
+    pub fn new(db: Database) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(db)),
+        }
+    }
+
+    /// Run `f` with the database on the blocking thread pool
+    pub async fn call<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&Database) -> Result<T, E> + Send + 'static,
+        T: Send + 'static,
+        E: From<DatabaseTaskError> + Send + 'static,
+    {
+        let inner = Arc::clone(&self.inner);
+        tokio::task::spawn_blocking(move || {
+            let db = inner
+                .lock()
+                .map_err(|_| DatabaseTaskError::Poisoned)?;
+            f(&db)
+        })
+        .await
+        .map_err(|e| DatabaseTaskError::Join(e.to_string()))?
+    }
+
+    /// Run `f` with mutable access to the database on the blocking thread pool
+    pub async fn call_mut<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut Database) -> Result<T, E> + Send + 'static,
+        T: Send + 'static,
+        E: From<DatabaseTaskError> + Send + 'static,
+    {
+        let inner = Arc::clone(&self.inner);
+        tokio::task::spawn_blocking(move || {
+            let mut db = inner
+                .lock()
+                .map_err(|_| DatabaseTaskError::Poisoned)?;
+            f(&mut db)
+        })
+        .await
+        .map_err(|e| DatabaseTaskError::Join(e.to_string()))?
+    }
+
+    /// Lock synchronously (only from synchronous code, never across an `.await`)
+    pub fn lock(&self) -> Result<MutexGuard<'_, Database>, DatabaseTaskError> {
+        self.inner
+            .lock()
+            .map_err(|_| DatabaseTaskError::Poisoned)
+    }
+
+    /// The underlying mutex, for APIs that take `&Mutex<Database>`
+    pub fn as_mutex(&self) -> &Mutex<Database> {
+        &self.inner
+    }
+}
+
+impl From<Arc<Mutex<Database>>> for AsyncDatabase {
+    fn from(inner: Arc<Mutex<Database>>) -> Self {
+        Self { inner }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::params;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_call_runs_queries_off_the_runtime() {
+        let db = AsyncDatabase::new(Database::open_in_memory().unwrap());
+
+        db.call(|db| {
+            db.conn().execute("CREATE TABLE items (value INTEGER)", [])?;
+            Ok::<_, anyhow::Error>(())
+        })
+        .await
+        .unwrap();
+
+        let tasks: Vec<_> = (0..8)
+            .map(|i| {
+                let db = db.clone();
+                tokio::spawn(async move {
+                    db.call(move |db| {
+                        db.conn()
+                            .execute("INSERT INTO items (value) VALUES (?1)", params![i])
+                            .map_err(|e| e.to_string())
+                    })
+                    .await
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        let count: i64 = db
+            .call(|db| {
+                db.conn()
+                    .query_row("SELECT COUNT(*) FROM items", [], |row| row.get(0))
+                    .map_err(|e| e.to_string())
+            })
+            .await
+            .unwrap();
+        assert_eq!(count, 8);
+
+        let sum: i64 = db
+            .lock()
+            .unwrap()
+            .conn()
+            .query_row("SELECT SUM(value) FROM items", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(sum, 28);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_call_mut_replaces_database() {
+        let db = AsyncDatabase::new(Database::open_in_memory().unwrap());
+        db.call(|db| db.conn().execute_batch("CREATE TABLE stale (x INTEGER)").map_err(|e| e.to_string()))
+            .await
+            .unwrap();
+
+        db.call_mut(|db| {
+            *db = Database::open_in_memory().map_err(|e| e.to_string())?;
+            Ok::<_, String>(())
+        })
+        .await
+        .unwrap();
+
+        let stale: i64 = db
+            .call(|db| {
+                db.conn()
+                    .query_row("SELECT COUNT(*) FROM sqlite_master WHERE name = 'stale'", [], |row| row.get(0))
+                    .map_err(|e| e.to_string())
+            })
+            .await
+            .unwrap();
+        assert_eq!(stale, 0);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod async_db;
 pub mod models;
 pub mod schema;
 
@@ -5,9 +6,11 @@ pub mod schema;
 mod tests;
 
 use rusqlite::Connection;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use anyhow::{Context, Result as AnyhowResult};
 
+pub use async_db::{AsyncDatabase, DatabaseTaskError};
+
 pub struct Database {
     conn: Connection,
 }
@@ -15,7 +18,11 @@ pub struct Database {
 impl Database {
     /// Create a new database connection
     pub fn new() -> AnyhowResult<Self> {
-        let db_path = Self::get_db_path()?;
+        Self::open(&Self::get_db_path()?)
+    }
+
+    /// Open (or create) a database at a specific path (v3.9.0: per-profile databases)
+    pub fn open(db_path: &Path) -> AnyhowResult<Self> {
         log::info!("Database path: {:?}", db_path);
 
         // Ensure parent directory exists
@@ -24,7 +31,8 @@ impl Database {
                 .context("Failed to create database directory")?;
         }
 
-        let conn = Connection::open(&db_path)
+        // Keyed with the SQLCipher data key when encryption is unlocked (v3.9.0)
+        let conn = crate::services::encryption::open_connection(db_path)
             .context("Failed to open database connection")?;
 
         // Enable foreign keys
@@ -37,6 +45,19 @@ impl Database {
         Ok(db)
     }
 
+    /// Create an in-memory placeholder database (v3.9.0: used while storage is locked)
+    pub fn open_in_memory() -> AnyhowResult<Self> {
+        let conn = Connection::open_in_memory()
+            .context("Failed to create in-memory database")?;
+
+        conn.execute("PRAGMA foreign_keys = ON", [])?;
+
+        let mut db = Self { conn };
+        db.initialize()?;
+
+        Ok(db)
+    }
+
     /// Create an in-memory database for testing
     #[cfg(test)]
     pub fn new_test_db() -> AnyhowResult<Self> {
@@ -72,6 +93,9 @@ impl Database {
         // Migrate persona settings to v3.3.0 (10 parameters)
         schema::migrate_persona_settings(&self.conn)?;
 
+        // Widen persona change reasons and add evidence (v3.9.0)
+        schema::migrate_persona_changes(&self.conn)?;
+
         // Initialize tool settings (v3.3.0)
         schema::initialize_tool_settings(&self.conn)?;
 
@@ -155,6 +179,19 @@ impl Database {
 
     /// Update persona parameters and track changes (v3.8.0)
     pub fn update_persona(&self, new_params: &models::PersonaParameters, reason: &str) -> AnyhowResult<()> {
+        self.update_persona_with_context(new_params, reason, None).map(|_| ())
+    }
+
+    /// Update persona parameters, recording what drove the change (v3.9.0)
+    ///
+    /// `context` is stored as JSON with the change (feedback, memories,
+    /// preset, experiment...). Returns the change id, `None` if nothing changed.
+    pub fn update_persona_with_context(
+        &self,
+        new_params: &models::PersonaParameters,
+        reason: &str,
+        context: Option<&serde_json::Value>,
+    ) -> AnyhowResult<Option<String>> {
         let now = chrono::Utc::now().timestamp_millis();
 
         // Load current persona
@@ -188,29 +225,33 @@ impl Database {
 
         // Record change in persona_changes table
         if change_magnitude > 0.0 {
+            let change_id = uuid::Uuid::new_v4().to_string();
             let previous_json = serde_json::to_string(&current_params)?;
             let new_json = serde_json::to_string(new_params)?;
             let changed_json = serde_json::to_string(&changed_params)?;
+            let context_json = context.map(serde_json::to_string).transpose()?;
 
             self.conn.execute(
-                "INSERT INTO persona_changes (id, previous_params, new_params, changed_parameters, change_magnitude, timestamp, reason)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                "INSERT INTO persona_changes (id, previous_params, new_params, changed_parameters, change_magnitude, timestamp, reason, context)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
                 rusqlite::params![
-                    uuid::Uuid::new_v4().to_string(),
+                    change_id,
                     previous_json,
                     new_json,
                     changed_json,
                     change_magnitude,
                     now,
                     reason,
+                    context_json,
                 ],
             )?;
 
             log::info!("Persona updated: {} parameters changed, magnitude = {:.2}, reason = {}",
                        changed_params.len(), change_magnitude, reason);
+            return Ok(Some(change_id));
         }
 
-        Ok(())
+        Ok(None)
     }
 
     /// Calculate persona change magnitude and identify changed parameters
@@ -21,6 +21,10 @@ pub struct Message {
     pub response_time: Option<i32>,
     pub context_level: Option<i32>,
     pub satisfaction: Option<String>,
+    #[serde(default)]
+    pub is_stale: bool,          // v3.9.0: Superseded by an edit further up the conversation
+    #[serde(default)]
+    pub edited_at: Option<i64>,  // v3.9.0: Set when a user message was edited
 }
 
 /// Persona parameters from database (v3.8.0: 10 standardized parameters, 0-100 scale)
@@ -54,6 +58,23 @@ impl PersonaParameters {
             questioning: self.questioning as f32 / 100.0,
         }
     }
+
+    /// Convert from the learning service's 0-1 scale (v3.9.0)
+    pub fn from_learning_params(params: &crate::services::learning::PersonaParameters) -> Self {
+        let scale = |value: f32| (value.clamp(0.0, 1.0) * 100.0).round() as i32;
+        Self {
+            formality: scale(params.formality),
+            verbosity: scale(params.verbosity),
+            humor: scale(params.humor),
+            emoji_usage: scale(params.emoji_usage),
+            empathy: scale(params.empathy),
+            creativity: scale(params.creativity),
+            proactiveness: scale(params.proactiveness),
+            technical_depth: scale(params.technical_depth),
+            code_examples: scale(params.code_examples),
+            questioning: scale(params.questioning),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +15,77 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
         [],
     )?;
 
+    // Migration: Conversation organization columns (v3.9.0)
+    conn.execute_batch(
+        "ALTER TABLE conversations ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0;
+         ALTER TABLE conversations ADD COLUMN favorite INTEGER NOT NULL DEFAULT 0;
+         ALTER TABLE conversations ADD COLUMN folder_id TEXT;
+         ALTER TABLE conversations ADD COLUMN archived_at INTEGER;"
+    ).ok(); // Ignore errors if columns already exist
+
+    // Conversation folders and tags (v3.9.0)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS conversation_folders (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+            created_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS conversation_tags (
+            conversation_id TEXT NOT NULL,
+            tag TEXT NOT NULL COLLATE NOCASE,
+            PRIMARY KEY (conversation_id, tag),
+            FOREIGN KEY (conversation_id) REFERENCES conversations(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Migration: Automatic titling / topic labeling marker (v3.9.0)
+    conn.execute(
+        "ALTER TABLE conversations ADD COLUMN topics_labeled_at INTEGER",
+        [],
+    ).ok(); // Ignore error if column already exists
+
+    // Learned topic taxonomy and per-conversation topic labels (v3.9.0)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS conversation_topics (
+            name TEXT PRIMARY KEY COLLATE NOCASE,
+            created_at INTEGER NOT NULL,
+            last_used_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS conversation_topic_labels (
+            conversation_id TEXT NOT NULL,
+            topic TEXT NOT NULL COLLATE NOCASE,
+            source TEXT NOT NULL DEFAULT 'auto' CHECK(source IN ('auto', 'user')),
+            assigned_at INTEGER NOT NULL,
+            PRIMARY KEY (conversation_id, topic),
+            FOREIGN KEY (conversation_id) REFERENCES conversations(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Custom tool sets and the per-conversation binding (v3.9.0)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS tool_sets (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+            description TEXT NOT NULL DEFAULT '',
+            tools TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "ALTER TABLE conversations ADD COLUMN tool_set_id TEXT",
+        [],
+    ).ok(); // Ignore error if column already exists
+
     // Messages table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS messages (
@@ -32,6 +103,28 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
         [],
     )?;
 
+    // Migration: Message editing columns (v3.9.0)
+    conn.execute_batch(
+        "ALTER TABLE messages ADD COLUMN is_stale INTEGER NOT NULL DEFAULT 0;
+         ALTER TABLE messages ADD COLUMN edited_at INTEGER;"
+    ).ok(); // Ignore errors if columns already exist
+
+    // Message revisions table (v3.9.0 - audit trail for edited / superseded messages)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS message_revisions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            message_id TEXT NOT NULL,
+            conversation_id TEXT NOT NULL,
+            role TEXT NOT NULL,
+            content TEXT NOT NULL,
+            timestamp INTEGER NOT NULL,
+            reason TEXT NOT NULL CHECK(reason IN ('edited', 'superseded')),
+            superseded_at INTEGER NOT NULL,
+            FOREIGN KEY (conversation_id) REFERENCES conversations(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
     // Persona settings table (v3.8.0: Standardized to 10 core parameters)
     conn.execute(
         "CREATE TABLE IF NOT EXISTS persona_settings (
@@ -96,6 +189,25 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
         [],
     )?;
 
+    // Migration: Episode provenance columns (v3.9.0)
+    conn.execute("ALTER TABLE episodic_memory ADD COLUMN conversation_id TEXT", []).ok();
+    conn.execute("ALTER TABLE episodic_memory ADD COLUMN message_id TEXT", []).ok();
+
+    // Migration: Source document of chunked episodes (v3.9.0)
+    conn.execute("ALTER TABLE episodic_memory ADD COLUMN document TEXT", []).ok();
+
+    // Migration: Language tag of episodes (v3.9.0)
+    conn.execute("ALTER TABLE episodic_memory ADD COLUMN language TEXT", []).ok();
+
+    // Migration: Manually authored / corrected memories and user notes (v3.9.0)
+    conn.execute("ALTER TABLE episodic_memory ADD COLUMN user_asserted INTEGER NOT NULL DEFAULT 0", []).ok();
+    conn.execute("ALTER TABLE episodic_memory ADD COLUMN user_note TEXT", []).ok();
+
+    // Migration: Memory scope (v3.9.0) - existing memories become global
+    conn.execute("ALTER TABLE episodic_memory ADD COLUMN scope TEXT NOT NULL DEFAULT 'global'", []).ok();
+    conn.execute("ALTER TABLE episodic_memory ADD COLUMN scope_id TEXT", []).ok();
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_episodic_memory_scope ON episodic_memory(scope, scope_id)", [])?;
+
     // Learning data table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS learning_data (
@@ -130,7 +242,8 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
             changed_parameters TEXT NOT NULL,
             change_magnitude REAL NOT NULL,
             timestamp INTEGER NOT NULL,
-            reason TEXT CHECK(reason IN ('manual', 'preset', 'optimization', 'reset')) NOT NULL
+            reason TEXT CHECK(reason IN ('manual', 'preset', 'optimization', 'reset', 'ab_test', 'rollback')) NOT NULL,
+            context TEXT
         )",
         [],
     )?;
@@ -414,6 +527,24 @@ pub fn create_indexes(conn: &Connection) -> Result<()> {
         [],
     )?;
 
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_conversations_folder
+         ON conversations(folder_id)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_conversation_tags_tag
+         ON conversation_tags(tag)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_conversation_topic_labels_topic
+         ON conversation_topic_labels(topic)",
+        [],
+    )?;
+
     // TODO: episodic_memory table doesn't have a category column yet
     // conn.execute(
     //     "CREATE INDEX IF NOT EXISTS idx_episodic_memory_category
@@ -434,13 +565,6 @@ pub fn create_indexes(conn: &Connection) -> Result<()> {
         [],
     )?;
 
-    // Index for temporal retrieval (retention score + importance)
-    conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_episodic_memory_retention
-         ON episodic_memory(retention_score DESC, importance DESC)",
-        [],
-    )?;
-
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_learning_data_timestamp
          ON learning_data(timestamp DESC)",
@@ -685,6 +809,62 @@ pub fn migrate_persona_settings(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Widen persona_changes reasons and add the evidence column (v3.9.0)
+///
+/// SQLite can't alter a CHECK constraint, so older tables are rebuilt.
+pub fn migrate_persona_changes(conn: &Connection) -> Result<()> {
+    let table_sql: String = conn.query_row(
+        "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'persona_changes'",
+        [],
+        |row| row.get(0),
+    )?;
+
+    if table_sql.contains("'rollback'") {
+        log::debug!("persona_changes table already on v3.9.0 schema, no migration needed");
+        return Ok(());
+    }
+
+    log::info!("Migrating persona_changes table to v3.9.0 (ab_test/rollback reasons, context)");
+
+    conn.execute("DROP TABLE IF EXISTS persona_changes_old", [])?;
+    conn.execute("ALTER TABLE persona_changes RENAME TO persona_changes_old", [])?;
+    conn.execute(
+        "CREATE TABLE persona_changes (
+            id TEXT PRIMARY KEY,
+            previous_params TEXT NOT NULL,
+            new_params TEXT NOT NULL,
+            changed_parameters TEXT NOT NULL,
+            change_magnitude REAL NOT NULL,
+            timestamp INTEGER NOT NULL,
+            reason TEXT CHECK(reason IN ('manual', 'preset', 'optimization', 'reset', 'ab_test', 'rollback')) NOT NULL,
+            context TEXT
+        )",
+        [],
+    )?;
+    conn.execute(
+        "INSERT INTO persona_changes (id, previous_params, new_params, changed_parameters, change_magnitude, timestamp, reason)
+         SELECT id, previous_params, new_params, changed_parameters, change_magnitude, timestamp, reason
+         FROM persona_changes_old",
+        [],
+    )?;
+    conn.execute("DROP TABLE persona_changes_old", [])?;
+
+    // Indexes were dropped along with the old table
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_persona_changes_timestamp
+         ON persona_changes(timestamp DESC)",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_persona_changes_reason
+         ON persona_changes(reason)",
+        [],
+    )?;
+
+    log::info!("persona_changes migration completed successfully");
+    Ok(())
+}
+
 /// Initialize default tool settings for all 6 production tools
 pub fn initialize_tool_settings(conn: &Connection) -> Result<()> {
     let now = chrono::Utc::now().timestamp();
@@ -7,6 +7,12 @@
  * - Human-readable message
  * - Recovery suggestion for users
  * - Optional technical details for debugging
+ * - Whether retrying can succeed (v3.9.0)
+ *
+ * Every command returns `AppResult` (v3.9.0). Error sites pick their code
+ * with the typed constructors (`AppError::not_found`, ...) or through the
+ * `From` impls below, which also look inside `anyhow` chains. Plain `String`
+ * errors from older services fall back to classification by their text.
  */
 
 use serde::{Deserialize, Serialize};
@@ -54,6 +60,7 @@ pub enum ErrorCode {
     InvalidInput = 6001,
     MissingRequiredField = 6002,
     ValidationFailed = 6003,
+    NotFound = 6004,
 
     // Authentication errors (7xxx)
     OAuthFailed = 7001,
@@ -62,6 +69,7 @@ pub enum ErrorCode {
 
     // System errors (9xxx)
     InternalError = 9001,
+    ServiceUnavailable = 9002,
     Unknown = 9999,
 }
 
@@ -85,6 +93,92 @@ impl ErrorCode {
             _ => "Unknown",
         }
     }
+
+    /// Whether the same request can succeed later without changing the input
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::OllamaNotRunning
+                | ErrorCode::OllamaConnectionFailed
+                | ErrorCode::NetworkTimeout
+                | ErrorCode::NetworkUnavailable
+                | ErrorCode::ModelDownloadFailed
+                | ErrorCode::ModelLoadFailed
+                | ErrorCode::DatabaseQueryFailed
+                | ErrorCode::DatabaseLocked
+                | ErrorCode::InferenceFailed
+                | ErrorCode::EmbeddingFailed
+                | ErrorCode::RagRetrievalFailed
+                | ErrorCode::OAuthFailed
+                | ErrorCode::ServiceUnavailable
+        )
+    }
+
+    /// Best-effort code for a plain error message (v3.9.0)
+    ///
+    /// Fallback for `String` errors only; needles are whole phrases so that
+    /// ordinary words in a message don't pick the code.
+    pub fn classify(message: &str) -> Self {
+        let m = message.to_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|n| m.contains(n));
+
+        if has(&["poisoned", "lock error", "failed to lock"]) {
+            // std locks only fail when a holder panicked; retrying won't fix that
+            ErrorCode::InternalError
+        } else if has(&["database is locked", "database table is locked", "database busy"]) {
+            ErrorCode::DatabaseLocked
+        } else if has(&["ollama"]) && has(&["not running", "connection refused", "failed to connect", "is ollama running"]) {
+            ErrorCode::OllamaNotRunning
+        } else if has(&["ollama"]) && has(&["connect", "request failed"]) {
+            ErrorCode::OllamaConnectionFailed
+        } else if has(&["timed out", "timeout"]) {
+            ErrorCode::NetworkTimeout
+        } else if has(&["is unavailable", "not initialized", "not available"]) {
+            ErrorCode::ServiceUnavailable
+        } else if has(&["model"]) && has(&["not found", "not installed", "pull"]) {
+            ErrorCode::ModelNotFound
+        } else if has(&["insufficient vram", "out of memory"]) {
+            ErrorCode::InsufficientVram
+        } else if has(&["permission denied", "access denied", "not permitted"]) {
+            ErrorCode::PermissionDenied
+        } else if has(&["no space left", "disk full"]) {
+            ErrorCode::DiskFull
+        } else if has(&["token expired", "session expired"]) {
+            ErrorCode::TokenExpired
+        } else if has(&["oauth", "csrf"]) {
+            ErrorCode::OAuthFailed
+        } else if has(&["storage is locked", "unauthorized", "not authorized", "wrong passphrase"]) {
+            ErrorCode::Unauthorized
+        } else if has(&["cannot be empty", "is required", "missing"]) {
+            ErrorCode::MissingRequiredField
+        } else if has(&["not found", "no such", "does not exist"]) {
+            ErrorCode::NotFound
+        } else if has(&["invalid", "must be", "too long", "not allowed", "unsupported"]) {
+            ErrorCode::InvalidInput
+        } else if has(&["context window", "context length"]) {
+            ErrorCode::ContextTooLong
+        } else if has(&["embedding", "embed "]) {
+            ErrorCode::EmbeddingFailed
+        } else if has(&["rag", "retriev", "vector store", "lancedb"]) {
+            ErrorCode::RagRetrievalFailed
+        } else if has(&["generate", "inference", "llm", "llava"]) {
+            ErrorCode::InferenceFailed
+        } else if has(&["corrupt", "not a database"]) {
+            ErrorCode::DatabaseCorrupted
+        } else if has(&["migration"]) {
+            ErrorCode::MigrationFailed
+        } else if has(&["database", "sqlite", "query failed", "sql error"]) {
+            ErrorCode::DatabaseQueryFailed
+        } else if has(&["failed to read", "read error"]) {
+            ErrorCode::FileReadFailed
+        } else if has(&["failed to write", "failed to create file", "write error"]) {
+            ErrorCode::FileWriteFailed
+        } else if has(&["network", "connection refused", "dns", "http"]) {
+            ErrorCode::NetworkUnavailable
+        } else {
+            ErrorCode::InternalError
+        }
+    }
 }
 
 /// Structured application error with recovery suggestions
@@ -99,6 +193,9 @@ pub struct AppError {
     /// Optional technical details for debugging
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<String>,
+    /// Whether retrying the same request can succeed (v3.9.0)
+    #[serde(default)]
+    pub retryable: bool,
     /// Error timestamp
     pub timestamp: i64,
 }
@@ -111,10 +208,57 @@ impl AppError {
             message: message.into(),
             recovery: recovery.into(),
             details: None,
+            retryable: code.is_retryable(),
             timestamp: chrono::Utc::now().timestamp_millis(),
         }
     }
 
+    /// Error with the default recovery of `code` and a specific message (v3.9.0)
+    pub fn with_code(code: ErrorCode, message: impl Into<String>) -> Self {
+        let (_, recovery) = Self::default_messages(code);
+        Self::new(code, message, recovery)
+    }
+
+    /// Input the user has to change (v3.9.0)
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::with_code(ErrorCode::InvalidInput, message)
+    }
+
+    /// Required value left empty (v3.9.0)
+    pub fn missing_field(message: impl Into<String>) -> Self {
+        Self::with_code(ErrorCode::MissingRequiredField, message)
+    }
+
+    /// The requested item doesn't exist (v3.9.0)
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::with_code(ErrorCode::NotFound, message)
+    }
+
+    /// An optional service failed to start (v3.9.0)
+    pub fn unavailable(message: impl Into<String>) -> Self {
+        Self::with_code(ErrorCode::ServiceUnavailable, message)
+    }
+
+    /// A lock whose holder panicked (v3.9.0)
+    ///
+    /// The guarded state may be half-updated, so this is an internal error
+    /// and not retryable, unlike a busy SQLite database.
+    pub fn lock_poisoned(what: &str) -> Self {
+        Self::with_code(ErrorCode::InternalError, format!("{} lock poisoned", what))
+    }
+
+    /// Classify a plain error message (v3.9.0)
+    pub fn from_message(message: impl Into<String>) -> Self {
+        let message = message.into();
+        Self::with_code(ErrorCode::classify(&message), message)
+    }
+
+    /// Override whether the error is retryable
+    pub fn with_retryable(mut self, retryable: bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
     /// Add technical details
     pub fn with_details(mut self, details: impl Into<String>) -> Self {
         self.details = Some(details.into());
@@ -129,6 +273,7 @@ impl AppError {
             message,
             recovery,
             details: Some(error.to_string()),
+            retryable: code.is_retryable(),
             timestamp: chrono::Utc::now().timestamp_millis(),
         }
     }
@@ -255,6 +400,10 @@ impl AppError {
                 "Validation failed".into(),
                 "Please check your input and try again".into(),
             ),
+            ErrorCode::NotFound => (
+                "Not found".into(),
+                "It may have been deleted. Refresh and try again".into(),
+            ),
 
             // Authentication errors
             ErrorCode::OAuthFailed => (
@@ -275,6 +424,10 @@ impl AppError {
                 "An internal error occurred".into(),
                 "Please restart the application. If the problem persists, contact support".into(),
             ),
+            ErrorCode::ServiceUnavailable => (
+                "This feature is unavailable".into(),
+                "It failed to start. Retry it from Settings > Diagnostics or restart the application".into(),
+            ),
             ErrorCode::Unknown => (
                 "An unknown error occurred".into(),
                 "Please try again. If the problem persists, restart the application".into(),
@@ -293,57 +446,115 @@ impl std::error::Error for AppError {}
 
 // Conversion from common error types
 
+fn sqlite_code(error: &rusqlite::Error) -> ErrorCode {
+    match error {
+        rusqlite::Error::SqliteFailure(e, _) => {
+            match e.code {
+                rusqlite::ErrorCode::DatabaseBusy |
+                rusqlite::ErrorCode::DatabaseLocked => ErrorCode::DatabaseLocked,
+                rusqlite::ErrorCode::DatabaseCorrupt |
+                rusqlite::ErrorCode::NotADatabase => ErrorCode::DatabaseCorrupted,
+                _ => ErrorCode::DatabaseQueryFailed,
+            }
+        }
+        _ => ErrorCode::DatabaseQueryFailed,
+    }
+}
+
+fn io_code(error: &std::io::Error) -> ErrorCode {
+    match error.kind() {
+        std::io::ErrorKind::NotFound => ErrorCode::FileNotFound,
+        std::io::ErrorKind::PermissionDenied => ErrorCode::PermissionDenied,
+        std::io::ErrorKind::TimedOut => ErrorCode::NetworkTimeout,
+        _ => ErrorCode::InternalError,
+    }
+}
+
+fn reqwest_code(error: &reqwest::Error) -> ErrorCode {
+    if error.is_timeout() {
+        ErrorCode::NetworkTimeout
+    } else if error.is_connect() {
+        ErrorCode::OllamaConnectionFailed
+    } else {
+        ErrorCode::NetworkUnavailable
+    }
+}
+
 impl From<rusqlite::Error> for AppError {
     fn from(error: rusqlite::Error) -> Self {
-        let code = match &error {
-            rusqlite::Error::SqliteFailure(e, _) => {
-                match e.code {
-                    rusqlite::ErrorCode::DatabaseBusy |
-                    rusqlite::ErrorCode::DatabaseLocked => ErrorCode::DatabaseLocked,
-                    rusqlite::ErrorCode::DatabaseCorrupt |
-                    rusqlite::ErrorCode::NotADatabase => ErrorCode::DatabaseCorrupted,
-                    _ => ErrorCode::DatabaseQueryFailed,
-                }
-            }
-            _ => ErrorCode::DatabaseQueryFailed,
-        };
-        AppError::from_error(code, error)
+        AppError::from_error(sqlite_code(&error), error)
     }
 }
 
 impl From<std::io::Error> for AppError {
     fn from(error: std::io::Error) -> Self {
-        let code = match error.kind() {
-            std::io::ErrorKind::NotFound => ErrorCode::FileNotFound,
-            std::io::ErrorKind::PermissionDenied => ErrorCode::PermissionDenied,
-            std::io::ErrorKind::TimedOut => ErrorCode::NetworkTimeout,
-            _ => ErrorCode::InternalError,
-        };
-        AppError::from_error(code, error)
+        AppError::from_error(io_code(&error), error)
     }
 }
 
 impl From<reqwest::Error> for AppError {
     fn from(error: reqwest::Error) -> Self {
-        let code = if error.is_timeout() {
-            ErrorCode::NetworkTimeout
-        } else if error.is_connect() {
-            ErrorCode::OllamaConnectionFailed
-        } else {
-            ErrorCode::NetworkUnavailable
-        };
-        AppError::from_error(code, error)
+        AppError::from_error(reqwest_code(&error), error)
     }
 }
 
+/// The code comes from the first typed error in the chain; the message keeps
+/// every context layer
 impl From<anyhow::Error> for AppError {
     fn from(error: anyhow::Error) -> Self {
-        AppError::new(
-            ErrorCode::InternalError,
-            "An error occurred",
-            "Please try again",
-        )
-        .with_details(error.to_string())
+        let message = format!("{:#}", error);
+        for cause in error.chain() {
+            let code = if let Some(e) = cause.downcast_ref::<AppError>() {
+                return e.clone();
+            } else if let Some(e) = cause.downcast_ref::<crate::database::DatabaseTaskError>() {
+                return AppError::from(e.clone());
+            } else if let Some(e) = cause.downcast_ref::<rusqlite::Error>() {
+                sqlite_code(e)
+            } else if let Some(e) = cause.downcast_ref::<std::io::Error>() {
+                io_code(e)
+            } else if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+                reqwest_code(e)
+            } else {
+                continue;
+            };
+            return AppError::with_code(code, message);
+        }
+        AppError::from_message(message)
+    }
+}
+
+impl<T> From<std::sync::PoisonError<T>> for AppError {
+    fn from(_: std::sync::PoisonError<T>) -> Self {
+        AppError::lock_poisoned("State")
+    }
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::from_message(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        AppError::from_message(message)
+    }
+}
+
+impl From<crate::database::DatabaseTaskError> for AppError {
+    fn from(error: crate::database::DatabaseTaskError) -> Self {
+        use crate::database::DatabaseTaskError;
+        match error {
+            DatabaseTaskError::Poisoned => AppError::lock_poisoned("Database"),
+            DatabaseTaskError::Join(e) => AppError::with_code(ErrorCode::InternalError, format!("Database task failed: {}", e)),
+        }
+    }
+}
+
+/// For helpers that still return `Result<T, String>`
+impl From<AppError> for String {
+    fn from(error: AppError) -> Self {
+        error.to_string()
     }
 }
 
@@ -394,4 +605,80 @@ mod tests {
         assert!(error.details.is_some());
         assert!(error.details.unwrap().contains("SQL syntax"));
     }
+
+    #[test]
+    fn test_classify_legacy_messages() {
+        assert_eq!(ErrorCode::classify("DB lock error: poisoned lock: another task failed inside"), ErrorCode::InternalError);
+        assert_eq!(ErrorCode::classify("database is locked"), ErrorCode::DatabaseLocked);
+        assert_eq!(ErrorCode::classify("Webhook not found: daily"), ErrorCode::NotFound);
+        assert_eq!(ErrorCode::classify("Message content cannot be empty"), ErrorCode::MissingRequiredField);
+        assert_eq!(ErrorCode::classify("Backup Service is unavailable: disk"), ErrorCode::ServiceUnavailable);
+        assert_eq!(ErrorCode::classify("Failed to connect to Ollama. Is Ollama running?"), ErrorCode::OllamaNotRunning);
+        assert_eq!(ErrorCode::classify("something odd"), ErrorCode::InternalError);
+
+        // Common words no longer pick a code on their own
+        assert_eq!(ErrorCode::classify("Unknown failure while exporting"), ErrorCode::InternalError);
+        assert_eq!(ErrorCode::classify("Cannot reach the sync peer"), ErrorCode::InternalError);
+        assert_eq!(ErrorCode::classify("Only part of the file was written"), ErrorCode::InternalError);
+    }
+
+    #[test]
+    fn test_poisoned_locks_are_not_retryable() {
+        let error = AppError::from("DB lock error: poisoned lock: another task failed inside");
+        assert_eq!(error.code, ErrorCode::InternalError);
+        assert!(!error.retryable);
+
+        let mutex = std::sync::Arc::new(std::sync::Mutex::new(0));
+        let clone = std::sync::Arc::clone(&mutex);
+        let _ = std::thread::spawn(move || {
+            let _guard = clone.lock().unwrap();
+            panic!("poison the lock");
+        })
+        .join();
+        let error = AppError::from(mutex.lock().unwrap_err());
+        assert_eq!(error.code, ErrorCode::InternalError);
+        assert!(!error.retryable);
+
+        let error = AppError::from(crate::database::DatabaseTaskError::Poisoned);
+        assert_eq!(error.code, ErrorCode::InternalError);
+        assert!(!error.retryable);
+    }
+
+    #[test]
+    fn test_codes_from_typed_errors_in_anyhow_chains() {
+        use anyhow::Context;
+
+        let busy = rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+            None,
+        );
+        let error = AppError::from(anyhow::Error::new(busy).context("Failed to save settings"));
+        assert_eq!(error.code, ErrorCode::DatabaseLocked);
+        assert!(error.retryable);
+        assert!(error.message.starts_with("Failed to save settings"));
+
+        let missing = std::io::Error::new(std::io::ErrorKind::NotFound, "gone");
+        let error = AppError::from(Err::<(), _>(missing).context("Only the vault").unwrap_err());
+        assert_eq!(error.code, ErrorCode::FileNotFound);
+
+        let error = AppError::from(anyhow::Error::new(AppError::not_found("Plan 7 not found")));
+        assert_eq!(error.code, ErrorCode::NotFound);
+        assert_eq!(error.message, "Plan 7 not found");
+    }
+
+    #[test]
+    fn test_string_errors_keep_message_and_get_hints() {
+        let error = AppError::from(format!("Webhook not found: {}", "daily"));
+        assert_eq!(error.code, ErrorCode::NotFound);
+        assert_eq!(error.message, "Webhook not found: daily");
+        assert!(!error.recovery.is_empty());
+        assert!(!error.retryable);
+
+        let error = AppError::from("database is locked");
+        assert!(error.retryable);
+
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], "DATABASE_LOCKED");
+        assert_eq!(json["retryable"], true);
+    }
 }
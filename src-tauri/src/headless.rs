@@ -0,0 +1,294 @@
+//! Headless runtime (v3.9.0)
+//!
+//! Opens the desktop app's data directory without Tauri, for `garden-cli`
+//! and scripts.
+//!
+//! Features:
+//! - Same storage resolution as the app: active (or chosen) profile, unlocked
+//!   with the keychain key or a passphrase when encryption is on
+//! - Embeddings and RAG are opened lazily, so export and diagnostics stay fast
+//! - Chat goes through the app's persona/RAG pipeline and is saved like a chat
+//!   from the UI
+
+use crate::database::Database;
+use crate::services::embedding::UnifiedEmbeddingService;
+use crate::services::encryption::{self, EncryptionService};
+use crate::services::memory_browser::{self, MemoryFilter, MemoryItem};
+use crate::services::ollama;
+use crate::services::profile::{ProfilePaths, ProfileService};
+use crate::services::provenance::Citation;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+
+#[cfg(feature = "lancedb-support")]
+pub use crate::services::rag_v2::RagServiceV2 as RagEngine;
+#[cfg(not(feature = "lancedb-support"))]
+pub use crate::services::rag::RagService as RagEngine;
+
+/// Tables counted by `diagnostics`
+const COUNTED_TABLES: &[&str] = &["conversations", "messages", "episodic_memory", "wiki_facts"];
+
+/// How to open the data directory
+#[derive(Debug, Clone, Default)]
+pub struct HeadlessOptions {
+    /// Defaults to the app's data directory
+    pub data_dir: Option<PathBuf>,
+    /// Defaults to the profile that was active when the app last ran
+    pub profile: Option<String>,
+    /// Needed when storage is encrypted with a passphrase
+    pub passphrase: Option<String>,
+}
+
+/// A RAG search hit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub id: String,
+    pub score: f32,
+    pub user_message: String,
+    pub ai_response: String,
+    pub created_at: i64,
+    pub conversation_id: Option<String>,
+}
+
+/// Reply of a headless chat turn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatReply {
+    pub conversation_id: String,
+    pub response: String,
+    pub citations: Vec<Citation>,
+    /// Episode id when the exchange was stored in memory
+    pub memory_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostics {
+    pub version: String,
+    pub data_dir: PathBuf,
+    pub db_path: PathBuf,
+    pub db_size_bytes: u64,
+    pub encrypted: bool,
+    /// `PRAGMA quick_check` result ("ok" when healthy)
+    pub integrity: String,
+    pub counts: BTreeMap<String, i64>,
+    pub chat_model: String,
+    pub ollama_reachable: bool,
+    /// Only filled when the embedding model was loaded
+    pub embedding_mode: Option<String>,
+}
+
+/// The app's data directory
+pub fn default_data_dir() -> Result<PathBuf> {
+    Ok(dirs::data_dir()
+        .ok_or_else(|| anyhow!("Failed to get data directory"))?
+        .join("garden-of-eden-v3"))
+}
+
+/// Opened data directory
+pub struct Headless {
+    data_dir: PathBuf,
+    paths: ProfilePaths,
+    db: Arc<Mutex<Database>>,
+    embedding: OnceLock<Arc<UnifiedEmbeddingService>>,
+    rag: OnceLock<Arc<RagEngine>>,
+}
+
+impl Headless {
+    pub fn open(options: HeadlessOptions) -> Result<Self> {
+        let data_dir = match options.data_dir {
+            Some(dir) => dir,
+            None => default_data_dir()?,
+        };
+
+        // Must unlock before any database is opened
+        let encryption_service = EncryptionService::new(data_dir.clone())?;
+        if let Err(e) = encryption_service.unlock_from_keychain() {
+            log::warn!("Failed to unlock storage from keychain: {}", e);
+        }
+        if encryption::is_locked() {
+            let passphrase = options
+                .passphrase
+                .ok_or_else(|| anyhow!("Storage is encrypted: pass a passphrase to unlock it"))?;
+            encryption_service.unlock(&passphrase)?;
+        }
+
+        let paths = match &options.profile {
+            Some(profile) => ProfileService::registered_paths(&data_dir, profile)?,
+            None => ProfileService::active_paths(&data_dir)?,
+        };
+        let db = Database::open(&paths.db)
+            .with_context(|| format!("Failed to open {}", paths.db.display()))?;
+
+        Ok(Self {
+            data_dir,
+            paths,
+            db: Arc::new(Mutex::new(db)),
+            embedding: OnceLock::new(),
+            rag: OnceLock::new(),
+        })
+    }
+
+    pub fn db(&self) -> Arc<Mutex<Database>> {
+        Arc::clone(&self.db)
+    }
+
+    pub fn paths(&self) -> &ProfilePaths {
+        &self.paths
+    }
+
+    /// Embedding model (loaded on first use, 2-4 seconds)
+    pub fn embedding(&self) -> Arc<UnifiedEmbeddingService> {
+        Arc::clone(self.embedding.get_or_init(|| Arc::new(UnifiedEmbeddingService::new())))
+    }
+
+    pub fn rag(&self) -> Arc<RagEngine> {
+        Arc::clone(self.rag.get_or_init(|| {
+            Arc::new(RagEngine::new_lazy(self.db(), self.embedding(), self.paths.lance_db.clone()))
+        }))
+    }
+
+    /// Memories most relevant to a query
+    pub async fn search(&self, query: &str, top_k: usize) -> Result<Vec<SearchHit>> {
+        let results = self.rag().search_with_scores(query, top_k).await?;
+        Ok(results
+            .into_iter()
+            .map(|(episode, score)| SearchHit {
+                id: episode.id,
+                score,
+                user_message: episode.user_message,
+                ai_response: episode.ai_response,
+                created_at: episode.created_at,
+                conversation_id: episode.conversation_id,
+            })
+            .collect())
+    }
+
+    /// One chat turn, saved to `conversation_id` (a new conversation when `None`)
+    ///
+    /// `remember` stores the exchange in episodic memory like the app does.
+    pub async fn chat(&self, message: &str, conversation_id: Option<&str>, remember: bool) -> Result<ChatReply> {
+        let conversation_id = conversation_id
+            .map(str::to_string)
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let user_message_id = uuid::Uuid::new_v4().to_string();
+        let ai_message_id = uuid::Uuid::new_v4().to_string();
+        let now = chrono::Utc::now().timestamp_millis();
+
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            let conn = db.conn();
+            let title: String = message.chars().take(50).collect();
+            conn.execute(
+                "INSERT OR IGNORE INTO conversations (id, title, mode, created_at, updated_at, message_count)
+                 VALUES (?1, ?2, 'user-led', ?3, ?3, 0)",
+                rusqlite::params![conversation_id, title, now],
+            )?;
+            conn.execute(
+                "INSERT INTO messages (id, conversation_id, role, content, timestamp)
+                 VALUES (?1, ?2, 'user', ?3, ?4)",
+                rusqlite::params![user_message_id, conversation_id, message, now],
+            )?;
+        }
+
+        let cited = ollama::generate_cited_response_for_conversation(
+            message,
+            Some(conversation_id.as_str()),
+            Some(self.rag()),
+            Some(&*self.db),
+        )
+        .await
+        .map_err(|e| anyhow!(e))?;
+
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            let conn = db.conn();
+            let now = chrono::Utc::now().timestamp_millis();
+            conn.execute(
+                "INSERT INTO messages (id, conversation_id, role, content, timestamp)
+                 VALUES (?1, ?2, 'assistant', ?3, ?4)",
+                rusqlite::params![ai_message_id, conversation_id, cited.response, now],
+            )?;
+            conn.execute(
+                "UPDATE conversations SET updated_at = ?1, message_count = message_count + 2 WHERE id = ?2",
+                rusqlite::params![now, conversation_id],
+            )?;
+        }
+
+        let memory_id = if remember {
+            let stored = ollama::store_conversation_in_rag(
+                self.rag(),
+                message,
+                &cited.response,
+                0.5,
+                Some(conversation_id.as_str()),
+                Some(ai_message_id.as_str()),
+            )
+            .await;
+            match stored {
+                Ok(id) => Some(id),
+                Err(e) => {
+                    log::warn!("{}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        Ok(ChatReply {
+            conversation_id,
+            response: cited.response,
+            citations: cited.citations,
+            memory_id,
+        })
+    }
+
+    /// Every memory matching the filter (paging and sort are handled here)
+    pub fn export_memories(&self, filter: &MemoryFilter) -> Result<Vec<MemoryItem>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut filter = filter.clone();
+        let mut items = Vec::new();
+        loop {
+            filter.offset = Some(items.len());
+            let page = memory_browser::browse_memories(db.conn(), &filter)?;
+            let has_more = page.has_more && !page.items.is_empty();
+            items.extend(page.items);
+            if !has_more {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    /// Storage, data and service health. `check_embedding` loads the embedding model.
+    pub async fn diagnostics(&self, check_embedding: bool) -> Result<Diagnostics> {
+        let (integrity, counts) = {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            let conn = db.conn();
+            let integrity: String = conn.query_row("PRAGMA quick_check", [], |row| row.get(0))?;
+            let mut counts: BTreeMap<String, i64> = BTreeMap::new();
+            for table in COUNTED_TABLES {
+                // Some tables are created by services that may not have run yet
+                if let Ok(count) = conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0)) {
+                    counts.insert(table.to_string(), count);
+                }
+            }
+            (integrity, counts)
+        };
+
+        Ok(Diagnostics {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            data_dir: self.data_dir.clone(),
+            db_path: self.paths.db.clone(),
+            db_size_bytes: std::fs::metadata(&self.paths.db).map(|m| m.len()).unwrap_or(0),
+            encrypted: encryption::has_key(),
+            integrity,
+            counts,
+            chat_model: ollama::chat_model(),
+            ollama_reachable: ollama::test_connection().await.unwrap_or(false),
+            embedding_mode: check_embedding.then(|| self.embedding().mode_description().to_string()),
+        })
+    }
+}
@@ -1,9 +1,16 @@
 // Library entry point for Garden of Eden V3
-// Exposes modules for testing and internal use
+// Exposes modules for testing, internal use and the headless CLI
 
 pub mod database;
 pub mod services;
+pub mod headless;  // v3.9.0: Tauri-free runtime used by garden-cli
 
 // Re-export commonly used types for testing
 pub use database::{Database, models, schema};
 pub use services::learning;
+
+// Headless API (v3.9.0)
+pub use headless::{Headless, HeadlessOptions, RagEngine};
+pub use services::embedding::UnifiedEmbeddingService;
+pub use services::memory_browser::{MemoryFilter, MemoryItem};
+pub use services::profile::{ProfilePaths, ProfileService};
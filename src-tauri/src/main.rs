@@ -15,10 +15,11 @@ pub use app_state::{
     CoreServices, AiServices, ToolServices, MemoryServices, IntegrationServices,
 };
 
-use database::Database;
+use database::{AsyncDatabase, Database};
 use services::screen::ScreenCaptureService;
 use services::llava::LlavaService;
 use services::model_installer::ModelInstallerService;
+use services::ollama_supervisor::{OllamaSupervisor, SupervisorConfig};
 use services::learning::LearningService;
 use services::webhook_triggers::WebhookTriggerManager;
 use services::crash_reporter::CrashReporterService;
@@ -31,7 +32,8 @@ use services::plugin::PluginService;
 use services::tool_calling::ToolService;
 use services::tool_implementations::{
     WebSearchTool, UrlFetchTool, FileReadTool, FileWriteTool,
-    SystemInfoTool, CalculatorTool,
+    EditFileTool, DeleteFileTool, GitCommitTool, TerminalHistoryTool, SystemInfoTool, CalculatorTool, TranslateTool,
+    SqlQueryTool, CreateChartTool, RunCodeTool, WindowManagementTool, RunShortcutTool, RunPowerShellScriptTool,
 };
 use services::tool_history::ToolHistoryService;
 use services::tool_settings::ToolSettingsService;
@@ -49,9 +51,10 @@ use services::graph_retrieval::GraphRetrievalEngine;
 use services::react_agent::ReActAgent;
 use services::planner::{Planner, Plan};
 use services::computer_control::ComputerControlService;
+use services::session_context::SessionContextBridge;
+use services::agent_actions::AgentActionLog;
 use services::streaming_vision::StreamingVisionService;
 use services::temporal_memory::TemporalMemoryService;
-use services::decay_worker::DecayWorker;
 use services::pattern_detector::LlmPatternDetector;
 #[cfg(feature = "phase4")]
 use services::contextual_retrieval::ContextualRetrievalService;
@@ -66,6 +69,58 @@ use services::memory_enhancer::MemoryEnhancerService;
 use services::task_planner::TaskPlannerService;
 use services::learning_style_adapter::LearningStyleAdapterService;
 use services::goal_tracker::GoalTrackerService;
+use services::activity_timeline::ActivityTimelineService;
+use services::weekly_review::WeeklyReviewService;
+use services::conversation_language::ConversationLanguageService;
+use services::conversation_topics::ConversationTopicsService;
+use services::conversation_digest::ConversationDigestService;
+use services::openapi_tools::OpenApiToolService;
+use services::sql_query::SqlQueryService;
+use services::charts::ChartService;
+use services::code_sandbox::CodeSandboxService;
+use services::calculator::CalculatorService;
+use services::window_manager::WindowManagerService;
+use services::os_automation::OsAutomationService;
+use services::localization::LocalizationService;
+use services::translation::TranslationService;
+use services::screen_history::ScreenHistoryService;
+use services::conversation_documents::ConversationDocumentService;
+use services::clipboard_history::ClipboardHistoryService;
+use services::quick_ask::QuickAskService;
+use services::prompt_templates::PromptTemplateService;
+use services::notification::NotificationService;
+use services::proactive_engine::ProactiveSuggestionEngine;
+use services::profile::ProfileService;
+use services::encryption::EncryptionService;
+use services::secrets::SecretsService;
+use services::privacy::PrivacyService;
+use services::knowledge_export::KnowledgeExportService;
+use services::note_import::NoteImportService;
+use services::analytics::AnalyticsService;
+use services::structured_logging::LlmCallLog;
+use services::backup::{BackupConfig, BackupService};
+use services::device_sync::DeviceSyncService;
+use services::scripting::{AppScriptHost, ScriptingService};
+use services::background_jobs::{BackgroundJobsService, DecayJob, GoalProgressJob, GraphMaintenanceJob, RecurringTasksJob, ReviewReminderJob, WeeklyReviewJob, WikiExtractionJob, ConversationTopicsJob, ConversationDigestJob, ScheduledScriptsJob, VaultSyncJob};
+#[cfg(feature = "phase4")]
+use services::background_jobs::ConsolidationJob;
+#[cfg(feature = "lancedb-support")]
+use services::background_jobs::RetrievalTuningJob;
+use services::review_queue::ReviewQueueService;
+use services::rag_eval::RagEvalService;
+use services::persona_presets::PersonaPresetService;
+use services::sentiment::SentimentService;
+use services::lora_training::LoRATrainingService;
+use services::model_context::ModelContextService;
+use services::model_router::ModelRouterService;
+use services::response_verifier::ResponseVerifierService;
+use services::response_variants::ResponseVariantService;
+use services::benchmark::BenchmarkService;
+use services::startup::LazyService;
+use services::degradation::{ServiceSlot, ServiceState};
+use services::search_history::SearchHistoryService;
+use services::workspace::WorkspaceService;
+use services::terminal_capture::TerminalCaptureService;
 use commands::calendar::CalendarServiceWrapper;
 use commands::crash_reporter::CrashReporterState;
 use std::sync::{Arc, Mutex};
@@ -86,9 +141,9 @@ use rusqlite::Connection;
 /// - Integrations: webhook_trigger_manager, calendar_service
 pub struct AppState {
     // === Core Services ===
-    pub db: Mutex<Database>,
+    pub db: AsyncDatabase,  // v3.9.0: queries run on the blocking pool
     pub screen_service: Arc<ScreenCaptureService>,
-    pub llava_service: Mutex<LlavaService>,
+    pub llava_service: Arc<ServiceSlot<LlavaService>>,  // v3.9.0: Shared vision client, may be unavailable
     pub model_installer: Arc<ModelInstallerService>,
     pub learning_service: LearningService,
 
@@ -128,6 +183,7 @@ pub struct AppState {
 fn main() {
     // Track total initialization time (v3.6.0 P4)
     let init_start = std::time::Instant::now();
+    services::startup::begin();  // v3.9.0: Per-service timings for startup_report
 
     // Initialize structured logging (v3.6.0 P3)
     // Falls back to env_logger if tracing init fails
@@ -143,51 +199,246 @@ fn main() {
         "Starting Garden of Eden V3 (Tauri)"
     );
 
-    // Initialize database
-    let db = Database::new().expect("Failed to initialize database");
-    let db_arc = Arc::new(Mutex::new(db));
-
     // Get data directory for audio files
     let data_dir = dirs::data_dir()
         .expect("Failed to get data directory")
         .join("garden-of-eden-v3");
 
+    // Initialize Encryption Service (v3.9.0) - must unlock before any database is opened
+    log::info!("Initializing Encryption Service...");
+    let encryption_arc = Arc::new(
+        EncryptionService::new(data_dir.clone()).expect("Failed to initialize Encryption Service")
+    );
+    if let Err(e) = encryption_arc.unlock_from_keychain() {
+        log::error!("Failed to unlock storage from keychain: {}", e);
+    }
+    // Locked storage: services start on in-memory placeholders until encryption_unlock
+    let storage_locked = services::encryption::is_locked();
+    if storage_locked {
+        log::warn!("Storage is encrypted and locked - waiting for passphrase");
+    }
+    log::info!("✓ Encryption Service initialized");
+    services::startup::checkpoint("encryption");
+
+    // Initialize Secrets Service (v3.9.0) - OS keychain vault for API keys and OAuth tokens
+    let secrets_arc = Arc::new(
+        SecretsService::new().expect("Failed to initialize Secrets Service")
+    );
+    services::startup::checkpoint("secrets");
+
+    // Resolve the active profile's storage (v3.9.0)
+    let profile_paths = ProfileService::active_paths(&data_dir)
+        .expect("Failed to load profile registry");
+
+    // Initialize database
+    let db = if storage_locked {
+        Database::open_in_memory()
+    } else {
+        Database::open(&profile_paths.db)
+    }.expect("Failed to initialize database");
+    let db_arc = Arc::new(Mutex::new(db));
+    services::startup::checkpoint("database");
+
+    // Start independent heavy services on init threads (v3.9.0) - joined where first needed
+    // Embedding: ONNX model loading takes 2-4 seconds
+    let embedding_init = services::startup::spawn("embedding", || {
+        // Initialize Embedding Service (v3.6.0 - BGE-M3 with graceful fallback to TF-IDF)
+        tracing::info!("Initializing Embedding Service (BGE-M3 with fallback)...");
+        let embedding_start = std::time::Instant::now();
+        let embedding_service = services::startup::stage("embedding", || Arc::new(UnifiedEmbeddingService::new()));
+        let embedding_duration = embedding_start.elapsed();
+        tracing::info!(
+            duration_ms = embedding_duration.as_millis() as u64,
+            mode = embedding_service.mode_description(),
+            "Embedding Service initialized"
+        );
+        if embedding_service.is_full_mode() {
+            services::degradation::report("Embedding Service", ServiceState::Available, None);
+        } else {
+            services::degradation::report(
+                "Embedding Service",
+                ServiceState::Degraded,
+                Some("BGE-M3 unavailable, using TF-IDF fallback (reduced accuracy)".to_string()),
+            );
+            log::warn!("╔════════════════════════════════════════════════════════════════╗");
+            log::warn!("║  WARNING: Running in reduced accuracy mode (TF-IDF fallback)  ║");
+            log::warn!("╚════════════════════════════════════════════════════════════════╝");
+            log::warn!("RAG and semantic search will have reduced accuracy.");
+            log::warn!("To restore full accuracy, run:");
+            log::warn!("  rm -rf ~/Library/Application\\ Support/garden-of-eden-v3/models/bge-m3");
+            log::warn!("Then restart the app to re-download BGE-M3 (~543MB).");
+        }
+        embedding_service
+    });
+
+    // Knowledge graph storage (v3.7.0) - separate database file
+    let graph_init = {
+        let graph_db_path = if storage_locked {
+            std::path::PathBuf::from(":memory:")
+        } else {
+            profile_paths.knowledge_graph.clone()
+        };
+        services::startup::spawn("graph", move || {
+            services::startup::stage("graph_storage", || {
+                // Fall back to an in-memory graph rather than aborting (v3.9.0)
+                match GraphStorage::new(graph_db_path.to_str().expect("Invalid graph DB path")) {
+                    Ok(storage) => {
+                        services::degradation::report("Graph Storage", ServiceState::Available, None);
+                        storage
+                    }
+                    Err(e) => {
+                        services::degradation::report(
+                            "Graph Storage",
+                            ServiceState::Degraded,
+                            Some(format!("Knowledge graph kept in memory until restart: {}", e)),
+                        );
+                        GraphStorage::new(":memory:").expect("Failed to initialize Graph Storage")
+                    }
+                }
+            })
+        })
+    };
+
+    // Initialize Profile Service (v3.9.0) - rebinds shared DB handles on switch
+    log::info!("Initializing Profile Service...");
+    let profile_arc = Arc::new(
+        ProfileService::new(data_dir.clone(), Arc::clone(&db_arc))
+            .expect("Failed to initialize Profile Service")
+    );
+    log::info!("✓ Profile Service initialized");
+    services::startup::checkpoint("profile");
+
     // Initialize screen capture service
     let screen_service = ScreenCaptureService::new(Arc::clone(&db_arc));
     let screen_service_arc = Arc::new(screen_service);
+    services::startup::checkpoint("screen_capture");
+
+    // Initialize LLaVA service - shared by Computer Control, Streaming Vision and Proactive Manager (v3.9.0)
+    let llava_arc = ServiceSlot::init("LLaVA Vision Service", || Ok(Arc::new(LlavaService::new()?)));
+    services::startup::checkpoint("llava");
+
+    // Vision (v3.9.0): load the model selection and build Computer Control (v3.8.0, LAM) on an init thread
+    let vision_init = {
+        let db_arc = Arc::clone(&db_arc);
+        let db_path = profile_paths.db.clone();
+        let llava_arc = Arc::clone(&llava_arc);
+        services::startup::spawn("vision", move || {
+            // Load vision model selection before any vision service is used
+            services::startup::stage("vision_config", || {
+                if let Err(e) = services::vision_backend::load_persisted_config(&db_arc) {
+                    log::warn!("Failed to load vision model config, using defaults: {}", e);
+                }
+            });
+
+            services::startup::stage("computer_control", || {
+                log::info!("Initializing Computer Control Service (LAM)...");
+                // Create new instances for computer control service
+                let cc_screen_service = ScreenCaptureService::new(Arc::clone(&db_arc));
+
+                // Computer Control needs a separate Connection instance
+                let cc_conn = if storage_locked {
+                    Connection::open_in_memory()
+                } else {
+                    services::encryption::open_connection(&db_path)
+                }.expect("Failed to open database connection for Computer Control");
+                let cc_db_arc = Arc::new(Mutex::new(cc_conn));
+
+                let computer_control = ComputerControlService::new(
+                    Arc::new(cc_screen_service),
+                    llava_arc,
+                    Arc::clone(&cc_db_arc)
+                ).expect("Failed to initialize Computer Control Service");
+                log::info!("✓ Computer Control Service initialized");
+                (Arc::new(computer_control), cc_db_arc)
+            })
+        })
+    };
 
-    // Initialize LLaVA service
-    let llava_service = LlavaService::new()
-        .expect("Failed to initialize LLaVA service");
-    // Create a second instance for ProactiveManager
-    let llava_service_for_proactive = Arc::new(
-        LlavaService::new().expect("Failed to initialize LLaVA service for proactive")
-    );
+    // ProactiveManager - proactive mode runs without screen analysis without LLaVA (v3.9.0)
+    let llava_service_for_proactive = match llava_arc.get() {
+        Ok(service) => Some(service),
+        Err(e) => {
+            services::degradation::report(
+                "Proactive Manager",
+                ServiceState::Degraded,
+                Some(format!("Screen analysis disabled: {}", e)),
+            );
+            None
+        }
+    };
 
     // Initialize Model Installer service
     let model_installer = Arc::new(ModelInstallerService::new());
+    services::startup::checkpoint("model_installer");
+
+    // Initialize Ollama Supervisor (v3.9.0) - watchdog starts in setup() once the runtime exists
+    let ollama_supervisor_arc = Arc::new(OllamaSupervisor::new(SupervisorConfig::default()));
+    ollama_supervisor_arc.install_global();
+    services::startup::checkpoint("ollama_supervisor");
+
+    // Initialize Analytics Service (v3.9.0) - global recorder for ollama.rs and ToolService
+    let analytics_arc = Arc::new(
+        AnalyticsService::new(Arc::clone(&db_arc)).expect("Failed to initialize Analytics Service")
+    );
+    analytics_arc.install_global();
+    services::startup::checkpoint("analytics");
+
+    // Persist LLM call spans from the structured logging layer (v3.9.0)
+    let llm_call_log_arc = Arc::new(
+        LlmCallLog::new(Arc::clone(&db_arc)).expect("Failed to initialize LLM call log")
+    );
+    llm_call_log_arc.start();
+    services::startup::checkpoint("llm_call_log");
+
+    // Initialize Backup Service (v3.9.0) - scheduled snapshots with rotation
+    // Non-critical services start unavailable instead of aborting the app (v3.9.0)
+    let backup_arc = {
+        let data_dir = data_dir.clone();
+        let profile_arc = Arc::clone(&profile_arc);
+        ServiceSlot::init("Backup Service", move || {
+            let service = Arc::new(BackupService::new(data_dir.clone(), Arc::clone(&profile_arc), BackupConfig::default())?);
+            service.start();
+            Ok(service)
+        })
+    };
+    services::startup::checkpoint("backup");
+
+    // Initialize Notification Service (v3.9.0) - shared by background services
+    log::info!("Initializing Notification Service...");
+    let notification_arc = Arc::new(
+        NotificationService::new().expect("Failed to initialize Notification Service")
+    );
+    log::info!("✓ Notification Service initialized");
+    services::startup::checkpoint("notification");
 
     // Initialize Learning service
     let learning_service = LearningService::new(Arc::clone(&db_arc))
         .expect("Failed to initialize Learning service");
+    services::startup::checkpoint("learning");
 
     // Initialize Webhook Trigger Manager
     let webhook_trigger_manager = Arc::new(WebhookTriggerManager::new(Arc::clone(&db_arc)));
+    webhook_trigger_manager.attach_secrets(Arc::clone(&secrets_arc));
+    services::startup::checkpoint("webhooks");
 
     // Initialize Calendar Service Wrapper
     let calendar_service = CalendarServiceWrapper::new();
+    services::startup::checkpoint("calendar");
 
     // Initialize Cloud Sync Service Wrapper (v3.6.0 - Google Drive backup/restore)
     let cloud_sync_service = commands::cloud_sync::CloudSyncServiceWrapper::new();
+    services::startup::checkpoint("cloud_sync");
 
     // Initialize Proactive Manager (v3.6.0 - Phase 4: AI-Led Proactive Mode)
     log::info!("Initializing Proactive Manager...");
     let proactive_manager = ProactiveManager::new(
-        Some(llava_service_for_proactive),
+        llava_service_for_proactive,
         Arc::clone(&screen_service_arc),
     ).expect("Failed to initialize Proactive Manager");
+    proactive_manager.attach_notifications(Arc::clone(&notification_arc));
     let proactive_manager_arc = Arc::new(TokioMutex::new(proactive_manager));
     log::info!("✓ Proactive Manager initialized");
+    services::startup::checkpoint("proactive_manager");
 
     // Initialize LoRA Services (v3.6.0 - Phase 5: LoRA Training System)
     log::info!("Initializing LoRA Services...");
@@ -200,6 +451,22 @@ fn main() {
         adapter_manager: Arc::new(Mutex::new(lora_adapter_manager)),
     };
     log::info!("✓ LoRA Services initialized");
+    services::startup::checkpoint("lora");
+
+    // Initialize LoRA Training Orchestrator (v3.9.0) - shares the LoRA services above
+    let lora_training_arc = {
+        let db_arc = Arc::clone(&db_arc);
+        let data_collector = Arc::clone(&lora_state.data_collector);
+        let adapter_manager = Arc::clone(&lora_state.adapter_manager);
+        ServiceSlot::init("LoRA Training Service", move || {
+            Ok(Arc::new(LoRATrainingService::new(
+                Arc::clone(&db_arc),
+                Arc::clone(&data_collector),
+                Arc::clone(&adapter_manager),
+            )?))
+        })
+    };
+    services::startup::checkpoint("lora_training");
 
     // Initialize Plugin System (v3.6.0 - Phase 10: Plugin Architecture)
     log::info!("Initializing Plugin System...");
@@ -210,13 +477,50 @@ fn main() {
         service: Arc::new(Mutex::new(plugin_service)),
     };
     log::info!("✓ Plugin System initialized");
+    services::startup::checkpoint("plugins");
+
+    // Initialize Workspace Service (v3.9.0) - file tools resolve paths in the active project
+    log::info!("Initializing Workspace Service...");
+    let workspace_arc = Arc::new(
+        WorkspaceService::new(Arc::clone(&db_arc))
+            .expect("Failed to initialize Workspace Service")
+    );
+    workspace_arc.install_global();
+
+    // Initialize Terminal Capture (v3.9.0) - only sessions started with the shell hook's `garden_capture`
+    log::info!("Initializing Terminal Capture Service...");
+    let terminal_capture_arc = {
+        let db_arc = Arc::clone(&db_arc);
+        let capture_dir = data_dir.join("terminal_capture");
+        ServiceSlot::init("Terminal Capture Service", move || {
+            Ok(Arc::new(TerminalCaptureService::new(Arc::clone(&db_arc), capture_dir.clone())?))
+        })
+    };
+    if let Ok(terminal_capture) = terminal_capture_arc.get() {
+        terminal_capture.start();
+    }
+
+    // Initialize Translation (v3.9.0) - local-model translation used by the translate / fetch_url tools
+    let translation_arc = Arc::new(TranslationService::new(Arc::clone(&db_arc)));
+
+    // Initialize Agent Action Log (v3.9.0) - file/git changes made by tools can be undone
+    let agent_actions_arc = Arc::new(
+        AgentActionLog::new(Arc::clone(&db_arc), data_dir.join("agent_snapshots"))
+            .expect("Failed to initialize Agent Action Log")
+    );
+    agent_actions_arc.install_global();
 
-    // Initialize Tool Service with all 6 production tools (v3.6.0)
-    log::info!("Initializing Tool Service with 6 production tools...");
+    // Initialize Tool Service with all 11 production tools (v3.6.0, edit_file / delete_file / git_commit / terminal_history / translate v3.9.0)
+    log::info!("Initializing Tool Service with 11 production tools...");
     let mut tool_service = ToolService::new();
 
     // Register web tools
-    match WebSearchTool::new() {
+    // v3.9.0: Searches are kept in a history that RAG indexes once it is up
+    let search_history_arc = Arc::new(
+        SearchHistoryService::new(Arc::clone(&db_arc))
+            .expect("Failed to initialize Search History")
+    );
+    match WebSearchTool::with_secrets_and_history(Arc::clone(&secrets_arc), Arc::clone(&search_history_arc)) {
         Ok(tool) => {
             tool_service.register_tool(Box::new(tool));
             log::info!("✓ Registered WebSearchTool");
@@ -224,7 +528,7 @@ fn main() {
         Err(e) => log::warn!("Failed to initialize WebSearchTool: {}", e),
     }
 
-    match UrlFetchTool::new() {
+    match UrlFetchTool::new().map(|tool| tool.with_translation(Arc::clone(&translation_arc))) {
         Ok(tool) => {
             tool_service.register_tool(Box::new(tool));
             log::info!("✓ Registered UrlFetchTool");
@@ -239,15 +543,121 @@ fn main() {
     tool_service.register_tool(Box::new(FileWriteTool));
     log::info!("✓ Registered FileWriteTool");
 
+    tool_service.register_tool(Box::new(EditFileTool::new(data_dir.join("edit_backups"))));
+    log::info!("✓ Registered EditFileTool");
+
+    tool_service.register_tool(Box::new(DeleteFileTool));
+    log::info!("✓ Registered DeleteFileTool");
+
+    // Register git tools (v3.9.0)
+    tool_service.register_tool(Box::new(GitCommitTool));
+    log::info!("✓ Registered GitCommitTool");
+
+    if let Ok(terminal_capture) = terminal_capture_arc.get() {
+        tool_service.register_tool(Box::new(TerminalHistoryTool::new(terminal_capture)));
+        log::info!("✓ Registered TerminalHistoryTool");
+    }
+
     // Register system tools
     tool_service.register_tool(Box::new(SystemInfoTool));
     log::info!("✓ Registered SystemInfoTool");
 
-    tool_service.register_tool(Box::new(CalculatorTool));
+    let calculator_arc = Arc::new(
+        CalculatorService::new(Arc::clone(&db_arc)).expect("Failed to initialize Calculator")
+    );
+    tool_service.register_tool(Box::new(CalculatorTool::new(Arc::clone(&calculator_arc))));
     log::info!("✓ Registered CalculatorTool");
 
+    let window_manager_arc = Arc::new(WindowManagerService::new());
+    tool_service.register_tool(Box::new(WindowManagementTool::new(Arc::clone(&window_manager_arc))));
+    log::info!("✓ Registered WindowManagementTool");
+
+    // Register OS automation tools for the current platform (v3.9.0)
+    let os_automation_arc = {
+        let db_arc = Arc::clone(&db_arc);
+        ServiceSlot::init("OS Automation Service", move || {
+            Ok(Arc::new(OsAutomationService::new(Arc::clone(&db_arc))?))
+        })
+    };
+    if let Ok(os_automation) = os_automation_arc.get() {
+        if cfg!(target_os = "macos") {
+            tool_service.register_tool(Box::new(RunShortcutTool::new(Arc::clone(&os_automation))));
+            log::info!("✓ Registered RunShortcutTool");
+        }
+        if cfg!(target_os = "windows") {
+            tool_service.register_tool(Box::new(RunPowerShellScriptTool::new(Arc::clone(&os_automation))));
+            log::info!("✓ Registered RunPowerShellScriptTool");
+        }
+    }
+
+    // Register language tools (v3.9.0)
+    tool_service.register_tool(Box::new(TranslateTool::new(Arc::clone(&translation_arc))));
+    log::info!("✓ Registered TranslateTool");
+
+    // Register database tools (v3.9.0)
+    let sql_query_arc = {
+        let db_arc = Arc::clone(&db_arc);
+        let secrets_arc = Arc::clone(&secrets_arc);
+        ServiceSlot::init("SQL Query Service", move || {
+            Ok(Arc::new(SqlQueryService::new(Arc::clone(&db_arc), Arc::clone(&secrets_arc))?))
+        })
+    };
+    if let Ok(sql_query) = sql_query_arc.get() {
+        tool_service.register_tool(Box::new(SqlQueryTool::new(sql_query)));
+        log::info!("✓ Registered SqlQueryTool");
+    }
+
+    // Register chart tools (v3.9.0)
+    let charts_arc = Arc::new(
+        ChartService::new(Arc::clone(&db_arc)).expect("Failed to initialize Chart Service")
+    );
+    tool_service.register_tool(Box::new(CreateChartTool::new(Arc::clone(&charts_arc))));
+    log::info!("✓ Registered CreateChartTool");
+
+    // Register code execution tools (v3.9.0)
+    let code_sandbox_arc = {
+        let db_arc = Arc::clone(&db_arc);
+        ServiceSlot::init("Code Sandbox Service", move || {
+            Ok(Arc::new(CodeSandboxService::new(Arc::clone(&db_arc))?))
+        })
+    };
+    if let Ok(code_sandbox) = code_sandbox_arc.get() {
+        tool_service.register_tool(Box::new(RunCodeTool::new(code_sandbox)));
+        log::info!("✓ Registered RunCodeTool");
+    }
+
+    // v3.9.0: Per-tool timeout overrides
+    if let Ok(db) = db_arc.lock() {
+        match services::tool_runtime::load_overrides(db.conn()) {
+            Ok(overrides) => tool_service.load_timeout_overrides(overrides),
+            Err(e) => log::warn!("Failed to load tool timeouts: {}", e),
+        }
+    }
+
     let tool_service = Arc::new(tool_service);
+
+    // v3.9.0: Tools generated from imported OpenAPI documents
+    let openapi_tools_arc = {
+        let db_arc = Arc::clone(&db_arc);
+        let secrets_arc = Arc::clone(&secrets_arc);
+        let tool_service = Arc::clone(&tool_service);
+        ServiceSlot::init("OpenAPI Tool Service", move || {
+            Ok(Arc::new(OpenApiToolService::new(
+                Arc::clone(&db_arc),
+                Arc::clone(&secrets_arc),
+                Arc::clone(&tool_service),
+            )?))
+        })
+    };
+    if let Ok(openapi_tools) = openapi_tools_arc.get() {
+        match openapi_tools.register_saved() {
+            Ok(0) => {}
+            Ok(count) => log::info!("✓ Registered {} imported API tools", count),
+            Err(e) => log::warn!("Failed to register imported API tools: {}", e),
+        }
+    }
     log::info!("Tool Service initialized with {} tools", tool_service.list_tools().len());
+    services::startup::checkpoint("tools");
 
     // Initialize Tool History Service (v3.3.0)
     log::info!("Initializing Tool History Service...");
@@ -255,79 +665,28 @@ fn main() {
         .expect("Failed to initialize Tool History Service");
     let tool_history_service = Arc::new(TokioMutex::new(tool_history_service));
     log::info!("✓ Tool History Service initialized");
+    services::startup::checkpoint("tool_history");
 
     // Initialize Tool Settings Service (v3.3.0)
     log::info!("Initializing Tool Settings Service...");
     let tool_settings_service = ToolSettingsService::new(Arc::clone(&db_arc));
     let tool_settings_service = Arc::new(TokioMutex::new(tool_settings_service));
     log::info!("✓ Tool Settings Service initialized");
-
-    // Initialize Embedding Service (v3.6.0 - BGE-M3 with graceful fallback to TF-IDF)
-    // This is a heavy operation - ONNX model loading takes 2-4 seconds
-    tracing::info!("Initializing Embedding Service (BGE-M3 with fallback)...");
-    let embedding_start = std::time::Instant::now();
-    let embedding_service = Arc::new(UnifiedEmbeddingService::new());
-    let embedding_duration = embedding_start.elapsed();
-    tracing::info!(
-        duration_ms = embedding_duration.as_millis() as u64,
-        mode = embedding_service.mode_description(),
-        "Embedding Service initialized"
-    );
-    if !embedding_service.is_full_mode() {
-        log::warn!("╔════════════════════════════════════════════════════════════════╗");
-        log::warn!("║  WARNING: Running in reduced accuracy mode (TF-IDF fallback)  ║");
-        log::warn!("╚════════════════════════════════════════════════════════════════╝");
-        log::warn!("RAG and semantic search will have reduced accuracy.");
-        log::warn!("To restore full accuracy, run:");
-        log::warn!("  rm -rf ~/Library/Application\\ Support/garden-of-eden-v3/models/bge-m3");
-        log::warn!("Then restart the app to re-download BGE-M3 (~543MB).");
-    }
-
-    // Initialize RAG Service v2 (v3.4.0 Phase 6 - LanceDB for maximum performance)
-    // LanceDB initialization can take 1-3 seconds for first run
-    tracing::info!("Initializing RAG Service with LanceDB...");
-    let rag_start = std::time::Instant::now();
-    let lance_db_path = data_dir.join("lance_db");
-    let rag_service = tokio::runtime::Runtime::new()
-        .expect("Failed to create tokio runtime")
-        .block_on(async {
-            RagServiceV2::new(
-                Arc::clone(&db_arc),
-                Arc::clone(&embedding_service),
-                lance_db_path,
-            ).await
-        })
-        .expect("Failed to initialize RAG Service");
-    let rag_service_arc = Arc::new(rag_service);
-    let rag_duration = rag_start.elapsed();
-    tracing::info!(
-        duration_ms = rag_duration.as_millis() as u64,
-        "RAG Service initialized with LanceDB"
-    );
-
-    // Initialize Hybrid Search Engine (v3.6.0) - only when LanceDB is enabled
-    #[cfg(feature = "lancedb-support")]
-    let hybrid_search_engine = {
-        log::info!("Initializing Hybrid Search Engine...");
-        let engine = HybridSearchEngine::new(
-            Arc::clone(&embedding_service),
-            Arc::clone(&rag_service_arc),
-        );
-        log::info!("✓ Hybrid Search Engine initialized");
-        engine
-    };
+    services::startup::checkpoint("tool_settings");
 
     // Initialize Attention Sink Manager (v3.6.0)
     log::info!("Initializing Attention Sink Manager...");
     let attention_sink_manager = services::attention_sink::AttentionSinkManager::new();
     let attention_sink_arc = Arc::new(attention_sink_manager);
     log::info!("✓ Attention Sink Manager initialized");
+    services::startup::checkpoint("attention_sink");
 
     // Initialize Prompt Cache (v3.6.0)
     log::info!("Initializing Prompt Cache...");
     let prompt_cache = services::prompt_cache::PromptCache::new();
     let prompt_cache_arc = Arc::new(Mutex::new(prompt_cache));
     log::info!("✓ Prompt Cache initialized");
+    services::startup::checkpoint("prompt_cache");
 
     // Initialize GraphRAG Services (v3.7.0)
     log::info!("Initializing GraphRAG Services...");
@@ -336,12 +695,10 @@ fn main() {
     let entity_extractor = EntityExtractor::new();
     let entity_extractor_arc = Arc::new(entity_extractor);
     log::info!("✓ Entity Extractor initialized");
+    services::startup::checkpoint("entity_extractor");
 
-    // Graph Storage
-    let graph_db_path = data_dir.join("knowledge_graph.db");
-    let graph_storage = GraphStorage::new(
-        graph_db_path.to_str().expect("Invalid graph DB path")
-    ).expect("Failed to initialize Graph Storage");
+    // Graph Storage (started on an init thread above)
+    let graph_storage = graph_init.join();
     let graph_storage_arc = Arc::new(graph_storage);
     log::info!("✓ Graph Storage initialized");
 
@@ -356,6 +713,7 @@ fn main() {
     log::info!("✓ Graph Retrieval Engine initialized");
 
     log::info!("✓ All GraphRAG Services initialized successfully");
+    services::startup::checkpoint("graph_services");
 
     // Initialize ReAct Agent (v3.7.0)
     log::info!("Initializing ReAct Agent...");
@@ -365,6 +723,7 @@ fn main() {
     );
     let react_agent_arc = Arc::new(react_agent);
     log::info!("✓ ReAct Agent initialized");
+    services::startup::checkpoint("react_agent");
 
     // Initialize Plan-and-Solve Planner (v3.7.0)
     log::info!("Initializing Plan-and-Solve Planner...");
@@ -376,42 +735,28 @@ fn main() {
     let approved_plans = Arc::new(TokioMutex::new(HashMap::new()));
     let plan_history = Arc::new(TokioMutex::new(HashMap::new()));
     log::info!("✓ Plan-and-Solve Planner initialized");
-
-    // Initialize Computer Control Service (v3.8.0)
-    log::info!("Initializing Computer Control Service (LAM)...");
-    // Create new instances for computer control service
-    let cc_screen_service = ScreenCaptureService::new(Arc::clone(&db_arc));
-    let cc_llava_service = LlavaService::new()
-        .expect("Failed to initialize LLaVA for Computer Control");
-
-    // Computer Control needs a separate Connection instance
-    let cc_db_path = Database::get_db_path()
-        .expect("Failed to get database path for Computer Control");
-    let cc_conn = Connection::open(&cc_db_path)
-        .expect("Failed to open database connection for Computer Control");
-    let cc_db_arc = Arc::new(Mutex::new(cc_conn));
-
-    let computer_control = ComputerControlService::new(
-        Arc::new(cc_screen_service),
-        Arc::new(cc_llava_service),
-        cc_db_arc
-    ).expect("Failed to initialize Computer Control Service");
-    let computer_control_arc = Arc::new(computer_control);
-    log::info!("✓ Computer Control Service initialized");
-
-    // Initialize Streaming Vision Service (v3.8.0 Phase 2)
-    log::info!("Initializing Streaming Vision Service...");
-    let sv_screen_service = ScreenCaptureService::new(Arc::clone(&db_arc));
-    let sv_llava_service = LlavaService::new()
-        .expect("Failed to initialize LLaVA for Streaming Vision");
-
-    let streaming_vision = StreamingVisionService::new(
-        Arc::new(sv_screen_service),
-        Arc::new(sv_llava_service),
-        Arc::clone(&db_arc)
-    ).expect("Failed to initialize Streaming Vision Service");
-    let streaming_vision_arc = Arc::new(streaming_vision);
-    log::info!("✓ Streaming Vision Service initialized");
+    services::startup::checkpoint("planner");
+
+    // Computer Control Service (v3.8.0) - started on the vision init thread above
+    let (computer_control_arc, cc_db_arc) = vision_init.join();
+    profile_arc.bind_connection(cc_db_arc);
+
+    // Initialize Streaming Vision Service (v3.8.0 Phase 2) - lazy, monitoring is opt-in (v3.9.0)
+    let sv_db_arc = Arc::clone(&db_arc);
+    let sv_notification_arc = Arc::clone(&notification_arc);
+    let sv_llava_arc = Arc::clone(&llava_arc);
+    let streaming_vision_lazy = LazyService::new("streaming_vision", move || {
+        let sv_screen_service = ScreenCaptureService::new(Arc::clone(&sv_db_arc));
+
+        let streaming_vision = StreamingVisionService::new(
+            Arc::new(sv_screen_service),
+            sv_llava_arc,
+            sv_db_arc
+        ).expect("Failed to initialize Streaming Vision Service");
+        streaming_vision.attach_notifications(sv_notification_arc);
+        log::info!("✓ Streaming Vision Service initialized");
+        streaming_vision
+    });
 
     // Initialize Temporal Memory Service (v3.8.0 Phase 3)
     log::info!("Initializing Temporal Memory Service...");
@@ -419,17 +764,57 @@ fn main() {
         .expect("Failed to initialize Temporal Memory Service");
     let temporal_memory_arc = Arc::new(temporal_memory);
     log::info!("✓ Temporal Memory Service initialized");
-
-    // Start Memory Decay Worker (v3.8.0 Phase 3)
-    log::info!("Starting Memory Decay Worker (24h interval)...");
-    let _decay_worker = DecayWorker::start(Arc::clone(&temporal_memory_arc), true);
-    log::info!("✓ Memory Decay Worker started with auto-prune enabled");
+    services::startup::checkpoint("temporal_memory");
 
     // Initialize Pattern Detector (v3.8.0 Phase 4)
     log::info!("Initializing Pattern Detector (ML-based trait analysis)...");
     let pattern_detector = LlmPatternDetector::new();
     let pattern_detector_arc = Arc::new(pattern_detector);
     log::info!("✓ Pattern Detector initialized");
+    services::startup::checkpoint("pattern_detector");
+
+    // Embedding (started on an init thread above) - first needed here
+    let embedding_service = embedding_init.join();
+
+    // Initialize RAG Service v2 (v3.4.0 Phase 6 - LanceDB for maximum performance)
+    // v3.9.0: LanceDB opens in the background after the window is up (see setup below);
+    // retrieval before then waits for it
+    let rag_service_arc = Arc::new(RagServiceV2::new_lazy(
+        Arc::clone(&db_arc),
+        Arc::clone(&embedding_service),
+        profile_paths.lance_db.clone(),
+    ));
+    services::startup::defer("rag");
+    services::startup::checkpoint("rag_service");
+    search_history_arc.attach_rag(Arc::clone(&rag_service_arc));
+    if let Ok(terminal_capture) = terminal_capture_arc.get() {
+        terminal_capture.attach_rag(Arc::clone(&rag_service_arc));
+    }
+
+    // v3.9.0: Keep the relevance threshold retuned from retrieval feedback
+    let tuned_threshold = db_arc
+        .lock()
+        .ok()
+        .and_then(|db| services::retrieval_feedback::latest_threshold(db.conn()).ok().flatten());
+    if let Some(relevance_threshold) = tuned_threshold {
+        let raft_config = rag_service_arc.get_raft_config().unwrap_or_default();
+        if let Err(e) = rag_service_arc.update_raft_config(services::raft::RaftConfig { relevance_threshold, ..raft_config }) {
+            log::warn!("Failed to apply tuned relevance threshold: {}", e);
+        }
+    }
+
+    // Initialize Hybrid Search Engine (v3.6.0) - only when LanceDB is enabled
+    #[cfg(feature = "lancedb-support")]
+    let hybrid_search_arc = {
+        log::info!("Initializing Hybrid Search Engine...");
+        let engine = HybridSearchEngine::new(
+            Arc::clone(&embedding_service),
+            Arc::clone(&rag_service_arc),
+        );
+        log::info!("✓ Hybrid Search Engine initialized");
+        Arc::new(TokioMutex::new(engine))
+    };
+    services::startup::checkpoint("hybrid_search");
 
     // Initialize Contextual Retrieval Service (v3.8.0 Phase 4) - only when phase4 is enabled
     #[cfg(feature = "phase4")]
@@ -442,6 +827,7 @@ fn main() {
         log::info!("✓ Contextual Retrieval Service initialized");
         Arc::new(service)
     };
+    services::startup::checkpoint("contextual_retrieval");
 
     // Initialize Memory Consolidation Service (v3.8.0 Phase 4) - only when phase4 is enabled
     #[cfg(feature = "phase4")]
@@ -455,12 +841,14 @@ fn main() {
         log::info!("✓ Memory Consolidation Service initialized");
         Arc::new(service)
     };
+    services::startup::checkpoint("memory_consolidation");
 
     // Initialize Chain-of-Thought Engine (v3.9.0 Phase 5)
     log::info!("Initializing Chain-of-Thought Engine...");
     let cot_engine = ChainOfThoughtEngine::new();
     let cot_engine_arc = Arc::new(cot_engine);
     log::info!("✓ Chain-of-Thought Engine initialized");
+    services::startup::checkpoint("chain_of_thought");
 
     // Initialize Visual Analyzer (v3.9.0 Phase 5 - Stage 1)
     log::info!("Initializing Visual Analyzer...");
@@ -471,6 +859,7 @@ fn main() {
     ).expect("Failed to initialize Visual Analyzer");
     let visual_analyzer_arc = Arc::new(TokioMutex::new(visual_analyzer));
     log::info!("✓ Visual Analyzer initialized (lazy LLaVA loading)");
+    services::startup::checkpoint("visual_analyzer");
 
     // Initialize Context Enricher (v3.9.0 Phase 5 - Stage 1) - only when phase5 is enabled
     #[cfg(feature = "phase5")]
@@ -484,6 +873,7 @@ fn main() {
         log::info!("✓ Context Enricher initialized");
         Arc::new(service)
     };
+    services::startup::checkpoint("context_enricher");
 
     // Initialize Semantic Wiki (v3.9.0 Phase 5 - Stage 2)
     log::info!("Initializing Semantic Wiki...");
@@ -492,7 +882,145 @@ fn main() {
         Arc::clone(&embedding_service)
     ).expect("Failed to initialize Semantic Wiki");
     let semantic_wiki_arc = Arc::new(semantic_wiki);
+    semantic_wiki_arc.attach_graph(Arc::clone(&graph_storage_arc));
     log::info!("✓ Semantic Wiki initialized");
+    #[cfg(feature = "phase5")]
+    context_enricher_arc.attach_wiki(Arc::clone(&semantic_wiki_arc));
+    #[cfg(feature = "phase5")]
+    context_enricher_arc.attach_workspace(Arc::clone(&workspace_arc));
+    services::startup::checkpoint("semantic_wiki");
+
+    // Initialize Session Context Bridge (v3.9.0) - chat memories for computer control
+    let session_context = SessionContextBridge::new(Arc::clone(&db_arc))
+        .expect("Failed to initialize Session Context Bridge");
+    session_context.attach_wiki(Arc::clone(&semantic_wiki_arc));
+    let session_context_arc = Arc::new(session_context);
+
+    // Initialize Privacy Service (v3.9.0) - spans episodic memory, vectors, wiki, graph, clipboard and screen history
+    log::info!("Initializing Privacy Service...");
+    let privacy_arc = Arc::new(
+        PrivacyService::new(Arc::clone(&db_arc), Arc::clone(&graph_storage_arc), Arc::clone(&rag_service_arc))
+            .expect("Failed to initialize Privacy Service")
+    );
+    services::startup::checkpoint("privacy");
+
+    // Initialize Knowledge Export (v3.9.0) - wiki and graph as a Markdown vault
+    let knowledge_export_arc = Arc::new(KnowledgeExportService::new(
+        Arc::clone(&db_arc),
+        Arc::clone(&semantic_wiki_arc),
+        Arc::clone(&graph_storage_arc),
+    ));
+
+    // Initialize Note Import (v3.9.0) - Obsidian / Notion notes into memory
+    let note_import_arc = {
+        let db_arc = Arc::clone(&db_arc);
+        let semantic_wiki_arc = Arc::clone(&semantic_wiki_arc);
+        let graph_storage_arc = Arc::clone(&graph_storage_arc);
+        let rag_service_arc = Arc::clone(&rag_service_arc);
+        ServiceSlot::init("Note Import Service", move || {
+            Ok(Arc::new(NoteImportService::new(
+                Arc::clone(&db_arc),
+                Arc::clone(&semantic_wiki_arc),
+                Arc::clone(&graph_storage_arc),
+                Arc::clone(&rag_service_arc),
+            )?))
+        })
+    };
+
+    // Initialize Device Sync (v3.9.0) - optional E2E-encrypted replication across the user's devices
+    let device_sync_arc = {
+        let db_arc = Arc::clone(&db_arc);
+        let secrets_arc = Arc::clone(&secrets_arc);
+        let rag_arc = Arc::clone(&rag_service_arc);
+        let wiki_arc = Arc::clone(&semantic_wiki_arc);
+        ServiceSlot::init("Device Sync Service", move || {
+            let service = Arc::new(DeviceSyncService::new(Arc::clone(&db_arc), Arc::clone(&secrets_arc))?);
+            // Embeddings aren't synced: re-embed what other devices wrote
+            let (rag_arc, wiki_arc) = (Arc::clone(&rag_arc), Arc::clone(&wiki_arc));
+            service.set_import_hook(move |report| {
+                let (rag, wiki) = (Arc::clone(&rag_arc), Arc::clone(&wiki_arc));
+                let (memory_ids, fact_ids) = (report.memory_ids.clone(), report.fact_ids.clone());
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = rag.reindex_memories(&memory_ids).await {
+                        log::warn!("Failed to re-embed synced memories: {}", e);
+                    }
+                    let result = tokio::task::spawn_blocking(move || wiki.reindex_facts(&fact_ids)).await;
+                    if let Ok(Err(e)) = result {
+                        log::warn!("Failed to re-embed synced facts: {}", e);
+                    }
+                });
+            });
+            service.start();
+            Ok(service)
+        })
+    };
+    services::startup::checkpoint("device_sync");
+
+    // Initialize Review Queue (v3.9.0) - spaced-repetition review of at-risk memories
+    log::info!("Initializing Review Queue...");
+    let review_queue_arc = Arc::new(
+        ReviewQueueService::new(
+            Arc::clone(&db_arc),
+            Arc::clone(&temporal_memory_arc),
+            Arc::clone(&embedding_service),
+        )
+        .expect("Failed to initialize Review Queue")
+    );
+    services::startup::checkpoint("review_queue");
+
+    // Initialize RAG Eval (v3.9.0) - golden datasets for retrieval regression tracking
+    let rag_eval_arc = {
+        let db_arc = Arc::clone(&db_arc);
+        ServiceSlot::init("RAG Eval Service", move || Ok(Arc::new(RagEvalService::new(Arc::clone(&db_arc))?)))
+    };
+    services::startup::checkpoint("rag_eval");
+
+    // Initialize Persona Presets (v3.9.0) - one-click persona profiles
+    let persona_presets_arc = {
+        let db_arc = Arc::clone(&db_arc);
+        ServiceSlot::init("Persona Preset Service", move || Ok(Arc::new(PersonaPresetService::new(Arc::clone(&db_arc))?)))
+    };
+    services::startup::checkpoint("persona_presets");
+
+    // Initialize Background Jobs (v3.9.0) - decay, consolidation, wiki extraction, graph maintenance, review reminders
+    log::info!("Initializing Background Jobs...");
+    let background_jobs_arc = Arc::new(
+        BackgroundJobsService::new(Arc::clone(&db_arc))
+            .expect("Failed to initialize Background Jobs Service")
+    );
+    background_jobs_arc
+        .register(Arc::new(DecayJob::new(
+            Arc::clone(&temporal_memory_arc),
+            true,
+            Some(Arc::clone(&notification_arc)),
+        )))
+        .expect("Failed to register memory decay job");
+    #[cfg(feature = "phase4")]
+    background_jobs_arc
+        .register(Arc::new(ConsolidationJob::new(Arc::clone(&memory_consolidation_arc))))
+        .expect("Failed to register memory consolidation job");
+    background_jobs_arc
+        .register(Arc::new(WikiExtractionJob::new(Arc::clone(&db_arc), Arc::clone(&semantic_wiki_arc))))
+        .expect("Failed to register wiki extraction job");
+    background_jobs_arc
+        .register(Arc::new(GraphMaintenanceJob::new(Arc::clone(&graph_storage_arc))))
+        .expect("Failed to register graph maintenance job");
+    background_jobs_arc
+        .register(Arc::new(ReviewReminderJob::new(Arc::clone(&review_queue_arc), Arc::clone(&notification_arc))))
+        .expect("Failed to register memory review reminder job");
+    background_jobs_arc
+        .register(Arc::new(VaultSyncJob::new(Arc::clone(&knowledge_export_arc))))
+        .expect("Failed to register knowledge vault sync job");
+    #[cfg(feature = "lancedb-support")]
+    background_jobs_arc
+        .register(Arc::new(RetrievalTuningJob::new(
+            Arc::clone(&db_arc),
+            Arc::clone(&rag_service_arc),
+            Arc::clone(&hybrid_search_arc),
+        )))
+        .expect("Failed to register retrieval tuning job");
+    background_jobs_arc.start();
+    services::startup::checkpoint("background_jobs");
 
     // Initialize Memory Enhancer (v3.9.0 Phase 5 - Stage 2)
     log::info!("Initializing Memory Enhancer...");
@@ -501,6 +1029,7 @@ fn main() {
     ).expect("Failed to initialize Memory Enhancer");
     let memory_enhancer_arc = Arc::new(memory_enhancer);
     log::info!("✓ Memory Enhancer initialized");
+    services::startup::checkpoint("memory_enhancer");
 
     // Initialize Task Planner (v3.9.0 Phase 5 - Stage 4)
     log::info!("Initializing Task Planner...");
@@ -508,7 +1037,12 @@ fn main() {
         Arc::clone(&db_arc)
     ).expect("Failed to initialize Task Planner");
     let task_planner_arc = Arc::new(task_planner);
+    notification_arc.attach_task_planner(Arc::clone(&task_planner_arc));
+    background_jobs_arc
+        .register(Arc::new(RecurringTasksJob::new(Arc::clone(&task_planner_arc))))
+        .expect("Failed to register recurring tasks job");
     log::info!("✓ Task Planner initialized");
+    services::startup::checkpoint("task_planner");
 
     // Initialize Learning Style Adapter (v3.9.0 Phase 5 - Stage 4)
     log::info!("Initializing Learning Style Adapter...");
@@ -517,14 +1051,193 @@ fn main() {
     ).expect("Failed to initialize Learning Style Adapter");
     let learning_style_adapter_arc = Arc::new(learning_style_adapter);
     log::info!("✓ Learning Style Adapter initialized");
+    services::startup::checkpoint("learning_style_adapter");
 
     // Initialize Goal Tracker (v3.9.0 Phase 5 - Stage 4)
     log::info!("Initializing Goal Tracker...");
     let goal_tracker = GoalTrackerService::new(
         Arc::clone(&db_arc)
     ).expect("Failed to initialize Goal Tracker");
+    goal_tracker.attach_notifications(Arc::clone(&notification_arc));
+    goal_tracker.attach_task_planner(Arc::clone(&task_planner_arc));
+    goal_tracker.attach_workspace(Arc::clone(&workspace_arc));
     let goal_tracker_arc = Arc::new(goal_tracker);
+    background_jobs_arc
+        .register(Arc::new(GoalProgressJob::new(Arc::clone(&goal_tracker_arc))))
+        .expect("Failed to register goal progress job");
     log::info!("✓ Goal Tracker initialized");
+    services::startup::checkpoint("goal_tracker");
+
+    // Initialize Proactive Suggestion Engine (v3.9.0) - started on demand
+    log::info!("Initializing Proactive Suggestion Engine...");
+    let proactive_engine = ProactiveSuggestionEngine::new(
+        Arc::clone(&db_arc),
+        Arc::clone(&goal_tracker_arc),
+        Arc::clone(&task_planner_arc),
+        Arc::clone(&calendar_service.service),
+        Arc::clone(&notification_arc),
+    ).expect("Failed to initialize Proactive Suggestion Engine");
+    #[cfg(feature = "phase5")]
+    proactive_engine.attach_context_enricher(Arc::clone(&context_enricher_arc));
+    let proactive_engine_arc = Arc::new(proactive_engine);
+    log::info!("✓ Proactive Suggestion Engine initialized");
+    services::startup::checkpoint("proactive_engine");
+
+    // Initialize Activity Timeline (v3.9.0)
+    log::info!("Initializing Activity Timeline...");
+    let activity_timeline = ActivityTimelineService::new(
+        Arc::clone(&db_arc)
+    ).expect("Failed to initialize Activity Timeline");
+    let activity_timeline_arc = Arc::new(activity_timeline);
+    log::info!("✓ Activity Timeline initialized");
+    services::startup::checkpoint("activity_timeline");
+
+    // Initialize Weekly Review (v3.9.0)
+    log::info!("Initializing Weekly Review...");
+    let weekly_review_arc = Arc::new(WeeklyReviewService::new(
+        Arc::clone(&db_arc),
+        Arc::clone(&activity_timeline_arc),
+        Arc::clone(&notification_arc),
+        Arc::clone(&webhook_trigger_manager),
+    ).expect("Failed to initialize Weekly Review"));
+    background_jobs_arc
+        .register(Arc::new(WeeklyReviewJob::new(Arc::clone(&weekly_review_arc))))
+        .expect("Failed to register weekly review job");
+    log::info!("✓ Weekly Review initialized");
+    services::startup::checkpoint("weekly_review");
+
+    // Initialize Conversation Language Lock (v3.9.0)
+    log::info!("Initializing Conversation Language Service...");
+    let conversation_language = ConversationLanguageService::new(
+        Arc::clone(&db_arc)
+    ).expect("Failed to initialize Conversation Language Service");
+    let conversation_language_arc = Arc::new(conversation_language);
+    log::info!("✓ Conversation Language Service initialized");
+    services::startup::checkpoint("conversation_language");
+
+    // Initialize Conversation Topics (v3.9.0) - background titling and topic labeling
+    let conversation_topics_arc = Arc::new(ConversationTopicsService::new(Arc::clone(&db_arc)));
+    background_jobs_arc
+        .register(Arc::new(ConversationTopicsJob::new(Arc::clone(&conversation_topics_arc))))
+        .expect("Failed to register conversation topics job");
+    services::startup::checkpoint("conversation_topics");
+
+    // Initialize Conversation Digest (v3.9.0) - finished conversations into durable memories
+    let conversation_digest_arc = Arc::new(
+        ConversationDigestService::new(Arc::clone(&db_arc), Arc::clone(&rag_service_arc))
+            .expect("Failed to initialize Conversation Digest Service")
+    );
+    background_jobs_arc
+        .register(Arc::new(ConversationDigestJob::new(Arc::clone(&conversation_digest_arc))))
+        .expect("Failed to register conversation digest job");
+    services::startup::checkpoint("conversation_digest");
+
+    // Initialize Scripting (v3.9.0) - user scripts on hotkeys, schedules and webhook events
+    let scripting_arc = {
+        let db_arc = Arc::clone(&db_arc);
+        ServiceSlot::init("Scripting Service", move || Ok(Arc::new(ScriptingService::new(Arc::clone(&db_arc))?)))
+    };
+    if let Ok(scripting) = scripting_arc.get() {
+        scripting.set_host(Arc::new(AppScriptHost::new(
+            Arc::clone(&db_arc),
+            Arc::clone(&rag_service_arc),
+            Arc::clone(&tool_service),
+            Arc::clone(&notification_arc),
+        )));
+        webhook_trigger_manager.attach_scripts(Arc::clone(&scripting));
+        background_jobs_arc
+            .register(Arc::new(ScheduledScriptsJob::new(scripting)))
+            .expect("Failed to register scheduled scripts job");
+    }
+    services::startup::checkpoint("scripting");
+
+    // Initialize Localization (v3.9.0)
+    let localization_arc = Arc::new(LocalizationService::new(Arc::clone(&db_arc)));
+
+    // Initialize Model Context Windows (v3.9.0) - detected per model via /api/show
+    let model_context_arc = Arc::new(
+        ModelContextService::new(Arc::clone(&db_arc)).expect("Failed to initialize Model Context Service")
+    );
+    services::startup::checkpoint("model_context");
+
+    // Initialize Model Router (v3.9.0) - fast-model triage with escalation
+    let model_router_arc = Arc::new(
+        ModelRouterService::new(Arc::clone(&db_arc)).expect("Failed to initialize Model Router")
+    );
+    services::startup::checkpoint("model_router");
+
+    // Initialize Response Verifier (v3.9.0) - reply self-check with one retry
+    let response_verifier_arc = Arc::new(
+        ResponseVerifierService::new(Arc::clone(&db_arc)).expect("Failed to initialize Response Verifier")
+    );
+    services::startup::checkpoint("response_verifier");
+
+    // Initialize Response Variants (v3.9.0) - regenerated replies next to the original
+    let response_variants_arc = Arc::new(
+        ResponseVariantService::new(Arc::clone(&db_arc)).expect("Failed to initialize Response Variants")
+    );
+    services::startup::checkpoint("response_variants");
+
+    // Initialize Benchmark Service (v3.9.0) - end-to-end latency history
+    let benchmark_arc = {
+        let db_arc = Arc::clone(&db_arc);
+        ServiceSlot::init("Benchmark Service", move || Ok(Arc::new(BenchmarkService::new(Arc::clone(&db_arc))?)))
+    };
+    services::startup::checkpoint("benchmark");
+
+    // Initialize Sentiment Tracking (v3.9.0) - mood timeline and session empathy
+    let sentiment_arc = Arc::new(
+        SentimentService::new(Arc::clone(&db_arc)).expect("Failed to initialize Sentiment Service")
+    );
+    services::startup::checkpoint("sentiment");
+
+    // Initialize Screenshot History (v3.9.0)
+    log::info!("Initializing Screen History Service...");
+    let screen_history = ScreenHistoryService::new(
+        Arc::clone(&db_arc),
+        Arc::clone(&rag_service_arc)
+    ).expect("Failed to initialize Screen History Service");
+    let screen_history_arc = Arc::new(screen_history);
+    screen_service_arc.attach_history(Arc::clone(&screen_history_arc));
+    log::info!("✓ Screen History Service initialized");
+    services::startup::checkpoint("screen_history");
+
+    // Initialize Conversation Documents (v3.9.0) - per-conversation file index
+    let conversation_documents_arc = Arc::new(
+        ConversationDocumentService::new(Arc::clone(&db_arc), Arc::clone(&embedding_service))
+            .expect("Failed to initialize Conversation Documents")
+    );
+    services::startup::checkpoint("conversation_documents");
+
+    // Initialize Clipboard History (v3.9.0)
+    log::info!("Initializing Clipboard History Service...");
+    let clipboard_history_arc = {
+        let db_arc = Arc::clone(&db_arc);
+        let rag_service_arc = Arc::clone(&rag_service_arc);
+        ServiceSlot::init("Clipboard History Service", move || {
+            Ok(Arc::new(ClipboardHistoryService::new(
+                Arc::clone(&db_arc),
+                Arc::clone(&rag_service_arc)
+            )?))
+        })
+    };
+    services::startup::checkpoint("clipboard_history");
+
+    // Initialize Quick Ask (v3.9.0)
+    log::info!("Initializing Quick Ask Service...");
+    let quick_ask = QuickAskService::new(
+        Arc::clone(&db_arc),
+        Arc::clone(&screen_service_arc)
+    ).expect("Failed to initialize Quick Ask Service");
+    let quick_ask_arc = Arc::new(quick_ask);
+    log::info!("✓ Quick Ask Service initialized");
+    services::startup::checkpoint("quick_ask");
+
+    // Initialize Prompt Templates (v3.9.0) - reusable prompts with variables
+    let prompt_templates_arc = Arc::new(
+        PromptTemplateService::new(Arc::clone(&db_arc)).expect("Failed to initialize Prompt Template Service")
+    );
+    services::startup::checkpoint("prompt_templates");
 
     // Initialize Crash Reporter Service (v3.4.0)
     log::info!("Initializing Crash Reporter Service...");
@@ -536,19 +1249,34 @@ fn main() {
     CrashReporterService::setup_panic_handler(Arc::clone(&crash_reporter_arc));
     log::info!("✓ Crash Reporter Service initialized with panic handler");
 
+    // Send reports queued by earlier panics if the user skips review (v3.9.0)
+    let crash_flush_arc = Arc::clone(&crash_reporter_arc);
+    tauri::async_runtime::spawn(async move {
+        match services::crash_reporter::flush_pending_reports(crash_flush_arc).await {
+            Ok(0) => {}
+            Ok(sent) => log::info!("✓ Sent {} queued crash reports", sent),
+            Err(e) => log::warn!("Failed to send queued crash reports: {}", e),
+        }
+    });
+
     let crash_reporter_state = CrashReporterState {
         service: crash_reporter_arc,
     };
+    services::startup::checkpoint("crash_reporter");
 
     // Build AppState organized by domain groups (v3.5.2)
     log::info!("Building AppState with domain-grouped services...");
     let app_state = AppState {
         // === Core Services ===
-        db: Mutex::new(
-            Database::new().expect("Failed to initialize database for app state")
+        db: AsyncDatabase::new(
+            if storage_locked {
+                Database::open_in_memory()
+            } else {
+                Database::open(&profile_paths.db)
+            }.expect("Failed to initialize database for app state")
         ),
         screen_service: Arc::clone(&screen_service_arc),
-        llava_service: Mutex::new(llava_service),
+        llava_service: Arc::clone(&llava_arc),
         model_installer,
         learning_service,
 
@@ -556,7 +1284,7 @@ fn main() {
         embedding: embedding_service,
         rag: rag_service_arc,
         #[cfg(feature = "lancedb-support")]
-        hybrid_search: Arc::new(TokioMutex::new(hybrid_search_engine)),
+        hybrid_search: hybrid_search_arc,
         react_agent: react_agent_arc,
         planner: planner_arc,
         approved_plans,
@@ -584,9 +1312,12 @@ fn main() {
         // === Proactive Mode (Phase 4) ===
         proactive_manager: proactive_manager_arc,
     };
+    services::startup::checkpoint("app_state");
 
     // Log total initialization time (v3.6.0 P4)
     let init_duration = init_start.elapsed();
+    services::benchmark::record_cold_start(init_duration);  // v3.9.0: Reported by system_benchmark
+    services::startup::finish(init_duration);  // v3.9.0: Reported by startup_report
     tracing::info!(
         total_init_ms = init_duration.as_millis() as u64,
         "All services initialized - starting Tauri"
@@ -594,7 +1325,7 @@ fn main() {
     if init_duration.as_secs() > 5 {
         tracing::warn!(
             duration_secs = init_duration.as_secs(),
-            "Initialization took longer than 5 seconds. Consider investigating slow services."
+            "Initialization took longer than 5 seconds. See startup_report for per-service timings."
         );
     }
 
@@ -602,9 +1333,12 @@ fn main() {
         .manage(app_state)
         .manage(crash_reporter_state)
         .manage(lora_state)  // v3.6.0: LoRA training data and adapter management
+        .manage(lora_training_arc)  // v3.9.0: LoRA fine-tune orchestration (may be unavailable)
         .manage(plugin_state)  // v3.6.0: Plugin system for user extensions
         .manage(computer_control_arc)  // v3.8.0: LAM service for commands
-        .manage(streaming_vision_arc)  // v3.8.0 Phase 2: Streaming vision service
+        .manage(session_context_arc)  // v3.9.0: Chat context for computer control
+        .manage(agent_actions_arc)  // v3.9.0: Undo queue for agent file/git changes
+        .manage(streaming_vision_lazy)  // v3.8.0 Phase 2: Streaming vision service (v3.9.0: lazy)
         .manage(temporal_memory_arc)  // v3.8.0 Phase 3: Temporal memory service
         .manage(pattern_detector_arc);  // v3.8.0 Phase 4: Pattern detector service
 
@@ -631,18 +1365,136 @@ fn main() {
         .manage(memory_enhancer_arc)  // v3.9.0 Phase 5 Stage 2: Memory quality scoring and enhancement
         .manage(task_planner_arc)  // v3.9.0 Phase 5 Stage 4: Task planning and execution
         .manage(learning_style_adapter_arc)  // v3.9.0 Phase 5 Stage 4: Learning style adaptation
-        .manage(goal_tracker_arc)  // v3.9.0 Phase 5 Stage 4: Goal tracking and achievement
-        .plugin(tauri_plugin_updater::Builder::new().build());  // v3.4.0: Auto-updater
+        .manage(Arc::clone(&goal_tracker_arc))  // v3.9.0 Phase 5 Stage 4: Goal tracking and achievement
+        .manage(activity_timeline_arc)  // v3.9.0: Activity timeline and daily summaries
+        .manage(weekly_review_arc)  // v3.9.0: Weekly review reports
+        .manage(conversation_language_arc)  // v3.9.0: Conversation language lock
+        .manage(localization_arc)  // v3.9.0: Primary language
+        .manage(translation_arc)  // v3.9.0: Translation and glossary
+        .manage(conversation_topics_arc)  // v3.9.0: Conversation titles and topics
+        .manage(conversation_digest_arc)  // v3.9.0: Conversation digests
+        .manage(openapi_tools_arc)  // v3.9.0: Tools from imported OpenAPI documents
+        .manage(sql_query_arc)  // v3.9.0: Read-only SQL on user databases
+        .manage(charts_arc)  // v3.9.0: Charts in chat responses
+        .manage(code_sandbox_arc)  // v3.9.0: Sandboxed code execution
+        .manage(calculator_arc)  // v3.9.0: Calculator settings and exchange rates
+        .manage(window_manager_arc)  // v3.9.0: App and window management
+        .manage(os_automation_arc)  // v3.9.0: Shortcuts and approved PowerShell scripts
+        .manage(sentiment_arc)  // v3.9.0: Sentiment tracking
+        .manage(Arc::clone(&model_context_arc))  // v3.9.0: Per-model context windows
+        .manage(model_router_arc)  // v3.9.0: Dual-model routing
+        .manage(response_verifier_arc)  // v3.9.0: Response self-check
+        .manage(response_variants_arc)  // v3.9.0: Regeneration variants
+        .manage(benchmark_arc)  // v3.9.0: System benchmark history
+        .manage(screen_history_arc)  // v3.9.0: Screenshot history search
+        .manage(conversation_documents_arc)  // v3.9.0: Files attached to conversations
+        .manage(clipboard_history_arc)  // v3.9.0: Clipboard history (may be unavailable)
+        .manage(Arc::clone(&quick_ask_arc))  // v3.9.0: Global hotkey quick ask
+        .manage(prompt_templates_arc)  // v3.9.0: Prompt template library
+        .manage(Arc::clone(&notification_arc))  // v3.9.0: Native notifications with actions
+        .manage(proactive_engine_arc)  // v3.9.0: Context-driven proactive suggestions
+        .manage(profile_arc)  // v3.9.0: Isolated profiles / workspaces
+        .manage(encryption_arc)  // v3.9.0: Encryption at rest
+        .manage(secrets_arc)  // v3.9.0: Secrets vault
+        .manage(privacy_arc)  // v3.9.0: Topic export / forget
+        .manage(knowledge_export_arc)  // v3.9.0: Markdown vault export
+        .manage(note_import_arc)  // v3.9.0: Obsidian / Notion import
+        .manage(analytics_arc)  // v3.9.0: Usage analytics
+        .manage(llm_call_log_arc)  // v3.9.0: LLM call tracing
+        .manage(backup_arc)  // v3.9.0: Backup and restore (may be unavailable)
+        .manage(device_sync_arc)  // v3.9.0: Multi-device sync (may be unavailable)
+        .manage(scripting_arc)  // v3.9.0: Automation scripts
+        .manage(background_jobs_arc)  // v3.9.0: Background job scheduler
+        .manage(review_queue_arc)  // v3.9.0: Memory review queue
+        .manage(rag_eval_arc)  // v3.9.0: Retrieval evaluation (may be unavailable)
+        .manage(persona_presets_arc)  // v3.9.0: Persona presets (may be unavailable)
+        .manage(Arc::clone(&ollama_supervisor_arc))  // v3.9.0: Ollama health watchdog
+        .manage(search_history_arc)  // v3.9.0: Web search history
+        .manage(workspace_arc)  // v3.9.0: Active project
+        .manage(terminal_capture_arc)  // v3.9.0: Terminal session capture
+        .plugin(tauri_plugin_updater::Builder::new().build())  // v3.4.0: Auto-updater
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build());  // v3.9.0: Quick ask, script and template hotkeys
+
+    // Start Ollama supervisor with an AppHandle for status events (v3.9.0)
+    let supervisor_for_setup = Arc::clone(&ollama_supervisor_arc);
+    let quick_ask_for_setup = Arc::clone(&quick_ask_arc);
+    let notification_for_setup = Arc::clone(&notification_arc);
+    let goal_tracker_for_setup = Arc::clone(&goal_tracker_arc);
+    builder = builder.setup(move |app| {
+        // Open LanceDB now that the window can appear; emits memory-ready (v3.9.0)
+        tauri::async_runtime::spawn(commands::rag::warm_up_memory(app.handle().clone()));
+
+        let handle = app.handle().clone();
+        tauri::async_runtime::spawn(async move {
+            supervisor_for_setup.set_app_handle(handle).await;
+            supervisor_for_setup.start();
+
+            // Detect the active model's context window once Ollama is up (v3.9.0)
+            if services::ollama_supervisor::wait_for_ollama().await.is_ok() {
+                if let Err(e) = model_context_arc.detect_active().await {
+                    log::warn!("Failed to detect context window: {}", e);
+                }
+            }
+        });
+
+        // Register quick ask, script and template hotkeys (v3.9.0)
+        let handle = app.handle().clone();
+        if let Err(e) = commands::quick_ask::register_hotkeys(&handle, Arc::clone(&quick_ask_for_setup)) {
+            log::warn!("Failed to register quick ask hotkeys: {}", e);
+        }
+        tauri::async_runtime::spawn(async move {
+            quick_ask_for_setup.set_app_handle(handle).await;
+        });
+
+        // Notifications need the AppHandle for events; goal check-ins run daily (v3.9.0)
+        let handle = app.handle().clone();
+        tauri::async_runtime::spawn(async move {
+            notification_for_setup.set_app_handle(handle).await;
+
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(24 * 60 * 60));
+            loop {
+                interval.tick().await;
+                match goal_tracker_for_setup.send_stale_goal_reminders(7).await {
+                    Ok(sent) if sent > 0 => log::info!("Sent {} goal check-in reminders", sent),
+                    Ok(_) => {}
+                    Err(e) => log::warn!("Failed to send goal reminders: {}", e),
+                }
+            }
+        });
+        Ok(())
+    });
 
     builder
         .invoke_handler(tauri::generate_handler![
             commands::ai::chat,
             commands::ai::chat_stream,
             commands::ai::chat_with_tools,  // v3.6.0: Tool-enabled chat
+            commands::ai::chat_format_response,  // v3.9.0: Structured response segments
+            commands::ai::chat_structured,  // v3.9.0: JSON-schema constrained output
+            commands::ai::chat_inspect_context,  // v3.9.0: Context window inspector
+            commands::ai::chat_regenerate,  // v3.9.0: Steerable regeneration
+            commands::ai::chat_list_variants,
+            commands::ai::chat_select_variant,
+            commands::ai::chat_rate_variant,
             commands::conversation::get_conversations,
             commands::conversation::get_conversation_messages,
             commands::conversation::delete_conversation,
             commands::conversation::update_conversation_title,
+            commands::conversation::conversation_edit_message,  // v3.9.0: Edit + regenerate
+            commands::conversation::conversation_get_message_revisions,
+            commands::conversation::conversation_set_pinned,  // v3.9.0: Organization
+            commands::conversation::conversation_set_favorite,
+            commands::conversation::conversation_set_archived,
+            commands::conversation::conversation_set_tags,
+            commands::conversation::conversation_list_tags,
+            commands::conversation::conversation_move_to_folder,
+            commands::conversation::conversation_list_folders,
+            commands::conversation::conversation_create_folder,
+            commands::conversation::conversation_rename_folder,
+            commands::conversation::conversation_delete_folder,
+            commands::conversation::conversation_attach_document,  // v3.9.0: Document Q&A
+            commands::conversation::conversation_list_documents,
+            commands::conversation::conversation_detach_document,
             commands::onboarding::check_onboarding_status,
             commands::onboarding::complete_onboarding,
             commands::onboarding::detect_system_specs,
@@ -658,6 +1510,11 @@ fn main() {
             commands::onboarding::save_onboarding_state,
             commands::onboarding::save_survey_results,
             commands::onboarding::mark_onboarding_completed,
+            // Ollama Supervisor (v3.9.0)
+            commands::ollama_supervisor::ollama_get_status,
+            commands::ollama_supervisor::ollama_restart,
+            commands::ollama_supervisor::ollama_get_supervisor_config,
+            commands::ollama_supervisor::ollama_update_supervisor_config,
             commands::screen::screen_start_tracking,
             commands::screen::screen_stop_tracking,
             commands::screen::screen_toggle_tracking,
@@ -667,6 +1524,10 @@ fn main() {
             commands::screen::screen_capture_with_context,
             commands::screen::screen_get_active_window,
             commands::screen::screen_analyze_current,
+            commands::screen::screen_configure_privacy,  // v3.9.0: Privacy zones
+            commands::screen::screen_get_privacy_config,
+            commands::screen::screen_search_history,  // v3.9.0: Screenshot history
+            commands::screen::screen_clear_history,
             commands::settings::get_settings,
             commands::settings::update_settings,
             commands::settings::get_available_models_for_system,
@@ -678,6 +1539,10 @@ fn main() {
             commands::settings::get_model_description,
             commands::settings::get_phase5_settings,
             commands::settings::update_phase5_settings,
+            commands::settings::get_vision_model_config,  // v3.9.0: Vision backend selection
+            commands::settings::update_vision_model_config,
+            commands::settings::get_vector_backend_config,  // v3.9.0: LanceDB / SQLite / Qdrant
+            commands::settings::update_vector_backend_config,
             commands::system::get_system_info,
             commands::learning::learning_record_feedback,
             commands::learning::learning_optimize_persona,
@@ -686,6 +1551,9 @@ fn main() {
             commands::learning::learning_save_persona,
             commands::learning::learning_load_persona,
             commands::learning::learning_evolve_full_persona_from_temporal,  // Phase 4
+            commands::learning::learning_ab_start,  // v3.9.0: Persona A/B testing
+            commands::learning::learning_ab_status,
+            commands::learning::learning_ab_stop,
             commands::webhook::register_webhook,
             commands::webhook::list_webhooks,
             commands::webhook::get_webhook,
@@ -765,6 +1633,9 @@ fn main() {
             commands::llm::llm_update_vram,
             commands::llm::llm_set_context_window,
             commands::llm::llm_set_max_ram,
+            commands::llm::llm_detect_context_window,  // v3.9.0
+            commands::llm::llm_get_context_budget,
+            commands::llm::llm_list_model_contexts,
             // Conversation Memory Commands (v3.5.0)
             commands::conversation_memory::memory_get_context,
             commands::conversation_memory::memory_needs_summarization,
@@ -785,13 +1656,24 @@ fn main() {
             #[cfg(feature = "lancedb-support")]
             commands::hybrid_search::hybrid_search_set_rrf_k,
             #[cfg(feature = "lancedb-support")]
+            commands::hybrid_search::hybrid_search_set_tokenizer,  // v3.9.0: Korean-aware BM25
+            #[cfg(feature = "lancedb-support")]
+            commands::hybrid_search::hybrid_search_record_click,  // v3.9.0: Click feedback for learned fusion
+            #[cfg(feature = "lancedb-support")]
+            commands::hybrid_search::hybrid_search_train_fusion,  // v3.9.0: Learned fusion
+            #[cfg(feature = "lancedb-support")]
             commands::hybrid_search::hybrid_search_stats,
             #[cfg(feature = "lancedb-support")]
             commands::hybrid_search::hybrid_search_compare,
             #[cfg(feature = "lancedb-support")]
             commands::hybrid_search::hybrid_search_toggle_reranking,
+            #[cfg(feature = "lancedb-support")]
+            commands::hybrid_search::hybrid_search_set_reranker_model,
+            #[cfg(feature = "lancedb-support")]
+            commands::hybrid_search::hybrid_search_list_reranker_models,
             // Attention Sink Commands (v3.6.0)
             commands::attention_sink::attention_sink_manage_context,
+            commands::attention_sink::attention_sink_manage_conversation,
             commands::attention_sink::attention_sink_format_prompt,
             commands::attention_sink::attention_sink_needs_compression,
             commands::attention_sink::attention_sink_estimate_tokens,
@@ -817,6 +1699,9 @@ fn main() {
             commands::crash_reporter::crash_reporter_test,
             commands::crash_reporter::crash_reporter_get_local_reports,  // v3.4.0
             commands::crash_reporter::crash_reporter_cleanup_old_reports,  // v3.4.0
+            commands::crash_reporter::crash_reporter_get_pending_reports,  // v3.9.0
+            commands::crash_reporter::crash_reporter_send_report,  // v3.9.0
+            commands::crash_reporter::crash_reporter_discard_report,  // v3.9.0
             // Tool History Commands (v3.3.0)
             commands::tool_history::get_tool_history,
             commands::tool_history::get_tool_statistics,
@@ -835,6 +1720,7 @@ fn main() {
             commands::tool_settings::get_tool_default_config,
             // GraphRAG Commands (v3.7.0)
             commands::graphrag::graphrag_extract_entities,
+            commands::graphrag::graphrag_extract_conversation,  // v3.9.0: Batched, coreference-resolved
             commands::graphrag::graphrag_build_graph,
             commands::graphrag::graphrag_save_graph,
             commands::graphrag::graphrag_load_entity,
@@ -842,9 +1728,12 @@ fn main() {
             commands::graphrag::graphrag_get_neighbors,
             commands::graphrag::graphrag_get_community,
             commands::graphrag::graphrag_retrieve,
+            commands::graphrag::graphrag_answer,  // v3.9.0: Answers with reasoning traces
             commands::graphrag::graphrag_find_path,
             commands::graphrag::graphrag_stats,
             commands::graphrag::graphrag_delete_entity,
+            commands::graphrag::graphrag_find_merge_candidates,
+            commands::graphrag::graphrag_merge_entities,
             commands::graphrag::graphrag_clear_all,
             commands::graphrag::graphrag_get_extractor_config,
             commands::graphrag::graphrag_get_retrieval_config,
@@ -881,6 +1770,17 @@ fn main() {
             commands::computer_control::computer_test_connection,
             #[cfg(target_os = "macos")]
             commands::computer_control::computer_execute_applescript,
+            // Session Context (v3.9.0)
+            commands::session_context::computer_context_share,
+            commands::session_context::computer_context_get,
+            commands::session_context::computer_context_close,
+            commands::session_context::computer_context_fill_form,
+            commands::session_context::computer_context_audit,
+            // Agent Undo (v3.9.0)
+            commands::agent_actions::agent_undo_last_action,
+            commands::agent_actions::agent_undo_action,
+            commands::agent_actions::agent_rollback_plan,
+            commands::agent_actions::agent_list_actions,
             // Streaming Vision Commands (v3.8.0 Phase 2)
             commands::streaming_vision::streaming_vision_start,
             commands::streaming_vision::streaming_vision_stop,
@@ -930,6 +1830,14 @@ fn main() {
             commands::memory_consolidation::consolidation_update_config,
             #[cfg(feature = "phase4")]
             commands::memory_consolidation::consolidation_get_config,
+            #[cfg(feature = "phase4")]
+            commands::memory_consolidation::consolidation_propose,  // v3.9.0: Merge review queue
+            #[cfg(feature = "phase4")]
+            commands::memory_consolidation::consolidation_list_proposals,
+            #[cfg(feature = "phase4")]
+            commands::memory_consolidation::consolidation_accept_proposal,
+            #[cfg(feature = "phase4")]
+            commands::memory_consolidation::consolidation_reject_proposal,
             // Chain-of-Thought (Phase 5)
             commands::chain_of_thought::cot_reason,
             commands::chain_of_thought::cot_update_config,
@@ -953,11 +1861,18 @@ fn main() {
             // Semantic Wiki (Phase 5 - Stage 2)
             commands::semantic_wiki::wiki_extract_facts,
             commands::semantic_wiki::wiki_store_facts,
+            commands::semantic_wiki::wiki_create_fact,  // v3.9.0: Manual fact authoring
+            commands::semantic_wiki::wiki_update_fact,
+            commands::semantic_wiki::wiki_annotate_fact,
             commands::semantic_wiki::wiki_search,
             commands::semantic_wiki::wiki_get_by_entity,
+            commands::semantic_wiki::wiki_get_entity_page,  // v3.9.0: Personal wikipedia pages
             commands::semantic_wiki::wiki_get_stats,
             commands::semantic_wiki::wiki_update_config,
             commands::semantic_wiki::wiki_get_config,
+            commands::semantic_wiki::wiki_list_conflicts,
+            commands::semantic_wiki::wiki_resolve_conflict,
+            commands::semantic_wiki::wiki_adjudicate_conflict,
             // Memory Enhancer (Phase 5 - Stage 2)
             commands::memory_enhancer::memory_analyze_quality,
             commands::memory_enhancer::memory_enhance,
@@ -977,12 +1892,23 @@ fn main() {
             commands::task_planner::task_get_subtasks,
             commands::task_planner::task_get_all,
             commands::task_planner::task_delete,
+            commands::task_planner::task_add_dependency,
+            commands::task_planner::task_remove_dependency,
+            commands::task_planner::task_get_dependency_graph,
+            commands::task_planner::task_create_recurring,
+            commands::task_planner::task_list_recurring,
+            commands::task_planner::task_set_recurring_active,
+            commands::task_planner::task_delete_recurring,
+            commands::task_planner::task_get_schedule,
             // Learning Style Adapter (Phase 5 - Stage 4)
             commands::learning_style::learning_style_get_profile,
             commands::learning_style::learning_style_record_interaction,
             commands::learning_style::learning_style_update_profile,
             commands::learning_style::learning_style_adapt_response,
             commands::learning_style::learning_style_update_manual,
+            commands::learning_style::learning_style_get_conversation_transforms,
+            commands::learning_style::learning_style_set_conversation_transforms,
+            commands::learning_style::learning_style_transform_response,
             // Goal Tracker (Phase 5 - Stage 4)
             commands::goal_tracker::goal_create,
             commands::goal_tracker::goal_get,
@@ -993,6 +1919,150 @@ fn main() {
             commands::goal_tracker::goal_detect_progress,
             commands::goal_tracker::goal_get_achievements,
             commands::goal_tracker::goal_delete,
+            commands::goal_tracker::goal_get_evidence,
+            commands::goal_tracker::goal_reject_evidence,
+            commands::goal_tracker::goal_infer_progress,
+            commands::goal_tracker::goal_get_inference_config,
+            commands::goal_tracker::goal_update_inference_config,
+            // Activity Timeline (v3.9.0)
+            commands::activity_timeline::timeline_get_day,
+            commands::activity_timeline::timeline_generate_summary,
+            // Weekly Review (v3.9.0)
+            commands::weekly_review::review_generate_weekly,
+            commands::weekly_review::review_list_weekly,
+            commands::weekly_review::review_get_weekly_config,
+            commands::weekly_review::review_update_weekly_config,
+            // Conversation Language (v3.9.0)
+            commands::conversation_language::conversation_get_language,
+            commands::conversation_language::conversation_set_language,
+            // Localization (v3.9.0)
+            commands::localization::localization_get_language,
+            commands::localization::localization_get_config,
+            commands::localization::localization_update_config,
+            // Translation (v3.9.0)
+            commands::translation::translate_text,
+            commands::translation::translation_get_glossary,
+            commands::translation::translation_update_glossary,
+            // Conversation Topics (v3.9.0)
+            commands::conversation_topics::conversation_topics_list,
+            commands::conversation_topics::conversation_topics_get,
+            commands::conversation_topics::conversation_topics_set,
+            commands::conversation_topics::conversation_topics_relabel,
+            // Conversation Digest (v3.9.0)
+            commands::conversation_digest::conversation_digest_get,
+            commands::conversation_digest::conversation_digest_now,
+            // Clipboard History (v3.9.0)
+            commands::clipboard_history::clipboard_history_start,
+            commands::clipboard_history::clipboard_history_stop,
+            commands::clipboard_history::clipboard_history_get_recent,
+            commands::clipboard_history::clipboard_history_search,
+            commands::clipboard_history::clipboard_transform,
+            commands::clipboard_history::clipboard_history_delete,
+            commands::clipboard_history::clipboard_history_clear,
+            commands::clipboard_history::clipboard_history_get_config,
+            commands::clipboard_history::clipboard_history_update_config,
+            // Quick Ask (v3.9.0)
+            commands::quick_ask::quick_ask_run,
+            commands::quick_ask::quick_ask_get_config,
+            commands::quick_ask::quick_ask_update_config,
+            // Prompt templates (v3.9.0)
+            commands::prompt_templates::prompt_template_list,
+            commands::prompt_templates::prompt_template_get,
+            commands::prompt_templates::prompt_template_categories,
+            commands::prompt_templates::prompt_template_save,
+            commands::prompt_templates::prompt_template_delete,
+            commands::prompt_templates::prompt_template_render,
+            // Scripting (v3.9.0)
+            commands::scripting::scripts_list,
+            commands::scripting::scripts_save,
+            commands::scripting::scripts_delete,
+            commands::scripting::scripts_run,
+            commands::scripting::scripts_check_syntax,
+            commands::scripting::scripts_event_names,
+            // Notifications (v3.9.0)
+            commands::notification::notification_handle_action,
+            // Profiles (v3.9.0)
+            commands::profile::profile_list,
+            commands::profile::profile_get_active,
+            commands::profile::profile_create,
+            commands::profile::profile_rename,
+            commands::profile::profile_delete,
+            commands::profile::profile_switch,
+            // Encryption at rest (v3.9.0)
+            commands::encryption::encryption_status,
+            commands::encryption::encryption_enable,
+            commands::encryption::encryption_unlock,
+            // Secrets vault (v3.9.0)
+            commands::secrets::secrets_set,
+            commands::secrets::secrets_get,
+            commands::secrets::secrets_delete,
+            // Privacy (v3.9.0)
+            commands::privacy::memory_export_topic,
+            commands::privacy::memory_forget_topic,
+            // Knowledge vault export (v3.9.0)
+            commands::knowledge_export::knowledge_export_vault,
+            commands::knowledge_export::knowledge_get_vault_path,
+            commands::note_import::notes_import_vault,  // v3.9.0: Obsidian / Notion import
+            // Usage analytics (v3.9.0)
+            commands::analytics::analytics_get_summary,
+            commands::analytics::analytics_get_series,
+            // LLM call tracing (v3.9.0)
+            commands::llm_calls::llm_calls_query,
+            // Backup and restore (v3.9.0)
+            commands::backup::backup_create_now,
+            commands::backup::backup_list,
+            commands::backup::backup_restore,
+            // Multi-device sync (v3.9.0)
+            commands::device_sync::sync_status,
+            commands::device_sync::sync_enable,
+            commands::device_sync::sync_join,
+            commands::device_sync::sync_update_config,
+            commands::device_sync::sync_pairing_code,
+            commands::device_sync::sync_now,
+            commands::device_sync::sync_disable,
+            // Background jobs (v3.9.0)
+            commands::background_jobs::background_jobs_list,
+            commands::background_jobs::background_jobs_pause,
+            commands::background_jobs::background_jobs_resume,
+            commands::background_jobs::background_jobs_run_now,
+            commands::background_jobs::background_jobs_update_schedule,
+            // Memory review queue (v3.9.0)
+            commands::review_queue::review_queue_get,
+            commands::review_queue::review_queue_submit,
+            commands::review_queue::review_queue_get_stats,
+            commands::review_queue::review_queue_get_config,
+            commands::review_queue::review_queue_update_config,
+            // Retrieval evaluation (v3.9.0)
+            commands::rag_eval::rag_eval_add_cases,
+            commands::rag_eval::rag_eval_list_cases,
+            commands::rag_eval::rag_eval_list_datasets,
+            commands::rag_eval::rag_eval_delete_case,
+            #[cfg(feature = "lancedb-support")]
+            commands::rag_eval::rag_eval_run,
+            commands::rag_eval::rag_eval_history,
+            commands::rag_eval::rag_eval_run_details,
+            // Feedback-driven retrieval tuning (v3.9.0)
+            commands::retrieval_feedback::retrieval_feedback_report,
+            #[cfg(feature = "lancedb-support")]
+            commands::retrieval_feedback::retrieval_feedback_retrain,
+            // Document ingestion & chunking (v3.9.0)
+            commands::rag::rag_ingest_document,
+            commands::rag::rag_get_chunking_config,
+            commands::rag::rag_update_chunking_config,
+            commands::rag::rag_preview_chunks,
+            commands::rag::rag_get_status,  // v3.9.0: Memory readiness
+            commands::rag::rag_get_index_config,  // v3.9.0: ANN index tuning
+            commands::rag::rag_configure_index,
+            // Persona presets (v3.9.0)
+            commands::persona_presets::persona_save_preset,
+            commands::persona_presets::persona_apply_preset,
+            commands::persona_presets::persona_list_presets,
+            commands::persona_presets::persona_delete_preset,
+            // Persona change log (v3.9.0)
+            commands::persona_changes::persona_get_change_log,
+            commands::persona_changes::persona_rollback_to,
+            // Mood timeline (v3.9.0)
+            commands::mood::mood_get_timeline,
             // RAFT Commands (v3.4.0 Phase 7)
             commands::raft::get_raft_config,
             commands::raft::update_raft_config,
@@ -1006,6 +2076,12 @@ fn main() {
             commands::proactive::proactive_get_config,
             commands::proactive::proactive_dismiss_suggestion,
             commands::proactive::proactive_accept_suggestion,
+            commands::proactive::proactive_engine_start,  // v3.9.0: Context-driven suggestions
+            commands::proactive::proactive_engine_stop,
+            commands::proactive::proactive_engine_run_now,
+            commands::proactive::proactive_engine_get_config,
+            commands::proactive::proactive_engine_update_config,
+            commands::proactive::proactive_engine_get_stats,
             // LoRA Training Commands (v3.6.0 Phase 5)
             commands::lora::lora_collect_data,
             commands::lora::lora_get_filter,
@@ -1022,6 +2098,115 @@ fn main() {
             commands::lora::lora_create_ollama_model,
             commands::lora::lora_delete_ollama_model,
             commands::lora::lora_list_ollama_models,
+            // LoRA training orchestration (v3.9.0)
+            commands::lora_training::lora_training_check,
+            commands::lora_training::lora_training_start,
+            commands::lora_training::lora_training_status,
+            commands::lora_training::lora_training_list,
+            commands::lora_training::lora_training_cancel,
+            commands::lora_training::lora_training_use_adapter,
+            commands::lora_training::lora_training_get_chat_model,
+            // Model routing (v3.9.0)
+            commands::model_router::router_get_config,
+            commands::model_router::router_set_config,
+            commands::model_router::router_get_metrics,
+            commands::model_router::router_classify,
+            // Response self-check (v3.9.0)
+            commands::response_verifier::verifier_get_config,
+            commands::response_verifier::verifier_set_config,
+            commands::response_verifier::verifier_get_stats,
+            commands::response_verifier::verifier_check,
+            // System benchmark (v3.9.0)
+            commands::benchmark::system_benchmark,
+            commands::benchmark::system_benchmark_history,
+            // Startup report (v3.9.0)
+            commands::startup::startup_report,
+            // Service degradation matrix (v3.9.0)
+            commands::degradation::services_get_status,
+            commands::degradation::services_retry_init,
+            // Tool result cache (v3.9.0)
+            commands::tool_cache::tool_cache_clear,
+            commands::tool_cache::tool_cache_stats,
+            // Tool timeouts and cancellation (v3.9.0)
+            commands::tool_runtime::tool_cancel,
+            commands::tool_runtime::tool_cancel_all,
+            commands::tool_runtime::tool_list_running,
+            commands::tool_runtime::tool_get_timeouts,
+            commands::tool_runtime::tool_set_timeout,
+            // Tool sets (v3.9.0)
+            commands::tool_sets::tool_set_list,
+            commands::tool_sets::tool_set_create,
+            commands::tool_sets::tool_set_update,
+            commands::tool_sets::tool_set_delete,
+            commands::tool_sets::conversation_get_tool_set,
+            commands::tool_sets::conversation_set_tool_set,
+            // Imported HTTP API tools (v3.9.0)
+            commands::openapi_tools::api_tools_import,
+            commands::openapi_tools::api_tools_list,
+            commands::openapi_tools::api_tools_remove,
+            commands::openapi_tools::api_tools_set_credential,
+            // SQL query tool (v3.9.0)
+            commands::sql_query::sql_add_connection,
+            commands::sql_query::sql_list_connections,
+            commands::sql_query::sql_remove_connection,
+            commands::sql_query::sql_refresh_schema,
+            commands::sql_query::sql_run_query,
+            commands::sql_query::sql_query_history,
+            // Charts (v3.9.0)
+            commands::charts::chart_create,
+            commands::charts::chart_preview,
+            commands::charts::chart_get,
+            commands::charts::chart_list,
+            commands::charts::chart_render,
+            commands::charts::chart_export,
+            commands::charts::chart_delete,
+            // Calculator (v3.9.0)
+            commands::calculator::calculator_evaluate,
+            commands::calculator::calculator_get_config,
+            commands::calculator::calculator_update_config,
+            commands::calculator::calculator_refresh_rates,
+            // Window management (v3.9.0)
+            commands::window_manager::window_list,
+            commands::window_manager::window_focus,
+            commands::window_manager::window_open_app,
+            commands::window_manager::window_open_url,
+            commands::window_manager::window_move_resize,
+            // OS automation (v3.9.0)
+            commands::os_automation::os_automation_get_config,
+            commands::os_automation::os_automation_update_config,
+            commands::os_automation::os_automation_list_shortcuts,
+            commands::os_automation::os_automation_run_shortcut,
+            commands::os_automation::os_automation_approve_script,
+            commands::os_automation::os_automation_remove_script,
+            commands::os_automation::os_automation_run_script,
+            // Code sandbox (v3.9.0)
+            commands::code_sandbox::sandbox_run_code,
+            commands::code_sandbox::sandbox_get_config,
+            commands::code_sandbox::sandbox_update_config,
+            // Reports (v3.9.0)
+            commands::reports::report_generate,
+            // Network policy for web tools (v3.9.0)
+            commands::network::network_set_offline,
+            commands::network::network_get_status,
+            commands::network::network_set_config,
+            // Web search history (v3.9.0)
+            commands::search_history::search_history_get_recent,
+            commands::search_history::search_history_search,
+            commands::search_history::search_history_delete,
+            commands::search_history::search_history_clear,
+            // Workspace awareness (v3.9.0)
+            commands::workspace::workspace_get_active,
+            commands::workspace::workspace_set_active,
+            commands::workspace::workspace_get_config,
+            commands::workspace::workspace_update_config,
+            // Terminal capture (v3.9.0)
+            commands::terminal_capture::terminal_capture_install_hook,
+            commands::terminal_capture::terminal_capture_list_sessions,
+            commands::terminal_capture::terminal_capture_get_commands,
+            commands::terminal_capture::terminal_capture_delete,
+            commands::terminal_capture::terminal_capture_clear,
+            commands::terminal_capture::terminal_capture_get_config,
+            commands::terminal_capture::terminal_capture_update_config,
             // Plugin System Commands (v3.6.0 Phase 10)
             commands::plugin::plugin_discover,
             commands::plugin::plugin_list,
@@ -1033,15 +2218,23 @@ fn main() {
             commands::plugin::plugin_install,
             commands::plugin::plugin_uninstall,
             commands::plugin::plugin_has_permission,
+            commands::plugin::plugin_chat_structured,  // v3.9.0: Structured output for plugins
             commands::plugin::plugin_get_directory,
             commands::plugin::plugin_get_stats,
             // v3.6.0: Episodic memory visualization commands
             commands::episodic_memory::episodic_get_recent,
             commands::episodic_memory::episodic_get_stats,
             commands::episodic_memory::episodic_search,
+            commands::episodic_memory::episodic_semantic_search,
             commands::episodic_memory::episodic_export,
             commands::episodic_memory::episodic_import,
             commands::episodic_memory::episodic_delete,
+            commands::episodic_memory::episodic_browse,  // v3.9.0: Filtered browsing
+            commands::episodic_memory::memory_get_timeline,  // v3.9.0: Timeline aggregation
+            commands::episodic_memory::episodic_create,  // v3.9.0: Manual memory authoring
+            commands::episodic_memory::episodic_edit,
+            commands::episodic_memory::episodic_annotate,
+            commands::episodic_memory::episodic_set_scope,  // v3.9.0: Conversation / project memory scopes
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
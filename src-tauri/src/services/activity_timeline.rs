@@ -0,0 +1,536 @@
+//! Activity Timeline Service (v3.9.0)
+//!
+//! Combines screen tracking samples, active window information, and conversation
+//! data into a per-day activity timeline.
+//!
+//! Features:
+//! - App-usage sessions built from consecutive screen captures
+//! - Per-app usage totals for a given day
+//! - Conversation activity for the same day, grouped by topic
+//! - LLM-generated daily summary ("what did I work on today")
+
+#![allow(dead_code)]  // Phase 5: Activity timeline (some helpers used by future UI)
+
+use crate::database::Database;
+use crate::services::conversation_topics::topics_by_conversation;
+use crate::services::ollama;
+use anyhow::{anyhow, Context, Result};
+use chrono::{NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Two captures further apart than this start a new session (10 minutes)
+const DEFAULT_MAX_GAP_MS: i64 = 10 * 60 * 1000;
+
+/// Maximum number of sessions included in the summary prompt
+const MAX_SESSIONS_IN_PROMPT: usize = 40;
+
+/// A single screen capture sample used to build sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivitySample {
+    pub app_name: String,
+    pub window_title: Option<String>,
+    pub timestamp: i64, // Unix millis
+}
+
+/// Continuous usage of a single application
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivitySession {
+    pub id: String,
+    pub app_name: String,
+    pub window_titles: Vec<String>,
+    pub started_at: i64, // Unix millis
+    pub ended_at: i64,   // Unix millis
+    pub duration_secs: i64,
+    pub sample_count: u32,
+}
+
+/// Total usage of one application on a given day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppUsage {
+    pub app_name: String,
+    pub total_secs: i64,
+    pub session_count: u32,
+}
+
+/// Conversation activity on a given day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationActivity {
+    pub conversation_id: String,
+    pub title: String,
+    pub message_count: u32,
+    pub first_message_at: i64,
+    pub last_message_at: i64,
+    /// Topic labels of the conversation (v3.9.0)
+    #[serde(default)]
+    pub topics: Vec<String>,
+}
+
+/// Conversation activity grouped by topic (v3.9.0)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicActivity {
+    pub topic: String,
+    pub conversation_count: u32,
+    pub message_count: u32,
+}
+
+/// Full timeline for one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayTimeline {
+    pub date: String, // YYYY-MM-DD (local time)
+    pub sessions: Vec<ActivitySession>,
+    pub app_usage: Vec<AppUsage>,
+    pub conversations: Vec<ConversationActivity>,
+    /// The day's conversations grouped by topic, busiest first (v3.9.0)
+    #[serde(default)]
+    pub topics: Vec<TopicActivity>,
+    pub total_tracked_secs: i64,
+    pub summary: Option<DailySummary>,
+}
+
+/// LLM-generated summary of a day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailySummary {
+    pub date: String,
+    pub summary: String,
+    pub generated_at: i64, // Unix millis
+}
+
+/// Activity Timeline Service
+pub struct ActivityTimelineService {
+    db: Arc<Mutex<Database>>,
+    max_gap_ms: i64,
+}
+
+impl ActivityTimelineService {
+    /// Create new Activity Timeline service
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        let service = Self {
+            db,
+            max_gap_ms: DEFAULT_MAX_GAP_MS,
+        };
+        service.init_database()?;
+        Ok(service)
+    }
+
+    /// Initialize database tables
+    fn init_database(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("Failed to lock database: {}", e))?;
+        let conn = db.conn();
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS activity_sessions (
+                id TEXT PRIMARY KEY,
+                day TEXT NOT NULL,
+                app_name TEXT NOT NULL,
+                window_titles TEXT NOT NULL, -- JSON array
+                started_at INTEGER NOT NULL,
+                ended_at INTEGER NOT NULL,
+                duration_secs INTEGER NOT NULL,
+                sample_count INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS activity_daily_summaries (
+                day TEXT PRIMARY KEY,
+                summary TEXT NOT NULL,
+                generated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_activity_sessions_day ON activity_sessions(day, started_at)",
+            [],
+        )?;
+
+        log::info!("✓ Activity Timeline database initialized");
+        Ok(())
+    }
+
+    /// Get the timeline for a day (defaults to today), rebuilding sessions from screen captures
+    pub fn get_day(&self, date: Option<&str>) -> Result<DayTimeline> {
+        let day = resolve_day(date)?;
+        let (start_ms, end_ms) = day_bounds_ms(day)?;
+        let day_str = day.format("%Y-%m-%d").to_string();
+
+        let db = self.db.lock().map_err(|e| anyhow!("Failed to lock database: {}", e))?;
+        let conn = db.conn();
+
+        // Load screen samples for the day
+        let mut stmt = conn.prepare(
+            "SELECT application_name, window_title, timestamp
+             FROM screen_context
+             WHERE timestamp >= ?1 AND timestamp < ?2 AND application_name IS NOT NULL
+             ORDER BY timestamp ASC",
+        )?;
+        let samples = stmt
+            .query_map(rusqlite::params![start_ms, end_ms], |row| {
+                Ok(ActivitySample {
+                    app_name: row.get(0)?,
+                    window_title: row.get(1)?,
+                    timestamp: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        drop(stmt);
+
+        let sessions = build_sessions(&samples, self.max_gap_ms);
+
+        // Persist sessions for the day (replace previous build)
+        conn.execute("DELETE FROM activity_sessions WHERE day = ?1", [&day_str])?;
+        for session in &sessions {
+            conn.execute(
+                "INSERT INTO activity_sessions (id, day, app_name, window_titles, started_at,
+                 ended_at, duration_secs, sample_count)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                rusqlite::params![
+                    session.id,
+                    day_str,
+                    session.app_name,
+                    serde_json::to_string(&session.window_titles)?,
+                    session.started_at,
+                    session.ended_at,
+                    session.duration_secs,
+                    session.sample_count,
+                ],
+            )?;
+        }
+
+        // Conversation activity
+        let mut stmt = conn.prepare(
+            "SELECT c.id, c.title, COUNT(m.id), MIN(m.timestamp), MAX(m.timestamp)
+             FROM messages m
+             JOIN conversations c ON c.id = m.conversation_id
+             WHERE m.timestamp >= ?1 AND m.timestamp < ?2
+             GROUP BY c.id
+             ORDER BY MIN(m.timestamp) ASC",
+        )?;
+        let mut conversations: Vec<ConversationActivity> = stmt
+            .query_map(rusqlite::params![start_ms, end_ms], |row| {
+                Ok(ConversationActivity {
+                    conversation_id: row.get(0)?,
+                    title: row.get(1)?,
+                    message_count: row.get(2)?,
+                    first_message_at: row.get(3)?,
+                    last_message_at: row.get(4)?,
+                    topics: Vec::new(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        drop(stmt);
+        let mut topics = topics_by_conversation(conn, conversations.iter().map(|c| c.conversation_id.as_str()))?;
+        for conversation in &mut conversations {
+            conversation.topics = topics.remove(&conversation.conversation_id).unwrap_or_default();
+        }
+
+        let summary = match conn.query_row(
+            "SELECT day, summary, generated_at FROM activity_daily_summaries WHERE day = ?1",
+            [&day_str],
+            |row| {
+                Ok(DailySummary {
+                    date: row.get(0)?,
+                    summary: row.get(1)?,
+                    generated_at: row.get(2)?,
+                })
+            },
+        ) {
+            Ok(summary) => Some(summary),
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        let app_usage = aggregate_app_usage(&sessions);
+        let topics = aggregate_topics(&conversations);
+        let total_tracked_secs = sessions.iter().map(|s| s.duration_secs).sum();
+
+        Ok(DayTimeline {
+            date: day_str,
+            sessions,
+            app_usage,
+            conversations,
+            topics,
+            total_tracked_secs,
+            summary,
+        })
+    }
+
+    /// Generate (or regenerate) the LLM daily summary for a day
+    pub async fn generate_summary(&self, date: Option<&str>) -> Result<DailySummary> {
+        let timeline = self.get_day(date)?;
+
+        if timeline.sessions.is_empty() && timeline.conversations.is_empty() {
+            return Err(anyhow!("No activity recorded for {}", timeline.date));
+        }
+
+        let prompt = build_summary_prompt(&timeline);
+        let response = ollama::generate_response(&prompt)
+            .await
+            .map_err(|e| anyhow!("Failed to generate daily summary: {}", e))?;
+
+        let summary = DailySummary {
+            date: timeline.date.clone(),
+            summary: response.trim().to_string(),
+            generated_at: chrono::Utc::now().timestamp_millis(),
+        };
+
+        let db = self.db.lock().map_err(|e| anyhow!("Failed to lock database: {}", e))?;
+        db.conn().execute(
+            "INSERT OR REPLACE INTO activity_daily_summaries (day, summary, generated_at)
+             VALUES (?1, ?2, ?3)",
+            rusqlite::params![summary.date, summary.summary, summary.generated_at],
+        )?;
+
+        log::info!("✓ Daily summary generated for {}", summary.date);
+        Ok(summary)
+    }
+}
+
+/// Parse a YYYY-MM-DD date, or return today's local date
+fn resolve_day(date: Option<&str>) -> Result<NaiveDate> {
+    match date {
+        Some(d) => NaiveDate::parse_from_str(d, "%Y-%m-%d")
+            .with_context(|| format!("Invalid date '{}', expected YYYY-MM-DD", d)),
+        None => Ok(chrono::Local::now().date_naive()),
+    }
+}
+
+/// Local-time [start, end) bounds of a day in Unix millis
+fn day_bounds_ms(day: NaiveDate) -> Result<(i64, i64)> {
+    let to_ms = |d: NaiveDate| -> Result<i64> {
+        let naive = d.and_hms_opt(0, 0, 0).ok_or_else(|| anyhow!("Invalid day start"))?;
+        chrono::Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|dt| dt.timestamp_millis())
+            .ok_or_else(|| anyhow!("Ambiguous local time for {}", d))
+    };
+
+    let next = day.succ_opt().ok_or_else(|| anyhow!("Date out of range"))?;
+    Ok((to_ms(day)?, to_ms(next)?))
+}
+
+/// Merge consecutive samples of the same app into sessions
+///
+/// Samples must be sorted by timestamp. A new session starts whenever the app
+/// changes or the gap between two samples exceeds `max_gap_ms`.
+pub fn build_sessions(samples: &[ActivitySample], max_gap_ms: i64) -> Vec<ActivitySession> {
+    let mut sessions: Vec<ActivitySession> = Vec::new();
+
+    for sample in samples {
+        let extend = sessions.last().is_some_and(|last| {
+            last.app_name == sample.app_name && sample.timestamp - last.ended_at <= max_gap_ms
+        });
+
+        if extend {
+            let last = sessions.last_mut().expect("checked above");
+            last.ended_at = sample.timestamp;
+            last.duration_secs = (last.ended_at - last.started_at) / 1000;
+            last.sample_count += 1;
+            if let Some(title) = &sample.window_title {
+                if !last.window_titles.contains(title) {
+                    last.window_titles.push(title.clone());
+                }
+            }
+        } else {
+            sessions.push(ActivitySession {
+                id: format!("session_{}_{}", sample.timestamp, sessions.len()),
+                app_name: sample.app_name.clone(),
+                window_titles: sample.window_title.iter().cloned().collect(),
+                started_at: sample.timestamp,
+                ended_at: sample.timestamp,
+                duration_secs: 0,
+                sample_count: 1,
+            });
+        }
+    }
+
+    sessions
+}
+
+/// Sum session durations per app, sorted by total time descending
+pub fn aggregate_app_usage(sessions: &[ActivitySession]) -> Vec<AppUsage> {
+    let mut usage: HashMap<String, AppUsage> = HashMap::new();
+
+    for session in sessions {
+        let entry = usage.entry(session.app_name.clone()).or_insert_with(|| AppUsage {
+            app_name: session.app_name.clone(),
+            total_secs: 0,
+            session_count: 0,
+        });
+        entry.total_secs += session.duration_secs;
+        entry.session_count += 1;
+    }
+
+    let mut usage: Vec<AppUsage> = usage.into_values().collect();
+    usage.sort_by(|a, b| b.total_secs.cmp(&a.total_secs).then_with(|| a.app_name.cmp(&b.app_name)));
+    usage
+}
+
+/// Group a day's conversations by topic (v3.9.0)
+pub fn aggregate_topics(conversations: &[ConversationActivity]) -> Vec<TopicActivity> {
+    let mut topics: HashMap<String, TopicActivity> = HashMap::new();
+
+    for conversation in conversations {
+        for topic in &conversation.topics {
+            let entry = topics.entry(topic.clone()).or_insert_with(|| TopicActivity {
+                topic: topic.clone(),
+                conversation_count: 0,
+                message_count: 0,
+            });
+            entry.conversation_count += 1;
+            entry.message_count += conversation.message_count;
+        }
+    }
+
+    let mut topics: Vec<TopicActivity> = topics.into_values().collect();
+    topics.sort_by(|a, b| b.message_count.cmp(&a.message_count).then_with(|| a.topic.cmp(&b.topic)));
+    topics
+}
+
+/// Build the prompt used for daily summary generation
+fn build_summary_prompt(timeline: &DayTimeline) -> String {
+    let app_lines: Vec<String> = timeline
+        .app_usage
+        .iter()
+        .map(|u| format!("- {}: {} min ({} sessions)", u.app_name, u.total_secs / 60, u.session_count))
+        .collect();
+
+    let session_lines: Vec<String> = timeline
+        .sessions
+        .iter()
+        .filter(|s| s.duration_secs > 0)
+        .take(MAX_SESSIONS_IN_PROMPT)
+        .map(|s| {
+            let start = chrono::Local
+                .timestamp_millis_opt(s.started_at)
+                .single()
+                .map(|dt| dt.format("%H:%M").to_string())
+                .unwrap_or_default();
+            format!("- {} {} ({} min): {}", start, s.app_name, s.duration_secs / 60, s.window_titles.join(" | "))
+        })
+        .collect();
+
+    let conversation_lines: Vec<String> = timeline
+        .conversations
+        .iter()
+        .map(|c| {
+            if c.topics.is_empty() {
+                format!("- \"{}\" ({} messages)", c.title, c.message_count)
+            } else {
+                format!("- \"{}\" ({} messages; topics: {})", c.title, c.message_count, c.topics.join(", "))
+            }
+        })
+        .collect();
+
+    format!(
+        r#"You are summarizing the user's day based on their computer activity.
+
+Date: {}
+
+App usage:
+{}
+
+Sessions:
+{}
+
+Conversations with the assistant:
+{}
+
+Write a short summary (3-6 sentences) of what the user worked on today.
+Group related activity, mention the main projects or topics, and note how time was split.
+Respond in the same language the user mostly uses in window titles and conversations."#,
+        timeline.date,
+        if app_lines.is_empty() { "- (none)".to_string() } else { app_lines.join("\n") },
+        if session_lines.is_empty() { "- (none)".to_string() } else { session_lines.join("\n") },
+        if conversation_lines.is_empty() { "- (none)".to_string() } else { conversation_lines.join("\n") },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(app: &str, title: &str, timestamp: i64) -> ActivitySample {
+        ActivitySample {
+            app_name: app.to_string(),
+            window_title: Some(title.to_string()),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_build_sessions_merges_consecutive_samples() {
+        let samples = vec![
+            sample("Code", "main.rs", 0),
+            sample("Code", "lib.rs", 60_000),
+            sample("Safari", "Docs", 120_000),
+            sample("Code", "main.rs", 180_000),
+        ];
+
+        let sessions = build_sessions(&samples, DEFAULT_MAX_GAP_MS);
+        assert_eq!(sessions.len(), 3);
+        assert_eq!(sessions[0].app_name, "Code");
+        assert_eq!(sessions[0].duration_secs, 60);
+        assert_eq!(sessions[0].window_titles, vec!["main.rs", "lib.rs"]);
+        assert_eq!(sessions[1].app_name, "Safari");
+    }
+
+    #[test]
+    fn test_build_sessions_splits_on_gap() {
+        let samples = vec![
+            sample("Code", "main.rs", 0),
+            sample("Code", "main.rs", DEFAULT_MAX_GAP_MS + 1),
+        ];
+
+        let sessions = build_sessions(&samples, DEFAULT_MAX_GAP_MS);
+        assert_eq!(sessions.len(), 2);
+    }
+
+    #[test]
+    fn test_aggregate_app_usage() {
+        let samples = vec![
+            sample("Code", "a", 0),
+            sample("Code", "a", 120_000),
+            sample("Safari", "b", 180_000),
+            sample("Safari", "b", 240_000),
+            sample("Code", "a", 300_000),
+            sample("Code", "a", 360_000),
+        ];
+
+        let usage = aggregate_app_usage(&build_sessions(&samples, DEFAULT_MAX_GAP_MS));
+        assert_eq!(usage[0].app_name, "Code");
+        assert_eq!(usage[0].total_secs, 180);
+        assert_eq!(usage[0].session_count, 2);
+        assert_eq!(usage[1].total_secs, 60);
+    }
+
+    #[test]
+    fn test_aggregate_topics() {
+        let conversation = |id: &str, messages: u32, topics: &[&str]| ConversationActivity {
+            conversation_id: id.to_string(),
+            title: id.to_string(),
+            message_count: messages,
+            first_message_at: 0,
+            last_message_at: 0,
+            topics: topics.iter().map(|t| t.to_string()).collect(),
+        };
+        let topics = aggregate_topics(&[
+            conversation("a", 4, &["rust"]),
+            conversation("b", 10, &["rust", "databases"]),
+            conversation("c", 2, &[]),
+        ]);
+        assert_eq!(topics.len(), 2);
+        assert_eq!(topics[0].topic, "rust");
+        assert_eq!(topics[0].conversation_count, 2);
+        assert_eq!(topics[0].message_count, 14);
+    }
+
+    #[test]
+    fn test_resolve_day_rejects_bad_format() {
+        assert!(resolve_day(Some("15/01/2025")).is_err());
+    }
+}
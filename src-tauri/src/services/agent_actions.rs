@@ -0,0 +1,527 @@
+//! Agent Action Log (v3.9.0)
+//!
+//! Undo queue for filesystem and git changes made by agent tools.
+//!
+//! Features:
+//! - Every write_file / edit_file / delete_file call snapshots the file first;
+//!   the action is only logged when the change succeeded
+//! - git_commit calls record the commit and its parent
+//! - Undo the last action, one action by id, or roll back a whole plan
+//!   (actions are grouped by the plan being executed)
+//! - Undo refuses to clobber files changed since the agent touched them unless forced
+//! - Snapshots of the oldest actions are pruned beyond `MAX_ACTIONS`
+//!
+//! Tools reach the log through a process-wide instance (see `install_global`),
+//! like the workspace used for path resolution.
+
+#![allow(dead_code)]  // Phase 5: Agent undo queue
+
+use crate::database::Database;
+use crate::services::git::GitService;
+use anyhow::{anyhow, Result};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Process-wide action log used by the agent tools
+static GLOBAL_ACTION_LOG: OnceLock<Arc<AgentActionLog>> = OnceLock::new();
+
+/// Actions kept (with their snapshots); older ones can no longer be undone
+const MAX_ACTIONS: usize = 500;
+
+/// Kind of change an agent made
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionKind {
+    WriteFile,
+    EditFile,
+    DeleteFile,
+    GitCommit,
+}
+
+impl ActionKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ActionKind::WriteFile => "write_file",
+            ActionKind::EditFile => "edit_file",
+            ActionKind::DeleteFile => "delete_file",
+            ActionKind::GitCommit => "git_commit",
+        }
+    }
+
+    fn from_str(value: &str) -> Self {
+        match value {
+            "edit_file" => ActionKind::EditFile,
+            "delete_file" => ActionKind::DeleteFile,
+            "git_commit" => ActionKind::GitCommit,
+            _ => ActionKind::WriteFile,
+        }
+    }
+}
+
+/// Logged agent action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentAction {
+    pub id: String,
+    /// Plan the action was part of
+    pub group_id: Option<String>,
+    pub kind: ActionKind,
+    /// File path, or repository path for git actions
+    pub path: String,
+    pub description: String,
+    /// File existed before the action (file actions)
+    pub existed_before: bool,
+    /// sha256 of the file right after the action, None if it no longer existed
+    pub after_sha256: Option<String>,
+    /// Commit created and its parent (git actions)
+    pub commit_id: Option<String>,
+    pub parent_commit_id: Option<String>,
+    pub undone: bool,
+    pub created_at: i64, // Unix millis
+    pub undone_at: Option<i64>,
+}
+
+/// Action that could not be undone during a rollback
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoFailure {
+    pub action_id: String,
+    pub path: String,
+    pub error: String,
+}
+
+/// Result of rolling back a plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollbackReport {
+    pub group_id: String,
+    pub undone: Vec<AgentAction>,
+    pub failed: Vec<UndoFailure>,
+}
+
+/// Agent action log with file snapshots
+pub struct AgentActionLog {
+    db: Arc<Mutex<Database>>,
+    snapshot_dir: PathBuf,
+    /// Plan currently being executed; new actions join its group
+    active_group: Mutex<Option<String>>,
+}
+
+impl AgentActionLog {
+    pub fn new(db: Arc<Mutex<Database>>, snapshot_dir: PathBuf) -> Result<Self> {
+        let log = Self {
+            db,
+            snapshot_dir,
+            active_group: Mutex::new(None),
+        };
+        log.init_database()?;
+        log::info!("✓ Agent Action Log initialized");
+        Ok(log)
+    }
+
+    fn init_database(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn().execute_batch(
+            "CREATE TABLE IF NOT EXISTS agent_actions (
+                id TEXT PRIMARY KEY,
+                group_id TEXT,
+                kind TEXT NOT NULL,
+                path TEXT NOT NULL,
+                description TEXT NOT NULL,
+                existed_before INTEGER NOT NULL DEFAULT 0,
+                snapshot_path TEXT,
+                after_sha256 TEXT,
+                commit_id TEXT,
+                parent_commit_id TEXT,
+                undone_at INTEGER,
+                created_at INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_agent_actions_group ON agent_actions(group_id, created_at DESC);",
+        )?;
+        Ok(())
+    }
+
+    /// Make this the log the agent tools record into
+    pub fn install_global(self: &Arc<Self>) {
+        if GLOBAL_ACTION_LOG.set(Arc::clone(self)).is_err() {
+            log::warn!("Agent action log already installed, ignoring");
+        }
+    }
+
+    /// Group subsequent actions under `group_id` (a plan id) until `end_group`
+    pub fn begin_group(&self, group_id: &str) {
+        if let Ok(mut active) = self.active_group.lock() {
+            *active = Some(group_id.to_string());
+        }
+    }
+
+    pub fn end_group(&self) {
+        if let Ok(mut active) = self.active_group.lock() {
+            *active = None;
+        }
+    }
+
+    fn current_group(&self) -> Option<String> {
+        self.active_group.lock().ok().and_then(|active| active.clone())
+    }
+
+    /// Snapshot `path`, run `change`, and log the action if it succeeded.
+    /// Returns the change's result and the action id.
+    pub fn track_file_change<T>(
+        &self,
+        kind: ActionKind,
+        path: &str,
+        description: &str,
+        change: impl FnOnce() -> Result<T>,
+    ) -> Result<(T, String)> {
+        let id = format!("act_{}", uuid::Uuid::new_v4());
+        let snapshot_path = match fs::read(path) {
+            Ok(content) => {
+                fs::create_dir_all(&self.snapshot_dir)?;
+                let snapshot_path = self.snapshot_dir.join(&id);
+                fs::write(&snapshot_path, content)?;
+                Some(snapshot_path)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(anyhow!("Failed to snapshot {}: {}", path, e)),
+        };
+
+        let output = match change() {
+            Ok(output) => output,
+            Err(e) => {
+                if let Some(snapshot_path) = &snapshot_path {
+                    let _ = fs::remove_file(snapshot_path);
+                }
+                return Err(e);
+            }
+        };
+
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn().execute(
+            "INSERT INTO agent_actions
+             (id, group_id, kind, path, description, existed_before, snapshot_path, after_sha256, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                id,
+                self.current_group(),
+                kind.as_str(),
+                path,
+                description,
+                snapshot_path.is_some(),
+                snapshot_path.as_ref().map(|p| p.to_string_lossy().to_string()),
+                file_hash(Path::new(path)),
+                chrono::Utc::now().timestamp_millis(),
+            ],
+        )?;
+        drop(db);
+        self.prune()?;
+
+        log::info!("Logged agent action {} ({} {})", id, kind.as_str(), path);
+        Ok((output, id))
+    }
+
+    /// Log a commit the agent created
+    pub fn record_git_commit(
+        &self,
+        repo_path: &str,
+        commit_id: &str,
+        parent_commit_id: Option<&str>,
+        description: &str,
+    ) -> Result<String> {
+        let id = format!("act_{}", uuid::Uuid::new_v4());
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn().execute(
+            "INSERT INTO agent_actions
+             (id, group_id, kind, path, description, commit_id, parent_commit_id, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                id,
+                self.current_group(),
+                ActionKind::GitCommit.as_str(),
+                repo_path,
+                description,
+                commit_id,
+                parent_commit_id,
+                chrono::Utc::now().timestamp_millis(),
+            ],
+        )?;
+        Ok(id)
+    }
+
+    /// Logged actions, newest first, optionally for one plan
+    pub fn list_actions(&self, group_id: Option<&str>, limit: usize) -> Result<Vec<AgentAction>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT id, group_id, kind, path, description, existed_before, after_sha256,
+                    commit_id, parent_commit_id, undone_at, created_at
+             FROM agent_actions
+             WHERE ?1 IS NULL OR group_id = ?1
+             ORDER BY created_at DESC, rowid DESC
+             LIMIT ?2",
+        )?;
+        let actions = stmt
+            .query_map(params![group_id, limit as i64], action_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(actions)
+    }
+
+    /// Undo the most recent action that hasn't been undone
+    pub fn undo_last(&self, force: bool) -> Result<AgentAction> {
+        let id: String = {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            db.conn()
+                .query_row(
+                    "SELECT id FROM agent_actions WHERE undone_at IS NULL ORDER BY created_at DESC, rowid DESC LIMIT 1",
+                    [],
+                    |row| row.get(0),
+                )
+                .optional()?
+                .ok_or_else(|| anyhow!("No agent actions to undo"))?
+        };
+        self.undo_action(&id, force)
+    }
+
+    /// Undo one action. Unless `force`, refuses when the file (or branch)
+    /// changed since the agent's action.
+    pub fn undo_action(&self, action_id: &str, force: bool) -> Result<AgentAction> {
+        let (action, snapshot_path) = {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            db.conn()
+                .query_row(
+                    "SELECT id, group_id, kind, path, description, existed_before, after_sha256,
+                            commit_id, parent_commit_id, undone_at, created_at, snapshot_path
+                     FROM agent_actions WHERE id = ?1",
+                    [action_id],
+                    |row| Ok((action_from_row(row)?, row.get::<_, Option<String>>(11)?)),
+                )
+                .optional()?
+                .ok_or_else(|| anyhow!("Agent action not found: {}", action_id))?
+        };
+        if action.undone {
+            return Err(anyhow!("Action {} was already undone", action_id));
+        }
+
+        match action.kind {
+            ActionKind::GitCommit => undo_commit(&action, force)?,
+            _ => undo_file_change(&action, snapshot_path.as_deref(), force)?,
+        }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn().execute("UPDATE agent_actions SET undone_at = ?1 WHERE id = ?2", params![now, action_id])?;
+        if let Some(snapshot_path) = snapshot_path {
+            let _ = fs::remove_file(snapshot_path);
+        }
+
+        log::info!("Undid agent action {} ({} {})", action.id, action.kind.as_str(), action.path);
+        Ok(AgentAction {
+            undone: true,
+            undone_at: Some(now),
+            ..action
+        })
+    }
+
+    /// Undo every action of a plan, newest first. Failures are reported and
+    /// the remaining actions are still attempted.
+    pub fn rollback_group(&self, group_id: &str, force: bool) -> Result<RollbackReport> {
+        let actions = self.list_actions(Some(group_id), MAX_ACTIONS)?;
+        if actions.is_empty() {
+            return Err(anyhow!("No agent actions recorded for {}", group_id));
+        }
+
+        let mut report = RollbackReport {
+            group_id: group_id.to_string(),
+            undone: Vec::new(),
+            failed: Vec::new(),
+        };
+        for action in actions.into_iter().filter(|action| !action.undone) {
+            match self.undo_action(&action.id, force) {
+                Ok(undone) => report.undone.push(undone),
+                Err(e) => report.failed.push(UndoFailure {
+                    action_id: action.id,
+                    path: action.path,
+                    error: e.to_string(),
+                }),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Drop the oldest actions and their snapshots beyond `MAX_ACTIONS`
+    fn prune(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let conn = db.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, snapshot_path FROM agent_actions ORDER BY created_at DESC, rowid DESC LIMIT -1 OFFSET ?1",
+        )?;
+        let stale: Vec<(String, Option<String>)> = stmt
+            .query_map([MAX_ACTIONS as i64], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+        drop(stmt);
+        for (id, snapshot_path) in stale {
+            if let Some(snapshot_path) = snapshot_path {
+                let _ = fs::remove_file(snapshot_path);
+            }
+            conn.execute("DELETE FROM agent_actions WHERE id = ?1", [&id])?;
+        }
+        Ok(())
+    }
+}
+
+/// Run a file change through the global action log (untracked if none is installed)
+pub fn track_file_change<T>(
+    kind: ActionKind,
+    path: &str,
+    description: &str,
+    change: impl FnOnce() -> Result<T>,
+) -> Result<(T, Option<String>)> {
+    match GLOBAL_ACTION_LOG.get() {
+        Some(log) => log
+            .track_file_change(kind, path, description, change)
+            .map(|(output, id)| (output, Some(id))),
+        None => change().map(|output| (output, None)),
+    }
+}
+
+/// Log a commit in the global action log, if installed
+pub fn record_git_commit(
+    repo_path: &str,
+    commit_id: &str,
+    parent_commit_id: Option<&str>,
+    description: &str,
+) -> Option<String> {
+    let log = GLOBAL_ACTION_LOG.get()?;
+    match log.record_git_commit(repo_path, commit_id, parent_commit_id, description) {
+        Ok(id) => Some(id),
+        Err(e) => {
+            log::warn!("Failed to log agent commit {}: {}", commit_id, e);
+            None
+        }
+    }
+}
+
+/// Group actions of the global log under a plan until `end_group`
+pub fn begin_group(group_id: &str) {
+    if let Some(log) = GLOBAL_ACTION_LOG.get() {
+        log.begin_group(group_id);
+    }
+}
+
+pub fn end_group() {
+    if let Some(log) = GLOBAL_ACTION_LOG.get() {
+        log.end_group();
+    }
+}
+
+fn action_from_row(row: &rusqlite::Row) -> rusqlite::Result<AgentAction> {
+    let kind: String = row.get(2)?;
+    let undone_at: Option<i64> = row.get(9)?;
+    Ok(AgentAction {
+        id: row.get(0)?,
+        group_id: row.get(1)?,
+        kind: ActionKind::from_str(&kind),
+        path: row.get(3)?,
+        description: row.get(4)?,
+        existed_before: row.get::<_, i64>(5)? != 0,
+        after_sha256: row.get(6)?,
+        commit_id: row.get(7)?,
+        parent_commit_id: row.get(8)?,
+        undone: undone_at.is_some(),
+        created_at: row.get(10)?,
+        undone_at,
+    })
+}
+
+fn file_hash(path: &Path) -> Option<String> {
+    fs::read(path).ok().map(|content| format!("{:x}", Sha256::digest(&content)))
+}
+
+/// Restore the snapshot (or remove a file the agent created)
+fn undo_file_change(action: &AgentAction, snapshot_path: Option<&str>, force: bool) -> Result<()> {
+    let path = Path::new(&action.path);
+    if !force && file_hash(path) != action.after_sha256 {
+        return Err(anyhow!(
+            "{} changed after the agent's {}; undo with force to overwrite",
+            action.path,
+            action.kind.as_str()
+        ));
+    }
+
+    if action.existed_before {
+        let snapshot_path = snapshot_path.ok_or_else(|| anyhow!("Snapshot missing for {}", action.id))?;
+        let content = fs::read(snapshot_path).map_err(|e| anyhow!("Snapshot for {} is gone: {}", action.id, e))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+    } else if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Move the branch back to the commit's parent, keeping the changes staged
+fn undo_commit(action: &AgentAction, force: bool) -> Result<()> {
+    let commit_id = action.commit_id.as_deref().ok_or_else(|| anyhow!("Commit id missing for {}", action.id))?;
+    let parent = action
+        .parent_commit_id
+        .as_deref()
+        .ok_or_else(|| anyhow!("Can't undo the repository's initial commit"))?;
+    let head = GitService::head_commit_id(&action.path)?;
+    if !force && head.as_deref() != Some(commit_id) {
+        return Err(anyhow!("{} has new commits since the agent's commit; undo with force to reset anyway", action.path));
+    }
+    GitService::reset_soft(&action.path, parent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_and_rollback() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let log = AgentActionLog::new(db, dir.path().join("snapshots")).unwrap();
+
+        let existing = dir.path().join("notes.txt");
+        fs::write(&existing, "original").unwrap();
+        let existing = existing.to_string_lossy().to_string();
+        let created = dir.path().join("new/plan.md").to_string_lossy().to_string();
+
+        log.begin_group("plan_1");
+        log.track_file_change(ActionKind::WriteFile, &existing, "rewrite notes", || {
+            fs::write(&existing, "agent version").map_err(Into::into)
+        })
+        .unwrap();
+        log.track_file_change(ActionKind::WriteFile, &created, "create plan", || {
+            fs::create_dir_all(dir.path().join("new"))?;
+            fs::write(&created, "# Plan").map_err(Into::into)
+        })
+        .unwrap();
+        log.end_group();
+
+        // Failed changes are not logged
+        assert!(log
+            .track_file_change(ActionKind::DeleteFile, &existing, "fail", || -> Result<()> { Err(anyhow!("boom")) })
+            .is_err());
+        assert_eq!(log.list_actions(None, 10).unwrap().len(), 2);
+
+        // Undo last removes the created file
+        let undone = log.undo_last(false).unwrap();
+        assert_eq!(undone.path, created);
+        assert!(!Path::new(&created).exists());
+
+        // Rollback refuses to clobber a user edit unless forced
+        fs::write(&existing, "user edit").unwrap();
+        let report = log.rollback_group("plan_1", false).unwrap();
+        assert_eq!(report.failed.len(), 1);
+        let report = log.rollback_group("plan_1", true).unwrap();
+        assert_eq!(report.undone.len(), 1);
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "original");
+        assert!(log.undo_last(false).is_err());
+    }
+}
@@ -0,0 +1,464 @@
+//! Usage Analytics Service (v3.9.0)
+//!
+//! Local-only usage metrics for the dashboard view. Nothing leaves the device.
+//!
+//! Features:
+//! - Per-day counters: prompt/completion tokens, LLM calls, tool calls, RAG lookups
+//! - Latency samples for p50/p90/p99 percentiles
+//! - Model breakdown (calls, tokens, average latency)
+//! - Process-wide recorder used by the free functions in `ollama.rs` and `ToolService`
+
+#![allow(dead_code)]  // Phase 5: Usage analytics dashboard
+
+use crate::database::Database;
+use anyhow::{anyhow, Result};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Process-wide analytics used by code paths without access to managed state
+static GLOBAL_ANALYTICS: OnceLock<Arc<AnalyticsService>> = OnceLock::new();
+
+/// Latency samples older than this are pruned at startup (counters are kept)
+const LATENCY_RETENTION_DAYS: i64 = 90;
+
+const METRIC_PROMPT_TOKENS: &str = "prompt_tokens";
+const METRIC_COMPLETION_TOKENS: &str = "completion_tokens";
+const METRIC_LLM_CALLS: &str = "llm_calls";
+const METRIC_TOOL_CALLS: &str = "tool_calls";
+const METRIC_TOOL_ERRORS: &str = "tool_errors";
+const METRIC_RAG_LOOKUPS: &str = "rag_lookups";
+const METRIC_RAG_HITS: &str = "rag_hits";
+
+/// Latency percentiles in milliseconds
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LatencyPercentiles {
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub samples: usize,
+}
+
+/// Per-tool invocation counts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolUsage {
+    pub tool: String,
+    pub calls: i64,
+    pub errors: i64,
+}
+
+/// Per-model usage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelUsage {
+    pub model: String,
+    pub calls: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub avg_latency_ms: f64,
+}
+
+/// Dashboard summary over the last `days` days
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyticsSummary {
+    pub days: u32,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub llm_calls: i64,
+    pub tool_calls: i64,
+    pub rag_lookups: i64,
+    /// Share of RAG lookups that returned at least one memory (0.0-1.0)
+    pub rag_hit_rate: f64,
+    pub latency: LatencyPercentiles,
+    pub tools: Vec<ToolUsage>,
+    pub models: Vec<ModelUsage>,
+}
+
+/// Metrics available as a daily series
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SeriesMetric {
+    Tokens,
+    LlmCalls,
+    ToolCalls,
+    RagHitRate,
+    LatencyP50,
+    LatencyP90,
+    LatencyP99,
+}
+
+/// One day of a series
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SeriesPoint {
+    /// Local date (YYYY-MM-DD)
+    pub day: String,
+    pub value: f64,
+}
+
+/// Nearest-rank percentile of an ascending slice (0 when empty)
+pub fn percentile(sorted: &[u64], p: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// p50/p90/p99 of an ascending slice
+pub fn latency_percentiles(sorted: &[u64]) -> LatencyPercentiles {
+    LatencyPercentiles {
+        p50: percentile(sorted, 50.0),
+        p90: percentile(sorted, 90.0),
+        p99: percentile(sorted, 99.0),
+        samples: sorted.len(),
+    }
+}
+
+/// The last `days` local dates ending at `today`, oldest first
+pub fn day_range(today: chrono::NaiveDate, days: u32) -> Vec<String> {
+    (0..days.max(1) as i64)
+        .rev()
+        .map(|offset| (today - chrono::Duration::days(offset)).format("%Y-%m-%d").to_string())
+        .collect()
+}
+
+fn today() -> chrono::NaiveDate {
+    chrono::Local::now().date_naive()
+}
+
+fn today_key() -> String {
+    today().format("%Y-%m-%d").to_string()
+}
+
+/// Usage analytics service
+pub struct AnalyticsService {
+    db: Arc<Mutex<Database>>,
+}
+
+impl AnalyticsService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        let service = Self { db };
+        service.init_database()?;
+        log::info!("✓ Analytics Service initialized");
+        Ok(service)
+    }
+
+    /// Register this service as the process-wide recorder
+    pub fn install_global(self: &Arc<Self>) {
+        if GLOBAL_ANALYTICS.set(Arc::clone(self)).is_err() {
+            log::warn!("Analytics service already installed, ignoring");
+        }
+    }
+
+    fn init_database(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let conn = db.conn();
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS analytics_daily (
+                day TEXT NOT NULL,
+                metric TEXT NOT NULL,
+                dimension TEXT NOT NULL DEFAULT '',
+                value INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (day, metric, dimension)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS analytics_latency (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                day TEXT NOT NULL,
+                model TEXT NOT NULL,
+                latency_ms INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_analytics_latency_day ON analytics_latency(day)",
+            [],
+        )?;
+
+        let cutoff = (today() - chrono::Duration::days(LATENCY_RETENTION_DAYS))
+            .format("%Y-%m-%d")
+            .to_string();
+        let pruned = conn.execute("DELETE FROM analytics_latency WHERE day < ?1", params![cutoff])?;
+        if pruned > 0 {
+            log::info!("Pruned {} old latency samples", pruned);
+        }
+
+        Ok(())
+    }
+
+    fn increment(&self, metric: &str, dimension: &str, amount: i64) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        db.conn().execute(
+            "INSERT INTO analytics_daily (day, metric, dimension, value) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(day, metric, dimension) DO UPDATE SET value = value + excluded.value",
+            params![today_key(), metric, dimension, amount],
+        )?;
+        Ok(())
+    }
+
+    /// Record one LLM request
+    pub fn record_llm_call(
+        &self,
+        model: &str,
+        prompt_tokens: u32,
+        completion_tokens: u32,
+        latency_ms: u64,
+    ) -> Result<()> {
+        self.increment(METRIC_LLM_CALLS, model, 1)?;
+        self.increment(METRIC_PROMPT_TOKENS, model, prompt_tokens as i64)?;
+        self.increment(METRIC_COMPLETION_TOKENS, model, completion_tokens as i64)?;
+
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        db.conn().execute(
+            "INSERT INTO analytics_latency (day, model, latency_ms) VALUES (?1, ?2, ?3)",
+            params![today_key(), model, latency_ms as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Record one tool invocation
+    pub fn record_tool_call(&self, tool: &str, success: bool) -> Result<()> {
+        self.increment(METRIC_TOOL_CALLS, tool, 1)?;
+        if !success {
+            self.increment(METRIC_TOOL_ERRORS, tool, 1)?;
+        }
+        Ok(())
+    }
+
+    /// Record one RAG lookup (hit = at least one memory returned)
+    pub fn record_rag_lookup(&self, hit: bool) -> Result<()> {
+        self.increment(METRIC_RAG_LOOKUPS, "", 1)?;
+        if hit {
+            self.increment(METRIC_RAG_HITS, "", 1)?;
+        }
+        Ok(())
+    }
+
+    /// Totals over the last `days` days (including today)
+    pub fn get_summary(&self, days: u32) -> Result<AnalyticsSummary> {
+        let days = days.max(1);
+        let since = day_range(today(), days).remove(0);
+
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let conn = db.conn();
+
+        // (metric, dimension) -> total
+        let mut stmt = conn.prepare(
+            "SELECT metric, dimension, SUM(value) FROM analytics_daily
+             WHERE day >= ?1 GROUP BY metric, dimension",
+        )?;
+        let totals: Vec<(String, String, i64)> = stmt
+            .query_map(params![since], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let total = |metric: &str| -> i64 {
+            totals.iter().filter(|(m, _, _)| m == metric).map(|(_, _, v)| v).sum()
+        };
+        let by_dimension = |metric: &str| -> HashMap<String, i64> {
+            totals
+                .iter()
+                .filter(|(m, _, _)| m == metric)
+                .map(|(_, d, v)| (d.clone(), *v))
+                .collect()
+        };
+
+        let rag_lookups = total(METRIC_RAG_LOOKUPS);
+        let rag_hit_rate = if rag_lookups > 0 {
+            total(METRIC_RAG_HITS) as f64 / rag_lookups as f64
+        } else {
+            0.0
+        };
+
+        let tool_errors = by_dimension(METRIC_TOOL_ERRORS);
+        let mut tools: Vec<ToolUsage> = by_dimension(METRIC_TOOL_CALLS)
+            .into_iter()
+            .map(|(tool, calls)| ToolUsage {
+                errors: tool_errors.get(&tool).copied().unwrap_or(0),
+                tool,
+                calls,
+            })
+            .collect();
+        tools.sort_by(|a, b| b.calls.cmp(&a.calls).then_with(|| a.tool.cmp(&b.tool)));
+
+        let mut stmt = conn.prepare(
+            "SELECT model, AVG(latency_ms) FROM analytics_latency WHERE day >= ?1 GROUP BY model",
+        )?;
+        let avg_latency: HashMap<String, f64> = stmt
+            .query_map(params![since], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        let prompt_tokens = by_dimension(METRIC_PROMPT_TOKENS);
+        let completion_tokens = by_dimension(METRIC_COMPLETION_TOKENS);
+        let mut models: Vec<ModelUsage> = by_dimension(METRIC_LLM_CALLS)
+            .into_iter()
+            .map(|(model, calls)| ModelUsage {
+                prompt_tokens: prompt_tokens.get(&model).copied().unwrap_or(0),
+                completion_tokens: completion_tokens.get(&model).copied().unwrap_or(0),
+                avg_latency_ms: avg_latency.get(&model).copied().unwrap_or(0.0),
+                model,
+                calls,
+            })
+            .collect();
+        models.sort_by(|a, b| b.calls.cmp(&a.calls).then_with(|| a.model.cmp(&b.model)));
+
+        let mut stmt = conn.prepare(
+            "SELECT latency_ms FROM analytics_latency WHERE day >= ?1 ORDER BY latency_ms ASC",
+        )?;
+        let latencies: Vec<u64> = stmt
+            .query_map(params![since], |row| row.get::<_, i64>(0))?
+            .map(|r| r.map(|ms| ms as u64))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(AnalyticsSummary {
+            days,
+            prompt_tokens: total(METRIC_PROMPT_TOKENS),
+            completion_tokens: total(METRIC_COMPLETION_TOKENS),
+            llm_calls: total(METRIC_LLM_CALLS),
+            tool_calls: total(METRIC_TOOL_CALLS),
+            rag_lookups,
+            rag_hit_rate,
+            latency: latency_percentiles(&latencies),
+            tools,
+            models,
+        })
+    }
+
+    /// Daily values for one metric over the last `days` days (days without data are 0)
+    pub fn get_series(&self, metric: SeriesMetric, days: u32) -> Result<Vec<SeriesPoint>> {
+        let range = day_range(today(), days);
+        let since = range[0].clone();
+
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let conn = db.conn();
+
+        let counter_sums = |metrics: &[&str]| -> Result<HashMap<String, i64>> {
+            let placeholders = vec!["?"; metrics.len()].join(", ");
+            let sql = format!(
+                "SELECT day, SUM(value) FROM analytics_daily
+                 WHERE day >= ? AND metric IN ({}) GROUP BY day",
+                placeholders
+            );
+            let mut values: Vec<&dyn rusqlite::ToSql> = vec![&since];
+            values.extend(metrics.iter().map(|m| m as &dyn rusqlite::ToSql));
+
+            let mut stmt = conn.prepare(&sql)?;
+            let sums = stmt
+                .query_map(values.as_slice(), |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<Result<HashMap<_, _>, _>>()?;
+            Ok(sums)
+        };
+
+        let by_day: HashMap<String, f64> = match metric {
+            SeriesMetric::Tokens => counter_sums(&[METRIC_PROMPT_TOKENS, METRIC_COMPLETION_TOKENS])?
+                .into_iter()
+                .map(|(day, v)| (day, v as f64))
+                .collect(),
+            SeriesMetric::LlmCalls => counter_sums(&[METRIC_LLM_CALLS])?
+                .into_iter()
+                .map(|(day, v)| (day, v as f64))
+                .collect(),
+            SeriesMetric::ToolCalls => counter_sums(&[METRIC_TOOL_CALLS])?
+                .into_iter()
+                .map(|(day, v)| (day, v as f64))
+                .collect(),
+            SeriesMetric::RagHitRate => {
+                let hits = counter_sums(&[METRIC_RAG_HITS])?;
+                counter_sums(&[METRIC_RAG_LOOKUPS])?
+                    .into_iter()
+                    .filter(|(_, lookups)| *lookups > 0)
+                    .map(|(day, lookups)| {
+                        let rate = hits.get(&day).copied().unwrap_or(0) as f64 / lookups as f64;
+                        (day, rate)
+                    })
+                    .collect()
+            }
+            SeriesMetric::LatencyP50 | SeriesMetric::LatencyP90 | SeriesMetric::LatencyP99 => {
+                let p = match metric {
+                    SeriesMetric::LatencyP50 => 50.0,
+                    SeriesMetric::LatencyP90 => 90.0,
+                    _ => 99.0,
+                };
+
+                let mut stmt = conn.prepare(
+                    "SELECT day, latency_ms FROM analytics_latency
+                     WHERE day >= ?1 ORDER BY day, latency_ms ASC",
+                )?;
+                let mut samples: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+                let rows = stmt.query_map(params![since], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+                })?;
+                for row in rows {
+                    let (day, ms) = row?;
+                    samples.entry(day).or_default().push(ms as u64);
+                }
+
+                samples
+                    .into_iter()
+                    .map(|(day, sorted)| (day, percentile(&sorted, p) as f64))
+                    .collect()
+            }
+        };
+
+        Ok(range
+            .into_iter()
+            .map(|day| SeriesPoint {
+                value: by_day.get(&day).copied().unwrap_or(0.0),
+                day,
+            })
+            .collect())
+    }
+}
+
+/// Record an LLM request on the global recorder (no-op when none is installed)
+pub fn record_llm_call(model: &str, prompt_tokens: u32, completion_tokens: u32, latency_ms: u64) {
+    if let Some(analytics) = GLOBAL_ANALYTICS.get() {
+        if let Err(e) = analytics.record_llm_call(model, prompt_tokens, completion_tokens, latency_ms) {
+            log::warn!("Failed to record LLM analytics: {}", e);
+        }
+    }
+}
+
+/// Record a tool invocation on the global recorder
+pub fn record_tool_call(tool: &str, success: bool) {
+    if let Some(analytics) = GLOBAL_ANALYTICS.get() {
+        if let Err(e) = analytics.record_tool_call(tool, success) {
+            log::warn!("Failed to record tool analytics: {}", e);
+        }
+    }
+}
+
+/// Record a RAG lookup on the global recorder
+pub fn record_rag_lookup(hit: bool) {
+    if let Some(analytics) = GLOBAL_ANALYTICS.get() {
+        if let Err(e) = analytics.record_rag_lookup(hit) {
+            log::warn!("Failed to record RAG analytics: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        assert_eq!(percentile(&[], 50.0), 0);
+        assert_eq!(percentile(&[42], 99.0), 42);
+
+        let samples: Vec<u64> = (1..=100).collect();
+        let p = latency_percentiles(&samples);
+        assert_eq!((p.p50, p.p90, p.p99, p.samples), (50, 90, 99, 100));
+    }
+
+    #[test]
+    fn test_day_range() {
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        assert_eq!(day_range(today, 3), vec!["2026-02-28", "2026-03-01", "2026-03-02"]);
+        assert_eq!(day_range(today, 0), vec!["2026-03-02"]);
+    }
+}
@@ -11,6 +11,17 @@
  *    - Result: ~200 tokens per chunk (10x compression)
  * 3. Keep recent 4000 tokens (working memory)
  *
+ * Hierarchical compression (v3.9.0):
+ * - The newest middle chunks keep a detailed summary, older chunks are merged
+ *   into coarse summaries (recent-detailed, older-coarse)
+ * - A stored Summary Buffer summary (conversation_memory) replaces the coarse
+ *   tier for the turns it already covers, and the new rolling summary is
+ *   written back there so evicted turns are never silently dropped
+ *
+ * Context window (v3.9.0):
+ * - max_context_tokens and window_size are upper bounds; the active model's
+ *   history budget (model_context) lowers them for smaller windows
+ *
  * Output: 4 + compressed_middle + 4000 tokens (~10K total)
  *
  * Benefits:
@@ -20,6 +31,7 @@
  * - Preserves conversation coherence
  */
 
+use super::model_context;
 use log::{debug, info, warn};
 use serde::{Deserialize, Serialize};
 
@@ -31,8 +43,27 @@ pub struct AttentionSinkConfig {
     pub compression_ratio: f32,  // How much to compress middle (default: 0.1)
     pub chunk_size: usize,       // Size of chunks for compression (default: 2000)
     pub max_context_tokens: usize, // Max tokens before compression (default: 32768)
+    #[serde(default = "default_detailed_chunks")]
+    pub detailed_chunks: usize,    // v3.9.0: Newest middle chunks kept detailed (default: 2)
+    #[serde(default = "default_coarse_group_size")]
+    pub coarse_group_size: usize,  // v3.9.0: Older chunks merged per coarse summary (default: 4)
+}
+
+fn default_detailed_chunks() -> usize {
+    2
+}
+
+fn default_coarse_group_size() -> usize {
+    4
 }
 
+/// Messages per middle chunk
+const MIDDLE_CHUNK_MESSAGES: usize = 10;
+
+/// Key lines kept per summary at each level
+const DETAILED_SUMMARY_LINES: usize = 5;
+const COARSE_SUMMARY_LINES: usize = 3;
+
 impl Default for AttentionSinkConfig {
     fn default() -> Self {
         AttentionSinkConfig {
@@ -41,6 +72,8 @@ impl Default for AttentionSinkConfig {
             compression_ratio: 0.1,
             chunk_size: 2000,
             max_context_tokens: 32768,  // Qwen 2.5 context window
+            detailed_chunks: default_detailed_chunks(),
+            coarse_group_size: default_coarse_group_size(),
         }
     }
 }
@@ -55,6 +88,33 @@ pub struct ManagedContext {
     pub compressed_tokens: usize,
     pub compression_ratio_achieved: f32,
     pub requires_compression: bool,
+    /// Middle summaries, oldest first (v3.9.0)
+    #[serde(default)]
+    pub summary_segments: Vec<SummarySegment>,
+    /// Messages replaced by summaries (v3.9.0)
+    #[serde(default)]
+    pub evicted_messages: usize,
+}
+
+/// Granularity of a middle summary (v3.9.0)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryLevel {
+    /// Recently evicted turns, a few key lines per chunk
+    Detailed,
+    /// Older turns, several chunks merged into one summary
+    Coarse,
+    /// Summary Buffer summary carried over from conversation_memory
+    Stored,
+}
+
+/// One summary of consecutive evicted messages (v3.9.0)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SummarySegment {
+    pub level: SummaryLevel,
+    pub first_message: usize,
+    pub message_count: usize,
+    pub text: String,
 }
 
 /// Attention Sink Manager
@@ -80,9 +140,20 @@ impl AttentionSinkManager {
         AttentionSinkManager { config }
     }
 
+    /// Compression threshold: the configured maximum, capped by the history
+    /// budget of the active model's context window (v3.9.0)
+    pub fn max_context_tokens(&self) -> usize {
+        self.config.max_context_tokens.min(model_context::history_budget())
+    }
+
+    /// Recent window size, at most half the compression threshold (v3.9.0)
+    pub fn window_tokens(&self) -> usize {
+        self.config.window_size.min(self.max_context_tokens() / 2)
+    }
+
     /// Check if context needs compression
     pub fn needs_compression(&self, token_count: usize) -> bool {
-        token_count > self.config.max_context_tokens
+        token_count > self.max_context_tokens()
     }
 
     /// Estimate token count (simple heuristic: ~1.3 tokens per word)
@@ -93,11 +164,31 @@ impl AttentionSinkManager {
 
     /// Manage context with attention sink pattern
     pub fn manage_context(&self, full_context: &str) -> ManagedContext {
+        let messages: Vec<&str> = full_context.split("\n\n").collect();
+        self.manage_messages(full_context, &messages, None)
+    }
+
+    /// Manage a list of conversation turns (v3.9.0)
+    ///
+    /// `stored_summary` is the Summary Buffer summary and the number of leading
+    /// turns it covers; it stands in for those turns in the compressed middle.
+    pub fn manage_turns(&self, turns: &[String], stored_summary: Option<(&str, usize)>) -> ManagedContext {
+        let messages: Vec<&str> = turns.iter().map(String::as_str).collect();
+        let full_context = messages.join("\n\n");
+        self.manage_messages(&full_context, &messages, stored_summary)
+    }
+
+    fn manage_messages(
+        &self,
+        full_context: &str,
+        messages: &[&str],
+        stored_summary: Option<(&str, usize)>,
+    ) -> ManagedContext {
         let estimated_tokens = self.estimate_tokens(full_context);
 
         debug!(
             "Managing context: {} estimated tokens (max: {})",
-            estimated_tokens, self.config.max_context_tokens
+            estimated_tokens, self.max_context_tokens()
         );
 
         // If context is small enough, no compression needed
@@ -111,14 +202,13 @@ impl AttentionSinkManager {
                 compressed_tokens: estimated_tokens,
                 compression_ratio_achieved: 1.0,
                 requires_compression: false,
+                summary_segments: Vec::new(),
+                evicted_messages: 0,
             };
         }
 
         info!("Context exceeds limit, applying attention sink compression");
 
-        // Split context into messages/chunks
-        let messages: Vec<&str> = full_context.split("\n\n").collect();
-
         if messages.is_empty() {
             warn!("Empty context provided");
             return self.empty_context();
@@ -130,8 +220,8 @@ impl AttentionSinkManager {
 
         // 2. Extract recent window (last N messages)
         let window_messages_count = self.estimate_message_count_for_tokens(
-            &messages,
-            self.config.window_size,
+            messages,
+            self.window_tokens(),
         );
         let recent_start = (messages.len() - window_messages_count).max(sink_messages_count);
         let recent_window: String = messages[recent_start..].join("\n\n");
@@ -140,12 +230,16 @@ impl AttentionSinkManager {
         let middle_start = sink_messages_count;
         let middle_end = recent_start;
 
-        let compressed_middle = if middle_end > middle_start {
-            let middle_messages = &messages[middle_start..middle_end];
-            self.compress_middle(middle_messages)
+        let summary_segments = if middle_end > middle_start {
+            self.compress_middle(messages, middle_start, middle_end, stored_summary)
         } else {
-            String::new()
+            Vec::new()
         };
+        let compressed_middle = summary_segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n[...]\n\n");
 
         // Calculate compression statistics
         let sink_tokens = self.estimate_tokens(&attention_sink);
@@ -170,52 +264,100 @@ impl AttentionSinkManager {
             compressed_tokens: compressed_total,
             compression_ratio_achieved,
             requires_compression: true,
+            summary_segments,
+            evicted_messages: middle_end.saturating_sub(middle_start),
         }
     }
 
-    /// Compress middle section by summarizing in chunks
-    fn compress_middle(&self, messages: &[&str]) -> String {
-        if messages.is_empty() {
-            return String::new();
+    /// Compress middle section into hierarchical summaries (v3.9.0)
+    ///
+    /// Turns covered by the stored summary reuse it; the rest is split into
+    /// chunks, the newest `detailed_chunks` summarized in detail and older ones
+    /// merged `coarse_group_size` at a time into coarse summaries.
+    fn compress_middle(
+        &self,
+        messages: &[&str],
+        start: usize,
+        end: usize,
+        stored_summary: Option<(&str, usize)>,
+    ) -> Vec<SummarySegment> {
+        let mut segments = Vec::new();
+        let mut start = start;
+
+        if let Some((text, covered)) = stored_summary {
+            let covered_end = covered.min(end);
+            if covered_end > start && !text.trim().is_empty() {
+                segments.push(SummarySegment {
+                    level: SummaryLevel::Stored,
+                    first_message: start,
+                    message_count: covered_end - start,
+                    text: format!("[Conversation summary] {}", text.trim()),
+                });
+                start = covered_end;
+            }
         }
 
-        debug!("Compressing {} middle messages", messages.len());
-
-        // Group messages into chunks
-        let chunk_size = 10; // ~10 messages per chunk
-        let mut compressed_chunks = Vec::new();
+        if end <= start {
+            return segments;
+        }
 
-        for chunk in messages.chunks(chunk_size) {
-            let chunk_text = chunk.join("\n");
-            let summary = self.summarize_chunk(&chunk_text);
-            compressed_chunks.push(summary);
+        debug!("Compressing {} middle messages", end - start);
+
+        let chunks: Vec<(usize, &[&str])> = messages[start..end]
+            .chunks(MIDDLE_CHUNK_MESSAGES)
+            .enumerate()
+            .map(|(i, chunk)| (start + i * MIDDLE_CHUNK_MESSAGES, chunk))
+            .collect();
+        let detailed_from = chunks.len().saturating_sub(self.config.detailed_chunks);
+
+        for group in chunks[..detailed_from].chunks(self.config.coarse_group_size.max(1)) {
+            let first_message = group[0].0;
+            let group_messages: Vec<&str> = group.iter().flat_map(|(_, chunk)| chunk.iter().copied()).collect();
+            segments.push(SummarySegment {
+                level: SummaryLevel::Coarse,
+                first_message,
+                message_count: group_messages.len(),
+                text: format!(
+                    "[Earlier summary] {}",
+                    self.summarize_chunk(&group_messages.join("\n"), COARSE_SUMMARY_LINES)
+                ),
+            });
         }
 
-        let result = compressed_chunks.join("\n\n[...]\n\n");
-        debug!("Compressed to {} summary chunks", compressed_chunks.len());
+        for (first_message, chunk) in &chunks[detailed_from..] {
+            segments.push(SummarySegment {
+                level: SummaryLevel::Detailed,
+                first_message: *first_message,
+                message_count: chunk.len(),
+                text: format!(
+                    "[Summary] {}",
+                    self.summarize_chunk(&chunk.join("\n"), DETAILED_SUMMARY_LINES)
+                ),
+            });
+        }
 
-        result
+        debug!("Compressed to {} summary segments", segments.len());
+        segments
     }
 
     /// Summarize a chunk of text (placeholder for now)
-    fn summarize_chunk(&self, chunk: &str) -> String {
+    fn summarize_chunk(&self, chunk: &str, max_lines: usize) -> String {
         // For v3.6.0, we use a simple extractive summary
         // TODO: In production, call LLM for abstractive summarization
 
-        let lines: Vec<&str> = chunk.lines().collect();
+        let lines: Vec<&str> = chunk.lines().filter(|line| !line.trim().is_empty()).collect();
 
-        if lines.len() <= 3 {
-            return chunk.to_string();
+        if lines.len() <= max_lines {
+            return lines.join(" ... ");
         }
 
-        // Extract key lines (first, last, and middle)
-        let summary_lines: Vec<String> = vec![
-            lines.first().unwrap_or(&"").to_string(),
-            lines.get(lines.len() / 2).unwrap_or(&"").to_string(),
-            lines.last().unwrap_or(&"").to_string(),
-        ];
+        // Extract key lines evenly spread from first to last
+        let max_lines = max_lines.max(2);
+        let summary_lines: Vec<&str> = (0..max_lines)
+            .map(|i| lines[i * (lines.len() - 1) / (max_lines - 1)])
+            .collect();
 
-        format!("[Summary] {}", summary_lines.join(" ... "))
+        summary_lines.join(" ... ")
     }
 
     /// Estimate how many messages fit in token budget
@@ -270,6 +412,8 @@ impl AttentionSinkManager {
             compressed_tokens: 0,
             compression_ratio_achieved: 1.0,
             requires_compression: false,
+            summary_segments: Vec::new(),
+            evicted_messages: 0,
         }
     }
 
@@ -291,10 +435,11 @@ impl AttentionSinkManager {
     pub fn stats(&self) -> AttentionSinkStats {
         AttentionSinkStats {
             sink_size: self.config.sink_size,
-            window_size: self.config.window_size,
+            window_size: self.window_tokens(),
             compression_ratio: self.config.compression_ratio,
             chunk_size: self.config.chunk_size,
-            max_context_tokens: self.config.max_context_tokens,
+            max_context_tokens: self.max_context_tokens(),
+            context_window: model_context::context_window(),
         }
     }
 }
@@ -313,6 +458,7 @@ pub struct AttentionSinkStats {
     pub compression_ratio: f32,
     pub chunk_size: usize,
     pub max_context_tokens: usize,
+    pub context_window: usize, // v3.9.0: Window of the active model
 }
 
 #[cfg(test)]
@@ -332,9 +478,13 @@ mod tests {
     #[test]
     fn test_needs_compression() {
         let manager = AttentionSinkManager::new();
+        let limit = manager.max_context_tokens();
 
+        // Follows the active model's window, never above the configured maximum
+        assert!(limit <= AttentionSinkConfig::default().max_context_tokens);
         assert!(!manager.needs_compression(1000));
-        assert!(!manager.needs_compression(30000));
+        assert!(!manager.needs_compression(limit));
+        assert!(manager.needs_compression(limit + 1));
         assert!(manager.needs_compression(35000));
         assert!(manager.needs_compression(100000));
     }
@@ -374,6 +524,37 @@ mod tests {
         assert!(result.compression_ratio_achieved < 1.0);
     }
 
+    #[test]
+    fn test_hierarchical_summaries() {
+        let config = AttentionSinkConfig {
+            sink_size: 2,
+            window_size: 30,
+            max_context_tokens: 100,
+            ..AttentionSinkConfig::default()
+        };
+        let manager = AttentionSinkManager::with_config(config);
+        let turns: Vec<String> = (0..80).map(|i| format!("user: message number {}", i)).collect();
+
+        let result = manager.manage_turns(&turns, None);
+        let levels: Vec<SummaryLevel> = result.summary_segments.iter().map(|s| s.level).collect();
+
+        assert!(result.requires_compression);
+        assert_eq!(levels.last(), Some(&SummaryLevel::Detailed));
+        assert_eq!(levels.first(), Some(&SummaryLevel::Coarse));
+        assert_eq!(
+            result.summary_segments.iter().map(|s| s.message_count).sum::<usize>(),
+            result.evicted_messages
+        );
+        assert!(result.compressed_middle.contains("message number 2"));
+
+        // A stored summary stands in for the turns it covers
+        let result = manager.manage_turns(&turns, Some(("Talked about numbers.", 40)));
+        let stored = &result.summary_segments[0];
+        assert_eq!(stored.level, SummaryLevel::Stored);
+        assert_eq!(stored.first_message + stored.message_count, 40);
+        assert!(result.compressed_middle.starts_with("[Conversation summary] Talked about numbers."));
+    }
+
     #[test]
     fn test_format_for_prompt() {
         let manager = AttentionSinkManager::new();
@@ -386,6 +567,8 @@ mod tests {
             compressed_tokens: 500,
             compression_ratio_achieved: 0.5,
             requires_compression: true,
+            summary_segments: Vec::new(),
+            evicted_messages: 0,
         };
 
         let formatted = manager.format_for_prompt(&context);
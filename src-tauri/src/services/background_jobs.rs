@@ -0,0 +1,1045 @@
+//! Background Jobs Service (v3.9.0)
+//!
+//! One scheduler for the app's periodic maintenance work.
+//!
+//! Features:
+//! - Per-job schedules (interval + random jitter) persisted in `background_jobs`
+//! - Jobs: memory decay, memory consolidation, wiki fact extraction, graph maintenance,
+//!   memory review reminders, recurring task generation, goal progress inference, weekly review,
+//!   conversation titling and topic labeling, scheduled user scripts, retrieval tuning,
+//!   knowledge vault sync, conversation digests
+//! - Pause/resume (survives restarts), run-now, next-run introspection
+//! - Startup jitter so jobs don't all fire the moment the app starts
+//! - Nothing runs while encrypted storage is locked
+
+#![allow(dead_code)]  // Phase 5: Background jobs
+
+use crate::database::Database;
+use crate::services::conversation_digest::{self, ConversationDigestService};
+use crate::services::conversation_topics::{ConversationTopicsService, DEFAULT_BATCH_SIZE};
+use crate::services::decay_worker::run_decay_cycle;
+use crate::services::encryption;
+use crate::services::goal_tracker::GoalTrackerService;
+use crate::services::graph_storage::GraphStorage;
+use crate::services::knowledge_export::KnowledgeExportService;
+#[cfg(feature = "lancedb-support")]
+use crate::services::hybrid_search::HybridSearchEngine;
+#[cfg(feature = "phase4")]
+use crate::services::memory_consolidation::MemoryConsolidationService;
+use crate::services::notification::{AppNotification, NotificationAction, NotificationService, NotificationSource, DEFAULT_SNOOZE_MINUTES};
+use crate::services::review_queue::ReviewQueueService;
+#[cfg(feature = "lancedb-support")]
+use crate::services::rag_v2::RagServiceV2;
+#[cfg(feature = "lancedb-support")]
+use crate::services::retrieval_feedback;
+use crate::services::scripting::ScriptingService;
+use crate::services::semantic_wiki::SemanticWikiService;
+use crate::services::task_planner::TaskPlannerService;
+use crate::services::temporal_memory::TemporalMemoryService;
+use crate::services::weekly_review::WeeklyReviewService;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How often the scheduler looks for due jobs
+const TICK_SECS: u64 = 30;
+
+/// Minimum delay before any job runs after startup
+const STARTUP_DELAY_MINUTES: u64 = 2;
+
+/// Overdue jobs are spread over this window after startup
+const STARTUP_JITTER_MINUTES: u64 = 10;
+
+/// Conversation turns handed to fact extraction per run
+const WIKI_TURNS_PER_RUN: usize = 25;
+
+/// Due reviews needed before the reminder job notifies
+const REVIEW_REMINDER_MIN_DUE: usize = 3;
+
+const MINUTE_MS: i64 = 60_000;
+
+/// How often a job runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobSchedule {
+    pub interval_minutes: u64,
+    /// Random extra delay (0..=jitter) added to every run
+    pub jitter_minutes: u64,
+}
+
+/// A periodic maintenance task
+#[async_trait]
+pub trait BackgroundJob: Send + Sync {
+    /// Stable id used by commands and persistence ("memory_decay")
+    fn id(&self) -> &'static str;
+
+    fn default_schedule(&self) -> JobSchedule;
+
+    /// Run once. `since` is the start of the last successful run (Unix millis).
+    /// Returns a short summary for `JobStatus::last_result`.
+    async fn run(&self, since: Option<i64>) -> Result<String>;
+}
+
+/// Job state returned to the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobStatus {
+    pub id: String,
+    pub schedule: JobSchedule,
+    pub paused: bool,
+    pub running: bool,
+    pub last_run_at: Option<i64>,
+    pub last_success_at: Option<i64>,
+    pub last_duration_ms: Option<u64>,
+    pub last_result: Option<String>,
+    pub last_error: Option<String>,
+    /// `None` while paused or running
+    pub next_run_at: Option<i64>,
+}
+
+struct JobState {
+    schedule: JobSchedule,
+    paused: bool,
+    running: bool,
+    last_run_at: Option<i64>,
+    last_success_at: Option<i64>,
+    last_duration_ms: Option<u64>,
+    last_result: Option<String>,
+    last_error: Option<String>,
+    next_run_at: i64,
+}
+
+struct JobEntry {
+    job: Arc<dyn BackgroundJob>,
+    state: Mutex<JobState>,
+}
+
+impl JobEntry {
+    fn status(&self) -> Result<JobStatus> {
+        let state = self.state.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        Ok(JobStatus {
+            id: self.job.id().to_string(),
+            schedule: state.schedule,
+            paused: state.paused,
+            running: state.running,
+            last_run_at: state.last_run_at,
+            last_success_at: state.last_success_at,
+            last_duration_ms: state.last_duration_ms,
+            last_result: state.last_result.clone(),
+            last_error: state.last_error.clone(),
+            next_run_at: (!state.paused && !state.running).then_some(state.next_run_at),
+        })
+    }
+}
+
+/// Random delay in `0..=max_minutes`, in millis
+pub fn random_jitter_ms(max_minutes: u64) -> i64 {
+    if max_minutes == 0 {
+        return 0;
+    }
+    let random = std::collections::hash_map::RandomState::new().build_hasher().finish();
+    (random % (max_minutes * 60_000 + 1)) as i64
+}
+
+/// First run after startup: when it's next due, but never before the startup delay
+pub fn first_run_at(now: i64, last_success_at: Option<i64>, schedule: JobSchedule, startup_jitter_ms: i64) -> i64 {
+    let earliest = now + STARTUP_DELAY_MINUTES as i64 * MINUTE_MS + startup_jitter_ms;
+    match last_success_at {
+        Some(last) => (last + schedule.interval_minutes as i64 * MINUTE_MS).max(earliest),
+        None => earliest,
+    }
+}
+
+/// Next run after one finished
+pub fn next_run_after(finished_at: i64, schedule: JobSchedule, jitter_ms: i64) -> i64 {
+    finished_at + schedule.interval_minutes as i64 * MINUTE_MS + jitter_ms
+}
+
+/// Background jobs service
+pub struct BackgroundJobsService {
+    db: Arc<Mutex<Database>>,
+    jobs: Mutex<Vec<Arc<JobEntry>>>,
+    running: AtomicBool,
+}
+
+impl BackgroundJobsService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        {
+            let db = db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            db.conn().execute(
+                "CREATE TABLE IF NOT EXISTS background_jobs (
+                    job_id TEXT PRIMARY KEY,
+                    interval_minutes INTEGER NOT NULL,
+                    jitter_minutes INTEGER NOT NULL,
+                    paused INTEGER NOT NULL DEFAULT 0,
+                    last_run_at INTEGER,
+                    last_success_at INTEGER,
+                    last_error TEXT
+                )",
+                [],
+            )?;
+        }
+
+        log::info!("✓ Background Jobs Service initialized");
+        Ok(Self {
+            db,
+            jobs: Mutex::new(Vec::new()),
+            running: AtomicBool::new(false),
+        })
+    }
+
+    /// Add a job, restoring its schedule and pause state from the last session
+    pub fn register(&self, job: Arc<dyn BackgroundJob>) -> Result<()> {
+        let saved = {
+            let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            db.conn()
+                .query_row(
+                    "SELECT interval_minutes, jitter_minutes, paused, last_run_at, last_success_at, last_error
+                     FROM background_jobs WHERE job_id = ?1",
+                    params![job.id()],
+                    |row| {
+                        Ok((
+                            JobSchedule {
+                                interval_minutes: row.get::<_, i64>(0)?.max(1) as u64,
+                                jitter_minutes: row.get::<_, i64>(1)?.max(0) as u64,
+                            },
+                            row.get::<_, bool>(2)?,
+                            row.get::<_, Option<i64>>(3)?,
+                            row.get::<_, Option<i64>>(4)?,
+                            row.get::<_, Option<String>>(5)?,
+                        ))
+                    },
+                )
+                .optional()?
+        };
+
+        let (schedule, paused, last_run_at, last_success_at, last_error) =
+            saved.unwrap_or((job.default_schedule(), false, None, None, None));
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let state = JobState {
+            schedule,
+            paused,
+            running: false,
+            last_run_at,
+            last_success_at,
+            last_duration_ms: None,
+            last_result: None,
+            last_error,
+            next_run_at: first_run_at(now, last_success_at, schedule, random_jitter_ms(STARTUP_JITTER_MINUTES)),
+        };
+        self.persist(job.id(), &state)?;
+
+        log::info!(
+            "Registered background job {} (every {}m ±{}m{})",
+            job.id(),
+            schedule.interval_minutes,
+            schedule.jitter_minutes,
+            if paused { ", paused" } else { "" }
+        );
+        self.jobs
+            .lock()
+            .map_err(|e| anyhow!("Lock error: {}", e))?
+            .push(Arc::new(JobEntry {
+                job,
+                state: Mutex::new(state),
+            }));
+        Ok(())
+    }
+
+    fn persist(&self, job_id: &str, state: &JobState) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        db.conn().execute(
+            "INSERT INTO background_jobs (job_id, interval_minutes, jitter_minutes, paused, last_run_at, last_success_at, last_error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(job_id) DO UPDATE SET
+                interval_minutes = excluded.interval_minutes,
+                jitter_minutes = excluded.jitter_minutes,
+                paused = excluded.paused,
+                last_run_at = excluded.last_run_at,
+                last_success_at = excluded.last_success_at,
+                last_error = excluded.last_error",
+            params![
+                job_id,
+                state.schedule.interval_minutes as i64,
+                state.schedule.jitter_minutes as i64,
+                state.paused,
+                state.last_run_at,
+                state.last_success_at,
+                state.last_error,
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn entry(&self, job_id: &str) -> Result<Arc<JobEntry>> {
+        self.jobs
+            .lock()
+            .map_err(|e| anyhow!("Lock error: {}", e))?
+            .iter()
+            .find(|entry| entry.job.id() == job_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Unknown background job: {}", job_id))
+    }
+
+    /// All jobs with their schedules and next run times
+    pub fn list_jobs(&self) -> Result<Vec<JobStatus>> {
+        let jobs = self.jobs.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        jobs.iter().map(|entry| entry.status()).collect()
+    }
+
+    /// Stop a job from being scheduled (a run in progress finishes)
+    pub fn pause(&self, job_id: &str) -> Result<JobStatus> {
+        let entry = self.entry(job_id)?;
+        {
+            let mut state = entry.state.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            state.paused = true;
+            self.persist(job_id, &state)?;
+        }
+        log::info!("Paused background job {}", job_id);
+        entry.status()
+    }
+
+    /// Resume a paused job; an overdue job runs on the next scheduler tick
+    pub fn resume(&self, job_id: &str) -> Result<JobStatus> {
+        let entry = self.entry(job_id)?;
+        {
+            let mut state = entry.state.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            state.paused = false;
+            self.persist(job_id, &state)?;
+        }
+        log::info!("Resumed background job {}", job_id);
+        entry.status()
+    }
+
+    /// Change a job's schedule; the next run is recomputed from the last one
+    pub fn update_schedule(&self, job_id: &str, schedule: JobSchedule) -> Result<JobStatus> {
+        if schedule.interval_minutes == 0 {
+            return Err(anyhow!("Interval must be at least one minute"));
+        }
+
+        let entry = self.entry(job_id)?;
+        {
+            let mut state = entry.state.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            state.schedule = schedule;
+            let now = chrono::Utc::now().timestamp_millis();
+            let base = state.last_run_at.unwrap_or(now);
+            state.next_run_at = next_run_after(base, schedule, random_jitter_ms(schedule.jitter_minutes)).max(now);
+            self.persist(job_id, &state)?;
+        }
+        log::info!(
+            "Background job {} now runs every {}m ±{}m",
+            job_id,
+            schedule.interval_minutes,
+            schedule.jitter_minutes
+        );
+        entry.status()
+    }
+
+    /// Run a job right away (also when paused)
+    pub fn run_now(self: &Arc<Self>, job_id: &str) -> Result<JobStatus> {
+        let entry = self.entry(job_id)?;
+        {
+            let mut state = entry.state.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            if state.running {
+                return Err(anyhow!("Background job {} is already running", job_id));
+            }
+            state.running = true;
+        }
+        self.spawn_run(Arc::clone(&entry));
+        entry.status()
+    }
+
+    /// Jobs due at `now`, marked running so the next tick doesn't start them twice
+    fn take_due(&self, now: i64) -> Result<Vec<Arc<JobEntry>>> {
+        if encryption::is_locked() {
+            return Ok(Vec::new());
+        }
+
+        let jobs = self.jobs.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let mut due = Vec::new();
+        for entry in jobs.iter() {
+            let mut state = entry.state.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            if !state.paused && !state.running && state.next_run_at <= now {
+                state.running = true;
+                due.push(Arc::clone(entry));
+            }
+        }
+        Ok(due)
+    }
+
+    fn spawn_run(self: &Arc<Self>, entry: Arc<JobEntry>) {
+        let service = Arc::clone(self);
+        tauri::async_runtime::spawn(async move {
+            service.execute(entry).await;
+        });
+    }
+
+    async fn execute(&self, entry: Arc<JobEntry>) {
+        let job_id = entry.job.id();
+        let since = entry.state.lock().ok().and_then(|state| state.last_success_at);
+
+        log::info!("Running background job {}", job_id);
+        let started_at = chrono::Utc::now().timestamp_millis();
+        let started = std::time::Instant::now();
+        let result = entry.job.run(since).await;
+        let finished_at = chrono::Utc::now().timestamp_millis();
+
+        match &result {
+            Ok(summary) => log::info!("✓ Background job {} finished: {}", job_id, summary),
+            Err(e) => log::warn!("Background job {} failed: {}", job_id, e),
+        }
+
+        let mut state = match entry.state.lock() {
+            Ok(state) => state,
+            Err(e) => {
+                log::error!("Background job {} state lock error: {}", job_id, e);
+                return;
+            }
+        };
+        state.running = false;
+        state.last_run_at = Some(started_at);
+        state.last_duration_ms = Some(started.elapsed().as_millis() as u64);
+        match result {
+            Ok(summary) => {
+                state.last_success_at = Some(started_at);
+                state.last_result = Some(summary);
+                state.last_error = None;
+            }
+            Err(e) => state.last_error = Some(e.to_string()),
+        }
+        state.next_run_at = next_run_after(finished_at, state.schedule, random_jitter_ms(state.schedule.jitter_minutes));
+
+        if let Err(e) = self.persist(job_id, &state) {
+            log::warn!("Failed to save background job {} state: {}", job_id, e);
+        }
+    }
+
+    /// Start the scheduler loop
+    pub fn start(self: &Arc<Self>) {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return; // Already running
+        }
+
+        let service = Arc::clone(self);
+        tauri::async_runtime::spawn(async move {
+            log::info!("Background jobs scheduler started");
+
+            while service.running.load(Ordering::SeqCst) {
+                match service.take_due(chrono::Utc::now().timestamp_millis()) {
+                    Ok(due) => {
+                        for entry in due {
+                            service.spawn_run(entry);
+                        }
+                    }
+                    Err(e) => log::warn!("Background jobs scheduler error: {}", e),
+                }
+                tokio::time::sleep(Duration::from_secs(TICK_SECS)).await;
+            }
+
+            log::info!("Background jobs scheduler stopped");
+        });
+    }
+
+    /// Stop the scheduler loop (runs in progress finish)
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Ebbinghaus retention update with optional pruning
+pub struct DecayJob {
+    temporal: Arc<TemporalMemoryService>,
+    auto_prune: bool,
+    notifications: Option<Arc<NotificationService>>,
+}
+
+impl DecayJob {
+    pub fn new(
+        temporal: Arc<TemporalMemoryService>,
+        auto_prune: bool,
+        notifications: Option<Arc<NotificationService>>,
+    ) -> Self {
+        Self {
+            temporal,
+            auto_prune,
+            notifications,
+        }
+    }
+}
+
+#[async_trait]
+impl BackgroundJob for DecayJob {
+    fn id(&self) -> &'static str {
+        "memory_decay"
+    }
+
+    fn default_schedule(&self) -> JobSchedule {
+        JobSchedule {
+            interval_minutes: self.temporal.get_config().decay_worker_interval_hours.max(1) * 60,
+            jitter_minutes: 30,
+        }
+    }
+
+    async fn run(&self, _since: Option<i64>) -> Result<String> {
+        let updated = run_decay_cycle(&self.temporal, self.auto_prune, self.notifications.as_ref()).await?;
+        Ok(format!("Updated {} retention scores", updated))
+    }
+}
+
+/// Merge clusters of similar low-retention memories (or propose merges for review)
+#[cfg(feature = "phase4")]
+pub struct ConsolidationJob {
+    consolidation: Arc<MemoryConsolidationService>,
+}
+
+#[cfg(feature = "phase4")]
+impl ConsolidationJob {
+    pub fn new(consolidation: Arc<MemoryConsolidationService>) -> Self {
+        Self { consolidation }
+    }
+}
+
+#[cfg(feature = "phase4")]
+#[async_trait]
+impl BackgroundJob for ConsolidationJob {
+    fn id(&self) -> &'static str {
+        "memory_consolidation"
+    }
+
+    fn default_schedule(&self) -> JobSchedule {
+        JobSchedule {
+            interval_minutes: 24 * 60,
+            jitter_minutes: 60,
+        }
+    }
+
+    async fn run(&self, _since: Option<i64>) -> Result<String> {
+        // v3.9.0: Merges wait for the user's review unless review is turned off
+        if self.consolidation.get_config().require_review {
+            let proposals = self.consolidation.propose_merges().await?;
+            return Ok(format!("Proposed {} memory merges for review", proposals.len()));
+        }
+        let results = self.consolidation.consolidate_memories().await?;
+        Ok(format!("Consolidated {} memory clusters", results.len()))
+    }
+}
+
+/// Extract wiki facts from conversation turns since the last run
+pub struct WikiExtractionJob {
+    db: Arc<Mutex<Database>>,
+    wiki: Arc<SemanticWikiService>,
+}
+
+impl WikiExtractionJob {
+    pub fn new(db: Arc<Mutex<Database>>, wiki: Arc<SemanticWikiService>) -> Self {
+        Self { db, wiki }
+    }
+
+    /// (message id, conversation id, user message, assistant reply) after `since`
+    fn turns_since(&self, since: i64) -> Result<Vec<(String, String, String, String)>> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT a.id, a.conversation_id,
+                    (SELECT u.content FROM messages u
+                     WHERE u.conversation_id = a.conversation_id AND u.role = 'user' AND u.timestamp <= a.timestamp
+                     ORDER BY u.timestamp DESC LIMIT 1),
+                    a.content
+             FROM messages a
+             WHERE a.role = 'assistant' AND a.timestamp > ?1
+             ORDER BY a.timestamp ASC
+             LIMIT ?2",
+        )?;
+        let turns = stmt
+            .query_map(params![since, WIKI_TURNS_PER_RUN as i64], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })?
+            .filter_map(|row| row.ok())
+            .filter_map(|(id, conversation_id, user, reply)| user.map(|user| (id, conversation_id, user, reply)))
+            .collect();
+        Ok(turns)
+    }
+}
+
+#[async_trait]
+impl BackgroundJob for WikiExtractionJob {
+    fn id(&self) -> &'static str {
+        "wiki_extraction"
+    }
+
+    fn default_schedule(&self) -> JobSchedule {
+        JobSchedule {
+            interval_minutes: 60,
+            jitter_minutes: 10,
+        }
+    }
+
+    async fn run(&self, since: Option<i64>) -> Result<String> {
+        if !self.wiki.get_config().auto_extract {
+            return Ok("Automatic extraction is disabled".to_string());
+        }
+
+        let since = since.unwrap_or_else(|| chrono::Utc::now().timestamp_millis() - 24 * 60 * MINUTE_MS);
+        let turns = self.turns_since(since)?;
+
+        let mut stored = 0;
+        let mut entities = Vec::new();
+        for (message_id, conversation_id, user_message, ai_response) in &turns {
+            match self
+                .wiki
+                .extract_facts(user_message, ai_response, conversation_id, Some(message_id))
+                .await
+            {
+                Ok(facts) if !facts.is_empty() => {
+                    entities.extend(facts.iter().map(|f| f.entity.clone()));
+                    stored += self.wiki.store_facts(facts).await?;
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Fact extraction failed for message {}: {}", message_id, e),
+            }
+        }
+
+        // Keep generated entity pages in step with the new facts
+        let pages = self.wiki.refresh_entity_pages(&entities).await?;
+
+        Ok(format!("Stored {} facts from {} turns, updated {} entity pages", stored, turns.len(), pages))
+    }
+}
+
+/// Knowledge graph cleanup (dangling rows, cached degrees, `PRAGMA optimize`)
+pub struct GraphMaintenanceJob {
+    graph: Arc<GraphStorage>,
+}
+
+impl GraphMaintenanceJob {
+    pub fn new(graph: Arc<GraphStorage>) -> Self {
+        Self { graph }
+    }
+}
+
+#[async_trait]
+impl BackgroundJob for GraphMaintenanceJob {
+    fn id(&self) -> &'static str {
+        "graph_maintenance"
+    }
+
+    fn default_schedule(&self) -> JobSchedule {
+        JobSchedule {
+            interval_minutes: 7 * 24 * 60,
+            jitter_minutes: 120,
+        }
+    }
+
+    async fn run(&self, _since: Option<i64>) -> Result<String> {
+        let graph = Arc::clone(&self.graph);
+        let report = tokio::task::spawn_blocking(move || graph.run_maintenance())
+            .await
+            .map_err(|e| anyhow!("Task join error: {}", e))?
+            .map_err(|e| anyhow!(e))?;
+        Ok(format!(
+            "Removed {} dangling relationships and {} document links, refreshed {} degrees",
+            report.dangling_relationships, report.dangling_documents, report.degrees_updated
+        ))
+    }
+}
+
+/// Re-sync the Markdown knowledge vault once a folder was chosen
+pub struct VaultSyncJob {
+    export: Arc<KnowledgeExportService>,
+}
+
+impl VaultSyncJob {
+    pub fn new(export: Arc<KnowledgeExportService>) -> Self {
+        Self { export }
+    }
+}
+
+#[async_trait]
+impl BackgroundJob for VaultSyncJob {
+    fn id(&self) -> &'static str {
+        "knowledge_vault_sync"
+    }
+
+    fn default_schedule(&self) -> JobSchedule {
+        JobSchedule {
+            interval_minutes: 6 * 60,
+            jitter_minutes: 30,
+        }
+    }
+
+    async fn run(&self, _since: Option<i64>) -> Result<String> {
+        if self.export.vault_path()?.is_none() {
+            return Ok("No vault folder configured".to_string());
+        }
+        let export = Arc::clone(&self.export);
+        let report = tokio::task::spawn_blocking(move || export.export_vault(None, false))
+            .await
+            .map_err(|e| anyhow!("Task join error: {}", e))??;
+        Ok(format!(
+            "Wrote {} of {} notes, removed {}, {} edited in the vault",
+            report.written, report.notes, report.removed, report.skipped_modified.len()
+        ))
+    }
+}
+
+/// Remind the user when enough memories are due for review
+pub struct ReviewReminderJob {
+    queue: Arc<ReviewQueueService>,
+    notifications: Arc<NotificationService>,
+}
+
+impl ReviewReminderJob {
+    pub fn new(queue: Arc<ReviewQueueService>, notifications: Arc<NotificationService>) -> Self {
+        Self { queue, notifications }
+    }
+}
+
+#[async_trait]
+impl BackgroundJob for ReviewReminderJob {
+    fn id(&self) -> &'static str {
+        "memory_review_reminder"
+    }
+
+    fn default_schedule(&self) -> JobSchedule {
+        JobSchedule {
+            interval_minutes: 24 * 60,
+            jitter_minutes: 120,
+        }
+    }
+
+    async fn run(&self, _since: Option<i64>) -> Result<String> {
+        let due = self.queue.get_stats()?.due;
+        if due < REVIEW_REMINDER_MIN_DUE {
+            return Ok(format!("{} memories due for review", due));
+        }
+
+        let notification = AppNotification::new(
+            NotificationSource::MemoryReview,
+            "Memories to review",
+            format!("{} things I know about you are fading. Are they still right?", due),
+        )
+        .with_action(NotificationAction::Snooze { minutes: DEFAULT_SNOOZE_MINUTES * 48 });
+        let shown = self.notifications.notify(notification).await?;
+
+        Ok(format!(
+            "{} memories due for review{}",
+            due,
+            if shown { ", reminder sent" } else { "" }
+        ))
+    }
+}
+
+/// Generate the next instances of recurring tasks
+pub struct RecurringTasksJob {
+    task_planner: Arc<TaskPlannerService>,
+}
+
+impl RecurringTasksJob {
+    pub fn new(task_planner: Arc<TaskPlannerService>) -> Self {
+        Self { task_planner }
+    }
+}
+
+#[async_trait]
+impl BackgroundJob for RecurringTasksJob {
+    fn id(&self) -> &'static str {
+        "recurring_tasks"
+    }
+
+    fn default_schedule(&self) -> JobSchedule {
+        JobSchedule {
+            interval_minutes: 30,
+            jitter_minutes: 5,
+        }
+    }
+
+    async fn run(&self, _since: Option<i64>) -> Result<String> {
+        let task_planner = Arc::clone(&self.task_planner);
+        let created = tokio::task::spawn_blocking(move || {
+            task_planner.generate_recurring_tasks(chrono::Utc::now().timestamp())
+        })
+        .await
+        .map_err(|e| anyhow!("Task join error: {}", e))??;
+        Ok(format!("Generated {} recurring tasks", created.len()))
+    }
+}
+
+/// Infer goal progress from commits, completed tasks and conversations since the last run
+pub struct GoalProgressJob {
+    goal_tracker: Arc<GoalTrackerService>,
+}
+
+impl GoalProgressJob {
+    pub fn new(goal_tracker: Arc<GoalTrackerService>) -> Self {
+        Self { goal_tracker }
+    }
+}
+
+#[async_trait]
+impl BackgroundJob for GoalProgressJob {
+    fn id(&self) -> &'static str {
+        "goal_progress"
+    }
+
+    fn default_schedule(&self) -> JobSchedule {
+        JobSchedule {
+            interval_minutes: 60,
+            jitter_minutes: 10,
+        }
+    }
+
+    async fn run(&self, since: Option<i64>) -> Result<String> {
+        if !self.goal_tracker.get_inference_config().enabled {
+            return Ok("Goal progress inference is disabled".to_string());
+        }
+
+        let since = since.unwrap_or_else(|| chrono::Utc::now().timestamp_millis() - 24 * 60 * MINUTE_MS);
+        let report = self.goal_tracker.infer_progress(since).await?;
+        Ok(format!(
+            "Recorded {} goal evidence entries from {} commits, {} tasks and {} conversations",
+            report.evidence.len(),
+            report.commits_checked,
+            report.tasks_checked,
+            report.conversations_checked
+        ))
+    }
+}
+
+/// Deliver the weekly review once the configured day and hour have come
+pub struct WeeklyReviewJob {
+    weekly_review: Arc<WeeklyReviewService>,
+}
+
+impl WeeklyReviewJob {
+    pub fn new(weekly_review: Arc<WeeklyReviewService>) -> Self {
+        Self { weekly_review }
+    }
+}
+
+#[async_trait]
+impl BackgroundJob for WeeklyReviewJob {
+    fn id(&self) -> &'static str {
+        "weekly_review"
+    }
+
+    fn default_schedule(&self) -> JobSchedule {
+        JobSchedule {
+            interval_minutes: 60,
+            jitter_minutes: 5,
+        }
+    }
+
+    async fn run(&self, _since: Option<i64>) -> Result<String> {
+        match self.weekly_review.deliver_if_due().await? {
+            Some(report) => Ok(format!("Delivered weekly review for week of {}", report.week_start)),
+            None => Ok("Weekly review not due".to_string()),
+        }
+    }
+}
+
+/// Title untitled conversations and assign topic labels once they have a few turns
+pub struct ConversationTopicsJob {
+    topics: Arc<ConversationTopicsService>,
+}
+
+impl ConversationTopicsJob {
+    pub fn new(topics: Arc<ConversationTopicsService>) -> Self {
+        Self { topics }
+    }
+}
+
+#[async_trait]
+impl BackgroundJob for ConversationTopicsJob {
+    fn id(&self) -> &'static str {
+        "conversation_topics"
+    }
+
+    fn default_schedule(&self) -> JobSchedule {
+        JobSchedule {
+            interval_minutes: 15,
+            jitter_minutes: 3,
+        }
+    }
+
+    async fn run(&self, _since: Option<i64>) -> Result<String> {
+        let report = self.topics.label_pending(DEFAULT_BATCH_SIZE).await?;
+        if report.failed > 0 && report.labeled == 0 {
+            return Err(anyhow!("Failed to label {} conversations", report.failed));
+        }
+        Ok(format!(
+            "Labeled {} conversations ({} titled, {} failed)",
+            report.labeled, report.titled, report.failed
+        ))
+    }
+}
+
+/// Summarize idle conversations into durable memories and drop the raw
+/// episodes of old digested ones
+pub struct ConversationDigestJob {
+    digests: Arc<ConversationDigestService>,
+}
+
+impl ConversationDigestJob {
+    pub fn new(digests: Arc<ConversationDigestService>) -> Self {
+        Self { digests }
+    }
+}
+
+#[async_trait]
+impl BackgroundJob for ConversationDigestJob {
+    fn id(&self) -> &'static str {
+        "conversation_digest"
+    }
+
+    fn default_schedule(&self) -> JobSchedule {
+        JobSchedule {
+            interval_minutes: 30,
+            jitter_minutes: 5,
+        }
+    }
+
+    async fn run(&self, _since: Option<i64>) -> Result<String> {
+        let report = self.digests.digest_pending(conversation_digest::DEFAULT_BATCH_SIZE).await?;
+        if report.failed > 0 && report.digested == 0 && report.compacted == 0 {
+            return Err(anyhow!("Failed to digest {} conversations", report.failed));
+        }
+        Ok(format!(
+            "Digested {} conversations, replaced raw episodes of {} ({} removed, {} failed)",
+            report.digested, report.compacted, report.episodes_removed, report.failed
+        ))
+    }
+}
+
+/// Retrain hybrid-search weights and the relevance threshold from rated responses
+#[cfg(feature = "lancedb-support")]
+pub struct RetrievalTuningJob {
+    db: Arc<Mutex<Database>>,
+    rag: Arc<RagServiceV2>,
+    hybrid_search: Arc<tokio::sync::Mutex<HybridSearchEngine>>,
+}
+
+#[cfg(feature = "lancedb-support")]
+impl RetrievalTuningJob {
+    pub fn new(
+        db: Arc<Mutex<Database>>,
+        rag: Arc<RagServiceV2>,
+        hybrid_search: Arc<tokio::sync::Mutex<HybridSearchEngine>>,
+    ) -> Self {
+        Self { db, rag, hybrid_search }
+    }
+}
+
+#[cfg(feature = "lancedb-support")]
+#[async_trait]
+impl BackgroundJob for RetrievalTuningJob {
+    fn id(&self) -> &'static str {
+        "retrieval_tuning"
+    }
+
+    fn default_schedule(&self) -> JobSchedule {
+        JobSchedule {
+            interval_minutes: 24 * 60,
+            jitter_minutes: 60,
+        }
+    }
+
+    async fn run(&self, _since: Option<i64>) -> Result<String> {
+        let run = retrieval_feedback::retrain(&self.db, &self.rag, &self.hybrid_search).await?;
+        Ok(format!(
+            "Retrained on {} rated turns (precision {:.2}, MRR {:.2}; threshold {}, weights {})",
+            run.rated_turns,
+            run.precision,
+            run.mrr,
+            if run.relevance_threshold.is_some() { "updated" } else { "kept" },
+            if run.weights.is_some() { "updated" } else { "kept" },
+        ))
+    }
+}
+
+/// Run user scripts whose schedule is due
+pub struct ScheduledScriptsJob {
+    scripts: Arc<ScriptingService>,
+}
+
+impl ScheduledScriptsJob {
+    pub fn new(scripts: Arc<ScriptingService>) -> Self {
+        Self { scripts }
+    }
+}
+
+#[async_trait]
+impl BackgroundJob for ScheduledScriptsJob {
+    fn id(&self) -> &'static str {
+        "scheduled_scripts"
+    }
+
+    // Scripts carry their own intervals; this only checks which are due
+    fn default_schedule(&self) -> JobSchedule {
+        JobSchedule {
+            interval_minutes: 1,
+            jitter_minutes: 0,
+        }
+    }
+
+    async fn run(&self, _since: Option<i64>) -> Result<String> {
+        let due = self.scripts.due_scheduled(chrono::Utc::now().timestamp_millis())?;
+        let mut failed = 0;
+        for id in &due {
+            match self.scripts.run_async(id.clone(), None).await {
+                Ok(run) if run.success => {}
+                Ok(_) => failed += 1,
+                Err(e) => {
+                    log::warn!("Failed to run scheduled script {}: {}", id, e);
+                    failed += 1;
+                }
+            }
+        }
+        Ok(format!("Ran {} scheduled scripts ({} failed)", due.len(), failed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOURLY: JobSchedule = JobSchedule {
+        interval_minutes: 60,
+        jitter_minutes: 5,
+    };
+
+    #[test]
+    fn test_first_run_honors_startup_delay() {
+        let now = 1_000_000_000;
+        let startup = now + STARTUP_DELAY_MINUTES as i64 * MINUTE_MS;
+
+        // Never run before: after the startup delay plus jitter
+        assert_eq!(first_run_at(now, None, HOURLY, 0), startup);
+        assert_eq!(first_run_at(now, None, HOURLY, 1234), startup + 1234);
+
+        // Overdue: still waits for the startup delay
+        assert_eq!(first_run_at(now, Some(now - 5 * 60 * MINUTE_MS), HOURLY, 0), startup);
+
+        // Ran recently: keeps its schedule
+        let last = now - 10 * MINUTE_MS;
+        assert_eq!(first_run_at(now, Some(last), HOURLY, 0), last + 60 * MINUTE_MS);
+
+        assert_eq!(next_run_after(now, HOURLY, 42), now + 60 * MINUTE_MS + 42);
+    }
+
+    #[test]
+    fn test_random_jitter_bounds() {
+        assert_eq!(random_jitter_ms(0), 0);
+        for _ in 0..100 {
+            let jitter = random_jitter_ms(5);
+            assert!((0..=5 * MINUTE_MS).contains(&jitter));
+        }
+    }
+}
@@ -0,0 +1,565 @@
+//! Backup Service (v3.9.0)
+//!
+//! Automatic local backups of the active profile's stores, with verified restore.
+//!
+//! Features:
+//! - Consistent snapshots of data.db and knowledge_graph.db via `VACUUM INTO`
+//!   (safe while the app is running, keeps SQLCipher encryption)
+//! - Copy of the LanceDB directory
+//! - `manifest.json` with a SHA-256 checksum for every file
+//! - Scheduled backups with rotation (newest `keep` backups are kept)
+//! - Restore verifies checksums and `PRAGMA integrity_check` before touching
+//!   live data, and snapshots the current state first
+
+#![allow(dead_code)]  // Phase 5: Backups (config tuning used by future settings UI)
+
+use crate::services::encryption;
+use crate::services::profile::ProfileService;
+use anyhow::{anyhow, Context, Result};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const BACKUP_DIR: &str = "backups";
+const MANIFEST_FILE: &str = "manifest.json";
+const DB_FILE: &str = "data.db";
+const GRAPH_FILE: &str = "knowledge_graph.db";
+const LANCE_DIR: &str = "lance_db";
+
+/// Suffix of a backup that is still being written (never listed or restored)
+const PARTIAL_SUFFIX: &str = ".partial";
+
+/// How often the scheduler checks whether a backup is due
+const SCHEDULER_CHECK_SECS: u64 = 15 * 60;
+
+/// Backup schedule and rotation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupConfig {
+    pub interval_hours: u64,
+    /// Number of backups kept per profile
+    pub keep: usize,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            interval_hours: 24,
+            keep: 7,
+        }
+    }
+}
+
+/// What triggered a backup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupKind {
+    Scheduled,
+    Manual,
+    /// Taken automatically right before a restore
+    PreRestore,
+}
+
+/// A file inside a backup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupFile {
+    /// Path relative to the backup directory ("lance_db/episodes.lance/...")
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// Contents of `manifest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub id: String,
+    pub profile_id: String,
+    pub kind: BackupKind,
+    pub created_at: i64, // Unix millis
+    pub app_version: String,
+    pub encrypted: bool,
+    pub files: Vec<BackupFile>,
+}
+
+/// Summary returned by `backup_list`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupInfo {
+    pub id: String,
+    pub profile_id: String,
+    pub kind: BackupKind,
+    pub created_at: i64,
+    pub size_bytes: u64,
+    pub file_count: usize,
+}
+
+impl From<&BackupManifest> for BackupInfo {
+    fn from(manifest: &BackupManifest) -> Self {
+        Self {
+            id: manifest.id.clone(),
+            profile_id: manifest.profile_id.clone(),
+            kind: manifest.kind,
+            created_at: manifest.created_at,
+            size_bytes: manifest.files.iter().map(|f| f.size).sum(),
+            file_count: manifest.files.len(),
+        }
+    }
+}
+
+/// Sortable backup id ("20261014-093000-123")
+pub fn backup_id(now: chrono::DateTime<chrono::Utc>) -> String {
+    now.format("%Y%m%d-%H%M%S-%3f").to_string()
+}
+
+/// Backup ids are generated; anything else could point outside the backup directory
+pub fn validate_backup_id(id: &str) -> Result<()> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit() || c == '-') {
+        return Err(anyhow!("Invalid backup id: {}", id));
+    }
+    Ok(())
+}
+
+/// Ids of the backups that fall outside the newest `keep`
+pub fn select_for_rotation(manifests: &[BackupManifest], keep: usize) -> Vec<String> {
+    let mut sorted: Vec<&BackupManifest> = manifests.iter().collect();
+    sorted.sort_by_key(|m| std::cmp::Reverse(m.created_at));
+    sorted.into_iter().skip(keep.max(1)).map(|m| m.id.clone()).collect()
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Checksum every file under a backup directory (except the manifest)
+fn collect_files(dir: &Path) -> Result<Vec<BackupFile>> {
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(dir)?;
+        if relative == Path::new(MANIFEST_FILE) {
+            continue;
+        }
+        files.push(BackupFile {
+            path: relative.to_string_lossy().replace('\\', "/"),
+            size: entry.metadata()?.len(),
+            sha256: sha256_file(entry.path())?,
+        });
+    }
+    Ok(files)
+}
+
+/// Check that every file in the manifest is present and unchanged
+pub fn verify_checksums(dir: &Path, manifest: &BackupManifest) -> Result<()> {
+    for file in &manifest.files {
+        let path = dir.join(&file.path);
+        if !path.is_file() {
+            return Err(anyhow!("Backup is missing {}", file.path));
+        }
+        if sha256_file(&path)? != file.sha256 {
+            return Err(anyhow!("Backup file {} is corrupted (checksum mismatch)", file.path));
+        }
+    }
+    Ok(())
+}
+
+/// Consistent copy of a live SQLite database
+fn snapshot_sqlite(source: &Path, dest: &Path) -> Result<()> {
+    let conn = encryption::open_connection(source)
+        .with_context(|| format!("Failed to open {}", source.display()))?;
+    conn.execute("VACUUM INTO ?1", params![dest.to_string_lossy()])
+        .with_context(|| format!("Failed to snapshot {}", source.display()))?;
+    Ok(())
+}
+
+/// `PRAGMA integrity_check` (also fails when the file can't be read with the current key)
+fn integrity_check(path: &Path) -> Result<()> {
+    let conn = encryption::open_connection(path)?;
+    let result: String = conn
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .map_err(|e| anyhow!("{} is unreadable: {}", path.display(), e))?;
+    if result != "ok" {
+        return Err(anyhow!("{} failed integrity check: {}", path.display(), result));
+    }
+    Ok(())
+}
+
+fn copy_dir(source: &Path, dest: &Path) -> Result<()> {
+    for entry in walkdir::WalkDir::new(source) {
+        let entry = entry?;
+        let target = dest.join(entry.path().strip_prefix(source)?);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else if entry.file_type().is_file() {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Replace a live SQLite file with a staged copy
+///
+/// Leftover journal files belong to the old database and would corrupt the new one.
+fn swap_sqlite(staged: &Path, live: &Path) -> Result<()> {
+    fs::rename(staged, live)?;
+    for suffix in ["-journal", "-wal", "-shm"] {
+        let mut sidecar = live.as_os_str().to_owned();
+        sidecar.push(suffix);
+        let sidecar = PathBuf::from(sidecar);
+        if sidecar.exists() {
+            fs::remove_file(&sidecar)?;
+        }
+    }
+    Ok(())
+}
+
+fn swap_dir(staged: &Path, live: &Path) -> Result<()> {
+    let old = live.with_extension("old");
+    if old.exists() {
+        fs::remove_dir_all(&old)?;
+    }
+    if live.exists() {
+        fs::rename(live, &old)?;
+    }
+    fs::rename(staged, live)?;
+    if old.exists() {
+        fs::remove_dir_all(&old)?;
+    }
+    Ok(())
+}
+
+fn staged_path(live: &Path) -> PathBuf {
+    let mut staged = live.as_os_str().to_owned();
+    staged.push(".restore-tmp");
+    PathBuf::from(staged)
+}
+
+/// Backup service
+pub struct BackupService {
+    data_dir: PathBuf,
+    profiles: Arc<ProfileService>,
+    config: BackupConfig,
+    /// Serializes backups and restores
+    busy: Mutex<()>,
+    running: AtomicBool,
+}
+
+impl BackupService {
+    pub fn new(data_dir: PathBuf, profiles: Arc<ProfileService>, config: BackupConfig) -> Result<Self> {
+        fs::create_dir_all(data_dir.join(BACKUP_DIR))?;
+
+        log::info!(
+            "✓ Backup Service initialized (every {}h, keep {})",
+            config.interval_hours,
+            config.keep
+        );
+        Ok(Self {
+            data_dir,
+            profiles,
+            config,
+            busy: Mutex::new(()),
+            running: AtomicBool::new(false),
+        })
+    }
+
+    fn profile_dir(&self, profile_id: &str) -> PathBuf {
+        self.data_dir.join(BACKUP_DIR).join(profile_id)
+    }
+
+    /// Back up the active profile now
+    pub fn create_backup(&self, kind: BackupKind) -> Result<BackupManifest> {
+        let _busy = self.busy.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        self.create_backup_locked(kind)
+    }
+
+    fn create_backup_locked(&self, kind: BackupKind) -> Result<BackupManifest> {
+        if encryption::is_locked() {
+            return Err(anyhow!("Storage is locked"));
+        }
+
+        let profile = self.profiles.active_profile()?;
+        let paths = self.profiles.paths(&profile.id);
+        let id = backup_id(chrono::Utc::now());
+
+        // Written under a temporary name so an interrupted backup never shows up
+        let root = self.profile_dir(&profile.id);
+        let partial = root.join(format!("{}{}", id, PARTIAL_SUFFIX));
+        fs::create_dir_all(&partial)?;
+
+        let result = (|| -> Result<BackupManifest> {
+            if paths.db.exists() {
+                snapshot_sqlite(&paths.db, &partial.join(DB_FILE))?;
+            }
+            if paths.knowledge_graph.exists() {
+                snapshot_sqlite(&paths.knowledge_graph, &partial.join(GRAPH_FILE))?;
+            }
+            if paths.lance_db.is_dir() {
+                copy_dir(&paths.lance_db, &partial.join(LANCE_DIR))?;
+            }
+
+            let manifest = BackupManifest {
+                id: id.clone(),
+                profile_id: profile.id.clone(),
+                kind,
+                created_at: chrono::Utc::now().timestamp_millis(),
+                app_version: env!("CARGO_PKG_VERSION").to_string(),
+                encrypted: encryption::has_key(),
+                files: collect_files(&partial)?,
+            };
+            fs::write(partial.join(MANIFEST_FILE), serde_json::to_string_pretty(&manifest)?)?;
+            fs::rename(&partial, root.join(&id))?;
+            Ok(manifest)
+        })();
+
+        let manifest = match result {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                let _ = fs::remove_dir_all(&partial);
+                return Err(e);
+            }
+        };
+
+        match self.rotate(&profile.id) {
+            Ok(0) => {}
+            Ok(removed) => log::info!("Rotated out {} old backups", removed),
+            Err(e) => log::warn!("Failed to rotate backups: {}", e),
+        }
+
+        log::info!(
+            "✓ Backup {} created ({:?}, {} files)",
+            manifest.id,
+            manifest.kind,
+            manifest.files.len()
+        );
+        Ok(manifest)
+    }
+
+    fn load_manifests(&self, profile_id: &str) -> Result<Vec<BackupManifest>> {
+        let mut manifests = Vec::new();
+
+        if let Ok(entries) = fs::read_dir(self.profile_dir(profile_id)) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.to_string_lossy().ends_with(PARTIAL_SUFFIX) {
+                    continue;
+                }
+                if let Ok(json) = fs::read_to_string(path.join(MANIFEST_FILE)) {
+                    if let Ok(manifest) = serde_json::from_str::<BackupManifest>(&json) {
+                        manifests.push(manifest);
+                    }
+                }
+            }
+        }
+
+        manifests.sort_by_key(|m| std::cmp::Reverse(m.created_at));
+        Ok(manifests)
+    }
+
+    /// Delete backups beyond the newest `keep`
+    fn rotate(&self, profile_id: &str) -> Result<usize> {
+        let manifests = self.load_manifests(profile_id)?;
+        let expired = select_for_rotation(&manifests, self.config.keep);
+        for id in &expired {
+            fs::remove_dir_all(self.profile_dir(profile_id).join(id))?;
+        }
+        Ok(expired.len())
+    }
+
+    /// Backups of the active profile, newest first
+    pub fn list_backups(&self) -> Result<Vec<BackupInfo>> {
+        let profile = self.profiles.active_profile()?;
+        Ok(self.load_manifests(&profile.id)?.iter().map(BackupInfo::from).collect())
+    }
+
+    /// Check a backup of the active profile without restoring it
+    pub fn verify_backup(&self, id: &str) -> Result<BackupManifest> {
+        validate_backup_id(id)?;
+        let profile = self.profiles.active_profile()?;
+        let dir = self.profile_dir(&profile.id).join(id);
+
+        let json = fs::read_to_string(dir.join(MANIFEST_FILE))
+            .map_err(|_| anyhow!("Backup not found: {}", id))?;
+        let manifest: BackupManifest = serde_json::from_str(&json).context("Backup manifest is corrupted")?;
+        if manifest.profile_id != profile.id {
+            return Err(anyhow!("Backup {} belongs to profile {}", id, manifest.profile_id));
+        }
+
+        verify_checksums(&dir, &manifest)?;
+        for file in [DB_FILE, GRAPH_FILE] {
+            let path = dir.join(file);
+            if path.exists() {
+                integrity_check(&path)?;
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// Restore a verified backup over the active profile's stores
+    ///
+    /// Stores missing from the backup are left untouched. The caller must reopen
+    /// every handle on the profile afterwards (`ProfileService::reload_active`).
+    pub fn restore_backup(&self, id: &str) -> Result<BackupManifest> {
+        let _busy = self.busy.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        if encryption::is_locked() {
+            return Err(anyhow!("Storage is locked"));
+        }
+
+        let manifest = self.verify_backup(id)?;
+        let dir = self.profile_dir(&manifest.profile_id).join(id);
+        let paths = self.profiles.paths(&manifest.profile_id);
+
+        let stores = [
+            (dir.join(DB_FILE), paths.db.clone()),
+            (dir.join(GRAPH_FILE), paths.knowledge_graph.clone()),
+            (dir.join(LANCE_DIR), paths.lance_db.clone()),
+        ];
+
+        // Stage first: the safety backup below may rotate this backup away
+        for (source, live) in &stores {
+            let staged = staged_path(live);
+            if staged.is_dir() {
+                fs::remove_dir_all(&staged)?;
+            } else if staged.exists() {
+                fs::remove_file(&staged)?;
+            }
+            if source.is_dir() {
+                copy_dir(source, &staged)?;
+            } else if source.is_file() {
+                fs::copy(source, &staged)?;
+            }
+        }
+
+        // Best effort: a damaged live database is often the reason for restoring
+        if let Err(e) = self.create_backup_locked(BackupKind::PreRestore) {
+            log::warn!("Failed to back up current data before restore: {}", e);
+        }
+
+        for (source, live) in &stores {
+            let staged = staged_path(live);
+            if source.is_dir() {
+                swap_dir(&staged, live)?;
+            } else if source.is_file() {
+                swap_sqlite(&staged, live)?;
+            }
+        }
+
+        log::info!("✓ Restored backup {}", id);
+        Ok(manifest)
+    }
+
+    fn is_due(&self) -> Result<bool> {
+        let profile = self.profiles.active_profile()?;
+        let latest = self.load_manifests(&profile.id)?.first().map(|m| m.created_at);
+        let interval_ms = (self.config.interval_hours.max(1) * 60 * 60 * 1000) as i64;
+        Ok(latest.is_none_or(|at| chrono::Utc::now().timestamp_millis() - at >= interval_ms))
+    }
+
+    /// Start the scheduled backup loop
+    pub fn start(self: &Arc<Self>) {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return; // Already running
+        }
+
+        let service = Arc::clone(self);
+        tauri::async_runtime::spawn(async move {
+            log::info!("Backup scheduler started");
+
+            while service.running.load(Ordering::SeqCst) {
+                if !encryption::is_locked() && service.is_due().unwrap_or(false) {
+                    let worker = Arc::clone(&service);
+                    match tokio::task::spawn_blocking(move || worker.create_backup(BackupKind::Scheduled)).await {
+                        Ok(Ok(_)) => {}
+                        Ok(Err(e)) => log::warn!("Scheduled backup failed: {}", e),
+                        Err(e) => log::warn!("Scheduled backup task failed: {}", e),
+                    }
+                }
+                tokio::time::sleep(Duration::from_secs(SCHEDULER_CHECK_SECS)).await;
+            }
+
+            log::info!("Backup scheduler stopped");
+        });
+    }
+
+    /// Stop the scheduled backup loop (takes effect after the current check)
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(id: &str, created_at: i64) -> BackupManifest {
+        BackupManifest {
+            id: id.to_string(),
+            profile_id: "default".to_string(),
+            kind: BackupKind::Scheduled,
+            created_at,
+            app_version: "test".to_string(),
+            encrypted: false,
+            files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_backup_ids_and_rotation() {
+        let id = backup_id(chrono::DateTime::from_timestamp_millis(1_760_434_200_123).unwrap());
+        assert_eq!(id, "20251014-093000-123");
+        assert!(validate_backup_id(&id).is_ok());
+        assert!(validate_backup_id("../default").is_err());
+        assert!(validate_backup_id("").is_err());
+
+        let manifests = vec![manifest("b", 2), manifest("d", 4), manifest("a", 1), manifest("c", 3)];
+        assert_eq!(select_for_rotation(&manifests, 2), vec!["b", "a"]);
+        assert!(select_for_rotation(&manifests, 10).is_empty());
+        // Never rotate away the only backup
+        assert_eq!(select_for_rotation(&manifests, 0).len(), 3);
+    }
+
+    #[test]
+    fn test_snapshot_verify_and_detect_corruption() {
+        let dir = tempfile::tempdir().unwrap();
+        let live = dir.path().join("live.db");
+        {
+            let conn = rusqlite::Connection::open(&live).unwrap();
+            conn.execute_batch("CREATE TABLE notes (body TEXT); INSERT INTO notes VALUES ('hello');")
+                .unwrap();
+        }
+
+        let backup = dir.path().join("backup");
+        fs::create_dir_all(backup.join(LANCE_DIR)).unwrap();
+        snapshot_sqlite(&live, &backup.join(DB_FILE)).unwrap();
+        fs::write(backup.join(LANCE_DIR).join("episodes.lance"), b"vectors").unwrap();
+        integrity_check(&backup.join(DB_FILE)).unwrap();
+
+        let mut m = manifest("1", 1);
+        m.files = collect_files(&backup).unwrap();
+        assert_eq!(m.files.len(), 2);
+        assert!(m.files.iter().any(|f| f.path == "lance_db/episodes.lance"));
+        verify_checksums(&backup, &m).unwrap();
+
+        fs::write(backup.join(LANCE_DIR).join("episodes.lance"), b"tampered").unwrap();
+        assert!(verify_checksums(&backup, &m).is_err());
+
+        fs::write(backup.join(DB_FILE), b"not a database at all").unwrap();
+        assert!(integrity_check(&backup.join(DB_FILE)).is_err());
+    }
+}
@@ -0,0 +1,248 @@
+//! System Benchmark Service (v3.9.0)
+//!
+//! End-to-end latency budgets measured on the user's machine.
+//!
+//! Features:
+//! - Cold start (service initialization) time of the current launch
+//! - Embedding throughput, RAG retrieval and tool round-trip P50/P95
+//! - First-token latency of the chat model
+//! - Results stored historically in `system_benchmarks`, with regressions
+//!   flagged against the previous run (e.g. after an update)
+
+#![allow(dead_code)]  // Phase 5: System benchmark
+
+use crate::database::Database;
+use crate::services::analytics::percentile;
+use anyhow::{anyhow, Result};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+/// Metrics more than this much worse than the previous run are flagged
+const REGRESSION_TOLERANCE: f64 = 0.2;
+
+/// Regressions smaller than this (ms) are noise
+const MIN_REGRESSION_MS: f64 = 5.0;
+
+/// Service initialization time of this launch
+static COLD_START_MS: OnceLock<u64> = OnceLock::new();
+
+/// Remember how long startup took (called once from main)
+pub fn record_cold_start(duration: Duration) {
+    let _ = COLD_START_MS.set(duration.as_millis() as u64);
+}
+
+pub fn cold_start_ms() -> Option<u64> {
+    COLD_START_MS.get().copied()
+}
+
+/// Latency distribution of one stage
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LatencySummary {
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub mean_ms: f64,
+    pub samples: usize,
+}
+
+impl LatencySummary {
+    pub fn from_samples(samples: &[u64]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        Self {
+            p50_ms: percentile(&sorted, 50.0),
+            p95_ms: percentile(&sorted, 95.0),
+            mean_ms: if sorted.is_empty() { 0.0 } else { sorted.iter().sum::<u64>() as f64 / sorted.len() as f64 },
+            samples: sorted.len(),
+        }
+    }
+}
+
+/// Metric that got worse since the previous run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkRegression {
+    pub metric: String,
+    pub previous: f64,
+    pub current: f64,
+    /// Relative change, positive is worse
+    pub change: f64,
+}
+
+/// One benchmark run; stages that failed are None and listed in `errors`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    pub id: String,
+    pub created_at: i64, // Unix millis
+    pub app_version: String,
+    pub platform: String,
+    pub cold_start_ms: Option<u64>,
+    /// Embeddings per second
+    pub embedding_throughput: Option<f64>,
+    pub rag_retrieval: Option<LatencySummary>,
+    pub first_token_ms: Option<u64>,
+    pub full_response_ms: Option<u64>,
+    pub tool_round_trip: Option<LatencySummary>,
+    pub errors: Vec<String>,
+    /// Filled when the run is recorded
+    #[serde(default)]
+    pub regressions: Vec<BenchmarkRegression>,
+}
+
+impl BenchmarkResult {
+    pub fn new() -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            created_at: chrono::Utc::now().timestamp_millis(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            platform: format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH),
+            cold_start_ms: cold_start_ms(),
+            ..Default::default()
+        }
+    }
+
+    /// Lower-is-better metrics by name (throughput is inverted to ms per embedding)
+    fn latency_metrics(&self) -> Vec<(&'static str, f64)> {
+        let mut metrics = Vec::new();
+        if let Some(ms) = self.cold_start_ms {
+            metrics.push(("cold_start_ms", ms as f64));
+        }
+        if let Some(per_sec) = self.embedding_throughput.filter(|t| *t > 0.0) {
+            metrics.push(("embedding_ms", 1000.0 / per_sec));
+        }
+        if let Some(rag) = &self.rag_retrieval {
+            metrics.push(("rag_p50_ms", rag.p50_ms as f64));
+            metrics.push(("rag_p95_ms", rag.p95_ms as f64));
+        }
+        if let Some(ms) = self.first_token_ms {
+            metrics.push(("first_token_ms", ms as f64));
+        }
+        if let Some(tools) = &self.tool_round_trip {
+            metrics.push(("tool_p50_ms", tools.p50_ms as f64));
+            metrics.push(("tool_p95_ms", tools.p95_ms as f64));
+        }
+        metrics
+    }
+}
+
+/// Metrics of `current` that are noticeably worse than in `previous`
+pub fn find_regressions(current: &BenchmarkResult, previous: &BenchmarkResult) -> Vec<BenchmarkRegression> {
+    let before = previous.latency_metrics();
+    current
+        .latency_metrics()
+        .into_iter()
+        .filter_map(|(metric, now)| {
+            let (_, then) = before.iter().find(|(name, _)| *name == metric)?;
+            let change = if *then > 0.0 { (now - then) / then } else { 0.0 };
+            (change > REGRESSION_TOLERANCE && now - then >= MIN_REGRESSION_MS).then(|| BenchmarkRegression {
+                metric: metric.to_string(),
+                previous: *then,
+                current: now,
+                change,
+            })
+        })
+        .collect()
+}
+
+/// Benchmark history service
+pub struct BenchmarkService {
+    db: Arc<Mutex<Database>>,
+}
+
+impl BenchmarkService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        let service = Self { db };
+        service.init_database()?;
+        log::info!("✓ Benchmark Service initialized");
+        Ok(service)
+    }
+
+    fn init_database(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        db.conn().execute(
+            "CREATE TABLE IF NOT EXISTS system_benchmarks (
+                id TEXT PRIMARY KEY,
+                app_version TEXT NOT NULL,
+                result TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Store a run, comparing it with the previous one
+    pub fn record(&self, mut result: BenchmarkResult) -> Result<BenchmarkResult> {
+        if let Some(previous) = self.latest()? {
+            result.regressions = find_regressions(&result, &previous);
+        }
+        for regression in &result.regressions {
+            log::warn!(
+                "Benchmark regression: {} {:.0} → {:.0} ({:+.0}%)",
+                regression.metric, regression.previous, regression.current, regression.change * 100.0
+            );
+        }
+
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        db.conn().execute(
+            "INSERT INTO system_benchmarks (id, app_version, result, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![result.id, result.app_version, serde_json::to_string(&result)?, result.created_at],
+        )?;
+        Ok(result)
+    }
+
+    /// Most recent run
+    pub fn latest(&self) -> Result<Option<BenchmarkResult>> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let json: Option<String> = db
+            .conn()
+            .query_row("SELECT result FROM system_benchmarks ORDER BY created_at DESC LIMIT 1", [], |row| row.get(0))
+            .optional()?;
+        Ok(json.and_then(|json| serde_json::from_str(&json).ok()))
+    }
+
+    /// Past runs, newest first
+    pub fn history(&self, limit: usize) -> Result<Vec<BenchmarkResult>> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let mut stmt = db
+            .conn()
+            .prepare("SELECT result FROM system_benchmarks ORDER BY created_at DESC LIMIT ?1")?;
+        let rows = stmt
+            .query_map([limit as i64], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows.iter().filter_map(|json| serde_json::from_str(json).ok()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_summary() {
+        let summary = LatencySummary::from_samples(&[30, 10, 20, 40, 100]);
+        assert_eq!(summary.p50_ms, 30);
+        assert_eq!(summary.p95_ms, 100);
+        assert_eq!(summary.mean_ms, 40.0);
+        assert_eq!(LatencySummary::from_samples(&[]).samples, 0);
+    }
+
+    #[test]
+    fn test_find_regressions() {
+        let previous = BenchmarkResult {
+            embedding_throughput: Some(50.0),
+            rag_retrieval: Some(LatencySummary { p50_ms: 20, p95_ms: 40, mean_ms: 25.0, samples: 10 }),
+            first_token_ms: Some(800),
+            ..Default::default()
+        };
+        let current = BenchmarkResult {
+            embedding_throughput: Some(25.0),
+            rag_retrieval: Some(LatencySummary { p50_ms: 22, p95_ms: 90, mean_ms: 30.0, samples: 10 }),
+            first_token_ms: Some(700),
+            ..Default::default()
+        };
+
+        let metrics: Vec<String> = find_regressions(&current, &previous).into_iter().map(|r| r.metric).collect();
+        assert_eq!(metrics, vec!["embedding_ms", "rag_p95_ms"]);
+    }
+}
@@ -9,17 +9,169 @@
 //! - f(qi, D) = term frequency in document
 //! - |D| = document length
 //! - avgdl = average document length
-//! - IDF(qi) = log((N - df(qi) + 0.5) / (df(qi) + 0.5))
+//! - IDF(qi) = log(1 + (N - df(qi) + 0.5) / (df(qi) + 0.5))  (never negative)
 //! - k1 = 1.5 (term frequency saturation)
 //! - b = 0.75 (length normalization)
+//!
+//! v3.9.0:
+//! - Incremental add / remove; document frequencies and avgdl are kept up to date
+//! - Postings persisted in the profile database and reconciled with episodic memory on load
+//! - Pluggable tokenizer; the Korean analyzer strips particles and indexes syllable bigrams
 
 #![allow(dead_code)]  // Phase 13: Hybrid search (LanceDB feature)
 
 use log::{debug, info};
-use rusqlite::Connection;
-use std::collections::HashMap;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use unicode_segmentation::UnicodeSegmentation;
 
+/// user_preferences key holding the selected tokenizer
+pub const TOKENIZER_PREFERENCE_KEY: &str = "bm25_tokenizer";
+
+/// Splits text into index terms; queries and documents go through the same tokenizer
+pub trait Tokenizer: Send + Sync {
+    /// Stored with persisted postings, so an index built by another tokenizer is rebuilt
+    fn name(&self) -> &'static str;
+
+    fn tokenize(&self, text: &str) -> Vec<String>;
+}
+
+/// Lowercased Unicode words (UAX #29)
+pub struct UnicodeTokenizer;
+
+impl Tokenizer for UnicodeTokenizer {
+    fn name(&self) -> &'static str {
+        "unicode"
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        text.to_lowercase()
+            .unicode_words()
+            .map(|s| s.to_string())
+            .collect()
+    }
+}
+
+/// Korean-aware analyzer
+///
+/// Korean attaches particles and endings to words ("서울에서", "서울은"), so whole
+/// words rarely match. Hangul runs lose a trailing particle and are indexed as
+/// syllable bigrams; other text is tokenized like `UnicodeTokenizer`.
+pub struct KoreanTokenizer;
+
+/// Common particles, longest first
+const KOREAN_PARTICLES: &[&str] = &[
+    "에서는", "에게서", "으로는", "이라고", "에서", "에게", "한테", "으로", "까지", "부터", "처럼", "보다",
+    "하고", "이나", "이랑", "은", "는", "이", "가", "을", "를", "에", "의", "로", "와", "과", "도", "만",
+];
+
+/// Hangul syllables and jamo
+pub fn is_hangul(c: char) -> bool {
+    matches!(c, '\u{AC00}'..='\u{D7A3}' | '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}')
+}
+
+/// `word` without a trailing particle, keeping at least two syllables
+fn strip_particle(word: &str) -> &str {
+    for particle in KOREAN_PARTICLES {
+        if let Some(stem) = word.strip_suffix(particle) {
+            if stem.chars().count() >= 2 {
+                return stem;
+            }
+        }
+    }
+    word
+}
+
+impl KoreanTokenizer {
+    fn push_hangul(run: &str, after_other_script: bool, tokens: &mut Vec<String>) {
+        // "Rust는": the Hangul part is only a particle
+        if after_other_script && KOREAN_PARTICLES.contains(&run) {
+            return;
+        }
+        let stem: Vec<char> = strip_particle(run).chars().collect();
+        if stem.len() <= 2 {
+            tokens.push(stem.into_iter().collect());
+        } else {
+            tokens.extend(stem.windows(2).map(|pair| pair.iter().collect::<String>()));
+        }
+    }
+}
+
+impl Tokenizer for KoreanTokenizer {
+    fn name(&self) -> &'static str {
+        "korean_bigram"
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        let mut tokens = Vec::new();
+        for word in text.to_lowercase().unicode_words() {
+            let mut start = 0;
+            let mut chars = word.char_indices().peekable();
+            while let Some((_, c)) = chars.next() {
+                let hangul = is_hangul(c);
+                let boundary = match chars.peek() {
+                    Some(&(next_index, next)) if is_hangul(next) != hangul => Some(next_index),
+                    Some(_) => None,
+                    None => Some(word.len()),
+                };
+                if let Some(end) = boundary {
+                    let run = &word[start..end];
+                    if hangul {
+                        Self::push_hangul(run, start > 0, &mut tokens);
+                    } else {
+                        tokens.push(run.to_string());
+                    }
+                    start = end;
+                }
+            }
+        }
+        tokens
+    }
+}
+
+/// Built-in tokenizers, selectable in settings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenizerKind {
+    Unicode,
+    /// Also handles non-Korean text, so it is the default
+    #[default]
+    Korean,
+}
+
+impl TokenizerKind {
+    pub fn tokenizer(self) -> Arc<dyn Tokenizer> {
+        match self {
+            TokenizerKind::Unicode => Arc::new(UnicodeTokenizer),
+            TokenizerKind::Korean => Arc::new(KoreanTokenizer),
+        }
+    }
+
+    /// Persisted selection of the profile, or the default
+    pub fn load(conn: &Connection) -> Self {
+        conn.query_row(
+            "SELECT value FROM user_preferences WHERE key = ?1",
+            [TOKENIZER_PREFERENCE_KEY],
+            |row| row.get::<_, String>(0),
+        )
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+    }
+
+    pub fn save(self, conn: &Connection) -> Result<(), String> {
+        let json = serde_json::to_string(&self).map_err(|e| format!("Failed to serialize tokenizer: {}", e))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+            params![TOKENIZER_PREFERENCE_KEY, json, chrono::Utc::now().timestamp_millis()],
+        )
+        .map_err(|e| format!("Failed to save tokenizer: {}", e))?;
+        Ok(())
+    }
+}
+
 /// Document representation for BM25 indexing
 #[derive(Clone, Debug)]
 pub struct Document {
@@ -37,50 +189,66 @@ pub struct ScoredDocument {
     pub content: String,
 }
 
+/// Changes applied by `BM25Index::sync_with_database`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncStats {
+    pub added: usize,
+    pub removed: usize,
+}
+
 /// BM25 Index with tunable parameters
 pub struct BM25Index {
     documents: HashMap<String, Document>,
-    idf_scores: HashMap<String, f32>,
-    avg_doc_length: f32,
     k1: f32,  // Term frequency saturation (default: 1.5)
     b: f32,   // Length normalization (default: 0.75)
-    total_docs: usize,
+    total_length: usize,  // v3.9.0: Sum of document lengths, for avgdl
     document_frequency: HashMap<String, usize>,  // Number of docs containing term
+    tokenizer: Arc<dyn Tokenizer>,  // v3.9.0: Pluggable analyzer
+}
+
+/// Text of an episode as it is indexed
+fn episode_content(user_message: &str, ai_response: &str) -> String {
+    format!("{} {}", user_message, ai_response)
 }
 
 impl BM25Index {
     /// Create a new BM25 index with default parameters
     pub fn new() -> Self {
-        BM25Index {
-            documents: HashMap::new(),
-            idf_scores: HashMap::new(),
-            avg_doc_length: 0.0,
-            k1: 1.5,
-            b: 0.75,
-            total_docs: 0,
-            document_frequency: HashMap::new(),
-        }
+        Self::with_params(1.5, 0.75)
     }
 
     /// Create a new BM25 index with custom parameters
     pub fn with_params(k1: f32, b: f32) -> Self {
         BM25Index {
             documents: HashMap::new(),
-            idf_scores: HashMap::new(),
-            avg_doc_length: 0.0,
             k1,
             b,
-            total_docs: 0,
+            total_length: 0,
             document_frequency: HashMap::new(),
+            tokenizer: TokenizerKind::default().tokenizer(),
         }
     }
 
-    /// Tokenize text into terms (lowercase, split by whitespace and punctuation)
-    fn tokenize(text: &str) -> Vec<String> {
-        text.to_lowercase()
-            .unicode_words()
-            .map(|s| s.to_string())
-            .collect()
+    /// Create a new BM25 index with another tokenizer
+    pub fn with_tokenizer(tokenizer: Arc<dyn Tokenizer>) -> Self {
+        BM25Index { tokenizer, ..Self::new() }
+    }
+
+    /// Switch tokenizer; the index is cleared since its terms no longer match
+    pub fn set_tokenizer(&mut self, tokenizer: Arc<dyn Tokenizer>) {
+        if tokenizer.name() != self.tokenizer.name() {
+            self.clear();
+        }
+        self.tokenizer = tokenizer;
+    }
+
+    pub fn tokenizer_name(&self) -> &'static str {
+        self.tokenizer.name()
+    }
+
+    /// Tokenize text into terms with the configured tokenizer
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        self.tokenizer.tokenize(text)
     }
 
     /// Compute term frequencies for a document
@@ -92,37 +260,87 @@ impl BM25Index {
         frequencies
     }
 
-    /// Add a document to the index
+    /// Add a document to the index, replacing one with the same id
     pub fn add_document(&mut self, id: String, content: String) {
-        let tokens = Self::tokenize(&content);
+        let tokens = self.tokenize(&content);
         let term_frequencies = Self::compute_term_frequencies(&tokens);
         let length = tokens.len();
+        self.insert(Document { id, content, term_frequencies, length });
+    }
+
+    fn insert(&mut self, document: Document) {
+        self.remove_document(&document.id);
 
         // Update document frequency for each unique term
-        for term in term_frequencies.keys() {
+        for term in document.term_frequencies.keys() {
             *self.document_frequency.entry(term.clone()).or_insert(0) += 1;
         }
+        self.total_length += document.length;
+        self.documents.insert(document.id.clone(), document);
+    }
 
-        let document = Document {
-            id: id.clone(),
-            content,
-            term_frequencies,
-            length,
+    /// Remove a document from the index; returns whether it was indexed
+    pub fn remove_document(&mut self, id: &str) -> bool {
+        let Some(document) = self.documents.remove(id) else {
+            return false;
         };
+        for term in document.term_frequencies.keys() {
+            if let Some(df) = self.document_frequency.get_mut(term) {
+                *df -= 1;
+                if *df == 0 {
+                    self.document_frequency.remove(term);
+                }
+            }
+        }
+        self.total_length -= document.length;
+        true
+    }
+
+    /// Average document length
+    fn avg_doc_length(&self) -> f32 {
+        if self.documents.is_empty() {
+            0.0
+        } else {
+            self.total_length as f32 / self.documents.len() as f32
+        }
+    }
 
-        self.documents.insert(id, document);
-        self.total_docs = self.documents.len();
+    /// Create the table postings are persisted in
+    pub fn init_storage(conn: &Connection) -> Result<(), String> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS bm25_postings (
+                id TEXT PRIMARY KEY,
+                tokenizer TEXT NOT NULL,
+                length INTEGER NOT NULL,
+                terms TEXT NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| format!("Failed to create BM25 table: {}", e))?;
+        Ok(())
+    }
+
+    fn persist(&self, conn: &Connection, document: &Document) -> Result<(), String> {
+        let terms = serde_json::to_string(&document.term_frequencies)
+            .map_err(|e| format!("Failed to serialize terms: {}", e))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO bm25_postings (id, tokenizer, length, terms) VALUES (?1, ?2, ?3, ?4)",
+            params![document.id, self.tokenizer.name(), document.length as i64, terms],
+        )
+        .map_err(|e| format!("Failed to persist BM25 postings: {}", e))?;
+        Ok(())
     }
 
-    /// Build index from episodic memory in database
+    /// Build index from episodic memory in database (replaces persisted postings)
     pub fn build_from_database(&mut self, conn: &Connection) -> Result<(), String> {
         info!("Building BM25 index from episodic memory");
+        Self::init_storage(conn)?;
 
         let mut stmt = conn
             .prepare(
-                "SELECT id, user_input, system_response
+                "SELECT id, user_message, ai_response
                  FROM episodic_memory
-                 ORDER BY timestamp DESC",
+                 ORDER BY created_at DESC",
             )
             .map_err(|e| format!("Failed to prepare statement: {}", e))?;
 
@@ -130,72 +348,170 @@ impl BM25Index {
             .query_map([], |row| {
                 Ok((
                     row.get::<_, String>(0)?,  // id
-                    row.get::<_, String>(1)?,  // user_input
-                    row.get::<_, String>(2)?,  // system_response
+                    row.get::<_, String>(1)?,  // user_message
+                    row.get::<_, String>(2)?,  // ai_response
                 ))
             })
             .map_err(|e| format!("Failed to query episodes: {}", e))?;
 
         let mut count = 0;
         for episode in episodes {
-            let (id, user_input, system_response) = episode
+            let (id, user_message, ai_response) = episode
                 .map_err(|e| format!("Failed to read episode: {}", e))?;
 
-            // Combine user input and system response for indexing
-            let combined_content = format!("{} {}", user_input, system_response);
-            self.add_document(id, combined_content);
+            // Combine user message and AI response for indexing
+            self.add_document(id, episode_content(&user_message, &ai_response));
             count += 1;
         }
 
-        // Compute IDF scores after all documents are added
-        self.compute_idf_scores();
-
-        // Compute average document length
-        let total_length: usize = self.documents.values().map(|d| d.length).sum();
-        self.avg_doc_length = if self.total_docs > 0 {
-            total_length as f32 / self.total_docs as f32
-        } else {
-            0.0
-        };
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        tx.execute("DELETE FROM bm25_postings", [])
+            .map_err(|e| format!("Failed to clear BM25 postings: {}", e))?;
+        for document in self.documents.values() {
+            self.persist(&tx, document)?;
+        }
+        tx.commit().map_err(|e| format!("Failed to persist BM25 index: {}", e))?;
 
         info!(
             "BM25 index built: {} documents, avg_length: {:.2}, unique_terms: {}",
             count,
-            self.avg_doc_length,
-            self.idf_scores.len()
+            self.avg_doc_length(),
+            self.document_frequency.len()
         );
 
         Ok(())
     }
 
-    /// Compute IDF scores for all terms
-    fn compute_idf_scores(&mut self) {
-        let n = self.total_docs as f32;
+    /// Load persisted postings, then pick up episodes added or deleted since (v3.9.0)
+    ///
+    /// Postings written by another tokenizer are discarded and re-tokenized.
+    pub fn load_from_database(&mut self, conn: &Connection) -> Result<SyncStats, String> {
+        Self::init_storage(conn)?;
+        self.clear();
+        conn.execute("DELETE FROM bm25_postings WHERE tokenizer != ?1", [self.tokenizer.name()])
+            .map_err(|e| format!("Failed to drop stale BM25 postings: {}", e))?;
+        let orphans = conn
+            .execute("DELETE FROM bm25_postings WHERE id NOT IN (SELECT id FROM episodic_memory)", [])
+            .map_err(|e| format!("Failed to drop stale BM25 postings: {}", e))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT p.id, p.length, p.terms, e.user_message, e.ai_response
+                 FROM bm25_postings p
+                 JOIN episodic_memory e ON e.id = p.id",
+            )
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            })
+            .map_err(|e| format!("Failed to query BM25 postings: {}", e))?;
+        for row in rows {
+            let (id, length, terms, user_message, ai_response) =
+                row.map_err(|e| format!("Failed to read BM25 postings: {}", e))?;
+            let term_frequencies = serde_json::from_str(&terms)
+                .map_err(|e| format!("Corrupt BM25 postings for {}: {}", id, e))?;
+            self.insert(Document {
+                id,
+                content: episode_content(&user_message, &ai_response),
+                term_frequencies,
+                length: length as usize,
+            });
+        }
+        let loaded = self.documents.len();
+
+        let mut sync = self.sync_with_database(conn)?;
+        sync.removed += orphans;
+        info!(
+            "BM25 index loaded: {} persisted documents, {} added, {} removed",
+            loaded, sync.added, sync.removed
+        );
+        Ok(sync)
+    }
+
+    /// Index episodes missing from the index and drop deleted ones (v3.9.0)
+    pub fn sync_with_database(&mut self, conn: &Connection) -> Result<SyncStats, String> {
+        let mut stmt = conn
+            .prepare("SELECT id FROM episodic_memory")
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+        let ids = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| format!("Failed to query episodes: {}", e))?
+            .collect::<rusqlite::Result<HashSet<String>>>()
+            .map_err(|e| format!("Failed to read episode: {}", e))?;
+
+        let removed: Vec<String> = self.documents.keys().filter(|id| !ids.contains(*id)).cloned().collect();
+        for id in &removed {
+            self.remove_episode(conn, id)?;
+        }
+        let added: Vec<&String> = ids.iter().filter(|id| !self.documents.contains_key(*id)).collect();
+        for id in &added {
+            self.index_episode(conn, id)?;
+        }
 
-        for (term, df) in &self.document_frequency {
-            // IDF formula: log((N - df + 0.5) / (df + 0.5))
-            let idf = ((n - *df as f32 + 0.5) / (*df as f32 + 0.5)).ln();
-            self.idf_scores.insert(term.clone(), idf);
+        if !added.is_empty() || !removed.is_empty() {
+            debug!("BM25 sync: {} added, {} removed", added.len(), removed.len());
         }
+        Ok(SyncStats { added: added.len(), removed: removed.len() })
+    }
+
+    /// (Re-)index one episode and persist its postings (v3.9.0)
+    pub fn index_episode(&mut self, conn: &Connection, id: &str) -> Result<(), String> {
+        let episode: Option<(String, String)> = conn
+            .query_row(
+                "SELECT user_message, ai_response FROM episodic_memory WHERE id = ?1",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to read episode: {}", e))?;
+        let Some((user_message, ai_response)) = episode else {
+            return self.remove_episode(conn, id);
+        };
+        self.add_document(id.to_string(), episode_content(&user_message, &ai_response));
+        self.persist(conn, &self.documents[id])
+    }
+
+    /// Remove one episode and its persisted postings (v3.9.0)
+    pub fn remove_episode(&mut self, conn: &Connection, id: &str) -> Result<(), String> {
+        self.remove_document(id);
+        conn.execute("DELETE FROM bm25_postings WHERE id = ?1", [id])
+            .map_err(|e| format!("Failed to delete BM25 postings: {}", e))?;
+        Ok(())
     }
 
     /// Get IDF score for a term
     fn idf(&self, term: &str) -> f32 {
-        *self.idf_scores.get(term).unwrap_or(&0.0)
+        let Some(&df) = self.document_frequency.get(term) else {
+            return 0.0;
+        };
+        let n = self.documents.len() as f32;
+        (1.0 + (n - df as f32 + 0.5) / (df as f32 + 0.5)).ln()
     }
 
     /// Compute BM25 score for a document given query terms
-    fn compute_score(&self, doc: &Document, query_terms: &[String]) -> f32 {
+    fn compute_score(&self, doc: &Document, query_terms: &[String], avg_doc_length: f32) -> f32 {
         let mut score = 0.0;
 
         for term in query_terms {
-            let idf = self.idf(term);
             let tf = *doc.term_frequencies.get(term).unwrap_or(&0) as f32;
+            if tf == 0.0 {
+                continue;
+            }
+            let idf = self.idf(term);
 
             // BM25 formula
             let numerator = tf * (self.k1 + 1.0);
             let denominator = tf
-                + self.k1 * (1.0 - self.b + self.b * (doc.length as f32 / self.avg_doc_length));
+                + self.k1 * (1.0 - self.b + self.b * (doc.length as f32 / avg_doc_length));
 
             score += idf * (numerator / denominator);
         }
@@ -207,18 +523,19 @@ impl BM25Index {
     pub fn search(&self, query: &str, top_k: usize) -> Vec<ScoredDocument> {
         debug!("BM25 search: '{}' (top_k: {})", query, top_k);
 
-        let query_terms = Self::tokenize(query);
+        let query_terms = self.tokenize(query);
 
         if query_terms.is_empty() {
             return Vec::new();
         }
 
         // Score all documents
+        let avg_doc_length = self.avg_doc_length();
         let mut scored_docs: Vec<ScoredDocument> = self
             .documents
             .values()
             .map(|doc| {
-                let score = self.compute_score(doc, &query_terms);
+                let score = self.compute_score(doc, &query_terms, avg_doc_length);
                 ScoredDocument {
                     document_id: doc.id.clone(),
                     score,
@@ -250,11 +567,12 @@ impl BM25Index {
     /// Get index statistics
     pub fn stats(&self) -> IndexStats {
         IndexStats {
-            total_documents: self.total_docs,
-            unique_terms: self.idf_scores.len(),
-            avg_doc_length: self.avg_doc_length,
+            total_documents: self.documents.len(),
+            unique_terms: self.document_frequency.len(),
+            avg_doc_length: self.avg_doc_length(),
             k1: self.k1,
             b: self.b,
+            tokenizer: self.tokenizer.name(),
         }
     }
 
@@ -267,10 +585,8 @@ impl BM25Index {
     /// Clear the index
     pub fn clear(&mut self) {
         self.documents.clear();
-        self.idf_scores.clear();
         self.document_frequency.clear();
-        self.total_docs = 0;
-        self.avg_doc_length = 0.0;
+        self.total_length = 0;
     }
 }
 
@@ -288,17 +604,42 @@ pub struct IndexStats {
     pub avg_doc_length: f32,
     pub k1: f32,
     pub b: f32,
+    pub tokenizer: &'static str,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::database::Database;
 
     #[test]
     fn test_tokenization() {
         let text = "Hello, World! This is a test.";
-        let tokens = BM25Index::tokenize(text);
+        let tokens = BM25Index::with_tokenizer(Arc::new(UnicodeTokenizer)).tokenize(text);
         assert_eq!(tokens, vec!["hello", "world", "this", "is", "a", "test"]);
+        assert_eq!(KoreanTokenizer.tokenize(text), tokens);
+    }
+
+    #[test]
+    fn test_korean_tokenization() {
+        assert_eq!(
+            KoreanTokenizer.tokenize("서울에서 맛집을 찾았어요"),
+            vec!["서울", "맛집", "찾았", "았어", "어요"]
+        );
+        assert_eq!(KoreanTokenizer.tokenize("Rust는 빠르다"), vec!["rust", "빠르", "르다"]);
+        // A two-syllable word keeps a particle-like final syllable
+        assert_eq!(KoreanTokenizer.tokenize("사과"), vec!["사과"]);
+
+        let mut index = BM25Index::new();
+        index.add_document("doc1".to_string(), "서울에서 맛집을 찾았어요".to_string());
+        index.add_document("doc2".to_string(), "부산 여행 계획을 세웠다".to_string());
+        index.add_document("doc3".to_string(), "서울의 날씨는 맑음".to_string());
+        let results = index.search("서울 맛집", 3);
+        assert_eq!(results[0].document_id, "doc1");
+        assert_eq!(results.len(), 2);
+
+        let unicode = BM25Index::with_tokenizer(Arc::new(UnicodeTokenizer));
+        assert_eq!(unicode.tokenize("서울에서"), vec!["서울에서"]);
     }
 
     #[test]
@@ -327,9 +668,6 @@ mod tests {
         );
         index.add_document("doc3".to_string(), "a completely different document".to_string());
 
-        index.compute_idf_scores();
-        index.avg_doc_length = 7.0;
-
         let results = index.search("quick brown", 3);
 
         assert!(results.len() >= 2);
@@ -339,15 +677,59 @@ mod tests {
     }
 
     #[test]
-    fn test_index_stats() {
+    fn test_incremental_updates() {
         let mut index = BM25Index::new();
         index.add_document("doc1".to_string(), "hello world".to_string());
         index.add_document("doc2".to_string(), "goodbye world".to_string());
-        index.compute_idf_scores();
+        index.add_document("doc1".to_string(), "hello there friend".to_string());
+        assert_eq!(index.stats().total_documents, 2);
+        assert_eq!(index.document_frequency.get("world"), Some(&1));
+        assert!((index.stats().avg_doc_length - 2.5).abs() < 1e-6);
+
+        assert!(index.remove_document("doc2"));
+        assert!(!index.remove_document("doc2"));
+        assert!(!index.document_frequency.contains_key("goodbye"));
+        assert!(index.search("world", 5).is_empty());
+        assert_eq!(index.search("friend", 5)[0].document_id, "doc1");
+    }
 
-        let stats = index.stats();
-        assert_eq!(stats.total_documents, 2);
-        assert_eq!(stats.k1, 1.5);
-        assert_eq!(stats.b, 0.75);
+    #[test]
+    fn test_persistence() {
+        let db = Database::new_test_db().unwrap();
+        let conn = db.conn();
+        conn.execute(
+            "INSERT INTO episodic_memory (id, user_message, ai_response, satisfaction, created_at)
+             VALUES ('e1', '서울 맛집 추천', '을지로에 있어요', 0.5, 1), ('e2', 'rust tips', 'use clippy', 0.5, 2)",
+            [],
+        )
+        .unwrap();
+
+        let mut index = BM25Index::new();
+        index.build_from_database(conn).unwrap();
+        assert_eq!(index.stats().total_documents, 2);
+
+        conn.execute("DELETE FROM episodic_memory WHERE id = 'e2'", []).unwrap();
+        conn.execute(
+            "INSERT INTO episodic_memory (id, user_message, ai_response, satisfaction, created_at)
+             VALUES ('e3', 'rust 서울 meetup', 'Thursday', 0.5, 3)",
+            [],
+        )
+        .unwrap();
+
+        let mut reloaded = BM25Index::new();
+        let sync = reloaded.load_from_database(conn).unwrap();
+        assert_eq!(sync, SyncStats { added: 1, removed: 1 });
+        assert_eq!(reloaded.stats().total_documents, 2);
+        let ids: Vec<String> = reloaded.search("서울", 5).into_iter().map(|d| d.document_id).collect();
+        assert_eq!(ids.len(), 2);
+        let persisted: i64 = conn.query_row("SELECT COUNT(*) FROM bm25_postings", [], |row| row.get(0)).unwrap();
+        assert_eq!(persisted, 2);
+
+        // Postings of another tokenizer are rebuilt
+        let mut unicode = BM25Index::with_tokenizer(Arc::new(UnicodeTokenizer));
+        assert_eq!(unicode.load_from_database(conn).unwrap().added, 2);
+        assert_eq!(TokenizerKind::load(conn), TokenizerKind::Korean);
+        TokenizerKind::Unicode.save(conn).unwrap();
+        assert_eq!(TokenizerKind::load(conn), TokenizerKind::Unicode);
     }
 }
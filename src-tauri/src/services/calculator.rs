@@ -0,0 +1,556 @@
+//! Calculator (v3.9.0)
+//!
+//! Expression engine behind the `calculate` tool (fend), with dates and
+//! currency on top.
+//!
+//! Features:
+//! - Arithmetic, functions, constants, bases and exact fractions
+//! - Units and conversions: "5 ft to m", "30 km/h in mph", "3 h + 45 min to min"
+//! - Percentages: "20% of 150", "150 + 10%" (= 165, an increase by 10%)
+//! - Variables: "a = 5; b = a^2; b * 2", or passed in with the call
+//! - Dates: today / tomorrow / YYYY-MM-DD plus or minus days, weeks, months
+//!   or years, days between two dates, days until / since a date
+//! - Currency: "100 USD to KRW" with rates from an optional provider, cached
+//!   and kept for offline use
+//!
+//! Exchange rates are off until the user turns them on; fetching respects
+//! the network policy's offline mode.
+
+#![allow(dead_code)]  // Phase 5: Calculator
+
+use crate::database::Database;
+use crate::services::network_policy;
+use anyhow::{anyhow, Result};
+use chrono::{Datelike, Local, Months, NaiveDate};
+use fend_core::Interrupt;
+use regex::Regex;
+use reqwest::header::USER_AGENT;
+use reqwest::{Client, Url};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+const CONFIG_KEY: &str = "calculator_config";
+const RATES_KEY: &str = "calculator_exchange_rates";
+
+/// ECB reference rates, no API key needed
+const DEFAULT_RATES_URL: &str = "https://api.frankfurter.dev/v1/latest?base=USD";
+
+const MAX_EXPRESSION_CHARS: usize = 2_000;
+
+/// Evaluation time limit (huge factorials and powers stop here)
+const EVAL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Calculator settings, stored in user_preferences
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalculatorConfig {
+    /// Fetch exchange rates for currency conversion
+    pub exchange_rates_enabled: bool,
+    /// JSON endpoint with a `rates` object (frankfurter / exchangerate-api format)
+    pub rates_url: String,
+    /// Rates older than this are fetched again (the old ones are used offline)
+    pub rates_max_age_hours: u64,
+}
+
+impl Default for CalculatorConfig {
+    fn default() -> Self {
+        Self {
+            exchange_rates_enabled: false,
+            rates_url: DEFAULT_RATES_URL.to_string(),
+            rates_max_age_hours: 12,
+        }
+    }
+}
+
+/// Exchange rates: units of each currency per one unit of `base`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeRates {
+    pub base: String,
+    /// Date the provider published the rates, if it says
+    pub date: Option<String>,
+    pub rates: HashMap<String, f64>,
+    /// Unix seconds
+    pub fetched_at: i64,
+}
+
+impl ExchangeRates {
+    fn age(&self) -> Duration {
+        Duration::from_secs((chrono::Utc::now().timestamp() - self.fetched_at).max(0) as u64)
+    }
+
+    fn rate(&self, currency: &str) -> Option<f64> {
+        if currency.eq_ignore_ascii_case(&self.base) {
+            return Some(1.0);
+        }
+        self.rates.get(&currency.to_uppercase()).copied()
+    }
+}
+
+/// Result of one calculation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Calculation {
+    pub expression: String,
+    /// Formatted result, e.g. "1.524 meters" or "2026-11-13 (Friday, week 46)"
+    pub result: String,
+    /// The number in `result`, when it starts with one
+    pub value: Option<f64>,
+    /// What follows the number (unit or currency)
+    pub unit: Option<String>,
+    /// The result was rounded for display
+    pub approximate: bool,
+    /// Publication date of the exchange rates used
+    pub rates_date: Option<String>,
+}
+
+pub struct CalculatorService {
+    db: Arc<Mutex<Database>>,
+    client: Client,
+    config: RwLock<CalculatorConfig>,
+    rates: RwLock<Option<Arc<ExchangeRates>>>,
+}
+
+impl CalculatorService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        let (config, rates) = {
+            let db = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            let rates = load_preference::<ExchangeRates>(db.conn(), RATES_KEY)?.map(Arc::new);
+            (load_preference(db.conn(), CONFIG_KEY)?.unwrap_or_default(), rates)
+        };
+        let client = Client::builder().timeout(Duration::from_secs(10)).build()?;
+
+        log::info!("✓ Calculator initialized");
+        Ok(Self { db, client, config: RwLock::new(config), rates: RwLock::new(rates) })
+    }
+
+    pub fn get_config(&self) -> CalculatorConfig {
+        self.config.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn update_config(&self, mut config: CalculatorConfig) -> Result<CalculatorConfig> {
+        config.rates_url = config.rates_url.trim().to_string();
+        if config.rates_url.is_empty() {
+            config.rates_url = DEFAULT_RATES_URL.to_string();
+        }
+        Url::parse(&config.rates_url).map_err(|e| anyhow!("Invalid rates URL: {}", e))?;
+        config.rates_max_age_hours = config.rates_max_age_hours.clamp(1, 24 * 30);
+
+        let url_changed = self.get_config().rates_url != config.rates_url;
+        save_preference(&self.db, CONFIG_KEY, &config)?;
+        *self.config.write().unwrap_or_else(|e| e.into_inner()) = config.clone();
+        if url_changed {
+            // Rates from another provider may use another base
+            *self.rates.write().unwrap_or_else(|e| e.into_inner()) = None;
+        }
+        log::info!("Calculator config updated");
+        Ok(config)
+    }
+
+    /// Cached exchange rates, if any were fetched
+    pub fn cached_rates(&self) -> Option<ExchangeRates> {
+        self.rates.read().unwrap_or_else(|e| e.into_inner()).as_deref().cloned()
+    }
+
+    /// Fetch exchange rates now
+    pub async fn refresh_rates(&self) -> Result<ExchangeRates> {
+        let config = self.get_config();
+        let url = Url::parse(&config.rates_url)?;
+        let policy = network_policy::global();
+        let _permit = policy.acquire(&url).await?;
+
+        log::info!("Fetching exchange rates from {}", url.host_str().unwrap_or_default());
+        let response = self.client.get(url).header(USER_AGENT, policy.user_agent()).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("Exchange rate provider returned HTTP {}", response.status()));
+        }
+        let rates = parse_rates(&response.json::<serde_json::Value>().await?)?;
+
+        save_preference(&self.db, RATES_KEY, &rates)?;
+        *self.rates.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(rates.clone()));
+        Ok(rates)
+    }
+
+    /// Evaluate an expression; `variables` are defined first (numbers or expressions)
+    pub async fn calculate(&self, expression: &str, variables: &HashMap<String, serde_json::Value>) -> Result<Calculation> {
+        let expression = expression.trim();
+        if expression.is_empty() {
+            return Err(anyhow!("Empty expression"));
+        }
+        if expression.chars().count() > MAX_EXPRESSION_CHARS {
+            return Err(anyhow!("Expression is too long ({} characters max)", MAX_EXPRESSION_CHARS));
+        }
+        if let Some(result) = evaluate_date(expression, Local::now().date_naive()) {
+            return Ok(Calculation {
+                expression: expression.to_string(),
+                ..to_calculation(result?, false)
+            });
+        }
+        let source = with_variables(&rewrite_percentages(expression), variables)?;
+
+        let config = self.get_config();
+        let cached = self.rates.read().unwrap_or_else(|e| e.into_inner()).clone();
+        let first = evaluate_blocking(source.clone(), cached.clone()).await?;
+        if !first.wanted_rates {
+            return first.outcome.map(|calc| Calculation { expression: expression.to_string(), ..calc });
+        }
+
+        // The expression converts currencies
+        if !config.exchange_rates_enabled {
+            return Err(anyhow!("Currency conversion is off; turn on exchange rates in the calculator settings"));
+        }
+        let stale = cached
+            .as_ref()
+            .is_none_or(|rates| rates.age() > Duration::from_secs(config.rates_max_age_hours * 3600));
+        let rates = if stale && !network_policy::global().is_offline() {
+            match self.refresh_rates().await {
+                Ok(rates) => Some(Arc::new(rates)),
+                Err(e) if cached.is_some() => {
+                    log::warn!("Using cached exchange rates, refresh failed: {}", e);
+                    cached
+                }
+                Err(e) => return Err(anyhow!("No exchange rates available: {}", e)),
+            }
+        } else {
+            cached
+        };
+        let Some(rates) = rates else {
+            return Err(anyhow!("No exchange rates available while offline"));
+        };
+
+        let rates_date = rates.date.clone().or_else(|| {
+            chrono::DateTime::from_timestamp(rates.fetched_at, 0).map(|t| t.format("%Y-%m-%d").to_string())
+        });
+        let outcome = evaluate_blocking(source, Some(rates)).await?.outcome?;
+        Ok(Calculation { expression: expression.to_string(), rates_date, ..outcome })
+    }
+}
+
+fn load_preference<T: serde::de::DeserializeOwned>(conn: &Connection, key: &str) -> Result<Option<T>> {
+    let json: Option<String> = conn
+        .query_row("SELECT value FROM user_preferences WHERE key = ?1", [key], |row| row.get(0))
+        .optional()?;
+    json.map(|json| serde_json::from_str(&json).map_err(Into::into)).transpose()
+}
+
+fn save_preference<T: Serialize>(db: &Mutex<Database>, key: &str, value: &T) -> Result<()> {
+    let db = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+    db.conn().execute(
+        "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+        rusqlite::params![key, serde_json::to_string(value)?, chrono::Utc::now().timestamp()],
+    )?;
+    Ok(())
+}
+
+/// Rates from `{"base": "USD", "date": "...", "rates": {...}}` (or `base_code`)
+fn parse_rates(body: &serde_json::Value) -> Result<ExchangeRates> {
+    let rates: HashMap<String, f64> = body
+        .get("rates")
+        .and_then(|r| r.as_object())
+        .ok_or_else(|| anyhow!("Exchange rate response has no 'rates' object"))?
+        .iter()
+        .filter_map(|(code, rate)| Some((code.to_uppercase(), rate.as_f64().filter(|r| *r > 0.0)?)))
+        .collect();
+    let base = body
+        .get("base")
+        .or_else(|| body.get("base_code"))
+        .and_then(|b| b.as_str())
+        .ok_or_else(|| anyhow!("Exchange rate response doesn't name its base currency"))?
+        .to_uppercase();
+    if rates.is_empty() {
+        return Err(anyhow!("Exchange rate response has no rates"));
+    }
+    let date = body.get("date").and_then(|d| d.as_str()).map(str::to_string);
+    Ok(ExchangeRates { base, date, rates, fetched_at: chrono::Utc::now().timestamp() })
+}
+
+// ---------------------------------------------------------------------------
+// fend
+// ---------------------------------------------------------------------------
+
+struct Evaluation {
+    outcome: Result<Calculation>,
+    /// fend asked for an exchange rate
+    wanted_rates: bool,
+}
+
+struct Deadline(Instant);
+
+impl Interrupt for Deadline {
+    fn should_interrupt(&self) -> bool {
+        Instant::now() >= self.0
+    }
+}
+
+/// Answers fend's rate lookups from a snapshot and notes that it asked
+struct RateLookup {
+    rates: Option<Arc<ExchangeRates>>,
+    asked: Arc<AtomicBool>,
+}
+
+impl fend_core::ExchangeRateFnV2 for RateLookup {
+    fn relative_to_base_currency(
+        &self,
+        currency: &str,
+        _options: &fend_core::ExchangeRateFnV2Options,
+    ) -> std::result::Result<f64, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        self.asked.store(true, Ordering::Relaxed);
+        let rates = self.rates.as_ref().ok_or("no exchange rates loaded")?;
+        rates
+            .rate(currency)
+            .ok_or_else(|| format!("no exchange rate for {}", currency).into())
+    }
+}
+
+async fn evaluate_blocking(source: String, rates: Option<Arc<ExchangeRates>>) -> Result<Evaluation> {
+    tokio::task::spawn_blocking(move || evaluate_fend(&source, rates))
+        .await
+        .map_err(|e| anyhow!("Calculation task failed: {}", e))
+}
+
+fn evaluate_fend(source: &str, rates: Option<Arc<ExchangeRates>>) -> Evaluation {
+    let asked = Arc::new(AtomicBool::new(false));
+    let mut context = fend_core::Context::new();
+    context.set_exchange_rate_handler_v2(RateLookup { rates, asked: Arc::clone(&asked) });
+
+    let deadline = Deadline(Instant::now() + EVAL_TIMEOUT);
+    let outcome = match fend_core::evaluate_with_interrupt(source, &mut context, &deadline) {
+        Ok(result) if result.get_main_result().trim().is_empty() => Err(anyhow!("The expression has no result")),
+        Ok(result) => {
+            let text = result.get_main_result();
+            match text.strip_prefix("approx. ") {
+                Some(rounded) => Ok(to_calculation(rounded.to_string(), true)),
+                None => Ok(to_calculation(text.to_string(), false)),
+            }
+        }
+        Err(_) if deadline.should_interrupt() => Err(anyhow!("Calculation took too long")),
+        Err(e) => Err(anyhow!("{}", e)),
+    };
+    Evaluation { outcome, wanted_rates: asked.load(Ordering::Relaxed) }
+}
+
+fn to_calculation(result: String, approximate: bool) -> Calculation {
+    let (value, unit) = split_number(&result);
+    Calculation { expression: String::new(), result, value, unit, approximate, rates_date: None }
+}
+
+/// "1.524 meters" → (1.524, "meters"); "138,000 KRW" → (138000, "KRW")
+fn split_number(result: &str) -> (Option<f64>, Option<String>) {
+    let (number, rest) = result.split_once(' ').unwrap_or((result, ""));
+    match number.replace(',', "").parse::<f64>() {
+        Ok(value) if value.is_finite() => {
+            let unit = rest.trim();
+            (Some(value), (!unit.is_empty()).then(|| unit.to_string()))
+        }
+        _ => (None, None),
+    }
+}
+
+fn percent_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^(.+?)\s*([+-])\s*(\d+(?:\.\d+)?)\s*%$").unwrap())
+}
+
+/// "X + 10%" means X increased by 10%, not X + 0.1 as fend reads it
+fn rewrite_percentages(expression: &str) -> String {
+    match percent_regex().captures(expression) {
+        Some(c) if !c[1].trim_end().ends_with(['+', '-', '*', '/', '^', '(']) => {
+            format!("({}) * (1 {} {} / 100)", &c[1], &c[2], &c[3])
+        }
+        _ => expression.to_string(),
+    }
+}
+
+/// Prefix `name = value;` definitions
+fn with_variables(expression: &str, variables: &HashMap<String, serde_json::Value>) -> Result<String> {
+    let mut names: Vec<&String> = variables.keys().collect();
+    names.sort();
+    let mut source = String::new();
+    for name in names {
+        let valid = name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !valid {
+            return Err(anyhow!("Invalid variable name '{}'", name));
+        }
+        let value = match &variables[name] {
+            serde_json::Value::Number(n) => n.to_string(),
+            serde_json::Value::String(s) if !s.contains(';') => format!("({})", s),
+            _ => return Err(anyhow!("Variable '{}' must be a number or an expression", name)),
+        };
+        source.push_str(&format!("{} = {}; ", name, value));
+    }
+    source.push_str(expression);
+    Ok(source)
+}
+
+// ---------------------------------------------------------------------------
+// Dates
+// ---------------------------------------------------------------------------
+
+const DATE_TERM: &str = r"today|now|tomorrow|yesterday|@?\d{4}-\d{1,2}-\d{1,2}";
+
+fn date_regexes() -> &'static [Regex; 5] {
+    static RE: OnceLock<[Regex; 5]> = OnceLock::new();
+    RE.get_or_init(|| {
+        let t = DATE_TERM;
+        [
+            // days between A and B
+            Regex::new(&format!(r"^(?:days\s+)?(?:between|from)\s+({t})\s+(?:and|to|until)\s+({t})$")).unwrap(),
+            // days until / since A
+            Regex::new(&format!(r"^days\s+(until|till|to|since)\s+({t})$")).unwrap(),
+            // A - B
+            Regex::new(&format!(r"^({t})\s*-\s*({t})$")).unwrap(),
+            // A + 3 weeks - 2 days
+            Regex::new(&format!(r"^({t})((?:\s*[+-]\s*\d+\s*[a-z]+)+)$")).unwrap(),
+            Regex::new(&format!(r"^({t})$")).unwrap(),
+        ]
+    })
+}
+
+fn shift_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\s*([+-])\s*(\d+)\s*([a-z]+)").unwrap())
+}
+
+/// Date arithmetic; `None` when the expression isn't about dates
+fn evaluate_date(expression: &str, today: NaiveDate) -> Option<Result<String>> {
+    let expression = expression.trim().to_lowercase();
+    let [between, relative, difference, shift, alone] = date_regexes();
+
+    if let Some(c) = between.captures(&expression) {
+        return Some(parse_dates(&[&c[1], &c[2]], today).map(|d| format_days((d[1] - d[0]).num_days())));
+    }
+    if let Some(c) = relative.captures(&expression) {
+        return Some(parse_dates(&[&c[2]], today).map(|d| {
+            let days = (d[0] - today).num_days();
+            format_days(if &c[1] == "since" { -days } else { days })
+        }));
+    }
+    if let Some(c) = difference.captures(&expression) {
+        return Some(parse_dates(&[&c[1], &c[2]], today).map(|d| format_days((d[0] - d[1]).num_days())));
+    }
+    if let Some(c) = shift.captures(&expression) {
+        return Some(parse_dates(&[&c[1]], today).and_then(|d| {
+            let mut date = d[0];
+            for step in shift_regex().captures_iter(&c[2]) {
+                let amount: u32 = step[2].parse().map_err(|_| anyhow!("'{}' is too large", &step[2]))?;
+                date = shift_date(date, &step[1] == "-", amount, &step[3])?;
+            }
+            Ok(format_date(date))
+        }));
+    }
+    if let Some(c) = alone.captures(&expression) {
+        return Some(parse_dates(&[&c[1]], today).map(|d| format_date(d[0])));
+    }
+    None
+}
+
+fn parse_dates(terms: &[&str], today: NaiveDate) -> Result<Vec<NaiveDate>> {
+    terms
+        .iter()
+        .map(|term| match *term {
+            "today" | "now" => Ok(today),
+            "tomorrow" => today.succ_opt().ok_or_else(|| anyhow!("Date out of range")),
+            "yesterday" => today.pred_opt().ok_or_else(|| anyhow!("Date out of range")),
+            date => NaiveDate::parse_from_str(date.trim_start_matches('@'), "%Y-%m-%d")
+                .map_err(|_| anyhow!("Invalid date '{}', expected YYYY-MM-DD", date)),
+        })
+        .collect()
+}
+
+fn shift_date(date: NaiveDate, backwards: bool, amount: u32, unit: &str) -> Result<NaiveDate> {
+    let shifted = match unit {
+        "d" | "day" | "days" => {
+            let days = chrono::Duration::days(amount as i64);
+            if backwards { date.checked_sub_signed(days) } else { date.checked_add_signed(days) }
+        }
+        "w" | "wk" | "week" | "weeks" => {
+            let days = chrono::Duration::weeks(amount as i64);
+            if backwards { date.checked_sub_signed(days) } else { date.checked_add_signed(days) }
+        }
+        "mo" | "month" | "months" | "years" | "year" | "y" | "yr" | "yrs" => {
+            let months = if unit.starts_with('y') { amount.checked_mul(12) } else { Some(amount) }
+                .ok_or_else(|| anyhow!("Date out of range"))?;
+            if backwards { date.checked_sub_months(Months::new(months)) } else { date.checked_add_months(Months::new(months)) }
+        }
+        other => return Err(anyhow!("Dates move by days, weeks, months or years, not '{}'", other)),
+    };
+    shifted.ok_or_else(|| anyhow!("Date out of range"))
+}
+
+fn format_date(date: NaiveDate) -> String {
+    format!("{} ({}, week {})", date.format("%Y-%m-%d"), date.format("%A"), date.iso_week().week())
+}
+
+fn format_days(days: i64) -> String {
+    let unit = if days.abs() == 1 { "day" } else { "days" };
+    if days.abs() < 7 {
+        return format!("{} {}", days, unit);
+    }
+    let (weeks, rest) = (days.abs() / 7, days.abs() % 7);
+    let sign = if days < 0 { "-" } else { "" };
+    match rest {
+        0 => format!("{} {} ({}{} weeks)", days, unit, sign, weeks),
+        rest => format!("{} {} ({}{} weeks {} {})", days, unit, sign, weeks, rest, if rest == 1 { "day" } else { "days" }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calc(expression: &str) -> Calculation {
+        let source = with_variables(&rewrite_percentages(expression), &HashMap::new()).unwrap();
+        evaluate_fend(&source, None).outcome.unwrap()
+    }
+
+    #[test]
+    fn test_expressions() {
+        assert_eq!(calc("2 + 3 * 4").value, Some(14.0));
+        let meters = calc("5 feet to meters");
+        assert_eq!((meters.value, meters.unit.as_deref()), (Some(1.524), Some("meters")));
+        assert_eq!(calc("3 hours + 45 min to minutes").value, Some(225.0));
+        assert_eq!(calc("20% of 150").value, Some(30.0));
+        assert_eq!(calc("150 + 10%").value, Some(165.0));
+        assert_eq!(calc("(100 - 20) - 25%").value, Some(60.0));
+        assert_eq!(calc("a = 5; b = a^2; b * 2").value, Some(50.0));
+        assert!(calc("sqrt(2)").approximate);
+
+        let variables = HashMap::from([
+            ("price".to_string(), serde_json::json!(1200)),
+            ("qty".to_string(), serde_json::json!("3 * 2")),
+        ]);
+        let source = with_variables("price * qty", &variables).unwrap();
+        assert_eq!(evaluate_fend(&source, None).outcome.unwrap().value, Some(7200.0));
+        assert!(with_variables("1", &HashMap::from([("a b".to_string(), serde_json::json!(1))])).is_err());
+
+        // Currency needs rates
+        let evaluation = evaluate_fend("100 USD to KRW", None);
+        assert!(evaluation.wanted_rates && evaluation.outcome.is_err());
+        let rates = ExchangeRates {
+            base: "USD".to_string(),
+            date: None,
+            rates: HashMap::from([("KRW".to_string(), 1400.0), ("EUR".to_string(), 0.9)]),
+            fetched_at: 0,
+        };
+        let converted = evaluate_fend("100 USD to KRW", Some(Arc::new(rates))).outcome.unwrap();
+        assert_eq!((converted.value, converted.unit.as_deref()), (Some(140000.0), Some("KRW")));
+    }
+
+    #[test]
+    fn test_dates() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+        let date = |e: &str| evaluate_date(e, today).unwrap().unwrap();
+        assert_eq!(date("today"), "2026-10-14 (Wednesday, week 42)");
+        assert_eq!(date("today + 30 days"), "2026-11-13 (Friday, week 46)");
+        assert_eq!(date("2026-01-31 + 1 month"), "2026-02-28 (Saturday, week 9)");
+        assert_eq!(date("tomorrow + 1 week - 2 days"), "2026-10-20 (Tuesday, week 43)");
+        assert_eq!(date("2026-12-25 - today"), "72 days (10 weeks 2 days)");
+        assert_eq!(date("days until 2026-10-17"), "3 days");
+        assert_eq!(date("days between 2026-01-01 and 2026-01-15"), "14 days (2 weeks)");
+        assert!(evaluate_date("today + 3 hours", today).unwrap().is_err());
+        assert!(evaluate_date("5 feet to meters", today).is_none());
+    }
+}
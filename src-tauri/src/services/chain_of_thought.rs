@@ -15,14 +15,33 @@
 //! - Confidence scoring
 //! - Intermediate result caching
 //! - VRAM efficient (reuses Ollama instance)
+//! - Visible reasoning: steps stream to a "thinking" channel, sanitized of
+//!   private memory content (the raw trace stays in the logs)
 
 #![allow(dead_code)]  // Phase 5: Reasoning Engine 2.0 (scheduled)
 
 use crate::services::ollama;
+use crate::services::raft::terms;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 
+/// Tauri event carrying one visible reasoning step
+pub const THINKING_EVENT: &str = "cot-thinking";
+
+/// Tauri event sent when the visible trace is complete
+pub const THINKING_COMPLETE_EVENT: &str = "cot-thinking-complete";
+
+/// Replacement for trace sentences restating private context
+const REDACTED_MEMORY: &str = "[private memory]";
+
+/// Replacement for emails and long numbers
+const REDACTED_PII: &str = "[redacted]";
+
+/// Share of a sentence's terms that, when only found in the context, marks it private
+const PRIVATE_TERM_RATIO: f32 = 0.5;
+
 /// Single step in chain of thought
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReasoningStep {
@@ -78,6 +97,10 @@ pub struct CoTConfig {
 
     /// Whether to cache intermediate results
     pub enable_caching: bool,
+
+    /// Whether reasoning steps stream to the UI's thinking channel
+    #[serde(default)]
+    pub show_reasoning: bool,
 }
 
 impl Default for CoTConfig {
@@ -87,8 +110,87 @@ impl Default for CoTConfig {
             min_confidence: 0.6,
             enable_self_correction: true,
             enable_caching: true,
+            show_reasoning: false,
+        }
+    }
+}
+
+/// Reasoning step as shown in the thinking channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThinkingStep {
+    pub step_number: usize,
+    pub understanding: String,
+    pub next_question: String,
+    pub is_complete: bool,
+    pub confidence: f32,
+    /// The sanitizer removed something from this step
+    pub redacted: bool,
+}
+
+fn scrub_pii(text: &str) -> String {
+    text.split_inclusive(char::is_whitespace)
+        .map(|token| {
+            let word = token.trim_end();
+            let is_email = word.split_once('@').is_some_and(|(user, host)| !user.is_empty() && host.contains('.'));
+            let digits = word.chars().filter(char::is_ascii_digit).count();
+            if is_email || digits >= 7 {
+                format!("{}{}", REDACTED_PII, &token[word.len()..])
+            } else {
+                token.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Remove private context from a visible reasoning text.
+///
+/// Sentences mostly made of context terms that the query does not mention
+/// are replaced, then emails and long numbers are scrubbed.
+pub fn sanitize_trace(text: &str, query: &str, context: Option<&str>) -> String {
+    let private: HashSet<String> = match context.filter(|c| !c.trim().is_empty()) {
+        Some(context) => {
+            let query_terms: HashSet<String> = terms(query).into_iter().collect();
+            terms(context).into_iter().filter(|term| !query_terms.contains(term)).collect()
+        }
+        None => HashSet::new(),
+    };
+
+    let mut sanitized = String::new();
+    if private.is_empty() {
+        sanitized.push_str(text);
+    } else {
+        for sentence in text.split_inclusive(['.', '!', '?', '。', '\n']) {
+            let body = sentence.trim_end_matches(['.', '!', '?', '。', '\n']);
+            let sentence_terms = terms(body);
+            let private_count = sentence_terms.iter().filter(|term| private.contains(*term)).count();
+            let is_private = sentence_terms.len() >= 3
+                && private_count as f32 / sentence_terms.len() as f32 >= PRIVATE_TERM_RATIO;
+            if !is_private {
+                sanitized.push_str(sentence);
+            } else if !sanitized.trim_end().ends_with(REDACTED_MEMORY) {
+                let leading = &body[..body.len() - body.trim_start().len()];
+                sanitized.push_str(leading);
+                sanitized.push_str(REDACTED_MEMORY);
+                sanitized.push_str(&sentence[body.len()..]);
+            }
         }
     }
+
+    scrub_pii(&sanitized)
+}
+
+/// Step as shown in the thinking channel
+pub fn visible_step(step: &ReasoningStep, query: &str, context: Option<&str>) -> ThinkingStep {
+    let understanding = sanitize_trace(&step.understanding, query, context);
+    let next_question = sanitize_trace(&step.next_question, query, context);
+    ThinkingStep {
+        step_number: step.step_number,
+        redacted: understanding != step.understanding || next_question != step.next_question,
+        understanding,
+        next_question,
+        is_complete: step.is_complete,
+        confidence: step.confidence,
+    }
 }
 
 /// Chain-of-Thought Engine
@@ -115,6 +217,15 @@ impl ChainOfThoughtEngine {
     /// # Returns
     /// Complete reasoning chain with final answer
     pub async fn reason(&self, query: &str, context: Option<&str>) -> Result<Reasoning> {
+        self.reason_observed(query, context, |_| {}).await
+    }
+
+    /// Same as `reason`, calling `on_step` with every step as it is accepted
+    /// (cached results replay their steps)
+    pub async fn reason_observed<F>(&self, query: &str, context: Option<&str>, mut on_step: F) -> Result<Reasoning>
+    where
+        F: FnMut(&ReasoningStep) + Send,
+    {
         let start_time = std::time::Instant::now();
         let config = self.config.lock().unwrap().clone();
 
@@ -125,6 +236,7 @@ impl ChainOfThoughtEngine {
             let cache_key = self.generate_cache_key(query, context);
             if let Some(cached) = self.get_cached(&cache_key) {
                 log::info!("Using cached reasoning result");
+                cached.steps.iter().for_each(&mut on_step);
                 return Ok(cached);
             }
         }
@@ -147,6 +259,8 @@ impl ChainOfThoughtEngine {
                 );
 
                 let corrected = self.self_correct(&step, context).await?;
+                Self::log_step(&corrected);
+                on_step(&corrected);
                 steps.push(corrected.clone());
 
                 if corrected.is_complete {
@@ -155,6 +269,8 @@ impl ChainOfThoughtEngine {
 
                 current_thought = corrected.next_question;
             } else {
+                Self::log_step(&step);
+                on_step(&step);
                 steps.push(step.clone());
 
                 if step.is_complete {
@@ -195,6 +311,14 @@ impl ChainOfThoughtEngine {
         Ok(reasoning)
     }
 
+    /// Full step in the logs; only the sanitized copy reaches the UI
+    fn log_step(step: &ReasoningStep) {
+        log::debug!(
+            "Step {} (confidence {:.2}): {} → {}",
+            step.step_number, step.confidence, step.understanding, step.next_question
+        );
+    }
+
     /// Execute single reasoning step
     async fn think_step(
         &self,
@@ -536,6 +660,29 @@ mod tests {
         assert!(json.contains("understanding"));
     }
 
+    #[test]
+    fn test_sanitize_trace() {
+        let context = "Past conversation: my sister Jiyoung lives in Busan and works at Samsung Heavy Industries.";
+        let query = "What gift should I buy for my sister?";
+
+        let trace = "The user wants a gift idea. Jiyoung lives in Busan and works at Samsung Heavy Industries. Consider her hobbies.";
+        let sanitized = sanitize_trace(trace, query, Some(context));
+        assert_eq!(sanitized, "The user wants a gift idea. [private memory]. Consider her hobbies.");
+
+        let pii = sanitize_trace("Email kim@example.com or call 010-1234-5678 today", query, None);
+        assert_eq!(pii, "Email [redacted] or call [redacted] today");
+
+        let step = ReasoningStep {
+            step_number: 1,
+            understanding: "A gift for a sister".to_string(),
+            next_question: "Final answer ready".to_string(),
+            is_complete: true,
+            confidence: 0.9,
+            timestamp: 0,
+        };
+        assert!(!visible_step(&step, query, Some(context)).redacted);
+    }
+
     #[test]
     fn test_cache_key_generation() {
         let engine = ChainOfThoughtEngine::new();
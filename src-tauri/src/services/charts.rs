@@ -0,0 +1,525 @@
+//! Chart Service (v3.9.0)
+//!
+//! Renders structured data (query results, analysis numbers) as charts that
+//! are shown alongside chat responses.
+//!
+//! Features:
+//! - Line, bar, area and scatter charts with one or more series (plotters)
+//! - SVG for the chat view, PNG for exports and sharing
+//! - Chart specs are stored, not images, so any chart can be re-rendered at
+//!   another size or format later
+//! - Charts created by the `create_chart` tool while a reply is generated are
+//!   attached to that reply
+
+#![allow(dead_code)]  // Phase 5: Charts
+
+use crate::database::Database;
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Series per chart and points per series
+const MAX_SERIES: usize = 8;
+const MAX_POINTS: usize = 2_000;
+
+/// Longest title / axis label (characters)
+const MAX_LABEL_CHARS: usize = 120;
+
+/// Default and allowed image sizes (pixels)
+pub const DEFAULT_WIDTH: u32 = 800;
+pub const DEFAULT_HEIGHT: u32 = 480;
+const SIZE_RANGE: (u32, u32) = (200, 2_400);
+
+/// Category labels drawn on the x axis at most (the rest are skipped)
+const MAX_X_LABELS: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartKind {
+    Line,
+    Bar,
+    Area,
+    Scatter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartFormat {
+    #[default]
+    Svg,
+    Png,
+}
+
+impl ChartFormat {
+    /// Format for an export path ("sales.png" → PNG)
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "svg" => Some(ChartFormat::Svg),
+            "png" => Some(ChartFormat::Png),
+            _ => None,
+        }
+    }
+}
+
+/// One line / set of bars
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChartSeries {
+    #[serde(default)]
+    pub name: String,
+    pub values: Vec<f64>,
+    /// Numeric x positions (line, area and scatter); otherwise values are
+    /// placed at the chart's `labels`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<Vec<f64>>,
+}
+
+/// What to draw, as stored and as the `create_chart` tool receives it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChartSpec {
+    pub kind: ChartKind,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub x_label: Option<String>,
+    #[serde(default)]
+    pub y_label: Option<String>,
+    /// Category names along the x axis, one per value
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub series: Vec<ChartSeries>,
+}
+
+impl ChartSpec {
+    /// Check limits and that the data can be drawn
+    pub fn validate(&self) -> Result<()> {
+        if self.series.is_empty() {
+            return Err(anyhow!("A chart needs at least one series"));
+        }
+        if self.series.len() > MAX_SERIES {
+            return Err(anyhow!("A chart can have at most {} series", MAX_SERIES));
+        }
+        for text in [&self.title, &self.x_label, &self.y_label].into_iter().flatten() {
+            if text.chars().count() > MAX_LABEL_CHARS {
+                return Err(anyhow!("Chart labels are limited to {} characters", MAX_LABEL_CHARS));
+            }
+        }
+
+        let numeric_x = self.series.iter().any(|series| series.x.is_some());
+        if numeric_x && self.kind == ChartKind::Bar {
+            return Err(anyhow!("Bar charts use labels, not numeric x values"));
+        }
+        for series in &self.series {
+            if series.values.is_empty() || series.values.len() > MAX_POINTS {
+                return Err(anyhow!("Each series needs 1 to {} values", MAX_POINTS));
+            }
+            if series.values.iter().any(|v| !v.is_finite()) {
+                return Err(anyhow!("Series '{}' has a value that isn't a finite number", series.name));
+            }
+            match &series.x {
+                Some(x) if x.len() != series.values.len() || x.iter().any(|v| !v.is_finite()) => {
+                    return Err(anyhow!("Series '{}' needs one finite x value per value", series.name));
+                }
+                None if numeric_x => {
+                    return Err(anyhow!("Either every series has x values or none does"));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn has_numeric_x(&self) -> bool {
+        self.series.iter().all(|series| series.x.is_some())
+    }
+
+    /// Categories along the x axis when it isn't numeric
+    fn category_count(&self) -> usize {
+        self.series.iter().map(|series| series.values.len()).max().unwrap_or(0).max(self.labels.len())
+    }
+}
+
+/// A chart as stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredChart {
+    pub id: String,
+    pub conversation_id: Option<String>,
+    pub message_id: Option<String>,
+    pub spec: ChartSpec,
+    pub created_at: i64,
+}
+
+/// A rendered chart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartImage {
+    pub chart_id: Option<String>,
+    pub title: Option<String>,
+    pub format: ChartFormat,
+    pub width: u32,
+    pub height: u32,
+    /// SVG markup, or base64 PNG data
+    pub data: String,
+}
+
+/// Chart service
+pub struct ChartService {
+    db: Arc<Mutex<Database>>,
+}
+
+impl ChartService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        init_tables(&*db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?)?;
+        log::info!("✓ Chart Service initialized");
+        Ok(Self { db })
+    }
+
+    /// Store a chart; tool-created charts get their message when the reply is saved
+    pub fn create(&self, spec: ChartSpec, conversation_id: Option<&str>, message_id: Option<&str>) -> Result<StoredChart> {
+        spec.validate()?;
+        let chart = StoredChart {
+            id: format!("chart_{}", uuid::Uuid::new_v4().simple()),
+            conversation_id: conversation_id.map(str::to_string),
+            message_id: message_id.map(str::to_string),
+            spec,
+            created_at: chrono::Utc::now().timestamp_millis(),
+        };
+
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn().execute(
+            "INSERT INTO charts (id, conversation_id, message_id, spec, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                chart.id,
+                chart.conversation_id,
+                chart.message_id,
+                serde_json::to_string(&chart.spec)?,
+                chart.created_at
+            ],
+        )?;
+        Ok(chart)
+    }
+
+    pub fn get(&self, chart_id: &str) -> Result<StoredChart> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn()
+            .query_row(
+                "SELECT id, conversation_id, message_id, spec, created_at FROM charts WHERE id = ?1",
+                [chart_id],
+                row_to_chart,
+            )
+            .optional()?
+            .flatten()
+            .ok_or_else(|| anyhow!("Chart not found: {}", chart_id))
+    }
+
+    /// A conversation's charts, oldest first
+    pub fn list_for_conversation(&self, conversation_id: &str) -> Result<Vec<StoredChart>> {
+        self.query(
+            "SELECT id, conversation_id, message_id, spec, created_at FROM charts
+             WHERE conversation_id = ?1 ORDER BY created_at, id",
+            conversation_id,
+        )
+    }
+
+    pub fn list_for_message(&self, message_id: &str) -> Result<Vec<StoredChart>> {
+        self.query(
+            "SELECT id, conversation_id, message_id, spec, created_at FROM charts
+             WHERE message_id = ?1 ORDER BY created_at, id",
+            message_id,
+        )
+    }
+
+    /// Attach charts created without a message since `since` (ms) to a reply
+    ///
+    /// Tools don't know which reply they're part of, so chat commands call
+    /// this after saving the reply.
+    pub fn attach_unassigned(&self, since: i64, conversation_id: &str, message_id: &str) -> Result<Vec<StoredChart>> {
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            db.conn().execute(
+                "UPDATE charts SET conversation_id = ?1, message_id = ?2
+                 WHERE message_id IS NULL AND conversation_id IS NULL AND created_at >= ?3",
+                params![conversation_id, message_id, since],
+            )?;
+        }
+        self.list_for_message(message_id)
+    }
+
+    pub fn delete(&self, chart_id: &str) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        if db.conn().execute("DELETE FROM charts WHERE id = ?1", [chart_id])? == 0 {
+            return Err(anyhow!("Chart not found: {}", chart_id));
+        }
+        Ok(())
+    }
+
+    /// Render a stored chart
+    pub fn render(&self, chart_id: &str, format: ChartFormat, width: Option<u32>, height: Option<u32>) -> Result<ChartImage> {
+        let chart = self.get(chart_id)?;
+        let mut image = render(&chart.spec, format, width, height)?;
+        image.chart_id = Some(chart.id);
+        Ok(image)
+    }
+
+    /// Write a stored chart to a file, in the format its extension names (SVG otherwise)
+    pub fn export(&self, chart_id: &str, path: &Path, width: Option<u32>, height: Option<u32>) -> Result<PathBuf> {
+        let format = ChartFormat::from_path(path).unwrap_or_default();
+        let image = self.render(chart_id, format, width, height)?;
+        let bytes = match format {
+            ChartFormat::Svg => image.data.into_bytes(),
+            ChartFormat::Png => base64::engine::general_purpose::STANDARD.decode(image.data)?,
+        };
+        std::fs::write(path, bytes).map_err(|e| anyhow!("Failed to write {}: {}", path.display(), e))?;
+        log::info!("Exported chart {} to {}", chart_id, path.display());
+        Ok(path.to_path_buf())
+    }
+
+    fn query(&self, sql: &str, key: &str) -> Result<Vec<StoredChart>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(sql)?;
+        let charts = stmt
+            .query_map([key], row_to_chart)?
+            .filter_map(|r| r.ok())
+            .flatten()
+            .collect();
+        Ok(charts)
+    }
+}
+
+fn init_tables(db: &Database) -> Result<()> {
+    db.conn().execute_batch(
+        "CREATE TABLE IF NOT EXISTS charts (
+            id TEXT PRIMARY KEY,
+            conversation_id TEXT,
+            message_id TEXT,
+            spec TEXT NOT NULL,
+            created_at INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_charts_conversation ON charts(conversation_id, created_at);
+        CREATE INDEX IF NOT EXISTS idx_charts_message ON charts(message_id);",
+    )?;
+    Ok(())
+}
+
+/// Rows whose spec no longer parses are skipped
+fn row_to_chart(row: &rusqlite::Row) -> rusqlite::Result<Option<StoredChart>> {
+    let spec: String = row.get(3)?;
+    Ok(serde_json::from_str(&spec).ok().map(|spec| StoredChart {
+        id: row.get(0).unwrap_or_default(),
+        conversation_id: row.get(1).unwrap_or_default(),
+        message_id: row.get(2).unwrap_or_default(),
+        spec,
+        created_at: row.get(4).unwrap_or_default(),
+    }))
+}
+
+/// Render a spec as SVG markup or base64 PNG
+pub fn render(spec: &ChartSpec, format: ChartFormat, width: Option<u32>, height: Option<u32>) -> Result<ChartImage> {
+    spec.validate()?;
+    let width = width.unwrap_or(DEFAULT_WIDTH).clamp(SIZE_RANGE.0, SIZE_RANGE.1);
+    let height = height.unwrap_or(DEFAULT_HEIGHT).clamp(SIZE_RANGE.0, SIZE_RANGE.1);
+
+    let data = match format {
+        ChartFormat::Svg => {
+            let mut svg = String::new();
+            {
+                let root = SVGBackend::with_string(&mut svg, (width, height)).into_drawing_area();
+                draw(&root, spec)?;
+                root.present().map_err(draw_error)?;
+            }
+            svg
+        }
+        ChartFormat::Png => {
+            let mut pixels = vec![0u8; width as usize * height as usize * 3];
+            {
+                let root = BitMapBackend::with_buffer(&mut pixels, (width, height)).into_drawing_area();
+                draw(&root, spec)?;
+                root.present().map_err(draw_error)?;
+            }
+            let image = image::RgbImage::from_raw(width, height, pixels)
+                .ok_or_else(|| anyhow!("Chart image buffer has the wrong size"))?;
+            let mut png = Vec::new();
+            image::DynamicImage::ImageRgb8(image).write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)?;
+            base64::engine::general_purpose::STANDARD.encode(png)
+        }
+    };
+
+    Ok(ChartImage { chart_id: None, title: spec.title.clone(), format, width, height, data })
+}
+
+fn draw_error<E: std::error::Error + Send + Sync>(e: DrawingAreaErrorKind<E>) -> anyhow::Error {
+    anyhow!("Chart rendering failed: {}", e)
+}
+
+/// (min, max) widened so the range is never empty
+fn padded_range(values: impl Iterator<Item = f64>, include_zero: bool) -> (f64, f64) {
+    let (mut min, mut max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    if include_zero {
+        min = min.min(0.0);
+        max = max.max(0.0);
+    }
+    if (max - min).abs() < f64::EPSILON {
+        return (min - 1.0, max + 1.0);
+    }
+    let pad = (max - min) * 0.05;
+    (if include_zero && min == 0.0 { 0.0 } else { min - pad }, if include_zero && max == 0.0 { 0.0 } else { max + pad })
+}
+
+fn draw<DB: DrawingBackend>(root: &DrawingArea<DB, Shift>, spec: &ChartSpec) -> Result<()> {
+    root.fill(&WHITE).map_err(draw_error)?;
+
+    let numeric_x = spec.has_numeric_x();
+    let categories = spec.category_count();
+    let (x_min, x_max) = if numeric_x {
+        padded_range(spec.series.iter().flat_map(|s| s.x.iter().flatten().copied()), false)
+    } else {
+        (-0.5, categories as f64 - 0.5)
+    };
+    let include_zero = matches!(spec.kind, ChartKind::Bar | ChartKind::Area);
+    let (y_min, y_max) = padded_range(spec.series.iter().flat_map(|s| s.values.iter().copied()), include_zero);
+
+    let mut builder = ChartBuilder::on(root);
+    builder.margin(16).x_label_area_size(40).y_label_area_size(60);
+    if let Some(title) = spec.title.as_deref().filter(|t| !t.trim().is_empty()) {
+        builder.caption(title, ("sans-serif", 22));
+    }
+    let mut chart = builder.build_cartesian_2d(x_min..x_max, y_min..y_max).map_err(draw_error)?;
+
+    // Category axes only label whole positions
+    let category_label = |x: &f64| {
+        let index = x.round();
+        if (x - index).abs() > 1e-6 || index < 0.0 {
+            return String::new();
+        }
+        spec.labels.get(index as usize).cloned().unwrap_or_else(|| (index as usize + 1).to_string())
+    };
+    let mut mesh = chart.configure_mesh();
+    mesh.x_desc(spec.x_label.clone().unwrap_or_default())
+        .y_desc(spec.y_label.clone().unwrap_or_default())
+        .light_line_style(WHITE.mix(0.0));
+    if !numeric_x {
+        mesh.x_labels(categories.min(MAX_X_LABELS)).x_label_formatter(&category_label).disable_x_mesh();
+    }
+    mesh.draw().map_err(draw_error)?;
+
+    let series_count = spec.series.len();
+    for (index, series) in spec.series.iter().enumerate() {
+        let color = Palette99::pick(index).to_rgba();
+        let points: Vec<(f64, f64)> = match &series.x {
+            Some(x) => x.iter().copied().zip(series.values.iter().copied()).collect(),
+            None => series.values.iter().enumerate().map(|(i, v)| (i as f64, *v)).collect(),
+        };
+
+        let annotation = match spec.kind {
+            ChartKind::Line => chart
+                .draw_series(LineSeries::new(points, color.stroke_width(2)))
+                .map_err(draw_error)?,
+            ChartKind::Area => chart
+                .draw_series(AreaSeries::new(points, 0.0, color.mix(0.25)).border_style(color.stroke_width(2)))
+                .map_err(draw_error)?,
+            ChartKind::Scatter => chart
+                .draw_series(points.into_iter().map(|point| Circle::new(point, 4, color.filled())))
+                .map_err(draw_error)?,
+            ChartKind::Bar => {
+                // Series share each category slot side by side
+                let slot = 0.8 / series_count as f64;
+                chart
+                    .draw_series(points.into_iter().map(|(x, y)| {
+                        let left = x - 0.4 + slot * index as f64;
+                        Rectangle::new([(left, 0.0), (left + slot * 0.9, y)], color.filled())
+                    }))
+                    .map_err(draw_error)?
+            }
+        };
+        if !series.name.is_empty() {
+            annotation
+                .label(series.name.clone())
+                .legend(move |(x, y)| Rectangle::new([(x, y - 5), (x + 14, y + 5)], color.filled()));
+        }
+    }
+
+    if spec.series.iter().any(|series| !series.name.is_empty()) {
+        chart
+            .configure_series_labels()
+            .position(SeriesLabelPosition::UpperRight)
+            .background_style(WHITE.mix(0.85))
+            .border_style(BLACK.mix(0.3))
+            .draw()
+            .map_err(draw_error)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sales() -> ChartSpec {
+        ChartSpec {
+            kind: ChartKind::Bar,
+            title: Some("Monthly sales".to_string()),
+            x_label: None,
+            y_label: Some("KRW (millions)".to_string()),
+            labels: vec!["Jan".into(), "Feb".into(), "Mar".into()],
+            series: vec![
+                ChartSeries { name: "2025".into(), values: vec![12.0, 15.5, 9.0], x: None },
+                ChartSeries { name: "2026".into(), values: vec![14.0, 18.0, 21.0], x: None },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_validate_and_render() {
+        assert!(sales().validate().is_ok());
+
+        let mut bad = sales();
+        bad.series[0].values[1] = f64::NAN;
+        assert!(bad.validate().is_err());
+        let mut bad = sales();
+        bad.series[0].x = Some(vec![1.0, 2.0, 3.0]);
+        assert!(bad.validate().is_err(), "bar charts take labels, and x values are all-or-nothing");
+
+        let svg = render(&sales(), ChartFormat::Svg, None, None).unwrap();
+        assert!(svg.data.starts_with("<svg"));
+        assert!(svg.data.contains("Monthly sales") && svg.data.contains("Feb"));
+        assert_eq!((svg.width, svg.height), (DEFAULT_WIDTH, DEFAULT_HEIGHT));
+
+        let scatter = ChartSpec {
+            kind: ChartKind::Scatter,
+            title: None,
+            x_label: Some("latency".into()),
+            y_label: None,
+            labels: Vec::new(),
+            series: vec![ChartSeries { name: String::new(), values: vec![3.0, 3.0], x: Some(vec![1.0, 1.0]) }],
+        };
+        let png = render(&scatter, ChartFormat::Png, Some(10), Some(5000)).unwrap();
+        assert_eq!((png.width, png.height), (SIZE_RANGE.0, SIZE_RANGE.1));
+        let bytes = base64::engine::general_purpose::STANDARD.decode(png.data).unwrap();
+        assert!(bytes.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn test_charts_attach_to_reply() {
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let service = ChartService::new(db).unwrap();
+
+        let before = chrono::Utc::now().timestamp_millis();
+        let from_tool = service.create(sales(), None, None).unwrap();
+        let other = service.create(sales(), Some("conv_other"), Some("msg_other")).unwrap();
+
+        let attached = service.attach_unassigned(before, "conv_1", "msg_2").unwrap();
+        assert_eq!(attached.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(), vec![from_tool.id.as_str()]);
+        assert_eq!(service.get(&other.id).unwrap().message_id.as_deref(), Some("msg_other"));
+        assert_eq!(service.list_for_conversation("conv_1").unwrap().len(), 1);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = service.export(&from_tool.id, &dir.path().join("sales.svg"), None, None).unwrap();
+        assert!(std::fs::read_to_string(path).unwrap().contains("Monthly sales"));
+
+        service.delete(&from_tool.id).unwrap();
+        assert!(service.get(&from_tool.id).is_err());
+    }
+}
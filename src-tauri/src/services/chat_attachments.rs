@@ -0,0 +1,151 @@
+//! Chat Image Attachments (v3.9.0)
+//!
+//! Images sent along with a chat message, described by the visual analyzer.
+//!
+//! Features:
+//! - Attachments as base64 data (a `data:` URL is accepted) or a file path
+//! - LLaVA descriptions appended to the message the model answers
+//! - One RAG episode per image, so previously shared images can be recalled
+
+use crate::services::visual_analyzer::{VisualAnalysis, VisualAnalyzerService};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex as TokioMutex;
+
+/// Images accepted per message (LLaVA loads once per image)
+pub const MAX_IMAGES: usize = 4;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageAttachment {
+    /// Shown to the model and in recalled memories, e.g. "screenshot.png"
+    pub name: Option<String>,
+    /// Base64 image data
+    pub data: Option<String>,
+    /// Image file on disk, used when `data` is not set
+    pub path: Option<String>,
+}
+
+impl ImageAttachment {
+    /// `name`, else the file name of `path`, else "image N"
+    pub fn display_name(&self, index: usize) -> String {
+        self.name
+            .clone()
+            .filter(|name| !name.trim().is_empty())
+            .or_else(|| {
+                let path = self.path.as_deref()?;
+                let file_name = std::path::Path::new(path).file_name()?;
+                Some(file_name.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| format!("image {}", index + 1))
+    }
+}
+
+/// An attachment with its analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DescribedImage {
+    pub name: String,
+    pub analysis: VisualAnalysis,
+}
+
+/// Strip a `data:image/png;base64,` prefix
+fn strip_data_url(data: &str) -> &str {
+    match data.split_once(";base64,") {
+        Some((prefix, payload)) if prefix.starts_with("data:") => payload,
+        _ => data,
+    }
+}
+
+/// What the model (and later retrieval) learns about an image
+pub fn describe(image: &DescribedImage) -> String {
+    let analysis = &image.analysis;
+    let content_type = format!("{:?}", analysis.content_type).to_lowercase();
+    let mut text = format!("Image \"{}\" ({}): {}", image.name, content_type, analysis.description.trim());
+    if let Some(extracted) = analysis.extracted_text.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        text.push_str(&format!("\nVisible text: {}", extracted));
+    }
+    for snippet in &analysis.code_snippets {
+        text.push_str(&format!("\nCode ({}):\n{}", snippet.language, snippet.code));
+    }
+    if !analysis.errors.is_empty() {
+        text.push_str(&format!("\nErrors: {}", analysis.errors.join("; ")));
+    }
+    text
+}
+
+/// The user's message with the image descriptions the model answers from
+pub fn augment_message(message: &str, images: &[DescribedImage]) -> String {
+    if images.is_empty() {
+        return message.to_string();
+    }
+    let descriptions: Vec<String> = images.iter().map(describe).collect();
+    format!(
+        "{}\n\n# Attached images\n(Described by the vision model; the user can see them.)\n{}",
+        message,
+        descriptions.join("\n\n")
+    )
+}
+
+/// User side of the RAG episode stored for an image
+pub fn memory_label(name: &str, message: &str) -> String {
+    format!("[Image: {}] {}", name, message)
+}
+
+/// Analyze each attachment with the user's message as the question
+pub async fn analyze_all(
+    analyzer: &TokioMutex<VisualAnalyzerService>,
+    attachments: &[ImageAttachment],
+    question: &str,
+) -> Result<Vec<DescribedImage>> {
+    if attachments.len() > MAX_IMAGES {
+        return Err(anyhow!("At most {} images can be attached to a message", MAX_IMAGES));
+    }
+
+    let question = Some(question).filter(|q| !q.trim().is_empty());
+    let analyzer = analyzer.lock().await;
+    let mut images = Vec::with_capacity(attachments.len());
+    for (index, attachment) in attachments.iter().enumerate() {
+        let name = attachment.display_name(index);
+        let mut analysis = match (&attachment.data, &attachment.path) {
+            (Some(data), _) => analyzer.analyze_base64(strip_data_url(data), question).await,
+            (None, Some(path)) => analyzer.analyze(path, question).await,
+            (None, None) => Err(anyhow!("no image data or path")),
+        }
+        .map_err(|e| anyhow!("{}: {}", name, e))?;
+        analysis.image_path = attachment.path.clone().unwrap_or_else(|| name.clone());
+        images.push(DescribedImage { name, analysis });
+    }
+    log::info!("Described {} attached images", images.len());
+    Ok(images)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::visual_analyzer::{CodeSnippet, VisualContentType};
+
+    #[test]
+    fn test_augment_message() {
+        let image = DescribedImage {
+            name: ImageAttachment { path: Some("/tmp/shots/error.png".into()), ..Default::default() }.display_name(0),
+            analysis: VisualAnalysis {
+                content_type: VisualContentType::Error,
+                description: "A compiler error in a terminal ".to_string(),
+                extracted_text: Some("error[E0382]: use of moved value".to_string()),
+                code_snippets: vec![CodeSnippet { language: "rust".into(), code: "let b = a;".into(), line_numbers: None }],
+                errors: vec!["use of moved value".to_string()],
+                confidence: 0.9,
+                timestamp: 0,
+                image_path: String::new(),
+            },
+        };
+
+        let text = augment_message("What's in this screenshot?", std::slice::from_ref(&image));
+        assert!(text.starts_with("What's in this screenshot?\n\n# Attached images\n"));
+        assert!(text.contains("Image \"error.png\" (error): A compiler error in a terminal\nVisible text: error[E0382]"));
+        assert!(text.contains("Code (rust):\nlet b = a;\nErrors: use of moved value"));
+        assert_eq!(augment_message("hi", &[]), "hi");
+        assert_eq!(strip_data_url("data:image/png;base64,iVBOR"), "iVBOR");
+        assert_eq!(ImageAttachment::default().display_name(1), "image 2");
+    }
+}
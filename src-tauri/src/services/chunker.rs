@@ -0,0 +1,473 @@
+//! Chunking Strategies for RAG Ingestion (v3.9.0)
+//!
+//! Splits documents and long conversation turns into retrieval-sized chunks
+//! before embedding.
+//!
+//! Features:
+//! - Fixed token windows, sentence packing, recursive separator splitting and
+//!   semantic (embedding breakpoint) chunking
+//! - Token overlap between consecutive chunks
+//! - Per-source settings so code, markdown, prose and conversations are
+//!   chunked differently
+//! - Chunks keep byte offsets into the original text
+
+#![allow(dead_code)]  // Phase 5: Chunking for RAG ingestion
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Rough tokens per whitespace-separated word (matches attention_sink)
+const TOKENS_PER_WORD: f32 = 1.3;
+
+/// Chunking strategy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkStrategy {
+    /// Fixed-size token windows
+    FixedTokens,
+    /// Whole sentences packed up to the chunk size
+    Sentence,
+    /// Split on the coarsest separator that fits (paragraphs, lines, words)
+    Recursive,
+    /// Sentences grouped until adjacent-sentence similarity drops
+    Semantic,
+}
+
+/// Kind of text being ingested
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceKind {
+    Conversation,
+    Prose,
+    Markdown,
+    Code,
+}
+
+impl SourceKind {
+    pub const ALL: [SourceKind; 4] = [
+        SourceKind::Conversation,
+        SourceKind::Prose,
+        SourceKind::Markdown,
+        SourceKind::Code,
+    ];
+
+    /// Guess from a file name, falling back to the content
+    pub fn detect(name: &str, text: &str) -> Self {
+        let extension = name.rsplit_once('.').map(|(_, ext)| ext.to_lowercase());
+        match extension.as_deref() {
+            Some("md" | "markdown" | "mdx") => SourceKind::Markdown,
+            Some(
+                "rs" | "py" | "js" | "jsx" | "ts" | "tsx" | "go" | "java" | "kt" | "swift" | "c"
+                | "h" | "cpp" | "hpp" | "cs" | "rb" | "php" | "sh" | "sql" | "toml" | "yaml"
+                | "yml" | "json",
+            ) => SourceKind::Code,
+            Some("txt" | "rtf" | "html" | "htm") => SourceKind::Prose,
+            _ if looks_like_code(text) => SourceKind::Code,
+            _ => SourceKind::Prose,
+        }
+    }
+
+    /// Separators for recursive splitting, coarsest first
+    fn separators(&self) -> &'static [&'static str] {
+        match self {
+            SourceKind::Code => &["\n\n\n", "\n\n", "\n", " "],
+            SourceKind::Markdown => &["\n# ", "\n## ", "\n### ", "\n\n", "\n", " "],
+            SourceKind::Prose | SourceKind::Conversation => &["\n\n", "\n", " "],
+        }
+    }
+}
+
+/// Share of lines ending like code statements
+fn looks_like_code(text: &str) -> bool {
+    let lines: Vec<&str> = text.lines().map(str::trim_end).filter(|l| !l.is_empty()).collect();
+    if lines.len() < 3 {
+        return false;
+    }
+    let code_lines = lines
+        .iter()
+        .filter(|l| l.ends_with(';') || l.ends_with('{') || l.ends_with('}') || l.ends_with(')'))
+        .count();
+    code_lines * 3 >= lines.len()
+}
+
+/// Chunking settings for one source kind
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkingConfig {
+    pub strategy: ChunkStrategy,
+    /// Target chunk size in (estimated) tokens
+    pub chunk_size: usize,
+    /// Tokens repeated from the end of the previous chunk
+    pub overlap: usize,
+    /// Semantic strategy: start a new chunk below this adjacent-sentence similarity
+    #[serde(default = "default_semantic_threshold")]
+    pub semantic_threshold: f32,
+}
+
+fn default_semantic_threshold() -> f32 {
+    0.75
+}
+
+impl ChunkingConfig {
+    pub fn new(strategy: ChunkStrategy, chunk_size: usize, overlap: usize) -> Self {
+        Self {
+            strategy,
+            chunk_size,
+            overlap,
+            semantic_threshold: default_semantic_threshold(),
+        }
+    }
+}
+
+/// Per-source chunking settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkingSettings {
+    pub conversation: ChunkingConfig,
+    pub prose: ChunkingConfig,
+    pub markdown: ChunkingConfig,
+    pub code: ChunkingConfig,
+}
+
+impl Default for ChunkingSettings {
+    fn default() -> Self {
+        Self {
+            conversation: ChunkingConfig::new(ChunkStrategy::Sentence, 384, 48),
+            prose: ChunkingConfig::new(ChunkStrategy::Sentence, 320, 48),
+            markdown: ChunkingConfig::new(ChunkStrategy::Recursive, 320, 32),
+            code: ChunkingConfig::new(ChunkStrategy::Recursive, 256, 32),
+        }
+    }
+}
+
+impl ChunkingSettings {
+    pub fn for_source(&self, source: SourceKind) -> &ChunkingConfig {
+        match source {
+            SourceKind::Conversation => &self.conversation,
+            SourceKind::Prose => &self.prose,
+            SourceKind::Markdown => &self.markdown,
+            SourceKind::Code => &self.code,
+        }
+    }
+}
+
+/// One chunk of the original text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chunk {
+    pub index: usize,
+    pub text: String,
+    /// Byte offsets into the original text
+    pub start: usize,
+    pub end: usize,
+    pub tokens: usize,
+}
+
+/// Token estimate used for chunk sizing
+pub fn estimate_tokens(text: &str) -> usize {
+    (text.split_whitespace().count() as f32 * TOKENS_PER_WORD).ceil() as usize
+}
+
+/// Byte span of the original text
+type Span = (usize, usize);
+
+/// Chunk text with a non-semantic strategy (semantic falls back to sentences)
+pub fn chunk_text(text: &str, source: SourceKind, config: &ChunkingConfig) -> Vec<Chunk> {
+    let size = config.chunk_size.max(1);
+    let spans = match config.strategy {
+        ChunkStrategy::FixedTokens => word_spans(text, 0, text.len()),
+        ChunkStrategy::Sentence | ChunkStrategy::Semantic => {
+            split_oversized(text, sentence_spans(text), source, size)
+        }
+        ChunkStrategy::Recursive => recursive_spans(text, 0, text.len(), source.separators(), size),
+    };
+    pack(text, &spans, size, config.overlap)
+}
+
+/// Chunk text, using `embed` for the semantic strategy
+///
+/// Other strategies ignore the embedder; if embedding fails the sentence
+/// strategy is used instead.
+pub fn chunk_text_with_embedder<F>(
+    text: &str,
+    source: SourceKind,
+    config: &ChunkingConfig,
+    embed: F,
+) -> Vec<Chunk>
+where
+    F: Fn(&str) -> Result<Vec<f32>>,
+{
+    if config.strategy != ChunkStrategy::Semantic {
+        return chunk_text(text, source, config);
+    }
+
+    let size = config.chunk_size.max(1);
+    let spans = split_oversized(text, sentence_spans(text), source, size);
+    let embeddings: Result<Vec<Vec<f32>>> = spans.iter().map(|&(s, e)| embed(&text[s..e])).collect();
+    let embeddings = match embeddings {
+        Ok(embeddings) => embeddings,
+        Err(e) => {
+            log::warn!("Semantic chunking failed, using sentence chunking: {}", e);
+            return chunk_text(text, source, config);
+        }
+    };
+
+    // Break where a sentence drifts away from the previous one
+    let mut groups: Vec<Vec<Span>> = Vec::new();
+    let mut tokens = 0;
+    for (i, &span) in spans.iter().enumerate() {
+        let span_tokens = estimate_tokens(&text[span.0..span.1]);
+        let drifted = i > 0 && cosine(&embeddings[i - 1], &embeddings[i]) < config.semantic_threshold;
+        match groups.last_mut() {
+            Some(group) if !drifted && tokens + span_tokens <= size => {
+                group.push(span);
+                tokens += span_tokens;
+            }
+            _ => {
+                groups.push(vec![span]);
+                tokens = span_tokens;
+            }
+        }
+    }
+
+    let mut chunks = Vec::with_capacity(groups.len());
+    for group in groups {
+        let (start, end) = (group[0].0, group[group.len() - 1].1);
+        push_chunk(&mut chunks, text, start, end);
+    }
+    chunks
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+fn words_for(tokens: usize) -> usize {
+    ((tokens as f32 / TOKENS_PER_WORD).floor() as usize).max(1)
+}
+
+/// Windows of `max_words` words within `[start, end)`
+fn word_windows(text: &str, start: usize, end: usize, max_words: usize) -> Vec<Span> {
+    let words = word_spans(text, start, end);
+    words
+        .chunks(max_words.max(1))
+        .map(|window| (window[0].0, window[window.len() - 1].1))
+        .collect()
+}
+
+fn word_spans(text: &str, start: usize, end: usize) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut word_start: Option<usize> = None;
+    for (offset, c) in text[start..end].char_indices() {
+        let pos = start + offset;
+        if c.is_whitespace() {
+            if let Some(s) = word_start.take() {
+                spans.push((s, pos));
+            }
+        } else if word_start.is_none() {
+            word_start = Some(pos);
+        }
+    }
+    if let Some(s) = word_start {
+        spans.push((s, end));
+    }
+    spans
+}
+
+/// Sentence spans; paragraph breaks also end a sentence
+fn sentence_spans(text: &str) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((pos, c)) = chars.next() {
+        let next = chars.peek().map(|&(_, n)| n);
+        let sentence_end = matches!(c, '.' | '!' | '?' | '。' | '！' | '？')
+            && next.is_none_or(char::is_whitespace);
+        let paragraph_end = c == '\n' && next == Some('\n');
+        if sentence_end || paragraph_end {
+            let end = pos + c.len_utf8();
+            if !text[start..end].trim().is_empty() {
+                spans.push((start, end));
+            }
+            start = end;
+        }
+    }
+    if !text[start..].trim().is_empty() {
+        spans.push((start, text.len()));
+    }
+    spans
+}
+
+/// Split spans larger than the chunk size with the recursive splitter
+fn split_oversized(text: &str, spans: Vec<Span>, source: SourceKind, size: usize) -> Vec<Span> {
+    spans
+        .into_iter()
+        .flat_map(|(s, e)| {
+            if estimate_tokens(&text[s..e]) <= size {
+                vec![(s, e)]
+            } else {
+                recursive_spans(text, s, e, source.separators(), size)
+            }
+        })
+        .collect()
+}
+
+/// Split `[start, end)` on the coarsest separator whose pieces fit
+///
+/// Pieces start at their separator, so headings stay with their section.
+fn recursive_spans(text: &str, start: usize, end: usize, separators: &[&str], size: usize) -> Vec<Span> {
+    if estimate_tokens(&text[start..end]) <= size {
+        return vec![(start, end)];
+    }
+    let Some((separator, rest)) = separators.split_first() else {
+        return word_windows(text, start, end, words_for(size));
+    };
+
+    let slice = &text[start..end];
+    let mut cuts: Vec<usize> = slice
+        .match_indices(separator)
+        .map(|(offset, _)| start + offset)
+        .filter(|&pos| pos > start)
+        .collect();
+    if cuts.is_empty() {
+        return recursive_spans(text, start, end, rest, size);
+    }
+    cuts.insert(0, start);
+    cuts.push(end);
+
+    cuts.windows(2)
+        .flat_map(|w| recursive_spans(text, w[0], w[1], rest, size))
+        .collect()
+}
+
+/// Greedily pack spans into chunks, carrying trailing spans as overlap
+///
+/// Sizes are compared in words so single-word spans don't round up.
+fn pack(text: &str, spans: &[Span], size: usize, overlap: usize) -> Vec<Chunk> {
+    let tokens: Vec<usize> = spans
+        .iter()
+        .map(|&(s, e)| text[s..e].split_whitespace().count())
+        .collect();
+    let size = words_for(size);
+    let overlap = (overlap as f32 / TOKENS_PER_WORD).floor() as usize;
+    let mut chunks = Vec::new();
+    let mut first = 0;
+
+    while first < spans.len() {
+        // Take spans while they fit (always at least one)
+        let mut last = first;
+        let mut total = tokens[first];
+        while last + 1 < spans.len() && total + tokens[last + 1] <= size {
+            last += 1;
+            total += tokens[last];
+        }
+        push_chunk(&mut chunks, text, spans[first].0, spans[last].1);
+
+        if last + 1 >= spans.len() {
+            break;
+        }
+
+        // Next chunk starts with the trailing spans that fit in the overlap,
+        // but always moves forward
+        let mut next = last + 1;
+        let mut carried = 0;
+        while next > first + 1 && carried + tokens[next - 1] <= overlap {
+            next -= 1;
+            carried += tokens[next];
+        }
+        first = next;
+    }
+    chunks
+}
+
+/// Add a chunk with surrounding whitespace trimmed (offsets adjusted)
+fn push_chunk(chunks: &mut Vec<Chunk>, text: &str, start: usize, end: usize) {
+    let raw = &text[start..end];
+    let trimmed_start = raw.len() - raw.trim_start().len();
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return;
+    }
+    let start = start + trimmed_start;
+    chunks.push(Chunk {
+        index: chunks.len(),
+        text: trimmed.to_string(),
+        start,
+        end: start + trimmed.len(),
+        tokens: estimate_tokens(trimmed),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(n: usize) -> String {
+        (0..n).map(|i| format!("w{}", i)).collect::<Vec<_>>().join(" ")
+    }
+
+    #[test]
+    fn test_fixed_token_overlap() {
+        let text = words(100);
+        // 13 tokens = 10 words per window, 3 tokens overlap = 2 words
+        let config = ChunkingConfig::new(ChunkStrategy::FixedTokens, 13, 3);
+        let chunks = chunk_text(&text, SourceKind::Prose, &config);
+
+        assert!(chunks.len() > 10);
+        assert!(chunks[0].text.starts_with("w0 ") && chunks[0].text.ends_with(" w9"));
+        assert!(chunks[1].text.starts_with("w8 "));
+        assert_eq!(&text[chunks[1].start..chunks[1].end], chunks[1].text);
+        assert!(chunks.last().unwrap().text.ends_with("w99"));
+    }
+
+    #[test]
+    fn test_sentence_chunks_keep_sentences_whole() {
+        let text = "Rust is fast. It has no GC! Is it hard? Sometimes.\n\nNew paragraph here.";
+        let config = ChunkingConfig::new(ChunkStrategy::Sentence, 8, 0);
+        let chunks = chunk_text(text, SourceKind::Prose, &config);
+
+        assert!(chunks.len() >= 2);
+        for chunk in &chunks {
+            assert!(chunk.text.ends_with('.') || chunk.text.ends_with('!') || chunk.text.ends_with('?'));
+        }
+        assert_eq!(chunks.last().unwrap().text, "New paragraph here.");
+    }
+
+    #[test]
+    fn test_recursive_code_splits_on_blank_lines() {
+        let function = |name: &str| format!("fn {}() {{\n    let x = 1;\n    let y = 2;\n    x + y\n}}", name);
+        let text = format!("{}\n\n{}\n\n{}", function("a"), function("b"), function("c"));
+        let config = ChunkingConfig::new(ChunkStrategy::Recursive, 20, 0);
+        let chunks = chunk_text(&text, SourceKind::Code, &config);
+
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[1].text.starts_with("fn b()"));
+        assert!(chunks[1].text.ends_with('}'));
+    }
+
+    #[test]
+    fn test_semantic_breakpoints() {
+        let text = "Cats purr. Cats nap. Rust compiles. Rust borrows.";
+        let config = ChunkingConfig::new(ChunkStrategy::Semantic, 100, 0);
+        let embed = |s: &str| -> Result<Vec<f32>> {
+            Ok(if s.contains("Cats") { vec![1.0, 0.0] } else { vec![0.0, 1.0] })
+        };
+        let chunks = chunk_text_with_embedder(text, SourceKind::Prose, &config, embed);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].text, "Cats purr. Cats nap.");
+        assert_eq!(chunks[1].text, "Rust compiles. Rust borrows.");
+    }
+
+    #[test]
+    fn test_detect_source_kind() {
+        assert_eq!(SourceKind::detect("notes.md", ""), SourceKind::Markdown);
+        assert_eq!(SourceKind::detect("main.rs", ""), SourceKind::Code);
+        assert_eq!(SourceKind::detect("paste", "fn a() {\n  b();\n}\n"), SourceKind::Code);
+        assert_eq!(SourceKind::detect("letter", "Dear Bob,\nThanks.\nSee you."), SourceKind::Prose);
+    }
+}
@@ -0,0 +1,462 @@
+//! Code Sandbox (v3.9.0)
+//!
+//! Lets the agent run JavaScript for real computation, data wrangling and
+//! checking its own answers instead of guessing arithmetic.
+//!
+//! Features:
+//! - Embedded QuickJS engine: a fresh runtime per run, nothing shared between runs
+//! - Memory, stack and wall-clock limits; runaway loops are interrupted
+//! - No network access at all and no file access by default; the user can
+//!   allow reading files below chosen folders (`readFile(path)`)
+//! - `console.log` output and the value of the last expression (as JSON)
+//!   are returned; promises are awaited
+//! - Optional JSON `input` made available to the script as `input`
+//!
+//! Python isn't available - there's no interpreter to embed without a WASI
+//! runtime, so the tool only accepts JavaScript.
+
+#![allow(dead_code)]  // Phase 5: Code sandbox
+
+use crate::database::Database;
+use anyhow::{anyhow, Result};
+use rquickjs::function::Rest;
+use rquickjs::{Context, Ctx, Exception, Function, Promise, Runtime, Value};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+const CONFIG_KEY: &str = "code_sandbox_config";
+
+const MAX_CODE_BYTES: usize = 256 * 1024;
+const MAX_INPUT_BYTES: usize = 4 * 1024 * 1024;
+
+/// Largest file `readFile` returns
+const MAX_READ_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Limits users can choose (timeout seconds, memory MB)
+const TIMEOUT_RANGE: (u64, u64) = (1, 120);
+const MEMORY_RANGE: (usize, usize) = (8, 1024);
+
+/// QuickJS stack limit (deep recursion fails instead of overflowing the thread)
+const MAX_STACK_BYTES: usize = 1024 * 1024;
+
+/// Stack of the thread a run executes on, room for `MAX_STACK_BYTES` plus the host
+const THREAD_STACK_BYTES: usize = 8 * 1024 * 1024;
+
+/// `console.*` and host functions, installed before the user's code
+const PRELUDE: &str = r#"
+(() => {
+  const emit = globalThis.__emit;
+  delete globalThis.__emit;
+  const show = (v) => {
+    if (typeof v === 'string') return v;
+    if (typeof v === 'function') return '[Function ' + (v.name || 'anonymous') + ']';
+    if (v instanceof Error) return v.stack ? v.name + ': ' + v.message + '\n' + v.stack : String(v);
+    if (typeof v === 'bigint') return v.toString() + 'n';
+    try {
+      const json = JSON.stringify(v, (_, x) => typeof x === 'bigint' ? x.toString() : x, 2);
+      return json === undefined ? String(v) : json;
+    } catch (_) {
+      return String(v);
+    }
+  };
+  const log = (level) => (...args) => emit(level, args.map(show).join(' '));
+  globalThis.console = {
+    log: log('log'), info: log('info'), debug: log('debug'),
+    warn: log('warn'), error: log('error'),
+  };
+  globalThis.print = globalThis.console.log;
+})();
+"#;
+
+/// Sandbox settings, stored in user_preferences
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CodeSandboxConfig {
+    pub enabled: bool,
+    /// Wall-clock limit per run
+    pub timeout_secs: u64,
+    /// Heap limit per run
+    pub memory_limit_mb: usize,
+    /// Characters of console output returned; the rest is dropped
+    pub max_output_chars: usize,
+    /// Folders whose files scripts may read; empty = no file access
+    pub readable_paths: Vec<String>,
+}
+
+impl Default for CodeSandboxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            timeout_secs: 10,
+            memory_limit_mb: 64,
+            max_output_chars: 20_000,
+            readable_paths: Vec::new(),
+        }
+    }
+}
+
+impl CodeSandboxConfig {
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+}
+
+/// Outcome of a run; script errors are reported here, not as `Err`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeRunResult {
+    pub success: bool,
+    /// Last expression value (promises resolved), `None` for undefined
+    pub result: Option<serde_json::Value>,
+    /// What the script logged, one line per call
+    pub output: String,
+    pub output_truncated: bool,
+    pub error: Option<String>,
+    pub timed_out: bool,
+    pub duration_ms: u64,
+}
+
+pub struct CodeSandboxService {
+    db: Arc<Mutex<Database>>,
+    config: RwLock<CodeSandboxConfig>,
+}
+
+impl CodeSandboxService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        let config = {
+            let db = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            load_config(db.conn())?
+        };
+        log::info!("✓ Code Sandbox initialized");
+        Ok(Self { db, config: RwLock::new(config) })
+    }
+
+    pub fn get_config(&self) -> CodeSandboxConfig {
+        self.config.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Clamp the limits and check that readable folders exist
+    pub fn update_config(&self, mut config: CodeSandboxConfig) -> Result<CodeSandboxConfig> {
+        config.timeout_secs = config.timeout_secs.clamp(TIMEOUT_RANGE.0, TIMEOUT_RANGE.1);
+        config.memory_limit_mb = config.memory_limit_mb.clamp(MEMORY_RANGE.0, MEMORY_RANGE.1);
+        config.max_output_chars = config.max_output_chars.clamp(1_000, 1_000_000);
+        config.readable_paths = config
+            .readable_paths
+            .iter()
+            .map(|path| {
+                let canonical = Path::new(path)
+                    .canonicalize()
+                    .map_err(|e| anyhow!("Can't allow '{}': {}", path, e))?;
+                if !canonical.is_dir() {
+                    return Err(anyhow!("'{}' is not a folder", path));
+                }
+                Ok(canonical.to_string_lossy().into_owned())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        config.readable_paths.dedup();
+
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            db.conn().execute(
+                "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+                rusqlite::params![CONFIG_KEY, serde_json::to_string(&config)?, chrono::Utc::now().timestamp()],
+            )?;
+        }
+        *self.config.write().unwrap_or_else(|e| e.into_inner()) = config.clone();
+        log::info!("Code sandbox config updated");
+        Ok(config)
+    }
+
+    /// Run a script with the configured limits (a shorter timeout can be asked for)
+    pub async fn run(&self, code: &str, input: Option<serde_json::Value>, timeout_secs: Option<u64>) -> Result<CodeRunResult> {
+        let mut config = self.get_config();
+        if !config.enabled {
+            return Err(anyhow!("Code execution is turned off in settings"));
+        }
+        if let Some(timeout_secs) = timeout_secs {
+            config.timeout_secs = timeout_secs.clamp(TIMEOUT_RANGE.0, config.timeout_secs);
+        }
+
+        let code = code.to_string();
+        let (sender, receiver) = tokio::sync::oneshot::channel();
+        // QuickJS runtimes stay on the thread that made them; a dedicated
+        // thread also gives recursion-heavy scripts a known stack size
+        std::thread::Builder::new()
+            .name("code-sandbox".to_string())
+            .stack_size(THREAD_STACK_BYTES)
+            .spawn(move || {
+                let _ = sender.send(execute(&code, input.as_ref(), &config));
+            })?;
+        receiver.await.map_err(|_| anyhow!("Code sandbox thread stopped unexpectedly"))?
+    }
+}
+
+fn load_config(conn: &Connection) -> Result<CodeSandboxConfig> {
+    let json: Option<String> = conn
+        .query_row("SELECT value FROM user_preferences WHERE key = ?1", [CONFIG_KEY], |row| row.get(0))
+        .optional()?;
+    match json {
+        Some(json) => Ok(serde_json::from_str(&json)?),
+        None => Ok(CodeSandboxConfig::default()),
+    }
+}
+
+/// Collected console output
+#[derive(Default)]
+struct Output {
+    text: String,
+    chars: usize,
+    truncated: bool,
+}
+
+impl Output {
+    fn push(&mut self, level: &str, line: &str, limit: usize) {
+        if self.truncated {
+            return;
+        }
+        let prefix = match level {
+            "warn" => "[warn] ",
+            "error" => "[error] ",
+            _ => "",
+        };
+        let line = format!("{}{}\n", prefix, line);
+        let chars = line.chars().count();
+        if self.chars + chars > limit {
+            self.text.extend(line.chars().take(limit.saturating_sub(self.chars)));
+            self.truncated = true;
+        } else {
+            self.text.push_str(&line);
+        }
+        self.chars += chars;
+    }
+}
+
+/// Run a script in a new runtime on the current thread
+pub fn execute(code: &str, input: Option<&serde_json::Value>, config: &CodeSandboxConfig) -> Result<CodeRunResult> {
+    if code.trim().is_empty() {
+        return Err(anyhow!("No code to run"));
+    }
+    if code.len() > MAX_CODE_BYTES {
+        return Err(anyhow!("Code is too long ({} KB max)", MAX_CODE_BYTES / 1024));
+    }
+    let input_json = input.map(serde_json::to_string).transpose()?;
+    if input_json.as_ref().is_some_and(|json| json.len() > MAX_INPUT_BYTES) {
+        return Err(anyhow!("Input is too large ({} MB max)", MAX_INPUT_BYTES / 1024 / 1024));
+    }
+
+    let started = Instant::now();
+    let runtime = Runtime::new().map_err(|e| anyhow!("Failed to start JavaScript runtime: {}", e))?;
+    runtime.set_memory_limit(config.memory_limit_mb * 1024 * 1024);
+    runtime.set_max_stack_size(MAX_STACK_BYTES);
+
+    let timed_out = Arc::new(AtomicBool::new(false));
+    {
+        let deadline = started + config.timeout();
+        let timed_out = Arc::clone(&timed_out);
+        runtime.set_interrupt_handler(Some(Box::new(move || {
+            let expired = Instant::now() >= deadline;
+            if expired {
+                timed_out.store(true, Ordering::Relaxed);
+            }
+            expired
+        })));
+    }
+    let context = Context::full(&runtime).map_err(|e| anyhow!("Failed to create JavaScript context: {}", e))?;
+
+    let output = Rc::new(RefCell::new(Output::default()));
+    let readable: Vec<PathBuf> = config.readable_paths.iter().map(PathBuf::from).collect();
+    let max_output_chars = config.max_output_chars;
+
+    let outcome: std::result::Result<Option<serde_json::Value>, String> = context.with(|ctx| {
+        install_globals(&ctx, Rc::clone(&output), max_output_chars, readable, input_json.as_deref())
+            .map_err(|e| describe_error(&ctx, e))?;
+
+        let value: Value = ctx.eval(code.as_bytes()).map_err(|e| describe_error(&ctx, e))?;
+        let value = match value.as_promise() {
+            Some(promise) => resolve(&ctx, promise).map_err(|e| describe_error(&ctx, e))?,
+            None => value,
+        };
+        to_json(&ctx, value).map_err(|e| describe_error(&ctx, e))
+    });
+
+    let timed_out = timed_out.load(Ordering::Relaxed);
+    let output = Rc::try_unwrap(output).map(RefCell::into_inner).unwrap_or_else(|rc| {
+        let mut output = rc.borrow_mut();
+        std::mem::take(&mut *output)
+    });
+    let (result, error) = match outcome {
+        Ok(result) => (result, None),
+        Err(_) if timed_out => (None, Some(format!("Timed out after {} s", config.timeout_secs))),
+        Err(error) => (None, Some(error)),
+    };
+
+    Ok(CodeRunResult {
+        success: error.is_none(),
+        result,
+        output: output.text,
+        output_truncated: output.truncated,
+        error,
+        timed_out,
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+fn install_globals<'js>(
+    ctx: &Ctx<'js>,
+    output: Rc<RefCell<Output>>,
+    max_output_chars: usize,
+    readable: Vec<PathBuf>,
+    input_json: Option<&str>,
+) -> rquickjs::Result<()> {
+    let globals = ctx.globals();
+    globals.set(
+        "__emit",
+        Function::new(ctx.clone(), move |level: String, line: String| {
+            output.borrow_mut().push(&level, &line, max_output_chars);
+        })?,
+    )?;
+    ctx.eval::<(), _>(PRELUDE)?;
+
+    if !readable.is_empty() {
+        globals.set(
+            "readFile",
+            Function::new(ctx.clone(), move |ctx: Ctx<'js>, path: String| -> rquickjs::Result<String> {
+                read_allowed_file(&readable, &path).map_err(|e| Exception::throw_message(&ctx, &e.to_string()))
+            })?,
+        )?;
+    }
+
+    match input_json {
+        Some(json) => globals.set("input", ctx.json_parse(json)?)?,
+        None => globals.set("input", Value::new_null(ctx.clone()))?,
+    }
+    Ok(())
+}
+
+/// Contents of a text file below one of the allowed folders
+fn read_allowed_file(readable: &[PathBuf], path: &str) -> Result<String> {
+    let canonical = Path::new(path).canonicalize().map_err(|e| anyhow!("Can't read '{}': {}", path, e))?;
+    if !readable.iter().any(|root| canonical.starts_with(root)) {
+        return Err(anyhow!("'{}' is outside the folders the sandbox may read", path));
+    }
+    let size = std::fs::metadata(&canonical)?.len();
+    if size > MAX_READ_BYTES {
+        return Err(anyhow!("'{}' is too large ({} MB max)", path, MAX_READ_BYTES / 1024 / 1024));
+    }
+    std::fs::read_to_string(&canonical).map_err(|e| anyhow!("Can't read '{}': {}", path, e))
+}
+
+/// Drive the job queue until the promise settles
+fn resolve<'js>(ctx: &Ctx<'js>, promise: &Promise<'js>) -> rquickjs::Result<Value<'js>> {
+    match promise.finish::<Value>() {
+        Err(rquickjs::Error::WouldBlock) => {
+            Err(Exception::throw_message(ctx, "The script's promise never settled (timers and I/O aren't available)"))
+        }
+        result => result,
+    }
+}
+
+/// JSON of a value; values JSON can't represent become strings
+fn to_json<'js>(ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Option<serde_json::Value>> {
+    if value.is_undefined() || value.is_function() {
+        return Ok(None);
+    }
+    let json = match ctx.json_stringify(value.clone()) {
+        Ok(Some(json)) => json.to_string()?,
+        _ => {
+            // BigInts, cycles and the like
+            let _ = ctx.catch();
+            let as_string: Function = ctx.globals().get("String")?;
+            let text: String = as_string.call((value,))?;
+            return Ok(Some(serde_json::Value::String(text)));
+        }
+    };
+    Ok(serde_json::from_str(&json).ok())
+}
+
+/// Message (and stack) of the exception behind an error
+fn describe_error(ctx: &Ctx<'_>, error: rquickjs::Error) -> String {
+    match error {
+        rquickjs::Error::Exception => {
+            let thrown = ctx.catch();
+            if let Some(exception) = thrown.as_exception() {
+                let message = exception.message().unwrap_or_default();
+                let name: String = exception.get("name").unwrap_or_else(|_| "Error".to_string());
+                match exception.stack().filter(|s| !s.trim().is_empty()) {
+                    Some(stack) => format!("{}: {}\n{}", name, message, stack.trim_end()),
+                    None => format!("{}: {}", name, message),
+                }
+            } else if let Some(text) = thrown.as_string().and_then(|s| s.to_string().ok()) {
+                format!("Uncaught {}", text)
+            } else {
+                ctx.json_stringify(thrown)
+                    .ok()
+                    .flatten()
+                    .and_then(|s| s.to_string().ok())
+                    .map(|s| format!("Uncaught {}", s))
+                    .unwrap_or_else(|| "Uncaught exception".to_string())
+            }
+        }
+        rquickjs::Error::Allocation => "Out of memory".to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_execute_script() {
+        let config = CodeSandboxConfig::default();
+        let result = execute(
+            "const xs = input.values; console.log('sum of', xs.length); xs.reduce((a, b) => a + b, 0) / xs.length",
+            Some(&serde_json::json!({ "values": [3, 4, 8] })),
+            &config,
+        )
+        .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.result, Some(serde_json::json!(5)));
+        assert_eq!(result.output, "sum of 3\n");
+
+        let result = execute("(async () => ({ big: (2n ** 70n).toString(), ok: true }))()", None, &config).unwrap();
+        assert_eq!(result.result, Some(serde_json::json!({ "big": "1180591620717411303424", "ok": true })));
+
+        let result = execute("function f() { throw new TypeError('bad input') }\nf()", None, &config).unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().starts_with("TypeError: bad input"));
+
+        // No host access unless granted
+        let result = execute("typeof readFile + typeof fetch + typeof require", None, &config).unwrap();
+        assert_eq!(result.result, Some(serde_json::json!("undefinedundefinedundefined")));
+    }
+
+    #[test]
+    fn test_limits() {
+        let config = CodeSandboxConfig { timeout_secs: 1, memory_limit_mb: 16, ..Default::default() };
+        let result = execute("while (true) {}", None, &config).unwrap();
+        assert!(result.timed_out && !result.success);
+
+        let result = execute("const a = []; while (true) a.push(new Array(10000).fill(1));", None, &config).unwrap();
+        assert!(!result.success && !result.timed_out, "{:?}", result.error);
+
+        let dir = std::env::temp_dir().join(format!("code_sandbox_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("data.csv"), "a,b\n1,2\n").unwrap();
+        let config = CodeSandboxConfig {
+            readable_paths: vec![dir.canonicalize().unwrap().to_string_lossy().into_owned()],
+            ..Default::default()
+        };
+        let path = serde_json::to_string(&dir.join("data.csv")).unwrap();
+        let result = execute(&format!("readFile({}).split('\\n')[1]", path), None, &config).unwrap();
+        assert_eq!(result.result, Some(serde_json::json!("1,2")));
+        let result = execute("readFile('/etc/passwd')", None, &config).unwrap();
+        assert!(result.error.unwrap().contains("outside"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,4 +1,6 @@
 use crate::services::{screen::ScreenCaptureService, llava::LlavaService};
+use crate::services::degradation::ServiceSlot;
+use crate::services::vision_backend::VisionTask;
 use anyhow::{Context, Result, anyhow};
 use enigo::{Enigo, Mouse, Keyboard, Button as EnigoButton, Coordinate, Direction};
 use rdev::{simulate, EventType, Key as RdevKey};
@@ -105,7 +107,7 @@ impl Default for SafetyConfig {
 /// Note: Enigo is not Send on macOS, so we recreate it for each operation
 pub struct ComputerControlService {
     screen_service: Arc<ScreenCaptureService>,
-    llava_service: Arc<LlavaService>,
+    llava_service: Arc<ServiceSlot<LlavaService>>,  // v3.9.0: Unavailable vision fails element lookup only
     safety_config: SafetyConfig,
     pub db: Arc<Mutex<Connection>>,  // Public for testing
 }
@@ -114,7 +116,7 @@ impl ComputerControlService {
     /// Create a new ComputerControlService
     pub fn new(
         screen_service: Arc<ScreenCaptureService>,
-        llava_service: Arc<LlavaService>,
+        llava_service: Arc<ServiceSlot<LlavaService>>,
         db: Arc<Mutex<Connection>>,
     ) -> Result<Self> {
         let service = Self {
@@ -193,7 +195,11 @@ impl ComputerControlService {
             description
         );
 
-        let analysis = self.llava_service.analyze_image(screenshot_before.clone(), Some(prompt)).await
+        let analysis = self.llava_service
+            .get()
+            .map_err(|e| anyhow!(e))?
+            .analyze_image_for_task(screenshot_before.clone(), Some(prompt), VisionTask::Fast)
+            .await
             .context("Failed to analyze image with LLaVA")?;
 
         // 3. Parse bounding box
@@ -242,6 +248,12 @@ impl ComputerControlService {
 
     /// Type text at current cursor position
     pub async fn type_text(&self, text: &str) -> Result<ActionResult> {
+        self.type_text_described(text, &format!("Type: {}", text)).await
+    }
+
+    /// Type text, logging `description` instead of the text itself
+    /// (used for values taken from memory, v3.9.0)
+    pub async fn type_text_described(&self, text: &str, description: &str) -> Result<ActionResult> {
         let start = Instant::now();
 
         let screenshot_before = self.capture_screen_simple().await.ok();
@@ -261,7 +273,7 @@ impl ComputerControlService {
         let result = ActionResult {
             success: true,
             action_type: ActionType::Type,
-            target_description: Some(format!("Type: {}", text)),
+            target_description: Some(description.to_string()),
             coordinates: None,
             execution_time_ms: execution_time,
             error: None,
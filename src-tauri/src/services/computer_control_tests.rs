@@ -14,6 +14,7 @@ mod lam_phase1_tests {
     use super::super::computer_control::*;
     use super::super::screen::ScreenCaptureService;
     use super::super::llava::LlavaService;
+    use super::super::degradation::ServiceSlot;
     use crate::database::Database;
     use rusqlite::Connection;
     use std::sync::{Arc, Mutex};
@@ -32,7 +33,7 @@ mod lam_phase1_tests {
     fn create_test_service() -> Result<ComputerControlService, anyhow::Error> {
         let (db_arc, conn_arc) = create_test_db();
         let screen_service = Arc::new(ScreenCaptureService::new(db_arc));
-        let llava_service = Arc::new(LlavaService::new().unwrap());
+        let llava_service = ServiceSlot::init("LLaVA Vision Service", || Ok(Arc::new(LlavaService::new()?)));
 
         ComputerControlService::new(screen_service, llava_service, conn_arc)
     }
@@ -226,6 +227,7 @@ mod lam_tools_tests {
     fn create_mock_service() -> Arc<ComputerControlService> {
         use super::super::screen::ScreenCaptureService;
         use super::super::llava::LlavaService;
+        use super::super::degradation::ServiceSlot;
         use crate::database::Database;
         use rusqlite::Connection;
         use std::sync::Mutex;
@@ -234,7 +236,7 @@ mod lam_tools_tests {
         let conn_arc = Arc::new(Mutex::new(Connection::open_in_memory().unwrap()));
 
         let screen_service = Arc::new(ScreenCaptureService::new(db_arc));
-        let llava_service = Arc::new(LlavaService::new().unwrap());
+        let llava_service = ServiceSlot::init("LLaVA Vision Service", || Ok(Arc::new(LlavaService::new()?)));
 
         Arc::new(ComputerControlService::new(screen_service, llava_service, conn_arc).unwrap())
     }
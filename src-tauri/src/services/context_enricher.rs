@@ -9,6 +9,8 @@
  * 3. Recent visual analyses (if available)
  * 4. Temporal context (time of day, day of week)
  * 5. RAG-retrieved relevant memories
+ * 6. Semantic wiki facts, preferring high effective confidence (v3.9.0)
+ * 7. Active project: languages, build systems, recent files (v3.9.0)
  *
  * Features:
  * - Multi-source context aggregation
@@ -25,12 +27,15 @@
 use crate::database::Database;
 use crate::services::active_window::ActiveWindowService;
 use crate::services::visual_analyzer::VisualAnalyzerService;
+use crate::services::provenance::{Provenance, ProvenanceSource};
+use crate::services::semantic_wiki::SemanticWikiService;
+use crate::services::workspace::WorkspaceService;
 #[cfg(feature = "lancedb-support")]
 use crate::services::rag_v2::RagServiceV2;  // v3.4.0: LanceDB migration
 use anyhow::{Context, Result};
 use chrono::{Datelike, Timelike};
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 use tokio::sync::Mutex as TokioMutex;
 
 /// Enriched context for a query
@@ -66,6 +71,10 @@ pub struct ContextPiece {
 
     /// Priority (higher = more important)
     pub priority: u8,
+
+    /// Where the snippet came from (v3.9.0, stored sources only)
+    #[serde(default)]
+    pub provenance: Option<Provenance>,
 }
 
 /// Source of context
@@ -82,6 +91,10 @@ pub enum ContextSource {
     Temporal,
     /// RAG-retrieved memories
     Memory,
+    /// Semantic wiki facts
+    Knowledge,
+    /// Active project workspace
+    Workspace,
 }
 
 /// Configuration for context enricher
@@ -104,6 +117,22 @@ pub struct ContextEnricherConfig {
 
     /// Number of RAG memories to retrieve
     pub rag_memory_limit: usize,
+
+    /// Number of wiki facts to include (v3.9.0)
+    #[serde(default = "default_wiki_fact_limit")]
+    pub wiki_fact_limit: usize,
+
+    /// Whether to include the active project (v3.9.0)
+    #[serde(default = "default_include_workspace")]
+    pub include_workspace: bool,
+}
+
+fn default_wiki_fact_limit() -> usize {
+    3
+}
+
+fn default_include_workspace() -> bool {
+    true
 }
 
 impl Default for ContextEnricherConfig {
@@ -115,6 +144,8 @@ impl Default for ContextEnricherConfig {
             include_active_window: true,
             include_temporal: true,
             rag_memory_limit: 3,
+            wiki_fact_limit: default_wiki_fact_limit(),
+            include_workspace: default_include_workspace(),
         }
     }
 }
@@ -125,6 +156,8 @@ pub struct ContextEnricherService {
     active_window: ActiveWindowService,
     visual_analyzer: Option<Arc<TokioMutex<VisualAnalyzerService>>>,
     rag: Arc<RagServiceV2>,  // v3.4.0: LanceDB
+    wiki: OnceLock<Arc<SemanticWikiService>>,  // v3.9.0: Attached after construction
+    workspace: OnceLock<Arc<WorkspaceService>>,  // v3.9.0: Attached after construction
     config: Arc<Mutex<ContextEnricherConfig>>,
 }
 
@@ -146,10 +179,22 @@ impl ContextEnricherService {
             active_window,
             visual_analyzer,
             rag,
+            wiki: OnceLock::new(),
+            workspace: OnceLock::new(),
             config: Arc::new(Mutex::new(ContextEnricherConfig::default())),
         })
     }
 
+    /// Attach the semantic wiki as a fact source
+    pub fn attach_wiki(&self, wiki: Arc<SemanticWikiService>) {
+        let _ = self.wiki.set(wiki);
+    }
+
+    /// Attach the workspace service as the active project source
+    pub fn attach_workspace(&self, workspace: Arc<WorkspaceService>) {
+        let _ = self.workspace.set(workspace);
+    }
+
     /// Enrich a user query with context
     ///
     /// # Arguments
@@ -182,6 +227,13 @@ impl ContextEnricherService {
             }
         }
 
+        // 2b. Active project
+        if config.include_workspace {
+            if let Some(workspace_ctx) = self.get_workspace_context().await {
+                context_pieces.push(workspace_ctx);
+            }
+        }
+
         // 3. Conversation history
         if let Some(conv_id) = conversation_id {
             let history = self.get_conversation_context(conv_id, config.conversation_history_limit)?;
@@ -192,6 +244,10 @@ impl ContextEnricherService {
         let memories = self.get_rag_context(query, config.rag_memory_limit).await?;
         context_pieces.extend(memories);
 
+        // 4b. Wiki facts (high-confidence first)
+        let facts = self.get_wiki_context(query, config.wiki_fact_limit).await;
+        context_pieces.extend(facts);
+
         // 5. Visual context (if available and enabled)
         if config.include_visual && self.visual_analyzer.is_some() {
             if let Some(visual) = self.get_visual_context().await {
@@ -267,6 +323,7 @@ impl ContextEnricherService {
             content,
             relevance: 0.3, // Low relevance, but always included
             priority: 1,
+            provenance: None,
         })
     }
 
@@ -285,6 +342,7 @@ impl ContextEnricherService {
                     content,
                     relevance: 0.6, // Medium-high relevance
                     priority: 3,
+                    provenance: None,
                 })
             }
             Err(e) => {
@@ -294,6 +352,31 @@ impl ContextEnricherService {
         }
     }
 
+    /// Get active project context
+    async fn get_workspace_context(&self) -> Option<ContextPiece> {
+        let workspace = Arc::clone(self.workspace.get()?);
+
+        // Detection may scan the project tree
+        match tokio::task::spawn_blocking(move || workspace.current()).await {
+            Ok(Ok(Some(workspace))) => Some(ContextPiece {
+                source: ContextSource::Workspace,
+                content: workspace.summary(),
+                relevance: 0.6,
+                priority: 3,
+                provenance: None,
+            }),
+            Ok(Ok(None)) => None,
+            Ok(Err(e)) => {
+                log::debug!("Could not detect workspace: {}", e);
+                None
+            }
+            Err(e) => {
+                log::warn!("Workspace detection task failed: {}", e);
+                None
+            }
+        }
+    }
+
     /// Get conversation history context
     fn get_conversation_context(
         &self,
@@ -304,15 +387,15 @@ impl ContextEnricherService {
         let conn = db.conn();
 
         let mut stmt = conn.prepare(
-            "SELECT role, content FROM messages
-             WHERE conversation_id = ?1
-             ORDER BY created_at DESC
+            "SELECT id, role, content, timestamp FROM messages
+             WHERE conversation_id = ?1 AND is_stale = 0
+             ORDER BY timestamp DESC
              LIMIT ?2"
         )?;
 
-        let messages: Vec<(String, String)> = stmt
+        let messages: Vec<(String, String, String, i64)> = stmt
             .query_map([conversation_id, &limit.to_string()], |row| {
-                Ok((row.get(0)?, row.get(1)?))
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
             })?
             .collect::<rusqlite::Result<Vec<_>>>()?;
 
@@ -322,12 +405,17 @@ impl ContextEnricherService {
         let pieces: Vec<ContextPiece> = messages
             .into_iter()
             .enumerate()
-            .map(|(i, (role, content))| {
+            .map(|(i, (message_id, role, content, timestamp))| {
                 ContextPiece {
                     source: ContextSource::Conversation,
                     content: format!("{}: {}", role, content),
                     relevance: 0.7 + (i as f32 * 0.1), // More recent = higher relevance
                     priority: 4,
+                    provenance: Some(
+                        Provenance::new(ProvenanceSource::Message, &message_id)
+                            .with_conversation(Some(conversation_id.to_string()), Some(message_id))
+                            .with_timestamp(timestamp),
+                    ),
                 }
             })
             .collect();
@@ -348,6 +436,7 @@ impl ContextEnricherService {
                             content,
                             relevance: score,
                             priority: 2,
+                            provenance: Some(episode.provenance()),
                         }
                     })
                     .collect();
@@ -361,6 +450,33 @@ impl ContextEnricherService {
         }
     }
 
+    /// Get wiki fact context, ranked by similarity x effective confidence
+    async fn get_wiki_context(&self, query: &str, limit: usize) -> Vec<ContextPiece> {
+        let Some(wiki) = self.wiki.get() else {
+            return Vec::new();
+        };
+        if limit == 0 {
+            return Vec::new();
+        }
+
+        match wiki.search_for_context(query, limit).await {
+            Ok(results) => results
+                .into_iter()
+                .map(|(fact, score)| ContextPiece {
+                    source: ContextSource::Knowledge,
+                    content: fact.context_text(),
+                    relevance: score,
+                    priority: 2,
+                    provenance: Some(fact.provenance()),
+                })
+                .collect(),
+            Err(e) => {
+                log::warn!("Failed to retrieve wiki facts: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
     /// Get recent visual context
     async fn get_visual_context(&self) -> Option<ContextPiece> {
         if let Some(visual_analyzer) = &self.visual_analyzer {
@@ -381,6 +497,7 @@ impl ContextEnricherService {
                         content,
                         relevance: analysis.confidence,
                         priority: 3,
+                        provenance: None,
                     })
                 }
                 _ => None
@@ -416,6 +533,21 @@ impl ContextEnricherService {
         (total / pieces.len() as f32).clamp(0.0, 1.0)
     }
 
+    /// Cheap query-independent context (time + active window) for background services
+    pub fn ambient_context(&self) -> Vec<ContextPiece> {
+        let config = self.config.lock().unwrap().clone();
+        let mut pieces = Vec::new();
+
+        if config.include_temporal {
+            pieces.extend(self.get_temporal_context());
+        }
+        if config.include_active_window {
+            pieces.extend(self.get_active_window_context());
+        }
+
+        pieces
+    }
+
     /// Update configuration
     pub fn update_config(&self, new_config: ContextEnricherConfig) {
         *self.config.lock().unwrap() = new_config;
@@ -451,12 +583,14 @@ mod tests {
                 content: "low priority".to_string(),
                 relevance: 0.9,
                 priority: 1,
+                provenance: None,
             },
             ContextPiece {
                 source: ContextSource::Conversation,
                 content: "high priority".to_string(),
                 relevance: 0.5,
                 priority: 4,
+                provenance: None,
             },
         ];
 
@@ -0,0 +1,249 @@
+//! Context Inspector (v3.9.0)
+//!
+//! What the model would receive for a conversation's next turn, to debug why
+//! the assistant "forgot" something.
+//!
+//! Features:
+//! - The exact prompt, from the same builder the chat pipeline uses
+//!   (`ollama::build_chat_prompt`), without touching access counts or analytics
+//! - Token estimates per section against the active model's budget
+//! - Every retrieved memory with its score and whether it was sent, fell
+//!   below the relevance threshold or didn't fit the budget
+//! - What exists but isn't sent: earlier messages and the stored summary
+
+use crate::database::Database;
+use crate::services::chunker::estimate_tokens;
+use crate::services::model_context;
+use crate::services::ollama::{self, ChatPrompt};
+use anyhow::{anyhow, Result};
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "lancedb-support")]
+use crate::services::rag_v2::{Episode, RagServiceV2};
+#[cfg(not(feature = "lancedb-support"))]
+use crate::services::rag::{Episode, RagService as RagServiceV2};
+
+/// Why a retrieved memory is or isn't in the prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryStatus {
+    Sent,
+    /// Dropped by the RAFT relevance threshold
+    BelowRelevance,
+    /// Relevant, but the prompt budget ran out
+    OverBudget,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InspectedMemory {
+    pub id: String,
+    pub score: f32,
+    pub user_message: String,
+    pub ai_response: String,
+    pub created_at: i64,
+    pub status: MemoryStatus,
+}
+
+/// One part of the prompt, in prompt order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextSection {
+    /// "persona", "reply_language", "memories" or "user_message"
+    pub name: String,
+    pub content: String,
+    pub tokens: usize,
+}
+
+/// Summary Buffer summary stored for the conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredSummary {
+    pub text: String,
+    pub messages_summarized: i64,
+    pub tokens: usize,
+    /// Always false: the chat prompt carries memories, not the summary
+    pub sent: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextInspection {
+    pub conversation_id: String,
+    /// User message the turn was built for
+    pub message: String,
+    /// `message` is a draft; otherwise the conversation's last user message is replayed
+    pub draft: bool,
+    pub model: String,
+    pub context_window: usize,
+    pub prompt_budget: usize,
+    pub sections: Vec<ContextSection>,
+    pub memories: Vec<InspectedMemory>,
+    /// Exactly what is sent to the model
+    pub prompt: String,
+    pub total_tokens: usize,
+    /// Memory question with nothing relevant remembered: answered without the model
+    pub abstains: bool,
+    pub retrieval_error: Option<String>,
+    /// Earlier messages of the conversation (none are sent)
+    pub history_messages: i64,
+    pub summary: Option<StoredSummary>,
+}
+
+/// Status of every retrieved candidate, best score first
+pub fn classify_memories(candidates: &[(Episode, f32)], relevant: &[Episode], sent: &[Episode]) -> Vec<InspectedMemory> {
+    let mut memories: Vec<InspectedMemory> = candidates
+        .iter()
+        .map(|(episode, score)| {
+            let status = if sent.iter().any(|e| e.id == episode.id) {
+                MemoryStatus::Sent
+            } else if relevant.iter().any(|e| e.id == episode.id) {
+                MemoryStatus::OverBudget
+            } else {
+                MemoryStatus::BelowRelevance
+            };
+            InspectedMemory {
+                id: episode.id.clone(),
+                score: *score,
+                user_message: episode.user_message.clone(),
+                ai_response: episode.ai_response.clone(),
+                created_at: episode.created_at,
+                status,
+            }
+        })
+        .collect();
+    memories.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    memories
+}
+
+fn section(name: &str, content: String) -> ContextSection {
+    ContextSection {
+        name: name.to_string(),
+        tokens: estimate_tokens(&content),
+        content,
+    }
+}
+
+/// Split a built prompt into its sections; concatenated they are `prompt.full_prompt`
+fn prompt_sections(prompt: &ChatPrompt, user_message: &str) -> Vec<ContextSection> {
+    let mut sections = vec![
+        section("persona", prompt.persona.clone()),
+        section("reply_language", prompt.reply_instruction.clone()),
+    ];
+    if !prompt.memory_section.is_empty() {
+        sections.push(section("memories", prompt.memory_section.clone()));
+    }
+    let turn = ollama::format_turn_prompt("", user_message);
+    sections.push(section("user_message", turn));
+    sections
+}
+
+/// Build the next turn of `conversation_id` for `message` (default: replay the last user message)
+pub async fn inspect(
+    conversation_id: &str,
+    message: Option<&str>,
+    rag: Option<&Arc<RagServiceV2>>,
+    db: &Mutex<Database>,
+) -> Result<ContextInspection> {
+    let (last_message, history_messages, summary) = {
+        let db_guard = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let conn = db_guard.conn();
+        let last_message: Option<String> = conn
+            .query_row(
+                "SELECT content FROM messages
+                 WHERE conversation_id = ?1 AND role = 'user' AND is_stale = 0
+                 ORDER BY timestamp DESC LIMIT 1",
+                [conversation_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let history_messages: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM messages WHERE conversation_id = ?1 AND is_stale = 0",
+            [conversation_id],
+            |row| row.get(0),
+        )?;
+        let summary = conn
+            .query_row(
+                "SELECT summary_text, messages_summarized FROM conversation_summaries
+                 WHERE conversation_id = ?1
+                 ORDER BY last_updated DESC LIMIT 1",
+                [conversation_id],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+            )
+            .optional()?
+            .map(|(text, messages_summarized)| StoredSummary {
+                tokens: estimate_tokens(&text),
+                text,
+                messages_summarized,
+                sent: false,
+            });
+        (last_message, history_messages, summary)
+    };
+
+    let draft = message.is_some_and(|m| !m.trim().is_empty());
+    let message = match message.filter(|m| !m.trim().is_empty()) {
+        Some(message) => message.to_string(),
+        None => last_message.ok_or_else(|| anyhow!("No message to inspect: type a draft or send a message first"))?,
+    };
+
+    let prompt = ollama::build_chat_prompt(&message, Some(conversation_id), rag, Some(db), &Default::default()).await;
+    let full_prompt = prompt.full_prompt(&message);
+    let budget = model_context::active_budget();
+
+    Ok(ContextInspection {
+        conversation_id: conversation_id.to_string(),
+        draft,
+        model: prompt.model.clone(),
+        context_window: budget.context_window,
+        prompt_budget: budget.prompt_budget,
+        sections: prompt_sections(&prompt, &message),
+        memories: classify_memories(&prompt.candidates, &prompt.relevant, &prompt.memories),
+        total_tokens: estimate_tokens(&full_prompt),
+        prompt: full_prompt,
+        abstains: prompt.abstains(),
+        retrieval_error: prompt.retrieval_error.clone(),
+        history_messages,
+        summary,
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn episode(id: &str) -> Episode {
+        Episode {
+            id: id.to_string(),
+            user_message: format!("question {}", id),
+            ai_response: String::new(),
+            satisfaction: 0.5,
+            created_at: 0,
+            access_count: 0,
+            importance: 0.5,
+            embedding_id: None,
+            conversation_id: None,
+            message_id: None,
+            document: None,
+            language: None,
+            user_asserted: false,
+            user_note: None,
+        }
+    }
+
+    #[test]
+    fn test_classify_memories() {
+        let candidates = vec![(episode("weak"), 0.2), (episode("sent"), 0.9), (episode("cut"), 0.7)];
+        let relevant = vec![episode("sent"), episode("cut")];
+        let sent = vec![episode("sent")];
+
+        let memories = classify_memories(&candidates, &relevant, &sent);
+        let statuses: Vec<(&str, MemoryStatus)> = memories.iter().map(|m| (m.id.as_str(), m.status)).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("sent", MemoryStatus::Sent),
+                ("cut", MemoryStatus::OverBudget),
+                ("weak", MemoryStatus::BelowRelevance),
+            ]
+        );
+    }
+}
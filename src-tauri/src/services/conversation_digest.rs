@@ -0,0 +1,537 @@
+//! Conversation Digest Service (v3.9.0)
+//!
+//! Summarizes finished conversations into a few durable memories so the
+//! vector store doesn't keep every turn of every chat forever.
+//!
+//! Features:
+//! - Conversations idle for `IDLE_MINUTES` (or ended from the UI) are summarized
+//!   into 1-3 episodic memories with the decisions and preferences they contain
+//! - A conversation that continues later is summarized again, replacing its
+//!   previous digest
+//! - Once a digested conversation is older than `REPLACE_AFTER_DAYS`, its raw
+//!   per-turn episodes are deleted; user-asserted or annotated memories and
+//!   attached documents are kept
+//! - Digests inherit the memory scope of the conversation's episodes
+
+#![allow(dead_code)]  // Phase 5: Conversation digests
+
+use crate::database::Database;
+use crate::services::memory_scope::MemoryScope;
+use crate::services::ollama;
+#[cfg(feature = "lancedb-support")]
+use crate::services::rag_v2::RagServiceV2;
+#[cfg(not(feature = "lancedb-support"))]
+use crate::services::rag::RagService as RagServiceV2;
+use anyhow::{anyhow, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Minutes without a new message before a conversation counts as finished
+pub const IDLE_MINUTES: i64 = 30;
+
+/// Age after which a digested conversation's raw episodes are replaced
+pub const REPLACE_AFTER_DAYS: i64 = 7;
+
+/// Messages (both roles) before a conversation is worth a digest
+const MIN_MESSAGES: i64 = 4;
+
+const MAX_DIGEST_MEMORIES: usize = 3;
+const MAX_ITEMS_PER_LIST: usize = 8;
+
+/// Transcript budget for the prompt; the first messages and the most recent
+/// ones are kept when a conversation is longer
+const MAX_TRANSCRIPT_CHARS: usize = 12_000;
+const MAX_MESSAGE_CHARS: usize = 800;
+const LEADING_MESSAGES: usize = 2;
+
+/// Rendered memory length; keeps a digest memory within one chunk
+const MAX_MEMORY_CHARS: usize = 1_200;
+
+/// Digests are what the user would want remembered, so they start important
+const DIGEST_SATISFACTION: f32 = 0.8;
+
+/// Conversations digested per background run
+pub const DEFAULT_BATCH_SIZE: usize = 5;
+
+const MINUTE_MS: i64 = 60_000;
+const DAY_MS: i64 = 24 * 60 * MINUTE_MS;
+
+/// One durable memory distilled from a conversation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DigestMemory {
+    pub topic: String,
+    pub summary: String,
+    #[serde(default)]
+    pub decisions: Vec<String>,
+    #[serde(default)]
+    pub preferences: Vec<String>,
+}
+
+/// Digest of one conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationDigest {
+    pub conversation_id: String,
+    pub memories: Vec<DigestMemory>,
+    /// Episodic memories the digest was stored as
+    pub memory_ids: Vec<String>,
+    /// Conversation length when it was digested
+    pub message_count: i64,
+    pub digested_at: i64,
+    /// When the raw per-turn episodes were deleted
+    pub raw_replaced_at: Option<i64>,
+}
+
+/// Outcome of a background digest run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DigestReport {
+    pub digested: usize,
+    /// Conversations whose raw episodes were replaced by their digest
+    pub compacted: usize,
+    pub episodes_removed: usize,
+    pub failed: usize,
+}
+
+#[derive(Deserialize)]
+struct RawDigest {
+    #[serde(default)]
+    memories: Vec<DigestMemory>,
+}
+
+/// Conversation digest service
+pub struct ConversationDigestService {
+    db: Arc<Mutex<Database>>,
+    rag: Arc<RagServiceV2>,
+}
+
+impl ConversationDigestService {
+    pub fn new(db: Arc<Mutex<Database>>, rag: Arc<RagServiceV2>) -> Result<Self> {
+        init_tables(&*db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?)?;
+        log::info!("✓ Conversation Digest Service initialized");
+        Ok(Self { db, rag })
+    }
+
+    /// Idle conversations with no digest, or new messages since their digest
+    pub fn pending_digests(&self, limit: usize) -> Result<Vec<String>> {
+        let idle_before = chrono::Utc::now().timestamp_millis() - IDLE_MINUTES * MINUTE_MS;
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT c.id FROM conversations c
+             LEFT JOIN conversation_digests d ON d.conversation_id = c.id
+             WHERE c.message_count >= ?1 AND c.updated_at <= ?2
+               AND (d.conversation_id IS NULL OR d.message_count < c.message_count)
+             ORDER BY c.updated_at DESC
+             LIMIT ?3",
+        )?;
+        let ids = stmt
+            .query_map(params![MIN_MESSAGES, idle_before, limit as i64], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(ids)
+    }
+
+    /// Digested conversations old enough to drop their raw episodes
+    pub fn pending_replacements(&self, limit: usize) -> Result<Vec<String>> {
+        let old_before = chrono::Utc::now().timestamp_millis() - REPLACE_AFTER_DAYS * DAY_MS;
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT c.id FROM conversations c
+             JOIN conversation_digests d ON d.conversation_id = c.id
+             WHERE d.raw_replaced_at IS NULL AND d.message_count >= c.message_count AND c.updated_at <= ?1
+             ORDER BY c.updated_at ASC
+             LIMIT ?2",
+        )?;
+        let ids = stmt
+            .query_map(params![old_before, limit as i64], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(ids)
+    }
+
+    /// Summarize a conversation now, replacing any earlier digest of it
+    pub async fn digest_conversation(&self, conversation_id: &str) -> Result<ConversationDigest> {
+        let (title, messages, message_count, scope) = {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            let conn = db.conn();
+            let (title, message_count): (String, i64) = conn
+                .query_row(
+                    "SELECT title, message_count FROM conversations WHERE id = ?1",
+                    [conversation_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?
+                .ok_or_else(|| anyhow!("Conversation not found: {}", conversation_id))?;
+            (title, load_messages(conn, conversation_id)?, message_count, episode_scope(conn, conversation_id)?)
+        };
+        if messages.is_empty() {
+            return Err(anyhow!("Conversation has no messages: {}", conversation_id));
+        }
+
+        let prompt = digest_prompt(&title, &format_transcript(&messages));
+        let reply = ollama::generate_response(&prompt)
+            .await
+            .map_err(|e| anyhow!("Conversation digest failed: {}", e))?;
+        let memories = parse_digest(&reply).ok_or_else(|| anyhow!("Unparseable conversation digest: {}", reply))?;
+
+        if let Some(previous) = self.get_digest(conversation_id)? {
+            self.rag.delete_memories(&previous.memory_ids).await?;
+        }
+
+        let mut memory_ids = Vec::with_capacity(memories.len());
+        for memory in &memories {
+            let id = self
+                .rag
+                .store_episode_in_scope(
+                    &memory.topic,
+                    &render_memory(memory),
+                    DIGEST_SATISFACTION,
+                    Some(conversation_id),
+                    None,
+                    &scope,
+                )
+                .await?;
+            memory_ids.push(id);
+        }
+
+        let digest = ConversationDigest {
+            conversation_id: conversation_id.to_string(),
+            memories,
+            memory_ids,
+            message_count,
+            digested_at: chrono::Utc::now().timestamp_millis(),
+            raw_replaced_at: None,
+        };
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        save_digest(db.conn(), &digest)?;
+
+        log::info!("Digested conversation {} into {} memories", conversation_id, digest.memories.len());
+        Ok(digest)
+    }
+
+    /// Delete a digested conversation's raw per-turn episodes
+    ///
+    /// Returns the number of episodes removed.
+    pub async fn replace_raw_episodes(&self, conversation_id: &str) -> Result<usize> {
+        let digest = self
+            .get_digest(conversation_id)?
+            .ok_or_else(|| anyhow!("Conversation has no digest: {}", conversation_id))?;
+
+        let raw_ids: Vec<String> = {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            raw_episode_ids(db.conn(), conversation_id)?
+                .into_iter()
+                .filter(|id| !digest.memory_ids.contains(id))
+                .collect()
+        };
+        let removed = self.rag.delete_memories(&raw_ids).await?;
+
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn().execute(
+            "UPDATE conversation_digests SET raw_replaced_at = ?1 WHERE conversation_id = ?2",
+            params![chrono::Utc::now().timestamp_millis(), conversation_id],
+        )?;
+
+        log::info!("Replaced {} raw episodes of conversation {} with its digest", removed, conversation_id);
+        Ok(removed)
+    }
+
+    pub fn get_digest(&self, conversation_id: &str) -> Result<Option<ConversationDigest>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        load_digest(db.conn(), conversation_id)
+    }
+
+    /// Digest idle conversations and compact old ones (the background job's entry point)
+    pub async fn digest_pending(&self, limit: usize) -> Result<DigestReport> {
+        let mut report = DigestReport::default();
+        for conversation_id in self.pending_digests(limit)? {
+            match self.digest_conversation(&conversation_id).await {
+                Ok(_) => report.digested += 1,
+                Err(e) => {
+                    log::warn!("Failed to digest conversation {}: {}", conversation_id, e);
+                    report.failed += 1;
+                }
+            }
+        }
+        for conversation_id in self.pending_replacements(limit)? {
+            match self.replace_raw_episodes(&conversation_id).await {
+                Ok(removed) => {
+                    report.compacted += 1;
+                    report.episodes_removed += removed;
+                }
+                Err(e) => {
+                    log::warn!("Failed to compact conversation {}: {}", conversation_id, e);
+                    report.failed += 1;
+                }
+            }
+        }
+        Ok(report)
+    }
+}
+
+fn init_tables(db: &Database) -> Result<()> {
+    db.conn().execute(
+        "CREATE TABLE IF NOT EXISTS conversation_digests (
+            conversation_id TEXT PRIMARY KEY,
+            memories TEXT NOT NULL,
+            memory_ids TEXT NOT NULL,
+            message_count INTEGER NOT NULL,
+            digested_at INTEGER NOT NULL,
+            raw_replaced_at INTEGER,
+            FOREIGN KEY (conversation_id) REFERENCES conversations(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn save_digest(conn: &Connection, digest: &ConversationDigest) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO conversation_digests
+            (conversation_id, memories, memory_ids, message_count, digested_at, raw_replaced_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            digest.conversation_id,
+            serde_json::to_string(&digest.memories)?,
+            serde_json::to_string(&digest.memory_ids)?,
+            digest.message_count,
+            digest.digested_at,
+            digest.raw_replaced_at,
+        ],
+    )?;
+    Ok(())
+}
+
+fn load_digest(conn: &Connection, conversation_id: &str) -> Result<Option<ConversationDigest>> {
+    let row: Option<(String, String, i64, i64, Option<i64>)> = conn
+        .query_row(
+            "SELECT memories, memory_ids, message_count, digested_at, raw_replaced_at
+             FROM conversation_digests WHERE conversation_id = ?1",
+            [conversation_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+        )
+        .optional()?;
+    let Some((memories, memory_ids, message_count, digested_at, raw_replaced_at)) = row else {
+        return Ok(None);
+    };
+    Ok(Some(ConversationDigest {
+        conversation_id: conversation_id.to_string(),
+        memories: serde_json::from_str(&memories)?,
+        memory_ids: serde_json::from_str(&memory_ids)?,
+        message_count,
+        digested_at,
+        raw_replaced_at,
+    }))
+}
+
+/// Non-stale user / assistant messages in order
+fn load_messages(conn: &Connection, conversation_id: &str) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT role, content FROM messages
+         WHERE conversation_id = ?1 AND is_stale = 0 AND role IN ('user', 'assistant')
+         ORDER BY timestamp ASC",
+    )?;
+    let messages = stmt
+        .query_map([conversation_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(messages)
+}
+
+/// Scope shared by all of the conversation's episodes, global if they differ
+fn episode_scope(conn: &Connection, conversation_id: &str) -> Result<MemoryScope> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT scope, scope_id FROM episodic_memory WHERE conversation_id = ?1",
+    )?;
+    let scopes: Vec<MemoryScope> = stmt
+        .query_map([conversation_id], |row| {
+            Ok(MemoryScope::from_columns(row.get::<_, Option<String>>(0)?.as_deref(), row.get(1)?))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    match scopes.as_slice() {
+        [scope] => Ok(scope.clone()),
+        _ => Ok(MemoryScope::Global),
+    }
+}
+
+/// Per-turn episodes a digest can replace
+fn raw_episode_ids(conn: &Connection, conversation_id: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT id FROM episodic_memory
+         WHERE conversation_id = ?1 AND document IS NULL
+           AND user_asserted = 0 AND user_note IS NULL",
+    )?;
+    let ids = stmt
+        .query_map([conversation_id], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(ids)
+}
+
+/// Transcript within `MAX_TRANSCRIPT_CHARS`: the opening messages plus as many
+/// of the latest ones as fit
+fn format_transcript(messages: &[(String, String)]) -> String {
+    let lines: Vec<String> = messages
+        .iter()
+        .map(|(role, content)| {
+            let speaker = if role == "user" { "User" } else { "Assistant" };
+            format!("{}: {}", speaker, truncate_chars(content.trim(), MAX_MESSAGE_CHARS))
+        })
+        .collect();
+    let total: usize = lines.iter().map(|line| line.chars().count() + 1).sum();
+    if total <= MAX_TRANSCRIPT_CHARS {
+        return lines.join("\n");
+    }
+
+    let leading = LEADING_MESSAGES.min(lines.len());
+    let mut budget = MAX_TRANSCRIPT_CHARS
+        .saturating_sub(lines[..leading].iter().map(|line| line.chars().count() + 1).sum());
+    let mut tail_start = lines.len();
+    while tail_start > leading {
+        let size = lines[tail_start - 1].chars().count() + 1;
+        if size > budget {
+            break;
+        }
+        budget -= size;
+        tail_start -= 1;
+    }
+
+    let mut transcript = lines[..leading].to_vec();
+    if tail_start > leading {
+        transcript.push(format!("[... {} messages omitted ...]", tail_start - leading));
+    }
+    transcript.extend_from_slice(&lines[tail_start..]);
+    transcript.join("\n")
+}
+
+fn digest_prompt(title: &str, transcript: &str) -> String {
+    format!(
+        r#"Summarize this finished conversation into durable memories for a personal assistant.
+
+Conversation title: {title}
+
+{transcript}
+
+Write 1-{max} memories, one per distinct subject (most conversations need one).
+Each memory has:
+- "topic": a short phrase naming the subject
+- "summary": 2-4 sentences on what was discussed and concluded, with names, numbers and dates
+- "decisions": things the user decided or committed to (may be empty)
+- "preferences": likes, dislikes and habits the user revealed (may be empty)
+
+Write in the language of the conversation. Leave out small talk and anything
+only relevant during the chat.
+
+Respond ONLY with JSON (no other text):
+{{"memories": [{{"topic": "...", "summary": "...", "decisions": ["..."], "preferences": ["..."]}}]}}"#,
+        max = MAX_DIGEST_MEMORIES,
+    )
+}
+
+/// Memories from the model's JSON reply (tolerates text around the object)
+fn parse_digest(reply: &str) -> Option<Vec<DigestMemory>> {
+    let start = reply.find('{')?;
+    let end = reply.rfind('}')?;
+    if end < start {
+        return None;
+    }
+    let raw: RawDigest = serde_json::from_str(&reply[start..=end]).ok()?;
+    let clean_list = |items: Vec<String>| -> Vec<String> {
+        items
+            .into_iter()
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .take(MAX_ITEMS_PER_LIST)
+            .collect()
+    };
+    let memories: Vec<DigestMemory> = raw
+        .memories
+        .into_iter()
+        .filter_map(|memory| {
+            let summary = memory.summary.trim().to_string();
+            if summary.is_empty() {
+                return None;
+            }
+            let topic = memory.topic.trim();
+            Some(DigestMemory {
+                topic: if topic.is_empty() { truncate_chars(&summary, 60) } else { topic.to_string() },
+                summary,
+                decisions: clean_list(memory.decisions),
+                preferences: clean_list(memory.preferences),
+            })
+        })
+        .take(MAX_DIGEST_MEMORIES)
+        .collect();
+    if memories.is_empty() {
+        None
+    } else {
+        Some(memories)
+    }
+}
+
+/// Memory text: the summary followed by its decisions and preferences
+fn render_memory(memory: &DigestMemory) -> String {
+    let mut text = memory.summary.clone();
+    for (heading, items) in [("Decisions", &memory.decisions), ("Preferences", &memory.preferences)] {
+        if items.is_empty() {
+            continue;
+        }
+        text.push_str(&format!("\n\n{}:", heading));
+        for item in items {
+            text.push_str(&format!("\n- {}", item));
+        }
+    }
+    truncate_chars(&text, MAX_MEMORY_CHARS)
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_render_digest() {
+        let reply = r#"Sure! {"memories": [
+            {"topic": "Trip to Busan", "summary": "Planned a three-day trip to Busan in May.",
+             "decisions": ["Book the KTX on Friday", " "], "preferences": ["Prefers window seats"]},
+            {"topic": "", "summary": "  "}
+        ]}"#;
+        let memories = parse_digest(reply).unwrap();
+        assert_eq!(memories.len(), 1);
+        assert_eq!(memories[0].decisions, vec!["Book the KTX on Friday".to_string()]);
+        assert_eq!(
+            render_memory(&memories[0]),
+            "Planned a three-day trip to Busan in May.\n\nDecisions:\n- Book the KTX on Friday\n\nPreferences:\n- Prefers window seats"
+        );
+
+        assert!(parse_digest(r#"{"memories": []}"#).is_none());
+        assert!(parse_digest("no json here").is_none());
+    }
+
+    #[test]
+    fn test_transcript_keeps_opening_and_latest() {
+        let messages: Vec<(String, String)> = (0..100)
+            .map(|i| {
+                let role = if i % 2 == 0 { "user" } else { "assistant" };
+                (role.to_string(), format!("message {} {}", i, "x".repeat(300)))
+            })
+            .collect();
+        let transcript = format_transcript(&messages);
+        assert!(transcript.chars().count() <= MAX_TRANSCRIPT_CHARS + 40);
+        assert!(transcript.starts_with("User: message 0 "));
+        assert!(transcript.contains("messages omitted"));
+        assert!(transcript.ends_with(&"x".repeat(300)));
+        assert!(transcript.contains("Assistant: message 99 "));
+
+        let short = format_transcript(&messages[..2]);
+        assert_eq!(short.lines().count(), 2);
+    }
+}
@@ -0,0 +1,308 @@
+//! Conversation Documents (v3.9.0)
+//!
+//! Local files attached to a chat, answered from with conversation-scoped retrieval.
+//!
+//! Features:
+//! - Files are chunked and embedded into a per-conversation index, kept out of
+//!   long-term memory so they never surface in other chats
+//! - Re-attaching a file replaces its chunks
+//! - Chunks most similar to each message are added to the prompt, within budget
+//! - The index is deleted with the conversation (ON DELETE CASCADE)
+
+use crate::database::Database;
+use crate::services::chunker::{estimate_tokens, Chunk, SourceKind};
+use crate::services::embedding::UnifiedEmbeddingService;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Largest file that is ingested
+pub const MAX_FILE_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Chunks retrieved per message
+pub const TOP_K: usize = 4;
+
+/// Chunks less similar than this are not sent
+const MIN_SIMILARITY: f32 = 0.2;
+
+/// A file attached to a conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationDocument {
+    pub conversation_id: String,
+    pub name: String,
+    pub path: String,
+    pub chunks: usize,
+    pub attached_at: i64, // Unix millis
+}
+
+/// A retrieved chunk with its similarity to the message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentChunk {
+    pub document: String,
+    pub chunk_index: usize,
+    pub chunk_count: usize,
+    pub text: String,
+    pub score: f32,
+}
+
+/// Name and text content of a local file
+pub fn read_document(path: &str) -> Result<(String, String)> {
+    let file = Path::new(path);
+    let size = std::fs::metadata(file)
+        .with_context(|| format!("Cannot read {}", path))?
+        .len();
+    if size > MAX_FILE_BYTES {
+        return Err(anyhow!("{} is larger than {} MB", path, MAX_FILE_BYTES / (1024 * 1024)));
+    }
+    let bytes = std::fs::read(file).with_context(|| format!("Cannot read {}", path))?;
+    let content = String::from_utf8(bytes).map_err(|_| anyhow!("{} is not a text file", path))?;
+    let name = file
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string());
+    Ok((name, content))
+}
+
+/// Best-scoring chunks that fit in `budget` tokens
+pub fn chunks_within_budget(mut chunks: Vec<DocumentChunk>, budget: usize) -> Vec<DocumentChunk> {
+    chunks.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    let mut used = 0;
+    chunks
+        .into_iter()
+        .take_while(|chunk| {
+            used += estimate_tokens(&chunk.text);
+            used <= budget
+        })
+        .collect()
+}
+
+/// The user's message with the document passages it is answered from
+pub fn augment_message(message: &str, chunks: &[DocumentChunk]) -> String {
+    if chunks.is_empty() {
+        return message.to_string();
+    }
+    let mut text = format!(
+        "{}\n\n# Attached documents\n(Excerpts of files the user attached to this conversation; answer from them and name the file.)\n",
+        message
+    );
+    for chunk in chunks {
+        text.push_str(&format!(
+            "\n[{} (part {}/{})]\n{}\n",
+            chunk.document, chunk.chunk_index + 1, chunk.chunk_count, chunk.text.trim()
+        ));
+    }
+    text
+}
+
+pub struct ConversationDocumentService {
+    db: Arc<Mutex<Database>>,
+    embedding: Arc<UnifiedEmbeddingService>,
+}
+
+impl ConversationDocumentService {
+    pub fn new(db: Arc<Mutex<Database>>, embedding: Arc<UnifiedEmbeddingService>) -> Result<Self> {
+        let service = Self { db, embedding };
+        service.init_database()?;
+        log::info!("✓ Conversation Document Service initialized");
+        Ok(service)
+    }
+
+    fn init_database(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let conn = db.conn();
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS conversation_document_chunks (
+                id TEXT PRIMARY KEY,
+                conversation_id TEXT NOT NULL,
+                document TEXT NOT NULL,
+                path TEXT NOT NULL,
+                chunk_index INTEGER NOT NULL,
+                chunk_count INTEGER NOT NULL,
+                text TEXT NOT NULL,
+                embedding TEXT NOT NULL,
+                attached_at INTEGER NOT NULL,
+                FOREIGN KEY (conversation_id) REFERENCES conversations(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        let _ = conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_conversation_document_chunks ON conversation_document_chunks(conversation_id)",
+            [],
+        );
+
+        Ok(())
+    }
+
+    /// Index `chunks` of the file at `path` for one conversation
+    pub fn attach(&self, conversation_id: &str, path: &str, name: &str, chunks: &[Chunk]) -> Result<ConversationDocument> {
+        if chunks.is_empty() {
+            return Err(anyhow!("{} has no text to index", name));
+        }
+
+        // Generate embeddings outside of the DB lock
+        let embeddings = chunks
+            .iter()
+            .map(|chunk| Ok(serde_json::to_string(&self.embedding.embed(&chunk.text)?)?))
+            .collect::<Result<Vec<_>>>()?;
+
+        let attached_at = chrono::Utc::now().timestamp_millis();
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let tx = db.conn().unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM conversation_document_chunks WHERE conversation_id = ?1 AND path = ?2",
+            rusqlite::params![conversation_id, path],
+        )?;
+        for (chunk, embedding) in chunks.iter().zip(&embeddings) {
+            tx.execute(
+                "INSERT INTO conversation_document_chunks
+                    (id, conversation_id, document, path, chunk_index, chunk_count, text, embedding, attached_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                rusqlite::params![
+                    uuid::Uuid::new_v4().to_string(),
+                    conversation_id,
+                    name,
+                    path,
+                    chunk.index as i64,
+                    chunks.len() as i64,
+                    chunk.text,
+                    embedding,
+                    attached_at,
+                ],
+            )?;
+        }
+        tx.commit()?;
+
+        log::info!("Attached {} to conversation {} ({} chunks)", name, conversation_id, chunks.len());
+        Ok(ConversationDocument {
+            conversation_id: conversation_id.to_string(),
+            name: name.to_string(),
+            path: path.to_string(),
+            chunks: chunks.len(),
+            attached_at,
+        })
+    }
+
+    /// Read, chunk and index a local file
+    ///
+    /// `chunk` applies the RAG chunking settings for the detected source kind.
+    pub fn attach_file<F>(&self, conversation_id: &str, path: &str, chunk: F) -> Result<ConversationDocument>
+    where
+        F: Fn(&str, SourceKind) -> Vec<Chunk>,
+    {
+        let (name, content) = read_document(path)?;
+        let chunks = chunk(&content, SourceKind::detect(&name, &content));
+        self.attach(conversation_id, path, &name, &chunks)
+    }
+
+    /// Files attached to a conversation, oldest first
+    pub fn list(&self, conversation_id: &str) -> Result<Vec<ConversationDocument>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT document, path, COUNT(*), MIN(attached_at)
+             FROM conversation_document_chunks
+             WHERE conversation_id = ?1
+             GROUP BY path
+             ORDER BY MIN(attached_at)",
+        )?;
+        let documents = stmt
+            .query_map([conversation_id], |row| {
+                Ok(ConversationDocument {
+                    conversation_id: conversation_id.to_string(),
+                    name: row.get(0)?,
+                    path: row.get(1)?,
+                    chunks: row.get::<_, i64>(2)? as usize,
+                    attached_at: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(documents)
+    }
+
+    /// Remove one file from a conversation's index
+    pub fn detach(&self, conversation_id: &str, path: &str) -> Result<usize> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let deleted = db.conn().execute(
+            "DELETE FROM conversation_document_chunks WHERE conversation_id = ?1 AND path = ?2",
+            rusqlite::params![conversation_id, path],
+        )?;
+        Ok(deleted)
+    }
+
+    /// Chunks of the conversation's files most similar to `query`
+    pub fn search(&self, conversation_id: &str, query: &str, limit: usize) -> Result<Vec<DocumentChunk>> {
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            let has_documents: bool = db.conn().query_row(
+                "SELECT EXISTS(SELECT 1 FROM conversation_document_chunks WHERE conversation_id = ?1)",
+                [conversation_id],
+                |row| row.get(0),
+            )?;
+            if !has_documents {
+                return Ok(Vec::new());
+            }
+        }
+
+        let query_embedding = self.embedding.embed(query)?;
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT document, chunk_index, chunk_count, text, embedding
+             FROM conversation_document_chunks
+             WHERE conversation_id = ?1",
+        )?;
+
+        let mut chunks: Vec<DocumentChunk> = stmt
+            .query_map([conversation_id], |row| {
+                let embedding_json: String = row.get(4)?;
+                Ok((
+                    DocumentChunk {
+                        document: row.get(0)?,
+                        chunk_index: row.get::<_, i64>(1)? as usize,
+                        chunk_count: row.get::<_, i64>(2)? as usize,
+                        text: row.get(3)?,
+                        score: 0.0,
+                    },
+                    embedding_json,
+                ))
+            })?
+            .filter_map(|row| row.ok())
+            .filter_map(|(chunk, embedding_json)| {
+                let embedding: Vec<f32> = serde_json::from_str(&embedding_json).ok()?;
+                let score = UnifiedEmbeddingService::cosine_similarity(&query_embedding, &embedding);
+                (score >= MIN_SIMILARITY).then_some(DocumentChunk { score, ..chunk })
+            })
+            .collect();
+
+        chunks.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        chunks.truncate(limit);
+        Ok(chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(document: &str, index: usize, text: &str, score: f32) -> DocumentChunk {
+        DocumentChunk { document: document.to_string(), chunk_index: index, chunk_count: 3, text: text.to_string(), score }
+    }
+
+    #[test]
+    fn test_budget_and_augment() {
+        let chunks = vec![
+            chunk("notes.md", 2, "low scoring passage", 0.3),
+            chunk("notes.md", 0, "Revenue grew 12% in Q3.", 0.9),
+            chunk("notes.md", 1, &"filler ".repeat(200), 0.6),
+        ];
+        let kept = chunks_within_budget(chunks, 50);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].chunk_index, 0);
+
+        let text = augment_message("How much did revenue grow?", &kept);
+        assert!(text.starts_with("How much did revenue grow?\n\n# Attached documents\n"));
+        assert!(text.ends_with("[notes.md (part 1/3)]\nRevenue grew 12% in Q3.\n"));
+        assert_eq!(augment_message("hi", &[]), "hi");
+    }
+}
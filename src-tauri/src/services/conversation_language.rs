@@ -0,0 +1,319 @@
+//! Conversation Language Lock Service (v3.9.0)
+//!
+//! Keeps replies in the conversation's language instead of relying on the
+//! system prompt alone, which occasionally drifts mid-conversation.
+//!
+//! Features:
+//! - Per-conversation detected language (from the first clear user message)
+//! - Manual per-conversation override (locked language)
+//! - Post-generation language check on every reply
+//! - Corrective regeneration when the reply language mismatches the lock
+
+#![allow(dead_code)]  // Phase 5: Language lock (some helpers used by future UI)
+
+use crate::database::Database;
+use crate::services::{language_detection, ollama};
+use anyhow::{anyhow, Result};
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Number of corrective regenerations attempted per reply
+const MAX_CORRECTIONS: usize = 1;
+
+/// Supported conversation languages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ConversationLanguage {
+    #[serde(rename = "ko")]
+    Korean,
+    #[default]
+    #[serde(rename = "en")]
+    English,
+}
+
+impl ConversationLanguage {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Korean => "ko",
+            Self::English => "en",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_lowercase().as_str() {
+            "ko" | "korean" | "한국어" => Some(Self::Korean),
+            "en" | "english" | "영어" => Some(Self::English),
+            _ => None,
+        }
+    }
+
+    fn instruction(&self) -> &'static str {
+        match self {
+            Self::Korean => "반드시 한국어로만 답변하세요. 영어 문장을 섞지 마세요 (코드와 고유명사는 예외).",
+            Self::English => "You must answer in English only. Do not switch to Korean (code and proper nouns excepted).",
+        }
+    }
+}
+
+/// Language state of a conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationLanguageState {
+    pub conversation_id: String,
+    pub detected_language: Option<ConversationLanguage>,
+    pub locked_language: Option<ConversationLanguage>,
+    /// Language replies are enforced in (lock wins over detection)
+    pub effective_language: Option<ConversationLanguage>,
+}
+
+/// Result of enforcing the language on a generated reply
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageCheck {
+    pub response: String,
+    pub expected: Option<ConversationLanguage>,
+    pub detected: Option<ConversationLanguage>,
+    pub regenerated: bool,
+}
+
+/// Classify text as Korean or English, ignoring code blocks.
+/// Returns None when there are too few letters to decide or the text is in
+/// another language.
+pub fn detect_language(text: &str) -> Option<ConversationLanguage> {
+    language_detection::detect_conversation_language(text)
+}
+
+/// Whether a reply satisfies the expected language (undecidable replies pass)
+pub fn response_matches(response: &str, expected: ConversationLanguage) -> bool {
+    detect_language(response).is_none_or(|lang| lang == expected)
+}
+
+/// Conversation language lock service
+pub struct ConversationLanguageService {
+    db: Arc<Mutex<Database>>,
+}
+
+impl ConversationLanguageService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        let service = Self { db };
+        service.init_database()?;
+        log::info!("✓ Conversation Language Service initialized");
+        Ok(service)
+    }
+
+    fn init_database(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let conn = db.conn();
+
+        // Note: SQLite doesn't support ALTER TABLE if column exists, so we ignore errors
+        let _ = conn.execute(
+            "ALTER TABLE conversations ADD COLUMN detected_language TEXT",
+            [],
+        );
+        let _ = conn.execute(
+            "ALTER TABLE conversations ADD COLUMN locked_language TEXT",
+            [],
+        );
+
+        Ok(())
+    }
+
+    /// Get the language state of a conversation
+    pub fn get_state(&self, conversation_id: &str) -> Result<ConversationLanguageState> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let conn = db.conn();
+
+        let (detected, locked): (Option<String>, Option<String>) = conn
+            .query_row(
+                "SELECT detected_language, locked_language FROM conversations WHERE id = ?1",
+                [conversation_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?
+            .unwrap_or((None, None));
+
+        let detected_language = detected.as_deref().and_then(ConversationLanguage::from_code);
+        let locked_language = locked.as_deref().and_then(ConversationLanguage::from_code);
+
+        Ok(ConversationLanguageState {
+            conversation_id: conversation_id.to_string(),
+            detected_language,
+            locked_language,
+            effective_language: locked_language.or(detected_language),
+        })
+    }
+
+    /// Resolve the language for a new user message, recording the detected
+    /// language the first time it can be determined
+    pub fn resolve_for_message(
+        &self,
+        conversation_id: &str,
+        user_message: &str,
+    ) -> Result<Option<ConversationLanguage>> {
+        let state = self.get_state(conversation_id)?;
+        if state.effective_language.is_some() {
+            return Ok(state.effective_language);
+        }
+
+        let detected = detect_language(user_message);
+        if let Some(lang) = detected {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            db.conn().execute(
+                "UPDATE conversations SET detected_language = ?1 WHERE id = ?2",
+                rusqlite::params![lang.code(), conversation_id],
+            )?;
+            log::info!("Conversation {} language detected: {}", conversation_id, lang.code());
+        }
+
+        Ok(detected)
+    }
+
+    /// Lock a conversation to a language, or clear the lock with None
+    pub fn set_override(
+        &self,
+        conversation_id: &str,
+        language: Option<ConversationLanguage>,
+    ) -> Result<ConversationLanguageState> {
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            let updated = db.conn().execute(
+                "UPDATE conversations SET locked_language = ?1 WHERE id = ?2",
+                rusqlite::params![language.map(|l| l.code()), conversation_id],
+            )?;
+            if updated == 0 {
+                return Err(anyhow!("Conversation not found: {}", conversation_id));
+            }
+        }
+
+        log::info!(
+            "Conversation {} language lock: {}",
+            conversation_id,
+            language.map(|l| l.code()).unwrap_or("none")
+        );
+        self.get_state(conversation_id)
+    }
+
+    /// Check a generated reply and regenerate it when it drifted from the
+    /// expected language. The original reply is kept if correction fails.
+    pub async fn enforce(
+        &self,
+        expected: Option<ConversationLanguage>,
+        user_message: &str,
+        response: String,
+    ) -> LanguageCheck {
+        let expected_lang = match expected {
+            Some(lang) => lang,
+            None => {
+                return LanguageCheck {
+                    detected: detect_language(&response),
+                    response,
+                    expected,
+                    regenerated: false,
+                }
+            }
+        };
+
+        if response_matches(&response, expected_lang) {
+            return LanguageCheck {
+                detected: Some(expected_lang),
+                response,
+                expected,
+                regenerated: false,
+            };
+        }
+
+        log::warn!(
+            "Reply language mismatch (expected {}), regenerating",
+            expected_lang.code()
+        );
+
+        for attempt in 1..=MAX_CORRECTIONS {
+            let prompt = Self::corrective_prompt(expected_lang, user_message, &response);
+            match ollama::generate_response(&prompt).await {
+                Ok(corrected) if response_matches(&corrected, expected_lang) => {
+                    log::info!("✓ Reply corrected to {} (attempt {})", expected_lang.code(), attempt);
+                    return LanguageCheck {
+                        response: corrected,
+                        expected,
+                        detected: Some(expected_lang),
+                        regenerated: true,
+                    };
+                }
+                Ok(_) => log::warn!("Corrective regeneration still mismatched (attempt {})", attempt),
+                Err(e) => log::warn!("Corrective regeneration failed: {}", e),
+            }
+        }
+
+        LanguageCheck {
+            detected: detect_language(&response),
+            response,
+            expected,
+            regenerated: false,
+        }
+    }
+
+    fn corrective_prompt(
+        language: ConversationLanguage,
+        user_message: &str,
+        previous: &str,
+    ) -> String {
+        format!(
+            "{}\n\nThe previous answer was written in the wrong language. \
+             Answer the user's message again with the same content.\n\n\
+             User message:\n{}\n\nPrevious answer:\n{}\n\nAnswer:",
+            language.instruction(),
+            user_message,
+            previous
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(detect_language("안녕하세요, 오늘 날씨 어때요?"), Some(ConversationLanguage::Korean));
+        assert_eq!(detect_language("How is the weather today?"), Some(ConversationLanguage::English));
+        assert_eq!(detect_language("Rust에서 Vec을 정렬하는 방법"), Some(ConversationLanguage::Korean));
+        assert_eq!(detect_language("ok"), None);
+        assert_eq!(detect_language("12345 !!"), None);
+    }
+
+    #[test]
+    fn test_detect_ignores_code_blocks() {
+        let text = "이렇게 하면 됩니다:\n```rust\nfn main() { println!(\"hello world\"); }\n```";
+        assert_eq!(detect_language(text), Some(ConversationLanguage::Korean));
+    }
+
+    #[test]
+    fn test_response_matches() {
+        assert!(response_matches("네, 알겠습니다!", ConversationLanguage::Korean));
+        assert!(!response_matches("Sure, here you go!", ConversationLanguage::Korean));
+        assert!(response_matches("👍", ConversationLanguage::English));
+    }
+
+    #[test]
+    fn test_get_state_propagates_db_errors() {
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let service = ConversationLanguageService::new(Arc::clone(&db)).unwrap();
+
+        let missing = service.get_state("missing").unwrap();
+        assert_eq!(missing.effective_language, None);
+
+        db.lock().unwrap().conn()
+            .execute("ALTER TABLE conversations RENAME COLUMN locked_language TO locked_old", [])
+            .unwrap();
+        assert!(service.get_state("missing").is_err());
+    }
+
+    #[test]
+    fn test_language_codes() {
+        assert_eq!(ConversationLanguage::from_code("KO"), Some(ConversationLanguage::Korean));
+        assert_eq!(ConversationLanguage::from_code("english"), Some(ConversationLanguage::English));
+        assert_eq!(ConversationLanguage::from_code("fr"), None);
+        assert_eq!(
+            serde_json::to_string(&ConversationLanguage::Korean).unwrap(),
+            "\"ko\""
+        );
+    }
+}
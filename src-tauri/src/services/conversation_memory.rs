@@ -7,13 +7,19 @@
 //! - Keep recent N messages in full
 //! - Summarize older messages into a rolling summary
 //! - Provide context (summary + recent messages) to LLM
+//! - Attention-sink context over the full conversation, where evicted middle
+//!   turns become the rolling summary (v3.9.0)
+//! - Recent messages limited to the active model's history budget (v3.9.0)
 
 #![allow(dead_code)]  // Phase 12: Summary buffer (on-demand)
 
 use crate::database::Database;
+use crate::services::attention_sink::{AttentionSinkManager, ManagedContext};
+use crate::services::chunker::estimate_tokens;
+use crate::services::model_context;
 use anyhow::{anyhow, Result};
 use log::info;
-use rusqlite::params;
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 
@@ -67,7 +73,7 @@ impl ConversationMemoryService {
         // Get total message count
         let total_messages: usize = conn
             .query_row(
-                "SELECT COUNT(*) FROM messages WHERE conversation_id = ?1",
+                "SELECT COUNT(*) FROM messages WHERE conversation_id = ?1 AND is_stale = 0",
                 [conversation_id],
                 |row| row.get(0),
             )
@@ -77,12 +83,12 @@ impl ConversationMemoryService {
         let mut stmt = conn.prepare(
             "SELECT id, role, content, created_at
              FROM messages
-             WHERE conversation_id = ?1
+             WHERE conversation_id = ?1 AND is_stale = 0
              ORDER BY created_at DESC
              LIMIT ?2"
         )?;
 
-        let recent_messages: Vec<ConversationMessage> = stmt
+        let mut recent_messages: Vec<ConversationMessage> = stmt
             .query_map(params![conversation_id, MAX_RECENT_MESSAGES], |row| {
                 Ok(ConversationMessage {
                     id: row.get(0)?,
@@ -95,6 +101,7 @@ impl ConversationMemoryService {
             .into_iter()
             .rev() // Reverse to get chronological order
             .collect();
+        let recent_messages = recent_messages.split_off(recent_messages.len() - recent_count(&recent_messages));
 
         // Get summary if exists
         let summary: Option<String> = conn
@@ -122,13 +129,27 @@ impl ConversationMemoryService {
 
         let message_count: usize = conn
             .query_row(
-                "SELECT COUNT(*) FROM messages WHERE conversation_id = ?1",
+                "SELECT COUNT(*) FROM messages WHERE conversation_id = ?1 AND is_stale = 0",
                 [conversation_id],
                 |row| row.get(0),
             )
             .unwrap_or(0);
 
-        Ok(message_count >= SUMMARIZE_THRESHOLD)
+        if message_count >= SUMMARIZE_THRESHOLD {
+            return Ok(true);
+        }
+
+        // v3.9.0: Fewer but longer messages can still overflow a small window
+        let mut stmt = conn.prepare(
+            "SELECT content FROM messages WHERE conversation_id = ?1 AND is_stale = 0"
+        )?;
+        let tokens: usize = stmt
+            .query_map([conversation_id], |row| row.get::<_, String>(0))?
+            .filter_map(|content| content.ok())
+            .map(|content| estimate_tokens(&content))
+            .sum();
+
+        Ok(message_count > 1 && tokens > model_context::history_budget())
     }
 
     /// Create or update conversation summary
@@ -189,7 +210,7 @@ impl ConversationMemoryService {
         Ok(())
     }
 
-    /// Get messages for summarization (all except recent N)
+    /// Get messages for summarization (all except the recent ones kept in full)
     pub fn get_messages_for_summary(&self, conversation_id: &str) -> Result<Vec<ConversationMessage>> {
         let db = self.db.lock().map_err(|e| anyhow!("Database lock error: {}", e))?;
         let conn = db.conn();
@@ -198,7 +219,7 @@ impl ConversationMemoryService {
         let mut stmt = conn.prepare(
             "SELECT id, role, content, created_at
              FROM messages
-             WHERE conversation_id = ?1
+             WHERE conversation_id = ?1 AND is_stale = 0
              ORDER BY created_at ASC"
         )?;
 
@@ -213,12 +234,9 @@ impl ConversationMemoryService {
             })?
             .collect::<std::result::Result<Vec<_>, _>>()?;
 
-        // Return all except recent N messages
-        let messages_to_summarize = if all_messages.len() > MAX_RECENT_MESSAGES {
-            all_messages[..all_messages.len() - MAX_RECENT_MESSAGES].to_vec()
-        } else {
-            Vec::new()
-        };
+        // Return all except the recent messages kept in full
+        let keep = recent_count(&all_messages);
+        let messages_to_summarize = all_messages[..all_messages.len() - keep].to_vec();
 
         Ok(messages_to_summarize)
     }
@@ -266,6 +284,18 @@ impl ConversationMemoryService {
         Ok(())
     }
 
+    /// Attention-sink context over the whole conversation (v3.9.0)
+    ///
+    /// See [`managed_context`].
+    pub fn get_managed_context(
+        &self,
+        conversation_id: &str,
+        sink: &AttentionSinkManager,
+    ) -> Result<ManagedContext> {
+        let db = self.db.lock().map_err(|e| anyhow!("Database lock error: {}", e))?;
+        managed_context(db.conn(), conversation_id, sink)
+    }
+
     /// Format context for LLM prompt
     pub fn format_context_for_llm(&self, context: &ConversationContext) -> String {
         let mut formatted = String::new();
@@ -289,6 +319,95 @@ impl ConversationMemoryService {
     }
 }
 
+/// How many of the newest `messages` (chronological) stay in full: at most
+/// MAX_RECENT_MESSAGES, within the active model's history budget, at least one (v3.9.0)
+fn recent_count(messages: &[ConversationMessage]) -> usize {
+    let budget = model_context::history_budget();
+    let mut tokens = 0;
+    let mut count = 0;
+    for message in messages.iter().rev().take(MAX_RECENT_MESSAGES) {
+        tokens += estimate_tokens(&message.content);
+        if tokens > budget && count > 0 {
+            break;
+        }
+        count += 1;
+    }
+    count
+}
+
+/// Attention-sink context over the whole conversation (v3.9.0)
+///
+/// The stored summary stands in for the turns it covers. When more turns are
+/// evicted than it covers, the new rolling summary replaces it, so turns that
+/// drop out of the prompt always remain in the Summary Buffer.
+pub fn managed_context(
+    conn: &Connection,
+    conversation_id: &str,
+    sink: &AttentionSinkManager,
+) -> Result<ManagedContext> {
+    let mut stmt = conn.prepare(
+        "SELECT role, content
+         FROM messages
+         WHERE conversation_id = ?1 AND is_stale = 0
+         ORDER BY created_at ASC"
+    )?;
+    let turns: Vec<String> = stmt
+        .query_map([conversation_id], |row| {
+            Ok(format!("{}: {}", row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let stored: Option<(String, i64)> = conn
+        .query_row(
+            "SELECT summary_text, messages_summarized FROM conversation_summaries
+             WHERE conversation_id = ?1
+             ORDER BY last_updated DESC
+             LIMIT 1",
+            [conversation_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+
+    let context = sink.manage_turns(
+        &turns,
+        stored.as_ref().map(|(text, covered)| (text.as_str(), *covered as usize)),
+    );
+
+    let covered = context
+        .summary_segments
+        .last()
+        .map(|segment| (segment.first_message + segment.message_count) as i64)
+        .unwrap_or(0);
+    let previously_covered = stored.as_ref().map(|(_, covered)| *covered).unwrap_or(0);
+
+    if covered > previously_covered {
+        let now = chrono::Utc::now().timestamp();
+        let updated = conn.execute(
+            "UPDATE conversation_summaries
+             SET summary_text = ?1, messages_summarized = ?2, last_updated = ?3
+             WHERE id = (SELECT id FROM conversation_summaries
+                         WHERE conversation_id = ?4
+                         ORDER BY last_updated DESC
+                         LIMIT 1)",
+            params![context.compressed_middle, covered, now, conversation_id],
+        )?;
+        if updated == 0 {
+            conn.execute(
+                "INSERT INTO conversation_summaries
+                 (conversation_id, summary_text, messages_summarized, last_updated)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![conversation_id, context.compressed_middle, covered, now],
+            )?;
+        }
+        info!(
+            "Rolling summary for conversation {} now covers {} messages",
+            conversation_id, covered
+        );
+    }
+
+    Ok(context)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,499 @@
+//! Conversation Organizer (v3.9.0)
+//!
+//! Keeps hundreds of chats manageable.
+//!
+//! Features:
+//! - Pinned and favorite flags (pinned chats list first)
+//! - User-defined tags (case-insensitive, many per conversation)
+//! - Folders (one per conversation); deleting one makes its memories global
+//! - Archive state (archived chats hidden from the default listing)
+//! - Filtered listing by tag, topic, folder, flags, date range and title
+
+#![allow(dead_code)]  // Phase 5: Conversation organization
+
+use crate::services::conversation_topics::topics_by_conversation;
+use crate::services::memory_scope;
+use anyhow::{anyhow, Result};
+use rusqlite::{params_from_iter, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Default and maximum number of conversations per listing
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1_000;
+
+/// Maximum length of a tag / folder name (characters)
+const MAX_NAME_CHARS: usize = 40;
+
+/// `folder_id` filter value matching conversations outside any folder
+pub const NO_FOLDER: &str = "none";
+
+/// Conversation list entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationSummary {
+    pub id: String,
+    pub title: String,
+    pub mode: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub message_count: i32,
+    pub last_message_preview: Option<String>,
+    pub pinned: bool,
+    pub favorite: bool,
+    pub folder_id: Option<String>,
+    /// When the conversation was archived (Unix millis)
+    pub archived_at: Option<i64>,
+    pub tags: Vec<String>,
+    /// Topic labels (see `conversation_topics`)
+    pub topics: Vec<String>,
+}
+
+/// Listing filters; all given filters must match
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConversationFilter {
+    /// Conversations carrying every one of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Conversations labeled with this topic
+    pub topic: Option<String>,
+    /// Folder id, or "none" for conversations outside any folder
+    pub folder_id: Option<String>,
+    #[serde(default)]
+    pub pinned_only: bool,
+    #[serde(default)]
+    pub favorites_only: bool,
+    /// None hides archived conversations, true lists only archived ones,
+    /// false lists both
+    pub archived: Option<bool>,
+    /// Last activity on or after (Unix millis)
+    pub updated_after: Option<i64>,
+    /// Last activity before (Unix millis)
+    pub updated_before: Option<i64>,
+    /// Substring of the title
+    pub query: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// Folder with its conversation count
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationFolder {
+    pub id: String,
+    pub name: String,
+    pub created_at: i64,
+    pub conversation_count: usize,
+}
+
+/// Tag with its conversation count
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+/// Conversations matching `filter`, pinned first, then most recent
+pub fn list_conversations(conn: &Connection, filter: &ConversationFilter) -> Result<Vec<ConversationSummary>> {
+    let mut conditions: Vec<String> = Vec::new();
+    let mut values: Vec<rusqlite::types::Value> = Vec::new();
+
+    let tags = normalize_tags(&filter.tags);
+    if !tags.is_empty() {
+        let placeholders = vec!["?"; tags.len()].join(", ");
+        conditions.push(format!(
+            "c.id IN (SELECT conversation_id FROM conversation_tags WHERE tag IN ({})
+                      GROUP BY conversation_id HAVING COUNT(DISTINCT tag) = {})",
+            placeholders,
+            tags.len()
+        ));
+        values.extend(tags.into_iter().map(rusqlite::types::Value::Text));
+    }
+    if let Some(topic) = filter.topic.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        conditions.push("c.id IN (SELECT conversation_id FROM conversation_topic_labels WHERE topic = ?)".to_string());
+        values.push(topic.to_string().into());
+    }
+    match filter.folder_id.as_deref() {
+        Some(NO_FOLDER) => conditions.push("c.folder_id IS NULL".to_string()),
+        Some(folder_id) => {
+            conditions.push("c.folder_id = ?".to_string());
+            values.push(folder_id.to_string().into());
+        }
+        None => {}
+    }
+    if filter.pinned_only {
+        conditions.push("c.pinned = 1".to_string());
+    }
+    if filter.favorites_only {
+        conditions.push("c.favorite = 1".to_string());
+    }
+    match filter.archived {
+        None => conditions.push("c.archived_at IS NULL".to_string()),
+        Some(true) => conditions.push("c.archived_at IS NOT NULL".to_string()),
+        Some(false) => {}
+    }
+    if let Some(after) = filter.updated_after {
+        conditions.push("c.updated_at >= ?".to_string());
+        values.push(after.into());
+    }
+    if let Some(before) = filter.updated_before {
+        conditions.push("c.updated_at < ?".to_string());
+        values.push(before.into());
+    }
+    if let Some(query) = filter.query.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+        conditions.push("c.title LIKE ? ESCAPE '\\'".to_string());
+        let escaped = query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        values.push(format!("%{}%", escaped).into());
+    }
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+    let limit = filter.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    // Single pass: ROW_NUMBER() finds the most recent message per conversation
+    let sql = format!(
+        "WITH ranked_messages AS (
+            SELECT
+                conversation_id,
+                content,
+                ROW_NUMBER() OVER (PARTITION BY conversation_id ORDER BY timestamp DESC) as rn
+            FROM messages
+            WHERE is_stale = 0
+        )
+        SELECT
+            c.id, c.title, c.mode, c.created_at, c.updated_at, c.message_count,
+            rm.content as last_message, c.pinned, c.favorite, c.folder_id, c.archived_at
+        FROM conversations c
+        LEFT JOIN ranked_messages rm ON c.id = rm.conversation_id AND rm.rn = 1
+        {}
+        ORDER BY c.pinned DESC, c.updated_at DESC
+        LIMIT {}",
+        where_clause, limit
+    );
+
+    let mut stmt = conn.prepare(&sql)?;
+    let mut conversations: Vec<ConversationSummary> = stmt
+        .query_map(params_from_iter(values), |row| {
+            Ok(ConversationSummary {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                mode: row.get(2)?,
+                created_at: row.get(3)?,
+                updated_at: row.get(4)?,
+                message_count: row.get(5)?,
+                last_message_preview: row.get(6).ok(),
+                pinned: row.get::<_, i64>(7)? != 0,
+                favorite: row.get::<_, i64>(8)? != 0,
+                folder_id: row.get(9)?,
+                archived_at: row.get(10)?,
+                tags: Vec::new(),
+                topics: Vec::new(),
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut tags = tags_by_conversation(conn, conversations.iter().map(|c| c.id.as_str()))?;
+    let mut topics = topics_by_conversation(conn, conversations.iter().map(|c| c.id.as_str()))?;
+    for conversation in &mut conversations {
+        conversation.tags = tags.remove(&conversation.id).unwrap_or_default();
+        conversation.topics = topics.remove(&conversation.id).unwrap_or_default();
+    }
+    Ok(conversations)
+}
+
+fn tags_by_conversation<'a>(
+    conn: &Connection,
+    ids: impl Iterator<Item = &'a str>,
+) -> Result<HashMap<String, Vec<String>>> {
+    let ids: Vec<&str> = ids.collect();
+    let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+    if ids.is_empty() {
+        return Ok(tags);
+    }
+    let placeholders = vec!["?"; ids.len()].join(", ");
+    let mut stmt = conn.prepare(&format!(
+        "SELECT conversation_id, tag FROM conversation_tags WHERE conversation_id IN ({}) ORDER BY tag",
+        placeholders
+    ))?;
+    let rows = stmt.query_map(params_from_iter(ids), |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+    for (conversation_id, tag) in rows.filter_map(|r| r.ok()) {
+        tags.entry(conversation_id).or_default().push(tag);
+    }
+    Ok(tags)
+}
+
+pub fn set_pinned(conn: &Connection, conversation_id: &str, pinned: bool) -> Result<()> {
+    update_conversation(conn, conversation_id, "pinned = ?1", pinned)
+}
+
+pub fn set_favorite(conn: &Connection, conversation_id: &str, favorite: bool) -> Result<()> {
+    update_conversation(conn, conversation_id, "favorite = ?1", favorite)
+}
+
+/// Archive (hide from the default listing) or restore a conversation
+pub fn set_archived(conn: &Connection, conversation_id: &str, archived: bool) -> Result<()> {
+    let archived_at = archived.then(|| chrono::Utc::now().timestamp_millis());
+    update_conversation(conn, conversation_id, "archived_at = ?1", archived_at)
+}
+
+/// Move a conversation into a folder, or out of any folder with None
+pub fn move_to_folder(conn: &Connection, conversation_id: &str, folder_id: Option<&str>) -> Result<()> {
+    if let Some(folder_id) = folder_id {
+        let exists = conn
+            .query_row("SELECT 1 FROM conversation_folders WHERE id = ?1", [folder_id], |_| Ok(()))
+            .optional()?
+            .is_some();
+        if !exists {
+            return Err(anyhow!("Folder not found: {}", folder_id));
+        }
+    }
+    update_conversation(conn, conversation_id, "folder_id = ?1", folder_id)
+}
+
+/// Organization changes don't touch `updated_at`, so the chat keeps its place
+fn update_conversation(
+    conn: &Connection,
+    conversation_id: &str,
+    assignment: &str,
+    value: impl rusqlite::ToSql,
+) -> Result<()> {
+    let changed = conn.execute(
+        &format!("UPDATE conversations SET {} WHERE id = ?2", assignment),
+        rusqlite::params![value, conversation_id],
+    )?;
+    if changed == 0 {
+        return Err(anyhow!("Conversation not found: {}", conversation_id));
+    }
+    Ok(())
+}
+
+/// Replace the tags of a conversation; returns the stored tags
+pub fn set_tags(conn: &Connection, conversation_id: &str, tags: &[String]) -> Result<Vec<String>> {
+    let tags = normalize_tags(tags);
+    let exists = conn
+        .query_row("SELECT 1 FROM conversations WHERE id = ?1", [conversation_id], |_| Ok(()))
+        .optional()?
+        .is_some();
+    if !exists {
+        return Err(anyhow!("Conversation not found: {}", conversation_id));
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM conversation_tags WHERE conversation_id = ?1", [conversation_id])?;
+    for tag in &tags {
+        tx.execute(
+            "INSERT OR IGNORE INTO conversation_tags (conversation_id, tag) VALUES (?1, ?2)",
+            [conversation_id, tag.as_str()],
+        )?;
+    }
+    tx.commit()?;
+    Ok(tags)
+}
+
+/// All tags in use, most used first
+pub fn list_tags(conn: &Connection) -> Result<Vec<TagCount>> {
+    let mut stmt = conn.prepare(
+        "SELECT tag, COUNT(*) FROM conversation_tags GROUP BY tag ORDER BY COUNT(*) DESC, tag",
+    )?;
+    let tags = stmt
+        .query_map([], |row| {
+            Ok(TagCount {
+                tag: row.get(0)?,
+                count: row.get::<_, i64>(1)? as usize,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(tags)
+}
+
+pub fn list_folders(conn: &Connection) -> Result<Vec<ConversationFolder>> {
+    let mut stmt = conn.prepare(
+        "SELECT f.id, f.name, f.created_at, COUNT(c.id)
+         FROM conversation_folders f
+         LEFT JOIN conversations c ON c.folder_id = f.id
+         GROUP BY f.id
+         ORDER BY f.name COLLATE NOCASE",
+    )?;
+    let folders = stmt
+        .query_map([], |row| {
+            Ok(ConversationFolder {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                created_at: row.get(2)?,
+                conversation_count: row.get::<_, i64>(3)? as usize,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(folders)
+}
+
+pub fn create_folder(conn: &Connection, name: &str) -> Result<ConversationFolder> {
+    let name = normalize_name(name).ok_or_else(|| anyhow!("Folder name is empty"))?;
+    ensure_folder_name_free(conn, &name, None)?;
+
+    let folder = ConversationFolder {
+        id: format!("folder_{}", uuid::Uuid::new_v4()),
+        name,
+        created_at: chrono::Utc::now().timestamp_millis(),
+        conversation_count: 0,
+    };
+    conn.execute(
+        "INSERT INTO conversation_folders (id, name, created_at) VALUES (?1, ?2, ?3)",
+        rusqlite::params![folder.id, folder.name, folder.created_at],
+    )?;
+    log::info!("Created conversation folder '{}'", folder.name);
+    Ok(folder)
+}
+
+pub fn rename_folder(conn: &Connection, folder_id: &str, name: &str) -> Result<()> {
+    let name = normalize_name(name).ok_or_else(|| anyhow!("Folder name is empty"))?;
+    ensure_folder_name_free(conn, &name, Some(folder_id))?;
+    let changed = conn.execute(
+        "UPDATE conversation_folders SET name = ?1 WHERE id = ?2",
+        [name.as_str(), folder_id],
+    )?;
+    if changed == 0 {
+        return Err(anyhow!("Folder not found: {}", folder_id));
+    }
+    Ok(())
+}
+
+/// Delete a folder; its conversations move out of any folder
+pub fn delete_folder(conn: &Connection, folder_id: &str) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("UPDATE conversations SET folder_id = NULL WHERE folder_id = ?1", [folder_id])?;
+    let deleted = tx.execute("DELETE FROM conversation_folders WHERE id = ?1", [folder_id])?;
+    if deleted == 0 {
+        return Err(anyhow!("Folder not found: {}", folder_id));
+    }
+    // Its conversations are outside any folder now, so are its memories
+    memory_scope::release_project(&tx, folder_id)?;
+    tx.commit()?;
+    Ok(())
+}
+
+fn ensure_folder_name_free(conn: &Connection, name: &str, except_id: Option<&str>) -> Result<()> {
+    let existing: Option<String> = conn
+        .query_row(
+            "SELECT id FROM conversation_folders WHERE name = ?1 COLLATE NOCASE",
+            [name],
+            |row| row.get(0),
+        )
+        .optional()?;
+    match existing {
+        Some(id) if Some(id.as_str()) != except_id => Err(anyhow!("A folder named '{}' already exists", name)),
+        _ => Ok(()),
+    }
+}
+
+/// Trimmed, '#'-less, de-duplicated tags (case-insensitive)
+fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        if let Some(tag) = normalize_name(tag.trim().trim_start_matches('#')) {
+            if !normalized.iter().any(|existing| existing.to_lowercase() == tag.to_lowercase()) {
+                normalized.push(tag);
+            }
+        }
+    }
+    normalized
+}
+
+fn normalize_name(name: &str) -> Option<String> {
+    let name: String = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() {
+        return None;
+    }
+    Some(name.chars().take(MAX_NAME_CHARS).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+
+    fn insert_conversation(conn: &Connection, id: &str, title: &str, updated_at: i64) {
+        conn.execute(
+            "INSERT INTO conversations (id, title, mode, created_at, updated_at, message_count)
+             VALUES (?1, ?2, 'user-led', ?3, ?3, 0)",
+            rusqlite::params![id, title, updated_at],
+        )
+        .unwrap();
+    }
+
+    fn ids(conversations: &[ConversationSummary]) -> Vec<&str> {
+        conversations.iter().map(|c| c.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_filters_and_ordering() {
+        let db = Database::new_test_db().unwrap();
+        let conn = db.conn();
+        insert_conversation(conn, "a", "Rust lifetimes", 1_000);
+        insert_conversation(conn, "b", "Trip to Jeju", 2_000);
+        insert_conversation(conn, "c", "Rust async 100%", 3_000);
+
+        // Pinned first, archived hidden
+        set_pinned(conn, "a", true).unwrap();
+        set_archived(conn, "b", true).unwrap();
+        assert_eq!(ids(&list_conversations(conn, &ConversationFilter::default()).unwrap()), vec!["a", "c"]);
+
+        let archived = ConversationFilter { archived: Some(true), ..Default::default() };
+        assert_eq!(ids(&list_conversations(conn, &archived).unwrap()), vec!["b"]);
+
+        let all = ConversationFilter { archived: Some(false), updated_after: Some(1_500), ..Default::default() };
+        assert_eq!(ids(&list_conversations(conn, &all).unwrap()), vec!["c", "b"]);
+
+        let query = ConversationFilter { query: Some("100%".into()), ..Default::default() };
+        assert_eq!(ids(&list_conversations(conn, &query).unwrap()), vec!["c"]);
+
+        assert!(set_favorite(conn, "missing", true).is_err());
+    }
+
+    #[test]
+    fn test_tags_and_folders() {
+        let db = Database::new_test_db().unwrap();
+        let conn = db.conn();
+        insert_conversation(conn, "a", "Rust lifetimes", 1_000);
+        insert_conversation(conn, "b", "Rust async", 2_000);
+
+        let stored = set_tags(conn, "a", &["#rust".into(), "Rust".into(), " work ".into(), "".into()]).unwrap();
+        assert_eq!(stored, vec!["rust", "work"]);
+        set_tags(conn, "b", &["RUST".into()]).unwrap();
+
+        let by_tags = |tags: &[&str]| {
+            let filter = ConversationFilter { tags: tags.iter().map(|t| t.to_string()).collect(), ..Default::default() };
+            ids(&list_conversations(conn, &filter).unwrap()).into_iter().map(str::to_string).collect::<Vec<_>>()
+        };
+        assert_eq!(by_tags(&["rust"]), vec!["b", "a"]);
+        assert_eq!(by_tags(&["rust", "work"]), vec!["a"]);
+        assert_eq!(list_tags(conn).unwrap()[0].count, 2);
+
+        let folder = create_folder(conn, "  Side   projects ").unwrap();
+        assert_eq!(folder.name, "Side projects");
+        assert!(create_folder(conn, "side projects").is_err());
+        move_to_folder(conn, "a", Some(&folder.id)).unwrap();
+        assert!(move_to_folder(conn, "a", Some("folder_missing")).is_err());
+
+        let in_folder = ConversationFilter { folder_id: Some(folder.id.clone()), ..Default::default() };
+        let listed = list_conversations(conn, &in_folder).unwrap();
+        assert_eq!(ids(&listed), vec!["a"]);
+        assert_eq!(listed[0].tags, vec!["rust", "work"]);
+        assert_eq!(list_folders(conn).unwrap()[0].conversation_count, 1);
+
+        conn.execute(
+            "INSERT INTO episodic_memory (id, user_message, ai_response, satisfaction, created_at, scope, scope_id)
+             VALUES ('m1', 'q', 'a', 0.5, 0, 'project', ?1)",
+            [&folder.id],
+        )
+        .unwrap();
+        delete_folder(conn, &folder.id).unwrap();
+        let unfiled = ConversationFilter { folder_id: Some(NO_FOLDER.into()), ..Default::default() };
+        assert_eq!(list_conversations(conn, &unfiled).unwrap().len(), 2);
+        let scope: String = conn.query_row("SELECT scope FROM episodic_memory WHERE id = 'm1'", [], |row| row.get(0)).unwrap();
+        assert_eq!(scope, "global");
+    }
+}
@@ -0,0 +1,489 @@
+//! Conversation Topics Service (v3.9.0)
+//!
+//! Titles and topic labels for conversations, generated in the background
+//! once a conversation has a few turns.
+//!
+//! Features:
+//! - Titles for conversations still named "New Chat" (user titles are never touched)
+//! - 1-3 topic labels per conversation from a learned taxonomy: the model is shown
+//!   the topics already in use and only adds a new one when none fits
+//! - User-set topics override automatic labels and are never relabeled
+//! - Topics per conversation for filtered listing and the activity timeline
+
+#![allow(dead_code)]  // Phase 5: Conversation topics (some helpers used by future UI)
+
+use crate::database::Database;
+use crate::services::ollama;
+use anyhow::{anyhow, Result};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Titles given to conversations on creation; only these get replaced
+pub const DEFAULT_TITLES: &[&str] = &["New Chat", "New Chat (Tools)", "New Conversation", ""];
+
+/// Messages (both roles) before a conversation is labeled
+const MIN_MESSAGES: i64 = 4;
+
+/// Messages and characters per message shown to the model
+const MAX_TRANSCRIPT_MESSAGES: usize = 8;
+const MAX_MESSAGE_CHARS: usize = 400;
+
+/// Known topics listed in the prompt (most recently used first)
+const MAX_KNOWN_TOPICS: usize = 40;
+
+const MAX_TOPICS_PER_CONVERSATION: usize = 3;
+const MAX_TOPIC_CHARS: usize = 40;
+const MAX_TOPIC_WORDS: usize = 4;
+const MAX_TITLE_CHARS: usize = 60;
+
+/// Completion budget for the title + topics JSON
+const LABEL_MAX_TOKENS: i32 = 120;
+
+/// Conversations labeled per background run
+pub const DEFAULT_BATCH_SIZE: usize = 10;
+
+/// Result of labeling one conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationLabels {
+    pub conversation_id: String,
+    /// New title, if the conversation was still untitled
+    pub title: Option<String>,
+    pub topics: Vec<String>,
+}
+
+/// Topic in the taxonomy with its usage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicCount {
+    pub topic: String,
+    pub conversation_count: usize,
+    pub last_used_at: i64,
+}
+
+/// Outcome of a background labeling run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LabelingReport {
+    pub labeled: usize,
+    pub titled: usize,
+    pub failed: usize,
+}
+
+#[derive(Deserialize)]
+struct RawLabels {
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    topics: Vec<String>,
+}
+
+/// Topics of each conversation in `ids`, sorted by name
+pub fn topics_by_conversation<'a>(
+    conn: &Connection,
+    ids: impl Iterator<Item = &'a str>,
+) -> Result<HashMap<String, Vec<String>>> {
+    let ids: Vec<&str> = ids.collect();
+    let mut topics: HashMap<String, Vec<String>> = HashMap::new();
+    if ids.is_empty() {
+        return Ok(topics);
+    }
+    let placeholders = vec!["?"; ids.len()].join(", ");
+    let mut stmt = conn.prepare(&format!(
+        "SELECT conversation_id, topic FROM conversation_topic_labels WHERE conversation_id IN ({}) ORDER BY topic",
+        placeholders
+    ))?;
+    let rows = stmt.query_map(params_from_iter(ids), |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+    for (conversation_id, topic) in rows.filter_map(|r| r.ok()) {
+        topics.entry(conversation_id).or_default().push(topic);
+    }
+    Ok(topics)
+}
+
+/// Conversation topics service
+pub struct ConversationTopicsService {
+    db: Arc<Mutex<Database>>,
+}
+
+impl ConversationTopicsService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Self {
+        log::info!("✓ Conversation Topics Service initialized");
+        Self { db }
+    }
+
+    /// Conversations with enough turns that haven't been labeled yet, most recent first
+    pub fn pending_conversations(&self, limit: usize) -> Result<Vec<String>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT id FROM conversations
+             WHERE topics_labeled_at IS NULL AND archived_at IS NULL AND message_count >= ?1
+             ORDER BY updated_at DESC
+             LIMIT ?2",
+        )?;
+        let ids = stmt
+            .query_map(params![MIN_MESSAGES, limit as i64], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(ids)
+    }
+
+    /// Title (if untitled) and label one conversation now
+    pub async fn label_conversation(&self, conversation_id: &str) -> Result<ConversationLabels> {
+        let (transcript, first_user_message, known_topics) = {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            let conn = db.conn();
+            let messages = load_messages(conn, conversation_id)?;
+            if messages.is_empty() {
+                return Err(anyhow!("Conversation has no messages: {}", conversation_id));
+            }
+            let first_user_message = messages
+                .iter()
+                .find(|(role, _)| role == "user")
+                .map(|(_, content)| content.clone())
+                .unwrap_or_default();
+            (format_transcript(&messages), first_user_message, known_topics(conn)?)
+        };
+
+        let reply = ollama::label_conversation(&transcript, &known_topics, LABEL_MAX_TOKENS)
+            .await
+            .map_err(|e| anyhow!("Conversation labeling failed: {}", e))?;
+        let (title, topics) = match parse_labels(&reply) {
+            Some(labels) => labels,
+            None => {
+                log::warn!("Unparseable conversation labels for {}: {}", conversation_id, reply);
+                (None, Vec::new())
+            }
+        };
+        let title = title.or_else(|| fallback_title(&first_user_message));
+
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        store_labels(db.conn(), conversation_id, title.as_deref(), &topics, chrono::Utc::now().timestamp_millis())
+    }
+
+    /// Label up to `limit` pending conversations (the background job's entry point)
+    pub async fn label_pending(&self, limit: usize) -> Result<LabelingReport> {
+        let mut report = LabelingReport::default();
+        for conversation_id in self.pending_conversations(limit)? {
+            match self.label_conversation(&conversation_id).await {
+                Ok(labels) => {
+                    report.labeled += 1;
+                    if labels.title.is_some() {
+                        report.titled += 1;
+                    }
+                }
+                Err(e) => {
+                    log::warn!("Failed to label conversation {}: {}", conversation_id, e);
+                    report.failed += 1;
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Topic taxonomy with conversation counts, most used first
+    pub fn list_topics(&self) -> Result<Vec<TopicCount>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT t.name, COUNT(l.conversation_id), t.last_used_at
+             FROM conversation_topics t
+             LEFT JOIN conversation_topic_labels l ON l.topic = t.name
+             GROUP BY t.name
+             ORDER BY COUNT(l.conversation_id) DESC, t.name",
+        )?;
+        let topics = stmt
+            .query_map([], |row| {
+                Ok(TopicCount {
+                    topic: row.get(0)?,
+                    conversation_count: row.get::<_, i64>(1)? as usize,
+                    last_used_at: row.get(2)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(topics)
+    }
+
+    pub fn get_topics(&self, conversation_id: &str) -> Result<Vec<String>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut topics = topics_by_conversation(db.conn(), std::iter::once(conversation_id))?;
+        Ok(topics.remove(conversation_id).unwrap_or_default())
+    }
+
+    /// Replace a conversation's topics with user-chosen ones (no longer relabeled automatically)
+    pub fn set_topics(&self, conversation_id: &str, topics: &[String]) -> Result<Vec<String>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let conn = db.conn();
+        let now = chrono::Utc::now().timestamp_millis();
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM conversations WHERE id = ?1)",
+            [conversation_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(anyhow!("Conversation not found: {}", conversation_id));
+        }
+
+        let tx = conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM conversation_topic_labels WHERE conversation_id = ?1", [conversation_id])?;
+        let topics = insert_topics(&tx, conversation_id, topics, "user", now)?;
+        tx.execute(
+            "UPDATE conversations SET topics_labeled_at = ?1 WHERE id = ?2",
+            params![now, conversation_id],
+        )?;
+        tx.commit()?;
+        Ok(topics)
+    }
+}
+
+/// Non-stale messages in order, at most `MAX_TRANSCRIPT_MESSAGES`
+fn load_messages(conn: &Connection, conversation_id: &str) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT role, content FROM messages
+         WHERE conversation_id = ?1 AND is_stale = 0
+         ORDER BY timestamp ASC
+         LIMIT ?2",
+    )?;
+    let messages = stmt
+        .query_map(params![conversation_id, MAX_TRANSCRIPT_MESSAGES as i64], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(messages)
+}
+
+fn format_transcript(messages: &[(String, String)]) -> String {
+    messages
+        .iter()
+        .map(|(role, content)| {
+            let speaker = if role == "user" { "User" } else { "Assistant" };
+            format!("{}: {}", speaker, truncate_chars(content.trim(), MAX_MESSAGE_CHARS))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn known_topics(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT name FROM conversation_topics ORDER BY last_used_at DESC LIMIT ?1")?;
+    let topics = stmt
+        .query_map([MAX_KNOWN_TOPICS as i64], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(topics)
+}
+
+/// Apply generated labels: the title only replaces a default title, and
+/// conversations with user-set topics keep them
+fn store_labels(
+    conn: &Connection,
+    conversation_id: &str,
+    title: Option<&str>,
+    topics: &[String],
+    now: i64,
+) -> Result<ConversationLabels> {
+    let tx = conn.unchecked_transaction()?;
+
+    let placeholders = vec!["?"; DEFAULT_TITLES.len()].join(", ");
+    let titled = match title {
+        Some(title) => {
+            let mut values: Vec<&str> = vec![title, conversation_id];
+            values.extend(DEFAULT_TITLES.iter().copied());
+            tx.execute(
+                &format!(
+                    "UPDATE conversations SET title = ? WHERE id = ? AND TRIM(title) IN ({})",
+                    placeholders
+                ),
+                params_from_iter(values),
+            )? > 0
+        }
+        None => false,
+    };
+
+    let user_labeled: bool = tx.query_row(
+        "SELECT EXISTS(SELECT 1 FROM conversation_topic_labels WHERE conversation_id = ?1 AND source = 'user')",
+        [conversation_id],
+        |row| row.get(0),
+    )?;
+    let topics = if user_labeled {
+        topics_by_conversation(&tx, std::iter::once(conversation_id))?
+            .remove(conversation_id)
+            .unwrap_or_default()
+    } else {
+        tx.execute(
+            "DELETE FROM conversation_topic_labels WHERE conversation_id = ?1 AND source = 'auto'",
+            [conversation_id],
+        )?;
+        insert_topics(&tx, conversation_id, topics, "auto", now)?
+    };
+
+    tx.execute(
+        "UPDATE conversations SET topics_labeled_at = ?1 WHERE id = ?2",
+        params![now, conversation_id],
+    )?;
+    tx.commit()?;
+
+    log::info!("Labeled conversation {} with {} topics", conversation_id, topics.len());
+    Ok(ConversationLabels {
+        conversation_id: conversation_id.to_string(),
+        title: if titled { title.map(str::to_string) } else { None },
+        topics,
+    })
+}
+
+/// Insert labels, reusing the taxonomy's spelling of known topics
+fn insert_topics(conn: &Connection, conversation_id: &str, topics: &[String], source: &str, now: i64) -> Result<Vec<String>> {
+    let mut stored: Vec<String> = Vec::new();
+    for topic in topics.iter().filter_map(|topic| normalize_topic(topic)) {
+        if stored.len() >= MAX_TOPICS_PER_CONVERSATION {
+            break;
+        }
+        let name: String = match conn
+            .query_row("SELECT name FROM conversation_topics WHERE name = ?1", [&topic], |row| row.get(0))
+            .optional()?
+        {
+            Some(existing) => {
+                conn.execute("UPDATE conversation_topics SET last_used_at = ?1 WHERE name = ?2", params![now, &existing])?;
+                existing
+            }
+            None => {
+                conn.execute(
+                    "INSERT INTO conversation_topics (name, created_at, last_used_at) VALUES (?1, ?2, ?2)",
+                    params![&topic, now],
+                )?;
+                topic
+            }
+        };
+        if stored.iter().any(|existing| existing.eq_ignore_ascii_case(&name)) {
+            continue;
+        }
+        conn.execute(
+            "INSERT OR IGNORE INTO conversation_topic_labels (conversation_id, topic, source, assigned_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![conversation_id, &name, source, now],
+        )?;
+        stored.push(name);
+    }
+    stored.sort();
+    Ok(stored)
+}
+
+/// Trimmed, single-spaced topic, or None if it isn't a short label
+fn normalize_topic(topic: &str) -> Option<String> {
+    let topic = topic.trim().trim_start_matches('#').trim_matches(|c: char| c == '"' || c == '\'' || c == '.');
+    let words: Vec<&str> = topic.split_whitespace().collect();
+    if words.is_empty() || words.len() > MAX_TOPIC_WORDS {
+        return None;
+    }
+    let topic = words.join(" ").to_lowercase();
+    if topic.chars().count() > MAX_TOPIC_CHARS {
+        return None;
+    }
+    Some(topic)
+}
+
+/// Title and topics from the model's JSON reply (tolerates text around the object)
+fn parse_labels(reply: &str) -> Option<(Option<String>, Vec<String>)> {
+    let start = reply.find('{')?;
+    let end = reply.rfind('}')?;
+    if end < start {
+        return None;
+    }
+    let raw: RawLabels = serde_json::from_str(&reply[start..=end]).ok()?;
+    let title = raw.title.as_deref().and_then(clean_title);
+    Some((title, raw.topics))
+}
+
+fn clean_title(title: &str) -> Option<String> {
+    let title = title
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .trim_end_matches('.')
+        .trim_matches(|c: char| c == '"' || c == '\'' || c == '`')
+        .trim_end_matches('.')
+        .trim();
+    if title.is_empty() || DEFAULT_TITLES.contains(&title) {
+        return None;
+    }
+    Some(truncate_chars(title, MAX_TITLE_CHARS))
+}
+
+/// Title from the first user message when the model gives none
+fn fallback_title(first_user_message: &str) -> Option<String> {
+    let line = first_user_message.lines().map(str::trim).find(|line| !line.is_empty())?;
+    clean_title(line)
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    format!("{}…", truncated.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn insert_conversation(conn: &Connection, id: &str, title: &str) {
+        conn.execute(
+            "INSERT INTO conversations (id, title, mode, created_at, updated_at, message_count)
+             VALUES (?1, ?2, 'user-led', 1000, 1000, 4)",
+            params![id, title],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_parse_labels() {
+        let reply = "Sure!\n{\"title\": \"\\\"Tokio 런타임 설정\\\".\", \"topics\": [\"Rust\", \"async programming\"]}";
+        let (title, topics) = parse_labels(reply).unwrap();
+        assert_eq!(title.as_deref(), Some("Tokio 런타임 설정"));
+        assert_eq!(topics, vec!["Rust", "async programming"]);
+        assert!(parse_labels("no json here").is_none());
+
+        assert_eq!(normalize_topic("  #Machine   Learning "), Some("machine learning".to_string()));
+        assert_eq!(normalize_topic("a very long topic label here"), None);
+        assert_eq!(fallback_title("\n  How do I deploy this?\nmore").as_deref(), Some("How do I deploy this?"));
+    }
+
+    #[test]
+    fn test_store_labels_respects_user_choices() {
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let service = ConversationTopicsService::new(Arc::clone(&db));
+        {
+            let guard = db.lock().unwrap();
+            insert_conversation(guard.conn(), "a", "New Chat");
+            insert_conversation(guard.conn(), "b", "My title");
+        }
+        assert_eq!(service.pending_conversations(10).unwrap().len(), 2);
+
+        {
+            let guard = db.lock().unwrap();
+            let topics = vec!["Rust".to_string(), "rust".to_string(), "Databases".to_string()];
+            let labels = store_labels(guard.conn(), "a", Some("SQLite 튜닝"), &topics, 2000).unwrap();
+            assert_eq!(labels.title.as_deref(), Some("SQLite 튜닝"));
+            assert_eq!(labels.topics, vec!["databases", "rust"]);
+
+            // User titles stay
+            let labels = store_labels(guard.conn(), "b", Some("Other"), &["RUST".to_string()], 3000).unwrap();
+            assert!(labels.title.is_none());
+            assert_eq!(labels.topics, vec!["rust"]);
+        }
+        assert!(service.pending_conversations(10).unwrap().is_empty());
+
+        service.set_topics("a", &["Cooking".to_string()]).unwrap();
+        {
+            let guard = db.lock().unwrap();
+            let labels = store_labels(guard.conn(), "a", None, &["rust".to_string()], 4000).unwrap();
+            assert_eq!(labels.topics, vec!["cooking"]);
+        }
+
+        let topics = service.list_topics().unwrap();
+        assert_eq!(topics.len(), 3);
+        assert!(topics.iter().any(|t| t.topic == "rust" && t.conversation_count == 1));
+        assert_eq!(topics.last().map(|t| t.topic.as_str()), Some("databases"));
+        assert!(service.set_topics("missing", &[]).is_err());
+    }
+}
@@ -8,19 +8,331 @@
  * - Optional Sentry reporting (opt-in only)
  * - Provides user control over crash reporting
  * - Sanitizes sensitive data before sending
+ * - Sentry-compatible envelope upload to a configurable DSN (v3.9.0)
+ * - PII scrubbing of paths and message contents (v3.9.0)
+ * - Breadcrumbs from recent tracing events (v3.9.0)
+ * - Review-before-send queue: nothing leaves the machine unseen (v3.9.0)
  */
 
 use anyhow::{anyhow, Result};
 use log::{error, info, warn};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::fs;
 use std::panic;
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Recent tracing events kept as breadcrumbs
+const MAX_BREADCRUMBS: usize = 50;
+
+/// Longest message kept per breadcrumb or uploaded field
+const MAX_SCRUBBED_CHARS: usize = 500;
+
+/// Upload timeout for a single report
+const UPLOAD_TIMEOUT_SECS: u64 = 10;
+
+static BREADCRUMBS: Mutex<VecDeque<Breadcrumb>> = Mutex::new(VecDeque::new());
+
+/// Recent log event attached to crash reports (v3.9.0)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Breadcrumb {
+    pub timestamp: i64,
+    pub level: String,
+    pub category: String,
+    pub message: String,
+}
+
+#[derive(Default)]
+struct BreadcrumbFields {
+    message: String,
+    log_target: Option<String>,
+}
+
+impl Visit for BreadcrumbFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "message" => self.message = value.to_string(),
+            "log.target" => self.log_target = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        }
+    }
+}
+
+/// Tracing layer that keeps the last `MAX_BREADCRUMBS` INFO+ events in memory (v3.9.0)
+///
+/// Messages are stored raw and only scrubbed when a report is built.
+pub struct BreadcrumbLayer;
+
+impl<S: Subscriber> Layer<S> for BreadcrumbLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if *metadata.level() > Level::INFO {
+            return;
+        }
+
+        let mut fields = BreadcrumbFields::default();
+        event.record(&mut fields);
+
+        let breadcrumb = Breadcrumb {
+            timestamp: chrono::Utc::now().timestamp(),
+            level: metadata.level().as_str().to_lowercase(),
+            // log-crate events arrive through the bridge with their real target in a field
+            category: fields.log_target.unwrap_or_else(|| metadata.target().to_string()),
+            message: truncate_chars(&fields.message, MAX_SCRUBBED_CHARS * 2),
+        };
+
+        // try_lock: never block (or deadlock inside a panic) just to record a breadcrumb
+        if let Ok(mut breadcrumbs) = BREADCRUMBS.try_lock() {
+            if breadcrumbs.len() >= MAX_BREADCRUMBS {
+                breadcrumbs.pop_front();
+            }
+            breadcrumbs.push_back(breadcrumb);
+        }
+    }
+}
+
+/// Scrubbed copy of the current breadcrumbs, oldest first
+pub fn recent_breadcrumbs() -> Vec<Breadcrumb> {
+    match BREADCRUMBS.try_lock() {
+        Ok(breadcrumbs) => breadcrumbs
+            .iter()
+            .map(|b| Breadcrumb {
+                message: scrub_pii(&b.message),
+                ..b.clone()
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((idx, _)) => format!("{}…", &text[..idx]),
+        None => text.to_string(),
+    }
+}
+
+fn path_regexes() -> &'static [Regex] {
+    static RES: OnceLock<Vec<Regex>> = OnceLock::new();
+    RES.get_or_init(|| {
+        [
+            // /Users/alice/project/ and $HOME/project/ (the file name is kept)
+            r#"(^|[\s"'(=\[{,])(?:\$HOME|~)?(?:/[^/\s"':]+)+/"#,
+            // C:\Users\alice\project\
+            r#"(^|[\s"'(=\[{,])[A-Za-z]:\\(?:[^\\\s"':]+\\)+"#,
+        ]
+        .iter()
+        .map(|p| Regex::new(p).unwrap())
+        .collect()
+    })
+}
+
+fn content_regexes() -> &'static [(Regex, &'static str)] {
+    static RES: OnceLock<Vec<(Regex, &'static str)>> = OnceLock::new();
+    RES.get_or_init(|| {
+        [
+            (r"\b[a-zA-Z][a-zA-Z0-9+.-]*://\S+", "[url]"),
+            (r"[\w.+-]+@[\w-]+\.[\w.-]+", "[email]"),
+            // Quoted text is usually user content (prompts, messages, file contents)
+            (r#""[^"\n]*""#, "\"[redacted]\""),
+            (r"(^|[^\w])'[^'\n]*'", "$1'[redacted]'"),
+            (r"(?i)\b(bearer|token|password|secret|api[_-]?key)(\s*[:=]?\s*)\S+", "$1$2[REDACTED]"),
+            (r"\b[A-Za-z0-9_-]{32,}\b", "[REDACTED]"),
+        ]
+        .iter()
+        .map(|(p, r)| (Regex::new(p).unwrap(), *r))
+        .collect()
+    })
+}
+
+/// Strip directory components from absolute paths (home dirs, user names)
+///
+/// Used on stack traces, where quoted function names must survive.
+pub fn scrub_paths(text: &str) -> String {
+    let mut scrubbed = text.to_string();
+    if let Ok(home) = std::env::var("HOME") {
+        if !home.is_empty() {
+            scrubbed = scrubbed.replace(&home, "$HOME");
+        }
+    }
+    for re in path_regexes() {
+        scrubbed = re.replace_all(&scrubbed, "${1}[path]/").to_string();
+    }
+    scrubbed
+}
+
+/// Scrub everything that could identify the user or leak their content
+///
+/// Removes URLs, emails, quoted text, credentials and long tokens, strips
+/// paths, and caps the length. Used on messages, context and breadcrumbs.
+pub fn scrub_pii(text: &str) -> String {
+    let mut scrubbed = text.to_string();
+    for (re, replacement) in content_regexes() {
+        scrubbed = re.replace_all(&scrubbed, *replacement).to_string();
+    }
+    truncate_chars(&scrub_paths(&scrubbed), MAX_SCRUBBED_CHARS)
+}
+
+/// Parsed Sentry DSN (`https://<public_key>@<host>/<project_id>`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentryDsn {
+    pub public_key: String,
+    pub envelope_url: String,
+}
+
+/// Parse a Sentry-compatible DSN into its envelope endpoint
+pub fn parse_dsn(dsn: &str) -> Result<SentryDsn> {
+    let url = reqwest::Url::parse(dsn.trim()).map_err(|e| anyhow!("Invalid DSN: {}", e))?;
+    if url.scheme() != "https" && url.scheme() != "http" {
+        return Err(anyhow!("Invalid DSN: unsupported scheme {}", url.scheme()));
+    }
+    if url.username().is_empty() {
+        return Err(anyhow!("Invalid DSN: missing public key"));
+    }
+    let host = url.host_str().ok_or_else(|| anyhow!("Invalid DSN: missing host"))?;
+
+    let path = url.path().trim_end_matches('/');
+    let (prefix, project_id) = path.rsplit_once('/').unwrap_or(("", path));
+    if project_id.is_empty() {
+        return Err(anyhow!("Invalid DSN: missing project id"));
+    }
+
+    let port = url.port().map(|p| format!(":{}", p)).unwrap_or_default();
+    Ok(SentryDsn {
+        public_key: url.username().to_string(),
+        envelope_url: format!("{}://{}{}{}/api/{}/envelope/", url.scheme(), host, port, prefix, project_id),
+    })
+}
+
+/// Build the Sentry event for a report, scrubbing every free-text field
+pub fn build_event(report: &CrashReport) -> serde_json::Value {
+    let level = if report.error_type == "Panic" { "fatal" } else { "error" };
+    let breadcrumbs: Vec<serde_json::Value> = report
+        .breadcrumbs
+        .iter()
+        .map(|b| {
+            serde_json::json!({
+                "timestamp": b.timestamp,
+                "level": b.level,
+                "category": b.category,
+                "message": scrub_pii(&b.message),
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "event_id": report.id,
+        "timestamp": report.timestamp,
+        "platform": "native",
+        "level": level,
+        "release": format!("garden-of-eden-v3@{}", report.app_version),
+        "contexts": { "os": { "name": report.os_version } },
+        "exception": {
+            "values": [{ "type": report.error_type, "value": scrub_pii(&report.error_message) }]
+        },
+        "extra": {
+            "context": report.context.as_deref().map(scrub_pii),
+            "stack_trace": report.stack_trace.as_deref().map(scrub_paths),
+        },
+        "breadcrumbs": { "values": breadcrumbs },
+    })
+}
+
+/// Report held for review before it is sent (v3.9.0)
+///
+/// `payload` is exactly the event that will be uploaded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingCrashReport {
+    pub id: String,
+    pub created_at: i64,
+    pub error_type: String,
+    pub payload: serde_json::Value,
+}
+
+/// Ready-to-send envelope, built under the service lock and sent without it
+#[derive(Debug, Clone)]
+pub struct CrashUpload {
+    pub id: String,
+    pub url: String,
+    pub auth_header: String,
+    pub body: String,
+}
+
+impl CrashUpload {
+    /// POST the envelope to the Sentry-compatible endpoint
+    pub async fn send(&self) -> Result<()> {
+        let response = reqwest::Client::new()
+            .post(&self.url)
+            .header("X-Sentry-Auth", &self.auth_header)
+            .header("Content-Type", "application/x-sentry-envelope")
+            .timeout(std::time::Duration::from_secs(UPLOAD_TIMEOUT_SECS))
+            .body(self.body.clone())
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Crash report upload failed with status {}", response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// Send one reviewed report and drop it from the queue
+pub async fn send_pending_report(service: &Arc<Mutex<CrashReporterService>>, id: &str) -> Result<()> {
+    let upload = {
+        let service = service.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        service.prepare_upload(id)?
+    };
+
+    upload.send().await?;
+
+    service
+        .lock()
+        .map_err(|e| anyhow!("Lock error: {}", e))?
+        .remove_pending_report(id)?;
+    info!("Crash report {} sent", id);
+    Ok(())
+}
+
+/// Send everything queued when the user opted out of reviewing first
+///
+/// Panics can't upload from inside the hook, so they are flushed on the next start.
+pub async fn flush_pending_reports(service: Arc<Mutex<CrashReporterService>>) -> Result<usize> {
+    let ids: Vec<String> = {
+        let service = service.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let settings = service.get_settings();
+        if !service.is_enabled() || settings.review_before_send {
+            return Ok(0);
+        }
+        service.get_pending_reports()?.into_iter().map(|r| r.id).collect()
+    };
+
+    let mut sent = 0;
+    for id in ids {
+        match send_pending_report(&service, &id).await {
+            Ok(()) => sent += 1,
+            Err(e) => warn!("Failed to send crash report {}: {}", id, e),
+        }
+    }
+    Ok(sent)
+}
 
 /// Crash report data (sanitized)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrashReport {
+    #[serde(default)]
+    pub id: String,
     pub timestamp: i64,
     pub error_message: String,
     pub error_type: String,
@@ -28,6 +340,8 @@ pub struct CrashReport {
     pub app_version: String,
     pub os_version: String,
     pub context: Option<String>,
+    #[serde(default)]
+    pub breadcrumbs: Vec<Breadcrumb>,
 }
 
 /// Crash reporting settings
@@ -36,6 +350,16 @@ pub struct CrashReportingSettings {
     pub enabled: bool,
     pub send_diagnostics: bool,
     pub send_performance_data: bool,
+    /// Sentry-compatible DSN reports are uploaded to (v3.9.0)
+    #[serde(default)]
+    pub dsn: Option<String>,
+    /// Hold reports until the user reviews and sends them (v3.9.0)
+    #[serde(default = "default_review_before_send")]
+    pub review_before_send: bool,
+}
+
+fn default_review_before_send() -> bool {
+    true
 }
 
 impl Default for CrashReportingSettings {
@@ -44,6 +368,8 @@ impl Default for CrashReportingSettings {
             enabled: false, // Privacy-first: opt-in by default
             send_diagnostics: false,
             send_performance_data: false,
+            dsn: None,
+            review_before_send: true,
         }
     }
 }
@@ -51,9 +377,7 @@ impl Default for CrashReportingSettings {
 /// Crash Reporter Service
 pub struct CrashReporterService {
     settings: Mutex<CrashReportingSettings>,
-    sentry_dsn: Option<String>,
     crash_log_dir: PathBuf,
-    initialized: bool,
 }
 
 impl CrashReporterService {
@@ -64,14 +388,30 @@ impl CrashReporterService {
             error!("Failed to create crash log directory: {}", e);
         }
 
+        if let Err(e) = fs::create_dir_all(crash_log_dir.join("pending")) {
+            error!("Failed to create pending crash report directory: {}", e);
+        }
+
+        // Opt-in and DSN must survive restarts, or queued panics could never be sent
+        let settings = fs::read_to_string(crash_log_dir.join("settings.json"))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+
         Self {
-            settings: Mutex::new(CrashReportingSettings::default()),
-            sentry_dsn: None,
+            settings: Mutex::new(settings),
             crash_log_dir,
-            initialized: false,
         }
     }
 
+    fn save_settings(&self, settings: &CrashReportingSettings) -> Result<()> {
+        fs::write(
+            self.crash_log_dir.join("settings.json"),
+            serde_json::to_string_pretty(settings)?,
+        )?;
+        Ok(())
+    }
+
     /// Setup panic handler to capture crashes (v3.4.0)
     ///
     /// This should be called early in the application startup
@@ -118,10 +458,10 @@ impl CrashReporterService {
                     error!("Failed to save crash report: {}", e);
                 }
 
-                // Send to Sentry if enabled (opt-in)
+                // Queue for upload if enabled (opt-in); sent after review or on next start
                 if service_lock.is_enabled() {
-                    if let Err(e) = service_lock.report_error(&message, Some(&location)) {
-                        error!("Failed to send crash report to Sentry: {}", e);
+                    if let Err(e) = service_lock.queue_for_upload(&crash_report) {
+                        error!("Failed to queue crash report for upload: {}", e);
                     }
                 }
             }
@@ -133,52 +473,36 @@ impl CrashReporterService {
         info!("✓ Panic handler installed successfully");
     }
 
-    /// Initialize Sentry (only if user opted in)
+    /// Configure the Sentry-compatible DSN used for remote submission
     pub fn initialize(&mut self, dsn: Option<String>) -> Result<()> {
-        if self.initialized {
-            warn!("Crash reporter already initialized");
-            return Ok(());
-        }
-
-        let settings = self.settings.lock().unwrap();
-        if !settings.enabled {
-            info!("Crash reporting disabled by user");
-            return Ok(());
-        }
-
-        if let Some(dsn_value) = dsn.clone() {
-            info!("Initializing Sentry crash reporting...");
-
-            // In production, initialize Sentry here:
-            // let _guard = sentry::init((
-            //     dsn_value.as_str(),
-            //     sentry::ClientOptions {
-            //         release: Some(env!("CARGO_PKG_VERSION").into()),
-            //         ..Default::default()
-            //     },
-            // ));
-
-            self.sentry_dsn = Some(dsn_value);
-            self.initialized = true;
+        let dsn_value = match dsn {
+            Some(dsn_value) => dsn_value,
+            None => {
+                warn!("No Sentry DSN provided, remote crash reporting disabled");
+                return Ok(());
+            }
+        };
+        parse_dsn(&dsn_value)?;
 
-            info!("Sentry crash reporting initialized");
-        } else {
-            warn!("No Sentry DSN provided, crash reporting disabled");
-        }
+        let mut settings = self.settings.lock().unwrap();
+        settings.dsn = Some(dsn_value);
+        self.save_settings(&settings)?;
 
+        info!("Sentry-compatible crash reporting configured");
         Ok(())
     }
 
-    /// Check if crash reporting is enabled
+    /// Check if crash reporting is enabled (opted in with a DSN configured)
     pub fn is_enabled(&self) -> bool {
         let settings = self.settings.lock().unwrap();
-        settings.enabled && self.initialized
+        settings.enabled && settings.dsn.as_deref().is_some_and(|dsn| !dsn.trim().is_empty())
     }
 
     /// Enable crash reporting (user opt-in)
     pub fn enable(&self) -> Result<()> {
         let mut settings = self.settings.lock().unwrap();
         settings.enabled = true;
+        self.save_settings(&settings)?;
         info!("Crash reporting enabled by user");
         Ok(())
     }
@@ -187,6 +511,7 @@ impl CrashReporterService {
     pub fn disable(&self) -> Result<()> {
         let mut settings = self.settings.lock().unwrap();
         settings.enabled = false;
+        self.save_settings(&settings)?;
         info!("Crash reporting disabled by user");
         Ok(())
     }
@@ -198,35 +523,130 @@ impl CrashReporterService {
 
     /// Update settings
     pub fn update_settings(&self, new_settings: CrashReportingSettings) -> Result<()> {
+        if let Some(dsn) = new_settings.dsn.as_deref().filter(|dsn| !dsn.trim().is_empty()) {
+            parse_dsn(dsn)?;
+        }
+
         let mut settings = self.settings.lock().unwrap();
         *settings = new_settings;
+        self.save_settings(&settings)?;
         info!("Crash reporting settings updated");
         Ok(())
     }
 
     /// Report an error (manual capture)
-    pub fn report_error(&self, error_message: &str, context: Option<&str>) -> Result<()> {
+    ///
+    /// Queues the scrubbed report for review and returns its pending id.
+    pub fn report_error(&self, error_message: &str, context: Option<&str>) -> Result<Option<String>> {
         if !self.is_enabled() {
-            return Ok(()); // Silently skip if disabled
+            return Ok(None); // Silently skip if disabled
+        }
+
+        info!("Reporting error: {}", scrub_pii(error_message));
+
+        let report = Self::create_crash_report(
+            error_message,
+            "Error",
+            None,
+            context.map(str::to_string),
+        );
+        self.queue_for_upload(&report).map(Some)
+    }
+
+    fn pending_dir(&self) -> PathBuf {
+        self.crash_log_dir.join("pending")
+    }
+
+    /// Pending ids are generated uuids; anything else could escape the directory
+    fn pending_path(&self, id: &str) -> Result<PathBuf> {
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(anyhow!("Invalid crash report id: {}", id));
         }
+        Ok(self.pending_dir().join(format!("{}.json", id)))
+    }
+
+    /// Hold a report's upload payload until it is reviewed (or flushed)
+    pub fn queue_for_upload(&self, report: &CrashReport) -> Result<String> {
+        let pending = PendingCrashReport {
+            id: report.id.clone(),
+            created_at: report.timestamp,
+            error_type: report.error_type.clone(),
+            payload: build_event(report),
+        };
+        fs::write(self.pending_path(&pending.id)?, serde_json::to_string_pretty(&pending)?)?;
 
-        info!("Reporting error: {}", error_message);
+        info!("Crash report {} queued for review", pending.id);
+        Ok(pending.id)
+    }
 
-        // In production, use sentry::capture_message or sentry::capture_error
-        // sentry::capture_message(error_message, sentry::Level::Error);
+    /// Reports waiting to be reviewed, newest first
+    pub fn get_pending_reports(&self) -> Result<Vec<PendingCrashReport>> {
+        let mut reports = Vec::new();
+
+        if let Ok(entries) = fs::read_dir(self.pending_dir()) {
+            for entry in entries.flatten() {
+                if let Ok(content) = fs::read_to_string(entry.path()) {
+                    if let Ok(report) = serde_json::from_str::<PendingCrashReport>(&content) {
+                        reports.push(report);
+                    }
+                }
+            }
+        }
 
-        // For now, just log locally
-        error!("Error captured: {} (context: {:?})", error_message, context);
+        reports.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+        Ok(reports)
+    }
 
+    /// Drop a report from the queue (after sending, or when the user discards it)
+    pub fn remove_pending_report(&self, id: &str) -> Result<()> {
+        let path = self.pending_path(id)?;
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
         Ok(())
     }
 
+    /// Build the Sentry envelope for a reviewed report
+    pub fn prepare_upload(&self, id: &str) -> Result<CrashUpload> {
+        if !self.is_enabled() {
+            return Err(anyhow!("Crash reporting is disabled"));
+        }
+        let dsn = self.get_settings().dsn.unwrap_or_default();
+        let dsn = parse_dsn(&dsn)?;
+
+        let content = fs::read_to_string(self.pending_path(id)?)
+            .map_err(|_| anyhow!("Crash report not found: {}", id))?;
+        let pending: PendingCrashReport = serde_json::from_str(&content)?;
+
+        let header = serde_json::json!({
+            "event_id": pending.id,
+            "sent_at": chrono::Utc::now().to_rfc3339(),
+        });
+        let body = format!("{}\n{}\n{}\n", header, serde_json::json!({ "type": "event" }), pending.payload);
+
+        Ok(CrashUpload {
+            id: pending.id,
+            url: dsn.envelope_url,
+            auth_header: format!(
+                "Sentry sentry_version=7, sentry_key={}, sentry_client=garden-of-eden-v3/{}",
+                dsn.public_key,
+                env!("CARGO_PKG_VERSION")
+            ),
+            body,
+        })
+    }
+
     /// Sanitize error message (remove sensitive data)
     pub fn sanitize_error_message(message: &str) -> String {
         // Remove potential file paths, API keys, tokens, etc.
-        let sanitized = message
-            .replace(std::env::var("HOME").unwrap_or_default().as_str(), "$HOME")
-            .replace(std::env::var("USER").unwrap_or_default().as_str(), "$USER");
+        let mut sanitized = message.to_string();
+        for (var, placeholder) in [("HOME", "$HOME"), ("USER", "$USER")] {
+            // An unset variable would otherwise replace "" and splice the placeholder everywhere
+            let value = std::env::var(var).unwrap_or_default();
+            if !value.is_empty() {
+                sanitized = sanitized.replace(&value, placeholder);
+            }
+        }
 
         // Remove patterns that look like API keys or tokens
         let re = regex::Regex::new(r"[a-fA-F0-9]{32,}").unwrap();
@@ -245,6 +665,7 @@ impl CrashReporterService {
         let sanitized_context = context.map(|c| Self::sanitize_error_message(&c));
 
         CrashReport {
+            id: uuid::Uuid::new_v4().simple().to_string(),
             timestamp: chrono::Utc::now().timestamp(),
             error_message: sanitized_message,
             error_type: error_type.to_string(),
@@ -252,6 +673,7 @@ impl CrashReporterService {
             app_version: env!("CARGO_PKG_VERSION").to_string(),
             os_version: Self::get_os_version(),
             context: sanitized_context,
+            breadcrumbs: recent_breadcrumbs(),
         }
     }
 
@@ -355,9 +777,14 @@ impl Default for CrashReporterService {
 mod tests {
     use super::*;
 
+    fn test_service() -> CrashReporterService {
+        let dir = std::env::temp_dir().join(format!("crash_reporter_test_{}", uuid::Uuid::new_v4().simple()));
+        CrashReporterService::new(dir)
+    }
+
     #[test]
     fn test_create_service() {
-        let service = CrashReporterService::new();
+        let service = test_service();
         assert!(!service.is_enabled());
     }
 
@@ -371,7 +798,7 @@ mod tests {
 
     #[test]
     fn test_enable_disable() {
-        let service = CrashReporterService::new();
+        let service = test_service();
         assert!(!service.is_enabled());
 
         service.enable().unwrap();
@@ -421,12 +848,13 @@ mod tests {
 
     #[test]
     fn test_update_settings() {
-        let service = CrashReporterService::new();
+        let service = test_service();
 
         let new_settings = CrashReportingSettings {
             enabled: true,
             send_diagnostics: true,
             send_performance_data: false,
+            ..Default::default()
         };
 
         service.update_settings(new_settings.clone()).unwrap();
@@ -436,4 +864,93 @@ mod tests {
         assert_eq!(settings.send_diagnostics, new_settings.send_diagnostics);
         assert_eq!(settings.send_performance_data, new_settings.send_performance_data);
     }
+
+    #[test]
+    fn test_scrub_pii() {
+        let scrubbed = scrub_pii(
+            "Failed to read /Users/alice/Documents/diary.txt: prompt \"I feel sad today\" from alice@example.com",
+        );
+        assert!(scrubbed.contains("[path]/diary.txt"));
+        assert!(!scrubbed.contains("alice"));
+        assert!(!scrubbed.contains("sad"));
+        assert!(scrubbed.contains("[email]"));
+
+        let scrubbed = scrub_pii(r"Error at C:\Users\bob\AppData\eden\data.db with Bearer abc.def");
+        assert!(scrubbed.contains(r"[path]/data.db"));
+        assert!(!scrubbed.contains("bob"));
+        assert!(!scrubbed.contains("abc.def"));
+
+        // Apostrophes inside words are not treated as quotes
+        assert_eq!(scrub_pii("can't connect to 'my server'"), "can't connect to '[redacted]'");
+
+        // Stack traces keep function names but lose directories
+        let trace = scrub_paths(r#"{ fn: "garden_of_eden_v3::main", file: "/home/alice/eden/src/main.rs", line: 7 }"#);
+        assert!(trace.contains("garden_of_eden_v3::main"));
+        assert!(trace.contains("\"[path]/main.rs\""));
+        assert!(!trace.contains("alice"));
+    }
+
+    #[test]
+    fn test_parse_dsn() {
+        let dsn = parse_dsn("https://abc123@o42.ingest.sentry.io/4501").unwrap();
+        assert_eq!(dsn.public_key, "abc123");
+        assert_eq!(dsn.envelope_url, "https://o42.ingest.sentry.io/api/4501/envelope/");
+
+        let dsn = parse_dsn("http://key@localhost:9000/glitchtip/7/").unwrap();
+        assert_eq!(dsn.envelope_url, "http://localhost:9000/glitchtip/api/7/envelope/");
+
+        assert!(parse_dsn("https://sentry.io/1").is_err());
+        assert!(parse_dsn("https://key@sentry.io/").is_err());
+        assert!(parse_dsn("not a url").is_err());
+    }
+
+    #[test]
+    fn test_review_queue() {
+        let service = test_service();
+        assert_eq!(service.report_error("ignored", None).unwrap(), None);
+
+        service
+            .update_settings(CrashReportingSettings {
+                enabled: true,
+                dsn: Some("https://key@sentry.example.com/1".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let id = service
+            .report_error("Failed to save \"secret note\"", Some("chat"))
+            .unwrap()
+            .unwrap();
+        let pending = service.get_pending_reports().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].payload["exception"]["values"][0]["value"], "Failed to save \"[redacted]\"");
+
+        let upload = service.prepare_upload(&id).unwrap();
+        assert_eq!(upload.url, "https://sentry.example.com/api/1/envelope/");
+        assert!(upload.auth_header.contains("sentry_key=key"));
+        assert_eq!(upload.body.lines().count(), 3);
+
+        assert!(service.remove_pending_report("../settings").is_err());
+        service.remove_pending_report(&id).unwrap();
+        assert!(service.get_pending_reports().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_breadcrumb_layer_captures_events() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let subscriber = tracing_subscriber::registry().with(BreadcrumbLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(target: "breadcrumb_test", "Opened \"private.txt\"");
+            tracing::debug!(target: "breadcrumb_test", "too verbose");
+        });
+
+        let breadcrumbs: Vec<Breadcrumb> = recent_breadcrumbs()
+            .into_iter()
+            .filter(|b| b.category == "breadcrumb_test")
+            .collect();
+        assert_eq!(breadcrumbs.len(), 1);
+        assert_eq!(breadcrumbs[0].level, "info");
+        assert_eq!(breadcrumbs[0].message, "Opened \"[redacted]\"");
+    }
 }
@@ -0,0 +1,237 @@
+//! Service Degradation (v3.9.0)
+//!
+//! Non-critical services that fail to initialize no longer abort startup.
+//!
+//! Features:
+//! - `ServiceSlot` holding either the service or the reason it is unavailable
+//! - Degraded status for services running on a fallback (e.g. TF-IDF embeddings)
+//! - Status matrix of every registered service for `services_get_status`
+//! - Retrying a failed initialization by name (`services_retry_init`)
+//!
+//! Critical services (database, encryption, profiles) still fail fast.
+
+#![allow(dead_code)]  // Phase 5: Graceful degradation
+
+use crate::AppError;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+
+static REGISTRY: Mutex<BTreeMap<&'static str, Entry>> = Mutex::new(BTreeMap::new());
+
+struct Entry {
+    status: ServiceStatus,
+    retry: Option<Arc<dyn RetryInit>>,
+}
+
+/// Something that can re-run a failed initialization
+trait RetryInit: Send + Sync {
+    fn retry_init(&self) -> Result<(), String>;
+}
+
+/// Health of one service
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceState {
+    Available,
+    /// Running, but on a fallback with reduced functionality
+    Degraded,
+    /// Failed to initialize; its commands return an error
+    Unavailable,
+}
+
+/// Row of the degradation matrix
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceStatus {
+    pub name: String,
+    pub state: ServiceState,
+    pub reason: Option<String>,
+    /// Initialization attempts (startup + retries)
+    pub attempts: u32,
+    /// Whether `services_retry_init` can re-run the initialization
+    pub retryable: bool,
+    pub updated_at: i64, // Unix millis
+}
+
+fn registry() -> MutexGuard<'static, BTreeMap<&'static str, Entry>> {
+    REGISTRY.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn update(name: &'static str, state: ServiceState, reason: Option<String>, retry: Option<Arc<dyn RetryInit>>) {
+    let mut registry = registry();
+    let entry = registry.entry(name).or_insert_with(|| Entry {
+        status: ServiceStatus {
+            name: name.to_string(),
+            state,
+            reason: None,
+            attempts: 0,
+            retryable: false,
+            updated_at: 0,
+        },
+        retry: None,
+    });
+    if retry.is_some() {
+        entry.retry = retry;
+    }
+    entry.status.state = state;
+    entry.status.reason = reason;
+    entry.status.attempts += 1;
+    entry.status.retryable = entry.retry.is_some() && state == ServiceState::Unavailable;
+    entry.status.updated_at = chrono::Utc::now().timestamp_millis();
+}
+
+/// Record the outcome of initializing a service that has no slot
+///
+/// Use for services that fall back instead of failing (TF-IDF embeddings,
+/// an in-memory knowledge graph, proactive mode without screen analysis).
+pub fn report(name: &'static str, state: ServiceState, reason: Option<String>) {
+    match state {
+        ServiceState::Available => {}
+        ServiceState::Degraded => log::warn!("{} degraded: {}", name, reason.as_deref().unwrap_or("unknown")),
+        ServiceState::Unavailable => log::error!("{} unavailable: {}", name, reason.as_deref().unwrap_or("unknown")),
+    }
+    update(name, state, reason, None);
+}
+
+/// Status of every registered service, by name
+pub fn status() -> Vec<ServiceStatus> {
+    registry().values().map(|entry| entry.status.clone()).collect()
+}
+
+/// Status of one service
+pub fn service_status(name: &str) -> Option<ServiceStatus> {
+    registry().get(name).map(|entry| entry.status.clone())
+}
+
+/// Re-run the initialization of an unavailable service
+///
+/// Returns the new status; whether the retry worked is in `state`/`reason`.
+pub fn retry(name: &str) -> Result<ServiceStatus, String> {
+    let retry = {
+        let registry = registry();
+        let entry = registry.get(name).ok_or_else(|| format!("Unknown service: {}", name))?;
+        match (&entry.retry, entry.status.state) {
+            (_, ServiceState::Available) => return Ok(entry.status.clone()),
+            (Some(retry), ServiceState::Unavailable) => Arc::clone(retry),
+            _ => return Err(format!("{} cannot be re-initialized while running", name)),
+        }
+    };
+    // Run outside the registry lock, initialization records its own status
+    let _ = retry.retry_init();
+    service_status(name).ok_or_else(|| format!("Unknown service: {}", name))
+}
+
+type InitFn<T> = Box<dyn Fn() -> Result<Arc<T>> + Send + Sync>;
+
+/// Non-critical service that may be unavailable
+///
+/// Managed as `Arc<ServiceSlot<T>>`; commands call `get()?` to reach the
+/// service or return the recorded reason to the UI.
+pub struct ServiceSlot<T> {
+    name: &'static str,
+    service: RwLock<Option<Arc<T>>>,
+    init: InitFn<T>,
+}
+
+impl<T: Send + Sync + 'static> ServiceSlot<T> {
+    /// Initialize now, recording the service as unavailable on failure
+    pub fn init(name: &'static str, init: impl Fn() -> Result<Arc<T>> + Send + Sync + 'static) -> Arc<Self> {
+        let slot = Arc::new(Self {
+            name,
+            service: RwLock::new(None),
+            init: Box::new(init),
+        });
+        let retry: Arc<dyn RetryInit> = Arc::clone(&slot) as Arc<dyn RetryInit>;
+        // A failure is recorded in the registry; the slot stays unavailable
+        let _ = slot.try_init(Some(retry));
+        slot
+    }
+
+    fn try_init(&self, retry: Option<Arc<dyn RetryInit>>) -> Result<(), String> {
+        if self.is_available() {
+            return Ok(());
+        }
+        match (self.init)() {
+            Ok(service) => {
+                *self.service.write().unwrap_or_else(|e| e.into_inner()) = Some(service);
+                log::info!("✓ {} initialized", self.name);
+                update(self.name, ServiceState::Available, None, retry);
+                Ok(())
+            }
+            Err(e) => {
+                log::error!("{} unavailable: {:#}", self.name, e);
+                let reason = format!("{:#}", e);
+                update(self.name, ServiceState::Unavailable, Some(reason.clone()), retry);
+                Err(reason)
+            }
+        }
+    }
+
+    /// The service, or why it is unavailable
+    pub fn get(&self) -> Result<Arc<T>, AppError> {
+        if let Some(service) = self.service.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            return Ok(Arc::clone(service));
+        }
+        let reason = service_status(self.name)
+            .and_then(|status| status.reason)
+            .unwrap_or_else(|| "not initialized".to_string());
+        Err(AppError::unavailable(format!("{} is unavailable: {}", self.name, reason)))
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.service.read().map(|s| s.is_some()).unwrap_or(false)
+    }
+}
+
+impl<T: Send + Sync + 'static> RetryInit for ServiceSlot<T> {
+    fn retry_init(&self) -> Result<(), String> {
+        self.try_init(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn test_slot_records_failure_and_retries() {
+        let fixed = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&fixed);
+        let slot = ServiceSlot::init("Test Flaky Service", move || {
+            if flag.load(Ordering::SeqCst) {
+                Ok(Arc::new(7u32))
+            } else {
+                Err(anyhow::anyhow!("model missing"))
+            }
+        });
+
+        let err = slot.get().unwrap_err();
+        assert!(err.message.contains("model missing"));
+        assert!(err.retryable);
+        let status = service_status("Test Flaky Service").unwrap();
+        assert_eq!(status.state, ServiceState::Unavailable);
+        assert!(status.retryable);
+
+        // Still failing: stays unavailable
+        assert_eq!(retry("Test Flaky Service").unwrap().state, ServiceState::Unavailable);
+
+        fixed.store(true, Ordering::SeqCst);
+        let status = retry("Test Flaky Service").unwrap();
+        assert_eq!(status.state, ServiceState::Available);
+        assert_eq!(status.attempts, 3);
+        assert!(!status.retryable);
+        assert_eq!(*slot.get().unwrap(), 7);
+    }
+
+    #[test]
+    fn test_reported_fallback_is_not_retryable() {
+        report("Test Fallback Service", ServiceState::Degraded, Some("in-memory".to_string()));
+        let status = service_status("Test Fallback Service").unwrap();
+        assert_eq!(status.state, ServiceState::Degraded);
+        assert!(!status.retryable);
+        assert!(retry("Test Fallback Service").is_err());
+        assert!(retry("No Such Service").is_err());
+    }
+}
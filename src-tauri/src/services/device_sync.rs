@@ -0,0 +1,1073 @@
+//! Device Sync Service (v3.9.0)
+//!
+//! Optional replication of conversations, memories, wiki facts and persona
+//! settings across the user's own devices.
+//!
+//! Features:
+//! - State-based CRDT: every synced column is a last-writer-wins register
+//!   stamped with a hybrid logical clock, so concurrent edits merge per field
+//!   and every device converges on the same result
+//! - Deletions are tombstones; an edit made after a delete revives the record
+//! - End-to-end encrypted: snapshots are sealed with AES-256-GCM under a sync
+//!   key that only the user's devices hold (shared as a pairing code, kept in
+//!   the OS keychain)
+//! - Two transports: a folder the user syncs themselves (Syncthing, iCloud
+//!   Drive, Dropbox, a USB stick) or a relay that only ever sees ciphertext
+//! - Embeddings are not synced; imported memories and facts are re-embedded
+//!   locally through the import hook
+
+#![allow(dead_code)]  // Phase 5: Device sync (transport settings used by future settings UI)
+
+use crate::database::Database;
+use crate::services::encryption;
+use crate::services::secrets::SecretsService;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use rusqlite::types::{Value, ValueRef};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const SNAPSHOT_VERSION: u32 = 1;
+const SNAPSHOT_EXTENSION: &str = "sync";
+const PAIRING_PREFIX: &str = "eden-sync:";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+/// How often the scheduler checks whether a sync is due
+const SCHEDULER_CHECK_SECS: u64 = 60;
+
+const META_DEVICE_ID: &str = "device_id";
+const META_CLOCK: &str = "clock";
+const META_CONFIG: &str = "config";
+const META_LAST_SYNC: &str = "last_sync_at";
+
+pub type SyncKey = [u8; KEY_LEN];
+
+/// A synced table
+pub struct EntitySpec {
+    pub name: &'static str,
+    table: &'static str,
+    key: &'static str,
+    /// Synced columns (missing ones are skipped, e.g. on an older schema)
+    columns: &'static [&'static str],
+    /// Stamps rows that existed before sync was enabled
+    modified: Option<&'static str>,
+}
+
+/// Synced tables, parents first (upserts run in this order, deletes in reverse)
+///
+/// Device-local bookkeeping (embeddings, retention scores, access counts) is left out.
+pub const ENTITIES: &[EntitySpec] = &[
+    EntitySpec {
+        name: "conversations",
+        table: "conversations",
+        key: "id",
+        columns: &["title", "mode", "created_at", "updated_at", "message_count", "pinned", "favorite", "archived_at"],
+        modified: Some("updated_at"),
+    },
+    EntitySpec {
+        name: "messages",
+        table: "messages",
+        key: "id",
+        columns: &[
+            "conversation_id", "role", "content", "timestamp", "tokens", "response_time",
+            "context_level", "satisfaction", "is_stale", "edited_at",
+        ],
+        modified: Some("timestamp"),
+    },
+    EntitySpec {
+        name: "memories",
+        table: "episodic_memory",
+        key: "id",
+        columns: &[
+            "user_message", "ai_response", "satisfaction", "created_at", "importance",
+            "conversation_id", "message_id", "document", "language", "user_asserted",
+            "user_note", "is_pinned", "memory_type",
+        ],
+        modified: Some("created_at"),
+    },
+    EntitySpec {
+        name: "wiki_facts",
+        table: "wiki_facts",
+        key: "id",
+        columns: &[
+            "statement", "entity", "category", "confidence", "source_conversation_id",
+            "source_message_id", "learned_at", "reinforcement_count", "related_facts",
+            "superseded_by", "source", "last_observed_at", "note",
+        ],
+        modified: Some("last_observed_at"),
+    },
+    EntitySpec {
+        name: "persona",
+        table: "persona_settings",
+        key: "id",
+        columns: &[
+            "formality", "verbosity", "humor", "emoji_usage", "empathy", "creativity",
+            "proactiveness", "technical_depth", "code_examples", "questioning",
+            "created_at", "updated_at",
+        ],
+        modified: Some("updated_at"),
+    },
+];
+
+/// Hybrid logical clock timestamp; the device id breaks ties
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Stamp {
+    pub millis: i64,
+    pub counter: u32,
+    pub device: String,
+}
+
+/// Hybrid logical clock: wall time, but never behind a stamp already seen
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Clock {
+    millis: i64,
+    counter: u32,
+}
+
+impl Clock {
+    pub fn tick(&mut self, now_ms: i64, device: &str) -> Stamp {
+        if now_ms > self.millis {
+            self.millis = now_ms;
+            self.counter = 0;
+        } else {
+            self.counter += 1;
+        }
+        Stamp {
+            millis: self.millis,
+            counter: self.counter,
+            device: device.to_string(),
+        }
+    }
+
+    pub fn observe(&mut self, stamp: &Stamp) {
+        if (stamp.millis, stamp.counter) > (self.millis, self.counter) {
+            self.millis = stamp.millis;
+            self.counter = stamp.counter;
+        }
+    }
+}
+
+/// Last-writer-wins register holding one column value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Register {
+    pub value: serde_json::Value,
+    pub stamp: Stamp,
+}
+
+/// Synced state of one row
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecordState {
+    pub fields: BTreeMap<String, Register>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted: Option<Stamp>,
+}
+
+impl RecordState {
+    /// Deleted unless some field was written after the tombstone
+    pub fn is_deleted(&self) -> bool {
+        match &self.deleted {
+            Some(deleted) => self.fields.values().all(|r| &r.stamp < deleted),
+            None => false,
+        }
+    }
+
+    fn latest_stamp(&self) -> Option<&Stamp> {
+        self.fields.values().map(|r| &r.stamp).chain(self.deleted.as_ref()).max()
+    }
+
+    /// Merge another replica's copy field by field; true when anything changed
+    pub fn merge(&mut self, other: &RecordState) -> bool {
+        let mut changed = false;
+        for (column, theirs) in &other.fields {
+            let newer = self.fields.get(column).is_none_or(|ours| theirs.stamp > ours.stamp);
+            if newer {
+                self.fields.insert(column.clone(), theirs.clone());
+                changed = true;
+            }
+        }
+        if other.deleted > self.deleted {
+            self.deleted = other.deleted.clone();
+            changed = true;
+        }
+        changed
+    }
+}
+
+/// Synced state of a replica: entity -> record id -> record
+pub type ReplicaState = BTreeMap<String, BTreeMap<String, RecordState>>;
+
+/// Where snapshots are exchanged
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SyncTransport {
+    /// A folder synced by the user's own tool; each device writes `<device_id>.sync`
+    Folder { path: PathBuf },
+    /// Relay server storing one sealed snapshot per device
+    Relay { url: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncConfig {
+    pub transport: SyncTransport,
+    /// Automatic sync interval, 0 = manual only
+    #[serde(default)]
+    pub interval_minutes: u64,
+}
+
+/// A sealed snapshot as stored in the folder or on the relay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncEnvelope {
+    pub version: u32,
+    pub device_id: String,
+    pub sealed_at: i64, // Unix millis
+    /// base64(nonce || AES-256-GCM ciphertext), the device id is authenticated data
+    pub payload: String,
+}
+
+/// Outcome of one sync round
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncReport {
+    pub device_id: String,
+    /// Devices whose snapshots were merged
+    pub peers: Vec<String>,
+    /// Snapshots that couldn't be opened (other key, corrupted)
+    pub rejected: usize,
+    /// Local edits stamped this round
+    pub local_changes: usize,
+    pub applied: usize,
+    pub deleted: usize,
+    /// Remote records that couldn't be written yet (retried next round)
+    pub skipped: usize,
+    /// Memories and facts to re-embed (imported, edited or deleted)
+    pub memory_ids: Vec<String>,
+    pub fact_ids: Vec<String>,
+    pub persona_changed: bool,
+    pub synced_at: i64,
+}
+
+impl SyncReport {
+    /// True when the merge touched local tables
+    pub fn has_imports(&self) -> bool {
+        self.applied > 0 || self.deleted > 0
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncStatus {
+    pub enabled: bool,
+    pub device_id: String,
+    pub config: Option<SyncConfig>,
+    pub last_sync_at: Option<i64>,
+    pub tracked_records: usize,
+}
+
+pub fn init_tables(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS sync_meta (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS sync_records (
+            entity TEXT NOT NULL,
+            record_id TEXT NOT NULL,
+            state TEXT NOT NULL,
+            PRIMARY KEY (entity, record_id)
+        );",
+    )?;
+    Ok(())
+}
+
+fn meta_get(conn: &Connection, key: &str) -> Result<Option<String>> {
+    Ok(conn
+        .query_row("SELECT value FROM sync_meta WHERE key = ?1", [key], |row| row.get(0))
+        .optional()?)
+}
+
+fn meta_set(conn: &Connection, key: &str, value: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO sync_meta (key, value) VALUES (?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        params![key, value],
+    )?;
+    Ok(())
+}
+
+/// This replica's id, created on first use
+pub fn device_id(conn: &Connection) -> Result<String> {
+    if let Some(id) = meta_get(conn, META_DEVICE_ID)? {
+        return Ok(id);
+    }
+    let id = uuid::Uuid::new_v4().to_string();
+    meta_set(conn, META_DEVICE_ID, &id)?;
+    Ok(id)
+}
+
+fn load_clock(conn: &Connection) -> Result<Clock> {
+    Ok(match meta_get(conn, META_CLOCK)? {
+        Some(json) => serde_json::from_str(&json)?,
+        None => Clock::default(),
+    })
+}
+
+fn save_clock(conn: &Connection, clock: &Clock) -> Result<()> {
+    meta_set(conn, META_CLOCK, &serde_json::to_string(clock)?)
+}
+
+pub fn load_config(conn: &Connection) -> Result<Option<SyncConfig>> {
+    match meta_get(conn, META_CONFIG)? {
+        Some(json) => Ok(Some(serde_json::from_str(&json).context("Sync settings are corrupted")?)),
+        None => Ok(None),
+    }
+}
+
+/// Columns of a spec present in the local schema, `None` if the table doesn't exist
+fn present_columns(conn: &Connection, spec: &EntitySpec) -> Result<Option<Vec<&'static str>>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", spec.table))?;
+    let existing: HashSet<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<_>>()?;
+    if existing.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(spec.columns.iter().copied().filter(|c| existing.contains(*c)).collect()))
+}
+
+fn to_json(value: ValueRef) -> serde_json::Value {
+    match value {
+        ValueRef::Null => serde_json::Value::Null,
+        ValueRef::Integer(i) => serde_json::json!(i),
+        ValueRef::Real(f) => serde_json::json!(f),
+        ValueRef::Text(text) => serde_json::Value::String(String::from_utf8_lossy(text).into_owned()),
+        // No synced column holds blobs
+        ValueRef::Blob(bytes) => serde_json::Value::String(general_purpose::STANDARD.encode(bytes)),
+    }
+}
+
+fn to_sql(value: &serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(b) => Value::Integer(*b as i64),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::Integer(i),
+            None => Value::Real(n.as_f64().unwrap_or(0.0)),
+        },
+        serde_json::Value::String(s) => Value::Text(s.clone()),
+        other => Value::Text(other.to_string()),
+    }
+}
+
+/// Some tables store seconds, others millis
+fn as_millis(timestamp: i64) -> i64 {
+    if timestamp < 100_000_000_000 {
+        timestamp * 1000
+    } else {
+        timestamp
+    }
+}
+
+fn load_entity(conn: &Connection, entity: &str) -> Result<BTreeMap<String, RecordState>> {
+    let mut stmt = conn.prepare("SELECT record_id, state FROM sync_records WHERE entity = ?1")?;
+    let rows = stmt
+        .query_map([entity], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut records = BTreeMap::new();
+    for (id, json) in rows {
+        records.insert(id, serde_json::from_str(&json)?);
+    }
+    Ok(records)
+}
+
+/// Tracking state of every synced entity
+pub fn load_state(conn: &Connection) -> Result<ReplicaState> {
+    let mut state = ReplicaState::new();
+    for spec in ENTITIES {
+        let records = load_entity(conn, spec.name)?;
+        if !records.is_empty() {
+            state.insert(spec.name.to_string(), records);
+        }
+    }
+    Ok(state)
+}
+
+fn save_record(conn: &Connection, entity: &str, id: &str, record: &RecordState) -> Result<()> {
+    conn.execute(
+        "INSERT INTO sync_records (entity, record_id, state) VALUES (?1, ?2, ?3)
+         ON CONFLICT(entity, record_id) DO UPDATE SET state = excluded.state",
+        params![entity, id, serde_json::to_string(record)?],
+    )?;
+    Ok(())
+}
+
+fn read_rows(conn: &Connection, spec: &EntitySpec, columns: &[&str]) -> Result<Vec<(String, Vec<serde_json::Value>)>> {
+    let mut select = format!("CAST({} AS TEXT)", spec.key);
+    for column in columns {
+        select.push_str(", ");
+        select.push_str(column);
+    }
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM {}", select, spec.table))?;
+    let rows = stmt
+        .query_map([], |row| {
+            let id: String = row.get(0)?;
+            let mut values = Vec::with_capacity(columns.len());
+            for i in 0..columns.len() {
+                values.push(to_json(row.get_ref(i + 1)?));
+            }
+            Ok((id, values))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(rows)
+}
+
+/// Stamp local edits since the last round: changed columns, new rows and deleted rows
+pub fn record_local_changes(conn: &Connection, device: &str, clock: &mut Clock, now_ms: i64) -> Result<usize> {
+    let mut changed = 0;
+
+    for spec in ENTITIES {
+        let Some(columns) = present_columns(conn, spec)? else {
+            continue;
+        };
+        let mut records = load_entity(conn, spec.name)?;
+        let modified_index = spec.modified.and_then(|m| columns.iter().position(|c| *c == m));
+        let mut seen = HashSet::new();
+
+        for (id, values) in read_rows(conn, spec, &columns)? {
+            let record = records.entry(id.clone()).or_default();
+            let first_seen = record.fields.is_empty();
+            let revived = record.is_deleted();
+            let dirty: Vec<usize> = (0..columns.len())
+                .filter(|&i| revived || record.fields.get(columns[i]).is_none_or(|r| r.value != values[i]))
+                .collect();
+
+            if !dirty.is_empty() {
+                let stamp = if first_seen {
+                    let modified = modified_index.and_then(|i| values[i].as_i64()).map(as_millis);
+                    Stamp {
+                        millis: modified.unwrap_or(0),
+                        counter: 0,
+                        device: device.to_string(),
+                    }
+                } else {
+                    clock.tick(now_ms, device)
+                };
+                for i in dirty {
+                    record.fields.insert(
+                        columns[i].to_string(),
+                        Register {
+                            value: values[i].clone(),
+                            stamp: stamp.clone(),
+                        },
+                    );
+                }
+                save_record(conn, spec.name, &id, record)?;
+                changed += 1;
+            }
+            seen.insert(id);
+        }
+
+        for (id, record) in records.iter_mut() {
+            if seen.contains(id) || record.is_deleted() || record.fields.is_empty() {
+                continue;
+            }
+            record.deleted = Some(clock.tick(now_ms, device));
+            save_record(conn, spec.name, id, record)?;
+            changed += 1;
+        }
+    }
+
+    Ok(changed)
+}
+
+fn upsert(conn: &Connection, spec: &EntitySpec, columns: &[&str], id: &str, record: &RecordState) -> Result<()> {
+    let mut names = vec![spec.key];
+    let mut values = vec![Value::Text(id.to_string())];
+    for column in columns {
+        if let Some(register) = record.fields.get(*column) {
+            names.push(*column);
+            values.push(to_sql(&register.value));
+        }
+    }
+
+    let placeholders: Vec<String> = (1..=names.len()).map(|i| format!("?{}", i)).collect();
+    let updates: Vec<String> = names[1..].iter().map(|c| format!("{} = excluded.{}", c, c)).collect();
+    let sql = if updates.is_empty() {
+        format!("INSERT OR IGNORE INTO {} ({}) VALUES ({})", spec.table, names.join(", "), placeholders.join(", "))
+    } else {
+        format!(
+            "INSERT INTO {} ({}) VALUES ({}) ON CONFLICT({}) DO UPDATE SET {}",
+            spec.table,
+            names.join(", "),
+            placeholders.join(", "),
+            spec.key,
+            updates.join(", ")
+        )
+    };
+    conn.execute(&sql, rusqlite::params_from_iter(values))?;
+    Ok(())
+}
+
+fn note_change(report: &mut SyncReport, spec: &EntitySpec, id: &str) {
+    match spec.name {
+        "memories" => report.memory_ids.push(id.to_string()),
+        "wiki_facts" => report.fact_ids.push(id.to_string()),
+        "persona" => report.persona_changed = true,
+        _ => {}
+    }
+}
+
+/// Merge one peer's state into the local tables and tracking state
+fn merge_replica(conn: &Connection, remote: &ReplicaState, clock: &mut Clock, report: &mut SyncReport) -> Result<()> {
+    let mut deletions = Vec::new();
+
+    for spec in ENTITIES {
+        let Some(remote_records) = remote.get(spec.name) else {
+            continue;
+        };
+        // Kept in the peer's snapshot until this device has the table
+        let Some(columns) = present_columns(conn, spec)? else {
+            continue;
+        };
+        let mut local = load_entity(conn, spec.name)?;
+
+        for (id, theirs) in remote_records {
+            if let Some(stamp) = theirs.latest_stamp() {
+                clock.observe(stamp);
+            }
+            let mut merged = local.remove(id).unwrap_or_default();
+            if !merged.merge(theirs) {
+                continue;
+            }
+            if merged.is_deleted() {
+                deletions.push((spec, id.clone(), merged));
+                continue;
+            }
+            // A failed write leaves the tracking state alone, so the record is retried
+            match upsert(conn, spec, &columns, id, &merged) {
+                Ok(()) => {
+                    save_record(conn, spec.name, id, &merged)?;
+                    report.applied += 1;
+                    note_change(report, spec, id);
+                }
+                Err(e) => {
+                    log::warn!("Skipped synced {} {}: {}", spec.name, id, e);
+                    report.skipped += 1;
+                }
+            }
+        }
+    }
+
+    // Children first
+    for (spec, id, merged) in deletions.into_iter().rev() {
+        let removed = conn.execute(&format!("DELETE FROM {} WHERE {} = ?1", spec.table, spec.key), [&id])?;
+        save_record(conn, spec.name, &id, &merged)?;
+        if removed > 0 {
+            report.deleted += 1;
+            note_change(report, spec, &id);
+        }
+    }
+
+    Ok(())
+}
+
+/// Stamp pending local edits and seal this replica's state for the peers
+pub fn local_snapshot(conn: &Connection, key: &SyncKey, now_ms: i64) -> Result<(SyncEnvelope, usize)> {
+    let device = device_id(conn)?;
+    let mut clock = load_clock(conn)?;
+    let changes = record_local_changes(conn, &device, &mut clock, now_ms)?;
+    save_clock(conn, &clock)?;
+    Ok((seal_snapshot(key, &device, &load_state(conn)?, now_ms)?, changes))
+}
+
+/// Merge the peers' snapshots into the local database in one transaction
+pub fn merge_snapshots(conn: &Connection, key: &SyncKey, envelopes: &[SyncEnvelope], now_ms: i64) -> Result<SyncReport> {
+    let device = device_id(conn)?;
+    let tx = conn.unchecked_transaction()?;
+    let mut clock = load_clock(&tx)?;
+    let mut report = SyncReport {
+        device_id: device.clone(),
+        synced_at: now_ms,
+        ..Default::default()
+    };
+
+    // Edits made while the snapshots were exchanged must not be overwritten unstamped
+    report.local_changes = record_local_changes(&tx, &device, &mut clock, now_ms)?;
+
+    for envelope in envelopes.iter().filter(|e| e.device_id != device) {
+        match open_snapshot(key, envelope) {
+            Ok(remote) => {
+                merge_replica(&tx, &remote, &mut clock, &mut report)?;
+                report.peers.push(envelope.device_id.clone());
+            }
+            Err(e) => {
+                log::warn!("Ignored sync snapshot from {}: {}", envelope.device_id, e);
+                report.rejected += 1;
+            }
+        }
+    }
+
+    save_clock(&tx, &clock)?;
+    meta_set(&tx, META_LAST_SYNC, &now_ms.to_string())?;
+    tx.commit()?;
+    Ok(report)
+}
+
+pub fn generate_key() -> SyncKey {
+    let mut key = [0u8; KEY_LEN];
+    OsRng.fill_bytes(&mut key);
+    key
+}
+
+/// Code entered on another device to join ("eden-sync:<base64url key>")
+pub fn pairing_code(key: &SyncKey) -> String {
+    format!("{}{}", PAIRING_PREFIX, general_purpose::URL_SAFE_NO_PAD.encode(key))
+}
+
+pub fn parse_pairing_code(code: &str) -> Result<SyncKey> {
+    let encoded = code.trim().strip_prefix(PAIRING_PREFIX).ok_or_else(|| anyhow!("Not a sync pairing code"))?;
+    let bytes = general_purpose::URL_SAFE_NO_PAD
+        .decode(encoded)
+        .map_err(|_| anyhow!("Pairing code is malformed"))?;
+    bytes.try_into().map_err(|_| anyhow!("Pairing code is malformed"))
+}
+
+/// Relay namespace shared by devices with the same key (reveals nothing about the key)
+pub fn group_id(key: &SyncKey) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(b"garden-of-eden-sync-group");
+    hasher.update(key);
+    format!("{:x}", hasher.finalize())[..32].to_string()
+}
+
+pub fn seal_snapshot(key: &SyncKey, device_id: &str, state: &ReplicaState, now_ms: i64) -> Result<SyncEnvelope> {
+    let plaintext = serde_json::to_vec(state)?;
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, Payload { msg: &plaintext, aad: device_id.as_bytes() })
+        .map_err(|e| anyhow!("Encryption failed: {}", e))?;
+
+    let mut payload = nonce.to_vec();
+    payload.extend_from_slice(&ciphertext);
+    Ok(SyncEnvelope {
+        version: SNAPSHOT_VERSION,
+        device_id: device_id.to_string(),
+        sealed_at: now_ms,
+        payload: general_purpose::STANDARD.encode(payload),
+    })
+}
+
+pub fn open_snapshot(key: &SyncKey, envelope: &SyncEnvelope) -> Result<ReplicaState> {
+    if envelope.version != SNAPSHOT_VERSION {
+        return Err(anyhow!("Unsupported snapshot version {}", envelope.version));
+    }
+    let payload = general_purpose::STANDARD.decode(&envelope.payload)?;
+    if payload.len() < NONCE_LEN {
+        return Err(anyhow!("Snapshot is truncated"));
+    }
+
+    let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: envelope.device_id.as_bytes() })
+        .map_err(|_| anyhow!("Decryption failed (different sync key?)"))?;
+    Ok(serde_json::from_slice(&plaintext)?)
+}
+
+/// Write this device's snapshot, return every other device's
+fn exchange_folder(dir: &Path, envelope: &SyncEnvelope) -> Result<Vec<SyncEnvelope>> {
+    fs::create_dir_all(dir).with_context(|| format!("Sync folder {} is not writable", dir.display()))?;
+
+    // Written under a temporary name so peers never read a half-written snapshot
+    let target = dir.join(format!("{}.{}", envelope.device_id, SNAPSHOT_EXTENSION));
+    let partial = dir.join(format!(".{}.partial", envelope.device_id));
+    fs::write(&partial, serde_json::to_vec(envelope)?)?;
+    fs::rename(&partial, &target)?;
+
+    let mut peers = Vec::new();
+    for entry in fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        if path == target || path.extension().is_none_or(|ext| ext != SNAPSHOT_EXTENSION) {
+            continue;
+        }
+        match fs::read(&path).map_err(anyhow::Error::from).and_then(|bytes| Ok(serde_json::from_slice(&bytes)?)) {
+            Ok(peer) => peers.push(peer),
+            Err(e) => log::warn!("Unreadable sync snapshot {}: {}", path.display(), e),
+        }
+    }
+    Ok(peers)
+}
+
+/// PUT this device's snapshot to the relay, GET the group's snapshots
+fn exchange_relay(url: &str, key: &SyncKey, envelope: &SyncEnvelope) -> Result<Vec<SyncEnvelope>> {
+    let group_url = format!("{}/v1/groups/{}/devices", url.trim_end_matches('/'), group_id(key));
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(60))
+        .build()?;
+
+    client
+        .put(format!("{}/{}", group_url, envelope.device_id))
+        .json(envelope)
+        .send()?
+        .error_for_status()
+        .map_err(|e| anyhow!("Relay rejected the snapshot: {}", e))?;
+
+    let peers: Vec<SyncEnvelope> = client
+        .get(&group_url)
+        .send()?
+        .error_for_status()
+        .map_err(|e| anyhow!("Relay request failed: {}", e))?
+        .json()?;
+    Ok(peers.into_iter().filter(|p| p.device_id != envelope.device_id).collect())
+}
+
+pub fn exchange(transport: &SyncTransport, key: &SyncKey, envelope: &SyncEnvelope) -> Result<Vec<SyncEnvelope>> {
+    match transport {
+        SyncTransport::Folder { path } => exchange_folder(path, envelope),
+        SyncTransport::Relay { url } => exchange_relay(url, key, envelope),
+    }
+}
+
+fn validate_config(config: &SyncConfig) -> Result<()> {
+    match &config.transport {
+        SyncTransport::Folder { path } if !path.is_absolute() => {
+            Err(anyhow!("Sync folder must be an absolute path"))
+        }
+        SyncTransport::Relay { url } if !url.starts_with("https://") && !url.starts_with("http://") => {
+            Err(anyhow!("Relay URL must start with http:// or https://"))
+        }
+        _ => Ok(()),
+    }
+}
+
+type ImportHook = Box<dyn Fn(&SyncReport) + Send + Sync>;
+
+/// Device sync service
+pub struct DeviceSyncService {
+    db: Arc<Mutex<Database>>,
+    secrets: Arc<SecretsService>,
+    /// Re-embeds imported memories and facts
+    import_hook: Mutex<Option<ImportHook>>,
+    /// Serializes sync rounds
+    busy: Mutex<()>,
+    running: AtomicBool,
+}
+
+impl DeviceSyncService {
+    pub fn new(db: Arc<Mutex<Database>>, secrets: Arc<SecretsService>) -> Result<Self> {
+        {
+            let db_guard = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            init_tables(db_guard.conn())?;
+        }
+
+        log::info!("✓ Device Sync Service initialized");
+        Ok(Self {
+            db,
+            secrets,
+            import_hook: Mutex::new(None),
+            busy: Mutex::new(()),
+            running: AtomicBool::new(false),
+        })
+    }
+
+    /// Called after every round that imported remote changes
+    pub fn set_import_hook(&self, hook: impl Fn(&SyncReport) + Send + Sync + 'static) {
+        if let Ok(mut slot) = self.import_hook.lock() {
+            *slot = Some(Box::new(hook));
+        }
+    }
+
+    fn with_conn<T>(&self, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+        if encryption::is_locked() {
+            return Err(anyhow!("Storage is locked"));
+        }
+        let db_guard = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        // The database is swapped on profile switch and restore
+        init_tables(db_guard.conn())?;
+        f(db_guard.conn())
+    }
+
+    fn secret_name(device_id: &str) -> String {
+        format!("device_sync.{}.key", device_id)
+    }
+
+    fn load_key(&self, device_id: &str) -> Result<Option<SyncKey>> {
+        match self.secrets.get(&Self::secret_name(device_id))? {
+            Some(code) => Ok(Some(parse_pairing_code(&code)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn activate(&self, key: &SyncKey, config: &SyncConfig) -> Result<SyncStatus> {
+        validate_config(config)?;
+        let device = self.with_conn(device_id)?;
+        self.secrets.set(&Self::secret_name(&device), &pairing_code(key))?;
+        self.with_conn(|conn| meta_set(conn, META_CONFIG, &serde_json::to_string(config)?))?;
+        self.status()
+    }
+
+    pub fn status(&self) -> Result<SyncStatus> {
+        let (device, config, last_sync_at, tracked_records) = self.with_conn(|conn| {
+            let last_sync_at = meta_get(conn, META_LAST_SYNC)?.and_then(|v| v.parse().ok());
+            let tracked: i64 = conn.query_row("SELECT COUNT(*) FROM sync_records", [], |row| row.get(0))?;
+            Ok((device_id(conn)?, load_config(conn)?, last_sync_at, tracked as usize))
+        })?;
+        let enabled = config.is_some() && self.load_key(&device)?.is_some();
+        Ok(SyncStatus {
+            enabled,
+            device_id: device,
+            config,
+            last_sync_at,
+            tracked_records,
+        })
+    }
+
+    /// Start a new sync group on this device; returns the pairing code for the others
+    pub fn enable(&self, config: SyncConfig) -> Result<String> {
+        let key = generate_key();
+        self.activate(&key, &config)?;
+        log::info!("Device sync enabled");
+        Ok(pairing_code(&key))
+    }
+
+    /// Join an existing sync group with a pairing code from another device
+    pub fn join(&self, code: &str, config: SyncConfig) -> Result<SyncStatus> {
+        let key = parse_pairing_code(code)?;
+        let status = self.activate(&key, &config)?;
+        log::info!("Joined device sync group");
+        Ok(status)
+    }
+
+    /// Change the transport or interval, keeping the key
+    pub fn update_config(&self, config: SyncConfig) -> Result<SyncStatus> {
+        validate_config(&config)?;
+        self.with_conn(|conn| meta_set(conn, META_CONFIG, &serde_json::to_string(&config)?))?;
+        self.status()
+    }
+
+    /// Pairing code for adding another device
+    pub fn pairing_code(&self) -> Result<String> {
+        let device = self.with_conn(device_id)?;
+        let key = self.load_key(&device)?.ok_or_else(|| anyhow!("Device sync is not enabled"))?;
+        Ok(pairing_code(&key))
+    }
+
+    /// Stop syncing and forget the key (local data and tracking state are kept)
+    pub fn disable(&self) -> Result<()> {
+        let device = self.with_conn(|conn| {
+            conn.execute("DELETE FROM sync_meta WHERE key = ?1", [META_CONFIG])?;
+            device_id(conn)
+        })?;
+        self.secrets.delete(&Self::secret_name(&device))?;
+        log::info!("Device sync disabled");
+        Ok(())
+    }
+
+    /// Run one sync round now
+    pub fn sync_now(&self) -> Result<SyncReport> {
+        let _busy = self.busy.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+
+        let (device, config) = self.with_conn(|conn| Ok((device_id(conn)?, load_config(conn)?)))?;
+        let config = config.ok_or_else(|| anyhow!("Device sync is not enabled"))?;
+        let key = self.load_key(&device)?.ok_or_else(|| anyhow!("Device sync key is missing, pair this device again"))?;
+
+        // The database lock isn't held while the relay or folder is slow
+        let (envelope, local_changes) =
+            self.with_conn(|conn| local_snapshot(conn, &key, chrono::Utc::now().timestamp_millis()))?;
+        let peers = exchange(&config.transport, &key, &envelope)?;
+        let mut report =
+            self.with_conn(|conn| merge_snapshots(conn, &key, &peers, chrono::Utc::now().timestamp_millis()))?;
+        report.local_changes += local_changes;
+
+        log::info!(
+            "✓ Device sync: {} peers, {} applied, {} deleted, {} skipped",
+            report.peers.len(),
+            report.applied,
+            report.deleted,
+            report.skipped
+        );
+
+        if report.has_imports() {
+            if let Ok(hook) = self.import_hook.lock() {
+                if let Some(hook) = hook.as_ref() {
+                    hook(&report);
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    fn is_due(&self) -> Result<bool> {
+        let status = self.status()?;
+        let interval = match &status.config {
+            Some(config) if status.enabled && config.interval_minutes > 0 => config.interval_minutes,
+            _ => return Ok(false),
+        };
+        let interval_ms = (interval * 60 * 1000) as i64;
+        Ok(status
+            .last_sync_at
+            .is_none_or(|at| chrono::Utc::now().timestamp_millis() - at >= interval_ms))
+    }
+
+    /// Start the automatic sync loop
+    pub fn start(self: &Arc<Self>) {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return; // Already running
+        }
+
+        let service = Arc::clone(self);
+        tauri::async_runtime::spawn(async move {
+            log::info!("Device sync scheduler started");
+
+            while service.running.load(Ordering::SeqCst) {
+                if !encryption::is_locked() && service.is_due().unwrap_or(false) {
+                    let worker = Arc::clone(&service);
+                    match tokio::task::spawn_blocking(move || worker.sync_now()).await {
+                        Ok(Ok(_)) => {}
+                        Ok(Err(e)) => log::warn!("Scheduled device sync failed: {}", e),
+                        Err(e) => log::warn!("Scheduled device sync task failed: {}", e),
+                    }
+                }
+                tokio::time::sleep(Duration::from_secs(SCHEDULER_CHECK_SECS)).await;
+            }
+
+            log::info!("Device sync scheduler stopped");
+        });
+    }
+
+    /// Stop the automatic sync loop (takes effect after the current check)
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn register(value: serde_json::Value, millis: i64, device: &str) -> Register {
+        Register {
+            value,
+            stamp: Stamp {
+                millis,
+                counter: 0,
+                device: device.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_concurrent_edits_merge_per_field() {
+        let mut a = RecordState::default();
+        a.fields.insert("title".to_string(), register(serde_json::json!("Trip"), 10, "a"));
+        a.fields.insert("pinned".to_string(), register(serde_json::json!(0), 10, "a"));
+        let mut b = a.clone();
+
+        // Concurrent: A renames, B pins
+        a.fields.insert("title".to_string(), register(serde_json::json!("Trip to Jeju"), 20, "a"));
+        b.fields.insert("pinned".to_string(), register(serde_json::json!(1), 25, "b"));
+
+        let mut ab = a.clone();
+        let mut ba = b.clone();
+        assert!(ab.merge(&b));
+        assert!(ba.merge(&a));
+        assert_eq!(ab, ba);
+        assert_eq!(ab.fields["title"].value, serde_json::json!("Trip to Jeju"));
+        assert_eq!(ab.fields["pinned"].value, serde_json::json!(1));
+        assert!(!ab.merge(&b), "merging twice changes nothing");
+
+        // A delete wins over older edits, a later edit revives the record
+        ab.deleted = Some(Stamp { millis: 30, counter: 0, device: "a".to_string() });
+        assert!(ab.is_deleted());
+        ab.fields.insert("title".to_string(), register(serde_json::json!("Trip"), 31, "b"));
+        assert!(!ab.is_deleted());
+
+        let key = generate_key();
+        assert_eq!(parse_pairing_code(&pairing_code(&key)).unwrap(), key);
+        assert!(parse_pairing_code("eden-sync:abc").is_err());
+    }
+
+    fn insert_conversation(conn: &Connection, id: &str, title: &str) {
+        conn.execute(
+            "INSERT INTO conversations (id, title, mode, created_at, updated_at, message_count)
+             VALUES (?1, ?2, 'user-led', 1000, 1000, 0)",
+            params![id, title],
+        )
+        .unwrap();
+    }
+
+    fn title(conn: &Connection, id: &str) -> Option<String> {
+        conn.query_row("SELECT title FROM conversations WHERE id = ?1", [id], |row| row.get(0))
+            .optional()
+            .unwrap()
+    }
+
+    /// One round for `db`: publish its snapshot, merge everyone else's
+    fn round(db: &Database, key: &SyncKey, transport: &SyncTransport, now: i64) -> SyncReport {
+        let (envelope, _) = local_snapshot(db.conn(), key, now).unwrap();
+        let peers = exchange(transport, key, &envelope).unwrap();
+        merge_snapshots(db.conn(), key, &peers, now).unwrap()
+    }
+
+    #[test]
+    fn test_folder_sync_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let transport = SyncTransport::Folder { path: dir.path().to_path_buf() };
+        let key = generate_key();
+
+        let a = Database::new_test_db().unwrap();
+        let b = Database::new_test_db().unwrap();
+        init_tables(a.conn()).unwrap();
+        init_tables(b.conn()).unwrap();
+
+        insert_conversation(a.conn(), "c1", "From A");
+        a.conn()
+            .execute(
+                "INSERT INTO messages (id, conversation_id, role, content, timestamp) VALUES ('m1', 'c1', 'user', 'hello', 1000)",
+                [],
+            )
+            .unwrap();
+        insert_conversation(b.conn(), "c2", "From B");
+
+        round(&a, &key, &transport, 2_000_000_000_000);
+        let report = round(&b, &key, &transport, 2_000_000_000_100);
+        assert_eq!(title(b.conn(), "c1").as_deref(), Some("From A"));
+        assert!(report.applied >= 2);
+        round(&a, &key, &transport, 2_000_000_000_200);
+        assert_eq!(title(a.conn(), "c2").as_deref(), Some("From B"));
+
+        // Concurrent rename on both devices: the later stamp wins everywhere
+        a.conn().execute("UPDATE conversations SET title = 'A rename' WHERE id = 'c1'", []).unwrap();
+        b.conn().execute("UPDATE conversations SET title = 'B rename' WHERE id = 'c1'", []).unwrap();
+        round(&a, &key, &transport, 2_000_000_001_000);
+        round(&b, &key, &transport, 2_000_000_002_000);
+        round(&a, &key, &transport, 2_000_000_003_000);
+        assert_eq!(title(a.conn(), "c1").as_deref(), Some("B rename"));
+        assert_eq!(title(b.conn(), "c1").as_deref(), Some("B rename"));
+
+        // Deletion propagates as a tombstone (the message goes with its conversation)
+        a.conn().execute("DELETE FROM conversations WHERE id = 'c2'", []).unwrap();
+        round(&a, &key, &transport, 2_000_000_004_000);
+        let report = round(&b, &key, &transport, 2_000_000_005_000);
+        assert_eq!(report.deleted, 1);
+        assert_eq!(title(b.conn(), "c2"), None);
+        assert_eq!(title(b.conn(), "c1").as_deref(), Some("B rename"));
+
+        // A device with another key can't read the snapshots
+        let stranger = Database::new_test_db().unwrap();
+        init_tables(stranger.conn()).unwrap();
+        let report = round(&stranger, &generate_key(), &transport, 2_000_000_006_000);
+        assert_eq!(report.rejected, 2);
+        assert_eq!(title(stranger.conn(), "c1"), None);
+    }
+}
@@ -171,6 +171,7 @@ impl EmbeddingService {
     #[instrument(skip(self, text), fields(text_len = text.len()))]
     pub fn embed(&self, text: &str) -> Result<Vec<f32>> {
         debug!("Generating embedding for text: {}", &text[..text.len().min(50)]);
+        let call_span = super::structured_logging::llm_call_span("embedding", "bge-m3", "embed");  // v3.9.0
 
         // Tokenize input
         let encoding = self
@@ -186,6 +187,7 @@ impl EmbeddingService {
         let seq_length = input_ids.len().min(MODEL_MAX_LENGTH);
         let input_ids = &input_ids[..seq_length];
         let attention_mask = &attention_mask[..seq_length];
+        call_span.record("prompt_tokens", seq_length as u64);
 
         // Convert to i64 for ONNX and create tensors
         let input_ids_i64: Vec<i64> = input_ids.iter().map(|&x| x as i64).collect();
@@ -221,6 +223,7 @@ impl EmbeddingService {
         let normalized = Self::normalize(&embedding);
 
         debug!(dimensions = normalized.len(), "Embedding generated");
+        call_span.record("success", true);
         Ok(normalized)
     }
 
@@ -371,6 +374,7 @@ impl EmbeddingService {
     /// Internal true batch processing implementation
     fn embed_batch_internal(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
         let batch_size = texts.len();
+        let call_span = super::structured_logging::llm_call_span("embedding", "bge-m3", "embed_batch");  // v3.9.0
 
         // Tokenize all texts
         let encodings: Vec<_> = texts
@@ -412,6 +416,8 @@ impl EmbeddingService {
             // Remaining positions are already 0 (padding)
         }
 
+        call_span.record("prompt_tokens", actual_lengths.iter().sum::<usize>() as u64);
+
         // Create ORT tensors for batch
         let input_ids_tensor = ort::value::Tensor::from_array((vec![batch_size, max_seq_len], input_ids_batch))
             .map_err(|e| anyhow!("Failed to create batch input_ids tensor: {:?}", e))?;
@@ -458,6 +464,7 @@ impl EmbeddingService {
         }
 
         debug!(count = results.len(), dimensions = hidden_dim, "Batch embedding complete");
+        call_span.record("success", true);
         Ok(results)
     }
 
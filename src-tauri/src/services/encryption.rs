@@ -0,0 +1,460 @@
+//! Encryption Service (v3.9.0)
+//!
+//! Optional encryption at rest for everything the app stores locally.
+//!
+//! Features:
+//! - SQLCipher encryption for data.db and knowledge_graph.db (every profile)
+//! - AES-256-GCM for LanceDB payload text and metadata
+//! - Key from a user passphrase (Argon2id) or a random key kept in the OS keychain
+//! - Unlock at startup; until then services run on in-memory placeholders
+//! - One-time migration of existing unencrypted databases on first unlock
+//! - Only available in builds with the `sqlcipher` cargo feature
+
+#![allow(dead_code)]  // Phase 5: Encryption (status helpers used by future settings UI)
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+
+const CONFIG_FILE: &str = "encryption.json";
+const KEYCHAIN_SERVICE: &str = "garden-of-eden-v3";
+const KEYCHAIN_ACCOUNT: &str = "data-encryption-key";
+
+/// Prefix marking an encrypted field value
+const SEALED_PREFIX: &str = "enc:v1:";
+
+/// Known plaintext used to check a passphrase without touching the databases
+const VERIFIER_PLAINTEXT: &str = "garden-of-eden-unlock-check";
+
+/// Header of every unencrypted SQLite file
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// True when SQLite was built with SQLCipher (`sqlcipher` cargo feature)
+pub const SQLCIPHER_AVAILABLE: bool = cfg!(feature = "sqlcipher");
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+type DataKey = [u8; KEY_LEN];
+
+/// Where the data key comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    /// Derived from a passphrase entered at every startup
+    Passphrase,
+    /// Random key stored in the OS keychain, unlocked automatically
+    Keychain,
+}
+
+/// Persisted encryption settings (never contains the key itself)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct EncryptionConfig {
+    enabled: bool,
+    key_source: Option<KeySource>,
+    salt: Option<String>,     // Base64, passphrase mode only
+    verifier: Option<String>, // VERIFIER_PLAINTEXT sealed with the key
+    pending_migration: bool,  // Existing plaintext files still to be converted
+}
+
+impl EncryptionConfig {
+    fn load(data_dir: &Path) -> Result<Self> {
+        let path = data_dir.join(CONFIG_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let json = std::fs::read_to_string(&path).context("Failed to read encryption config")?;
+        serde_json::from_str(&json).context("Failed to parse encryption config")
+    }
+
+    fn save(&self, data_dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(data_dir)?;
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(data_dir.join(CONFIG_FILE), json).context("Failed to write encryption config")
+    }
+}
+
+/// Encryption status for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptionStatus {
+    pub available: bool,
+    pub enabled: bool,
+    pub unlocked: bool,
+    pub key_source: Option<KeySource>,
+    pub pending_migration: bool,
+}
+
+// ============================================================================
+// PROCESS-WIDE KEY
+// ============================================================================
+
+struct KeyState {
+    enabled: bool,
+    key: Option<DataKey>,
+}
+
+static KEY_STATE: RwLock<KeyState> = RwLock::new(KeyState { enabled: false, key: None });
+
+fn active_key() -> Option<DataKey> {
+    KEY_STATE.read().ok().and_then(|s| s.key)
+}
+
+/// True while encryption is enabled but no key has been unlocked yet
+pub fn is_locked() -> bool {
+    KEY_STATE.read().map(|s| s.enabled && s.key.is_none()).unwrap_or(false)
+}
+
+/// True when a data key is unlocked, i.e. new databases are written encrypted
+pub fn has_key() -> bool {
+    active_key().is_some()
+}
+
+/// Open a SQLite connection, keyed with the active data key when unlocked
+pub fn open_connection(path: &Path) -> rusqlite::Result<Connection> {
+    let conn = Connection::open(path)?;
+    if let Some(key) = active_key() {
+        apply_key(&conn, &key)?;
+    }
+    Ok(conn)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// SQLCipher raw-key literal (skips SQLCipher's own KDF, the key is already derived)
+fn raw_key_literal(key: &DataKey) -> String {
+    format!("\"x'{}'\"", hex(key))
+}
+
+fn apply_key(conn: &Connection, key: &DataKey) -> rusqlite::Result<()> {
+    conn.execute_batch(&format!("PRAGMA key = {};", raw_key_literal(key)))
+}
+
+fn seal_with(key: &DataKey, plaintext: &str) -> Result<String> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|e| anyhow!("Encryption failed: {}", e))?;
+
+    let mut payload = nonce.to_vec();
+    payload.extend_from_slice(&ciphertext);
+    Ok(format!("{}{}", SEALED_PREFIX, general_purpose::STANDARD.encode(payload)))
+}
+
+fn open_with(key: &DataKey, sealed: &str) -> Result<String> {
+    let encoded = match sealed.strip_prefix(SEALED_PREFIX) {
+        Some(encoded) => encoded,
+        None => return Ok(sealed.to_string()),
+    };
+    let payload = general_purpose::STANDARD.decode(encoded)?;
+    if payload.len() < NONCE_LEN {
+        return Err(anyhow!("Encrypted value is truncated"));
+    }
+
+    let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("Decryption failed (wrong key?)"))?;
+    Ok(String::from_utf8(plaintext)?)
+}
+
+pub fn is_sealed(value: &str) -> bool {
+    value.starts_with(SEALED_PREFIX)
+}
+
+/// Encrypt a stored field when encryption is on (plaintext passthrough otherwise)
+pub fn seal_field(plaintext: &str) -> Result<String> {
+    if let Some(key) = active_key() {
+        return seal_with(&key, plaintext);
+    }
+    if is_locked() {
+        return Err(anyhow!("Storage is locked"));
+    }
+    Ok(plaintext.to_string())
+}
+
+/// Decrypt a stored field; unencrypted (legacy) values are returned as-is
+pub fn open_field(stored: &str) -> Result<String> {
+    if !is_sealed(stored) {
+        return Ok(stored.to_string());
+    }
+    match active_key() {
+        Some(key) => open_with(&key, stored),
+        None => Err(anyhow!("Storage is locked")),
+    }
+}
+
+/// Argon2id key derivation from a passphrase
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<DataKey> {
+    let mut key = [0u8; KEY_LEN];
+    argon2::Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+fn keychain_entry() -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT).map_err(|e| anyhow!("Keychain error: {}", e))
+}
+
+fn decode_hex_key(hex_key: &str) -> Result<DataKey> {
+    if hex_key.len() != KEY_LEN * 2 || !hex_key.is_ascii() {
+        return Err(anyhow!("Invalid key in keychain"));
+    }
+    let mut key = [0u8; KEY_LEN];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex_key[i * 2..i * 2 + 2], 16)?;
+    }
+    Ok(key)
+}
+
+// ============================================================================
+// MIGRATION
+// ============================================================================
+
+fn is_plaintext_sqlite(path: &Path) -> Result<bool> {
+    use std::io::Read;
+    let mut header = [0u8; 16];
+    let mut file = std::fs::File::open(path)?;
+    match file.read_exact(&mut header) {
+        Ok(()) => Ok(&header == SQLITE_HEADER),
+        Err(_) => Ok(false), // Empty / tiny file: nothing to migrate
+    }
+}
+
+/// Convert one unencrypted SQLite file in place. Returns false if there was nothing to do.
+fn migrate_database_file(path: &Path, key: &DataKey) -> Result<bool> {
+    if !path.exists() || !is_plaintext_sqlite(path)? {
+        return Ok(false);
+    }
+
+    log::info!("Encrypting database {:?}", path);
+    let encrypted_path = path.with_extension("db.encrypting");
+    let _ = std::fs::remove_file(&encrypted_path);
+
+    {
+        let conn = Connection::open(path)?;
+        conn.execute(
+            &format!("ATTACH DATABASE ?1 AS encrypted KEY {}", raw_key_literal(key)),
+            [encrypted_path.to_string_lossy()],
+        )?;
+        conn.query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(()))?;
+        conn.execute_batch("DETACH DATABASE encrypted;")?;
+    }
+
+    std::fs::rename(&encrypted_path, path).context("Failed to replace database with encrypted copy")?;
+    Ok(true)
+}
+
+/// Every database file across all profiles
+fn database_files(data_dir: &Path) -> Vec<PathBuf> {
+    let mut roots = vec![data_dir.to_path_buf()];
+    if let Ok(entries) = std::fs::read_dir(data_dir.join("profiles")) {
+        roots.extend(entries.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.is_dir()));
+    }
+    roots
+        .into_iter()
+        .flat_map(|root| [root.join("data.db"), root.join("knowledge_graph.db")])
+        .collect()
+}
+
+// ============================================================================
+// SERVICE
+// ============================================================================
+
+/// Encryption service
+pub struct EncryptionService {
+    data_dir: PathBuf,
+    config: Mutex<EncryptionConfig>,
+}
+
+impl EncryptionService {
+    pub fn new(data_dir: PathBuf) -> Result<Self> {
+        let config = EncryptionConfig::load(&data_dir)?;
+        if let Ok(mut state) = KEY_STATE.write() {
+            state.enabled = config.enabled;
+        }
+
+        log::info!("✓ Encryption Service initialized (enabled: {})", config.enabled);
+        Ok(Self {
+            data_dir,
+            config: Mutex::new(config),
+        })
+    }
+
+    pub fn status(&self) -> Result<EncryptionStatus> {
+        let config = self.config.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        Ok(EncryptionStatus {
+            available: SQLCIPHER_AVAILABLE,
+            enabled: config.enabled,
+            unlocked: !is_locked(),
+            key_source: config.key_source,
+            pending_migration: config.pending_migration,
+        })
+    }
+
+    /// Unlock with the keychain key (startup, keychain mode only)
+    pub fn unlock_from_keychain(&self) -> Result<bool> {
+        let source = {
+            let config = self.config.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            if !config.enabled {
+                return Ok(false);
+            }
+            config.key_source
+        };
+        if source != Some(KeySource::Keychain) {
+            return Ok(false);
+        }
+
+        let hex_key = keychain_entry()?
+            .get_password()
+            .map_err(|e| anyhow!("Failed to read key from keychain: {}", e))?;
+        self.activate(decode_hex_key(&hex_key)?)?;
+        Ok(true)
+    }
+
+    /// Unlock with a passphrase. Returns the number of databases migrated.
+    pub fn unlock(&self, passphrase: &str) -> Result<usize> {
+        let salt = {
+            let config = self.config.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            if !config.enabled {
+                return Err(anyhow!("Encryption is not enabled"));
+            }
+            if config.key_source != Some(KeySource::Passphrase) {
+                return Err(anyhow!("This device unlocks with the OS keychain"));
+            }
+            config.salt.clone().ok_or_else(|| anyhow!("Missing key salt"))?
+        };
+
+        let salt = general_purpose::STANDARD.decode(salt)?;
+        self.activate(derive_key(passphrase, &salt)?)
+    }
+
+    /// Check the key, convert leftover plaintext databases, then make the key active
+    fn activate(&self, key: DataKey) -> Result<usize> {
+        if !SQLCIPHER_AVAILABLE {
+            return Err(anyhow!("This build was compiled without SQLCipher; encrypted data cannot be opened"));
+        }
+
+        let mut config = self.config.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let verifier = config.verifier.as_deref().ok_or_else(|| anyhow!("Missing key verifier"))?;
+        if open_with(&key, verifier).ok().as_deref() != Some(VERIFIER_PLAINTEXT) {
+            return Err(anyhow!("Incorrect passphrase"));
+        }
+
+        let mut migrated = 0;
+        if config.pending_migration {
+            for path in database_files(&self.data_dir) {
+                if migrate_database_file(&path, &key)? {
+                    migrated += 1;
+                }
+            }
+            config.pending_migration = false;
+            config.save(&self.data_dir)?;
+            log::info!("✓ Encrypted {} existing databases", migrated);
+        }
+
+        let mut state = KEY_STATE.write().map_err(|e| anyhow!("Lock error: {}", e))?;
+        state.enabled = true;
+        state.key = Some(key);
+        Ok(migrated)
+    }
+
+    /// Turn encryption on. Existing data is converted on the next unlock at startup.
+    ///
+    /// Without a passphrase a random key is generated and stored in the OS keychain.
+    pub fn enable(&self, passphrase: Option<&str>) -> Result<EncryptionStatus> {
+        if !SQLCIPHER_AVAILABLE {
+            return Err(anyhow!("Encryption requires a build with the `sqlcipher` feature"));
+        }
+
+        {
+            let mut config = self.config.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            if config.enabled {
+                return Err(anyhow!("Encryption is already enabled"));
+            }
+
+            let key = match passphrase {
+                Some(passphrase) => {
+                    if passphrase.chars().count() < 8 {
+                        return Err(anyhow!("Passphrase must be at least 8 characters"));
+                    }
+                    let salt: [u8; 16] = random_bytes();
+                    config.key_source = Some(KeySource::Passphrase);
+                    config.salt = Some(general_purpose::STANDARD.encode(salt));
+                    derive_key(passphrase, &salt)?
+                }
+                None => {
+                    let key: DataKey = random_bytes();
+                    keychain_entry()?
+                        .set_password(&hex(&key))
+                        .map_err(|e| anyhow!("Failed to store key in keychain: {}", e))?;
+                    config.key_source = Some(KeySource::Keychain);
+                    config.salt = None;
+                    key
+                }
+            };
+
+            config.verifier = Some(seal_with(&key, VERIFIER_PLAINTEXT)?);
+            config.enabled = true;
+            config.pending_migration = true;
+            config.save(&self.data_dir)?;
+        }
+
+        log::info!("Encryption enabled; existing data will be encrypted on next unlock");
+        self.status()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_roundtrip() {
+        let key: DataKey = [7u8; KEY_LEN];
+        let sealed = seal_with(&key, "private note").unwrap();
+        assert!(is_sealed(&sealed));
+        assert_ne!(sealed, seal_with(&key, "private note").unwrap()); // fresh nonce
+        assert_eq!(open_with(&key, &sealed).unwrap(), "private note");
+        assert!(open_with(&[8u8; KEY_LEN], &sealed).is_err());
+        assert_eq!(open_with(&key, "legacy plaintext").unwrap(), "legacy plaintext");
+    }
+
+    #[test]
+    fn test_derive_key_and_hex() {
+        let a = derive_key("correct horse", b"0123456789abcdef").unwrap();
+        let b = derive_key("correct horse", b"0123456789abcdef").unwrap();
+        let c = derive_key("wrong horse", b"0123456789abcdef").unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(decode_hex_key(&hex(&a)).unwrap(), a);
+        assert!(decode_hex_key("abc").is_err());
+    }
+
+    #[test]
+    fn test_plaintext_detection() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.db");
+        Connection::open(&path).unwrap().execute_batch("CREATE TABLE t (x INTEGER);").unwrap();
+        assert!(is_plaintext_sqlite(&path).unwrap());
+
+        std::fs::write(dir.path().join("junk.db"), b"not a database at all").unwrap();
+        assert!(!is_plaintext_sqlite(&dir.path().join("junk.db")).unwrap());
+    }
+}
@@ -4,11 +4,16 @@
  * Extracts entities and relationships from conversation text using LLM
  *
  * Entity Types: Person, Organization, Location, Technology, Concept,
- *               Tool, Project, Document, Event
+ *               Tool, Project, Document, Event, Date
  *
  * Relationship Types: WorksWith, PartOf, Uses, Creates, Knows,
  *                     LocatedAt, DependsOn, RelatesTo
  *
+ * Modes (v3.9.0): auto (schema-constrained LLM, heuristics when Ollama is down),
+ * llm only, or heuristics only. Whole conversations are extracted in batches that
+ * carry the entities found so far, and coreferences ("he", "Minsu" for "Kim Minsu",
+ * "the company") are merged into one entity with its aliases.
+ *
  * Integration: Used by graph_builder.rs for knowledge graph construction
  */
 
@@ -16,6 +21,11 @@ use log::{debug, info, warn};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::services::structured_output::{self, StructuredOptions};
+
+/// Small model used for NER
+const EXTRACTION_MODEL: &str = "llama3.2:3b";
+
 /// Response from LLM for entity extraction
 #[derive(Debug, Deserialize)]
 struct LLMEntityResponse {
@@ -29,6 +39,8 @@ struct LLMEntity {
     #[serde(rename = "type")]
     entity_type: String,
     confidence: Option<f32>,
+    #[serde(default)]
+    aliases: Vec<String>,  // v3.9.0: Other names / mentions of the same entity
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,6 +49,50 @@ struct LLMRelationship {
     target: String,
     relation: String,
     confidence: Option<f32>,
+    #[serde(default)]
+    valid_from: Option<String>,
+    #[serde(default)]
+    valid_to: Option<String>,
+}
+
+/// JSON schema of `LLMEntityResponse` (v3.9.0)
+fn extraction_schema() -> serde_json::Value {
+    let confidence = serde_json::json!({"type": "number", "minimum": 0.0, "maximum": 1.0});
+    let date = serde_json::json!({"type": ["string", "null"]});
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "entities": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string", "minLength": 1},
+                        "type": {"enum": ["Person", "Organization", "Location", "Technology", "Concept", "Tool", "Project", "Document", "Event", "Date"]},
+                        "confidence": confidence,
+                        "aliases": {"type": "array", "items": {"type": "string"}}
+                    },
+                    "required": ["name", "type"]
+                }
+            },
+            "relationships": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "source": {"type": "string"},
+                        "target": {"type": "string"},
+                        "relation": {"enum": ["WorksWith", "PartOf", "Uses", "Creates", "Knows", "LocatedAt", "DependsOn", "RelatesTo"]},
+                        "confidence": confidence,
+                        "valid_from": date,
+                        "valid_to": date
+                    },
+                    "required": ["source", "target", "relation"]
+                }
+            }
+        },
+        "required": ["entities", "relationships"]
+    })
 }
 
 /// Entity types for knowledge graph
@@ -51,6 +107,7 @@ pub enum EntityType {
     Project,
     Document,
     Event,
+    Date,  // v3.9.0: Dates and deadlines
 }
 
 impl EntityType {
@@ -65,6 +122,7 @@ impl EntityType {
             EntityType::Project => "Project",
             EntityType::Document => "Document",
             EntityType::Event => "Event",
+            EntityType::Date => "Date",
         }
     }
 
@@ -79,6 +137,7 @@ impl EntityType {
             "project" => Some(EntityType::Project),
             "document" => Some(EntityType::Document),
             "event" => Some(EntityType::Event),
+            "date" => Some(EntityType::Date),
             _ => None,
         }
     }
@@ -151,6 +210,21 @@ pub struct ExtractionResult {
     pub entities: Vec<Entity>,
     pub relationships: Vec<Relationship>,
     pub source_text: String,
+    #[serde(default)]
+    pub used_llm: bool,  // v3.9.0: False when heuristics produced the result
+}
+
+/// How entities are extracted (v3.9.0)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExtractionMode {
+    /// LLM extraction, heuristics when Ollama is unavailable
+    #[default]
+    Auto,
+    /// LLM extraction only; errors when Ollama is unavailable
+    Llm,
+    /// Heuristics only (no model calls)
+    Heuristic,
 }
 
 /// Entity extraction configuration
@@ -159,6 +233,15 @@ pub struct EntityExtractorConfig {
     pub min_confidence: f32,
     pub max_entities_per_text: usize,
     pub enable_coreference_resolution: bool,
+    #[serde(default)]
+    pub mode: ExtractionMode,  // v3.9.0
+    /// Conversation text per LLM call when extracting whole conversations (v3.9.0)
+    #[serde(default = "default_batch_chars")]
+    pub batch_chars: usize,
+}
+
+fn default_batch_chars() -> usize {
+    4000
 }
 
 impl Default for EntityExtractorConfig {
@@ -167,6 +250,8 @@ impl Default for EntityExtractorConfig {
             min_confidence: 0.5,
             max_entities_per_text: 50,
             enable_coreference_resolution: true,
+            mode: ExtractionMode::Auto,
+            batch_chars: default_batch_chars(),
         }
     }
 }
@@ -198,18 +283,82 @@ impl EntityExtractor {
     pub async fn extract(&self, text: &str) -> Result<ExtractionResult, String> {
         info!("Extracting entities from text (length: {})", text.len());
 
-        // Try LLM-based extraction first (v3.7.0)
-        match self.extract_with_llm(text).await {
-            Ok(result) => {
-                debug!(
-                    "LLM extracted {} entities, {} relationships",
-                    result.entities.len(),
-                    result.relationships.len()
-                );
-                return Ok(result);
+        let use_llm = self.config.mode != ExtractionMode::Heuristic;
+        let mut result = self.extract_chunk(text, &[], use_llm).await?;
+        if self.config.enable_coreference_resolution {
+            resolve_coreferences(&mut result);
+        }
+        Ok(result)
+    }
+
+    /// Extract several independent texts (v3.9.0)
+    ///
+    /// In auto mode, once Ollama fails the remaining texts go straight to heuristics.
+    pub async fn extract_batch(&self, texts: &[String]) -> Result<Vec<ExtractionResult>, String> {
+        let mut use_llm = self.config.mode != ExtractionMode::Heuristic;
+        let mut results = Vec::with_capacity(texts.len());
+        for text in texts {
+            let mut result = self.extract_chunk(text, &[], use_llm).await?;
+            use_llm &= result.used_llm;
+            if self.config.enable_coreference_resolution {
+                resolve_coreferences(&mut result);
             }
-            Err(e) => {
-                warn!("LLM extraction failed, falling back to heuristics: {}", e);
+            results.push(result);
+        }
+        info!("Extracted entities from {} texts", results.len());
+        Ok(results)
+    }
+
+    /// Extract one conversation's messages as a whole (v3.9.0)
+    ///
+    /// Messages are sent in batches of about `batch_chars`; each batch sees the
+    /// entities found so far so later mentions resolve to them.
+    pub async fn extract_conversation(&self, messages: &[String]) -> Result<ExtractionResult, String> {
+        let batches = batch_messages(messages, self.config.batch_chars);
+        info!(
+            "Extracting entities from conversation ({} messages, {} batches)",
+            messages.len(),
+            batches.len()
+        );
+
+        let mut use_llm = self.config.mode != ExtractionMode::Heuristic;
+        let mut merged = ExtractionResult {
+            entities: Vec::new(),
+            relationships: Vec::new(),
+            source_text: messages.join("\n"),
+            used_llm: false,
+        };
+        for batch in &batches {
+            let result = self.extract_chunk(batch, &merged.entities, use_llm).await?;
+            use_llm &= result.used_llm;
+            merged.used_llm |= result.used_llm;
+            merge_extraction(&mut merged, result);
+        }
+
+        if self.config.enable_coreference_resolution {
+            resolve_coreferences(&mut merged);
+        }
+        merged.entities.truncate(self.config.max_entities_per_text);
+        Ok(merged)
+    }
+
+    /// LLM extraction per the configured mode, heuristics as the auto-mode fallback
+    async fn extract_chunk(&self, text: &str, known: &[Entity], use_llm: bool) -> Result<ExtractionResult, String> {
+        if use_llm {
+            // Try LLM-based extraction first (v3.7.0)
+            match self.extract_with_llm(text, known).await {
+                Ok(result) => {
+                    debug!(
+                        "LLM extracted {} entities, {} relationships",
+                        result.entities.len(),
+                        result.relationships.len()
+                    );
+                    return Ok(result);
+                }
+                Err(e) if self.config.mode == ExtractionMode::Llm => return Err(e),
+                Err(e) => {
+                    warn!("LLM extraction failed, falling back to heuristics: {}", e);
+                }
             }
         }
 
@@ -227,13 +376,14 @@ impl EntityExtractor {
             entities,
             relationships,
             source_text: text.to_string(),
+            used_llm: false,
         })
     }
 
     /// Extract entities using LLM (Ollama)
-    async fn extract_with_llm(&self, text: &str) -> Result<ExtractionResult, String> {
+    async fn extract_with_llm(&self, text: &str, known: &[Entity]) -> Result<ExtractionResult, String> {
         // Construct NER prompt
-        let prompt = format!(
+        let mut prompt = format!(
             r#"Extract named entities and relationships from the following text.
 
 TEXT:
@@ -242,57 +392,47 @@ TEXT:
 Respond ONLY with a valid JSON object in this exact format:
 {{
   "entities": [
-    {{"name": "entity name", "type": "Person|Organization|Location|Technology|Concept|Tool|Project|Document|Event", "confidence": 0.9}}
+    {{"name": "entity name", "type": "Person|Organization|Location|Technology|Concept|Tool|Project|Document|Event|Date", "confidence": 0.9, "aliases": ["other name"]}}
   ],
   "relationships": [
-    {{"source": "entity1", "target": "entity2", "relation": "WorksWith|PartOf|Uses|Creates|Knows|LocatedAt|DependsOn|RelatesTo", "confidence": 0.8}}
+    {{"source": "entity1", "target": "entity2", "relation": "WorksWith|PartOf|Uses|Creates|Knows|LocatedAt|DependsOn|RelatesTo", "confidence": 0.8, "valid_from": "2024-06", "valid_to": null}}
   ]
 }}
 
 Rules:
 - Only extract concrete, specific entities (not generic concepts)
+- Dates and deadlines ("2024-06-01", "next Friday") are Date entities
 - Confidence should be between 0.0 and 1.0
 - Source and target in relationships must match entity names exactly
+- valid_from / valid_to are optional (YYYY, YYYY-MM or YYYY-MM-DD); set them only when the text says when the relationship started or ended
 - Return empty arrays if no entities found"#,
             text
         );
-
-        // Call Ollama API directly
-        let client = reqwest::Client::new();
-        let response = client
-            .post("http://127.0.0.1:11434/api/generate")
-            .json(&serde_json::json!({
-                "model": "llama3.2:3b",
-                "prompt": prompt,
-                "stream": false,
-                "options": {
-                    "temperature": 0.1,
-                    "num_predict": 1024
-                }
-            }))
-            .send()
-            .await
-            .map_err(|e| format!("Failed to connect to Ollama: {}", e))?;
-
-        if !response.status().is_success() {
-            return Err(format!("Ollama request failed: {}", response.status()));
+        if self.config.enable_coreference_resolution {
+            prompt.push_str(
+                "\n- Resolve pronouns and short references (\"he\", \"she\", \"it\", \"the company\", first names) \
+                 to the entity they refer to: use its most complete name as \"name\" and list the other names in \"aliases\"",
+            );
+        }
+        if !known.is_empty() {
+            let names: Vec<String> = known
+                .iter()
+                .map(|e| format!("{} ({})", e.name, e.entity_type.as_str()))
+                .collect();
+            prompt.push_str(&format!(
+                "\n- Entities already mentioned earlier in this conversation (use these exact names when the text refers to them): {}",
+                names.join(", ")
+            ));
         }
 
-        let response_json: serde_json::Value = response.json().await
-            .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-        let response_text = response_json.get("response")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| "No response field in Ollama output".to_string())?;
-
-        // Parse LLM response as JSON
-        // Try to find JSON in the response (LLM might include extra text)
-        let json_start = response_text.find('{').ok_or("No JSON found in response")?;
-        let json_end = response_text.rfind('}').ok_or("No closing brace in response")?;
-        let json_str = &response_text[json_start..=json_end];
-
-        let llm_response: LLMEntityResponse = serde_json::from_str(json_str)
-            .map_err(|e| format!("Failed to parse LLM JSON: {}", e))?;
+        // v3.9.0: Schema-constrained generation, validated and retried on bad JSON
+        let options = StructuredOptions {
+            model: Some(EXTRACTION_MODEL.to_string()),
+            ..StructuredOptions::default()
+        };
+        let llm_response: LLMEntityResponse = structured_output::generate_typed(&prompt, &extraction_schema(), &options)
+            .await
+            .map_err(|e| format!("LLM extraction failed: {}", e))?;
 
         // Convert LLM response to our entity format
         let entities: Vec<Entity> = llm_response.entities
@@ -301,10 +441,19 @@ Rules:
                 let entity_type = EntityType::from_str(&e.entity_type)?;
                 let confidence = e.confidence.unwrap_or(0.8);
                 if confidence >= self.config.min_confidence {
+                    let mut properties = HashMap::new();
+                    let aliases: Vec<&str> = e.aliases
+                        .iter()
+                        .map(|a| a.trim())
+                        .filter(|a| !a.is_empty() && !a.eq_ignore_ascii_case(&e.name))
+                        .collect();
+                    if !aliases.is_empty() {
+                        properties.insert("aliases".to_string(), aliases.join(", "));
+                    }
                     Some(Entity {
-                        name: e.name.clone(),
+                        name: e.name.trim().to_string(),
                         entity_type,
-                        properties: HashMap::new(),
+                        properties,
                         confidence,
                     })
                 } else {
@@ -320,11 +469,18 @@ Rules:
                 let rel_type = RelationshipType::from_str(&r.relation)?;
                 let confidence = r.confidence.unwrap_or(0.7);
                 if confidence >= self.config.min_confidence {
+                    let mut properties = HashMap::new();
+                    if let Some(valid_from) = r.valid_from {
+                        properties.insert("valid_from".to_string(), valid_from);
+                    }
+                    if let Some(valid_to) = r.valid_to {
+                        properties.insert("valid_to".to_string(), valid_to);
+                    }
                     Some(Relationship {
                         source_entity: r.source,
                         target_entity: r.target,
                         relationship_type: rel_type,
-                        properties: HashMap::new(),
+                        properties,
                         confidence,
                     })
                 } else {
@@ -337,6 +493,7 @@ Rules:
             entities,
             relationships,
             source_text: text.to_string(),
+            used_llm: true,
         })
     }
 
@@ -357,6 +514,17 @@ Rules:
                 continue;
             }
 
+            // Check for dates (v3.9.0)
+            if is_date(word) {
+                entities.push(Entity {
+                    name: word.trim_matches(|c: char| !c.is_ascii_digit()).to_string(),
+                    entity_type: EntityType::Date,
+                    properties: HashMap::new(),
+                    confidence: 0.9,
+                });
+                continue;
+            }
+
             // Check if capitalized (potential entity)
             if word.chars().next().unwrap().is_uppercase() {
                 let entity_name = word.trim_matches(|c: char| !c.is_alphanumeric());
@@ -568,6 +736,156 @@ impl Default for EntityExtractor {
     }
 }
 
+/// ISO-style dates: 2024-06-01, 2024/06/01, 2024.06.01 (v3.9.0)
+fn is_date(word: &str) -> bool {
+    let word = word.trim_matches(|c: char| !c.is_ascii_digit());
+    let normalized = word.replace(['/', '.'], "-");
+    chrono::NaiveDate::parse_from_str(&normalized, "%Y-%m-%d").is_ok()
+}
+
+/// Group messages into batches of about `max_chars` (a long message is its own batch)
+fn batch_messages(messages: &[String], max_chars: usize) -> Vec<String> {
+    let mut batches = Vec::new();
+    let mut current = String::new();
+    for message in messages.iter().map(|m| m.trim()).filter(|m| !m.is_empty()) {
+        if !current.is_empty() && current.len() + message.len() + 1 > max_chars {
+            batches.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(message);
+    }
+    if !current.is_empty() {
+        batches.push(current);
+    }
+    batches
+}
+
+/// Add a batch result to the conversation result, merging repeated entities (v3.9.0)
+fn merge_extraction(merged: &mut ExtractionResult, result: ExtractionResult) {
+    for entity in result.entities {
+        match merged
+            .entities
+            .iter_mut()
+            .find(|e| e.entity_type == entity.entity_type && e.name.eq_ignore_ascii_case(&entity.name))
+        {
+            Some(existing) => absorb_entity(existing, entity),
+            None => merged.entities.push(entity),
+        }
+    }
+    for relationship in result.relationships {
+        let duplicate = merged.relationships.iter().any(|r| {
+            r.relationship_type == relationship.relationship_type
+                && r.source_entity.eq_ignore_ascii_case(&relationship.source_entity)
+                && r.target_entity.eq_ignore_ascii_case(&relationship.target_entity)
+        });
+        if !duplicate {
+            merged.relationships.push(relationship);
+        }
+    }
+}
+
+fn aliases_of(entity: &Entity) -> Vec<String> {
+    entity
+        .properties
+        .get("aliases")
+        .map(|aliases| aliases.split(", ").map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Fold `other` (the same entity) into `target`: best confidence, all names as aliases
+fn absorb_entity(target: &mut Entity, other: Entity) {
+    let mut aliases = aliases_of(target);
+    for alias in std::iter::once(other.name.clone()).chain(aliases_of(&other)) {
+        if !alias.eq_ignore_ascii_case(&target.name) && !aliases.iter().any(|a| a.eq_ignore_ascii_case(&alias)) {
+            aliases.push(alias);
+        }
+    }
+    for (key, value) in other.properties {
+        target.properties.entry(key).or_insert(value);
+    }
+    if aliases.is_empty() {
+        target.properties.remove("aliases");
+    } else {
+        target.properties.insert("aliases".to_string(), aliases.join(", "));
+    }
+    target.confidence = target.confidence.max(other.confidence);
+}
+
+/// Merge entities that refer to the same thing and point relationships at the merged one (v3.9.0)
+///
+/// An entity is folded into another when its name is one of the other's aliases, or
+/// when it is a one-word person name matching the first or last word of exactly one
+/// longer person name ("Minsu" → "Kim Minsu").
+fn resolve_coreferences(result: &mut ExtractionResult) {
+    let mut canonical: HashMap<String, String> = HashMap::new();
+    for entity in &result.entities {
+        for alias in aliases_of(entity) {
+            canonical.entry(alias.to_lowercase()).or_insert_with(|| entity.name.clone());
+        }
+    }
+    for entity in result.entities.iter().filter(|e| e.entity_type == EntityType::Person && !e.name.contains(' ')) {
+        let matches: Vec<&Entity> = result
+            .entities
+            .iter()
+            .filter(|other| other.entity_type == EntityType::Person && other.name.contains(' '))
+            .filter(|other| {
+                let mut words = other.name.split_whitespace();
+                let first = words.next().unwrap_or_default();
+                let last = words.last().unwrap_or_default();
+                first.eq_ignore_ascii_case(&entity.name) || last.eq_ignore_ascii_case(&entity.name)
+            })
+            .collect();
+        if let [full] = matches.as_slice() {
+            canonical.entry(entity.name.to_lowercase()).or_insert_with(|| full.name.clone());
+        }
+    }
+    // An alias that is itself the canonical name of another entity would form a cycle
+    canonical.retain(|alias, target| !alias.eq_ignore_ascii_case(target));
+    if canonical.is_empty() {
+        return;
+    }
+
+    let resolve = |name: &str| canonical.get(&name.to_lowercase()).cloned();
+    let mut entities: Vec<Entity> = Vec::with_capacity(result.entities.len());
+    let mut folded = Vec::new();
+    for entity in result.entities.drain(..) {
+        match resolve(&entity.name) {
+            Some(target) => folded.push((target, entity)),
+            None => entities.push(entity),
+        }
+    }
+    for (target, entity) in folded {
+        match entities.iter_mut().find(|e| e.name == target) {
+            Some(existing) => absorb_entity(existing, entity),
+            // The target was folded too (chained aliases); keep this one as is
+            None => entities.push(entity),
+        }
+    }
+    result.entities = entities;
+
+    let mut relationships: Vec<Relationship> = Vec::with_capacity(result.relationships.len());
+    for mut relationship in result.relationships.drain(..) {
+        if let Some(source) = resolve(&relationship.source_entity) {
+            relationship.source_entity = source;
+        }
+        if let Some(target) = resolve(&relationship.target_entity) {
+            relationship.target_entity = target;
+        }
+        let is_loop = relationship.source_entity.eq_ignore_ascii_case(&relationship.target_entity);
+        let duplicate = relationships.iter().any(|r| {
+            r.relationship_type == relationship.relationship_type
+                && r.source_entity == relationship.source_entity
+                && r.target_entity == relationship.target_entity
+        });
+        if !is_loop && !duplicate {
+            relationships.push(relationship);
+        }
+    }
+    result.relationships = relationships;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -629,6 +947,68 @@ mod tests {
         assert_eq!(RelationshipType::from_str("invalid"), None);
     }
 
+    fn entity(name: &str, entity_type: EntityType, aliases: &[&str]) -> Entity {
+        let mut properties = HashMap::new();
+        if !aliases.is_empty() {
+            properties.insert("aliases".to_string(), aliases.join(", "));
+        }
+        Entity { name: name.to_string(), entity_type, properties, confidence: 0.8 }
+    }
+
+    fn relationship(source: &str, target: &str, relationship_type: RelationshipType) -> Relationship {
+        Relationship {
+            source_entity: source.to_string(),
+            target_entity: target.to_string(),
+            relationship_type,
+            properties: HashMap::new(),
+            confidence: 0.7,
+        }
+    }
+
+    #[test]
+    fn test_resolve_coreferences() {
+        let mut result = ExtractionResult {
+            entities: vec![
+                entity("Kim Minsu", EntityType::Person, &[]),
+                entity("Minsu", EntityType::Person, &[]),
+                entity("Acme Corp", EntityType::Organization, &["the company"]),
+                entity("the company", EntityType::Organization, &[]),
+                entity("Garden", EntityType::Project, &[]),
+            ],
+            relationships: vec![
+                relationship("Minsu", "Acme Corp", RelationshipType::PartOf),
+                relationship("Kim Minsu", "Acme Corp", RelationshipType::PartOf),
+                relationship("the company", "Garden", RelationshipType::Creates),
+            ],
+            source_text: String::new(),
+            used_llm: true,
+        };
+
+        resolve_coreferences(&mut result);
+
+        let names: Vec<&str> = result.entities.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["Kim Minsu", "Acme Corp", "Garden"]);
+        assert_eq!(result.entities[0].properties.get("aliases").map(String::as_str), Some("Minsu"));
+        assert_eq!(result.relationships.len(), 2);
+        assert_eq!(result.relationships[1].source_entity, "Acme Corp");
+    }
+
+    #[test]
+    fn test_batch_messages_and_dates() {
+        let messages = vec!["a".repeat(30), "b".repeat(30), "c".repeat(80), String::new()];
+        let batches = batch_messages(&messages, 64);
+        assert_eq!(batches.len(), 2);
+        assert!(batches[0].contains('a') && batches[0].contains('b'));
+
+        assert!(is_date("2024-06-01,"));
+        assert!(is_date("2024/6/1"));
+        assert!(!is_date("2024-13-01"));
+
+        let extractor = EntityExtractor::new();
+        let entities = extractor.extract_entities_heuristic("Release is due 2024-06-01 for Garden");
+        assert!(entities.iter().any(|e| e.entity_type == EntityType::Date && e.name == "2024-06-01"));
+    }
+
     #[tokio::test]
     async fn test_confidence_filtering() {
         let mut config = EntityExtractorConfig::default();
@@ -0,0 +1,634 @@
+//! File Editing Service (v3.9.0)
+//!
+//! Targeted edits for the `edit_file` tool, so agents change the lines they
+//! mean to instead of rewriting whole files.
+//!
+//! Features:
+//! - Unified diffs, applied by context (hunk line numbers may be off)
+//! - Search/replace blocks (`<<<<<<< SEARCH` / `=======` / `>>>>>>> REPLACE`)
+//! - Unified-diff preview of the result before anything is written
+//! - Conflict detection: the file's SHA-256 when it was read must still match
+//! - Pre-edit backups in the app data directory (oldest pruned)
+
+#![allow(dead_code)]  // Phase 7: File system integration (on-demand)
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::file::FileService;
+
+/// Backups kept across all files; the oldest are removed first
+const MAX_BACKUPS: usize = 200;
+
+/// Context lines around each change in previews
+const PREVIEW_CONTEXT: usize = 3;
+
+/// Above this many line pairs the preview skips the exact diff and shows the
+/// changed region as one replacement
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// One search/replace edit; `search` must occur exactly once
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchReplaceBlock {
+    pub search: String,
+    pub replace: String,
+}
+
+/// How the file should change
+#[derive(Debug, Clone)]
+pub enum EditRequest {
+    UnifiedDiff(String),
+    SearchReplace(Vec<SearchReplaceBlock>),
+}
+
+/// Edit computed against the current file, not yet written
+#[derive(Debug, Clone)]
+pub struct EditPlan {
+    pub path: String,
+    pub original: String,
+    pub updated: String,
+    /// Unified diff from `original` to `updated`
+    pub diff: String,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+}
+
+/// Result of writing an edit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditOutcome {
+    pub path: String,
+    /// Copy of the file before the edit
+    pub backup_path: String,
+    /// SHA-256 of the new content, for chaining further edits
+    pub sha256: String,
+}
+
+/// Diff/patch based editing with backups
+pub struct FileEditService {
+    backup_dir: PathBuf,
+}
+
+impl FileEditService {
+    pub fn new(backup_dir: PathBuf) -> Self {
+        Self { backup_dir }
+    }
+
+    /// Compute the edit without writing
+    ///
+    /// With `expected_sha256` (from `read_file`), fails if the file changed since then.
+    pub fn plan(&self, path: &str, request: &EditRequest, expected_sha256: Option<&str>) -> Result<EditPlan> {
+        let original = FileService::read_file(path)?;
+        check_unchanged(&original, expected_sha256)?;
+
+        let updated = match request {
+            EditRequest::UnifiedDiff(diff) => apply_unified_diff(&original, diff)?,
+            EditRequest::SearchReplace(blocks) => apply_search_replace(&original, blocks)?,
+        };
+        if updated == original {
+            return Err(anyhow!("Edit leaves {} unchanged", path));
+        }
+
+        let (diff, lines_added, lines_removed) = unified_diff(&original, &updated, path);
+        Ok(EditPlan {
+            path: path.to_string(),
+            original,
+            updated,
+            diff,
+            lines_added,
+            lines_removed,
+        })
+    }
+
+    /// Back up the current file and write the planned content
+    ///
+    /// Re-checks the file first, so a change between `plan` and `apply` is a conflict too.
+    pub fn apply(&self, plan: &EditPlan) -> Result<EditOutcome> {
+        let current = FileService::read_file(&plan.path)?;
+        check_unchanged(&current, Some(&content_hash(&plan.original)))?;
+
+        let backup_path = self.backup(&plan.path, &current)?;
+        FileService::write_file(&plan.path, &plan.updated)?;
+        log::info!(
+            "Edited {} (+{} -{}), backup at {:?}",
+            plan.path,
+            plan.lines_added,
+            plan.lines_removed,
+            backup_path
+        );
+
+        Ok(EditOutcome {
+            path: plan.path.clone(),
+            backup_path: backup_path.to_string_lossy().to_string(),
+            sha256: content_hash(&plan.updated),
+        })
+    }
+
+    fn backup(&self, path: &str, content: &str) -> Result<PathBuf> {
+        fs::create_dir_all(&self.backup_dir)?;
+        let file_name = Path::new(path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "file".to_string());
+        let backup_path = self.backup_dir.join(format!(
+            "{}-{}.bak",
+            Utc::now().format("%Y%m%d-%H%M%S%.3f"),
+            file_name
+        ));
+        fs::write(&backup_path, content)?;
+
+        if let Err(e) = self.prune_backups() {
+            log::warn!("Failed to prune edit backups: {}", e);
+        }
+        Ok(backup_path)
+    }
+
+    /// Names start with a timestamp, so name order is age order
+    fn prune_backups(&self) -> Result<()> {
+        let mut backups: Vec<PathBuf> = fs::read_dir(&self.backup_dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "bak"))
+            .collect();
+        if backups.len() <= MAX_BACKUPS {
+            return Ok(());
+        }
+        backups.sort();
+        for old in &backups[..backups.len() - MAX_BACKUPS] {
+            fs::remove_file(old)?;
+        }
+        Ok(())
+    }
+}
+
+/// SHA-256 of file content, hex-encoded
+pub fn content_hash(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+fn check_unchanged(current: &str, expected_sha256: Option<&str>) -> Result<()> {
+    match expected_sha256 {
+        Some(expected) if !expected.trim().eq_ignore_ascii_case(&content_hash(current)) => Err(anyhow!(
+            "File changed since it was read (sha256 no longer matches); read it again and rebuild the edit"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Parse `<<<<<<< SEARCH` / `=======` / `>>>>>>> REPLACE` blocks
+pub fn parse_search_replace_blocks(text: &str) -> Result<Vec<SearchReplaceBlock>> {
+    enum State {
+        Outside,
+        Search(Vec<String>),
+        Replace(Vec<String>, Vec<String>),
+    }
+
+    let mut blocks = Vec::new();
+    let mut state = State::Outside;
+    for line in text.lines() {
+        let marker = line.trim_end();
+        state = match state {
+            State::Outside if marker.starts_with("<<<<<<<") && marker.ends_with("SEARCH") => {
+                State::Search(Vec::new())
+            }
+            State::Outside => State::Outside,
+            State::Search(search) if marker == "=======" => State::Replace(search, Vec::new()),
+            State::Search(mut search) => {
+                search.push(line.to_string());
+                State::Search(search)
+            }
+            State::Replace(search, replace) if marker.starts_with(">>>>>>>") && marker.ends_with("REPLACE") => {
+                blocks.push(SearchReplaceBlock {
+                    search: join_block(&search),
+                    replace: join_block(&replace),
+                });
+                State::Outside
+            }
+            State::Replace(search, mut replace) => {
+                replace.push(line.to_string());
+                State::Replace(search, replace)
+            }
+        };
+    }
+
+    if !matches!(state, State::Outside) {
+        return Err(anyhow!("Unterminated SEARCH/REPLACE block"));
+    }
+    if blocks.is_empty() {
+        return Err(anyhow!("No SEARCH/REPLACE blocks found"));
+    }
+    Ok(blocks)
+}
+
+fn join_block(lines: &[String]) -> String {
+    if lines.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", lines.join("\n"))
+    }
+}
+
+/// Apply search/replace blocks in order
+///
+/// Each search text must match exactly one place; trailing whitespace
+/// differences are tolerated when there is no exact match.
+pub fn apply_search_replace(original: &str, blocks: &[SearchReplaceBlock]) -> Result<String> {
+    let eol = line_ending(original);
+    let mut content = original.to_string();
+
+    for (index, block) in blocks.iter().enumerate() {
+        let number = index + 1;
+        let search = block.search.replace("\r\n", "\n").replace('\n', eol);
+        let replace = block.replace.replace("\r\n", "\n").replace('\n', eol);
+
+        if search.is_empty() {
+            if content.is_empty() {
+                content = replace;
+                continue;
+            }
+            return Err(anyhow!("Edit {}: empty search text only works on an empty file", number));
+        }
+
+        match content.matches(&search).count() {
+            1 => {
+                content = content.replacen(&search, &replace, 1);
+                continue;
+            }
+            0 => {}
+            count => {
+                return Err(anyhow!(
+                    "Edit {}: search text matches {} places; include more surrounding lines",
+                    number,
+                    count
+                ))
+            }
+        }
+
+        // Same lines up to trailing whitespace
+        let lines = split_lines(&content);
+        let wanted = split_lines(&search);
+        let positions = find_all(&lines.lines, &wanted.lines);
+        match positions.as_slice() {
+            [position] => {
+                let mut result = lines.lines[..*position].to_vec();
+                result.extend(split_lines(&replace).lines);
+                result.extend_from_slice(&lines.lines[position + wanted.lines.len()..]);
+                content = lines.join_with(result);
+            }
+            [] => return Err(anyhow!("Edit {}: search text not found in the file", number)),
+            _ => {
+                return Err(anyhow!(
+                    "Edit {}: search text matches {} places; include more surrounding lines",
+                    number,
+                    positions.len()
+                ))
+            }
+        }
+    }
+    Ok(content)
+}
+
+/// Apply a unified diff
+///
+/// Hunks are located by their context and removed lines, nearest to the
+/// line number in the header, so slightly wrong numbers still apply.
+pub fn apply_unified_diff(original: &str, diff: &str) -> Result<String> {
+    let hunks = parse_hunks(diff)?;
+    let lines = split_lines(original);
+
+    let mut result: Vec<String> = Vec::with_capacity(lines.lines.len());
+    let mut cursor = 0;
+    let mut offset: isize = 0;
+
+    for (index, hunk) in hunks.iter().enumerate() {
+        let expected = ((hunk.old_start.saturating_sub(1)) as isize + offset).max(0) as usize;
+        let position = if hunk.old_lines.is_empty() {
+            // Pure insertion: trust the header line number
+            (hunk.old_start.min(lines.lines.len())).max(cursor)
+        } else {
+            nearest_match(&lines.lines, &hunk.old_lines, expected, cursor).ok_or_else(|| {
+                anyhow!(
+                    "Hunk {} (@@ -{} @@) does not match the file; first expected line: {:?}",
+                    index + 1,
+                    hunk.old_start,
+                    hunk.old_lines[0]
+                )
+            })?
+        };
+
+        result.extend_from_slice(&lines.lines[cursor..position]);
+        result.extend(hunk.new_lines.iter().cloned());
+        cursor = position + hunk.old_lines.len();
+        offset += hunk.new_lines.len() as isize - hunk.old_lines.len() as isize;
+    }
+    result.extend_from_slice(&lines.lines[cursor..]);
+
+    Ok(lines.join_with(result))
+}
+
+struct Hunk {
+    old_start: usize,
+    /// Context and removed lines
+    old_lines: Vec<String>,
+    /// Context and added lines
+    new_lines: Vec<String>,
+}
+
+fn parse_hunks(diff: &str) -> Result<Vec<Hunk>> {
+    let mut hunks: Vec<Hunk> = Vec::new();
+    for line in diff.lines() {
+        if let Some(header) = line.strip_prefix("@@") {
+            let old_start = header
+                .trim_start()
+                .strip_prefix('-')
+                .and_then(|rest| rest.split([',', ' ']).next())
+                .and_then(|n| n.parse::<usize>().ok())
+                .ok_or_else(|| anyhow!("Invalid hunk header: {}", line))?;
+            hunks.push(Hunk {
+                old_start,
+                old_lines: Vec::new(),
+                new_lines: Vec::new(),
+            });
+            continue;
+        }
+
+        // File headers before the first hunk
+        let Some(hunk) = hunks.last_mut() else {
+            continue;
+        };
+        if line.starts_with("--- ") || line.starts_with("+++ ") {
+            continue;
+        }
+        match line.chars().next() {
+            Some('+') => hunk.new_lines.push(line[1..].to_string()),
+            Some('-') => hunk.old_lines.push(line[1..].to_string()),
+            Some(' ') => {
+                hunk.old_lines.push(line[1..].to_string());
+                hunk.new_lines.push(line[1..].to_string());
+            }
+            Some('\\') => {}  // "\ No newline at end of file"
+            // Models often drop the single space of blank context lines
+            None => {
+                hunk.old_lines.push(String::new());
+                hunk.new_lines.push(String::new());
+            }
+            Some(_) => return Err(anyhow!("Unexpected line in hunk: {:?}", line)),
+        }
+    }
+
+    if hunks.is_empty() {
+        return Err(anyhow!("No hunks (@@ ... @@) found in diff"));
+    }
+    Ok(hunks)
+}
+
+/// Start of the match of `needle` (at or after `from`) closest to `expected`
+fn nearest_match(lines: &[String], needle: &[String], expected: usize, from: usize) -> Option<usize> {
+    find_all(lines, needle)
+        .into_iter()
+        .filter(|&position| position >= from)
+        .min_by_key(|&position| position.abs_diff(expected))
+}
+
+/// Every start index where `needle` matches, ignoring trailing whitespace
+fn find_all(lines: &[String], needle: &[String]) -> Vec<usize> {
+    if needle.is_empty() || needle.len() > lines.len() {
+        return Vec::new();
+    }
+    (0..=lines.len() - needle.len())
+        .filter(|&start| {
+            needle
+                .iter()
+                .zip(&lines[start..])
+                .all(|(a, b)| a.trim_end() == b.trim_end())
+        })
+        .collect()
+}
+
+/// Lines without terminators, plus what is needed to rebuild the text
+struct Lines {
+    lines: Vec<String>,
+    eol: &'static str,
+    trailing_newline: bool,
+}
+
+impl Lines {
+    fn join_with(&self, lines: Vec<String>) -> String {
+        if lines.is_empty() {
+            return String::new();
+        }
+        let mut text = lines.join(self.eol);
+        if self.trailing_newline {
+            text.push_str(self.eol);
+        }
+        text
+    }
+}
+
+fn split_lines(text: &str) -> Lines {
+    Lines {
+        lines: text.lines().map(str::to_string).collect(),
+        eol: line_ending(text),
+        // New content gets a final newline
+        trailing_newline: text.is_empty() || text.ends_with('\n'),
+    }
+}
+
+fn line_ending(text: &str) -> &'static str {
+    if text.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum DiffOp {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Unified diff between two texts, with added and removed line counts
+pub fn unified_diff(old: &str, new: &str, path: &str) -> (String, usize, usize) {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let added = ops.iter().filter(|(op, _, _)| *op == DiffOp::Insert).count();
+    let removed = ops.iter().filter(|(op, _, _)| *op == DiffOp::Delete).count();
+
+    let mut out = format!("--- a/{}\n+++ b/{}\n", path, path);
+    let changes: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _, _))| *op != DiffOp::Equal)
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut index = 0;
+    while index < changes.len() {
+        // Changes closer than twice the context share a hunk
+        let start = changes[index].saturating_sub(PREVIEW_CONTEXT);
+        let mut last = changes[index];
+        while index + 1 < changes.len() && changes[index + 1] - last <= PREVIEW_CONTEXT * 2 {
+            index += 1;
+            last = changes[index];
+        }
+        let end = (last + PREVIEW_CONTEXT + 1).min(ops.len());
+        index += 1;
+
+        let hunk = &ops[start..end];
+        let (old_start, new_start) = (hunk[0].1, hunk[0].2);
+        let old_count = hunk.iter().filter(|(op, _, _)| *op != DiffOp::Insert).count();
+        let new_count = hunk.iter().filter(|(op, _, _)| *op != DiffOp::Delete).count();
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            if old_count == 0 { old_start } else { old_start + 1 },
+            old_count,
+            if new_count == 0 { new_start } else { new_start + 1 },
+            new_count
+        ));
+        for (op, old_index, new_index) in hunk {
+            let (prefix, line) = match op {
+                DiffOp::Equal => (' ', old_lines[*old_index]),
+                DiffOp::Delete => ('-', old_lines[*old_index]),
+                DiffOp::Insert => ('+', new_lines[*new_index]),
+            };
+            out.push(prefix);
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+
+    (out, added, removed)
+}
+
+/// Line-level edit script as (op, old index, new index)
+///
+/// Indices point at the line the op refers to; for inserts/deletes the other
+/// index is where the line would sit.
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<(DiffOp, usize, usize)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<(DiffOp, usize, usize)> = (0..prefix).map(|i| (DiffOp::Equal, i, i)).collect();
+
+    if old_mid.len().saturating_mul(new_mid.len()) > MAX_DIFF_CELLS {
+        ops.extend((0..old_mid.len()).map(|i| (DiffOp::Delete, prefix + i, prefix)));
+        ops.extend((0..new_mid.len()).map(|j| (DiffOp::Insert, prefix + old_mid.len(), prefix + j)));
+    } else {
+        // Longest common subsequence over the changed middle
+        let (n, m) = (old_mid.len(), new_mid.len());
+        let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if old_mid[i] == new_mid[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            if i < n && j < m && old_mid[i] == new_mid[j] {
+                ops.push((DiffOp::Equal, prefix + i, prefix + j));
+                i += 1;
+                j += 1;
+            } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+                // Removals before additions, as in `diff -u`
+                ops.push((DiffOp::Delete, prefix + i, prefix + j));
+                i += 1;
+            } else {
+                ops.push((DiffOp::Insert, prefix + i, prefix + j));
+                j += 1;
+            }
+        }
+    }
+
+    let (old_end, new_end) = (old.len() - suffix, new.len() - suffix);
+    ops.extend((0..suffix).map(|k| (DiffOp::Equal, old_end + k, new_end + k)));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "fn main() {\n    let x = 1;\n    println!(\"{}\", x);\n}\n\nfn helper() {\n    todo!()\n}\n";
+
+    #[test]
+    fn test_unified_diff_applies_with_wrong_line_numbers() {
+        let diff = "--- a/main.rs\n+++ b/main.rs\n@@ -40,3 +40,3 @@\n fn helper() {\n-    todo!()\n+    42\n }\n";
+        let updated = apply_unified_diff(SOURCE, diff).unwrap();
+        assert!(updated.contains("    42\n"));
+        assert!(!updated.contains("todo!"));
+
+        let stale = "@@ -1,2 +1,2 @@\n fn main() {\n-    let x = 2;\n+    let x = 3;\n";
+        let err = apply_unified_diff(SOURCE, stale).unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+    }
+
+    #[test]
+    fn test_preview_round_trips() {
+        let updated = SOURCE.replace("let x = 1;", "let x = 2;\n    let y = x * 2;").replace("todo!()", "x");
+        let (diff, added, removed) = unified_diff(SOURCE, &updated, "main.rs");
+        assert_eq!((added, removed), (3, 2));
+        assert!(diff.starts_with("--- a/main.rs\n+++ b/main.rs\n@@ -1,"));
+        assert_eq!(apply_unified_diff(SOURCE, &diff).unwrap(), updated);
+    }
+
+    #[test]
+    fn test_search_replace_blocks() {
+        let text = "<<<<<<< SEARCH\n    let x = 1;\n=======\n    let x = 10;\n>>>>>>> REPLACE\n";
+        let blocks = parse_search_replace_blocks(text).unwrap();
+        assert_eq!(blocks[0].search, "    let x = 1;\n");
+        let updated = apply_search_replace(SOURCE, &blocks).unwrap();
+        assert!(updated.contains("let x = 10;"));
+
+        let ambiguous = vec![SearchReplaceBlock {
+            search: "}\n".to_string(),
+            replace: "};\n".to_string(),
+        }];
+        let err = apply_search_replace(SOURCE, &ambiguous).unwrap_err();
+        assert!(err.to_string().contains("matches 2 places"));
+    }
+
+    #[test]
+    fn test_conflict_detection_and_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main.rs");
+        fs::write(&path, SOURCE).unwrap();
+        let path = path.to_string_lossy().to_string();
+        let service = FileEditService::new(dir.path().join("backups"));
+
+        let request = EditRequest::SearchReplace(vec![SearchReplaceBlock {
+            search: "todo!()".to_string(),
+            replace: "0".to_string(),
+        }]);
+        let read_hash = content_hash(SOURCE);
+        let plan = service.plan(&path, &request, Some(&read_hash)).unwrap();
+        assert_eq!((plan.lines_added, plan.lines_removed), (1, 1));
+
+        // Someone else edits the file before the plan is applied
+        fs::write(&path, SOURCE.replace("let x = 1;", "let x = 5;")).unwrap();
+        assert!(service.apply(&plan).unwrap_err().to_string().contains("changed since it was read"));
+        assert!(service.plan(&path, &request, Some(&read_hash)).is_err());
+
+        let plan = service.plan(&path, &request, None).unwrap();
+        let outcome = service.apply(&plan).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains("    0\n"));
+        assert!(fs::read_to_string(&outcome.backup_path).unwrap().contains("todo!()"));
+        assert_eq!(outcome.sha256, content_hash(&plan.updated));
+    }
+}
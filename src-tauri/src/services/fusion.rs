@@ -0,0 +1,567 @@
+//! Hybrid Search Fusion (v3.9.0)
+//!
+//! Strategies for combining the BM25 and semantic rankings of hybrid search.
+//!
+//! Features:
+//! - Reciprocal rank fusion (rank positions only)
+//! - Weighted linear fusion of calibrated scores (min-max or z-score normalization)
+//! - Learned fusion: logistic regression over score / rank features, trained on
+//!   the results users clicked
+//! - Per-query-type weight profiles (code, prose, Korean)
+
+use crate::services::bm25::is_hangul;
+use anyhow::{anyhow, Result};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// user_preferences key holding the fusion configuration
+pub const FUSION_CONFIG_KEY: &str = "hybrid_fusion_config";
+
+/// user_preferences key holding the trained fusion model
+pub const FUSION_MODEL_KEY: &str = "hybrid_fusion_model";
+
+/// Impressions needed (with at least one click and one skip) to train
+pub const MIN_TRAINING_SAMPLES: usize = 20;
+
+/// Impressions kept for training; older ones are pruned
+const MAX_IMPRESSIONS: i64 = 10_000;
+
+/// Fusion weights for combining BM25 and semantic search
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FusionWeights {
+    pub bm25_weight: f32,
+    pub semantic_weight: f32,
+}
+
+impl Default for FusionWeights {
+    fn default() -> Self {
+        FusionWeights {
+            bm25_weight: 0.5,
+            semantic_weight: 0.5,
+        }
+    }
+}
+
+impl FusionWeights {
+    pub fn validate(&self) -> Result<()> {
+        if !(0.0..=1.0).contains(&self.bm25_weight) {
+            return Err(anyhow!("bm25_weight must be between 0.0 and 1.0"));
+        }
+        if !(0.0..=1.0).contains(&self.semantic_weight) {
+            return Err(anyhow!("semantic_weight must be between 0.0 and 1.0"));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FusionStrategy {
+    /// Σ weight / (k + rank)
+    #[default]
+    Rrf,
+    /// Σ weight × normalized score
+    Linear,
+    /// Trained on clicks; RRF until a model has been trained
+    Learned,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreNormalization {
+    /// (s - min) / (max - min) per result list
+    #[default]
+    MinMax,
+    /// sigmoid((s - mean) / std) per result list
+    ZScore,
+}
+
+/// What a query looks like, for weight profiles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryType {
+    /// Identifiers, paths and syntax: exact terms matter
+    Code,
+    Prose,
+    /// Mostly Hangul
+    Korean,
+}
+
+/// Markers that make a query look like code
+const CODE_MARKERS: &[&str] = &[
+    "::", "()", "=>", "->", "{", "}", "`", "==", "!=", ";", "fn ", "def ", "#include", "import ",
+];
+
+/// snake_case, camelCase or a file name like `main.rs`
+fn looks_like_identifier(word: &str) -> bool {
+    let word = word.trim_matches(|c: char| !c.is_alphanumeric() && c != '_');
+    let snake = word.contains('_') && word.split('_').filter(|part| !part.is_empty()).count() >= 2;
+    let camel = word
+        .chars()
+        .zip(word.chars().skip(1))
+        .any(|(a, b)| a.is_lowercase() && b.is_uppercase());
+    let file = match word.rsplit_once('.') {
+        Some((stem, ext)) => {
+            !stem.is_empty() && (1..=4).contains(&ext.len()) && ext.chars().all(|c| c.is_ascii_lowercase())
+        }
+        None => false,
+    };
+    snake || camel || file
+}
+
+impl QueryType {
+    pub fn classify(query: &str) -> Self {
+        let letters = query.chars().filter(|c| c.is_alphabetic()).count();
+        let hangul = query.chars().filter(|c| is_hangul(*c)).count();
+        if letters > 0 && hangul * 10 >= letters * 3 {
+            return QueryType::Korean;
+        }
+        if CODE_MARKERS.iter().any(|marker| query.contains(marker))
+            || query.split_whitespace().any(looks_like_identifier)
+        {
+            return QueryType::Code;
+        }
+        QueryType::Prose
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            QueryType::Code => "code",
+            QueryType::Prose => "prose",
+            QueryType::Korean => "korean",
+        }
+    }
+}
+
+/// Weights per query type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeightProfiles {
+    pub code: FusionWeights,
+    pub prose: FusionWeights,
+    pub korean: FusionWeights,
+}
+
+impl Default for WeightProfiles {
+    fn default() -> Self {
+        Self {
+            code: FusionWeights { bm25_weight: 0.7, semantic_weight: 0.3 },
+            prose: FusionWeights { bm25_weight: 0.4, semantic_weight: 0.6 },
+            korean: FusionWeights { bm25_weight: 0.6, semantic_weight: 0.4 },
+        }
+    }
+}
+
+impl WeightProfiles {
+    pub fn get(&self, query_type: QueryType) -> &FusionWeights {
+        match query_type {
+            QueryType::Code => &self.code,
+            QueryType::Prose => &self.prose,
+            QueryType::Korean => &self.korean,
+        }
+    }
+
+    pub fn set(&mut self, query_type: QueryType, weights: FusionWeights) {
+        match query_type {
+            QueryType::Code => self.code = weights,
+            QueryType::Prose => self.prose = weights,
+            QueryType::Korean => self.korean = weights,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FusionConfig {
+    pub strategy: FusionStrategy,
+    /// Used by linear fusion
+    pub normalization: ScoreNormalization,
+    /// Weights when query profiles are off
+    pub weights: FusionWeights,
+    /// Pick weights by the type of the query
+    pub query_profiles: bool,
+    pub profiles: WeightProfiles,
+}
+
+impl FusionConfig {
+    /// Weights applied to `query_type`
+    pub fn weights_for(&self, query_type: QueryType) -> &FusionWeights {
+        if self.query_profiles {
+            self.profiles.get(query_type)
+        } else {
+            &self.weights
+        }
+    }
+
+    /// Persisted configuration of the profile, or the default
+    pub fn load(conn: &Connection) -> Result<Self> {
+        Ok(load_preference(conn, FUSION_CONFIG_KEY)?.unwrap_or_default())
+    }
+
+    pub fn save(&self, conn: &Connection) -> Result<()> {
+        save_preference(conn, FUSION_CONFIG_KEY, self)
+    }
+}
+
+/// Per-result inputs of fusion, also logged for training
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct FusionFeatures {
+    /// Normalized BM25 score (0 when BM25 didn't return the result)
+    pub bm25: f32,
+    /// Normalized semantic similarity
+    pub semantic: f32,
+    /// (k + 1) / (k + rank): 1.0 for the first BM25 result
+    pub bm25_rank: f32,
+    pub semantic_rank: f32,
+}
+
+impl FusionFeatures {
+    fn values(&self) -> [f32; 4] {
+        [self.bm25, self.semantic, self.bm25_rank, self.semantic_rank]
+    }
+}
+
+/// A result with its fused score
+#[derive(Debug, Clone)]
+pub struct FusedHit {
+    pub id: String,
+    pub score: f32,
+    pub features: FusionFeatures,
+}
+
+/// Scores of one list mapped into 0..1
+pub fn normalize(scores: &[f32], normalization: ScoreNormalization) -> Vec<f32> {
+    if scores.is_empty() {
+        return Vec::new();
+    }
+    match normalization {
+        ScoreNormalization::MinMax => {
+            let min = scores.iter().cloned().fold(f32::INFINITY, f32::min);
+            let max = scores.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+            if (max - min).abs() < f32::EPSILON {
+                return vec![1.0; scores.len()];
+            }
+            scores.iter().map(|s| (s - min) / (max - min)).collect()
+        }
+        ScoreNormalization::ZScore => {
+            let n = scores.len() as f32;
+            let mean = scores.iter().sum::<f32>() / n;
+            let std = (scores.iter().map(|s| (s - mean).powi(2)).sum::<f32>() / n).sqrt();
+            if std < f32::EPSILON {
+                return vec![0.5; scores.len()];
+            }
+            scores.iter().map(|s| sigmoid((s - mean) / std)).collect()
+        }
+    }
+}
+
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// Combine BM25 and semantic results (each `(id, score)`, best first)
+///
+/// A `Learned` strategy without a model falls back to RRF.
+pub fn fuse(
+    bm25: &[(String, f32)],
+    semantic: &[(String, f32)],
+    config: &FusionConfig,
+    weights: &FusionWeights,
+    rrf_k: f32,
+    model: Option<&LearnedFusion>,
+) -> Vec<FusedHit> {
+    let mut features: Vec<(String, FusionFeatures)> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut entry = |id: &str| -> usize {
+        *positions.entry(id.to_string()).or_insert_with(|| {
+            features.push((id.to_string(), FusionFeatures::default()));
+            features.len() - 1
+        })
+    };
+
+    let bm25_norm = normalize(&bm25.iter().map(|(_, s)| *s).collect::<Vec<_>>(), config.normalization);
+    let bm25_slots: Vec<usize> = bm25.iter().map(|(id, _)| entry(id)).collect();
+    let semantic_norm = normalize(&semantic.iter().map(|(_, s)| *s).collect::<Vec<_>>(), config.normalization);
+    let semantic_slots: Vec<usize> = semantic.iter().map(|(id, _)| entry(id)).collect();
+
+    let rank_feature = |rank: usize| (rrf_k + 1.0) / (rrf_k + rank as f32 + 1.0);
+    for (rank, slot) in bm25_slots.into_iter().enumerate() {
+        features[slot].1.bm25 = bm25_norm[rank];
+        features[slot].1.bm25_rank = rank_feature(rank);
+    }
+    for (rank, slot) in semantic_slots.into_iter().enumerate() {
+        features[slot].1.semantic = semantic_norm[rank];
+        features[slot].1.semantic_rank = rank_feature(rank);
+    }
+
+    let strategy = match (config.strategy, model) {
+        (FusionStrategy::Learned, None) => FusionStrategy::Rrf,
+        (strategy, _) => strategy,
+    };
+    let mut hits: Vec<FusedHit> = features
+        .into_iter()
+        .map(|(id, f)| {
+            let score = match (strategy, model) {
+                (FusionStrategy::Learned, Some(model)) => model.predict(&f),
+                (FusionStrategy::Linear, _) => weights.bm25_weight * f.bm25 + weights.semantic_weight * f.semantic,
+                // rank_feature / (k + 1) = 1 / (k + rank)
+                _ => (weights.bm25_weight * f.bm25_rank + weights.semantic_weight * f.semantic_rank) / (rrf_k + 1.0),
+            };
+            FusedHit { id, score, features: f }
+        })
+        .collect();
+
+    hits.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    hits
+}
+
+/// Logistic regression over `FusionFeatures`: P(click | features)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LearnedFusion {
+    pub bias: f32,
+    pub weights: [f32; 4],
+    /// Impressions the model was trained on
+    pub samples: usize,
+    pub trained_at: i64, // Unix millis
+}
+
+impl LearnedFusion {
+    pub fn predict(&self, features: &FusionFeatures) -> f32 {
+        let z: f32 = self.bias + self.weights.iter().zip(features.values()).map(|(w, x)| w * x).sum::<f32>();
+        sigmoid(z)
+    }
+
+    /// Fit on `(features, clicked)` impressions with batch gradient descent
+    pub fn train(samples: &[(FusionFeatures, bool)]) -> Result<Self> {
+        let clicks = samples.iter().filter(|(_, clicked)| *clicked).count();
+        if samples.len() < MIN_TRAINING_SAMPLES || clicks == 0 || clicks == samples.len() {
+            return Err(anyhow!(
+                "Need at least {} impressions with both clicked and skipped results (have {}, {} clicked)",
+                MIN_TRAINING_SAMPLES,
+                samples.len(),
+                clicks
+            ));
+        }
+
+        // Clicks are rare; weight them so both classes count equally
+        let positive_weight = (samples.len() - clicks) as f32 / clicks as f32;
+        let (learning_rate, l2, epochs) = (0.5, 1e-3, 500);
+        let mut model = Self { bias: 0.0, weights: [0.0; 4], samples: samples.len(), trained_at: 0 };
+        for _ in 0..epochs {
+            let mut grad_bias = 0.0;
+            let mut grad = [0.0f32; 4];
+            let mut total_weight = 0.0;
+            for (features, clicked) in samples {
+                let (label, weight) = if *clicked { (1.0, positive_weight) } else { (0.0, 1.0) };
+                let error = (model.predict(features) - label) * weight;
+                grad_bias += error;
+                for (g, x) in grad.iter_mut().zip(features.values()) {
+                    *g += error * x;
+                }
+                total_weight += weight;
+            }
+            model.bias -= learning_rate * grad_bias / total_weight;
+            for (w, g) in model.weights.iter_mut().zip(grad) {
+                *w -= learning_rate * (g / total_weight + l2 * *w);
+            }
+        }
+        model.trained_at = chrono::Utc::now().timestamp_millis();
+        Ok(model)
+    }
+
+    pub fn load(conn: &Connection) -> Result<Option<Self>> {
+        load_preference(conn, FUSION_MODEL_KEY)
+    }
+
+    pub fn save(&self, conn: &Connection) -> Result<()> {
+        save_preference(conn, FUSION_MODEL_KEY, self)
+    }
+}
+
+fn load_preference<T: serde::de::DeserializeOwned>(conn: &Connection, key: &str) -> Result<Option<T>> {
+    let json: Option<String> = conn
+        .query_row("SELECT value FROM user_preferences WHERE key = ?1", [key], |row| row.get(0))
+        .ok();
+    match json {
+        Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+        None => Ok(None),
+    }
+}
+
+fn save_preference<T: Serialize>(conn: &Connection, key: &str, value: &T) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+        params![key, serde_json::to_string(value)?, chrono::Utc::now().timestamp_millis()],
+    )?;
+    Ok(())
+}
+
+// ============================================================================
+// Click feedback
+// ============================================================================
+
+pub fn init_tables(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS hybrid_search_impressions (
+            search_id TEXT NOT NULL,
+            episode_id TEXT NOT NULL,
+            query_type TEXT NOT NULL,
+            bm25 REAL NOT NULL,
+            semantic REAL NOT NULL,
+            bm25_rank REAL NOT NULL,
+            semantic_rank REAL NOT NULL,
+            clicked INTEGER NOT NULL DEFAULT 0,
+            created_at INTEGER NOT NULL,
+            PRIMARY KEY (search_id, episode_id)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Log the results shown for one search; returns the id clicks refer to
+pub fn log_impressions(conn: &Connection, query_type: QueryType, hits: &[(String, FusionFeatures)]) -> Result<String> {
+    init_tables(conn)?;
+    let search_id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().timestamp_millis();
+    let tx = conn.unchecked_transaction()?;
+    for (episode_id, f) in hits {
+        tx.execute(
+            "INSERT OR IGNORE INTO hybrid_search_impressions
+                (search_id, episode_id, query_type, bm25, semantic, bm25_rank, semantic_rank, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![search_id, episode_id, query_type.as_str(), f.bm25, f.semantic, f.bm25_rank, f.semantic_rank, now],
+        )?;
+    }
+    tx.execute(
+        "DELETE FROM hybrid_search_impressions WHERE rowid IN (
+            SELECT rowid FROM hybrid_search_impressions ORDER BY created_at DESC LIMIT -1 OFFSET ?1
+        )",
+        [MAX_IMPRESSIONS],
+    )?;
+    tx.commit()?;
+    Ok(search_id)
+}
+
+/// Mark a shown result as opened / found useful
+pub fn record_click(conn: &Connection, search_id: &str, episode_id: &str) -> Result<()> {
+    init_tables(conn)?;
+    let updated = conn.execute(
+        "UPDATE hybrid_search_impressions SET clicked = 1 WHERE search_id = ?1 AND episode_id = ?2",
+        params![search_id, episode_id],
+    )?;
+    if updated == 0 {
+        return Err(anyhow!("{} was not shown for search {}", episode_id, search_id));
+    }
+    Ok(())
+}
+
+/// Logged impressions of searches that got at least one click
+///
+/// Searches nobody clicked say nothing about which result was better.
+pub fn training_samples(conn: &Connection) -> Result<Vec<(FusionFeatures, bool)>> {
+    init_tables(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT bm25, semantic, bm25_rank, semantic_rank, clicked
+         FROM hybrid_search_impressions
+         WHERE search_id IN (SELECT search_id FROM hybrid_search_impressions WHERE clicked = 1)",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            FusionFeatures {
+                bm25: row.get(0)?,
+                semantic: row.get(1)?,
+                bm25_rank: row.get(2)?,
+                semantic_rank: row.get(3)?,
+            },
+            row.get::<_, i64>(4)? != 0,
+        ))
+    })?;
+    Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+
+    fn ids(hits: &[FusedHit]) -> Vec<&str> {
+        hits.iter().map(|hit| hit.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_fusion_strategies() {
+        let bm25 = vec![("a".to_string(), 12.0), ("b".to_string(), 11.5), ("c".to_string(), 2.0)];
+        let semantic = vec![("c".to_string(), 0.9), ("b".to_string(), 0.3)];
+        let weights = FusionWeights::default();
+
+        let rrf = fuse(&bm25, &semantic, &FusionConfig::default(), &weights, 60.0, None);
+        assert_eq!(ids(&rrf), vec!["c", "b", "a"]);
+        assert!((rrf[2].score - 0.5 / 61.0).abs() < 1e-6);
+
+        // Scores, not just ranks: "a" and "b" are close in BM25, "c" is far behind
+        let linear = FusionConfig { strategy: FusionStrategy::Linear, ..FusionConfig::default() };
+        let lexical = FusionWeights { bm25_weight: 0.6, semantic_weight: 0.4 };
+        let hits = fuse(&bm25, &semantic, &linear, &lexical, 60.0, None);
+        assert_eq!(ids(&hits), vec!["a", "b", "c"]);
+        assert_eq!(hits[2].features.bm25, 0.0);
+        assert_eq!(hits[2].features.semantic, 1.0);
+
+        let z = normalize(&[1.0, 2.0, 3.0], ScoreNormalization::ZScore);
+        assert!(z[0] < 0.5 && (z[1] - 0.5).abs() < 1e-6 && z[2] > 0.5);
+        assert_eq!(normalize(&[4.0, 4.0], ScoreNormalization::MinMax), vec![1.0, 1.0]);
+
+        // Learned without a model is RRF
+        let learned = FusionConfig { strategy: FusionStrategy::Learned, ..FusionConfig::default() };
+        assert_eq!(ids(&fuse(&bm25, &semantic, &learned, &weights, 60.0, None)), ids(&rrf));
+    }
+
+    #[test]
+    fn test_query_profiles() {
+        assert_eq!(QueryType::classify("서울 맛집 추천해줘"), QueryType::Korean);
+        assert_eq!(QueryType::classify("where is build_from_database defined"), QueryType::Code);
+        assert_eq!(QueryType::classify("error in main.rs"), QueryType::Code);
+        assert_eq!(QueryType::classify("Vec::new() panics"), QueryType::Code);
+        assert_eq!(QueryType::classify("What did we talk about yesterday?"), QueryType::Prose);
+
+        let mut config = FusionConfig::default();
+        assert_eq!(config.weights_for(QueryType::Code), &FusionWeights::default());
+        config.query_profiles = true;
+        assert_eq!(config.weights_for(QueryType::Code).bm25_weight, 0.7);
+        config.profiles.set(QueryType::Korean, FusionWeights { bm25_weight: 1.0, semantic_weight: 0.0 });
+        assert_eq!(config.weights_for(QueryType::Korean).bm25_weight, 1.0);
+    }
+
+    #[test]
+    fn test_learned_fusion() {
+        let db = Database::new_test_db().unwrap();
+        let conn = db.conn();
+        // Users click results the semantic retriever ranked high
+        for i in 0..10 {
+            let hits: Vec<(String, FusionFeatures)> = (0..3)
+                .map(|j| {
+                    let semantic = if j == 0 { 1.0 } else { 0.1 * j as f32 };
+                    (format!("e{}", j), FusionFeatures { bm25: 1.0 - semantic, semantic, bm25_rank: 0.5, semantic_rank: semantic })
+                })
+                .collect();
+            let search_id = log_impressions(conn, QueryType::Prose, &hits).unwrap();
+            if i < 9 {
+                record_click(conn, &search_id, "e0").unwrap();
+            }
+        }
+        assert!(record_click(conn, "missing", "e0").is_err());
+
+        let samples = training_samples(conn).unwrap();
+        assert_eq!(samples.len(), 27);
+        assert!(LearnedFusion::train(&samples[..5]).is_err());
+
+        let model = LearnedFusion::train(&samples).unwrap();
+        let semantic_hit = FusionFeatures { bm25: 0.0, semantic: 1.0, bm25_rank: 0.5, semantic_rank: 1.0 };
+        let lexical_hit = FusionFeatures { bm25: 1.0, semantic: 0.0, bm25_rank: 0.5, semantic_rank: 0.0 };
+        assert!(model.predict(&semantic_hit) > model.predict(&lexical_hit));
+
+        model.save(conn).unwrap();
+        assert_eq!(LearnedFusion::load(conn).unwrap(), Some(model));
+    }
+}
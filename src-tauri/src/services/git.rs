@@ -305,6 +305,28 @@ impl GitService {
         Ok(commit_id_str)
     }
 
+    /// Commit HEAD points to (None before the first commit)
+    pub fn head_commit_id(repo_path: &str) -> Result<Option<String>> {
+        let repo = Self::open_repo(repo_path)?;
+        let head = match repo.head() {
+            Ok(head) => head,
+            Err(_) => return Ok(None),
+        };
+        let commit_id = head.peel_to_commit()?.id().to_string();
+        Ok(Some(commit_id))
+    }
+
+    /// Move the current branch to `commit_id`, keeping index and working tree (git reset --soft)
+    pub fn reset_soft(repo_path: &str, commit_id: &str) -> Result<()> {
+        info!("Soft reset to {} in: {}", commit_id, repo_path);
+
+        let repo = Self::open_repo(repo_path)?;
+        let oid = git2::Oid::from_str(commit_id)?;
+        let target = repo.find_object(oid, Some(git2::ObjectType::Commit))?;
+        repo.reset(&target, git2::ResetType::Soft, None)?;
+        Ok(())
+    }
+
     /// Push to remote
     pub fn push(repo_path: &str, remote_name: &str, branch_name: &str) -> Result<()> {
         info!("Pushing to remote: {}/{}", remote_name, branch_name);
@@ -9,14 +9,52 @@
 //! - Proactive reminders
 //! - Achievement recognition
 //! - Goal-oriented context retrieval
+//! - Progress inferred from activity: git commits, completed tasks, conversations
+//! - Per-goal evidence log; rejecting an entry takes its progress back
+//!
+//! Inferred progress stops short of 100% so completing a goal stays the user's call.
+//! Inference settings persist in `user_preferences`.
 
 #![allow(dead_code)]  // Phase 5: Goal tracking (Stage 4)
 
 use crate::database::Database;
+use crate::services::git::GitService;
+use crate::services::notification::{AppNotification, NotificationAction, NotificationService, NotificationSource, DEFAULT_SNOOZE_MINUTES};
 use crate::services::ollama;
+use crate::services::task_planner::{TaskPlannerService, TaskStatus};
+use crate::services::workspace::WorkspaceService;
 use anyhow::{anyhow, Result};
+use rusqlite::OptionalExtension;
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+
+const CONFIG_KEY: &str = "goal_inference_config";
+
+/// Inferred progress never goes past this; the last step is the user's
+const AUTO_PROGRESS_CAP: f32 = 95.0;
+
+/// Progress for a fully relevant commit / completed task (scaled by relevance)
+const COMMIT_PROGRESS: f32 = 2.0;
+const TASK_PROGRESS: f32 = 5.0;
+
+/// Upper bound for one conversation's LLM-estimated progress
+const CONVERSATION_PROGRESS_MAX: f32 = 15.0;
+
+/// Commits read per repository per run
+const COMMITS_PER_REPO: usize = 200;
+
+/// User messages analyzed per run
+const CONVERSATION_MESSAGES_PER_RUN: usize = 100;
+
+/// Words that say nothing about what a goal is about
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "from", "into", "onto", "that", "this", "our", "my", "your",
+    "add", "get", "make", "ship", "finish", "build", "complete", "learn", "start", "improve",
+    "feature", "project", "goal", "work", "working", "new", "more", "better", "using", "use",
+    "fix", "update", "some", "all", "out", "about", "how", "what", "when", "will", "wip",
+];
 
 /// Goal status
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -126,19 +164,139 @@ pub struct GoalReminder {
     pub progress: f32,
 }
 
+/// Where a piece of progress evidence came from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum EvidenceSource {
+    Commit,
+    Task,
+    Conversation,
+}
+
+/// Activity that moved a goal forward
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalEvidence {
+    pub id: String,
+    pub goal_id: String,
+    pub source: EvidenceSource,
+    /// Commit hash, task id or conversation id
+    pub reference: String,
+    pub summary: String,
+    /// Progress actually applied (after the cap)
+    pub progress_delta: f32,
+    /// How well the activity matched the goal (0.0-1.0)
+    pub relevance: f32,
+    /// Rejected by the user; its progress was taken back
+    pub rejected: bool,
+    pub created_at: i64,
+}
+
+/// Automatic progress inference settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalInferenceConfig {
+    pub enabled: bool,
+    pub git_commits: bool,
+    pub completed_tasks: bool,
+    pub conversations: bool,
+    /// Repositories checked besides the workspace roots
+    #[serde(default)]
+    pub repositories: Vec<String>,
+    /// Minimum share of a goal's keywords the activity must mention
+    pub min_relevance: f32,
+}
+
+impl Default for GoalInferenceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            git_commits: true,
+            completed_tasks: true,
+            conversations: true,
+            repositories: Vec::new(),
+            min_relevance: 0.6,
+        }
+    }
+}
+
+/// What one inference run looked at and recorded
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InferenceReport {
+    pub commits_checked: usize,
+    pub tasks_checked: usize,
+    pub conversations_checked: usize,
+    pub evidence: Vec<GoalEvidence>,
+}
+
 /// Goal Tracker Service
 pub struct GoalTrackerService {
     db: Arc<Mutex<Database>>,
+    notifications: OnceLock<Arc<NotificationService>>,  // v3.9.0: Reminders and achievements
+    config: RwLock<GoalInferenceConfig>,
+    /// Signal sources for progress inference (v3.9.0)
+    task_planner: OnceLock<Arc<TaskPlannerService>>,
+    workspace: OnceLock<Arc<WorkspaceService>>,
 }
 
 impl GoalTrackerService {
     /// Create new Goal Tracker service
     pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
-        let service = Self { db };
+        let service = Self {
+            db,
+            notifications: OnceLock::new(),
+            config: RwLock::new(GoalInferenceConfig::default()),
+            task_planner: OnceLock::new(),
+            workspace: OnceLock::new(),
+        };
         service.init_database()?;
+        *service.config.write().unwrap_or_else(|e| e.into_inner()) = service.load_config()?;
         Ok(service)
     }
 
+    /// Attach the notification service for reminders and achievements (v3.9.0)
+    pub fn attach_notifications(&self, notifications: Arc<NotificationService>) {
+        let _ = self.notifications.set(notifications);
+    }
+
+    /// Completed tasks count as goal evidence (v3.9.0)
+    pub fn attach_task_planner(&self, task_planner: Arc<TaskPlannerService>) {
+        let _ = self.task_planner.set(task_planner);
+    }
+
+    /// Commits in workspace roots count as goal evidence (v3.9.0)
+    pub fn attach_workspace(&self, workspace: Arc<WorkspaceService>) {
+        let _ = self.workspace.set(workspace);
+    }
+
+    fn load_config(&self) -> Result<GoalInferenceConfig> {
+        let db = self.db.lock().unwrap();
+        let json: Option<String> = db
+            .conn()
+            .query_row("SELECT value FROM user_preferences WHERE key = ?1", [CONFIG_KEY], |row| row.get(0))
+            .optional()?;
+        match json {
+            Some(json) => Ok(serde_json::from_str(&json)?),
+            None => Ok(GoalInferenceConfig::default()),
+        }
+    }
+
+    pub fn get_inference_config(&self) -> GoalInferenceConfig {
+        self.config.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn update_inference_config(&self, mut config: GoalInferenceConfig) -> Result<()> {
+        config.min_relevance = config.min_relevance.clamp(0.1, 1.0);
+        {
+            let db = self.db.lock().unwrap();
+            db.conn().execute(
+                "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+                rusqlite::params![CONFIG_KEY, serde_json::to_string(&config)?, chrono::Utc::now().timestamp()],
+            )?;
+        }
+        *self.config.write().unwrap_or_else(|e| e.into_inner()) = config;
+        log::info!("Goal inference config updated");
+        Ok(())
+    }
+
     /// Initialize database tables
     fn init_database(&self) -> Result<()> {
         let db = self.db.lock().unwrap();
@@ -206,11 +364,33 @@ impl GoalTrackerService {
             [],
         )?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS goal_evidence (
+                id TEXT PRIMARY KEY,
+                goal_id TEXT NOT NULL,
+                source TEXT NOT NULL,
+                reference TEXT NOT NULL,
+                summary TEXT NOT NULL,
+                progress_delta REAL NOT NULL,
+                relevance REAL NOT NULL,
+                rejected INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL,
+                UNIQUE (goal_id, source, reference),
+                FOREIGN KEY (goal_id) REFERENCES goals(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_goals_status ON goals(status)",
             [],
         )?;
 
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_goal_evidence_goal ON goal_evidence(goal_id, created_at DESC)",
+            [],
+        )?;
+
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_milestones_goal ON milestones(goal_id)",
             [],
@@ -482,6 +662,24 @@ impl GoalTrackerService {
             ],
         )?;
 
+        if let Some(notifications) = self.notifications.get() {
+            let notifications = Arc::clone(notifications);
+            let notification = AppNotification::new(
+                NotificationSource::GoalTracker,
+                achievement.title.clone(),
+                achievement.celebration_message.clone(),
+            )
+            .with_action(NotificationAction::OpenChat {
+                prompt: Some("I just completed one of my goals!".to_string()),
+            });
+
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = notifications.notify(notification).await {
+                    log::warn!("Failed to send achievement notification: {}", e);
+                }
+            });
+        }
+
         log::info!("✓ Goal completed: {}", goal_id);
         Ok(())
     }
@@ -543,50 +741,365 @@ impl GoalTrackerService {
         Ok(reminders)
     }
 
+    /// Send check-in notifications for stale goals (v3.9.0)
+    pub async fn send_stale_goal_reminders(&self, days_threshold: i64) -> Result<usize> {
+        let notifications = match self.notifications.get() {
+            Some(notifications) => Arc::clone(notifications),
+            None => return Ok(0),
+        };
+
+        let mut sent = 0;
+        for reminder in self.get_stale_goals(days_threshold)? {
+            let notification = AppNotification::new(
+                NotificationSource::GoalTracker,
+                format!("Goal check-in: {}", reminder.goal_title),
+                reminder.message.clone(),
+            )
+            .with_action(NotificationAction::OpenChat {
+                prompt: Some(format!("Let's check in on my goal \"{}\"", reminder.goal_title)),
+            })
+            .with_action(NotificationAction::Snooze { minutes: DEFAULT_SNOOZE_MINUTES * 48 });
+
+            if notifications.notify(notification).await? {
+                sent += 1;
+            }
+        }
+
+        Ok(sent)
+    }
+
     /// Analyze conversation for goal-related progress
     pub async fn detect_progress_from_conversation(&self, conversation: &str, goal_id: &str) -> Result<Option<f32>> {
         let goal = self.get_goal(goal_id)?;
 
-        let prompt = format!(
-            r#"You are a goal progress analyzer. Analyze this conversation to detect progress toward the goal.
+        let Some((delta, description)) = analyze_conversation(&goal, conversation).await? else {
+            return Ok(None);
+        };
 
-Goal: "{}"
-Description: {}
-Success Criteria: {:?}
+        // Checked on request: each check is its own piece of evidence
+        let reference = format!("manual:{}", uuid::Uuid::new_v4());
+        let evidence = self.record_evidence(goal_id, EvidenceSource::Conversation, &reference, &description, delta, 1.0)?;
+        Ok(evidence.map(|e| e.progress_delta).filter(|delta| *delta > 0.0))
+    }
 
-Conversation:
-{}
+    /// Look for goal progress in activity since `since` (Unix millis): commits in the
+    /// workspace repositories, tasks completed in the planner and the user's messages
+    pub async fn infer_progress(self: &Arc<Self>, since: i64) -> Result<InferenceReport> {
+        let config = self.get_inference_config();
+        if !config.enabled {
+            return Ok(InferenceReport::default());
+        }
 
-Has the user made progress on this goal? Estimate progress change.
+        let this = Arc::clone(self);
+        let activity_config = config.clone();
+        let mut report = tokio::task::spawn_blocking(move || this.infer_from_activity(&activity_config, since / 1000))
+            .await
+            .map_err(|e| anyhow!("Task join error: {}", e))??;
 
-Respond ONLY with valid JSON:
-{{
-  "has_progress": true | false,
-  "progress_delta": 0.0-100.0,
-  "description": "Brief description of progress made"
-}}
+        if config.conversations {
+            let (checked, evidence) = self.infer_from_conversations(&config, since).await?;
+            report.conversations_checked = checked;
+            report.evidence.extend(evidence);
+        }
 
-If no progress detected, set has_progress to false and progress_delta to 0.0"#,
-            goal.title, goal.description, goal.success_criteria, conversation
-        );
+        if !report.evidence.is_empty() {
+            log::info!("✓ Inferred goal progress from {} activities", report.evidence.len());
+        }
+        Ok(report)
+    }
+
+    /// Commits and completed tasks since `since_secs` (blocking: runs git)
+    fn infer_from_activity(&self, config: &GoalInferenceConfig, since_secs: i64) -> Result<InferenceReport> {
+        let goals = self.get_active_goals()?;
+        let mut report = InferenceReport::default();
+        if goals.is_empty() {
+            return Ok(report);
+        }
 
-        let response = ollama::generate_response(&prompt).await
-            .map_err(|e| anyhow!("Failed to detect progress: {}", e))?;
+        if config.git_commits {
+            for repo in self.repositories(config) {
+                let commits = match commits_since(&repo, since_secs) {
+                    Ok(commits) => commits,
+                    Err(e) => {
+                        log::debug!("Skipping {} for goal inference: {}", repo.display(), e);
+                        continue;
+                    }
+                };
+                report.commits_checked += commits.len();
+                for (hash, subject) in commits {
+                    for goal in &goals {
+                        let relevance = relevance(goal, &subject);
+                        if relevance < config.min_relevance {
+                            continue;
+                        }
+                        let summary = format!("Commit {}: {}", &hash[..hash.len().min(8)], subject);
+                        if let Some(evidence) = self.record_evidence(
+                            &goal.id,
+                            EvidenceSource::Commit,
+                            &hash,
+                            &summary,
+                            COMMIT_PROGRESS * relevance,
+                            relevance,
+                        )? {
+                            report.evidence.push(evidence);
+                        }
+                    }
+                }
+            }
+        }
 
-        let analysis: serde_json::Value = serde_json::from_str(response.trim())
-            .map_err(|e| anyhow!("Failed to parse progress analysis: {}", e))?;
+        if config.completed_tasks {
+            if let Some(task_planner) = self.task_planner.get() {
+                let tasks: Vec<_> = task_planner
+                    .get_all_tasks(Some(TaskStatus::Completed))?
+                    .into_iter()
+                    .filter(|task| task.completed_at.is_some_and(|at| at > since_secs))
+                    .collect();
+                report.tasks_checked = tasks.len();
+                for task in tasks {
+                    let text = format!("{} {} {}", task.title, task.description, task.tags.join(" "));
+                    for goal in &goals {
+                        let relevance = relevance(goal, &text);
+                        if relevance < config.min_relevance {
+                            continue;
+                        }
+                        if let Some(evidence) = self.record_evidence(
+                            &goal.id,
+                            EvidenceSource::Task,
+                            &task.id,
+                            &format!("Task completed: {}", task.title),
+                            TASK_PROGRESS * relevance,
+                            relevance,
+                        )? {
+                            report.evidence.push(evidence);
+                        }
+                    }
+                }
+            }
+        }
 
-        if analysis["has_progress"].as_bool().unwrap_or(false) {
-            let delta = analysis["progress_delta"].as_f64().unwrap_or(0.0) as f32;
-            let description = analysis["description"].as_str().unwrap_or("Progress detected");
+        Ok(report)
+    }
 
-            if delta > 0.0 {
-                self.update_progress(goal_id, delta, description)?;
-                return Ok(Some(delta));
+    /// Configured repositories plus the workspace roots that are git repositories
+    fn repositories(&self, config: &GoalInferenceConfig) -> Vec<PathBuf> {
+        let mut roots: Vec<PathBuf> = config.repositories.iter().map(PathBuf::from).collect();
+        if let Some(workspace) = self.workspace.get() {
+            roots.extend(workspace.get_config().roots.iter().map(PathBuf::from));
+            match workspace.current() {
+                Ok(Some(current)) => roots.push(PathBuf::from(current.root)),
+                Ok(None) => {}
+                Err(e) => log::debug!("No active workspace for goal inference: {}", e),
             }
         }
 
-        Ok(None)
+        let mut seen = HashSet::new();
+        roots
+            .into_iter()
+            .filter(|root| root.join(".git").exists())
+            .filter(|root| seen.insert(root.canonicalize().unwrap_or_else(|_| root.clone())))
+            .collect()
+    }
+
+    /// User messages since `since` (Unix millis), per conversation, checked with the LLM
+    /// against the goals they mention
+    async fn infer_from_conversations(
+        &self,
+        config: &GoalInferenceConfig,
+        since: i64,
+    ) -> Result<(usize, Vec<GoalEvidence>)> {
+        let goals = self.get_active_goals()?;
+        if goals.is_empty() {
+            return Ok((0, Vec::new()));
+        }
+
+        // conversation id -> (last message id, user messages)
+        let mut conversations: BTreeMap<String, (String, Vec<String>)> = BTreeMap::new();
+        {
+            let db = self.db.lock().unwrap();
+            let mut stmt = db.conn().prepare(
+                "SELECT id, conversation_id, content FROM messages
+                 WHERE role = 'user' AND timestamp > ?1
+                 ORDER BY timestamp ASC LIMIT ?2",
+            )?;
+            let rows = stmt.query_map(rusqlite::params![since, CONVERSATION_MESSAGES_PER_RUN as i64], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+            })?;
+            for row in rows {
+                let (id, conversation_id, content) = row?;
+                let entry = conversations.entry(conversation_id).or_default();
+                entry.0 = id;
+                entry.1.push(content);
+            }
+        }
+
+        let mut evidence = Vec::new();
+        for (conversation_id, (last_message_id, messages)) in &conversations {
+            // Only the user's words: replies can echo goals from the injected context
+            let text = messages.join("\n");
+            for goal in &goals {
+                let relevance = relevance(goal, &text);
+                if relevance < config.min_relevance {
+                    continue;
+                }
+                let analysis = match analyze_conversation(goal, &text).await {
+                    Ok(analysis) => analysis,
+                    Err(e) => {
+                        log::warn!("Goal progress analysis failed for {}: {}", conversation_id, e);
+                        continue;
+                    }
+                };
+                let Some((delta, description)) = analysis else { continue };
+                let reference = format!("{}:{}", conversation_id, last_message_id);
+                if let Some(recorded) = self.record_evidence(
+                    &goal.id,
+                    EvidenceSource::Conversation,
+                    &reference,
+                    &description,
+                    delta,
+                    relevance,
+                )? {
+                    evidence.push(recorded);
+                }
+            }
+        }
+
+        Ok((conversations.len(), evidence))
+    }
+
+    /// Log evidence and apply its progress (capped). `None` when this activity was already
+    /// recorded for the goal.
+    pub fn record_evidence(
+        &self,
+        goal_id: &str,
+        source: EvidenceSource,
+        reference: &str,
+        summary: &str,
+        progress_delta: f32,
+        relevance: f32,
+    ) -> Result<Option<GoalEvidence>> {
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
+
+        let exists: bool = conn
+            .query_row(
+                "SELECT 1 FROM goal_evidence WHERE goal_id = ?1 AND source = ?2 AND reference = ?3",
+                rusqlite::params![goal_id, source_to_str(source)?, reference],
+                |_| Ok(true),
+            )
+            .optional()?
+            .unwrap_or(false);
+        if exists {
+            return Ok(None);
+        }
+
+        let current_progress: f32 = conn.query_row(
+            "SELECT progress_percentage FROM goals WHERE id = ?1",
+            [goal_id],
+            |row| row.get(0),
+        )?;
+        let applied = (current_progress + progress_delta.max(0.0)).min(AUTO_PROGRESS_CAP.max(current_progress))
+            - current_progress;
+
+        let now = chrono::Utc::now().timestamp();
+        let evidence = GoalEvidence {
+            id: uuid::Uuid::new_v4().to_string(),
+            goal_id: goal_id.to_string(),
+            source,
+            reference: reference.to_string(),
+            summary: summary.to_string(),
+            progress_delta: applied,
+            relevance: relevance.clamp(0.0, 1.0),
+            rejected: false,
+            created_at: now,
+        };
+        conn.execute(
+            "INSERT INTO goal_evidence (id, goal_id, source, reference, summary, progress_delta, relevance, rejected, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, ?8)",
+            rusqlite::params![
+                evidence.id,
+                evidence.goal_id,
+                source_to_str(source)?,
+                evidence.reference,
+                evidence.summary,
+                evidence.progress_delta,
+                evidence.relevance,
+                evidence.created_at,
+            ],
+        )?;
+
+        if applied > 0.0 {
+            conn.execute(
+                "UPDATE goals SET progress_percentage = ?1, updated_at = ?2, last_check_in = ?2 WHERE id = ?3",
+                rusqlite::params![current_progress + applied, now, goal_id],
+            )?;
+            conn.execute(
+                "INSERT INTO progress_updates (goal_id, update_type, description, progress_delta, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                rusqlite::params![goal_id, "progress", summary, applied, now],
+            )?;
+        }
+
+        Ok(Some(evidence))
+    }
+
+    /// Evidence log for a goal, newest first
+    pub fn get_evidence(&self, goal_id: &str) -> Result<Vec<GoalEvidence>> {
+        let db = self.db.lock().unwrap();
+        let mut stmt = db.conn().prepare(
+            "SELECT id, goal_id, source, reference, summary, progress_delta, relevance, rejected, created_at
+             FROM goal_evidence WHERE goal_id = ?1 ORDER BY created_at DESC",
+        )?;
+        let evidence = stmt
+            .query_map([goal_id], |row| {
+                let source: String = row.get(2)?;
+                Ok(GoalEvidence {
+                    id: row.get(0)?,
+                    goal_id: row.get(1)?,
+                    source: serde_json::from_str(&format!("\"{}\"", source)).unwrap_or(EvidenceSource::Conversation),
+                    reference: row.get(3)?,
+                    summary: row.get(4)?,
+                    progress_delta: row.get(5)?,
+                    relevance: row.get(6)?,
+                    rejected: row.get(7)?,
+                    created_at: row.get(8)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(evidence)
+    }
+
+    /// Mark evidence as wrong and take back the progress it added.
+    /// The entry stays in the log so the same activity isn't counted again.
+    pub fn reject_evidence(&self, evidence_id: &str) -> Result<bool> {
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
+
+        let evidence: Option<(String, f32, String)> = conn
+            .query_row(
+                "SELECT goal_id, progress_delta, summary FROM goal_evidence WHERE id = ?1 AND rejected = 0",
+                [evidence_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?;
+        let Some((goal_id, delta, summary)) = evidence else {
+            return Ok(false);
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        conn.execute("UPDATE goal_evidence SET rejected = 1 WHERE id = ?1", [evidence_id])?;
+        conn.execute(
+            "UPDATE goals SET progress_percentage = MAX(0.0, progress_percentage - ?1), updated_at = ?2 WHERE id = ?3",
+            rusqlite::params![delta, now, goal_id],
+        )?;
+        conn.execute(
+            "INSERT INTO progress_updates (goal_id, update_type, description, progress_delta, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![goal_id, "setback", format!("Evidence rejected: {}", summary), -delta, now],
+        )?;
+
+        log::info!("Goal evidence rejected: {}", evidence_id);
+        Ok(true)
     }
 
     /// Get achievements for a goal
@@ -624,3 +1137,232 @@ If no progress detected, set has_progress to false and progress_delta to 0.0"#,
         Ok(())
     }
 }
+
+fn source_to_str(source: EvidenceSource) -> Result<String> {
+    Ok(serde_json::to_string(&source)?.trim_matches('"').to_string())
+}
+
+/// Ask the LLM whether `conversation` shows progress on `goal`: (progress delta, description)
+async fn analyze_conversation(goal: &Goal, conversation: &str) -> Result<Option<(f32, String)>> {
+    let prompt = format!(
+        r#"You are a goal progress analyzer. Analyze this conversation to detect progress toward the goal.
+
+Goal: "{}"
+Description: {}
+Success Criteria: {:?}
+
+Conversation:
+{}
+
+Has the user made progress on this goal? Estimate progress change.
+
+Respond ONLY with valid JSON:
+{{
+  "has_progress": true | false,
+  "progress_delta": 0.0-100.0,
+  "description": "Brief description of progress made"
+}}
+
+If no progress detected, set has_progress to false and progress_delta to 0.0"#,
+        goal.title, goal.description, goal.success_criteria, conversation
+    );
+
+    let response = ollama::generate_response(&prompt).await
+        .map_err(|e| anyhow!("Failed to detect progress: {}", e))?;
+
+    let analysis: serde_json::Value = serde_json::from_str(response.trim())
+        .map_err(|e| anyhow!("Failed to parse progress analysis: {}", e))?;
+
+    if analysis["has_progress"].as_bool().unwrap_or(false) {
+        let delta = analysis["progress_delta"].as_f64().unwrap_or(0.0) as f32;
+        let description = analysis["description"].as_str().unwrap_or("Progress detected");
+        if delta > 0.0 {
+            return Ok(Some((delta.min(CONVERSATION_PROGRESS_MAX), description.to_string())));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Lowercase words of three or more letters, without stopwords and a plural "s"
+fn keywords(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        let word = word.to_lowercase();
+        if word.chars().count() < 3 || STOPWORDS.contains(&word.as_str()) {
+            continue;
+        }
+        let word = match word.strip_suffix('s') {
+            Some(stem) if stem.chars().count() >= 4 && !stem.ends_with('s') => stem.to_string(),
+            _ => word,
+        };
+        if !words.contains(&word) {
+            words.push(word);
+        }
+    }
+    words
+}
+
+/// Share of the goal's title keywords that `text` mentions, with matching tags adding a
+/// bonus (0.0-1.0). Goals whose title is all stopwords fall back to their tags.
+fn relevance(goal: &Goal, text: &str) -> f32 {
+    let text_words: HashSet<String> = keywords(text).into_iter().collect();
+    let title_words = keywords(&goal.title);
+    let tag_words: Vec<String> = goal.tags.iter().flat_map(|tag| keywords(tag)).collect();
+
+    let matched_tags = tag_words.iter().filter(|word| text_words.contains(*word)).count();
+    if title_words.is_empty() {
+        return if tag_words.is_empty() { 0.0 } else { matched_tags as f32 / tag_words.len() as f32 };
+    }
+
+    let matched_title = title_words.iter().filter(|word| text_words.contains(*word)).count();
+    (matched_title as f32 / title_words.len() as f32 + 0.25 * matched_tags as f32).min(1.0)
+}
+
+/// (hash, subject) of commits on HEAD after `since_secs`, by the repository's configured
+/// author when one is set
+fn commits_since(repo: &Path, since_secs: i64) -> Result<Vec<(String, String)>> {
+    let repo_path = repo.to_string_lossy();
+    let author = git2::Repository::open(repo)
+        .ok()
+        .and_then(|repository| repository.config().ok())
+        .and_then(|config| config.get_string("user.email").ok())
+        .filter(|email| !email.is_empty());
+
+    Ok(GitService::get_log(&repo_path, COMMITS_PER_REPO)?
+        .into_iter()
+        .filter(|commit| commit.timestamp > since_secs)
+        .filter(|commit| author.as_ref().is_none_or(|email| commit.email.eq_ignore_ascii_case(email)))
+        .filter(|commit| !commit.message.starts_with("Merge "))
+        .map(|commit| {
+            let subject = commit.message.lines().next().unwrap_or("").to_string();
+            (commit.id, subject)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::task_planner::{Task, TaskPriority};
+    use std::process::Command;
+
+    fn goal(title: &str, tags: &[&str]) -> Goal {
+        let now = chrono::Utc::now().timestamp();
+        Goal {
+            id: uuid::Uuid::new_v4().to_string(),
+            title: title.to_string(),
+            description: String::new(),
+            category: GoalCategory::Project,
+            status: GoalStatus::Active,
+            time_frame: GoalTimeFrame::Short,
+            target_date: None,
+            progress_percentage: 0.0,
+            milestones: Vec::new(),
+            success_criteria: Vec::new(),
+            obstacles: Vec::new(),
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+            last_check_in: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_relevance() {
+        let sync = goal("Ship offline sync feature", &[]);
+        assert_eq!(relevance(&sync, "Add offline sync queue for drafts"), 1.0);
+        assert_eq!(relevance(&sync, "Fix sync button color"), 0.5);
+        assert_eq!(relevance(&sync, "Update dependencies"), 0.0);
+
+        let tagged = goal("Finish the feature", &["billing"]);
+        assert_eq!(relevance(&tagged, "Billing page layout"), 1.0);
+    }
+
+    #[test]
+    fn test_evidence_is_capped_deduplicated_and_rejectable() {
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let tracker = GoalTrackerService::new(db).unwrap();
+        let mut sync = goal("Ship offline sync", &[]);
+        sync.progress_percentage = 90.0;
+        let goal_id = tracker.create_goal(sync).unwrap();
+
+        let evidence = tracker
+            .record_evidence(&goal_id, EvidenceSource::Commit, "abc123", "Commit abc123: offline sync", 10.0, 1.0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(evidence.progress_delta, AUTO_PROGRESS_CAP - 90.0);
+        assert!(tracker
+            .record_evidence(&goal_id, EvidenceSource::Commit, "abc123", "again", 10.0, 1.0)
+            .unwrap()
+            .is_none());
+        let updated = tracker.get_goal(&goal_id).unwrap();
+        assert_eq!(updated.progress_percentage, AUTO_PROGRESS_CAP);
+        assert_eq!(updated.status, GoalStatus::Active);
+
+        assert!(tracker.reject_evidence(&evidence.id).unwrap());
+        assert!(!tracker.reject_evidence(&evidence.id).unwrap());
+        assert_eq!(tracker.get_goal(&goal_id).unwrap().progress_percentage, 90.0);
+        let log = tracker.get_evidence(&goal_id).unwrap();
+        assert_eq!(log.len(), 1);
+        assert!(log[0].rejected);
+    }
+
+    #[test]
+    fn test_commits_and_tasks_become_evidence() {
+        let repo = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git").arg("-C").arg(repo.path()).args(args).output().unwrap().status;
+            assert!(status.success(), "git {:?} failed", args);
+        };
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "dev@example.com"]);
+        git(&["config", "user.name", "Dev"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "Add offline sync queue"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "Tweak README"]);
+
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let tracker = GoalTrackerService::new(Arc::clone(&db)).unwrap();
+        let planner = Arc::new(TaskPlannerService::new(db).unwrap());
+        tracker.attach_task_planner(Arc::clone(&planner));
+        let goal_id = tracker.create_goal(goal("Ship offline sync", &[])).unwrap();
+
+        planner
+            .create_task(Task {
+                id: "task-1".to_string(),
+                parent_id: None,
+                title: "Write offline sync tests".to_string(),
+                description: String::new(),
+                status: TaskStatus::Pending,
+                priority: TaskPriority::Medium,
+                dependencies: Vec::new(),
+                estimated_duration_minutes: None,
+                actual_duration_minutes: None,
+                progress_percentage: 0.0,
+                created_at: 0,
+                started_at: None,
+                completed_at: None,
+                tags: Vec::new(),
+                due_at: None,
+                recurrence_id: None,
+            })
+            .unwrap();
+        planner.update_task_status("task-1", TaskStatus::Completed).unwrap();
+
+        let config = GoalInferenceConfig {
+            repositories: vec![repo.path().to_string_lossy().to_string()],
+            ..GoalInferenceConfig::default()
+        };
+        let since = chrono::Utc::now().timestamp() - 3600;
+        let report = tracker.infer_from_activity(&config, since).unwrap();
+        assert_eq!(report.commits_checked, 2);
+        assert_eq!(report.tasks_checked, 1);
+        assert_eq!(report.evidence.len(), 2);
+
+        // Already-seen activity isn't counted twice
+        assert!(tracker.infer_from_activity(&config, since).unwrap().evidence.is_empty());
+        let progress = tracker.get_goal(&goal_id).unwrap().progress_percentage;
+        assert_eq!(progress, COMMIT_PROGRESS + TASK_PROGRESS);
+    }
+}
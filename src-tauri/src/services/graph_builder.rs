@@ -8,6 +8,7 @@
  * - Entity deduplication and merging
  * - Community detection (Louvain algorithm)
  * - Graph statistics and analytics
+ * - Time-scoped relationships (v3.9.0)
  *
  * Integration: Works with entity_extractor.rs and graph_storage.rs
  */
@@ -37,6 +38,47 @@ pub struct GraphEdge {
     pub relationship_type: String,
     pub weight: f32,
     pub properties: HashMap<String, String>,
+    /// Start of the period the relationship held (Unix seconds, v3.9.0)
+    #[serde(default)]
+    pub valid_from: Option<i64>,
+    /// End of that period, `None` if ongoing or unknown
+    #[serde(default)]
+    pub valid_to: Option<i64>,
+}
+
+impl GraphEdge {
+    /// Whether the relationship held at some point in `[start, end]`
+    /// (undated relationships always match)
+    pub fn overlaps(&self, start: i64, end: i64) -> bool {
+        self.valid_from.is_none_or(|from| from <= end) && self.valid_to.is_none_or(|to| to >= start)
+    }
+}
+
+/// Parse "2024", "2024-06" or "2024-06-15" as a UTC timestamp (v3.9.0)
+///
+/// `end_of_period` picks the last second of the year/month/day instead of the first.
+pub fn parse_date_bound(value: &str, end_of_period: bool) -> Option<i64> {
+    use chrono::NaiveDate;
+
+    let parts: Vec<&str> = value.trim().split('-').collect();
+    let year: i32 = parts.first()?.parse().ok()?;
+    let (start, next) = match parts.len() {
+        1 => (NaiveDate::from_ymd_opt(year, 1, 1)?, NaiveDate::from_ymd_opt(year + 1, 1, 1)?),
+        2 => {
+            let month: u32 = parts[1].parse().ok()?;
+            let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+            (start, start.checked_add_months(chrono::Months::new(1))?)
+        }
+        3 => {
+            let start = NaiveDate::from_ymd_opt(year, parts[1].parse().ok()?, parts[2].parse().ok()?)?;
+            (start, start.succ_opt()?)
+        }
+        _ => return None,
+    };
+
+    let date = if end_of_period { next } else { start };
+    let timestamp = date.and_hms_opt(0, 0, 0)?.and_utc().timestamp();
+    Some(if end_of_period { timestamp - 1 } else { timestamp })
 }
 
 /// Knowledge graph structure
@@ -270,10 +312,21 @@ impl GraphBuilder {
         let source_id = source_id.unwrap();
         let target_id = target_id.unwrap();
 
-        // Check if edge already exists
+        let valid_from = relationship
+            .properties
+            .get("valid_from")
+            .and_then(|v| parse_date_bound(v, false));
+        let valid_to = relationship
+            .properties
+            .get("valid_to")
+            .and_then(|v| parse_date_bound(v, true));
+
+        // Check if edge already exists (same pair over the same period)
         let exists = self.graph.edges.iter().any(|e| {
-            (e.source_id == source_id && e.target_id == target_id)
-                || (e.source_id == target_id && e.target_id == source_id)
+            ((e.source_id == source_id && e.target_id == target_id)
+                || (e.source_id == target_id && e.target_id == source_id))
+                && e.valid_from == valid_from
+                && e.valid_to == valid_to
         });
 
         if exists {
@@ -291,6 +344,8 @@ impl GraphBuilder {
             relationship_type: relationship.relationship_type.as_str().to_string(),
             weight: relationship.confidence,
             properties: relationship.properties,
+            valid_from,
+            valid_to,
         };
 
         self.graph.edges.push(edge);
@@ -510,10 +565,22 @@ mod tests {
             relationship_type: "RelatesTo".to_string(),
             weight: 1.0,
             properties: HashMap::new(),
+            valid_from: None,
+            valid_to: None,
         });
 
         let subgraph = graph.get_subgraph("a", 1);
         assert_eq!(subgraph.node_count(), 2);
         assert_eq!(subgraph.edge_count(), 1);
     }
+
+    #[test]
+    fn test_parse_date_bound() {
+        assert_eq!(parse_date_bound("2024", false), Some(1704067200)); // 2024-01-01T00:00:00Z
+        assert_eq!(parse_date_bound("2024", true), Some(1735689599)); // 2024-12-31T23:59:59Z
+        assert_eq!(parse_date_bound("2024-02", true), Some(1709251199)); // 2024-02-29T23:59:59Z
+        assert_eq!(parse_date_bound("2024-06-15", false), Some(1718409600));
+        assert_eq!(parse_date_bound("June", false), None);
+        assert_eq!(parse_date_bound("2024-13", false), None);
+    }
 }
@@ -8,15 +8,22 @@
  * - Community-based retrieval: Retrieve related entities in the same community
  * - Graph traversal: Multi-hop reasoning over relationships
  * - Hybrid retrieval: Combine graph structure with semantic search
+ * - Time-scoped retrieval: "who was X working with in June" follows only
+ *   relationships valid in that period (v3.9.0)
+ * - Question answering over multi-hop paths, returned as a reasoning trace
+ *   of the entities and relationships the answer used (v3.9.0)
  *
  * Integration: Works with graph_storage.rs and hybrid_search.rs
  */
 
-use crate::services::graph_builder::{GraphNode, KnowledgeGraph};
+use crate::services::graph_builder::{GraphEdge, GraphNode, KnowledgeGraph};
 use crate::services::graph_storage::GraphStorage;
+use crate::services::ollama;
+use crate::services::provenance::Provenance;
+use chrono::{Datelike, NaiveDate};
 use log::{debug, info};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 /// Graph retrieval result
@@ -26,8 +33,112 @@ pub struct GraphRetrievalResult {
     pub relevance_score: f32,
     pub retrieval_path: Vec<String>, // Path from query entity to this entity
     pub context: Vec<String>,        // Related entities for context
+    #[serde(default)]
+    pub provenance: Vec<Provenance>, // v3.9.0: Where the entity was learned from
 }
 
+/// Period a question is asking about (Unix seconds, inclusive)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeScope {
+    pub start: i64,
+    pub end: i64,
+    /// "June 2024", "last week", ...
+    pub label: String,
+}
+
+/// Relationships followed per entity while looking for reasoning paths
+const MAX_BRANCHING: usize = 20;
+
+/// Reasoning paths given to the model
+const MAX_REASONING_PATHS: usize = 8;
+
+/// Entity on a reasoning path (v3.9.0)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceEntity {
+    pub entity_id: String,
+    pub name: String,
+    pub entity_type: String,
+}
+
+impl From<&GraphNode> for TraceEntity {
+    fn from(node: &GraphNode) -> Self {
+        Self {
+            entity_id: node.entity_id.clone(),
+            name: node.name.clone(),
+            entity_type: node.entity_type.clone(),
+        }
+    }
+}
+
+/// One relationship followed from `from` to `to`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceStep {
+    pub from: TraceEntity,
+    pub to: TraceEntity,
+    pub relationship: String,
+    /// `false` when the stored relationship points from `to` to `from`
+    pub forward: bool,
+    pub valid_from: Option<i64>,
+    pub valid_to: Option<i64>,
+}
+
+/// Chain of relationships from an entity of the question to a related one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReasoningPath {
+    pub steps: Vec<TraceStep>,
+    pub score: f32,
+    /// Ends at another entity the question names
+    pub connects_seeds: bool,
+}
+
+impl ReasoningPath {
+    /// "Alice —works_at→ Acme ←founded— Bob"
+    pub fn describe(&self) -> String {
+        let Some(first) = self.steps.first() else {
+            return String::new();
+        };
+        let mut text = first.from.name.clone();
+        for step in &self.steps {
+            let period = describe_period(step.valid_from, step.valid_to);
+            if step.forward {
+                text.push_str(&format!(" —{}{}→ {}", step.relationship, period, step.to.name));
+            } else {
+                text.push_str(&format!(" ←{}{}— {}", step.relationship, period, step.to.name));
+            }
+        }
+        text
+    }
+
+    fn entity_ids(&self) -> impl Iterator<Item = &str> {
+        self.steps
+            .first()
+            .map(|step| step.from.entity_id.as_str())
+            .into_iter()
+            .chain(self.steps.iter().map(|step| step.to.entity_id.as_str()))
+    }
+}
+
+/// Answer to a question with the graph paths it is based on (v3.9.0)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphAnswer {
+    pub question: String,
+    /// `None` when the graph knows nothing connected to the question
+    pub answer: Option<String>,
+    /// Entities of the question the traversal started from
+    pub seeds: Vec<TraceEntity>,
+    pub paths: Vec<ReasoningPath>,
+    /// Indices into `paths` the answer cites (all paths if it cites none)
+    pub used_paths: Vec<usize>,
+    pub time_scope: Option<TimeScope>,
+    /// Where the entities on the used paths were learned from
+    pub provenance: Vec<Provenance>,
+}
+
+/// Words that make a bare month name a time reference ("in June", not "may I")
+const TIME_PREPOSITIONS: &[&str] = &[
+    "in", "during", "since", "from", "of", "last", "until", "around", "by", "before", "after",
+];
+
 /// Graph retrieval configuration
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GraphRetrievalConfig {
@@ -73,12 +184,28 @@ impl GraphRetrievalEngine {
         GraphRetrievalEngine { config, storage }
     }
 
+    /// Time scope of a question, relative to today (v3.9.0)
+    pub fn time_scope(&self, query: &str) -> Option<TimeScope> {
+        parse_time_scope(query, chrono::Local::now().date_naive())
+    }
+
     /// Retrieve entities by query
+    ///
+    /// When the query names a period ("in June", "last year"), traversal only
+    /// follows relationships that held during it.
     pub fn retrieve(&self, query: &str) -> Result<Vec<GraphRetrievalResult>, String> {
         info!("Graph retrieval for query: {}", query);
 
-        // Step 1: Search for entities matching the query
-        let seed_entities = self.storage.search_entities(query, 5)?;
+        let scope = self.time_scope(query);
+        if let Some(scope) = &scope {
+            debug!("Query time scope: {}", scope.label);
+        }
+
+        // Step 1: Search for entities matching the query, or mentioned in it
+        let mut seed_entities = self.storage.search_entities(query, 5)?;
+        if seed_entities.is_empty() {
+            seed_entities = self.storage.find_entities_mentioned(query, 3)?;
+        }
 
         if seed_entities.is_empty() {
             info!("No entities found matching query");
@@ -91,7 +218,7 @@ impl GraphRetrievalEngine {
         let mut all_results: Vec<GraphRetrievalResult> = Vec::new();
 
         for seed_entity in seed_entities {
-            let expanded = self.expand_from_entity(&seed_entity)?;
+            let expanded = self.expand_from_entity(&seed_entity, scope.as_ref())?;
             all_results.extend(expanded);
         }
 
@@ -116,6 +243,11 @@ impl GraphRetrievalEngine {
         // Limit results
         unique_results.truncate(self.config.max_results);
 
+        // Attach provenance for citations (v3.9.0)
+        for result in &mut unique_results {
+            result.provenance = self.storage.entity_provenance(&result.entity.entity_id, 3)?;
+        }
+
         info!("Retrieved {} unique entities", unique_results.len());
 
         Ok(unique_results)
@@ -125,6 +257,7 @@ impl GraphRetrievalEngine {
     fn expand_from_entity(
         &self,
         seed_entity: &GraphNode,
+        scope: Option<&TimeScope>,
     ) -> Result<Vec<GraphRetrievalResult>, String> {
         let mut results = Vec::new();
 
@@ -134,6 +267,7 @@ impl GraphRetrievalEngine {
             relevance_score: 1.0,
             retrieval_path: vec![seed_entity.entity_id.clone()],
             context: Vec::new(),
+            provenance: Vec::new(),
         });
 
         // BFS traversal up to max_hops
@@ -145,17 +279,36 @@ impl GraphRetrievalEngine {
             let mut next_level = Vec::new();
 
             for entity_id in &current_level {
-                let neighbors = self.storage.get_neighbors(entity_id)?;
-
-                for neighbor in neighbors {
+                let neighbors: Vec<(GraphNode, Option<GraphEdge>)> = match scope {
+                    Some(scope) => self
+                        .storage
+                        .get_relationships_during(entity_id, scope.start, scope.end, None)?
+                        .into_iter()
+                        .map(|(edge, node)| (node, Some(edge)))
+                        .collect(),
+                    None => self
+                        .storage
+                        .get_neighbors(entity_id)?
+                        .into_iter()
+                        .map(|node| (node, None))
+                        .collect(),
+                };
+
+                for (neighbor, edge) in neighbors {
                     if visited.contains(&neighbor.entity_id) {
                         continue;
                     }
 
                     visited.insert(neighbor.entity_id.clone());
 
-                    // Calculate relevance score (decay with distance)
-                    let relevance_score = 1.0 / (hop as f32 + 1.0);
+                    // Calculate relevance score (decay with distance); undated
+                    // relationships only might have held in a scoped period
+                    let mut relevance_score = 1.0 / (hop as f32 + 1.0);
+                    if let Some(edge) = &edge {
+                        if edge.valid_from.is_none() && edge.valid_to.is_none() {
+                            relevance_score *= 0.8;
+                        }
+                    }
 
                     if relevance_score < self.config.min_relevance_score {
                         continue;
@@ -164,11 +317,16 @@ impl GraphRetrievalEngine {
                     // Build retrieval path
                     let path = vec![seed_entity.entity_id.clone(), neighbor.entity_id.clone()];
 
+                    let context = edge
+                        .map(|edge| vec![describe_relationship(&edge, entity_id, &neighbor)])
+                        .unwrap_or_default();
+
                     results.push(GraphRetrievalResult {
                         entity: neighbor.clone(),
                         relevance_score,
                         retrieval_path: path,
-                        context: Vec::new(),
+                        context,
+                        provenance: Vec::new(),
                     });
 
                     next_level.push(neighbor.entity_id);
@@ -182,8 +340,8 @@ impl GraphRetrievalEngine {
             }
         }
 
-        // Community expansion if enabled
-        if self.config.enable_community_expansion {
+        // Community expansion if enabled (communities carry no time information)
+        if self.config.enable_community_expansion && scope.is_none() {
             if let Some(community_id) = seed_entity.community_id {
                 let community_entities = self.storage.get_community_entities(community_id)?;
 
@@ -202,6 +360,7 @@ impl GraphRetrievalEngine {
                             entity.entity_id.clone(),
                         ],
                         context: Vec::new(),
+                        provenance: Vec::new(),
                     });
                 }
             }
@@ -328,6 +487,132 @@ impl GraphRetrievalEngine {
         Ok(None)
     }
 
+    /// Answer a question from multi-hop paths through the graph (v3.9.0)
+    ///
+    /// The model only sees the numbered paths and cites the ones it used, so
+    /// the returned trace shows how the entities were connected.
+    pub async fn answer(&self, question: &str) -> Result<GraphAnswer, String> {
+        info!("Graph answer for question: {}", question);
+
+        let scope = self.time_scope(question);
+        let (seeds, paths) = self.reasoning_paths(question, scope.as_ref())?;
+        let mut result = GraphAnswer {
+            question: question.to_string(),
+            answer: None,
+            seeds: seeds.iter().map(TraceEntity::from).collect(),
+            paths,
+            used_paths: Vec::new(),
+            time_scope: scope,
+            provenance: Vec::new(),
+        };
+        if result.paths.is_empty() {
+            info!("No reasoning paths found for question");
+            return Ok(result);
+        }
+
+        let prompt = answer_prompt(question, &result.paths, result.time_scope.as_ref());
+        let answer = ollama::generate_response(&prompt)
+            .await
+            .map_err(|e| format!("Failed to generate answer: {}", e))?;
+        let answer = answer.trim().to_string();
+
+        result.used_paths = cited_paths(&answer, result.paths.len());
+        if result.used_paths.is_empty() {
+            result.used_paths = (0..result.paths.len()).collect();
+        }
+
+        let mut seen = HashSet::new();
+        for index in &result.used_paths {
+            for entity_id in result.paths[*index].entity_ids() {
+                if !seen.insert(entity_id.to_string()) {
+                    continue;
+                }
+                for provenance in self.storage.entity_provenance(entity_id, 2)? {
+                    if !result.provenance.contains(&provenance) {
+                        result.provenance.push(provenance);
+                    }
+                }
+            }
+        }
+
+        info!("Answered from {} of {} reasoning paths", result.used_paths.len(), result.paths.len());
+        result.answer = Some(answer);
+        Ok(result)
+    }
+
+    /// Entities the question names and the best paths leading away from them
+    ///
+    /// Paths joining two named entities rank first: they are how a question
+    /// like "how is Alice connected to Acme?" gets answered.
+    pub fn reasoning_paths(
+        &self,
+        question: &str,
+        scope: Option<&TimeScope>,
+    ) -> Result<(Vec<GraphNode>, Vec<ReasoningPath>), String> {
+        let mut seeds = self.storage.find_entities_mentioned(question, 3)?;
+        if seeds.is_empty() {
+            seeds = self.storage.search_entities(question, 3)?;
+        }
+        let seed_ids: HashSet<String> = seeds.iter().map(|s| s.entity_id.clone()).collect();
+        let (start, end) = scope.map_or((i64::MIN, i64::MAX), |s| (s.start, s.end));
+
+        let mut paths: Vec<ReasoningPath> = Vec::new();
+        for seed in &seeds {
+            let mut visited: HashSet<String> = HashSet::from([seed.entity_id.clone()]);
+            // (entity, steps that reached it, product of edge weights)
+            let mut frontier: Vec<(GraphNode, Vec<TraceStep>, f32)> = vec![(seed.clone(), Vec::new(), 1.0)];
+
+            for hop in 1..=self.config.max_hops {
+                let mut next = Vec::new();
+                for (node, steps, weight) in &frontier {
+                    let relationships = self.storage.get_relationships_during(&node.entity_id, start, end, None)?;
+                    for (edge, neighbor) in relationships.into_iter().take(MAX_BRANCHING) {
+                        if !visited.insert(neighbor.entity_id.clone()) {
+                            continue;
+                        }
+
+                        let mut edge_weight = edge.weight.clamp(0.1, 1.0);
+                        if scope.is_some() && edge.valid_from.is_none() && edge.valid_to.is_none() {
+                            edge_weight *= 0.8;
+                        }
+                        let weight = weight * edge_weight;
+                        let connects_seeds = seed_ids.contains(&neighbor.entity_id);
+                        let mut score = weight / (hop as f32 + 1.0);
+                        if connects_seeds {
+                            score *= 2.0;
+                        }
+
+                        let mut path_steps = steps.clone();
+                        path_steps.push(TraceStep {
+                            from: TraceEntity::from(node),
+                            to: TraceEntity::from(&neighbor),
+                            relationship: edge.relationship_type.clone(),
+                            forward: edge.source_id == node.entity_id,
+                            valid_from: edge.valid_from,
+                            valid_to: edge.valid_to,
+                        });
+                        paths.push(ReasoningPath {
+                            steps: path_steps.clone(),
+                            score,
+                            connects_seeds,
+                        });
+
+                        // A path reaching another named entity is complete
+                        if !connects_seeds {
+                            next.push((neighbor, path_steps, weight));
+                        }
+                    }
+                }
+                frontier = next;
+                if frontier.is_empty() {
+                    break;
+                }
+            }
+        }
+
+        Ok((seeds, select_paths(paths, MAX_REASONING_PATHS)))
+    }
+
     /// Get configuration
     pub fn config(&self) -> &GraphRetrievalConfig {
         &self.config
@@ -343,6 +628,237 @@ impl GraphRetrievalEngine {
     }
 }
 
+/// "WorksWith person:bob (2024-06-01 – ongoing)"
+fn describe_relationship(edge: &GraphEdge, from_id: &str, other: &GraphNode) -> String {
+    let direction = if edge.source_id == from_id { "→" } else { "←" };
+    let period = describe_period(edge.valid_from, edge.valid_to);
+    format!("{} {} {}{}", edge.relationship_type, direction, other.name, period)
+}
+
+/// " (2024-06-01 – ongoing)", empty for undated relationships
+fn describe_period(valid_from: Option<i64>, valid_to: Option<i64>) -> String {
+    let day = |ts: i64| {
+        chrono::DateTime::from_timestamp(ts, 0)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "?".to_string())
+    };
+    match (valid_from, valid_to) {
+        (None, None) => String::new(),
+        (Some(from), None) => format!(" ({} – ongoing)", day(from)),
+        (None, Some(to)) => format!(" (until {})", day(to)),
+        (Some(from), Some(to)) => format!(" ({} – {})", day(from), day(to)),
+    }
+}
+
+/// Best `limit` paths, without duplicates or paths contained in a longer one
+///
+/// A path found from both of its ends is kept once, and "Alice → Python" adds
+/// nothing next to "Alice → Python ← Acme" in either direction.
+fn select_paths(mut paths: Vec<ReasoningPath>, limit: usize) -> Vec<ReasoningPath> {
+    paths.sort_by(|a, b| {
+        b.connects_seeds
+            .cmp(&a.connects_seeds)
+            .then(b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal))
+            .then(a.steps.len().cmp(&b.steps.len()))
+    });
+
+    let mut best: HashMap<(String, String), usize> = HashMap::new();
+    let mut unique: Vec<ReasoningPath> = Vec::new();
+    for path in paths {
+        let ids: Vec<&str> = path.entity_ids().collect();
+        let (Some(first), Some(last)) = (ids.first(), ids.last()) else {
+            continue;
+        };
+        let key = if first <= last {
+            (first.to_string(), last.to_string())
+        } else {
+            (last.to_string(), first.to_string())
+        };
+        if best.contains_key(&key) {
+            continue;
+        }
+        best.insert(key, unique.len());
+        unique.push(path);
+    }
+
+    let contains = |longer: &ReasoningPath, shorter: &ReasoningPath| {
+        if longer.steps.len() <= shorter.steps.len() {
+            return false;
+        }
+        let outer: Vec<&str> = longer.entity_ids().collect();
+        let mut inner: Vec<&str> = shorter.entity_ids().collect();
+        let forward = outer.windows(inner.len()).any(|window| window == inner.as_slice());
+        inner.reverse();
+        forward || outer.windows(inner.len()).any(|window| window == inner.as_slice())
+    };
+    let mut selected: Vec<ReasoningPath> = Vec::new();
+    for (i, path) in unique.iter().enumerate() {
+        if selected.len() >= limit {
+            break;
+        }
+        let covered = unique.iter().enumerate().any(|(j, other)| j != i && contains(other, path));
+        if !covered {
+            selected.push(path.clone());
+        }
+    }
+    selected
+}
+
+fn answer_prompt(question: &str, paths: &[ReasoningPath], scope: Option<&TimeScope>) -> String {
+    let connections = paths
+        .iter()
+        .enumerate()
+        .map(|(i, path)| format!("[{}] {}", i + 1, path.describe()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let period = scope
+        .map(|scope| format!("\nThe question is about {}.", scope.label))
+        .unwrap_or_default();
+
+    format!(
+        r#"Answer the question using only these connections from the user's knowledge graph.
+Each line is a chain of entities linked by relationships.
+
+{connections}
+
+Question: {question}{period}
+
+Answer in 1-3 sentences in the language of the question. After each claim, cite
+the connections it relies on, like [1] or [2][3]. If the connections don't answer
+the question, say so briefly."#
+    )
+}
+
+/// Path indices cited as `[n]` in the answer, in order of first citation
+fn cited_paths(answer: &str, path_count: usize) -> Vec<usize> {
+    let mut cited = Vec::new();
+    for part in answer.split('[').skip(1) {
+        let Some((number, _)) = part.split_once(']') else {
+            continue;
+        };
+        if let Ok(n) = number.trim().parse::<usize>() {
+            if (1..=path_count).contains(&n) && !cited.contains(&(n - 1)) {
+                cited.push(n - 1);
+            }
+        }
+    }
+    cited
+}
+
+fn month_number(token: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "january", "february", "march", "april", "may", "june", "july", "august",
+        "september", "october", "november", "december",
+    ];
+
+    // Korean "6월" / "6월에"
+    if let Some((number, _)) = token.split_once('월') {
+        return number.parse().ok().filter(|m| (1..=12).contains(m));
+    }
+    if token.len() < 3 {
+        return None;
+    }
+    MONTHS
+        .iter()
+        .position(|m| *m == token || (token.len() == 3 && m.starts_with(token)))
+        .map(|i| i as u32 + 1)
+}
+
+fn parse_year(token: &str) -> Option<i32> {
+    let token = token.split_once('년').map_or(token, |(year, _)| year);
+    if token.len() != 4 {
+        return None;
+    }
+    token.parse().ok().filter(|y| (1900..=2100).contains(y))
+}
+
+fn scope_between(start: NaiveDate, end: NaiveDate, label: String) -> Option<TimeScope> {
+    Some(TimeScope {
+        start: start.and_hms_opt(0, 0, 0)?.and_utc().timestamp(),
+        end: end.succ_opt()?.and_hms_opt(0, 0, 0)?.and_utc().timestamp() - 1,
+        label,
+    })
+}
+
+fn month_bounds(year: i32, month: u32) -> Option<(NaiveDate, NaiveDate)> {
+    let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let end = start.checked_add_months(chrono::Months::new(1))?.pred_opt()?;
+    Some((start, end))
+}
+
+/// Period a question refers to, if any (v3.9.0)
+///
+/// Understands "last week/month/year", "this month/year", month names with
+/// an optional year ("in June", "June 2023", "2023년 6월"), bare years, and
+/// "since ..." (up to today). A month without a year means its most recent
+/// occurrence.
+pub fn parse_time_scope(query: &str, today: NaiveDate) -> Option<TimeScope> {
+    let lower = query.to_lowercase();
+    let tokens: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .collect();
+    let has_pair = |a: &str, b: &str| tokens.windows(2).any(|w| w[0] == a && w[1] == b);
+
+    if has_pair("last", "week") {
+        return scope_between(today - chrono::Duration::days(7), today, "last week".to_string());
+    }
+    if has_pair("last", "month") || has_pair("this", "month") {
+        let this_month = NaiveDate::from_ymd_opt(today.year(), today.month(), 1)?;
+        let month = if has_pair("last", "month") {
+            this_month.checked_sub_months(chrono::Months::new(1))?
+        } else {
+            this_month
+        };
+        let (start, end) = month_bounds(month.year(), month.month())?;
+        return scope_between(start, end, month.format("%B %Y").to_string());
+    }
+    if has_pair("last", "year") || has_pair("this", "year") {
+        let year = if has_pair("last", "year") { today.year() - 1 } else { today.year() };
+        let start = NaiveDate::from_ymd_opt(year, 1, 1)?;
+        let end = NaiveDate::from_ymd_opt(year, 12, 31)?;
+        return scope_between(start, end, year.to_string());
+    }
+
+    let since = |i: usize| i > 0 && tokens[i - 1] == "since";
+
+    for (i, token) in tokens.iter().enumerate() {
+        let Some(month) = month_number(token) else {
+            continue;
+        };
+
+        let year = tokens
+            .get(i + 1)
+            .and_then(|t| parse_year(t))
+            .or_else(|| i.checked_sub(1).and_then(|j| parse_year(tokens[j])));
+        let preceded = i > 0 && TIME_PREPOSITIONS.contains(&tokens[i - 1]);
+        if token.is_ascii() && year.is_none() && !preceded {
+            continue;
+        }
+
+        let year = year.unwrap_or(if month > today.month() { today.year() - 1 } else { today.year() });
+        let (start, end) = month_bounds(year, month)?;
+        let label = start.format("%B %Y").to_string();
+        if since(i) {
+            return scope_between(start, today, format!("since {}", label));
+        }
+        return scope_between(start, end, label);
+    }
+
+    for (i, token) in tokens.iter().enumerate() {
+        let Some(year) = parse_year(token) else {
+            continue;
+        };
+        let start = NaiveDate::from_ymd_opt(year, 1, 1)?;
+        if since(i) {
+            return scope_between(start, today, format!("since {}", year));
+        }
+        return scope_between(start, NaiveDate::from_ymd_opt(year, 12, 31)?, year.to_string());
+    }
+
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -407,4 +923,105 @@ mod tests {
         // Should include other community members (Alice)
         assert!(results.len() >= 1);
     }
+
+    #[test]
+    fn test_reasoning_paths() {
+        let storage = create_test_storage();
+        let acme = GraphNode {
+            entity_id: "organization:acme".to_string(),
+            name: "Acme".to_string(),
+            entity_type: "Organization".to_string(),
+            properties: HashMap::new(),
+            community_id: None,
+            degree: 2,
+        };
+        storage.save_entity(&acme).unwrap();
+        let edge = |source: &str, target: &str, relationship: &str| GraphEdge {
+            source_id: source.to_string(),
+            target_id: target.to_string(),
+            relationship_type: relationship.to_string(),
+            weight: 1.0,
+            properties: HashMap::new(),
+            valid_from: None,
+            valid_to: None,
+        };
+        storage.save_relationship(&edge("person:alice", "tech:python", "uses")).unwrap();
+        storage.save_relationship(&edge("organization:acme", "tech:python", "builds_with")).unwrap();
+
+        let engine = GraphRetrievalEngine::new(storage);
+        let (seeds, paths) = engine.reasoning_paths("How is Alice connected to Acme?", None).unwrap();
+        assert_eq!(seeds.len(), 2);
+        // Found from both ends, kept once; the one-hop paths it contains are dropped
+        assert_eq!(paths.len(), 1);
+        assert!(paths[0].connects_seeds);
+        let described = paths[0].describe();
+        assert!(
+            described == "Alice —uses→ Python ←builds_with— Acme"
+                || described == "Acme —builds_with→ Python ←uses— Alice",
+            "{}",
+            described
+        );
+
+        assert_eq!(cited_paths("Alice uses Python [1], like Acme [2][1] [9]", 2), vec![0, 1]);
+        assert!(cited_paths("No citations here", 2).is_empty());
+    }
+
+    #[test]
+    fn test_parse_time_scope() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let label = |q: &str| parse_time_scope(q, today).map(|s| s.label);
+
+        // A month still ahead this year means last year's
+        assert_eq!(label("Who was Bob working with in June?").as_deref(), Some("June 2024"));
+        assert_eq!(label("what happened in feb").as_deref(), Some("February 2025"));
+        assert_eq!(label("projects from June 2023").as_deref(), Some("June 2023"));
+        assert_eq!(label("2023년 6월에 누구랑 일했어?").as_deref(), Some("June 2023"));
+        assert_eq!(label("last month").as_deref(), Some("February 2025"));
+        assert_eq!(label("since 2022").as_deref(), Some("since 2022"));
+        assert_eq!(label("may I ask about Rust"), None);
+        assert_eq!(label("Who is Bob working with?"), None);
+
+        let june = parse_time_scope("in June", today).unwrap();
+        assert_eq!(june.start, 1717200000); // 2024-06-01T00:00:00Z
+        assert_eq!(june.end, 1719791999); // 2024-06-30T23:59:59Z
+    }
+
+    #[test]
+    fn test_time_scoped_retrieval() {
+        let storage = create_test_storage();
+        let bob = GraphNode {
+            entity_id: "person:bob".to_string(),
+            name: "Bob".to_string(),
+            entity_type: "Person".to_string(),
+            properties: HashMap::new(),
+            community_id: None,
+            degree: 1,
+        };
+        let carol = GraphNode {
+            entity_id: "person:carol".to_string(),
+            name: "Carol".to_string(),
+            entity_type: "Person".to_string(),
+            ..bob.clone()
+        };
+        storage.save_entity(&bob).unwrap();
+        storage.save_entity(&carol).unwrap();
+
+        let works_with = |target: &str, from: &str, to: &str| GraphEdge {
+            source_id: "person:bob".to_string(),
+            target_id: target.to_string(),
+            relationship_type: "WorksWith".to_string(),
+            weight: 1.0,
+            properties: HashMap::new(),
+            valid_from: crate::services::graph_builder::parse_date_bound(from, false),
+            valid_to: crate::services::graph_builder::parse_date_bound(to, true),
+        };
+        storage.save_relationship(&works_with("person:alice", "2020-01", "2020-12")).unwrap();
+        storage.save_relationship(&works_with("person:carol", "2021-05", "2022")).unwrap();
+
+        let engine = GraphRetrievalEngine::new(storage);
+        let results = engine.retrieve("who was Bob working with in June 2021").unwrap();
+        let names: Vec<&str> = results.iter().map(|r| r.entity.name.as_str()).collect();
+        assert_eq!(names, vec!["Bob", "Carol"]);
+        assert_eq!(results[1].context, vec!["WorksWith → Carol (2021-05-01 – 2022-12-31)".to_string()]);
+    }
 }
@@ -8,15 +8,19 @@
  * - kg_relationships: Relationship edges
  * - kg_entity_documents: Links entities to source documents
  * - kg_communities: Community detection results
+ * - kg_entity_aliases: Merged entity IDs and names pointing at their canonical entity
  *
  * Features:
  * - CRUD operations for entities and relationships
  * - Graph traversal queries
  * - Community-based retrieval
  * - Full-text search on entity properties
+ * - Duplicate detection and alias merging (v3.9.0)
+ * - Time-scoped relationships with valid_from/valid_to (v3.9.0)
  */
 
 use crate::services::graph_builder::{GraphEdge, GraphNode, KnowledgeGraph};
+use crate::services::provenance::{Provenance, ProvenanceSource};
 use log::{debug, info};
 use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
@@ -33,7 +37,7 @@ impl GraphStorage {
     pub fn new(db_path: &str) -> Result<Self, String> {
         info!("Initializing Graph Storage at: {}", db_path);
 
-        let conn = Connection::open(db_path)
+        let conn = crate::services::encryption::open_connection(std::path::Path::new(db_path))
             .map_err(|e| format!("Failed to open database: {}", e))?;
 
         let storage = GraphStorage {
@@ -46,6 +50,17 @@ impl GraphStorage {
         Ok(storage)
     }
 
+    /// Reopen storage on another database file (v3.9.0: profile switch)
+    pub fn reopen(&self, db_path: &str) -> Result<(), String> {
+        info!("Reopening Graph Storage at: {}", db_path);
+
+        let conn = crate::services::encryption::open_connection(std::path::Path::new(db_path))
+            .map_err(|e| format!("Failed to open database: {}", e))?;
+        *self.conn.lock().unwrap() = conn;
+
+        self.create_tables()
+    }
+
     /// Create database tables
     fn create_tables(&self) -> Result<(), String> {
         let conn = self.conn.lock().unwrap();
@@ -83,6 +98,10 @@ impl GraphStorage {
         )
         .map_err(|e| format!("Failed to create kg_relationships table: {}", e))?;
 
+        // Migration: time-scoped relationships (v3.9.0)
+        let _ = conn.execute("ALTER TABLE kg_relationships ADD COLUMN valid_from INTEGER", []);
+        let _ = conn.execute("ALTER TABLE kg_relationships ADD COLUMN valid_to INTEGER", []);
+
         // kg_entity_documents table (links entities to source documents)
         conn.execute(
             "CREATE TABLE IF NOT EXISTS kg_entity_documents (
@@ -111,6 +130,17 @@ impl GraphStorage {
         )
         .map_err(|e| format!("Failed to create kg_communities table: {}", e))?;
 
+        // kg_entity_aliases table (v3.9.0: merged duplicates)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS kg_entity_aliases (
+                alias TEXT PRIMARY KEY,
+                entity_id TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| format!("Failed to create kg_entity_aliases table: {}", e))?;
+
         // Create indexes for faster queries
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_entities_type ON kg_entities(entity_type)",
@@ -148,6 +178,12 @@ impl GraphStorage {
         )
         .map_err(|e| format!("Failed to create index: {}", e))?;
 
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_entity_aliases_entity ON kg_entity_aliases(entity_id)",
+            [],
+        )
+        .map_err(|e| format!("Failed to create index: {}", e))?;
+
         info!("Database tables created successfully");
         Ok(())
     }
@@ -161,6 +197,17 @@ impl GraphStorage {
             .unwrap()
             .as_secs() as i64;
 
+        // A merged duplicate extracted again only refreshes its canonical entity
+        if let Some(canonical) = resolve_alias(&conn, &node.entity_id)? {
+            conn.execute(
+                "UPDATE kg_entities SET updated_at = ?1 WHERE entity_id = ?2",
+                params![now, canonical],
+            )
+            .map_err(|e| format!("Failed to save entity: {}", e))?;
+            debug!("Saved entity {} as alias of {}", node.entity_id, canonical);
+            return Ok(());
+        }
+
         let properties_json = serde_json::to_string(&node.properties)
             .map_err(|e| format!("Failed to serialize properties: {}", e))?;
 
@@ -196,24 +243,29 @@ impl GraphStorage {
         let properties_json = serde_json::to_string(&edge.properties)
             .map_err(|e| format!("Failed to serialize properties: {}", e))?;
 
+        let source_id = resolve_alias(&conn, &edge.source_id)?.unwrap_or_else(|| edge.source_id.clone());
+        let target_id = resolve_alias(&conn, &edge.target_id)?.unwrap_or_else(|| edge.target_id.clone());
+
         conn.execute(
             "INSERT INTO kg_relationships
-             (source_id, target_id, relationship_type, weight, properties, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+             (source_id, target_id, relationship_type, weight, properties, created_at, valid_from, valid_to)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             params![
-                edge.source_id,
-                edge.target_id,
+                source_id,
+                target_id,
                 edge.relationship_type,
                 edge.weight,
                 properties_json,
                 now,
+                edge.valid_from,
+                edge.valid_to,
             ],
         )
         .map_err(|e| format!("Failed to save relationship: {}", e))?;
 
         debug!(
             "Saved relationship: {} -> {}",
-            edge.source_id, edge.target_id
+            source_id, target_id
         );
         Ok(())
     }
@@ -353,6 +405,115 @@ impl GraphStorage {
         Ok(results)
     }
 
+    /// Relationships of an entity that held at some point in `[start, end]`
+    /// (Unix seconds), each with the entity on the other side (v3.9.0)
+    ///
+    /// Undated relationships are included; they carry no time information
+    /// to rule them out.
+    pub fn get_relationships_during(
+        &self,
+        entity_id: &str,
+        start: i64,
+        end: i64,
+        relationship_type: Option<&str>,
+    ) -> Result<Vec<(GraphEdge, GraphNode)>, String> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT r.source_id, r.target_id, r.relationship_type, r.weight, r.properties,
+                        r.valid_from, r.valid_to,
+                        e.entity_id, e.name, e.entity_type, e.properties, e.community_id, e.degree
+                 FROM kg_relationships r
+                 INNER JOIN kg_entities e ON
+                     (r.source_id = ?1 AND r.target_id = e.entity_id) OR
+                     (r.target_id = ?1 AND r.source_id = e.entity_id)
+                 WHERE (r.valid_from IS NULL OR r.valid_from <= ?3)
+                   AND (r.valid_to IS NULL OR r.valid_to >= ?2)
+                   AND (?4 IS NULL OR r.relationship_type = ?4)
+                 ORDER BY (r.valid_from IS NULL), r.weight DESC, e.degree DESC",
+            )
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let rows = stmt
+            .query_map(params![entity_id, start, end, relationship_type], |row| {
+                let edge_properties: Option<String> = row.get(4)?;
+                let node_properties: Option<String> = row.get(10)?;
+
+                Ok((
+                    GraphEdge {
+                        source_id: row.get(0)?,
+                        target_id: row.get(1)?,
+                        relationship_type: row.get(2)?,
+                        weight: row.get::<_, f64>(3)? as f32,
+                        properties: edge_properties
+                            .and_then(|json| serde_json::from_str(&json).ok())
+                            .unwrap_or_default(),
+                        valid_from: row.get(5)?,
+                        valid_to: row.get(6)?,
+                    },
+                    GraphNode {
+                        entity_id: row.get(7)?,
+                        name: row.get(8)?,
+                        entity_type: row.get(9)?,
+                        properties: node_properties
+                            .and_then(|json| serde_json::from_str(&json).ok())
+                            .unwrap_or_default(),
+                        community_id: row.get(11)?,
+                        degree: row.get::<_, i64>(12)? as usize,
+                    },
+                ))
+            })
+            .map_err(|e| format!("Failed to get relationships: {}", e))?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.map_err(|e| format!("Failed to parse row: {}", e))?);
+        }
+
+        Ok(results)
+    }
+
+    /// Entities whose name appears in free text, longest name first (v3.9.0)
+    pub fn find_entities_mentioned(&self, text: &str, limit: usize) -> Result<Vec<GraphNode>, String> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT entity_id, name, entity_type, properties, community_id, degree
+                 FROM kg_entities
+                 WHERE length(name) >= 2 AND instr(lower(?1), lower(name)) > 0
+                 ORDER BY length(name) DESC, degree DESC
+                 LIMIT ?2",
+            )
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let rows = stmt
+            .query_map(params![text, limit as i64], |row| {
+                let properties_json: Option<String> = row.get(3)?;
+                let properties: HashMap<String, String> = properties_json
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default();
+
+                Ok(GraphNode {
+                    entity_id: row.get(0)?,
+                    name: row.get(1)?,
+                    entity_type: row.get(2)?,
+                    properties,
+                    community_id: row.get(4)?,
+                    degree: row.get::<_, i64>(5)? as usize,
+                })
+            })
+            .map_err(|e| format!("Failed to search entities: {}", e))?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.map_err(|e| format!("Failed to parse row: {}", e))?);
+        }
+
+        Ok(results)
+    }
+
     /// Get entities by community ID
     pub fn get_community_entities(
         &self,
@@ -394,6 +555,51 @@ impl GraphStorage {
         Ok(results)
     }
 
+    /// Where an entity was learned from (v3.9.0 - citations)
+    ///
+    /// Linked source documents when there are any, otherwise the entity's own
+    /// creation time.
+    pub fn entity_provenance(&self, entity_id: &str, limit: usize) -> Result<Vec<Provenance>, String> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT CAST(episode_id AS TEXT), created_at
+                 FROM kg_entity_documents
+                 WHERE entity_id = ?1
+                 ORDER BY relevance_score DESC, created_at DESC
+                 LIMIT ?2",
+            )
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let mut records = stmt
+            .query_map(params![entity_id, limit as i64], |row| {
+                Ok(Provenance::new(ProvenanceSource::GraphEntity, entity_id)
+                    .with_document(row.get::<_, String>(0)?)
+                    .with_timestamp_secs(row.get(1)?))
+            })
+            .map_err(|e| format!("Failed to load entity documents: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to parse row: {}", e))?;
+
+        if records.is_empty() {
+            let created_at: Option<i64> = conn
+                .query_row(
+                    "SELECT created_at FROM kg_entities WHERE entity_id = ?1",
+                    params![entity_id],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| format!("Failed to load entity: {}", e))?;
+
+            if let Some(created_at) = created_at {
+                records.push(Provenance::new(ProvenanceSource::GraphEntity, entity_id).with_timestamp_secs(created_at));
+            }
+        }
+
+        Ok(records)
+    }
+
     /// Get graph statistics
     pub fn get_stats(&self) -> Result<GraphStorageStats, String> {
         let conn = self.conn.lock().unwrap();
@@ -445,6 +651,462 @@ impl GraphStorage {
         Ok(())
     }
 
+    /// Find entities whose name or properties match a LIKE pattern (`\` escapes),
+    /// together with every relationship touching them (v3.9.0: privacy export)
+    pub fn find_by_pattern(
+        &self,
+        like_pattern: &str,
+    ) -> Result<(Vec<GraphNode>, Vec<GraphEdge>), String> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT entity_id, name, entity_type, properties, community_id, degree
+                 FROM kg_entities
+                 WHERE name LIKE ?1 ESCAPE '\\' OR properties LIKE ?1 ESCAPE '\\'",
+            )
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let entities = stmt
+            .query_map(params![like_pattern], |row| {
+                let properties_json: String = row.get(3)?;
+                let properties: HashMap<String, String> =
+                    serde_json::from_str(&properties_json).unwrap_or_default();
+
+                Ok(GraphNode {
+                    entity_id: row.get(0)?,
+                    name: row.get(1)?,
+                    entity_type: row.get(2)?,
+                    properties,
+                    community_id: row.get(4)?,
+                    degree: row.get::<_, i64>(5)? as usize,
+                })
+            })
+            .map_err(|e| format!("Failed to search entities: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to parse row: {}", e))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT source_id, target_id, relationship_type, weight, properties, valid_from, valid_to
+                 FROM kg_relationships
+                 WHERE source_id = ?1 OR target_id = ?1",
+            )
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let mut relationships: Vec<GraphEdge> = Vec::new();
+        for entity in &entities {
+            let rows = stmt
+                .query_map(params![entity.entity_id], |row| {
+                    let properties_json: Option<String> = row.get(4)?;
+                    let properties: HashMap<String, String> = properties_json
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .unwrap_or_default();
+
+                    Ok(GraphEdge {
+                        source_id: row.get(0)?,
+                        target_id: row.get(1)?,
+                        relationship_type: row.get(2)?,
+                        weight: row.get::<_, f64>(3)? as f32,
+                        properties,
+                        valid_from: row.get(5)?,
+                        valid_to: row.get(6)?,
+                    })
+                })
+                .map_err(|e| format!("Failed to get relationships: {}", e))?;
+
+            for row in rows {
+                let edge = row.map_err(|e| format!("Failed to parse row: {}", e))?;
+                // An edge between two matched entities is listed once
+                let seen = relationships.iter().any(|r| {
+                    r.source_id == edge.source_id
+                        && r.target_id == edge.target_id
+                        && r.relationship_type == edge.relationship_type
+                });
+                if !seen {
+                    relationships.push(edge);
+                }
+            }
+        }
+
+        Ok((entities, relationships))
+    }
+
+    /// Every entity and relationship in the graph (v3.9.0: vault export)
+    pub fn load_all(&self) -> Result<(Vec<GraphNode>, Vec<GraphEdge>), String> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT entity_id, name, entity_type, properties, community_id, degree
+                 FROM kg_entities
+                 ORDER BY name",
+            )
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let entities = stmt
+            .query_map([], |row| {
+                let properties_json: String = row.get(3)?;
+                let properties: HashMap<String, String> =
+                    serde_json::from_str(&properties_json).unwrap_or_default();
+
+                Ok(GraphNode {
+                    entity_id: row.get(0)?,
+                    name: row.get(1)?,
+                    entity_type: row.get(2)?,
+                    properties,
+                    community_id: row.get(4)?,
+                    degree: row.get::<_, i64>(5)? as usize,
+                })
+            })
+            .map_err(|e| format!("Failed to load entities: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to parse row: {}", e))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT source_id, target_id, relationship_type, weight, properties, valid_from, valid_to
+                 FROM kg_relationships
+                 ORDER BY weight DESC",
+            )
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let relationships = stmt
+            .query_map([], |row| {
+                let properties_json: Option<String> = row.get(4)?;
+                let properties: HashMap<String, String> = properties_json
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default();
+
+                Ok(GraphEdge {
+                    source_id: row.get(0)?,
+                    target_id: row.get(1)?,
+                    relationship_type: row.get(2)?,
+                    weight: row.get::<_, f64>(3)? as f32,
+                    properties,
+                    valid_from: row.get(5)?,
+                    valid_to: row.get(6)?,
+                })
+            })
+            .map_err(|e| format!("Failed to load relationships: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to parse row: {}", e))?;
+
+        Ok((entities, relationships))
+    }
+
+    /// Delete an entity's outgoing relationships of one type (v3.9.0: note re-import)
+    ///
+    /// Returns the number of relationships removed.
+    pub fn delete_relationships_from(&self, source_id: &str, relationship_type: &str) -> Result<usize, String> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM kg_relationships WHERE source_id = ?1 AND relationship_type = ?2",
+            params![source_id, relationship_type],
+        )
+        .map_err(|e| format!("Failed to delete relationships: {}", e))
+    }
+
+    /// Delete entities with their relationships and document links in one transaction
+    ///
+    /// Returns the number of relationships removed.
+    pub fn forget_entities(&self, entity_ids: &[String]) -> Result<usize, String> {
+        let conn = self.conn.lock().unwrap();
+
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        let mut relationships_deleted = 0;
+        for entity_id in entity_ids {
+            relationships_deleted += tx
+                .execute(
+                    "DELETE FROM kg_relationships WHERE source_id = ?1 OR target_id = ?1",
+                    params![entity_id],
+                )
+                .map_err(|e| format!("Failed to delete relationships: {}", e))?;
+            tx.execute(
+                "DELETE FROM kg_entity_documents WHERE entity_id = ?1",
+                params![entity_id],
+            )
+            .map_err(|e| format!("Failed to delete entity documents: {}", e))?;
+            tx.execute(
+                "DELETE FROM kg_entity_aliases WHERE entity_id = ?1",
+                params![entity_id],
+            )
+            .map_err(|e| format!("Failed to delete entity aliases: {}", e))?;
+            tx.execute(
+                "DELETE FROM kg_entities WHERE entity_id = ?1",
+                params![entity_id],
+            )
+            .map_err(|e| format!("Failed to delete entity: {}", e))?;
+        }
+
+        tx.commit()
+            .map_err(|e| format!("Failed to commit: {}", e))?;
+
+        info!("Forgot {} entities ({} relationships)", entity_ids.len(), relationships_deleted);
+        Ok(relationships_deleted)
+    }
+
+    /// Remove dangling rows (left by legacy data without foreign keys) and refresh
+    /// cached degrees (v3.9.0: graph maintenance background job)
+    pub fn run_maintenance(&self) -> Result<GraphMaintenanceReport, String> {
+        let conn = self.conn.lock().unwrap();
+
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        let dangling_relationships = tx
+            .execute(
+                "DELETE FROM kg_relationships
+                 WHERE source_id NOT IN (SELECT entity_id FROM kg_entities)
+                    OR target_id NOT IN (SELECT entity_id FROM kg_entities)",
+                [],
+            )
+            .map_err(|e| format!("Failed to delete dangling relationships: {}", e))?;
+
+        let dangling_documents = tx
+            .execute(
+                "DELETE FROM kg_entity_documents WHERE entity_id NOT IN (SELECT entity_id FROM kg_entities)",
+                [],
+            )
+            .map_err(|e| format!("Failed to delete dangling entity documents: {}", e))?;
+
+        let degrees_updated = tx
+            .execute(
+                "UPDATE kg_entities
+                 SET degree = (SELECT COUNT(*) FROM kg_relationships r
+                               WHERE r.source_id = kg_entities.entity_id OR r.target_id = kg_entities.entity_id)
+                 WHERE degree IS NOT (SELECT COUNT(*) FROM kg_relationships r
+                                      WHERE r.source_id = kg_entities.entity_id OR r.target_id = kg_entities.entity_id)",
+                [],
+            )
+            .map_err(|e| format!("Failed to refresh degrees: {}", e))?;
+
+        tx.commit()
+            .map_err(|e| format!("Failed to commit: {}", e))?;
+
+        conn.execute_batch("PRAGMA optimize;")
+            .map_err(|e| format!("Failed to optimize graph database: {}", e))?;
+
+        let report = GraphMaintenanceReport {
+            dangling_relationships,
+            dangling_documents,
+            degrees_updated,
+        };
+        info!("Graph maintenance: {:?}", report);
+        Ok(report)
+    }
+
+    /// Pairs of entities that likely name the same thing, best match first
+    /// (v3.9.0: "Bob" / "Bob Smith" / "bob@x.com")
+    pub fn find_merge_candidates(&self, limit: usize) -> Result<Vec<MergeCandidate>, String> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT entity_id, name, entity_type, properties, community_id, degree
+                 FROM kg_entities",
+            )
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let entities = stmt
+            .query_map([], |row| {
+                let properties_json: Option<String> = row.get(3)?;
+                let properties: HashMap<String, String> = properties_json
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default();
+
+                Ok(GraphNode {
+                    entity_id: row.get(0)?,
+                    name: row.get(1)?,
+                    entity_type: row.get(2)?,
+                    properties,
+                    community_id: row.get(4)?,
+                    degree: row.get::<_, i64>(5)? as usize,
+                })
+            })
+            .map_err(|e| format!("Failed to load entities: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to parse row: {}", e))?;
+
+        let mut candidates = Vec::new();
+        for (i, a) in entities.iter().enumerate() {
+            for b in &entities[i + 1..] {
+                let Some((score, reason)) = entity_similarity(a, b) else {
+                    continue;
+                };
+                if score >= MERGE_CANDIDATE_MIN_SCORE {
+                    let (primary, duplicate) = if prefer_as_primary(a, b) { (a, b) } else { (b, a) };
+                    candidates.push(MergeCandidate {
+                        primary: primary.clone(),
+                        duplicate: duplicate.clone(),
+                        score,
+                        reason: reason.to_string(),
+                    });
+                }
+            }
+        }
+
+        candidates.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        candidates.truncate(limit);
+
+        Ok(candidates)
+    }
+
+    /// Merge duplicates into `primary_id` in one transaction (v3.9.0)
+    ///
+    /// Relationships and document links move to the primary entity, missing
+    /// properties are copied over, and the duplicates' IDs and names become
+    /// aliases so later extractions resolve to the primary.
+    pub fn merge_entities(
+        &self,
+        primary_id: &str,
+        duplicate_ids: &[String],
+    ) -> Result<EntityMergeReport, String> {
+        let conn = self.conn.lock().unwrap();
+
+        let primary = load_node(&conn, primary_id)?
+            .ok_or_else(|| format!("Entity not found: {}", primary_id))?;
+
+        let mut duplicates = Vec::new();
+        for duplicate_id in duplicate_ids {
+            if duplicate_id == primary_id {
+                continue;
+            }
+            let node = load_node(&conn, duplicate_id)?
+                .ok_or_else(|| format!("Entity not found: {}", duplicate_id))?;
+            duplicates.push(node);
+        }
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        let mut properties = primary.properties.clone();
+        let mut relationships_redirected = 0;
+
+        for duplicate in &duplicates {
+            // Edges between the two would become self-loops
+            tx.execute(
+                "DELETE FROM kg_relationships
+                 WHERE (source_id = ?1 AND target_id = ?2) OR (source_id = ?2 AND target_id = ?1)",
+                params![primary_id, duplicate.entity_id],
+            )
+            .map_err(|e| format!("Failed to delete relationships: {}", e))?;
+
+            relationships_redirected += tx
+                .execute(
+                    "UPDATE kg_relationships SET source_id = ?1 WHERE source_id = ?2",
+                    params![primary_id, duplicate.entity_id],
+                )
+                .map_err(|e| format!("Failed to redirect relationships: {}", e))?;
+            relationships_redirected += tx
+                .execute(
+                    "UPDATE kg_relationships SET target_id = ?1 WHERE target_id = ?2",
+                    params![primary_id, duplicate.entity_id],
+                )
+                .map_err(|e| format!("Failed to redirect relationships: {}", e))?;
+
+            tx.execute(
+                "UPDATE kg_entity_documents SET entity_id = ?1 WHERE entity_id = ?2",
+                params![primary_id, duplicate.entity_id],
+            )
+            .map_err(|e| format!("Failed to redirect entity documents: {}", e))?;
+
+            // Aliases of the duplicate now point at the primary too
+            tx.execute(
+                "UPDATE kg_entity_aliases SET entity_id = ?1 WHERE entity_id = ?2",
+                params![primary_id, duplicate.entity_id],
+            )
+            .map_err(|e| format!("Failed to update entity aliases: {}", e))?;
+            for alias in [duplicate.entity_id.clone(), normalize_entity_name(&duplicate.name)] {
+                tx.execute(
+                    "INSERT OR REPLACE INTO kg_entity_aliases (alias, entity_id, created_at)
+                     VALUES (?1, ?2, ?3)",
+                    params![alias, primary_id, now],
+                )
+                .map_err(|e| format!("Failed to save entity alias: {}", e))?;
+            }
+
+            for (key, value) in &duplicate.properties {
+                properties.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+
+            tx.execute(
+                "DELETE FROM kg_entities WHERE entity_id = ?1",
+                params![duplicate.entity_id],
+            )
+            .map_err(|e| format!("Failed to delete entity: {}", e))?;
+        }
+
+        // Redirecting can leave the same edge twice
+        let relationships_deduplicated = tx
+            .execute(
+                "DELETE FROM kg_relationships
+                 WHERE (source_id = ?1 OR target_id = ?1)
+                   AND id NOT IN (SELECT MIN(id) FROM kg_relationships
+                                  WHERE source_id = ?1 OR target_id = ?1
+                                  GROUP BY source_id, target_id, relationship_type, valid_from, valid_to)",
+                params![primary_id],
+            )
+            .map_err(|e| format!("Failed to deduplicate relationships: {}", e))?;
+
+        let properties_json = serde_json::to_string(&properties)
+            .map_err(|e| format!("Failed to serialize properties: {}", e))?;
+        tx.execute(
+            "UPDATE kg_entities
+             SET properties = ?1,
+                 degree = (SELECT COUNT(*) FROM kg_relationships WHERE source_id = ?2 OR target_id = ?2),
+                 updated_at = ?3
+             WHERE entity_id = ?2",
+            params![properties_json, primary_id, now],
+        )
+        .map_err(|e| format!("Failed to update entity: {}", e))?;
+
+        tx.commit()
+            .map_err(|e| format!("Failed to commit: {}", e))?;
+
+        let report = EntityMergeReport {
+            primary_id: primary_id.to_string(),
+            primary_name: primary.name,
+            merged_ids: duplicates.iter().map(|d| d.entity_id.clone()).collect(),
+            merged_names: duplicates.into_iter().map(|d| d.name).collect(),
+            relationships_redirected,
+            relationships_deduplicated,
+        };
+        info!(
+            "Merged {} entities into {}",
+            report.merged_ids.len(),
+            report.primary_id
+        );
+        Ok(report)
+    }
+
+    /// Names and IDs merged into an entity (v3.9.0)
+    pub fn get_aliases(&self, entity_id: &str) -> Result<Vec<String>, String> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare("SELECT alias FROM kg_entity_aliases WHERE entity_id = ?1 ORDER BY created_at")
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let aliases = stmt
+            .query_map(params![entity_id], |row| row.get(0))
+            .map_err(|e| format!("Failed to get aliases: {}", e))?
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(|e| format!("Failed to parse row: {}", e))?;
+
+        Ok(aliases)
+    }
+
     /// Clear all graph data
     pub fn clear_all(&self) -> Result<(), String> {
         let conn = self.conn.lock().unwrap();
@@ -461,6 +1123,9 @@ impl GraphStorage {
         conn.execute("DELETE FROM kg_communities", [])
             .map_err(|e| format!("Failed to clear communities: {}", e))?;
 
+        conn.execute("DELETE FROM kg_entity_aliases", [])
+            .map_err(|e| format!("Failed to clear entity aliases: {}", e))?;
+
         info!("Cleared all graph data");
         Ok(())
     }
@@ -474,6 +1139,180 @@ pub struct GraphStorageStats {
     pub community_count: usize,
 }
 
+/// Result of `GraphStorage::run_maintenance`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphMaintenanceReport {
+    pub dangling_relationships: usize,
+    pub dangling_documents: usize,
+    pub degrees_updated: usize,
+}
+
+/// Two entities that likely name the same thing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeCandidate {
+    /// Suggested survivor (better connected, more specific name)
+    pub primary: GraphNode,
+    pub duplicate: GraphNode,
+    /// 0.0-1.0
+    pub score: f32,
+    pub reason: String,
+}
+
+/// Result of `GraphStorage::merge_entities`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityMergeReport {
+    pub primary_id: String,
+    pub primary_name: String,
+    pub merged_ids: Vec<String>,
+    /// Names of the merged entities (for propagating to wiki facts)
+    pub merged_names: Vec<String>,
+    pub relationships_redirected: usize,
+    pub relationships_deduplicated: usize,
+}
+
+/// Minimum similarity for a merge candidate
+const MERGE_CANDIDATE_MIN_SCORE: f32 = 0.75;
+
+fn load_node(conn: &Connection, entity_id: &str) -> Result<Option<GraphNode>, String> {
+    conn.query_row(
+        "SELECT entity_id, name, entity_type, properties, community_id, degree
+         FROM kg_entities
+         WHERE entity_id = ?1",
+        params![entity_id],
+        |row| {
+            let properties_json: Option<String> = row.get(3)?;
+            let properties: HashMap<String, String> = properties_json
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default();
+
+            Ok(GraphNode {
+                entity_id: row.get(0)?,
+                name: row.get(1)?,
+                entity_type: row.get(2)?,
+                properties,
+                community_id: row.get(4)?,
+                degree: row.get::<_, i64>(5)? as usize,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| format!("Failed to load entity: {}", e))
+}
+
+/// Canonical entity ID for a merged entity ID, if any
+fn resolve_alias(conn: &Connection, entity_id: &str) -> Result<Option<String>, String> {
+    conn.query_row(
+        "SELECT entity_id FROM kg_entity_aliases WHERE alias = ?1",
+        params![entity_id],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| format!("Failed to resolve alias: {}", e))
+}
+
+/// Lowercase, punctuation folded to spaces, whitespace collapsed
+pub fn normalize_entity_name(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Name tokens of an email's local part ("bob.smith@x.com" -> ["bob", "smith"])
+fn email_local_tokens(name: &str) -> Option<Vec<String>> {
+    let (local, domain) = name.trim().split_once('@')?;
+    if local.is_empty() || !domain.contains('.') {
+        return None;
+    }
+    Some(
+        local
+            .to_lowercase()
+            .split(|c: char| !c.is_alphabetic())
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .collect(),
+    )
+}
+
+/// "bob.smith@x.com" or "bobsmith@x.com" vs "Bob Smith", "bob@x.com" vs "Bob"
+fn email_matches_name(local: &[String], name: &str) -> Option<(f32, &'static str)> {
+    let tokens: Vec<&str> = name.split(' ').collect();
+    let matches = !local.is_empty()
+        && (local.concat() == tokens.concat() || local.iter().all(|t| tokens.contains(&t.as_str())));
+    matches.then_some((0.8, "email address matches name"))
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            current[j + 1] = (previous[j] + cost).min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// How likely two entities are the same, with the reason
+pub fn entity_similarity(a: &GraphNode, b: &GraphNode) -> Option<(f32, &'static str)> {
+    let name_a = normalize_entity_name(&a.name);
+    let name_b = normalize_entity_name(&b.name);
+    if name_a.is_empty() || name_b.is_empty() {
+        return None;
+    }
+
+    // An email address matches the person it belongs to, whatever the types
+    match (email_local_tokens(&a.name), email_local_tokens(&b.name)) {
+        (Some(_), Some(_)) => return None,
+        (Some(local), None) => return email_matches_name(&local, &name_b),
+        (None, Some(local)) => return email_matches_name(&local, &name_a),
+        (None, None) => {}
+    }
+
+    if a.entity_type != b.entity_type {
+        return None;
+    }
+
+    if name_a == name_b {
+        return Some((1.0, "same name"));
+    }
+
+    // "Bob" / "Bob Smith": one name's tokens are a prefix of the other's
+    let tokens_a: Vec<&str> = name_a.split(' ').collect();
+    let tokens_b: Vec<&str> = name_b.split(' ').collect();
+    let (shorter, longer) = if tokens_a.len() <= tokens_b.len() { (&tokens_a, &tokens_b) } else { (&tokens_b, &tokens_a) };
+    if longer.starts_with(shorter) && shorter.iter().map(|t| t.len()).sum::<usize>() >= 2 {
+        return Some((0.75 + 0.2 * shorter.len() as f32 / longer.len() as f32, "name is part of the other"));
+    }
+
+    // Typos and spelling variants
+    let max_len = name_a.chars().count().max(name_b.chars().count());
+    if max_len >= 5 {
+        let similarity = 1.0 - levenshtein(&name_a, &name_b) as f32 / max_len as f32;
+        if similarity >= 0.85 {
+            return Some((similarity, "similar spelling"));
+        }
+    }
+
+    None
+}
+
+/// Whether `a` should survive a merge with `b`
+fn prefer_as_primary(a: &GraphNode, b: &GraphNode) -> bool {
+    let a_email = email_local_tokens(&a.name).is_some();
+    let b_email = email_local_tokens(&b.name).is_some();
+    if a_email != b_email {
+        return b_email;
+    }
+    (a.degree, a.name.len()) >= (b.degree, b.name.len())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -572,6 +1411,8 @@ mod tests {
             relationship_type: "RelatesTo".to_string(),
             weight: 1.0,
             properties: HashMap::new(),
+            valid_from: None,
+            valid_to: None,
         };
 
         storage.save_relationship(&edge).unwrap();
@@ -600,4 +1441,171 @@ mod tests {
         let stats = storage.get_stats().unwrap();
         assert_eq!(stats.entity_count, 0);
     }
+
+    #[test]
+    fn test_find_and_forget_entities() {
+        let storage = GraphStorage::new(":memory:").unwrap();
+
+        for (id, name) in [("alice", "Alice"), ("acme", "Acme Corp"), ("rust", "Rust")] {
+            storage.save_entity(&GraphNode {
+                entity_id: id.to_string(),
+                name: name.to_string(),
+                entity_type: "Concept".to_string(),
+                properties: HashMap::new(),
+                community_id: None,
+                degree: 1,
+            }).unwrap();
+        }
+        storage.save_relationship(&GraphEdge {
+            source_id: "alice".to_string(),
+            target_id: "acme".to_string(),
+            relationship_type: "WorksAt".to_string(),
+            weight: 1.0,
+            properties: HashMap::new(),
+            valid_from: None,
+            valid_to: None,
+        }).unwrap();
+
+        let (entities, relationships) = storage.find_by_pattern("%alice%").unwrap();
+        assert_eq!(entities.len(), 1);
+        assert_eq!(relationships.len(), 1);
+
+        let ids: Vec<String> = entities.into_iter().map(|e| e.entity_id).collect();
+        assert_eq!(storage.forget_entities(&ids).unwrap(), 1);
+
+        let stats = storage.get_stats().unwrap();
+        assert_eq!(stats.entity_count, 2);
+        assert_eq!(stats.relationship_count, 0);
+    }
+
+    #[test]
+    fn test_run_maintenance() {
+        let storage = GraphStorage::new(":memory:").unwrap();
+
+        for id in ["a", "b"] {
+            storage
+                .save_entity(&GraphNode {
+                    entity_id: id.to_string(),
+                    name: id.to_uppercase(),
+                    entity_type: "Concept".to_string(),
+                    properties: HashMap::new(),
+                    community_id: None,
+                    degree: 7,
+                })
+                .unwrap();
+        }
+        // Legacy rows written before foreign keys were enforced
+        storage.conn.lock().unwrap().execute_batch("PRAGMA foreign_keys = OFF;").unwrap();
+        for target in ["b", "ghost"] {
+            storage
+                .save_relationship(&GraphEdge {
+                    source_id: "a".to_string(),
+                    target_id: target.to_string(),
+                    relationship_type: "RelatesTo".to_string(),
+                    weight: 1.0,
+                    properties: HashMap::new(),
+                    valid_from: None,
+                    valid_to: None,
+                })
+                .unwrap();
+        }
+
+        let report = storage.run_maintenance().unwrap();
+        assert_eq!(report.dangling_relationships, 1);
+        assert_eq!(report.degrees_updated, 2);
+        assert_eq!(storage.get_stats().unwrap().relationship_count, 1);
+        assert_eq!(storage.load_entity("a").unwrap().unwrap().degree, 1);
+
+        // Nothing left to fix on a second pass
+        let report = storage.run_maintenance().unwrap();
+        assert_eq!(report.dangling_relationships + report.degrees_updated, 0);
+    }
+
+    fn node(entity_id: &str, name: &str, entity_type: &str) -> GraphNode {
+        GraphNode {
+            entity_id: entity_id.to_string(),
+            name: name.to_string(),
+            entity_type: entity_type.to_string(),
+            properties: HashMap::new(),
+            community_id: None,
+            degree: 0,
+        }
+    }
+
+    fn edge(source_id: &str, target_id: &str) -> GraphEdge {
+        GraphEdge {
+            source_id: source_id.to_string(),
+            target_id: target_id.to_string(),
+            relationship_type: "Knows".to_string(),
+            weight: 1.0,
+            properties: HashMap::new(),
+            valid_from: None,
+            valid_to: None,
+        }
+    }
+
+    #[test]
+    fn test_entity_similarity() {
+        let bob = node("person:bob", "Bob", "Person");
+        let bob_smith = node("person:bob smith", "Bob Smith", "Person");
+        let email = node("document:bob@x.com", "bob@x.com", "Document");
+        let full_email = node("document:bob.smith@x.com", "bob.smith@x.com", "Document");
+
+        assert_eq!(entity_similarity(&bob, &bob_smith).unwrap().1, "name is part of the other");
+        assert_eq!(entity_similarity(&email, &bob).unwrap().1, "email address matches name");
+        assert_eq!(entity_similarity(&full_email, &bob_smith).unwrap().1, "email address matches name");
+        assert!(entity_similarity(&email, &node("person:alice", "Alice", "Person")).is_none());
+
+        let typo = entity_similarity(
+            &node("technology:kubernetes", "Kubernetes", "Technology"),
+            &node("technology:kubernates", "Kubernates", "Technology"),
+        );
+        assert_eq!(typo.unwrap().1, "similar spelling");
+
+        // Same name, different kinds of thing
+        assert!(entity_similarity(&bob, &node("project:bob", "Bob", "Project")).is_none());
+
+        // The email never survives over the name
+        assert!(prefer_as_primary(&bob, &email));
+        assert!(!prefer_as_primary(&email, &bob));
+    }
+
+    #[test]
+    fn test_merge_entities() {
+        let storage = GraphStorage::new(":memory:").unwrap();
+
+        for entity in [
+            node("person:bob", "Bob", "Person"),
+            node("person:bob smith", "Bob Smith", "Person"),
+            node("person:alice", "Alice", "Person"),
+        ] {
+            storage.save_entity(&entity).unwrap();
+        }
+        storage.save_relationship(&edge("person:bob", "person:alice")).unwrap();
+        storage.save_relationship(&edge("person:bob smith", "person:alice")).unwrap();
+        storage.save_relationship(&edge("person:bob", "person:bob smith")).unwrap();
+
+        let candidates = storage.find_merge_candidates(10).unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].duplicate.entity_id, "person:bob");
+
+        let report = storage
+            .merge_entities("person:bob smith", &["person:bob".to_string()])
+            .unwrap();
+        assert_eq!(report.merged_names, vec!["Bob".to_string()]);
+        assert_eq!(report.relationships_deduplicated, 1);
+
+        let stats = storage.get_stats().unwrap();
+        assert_eq!(stats.entity_count, 2);
+        assert_eq!(stats.relationship_count, 1);
+        assert_eq!(storage.load_entity("person:bob smith").unwrap().unwrap().degree, 1);
+        assert_eq!(storage.get_aliases("person:bob smith").unwrap(), vec!["person:bob", "bob"]);
+
+        // Re-extracted duplicates resolve to the primary
+        storage.save_entity(&node("person:bob", "Bob", "Person")).unwrap();
+        storage.save_relationship(&edge("person:alice", "person:bob")).unwrap();
+        assert!(storage.load_entity("person:bob").unwrap().is_none());
+        let neighbors = storage.get_neighbors("person:alice").unwrap();
+        assert!(neighbors.iter().all(|n| n.entity_id == "person:bob smith"));
+    }
 }
@@ -6,6 +6,7 @@
 //! 1. BM25 search (keyword-based) → top-20 results
 //! 2. BGE-M3 search (semantic) → top-20 results
 //! 3. RRF (Reciprocal Rank Fusion) → combine scores
+//!    (v3.9.0: or linear / learned fusion, see `fusion`)
 //! 4. Return top-K results
 //!
 //! RRF Formula:
@@ -17,31 +18,20 @@
 
 #![cfg(feature = "lancedb-support")]
 
-use super::bm25::{BM25Index, ScoredDocument as BM25ScoredDocument};
+use super::bm25::{BM25Index, ScoredDocument as BM25ScoredDocument, SyncStats, TokenizerKind};
 use super::embedding::UnifiedEmbeddingService;
+use super::fusion::{self, FusedHit, FusionConfig, FusionFeatures, LearnedFusion, QueryType};
 #[cfg(feature = "lancedb-support")]
 use super::rag_v2::{RagServiceV2, Episode};  // v3.4.0: Migrated to LanceDB for 10-100x faster search
-use super::reranker::HeuristicReranker;
+use super::query_expansion::{self, QueryExpansionOptions};
+use super::rag_eval::RetrievalMode;
+use super::reranker::RerankerService;
 use log::{debug, info};
 use rusqlite::Connection;
 use std::collections::HashMap;
 use std::sync::Arc;
 
-/// Fusion weights for combining BM25 and semantic search
-#[derive(Clone, Debug)]
-pub struct FusionWeights {
-    pub bm25_weight: f32,
-    pub semantic_weight: f32,
-}
-
-impl Default for FusionWeights {
-    fn default() -> Self {
-        FusionWeights {
-            bm25_weight: 0.5,
-            semantic_weight: 0.5,
-        }
-    }
-}
+pub use super::fusion::FusionWeights;
 
 /// Hybrid search result with combined score
 #[derive(Clone, Debug)]
@@ -54,6 +44,8 @@ pub struct HybridSearchResult {
     pub bm25_rank: Option<usize>,
     pub semantic_rank: Option<usize>,
     pub rerank_score: Option<f32>,  // Optional re-ranking score
+    pub query_type: QueryType,  // v3.9.0: Selects the weight profile
+    pub features: FusionFeatures,  // v3.9.0: Logged for learned fusion
 }
 
 /// Hybrid Search Engine combining BM25 + BGE-M3
@@ -61,8 +53,9 @@ pub struct HybridSearchEngine {
     bm25_index: BM25Index,
     embedding_service: Arc<UnifiedEmbeddingService>,
     rag_service: Arc<RagServiceV2>,  // v3.4.0: LanceDB-powered RAG
-    reranker: HeuristicReranker,  // Re-ranker for improved relevance
-    fusion_weights: FusionWeights,
+    reranker: Arc<RerankerService>,  // Re-ranker for improved relevance (cross-encoder or heuristic)
+    fusion: FusionConfig,  // v3.9.0: Strategy, weights and per-query-type profiles
+    learned_fusion: Option<LearnedFusion>,  // v3.9.0: Trained from clicks
     rrf_k: f32,  // RRF constant (default: 60)
     enable_reranking: bool,  // Toggle re-ranking on/off
 }
@@ -77,8 +70,9 @@ impl HybridSearchEngine {
             bm25_index: BM25Index::new(),
             embedding_service,
             rag_service,
-            reranker: HeuristicReranker::new(),
-            fusion_weights: FusionWeights::default(),
+            reranker: Arc::new(RerankerService::new()),
+            fusion: FusionConfig::default(),
+            learned_fusion: None,
             rrf_k: 60.0,
             enable_reranking: true,  // Enable by default
         }
@@ -94,17 +88,24 @@ impl HybridSearchEngine {
             bm25_index: BM25Index::new(),
             embedding_service,
             rag_service,
-            reranker: HeuristicReranker::new(),
-            fusion_weights: weights,
+            reranker: Arc::new(RerankerService::new()),
+            fusion: FusionConfig { weights, ..FusionConfig::default() },
+            learned_fusion: None,
             rrf_k: 60.0,
             enable_reranking: true,
         }
     }
 
     /// Build BM25 index from database
+    ///
+    /// v3.9.0: Loads the persisted index with the profile's tokenizer and only
+    /// indexes episodes added since.
     pub fn build_index(&mut self, conn: &Connection) -> Result<(), String> {
         info!("Building BM25 index for hybrid search");
-        self.bm25_index.build_from_database(conn)?;
+        self.bm25_index.set_tokenizer(TokenizerKind::load(conn).tokenizer());
+        self.bm25_index.load_from_database(conn)?;
+        self.fusion = FusionConfig::load(conn).map_err(|e| format!("Failed to load fusion config: {}", e))?;
+        self.learned_fusion = LearnedFusion::load(conn).map_err(|e| format!("Failed to load fusion model: {}", e))?;
         let stats = self.bm25_index.stats();
         info!(
             "BM25 index ready: {} docs, {} terms",
@@ -119,43 +120,143 @@ impl HybridSearchEngine {
         self.bm25_index.rebuild(conn)
     }
 
+    /// Index episodes stored or deleted since the last sync (v3.9.0)
+    pub fn sync_index(&mut self, conn: &Connection) -> Result<SyncStats, String> {
+        self.bm25_index.sync_with_database(conn)
+    }
+
+    /// Select the BM25 tokenizer and re-index with it (v3.9.0)
+    pub fn set_tokenizer(&mut self, conn: &Connection, kind: TokenizerKind) -> Result<(), String> {
+        info!("Switching BM25 tokenizer to {:?}", kind);
+        kind.save(conn)?;
+        self.bm25_index.set_tokenizer(kind.tokenizer());
+        self.bm25_index.rebuild(conn)
+    }
+
     /// Perform hybrid search with RRF fusion
     pub async fn search(
         &self,
         query: &str,
         top_k: usize,
+    ) -> Result<Vec<HybridSearchResult>, String> {
+        self.search_with_expansion(query, top_k, &QueryExpansionOptions::default()).await
+    }
+
+    /// Perform hybrid search, optionally expanding the query first (HyDE / multi-query)
+    ///
+    /// Retrieval runs once per query variant; re-ranking always scores
+    /// against the original query.
+    pub async fn search_with_expansion(
+        &self,
+        query: &str,
+        top_k: usize,
+        expansion: &QueryExpansionOptions,
+    ) -> Result<Vec<HybridSearchResult>, String> {
+        self.search_inner(query, top_k, expansion, self.enable_reranking).await
+    }
+
+    /// Episode ids retrieved by one configuration (for the eval harness)
+    pub async fn search_ids(
+        &self,
+        query: &str,
+        top_k: usize,
+        mode: RetrievalMode,
+    ) -> Result<Vec<String>, String> {
+        let ids = match mode {
+            RetrievalMode::Bm25 => self
+                .bm25_index
+                .search(query, top_k)
+                .into_iter()
+                .map(|doc| doc.document_id)
+                .collect(),
+            RetrievalMode::Dense => self
+                .rag_service
+                .search_with_scores(query, top_k)
+                .await
+                .map_err(|e| format!("Semantic search failed: {}", e))?
+                .into_iter()
+                .map(|(episode, _)| episode.id)
+                .collect(),
+            RetrievalMode::Hybrid | RetrievalMode::HybridRerank => self
+                .search_inner(
+                    query,
+                    top_k,
+                    &QueryExpansionOptions::default(),
+                    mode == RetrievalMode::HybridRerank,
+                )
+                .await?
+                .into_iter()
+                .map(|r| r.episode_id)
+                .collect(),
+        };
+        Ok(ids)
+    }
+
+    async fn search_inner(
+        &self,
+        query: &str,
+        top_k: usize,
+        expansion: &QueryExpansionOptions,
+        rerank: bool,
     ) -> Result<Vec<HybridSearchResult>, String> {
         info!("Hybrid search: '{}' (top_k: {})", query, top_k);
 
-        // Step 1: BM25 lexical search (top-20)
-        let bm25_results = self.bm25_index.search(query, 20);
+        let queries = query_expansion::expand_query(query, expansion).await;
+        if queries.len() > 1 {
+            debug!("Searching with {} query variants", queries.len());
+        }
+
+        // Step 1: BM25 lexical search (top-20 per variant)
+        let bm25_lists: Vec<Vec<BM25ScoredDocument>> = queries
+            .iter()
+            .map(|variant| self.bm25_index.search(variant, 20))
+            .collect();
+        let bm25_results = query_expansion::merge_ranked(bm25_lists, |doc| doc.document_id.clone(), 20);
         debug!("BM25 returned {} results", bm25_results.len());
 
-        // Step 2: Semantic search with BGE-M3 (top-20)
-        let semantic_episodes = self.rag_service.search_memory(query, 20).await
-            .map_err(|e| format!("Semantic search failed: {}", e))?;
+        // Step 2: Semantic search with BGE-M3 (top-20 per variant)
+        let mut semantic_lists = Vec::with_capacity(queries.len());
+        for variant in &queries {
+            let episodes = self.rag_service.search_with_scores(variant, 20).await
+                .map_err(|e| format!("Semantic search failed: {}", e))?;
+            semantic_lists.push(episodes);
+        }
+        let semantic_episodes = query_expansion::merge_ranked(semantic_lists, |(episode, _)| episode.id.clone(), 20);
         debug!("Semantic search returned {} results", semantic_episodes.len());
 
-        // Step 3: RRF fusion
-        let mut hybrid_results = self.rrf_fusion(bm25_results, semantic_episodes);
-        debug!("RRF fusion produced {} results", hybrid_results.len());
+        // Step 3: Fusion (RRF, linear or learned)
+        let mut hybrid_results = self.fuse_results(query, bm25_results, semantic_episodes);
+        debug!("{:?} fusion produced {} results", self.fusion.strategy, hybrid_results.len());
 
         // Step 4: Optional re-ranking
-        if self.enable_reranking && !hybrid_results.is_empty() {
-            debug!("Applying re-ranking to top {} results", hybrid_results.len().min(20));
+        if rerank && !hybrid_results.is_empty() {
+            let candidates = self.reranker.candidates();
+            debug!("Applying re-ranking to top {} results", hybrid_results.len().min(candidates));
 
             // Prepare results for re-ranking
             let results_for_reranking: Vec<(String, String, f32)> = hybrid_results
                 .iter()
-                .take(20)  // Re-rank top 20
+                .take(candidates)
                 .map(|r| (r.episode_id.clone(), r.content.clone(), r.hybrid_score))
                 .collect();
 
-            // Apply re-ranking
-            let reranked = self.reranker.rerank(query, results_for_reranking, top_k);
+            // Apply re-ranking (blocking: may load the cross-encoder on first use)
+            let reranker = Arc::clone(&self.reranker);
+            let query_owned = query.to_string();
+            let reranked = tokio::task::spawn_blocking(move || {
+                reranker.rerank(&query_owned, results_for_reranking, top_k)
+            })
+            .await
+            .map_err(|e| format!("Re-ranking task failed: {}", e))?;
 
             // Update hybrid results with re-ranking scores
+            let features: HashMap<String, FusionFeatures> = hybrid_results
+                .iter()
+                .map(|r| (r.episode_id.clone(), r.features))
+                .collect();
+            let query_type = QueryType::classify(query);
             hybrid_results = reranked.into_iter().map(|r| HybridSearchResult {
+                features: features.get(&r.document_id).copied().unwrap_or_default(),
                 episode_id: r.document_id,
                 content: r.content,
                 hybrid_score: r.cross_encoder_score,
@@ -164,6 +265,7 @@ impl HybridSearchEngine {
                 bm25_rank: None,
                 semantic_rank: None,
                 rerank_score: Some(r.cross_encoder_score),
+                query_type,
             }).collect();
 
             debug!("Re-ranking complete");
@@ -181,93 +283,57 @@ impl HybridSearchEngine {
         Ok(hybrid_results)
     }
 
-    /// RRF (Reciprocal Rank Fusion) score combination
-    fn rrf_fusion(
+    /// Combine BM25 and semantic results with the configured strategy (v3.9.0)
+    ///
+    /// Weights come from the query type's profile when profiles are enabled.
+    fn fuse_results(
         &self,
+        query: &str,
         bm25_results: Vec<BM25ScoredDocument>,
-        semantic_results: Vec<Episode>,
+        semantic_results: Vec<(Episode, f32)>,
     ) -> Vec<HybridSearchResult> {
-        // Build rank maps
-        let bm25_ranks: HashMap<String, (usize, f32)> = bm25_results
+        let query_type = QueryType::classify(query);
+        let weights = self.fusion.weights_for(query_type);
+        let bm25_hits: Vec<(String, f32)> = bm25_results.iter().map(|doc| (doc.document_id.clone(), doc.score)).collect();
+        let semantic_hits: Vec<(String, f32)> = semantic_results.iter().map(|(episode, score)| (episode.id.clone(), *score)).collect();
+        let fused = fusion::fuse(&bm25_hits, &semantic_hits, &self.fusion, weights, self.rrf_k, self.learned_fusion.as_ref());
+
+        let bm25_ranks: HashMap<&str, (usize, &BM25ScoredDocument)> = bm25_results
             .iter()
             .enumerate()
-            .map(|(rank, doc)| (doc.document_id.clone(), (rank + 1, doc.score)))
+            .map(|(rank, doc)| (doc.document_id.as_str(), (rank + 1, doc)))
             .collect();
-
-        // For semantic results, we use position as the rank (first result = rank 1)
-        // and importance as a proxy for score
-        let semantic_ranks: HashMap<String, (usize, f32)> = semantic_results
+        let semantic_ranks: HashMap<&str, (usize, &(Episode, f32))> = semantic_results
             .iter()
             .enumerate()
-            .map(|(rank, episode)| {
-                (episode.id.clone(), (rank + 1, episode.importance as f32))
-            })
+            .map(|(rank, result)| (result.0.id.as_str(), (rank + 1, result)))
             .collect();
 
-        // Collect all unique document IDs
-        let mut all_doc_ids: Vec<String> = bm25_ranks.keys().cloned().collect();
-        for id in semantic_ranks.keys() {
-            if !all_doc_ids.contains(id) {
-                all_doc_ids.push(id.clone());
-            }
-        }
-
-        // Compute RRF scores
-        let mut results: Vec<HybridSearchResult> = all_doc_ids
+        fused
             .into_iter()
-            .map(|doc_id| {
-                // RRF score: sum of 1/(k + rank) for each retriever
-                let bm25_rrf = if let Some((rank, _)) = bm25_ranks.get(&doc_id) {
-                    1.0 / (self.rrf_k + *rank as f32)
-                } else {
-                    0.0
-                };
-
-                let semantic_rrf = if let Some((rank, _)) = semantic_ranks.get(&doc_id) {
-                    1.0 / (self.rrf_k + *rank as f32)
-                } else {
-                    0.0
+            .map(|FusedHit { id, score, features }| {
+                let bm25 = bm25_ranks.get(id.as_str());
+                let semantic = semantic_ranks.get(id.as_str());
+                // Prefer BM25 content as it has both user message + AI response
+                let content = match (bm25, semantic) {
+                    (Some((_, doc)), _) => doc.content.clone(),
+                    (None, Some((_, (episode, _)))) => format!("{} {}", episode.user_message, episode.ai_response),
+                    (None, None) => String::new(),
                 };
-
-                // Weighted RRF score
-                let hybrid_score = bm25_rrf * self.fusion_weights.bm25_weight
-                    + semantic_rrf * self.fusion_weights.semantic_weight;
-
-                // Get original scores for debugging
-                let bm25_score = bm25_ranks.get(&doc_id).map(|(_, score)| *score).unwrap_or(0.0);
-                let semantic_score = semantic_ranks
-                    .get(&doc_id)
-                    .map(|(_, score)| *score)
-                    .unwrap_or(0.0);
-
-                // Get content (prefer BM25 content as it has both user_input + system_response)
-                let content = bm25_results
-                    .iter()
-                    .find(|r| r.document_id == doc_id)
-                    .map(|r| r.content.clone())
-                    .unwrap_or_default();
-
                 HybridSearchResult {
-                    episode_id: doc_id.clone(),
                     content,
-                    hybrid_score,
-                    bm25_score,
-                    semantic_score,
-                    bm25_rank: bm25_ranks.get(&doc_id).map(|(rank, _)| *rank),
-                    semantic_rank: semantic_ranks.get(&doc_id).map(|(rank, _)| *rank),
+                    hybrid_score: score,
+                    bm25_score: bm25.map(|(_, doc)| doc.score).unwrap_or(0.0),
+                    semantic_score: semantic.map(|(_, (_, score))| *score).unwrap_or(0.0),
+                    bm25_rank: bm25.map(|(rank, _)| *rank),
+                    semantic_rank: semantic.map(|(rank, _)| *rank),
                     rerank_score: None,  // Will be filled in by re-ranking if enabled
+                    query_type,
+                    features,
+                    episode_id: id,
                 }
             })
-            .collect();
-
-        // Sort by hybrid score descending
-        results.sort_by(|a, b| {
-            b.hybrid_score
-                .partial_cmp(&a.hybrid_score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-
-        results
+            .collect()
     }
 
     /// Update fusion weights
@@ -276,7 +342,36 @@ impl HybridSearchEngine {
             "Updating fusion weights: BM25={:.2}, Semantic={:.2}",
             weights.bm25_weight, weights.semantic_weight
         );
-        self.fusion_weights = weights;
+        self.fusion.weights = weights;
+    }
+
+    pub fn rrf_k(&self) -> f32 {
+        self.rrf_k
+    }
+
+    /// Episode ids of the BM25 results for `query`, best first (v3.9.0)
+    pub fn bm25_ranking(&self, query: &str, top_k: usize) -> Vec<String> {
+        self.bm25_index.search(query, top_k).into_iter().map(|doc| doc.document_id).collect()
+    }
+
+    /// Fusion strategy, weights and profiles (v3.9.0)
+    pub fn fusion_config(&self) -> &FusionConfig {
+        &self.fusion
+    }
+
+    pub fn set_fusion_config(&mut self, config: FusionConfig) {
+        info!("Updating fusion: {:?} (query profiles: {})", config.strategy, config.query_profiles);
+        self.fusion = config;
+    }
+
+    /// Train learned fusion on logged clicks and use it from now on (v3.9.0)
+    pub fn train_fusion(&mut self, conn: &Connection) -> Result<LearnedFusion, String> {
+        let samples = fusion::training_samples(conn).map_err(|e| format!("Failed to load feedback: {}", e))?;
+        let model = LearnedFusion::train(&samples).map_err(|e| e.to_string())?;
+        model.save(conn).map_err(|e| format!("Failed to save fusion model: {}", e))?;
+        info!("Learned fusion trained on {} impressions: {:?}", model.samples, model.weights);
+        self.learned_fusion = Some(model.clone());
+        Ok(model)
     }
 
     /// Update RRF constant
@@ -296,15 +391,24 @@ impl HybridSearchEngine {
         self.enable_reranking
     }
 
+    /// Re-ranker model selection
+    pub fn reranker(&self) -> &Arc<RerankerService> {
+        &self.reranker
+    }
+
     /// Get search engine statistics
     pub fn stats(&self) -> HybridSearchStats {
         let bm25_stats = self.bm25_index.stats();
         HybridSearchStats {
             bm25_documents: bm25_stats.total_documents,
             bm25_terms: bm25_stats.unique_terms,
-            fusion_weights: self.fusion_weights.clone(),
+            bm25_tokenizer: bm25_stats.tokenizer,
+            fusion_weights: self.fusion.weights.clone(),
+            fusion: self.fusion.clone(),
+            learned_fusion_samples: self.learned_fusion.as_ref().map(|model| model.samples),
             rrf_k: self.rrf_k,
             reranking_enabled: self.enable_reranking,
+            reranker_model: self.reranker.model().as_str().to_string(),
         }
     }
 }
@@ -314,9 +418,13 @@ impl HybridSearchEngine {
 pub struct HybridSearchStats {
     pub bm25_documents: usize,
     pub bm25_terms: usize,
+    pub bm25_tokenizer: &'static str,
     pub fusion_weights: FusionWeights,
+    pub fusion: FusionConfig,
+    pub learned_fusion_samples: Option<usize>,
     pub rrf_k: f32,
     pub reranking_enabled: bool,
+    pub reranker_model: String,
 }
 
 #[cfg(test)]
@@ -0,0 +1,213 @@
+//! ANN Index Tuning (v3.9.0)
+//!
+//! Recall vs latency controls for episodic memory search.
+//!
+//! Features:
+//! - LanceDB IVF-PQ build parameters (partitions, sub-vectors, PQ bits) and
+//!   query parameters (probed partitions, refine factor)
+//! - Batch size and candidate limit of the SQLite brute-force fallback
+//! - `auto_tune` derives all of them from the corpus size and a recall preference
+//! - Persisted per profile in `user_preferences`
+
+use crate::database::Database;
+use crate::services::vector_backend::{VectorBackendKind, EMBEDDING_DIM};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// user_preferences key holding the serialized tuning
+pub const INDEX_TUNING_KEY: &str = "vector_index_tuning";
+
+/// Below this many vectors an exact scan is faster than an IVF-PQ index
+pub const MIN_INDEXED_VECTORS: usize = 10_000;
+
+/// Upper bound on IVF partitions (training cost grows with them)
+const MAX_PARTITIONS: u32 = 4096;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecallPreference {
+    /// Lowest latency, a few relevant memories may be missed
+    Fast,
+    #[default]
+    Balanced,
+    /// Close to exact search, slower on large corpora
+    Accurate,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IndexTuning {
+    /// Preference the values were derived from (informational once edited)
+    pub preference: RecallPreference,
+    /// IVF partitions; None = sqrt(vector count) when the index is built
+    pub num_partitions: Option<u32>,
+    /// PQ sub-vectors; must divide the embedding dimension
+    pub num_sub_vectors: u32,
+    /// Bits per PQ code (4 or 8)
+    pub num_bits: u32,
+    /// IVF partitions searched per query
+    pub nprobes: usize,
+    /// Re-rank `refine_factor * top_k` candidates with full vectors; None = PQ distances only
+    pub refine_factor: Option<u32>,
+    /// Rows decoded per batch by the SQLite brute-force scan
+    pub sqlite_batch_size: usize,
+    /// Most recent episodes scored by the SQLite fallback search
+    pub sqlite_max_candidates: usize,
+}
+
+impl Default for IndexTuning {
+    fn default() -> Self {
+        Self {
+            preference: RecallPreference::Balanced,
+            num_partitions: None,
+            num_sub_vectors: (EMBEDDING_DIM / 16) as u32,
+            num_bits: 8,
+            nprobes: 20,
+            refine_factor: None,
+            sqlite_batch_size: 256,
+            sqlite_max_candidates: 500,
+        }
+    }
+}
+
+impl IndexTuning {
+    pub fn validate(&self) -> Result<()> {
+        if let Some(partitions) = self.num_partitions {
+            if !(1..=MAX_PARTITIONS).contains(&partitions) {
+                return Err(anyhow!("Partitions must be between 1 and {}", MAX_PARTITIONS));
+            }
+        }
+        if self.num_sub_vectors == 0 || !EMBEDDING_DIM.is_multiple_of(self.num_sub_vectors as usize) {
+            return Err(anyhow!("Sub-vectors must divide the embedding dimension ({})", EMBEDDING_DIM));
+        }
+        if !matches!(self.num_bits, 4 | 8) {
+            return Err(anyhow!("PQ bits must be 4 or 8"));
+        }
+        if self.nprobes == 0 {
+            return Err(anyhow!("At least one partition must be probed"));
+        }
+        if self.refine_factor == Some(0) {
+            return Err(anyhow!("Refine factor must be at least 1"));
+        }
+        if self.sqlite_batch_size == 0 || self.sqlite_max_candidates == 0 {
+            return Err(anyhow!("SQLite batch size and candidate limit must be positive"));
+        }
+        Ok(())
+    }
+
+    /// Partitions of an index built over `count` vectors
+    pub fn partitions_for(&self, count: usize) -> u32 {
+        self.num_partitions
+            .unwrap_or_else(|| ((count as f64).sqrt().round() as u32).clamp(1, MAX_PARTITIONS))
+    }
+}
+
+/// Parameters balancing recall and latency for a corpus of `corpus_size` vectors
+///
+/// Probing a fixed share of the sqrt(n) partitions keeps recall roughly constant
+/// as the corpus grows; the refine step and SQLite candidate limit trade
+/// latency for the last few percent of recall.
+pub fn auto_tune(corpus_size: usize, preference: RecallPreference) -> IndexTuning {
+    let partitions = ((corpus_size as f64).sqrt().round() as u32).clamp(1, MAX_PARTITIONS);
+    let (probe_share, min_probes, refine_factor, num_bits, sub_vector_width, max_candidates) = match preference {
+        RecallPreference::Fast => (40, 4, None, 4, 16, 250),
+        RecallPreference::Balanced => (20, 10, Some(5), 8, 16, 500),
+        RecallPreference::Accurate => (10, 20, Some(20), 8, 8, 2000),
+    };
+    let nprobes = (partitions as usize / probe_share).max(min_probes).min(partitions as usize);
+    IndexTuning {
+        preference,
+        num_partitions: Some(partitions),
+        num_sub_vectors: (EMBEDDING_DIM / sub_vector_width) as u32,
+        num_bits,
+        nprobes,
+        refine_factor,
+        sqlite_batch_size: (max_candidates / 4).clamp(64, 512),
+        sqlite_max_candidates: max_candidates,
+    }
+}
+
+/// Whether an IVF-PQ index pays off for `count` vectors
+pub fn should_index(count: usize) -> bool {
+    count >= MIN_INDEXED_VECTORS
+}
+
+/// Tuning in effect and what it applies to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexStatus {
+    pub backend: VectorBackendKind,
+    pub vectors: usize,
+    /// An ANN index exists (LanceDB only; other backends search exactly or index themselves)
+    pub indexed: bool,
+    pub tuning: IndexTuning,
+}
+
+/// Persisted tuning of the profile, or the default
+pub fn load(db: &Mutex<Database>) -> Result<IndexTuning> {
+    let db_guard = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+    let json: Option<String> = db_guard
+        .conn()
+        .query_row(
+            "SELECT value FROM user_preferences WHERE key = ?1",
+            [INDEX_TUNING_KEY],
+            |row| row.get(0),
+        )
+        .ok();
+    match json {
+        Some(json) => Ok(serde_json::from_str(&json)?),
+        None => Ok(IndexTuning::default()),
+    }
+}
+
+pub fn save(db: &Mutex<Database>, tuning: &IndexTuning) -> Result<()> {
+    tuning.validate()?;
+    let db_guard = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+    db_guard.conn().execute(
+        "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+        rusqlite::params![
+            INDEX_TUNING_KEY,
+            serde_json::to_string(tuning)?,
+            chrono::Utc::now().timestamp_millis()
+        ],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_tune() {
+        let small = auto_tune(100, RecallPreference::Balanced);
+        assert_eq!(small.num_partitions, Some(10));
+        assert_eq!(small.nprobes, 10);
+        assert!(small.validate().is_ok());
+        assert!(!should_index(100));
+
+        let fast = auto_tune(1_000_000, RecallPreference::Fast);
+        let accurate = auto_tune(1_000_000, RecallPreference::Accurate);
+        assert_eq!(fast.num_partitions, Some(1000));
+        assert_eq!((fast.nprobes, accurate.nprobes), (25, 100));
+        assert!(fast.refine_factor.is_none() && accurate.refine_factor == Some(20));
+        assert!(fast.sqlite_max_candidates < accurate.sqlite_max_candidates);
+        assert!(fast.validate().is_ok() && accurate.validate().is_ok());
+        assert_eq!(auto_tune(100_000_000, RecallPreference::Balanced).num_partitions, Some(MAX_PARTITIONS));
+
+        let invalid = IndexTuning { num_sub_vectors: 100, ..IndexTuning::default() };
+        assert!(invalid.validate().is_err());
+        assert!(IndexTuning { num_bits: 6, ..IndexTuning::default() }.validate().is_err());
+        assert_eq!(IndexTuning::default().partitions_for(40_000), 200);
+    }
+
+    #[test]
+    fn test_persisted_tuning() {
+        let db = Mutex::new(Database::new_test_db().unwrap());
+        assert_eq!(load(&db).unwrap(), IndexTuning::default());
+        let tuning = auto_tune(50_000, RecallPreference::Accurate);
+        save(&db, &tuning).unwrap();
+        assert_eq!(load(&db).unwrap(), tuning);
+        assert!(save(&db, &IndexTuning { nprobes: 0, ..tuning }).is_err());
+    }
+}
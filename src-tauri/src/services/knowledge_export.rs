@@ -0,0 +1,596 @@
+//! Knowledge Export Service (v3.9.0)
+//!
+//! Writes the semantic wiki and knowledge graph as a Markdown vault that
+//! Obsidian (or any Markdown note-taking tool) can open.
+//!
+//! Features:
+//! - One note per entity under `Entities/`: YAML frontmatter (type, aliases,
+//!   tags), the wiki page summary, facts grouped by category, and graph
+//!   relations as `[[wiki links]]`
+//! - `Index.md` linking every entity note, grouped by entity type
+//! - Incremental sync: a manifest of content hashes rewrites only changed
+//!   notes and removes notes of entities that were forgotten
+//! - Notes edited in the vault since the last sync are left alone unless
+//!   overwriting is requested
+//! - Vault folder persisted in `user_preferences` for the scheduled sync job
+
+#![allow(dead_code)]  // Phase 5: Knowledge export
+
+use crate::database::Database;
+use crate::services::graph_builder::{GraphEdge, GraphNode};
+use crate::services::graph_storage::{normalize_entity_name, GraphStorage};
+use crate::services::semantic_wiki::{Fact, FactCategory, SemanticWikiService};
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// user_preferences key holding the vault folder
+pub const VAULT_PATH_KEY: &str = "knowledge_vault_path";
+
+/// Sync state kept in the vault root
+const MANIFEST_FILE: &str = ".garden-of-eden.json";
+
+const ENTITY_DIR: &str = "Entities";
+const INDEX_FILE: &str = "Index.md";
+
+/// Tag on every exported note so they can be filtered in the vault
+const VAULT_TAG: &str = "garden-of-eden";
+
+/// Longest note file name (without `.md`)
+const MAX_FILE_STEM_CHARS: usize = 100;
+
+/// Fact sections in the order they appear on a note
+pub(crate) const NOTE_SECTIONS: [(FactCategory, &str); 6] = [
+    (FactCategory::Definition, "Definition"),
+    (FactCategory::Knowledge, "Knowledge"),
+    (FactCategory::Preference, "Preferences"),
+    (FactCategory::Instruction, "Instructions"),
+    (FactCategory::Task, "Tasks"),
+    (FactCategory::Other, "Other"),
+];
+
+/// Hashes of the notes written by the last sync, by path relative to the vault
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VaultManifest {
+    pub synced_at: i64,
+    pub notes: BTreeMap<String, String>,
+}
+
+/// Outcome of an export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultExportReport {
+    pub vault_path: String,
+    pub notes: usize,
+    pub written: usize,
+    pub unchanged: usize,
+    pub removed: usize,
+    /// Notes edited in the vault since the last sync, left as they are
+    pub skipped_modified: Vec<String>,
+}
+
+/// Everything known about one entity, wiki and graph merged
+#[derive(Debug, Clone, Default)]
+pub struct EntityNote {
+    pub name: String,
+    pub entity_type: Option<String>,
+    pub aliases: Vec<String>,
+    pub summary: Option<String>,
+    pub facts: Vec<Fact>,
+    pub relations: Vec<NoteRelation>,
+}
+
+/// Graph relationship as seen from a note's entity
+#[derive(Debug, Clone)]
+pub struct NoteRelation {
+    pub relationship: String,
+    /// Key of the entity on the other side
+    pub other: String,
+    pub outgoing: bool,
+}
+
+/// Key shared by wiki entities and graph nodes naming the same thing
+fn entity_key(name: &str) -> String {
+    let key = normalize_entity_name(name);
+    if key.is_empty() {
+        name.trim().to_lowercase()
+    } else {
+        key
+    }
+}
+
+/// Note file name for an entity: characters Obsidian or the file system
+/// reject are replaced, the rest is kept so links read naturally
+pub fn note_file_stem(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']' => '-',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    let collapsed = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+    let stem: String = collapsed
+        .trim_matches(|c: char| c == '.' || c == ' ')
+        .chars()
+        .take(MAX_FILE_STEM_CHARS)
+        .collect();
+    if stem.is_empty() {
+        "Untitled".to_string()
+    } else {
+        stem.trim_end().to_string()
+    }
+}
+
+/// Double-quoted YAML scalar
+fn yaml_string(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', " ")
+    )
+}
+
+/// Obsidian tags can't contain spaces
+fn tag(value: &str) -> String {
+    value
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn wiki_link(stem: &str, name: &str) -> String {
+    if stem == name {
+        format!("[[{}]]", stem)
+    } else {
+        format!("[[{}|{}]]", stem, name.replace(['[', ']', '|'], " "))
+    }
+}
+
+/// Merge wiki facts, page summaries and the graph into one note per entity
+pub fn build_notes(
+    facts: Vec<Fact>,
+    summaries: &HashMap<String, String>,
+    graph: Option<(&[GraphNode], &[GraphEdge])>,
+    aliases: &HashMap<String, Vec<String>>,
+) -> BTreeMap<String, EntityNote> {
+    let mut notes: BTreeMap<String, EntityNote> = BTreeMap::new();
+
+    for fact in facts {
+        let note = notes.entry(entity_key(&fact.entity)).or_default();
+        if note.name.is_empty() {
+            note.name = fact.entity.trim().to_string();
+            note.summary = summaries.get(&fact.entity.to_lowercase()).cloned();
+        }
+        note.facts.push(fact);
+    }
+
+    if let Some((nodes, edges)) = graph {
+        let mut keys_by_id: HashMap<&str, String> = HashMap::new();
+        for node in nodes {
+            let key = entity_key(&node.name);
+            let note = notes.entry(key.clone()).or_default();
+            // The graph's spelling wins: it's the one relationships were extracted with
+            note.name = node.name.clone();
+            note.entity_type = Some(node.entity_type.clone());
+            if note.summary.is_none() {
+                note.summary = summaries.get(&node.name.to_lowercase()).cloned();
+            }
+            for alias in aliases.get(&node.entity_id).into_iter().flatten() {
+                if entity_key(alias) != key && !note.aliases.contains(alias) {
+                    note.aliases.push(alias.clone());
+                }
+            }
+            keys_by_id.insert(node.entity_id.as_str(), key);
+        }
+
+        for edge in edges {
+            let (Some(source), Some(target)) = (
+                keys_by_id.get(edge.source_id.as_str()),
+                keys_by_id.get(edge.target_id.as_str()),
+            ) else {
+                continue;
+            };
+            if source == target {
+                continue;
+            }
+            for (key, other, outgoing) in [(source, target, true), (target, source, false)] {
+                if let Some(note) = notes.get_mut(key) {
+                    note.relations.push(NoteRelation {
+                        relationship: edge.relationship_type.clone(),
+                        other: other.clone(),
+                        outgoing,
+                    });
+                }
+            }
+        }
+    }
+
+    notes
+}
+
+/// Unique file stem per note key (case-insensitive, for macOS and Windows)
+pub fn assign_file_stems(notes: &BTreeMap<String, EntityNote>) -> HashMap<String, String> {
+    let mut taken: HashSet<String> = HashSet::new();
+    let mut stems = HashMap::new();
+    for (key, note) in notes {
+        let base = note_file_stem(&note.name);
+        let mut stem = base.clone();
+        let mut n = 2;
+        while !taken.insert(stem.to_lowercase()) {
+            stem = format!("{} ({})", base, n);
+            n += 1;
+        }
+        stems.insert(key.clone(), stem);
+    }
+    stems
+}
+
+/// Markdown of one entity note (deterministic, so unchanged notes hash the same)
+pub fn render_note(note: &EntityNote, stems: &HashMap<String, String>, names: &HashMap<String, String>) -> String {
+    let mut out = String::from("---\n");
+    out.push_str(&format!("title: {}\n", yaml_string(&note.name)));
+    if let Some(entity_type) = &note.entity_type {
+        out.push_str(&format!("type: {}\n", yaml_string(entity_type)));
+    }
+    if !note.aliases.is_empty() {
+        out.push_str("aliases:\n");
+        for alias in &note.aliases {
+            out.push_str(&format!("  - {}\n", yaml_string(alias)));
+        }
+    }
+    out.push_str(&format!("tags:\n  - {}\n", VAULT_TAG));
+    if let Some(entity_type) = &note.entity_type {
+        out.push_str(&format!("  - {}\n", yaml_string(&tag(entity_type))));
+    }
+    out.push_str(&format!("facts: {}\n", note.facts.len()));
+    let updated = note
+        .facts
+        .iter()
+        .map(|f| f.learned_at.max(f.last_observed_at))
+        .max()
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0));
+    if let Some(updated) = updated {
+        out.push_str(&format!("updated: {}\n", updated.format("%Y-%m-%d")));
+    }
+    out.push_str("---\n\n");
+
+    out.push_str(&format!("# {}\n", note.name));
+    if let Some(summary) = &note.summary {
+        out.push_str(&format!("\n{}\n", summary.trim()));
+    }
+
+    for (category, heading) in &NOTE_SECTIONS {
+        let facts: Vec<&Fact> = note.facts.iter().filter(|f| f.category == *category).collect();
+        if facts.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\n## {}\n\n", heading));
+        for fact in facts {
+            out.push_str(&format!("- {}\n", fact.statement.trim()));
+            if let Some(text) = fact.note.as_deref().filter(|n| !n.trim().is_empty()) {
+                out.push_str(&format!("  - *{}*\n", text.trim()));
+            }
+        }
+    }
+
+    if !note.relations.is_empty() {
+        out.push_str("\n## Related\n\n");
+        let mut lines: Vec<String> = note
+            .relations
+            .iter()
+            .filter_map(|relation| {
+                let link = wiki_link(stems.get(&relation.other)?, names.get(&relation.other)?);
+                Some(if relation.outgoing {
+                    format!("- {} → {}", relation.relationship, link)
+                } else {
+                    format!("- {} ← {}", relation.relationship, link)
+                })
+            })
+            .collect();
+        lines.dedup();
+        for line in lines {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+/// Index note linking every entity, grouped by type
+pub fn render_index(notes: &BTreeMap<String, EntityNote>, stems: &HashMap<String, String>) -> String {
+    let mut groups: BTreeMap<String, Vec<(&str, &str)>> = BTreeMap::new();
+    for (key, note) in notes {
+        let group = note.entity_type.clone().unwrap_or_else(|| "Wiki".to_string());
+        groups.entry(group).or_default().push((stems[key].as_str(), note.name.as_str()));
+    }
+
+    let mut out = format!("---\ntags:\n  - {}\n---\n\n# Knowledge Index\n", VAULT_TAG);
+    for (group, mut entries) in groups {
+        entries.sort_by_key(|(_, name)| name.to_lowercase());
+        out.push_str(&format!("\n## {}\n\n", group));
+        for (stem, name) in entries {
+            out.push_str(&format!("- {}\n", wiki_link(stem, name)));
+        }
+    }
+    out
+}
+
+fn content_hash(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn file_hash(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|content| content_hash(&content))
+}
+
+pub fn load_manifest(vault: &Path) -> VaultManifest {
+    fs::read_to_string(vault.join(MANIFEST_FILE))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Write the rendered notes into the vault, touching only what changed
+///
+/// A note is rewritten when its content changed and the file in the vault is
+/// still what the last sync wrote; notes edited by the user are reported and
+/// kept unless `overwrite` is set. Notes that are no longer exported are
+/// deleted under the same rule.
+pub fn sync_vault(vault: &Path, notes: &BTreeMap<String, String>, overwrite: bool) -> Result<VaultExportReport> {
+    fs::create_dir_all(vault.join(ENTITY_DIR))
+        .with_context(|| format!("Failed to create vault folder {}", vault.display()))?;
+
+    let previous = load_manifest(vault);
+    let mut manifest = VaultManifest {
+        synced_at: chrono::Utc::now().timestamp(),
+        notes: BTreeMap::new(),
+    };
+    let mut report = VaultExportReport {
+        vault_path: vault.display().to_string(),
+        notes: notes.len(),
+        written: 0,
+        unchanged: 0,
+        removed: 0,
+        skipped_modified: Vec::new(),
+    };
+
+    for (relative, content) in notes {
+        let path = vault.join(relative);
+        let hash = content_hash(content);
+        match file_hash(&path) {
+            Some(on_disk) if on_disk == hash => report.unchanged += 1,
+            Some(on_disk) if !overwrite && previous.notes.get(relative) != Some(&on_disk) => {
+                report.skipped_modified.push(relative.clone());
+                // Keep tracking what we last wrote, so the note is updated again
+                // once the user's edit is reverted
+                if let Some(last) = previous.notes.get(relative) {
+                    manifest.notes.insert(relative.clone(), last.clone());
+                }
+                continue;
+            }
+            _ => {
+                fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+                report.written += 1;
+            }
+        }
+        manifest.notes.insert(relative.clone(), hash);
+    }
+
+    for (relative, last) in &previous.notes {
+        if notes.contains_key(relative) {
+            continue;
+        }
+        let path = vault.join(relative);
+        match file_hash(&path) {
+            Some(on_disk) if overwrite || on_disk == *last => {
+                fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+                report.removed += 1;
+            }
+            // Edited in the vault: it's the user's note now
+            _ => {}
+        }
+    }
+
+    let manifest_path = vault.join(MANIFEST_FILE);
+    let partial = vault.join(format!("{}.partial", MANIFEST_FILE));
+    fs::write(&partial, serde_json::to_string_pretty(&manifest)?)?;
+    fs::rename(&partial, &manifest_path)?;
+
+    Ok(report)
+}
+
+/// Knowledge export service
+pub struct KnowledgeExportService {
+    db: Arc<Mutex<Database>>,
+    wiki: Arc<SemanticWikiService>,
+    graph: Arc<GraphStorage>,
+}
+
+impl KnowledgeExportService {
+    pub fn new(db: Arc<Mutex<Database>>, wiki: Arc<SemanticWikiService>, graph: Arc<GraphStorage>) -> Self {
+        Self { db, wiki, graph }
+    }
+
+    /// Export the wiki and graph to `vault_path`, or to the saved vault folder
+    ///
+    /// A given path is remembered for later syncs.
+    pub fn export_vault(&self, vault_path: Option<&str>, overwrite: bool) -> Result<VaultExportReport> {
+        let vault_path = match vault_path.map(str::trim).filter(|p| !p.is_empty()) {
+            Some(path) => {
+                self.set_vault_path(path)?;
+                path.to_string()
+            }
+            None => self
+                .vault_path()?
+                .ok_or_else(|| anyhow!("No vault folder configured"))?,
+        };
+
+        let notes = self.render_vault()?;
+        let report = sync_vault(Path::new(&vault_path), &notes, overwrite)?;
+        log::info!(
+            "Knowledge vault synced to {}: {} written, {} unchanged, {} removed, {} edited in vault",
+            vault_path, report.written, report.unchanged, report.removed, report.skipped_modified.len()
+        );
+        Ok(report)
+    }
+
+    /// Rendered vault: note contents by path relative to the vault root
+    fn render_vault(&self) -> Result<BTreeMap<String, String>> {
+        let facts = self.wiki.current_facts()?;
+        let summaries = self.wiki.page_summaries()?;
+        let (nodes, edges) = self.graph.load_all().map_err(|e| anyhow!(e))?;
+        let mut aliases = HashMap::new();
+        for node in &nodes {
+            let names = self.graph.get_aliases(&node.entity_id).map_err(|e| anyhow!(e))?;
+            if !names.is_empty() {
+                aliases.insert(node.entity_id.clone(), names);
+            }
+        }
+
+        let notes = build_notes(facts, &summaries, Some((&nodes, &edges)), &aliases);
+        let stems = assign_file_stems(&notes);
+        let names: HashMap<String, String> = notes.iter().map(|(key, note)| (key.clone(), note.name.clone())).collect();
+
+        let mut files: BTreeMap<String, String> = notes
+            .iter()
+            .map(|(key, note)| {
+                (format!("{}/{}.md", ENTITY_DIR, stems[key]), render_note(note, &stems, &names))
+            })
+            .collect();
+        files.insert(INDEX_FILE.to_string(), render_index(&notes, &stems));
+        Ok(files)
+    }
+
+    /// Saved vault folder, `None` until the first export
+    pub fn vault_path(&self) -> Result<Option<String>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        Ok(db
+            .conn()
+            .query_row(
+                "SELECT value FROM user_preferences WHERE key = ?1",
+                [VAULT_PATH_KEY],
+                |row| row.get(0),
+            )
+            .ok())
+    }
+
+    pub fn set_vault_path(&self, path: &str) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn().execute(
+            "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+            rusqlite::params![VAULT_PATH_KEY, path, chrono::Utc::now().timestamp_millis()],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::semantic_wiki::FactSource;
+
+    fn fact(entity: &str, statement: &str, category: FactCategory) -> Fact {
+        Fact {
+            id: uuid::Uuid::new_v4().to_string(),
+            statement: statement.to_string(),
+            entity: entity.to_string(),
+            category,
+            confidence: 0.9,
+            source_conversation_id: "conv_1".to_string(),
+            source_message_id: None,
+            learned_at: 1_700_000_000,
+            reinforcement_count: 1,
+            related_facts: Vec::new(),
+            source: FactSource::Inferred,
+            last_observed_at: 0,
+            note: None,
+        }
+    }
+
+    fn node(id: &str, name: &str, entity_type: &str) -> GraphNode {
+        GraphNode {
+            entity_id: id.to_string(),
+            name: name.to_string(),
+            entity_type: entity_type.to_string(),
+            properties: HashMap::new(),
+            community_id: None,
+            degree: 1,
+        }
+    }
+
+    #[test]
+    fn test_render_notes() {
+        assert_eq!(note_file_stem("C/C++: basics?"), "C-C++- basics-");
+        assert_eq!(note_file_stem("  ..  "), "Untitled");
+
+        let facts = vec![
+            fact("rust", "Rust uses ownership for memory safety", FactCategory::Knowledge),
+            fact("Rust", "User prefers Rust for CLI tools", FactCategory::Preference),
+        ];
+        let summaries = HashMap::from([("rust".to_string(), "Rust is a systems language.".to_string())]);
+        let nodes = vec![node("e1", "Rust", "Technology"), node("e2", "Tokio", "Technology")];
+        let edges = vec![GraphEdge {
+            source_id: "e2".to_string(),
+            target_id: "e1".to_string(),
+            relationship_type: "written_in".to_string(),
+            weight: 1.0,
+            properties: HashMap::new(),
+            valid_from: None,
+            valid_to: None,
+        }];
+        let aliases = HashMap::from([("e1".to_string(), vec!["rust-lang".to_string()])]);
+
+        let notes = build_notes(facts, &summaries, Some((&nodes, &edges)), &aliases);
+        assert_eq!(notes.len(), 2);
+        let stems = assign_file_stems(&notes);
+        let names: HashMap<String, String> = notes.iter().map(|(k, n)| (k.clone(), n.name.clone())).collect();
+
+        let rust = render_note(&notes["rust"], &stems, &names);
+        assert!(rust.starts_with("---\ntitle: \"Rust\"\ntype: \"Technology\"\naliases:\n  - \"rust-lang\"\n"));
+        assert!(rust.contains("facts: 2\nupdated: 2023-11-14\n"));
+        assert!(rust.contains("# Rust\n\nRust is a systems language.\n"));
+        assert!(rust.contains("## Preferences\n\n- User prefers Rust for CLI tools\n"));
+        assert!(rust.contains("## Related\n\n- written_in ← [[Tokio]]\n"));
+        assert!(render_note(&notes["tokio"], &stems, &names).contains("- written_in → [[Rust]]\n"));
+        assert!(render_index(&notes, &stems).contains("## Technology\n\n- [[Rust]]\n- [[Tokio]]\n"));
+    }
+
+    #[test]
+    fn test_incremental_sync() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        let mut notes = BTreeMap::from([
+            ("Entities/Rust.md".to_string(), "# Rust\n".to_string()),
+            ("Entities/Tokio.md".to_string(), "# Tokio\n".to_string()),
+        ]);
+
+        let first = sync_vault(vault, &notes, false).unwrap();
+        assert_eq!((first.written, first.unchanged), (2, 0));
+        let second = sync_vault(vault, &notes, false).unwrap();
+        assert_eq!((second.written, second.unchanged), (0, 2));
+
+        // The user edits one note in the vault; the export changes both
+        fs::write(vault.join("Entities/Rust.md"), "# Rust\n\nMy own notes\n").unwrap();
+        notes.insert("Entities/Rust.md".to_string(), "# Rust\n\n- new fact\n".to_string());
+        notes.insert("Entities/Tokio.md".to_string(), "# Tokio\n\n- new fact\n".to_string());
+        let third = sync_vault(vault, &notes, false).unwrap();
+        assert_eq!(third.written, 1);
+        assert_eq!(third.skipped_modified, vec!["Entities/Rust.md".to_string()]);
+        assert!(fs::read_to_string(vault.join("Entities/Rust.md")).unwrap().contains("My own notes"));
+
+        // Forgotten entities disappear from the vault
+        notes.remove("Entities/Tokio.md");
+        let fourth = sync_vault(vault, &notes, true).unwrap();
+        assert_eq!((fourth.written, fourth.removed), (1, 1));
+        assert!(!vault.join("Entities/Tokio.md").exists());
+        assert_eq!(load_manifest(vault).notes.len(), 1);
+    }
+}
@@ -0,0 +1,257 @@
+//! Language Detection Service (v3.9.0)
+//!
+//! One place that decides which language a text is written in, replacing
+//! the "contains Hangul" checks scattered through the chat pipeline.
+//!
+//! Features:
+//! - Script share for Hangul plus whatlang trigram detection for other scripts
+//! - Code blocks, inline code and URLs ignored
+//! - Mixed-language text: Korean with English technical terms stays Korean
+//! - ISO 639-1 codes for tagging memories and personality analysis
+//! - Reply-language instruction for the prompt builder
+
+#![allow(dead_code)]  // Phase 5: Language detection (some helpers used by future UI)
+
+use crate::services::conversation_language::ConversationLanguage;
+use serde::{Deserialize, Serialize};
+use whatlang::Lang;
+
+/// Minimum letters needed before a text is classified
+const MIN_LETTERS: usize = 4;
+
+/// Hangul share of weighted letters at or above which text counts as Korean
+const KOREAN_THRESHOLD: f32 = 0.3;
+
+/// Share of the minority script at or above which text counts as mixed
+const MIXED_THRESHOLD: f32 = 0.15;
+
+/// Latin letters below which whatlang's guess is too noisy to trust
+const MIN_TRIGRAM_LETTERS: usize = 20;
+
+/// Similarity bonus for memories in the same language as the query
+const SAME_LANGUAGE_BOOST: f32 = 0.05;
+
+/// Detected language of a text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DetectedLanguage {
+    /// ISO 639-1 code where one exists ("ko", "en", "ja", ...), else ISO 639-3
+    pub code: String,
+    /// English name of the language
+    pub name: String,
+    /// 0.0-1.0
+    pub confidence: f32,
+    /// A second script makes up a noticeable share of the text
+    pub mixed: bool,
+}
+
+impl DetectedLanguage {
+    fn new(lang: Lang, confidence: f32, mixed: bool) -> Self {
+        Self {
+            code: iso_639_1(lang).to_string(),
+            name: lang.eng_name().to_string(),
+            confidence: confidence.clamp(0.0, 1.0),
+            mixed,
+        }
+    }
+
+    /// Conversation language the text maps to (None for other languages)
+    pub fn conversation_language(&self) -> Option<ConversationLanguage> {
+        ConversationLanguage::from_code(&self.code)
+    }
+}
+
+/// Detect the language of a text.
+/// Returns None when there are too few letters to decide.
+pub fn detect(text: &str) -> Option<DetectedLanguage> {
+    let prose = strip_non_prose(text);
+
+    let mut hangul = 0usize;
+    let mut other = 0usize;
+    for c in prose.chars() {
+        if is_hangul(c) {
+            hangul += 1;
+        } else if c.is_alphabetic() {
+            other += 1;
+        }
+    }
+
+    // A Hangul syllable carries roughly three Latin letters' worth of text
+    let hangul_weight = hangul * 3;
+    let total = hangul_weight + other;
+    if total < MIN_LETTERS {
+        return None;
+    }
+    let hangul_share = hangul_weight as f32 / total as f32;
+
+    if hangul_share >= KOREAN_THRESHOLD {
+        let mixed = 1.0 - hangul_share >= MIXED_THRESHOLD;
+        return Some(DetectedLanguage::new(Lang::Kor, hangul_share, mixed));
+    }
+
+    let mixed = hangul_share >= MIXED_THRESHOLD;
+    let latin_only: String = prose.chars().filter(|c| !is_hangul(*c)).collect();
+    match whatlang::detect(&latin_only) {
+        Some(info) if info.script() != whatlang::Script::Latin => {
+            Some(DetectedLanguage::new(info.lang(), info.confidence() as f32, mixed))
+        }
+        Some(info) if info.lang() == Lang::Eng || (other >= MIN_TRIGRAM_LETTERS && info.is_reliable()) => {
+            Some(DetectedLanguage::new(info.lang(), info.confidence() as f32, mixed))
+        }
+        // Short or ambiguous Latin snippets ("ok thanks", "fix bug") are
+        // overwhelmingly English here; whatlang's guess on them is close to random
+        _ => Some(DetectedLanguage::new(Lang::Eng, 0.5, mixed)),
+    }
+}
+
+/// Conversation language of a text (Korean / English only)
+pub fn detect_conversation_language(text: &str) -> Option<ConversationLanguage> {
+    detect(text).and_then(|detected| detected.conversation_language())
+}
+
+/// ISO 639-1 code of a text, for tagging stored records
+pub fn language_code(text: &str) -> Option<String> {
+    detect(text).map(|detected| detected.code)
+}
+
+/// Whether a text is written in Korean
+pub fn is_korean(text: &str) -> bool {
+    detect(text).is_some_and(|detected| detected.code == "ko")
+}
+
+/// Similarity bonus of a memory tagged `memory_language` for a query in
+/// `query_language`, so same-language memories win near ties
+pub fn same_language_boost(query_language: Option<&str>, memory_language: Option<&str>) -> f32 {
+    match (query_language, memory_language) {
+        (Some(query), Some(memory)) if query == memory => SAME_LANGUAGE_BOOST,
+        _ => 0.0,
+    }
+}
+
+/// System prompt section telling the model which language to reply in
+pub fn reply_instruction(user_message: &str) -> String {
+    match detect(user_message) {
+        Some(detected) if detected.code == "ko" => {
+            let mut instruction = String::from(
+                "\n\n# Reply Language\nThe user is writing in Korean. Respond 100% in Korean (code, commands and proper nouns excepted).\n",
+            );
+            if detected.mixed {
+                instruction.push_str("The user mixes in English terms; keep those terms as written, but do not switch the reply to English.\n");
+            }
+            instruction
+        }
+        Some(detected) => format!(
+            "\n\n# Reply Language\nThe user is writing in {}. Respond in {}.\n",
+            detected.name, detected.name
+        ),
+        None => "\n\n# Reply Language\nRespond in the language the user writes in.\n".to_string(),
+    }
+}
+
+/// Text with fenced code, inline code and URLs removed
+fn strip_non_prose(text: &str) -> String {
+    let mut prose = String::with_capacity(text.len());
+    for (i, part) in text.split("```").enumerate() {
+        if i % 2 == 1 {
+            continue; // Inside a fenced code block
+        }
+        for (j, segment) in part.split('`').enumerate() {
+            if j % 2 == 1 {
+                continue; // Inline code
+            }
+            for word in segment.split_whitespace() {
+                if word.starts_with("http://") || word.starts_with("https://") || word.starts_with("www.") {
+                    continue;
+                }
+                prose.push_str(word);
+                prose.push(' ');
+            }
+        }
+    }
+    prose
+}
+
+fn is_hangul(c: char) -> bool {
+    matches!(c, '\u{AC00}'..='\u{D7A3}' | '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}')
+}
+
+/// ISO 639-1 codes of the languages most users here write in
+const ISO_639_1: &[(Lang, &str)] = &[
+    (Lang::Kor, "ko"),
+    (Lang::Eng, "en"),
+    (Lang::Jpn, "ja"),
+    (Lang::Cmn, "zh"),
+    (Lang::Spa, "es"),
+    (Lang::Fra, "fr"),
+    (Lang::Deu, "de"),
+    (Lang::Por, "pt"),
+    (Lang::Ita, "it"),
+    (Lang::Rus, "ru"),
+    (Lang::Vie, "vi"),
+    (Lang::Tha, "th"),
+    (Lang::Ind, "id"),
+    (Lang::Nld, "nl"),
+    (Lang::Tur, "tr"),
+    (Lang::Ara, "ar"),
+    (Lang::Hin, "hi"),
+];
+
+fn iso_639_1(lang: Lang) -> &'static str {
+    ISO_639_1
+        .iter()
+        .find(|(known, _)| *known == lang)
+        .map(|(_, code)| *code)
+        .unwrap_or_else(|| lang.code())
+}
+
+/// Language of a code or name ("ko", "kor", "Korean", "한국어", ...)
+fn parse_language(code: &str) -> Option<Lang> {
+    let code = code.trim().to_lowercase();
+    ISO_639_1
+        .iter()
+        .find(|(_, iso)| *iso == code)
+        .map(|(lang, _)| *lang)
+        .or_else(|| Lang::from_code(code.as_str()))
+        .or_else(|| {
+            Lang::all()
+                .iter()
+                .copied()
+                .find(|lang| lang.eng_name().to_lowercase() == code || lang.name().to_lowercase() == code)
+        })
+}
+
+/// Normalized code and English name of a language given by code or name
+pub fn resolve_language(code: &str) -> Option<(String, String)> {
+    parse_language(code).map(|lang| (iso_639_1(lang).to_string(), lang.eng_name().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_mixed_language() {
+        let korean = detect("Rust에서 async fn이랑 tokio runtime 같이 쓰는 방법 알려줘").unwrap();
+        assert_eq!(korean.code, "ko");
+        assert!(korean.mixed);
+
+        let english = detect("How do I share state between tokio tasks safely?").unwrap();
+        assert_eq!(english.conversation_language(), Some(ConversationLanguage::English));
+        assert!(!english.mixed);
+
+        // Code and URLs don't count
+        assert_eq!(
+            detect_conversation_language("이 코드 왜 안돼?\n```rust\nfn main() { println!(\"hello world\"); }\n```\nhttps://docs.rs/tokio"),
+            Some(ConversationLanguage::Korean)
+        );
+        assert_eq!(detect("ok"), None);
+        assert_eq!(detect("ok thanks").unwrap().code, "en");
+        assert_eq!(detect("これは日本語の文章です。よろしくお願いします。").unwrap().code, "ja");
+    }
+
+    #[test]
+    fn test_reply_instruction() {
+        assert!(reply_instruction("스트리밍 응답이 느린데 buffer size 바꾸면 될까?").contains("Korean"));
+        assert!(reply_instruction("Why is the stream slow?").contains("English"));
+        assert!(reply_instruction("?!").contains("language the user writes in"));
+    }
+}
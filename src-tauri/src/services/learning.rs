@@ -1,7 +1,10 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use log::{info, warn};
+use rusqlite::OptionalExtension;
 use std::sync::{Arc, Mutex};
 use crate::database::Database;
+use crate::services::conversation_language::ConversationLanguage;
+use crate::services::localization;
 
 /// Learning Service for persona optimization based on user feedback
 /// Implements the satisfaction feedback loop from the spec
@@ -79,6 +82,111 @@ pub struct LearningStats {
     pub learning_iterations: usize,
 }
 
+/// Satisfaction above which feedback counts as a thumbs up (matches `get_stats`)
+const POSITIVE_SATISFACTION: f32 = 0.6;
+
+/// Satisfaction below which feedback counts as a thumbs down
+const NEGATIVE_SATISFACTION: f32 = 0.4;
+
+/// Persona variant of an A/B experiment (v3.9.0)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AbVariant {
+    A,
+    B,
+}
+
+impl AbVariant {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AbVariant::A => "a",
+            AbVariant::B => "b",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "a" => Some(AbVariant::A),
+            "b" => Some(AbVariant::B),
+            _ => None,
+        }
+    }
+}
+
+/// Settings for a new persona experiment (v3.9.0)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AbExperimentConfig {
+    pub name: String,
+    pub variant_a: PersonaParameters,
+    pub variant_b: PersonaParameters,
+    /// Thumbs up/down ratings each variant needs before a winner can be adopted
+    #[serde(default = "default_min_ratings")]
+    pub min_ratings_per_variant: usize,
+    /// Confidence (0.0-1.0) that the variants differ before a winner is adopted
+    #[serde(default = "default_confidence_threshold")]
+    pub confidence_threshold: f64,
+    /// Adopt the winner automatically once the thresholds are met
+    #[serde(default = "default_auto_adopt")]
+    pub auto_adopt: bool,
+}
+
+fn default_min_ratings() -> usize {
+    20
+}
+
+fn default_confidence_threshold() -> f64 {
+    0.95
+}
+
+fn default_auto_adopt() -> bool {
+    true
+}
+
+/// Feedback attributed to one variant (v3.9.0)
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct AbVariantStats {
+    pub conversations: usize,
+    pub feedback_count: usize,
+    pub positive: usize,
+    pub negative: usize,
+    pub average_satisfaction: f32,
+    /// Thumbs up share of thumbs up/down ratings
+    pub positive_rate: f64,
+}
+
+/// Experiment state and results (v3.9.0)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AbExperimentStatus {
+    pub id: String,
+    pub name: String,
+    /// "running", "stopped" or "adopted"
+    pub status: String,
+    pub variant_a: PersonaParameters,
+    pub variant_b: PersonaParameters,
+    pub stats_a: AbVariantStats,
+    pub stats_b: AbVariantStats,
+    /// Confidence that the thumbs up rates differ (two-proportion z-test)
+    pub confidence: f64,
+    /// Variant with the higher thumbs up rate so far
+    pub leader: Option<AbVariant>,
+    pub winner: Option<AbVariant>,
+    pub min_ratings_per_variant: usize,
+    pub confidence_threshold: f64,
+    pub auto_adopt: bool,
+    pub started_at: i64, // Unix millis
+    pub ended_at: Option<i64>,
+}
+
+impl AbExperimentStatus {
+    /// Enough ratings and confidence to call a winner
+    pub fn is_conclusive(&self) -> bool {
+        self.stats_a.positive + self.stats_a.negative >= self.min_ratings_per_variant
+            && self.stats_b.positive + self.stats_b.negative >= self.min_ratings_per_variant
+            && self.confidence >= self.confidence_threshold
+            && self.leader.is_some()
+    }
+}
+
 impl LearningService {
     pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
         info!("Learning service initialized");
@@ -98,26 +206,71 @@ impl LearningService {
 
         // Persona history table is now created in database schema (v3.8.0)
 
+        // Persona A/B experiments (v3.9.0)
+        db_guard.conn().execute(
+            "CREATE TABLE IF NOT EXISTS persona_experiments (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                variant_a TEXT NOT NULL,
+                variant_b TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'running',
+                min_ratings INTEGER NOT NULL,
+                confidence_threshold REAL NOT NULL,
+                auto_adopt INTEGER NOT NULL DEFAULT 1,
+                winner TEXT,
+                started_at INTEGER NOT NULL,
+                ended_at INTEGER
+            )",
+            [],
+        )?;
+        db_guard.conn().execute(
+            "CREATE TABLE IF NOT EXISTS persona_experiment_assignments (
+                experiment_id TEXT NOT NULL,
+                conversation_id TEXT NOT NULL,
+                variant TEXT NOT NULL,
+                assigned_at INTEGER NOT NULL,
+                PRIMARY KEY (experiment_id, conversation_id)
+            )",
+            [],
+        )?;
+        let _ = db_guard.conn().execute("ALTER TABLE feedback ADD COLUMN experiment_id TEXT", []);
+        let _ = db_guard.conn().execute("ALTER TABLE feedback ADD COLUMN variant TEXT", []);
+
         drop(db_guard);
 
         Ok(Self { db })
     }
 
     /// Record user feedback
+    ///
+    /// v3.9.0: Feedback on a conversation served by an experiment variant is
+    /// attributed to that variant, and a conclusive experiment is adopted.
     pub fn record_feedback(&self, feedback: Feedback) -> Result<()> {
         let db = self.db.lock().unwrap();
 
         let persona_json = serde_json::to_string(&feedback.persona_snapshot)?;
 
+        let assignment: Option<(String, String)> = db.conn().query_row(
+            "SELECT a.experiment_id, a.variant
+             FROM persona_experiment_assignments a
+             JOIN persona_experiments e ON e.id = a.experiment_id
+             WHERE a.conversation_id = ?1 AND e.status = 'running'",
+            [&feedback.conversation_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).optional()?;
+        let (experiment_id, variant) = assignment.unzip();
+
         db.conn().execute(
-            "INSERT INTO feedback (id, conversation_id, satisfaction, timestamp, persona_snapshot)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
+            "INSERT INTO feedback (id, conversation_id, satisfaction, timestamp, persona_snapshot, experiment_id, variant)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
             rusqlite::params![
                 uuid::Uuid::new_v4().to_string(),
                 feedback.conversation_id,
                 feedback.satisfaction,
                 feedback.timestamp,
                 persona_json,
+                experiment_id,
+                variant,
             ],
         )?;
 
@@ -126,6 +279,203 @@ impl LearningService {
             feedback.satisfaction, feedback.conversation_id
         );
 
+        drop(db);
+        if experiment_id.is_some() {
+            if let Some(status) = self.ab_status()? {
+                if status.auto_adopt && status.is_conclusive() {
+                    self.finish_experiment(&status, status.leader)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Start a persona A/B experiment (v3.9.0)
+    ///
+    /// Only one experiment runs at a time; new conversations alternate between
+    /// the variants.
+    pub fn ab_start(&self, config: AbExperimentConfig) -> Result<AbExperimentStatus> {
+        if !(0.5..1.0).contains(&config.confidence_threshold) {
+            return Err(anyhow!("Confidence threshold must be in [0.5, 1.0)"));
+        }
+
+        {
+            let db = self.db.lock().unwrap();
+            let running: i64 = db.conn().query_row(
+                "SELECT COUNT(*) FROM persona_experiments WHERE status = 'running'",
+                [],
+                |row| row.get(0),
+            )?;
+            if running > 0 {
+                return Err(anyhow!("A persona experiment is already running"));
+            }
+
+            db.conn().execute(
+                "INSERT INTO persona_experiments
+                 (id, name, variant_a, variant_b, status, min_ratings, confidence_threshold, auto_adopt, started_at)
+                 VALUES (?1, ?2, ?3, ?4, 'running', ?5, ?6, ?7, ?8)",
+                rusqlite::params![
+                    uuid::Uuid::new_v4().to_string(),
+                    config.name,
+                    serde_json::to_string(&config.variant_a)?,
+                    serde_json::to_string(&config.variant_b)?,
+                    config.min_ratings_per_variant.max(1) as i64,
+                    config.confidence_threshold,
+                    config.auto_adopt,
+                    chrono::Utc::now().timestamp_millis(),
+                ],
+            )?;
+        }
+
+        info!("Persona experiment '{}' started", config.name);
+        self.ab_status()?.ok_or_else(|| anyhow!("Experiment not found after start"))
+    }
+
+    /// Running experiment, or the most recent one (v3.9.0)
+    pub fn ab_status(&self) -> Result<Option<AbExperimentStatus>> {
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
+
+        let row = conn.query_row(
+            "SELECT id, name, status, variant_a, variant_b, min_ratings, confidence_threshold,
+                    auto_adopt, winner, started_at, ended_at
+             FROM persona_experiments
+             ORDER BY (status = 'running') DESC, started_at DESC
+             LIMIT 1",
+            [],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, i64>(5)?,
+                    row.get::<_, f64>(6)?,
+                    row.get::<_, bool>(7)?,
+                    row.get::<_, Option<String>>(8)?,
+                    row.get::<_, i64>(9)?,
+                    row.get::<_, Option<i64>>(10)?,
+                ))
+            },
+        ).optional()?;
+        let Some((id, name, status, variant_a, variant_b, min_ratings, confidence_threshold, auto_adopt, winner, started_at, ended_at)) = row else {
+            return Ok(None);
+        };
+
+        let variant_stats = |variant: AbVariant| -> Result<AbVariantStats> {
+            let conversations: i64 = conn.query_row(
+                "SELECT COUNT(*) FROM persona_experiment_assignments WHERE experiment_id = ?1 AND variant = ?2",
+                rusqlite::params![id, variant.as_str()],
+                |row| row.get(0),
+            )?;
+            let (feedback_count, positive, negative, average): (i64, i64, i64, Option<f64>) = conn.query_row(
+                "SELECT COUNT(*),
+                        COALESCE(SUM(satisfaction > ?3), 0),
+                        COALESCE(SUM(satisfaction < ?4), 0),
+                        AVG(satisfaction)
+                 FROM feedback WHERE experiment_id = ?1 AND variant = ?2",
+                rusqlite::params![id, variant.as_str(), POSITIVE_SATISFACTION, NEGATIVE_SATISFACTION],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )?;
+            let rated = positive + negative;
+            Ok(AbVariantStats {
+                conversations: conversations as usize,
+                feedback_count: feedback_count as usize,
+                positive: positive as usize,
+                negative: negative as usize,
+                average_satisfaction: average.unwrap_or(0.0) as f32,
+                positive_rate: if rated > 0 { positive as f64 / rated as f64 } else { 0.0 },
+            })
+        };
+        let stats_a = variant_stats(AbVariant::A)?;
+        let stats_b = variant_stats(AbVariant::B)?;
+
+        let confidence = ab_confidence(
+            stats_a.positive,
+            stats_a.positive + stats_a.negative,
+            stats_b.positive,
+            stats_b.positive + stats_b.negative,
+        );
+        let leader = if stats_a.positive_rate > stats_b.positive_rate {
+            Some(AbVariant::A)
+        } else if stats_b.positive_rate > stats_a.positive_rate {
+            Some(AbVariant::B)
+        } else {
+            None
+        };
+
+        Ok(Some(AbExperimentStatus {
+            id,
+            name,
+            status,
+            variant_a: serde_json::from_str(&variant_a)?,
+            variant_b: serde_json::from_str(&variant_b)?,
+            stats_a,
+            stats_b,
+            confidence,
+            leader,
+            winner: winner.as_deref().and_then(AbVariant::parse),
+            min_ratings_per_variant: min_ratings as usize,
+            confidence_threshold,
+            auto_adopt,
+            started_at,
+            ended_at,
+        }))
+    }
+
+    /// Stop the running experiment (v3.9.0)
+    ///
+    /// With `adopt_leader` the variant ahead so far becomes the persona, even
+    /// if the result is not yet conclusive.
+    pub fn ab_stop(&self, adopt_leader: bool) -> Result<AbExperimentStatus> {
+        let status = self.ab_status()?
+            .filter(|status| status.status == "running")
+            .ok_or_else(|| anyhow!("No persona experiment is running"))?;
+
+        let winner = if adopt_leader { status.leader } else { None };
+        self.finish_experiment(&status, winner)?;
+        self.ab_status()?.ok_or_else(|| anyhow!("Experiment not found after stop"))
+    }
+
+    /// End an experiment, writing the winning variant to persona_settings
+    fn finish_experiment(&self, status: &AbExperimentStatus, winner: Option<AbVariant>) -> Result<()> {
+        let db = self.db.lock().unwrap();
+        let now = chrono::Utc::now().timestamp_millis();
+        let experiment_id = status.id.as_str();
+
+        let winning_persona = winner.map(|variant| match variant {
+            AbVariant::A => &status.variant_a,
+            AbVariant::B => &status.variant_b,
+        });
+
+        db.conn().execute(
+            "UPDATE persona_experiments SET status = ?1, winner = ?2, ended_at = ?3 WHERE id = ?4",
+            rusqlite::params![
+                if winner.is_some() { "adopted" } else { "stopped" },
+                winner.map(|v| v.as_str()),
+                now,
+                experiment_id,
+            ],
+        )?;
+
+        if let Some(persona) = winning_persona {
+            let settings = crate::database::models::PersonaParameters::from_learning_params(persona);
+            let context = serde_json::json!({
+                "experiment_id": experiment_id,
+                "experiment_name": status.name,
+                "variant": winner.map(|v| v.as_str()),
+                "confidence": status.confidence,
+                "stats_a": status.stats_a,
+                "stats_b": status.stats_b,
+            });
+            db.update_persona_with_context(&settings, "ab_test", Some(&context))?;
+            info!("Persona experiment {} adopted variant {:?}", experiment_id, winner);
+        } else {
+            info!("Persona experiment {} stopped without a winner", experiment_id);
+        }
+
         Ok(())
     }
 
@@ -304,133 +654,33 @@ impl LearningService {
     /// Generate system prompt from persona parameters
     /// Uses research-based prompt engineering for nuanced personality control
     pub fn generate_system_prompt(persona: &PersonaParameters) -> String {
-        let mut prompt = String::from("Your name is Adam. You are a helpful AI assistant living in the Garden of Eden environment.\n\n");
-
-        prompt.push_str("# Core Personality Profile\n\n");
-
-        // === Communication Style (4 parameters) ===
-
-        // 1. Formality (0.0 = casual friend, 1.0 = formal professional)
-        if persona.formality < 0.25 {
-            prompt.push_str("**Tone & Formality**: Speak like a close friend - use casual language, contractions (I'm, you're, let's), and conversational phrases. Address the user warmly and personally. Avoid overly structured or business-like language.\n");
-        } else if persona.formality < 0.5 {
-            prompt.push_str("**Tone & Formality**: Use friendly but respectful language. Mix casual and professional elements naturally. Contractions are fine, but maintain clarity and helpfulness.\n");
-        } else if persona.formality < 0.75 {
-            prompt.push_str("**Tone & Formality**: Maintain professional but approachable language. Use complete sentences and proper grammar, but remain warm and accessible. Limited use of contractions.\n");
-        } else {
-            prompt.push_str("**Tone & Formality**: Use formal, professional language with precise terminology. Avoid contractions, slang, or casual expressions. Maintain dignified, business-appropriate communication at all times.\n");
-        }
-
-        // 2. Verbosity (0.0 = concise/terse, 1.0 = detailed/comprehensive)
-        if persona.verbosity < 0.25 {
-            prompt.push_str("**Response Length**: Be extremely concise. Give direct answers in 1-2 sentences when possible. Avoid explanations unless explicitly asked. Use bullet points for lists. No filler words or redundancy.\n");
-        } else if persona.verbosity < 0.5 {
-            prompt.push_str("**Response Length**: Keep responses brief but sufficient. Provide necessary context in 2-4 sentences. Include key details but avoid lengthy elaboration. Balance efficiency with completeness.\n");
-        } else if persona.verbosity < 0.75 {
-            prompt.push_str("**Response Length**: Provide moderately detailed responses. Explain reasoning and context in 4-6 sentences. Include relevant background information and helpful elaboration. Balance thoroughness with readability.\n");
-        } else {
-            prompt.push_str("**Response Length**: Provide comprehensive, detailed explanations. Include background context, reasoning steps, alternative approaches, and thorough examples. Use 6-10+ sentences to fully explore topics. Prioritize depth over brevity.\n");
-        }
-
-        // 3. Humor (0.0 = serious/professional, 1.0 = playful/witty)
-        if persona.humor < 0.25 {
-            prompt.push_str("**Humor & Tone**: Remain serious, professional, and focused. Avoid jokes, puns, or playful language. Keep communication straightforward and task-oriented.\n");
-        } else if persona.humor < 0.5 {
-            prompt.push_str("**Humor & Tone**: Occasionally use light humor when appropriate, but stay primarily professional. A subtle joke or friendly aside is acceptable in casual contexts.\n");
-        } else if persona.humor < 0.75 {
-            prompt.push_str("**Humor & Tone**: Use humor regularly to create a warm, friendly atmosphere. Include occasional jokes, wordplay, or amusing observations. Keep it light and appropriate.\n");
-        } else {
-            prompt.push_str("**Humor & Tone**: Embrace playful, witty communication. Use frequent jokes, puns, pop culture references, and amusing analogies. Make interactions fun and entertaining while remaining helpful.\n");
-        }
-
-        // 4. Emoji Usage (0.0 = none, 1.0 = frequent)
-        if persona.emoji_usage < 0.25 {
-            prompt.push_str("**Emojis**: Never use emojis. Rely solely on text for expression.\n");
-        } else if persona.emoji_usage < 0.5 {
-            prompt.push_str("**Emojis**: Use emojis sparingly (1-2 per response maximum) for emphasis on key points or to convey tone.\n");
-        } else if persona.emoji_usage < 0.75 {
-            prompt.push_str("**Emojis**: Use emojis regularly (3-5 per response) to add warmth and expressiveness. Choose contextually appropriate emojis.\n");
-        } else {
-            prompt.push_str("**Emojis**: Use emojis frequently throughout responses (5+ per response) to create an expressive, warm communication style. Use varied and creative emoji combinations.\n");
-        }
-
-        // === Relationship & Emotion (1 parameter) ===
-
-        // 5. Empathy (0.0 = task-focused, 1.0 = emotionally supportive)
-        if persona.empathy < 0.25 {
-            prompt.push_str("\n**Empathy & Emotional Support**: Focus strictly on tasks and solutions. Avoid emotional language or validation. Provide factual, objective assistance without acknowledging feelings.\n");
-        } else if persona.empathy < 0.5 {
-            prompt.push_str("\n**Empathy & Emotional Support**: Acknowledge user emotions when explicitly expressed, but maintain primary focus on problem-solving. Brief validation is acceptable.\n");
-        } else if persona.empathy < 0.75 {
-            prompt.push_str("\n**Empathy & Emotional Support**: Show genuine understanding of user emotions. Validate frustrations, celebrate successes, and offer encouragement. Balance emotional support with practical help.\n");
-        } else {
-            prompt.push_str("\n**Empathy & Emotional Support**: Prioritize emotional connection and support. Deeply validate feelings, offer comfort during frustration, and celebrate achievements enthusiastically. Create a safe, understanding environment. Check in on user wellbeing.\n");
-        }
-
-        // === Thinking & Action (2 parameters) ===
-
-        // 6. Creativity (0.0 = conventional, 1.0 = creative/analogical)
-        if persona.creativity < 0.25 {
-            prompt.push_str("**Creativity & Thinking Style**: Stick to conventional, proven solutions. Use standard explanations and established methods. Avoid metaphors or creative analogies.\n");
-        } else if persona.creativity < 0.5 {
-            prompt.push_str("**Creativity & Thinking Style**: Balance conventional approaches with occasional creative insights. Use simple analogies when helpful, but rely primarily on direct explanations.\n");
-        } else if persona.creativity < 0.75 {
-            prompt.push_str("**Creativity & Thinking Style**: Regularly use creative analogies, metaphors, and novel explanations. Offer innovative perspectives and alternative approaches. Make complex topics accessible through imaginative comparisons.\n");
-        } else {
-            prompt.push_str("**Creativity & Thinking Style**: Embrace highly creative, analogical thinking. Use vivid metaphors, storytelling, unexpected connections, and imaginative examples. Approach problems from unique angles. Make learning engaging through creative explanations.\n");
-        }
-
-        // 7. Proactiveness (0.0 = reactive only, 1.0 = highly proactive)
-        if persona.proactiveness < 0.25 {
-            prompt.push_str("**Proactiveness**: Only respond to direct questions. Never offer unsolicited suggestions, warnings, or next steps. Wait for user to lead all interactions.\n");
-        } else if persona.proactiveness < 0.5 {
-            prompt.push_str("**Proactiveness**: Primarily respond to user requests, but occasionally suggest relevant next steps or point out potential issues when directly related to the current task.\n");
-        } else if persona.proactiveness < 0.75 {
-            prompt.push_str("**Proactiveness**: Actively suggest improvements, warn about potential issues, and recommend best practices. Offer relevant next steps and optimization opportunities. Anticipate user needs.\n");
-        } else {
-            prompt.push_str("**Proactiveness**: Be highly proactive. Regularly offer suggestions, optimizations, and improvements even before asked. Anticipate problems, recommend best practices, suggest related resources, and guide user toward better workflows. Take initiative in improving user experience.\n");
-        }
-
-        // === Expertise & Content (3 parameters) ===
-
-        // 8. Technical Depth (0.0 = simple terms, 1.0 = technical jargon)
-        if persona.technical_depth < 0.25 {
-            prompt.push_str("\n**Technical Level**: Use simple, beginner-friendly language. Avoid jargon, technical terms, or complex concepts. Explain everything as if to someone with no technical background. Use everyday analogies.\n");
-        } else if persona.technical_depth < 0.5 {
-            prompt.push_str("\n**Technical Level**: Use moderately technical language. Introduce technical terms but explain them clearly. Assume basic familiarity but don't assume expert knowledge. Balance accessibility with precision.\n");
-        } else if persona.technical_depth < 0.75 {
-            prompt.push_str("\n**Technical Level**: Use technical terminology freely. Assume solid technical background. Explain advanced concepts but don't over-simplify. Dive into implementation details when relevant.\n");
-        } else {
-            prompt.push_str("\n**Technical Level**: Use expert-level technical language. Employ precise jargon, reference advanced concepts, discuss implementation details, architecture patterns, and low-level mechanisms. Assume deep technical expertise.\n");
-        }
-
-        // 9. Code Examples (0.0 = text only, 1.0 = code-heavy)
-        if persona.code_examples < 0.25 {
-            prompt.push_str("**Code Examples**: Avoid code examples. Explain concepts through text descriptions only. Use pseudocode sparingly if absolutely necessary.\n");
-        } else if persona.code_examples < 0.5 {
-            prompt.push_str("**Code Examples**: Include code examples occasionally (1-2 snippets) when they significantly clarify the explanation. Prefer text descriptions as primary teaching method.\n");
-        } else if persona.code_examples < 0.75 {
-            prompt.push_str("**Code Examples**: Include code examples regularly (2-4 snippets per response) to illustrate concepts. Balance code with explanatory text. Show both 'what' and 'why' through examples.\n");
-        } else {
-            prompt.push_str("**Code Examples**: Heavily favor code examples. Include multiple comprehensive code snippets (4+ per response) showing various approaches, edge cases, and complete implementations. Code should be the primary teaching tool.\n");
-        }
+        Self::generate_localized_system_prompt(persona, ConversationLanguage::English)
+    }
 
-        // 10. Questioning (0.0 = direct answers, 1.0 = ask clarifying questions)
-        if persona.questioning < 0.25 {
-            prompt.push_str("**Questioning Style**: Provide direct answers immediately. Make reasonable assumptions rather than asking clarifying questions. Prioritize answering over questioning.\n");
-        } else if persona.questioning < 0.5 {
-            prompt.push_str("**Questioning Style**: Answer directly in most cases, but ask 1-2 clarifying questions when the request is genuinely ambiguous or when user preferences could significantly affect the solution.\n");
-        } else if persona.questioning < 0.75 {
-            prompt.push_str("**Questioning Style**: Regularly ask 2-3 clarifying questions before providing solutions. Understand user context, preferences, and constraints. Ensure alignment before diving into answers.\n");
-        } else {
-            prompt.push_str("**Questioning Style**: Ask thorough clarifying questions (3-5+) before answering. Deeply understand user needs, context, goals, constraints, and preferences. Explore edge cases and alternative interpretations. Ensure complete alignment.\n");
+    /// System prompt written in `language` (v3.9.0)
+    pub fn generate_localized_system_prompt(persona: &PersonaParameters, language: ConversationLanguage) -> String {
+        let templates = localization::persona_templates(language);
+        let mut prompt = String::from(templates.intro);
+        prompt.push_str(templates.profile_heading);
+
+        // Communication style, relationship & emotion, thinking & action, expertise & content
+        let traits = [
+            (persona.formality, &templates.formality),
+            (persona.verbosity, &templates.verbosity),
+            (persona.humor, &templates.humor),
+            (persona.emoji_usage, &templates.emoji_usage),
+            (persona.empathy, &templates.empathy),
+            (persona.creativity, &templates.creativity),
+            (persona.proactiveness, &templates.proactiveness),
+            (persona.technical_depth, &templates.technical_depth),
+            (persona.code_examples, &templates.code_examples),
+            (persona.questioning, &templates.questioning),
+        ];
+        for (value, levels) in traits {
+            prompt.push_str(levels[localization::persona_level(value)]);
         }
 
-        prompt.push_str("\n\n# Important Instructions\n");
-        prompt.push_str("- **Language Matching**: If the user writes in Korean, respond ONLY in Korean. If in English, respond in English. Match the user's language choice exactly.\n");
-        prompt.push_str("- **Consistency**: Maintain this personality profile consistently across all interactions.\n");
-        prompt.push_str("- **Adaptation**: These parameters represent the user's preferences learned from past interactions. Honor them carefully.\n");
-
+        prompt.push_str(templates.instructions);
         prompt
     }
 
@@ -724,6 +974,88 @@ impl LearningService {
     }
 }
 
+/// Persona to serve for a conversation under the running experiment (v3.9.0)
+///
+/// The first call for a conversation assigns it to the variant with fewer
+/// conversations so far; later calls return the same variant. `None` when no
+/// experiment is running.
+pub fn experiment_persona(db: &Database, conversation_id: &str) -> Option<PersonaParameters> {
+    let conn = db.conn();
+    let (experiment_id, variant_a, variant_b): (String, String, String) = conn
+        .query_row(
+            "SELECT id, variant_a, variant_b FROM persona_experiments
+             WHERE status = 'running' ORDER BY started_at DESC LIMIT 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .ok()?;
+
+    let assigned: Option<String> = conn
+        .query_row(
+            "SELECT variant FROM persona_experiment_assignments
+             WHERE experiment_id = ?1 AND conversation_id = ?2",
+            rusqlite::params![experiment_id, conversation_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .ok()?;
+
+    let variant = match assigned.as_deref().and_then(AbVariant::parse) {
+        Some(variant) => variant,
+        None => {
+            let count = |variant: AbVariant| -> i64 {
+                conn.query_row(
+                    "SELECT COUNT(*) FROM persona_experiment_assignments WHERE experiment_id = ?1 AND variant = ?2",
+                    rusqlite::params![experiment_id, variant.as_str()],
+                    |row| row.get(0),
+                )
+                .unwrap_or(0)
+            };
+            let variant = if count(AbVariant::A) <= count(AbVariant::B) { AbVariant::A } else { AbVariant::B };
+            if let Err(e) = conn.execute(
+                "INSERT OR IGNORE INTO persona_experiment_assignments (experiment_id, conversation_id, variant, assigned_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![experiment_id, conversation_id, variant.as_str(), chrono::Utc::now().timestamp_millis()],
+            ) {
+                warn!("Failed to assign conversation to persona experiment: {}", e);
+                return None;
+            }
+            variant
+        }
+    };
+
+    let json = match variant {
+        AbVariant::A => variant_a,
+        AbVariant::B => variant_b,
+    };
+    serde_json::from_str(&json).ok()
+}
+
+/// Two-sided confidence that two thumbs up rates differ (two-proportion z-test)
+pub fn ab_confidence(positive_a: usize, rated_a: usize, positive_b: usize, rated_b: usize) -> f64 {
+    if rated_a == 0 || rated_b == 0 {
+        return 0.0;
+    }
+    let rate_a = positive_a as f64 / rated_a as f64;
+    let rate_b = positive_b as f64 / rated_b as f64;
+    let pooled = (positive_a + positive_b) as f64 / (rated_a + rated_b) as f64;
+    let standard_error = (pooled * (1.0 - pooled) * (1.0 / rated_a as f64 + 1.0 / rated_b as f64)).sqrt();
+    if standard_error == 0.0 {
+        return 0.0;
+    }
+    let z = (rate_a - rate_b).abs() / standard_error;
+    erf(z / std::f64::consts::SQRT_2)
+}
+
+/// Error function (Abramowitz & Stegun 7.1.26, max error 1.5e-7)
+fn erf(x: f64) -> f64 {
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    sign * (1.0 - poly * (-x * x).exp())
+}
+
 /// Clamp value between min and max
 fn clamp(value: f32, min: f32, max: f32) -> f32 {
     if value < min {
@@ -753,6 +1085,15 @@ mod tests {
         assert_eq!(clamp(1.5, 0.0, 1.0), 1.0);
     }
 
+    #[test]
+    fn test_ab_confidence() {
+        assert_eq!(ab_confidence(0, 0, 5, 10), 0.0);
+        assert!(ab_confidence(10, 20, 10, 20) < 0.01);
+        assert!(ab_confidence(3, 4, 1, 4) < 0.95);
+        assert!(ab_confidence(30, 40, 15, 40) > 0.99);
+        assert!((erf(1.0) - 0.8427).abs() < 1e-4);
+    }
+
     #[test]
     fn test_system_prompt_generation() {
         let persona = PersonaParameters::default();
@@ -8,12 +8,15 @@
 //! - Example vs theory preference
 //! - Interaction pattern tracking
 //! - Automatic style recommendation
+//! - Post-generation transforms, toggled per conversation: step lists for sequential
+//!   learners, Mermaid diagrams for visual learners, practice questions for active learners
 
 #![allow(dead_code)]  // Phase 5: Learning style (Stage 4)
 
 use crate::database::Database;
 use crate::services::ollama;
 use anyhow::{anyhow, Result};
+use rusqlite::OptionalExtension;
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 
@@ -78,6 +81,22 @@ pub struct InteractionData {
     pub is_followup: bool,
 }
 
+/// Concrete rewrite applied to a finished response
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseTransform {
+    StepList,         // Sequential learners: explanation as numbered steps
+    MermaidDiagram,   // Visual learners: a Mermaid diagram of the explanation
+    PracticeQuestions, // Active learners: questions to try it themselves
+}
+
+/// Result of running the transform pipeline on a response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransformOutcome {
+    pub response: String,
+    pub applied: Vec<ResponseTransform>,
+}
+
 /// Adaptation result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdaptationResult {
@@ -86,6 +105,15 @@ pub struct AdaptationResult {
     pub confidence: f32,
 }
 
+/// Profile used by the chat pipeline (single local user, as in the learning style panel)
+pub const DEFAULT_USER_ID: &str = "default-user";
+
+/// Shorter replies are left as they are
+const MIN_TRANSFORM_CHARS: usize = 280;
+const MAX_PRACTICE_QUESTIONS: usize = 3;
+const MAX_DIAGRAM_NODES: usize = 10;
+const DIAGRAM_LABEL_CHARS: usize = 40;
+
 /// Learning Style Adapter Service
 pub struct LearningStyleAdapterService {
     db: Arc<Mutex<Database>>,
@@ -145,6 +173,12 @@ impl LearningStyleAdapterService {
             [],
         )?;
 
+        // Per-conversation transform toggle (NULL = off)
+        let _ = conn.execute(
+            "ALTER TABLE conversations ADD COLUMN learning_style_transforms INTEGER",
+            [],
+        );
+
         log::info!("✓ Learning Style Adapter database initialized");
         Ok(())
     }
@@ -529,6 +563,191 @@ Provide the adapted response. Maintain the core information but adjust the prese
         })
     }
 
+    /// Whether post-generation transforms are on for a conversation (off by default)
+    pub fn transforms_enabled(&self, conversation_id: &str) -> Result<bool> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let enabled: Option<Option<bool>> = db
+            .conn()
+            .query_row(
+                "SELECT learning_style_transforms FROM conversations WHERE id = ?1",
+                [conversation_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(enabled.flatten().unwrap_or(false))
+    }
+
+    pub fn set_transforms_enabled(&self, conversation_id: &str, enabled: bool) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let updated = db.conn().execute(
+            "UPDATE conversations SET learning_style_transforms = ?1 WHERE id = ?2",
+            rusqlite::params![enabled, conversation_id],
+        )?;
+        if updated == 0 {
+            return Err(anyhow!("Conversation not found: {}", conversation_id));
+        }
+        log::info!(
+            "Conversation {} learning style transforms: {}",
+            conversation_id,
+            if enabled { "on" } else { "off" }
+        );
+        Ok(())
+    }
+
+    /// Transforms that fit a profile, in pipeline order
+    pub fn transforms_for(profile: &LearningStyleProfile) -> Vec<ResponseTransform> {
+        let has_modality = |modality: LearningModality| {
+            profile.primary_modality == modality || profile.secondary_modality.as_ref() == Some(&modality)
+        };
+
+        let mut transforms = Vec::new();
+        if profile.prefers_step_by_step {
+            transforms.push(ResponseTransform::StepList);
+        }
+        if has_modality(LearningModality::Visual) {
+            transforms.push(ResponseTransform::MermaidDiagram);
+        }
+        if has_modality(LearningModality::Kinesthetic) {
+            transforms.push(ResponseTransform::PracticeQuestions);
+        }
+        transforms
+    }
+
+    /// Post-generation pipeline for chat replies. Runs only when the conversation has
+    /// transforms switched on; any failing step leaves the reply as it was.
+    pub async fn transform_response(
+        &self,
+        conversation_id: &str,
+        user_message: &str,
+        response: String,
+    ) -> TransformOutcome {
+        let enabled = self.transforms_enabled(conversation_id).unwrap_or_else(|e| {
+            log::warn!("Failed to read learning style toggle: {}", e);
+            false
+        });
+        if !enabled {
+            return TransformOutcome { response, applied: Vec::new() };
+        }
+
+        let transforms = match self.get_profile(DEFAULT_USER_ID) {
+            Ok(profile) => Self::transforms_for(&profile),
+            Err(e) => {
+                log::warn!("Failed to load learning style profile: {}", e);
+                return TransformOutcome { response, applied: Vec::new() };
+            }
+        };
+        self.apply_transforms(&transforms, user_message, response).await
+    }
+
+    /// Run the given transforms in order
+    pub async fn apply_transforms(
+        &self,
+        transforms: &[ResponseTransform],
+        user_message: &str,
+        response: String,
+    ) -> TransformOutcome {
+        let mut outcome = TransformOutcome { response, applied: Vec::new() };
+        if prose_len(&outcome.response) < MIN_TRANSFORM_CHARS {
+            return outcome;
+        }
+
+        for transform in transforms {
+            let transformed = match transform {
+                ResponseTransform::StepList => Self::step_list(&outcome.response).await,
+                ResponseTransform::MermaidDiagram => Self::mermaid_diagram(&outcome.response).await,
+                ResponseTransform::PracticeQuestions => {
+                    Self::practice_questions(user_message, &outcome.response).await
+                }
+            };
+            if let Some(response) = transformed {
+                outcome.response = response;
+                outcome.applied.push(*transform);
+            }
+        }
+
+        if !outcome.applied.is_empty() {
+            log::info!("✓ Learning style transforms applied: {:?}", outcome.applied);
+        }
+        outcome
+    }
+
+    /// Rewrite as numbered steps; falls back to numbering the paragraphs / sentences
+    async fn step_list(response: &str) -> Option<String> {
+        if count_list_items(response) >= 2 {
+            return None;
+        }
+
+        let prompt = format!(
+            "Rewrite this answer as a numbered list of steps (1., 2., 3.), one idea per step, \
+             in the order a learner should follow them. Keep every fact, keep code blocks exactly \
+             as they are, and answer in the same language. Reply with the rewritten answer only.\n\n\
+             Answer:\n{}",
+            response
+        );
+        match ollama::generate_response(&prompt).await {
+            Ok(rewritten) if numbered_steps(&rewritten).len() >= 2 && preserves_code(response, &rewritten) => {
+                Some(rewritten.trim().to_string())
+            }
+            Ok(_) => to_step_list(response),
+            Err(e) => {
+                log::warn!("Step list rewrite failed: {}", e);
+                to_step_list(response)
+            }
+        }
+    }
+
+    /// Append a Mermaid diagram; falls back to a flowchart of the numbered steps
+    async fn mermaid_diagram(response: &str) -> Option<String> {
+        if response.contains("```mermaid") {
+            return None;
+        }
+
+        let prompt = format!(
+            "Draw a Mermaid diagram that summarizes the explanation below: a flowchart TD for \
+             processes and structures, a sequenceDiagram for interactions between parties. \
+             Use at most {} nodes with short labels. Reply with only the Mermaid code.\n\n\
+             Explanation:\n{}",
+            MAX_DIAGRAM_NODES, response
+        );
+        let diagram = match ollama::generate_response(&prompt).await {
+            Ok(output) => extract_mermaid(&output),
+            Err(e) => {
+                log::warn!("Mermaid diagram generation failed: {}", e);
+                None
+            }
+        }
+        .or_else(|| steps_to_flowchart(&numbered_steps(response)))?;
+
+        Some(format!("{}\n\n```mermaid\n{}\n```", response.trim_end(), diagram))
+    }
+
+    /// Append a few questions to apply the answer right away
+    async fn practice_questions(user_message: &str, response: &str) -> Option<String> {
+        let prompt = format!(
+            "Write 2-3 short practice questions that let the learner apply this answer \
+             themselves (hands-on exercises, not recall). One question per line, each ending \
+             with a question mark, in the same language as the answer. Reply with the questions only.\n\n\
+             Question asked:\n{}\n\nAnswer:\n{}",
+            user_message, response
+        );
+        let questions = match ollama::generate_response(&prompt).await {
+            Ok(output) => parse_questions(&output),
+            Err(e) => {
+                log::warn!("Practice question generation failed: {}", e);
+                return None;
+            }
+        };
+        if questions.is_empty() {
+            return None;
+        }
+
+        let mut transformed = format!("{}\n\n**Practice questions**\n", response.trim_end());
+        for (i, question) in questions.iter().enumerate() {
+            transformed.push_str(&format!("\n{}. {}", i + 1, question));
+        }
+        Some(transformed)
+    }
+
     /// Manual profile update
     pub fn update_profile_manually(
         &self,
@@ -556,3 +775,285 @@ Provide the adapted response. Maintain the core information but adjust the prese
         Ok(())
     }
 }
+
+/// Split into (is_code, text) blocks on ``` fences; fences stay with their code block
+fn split_code_blocks(text: &str) -> Vec<(bool, String)> {
+    let mut blocks: Vec<(bool, String)> = Vec::new();
+    let mut current = String::new();
+    let mut in_code = false;
+
+    for line in text.lines() {
+        let fence = line.trim_start().starts_with("```");
+        if fence && !in_code {
+            if !current.trim().is_empty() {
+                blocks.push((false, current.clone()));
+            }
+            current = format!("{}\n", line);
+            in_code = true;
+        } else if fence && in_code {
+            current.push_str(line);
+            blocks.push((true, current.clone()));
+            current.clear();
+            in_code = false;
+        } else {
+            current.push_str(line);
+            current.push('\n');
+        }
+    }
+    if !current.trim().is_empty() {
+        blocks.push((in_code, current));
+    }
+    blocks
+}
+
+/// Characters outside code blocks
+fn prose_len(text: &str) -> usize {
+    split_code_blocks(text)
+        .iter()
+        .filter(|(is_code, _)| !is_code)
+        .map(|(_, block)| block.trim().chars().count())
+        .sum()
+}
+
+/// Every code block of the original still appears in the rewrite
+fn preserves_code(original: &str, rewritten: &str) -> bool {
+    split_code_blocks(original)
+        .iter()
+        .filter(|(is_code, _)| *is_code)
+        .all(|(_, block)| {
+            let body: String = block.lines().skip(1).filter(|l| !l.trim_start().starts_with("```")).collect::<Vec<_>>().join("\n");
+            rewritten.contains(body.trim())
+        })
+}
+
+fn strip_list_marker(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    if let Some(rest) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")) {
+        return Some(rest);
+    }
+    numbered_item(trimmed)
+}
+
+fn numbered_item(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    let digits = trimmed.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 || digits > 3 {
+        return None;
+    }
+    trimmed[digits..]
+        .strip_prefix(". ")
+        .or_else(|| trimmed[digits..].strip_prefix(") "))
+        .map(str::trim)
+}
+
+/// List items (bulleted or numbered) outside code blocks
+fn count_list_items(text: &str) -> usize {
+    split_code_blocks(text)
+        .iter()
+        .filter(|(is_code, _)| !is_code)
+        .flat_map(|(_, block)| block.lines().map(str::to_string).collect::<Vec<_>>())
+        .filter(|line| strip_list_marker(line).is_some())
+        .count()
+}
+
+/// Text of the numbered items outside code blocks
+fn numbered_steps(text: &str) -> Vec<String> {
+    split_code_blocks(text)
+        .iter()
+        .filter(|(is_code, _)| !is_code)
+        .flat_map(|(_, block)| {
+            block
+                .lines()
+                .filter_map(numbered_item)
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .filter(|step| !step.is_empty())
+        .collect()
+}
+
+fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        current.push(c);
+        if matches!(c, '.' | '?' | '!') && chars.peek().is_none_or(|next| next.is_whitespace()) {
+            let sentence = current.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !sentence.is_empty() {
+                sentences.push(sentence);
+            }
+            current.clear();
+        }
+    }
+    let rest = current.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !rest.is_empty() {
+        sentences.push(rest);
+    }
+    sentences
+}
+
+/// Deterministic step list: paragraphs become steps, or sentences for a single paragraph.
+/// Code blocks stay in place after the step that introduces them.
+pub fn to_step_list(text: &str) -> Option<String> {
+    let blocks = split_code_blocks(text);
+    let paragraphs: usize = blocks
+        .iter()
+        .filter(|(is_code, _)| !is_code)
+        .map(|(_, block)| block.split("\n\n").filter(|p| !p.trim().is_empty()).count())
+        .sum();
+
+    let mut out: Vec<String> = Vec::new();
+    let mut step = 0;
+    for (is_code, block) in &blocks {
+        if *is_code {
+            out.push(block.trim_end().to_string());
+            continue;
+        }
+        for paragraph in block.split("\n\n").filter(|p| !p.trim().is_empty()) {
+            let items = if paragraphs > 1 {
+                vec![paragraph.split_whitespace().collect::<Vec<_>>().join(" ")]
+            } else {
+                split_sentences(paragraph)
+            };
+            for item in items {
+                step += 1;
+                out.push(format!("{}. {}", step, item));
+            }
+        }
+    }
+
+    if step < 2 {
+        return None;
+    }
+    Some(out.join("\n\n"))
+}
+
+/// Mermaid code from model output, or None if it doesn't look like a diagram
+fn extract_mermaid(output: &str) -> Option<String> {
+    const DIAGRAM_TYPES: &[&str] = &[
+        "flowchart", "graph", "sequenceDiagram", "classDiagram", "stateDiagram", "erDiagram",
+        "mindmap", "timeline",
+    ];
+
+    let body: Vec<&str> = output
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("```"))
+        .skip_while(|line| line.trim().is_empty())
+        .collect();
+    let first = body.first()?.trim();
+    if !DIAGRAM_TYPES.iter().any(|kind| first.starts_with(kind)) || body.len() < 2 {
+        return None;
+    }
+    Some(body.join("\n").trim_end().to_string())
+}
+
+/// Top-down flowchart chaining the steps in order
+pub fn steps_to_flowchart(steps: &[String]) -> Option<String> {
+    if steps.len() < 2 {
+        return None;
+    }
+
+    let labels: Vec<String> = steps
+        .iter()
+        .take(MAX_DIAGRAM_NODES)
+        .map(|step| {
+            let clean: String = step
+                .chars()
+                .filter(|c| !matches!(c, '*' | '`' | '[' | ']' | '{' | '}' | '(' | ')' | '<' | '>'))
+                .map(|c| if c == '"' { '\'' } else { c })
+                .collect();
+            let clean = clean.split_whitespace().collect::<Vec<_>>().join(" ");
+            if clean.chars().count() > DIAGRAM_LABEL_CHARS {
+                format!("{}…", clean.chars().take(DIAGRAM_LABEL_CHARS).collect::<String>().trim_end())
+            } else {
+                clean
+            }
+        })
+        .collect();
+
+    let mut chart = String::from("flowchart TD");
+    for (i, label) in labels.iter().enumerate() {
+        chart.push_str(&format!("\n    S{}[\"{}\"]", i + 1, label));
+    }
+    for i in 1..labels.len() {
+        chart.push_str(&format!("\n    S{} --> S{}", i, i + 1));
+    }
+    Some(chart)
+}
+
+/// Question lines from model output, without numbering
+fn parse_questions(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(|line| strip_list_marker(line).unwrap_or(line).trim())
+        .filter(|line| line.ends_with('?') || line.ends_with('？'))
+        .take(MAX_PRACTICE_QUESTIONS)
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_step_list_keeps_code() {
+        let text = "Install the crate first.\n\nThen call it from main:\n```rust\nfn main() { run(); }\n```\nFinally run cargo build.";
+        let steps = to_step_list(text).unwrap();
+        assert!(steps.starts_with("1. Install the crate first."));
+        assert!(steps.contains("2. Then call it from main:\n\n```rust\nfn main() { run(); }\n```"));
+        assert!(steps.contains("3. Finally run cargo build."));
+        assert!(preserves_code(text, &steps));
+        assert_eq!(numbered_steps(&steps).len(), 3);
+
+        // A single paragraph is split into sentences
+        let single = to_step_list("Open the file. Change the value! Save it?").unwrap();
+        assert_eq!(numbered_steps(&single), vec!["Open the file.", "Change the value!", "Save it?"]);
+        assert!(to_step_list("Just one sentence.").is_none());
+    }
+
+    #[test]
+    fn test_diagram_and_questions() {
+        let chart = steps_to_flowchart(&["Read the \"config\"".to_string(), "Start [server]".to_string()]).unwrap();
+        assert_eq!(chart, "flowchart TD\n    S1[\"Read the 'config'\"]\n    S2[\"Start server\"]\n    S1 --> S2");
+        assert!(steps_to_flowchart(&["Only".to_string()]).is_none());
+
+        assert_eq!(
+            extract_mermaid("```mermaid\nflowchart TD\n  A --> B\n```").as_deref(),
+            Some("flowchart TD\n  A --> B")
+        );
+        assert!(extract_mermaid("Here is a diagram: A then B").is_none());
+
+        let questions = parse_questions("1. How would you add a retry?\n2) What happens on timeout?\nNot a question\n- Why?\n- Extra?");
+        assert_eq!(questions, vec!["How would you add a retry?", "What happens on timeout?", "Why?"]);
+    }
+
+    #[test]
+    fn test_transforms_for_profile() {
+        let now = chrono::Utc::now().timestamp();
+        let mut profile = LearningStyleProfile {
+            user_id: DEFAULT_USER_ID.to_string(),
+            primary_modality: LearningModality::Visual,
+            secondary_modality: Some(LearningModality::Kinesthetic),
+            complexity_level: ComplexityLevel::Intermediate,
+            explanation_style: ExplanationStyle::Balanced,
+            confidence_score: 0.8,
+            prefers_code_examples: true,
+            prefers_analogies: false,
+            prefers_step_by_step: true,
+            attention_span_minutes: 15,
+            created_at: now,
+            updated_at: now,
+            interaction_count: 10,
+        };
+        assert_eq!(
+            LearningStyleAdapterService::transforms_for(&profile),
+            vec![ResponseTransform::StepList, ResponseTransform::MermaidDiagram, ResponseTransform::PracticeQuestions]
+        );
+
+        profile.prefers_step_by_step = false;
+        profile.secondary_modality = None;
+        assert_eq!(LearningStyleAdapterService::transforms_for(&profile), vec![ResponseTransform::MermaidDiagram]);
+    }
+}
@@ -1,46 +1,19 @@
 use anyhow::Result;
 use log::{info, warn, error};
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use super::vision_backend::{self, VisionTask};
 
-const OLLAMA_API_URL: &str = "http://localhost:11434/api/generate";
-const LLAVA_MODEL: &str = "llava:7b";
-
-/// LLaVA Vision Model Service
-/// Handles image analysis for screen context understanding using Ollama LLaVA 7B
+/// Vision Model Service
+/// Handles image analysis for screen context understanding through Ollama vision models
+/// (v3.9.0: model selected per task via `vision_backend`, llava:7b by default)
 pub struct LlavaService {
-    client: Client,
     model_loaded: bool,
 }
 
-#[derive(Debug, Serialize)]
-struct LlavaRequest {
-    model: String,
-    prompt: String,
-    images: Vec<String>, // Base64 encoded images
-    stream: bool,
-    options: LlavaOptions,
-}
-
-#[derive(Debug, Serialize)]
-struct LlavaOptions {
-    temperature: f32,
-    top_p: f32,
-}
-
-#[derive(Debug, Deserialize)]
-struct LlavaResponse {
-    response: String,
-    #[allow(dead_code)]
-    done: bool,
-}
-
 impl LlavaService {
     pub fn new() -> Result<Self> {
-        info!("LLaVA service initialized with Ollama");
+        info!("Vision service initialized with Ollama");
 
         Ok(Self {
-            client: Client::new(),
             model_loaded: true, // Assume Ollama has the model
         })
     }
@@ -57,6 +30,16 @@ impl LlavaService {
         &self,
         image_base64: String,
         prompt: Option<String>,
+    ) -> Result<String> {
+        self.analyze_image_for_task(image_base64, prompt, VisionTask::Detailed).await
+    }
+
+    /// Analyze an image with the model configured for the given task (v3.9.0)
+    pub async fn analyze_image_for_task(
+        &self,
+        image_base64: String,
+        prompt: Option<String>,
+        task: VisionTask,
     ) -> Result<String> {
         let analysis_prompt = prompt.unwrap_or_else(||
             "Describe what you see in this screenshot. Focus on:\n\
@@ -66,47 +49,25 @@ impl LlavaService {
              - The general context of the work".to_string()
         );
 
-        info!("Analyzing image with LLaVA (prompt length: {} chars)", analysis_prompt.len());
-
-        // Prepare request for Ollama vision API
-        let request = LlavaRequest {
-            model: LLAVA_MODEL.to_string(),
-            prompt: analysis_prompt,
-            images: vec![image_base64],
-            stream: false,
-            options: LlavaOptions {
-                temperature: 0.3, // Lower temperature for more factual descriptions
-                top_p: 0.9,
-            },
-        };
+        let backend = vision_backend::backend_for_task(task)?;
 
-        // Send request to Ollama
-        let response = self.client
-            .post(OLLAMA_API_URL)
-            .json(&request)
-            .send()
+        info!(
+            "Analyzing image with {} ({:?} task, prompt length: {} chars)",
+            backend.model(),
+            task,
+            analysis_prompt.len()
+        );
+
+        let description = backend
+            .describe(image_base64, &analysis_prompt, task)
             .await
             .map_err(|e| {
-                error!("Failed to connect to Ollama for vision analysis: {}", e);
-                anyhow::anyhow!("Ollama connection failed: {}", e)
+                error!("Vision analysis failed: {}", e);
+                e
             })?;
 
-        // Check response status
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            error!("Ollama vision API error ({}): {}", status, error_text);
-            return Err(anyhow::anyhow!("Vision analysis failed: {} - {}", status, error_text));
-        }
-
-        // Parse response
-        let llava_response: LlavaResponse = response.json().await.map_err(|e| {
-            error!("Failed to parse LLaVA response: {}", e);
-            anyhow::anyhow!("Response parsing failed: {}", e)
-        })?;
-
-        info!("LLaVA analysis complete (length: {} chars)", llava_response.response.len());
-        Ok(llava_response.response.trim().to_string())
+        info!("Vision analysis complete (length: {} chars)", description.len());
+        Ok(description)
     }
 
     /// Analyze screen context for AI awareness
@@ -150,7 +111,11 @@ impl LlavaService {
 
         info!("Analyzing screen with context level {}", context_level);
 
-        let description = self.analyze_image(screenshot_base64, Some(prompt.to_string())).await?;
+        // Level 1 is a quick glance; deeper levels use the detailed model
+        let task = if context_level <= 1 { VisionTask::Fast } else { VisionTask::Detailed };
+        let description = self
+            .analyze_image_for_task(screenshot_base64, Some(prompt.to_string()), task)
+            .await?;
 
         // Parse description to extract structured data
         let analysis = self.parse_analysis(&description);
@@ -178,7 +143,9 @@ impl LlavaService {
                       - Is the user stuck or repeating a pattern?\n\
                       Respond with a list of observations, or 'NONE' if nothing notable.";
 
-        let analysis = self.analyze_image(screenshot_base64, Some(prompt.to_string())).await?;
+        let analysis = self
+            .analyze_image_for_task(screenshot_base64, Some(prompt.to_string()), VisionTask::Fast)
+            .await?;
 
         // Parse triggers from response
         let triggers = self.parse_triggers(&analysis);
@@ -196,13 +163,13 @@ impl LlavaService {
         // Create a simple 1x1 pixel white image in base64
         let test_image = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mP8/5+hHgAHggJ/PchI7wAAAABJRU5ErkJggg==";
 
-        match self.analyze_image(test_image.to_string(), Some("Test".to_string())).await {
+        match self.analyze_image_for_task(test_image.to_string(), Some("Test".to_string()), VisionTask::Fast).await {
             Ok(_) => {
-                info!("LLaVA vision connection test: SUCCESS");
+                info!("Vision connection test: SUCCESS");
                 Ok(true)
             }
             Err(e) => {
-                warn!("LLaVA vision connection test: FAILED - {}", e);
+                warn!("Vision connection test: FAILED - {}", e);
                 Ok(false)
             }
         }
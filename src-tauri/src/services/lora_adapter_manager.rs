@@ -38,6 +38,8 @@ pub struct LoRAAdapter {
     pub training_dataset_id: Option<String>, // Link to training dataset
     pub performance_metrics: Option<PerformanceMetrics>,
     pub is_active: bool,             // Currently loaded in Ollama
+    #[serde(default)]
+    pub ollama_model: Option<String>, // v3.9.0: Ollama model created from this adapter
 }
 
 /// Performance metrics for adapter evaluation
@@ -139,6 +141,7 @@ impl LoRAAdapterManager {
             training_dataset_id,
             performance_metrics: None,
             is_active: false,
+            ollama_model: None,
         };
 
         // Store in database
@@ -246,6 +249,25 @@ impl LoRAAdapterManager {
         Ok(())
     }
 
+    /// Deactivate all adapters (v3.9.0)
+    pub fn clear_active_adapter(&self) -> AnyhowResult<()> {
+        for mut adapter in self.list_adapters()? {
+            if adapter.is_active {
+                adapter.is_active = false;
+                self.save_adapter(&adapter)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Record the Ollama model created from an adapter (v3.9.0)
+    pub fn set_ollama_model(&self, adapter_id: &str, model_name: &str) -> AnyhowResult<()> {
+        let mut adapter = self.load_adapter(adapter_id)?
+            .ok_or_else(|| anyhow::anyhow!("Adapter not found: {}", adapter_id))?;
+        adapter.ollama_model = Some(model_name.to_string());
+        self.save_adapter(&adapter)
+    }
+
     /// Update adapter performance metrics
     pub fn update_performance_metrics(
         &self,
@@ -330,45 +352,7 @@ impl LoRAAdapterManager {
 
         log::info!("Creating Ollama model '{}' from adapter {}", model_name, adapter_id);
 
-        // Call Ollama's create API
-        let client = reqwest::Client::new();
-        let response = client
-            .post("http://127.0.0.1:11434/api/create")
-            .json(&serde_json::json!({
-                "name": model_name,
-                "modelfile": modelfile,
-                "stream": false
-            }))
-            .send()
-            .await
-            .context("Failed to connect to Ollama API")?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(anyhow::anyhow!("Ollama create failed: {}", error_text));
-        }
-
-        // Parse streaming response - Ollama returns NDJSON
-        let response_text = response.text().await?;
-
-        // Check for success in the last line of the NDJSON response
-        let last_line = response_text.lines().last().unwrap_or("");
-        if let Ok(json) = serde_json::from_str::<serde_json::Value>(last_line) {
-            if let Some(status) = json.get("status").and_then(|s| s.as_str()) {
-                if status == "success" {
-                    log::info!("Successfully created Ollama model: {}", model_name);
-                    return Ok(());
-                }
-            }
-        }
-
-        // If we couldn't parse success, check if the response indicates an error
-        if response_text.contains("error") {
-            return Err(anyhow::anyhow!("Ollama create failed: {}", response_text));
-        }
-
-        log::info!("Ollama model creation completed for: {}", model_name);
-        Ok(())
+        create_ollama_model_from_modelfile(model_name, &modelfile).await
     }
 
     /// Create Ollama model from adapter synchronously (blocking version)
@@ -512,6 +496,51 @@ impl LoRAAdapterManager {
     }
 }
 
+/// Create an Ollama model from Modelfile content via `/api/create`
+///
+/// Doesn't need the manager, so callers can release its lock before awaiting (v3.9.0).
+pub async fn create_ollama_model_from_modelfile(model_name: &str, modelfile: &str) -> AnyhowResult<()> {
+    // Call Ollama's create API
+    let client = reqwest::Client::new();
+    let response = client
+        .post("http://127.0.0.1:11434/api/create")
+        .json(&serde_json::json!({
+            "name": model_name,
+            "modelfile": modelfile,
+            "stream": false
+        }))
+        .send()
+        .await
+        .context("Failed to connect to Ollama API")?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(anyhow::anyhow!("Ollama create failed: {}", error_text));
+    }
+
+    // Parse streaming response - Ollama returns NDJSON
+    let response_text = response.text().await?;
+
+    // Check for success in the last line of the NDJSON response
+    let last_line = response_text.lines().last().unwrap_or("");
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(last_line) {
+        if let Some(status) = json.get("status").and_then(|s| s.as_str()) {
+            if status == "success" {
+                log::info!("Successfully created Ollama model: {}", model_name);
+                return Ok(());
+            }
+        }
+    }
+
+    // If we couldn't parse success, check if the response indicates an error
+    if response_text.contains("error") {
+        return Err(anyhow::anyhow!("Ollama create failed: {}", response_text));
+    }
+
+    log::info!("Ollama model creation completed for: {}", model_name);
+    Ok(())
+}
+
 /// Adapter comparison result
 #[derive(Debug, Clone, Serialize)]
 pub struct AdapterComparison {
@@ -0,0 +1,887 @@
+//! LoRA Training Orchestrator (v3.9.0)
+//!
+//! End-to-end path from collected conversations to a chat-selectable adapter.
+//!
+//! Features:
+//! - Exports collected data (Alpaca / ShareGPT JSONL) with an axolotl config
+//! - Launches the local trainer and parses step / loss / epoch from its output
+//! - Progress events (`lora-training-progress`, `lora-training-finished`)
+//! - Disk-space checks before export and while training
+//! - Registers the adapter, creates its Ollama model and can switch chat to it
+
+#![allow(dead_code)]  // Phase 5: LoRA training orchestration
+
+use crate::database::Database;
+use crate::services::lora_adapter_manager::{self, LoRAAdapterManager};
+use crate::services::lora_data_collector::{LoRADataCollectorService, TrainingFormat};
+use crate::services::ollama;
+use anyhow::{anyhow, Context, Result};
+use regex::Regex;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command as TokioCommand};
+use tokio::sync::oneshot;
+
+/// Emitted with the job on progress and status changes
+pub const PROGRESS_EVENT: &str = "lora-training-progress";
+
+/// Emitted once with the final job
+pub const FINISHED_EVENT: &str = "lora-training-finished";
+
+/// `user_preferences` key holding the selected chat model
+const CHAT_MODEL_KEY: &str = "chat_model";
+
+/// Minimum gap between progress events
+const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often free disk space is re-checked while training
+const DISK_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A running job is stopped once free space drops below this share of the
+/// configured minimum
+const DISK_ABORT_FRACTION: u64 = 4;
+
+/// Trainer output lines kept on the job
+const LOG_TAIL_LINES: usize = 20;
+
+/// Training job configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainingConfig {
+    /// Hugging Face model that is trained (same architecture as the Ollama base)
+    #[serde(default = "default_hf_base_model")]
+    pub hf_base_model: String,
+    /// Ollama model the adapter is applied to
+    #[serde(default = "default_ollama_base_model")]
+    pub ollama_base_model: String,
+    /// Ollama model created from the adapter (default: eden-lora-<job>)
+    #[serde(default)]
+    pub model_name: Option<String>,
+    /// Alpaca or ShareGPT (raw JSONL isn't a trainer format)
+    #[serde(default = "default_format")]
+    pub format: TrainingFormat,
+    /// Maximum number of examples exported
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default = "default_min_examples")]
+    pub min_examples: usize,
+    #[serde(default = "default_lora_r")]
+    pub lora_r: u32,
+    #[serde(default = "default_lora_alpha")]
+    pub lora_alpha: u32,
+    #[serde(default = "default_lora_dropout")]
+    pub lora_dropout: f32,
+    #[serde(default = "default_num_epochs")]
+    pub num_epochs: u32,
+    #[serde(default = "default_learning_rate")]
+    pub learning_rate: f64,
+    #[serde(default = "default_micro_batch_size")]
+    pub micro_batch_size: u32,
+    #[serde(default = "default_gradient_accumulation_steps")]
+    pub gradient_accumulation_steps: u32,
+    #[serde(default = "default_sequence_len")]
+    pub sequence_len: u32,
+    /// Trainer command line; the config path is appended
+    #[serde(default = "default_trainer_command")]
+    pub trainer_command: Vec<String>,
+    /// Free space required before starting (base model download + checkpoints)
+    #[serde(default = "default_min_free_disk_gb")]
+    pub min_free_disk_gb: u64,
+    /// Switch chat to the new model once training succeeds
+    #[serde(default)]
+    pub use_for_chat: bool,
+}
+
+fn default_hf_base_model() -> String { "Qwen/Qwen2.5-7B-Instruct".to_string() }
+fn default_ollama_base_model() -> String { "qwen2.5:7b".to_string() }
+fn default_format() -> TrainingFormat { TrainingFormat::Alpaca }
+fn default_min_examples() -> usize { 50 }
+fn default_lora_r() -> u32 { 16 }
+fn default_lora_alpha() -> u32 { 32 }
+fn default_lora_dropout() -> f32 { 0.05 }
+fn default_num_epochs() -> u32 { 3 }
+fn default_learning_rate() -> f64 { 2e-4 }
+fn default_micro_batch_size() -> u32 { 2 }
+fn default_gradient_accumulation_steps() -> u32 { 4 }
+fn default_sequence_len() -> u32 { 2048 }
+fn default_trainer_command() -> Vec<String> { vec!["axolotl".to_string(), "train".to_string()] }
+fn default_min_free_disk_gb() -> u64 { 30 }
+
+impl Default for TrainingConfig {
+    fn default() -> Self {
+        Self {
+            hf_base_model: default_hf_base_model(),
+            ollama_base_model: default_ollama_base_model(),
+            model_name: None,
+            format: default_format(),
+            limit: None,
+            min_examples: default_min_examples(),
+            lora_r: default_lora_r(),
+            lora_alpha: default_lora_alpha(),
+            lora_dropout: default_lora_dropout(),
+            num_epochs: default_num_epochs(),
+            learning_rate: default_learning_rate(),
+            micro_batch_size: default_micro_batch_size(),
+            gradient_accumulation_steps: default_gradient_accumulation_steps(),
+            sequence_len: default_sequence_len(),
+            trainer_command: default_trainer_command(),
+            min_free_disk_gb: default_min_free_disk_gb(),
+            use_for_chat: false,
+        }
+    }
+}
+
+impl TrainingConfig {
+    fn validate(&self) -> Result<()> {
+        if self.format == TrainingFormat::JSONL {
+            return Err(anyhow!("Raw JSONL is not a trainer format; use Alpaca or ShareGPT"));
+        }
+        if self.trainer_command.is_empty() {
+            return Err(anyhow!("Trainer command is empty"));
+        }
+        if self.num_epochs == 0 || self.micro_batch_size == 0 || self.lora_r == 0 {
+            return Err(anyhow!("Epochs, batch size and LoRA rank must be greater than 0"));
+        }
+        if !(0.0..1.0).contains(&self.lora_dropout) {
+            return Err(anyhow!("LoRA dropout must be in [0, 1)"));
+        }
+        Ok(())
+    }
+}
+
+/// Training job lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrainingStatus {
+    Running,
+    /// Trainer finished; adapter is being registered and loaded into Ollama
+    Registering,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl TrainingStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Registering => "registering",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// Progress parsed from the trainer output
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrainingProgress {
+    pub step: u64,
+    pub total_steps: Option<u64>,
+    pub epoch: Option<f32>,
+    pub loss: Option<f32>,
+    pub percent: f32,
+}
+
+/// A fine-tune job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainingJob {
+    pub id: String,
+    pub status: TrainingStatus,
+    pub config: TrainingConfig,
+    pub job_dir: String,
+    pub dataset_path: String,
+    pub dataset_examples: usize,
+    pub progress: TrainingProgress,
+    /// Last trainer output lines
+    pub log_tail: Vec<String>,
+    pub free_disk_gb: Option<u64>,
+    pub error: Option<String>,
+    pub adapter_id: Option<String>,
+    pub ollama_model: Option<String>,
+    pub created_at: i64, // Unix millis
+    pub updated_at: i64,
+    pub finished_at: Option<i64>,
+}
+
+/// Readiness check before starting a job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainingPreflight {
+    pub examples: usize,
+    pub free_disk_gb: Option<u64>,
+    pub required_disk_gb: u64,
+    pub trainer_found: bool,
+    pub issues: Vec<String>,
+    pub ready: bool,
+}
+
+/// Update `progress` from one trainer output line; true if anything changed
+///
+/// Understands tqdm bars (`45/100 [01:23<...]`) and HF Trainer log dicts
+/// (`{'loss': 1.23, ..., 'epoch': 0.5}`).
+pub fn parse_progress(line: &str, progress: &mut TrainingProgress) -> bool {
+    static RES: OnceLock<(Regex, Regex, Regex)> = OnceLock::new();
+    let (steps, loss, epoch) = RES.get_or_init(|| {
+        (
+            Regex::new(r"(\d+)/(\d+) \[").unwrap(),
+            Regex::new(r"'loss': '?([0-9]+(?:\.[0-9]+)?)").unwrap(),
+            Regex::new(r"'epoch': '?([0-9]+(?:\.[0-9]+)?)").unwrap(),
+        )
+    });
+    let before = progress.clone();
+
+    if let Some(caps) = steps.captures(line) {
+        if let (Ok(step), Ok(total)) = (caps[1].parse::<u64>(), caps[2].parse::<u64>()) {
+            if total > 0 {
+                progress.step = step;
+                progress.total_steps = Some(total);
+                progress.percent = (step as f32 / total as f32 * 100.0).min(100.0);
+            }
+        }
+    }
+    if let Some(value) = loss.captures(line).and_then(|c| c[1].parse().ok()) {
+        progress.loss = Some(value);
+    }
+    if let Some(value) = epoch.captures(line).and_then(|c| c[1].parse().ok()) {
+        progress.epoch = Some(value);
+    }
+
+    *progress != before
+}
+
+fn yaml_str(value: &str) -> String {
+    // JSON string quoting is valid YAML double-quoted style
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+/// axolotl config for a job
+pub fn axolotl_config(config: &TrainingConfig, dataset_path: &Path, output_dir: &Path) -> String {
+    let dataset = match config.format {
+        TrainingFormat::ShareGPT => "    type: chat_template\n    \
+             field_messages: conversations\n    \
+             message_property_mappings:\n      role: from\n      content: value\n    \
+             roles:\n      user: [\"human\"]\n      assistant: [\"gpt\"]\n"
+            .to_string(),
+        _ => "    type: alpaca\n".to_string(),
+    };
+
+    format!(
+        "# Generated by Garden of Eden v3 (LoRA training orchestrator)\n\
+         base_model: {base_model}\n\
+         adapter: lora\n\
+         lora_r: {lora_r}\n\
+         lora_alpha: {lora_alpha}\n\
+         lora_dropout: {lora_dropout}\n\
+         lora_target_linear: true\n\
+         sequence_len: {sequence_len}\n\
+         sample_packing: false\n\
+         datasets:\n  - path: {dataset_path}\n    ds_type: json\n{dataset}\
+         val_set_size: 0.05\n\
+         output_dir: {output_dir}\n\
+         num_epochs: {num_epochs}\n\
+         micro_batch_size: {micro_batch_size}\n\
+         gradient_accumulation_steps: {gradient_accumulation_steps}\n\
+         learning_rate: {learning_rate}\n\
+         optimizer: adamw_torch\n\
+         lr_scheduler: cosine\n\
+         bf16: auto\n\
+         logging_steps: 1\n",
+        base_model = yaml_str(&config.hf_base_model),
+        lora_r = config.lora_r,
+        lora_alpha = config.lora_alpha,
+        lora_dropout = config.lora_dropout,
+        sequence_len = config.sequence_len,
+        dataset_path = yaml_str(&dataset_path.to_string_lossy()),
+        dataset = dataset,
+        output_dir = yaml_str(&output_dir.to_string_lossy()),
+        num_epochs = config.num_epochs,
+        micro_batch_size = config.micro_batch_size,
+        gradient_accumulation_steps = config.gradient_accumulation_steps,
+        learning_rate = config.learning_rate,
+    )
+}
+
+/// Free space (GB) of the disk holding `path`
+pub fn free_disk_gb(path: &Path) -> Option<u64> {
+    let path = path.canonicalize().ok()?;
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space() / (1024 * 1024 * 1024))
+}
+
+/// Whether `program` resolves to an executable on PATH (or is a path itself)
+fn find_program(program: &str) -> bool {
+    if Path::new(program).components().count() > 1 {
+        return Path::new(program).is_file();
+    }
+    let Some(paths) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&paths).any(|dir| {
+        dir.join(program).is_file() || (cfg!(windows) && dir.join(format!("{}.exe", program)).is_file())
+    })
+}
+
+/// Whether a trainer output directory contains a PEFT adapter
+fn has_adapter(output_dir: &Path) -> bool {
+    output_dir.join("adapter_config.json").is_file()
+        && (output_dir.join("adapter_model.safetensors").is_file() || output_dir.join("adapter_model.bin").is_file())
+}
+
+/// How the trainer process ended
+enum ProcessExit {
+    Finished,
+    Cancelled,
+    Failed(String),
+}
+
+/// LoRA training orchestrator
+pub struct LoRATrainingService {
+    db: Arc<Mutex<Database>>,
+    data_collector: Arc<Mutex<LoRADataCollectorService>>,
+    adapter_manager: Arc<Mutex<LoRAAdapterManager>>,
+    training_dir: PathBuf,
+    /// Cancel handles of running jobs
+    running: Mutex<HashMap<String, oneshot::Sender<()>>>,
+}
+
+impl LoRATrainingService {
+    pub fn new(
+        db: Arc<Mutex<Database>>,
+        data_collector: Arc<Mutex<LoRADataCollectorService>>,
+        adapter_manager: Arc<Mutex<LoRAAdapterManager>>,
+    ) -> Result<Self> {
+        let training_dir = dirs::data_dir()
+            .context("Failed to get app data directory")?
+            .join("garden-of-eden-v3")
+            .join("lora_training");
+        std::fs::create_dir_all(&training_dir).context("Failed to create training directory")?;
+
+        let service = Self {
+            db,
+            data_collector,
+            adapter_manager,
+            training_dir,
+            running: Mutex::new(HashMap::new()),
+        };
+        service.init_database()?;
+        log::info!("✓ LoRA Training Service initialized");
+        Ok(service)
+    }
+
+    fn init_database(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let conn = db.conn();
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS lora_training_jobs (
+                id TEXT PRIMARY KEY,
+                status TEXT NOT NULL,
+                data TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // Jobs can't survive an app restart: their trainer process is gone
+        let mut stmt = conn.prepare(
+            "SELECT data FROM lora_training_jobs WHERE status IN ('running', 'registering')",
+        )?;
+        let interrupted = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        drop(stmt);
+        for json in interrupted {
+            if let Ok(mut job) = serde_json::from_str::<TrainingJob>(&json) {
+                job.status = TrainingStatus::Failed;
+                job.error = Some("Interrupted by app restart".to_string());
+                job.finished_at = Some(chrono::Utc::now().timestamp_millis());
+                Self::write_job(conn, &job)?;
+            }
+        }
+
+        // Restore the selected chat model
+        let chat_model: Option<String> = conn
+            .query_row(
+                "SELECT value FROM user_preferences WHERE key = ?1",
+                [CHAT_MODEL_KEY],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if chat_model.is_some() {
+            ollama::set_chat_model(chat_model);
+        }
+
+        Ok(())
+    }
+
+    fn write_job(conn: &rusqlite::Connection, job: &TrainingJob) -> Result<()> {
+        conn.execute(
+            "INSERT INTO lora_training_jobs (id, status, data, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(id) DO UPDATE SET status = excluded.status, data = excluded.data,
+                                           updated_at = excluded.updated_at",
+            params![job.id, job.status.as_str(), serde_json::to_string(job)?, job.created_at, job.updated_at],
+        )?;
+        Ok(())
+    }
+
+    fn save_job(&self, job: &mut TrainingJob) -> Result<()> {
+        job.updated_at = chrono::Utc::now().timestamp_millis();
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        Self::write_job(db.conn(), job)
+    }
+
+    /// Persist and announce a job update
+    fn publish(&self, job: &mut TrainingJob, app: Option<&AppHandle>) {
+        if let Err(e) = self.save_job(job) {
+            log::warn!("Failed to save training job {}: {}", job.id, e);
+        }
+        if let Some(app) = app {
+            let event = if job.status.is_finished() { FINISHED_EVENT } else { PROGRESS_EVENT };
+            if let Err(e) = app.emit(event, &*job) {
+                log::warn!("Failed to emit {}: {}", event, e);
+            }
+        }
+    }
+
+    fn available_examples(&self, config: &TrainingConfig) -> Result<usize> {
+        let collector = self.data_collector.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let (examples, _) = collector.collect_training_data(config.format, config.limit)?;
+        Ok(examples.len())
+    }
+
+    /// Check data, disk space and trainer before starting
+    pub fn preflight(&self, config: &TrainingConfig) -> Result<TrainingPreflight> {
+        let mut issues = Vec::new();
+        if let Err(e) = config.validate() {
+            issues.push(e.to_string());
+        }
+
+        let examples = self.available_examples(config)?;
+        if examples < config.min_examples {
+            issues.push(format!(
+                "Only {} training examples available, need at least {}",
+                examples, config.min_examples
+            ));
+        }
+
+        let free = free_disk_gb(&self.training_dir);
+        match free {
+            Some(free) if free < config.min_free_disk_gb => issues.push(format!(
+                "Only {} GB free disk space, need at least {} GB",
+                free, config.min_free_disk_gb
+            )),
+            None => issues.push("Could not determine free disk space".to_string()),
+            _ => {}
+        }
+
+        let trainer_found = config.trainer_command.first().is_some_and(|program| find_program(program));
+        if !trainer_found {
+            issues.push(format!(
+                "Trainer '{}' not found on PATH (install axolotl or set trainer_command)",
+                config.trainer_command.first().map(String::as_str).unwrap_or("")
+            ));
+        }
+
+        Ok(TrainingPreflight {
+            examples,
+            free_disk_gb: free,
+            required_disk_gb: config.min_free_disk_gb,
+            trainer_found,
+            ready: issues.is_empty(),
+            issues,
+        })
+    }
+
+    /// Export data, write the trainer config and launch the fine-tune
+    pub async fn start(self: &Arc<Self>, config: TrainingConfig, app: Option<AppHandle>) -> Result<TrainingJob> {
+        let preflight = self.preflight(&config)?;
+        if !preflight.ready {
+            return Err(anyhow!("Training is not ready: {}", preflight.issues.join("; ")));
+        }
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let job_dir = self.training_dir.join(&id);
+        std::fs::create_dir_all(&job_dir).context("Failed to create job directory")?;
+
+        // Export collected conversations as JSONL
+        let (examples, metadata) = {
+            let collector = self.data_collector.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            collector.collect_training_data(config.format, config.limit)?
+        };
+        let dataset_path = job_dir.join("dataset.jsonl");
+        {
+            let mut writer = std::io::BufWriter::new(
+                std::fs::File::create(&dataset_path).context("Failed to create dataset file")?,
+            );
+            for example in &examples {
+                serde_json::to_writer(&mut writer, example)?;
+                writer.write_all(b"\n")?;
+            }
+            writer.flush()?;
+        }
+        std::fs::write(job_dir.join("dataset.metadata.json"), serde_json::to_string_pretty(&metadata)?)?;
+
+        let output_dir = job_dir.join("adapter");
+        let config_path = job_dir.join("config.yml");
+        std::fs::write(&config_path, axolotl_config(&config, &dataset_path, &output_dir))
+            .context("Failed to write trainer config")?;
+
+        let mut child = TokioCommand::new(&config.trainer_command[0])
+            .args(&config.trainer_command[1..])
+            .arg(&config_path)
+            .current_dir(&job_dir)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to launch trainer '{}'", config.trainer_command[0]))?;
+        log::info!(
+            "Started LoRA training job {} ({} examples, pid {:?})",
+            id, examples.len(), child.id()
+        );
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut job = TrainingJob {
+            id: id.clone(),
+            status: TrainingStatus::Running,
+            config,
+            job_dir: job_dir.to_string_lossy().to_string(),
+            dataset_path: dataset_path.to_string_lossy().to_string(),
+            dataset_examples: examples.len(),
+            progress: TrainingProgress::default(),
+            log_tail: Vec::new(),
+            free_disk_gb: preflight.free_disk_gb,
+            error: None,
+            adapter_id: None,
+            ollama_model: None,
+            created_at: now,
+            updated_at: now,
+            finished_at: None,
+        };
+        if let Err(e) = self.save_job(&mut job) {
+            child.kill().await.ok();
+            return Err(e);
+        }
+
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+        self.running.lock().map_err(|e| anyhow!("Lock error: {}", e))?.insert(id, cancel_tx);
+
+        let service = Arc::clone(self);
+        let snapshot = job.clone();
+        tokio::spawn(async move {
+            service.run_job(job, child, cancel_rx, app, metadata.id).await;
+        });
+
+        Ok(snapshot)
+    }
+
+    /// Monitor the trainer, then register the adapter
+    async fn run_job(
+        self: Arc<Self>,
+        mut job: TrainingJob,
+        mut child: Child,
+        cancel: oneshot::Receiver<()>,
+        app: Option<AppHandle>,
+        dataset_id: String,
+    ) {
+        let exit = self.watch_process(&mut job, &mut child, cancel, app.as_ref()).await;
+        if let Ok(mut running) = self.running.lock() {
+            running.remove(&job.id);
+        }
+
+        match exit {
+            ProcessExit::Finished => {
+                job.status = TrainingStatus::Registering;
+                self.publish(&mut job, app.as_ref());
+                match self.register_result(&mut job, dataset_id).await {
+                    Ok(()) => {
+                        job.status = TrainingStatus::Completed;
+                        log::info!("LoRA training job {} completed: {:?}", job.id, job.ollama_model);
+                    }
+                    Err(e) => {
+                        job.status = TrainingStatus::Failed;
+                        job.error = Some(e.to_string());
+                    }
+                }
+            }
+            ProcessExit::Cancelled => {
+                job.status = TrainingStatus::Cancelled;
+                log::info!("LoRA training job {} cancelled", job.id);
+            }
+            ProcessExit::Failed(error) => {
+                job.status = TrainingStatus::Failed;
+                job.error = Some(error);
+            }
+        }
+
+        if let Some(error) = &job.error {
+            log::error!("LoRA training job {} failed: {}", job.id, error);
+        }
+        job.finished_at = Some(chrono::Utc::now().timestamp_millis());
+        self.publish(&mut job, app.as_ref());
+    }
+
+    async fn watch_process(
+        &self,
+        job: &mut TrainingJob,
+        child: &mut Child,
+        mut cancel: oneshot::Receiver<()>,
+        app: Option<&AppHandle>,
+    ) -> ProcessExit {
+        let (Some(stdout), Some(stderr)) = (child.stdout.take(), child.stderr.take()) else {
+            return ProcessExit::Failed("Failed to capture trainer output".to_string());
+        };
+        // tqdm redraws with '\r', so split on it as well as on newlines
+        let mut stdout = BufReader::new(stdout).split(b'\r');
+        let mut stderr = BufReader::new(stderr).split(b'\r');
+        let (mut stdout_open, mut stderr_open) = (true, true);
+
+        let mut log_file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(Path::new(&job.job_dir).join("train.log"))
+            .ok();
+        let mut tail: VecDeque<String> = VecDeque::with_capacity(LOG_TAIL_LINES);
+        let disk_floor = job.config.min_free_disk_gb / DISK_ABORT_FRACTION;
+        let mut disk_check = tokio::time::interval(DISK_CHECK_INTERVAL);
+        let mut last_emit = Instant::now();
+        let mut dirty = false;
+
+        loop {
+            let segment = tokio::select! {
+                segment = stdout.next_segment(), if stdout_open => match segment {
+                    Ok(Some(bytes)) => Some(bytes),
+                    _ => { stdout_open = false; None }
+                },
+                segment = stderr.next_segment(), if stderr_open => match segment {
+                    Ok(Some(bytes)) => Some(bytes),
+                    _ => { stderr_open = false; None }
+                },
+                status = child.wait(), if !stdout_open && !stderr_open => {
+                    job.log_tail = tail.into_iter().collect();
+                    return match status {
+                        Ok(status) if status.success() => ProcessExit::Finished,
+                        Ok(status) => ProcessExit::Failed(format!(
+                            "Trainer exited with {}: {}",
+                            status,
+                            job.log_tail.last().map(String::as_str).unwrap_or("no output")
+                        )),
+                        Err(e) => ProcessExit::Failed(format!("Failed to wait for trainer: {}", e)),
+                    };
+                }
+                _ = &mut cancel => {
+                    child.kill().await.ok();
+                    job.log_tail = tail.into_iter().collect();
+                    return ProcessExit::Cancelled;
+                }
+                _ = disk_check.tick() => {
+                    job.free_disk_gb = free_disk_gb(Path::new(&job.job_dir));
+                    if let Some(free) = job.free_disk_gb.filter(|free| *free < disk_floor) {
+                        child.kill().await.ok();
+                        job.log_tail = tail.into_iter().collect();
+                        return ProcessExit::Failed(format!(
+                            "Stopped: only {} GB free disk space left", free
+                        ));
+                    }
+                    None
+                }
+            };
+
+            if let Some(bytes) = segment {
+                let text = String::from_utf8_lossy(&bytes);
+                for line in text.lines().map(str::trim_end).filter(|l| !l.is_empty()) {
+                    if let Some(file) = log_file.as_mut() {
+                        let _ = writeln!(file, "{}", line);
+                    }
+                    dirty |= parse_progress(line, &mut job.progress);
+                    if tail.len() == LOG_TAIL_LINES {
+                        tail.pop_front();
+                    }
+                    tail.push_back(line.to_string());
+                }
+            }
+
+            if dirty && last_emit.elapsed() >= PROGRESS_EMIT_INTERVAL {
+                job.log_tail = tail.iter().cloned().collect();
+                self.publish(job, app);
+                last_emit = Instant::now();
+                dirty = false;
+            }
+        }
+    }
+
+    /// Register the trained adapter and create its Ollama model
+    async fn register_result(&self, job: &mut TrainingJob, dataset_id: String) -> Result<()> {
+        let output_dir = Path::new(&job.job_dir).join("adapter");
+        if !has_adapter(&output_dir) {
+            return Err(anyhow!("Trainer finished without writing an adapter to {}", output_dir.display()));
+        }
+
+        let model_name = job
+            .config
+            .model_name
+            .clone()
+            .unwrap_or_else(|| format!("eden-lora-{}", &job.id[..8]));
+
+        let modelfile = {
+            let manager = self.adapter_manager.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            let adapter = manager.register_adapter(
+                model_name.clone(),
+                format!(
+                    "Fine-tuned on {} examples ({:?}, {} epochs)",
+                    job.dataset_examples, job.config.format, job.config.num_epochs
+                ),
+                job.config.ollama_base_model.clone(),
+                output_dir.to_string_lossy().to_string(),
+                "1.0.0".to_string(),
+                Some(dataset_id),
+            )?;
+            job.adapter_id = Some(adapter.id.clone());
+            if let Some(loss) = job.progress.loss {
+                manager.update_performance_metrics(&adapter.id, lora_adapter_manager::PerformanceMetrics {
+                    avg_satisfaction: 0.0,
+                    total_conversations: 0,
+                    training_loss: Some(loss),
+                    eval_loss: None,
+                    perplexity: None,
+                })?;
+            }
+            manager.generate_modelfile(&adapter.id, None)?
+        };
+
+        lora_adapter_manager::create_ollama_model_from_modelfile(&model_name, &modelfile).await?;
+        job.ollama_model = Some(model_name.clone());
+
+        let adapter_id = job.adapter_id.clone().unwrap_or_default();
+        {
+            let manager = self.adapter_manager.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            manager.set_ollama_model(&adapter_id, &model_name)?;
+        }
+
+        if job.config.use_for_chat {
+            self.use_adapter(Some(&adapter_id))?;
+        }
+        Ok(())
+    }
+
+    /// Stop a running job
+    pub fn cancel(&self, job_id: &str) -> Result<bool> {
+        let sender = self.running.lock().map_err(|e| anyhow!("Lock error: {}", e))?.remove(job_id);
+        Ok(sender.is_some_and(|tx| tx.send(()).is_ok()))
+    }
+
+    pub fn get_job(&self, job_id: &str) -> Result<Option<TrainingJob>> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let json: Option<String> = db
+            .conn()
+            .query_row("SELECT data FROM lora_training_jobs WHERE id = ?1", [job_id], |row| row.get(0))
+            .optional()?;
+        json.map(|json| serde_json::from_str(&json).map_err(Into::into)).transpose()
+    }
+
+    /// Jobs, newest first
+    pub fn list_jobs(&self, limit: usize) -> Result<Vec<TrainingJob>> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT data FROM lora_training_jobs ORDER BY created_at DESC LIMIT ?1",
+        )?;
+        let rows = stmt
+            .query_map([limit as i64], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows.iter().map(|json| serde_json::from_str(json).map_err(Into::into)).collect()
+    }
+
+    /// Use an adapter's Ollama model for chat, or the default model with None
+    ///
+    /// Returns the chat model now in use.
+    pub fn use_adapter(&self, adapter_id: Option<&str>) -> Result<String> {
+        let model = {
+            let manager = self.adapter_manager.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            match adapter_id {
+                Some(id) => {
+                    let adapter = manager
+                        .load_adapter(id)?
+                        .ok_or_else(|| anyhow!("Adapter not found: {}", id))?;
+                    let model = adapter
+                        .ollama_model
+                        .ok_or_else(|| anyhow!("Adapter '{}' has no Ollama model yet", adapter.name))?;
+                    manager.set_active_adapter(id)?;
+                    Some(model)
+                }
+                None => {
+                    manager.clear_active_adapter()?;
+                    None
+                }
+            }
+        };
+
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            match &model {
+                Some(model) => db.conn().execute(
+                    "INSERT INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)
+                     ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+                    params![CHAT_MODEL_KEY, model, chrono::Utc::now().timestamp_millis()],
+                )?,
+                None => db.conn().execute("DELETE FROM user_preferences WHERE key = ?1", [CHAT_MODEL_KEY])?,
+            };
+        }
+
+        ollama::set_chat_model(model);
+        Ok(ollama::chat_model())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_progress() {
+        let mut progress = TrainingProgress::default();
+
+        assert!(parse_progress(" 45%|████▌     | 45/100 [01:23<01:40,  1.83s/it]", &mut progress));
+        assert_eq!(progress.step, 45);
+        assert_eq!(progress.total_steps, Some(100));
+        assert!((progress.percent - 45.0).abs() < 0.01);
+
+        assert!(parse_progress("{'loss': 1.2345, 'grad_norm': 0.5, 'learning_rate': 0.0002, 'epoch': 1.5}", &mut progress));
+        assert_eq!(progress.loss, Some(1.2345));
+        assert_eq!(progress.epoch, Some(1.5));
+
+        assert!(!parse_progress("Loading checkpoint shards", &mut progress));
+    }
+
+    #[test]
+    fn test_axolotl_config() {
+        let config = TrainingConfig { format: TrainingFormat::ShareGPT, ..TrainingConfig::default() };
+        let yaml = axolotl_config(&config, Path::new("/tmp/job/dataset.jsonl"), Path::new("/tmp/job/adapter"));
+
+        assert!(yaml.contains("base_model: \"Qwen/Qwen2.5-7B-Instruct\""));
+        assert!(yaml.contains("  - path: \"/tmp/job/dataset.jsonl\""));
+        assert!(yaml.contains("type: chat_template"));
+        assert!(yaml.contains("output_dir: \"/tmp/job/adapter\""));
+        assert!(yaml.contains("lora_r: 16"));
+
+        let jsonl = TrainingConfig { format: TrainingFormat::JSONL, ..TrainingConfig::default() };
+        assert!(jsonl.validate().is_err());
+    }
+}
@@ -0,0 +1,532 @@
+//! Memory Browser (v3.9.0)
+//!
+//! Filtered, paginated browsing of episodic memory and timeline aggregation
+//! for the Memory Visualization page.
+//!
+//! Features:
+//! - Filters: date range, retention band, memory type, satisfaction range,
+//!   conversation, conversation topic, pinned, user-asserted, text
+//! - Sorting and offset pagination with the total match count
+//! - Timeline by day / week / month: counts, averages, memory types and top topics
+//!
+//! Retention, type and pinning columns come from temporal_memory; memories
+//! without them count as fresh (1.0), conversational and unpinned.
+
+#![allow(dead_code)]  // Phase 5: Memory browsing
+
+use crate::services::conversation_topics::topics_by_conversation;
+use crate::services::memory_scope::MemoryScope;
+use crate::services::temporal_memory::MemoryType;
+use anyhow::Result;
+use rusqlite::{params_from_iter, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Default and maximum page size
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 500;
+
+/// Topics listed per timeline period
+const TOP_TOPICS_PER_PERIOD: usize = 3;
+
+const RETENTION: &str = "COALESCE(e.retention_score, 1.0)";
+const MEMORY_TYPE: &str = "COALESCE(e.memory_type, 'conversational')";
+const PINNED: &str = "COALESCE(e.is_pinned, 0)";
+
+/// Retention bands, matching the temporal memory stats
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RetentionBand {
+    /// Above 0.7
+    High,
+    /// 0.3 to 0.7
+    Medium,
+    /// Below 0.3 (consolidation / cleanup candidates)
+    Low,
+}
+
+impl RetentionBand {
+    fn condition(&self) -> String {
+        match self {
+            RetentionBand::High => format!("{} > 0.7", RETENTION),
+            RetentionBand::Medium => format!("{} BETWEEN 0.3 AND 0.7", RETENTION),
+            RetentionBand::Low => format!("{} < 0.3", RETENTION),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MemorySort {
+    #[default]
+    Newest,
+    Oldest,
+    /// Weakest retention first
+    Retention,
+    MostAccessed,
+    Satisfaction,
+}
+
+impl MemorySort {
+    fn order_by(&self) -> String {
+        match self {
+            MemorySort::Newest => "e.created_at DESC".to_string(),
+            MemorySort::Oldest => "e.created_at ASC".to_string(),
+            MemorySort::Retention => format!("{} ASC, e.created_at DESC", RETENTION),
+            MemorySort::MostAccessed => "COALESCE(e.access_count, 0) DESC, e.created_at DESC".to_string(),
+            MemorySort::Satisfaction => "e.satisfaction DESC, e.created_at DESC".to_string(),
+        }
+    }
+}
+
+/// Browsing filters; all given filters must match
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryFilter {
+    /// Created on or after (Unix seconds)
+    pub created_after: Option<i64>,
+    /// Created before (Unix seconds)
+    pub created_before: Option<i64>,
+    pub retention: Option<RetentionBand>,
+    pub memory_type: Option<MemoryType>,
+    pub min_satisfaction: Option<f32>,
+    pub max_satisfaction: Option<f32>,
+    pub conversation_id: Option<String>,
+    /// Memories from conversations labeled with this topic
+    pub topic: Option<String>,
+    #[serde(default)]
+    pub pinned_only: bool,
+    /// Only memories the user wrote or corrected by hand
+    #[serde(default)]
+    pub user_asserted_only: bool,
+    /// Substring of the user message or response
+    pub query: Option<String>,
+    #[serde(default)]
+    pub sort: MemorySort,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+/// Memory list entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryItem {
+    pub id: String,
+    pub user_message: String,
+    pub ai_response: String,
+    pub satisfaction: f32,
+    pub created_at: i64,
+    pub access_count: i32,
+    pub importance: f32,
+    pub retention_score: f32,
+    pub memory_type: MemoryType,
+    pub is_pinned: bool,
+    pub conversation_id: Option<String>,
+    /// Written or corrected by the user by hand
+    pub user_asserted: bool,
+    pub user_note: Option<String>,
+    /// Where the memory can be retrieved
+    pub scope: MemoryScope,
+    /// Topics of the source conversation
+    pub topics: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryPage {
+    pub items: Vec<MemoryItem>,
+    /// Memories matching the filter
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimelineBucket {
+    #[default]
+    Day,
+    /// Weeks start on Monday
+    Week,
+    Month,
+}
+
+impl TimelineBucket {
+    /// SQLite expression giving the period key of `e.created_at` shifted by `?1` seconds
+    fn period_expr(&self) -> &'static str {
+        match self {
+            TimelineBucket::Day => "strftime('%Y-%m-%d', e.created_at + ?1, 'unixepoch')",
+            TimelineBucket::Week => "date(e.created_at + ?1, 'unixepoch', '-6 days', 'weekday 1')",
+            TimelineBucket::Month => "strftime('%Y-%m', e.created_at + ?1, 'unixepoch')",
+        }
+    }
+}
+
+/// One timeline period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelinePeriod {
+    /// "2025-03-14" (day / week start) or "2025-03" (month)
+    pub period: String,
+    pub count: usize,
+    pub pinned: usize,
+    pub avg_satisfaction: f32,
+    pub avg_retention: f32,
+    /// Memory count per type ("factual", "procedural", ...)
+    pub by_type: BTreeMap<String, usize>,
+    pub top_topics: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryTimeline {
+    pub bucket: TimelineBucket,
+    pub periods: Vec<TimelinePeriod>,
+    pub total: usize,
+}
+
+/// Running sums for one timeline period
+#[derive(Default)]
+struct PeriodTotals {
+    count: usize,
+    pinned: usize,
+    satisfaction: f64,
+    retention: f64,
+    by_type: BTreeMap<String, usize>,
+}
+
+/// WHERE clause and values for `filter` (values are positional `?`)
+fn filter_conditions(filter: &MemoryFilter) -> (String, Vec<rusqlite::types::Value>) {
+    let mut conditions: Vec<String> = Vec::new();
+    let mut values: Vec<rusqlite::types::Value> = Vec::new();
+
+    if let Some(after) = filter.created_after {
+        conditions.push("e.created_at >= ?".to_string());
+        values.push(after.into());
+    }
+    if let Some(before) = filter.created_before {
+        conditions.push("e.created_at < ?".to_string());
+        values.push(before.into());
+    }
+    if let Some(band) = filter.retention {
+        conditions.push(band.condition());
+    }
+    if let Some(memory_type) = filter.memory_type {
+        conditions.push(format!("{} = ?", MEMORY_TYPE));
+        values.push(memory_type.as_str().to_string().into());
+    }
+    if let Some(min) = filter.min_satisfaction {
+        conditions.push("e.satisfaction >= ?".to_string());
+        values.push(f64::from(min).into());
+    }
+    if let Some(max) = filter.max_satisfaction {
+        conditions.push("e.satisfaction <= ?".to_string());
+        values.push(f64::from(max).into());
+    }
+    if let Some(conversation_id) = filter.conversation_id.as_deref().filter(|c| !c.is_empty()) {
+        conditions.push("e.conversation_id = ?".to_string());
+        values.push(conversation_id.to_string().into());
+    }
+    if let Some(topic) = filter.topic.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        conditions.push("e.conversation_id IN (SELECT conversation_id FROM conversation_topic_labels WHERE topic = ?)".to_string());
+        values.push(topic.to_string().into());
+    }
+    if filter.pinned_only {
+        conditions.push(format!("{} = 1", PINNED));
+    }
+    if filter.user_asserted_only {
+        conditions.push("COALESCE(e.user_asserted, 0) = 1".to_string());
+    }
+    if let Some(query) = filter.query.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+        conditions.push("(e.user_message LIKE ? ESCAPE '\\' OR e.ai_response LIKE ? ESCAPE '\\')".to_string());
+        let escaped = query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        let pattern = format!("%{}%", escaped);
+        values.push(pattern.clone().into());
+        values.push(pattern.into());
+    }
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+    (where_clause, values)
+}
+
+/// One page of memories matching `filter`
+pub fn browse_memories(conn: &Connection, filter: &MemoryFilter) -> Result<MemoryPage> {
+    let (where_clause, values) = filter_conditions(filter);
+    let limit = filter.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let offset = filter.offset.unwrap_or(0);
+
+    let total: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM episodic_memory e {}", where_clause),
+        params_from_iter(values.iter()),
+        |row| row.get(0),
+    )?;
+
+    let tail = format!("ORDER BY {} LIMIT {} OFFSET {}", filter.sort.order_by(), limit, offset);
+    let items = query_items(conn, &where_clause, &tail, &values)?;
+
+    let total = total as usize;
+    Ok(MemoryPage {
+        has_more: offset + items.len() < total,
+        items,
+        total,
+        offset,
+        limit,
+    })
+}
+
+/// One memory with its topics
+pub fn get_memory(conn: &Connection, id: &str) -> Result<Option<MemoryItem>> {
+    let values = vec![rusqlite::types::Value::from(id.to_string())];
+    Ok(query_items(conn, "WHERE e.id = ?", "", &values)?.into_iter().next())
+}
+
+/// Memories selected by `where_clause` and `tail` (ORDER BY / LIMIT), with topics
+fn query_items(
+    conn: &Connection,
+    where_clause: &str,
+    tail: &str,
+    values: &[rusqlite::types::Value],
+) -> Result<Vec<MemoryItem>> {
+    let sql = format!(
+        "SELECT e.id, e.user_message, e.ai_response, e.satisfaction, e.created_at,
+                COALESCE(e.access_count, 0), e.importance, {}, {}, {}, e.conversation_id,
+                COALESCE(e.user_asserted, 0), e.user_note, e.scope, e.scope_id
+         FROM episodic_memory e
+         {}
+         {}",
+        RETENTION, MEMORY_TYPE, PINNED, where_clause, tail
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let mut items: Vec<MemoryItem> = stmt
+        .query_map(params_from_iter(values.iter()), |row| {
+            let memory_type: String = row.get(8)?;
+            Ok(MemoryItem {
+                id: row.get(0)?,
+                user_message: row.get(1)?,
+                ai_response: row.get(2)?,
+                satisfaction: row.get(3)?,
+                created_at: row.get(4)?,
+                access_count: row.get(5)?,
+                importance: row.get(6)?,
+                retention_score: row.get(7)?,
+                memory_type: MemoryType::from_str(&memory_type),
+                is_pinned: row.get(9)?,
+                conversation_id: row.get(10)?,
+                user_asserted: row.get(11)?,
+                user_note: row.get(12)?,
+                scope: MemoryScope::from_columns(row.get::<_, Option<String>>(13)?.as_deref(), row.get(14)?),
+                topics: Vec::new(),
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let topics = topics_by_conversation(conn, items.iter().filter_map(|m| m.conversation_id.as_deref()))?;
+    for item in &mut items {
+        if let Some(conversation_id) = &item.conversation_id {
+            item.topics = topics.get(conversation_id).cloned().unwrap_or_default();
+        }
+    }
+    Ok(items)
+}
+
+/// Memories matching `filter` per period, oldest first
+///
+/// `utc_offset_minutes` shifts period boundaries to the user's local time.
+/// Sorting and pagination of the filter are ignored.
+pub fn memory_timeline(
+    conn: &Connection,
+    filter: &MemoryFilter,
+    bucket: TimelineBucket,
+    utc_offset_minutes: i32,
+) -> Result<MemoryTimeline> {
+    let (where_clause, filter_values) = filter_conditions(filter);
+    let offset_seconds = i64::from(utc_offset_minutes) * 60;
+    let mut values: Vec<rusqlite::types::Value> = vec![offset_seconds.into()];
+    values.extend(filter_values);
+    let period = bucket.period_expr();
+
+    let sql = format!(
+        "SELECT {period}, {memory_type}, COUNT(*), SUM({pinned}), SUM(e.satisfaction), SUM({retention})
+         FROM episodic_memory e
+         {where_clause}
+         GROUP BY 1, 2",
+        period = period,
+        memory_type = MEMORY_TYPE,
+        pinned = PINNED,
+        retention = RETENTION,
+        where_clause = where_clause,
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params_from_iter(values.iter()), |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, i64>(3)?,
+            row.get::<_, f64>(4)?,
+            row.get::<_, f64>(5)?,
+        ))
+    })?;
+
+    let mut periods: BTreeMap<String, PeriodTotals> = BTreeMap::new();
+    for (key, memory_type, count, pinned, satisfaction, retention) in rows.filter_map(|r| r.ok()) {
+        let totals = periods.entry(key).or_default();
+        totals.count += count as usize;
+        totals.pinned += pinned as usize;
+        totals.satisfaction += satisfaction;
+        totals.retention += retention;
+        *totals.by_type.entry(memory_type).or_default() += count as usize;
+    }
+
+    let topics = period_topics(conn, &where_clause, &values, period)?;
+
+    let total = periods.values().map(|p| p.count).sum();
+    let periods = periods
+        .into_iter()
+        .map(|(period, totals)| TimelinePeriod {
+            top_topics: topics.get(&period).cloned().unwrap_or_default(),
+            period,
+            count: totals.count,
+            pinned: totals.pinned,
+            avg_satisfaction: (totals.satisfaction / totals.count.max(1) as f64) as f32,
+            avg_retention: (totals.retention / totals.count.max(1) as f64) as f32,
+            by_type: totals.by_type,
+        })
+        .collect();
+
+    Ok(MemoryTimeline { bucket, periods, total })
+}
+
+/// Most frequent conversation topics per period
+fn period_topics(
+    conn: &Connection,
+    where_clause: &str,
+    values: &[rusqlite::types::Value],
+    period: &str,
+) -> Result<HashMap<String, Vec<String>>> {
+    let sql = format!(
+        "SELECT {}, l.topic, COUNT(*) AS n
+         FROM episodic_memory e
+         JOIN conversation_topic_labels l ON l.conversation_id = e.conversation_id
+         {}
+         GROUP BY 1, 2
+         ORDER BY 1, n DESC, l.topic",
+        period, where_clause
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params_from_iter(values.iter()), |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let mut topics: HashMap<String, Vec<String>> = HashMap::new();
+    for (period, topic) in rows.filter_map(|r| r.ok()) {
+        let period_topics = topics.entry(period).or_default();
+        if period_topics.len() < TOP_TOPICS_PER_PERIOD {
+            period_topics.push(topic);
+        }
+    }
+    Ok(topics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::services::temporal_memory::TemporalMemoryService;
+    use std::sync::{Arc, Mutex};
+
+    // 2025-03-10 00:00:00 UTC (a Monday)
+    const MONDAY: i64 = 1_741_564_800;
+    const DAY: i64 = 86_400;
+
+    fn setup() -> Arc<Mutex<Database>> {
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        TemporalMemoryService::new(Arc::clone(&db)).unwrap();
+        {
+            let db = db.lock().unwrap();
+            let conn = db.conn();
+            conn.execute(
+                "INSERT INTO conversations (id, title, mode, created_at, updated_at, message_count)
+                 VALUES ('c1', 'Rust', 'user-led', 0, 0, 0)",
+                [],
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO conversation_topic_labels (conversation_id, topic, source, assigned_at) VALUES ('c1', 'rust', 'auto', 0)",
+                [],
+            )
+            .unwrap();
+            let memories = [
+                ("m1", "How do lifetimes work?", 0.9, MONDAY, 0.9, "factual", Some("c1")),
+                ("m2", "Set up cargo workspace", 0.6, MONDAY + DAY, 0.5, "procedural", Some("c1")),
+                ("m3", "Hi there", 0.4, MONDAY + 8 * DAY, 0.2, "ephemeral", None),
+            ];
+            for (id, message, satisfaction, created_at, retention, memory_type, conversation_id) in memories {
+                conn.execute(
+                    "INSERT INTO episodic_memory (id, user_message, ai_response, satisfaction, created_at, importance,
+                                                  retention_score, memory_type, conversation_id)
+                     VALUES (?1, ?2, 'answer', ?3, ?4, 0.5, ?5, ?6, ?7)",
+                    rusqlite::params![id, message, satisfaction, created_at, retention, memory_type, conversation_id],
+                )
+                .unwrap();
+            }
+        }
+        db
+    }
+
+    #[test]
+    fn test_browse_filters_and_pagination() {
+        let db = setup();
+        let db = db.lock().unwrap();
+        let conn = db.conn();
+
+        let page = browse_memories(conn, &MemoryFilter { limit: Some(2), ..Default::default() }).unwrap();
+        assert_eq!(page.total, 3);
+        assert!(page.has_more);
+        assert_eq!(page.items[0].id, "m3");
+
+        let page = browse_memories(conn, &MemoryFilter { limit: Some(2), offset: Some(2), ..Default::default() }).unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert!(!page.has_more);
+
+        let by_topic = MemoryFilter { topic: Some("Rust".to_string()), sort: MemorySort::Oldest, ..Default::default() };
+        let page = browse_memories(conn, &by_topic).unwrap();
+        let ids: Vec<&str> = page.items.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["m1", "m2"]);
+        assert_eq!(page.items[0].topics, vec!["rust".to_string()]);
+
+        let low = MemoryFilter { retention: Some(RetentionBand::Low), ..Default::default() };
+        assert_eq!(browse_memories(conn, &low).unwrap().items[0].id, "m3");
+
+        let typed = MemoryFilter {
+            memory_type: Some(MemoryType::Procedural),
+            min_satisfaction: Some(0.5),
+            created_after: Some(MONDAY),
+            created_before: Some(MONDAY + 7 * DAY),
+            ..Default::default()
+        };
+        let page = browse_memories(conn, &typed).unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(page.items[0].memory_type, MemoryType::Procedural);
+    }
+
+    #[test]
+    fn test_timeline() {
+        let db = setup();
+        let db = db.lock().unwrap();
+        let conn = db.conn();
+
+        let weekly = memory_timeline(conn, &MemoryFilter::default(), TimelineBucket::Week, 0).unwrap();
+        assert_eq!(weekly.total, 3);
+        let periods: Vec<&str> = weekly.periods.iter().map(|p| p.period.as_str()).collect();
+        assert_eq!(periods, vec!["2025-03-10", "2025-03-17"]);
+        assert_eq!(weekly.periods[0].count, 2);
+        assert_eq!(weekly.periods[0].by_type.get("factual"), Some(&1));
+        assert_eq!(weekly.periods[0].top_topics, vec!["rust".to_string()]);
+        assert!((weekly.periods[0].avg_satisfaction - 0.75).abs() < 1e-6);
+
+        // Nine hours behind UTC, the first memory falls on the previous day
+        let daily = memory_timeline(conn, &MemoryFilter::default(), TimelineBucket::Day, -9 * 60).unwrap();
+        assert_eq!(daily.periods[0].period, "2025-03-09");
+    }
+}
@@ -11,6 +11,12 @@
  * 4. Delete original low-retention memories
  * 5. Preserve access patterns and satisfaction scores
  *
+ * Review (v3.9.0):
+ * - With `require_review` (default) clusters become merge proposals with their
+ *   similarity scores and proposed merged memory; nothing is merged until the
+ *   user accepts a proposal
+ * - Accept / reject decisions tune `similarity_threshold` (`tune_threshold`)
+ *
  * Benefits:
  * - Reduces database size by 30-50%
  * - Preserves knowledge from multiple similar conversations
@@ -29,6 +35,7 @@ use crate::services::embedding::UnifiedEmbeddingService;
 use crate::services::rag_v2::RagServiceV2;  // v3.4.0: LanceDB migration
 use crate::services::ollama;
 use anyhow::{Context, Result};
+use rusqlite::OptionalExtension;
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -50,6 +57,18 @@ pub struct ConsolidationConfig {
 
     /// Whether to use LLM for generating consolidated summaries
     pub use_llm_summaries: bool,
+
+    /// Queue merge proposals for review instead of merging directly (v3.9.0)
+    #[serde(default = "default_true")]
+    pub require_review: bool,
+
+    /// Tune `similarity_threshold` from accepted / rejected proposals (v3.9.0)
+    #[serde(default = "default_true")]
+    pub auto_tune_threshold: bool,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 impl Default for ConsolidationConfig {
@@ -60,6 +79,8 @@ impl Default for ConsolidationConfig {
             min_cluster_size: 2,           // Merge at least 2 memories
             max_cluster_size: 5,           // Max 5 memories per cluster
             use_llm_summaries: true,       // Use LLM for better summaries
+            require_review: true,          // User approves every merge
+            auto_tune_threshold: true,     // Learn the threshold from decisions
         }
     }
 }
@@ -74,6 +95,9 @@ pub struct MemoryCluster {
     pub total_access_count: i32,
     pub earliest_timestamp: i64,
     pub latest_timestamp: i64,
+    /// Similarity of each member to the memory the cluster was grown from (v3.9.0)
+    #[serde(default)]
+    pub similarities: Vec<f32>,
 }
 
 /// Consolidation result
@@ -86,6 +110,85 @@ pub struct ConsolidationResult {
     pub consolidated_at: i64,
 }
 
+/// Decided proposals considered when tuning the threshold (v3.9.0)
+const TUNING_WINDOW: usize = 100;
+/// Decisions needed before the threshold moves
+const MIN_DECISIONS_FOR_TUNING: usize = 5;
+/// Fraction of the way the threshold moves toward the best split per tuning
+const TUNING_RATE: f32 = 0.5;
+/// Step down when every recent proposal was accepted (proposals are only made
+/// above the threshold, so acceptances alone never show it is too high)
+const EXPLORE_STEP: f32 = 0.02;
+const MIN_SIMILARITY_THRESHOLD: f32 = 0.6;
+const MAX_SIMILARITY_THRESHOLD: f32 = 0.95;
+
+/// Proposal status (v3.9.0)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProposalStatus {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+impl ProposalStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProposalStatus::Pending => "pending",
+            ProposalStatus::Accepted => "accepted",
+            ProposalStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(ProposalStatus::Pending),
+            "accepted" => Some(ProposalStatus::Accepted),
+            "rejected" => Some(ProposalStatus::Rejected),
+            _ => None,
+        }
+    }
+}
+
+/// A memory in a merge proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalSource {
+    pub memory_id: String,
+    pub user_message: String,
+    pub ai_response: String,
+    pub similarity: f32,
+}
+
+/// Merge candidate awaiting the user's decision (v3.9.0)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeProposal {
+    pub id: String,
+    pub memory_ids: Vec<String>,
+    pub similarities: Vec<f32>,
+    /// Lowest member similarity; what the threshold is tuned against
+    pub min_similarity: f32,
+    /// Threshold in effect when the proposal was made
+    pub threshold: f32,
+    pub merged_user_message: String,
+    pub merged_ai_response: String,
+    /// Source memories that still exist
+    pub sources: Vec<ProposalSource>,
+    pub status: ProposalStatus,
+    pub created_at: i64,
+    pub decided_at: Option<i64>,
+}
+
+/// Outcome of accepting or rejecting a proposal (v3.9.0)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalDecision {
+    pub proposal_id: String,
+    pub status: ProposalStatus,
+    /// Merge result when accepted
+    pub result: Option<ConsolidationResult>,
+    /// Threshold after tuning
+    pub similarity_threshold: f32,
+}
+
 /// Statistics for consolidation operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsolidationStats {
@@ -119,6 +222,7 @@ impl MemoryConsolidationService {
         };
 
         service.init_database()?;
+        service.retune_threshold()?;
 
         Ok(service)
     }
@@ -159,6 +263,24 @@ impl MemoryConsolidationService {
             [],
         );
 
+        // Merge proposals awaiting review (v3.9.0)
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS memory_merge_proposals (
+                id TEXT PRIMARY KEY,
+                memory_ids TEXT NOT NULL,
+                similarities TEXT NOT NULL,
+                min_similarity REAL NOT NULL,
+                threshold REAL NOT NULL,
+                merged_user_message TEXT NOT NULL,
+                merged_ai_response TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'pending' CHECK(status IN ('pending', 'accepted', 'rejected')),
+                created_at INTEGER NOT NULL,
+                decided_at INTEGER
+            );
+            CREATE INDEX IF NOT EXISTS idx_memory_merge_proposals_status
+                ON memory_merge_proposals(status, created_at DESC);",
+        )?;
+
         log::info!("Memory consolidation database initialized");
 
         Ok(())
@@ -166,7 +288,8 @@ impl MemoryConsolidationService {
 
     /// Run memory consolidation process
     ///
-    /// Finds low-retention memory clusters and merges them
+    /// Finds low-retention memory clusters and merges them without review
+    /// (see `propose_merges` for the reviewed flow)
     pub async fn consolidate_memories(&self) -> Result<Vec<ConsolidationResult>> {
         log::info!("Starting memory consolidation process...");
 
@@ -278,14 +401,14 @@ impl MemoryConsolidationService {
                 .await?;
 
             // Filter by similarity threshold and exclude already-used memories
-            let mut cluster_members: Vec<(String, f32, f32, i32, i64)> = similar
+            let mut cluster_members: Vec<(String, f32, f32, i32, i64, f32)> = similar
                 .into_iter()
                 .filter(|(ep, sim)| {
                     *sim >= config.similarity_threshold
                         && !used_memory_ids.contains(&ep.id)
                         && candidates.iter().any(|(cid, _, _, _, _, _, _)| cid == &ep.id)
                 })
-                .map(|(ep, _)| {
+                .map(|(ep, sim)| {
                     // Find full candidate data
                     let (_, _, _, ret, sat, acc, ts) = candidates
                         .iter()
@@ -301,7 +424,7 @@ impl MemoryConsolidationService {
                             ep.created_at,
                         ));
 
-                    (ep.id, ret, sat, acc, ts)
+                    (ep.id, ret, sat, acc, ts, sim)
                 })
                 .collect();
 
@@ -314,12 +437,13 @@ impl MemoryConsolidationService {
             cluster_members.truncate(config.max_cluster_size);
 
             // Calculate cluster statistics
-            let memory_ids: Vec<String> = cluster_members.iter().map(|(id, _, _, _, _)| id.clone()).collect();
-            let avg_retention = cluster_members.iter().map(|(_, r, _, _, _)| r).sum::<f32>() / cluster_members.len() as f32;
-            let avg_satisfaction = cluster_members.iter().map(|(_, _, s, _, _)| s).sum::<f32>() / cluster_members.len() as f32;
-            let total_access = cluster_members.iter().map(|(_, _, _, a, _)| a).sum::<i32>();
-            let earliest = cluster_members.iter().map(|(_, _, _, _, t)| t).min().cloned().unwrap_or(0);
-            let latest = cluster_members.iter().map(|(_, _, _, _, t)| t).max().cloned().unwrap_or(0);
+            let memory_ids: Vec<String> = cluster_members.iter().map(|(id, _, _, _, _, _)| id.clone()).collect();
+            let avg_retention = cluster_members.iter().map(|(_, r, _, _, _, _)| r).sum::<f32>() / cluster_members.len() as f32;
+            let avg_satisfaction = cluster_members.iter().map(|(_, _, s, _, _, _)| s).sum::<f32>() / cluster_members.len() as f32;
+            let total_access = cluster_members.iter().map(|(_, _, _, a, _, _)| a).sum::<i32>();
+            let earliest = cluster_members.iter().map(|(_, _, _, _, t, _)| t).min().cloned().unwrap_or(0);
+            let latest = cluster_members.iter().map(|(_, _, _, _, t, _)| t).max().cloned().unwrap_or(0);
+            let similarities = cluster_members.iter().map(|(_, _, _, _, _, sim)| *sim).collect();
 
             let cluster = MemoryCluster {
                 cluster_id: uuid::Uuid::new_v4().to_string(),
@@ -329,6 +453,7 @@ impl MemoryConsolidationService {
                 total_access_count: total_access,
                 earliest_timestamp: earliest,
                 latest_timestamp: latest,
+                similarities,
             };
 
             // Mark memories as used
@@ -352,11 +477,40 @@ impl MemoryConsolidationService {
         let memories = self.fetch_cluster_memories(&cluster.memory_ids)?;
 
         // Generate consolidated summary
-        let (consolidated_user_msg, consolidated_ai_resp) = if config.use_llm_summaries {
-            self.generate_llm_summary(&memories).await?
+        let (consolidated_user_msg, consolidated_ai_resp) = self.summarize(&memories, config).await?;
+
+        self.merge_memories(
+            &memories,
+            &consolidated_user_msg,
+            &consolidated_ai_resp,
+            cluster.average_satisfaction,
+            cluster.total_access_count,
+        ).await
+    }
+
+    /// Consolidated (user message, assistant response) for a set of memories
+    async fn summarize(
+        &self,
+        memories: &[(String, String, String, f32, f32, i32, i64)],
+        config: &ConsolidationConfig,
+    ) -> Result<(String, String)> {
+        if config.use_llm_summaries {
+            self.generate_llm_summary(memories).await
         } else {
-            self.generate_simple_summary(&memories)
-        };
+            Ok(self.generate_simple_summary(memories))
+        }
+    }
+
+    /// Replace `memories` with one consolidated memory and record it
+    async fn merge_memories(
+        &self,
+        memories: &[(String, String, String, f32, f32, i32, i64)],
+        consolidated_user_msg: &str,
+        consolidated_ai_resp: &str,
+        satisfaction: f32,
+        total_access_count: i32,
+    ) -> Result<ConsolidationResult> {
+        let memory_ids: Vec<String> = memories.iter().map(|(id, _, _, _, _, _, _)| id.clone()).collect();
 
         // Calculate original size
         let original_size: usize = memories
@@ -369,15 +523,15 @@ impl MemoryConsolidationService {
 
         // Create consolidated memory
         let consolidated_id = self.create_consolidated_memory(
-            &consolidated_user_msg,
-            &consolidated_ai_resp,
-            cluster.average_satisfaction,
-            cluster.total_access_count,
-            cluster.memory_ids.len(),
+            consolidated_user_msg,
+            consolidated_ai_resp,
+            satisfaction,
+            total_access_count,
+            memory_ids.len(),
         ).await?;
 
         // Delete original memories
-        self.delete_source_memories(&cluster.memory_ids)?;
+        self.delete_source_memories(&memory_ids)?;
 
         // Record consolidation
         let now = SystemTime::now()
@@ -386,20 +540,292 @@ impl MemoryConsolidationService {
 
         self.record_consolidation(
             &consolidated_id,
-            &cluster.memory_ids,
+            &memory_ids,
             space_saved,
             now,
         )?;
 
         Ok(ConsolidationResult {
             consolidated_id,
-            source_memory_ids: cluster.memory_ids.clone(),
-            memories_merged: cluster.memory_ids.len(),
+            memories_merged: memory_ids.len(),
+            source_memory_ids: memory_ids,
             space_saved,
             consolidated_at: now,
         })
     }
 
+    /// Queue merge proposals for the current clusters (v3.9.0)
+    ///
+    /// Memories already in a pending proposal are skipped, as are clusters
+    /// identical to a rejected proposal.
+    pub async fn propose_merges(&self) -> Result<Vec<MergeProposal>> {
+        let config = self.config.lock().unwrap().clone();
+
+        let pending = self.memory_id_sets(ProposalStatus::Pending)?;
+        let rejected = self.memory_id_sets(ProposalStatus::Rejected)?;
+        let pending_ids: std::collections::HashSet<String> = pending.into_iter().flatten().collect();
+
+        let candidates: Vec<_> = self
+            .find_consolidation_candidates(config.retention_threshold)?
+            .into_iter()
+            .filter(|(id, _, _, _, _, _, _)| !pending_ids.contains(id))
+            .collect();
+        if candidates.len() < config.min_cluster_size {
+            log::info!("Not enough candidates for merge proposals ({} found)", candidates.len());
+            return Ok(Vec::new());
+        }
+
+        let clusters = self.cluster_similar_memories(&candidates, &config).await?;
+
+        let mut proposals = Vec::new();
+        for cluster in clusters {
+            let mut key = cluster.memory_ids.clone();
+            key.sort();
+            if rejected.contains(&key) {
+                continue;
+            }
+
+            let memories = self.fetch_cluster_memories(&cluster.memory_ids)?;
+            let (user_message, ai_response) = match self.summarize(&memories, &config).await {
+                Ok(summary) => summary,
+                Err(e) => {
+                    log::warn!("Failed to summarize cluster {}: {}", cluster.cluster_id, e);
+                    continue;
+                }
+            };
+            proposals.push(self.insert_proposal(&cluster, config.similarity_threshold, &user_message, &ai_response)?);
+        }
+
+        log::info!("Queued {} memory merge proposals", proposals.len());
+        Ok(proposals)
+    }
+
+    fn insert_proposal(
+        &self,
+        cluster: &MemoryCluster,
+        threshold: f32,
+        user_message: &str,
+        ai_response: &str,
+    ) -> Result<MergeProposal> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let min_similarity = cluster.similarities.iter().cloned().fold(1.0_f32, f32::min);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+
+        {
+            let db = self.db.lock().unwrap();
+            db.conn().execute(
+                "INSERT INTO memory_merge_proposals (
+                    id, memory_ids, similarities, min_similarity, threshold,
+                    merged_user_message, merged_ai_response, status, created_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 'pending', ?8)",
+                rusqlite::params![
+                    id,
+                    serde_json::to_string(&cluster.memory_ids)?,
+                    serde_json::to_string(&cluster.similarities)?,
+                    min_similarity,
+                    threshold,
+                    user_message,
+                    ai_response,
+                    now,
+                ],
+            )?;
+        }
+
+        self.get_proposal(&id)
+    }
+
+    /// Memory id sets (sorted) of the proposals with `status`
+    fn memory_id_sets(&self, status: ProposalStatus) -> Result<Vec<Vec<String>>> {
+        let db = self.db.lock().unwrap();
+        let mut stmt = db
+            .conn()
+            .prepare("SELECT memory_ids FROM memory_merge_proposals WHERE status = ?1")?;
+        let sets = stmt
+            .query_map([status.as_str()], |row| row.get::<_, String>(0))?
+            .filter_map(|r| r.ok())
+            .filter_map(|json| serde_json::from_str::<Vec<String>>(&json).ok())
+            .map(|mut ids| {
+                ids.sort();
+                ids
+            })
+            .collect();
+        Ok(sets)
+    }
+
+    /// Proposals, newest first (all statuses when `status` is None)
+    pub fn list_proposals(&self, status: Option<ProposalStatus>, limit: usize) -> Result<Vec<MergeProposal>> {
+        let ids: Vec<String> = {
+            let db = self.db.lock().unwrap();
+            let mut stmt = db.conn().prepare(
+                "SELECT id FROM memory_merge_proposals
+                 WHERE ?1 IS NULL OR status = ?1
+                 ORDER BY created_at DESC, rowid DESC
+                 LIMIT ?2",
+            )?;
+            let ids = stmt
+                .query_map(rusqlite::params![status.map(|s| s.as_str()), limit as i64], |row| row.get(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            ids
+        };
+        ids.iter().map(|id| self.get_proposal(id)).collect()
+    }
+
+    pub fn get_proposal(&self, proposal_id: &str) -> Result<MergeProposal> {
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
+
+        let (memory_ids, similarities, min_similarity, threshold, merged_user_message, merged_ai_response, status, created_at, decided_at): (
+            String, String, f32, f32, String, String, String, i64, Option<i64>,
+        ) = conn
+            .query_row(
+                "SELECT memory_ids, similarities, min_similarity, threshold, merged_user_message,
+                        merged_ai_response, status, created_at, decided_at
+                 FROM memory_merge_proposals WHERE id = ?1",
+                [proposal_id],
+                |row| {
+                    Ok((
+                        row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?,
+                        row.get(5)?, row.get(6)?, row.get(7)?, row.get(8)?,
+                    ))
+                },
+            )
+            .optional()?
+            .ok_or_else(|| anyhow::anyhow!("Merge proposal not found: {}", proposal_id))?;
+
+        let memory_ids: Vec<String> = serde_json::from_str(&memory_ids)?;
+        let similarities: Vec<f32> = serde_json::from_str(&similarities)?;
+
+        let mut sources = Vec::new();
+        for (i, memory_id) in memory_ids.iter().enumerate() {
+            let memory = conn
+                .query_row(
+                    "SELECT user_message, ai_response FROM episodic_memory WHERE id = ?1",
+                    [memory_id],
+                    |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+                )
+                .optional()?;
+            if let Some((user_message, ai_response)) = memory {
+                sources.push(ProposalSource {
+                    memory_id: memory_id.clone(),
+                    user_message,
+                    ai_response,
+                    similarity: similarities.get(i).copied().unwrap_or(min_similarity),
+                });
+            }
+        }
+
+        Ok(MergeProposal {
+            id: proposal_id.to_string(),
+            memory_ids,
+            similarities,
+            min_similarity,
+            threshold,
+            merged_user_message,
+            merged_ai_response,
+            sources,
+            status: ProposalStatus::from_str(&status).unwrap_or(ProposalStatus::Pending),
+            created_at,
+            decided_at,
+        })
+    }
+
+    /// Merge a proposal's memories, optionally with an edited merged memory
+    pub async fn accept_proposal(
+        &self,
+        proposal_id: &str,
+        user_message: Option<String>,
+        ai_response: Option<String>,
+    ) -> Result<ProposalDecision> {
+        let proposal = self.get_proposal(proposal_id)?;
+        if proposal.status != ProposalStatus::Pending {
+            anyhow::bail!("Merge proposal {} was already {}", proposal_id, proposal.status.as_str());
+        }
+
+        let memories = self.fetch_cluster_memories(&proposal.memory_ids)?;
+        if memories.len() < 2 {
+            anyhow::bail!("Only {} of the proposal's memories still exist; reject it instead", memories.len());
+        }
+
+        let satisfaction = memories.iter().map(|(_, _, _, s, _, _, _)| s).sum::<f32>() / memories.len() as f32;
+        let total_access = memories.iter().map(|(_, _, _, _, _, a, _)| a).sum::<i32>();
+        let user_message = user_message.unwrap_or(proposal.merged_user_message);
+        let ai_response = ai_response.unwrap_or(proposal.merged_ai_response);
+
+        let result = self
+            .merge_memories(&memories, &user_message, &ai_response, satisfaction, total_access)
+            .await?;
+        self.set_proposal_status(proposal_id, ProposalStatus::Accepted)?;
+
+        Ok(ProposalDecision {
+            proposal_id: proposal_id.to_string(),
+            status: ProposalStatus::Accepted,
+            result: Some(result),
+            similarity_threshold: self.retune_threshold()?,
+        })
+    }
+
+    /// Keep a proposal's memories separate
+    pub fn reject_proposal(&self, proposal_id: &str) -> Result<ProposalDecision> {
+        let proposal = self.get_proposal(proposal_id)?;
+        if proposal.status != ProposalStatus::Pending {
+            anyhow::bail!("Merge proposal {} was already {}", proposal_id, proposal.status.as_str());
+        }
+
+        self.set_proposal_status(proposal_id, ProposalStatus::Rejected)?;
+
+        Ok(ProposalDecision {
+            proposal_id: proposal_id.to_string(),
+            status: ProposalStatus::Rejected,
+            result: None,
+            similarity_threshold: self.retune_threshold()?,
+        })
+    }
+
+    fn set_proposal_status(&self, proposal_id: &str, status: ProposalStatus) -> Result<()> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        let db = self.db.lock().unwrap();
+        db.conn().execute(
+            "UPDATE memory_merge_proposals SET status = ?1, decided_at = ?2 WHERE id = ?3",
+            rusqlite::params![status.as_str(), now, proposal_id],
+        )?;
+        log::info!("Merge proposal {} {}", proposal_id, status.as_str());
+        Ok(())
+    }
+
+    /// Tune the similarity threshold from recent decisions; returns the threshold in effect
+    fn retune_threshold(&self) -> Result<f32> {
+        let current = self.config.lock().unwrap().clone();
+        if !current.auto_tune_threshold {
+            return Ok(current.similarity_threshold);
+        }
+
+        let decisions: Vec<(f32, bool)> = {
+            let db = self.db.lock().unwrap();
+            let mut stmt = db.conn().prepare(
+                "SELECT min_similarity, status = 'accepted' FROM memory_merge_proposals
+                 WHERE status != 'pending'
+                 ORDER BY decided_at DESC
+                 LIMIT ?1",
+            )?;
+            let decisions = stmt
+                .query_map([TUNING_WINDOW as i64], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            decisions
+        };
+
+        let tuned = tune_threshold(&decisions, current.similarity_threshold);
+        if (tuned - current.similarity_threshold).abs() > f32::EPSILON {
+            log::info!(
+                "Consolidation similarity threshold tuned {:.2} → {:.2} ({} decisions)",
+                current.similarity_threshold,
+                tuned,
+                decisions.len()
+            );
+            self.config.lock().unwrap().similarity_threshold = tuned;
+        }
+        Ok(tuned)
+    }
+
     /// Fetch full memory data for a cluster
     fn fetch_cluster_memories(
         &self,
@@ -698,6 +1124,39 @@ Respond ONLY with valid JSON in this format (no other text):
     }
 }
 
+/// Similarity threshold that best separates accepted from rejected proposals (v3.9.0)
+///
+/// `decisions` are (lowest member similarity, accepted). The threshold with the
+/// fewest misclassified decisions (closest to `current` on ties) is approached by
+/// `TUNING_RATE`; when everything was accepted it steps down by `EXPLORE_STEP`.
+pub fn tune_threshold(decisions: &[(f32, bool)], current: f32) -> f32 {
+    if decisions.len() < MIN_DECISIONS_FOR_TUNING {
+        return current;
+    }
+
+    let target = if decisions.iter().all(|(_, accepted)| *accepted) {
+        current - EXPLORE_STEP
+    } else {
+        let lowest = (MIN_SIMILARITY_THRESHOLD * 100.0).round() as i32;
+        let highest = (MAX_SIMILARITY_THRESHOLD * 100.0).round() as i32;
+        let mut best = (usize::MAX, f32::MAX, current);
+        for step in lowest..=highest {
+            let threshold = step as f32 / 100.0;
+            let errors = decisions
+                .iter()
+                .filter(|(similarity, accepted)| (*similarity >= threshold) != *accepted)
+                .count();
+            let distance = (threshold - current).abs();
+            if (errors, distance) < (best.0, best.1) {
+                best = (errors, distance, threshold);
+            }
+        }
+        current + (best.2 - current) * TUNING_RATE
+    };
+
+    ((target * 100.0).round() / 100.0).clamp(MIN_SIMILARITY_THRESHOLD, MAX_SIMILARITY_THRESHOLD)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -710,5 +1169,24 @@ mod tests {
         assert_eq!(config.min_cluster_size, 2);
         assert_eq!(config.max_cluster_size, 5);
         assert!(config.use_llm_summaries);
+        assert!(config.require_review);
+    }
+
+    #[test]
+    fn test_tune_threshold() {
+        // Too few decisions: unchanged
+        assert_eq!(tune_threshold(&[(0.8, false)], 0.75), 0.75);
+
+        // Rejections up to 0.80, acceptances from 0.88: best split is 0.81, moved halfway
+        let decisions = [
+            (0.78, false), (0.80, false), (0.79, false),
+            (0.90, true), (0.88, true), (0.92, true),
+        ];
+        assert_eq!(tune_threshold(&decisions, 0.75), 0.78);
+
+        // Everything accepted: explore downward, but not below the floor
+        let accepted = [(0.8, true); 5];
+        assert_eq!(tune_threshold(&accepted, 0.75), 0.73);
+        assert_eq!(tune_threshold(&accepted, 0.61), 0.6);
     }
 }
@@ -0,0 +1,216 @@
+//! Memory Scope (v3.9.0)
+//!
+//! Which chats a memory can be retrieved in.
+//!
+//! Features:
+//! - Global memories (the default, and every memory stored before scopes existed)
+//!   are retrievable everywhere
+//! - Conversation-scoped memories only inside the conversation they belong to
+//! - Project-scoped memories inside any conversation of a folder
+//! - One SQL filter shared by every retrieval path, so scoped memories never
+//!   leak into other chats
+//! - Deleting a conversation deletes its memories; deleting a folder makes its
+//!   memories global, as its conversations stay
+
+use crate::database::Database;
+use anyhow::{anyhow, Result};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Where a memory is retrievable, stored in `episodic_memory.scope` / `scope_id`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MemoryScope {
+    #[default]
+    Global,
+    Conversation { id: String },
+    /// A conversation folder
+    Project { id: String },
+}
+
+impl MemoryScope {
+    /// `(scope, scope_id)` column values
+    pub fn columns(&self) -> (&'static str, Option<&str>) {
+        match self {
+            MemoryScope::Global => ("global", None),
+            MemoryScope::Conversation { id } => ("conversation", Some(id)),
+            MemoryScope::Project { id } => ("project", Some(id)),
+        }
+    }
+
+    /// Read back from the columns; unknown or incomplete values are global
+    pub fn from_columns(scope: Option<&str>, scope_id: Option<String>) -> Self {
+        match (scope, scope_id) {
+            (Some("conversation"), Some(id)) => MemoryScope::Conversation { id },
+            (Some("project"), Some(id)) => MemoryScope::Project { id },
+            _ => MemoryScope::Global,
+        }
+    }
+}
+
+/// The conversation (and its folder) retrieval runs for
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetrievalScope {
+    pub conversation_id: Option<String>,
+    pub project_id: Option<String>,
+}
+
+impl RetrievalScope {
+    /// Only global memories
+    pub fn global() -> Self {
+        Self::default()
+    }
+
+    /// Global memories plus those of `conversation_id` and its folder
+    pub fn for_conversation(conn: &Connection, conversation_id: &str) -> Result<Self> {
+        let project_id: Option<String> = conn
+            .query_row(
+                "SELECT folder_id FROM conversations WHERE id = ?1",
+                [conversation_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+        Ok(Self {
+            conversation_id: Some(conversation_id.to_string()),
+            project_id,
+        })
+    }
+
+    /// Scope of a chat turn; without a conversation only global memories
+    pub fn for_turn(conversation_id: Option<&str>, db: Option<&Mutex<Database>>) -> Self {
+        let Some(id) = conversation_id else {
+            return Self::global();
+        };
+        let resolved = db.map(|db| {
+            let db = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            Self::for_conversation(db.conn(), id)
+        });
+        match resolved {
+            Some(Ok(scope)) => scope,
+            Some(Err(e)) => {
+                log::warn!("Failed to resolve the folder of {}: {}", id, e);
+                Self { conversation_id: Some(id.to_string()), project_id: None }
+            }
+            None => Self { conversation_id: Some(id.to_string()), project_id: None },
+        }
+    }
+
+    /// Whether a memory in `scope` is retrievable here
+    pub fn allows(&self, scope: &MemoryScope) -> bool {
+        match scope {
+            MemoryScope::Global => true,
+            MemoryScope::Conversation { id } => self.conversation_id.as_deref() == Some(id.as_str()),
+            MemoryScope::Project { id } => self.project_id.as_deref() == Some(id.as_str()),
+        }
+    }
+
+    /// SQL condition on `episodic_memory` matching `allows`; bind `params()` to its two `?`
+    pub fn sql_filter(&self) -> &'static str {
+        "(COALESCE(scope, 'global') = 'global'
+          OR (scope = 'conversation' AND scope_id = ?)
+          OR (scope = 'project' AND scope_id = ?))"
+    }
+
+    /// Values for the placeholders of `sql_filter` (empty ids match nothing)
+    pub fn params(&self) -> [&str; 2] {
+        [
+            self.conversation_id.as_deref().unwrap_or(""),
+            self.project_id.as_deref().unwrap_or(""),
+        ]
+    }
+}
+
+/// Move a memory to another scope
+pub fn set_scope(conn: &Connection, episode_id: &str, scope: &MemoryScope) -> Result<()> {
+    let (kind, scope_id) = scope.columns();
+    if scope_id.is_some_and(|id| id.trim().is_empty()) {
+        return Err(anyhow!("A {} scope needs an id", kind));
+    }
+    let updated = conn.execute(
+        "UPDATE episodic_memory SET scope = ?1, scope_id = ?2 WHERE id = ?3",
+        rusqlite::params![kind, scope_id, episode_id],
+    )?;
+    if updated == 0 {
+        return Err(anyhow!("Memory not found: {}", episode_id));
+    }
+    Ok(())
+}
+
+/// Set the scope of freshly stored episodes (no-op for global)
+pub fn assign(conn: &Connection, episode_ids: &[String], scope: &MemoryScope) -> Result<()> {
+    if *scope == MemoryScope::Global {
+        return Ok(());
+    }
+    for id in episode_ids {
+        set_scope(conn, id, scope)?;
+    }
+    Ok(())
+}
+
+/// Memories only retrievable in `conversation_id`, to delete along with it
+pub fn conversation_memory_ids(conn: &Connection, conversation_id: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT id FROM episodic_memory WHERE scope = 'conversation' AND scope_id = ?1")?;
+    let ids = stmt
+        .query_map([conversation_id], |row| row.get(0))?
+        .collect::<Result<Vec<String>, _>>()?;
+    Ok(ids)
+}
+
+/// Make the memories of a deleted folder global, returning how many moved
+pub fn release_project(conn: &Connection, project_id: &str) -> Result<usize> {
+    Ok(conn.execute(
+        "UPDATE episodic_memory SET scope = 'global', scope_id = NULL WHERE scope = 'project' AND scope_id = ?1",
+        [project_id],
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_filter() {
+        let db = Database::new_test_db().unwrap();
+        let conn = db.conn();
+        conn.execute(
+            "INSERT INTO conversations (id, title, mode, created_at, updated_at, message_count)
+             VALUES ('c1', 'Test', 'user-led', 0, 0, 0), ('c2', 'Other', 'user-led', 0, 0, 0)",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO episodic_memory (id, user_message, ai_response, satisfaction, created_at)
+             VALUES ('old', 'q', 'a', 0.5, 0), ('chat', 'q', 'a', 0.5, 0), ('proj', 'q', 'a', 0.5, 0)",
+            [],
+        )
+        .unwrap();
+        set_scope(conn, "chat", &MemoryScope::Conversation { id: "c1".into() }).unwrap();
+        set_scope(conn, "proj", &MemoryScope::Project { id: "f1".into() }).unwrap();
+        assert!(set_scope(conn, "missing", &MemoryScope::Global).is_err());
+
+        let visible = |scope: &RetrievalScope| -> Vec<String> {
+            let sql = format!("SELECT id FROM episodic_memory WHERE {} ORDER BY id", scope.sql_filter());
+            let mut stmt = conn.prepare(&sql).unwrap();
+            stmt.query_map(scope.params(), |row| row.get(0)).unwrap().map(|r| r.unwrap()).collect()
+        };
+        assert_eq!(visible(&RetrievalScope::global()), vec!["old"]);
+        assert_eq!(visible(&RetrievalScope::for_conversation(conn, "c1").unwrap()), vec!["chat", "old"]);
+
+        conn.execute("UPDATE conversations SET folder_id = 'f1' WHERE id = 'c2'", []).unwrap();
+        let c2 = RetrievalScope::for_conversation(conn, "c2").unwrap();
+        assert_eq!(visible(&c2), vec!["old", "proj"]);
+        assert!(c2.allows(&MemoryScope::Project { id: "f1".into() }));
+        assert!(!c2.allows(&MemoryScope::Conversation { id: "c1".into() }));
+        assert_eq!(
+            serde_json::to_string(&MemoryScope::Conversation { id: "c1".into() }).unwrap(),
+            r#"{"kind":"conversation","id":"c1"}"#
+        );
+
+        assert_eq!(conversation_memory_ids(conn, "c1").unwrap(), vec!["chat"]);
+        assert!(conversation_memory_ids(conn, "c2").unwrap().is_empty());
+        assert_eq!(release_project(conn, "f1").unwrap(), 1);
+        assert_eq!(visible(&RetrievalScope::global()), vec!["old", "proj"]);
+    }
+}
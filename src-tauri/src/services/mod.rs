@@ -1,10 +1,14 @@
 pub mod ollama;
+pub mod ollama_supervisor;  // v3.9.0: Health watchdog and auto-restart for the Ollama server
 pub mod screen;
 pub mod llava;
+pub mod vision_backend;  // v3.9.0: Vision model abstraction (llava / qwen2-vl / moondream)
 pub mod system_info;
 pub mod model_recommender;
 pub mod model_installer;
 pub mod prompt_customizer;
+pub mod response_formatter;  // v3.9.0: Structured text/code/math/table segments for rich rendering
+pub mod structured_output;  // v3.9.0: JSON-schema constrained generation with validation and retries
 
 // Phase 1: RAG & Episodic Memory
 pub mod embedding;
@@ -14,6 +18,8 @@ pub mod rag_v2;  // v3.4.0 Phase 6: RAG with LanceDB (10-100x faster, optional)
 pub mod raft;  // v3.2.0: RAFT hallucination reduction
 #[cfg(feature = "lancedb-support")]
 pub mod vector_store;  // v3.4.0 Phase 6: LanceDB vector database (optional)
+pub mod vector_backend;  // v3.9.0: VectorStore trait with LanceDB / SQLite / Qdrant backends
+pub mod index_tuning;  // v3.9.0: ANN index tuning (IVF-PQ / SQLite fallback)
 
 // Phase 2: Screen Context & Vision
 pub mod active_window;
@@ -39,6 +45,10 @@ pub mod cloud_sync;  // v3.6.0: Google Drive backup/restore for persona settings
 
 // Phase 7: File System & Git Integration
 pub mod file;
+pub mod file_edit;  // v3.9.0: Diff / search-replace edits with previews and backups
+pub mod agent_actions;  // v3.9.0: Undo queue for agent file/git changes
+pub mod workspace;  // v3.9.0: Active project detection and file tool scoping
+pub mod terminal_capture;  // v3.9.0: Opt-in terminal session capture
 pub mod git;
 
 // Phase 8: Auto-updater & Crash Reporting
@@ -48,6 +58,9 @@ pub mod crash_reporter;
 // Phase 9: Internet Access (v3.3.0)
 pub mod web_search;
 pub mod url_fetch;
+pub mod readability;  // v3.9.0: Main-content extraction and HTML → Markdown for fetched pages
+pub mod search_providers;  // v3.9.0: SearX / Brave / Tavily / DuckDuckGo backends for web search
+pub mod search_history;  // v3.9.0: Persisted web searches, indexed into RAG
 
 // Phase 10: Plugin System (v3.4.0)
 pub mod plugin;
@@ -65,6 +78,7 @@ pub mod conversation_memory; // v3.5.0: Summary Buffer Memory for multi-turn con
 
 // Phase 13: Hybrid Search (v3.6.0)
 pub mod bm25;  // v3.6.0: BM25 lexical search for hybrid retrieval
+pub mod fusion;  // v3.9.0: RRF / linear / learned fusion with per-query-type weights
 #[cfg(feature = "lancedb-support")]
 pub mod hybrid_search;  // v3.6.0: Hybrid search combining BM25 + BGE-M3 with RRF fusion (requires LanceDB)
 pub mod reranker;  // v3.6.0: Cross-encoder re-ranking for improved relevance
@@ -88,9 +102,12 @@ pub mod planner;      // v3.7.0: Plan-and-Solve with user confirmation and adapt
 // Phase 18: LAM (Large Action Model) - Computer Use (v3.8.0)
 pub mod computer_control;  // v3.8.0: Vision-guided mouse/keyboard automation with safety controls
 pub mod lam_tools;         // v3.8.0: LAM tools for ReAct agent (click, type, scroll, etc.)
+pub mod session_context;  // v3.9.0: Chat memories shared with computer control, with audit
 pub mod streaming_vision;  // v3.8.0 Phase 2: Continuous screen monitoring with proactive alerts
 pub mod temporal_memory;   // v3.8.0 Phase 3: Ebbinghaus forgetting curve with gradual decay
-pub mod decay_worker;      // v3.8.0 Phase 3: 24h background worker for memory retention updates
+pub mod memory_browser;  // v3.9.0: Filtered episodic memory browsing and timelines
+pub mod memory_scope;  // v3.9.0: Global / conversation / project memory scopes
+pub mod decay_worker;      // v3.8.0 Phase 3: Memory retention update cycle (scheduled by background_jobs)
 pub mod pattern_detector;  // v3.8.0 Phase 4: ML-based trait extraction using Ollama/Qwen
 #[cfg(feature = "phase4")]
 pub mod contextual_retrieval;  // v3.8.0 Phase 4: Topic-based retention boosting for active conversations
@@ -100,6 +117,7 @@ pub mod memory_consolidation;  // v3.8.0 Phase 4: Intelligent merging of similar
 // Phase 5: Reasoning Engine 2.0 (v3.9.0)
 pub mod chain_of_thought;  // v3.9.0: Step-by-step reasoning with self-correction
 pub mod visual_analyzer;   // v3.9.0 Stage 1: Image understanding with LLaVA (lazy loading)
+pub mod chat_attachments;  // v3.9.0: Images attached to chat messages, described with LLaVA
 #[cfg(feature = "phase5")]
 pub mod context_enricher;  // v3.9.0 Stage 1: Multi-source context aggregation
 pub mod semantic_wiki;     // v3.9.0 Stage 2: Fact extraction and knowledge base
@@ -107,6 +125,63 @@ pub mod memory_enhancer;   // v3.9.0 Stage 2: Memory quality scoring and enhance
 pub mod task_planner;      // v3.9.0 Stage 4: Autonomous task breakdown and execution planning
 pub mod learning_style_adapter;  // v3.9.0 Stage 4: Learning style detection and response adaptation
 pub mod goal_tracker;      // v3.9.0 Stage 4: Long-term goal monitoring and progress tracking
+pub mod activity_timeline; // v3.9.0: App-usage sessions and LLM daily summaries
+pub mod weekly_review; // v3.9.0: Weekly Markdown review with Friday delivery
+pub mod conversation_language;  // v3.9.0: Per-conversation language lock and reply correction
+pub mod conversation_organizer;  // v3.9.0: Pinned / favorite / archived chats, tags and folders
+pub mod conversation_topics;  // v3.9.0: Automatic titles and learned topic labels
+pub mod conversation_digest;  // v3.9.0: Idle conversations summarized into durable memories
+pub mod language_detection;  // v3.9.0: Script + trigram language detection shared by prompts, RAG and personality
+pub mod translation;  // v3.9.0: Local-model translation with a user glossary
+pub mod localization;  // v3.9.0: Prompts, tool descriptions and notifications in the primary language
+pub mod screen_history;    // v3.9.0: Downscaled frame history with semantic search
+pub mod conversation_documents;  // v3.9.0: Conversation-scoped index of attached files
+pub mod clipboard_history; // v3.9.0: Clipboard history with privacy filters and LLM transforms
+pub mod quick_ask; // v3.9.0: Global hotkey quick ask overlay
+pub mod scripting; // v3.9.0: Sandboxed Rhai automation scripts on hotkeys, schedules and webhook events
+pub mod prompt_templates; // v3.9.0: Reusable prompts with {{selection}}/{{clipboard}}/{{file}} variables
+pub mod notification; // v3.9.0: Native notifications with action buttons
+pub mod proactive_engine; // v3.9.0: Context-driven proactive suggestions with feedback
+pub mod profile; // v3.9.0: Isolated workspaces (persona, memory, conversations per profile)
+pub mod encryption; // v3.9.0: SQLCipher + field encryption at rest with passphrase/keychain unlock
+pub mod secrets; // v3.9.0: OS keychain vault for API keys and OAuth tokens
+pub mod privacy; // v3.9.0: Topic export and "forget me" across memory stores
+pub mod knowledge_export; // v3.9.0: Wiki and knowledge graph as an Obsidian-style Markdown vault
+pub mod note_import; // v3.9.0: Obsidian / Notion notes into RAG, wiki facts and graph links
+pub mod analytics; // v3.9.0: Local usage analytics (tokens, tools, RAG hit rate, latency)
+pub mod backup; // v3.9.0: Scheduled snapshots of data.db, knowledge graph and LanceDB with verified restore
+pub mod device_sync; // v3.9.0: CRDT-merged, E2E-encrypted sync of conversations, memories, wiki and persona across devices
+pub mod background_jobs; // v3.9.0: Scheduler for decay, consolidation, wiki extraction and graph maintenance jobs
+pub mod review_queue; // v3.9.0: Spaced-repetition review of at-risk, high-value memories
+pub mod provenance; // v3.9.0: Source records for retrieved context and chat citations
+pub mod query_expansion; // v3.9.0: HyDE and multi-query expansion before retrieval
+pub mod rag_eval; // v3.9.0: recall@k / MRR / latency evaluation of retrieval configurations
+pub mod retrieval_feedback; // v3.9.0: Memory usage from rated responses, retrained retrieval settings
+pub mod chunker; // v3.9.0: Fixed-token / sentence / recursive / semantic chunking per source kind
+pub mod persona_presets; // v3.9.0: Named persona snapshots with custom instructions
+pub mod persona_changes; // v3.9.0: Explainable persona change log with rollback
+pub mod sentiment; // v3.9.0: Valence/arousal/frustration scoring and mood timeline
+pub mod lora_training; // v3.9.0: LoRA fine-tune orchestration
+pub mod model_context; // v3.9.0: Per-model context windows and token budgets
+pub mod context_inspector; // v3.9.0: What the next chat turn sends to the model, section by section
+pub mod model_router; // v3.9.0: Fast/full model routing with escalation
+pub mod response_verifier; // v3.9.0: Post-generation self-check and auto-retry
+pub mod response_variants; // v3.9.0: Regenerated replies with their overrides, next to the original
+pub mod benchmark; // v3.9.0: Latency benchmark history and regressions
+pub mod startup; // v3.9.0: Parallel/lazy service initialization with per-service timings
+pub mod degradation; // v3.9.0: Unavailable/degraded service states with retry
+pub mod tool_cache; // v3.9.0: TTL cache of deterministic tool results
+pub mod tool_runtime; // v3.9.0: Tool timeouts, cancellation and parallel execution
+pub mod tool_sets; // v3.9.0: Named tool sets bound per conversation or agent run
+pub mod openapi_tools; // v3.9.0: Agent tools generated from OpenAPI / Swagger documents
+pub mod sql_query; // v3.9.0: Read-only SQL queries on user-configured databases
+pub mod charts; // v3.9.0: Charts rendered from structured data for chat responses
+pub mod calculator; // v3.9.0: Expression, unit, date and currency calculator
+pub mod code_sandbox; // v3.9.0: Sandboxed JavaScript execution for the agent
+pub mod window_manager; // v3.9.0: App and window management (list, focus, open, move/resize)
+pub mod os_automation; // v3.9.0: macOS Shortcuts and approved PowerShell scripts
+pub mod reports; // v3.9.0: HTML / PDF reports from conversations, reviews, plans and wiki pages
+pub mod network_policy; // v3.9.0: Offline mode, per-domain rate limits and robots.txt for web tools
 
 // Service Lifecycle Management (v3.5.2)
 pub mod lifecycle;  // v3.5.2: Graceful shutdown handling for all services
@@ -0,0 +1,394 @@
+//! Model Context Windows (v3.9.0)
+//!
+//! Detects each model's context length from Ollama instead of assuming one.
+//!
+//! Features:
+//! - Queries `/api/show` for context length, parameter count and quantization
+//! - Stores detected models in `model_context_info`
+//! - Token budgets for the active chat model (full window, prompt, history)
+//!   used by the prompt builder, attention_sink and conversation_memory
+//! - Honors the context window size set in LLM settings as an upper bound
+
+#![allow(dead_code)]  // Phase 5: Dynamic context windows
+
+use crate::database::Database;
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+const OLLAMA_SHOW_URL: &str = "http://localhost:11434/api/show";
+
+/// Window assumed until the active model has been detected
+pub const DEFAULT_CONTEXT_WINDOW: usize = 8192;
+
+/// Smallest window budgets are computed for
+const MIN_CONTEXT_WINDOW: usize = 2048;
+
+/// Tokens kept free for the response, at most a quarter of the window
+const MAX_RESPONSE_RESERVE: usize = 2048;
+
+/// A failed detection is retried after this long
+const RETRY_AFTER: Duration = Duration::from_secs(60);
+
+const SHOW_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Context details of one model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelContextInfo {
+    pub model: String,
+    /// Trained context length (tokens)
+    pub context_length: usize,
+    pub parameter_count: Option<u64>,
+    /// e.g. "7.6B"
+    pub parameter_size: Option<String>,
+    pub family: Option<String>,
+    pub quantization: Option<String>,
+    pub detected_at: i64, // Unix millis
+}
+
+/// Budgets of the active chat model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextBudget {
+    pub model: String,
+    /// None until the model has been detected
+    pub detected: Option<ModelContextInfo>,
+    /// Window size limit from LLM settings
+    pub window_cap: Option<usize>,
+    /// Window requested from Ollama (`num_ctx`)
+    pub context_window: usize,
+    pub prompt_budget: usize,
+    pub history_budget: usize,
+}
+
+struct Registry {
+    models: HashMap<String, ModelContextInfo>,
+    failures: HashMap<String, Instant>,
+    window_cap: Option<usize>,
+}
+
+fn registry() -> &'static RwLock<Registry> {
+    static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        RwLock::new(Registry {
+            models: HashMap::new(),
+            failures: HashMap::new(),
+            window_cap: None,
+        })
+    })
+}
+
+/// Detected info of `model`, if known
+pub fn cached(model: &str) -> Option<ModelContextInfo> {
+    registry().read().ok()?.models.get(model).cloned()
+}
+
+fn remember(info: ModelContextInfo) {
+    if let Ok(mut registry) = registry().write() {
+        registry.failures.remove(&info.model);
+        registry.models.insert(info.model.clone(), info);
+    }
+}
+
+/// Limit the window to the size chosen in LLM settings (None: model maximum)
+pub fn set_window_cap(cap: Option<usize>) {
+    if let Ok(mut registry) = registry().write() {
+        registry.window_cap = cap;
+    }
+}
+
+fn window_cap() -> Option<usize> {
+    registry().read().ok()?.window_cap
+}
+
+/// Window for `model`: detected length capped by settings, or the default
+pub fn context_window_for(model: &str) -> usize {
+    let window = cached(model).map(|info| info.context_length);
+    let window = match (window, window_cap()) {
+        (Some(window), Some(cap)) => window.min(cap),
+        (Some(window), None) => window,
+        (None, Some(cap)) => DEFAULT_CONTEXT_WINDOW.min(cap),
+        (None, None) => DEFAULT_CONTEXT_WINDOW,
+    };
+    window.max(MIN_CONTEXT_WINDOW)
+}
+
+/// Window of the active chat model
+pub fn context_window() -> usize {
+    context_window_for(&super::ollama::chat_model())
+}
+
+/// Tokens available for the prompt once the response reserve is taken out
+pub fn prompt_budget() -> usize {
+    let window = context_window();
+    window - (window / 4).min(MAX_RESPONSE_RESERVE)
+}
+
+/// Tokens available for conversation history (half the prompt budget, the
+/// rest goes to the system prompt and retrieved memories)
+pub fn history_budget() -> usize {
+    prompt_budget() / 2
+}
+
+/// Budgets of the active chat model
+pub fn active_budget() -> ContextBudget {
+    let model = super::ollama::chat_model();
+    ContextBudget {
+        detected: cached(&model),
+        window_cap: window_cap(),
+        context_window: context_window_for(&model),
+        prompt_budget: prompt_budget(),
+        history_budget: history_budget(),
+        model,
+    }
+}
+
+/// Parse an `/api/show` response
+pub fn parse_show_response(model: &str, body: &Value) -> Option<ModelContextInfo> {
+    let info = body.get("model_info")?.as_object()?;
+    let architecture = info.get("general.architecture").and_then(Value::as_str);
+
+    let context_length = architecture
+        .and_then(|arch| info.get(&format!("{}.context_length", arch)))
+        .or_else(|| {
+            info.iter()
+                .find(|(key, _)| key.ends_with(".context_length"))
+                .map(|(_, value)| value)
+        })
+        .and_then(Value::as_u64)?;
+
+    let details = body.get("details");
+    let detail = |key: &str| {
+        details
+            .and_then(|d| d.get(key))
+            .and_then(Value::as_str)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+
+    Some(ModelContextInfo {
+        model: model.to_string(),
+        context_length: context_length as usize,
+        parameter_count: info.get("general.parameter_count").and_then(Value::as_u64),
+        parameter_size: detail("parameter_size"),
+        family: detail("family").or_else(|| architecture.map(str::to_string)),
+        quantization: detail("quantization_level"),
+        detected_at: chrono::Utc::now().timestamp_millis(),
+    })
+}
+
+/// Query Ollama for a model's context details
+pub async fn fetch_model_info(model: &str) -> Result<ModelContextInfo> {
+    let client = Client::builder().timeout(SHOW_TIMEOUT).build()?;
+    let response = client
+        .post(OLLAMA_SHOW_URL)
+        .json(&serde_json::json!({ "model": model }))
+        .send()
+        .await
+        .map_err(|e| anyhow!("Failed to query Ollama for {}: {}", model, e))?;
+
+    if !response.status().is_success() {
+        return Err(anyhow!("Ollama could not show {}: HTTP {}", model, response.status()));
+    }
+
+    let body: Value = response.json().await?;
+    parse_show_response(model, &body)
+        .ok_or_else(|| anyhow!("Ollama reported no context length for {}", model))
+}
+
+/// Detect `model` unless it is known (or failed recently)
+///
+/// Called by the prompt builder before budgeting; keeps the in-memory
+/// registry only, [`ModelContextService::detect`] also stores the result.
+pub async fn ensure_model_info(model: &str) -> Option<ModelContextInfo> {
+    if let Some(info) = cached(model) {
+        return Some(info);
+    }
+    let recently_failed = registry()
+        .read()
+        .ok()
+        .and_then(|r| r.failures.get(model).copied())
+        .is_some_and(|at| at.elapsed() < RETRY_AFTER);
+    if recently_failed {
+        return None;
+    }
+
+    match fetch_model_info(model).await {
+        Ok(info) => {
+            log::info!("Context window of {}: {} tokens", model, info.context_length);
+            remember(info.clone());
+            Some(info)
+        }
+        Err(e) => {
+            log::debug!("Context window detection failed: {}", e);
+            if let Ok(mut registry) = registry().write() {
+                registry.failures.insert(model.to_string(), Instant::now());
+            }
+            None
+        }
+    }
+}
+
+/// Stored model context windows
+pub struct ModelContextService {
+    db: Arc<Mutex<Database>>,
+}
+
+impl ModelContextService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        let service = Self { db };
+        service.init_database()?;
+        log::info!("✓ Model Context Service initialized");
+        Ok(service)
+    }
+
+    fn init_database(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let conn = db.conn();
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS model_context_info (
+                model TEXT PRIMARY KEY,
+                context_length INTEGER NOT NULL,
+                parameter_count INTEGER,
+                parameter_size TEXT,
+                family TEXT,
+                quantization TEXT,
+                detected_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        for info in Self::load_all(conn)? {
+            remember(info);
+        }
+
+        // Window size chosen in LLM settings (v3.6.0)
+        let cap: Option<i64> = conn
+            .query_row("SELECT context_window_size FROM llm_settings WHERE id = 1", [], |row| row.get(0))
+            .optional()
+            .ok()
+            .flatten()
+            .flatten();
+        set_window_cap(cap.map(|size| size as usize));
+
+        Ok(())
+    }
+
+    fn load_all(conn: &rusqlite::Connection) -> Result<Vec<ModelContextInfo>> {
+        let mut stmt = conn.prepare(
+            "SELECT model, context_length, parameter_count, parameter_size, family, quantization, detected_at
+             FROM model_context_info ORDER BY model",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(ModelContextInfo {
+                    model: row.get(0)?,
+                    context_length: row.get::<_, i64>(1)? as usize,
+                    parameter_count: row.get::<_, Option<i64>>(2)?.map(|n| n as u64),
+                    parameter_size: row.get(3)?,
+                    family: row.get(4)?,
+                    quantization: row.get(5)?,
+                    detected_at: row.get(6)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+
+    fn store(&self, info: &ModelContextInfo) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        db.conn().execute(
+            "INSERT OR REPLACE INTO model_context_info
+             (model, context_length, parameter_count, parameter_size, family, quantization, detected_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                info.model,
+                info.context_length as i64,
+                info.parameter_count.map(|n| n as i64),
+                info.parameter_size,
+                info.family,
+                info.quantization,
+                info.detected_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Query Ollama for `model` and store the result
+    pub async fn detect(&self, model: &str) -> Result<ModelContextInfo> {
+        let info = fetch_model_info(model).await?;
+        self.store(&info)?;
+        remember(info.clone());
+        log::info!(
+            "Detected {}: {} token context, {}",
+            model,
+            info.context_length,
+            info.parameter_size.as_deref().unwrap_or("unknown size")
+        );
+        Ok(info)
+    }
+
+    /// Detect the active chat model
+    pub async fn detect_active(&self) -> Result<ModelContextInfo> {
+        self.detect(&super::ollama::chat_model()).await
+    }
+
+    /// Stored models
+    pub fn list(&self) -> Result<Vec<ModelContextInfo>> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        Self::load_all(db.conn())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_show_response() {
+        let body = serde_json::json!({
+            "parameters": "num_ctx 4096\nstop \"<|im_end|>\"",
+            "details": {
+                "format": "gguf",
+                "family": "qwen2",
+                "parameter_size": "7.6B",
+                "quantization_level": "Q4_K_M"
+            },
+            "model_info": {
+                "general.architecture": "qwen2",
+                "general.parameter_count": 7615616512u64,
+                "qwen2.context_length": 32768,
+                "qwen2.embedding_length": 3584
+            }
+        });
+
+        let info = parse_show_response("qwen2.5:7b", &body).unwrap();
+        assert_eq!(info.context_length, 32768);
+        assert_eq!(info.parameter_count, Some(7615616512));
+        assert_eq!(info.parameter_size.as_deref(), Some("7.6B"));
+        assert_eq!(info.family.as_deref(), Some("qwen2"));
+        assert_eq!(info.quantization.as_deref(), Some("Q4_K_M"));
+
+        assert!(parse_show_response("x", &serde_json::json!({ "details": {} })).is_none());
+    }
+
+    #[test]
+    fn test_context_window_for() {
+        remember(ModelContextInfo {
+            model: "test-window:1b".to_string(),
+            context_length: 131072,
+            parameter_count: None,
+            parameter_size: None,
+            family: None,
+            quantization: None,
+            detected_at: 0,
+        });
+        assert_eq!(context_window_for("test-window:1b"), 131072);
+        assert_eq!(context_window_for("test-unknown:1b"), DEFAULT_CONTEXT_WINDOW);
+    }
+}
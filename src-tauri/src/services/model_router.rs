@@ -0,0 +1,532 @@
+//! Model Router (v3.9.0)
+//!
+//! Speculative dual-model routing: trivial messages are answered by a small
+//! fast model, everything else goes to the full chat model.
+//!
+//! Features:
+//! - Complexity classification (length, code, tool/agentic intent, reasoning
+//!   and math cues, small talk) for English and Korean
+//! - Confidence check of the fast answer (self-escalation marker, hedging,
+//!   truncation, language mismatch) with escalation to the full model
+//! - Per-route metrics: requests, escalations, latency, confidence
+
+#![allow(dead_code)]  // Phase 5: Dual-model routing
+
+use crate::database::Database;
+use crate::services::chunker::estimate_tokens;
+use crate::services::language_detection;
+use crate::services::ollama::{self, ESCALATE_MARKER};
+use anyhow::{anyhow, Result};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// `user_preferences` key holding the router config
+const CONFIG_KEY: &str = "model_router_config";
+
+/// The fast path is skipped for this long after the fast model was missing
+const MISSING_MODEL_BACKOFF: Duration = Duration::from_secs(10 * 60);
+
+/// Tool / agentic intent
+const TOOL_CUES_EN: &[&str] = &[
+    "search", "google", "file", "folder", "open", "run", "execute", "schedule", "calendar",
+    "remind", "reminder", "email", "screenshot", "screen", "download", "install", "browse",
+    "website", "http", "https", "click", "type",
+];
+const TOOL_CUES_KO: &[&str] = &[
+    "검색", "파일", "폴더", "실행", "열어", "일정", "캘린더", "알림", "메일", "화면", "설치",
+    "다운로드", "사이트", "클릭",
+];
+/// Multi-step reasoning or generation
+const REASONING_CUES_EN: &[&str] = &[
+    "why", "explain", "compare", "analyze", "analyse", "difference", "plan", "write",
+    "implement", "debug", "fix", "translate", "summarize", "summarise", "calculate", "prove",
+    "design", "optimize", "refactor", "review",
+];
+const REASONING_PHRASES_EN: &[&str] = &["how do", "how does", "how to", "how can", "step by step", "pros and cons"];
+const REASONING_CUES_KO: &[&str] = &[
+    "왜", "설명", "어떻게", "비교", "분석", "차이", "단계", "계획", "작성", "구현", "디버그",
+    "고쳐", "번역", "요약", "계산", "증명", "설계", "최적화", "리뷰",
+];
+/// Greetings, thanks and acknowledgements
+const SMALL_TALK_EN: &[&str] = &[
+    "hi", "hello", "hey", "thanks", "thank", "thx", "bye", "ok", "okay", "cool", "nice",
+    "great", "lol", "morning", "night", "yes", "no", "sure",
+];
+const SMALL_TALK_KO: &[&str] = &["안녕", "고마워", "고맙", "감사", "ㅋㅋ", "ㅎㅎ", "좋아", "잘자", "응", "네", "그래"];
+/// Fast answers that signal uncertainty
+const HEDGES: &[&str] = &[
+    "i'm not sure", "i am not sure", "i don't know", "i do not know", "not certain",
+    "i can't", "i cannot", "as an ai", "잘 모르", "모르겠", "확실하지", "확실치", "할 수 없",
+];
+
+/// Router configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouterConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Small model for trivial messages
+    #[serde(default = "default_fast_model")]
+    pub fast_model: String,
+    /// Messages scoring below this go to the fast model (0.0-1.0)
+    #[serde(default = "default_complexity_threshold")]
+    pub complexity_threshold: f32,
+    /// Fast answers below this confidence are escalated (0.0-1.0)
+    #[serde(default = "default_confidence_threshold")]
+    pub confidence_threshold: f32,
+    /// Token limit of fast answers
+    #[serde(default = "default_fast_max_tokens")]
+    pub fast_max_tokens: i32,
+}
+
+fn default_enabled() -> bool { true }
+fn default_fast_model() -> String { "qwen2.5:1.5b".to_string() }
+fn default_complexity_threshold() -> f32 { 0.35 }
+fn default_confidence_threshold() -> f32 { 0.6 }
+fn default_fast_max_tokens() -> i32 { 256 }
+
+impl Default for RouterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            fast_model: default_fast_model(),
+            complexity_threshold: default_complexity_threshold(),
+            confidence_threshold: default_confidence_threshold(),
+            fast_max_tokens: default_fast_max_tokens(),
+        }
+    }
+}
+
+/// Model tier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Route {
+    Fast,
+    Full,
+}
+
+impl Route {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fast => "fast",
+            Self::Full => "full",
+        }
+    }
+}
+
+/// Classification of one message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteDecision {
+    pub route: Route,
+    /// 0.0 (trivial) - 1.0 (complex)
+    pub complexity: f32,
+    pub reasons: Vec<String>,
+}
+
+/// Result of the fast-model attempt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriageOutcome {
+    pub decision: RouteDecision,
+    /// Fast answer, None when the full model has to answer
+    pub answer: Option<String>,
+    pub confidence: Option<f32>,
+    /// Routed to the fast model but handed to the full model
+    pub escalated: bool,
+    pub escalation_reason: Option<String>,
+    pub fast_latency_ms: Option<u64>,
+}
+
+impl TriageOutcome {
+    /// Route that produced the final answer
+    pub fn served_by(&self) -> Route {
+        if self.answer.is_some() { Route::Fast } else { Route::Full }
+    }
+}
+
+/// Metrics of one route
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteMetrics {
+    pub route: Route,
+    pub requests: usize,
+    /// Requests classified fast but answered by the full model
+    pub escalations: usize,
+    pub avg_latency_ms: f64,
+    pub avg_complexity: f32,
+    pub avg_confidence: Option<f32>,
+}
+
+/// Router metrics over a period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouterMetrics {
+    pub days: u32,
+    pub total: usize,
+    /// Share of requests answered by the fast model
+    pub fast_share: f32,
+    /// Share of fast-classified requests that were escalated
+    pub escalation_rate: f32,
+    pub routes: Vec<RouteMetrics>,
+}
+
+/// Score a message's complexity and pick a route
+pub fn classify(message: &str, complexity_threshold: f32) -> RouteDecision {
+    let lower = message.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .collect();
+    let has_word = |cues: &[&str]| words.iter().any(|w| cues.contains(w));
+    let has_text = |cues: &[&str]| cues.iter().any(|cue| lower.contains(cue));
+
+    let mut score: f32 = 0.0;
+    let mut reasons = Vec::new();
+
+    let tokens = estimate_tokens(message).max(message.chars().count() / 4);
+    if tokens > 40 {
+        score += 0.3;
+        reasons.push("long message".to_string());
+    } else if tokens > 15 {
+        score += 0.15;
+        reasons.push("medium length".to_string());
+    }
+
+    if message.contains("```") || (message.contains('{') && message.contains('}')) || message.contains("();") {
+        score += 0.5;
+        reasons.push("code".to_string());
+    }
+
+    if has_word(TOOL_CUES_EN) || has_text(TOOL_CUES_KO) {
+        score += 0.6;
+        reasons.push("tool or agentic request".to_string());
+    }
+
+    if has_word(REASONING_CUES_EN) || has_text(REASONING_PHRASES_EN) || has_text(REASONING_CUES_KO) {
+        score += 0.35;
+        reasons.push("reasoning".to_string());
+    }
+
+    let has_math = message
+        .char_indices()
+        .any(|(i, c)| "+-*/^=".contains(c) && message[..i].chars().last().is_some_and(|p| p.is_ascii_digit() || p == ' '))
+        && message.chars().filter(char::is_ascii_digit).count() >= 2;
+    if has_math {
+        score += 0.4;
+        reasons.push("math".to_string());
+    }
+
+    let questions = message.matches(['?', '？']).count();
+    let sentences = message.matches(['.', '!', '?', '。', '\n']).count();
+    if questions >= 2 || sentences >= 3 {
+        score += 0.2;
+        reasons.push("multiple questions or sentences".to_string());
+    }
+
+    if words.len() <= 6 && (has_word(SMALL_TALK_EN) || has_text(SMALL_TALK_KO)) {
+        score -= 0.3;
+        reasons.push("small talk".to_string());
+    }
+
+    let complexity = score.clamp(0.0, 1.0);
+    RouteDecision {
+        route: if complexity < complexity_threshold { Route::Fast } else { Route::Full },
+        complexity,
+        reasons,
+    }
+}
+
+/// Confidence in a fast answer (0.0-1.0) and why it was lowered
+pub fn assess_fast_response(
+    message: &str,
+    response: &str,
+    truncated: bool,
+    complexity: f32,
+) -> (f32, Option<String>) {
+    let trimmed = response.trim();
+    if trimmed.is_empty() {
+        return (0.0, Some("empty answer".to_string()));
+    }
+    if trimmed.to_uppercase().starts_with(ESCALATE_MARKER) {
+        return (0.0, Some("fast model asked to escalate".to_string()));
+    }
+
+    let lower = trimmed.to_lowercase();
+    let mut confidence = 1.0 - complexity * 0.3;
+    let mut reason = None;
+
+    if truncated {
+        confidence -= 0.4;
+        reason = Some("answer hit the token limit".to_string());
+    }
+    if HEDGES.iter().any(|hedge| lower.contains(hedge)) {
+        confidence -= 0.5;
+        reason = Some("uncertain answer".to_string());
+    }
+    let asked = language_detection::detect(message).map(|detected| detected.code);
+    let answered = language_detection::detect(trimmed).map(|detected| detected.code);
+    if asked.is_some() && answered.is_some() && asked != answered {
+        confidence -= 0.5;
+        reason = Some("answered in the wrong language".to_string());
+    }
+
+    (confidence.clamp(0.0, 1.0), reason)
+}
+
+/// Dual-model router
+pub struct ModelRouterService {
+    db: Arc<Mutex<Database>>,
+    config: RwLock<RouterConfig>,
+    /// When the fast model was last found missing
+    fast_model_missing: Mutex<Option<Instant>>,
+}
+
+impl ModelRouterService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        let service = Self {
+            db,
+            config: RwLock::new(RouterConfig::default()),
+            fast_model_missing: Mutex::new(None),
+        };
+        service.init_database()?;
+        log::info!("✓ Model Router initialized");
+        Ok(service)
+    }
+
+    fn init_database(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let conn = db.conn();
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS model_routing_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                conversation_id TEXT,
+                planned_route TEXT NOT NULL,
+                served_route TEXT NOT NULL,
+                complexity REAL NOT NULL,
+                confidence REAL,
+                escalation_reason TEXT,
+                latency_ms INTEGER NOT NULL,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_model_routing_log_created ON model_routing_log(created_at)",
+            [],
+        )?;
+
+        let stored: Option<String> = conn
+            .query_row("SELECT value FROM user_preferences WHERE key = ?1", [CONFIG_KEY], |row| row.get(0))
+            .optional()?;
+        if let Some(config) = stored.and_then(|json| serde_json::from_str(&json).ok()) {
+            *self.config.write().map_err(|e| anyhow!("Lock error: {}", e))? = config;
+        }
+
+        Ok(())
+    }
+
+    pub fn config(&self) -> RouterConfig {
+        self.config.read().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    pub fn set_config(&self, config: RouterConfig) -> Result<RouterConfig> {
+        if !(0.0..=1.0).contains(&config.complexity_threshold) || !(0.0..=1.0).contains(&config.confidence_threshold) {
+            return Err(anyhow!("Thresholds must be between 0.0 and 1.0"));
+        }
+        if config.fast_model.trim().is_empty() || config.fast_max_tokens <= 0 {
+            return Err(anyhow!("Fast model and token limit are required"));
+        }
+
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            db.conn().execute(
+                "INSERT INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+                params![CONFIG_KEY, serde_json::to_string(&config)?, chrono::Utc::now().timestamp_millis()],
+            )?;
+        }
+        *self.config.write().map_err(|e| anyhow!("Lock error: {}", e))? = config.clone();
+        if let Ok(mut missing) = self.fast_model_missing.lock() {
+            *missing = None;
+        }
+        log::info!("Model router config updated (enabled: {}, fast model: {})", config.enabled, config.fast_model);
+        Ok(config)
+    }
+
+    /// Classify with the current thresholds
+    pub fn classify(&self, message: &str) -> RouteDecision {
+        classify(message, self.config().complexity_threshold)
+    }
+
+    fn fast_model_available(&self) -> bool {
+        self.fast_model_missing
+            .lock()
+            .map(|missing| missing.is_none_or(|at| at.elapsed() >= MISSING_MODEL_BACKOFF))
+            .unwrap_or(true)
+    }
+
+    /// Try the fast model; `answer` is None when the full model has to answer
+    pub async fn triage(
+        &self,
+        message: &str,
+        conversation_id: Option<&str>,
+        db: Option<&Mutex<Database>>,
+    ) -> TriageOutcome {
+        let config = self.config();
+        let decision = classify(message, config.complexity_threshold);
+        let mut outcome = TriageOutcome {
+            decision,
+            answer: None,
+            confidence: None,
+            escalated: false,
+            escalation_reason: None,
+            fast_latency_ms: None,
+        };
+
+        if !config.enabled || outcome.decision.route == Route::Full || !self.fast_model_available() {
+            return outcome;
+        }
+
+        let start = Instant::now();
+        let result = ollama::generate_fast_response(
+            message,
+            &config.fast_model,
+            config.fast_max_tokens,
+            conversation_id,
+            db,
+        )
+        .await;
+        outcome.fast_latency_ms = Some(start.elapsed().as_millis() as u64);
+
+        match result {
+            Ok(fast) => {
+                let (confidence, reason) =
+                    assess_fast_response(message, &fast.response, fast.truncated, outcome.decision.complexity);
+                outcome.confidence = Some(confidence);
+                if confidence >= config.confidence_threshold {
+                    log::info!("Routed to fast model {} (confidence {:.2})", config.fast_model, confidence);
+                    outcome.answer = Some(fast.response);
+                } else {
+                    outcome.escalated = true;
+                    outcome.escalation_reason = reason.or_else(|| Some("low confidence".to_string()));
+                }
+            }
+            Err(e) => {
+                if e.contains("not found") {
+                    log::warn!("Fast model {} is not installed, routing to the full model", config.fast_model);
+                    if let Ok(mut missing) = self.fast_model_missing.lock() {
+                        *missing = Some(Instant::now());
+                    }
+                }
+                outcome.escalated = true;
+                outcome.escalation_reason = Some(format!("fast model failed: {}", e));
+            }
+        }
+
+        if outcome.escalated {
+            log::info!(
+                "Escalating to full model: {}",
+                outcome.escalation_reason.as_deref().unwrap_or("low confidence")
+            );
+        }
+        outcome
+    }
+
+    /// Log a routed request (`latency` covers the whole answer)
+    pub fn record(&self, conversation_id: Option<&str>, outcome: &TriageOutcome, latency: Duration) {
+        let result = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e)).and_then(|db| {
+            db.conn().execute(
+                "INSERT INTO model_routing_log
+                 (conversation_id, planned_route, served_route, complexity, confidence,
+                  escalation_reason, latency_ms, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    conversation_id,
+                    outcome.decision.route.as_str(),
+                    outcome.served_by().as_str(),
+                    outcome.decision.complexity,
+                    outcome.confidence,
+                    outcome.escalation_reason,
+                    latency.as_millis() as i64,
+                    chrono::Utc::now().timestamp_millis(),
+                ],
+            )?;
+            Ok(())
+        });
+        if let Err(e) = result {
+            log::warn!("Failed to record model route: {}", e);
+        }
+    }
+
+    /// Per-route metrics over the last `days` days
+    pub fn metrics(&self, days: u32) -> Result<RouterMetrics> {
+        let days = days.max(1);
+        let since = chrono::Utc::now().timestamp_millis() - days as i64 * 24 * 60 * 60 * 1000;
+
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT served_route, COUNT(*),
+                    SUM(CASE WHEN planned_route = 'fast' AND served_route = 'full' THEN 1 ELSE 0 END),
+                    AVG(latency_ms), AVG(complexity), AVG(confidence)
+             FROM model_routing_log
+             WHERE created_at >= ?1
+             GROUP BY served_route",
+        )?;
+        let rows = stmt
+            .query_map([since], |row| {
+                let route: String = row.get(0)?;
+                Ok(RouteMetrics {
+                    route: if route == "fast" { Route::Fast } else { Route::Full },
+                    requests: row.get::<_, i64>(1)? as usize,
+                    escalations: row.get::<_, i64>(2)? as usize,
+                    avg_latency_ms: row.get(3)?,
+                    avg_complexity: row.get::<_, f64>(4)? as f32,
+                    avg_confidence: row.get::<_, Option<f64>>(5)?.map(|c| c as f32),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let total: usize = rows.iter().map(|r| r.requests).sum();
+        let fast = rows.iter().filter(|r| r.route == Route::Fast).map(|r| r.requests).sum::<usize>();
+        let escalations: usize = rows.iter().map(|r| r.escalations).sum();
+        let ratio = |n: usize, d: usize| if d == 0 { 0.0 } else { n as f32 / d as f32 };
+
+        Ok(RouterMetrics {
+            days,
+            total,
+            fast_share: ratio(fast, total),
+            escalation_rate: ratio(escalations, fast + escalations),
+            routes: rows,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify("hi!", 0.35).route, Route::Fast);
+        assert_eq!(classify("고마워 ㅎㅎ", 0.35).route, Route::Fast);
+        assert_eq!(classify("What is the capital of France?", 0.35).route, Route::Fast);
+
+        let decision = classify("Search my files for the quarterly report and open it", 0.35);
+        assert_eq!(decision.route, Route::Full);
+        assert!(decision.reasons.iter().any(|r| r.contains("tool")));
+
+        assert_eq!(classify("Explain why my Rust code panics:\n```\nfn main() { None::<u8>.unwrap(); }\n```", 0.35).route, Route::Full);
+        assert_eq!(classify("이 두 알고리즘의 차이를 설명해줘", 0.35).route, Route::Full);
+        assert_eq!(classify("what is 1234 * 5678", 0.35).route, Route::Full);
+    }
+
+    #[test]
+    fn test_assess_fast_response() {
+        let (confidence, _) = assess_fast_response("hi", "Hello! How can I help?", false, 0.0);
+        assert!(confidence >= 0.9);
+
+        assert_eq!(assess_fast_response("hi", "ESCALATE", false, 0.0).0, 0.0);
+        assert!(assess_fast_response("hi", "I'm not sure about that.", false, 0.0).0 < 0.6);
+        assert!(assess_fast_response("안녕", "Hello there!", false, 0.0).0 < 0.6);
+        assert!(assess_fast_response("hi", "Hello there and welcome", true, 0.2).0 < 0.6);
+    }
+}
@@ -0,0 +1,393 @@
+//! Network Politeness Policy (v3.9.0)
+//!
+//! Every outbound request made by the web search and URL fetch tools goes
+//! through one process-wide policy, so limits hold across tools and plans.
+//!
+//! Features:
+//! - Offline mode (`network_set_offline`) refusing all outbound tool traffic
+//! - Minimum interval between requests to the same domain
+//! - Cap on concurrent outbound requests
+//! - robots.txt checks for page fetches, cached per origin
+//! - Configurable user agent
+//!
+//! Offline mode lasts for the current session; it is not persisted.
+
+#![allow(dead_code)]  // Phase 5: Network politeness controls
+
+use anyhow::{anyhow, Result};
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+static GLOBAL_POLICY: OnceLock<NetworkPolicy> = OnceLock::new();
+
+/// How long a fetched robots.txt is trusted
+const ROBOTS_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// The process-wide policy used by the web tools
+pub fn global() -> &'static NetworkPolicy {
+    GLOBAL_POLICY.get_or_init(NetworkPolicy::default)
+}
+
+/// User-configurable politeness settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkPolicyConfig {
+    pub user_agent: String,
+    /// Minimum time between two requests to the same domain
+    pub per_domain_interval_ms: u64,
+    pub max_concurrent_requests: usize,
+    pub respect_robots_txt: bool,
+}
+
+impl Default for NetworkPolicyConfig {
+    fn default() -> Self {
+        Self {
+            user_agent: "Garden-of-Eden-V3/3.9.0 (Privacy-preserving AI assistant)".to_string(),
+            per_domain_interval_ms: 1000,
+            max_concurrent_requests: 4,
+            respect_robots_txt: true,
+        }
+    }
+}
+
+/// Current state for the settings UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkStatus {
+    pub offline: bool,
+    pub in_flight: usize,
+    pub config: NetworkPolicyConfig,
+}
+
+/// Slot for one outbound request; released on drop
+pub struct RequestPermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+pub struct NetworkPolicy {
+    offline: AtomicBool,
+    config: RwLock<NetworkPolicyConfig>,
+    /// Replaced when the cap changes; requests holding old permits finish normally
+    semaphore: RwLock<Arc<Semaphore>>,
+    /// Earliest time the next request to each domain may start
+    next_slot: Mutex<HashMap<String, Instant>>,
+    robots: Mutex<HashMap<String, (Instant, Arc<RobotsRules>)>>,
+}
+
+impl Default for NetworkPolicy {
+    fn default() -> Self {
+        Self::new(NetworkPolicyConfig::default())
+    }
+}
+
+impl NetworkPolicy {
+    pub fn new(config: NetworkPolicyConfig) -> Self {
+        Self {
+            offline: AtomicBool::new(false),
+            semaphore: RwLock::new(Arc::new(Semaphore::new(config.max_concurrent_requests))),
+            config: RwLock::new(config),
+            next_slot: Mutex::new(HashMap::new()),
+            robots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::SeqCst)
+    }
+
+    pub fn set_offline(&self, offline: bool) {
+        log::info!("Network offline mode {}", if offline { "enabled" } else { "disabled" });
+        self.offline.store(offline, Ordering::SeqCst);
+    }
+
+    /// Error if offline mode is on
+    pub fn ensure_online(&self) -> Result<()> {
+        if self.is_offline() {
+            return Err(anyhow!("Offline mode is on: outbound tool requests are disabled"));
+        }
+        Ok(())
+    }
+
+    pub fn config(&self) -> NetworkPolicyConfig {
+        self.config.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn set_config(&self, config: NetworkPolicyConfig) -> Result<()> {
+        if config.user_agent.trim().is_empty() {
+            return Err(anyhow!("User agent cannot be empty"));
+        }
+        if config.max_concurrent_requests == 0 {
+            return Err(anyhow!("Concurrent request cap must be at least 1"));
+        }
+
+        let mut current = self.config.write().unwrap_or_else(|e| e.into_inner());
+        if current.max_concurrent_requests != config.max_concurrent_requests {
+            *self.semaphore.write().unwrap_or_else(|e| e.into_inner()) =
+                Arc::new(Semaphore::new(config.max_concurrent_requests));
+        }
+        log::info!("Updating network policy: {:?}", config);
+        *current = config;
+        Ok(())
+    }
+
+    pub fn user_agent(&self) -> String {
+        self.config().user_agent
+    }
+
+    pub fn status(&self) -> NetworkStatus {
+        let config = self.config();
+        let available = self.semaphore.read().unwrap_or_else(|e| e.into_inner()).available_permits();
+        NetworkStatus {
+            offline: self.is_offline(),
+            in_flight: config.max_concurrent_requests.saturating_sub(available),
+            config,
+        }
+    }
+
+    /// Wait for a request slot and the domain's interval
+    ///
+    /// Fails immediately in offline mode, and again if offline mode was
+    /// switched on while waiting.
+    pub async fn acquire(&self, url: &Url) -> Result<RequestPermit> {
+        self.ensure_online()?;
+
+        let semaphore = Arc::clone(&self.semaphore.read().unwrap_or_else(|e| e.into_inner()));
+        let permit = semaphore
+            .acquire_owned()
+            .await
+            .map_err(|e| anyhow!("Network request slot unavailable: {}", e))?;
+
+        let interval = Duration::from_millis(self.config().per_domain_interval_ms);
+        let wait = self.reserve_slot(&url.host_str().unwrap_or_default().to_lowercase(), interval);
+        if !wait.is_zero() {
+            log::debug!("Waiting {:?} before next request to {}", wait, url.host_str().unwrap_or_default());
+            tokio::time::sleep(wait).await;
+        }
+
+        self.ensure_online()?;
+        Ok(RequestPermit { _permit: permit })
+    }
+
+    /// Claim the next free slot for `domain`, returning how long to wait for it
+    fn reserve_slot(&self, domain: &str, interval: Duration) -> Duration {
+        let mut next_slot = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let start = next_slot.get(domain).copied().filter(|t| *t > now).unwrap_or(now);
+        next_slot.insert(domain.to_string(), start + interval);
+        start - now
+    }
+
+    /// Whether robots.txt allows fetching `url` (always true when disabled)
+    ///
+    /// A missing or unreachable robots.txt allows everything.
+    pub async fn allowed_by_robots(&self, client: &Client, url: &Url) -> Result<bool> {
+        let config = self.config();
+        if !config.respect_robots_txt {
+            return Ok(true);
+        }
+
+        let origin = url.origin().ascii_serialization();
+        let cached = {
+            let robots = self.robots.lock().unwrap_or_else(|e| e.into_inner());
+            robots
+                .get(&origin)
+                .filter(|(fetched_at, _)| fetched_at.elapsed() < ROBOTS_TTL)
+                .map(|(_, rules)| Arc::clone(rules))
+        };
+
+        let rules = match cached {
+            Some(rules) => rules,
+            None => {
+                let robots_url = Url::parse(&format!("{}/robots.txt", origin))?;
+                let _permit = self.acquire(&robots_url).await?;
+                let body = match client
+                    .get(robots_url)
+                    .header(reqwest::header::USER_AGENT, &config.user_agent)
+                    .send()
+                    .await
+                {
+                    Ok(response) if response.status().is_success() => response.text().await.unwrap_or_default(),
+                    Ok(_) => String::new(),
+                    Err(e) => {
+                        log::debug!("robots.txt unavailable for {}: {}", origin, e);
+                        String::new()
+                    }
+                };
+                let rules = Arc::new(RobotsRules::parse(&body, &config.user_agent));
+                self.robots
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(origin, (Instant::now(), Arc::clone(&rules)));
+                rules
+            }
+        };
+
+        let path = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        Ok(rules.is_allowed(&path))
+    }
+}
+
+/// Allow/Disallow rules that apply to our user agent
+#[derive(Debug, Default)]
+pub struct RobotsRules {
+    /// (allow, path pattern)
+    rules: Vec<(bool, String)>,
+}
+
+impl RobotsRules {
+    /// Parse robots.txt, keeping the group for our product token, or `*` if none matches
+    pub fn parse(body: &str, user_agent: &str) -> Self {
+        let token = user_agent
+            .split(['/', ' '])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        let mut specific = Vec::new();
+        let mut wildcard = Vec::new();
+        let mut agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+
+        for line in body.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((field, value)) = line.split_once(':') else {
+                continue;
+            };
+            let field = field.trim().to_lowercase();
+            let value = value.trim();
+
+            match field.as_str() {
+                "user-agent" => {
+                    // A user-agent line after rules starts a new group
+                    if in_rules {
+                        agents.clear();
+                        in_rules = false;
+                    }
+                    agents.push(value.to_lowercase());
+                }
+                "allow" | "disallow" => {
+                    in_rules = true;
+                    // An empty Disallow allows everything
+                    if value.is_empty() {
+                        continue;
+                    }
+                    let rule = (field == "allow", value.to_string());
+                    if !token.is_empty() && agents.iter().any(|a| a != "*" && token.contains(a.as_str())) {
+                        specific.push(rule);
+                    } else if agents.iter().any(|a| a == "*") {
+                        wildcard.push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            rules: if specific.is_empty() { wildcard } else { specific },
+        }
+    }
+
+    /// Longest matching rule wins; Allow wins ties
+    pub fn is_allowed(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|(_, pattern)| pattern_matches(pattern, path))
+            .max_by_key(|(allow, pattern)| (pattern.len(), *allow))
+            .map(|(allow, _)| *allow)
+            .unwrap_or(true)
+    }
+}
+
+/// robots.txt path pattern: prefix match with `*` wildcards and a `$` end anchor
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(p) => (p, true),
+        None => (pattern, false),
+    };
+
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        let is_last = i == parts.len() - 1;
+        if is_last && anchored {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROBOTS: &str = "
+User-agent: *
+Disallow: /private/
+Allow: /private/public-page
+Disallow: /*.pdf$
+
+User-agent: BadBot
+Disallow: /
+";
+
+    #[test]
+    fn test_robots_rules() {
+        let rules = RobotsRules::parse(ROBOTS, "Garden-of-Eden-V3/3.9.0");
+        assert!(rules.is_allowed("/docs/intro"));
+        assert!(!rules.is_allowed("/private/notes"));
+        assert!(rules.is_allowed("/private/public-page"));
+        assert!(!rules.is_allowed("/files/report.pdf"));
+        assert!(rules.is_allowed("/files/report.pdf.html"));
+
+        let bad = RobotsRules::parse(ROBOTS, "BadBot/1.0");
+        assert!(!bad.is_allowed("/docs/intro"));
+
+        assert!(RobotsRules::parse("", "anything").is_allowed("/"));
+    }
+
+    #[test]
+    fn test_per_domain_interval() {
+        let policy = NetworkPolicy::default();
+        let interval = Duration::from_millis(500);
+
+        assert!(policy.reserve_slot("example.com", interval).is_zero());
+        let wait = policy.reserve_slot("example.com", interval);
+        assert!(wait > Duration::from_millis(400) && wait <= interval);
+        assert!(policy.reserve_slot("other.org", interval).is_zero());
+    }
+
+    #[tokio::test]
+    async fn test_offline_mode_and_config() {
+        let policy = NetworkPolicy::default();
+        let url = Url::parse("https://example.com/page").unwrap();
+
+        policy.set_offline(true);
+        assert!(policy.acquire(&url).await.is_err());
+        policy.set_offline(false);
+        let _permit = policy.acquire(&url).await.unwrap();
+        assert_eq!(policy.status().in_flight, 1);
+
+        let mut config = policy.config();
+        config.max_concurrent_requests = 0;
+        assert!(policy.set_config(config.clone()).is_err());
+        config.max_concurrent_requests = 2;
+        config.user_agent = "TestAgent/1.0".to_string();
+        policy.set_config(config).unwrap();
+        assert_eq!(policy.user_agent(), "TestAgent/1.0");
+        assert_eq!(policy.status().in_flight, 0);
+    }
+}
@@ -0,0 +1,743 @@
+//! Note Import Service (v3.9.0)
+//!
+//! Brings existing notes into memory: an Obsidian vault or an unzipped
+//! Notion Markdown export.
+//!
+//! Features:
+//! - Note bodies are chunked into RAG as documents
+//! - Frontmatter properties and top-level list items become wiki facts about
+//!   the note's title (open checkboxes as tasks)
+//! - `[[wiki links]]` and Notion page links become `links_to` relationships
+//!   between the notes in the knowledge graph
+//! - Re-import skips unchanged notes (SHA-256 of the file) and replaces what a
+//!   changed or deleted note contributed, so nothing is stored twice
+//! - Notes exported by this app (tagged `garden-of-eden`) are skipped
+
+#![allow(dead_code)]  // Phase 5: Note import
+
+use crate::database::Database;
+use crate::services::chunker::SourceKind;
+use crate::services::graph_builder::{GraphEdge, GraphNode};
+use crate::services::graph_storage::{normalize_entity_name, GraphStorage};
+#[cfg(feature = "lancedb-support")]
+use crate::services::rag_v2::RagServiceV2;
+#[cfg(not(feature = "lancedb-support"))]
+use crate::services::rag::RagService as RagServiceV2;
+use crate::services::semantic_wiki::{Fact, FactCategory, FactSource, SemanticWikiService};
+use anyhow::{anyhow, Context, Result};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Relationship type of a link between two notes
+pub const LINK_RELATIONSHIP: &str = "links_to";
+
+/// Tag of notes written by the knowledge vault export
+const EXPORT_TAG: &str = "garden-of-eden";
+
+/// Confidence of facts taken from the user's own notes
+const IMPORTED_FACT_CONFIDENCE: f32 = 0.8;
+
+/// Facts taken from one note at most
+const MAX_FACTS_PER_NOTE: usize = 50;
+
+/// List items outside this length aren't facts (headings, paragraphs)
+const MIN_FACT_CHARS: usize = 8;
+const MAX_FACT_CHARS: usize = 300;
+
+/// Largest note file read
+const MAX_NOTE_BYTES: u64 = 1024 * 1024;
+
+/// Linked files that are attachments rather than notes
+const ATTACHMENT_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "svg", "webp", "bmp", "pdf", "mp3", "wav", "m4a", "mp4", "mov", "webm", "csv",
+    "canvas", "excalidraw",
+];
+
+/// Frontmatter keys that describe the note rather than its subject
+const META_KEYS: &[&str] = &["title", "aliases", "alias", "tags", "tag", "cssclasses", "cssclass", "publish"];
+
+/// A Markdown note split into the parts that are imported
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedNote {
+    pub title: String,
+    pub aliases: Vec<String>,
+    pub tags: Vec<String>,
+    /// Frontmatter (or Notion property) key/value pairs
+    pub properties: Vec<(String, String)>,
+    /// Text without frontmatter, for RAG
+    pub body: String,
+    /// Titles of linked notes, in order of first appearance
+    pub links: Vec<String>,
+    /// Top-level list items with links reduced to their text; `true` = open task
+    pub items: Vec<(String, bool)>,
+}
+
+/// Outcome of an import
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NoteImportReport {
+    pub vault_path: String,
+    pub notes: usize,
+    pub imported: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    /// Notes gone from the vault whose memories were removed
+    pub removed: usize,
+    /// Notes exported by this app, too large, or unreadable
+    pub skipped: usize,
+    pub chunks: usize,
+    pub facts: usize,
+    pub links: usize,
+    pub errors: Vec<String>,
+}
+
+/// What an imported note contributed, so a re-import can replace it
+#[derive(Debug, Clone, Default)]
+struct ImportRecord {
+    content_hash: String,
+    entity_id: Option<String>,
+    episode_ids: Vec<String>,
+    fact_ids: Vec<String>,
+}
+
+/// Notion appends the page id to exported file names ("Page 0123…cdef")
+pub fn strip_notion_id(stem: &str) -> &str {
+    match stem.rsplit_once(' ') {
+        Some((title, id)) if id.len() == 32 && id.chars().all(|c| c.is_ascii_hexdigit()) => title,
+        _ => stem,
+    }
+}
+
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    let quoted = value.len() >= 2
+        && ((value.starts_with('"') && value.ends_with('"')) || (value.starts_with('\'') && value.ends_with('\'')));
+    if quoted {
+        value[1..value.len() - 1].to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Values of a frontmatter list: inline `[a, b]` or a bare scalar
+fn inline_list(value: &str) -> Vec<String> {
+    let value = value.trim();
+    let inner = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')).unwrap_or(value);
+    inner.split(',').map(unquote).filter(|v| !v.is_empty()).collect()
+}
+
+/// The flat `key: value` / `key:\n  - item` subset of YAML that notes use
+fn parse_frontmatter(yaml: &str) -> Vec<(String, Vec<String>)> {
+    let mut entries: Vec<(String, Vec<String>)> = Vec::new();
+    for line in yaml.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix("- ") {
+            if line.starts_with(char::is_whitespace) || line.starts_with('-') {
+                if let Some((_, values)) = entries.last_mut() {
+                    values.push(unquote(item));
+                }
+            }
+            continue;
+        }
+        if let Some((key, value)) = trimmed.split_once(':') {
+            let value = value.trim();
+            let values = if value.is_empty() { Vec::new() } else if value.starts_with('[') { inline_list(value) } else { vec![unquote(value)] };
+            entries.push((key.trim().to_string(), values));
+        }
+    }
+    entries
+}
+
+/// Notion encodes link targets ("Book%20Club.md", Hangul as UTF-8 escapes)
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| value.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Title of a link target: `Note#Heading|Alias` or `../Page%20abc….md`
+fn link_title(target: &str) -> Option<String> {
+    let target = target.split(['|', '#']).next()?.trim();
+    if target.is_empty() || target.contains("://") || target.starts_with("mailto:") {
+        return None;
+    }
+    let decoded = percent_decode(target);
+    let file = decoded.rsplit('/').next()?;
+    let stem = match file.rsplit_once('.') {
+        Some((stem, ext)) if ext.eq_ignore_ascii_case("md") => stem,
+        Some((_, ext)) if ATTACHMENT_EXTENSIONS.contains(&ext.to_lowercase().as_str()) => return None,
+        _ => file,
+    };
+    let title = strip_notion_id(stem).trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Replace `[[Target|Alias]]` with its visible text and collect the link targets
+fn reduce_links(text: &str, links: &mut Vec<String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("[[") {
+        let Some(len) = rest[start + 2..].find("]]") else {
+            break;
+        };
+        let inner = &rest[start + 2..start + 2 + len];
+        let embed = rest[..start].ends_with('!');
+        out.push_str(&rest[..if embed { start - 1 } else { start }]);
+        if !embed {
+            if let Some(title) = link_title(inner) {
+                if !links.contains(&title) {
+                    links.push(title);
+                }
+            }
+            let shown = inner.split_once('|').map(|(_, alias)| alias).unwrap_or_else(|| inner.split('#').next().unwrap_or(inner));
+            out.push_str(shown.trim());
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+
+    // Markdown links to other notes: [Text](Other%20Page.md)
+    let mut result = String::with_capacity(out.len());
+    let mut rest = out.as_str();
+    while let Some(open) = rest.find("](") {
+        let Some(close) = rest[open + 2..].find(')') else {
+            break;
+        };
+        let Some(text_start) = rest[..open].rfind('[') else {
+            result.push_str(&rest[..open + 2]);
+            rest = &rest[open + 2..];
+            continue;
+        };
+        let target = &rest[open + 2..open + 2 + close];
+        let embed = rest[..text_start].ends_with('!');
+        result.push_str(&rest[..if embed { text_start - 1 } else { text_start }]);
+        if !embed {
+            if target.ends_with(".md") || target.contains(".md#") {
+                if let Some(title) = link_title(target) {
+                    if !links.contains(&title) {
+                        links.push(title);
+                    }
+                }
+            }
+            result.push_str(&rest[text_start + 1..open]);
+        }
+        rest = &rest[open + 2 + close + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Split a note into title, properties, links, list items and body
+pub fn parse_note(file_stem: &str, content: &str) -> ParsedNote {
+    let content = content.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let notion = strip_notion_id(file_stem) != file_stem;
+    let mut note = ParsedNote {
+        title: strip_notion_id(file_stem).trim().to_string(),
+        ..ParsedNote::default()
+    };
+
+    let mut body = content.as_str();
+    if let Some(rest) = content.strip_prefix("---\n") {
+        if let Some(end) = rest.find("\n---") {
+            for (key, values) in parse_frontmatter(&rest[..end]) {
+                match key.to_lowercase().as_str() {
+                    "title" => {
+                        if let Some(title) = values.first().filter(|t| !t.is_empty()) {
+                            note.title = title.clone();
+                        }
+                    }
+                    "aliases" | "alias" => note.aliases.extend(values),
+                    "tags" | "tag" => note.tags.extend(values.iter().map(|t| t.trim_start_matches('#').to_string())),
+                    k if META_KEYS.contains(&k) => {}
+                    _ if !values.is_empty() => note.properties.push((key, values.join(", "))),
+                    _ => {}
+                }
+            }
+            body = rest[end + 4..].trim_start_matches(['-', '\n']);
+        }
+    }
+
+    // Notion pages start with "# Title", database pages followed by "Property: value" lines
+    let mut lines = body.lines().peekable();
+    let mut kept: Vec<&str> = Vec::new();
+    while lines.peek().is_some_and(|line| line.trim().is_empty()) {
+        lines.next();
+    }
+    if let Some(first) = lines.peek() {
+        if first.strip_prefix("# ").is_some_and(|h| h.trim() == note.title) {
+            lines.next();
+            while lines.peek().is_some_and(|line| line.trim().is_empty()) {
+                lines.next();
+            }
+            while let Some(line) = lines.peek().filter(|_| notion) {
+                let property = line.split_once(": ").filter(|(key, value)| {
+                    !key.is_empty()
+                        && key.chars().count() <= 30
+                        && !key.starts_with(['-', '*', '#', '>', '|'])
+                        && !value.trim().is_empty()
+                });
+                let Some((key, value)) = property else {
+                    break;
+                };
+                note.properties.push((key.trim().to_string(), value.trim().to_string()));
+                lines.next();
+            }
+        }
+    }
+    kept.extend(lines);
+
+    let text = kept.join("\n");
+    let mut links = Vec::new();
+    let mut in_code = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        let reduced = reduce_links(line, &mut links);
+        let item = reduced
+            .strip_prefix("- ")
+            .or_else(|| reduced.strip_prefix("* "))
+            .or_else(|| reduced.split_once(". ").filter(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())).map(|(_, rest)| rest));
+        let Some(item) = item else {
+            continue;
+        };
+        let (item, open_task) = if let Some(task) = item.strip_prefix("[ ] ") {
+            (task, true)
+        } else if item.starts_with("[x] ") || item.starts_with("[X] ") {
+            continue;
+        } else {
+            (item, false)
+        };
+        let item = item.trim();
+        if (MIN_FACT_CHARS..=MAX_FACT_CHARS).contains(&item.chars().count()) {
+            note.items.push((item.to_string(), open_task));
+        }
+    }
+    note.links = links.into_iter().filter(|link| *link != note.title).collect();
+    note.body = text.trim().to_string();
+    note
+}
+
+/// Wiki facts about the note's title
+pub fn note_facts(note: &ParsedNote) -> Vec<Fact> {
+    let now = chrono::Utc::now().timestamp();
+    let fact = |statement: String, category: FactCategory| Fact {
+        id: uuid::Uuid::new_v4().to_string(),
+        statement,
+        entity: note.title.clone(),
+        category,
+        confidence: IMPORTED_FACT_CONFIDENCE,
+        source_conversation_id: String::new(),
+        source_message_id: None,
+        learned_at: now,
+        reinforcement_count: 1,
+        related_facts: Vec::new(),
+        source: FactSource::UserStatement,
+        last_observed_at: now,
+        note: None,
+    };
+
+    note.properties
+        .iter()
+        .map(|(key, value)| fact(format!("{} {}: {}", note.title, key, value), FactCategory::Knowledge))
+        .chain(note.items.iter().map(|(item, open_task)| {
+            fact(item.clone(), if *open_task { FactCategory::Task } else { FactCategory::Knowledge })
+        }))
+        .take(MAX_FACTS_PER_NOTE)
+        .collect()
+}
+
+fn content_hash(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Markdown files of a vault, skipping hidden folders (`.obsidian`, `.trash`)
+fn markdown_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+            let path = entry?.path();
+            let hidden = path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with('.'));
+            if hidden {
+                continue;
+            }
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension().and_then(|e| e.to_str()).is_some_and(|e| e.eq_ignore_ascii_case("md")) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Note import service
+pub struct NoteImportService {
+    db: Arc<Mutex<Database>>,
+    wiki: Arc<SemanticWikiService>,
+    graph: Arc<GraphStorage>,
+    rag: Arc<RagServiceV2>,
+}
+
+impl NoteImportService {
+    pub fn new(
+        db: Arc<Mutex<Database>>,
+        wiki: Arc<SemanticWikiService>,
+        graph: Arc<GraphStorage>,
+        rag: Arc<RagServiceV2>,
+    ) -> Result<Self> {
+        init_tables(&*db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?)?;
+        Ok(Self { db, wiki, graph, rag })
+    }
+
+    /// Import (or re-import) every Markdown note under `vault_path`
+    pub async fn import_vault(&self, vault_path: &str) -> Result<NoteImportReport> {
+        let root = fs::canonicalize(vault_path.trim())
+            .with_context(|| format!("Vault folder not found: {}", vault_path))?;
+        let vault = root.display().to_string();
+        let files = markdown_files(&root)?;
+        log::info!("Importing {} notes from {}", files.len(), vault);
+
+        let mut report = NoteImportReport {
+            vault_path: vault.clone(),
+            notes: files.len(),
+            ..NoteImportReport::default()
+        };
+        let previous = self.load_records(&vault)?;
+
+        // Parse everything first so links can point at notes later in the vault
+        let mut changed: Vec<(String, String, ParsedNote)> = Vec::new();
+        let mut seen: HashSet<String> = HashSet::new();
+        for path in &files {
+            let relative = path.strip_prefix(&root).unwrap_or(path).to_string_lossy().replace('\\', "/");
+            let too_large = fs::metadata(path).map(|m| m.len() > MAX_NOTE_BYTES).unwrap_or(true);
+            let content = match fs::read_to_string(path) {
+                Ok(content) if !too_large => content,
+                _ => {
+                    report.skipped += 1;
+                    continue;
+                }
+            };
+            let hash = content_hash(&content);
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+            let note = parse_note(stem, &content);
+            if note.tags.iter().any(|t| t == EXPORT_TAG) {
+                report.skipped += 1;
+                continue;
+            }
+            seen.insert(relative.clone());
+            if previous.get(&relative).is_some_and(|record| record.content_hash == hash) {
+                report.unchanged += 1;
+                continue;
+            }
+            changed.push((relative, hash, note));
+        }
+
+        // Deleted notes take their memories with them
+        for (relative, record) in &previous {
+            if !seen.contains(relative) {
+                self.remove_contribution(record)?;
+                self.delete_record(&vault, relative)?;
+                report.removed += 1;
+            }
+        }
+
+        let mut entity_ids: HashMap<String, String> = HashMap::new();
+        for (relative, hash, note) in changed {
+            let replaced = previous.get(&relative);
+            if let Some(record) = replaced {
+                self.remove_contribution(record)?;
+            }
+            match self.import_note(&relative, &hash, &note, &mut entity_ids).await {
+                Ok((record, links)) => {
+                    report.chunks += record.episode_ids.len();
+                    report.facts += record.fact_ids.len();
+                    report.links += links;
+                    self.save_record(&vault, &relative, &note.title, &record)?;
+                    if replaced.is_some() {
+                        report.updated += 1;
+                    } else {
+                        report.imported += 1;
+                    }
+                }
+                Err(e) => {
+                    log::warn!("Failed to import note {}: {}", relative, e);
+                    // Forget the stale record so the next import retries the note
+                    self.delete_record(&vault, &relative)?;
+                    report.errors.push(format!("{}: {}", relative, e));
+                }
+            }
+        }
+
+        log::info!(
+            "✓ Imported notes from {}: {} new, {} updated, {} unchanged, {} removed",
+            vault, report.imported, report.updated, report.unchanged, report.removed
+        );
+        Ok(report)
+    }
+
+    /// Store one note's chunks, facts and links
+    async fn import_note(
+        &self,
+        relative: &str,
+        hash: &str,
+        note: &ParsedNote,
+        entity_ids: &mut HashMap<String, String>,
+    ) -> Result<(ImportRecord, usize)> {
+        let mut record = ImportRecord {
+            content_hash: hash.to_string(),
+            ..ImportRecord::default()
+        };
+
+        if !note.body.is_empty() {
+            record.episode_ids = self
+                .rag
+                .ingest_document(relative, &note.body, Some(SourceKind::Markdown))
+                .await?;
+        }
+
+        let facts = note_facts(note);
+        let fact_ids: Vec<String> = facts.iter().map(|f| f.id.clone()).collect();
+        self.wiki.store_facts(facts).await?;
+        // Facts merged into an existing one aren't ours to delete later; the ids
+        // simply won't exist
+        record.fact_ids = fact_ids;
+
+        let mut links = 0;
+        if !note.links.is_empty() {
+            let source = self.note_entity(&note.title, entity_ids)?;
+            self.graph
+                .delete_relationships_from(&source, LINK_RELATIONSHIP)
+                .map_err(|e| anyhow!(e))?;
+            for title in &note.links {
+                let target = self.note_entity(title, entity_ids)?;
+                let mut properties = HashMap::new();
+                properties.insert("note".to_string(), relative.to_string());
+                self.graph
+                    .save_relationship(&GraphEdge {
+                        source_id: source.clone(),
+                        target_id: target,
+                        relationship_type: LINK_RELATIONSHIP.to_string(),
+                        weight: 1.0,
+                        properties,
+                        valid_from: None,
+                        valid_to: None,
+                    })
+                    .map_err(|e| anyhow!(e))?;
+                links += 1;
+            }
+            record.entity_id = Some(source);
+        }
+
+        Ok((record, links))
+    }
+
+    /// Graph entity of a note title: an existing entity of that name, or a new document entity
+    fn note_entity(&self, title: &str, entity_ids: &mut HashMap<String, String>) -> Result<String> {
+        let name = normalize_entity_name(title);
+        if let Some(id) = entity_ids.get(&name) {
+            return Ok(id.clone());
+        }
+
+        let existing = self
+            .graph
+            .search_entities(title, 10)
+            .map_err(|e| anyhow!(e))?
+            .into_iter()
+            .find(|node| normalize_entity_name(&node.name) == name);
+        let id = match existing {
+            Some(node) => node.entity_id,
+            None => {
+                let mut properties = HashMap::new();
+                properties.insert("source".to_string(), "note_import".to_string());
+                let node = GraphNode {
+                    entity_id: format!("document:{}", title.to_lowercase()),
+                    name: title.to_string(),
+                    entity_type: "Document".to_string(),
+                    properties,
+                    community_id: None,
+                    degree: 0,
+                };
+                self.graph.save_entity(&node).map_err(|e| anyhow!(e))?;
+                node.entity_id
+            }
+        };
+        entity_ids.insert(name, id.clone());
+        Ok(id)
+    }
+
+    /// Delete the chunks, facts and outgoing links a note added
+    fn remove_contribution(&self, record: &ImportRecord) -> Result<()> {
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            for id in &record.episode_ids {
+                db.conn().execute("DELETE FROM episodic_memory WHERE id = ?1", params![id])?;
+            }
+        }
+        self.wiki.delete_facts(&record.fact_ids)?;
+        if let Some(entity_id) = &record.entity_id {
+            self.graph
+                .delete_relationships_from(entity_id, LINK_RELATIONSHIP)
+                .map_err(|e| anyhow!(e))?;
+        }
+        Ok(())
+    }
+
+    fn load_records(&self, vault: &str) -> Result<HashMap<String, ImportRecord>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT path, content_hash, entity_id, episode_ids, fact_ids FROM note_imports WHERE vault = ?1",
+        )?;
+        let records = stmt
+            .query_map(params![vault], |row| {
+                let episode_ids: String = row.get(3)?;
+                let fact_ids: String = row.get(4)?;
+                Ok((
+                    row.get::<_, String>(0)?,
+                    ImportRecord {
+                        content_hash: row.get(1)?,
+                        entity_id: row.get(2)?,
+                        episode_ids: serde_json::from_str(&episode_ids).unwrap_or_default(),
+                        fact_ids: serde_json::from_str(&fact_ids).unwrap_or_default(),
+                    },
+                ))
+            })?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(records)
+    }
+
+    fn save_record(&self, vault: &str, relative: &str, title: &str, record: &ImportRecord) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn().execute(
+            "INSERT OR REPLACE INTO note_imports
+             (vault, path, title, content_hash, entity_id, episode_ids, fact_ids, imported_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                vault,
+                relative,
+                title,
+                record.content_hash,
+                record.entity_id,
+                serde_json::to_string(&record.episode_ids)?,
+                serde_json::to_string(&record.fact_ids)?,
+                chrono::Utc::now().timestamp(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn delete_record(&self, vault: &str, relative: &str) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn().execute(
+            "DELETE FROM note_imports WHERE vault = ?1 AND path = ?2",
+            params![vault, relative],
+        )?;
+        Ok(())
+    }
+}
+
+fn init_tables(db: &Database) -> Result<()> {
+    db.conn().execute(
+        "CREATE TABLE IF NOT EXISTS note_imports (
+            vault TEXT NOT NULL,
+            path TEXT NOT NULL,
+            title TEXT NOT NULL,
+            content_hash TEXT NOT NULL,
+            entity_id TEXT,
+            episode_ids TEXT NOT NULL,
+            fact_ids TEXT NOT NULL,
+            imported_at INTEGER NOT NULL,
+            PRIMARY KEY (vault, path)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_obsidian_note() {
+        let content = "---\ntitle: Alice Kim\naliases: [Alice]\ntags:\n  - person\nbirthday: 1990-03-03\n---\n\
+                       Met at [[Acme Corp|work]] in 2019, see ![[photo.png]].\n\n\
+                       - Works on [[Garden Project#Goals]] backend\n\
+                       - [ ] Send the draft to Alice\n\
+                       - [x] Book the venue for Friday\n\
+                       - Short\n\
+                       ```\n- not a list item in code\n```\n";
+        let note = parse_note("alice", content);
+        assert_eq!(note.title, "Alice Kim");
+        assert_eq!(note.aliases, vec!["Alice".to_string()]);
+        assert_eq!(note.tags, vec!["person".to_string()]);
+        assert_eq!(note.properties, vec![("birthday".to_string(), "1990-03-03".to_string())]);
+        assert_eq!(note.links, vec!["Acme Corp".to_string(), "Garden Project".to_string()]);
+        assert_eq!(
+            note.items,
+            vec![
+                ("Works on Garden Project backend".to_string(), false),
+                ("Send the draft to Alice".to_string(), true),
+            ]
+        );
+        assert!(note.body.starts_with("Met at [[Acme Corp|work]]"));
+
+        let facts = note_facts(&note);
+        assert_eq!(facts.len(), 3);
+        assert_eq!(facts[0].statement, "Alice Kim birthday: 1990-03-03");
+        assert_eq!(facts[2].category, FactCategory::Task);
+        assert!(facts.iter().all(|f| f.entity == "Alice Kim"));
+    }
+
+    #[test]
+    fn test_parse_notion_page() {
+        assert_eq!(strip_notion_id("Reading List 0123456789abcdef0123456789abcdef"), "Reading List");
+        assert_eq!(strip_notion_id("Meeting 2024"), "Meeting 2024");
+
+        let content = "# Reading List\n\nStatus: Active\nOwner: Alice\n\n\
+                       Books to read, started from [Book Club](Book%20Club%20fedcba9876543210fedcba9876543210.md).\n\
+                       Docs at [the site](https://example.com/page.md).\n";
+        let note = parse_note("Reading List 0123456789abcdef0123456789abcdef", content);
+        assert_eq!(note.title, "Reading List");
+        assert_eq!(
+            note.properties,
+            vec![("Status".to_string(), "Active".to_string()), ("Owner".to_string(), "Alice".to_string())]
+        );
+        assert_eq!(note.links, vec!["Book Club".to_string()]);
+        assert!(note.body.starts_with("Books to read"));
+        assert_eq!(link_title("%EC%9D%BD%EA%B8%B0.md").as_deref(), Some("읽기"));
+        assert_eq!(link_title("node.js").as_deref(), Some("node.js"));
+    }
+}
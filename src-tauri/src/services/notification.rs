@@ -0,0 +1,339 @@
+//! Notification Service (v3.9.0)
+//!
+//! Native system notifications with action buttons for background services
+//! (proactive mode, goal tracker, streaming vision, memory decay).
+//!
+//! Features:
+//! - Native notifications (notify-rust) with "Snooze" / "Open chat" / "Run plan" buttons
+//! - Button clicks routed back into the app (main window, chat, task planner)
+//! - Per-source snooze
+//! - `notification-shown` event so the frontend can mirror buttons where the OS can't
+
+#![allow(dead_code)]  // Phase 5: Notifications (some helpers used by future UI)
+
+use crate::services::task_planner::TaskPlannerService;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Mutex as TokioMutex;
+
+const APP_NAME: &str = "Garden of Eden";
+
+/// Default snooze length when a notification doesn't specify one
+pub const DEFAULT_SNOOZE_MINUTES: i64 = 30;
+
+/// Notifications kept for routing late button clicks
+const MAX_PENDING: usize = 50;
+
+/// Which background service raised a notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationSource {
+    Proactive,
+    GoalTracker,
+    StreamingVision,
+    MemoryDecay,
+    MemoryReview,
+    WeeklyReview,
+    Script,  // v3.9.0: notify() from user scripts
+}
+
+/// Button attached to a notification
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NotificationAction {
+    /// Silence this notification's source for a while
+    Snooze { minutes: i64 },
+    /// Bring up the main window with an optional chat prompt prefilled
+    OpenChat { prompt: Option<String> },
+    /// Generate the execution plan for a task
+    RunPlan { task_id: String },
+}
+
+impl NotificationAction {
+    /// Stable identifier used for native action buttons
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Snooze { .. } => "snooze",
+            Self::OpenChat { .. } => "open_chat",
+            Self::RunPlan { .. } => "run_plan",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Snooze { .. } => "Snooze",
+            Self::OpenChat { .. } => "Open chat",
+            Self::RunPlan { .. } => "Run plan",
+        }
+    }
+}
+
+/// Notification sent to the OS and frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppNotification {
+    pub id: String,
+    pub source: NotificationSource,
+    pub title: String,
+    pub body: String,
+    pub actions: Vec<NotificationAction>,
+    pub created_at: i64, // Unix millis
+}
+
+impl AppNotification {
+    pub fn new(source: NotificationSource, title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            id: format!("notif_{}", uuid::Uuid::new_v4()),
+            source,
+            title: title.into(),
+            body: body.into(),
+            actions: Vec::new(),
+            created_at: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    pub fn with_action(mut self, action: NotificationAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// Find the action behind a button key
+    pub fn action_for_key(&self, key: &str) -> Option<&NotificationAction> {
+        self.actions.iter().find(|a| a.key() == key)
+    }
+}
+
+/// Where a button click was routed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NotificationRoute {
+    Snoozed { source: NotificationSource, until: i64 },
+    ChatOpened { prompt: Option<String> },
+    PlanGenerated { task_id: String, total_tasks: usize },
+}
+
+/// Per-source snooze bookkeeping (Unix millis)
+#[derive(Debug, Default)]
+struct SnoozeTable {
+    until: HashMap<NotificationSource, i64>,
+}
+
+impl SnoozeTable {
+    fn snooze(&mut self, source: NotificationSource, minutes: i64, now: i64) -> i64 {
+        let until = now + minutes.max(1) * 60 * 1000;
+        self.until.insert(source, until);
+        until
+    }
+
+    fn is_snoozed(&self, source: NotificationSource, now: i64) -> bool {
+        self.until.get(&source).is_some_and(|until| *until > now)
+    }
+}
+
+/// Notification service
+pub struct NotificationService {
+    app_handle: Arc<TokioMutex<Option<AppHandle>>>,
+    task_planner: OnceLock<Arc<TaskPlannerService>>,
+    snoozes: Mutex<SnoozeTable>,
+    pending: Mutex<Vec<AppNotification>>,
+}
+
+impl NotificationService {
+    pub fn new() -> Result<Self> {
+        log::info!("✓ Notification Service initialized");
+        Ok(Self {
+            app_handle: Arc::new(TokioMutex::new(None)),
+            task_planner: OnceLock::new(),
+            snoozes: Mutex::new(SnoozeTable::default()),
+            pending: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Set app handle for events and window focus
+    pub async fn set_app_handle(&self, handle: AppHandle) {
+        let mut app_handle = self.app_handle.lock().await;
+        *app_handle = Some(handle);
+    }
+
+    /// Attach the task planner used by "Run plan" buttons
+    pub fn attach_task_planner(&self, task_planner: Arc<TaskPlannerService>) {
+        let _ = self.task_planner.set(task_planner);
+    }
+
+    pub fn is_snoozed(&self, source: NotificationSource) -> bool {
+        self.snoozes
+            .lock()
+            .map(|s| s.is_snoozed(source, chrono::Utc::now().timestamp_millis()))
+            .unwrap_or(false)
+    }
+
+    /// Show a notification. Returns false when its source is snoozed.
+    pub async fn notify(self: &Arc<Self>, notification: AppNotification) -> Result<bool> {
+        if self.is_snoozed(notification.source) {
+            log::debug!("Notification from {:?} suppressed (snoozed)", notification.source);
+            return Ok(false);
+        }
+
+        {
+            let mut pending = self.pending.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            pending.push(notification.clone());
+            let overflow = pending.len().saturating_sub(MAX_PENDING);
+            pending.drain(..overflow);
+        }
+
+        self.emit("notification-shown", &notification).await;
+
+        let service = Arc::clone(self);
+        std::thread::spawn(move || {
+            if let Err(e) = service.show_native(&notification) {
+                log::warn!("Failed to show native notification: {}", e);
+            }
+        });
+
+        Ok(true)
+    }
+
+    /// Show the OS notification, blocking until a button is clicked where supported
+    fn show_native(self: &Arc<Self>, notification: &AppNotification) -> Result<()> {
+        let mut native = notify_rust::Notification::new();
+        native
+            .appname(APP_NAME)
+            .summary(&notification.title)
+            .body(&notification.body);
+
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            for action in &notification.actions {
+                native.action(action.key(), action.label());
+            }
+
+            let handle = native.show()?;
+            let service = Arc::clone(self);
+            let notification_id = notification.id.clone();
+            handle.wait_for_action(move |key| {
+                // "__closed" is sent when the notification is dismissed
+                if notification.action_for_key(key).is_some() {
+                    let service = Arc::clone(&service);
+                    let notification_id = notification_id.clone();
+                    let key = key.to_string();
+                    tauri::async_runtime::spawn(async move {
+                        if let Err(e) = service.handle_action(&notification_id, &key).await {
+                            log::warn!("Failed to handle notification action: {}", e);
+                        }
+                    });
+                }
+            });
+        }
+
+        // macOS / Windows: no native buttons, the frontend mirrors them from `notification-shown`
+        #[cfg(not(all(unix, not(target_os = "macos"))))]
+        {
+            native.show()?;
+        }
+
+        Ok(())
+    }
+
+    /// Route a button click (native or from the frontend) to its action
+    pub async fn handle_action(
+        &self,
+        notification_id: &str,
+        action_key: &str,
+    ) -> Result<NotificationRoute> {
+        let notification = {
+            let pending = self.pending.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            pending
+                .iter()
+                .find(|n| n.id == notification_id)
+                .cloned()
+                .ok_or_else(|| anyhow!("Notification not found: {}", notification_id))?
+        };
+
+        let action = notification
+            .action_for_key(action_key)
+            .cloned()
+            .ok_or_else(|| anyhow!("Unknown notification action: {}", action_key))?;
+
+        let route = match action {
+            NotificationAction::Snooze { minutes } => {
+                let until = self
+                    .snoozes
+                    .lock()
+                    .map_err(|e| anyhow!("Lock error: {}", e))?
+                    .snooze(notification.source, minutes, chrono::Utc::now().timestamp_millis());
+                log::info!("Snoozed {:?} notifications for {} min", notification.source, minutes);
+                NotificationRoute::Snoozed { source: notification.source, until }
+            }
+            NotificationAction::OpenChat { prompt } => {
+                self.focus_main_window().await;
+                NotificationRoute::ChatOpened { prompt }
+            }
+            NotificationAction::RunPlan { task_id } => {
+                let planner = self
+                    .task_planner
+                    .get()
+                    .ok_or_else(|| anyhow!("Task planner not available"))?;
+                let plan = planner.generate_execution_plan(&task_id)?;
+                self.focus_main_window().await;
+                self.emit("notification-plan", &plan).await;
+                NotificationRoute::PlanGenerated { task_id, total_tasks: plan.ordered_tasks.len() }
+            }
+        };
+
+        self.emit("notification-action", &route).await;
+        self.pending
+            .lock()
+            .map_err(|e| anyhow!("Lock error: {}", e))?
+            .retain(|n| n.id != notification_id);
+
+        Ok(route)
+    }
+
+    async fn focus_main_window(&self) {
+        if let Some(app) = self.app_handle.lock().await.as_ref() {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }
+    }
+
+    async fn emit<S: Serialize + Clone>(&self, event: &str, payload: S) {
+        if let Some(app) = self.app_handle.lock().await.as_ref() {
+            if let Err(e) = app.emit(event, payload) {
+                log::warn!("Failed to emit {}: {}", event, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snooze_table() {
+        let mut table = SnoozeTable::default();
+        let until = table.snooze(NotificationSource::Proactive, 30, 0);
+        assert_eq!(until, 30 * 60 * 1000);
+        assert!(table.is_snoozed(NotificationSource::Proactive, 1000));
+        assert!(!table.is_snoozed(NotificationSource::Proactive, until));
+        assert!(!table.is_snoozed(NotificationSource::GoalTracker, 1000));
+    }
+
+    #[test]
+    fn test_action_lookup() {
+        let notification = AppNotification::new(NotificationSource::GoalTracker, "Goal", "Check in")
+            .with_action(NotificationAction::Snooze { minutes: DEFAULT_SNOOZE_MINUTES })
+            .with_action(NotificationAction::RunPlan { task_id: "t1".to_string() });
+
+        assert_eq!(
+            notification.action_for_key("run_plan"),
+            Some(&NotificationAction::RunPlan { task_id: "t1".to_string() })
+        );
+        assert!(notification.action_for_key("open_chat").is_none());
+        assert!(notification.action_for_key("__closed").is_none());
+    }
+}
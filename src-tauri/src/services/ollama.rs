@@ -1,28 +1,63 @@
 use serde::{Deserialize, Serialize};
 use reqwest::Client;
 use futures_util::StreamExt;
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
 use tauri::Emitter;  // v3.3.0: For emit() method
 
 #[cfg(feature = "lancedb-support")]
-use super::rag_v2::{RagServiceV2, format_episodes_for_context};  // v3.4.0: LanceDB for 10-100x faster RAG
+use super::rag_v2::{RagServiceV2, Episode, format_episodes_for_context};  // v3.4.0: LanceDB for 10-100x faster RAG
 #[cfg(not(feature = "lancedb-support"))]
-use super::rag::{RagService as RagServiceV2, format_episodes_for_context};  // Fallback to SQLite-based RAG
+use super::rag::{RagService as RagServiceV2, Episode, format_episodes_for_context};  // Fallback to SQLite-based RAG
 use super::tool_calling::{ToolService, ToolCall, ToolDefinition};
-use super::learning::LearningService;
+use super::learning::{self, LearningService};
+use super::persona_presets;  // v3.9.0: Active preset instructions
+use super::conversation_language::ConversationLanguage;
+use super::localization;  // v3.9.0: Prompts in the user's language
+use super::language_detection;  // v3.9.0: Reply language of the current message
+use super::sentiment;  // v3.9.0: Session mood
+use super::model_context;  // v3.9.0: Context window of the active model
+use super::chunker::estimate_tokens;
+use super::provenance::Citation;
+use super::raft::{self, Groundedness, RaftService};  // v3.9.0: Grounding in the live pipeline
+use super::memory_scope::RetrievalScope;  // v3.9.0: Conversation / project memories
+use super::retrieval_feedback;  // v3.9.0: Memory usage for retrieval tuning
 use crate::database::Database;
+use crate::database::models::PersonaParameters;
 
 const OLLAMA_API_URL: &str = "http://localhost:11434/api/generate";
 const OLLAMA_CHAT_API_URL: &str = "http://localhost:11434/api/chat";
 const MODEL_NAME: &str = "qwen2.5:7b"; // Fast 3-4s responses, excellent Korean support, better reasoning
 const RAG_TOP_K: usize = 3; // Retrieve top 3 most relevant memories
 
+/// Chat model override, e.g. a fine-tuned LoRA model (v3.9.0)
+static CHAT_MODEL: RwLock<Option<String>> = RwLock::new(None);
+
+/// Model used for chat: the selected override, otherwise MODEL_NAME (v3.9.0)
+pub fn chat_model() -> String {
+    CHAT_MODEL
+        .read()
+        .ok()
+        .and_then(|model| model.clone())
+        .unwrap_or_else(|| MODEL_NAME.to_string())
+}
+
+/// Select the chat model; None goes back to the default (v3.9.0)
+pub fn set_chat_model(model: Option<String>) {
+    if let Ok(mut current) = CHAT_MODEL.write() {
+        log::info!("Chat model: {}", model.as_deref().unwrap_or(MODEL_NAME));
+        *current = model;
+    }
+}
+
 #[derive(Debug, Serialize)]
 struct OllamaRequest {
     model: String,
     prompt: String,
     stream: bool,
     options: OllamaOptions,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,  // v3.9.0: JSON schema (or "json") constraining the reply
 }
 
 #[derive(Debug, Serialize)]
@@ -31,12 +66,49 @@ struct OllamaOptions {
     top_p: f32,
     top_k: i32,
     repeat_penalty: f32, // Prevent overfitting and repetitive responses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_ctx: Option<usize>, // v3.9.0: Context window of the active model
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_predict: Option<i32>, // v3.9.0: Response token limit (fast model triage)
 }
 
 #[derive(Debug, Deserialize)]
 struct OllamaResponse {
     response: String,
     done: bool,
+    #[serde(default)]
+    prompt_eval_count: Option<u32>,  // v3.9.0: Token usage (final chunk only when streaming)
+    #[serde(default)]
+    eval_count: Option<u32>,
+    #[serde(default)]
+    done_reason: Option<String>,  // v3.9.0: "length" when num_predict was hit
+}
+
+/// Record token usage and success on an `llm_call` span (v3.9.0)
+fn record_call_usage(span: &tracing::Span, prompt_tokens: Option<u32>, completion_tokens: Option<u32>) {
+    if let Some(tokens) = prompt_tokens {
+        span.record("prompt_tokens", tokens);
+    }
+    if let Some(tokens) = completion_tokens {
+        span.record("completion_tokens", tokens);
+    }
+    span.record("success", true);
+}
+
+/// Highest-ranked memories that fit the prompt budget next to `used` tokens (v3.9.0)
+fn episodes_within_budget(episodes: &[Episode], used: usize) -> &[Episode] {
+    let budget = model_context::prompt_budget().saturating_sub(used);
+    let mut count = episodes.len();
+    while count > 0 && estimate_tokens(&format_episodes_for_context(&episodes[..count])) > budget {
+        count -= 1;
+    }
+    if count < episodes.len() {
+        log::info!(
+            "Context window: keeping {} of {} memories ({} tokens left)",
+            count, episodes.len(), budget
+        );
+    }
+    &episodes[..count]
 }
 
 /// Generate a response from Ollama (without RAG - fallback mode)
@@ -52,19 +124,55 @@ pub async fn generate_response_with_rag(
     generate_response_with_rag_and_persona_ref(user_message, rag_service, None).await
 }
 
+/// Response text with the memories it was grounded on (v3.9.0)
+#[derive(Debug, Clone)]
+pub struct CitedResponse {
+    pub response: String,
+    pub citations: Vec<Citation>,
+    /// Support of the response by the retrieved memories, None without memories
+    pub grounding: Option<Groundedness>,
+}
+
 /// Generate a response from Ollama with RAG context and persona (v3.8.0: Full personalization)
 pub async fn generate_response_with_rag_and_persona_ref(
     user_message: &str,
     rag_service: Option<Arc<RagServiceV2>>,  // v3.4.0: LanceDB
     db: Option<&std::sync::Mutex<Database>>,
 ) -> Result<String, String> {
-    log::info!("Generating AI response for message: {}", user_message);
+    generate_cited_response_with_rag_and_persona_ref(user_message, rag_service, db)
+        .await
+        .map(|cited| cited.response)
+}
 
-    // 🎯 STEP 1: Load persona from database (v3.8.0 - Critical connection!)
-    let mut system_prompt = if let Some(database) = &db {
+/// Same as `generate_response_with_rag_and_persona_ref`, also returning citations
+/// for the retrieved memories (v3.9.0)
+pub async fn generate_cited_response_with_rag_and_persona_ref(
+    user_message: &str,
+    rag_service: Option<Arc<RagServiceV2>>,  // v3.4.0: LanceDB
+    db: Option<&std::sync::Mutex<Database>>,
+) -> Result<CitedResponse, String> {
+    generate_cited_response_for_conversation(user_message, None, rag_service, db).await
+}
+
+/// Personalized system prompt of a conversation (v3.9.0: shared by the full and fast models)
+///
+/// Loads the persona (or the conversation's A/B variant), applies session mood
+/// and appends the active preset's instructions.
+pub fn persona_system_prompt(conversation_id: Option<&str>, db: Option<&std::sync::Mutex<Database>>) -> String {
+    persona_system_prompt_with(conversation_id, db, None)
+}
+
+/// Same as `persona_system_prompt`, with `persona` replacing the stored persona
+/// and any experiment variant (v3.9.0: steered regeneration)
+pub fn persona_system_prompt_with(
+    conversation_id: Option<&str>,
+    db: Option<&std::sync::Mutex<Database>>,
+    persona: Option<&PersonaParameters>,
+) -> String {
+    if let Some(database) = db {
         match database.lock() {
             Ok(db_guard) => {
-                match db_guard.load_persona() {
+                match persona.map_or_else(|| db_guard.load_persona(), |params| Ok(params.clone())) {
                     Ok(persona_params) => {
                         log::info!("Loaded persona from database: formality={}, verbosity={}, humor={}, emoji_usage={}, empathy={}, creativity={}, proactiveness={}, technical_depth={}, code_examples={}, questioning={}",
                                  persona_params.formality, persona_params.verbosity, persona_params.humor, persona_params.emoji_usage,
@@ -72,8 +180,32 @@ pub async fn generate_response_with_rag_and_persona_ref(
                                  persona_params.technical_depth, persona_params.code_examples, persona_params.questioning);
 
                         // Convert to learning service parameters and generate personalized prompt
-                        let learning_params = persona_params.to_learning_params();
-                        let prompt = LearningService::generate_system_prompt(&learning_params);
+                        // v3.9.0: A running persona experiment overrides the stored persona
+                        let mut learning_params = conversation_id
+                            .filter(|_| persona.is_none())
+                            .and_then(|id| learning::experiment_persona(&db_guard, id))
+                            .unwrap_or_else(|| persona_params.to_learning_params());
+
+                        // v3.9.0: Recent distress raises empathy for this session only
+                        let mood = conversation_id.and_then(|id| sentiment::session_mood(&db_guard, id));
+                        if let Some(mood) = mood.filter(|m| m.empathy_boost > 0) {
+                            learning_params.empathy = (learning_params.empathy + mood.empathy_boost as f32 / 100.0).min(1.0);
+                            log::debug!("Session empathy boost: +{}", mood.empathy_boost);
+                        }
+                        // v3.9.0: Prompt in the conversation's / user's primary language
+                        let language = localization::prompt_language(db_guard.conn(), conversation_id);
+                        let templates = localization::persona_templates(language);
+                        let mut prompt = LearningService::generate_localized_system_prompt(&learning_params, language);
+                        if mood.is_some_and(|m| m.frustrated) {
+                            prompt.push_str(templates.mood_frustrated);
+                        }
+
+                        // v3.9.0: Custom instructions of the active persona preset
+                        if let Some(instructions) = persona_presets::active_instructions(&db_guard) {
+                            prompt.push_str(templates.persona_instructions_heading);
+                            prompt.push_str(&instructions);
+                            prompt.push('\n');
+                        }
                         log::debug!("Generated personalized system prompt ({} chars)", prompt.len());
                         prompt
                     }
@@ -91,51 +223,237 @@ pub async fn generate_response_with_rag_and_persona_ref(
     } else {
         log::debug!("No database provided - Using default prompt");
         get_default_system_prompt()
+    }
+}
+
+/// Settings of a single generation that differ from the defaults (v3.9.0)
+///
+/// Used to steer a regenerated reply; unset fields keep the normal behavior.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GenerationOverrides {
+    /// Sampling temperature (default 0.8), clamped to 0.0-2.0
+    pub temperature: Option<f32>,
+    /// Model instead of the selected chat model
+    pub model: Option<String>,
+    /// false answers without retrieving memories
+    pub retrieval: Option<bool>,
+    /// Persona parameters (0-100) instead of the stored persona
+    pub persona: Option<PersonaParameters>,
+}
+
+impl GenerationOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.temperature.is_none() && self.model.is_none() && self.retrieval.is_none() && self.persona.is_none()
+    }
+
+    fn temperature(&self) -> f32 {
+        self.temperature.map_or(0.8, |t| t.clamp(0.0, 2.0))
+    }
+}
+
+/// Full-model prompt of a chat turn, kept in the parts it is built from (v3.9.0)
+///
+/// Shared by `generate_cited_response_for_conversation` and the context inspector,
+/// so the inspector shows exactly what the model receives.
+pub struct ChatPrompt {
+    pub model: String,
+    /// Persona (or A/B variant), session mood and preset instructions
+    pub persona: String,
+    /// Reply-language instruction for this message
+    pub reply_instruction: String,
+    /// Retrieved candidates with similarity scores
+    pub candidates: Vec<(Episode, f32)>,
+    /// Candidates passing the RAFT filter, best first
+    pub relevant: Vec<Episode>,
+    /// Relevant memories that fit the prompt budget (these are sent)
+    pub memories: Vec<Episode>,
+    /// Memory block with grounding instructions, empty without memories
+    pub memory_section: String,
+    pub memory_query: bool,
+    /// Retrieval ran (a RAG service was given)
+    pub retrieval: bool,
+    /// Retrieval failed; the turn continues without memories
+    pub retrieval_error: Option<String>,
+    raft: RaftService,
+}
+
+impl ChatPrompt {
+    pub fn system_prompt(&self) -> String {
+        format!("{}{}{}", self.persona, self.reply_instruction, self.memory_section)
+    }
+
+    pub fn full_prompt(&self, user_message: &str) -> String {
+        format_turn_prompt(&self.system_prompt(), user_message)
+    }
+
+    /// A memory question nothing relevant is remembered for: answered by
+    /// abstaining, without calling the model
+    pub fn abstains(&self) -> bool {
+        self.retrieval && self.memory_query && self.memories.is_empty()
+    }
+}
+
+/// Single-turn prompt sent to `/api/generate`
+pub fn format_turn_prompt(system_prompt: &str, user_message: &str) -> String {
+    format!("{}\n\nUser: {}\nAssistant:", system_prompt, user_message)
+}
+
+/// Build the full-model prompt for `user_message` without side effects (v3.9.0)
+///
+/// RAFT filtering drops weak matches and memories are trimmed to the active
+/// model's prompt budget.
+pub async fn build_chat_prompt(
+    user_message: &str,
+    conversation_id: Option<&str>,
+    rag_service: Option<&Arc<RagServiceV2>>,
+    db: Option<&std::sync::Mutex<Database>>,
+    overrides: &GenerationOverrides,
+) -> ChatPrompt {
+    // 🎯 STEP 1: Load persona from database (v3.8.0 - Critical connection!)
+    let persona = persona_system_prompt_with(conversation_id, db, overrides.persona.as_ref());
+    let reply_instruction = language_detection::reply_instruction(user_message);
+    let rag_service = rag_service.filter(|_| overrides.retrieval != Some(false));
+
+    // v3.9.0: Budget the prompt against the active model's context window
+    let model = overrides.model.clone().unwrap_or_else(chat_model);
+    model_context::ensure_model_info(&model).await;
+
+    let mut prompt = ChatPrompt {
+        model,
+        persona,
+        reply_instruction,
+        candidates: Vec::new(),
+        relevant: Vec::new(),
+        memories: Vec::new(),
+        memory_section: String::new(),
+        memory_query: raft::is_memory_query(user_message),
+        retrieval: rag_service.is_some(),
+        retrieval_error: None,
+        raft: RaftService::new_quiet(
+            rag_service.and_then(|rag| rag.get_raft_config().ok()).unwrap_or_default(),
+        ),
     };
 
     // 🎯 STEP 2: RAG - Retrieve relevant past conversations
-    if let Some(rag) = &rag_service {
-        let rag_start = std::time::Instant::now();
-        match rag.retrieve_relevant(user_message, RAG_TOP_K).await {
-            Ok(episodes) => {
-                if !episodes.is_empty() {
-                    log::info!("⏱️  [PERF] RAG Retrieval: {:?} ({} memories)", rag_start.elapsed(), episodes.len());
-                    let memory_context = format_episodes_for_context(&episodes);
-                    system_prompt.push_str("\n\n# Relevant Past Conversations\n");
-                    system_prompt.push_str(&memory_context);
-                    system_prompt.push_str("\n💡 Use the above memories to provide more contextual and personalized responses. Reference past conversations when relevant.\n");
-                } else {
-                    log::debug!("No relevant memories found");
-                }
+    let Some(rag) = rag_service else {
+        return prompt;
+    };
+    let rag_start = std::time::Instant::now();
+    // v3.9.0: Conversation / project memories only surface in their own chats
+    let scope = RetrievalScope::for_turn(conversation_id, db);
+    match rag.search_with_scores_in(user_message, RAG_TOP_K, &scope).await {
+        Ok(scored) => {
+            let (relevant, _) = prompt.raft.filter_and_rank(scored.clone(), Vec::new());
+            prompt.candidates = scored;
+            prompt.relevant = relevant.into_iter().map(|raft_ep| raft_ep.episode).collect();
+            let used = estimate_tokens(&format!("{}{}", prompt.persona, prompt.reply_instruction)) + estimate_tokens(user_message);
+            prompt.memories = episodes_within_budget(&prompt.relevant, used).to_vec();
+            if !prompt.memories.is_empty() {
+                log::info!("⏱️  [PERF] RAG Retrieval: {:?} ({} memories)", rag_start.elapsed(), prompt.memories.len());
+                let mut section = String::from("\n\n# Relevant Past Conversations\n");
+                section.push_str(&format_episodes_for_context(&prompt.memories));
+                section.push_str("\n💡 Use the above memories to provide more contextual and personalized responses. Reference past conversations when relevant.\n");
+                section.push_str(&prompt.raft.grounding_instructions(prompt.memory_query, raft::abstain_response(user_message)));
+                prompt.memory_section = section;
+            } else {
+                log::debug!("No relevant memories found");
             }
-            Err(e) => {
-                log::warn!("Failed to retrieve RAG context: {} - Continuing without memory", e);
+        }
+        Err(e) => {
+            log::warn!("Failed to retrieve RAG context: {} - Continuing without memory", e);
+            prompt.retrieval_error = Some(e.to_string());
+        }
+    }
+    prompt
+}
+
+/// Same as `generate_cited_response_with_rag_and_persona_ref`, serving the
+/// conversation's persona A/B variant while an experiment runs (v3.9.0)
+pub async fn generate_cited_response_for_conversation(
+    user_message: &str,
+    conversation_id: Option<&str>,
+    rag_service: Option<Arc<RagServiceV2>>,  // v3.4.0: LanceDB
+    db: Option<&std::sync::Mutex<Database>>,
+) -> Result<CitedResponse, String> {
+    generate_cited_response_with_overrides(user_message, conversation_id, rag_service, db, &GenerationOverrides::default()).await
+}
+
+/// Same as `generate_cited_response_for_conversation` with steered settings (v3.9.0)
+pub async fn generate_cited_response_with_overrides(
+    user_message: &str,
+    conversation_id: Option<&str>,
+    rag_service: Option<Arc<RagServiceV2>>,
+    db: Option<&std::sync::Mutex<Database>>,
+    overrides: &GenerationOverrides,
+) -> Result<CitedResponse, String> {
+    log::info!("Generating AI response for message: {}", user_message);
+
+    let rag_service = rag_service.filter(|_| overrides.retrieval != Some(false));
+    let prompt = build_chat_prompt(user_message, conversation_id, rag_service.as_ref(), db, overrides).await;
+    let model = prompt.model.clone();
+
+    let mut citations = Vec::new();
+    if let Some(rag) = &rag_service {
+        if prompt.retrieval_error.is_none() {
+            super::analytics::record_rag_lookup(!prompt.relevant.is_empty());
+        }
+        if !prompt.memories.is_empty() {
+            let ids: Vec<String> = prompt.memories.iter().map(|episode| episode.id.clone()).collect();
+            if let Err(e) = rag.increment_access_counts(&ids) {
+                log::warn!("Failed to update memory access counts: {}", e);
             }
+
+            // Same numbering as format_episodes_for_context (v3.9.0)
+            citations = prompt
+                .memories
+                .iter()
+                .enumerate()
+                .map(|(i, episode)| Citation::new(i + 1, episode.provenance(), &episode.user_message))
+                .collect();
         }
     }
 
-    let full_prompt = format!("{}\n\nUser: {}\nAssistant:", system_prompt, user_message);
+    // Abstain instead of inventing history when nothing relevant is remembered
+    if prompt.abstains() {
+        log::info!("Memory question without supporting memories - abstaining");
+        return Ok(CitedResponse {
+            response: raft::abstain_response(user_message).to_string(),
+            citations,
+            grounding: Some(Groundedness { score: 1.0, ..Default::default() }),
+        });
+    }
+
+    let full_prompt = prompt.full_prompt(user_message);
+
+    // Wait for the supervisor if Ollama is restarting (v3.9.0)
+    super::ollama_supervisor::wait_for_ollama().await?;
 
     // Create HTTP client
     let client = Client::new();
 
     // Prepare request with overfitting prevention parameters
+    let num_ctx = model_context::context_window_for(&model);
     let request = OllamaRequest {
-        model: MODEL_NAME.to_string(),
+        model,
         prompt: full_prompt,
         stream: false,
         options: OllamaOptions {
-            temperature: 0.8,      // Balanced creativity (prevent deterministic overfitting)
+            temperature: overrides.temperature(),  // Balanced creativity (prevent deterministic overfitting)
             top_p: 0.92,          // Nucleus sampling (diverse token selection)
             top_k: 45,            // Expanded token pool (avoid repetition)
             repeat_penalty: 1.15, // Penalize repetitive phrases (key overfitting prevention)
+            num_ctx: Some(num_ctx),
+            num_predict: None,
         },
+        format: None,
     };
 
     log::debug!("Sending request to Ollama: {:?}", request);
 
     // Send request to Ollama
     let inference_start = std::time::Instant::now();
+    let call_span = super::structured_logging::llm_call_span("ollama", &request.model, "generate");  // v3.9.0
     let response = client
         .post(OLLAMA_API_URL)
         .json(&request)
@@ -164,7 +482,276 @@ pub async fn generate_response_with_rag_and_persona_ref(
     })?;
 
     log::info!("⏱️  [PERF] Ollama LLM Inference: {:?}", inference_start.elapsed());
+    super::analytics::record_llm_call(
+        &request.model,
+        ollama_response.prompt_eval_count.unwrap_or(0),
+        ollama_response.eval_count.unwrap_or(0),
+        inference_start.elapsed().as_millis() as u64,
+    );
+    record_call_usage(&call_span, ollama_response.prompt_eval_count, ollama_response.eval_count);
     log::info!("Successfully generated AI response (done: {})", ollama_response.done);
+
+    // v3.9.0: Score how much of the answer the memories support
+    let mut response = ollama_response.response.trim().to_string();
+    let grounding = (!prompt.memories.is_empty()).then(|| raft::groundedness(&response, &prompt.memories));
+    if let Some(report) = &grounding {
+        log::info!(
+            "Groundedness: {:.2} ({}/{} claims supported)",
+            report.score, report.supported_claims, report.total_claims
+        );
+        if prompt.memory_query && report.score < prompt.raft.get_config().min_groundedness {
+            log::warn!("Unsupported answer to a memory question - abstaining: {:?}", report.unsupported);
+            response = raft::abstain_response(user_message).to_string();
+        }
+    }
+    record_retrieval_usage(&prompt, user_message, &response, conversation_id, db);
+    Ok(CitedResponse {
+        response,
+        citations,
+        grounding,
+    })
+}
+
+/// Log which retrieved memories reached the prompt and were used, for
+/// feedback-driven retrieval tuning (v3.9.0)
+fn record_retrieval_usage(
+    prompt: &ChatPrompt,
+    user_message: &str,
+    response: &str,
+    conversation_id: Option<&str>,
+    db: Option<&std::sync::Mutex<Database>>,
+) {
+    let (Some(conversation_id), Some(db)) = (conversation_id, db) else {
+        return;
+    };
+    if prompt.candidates.is_empty() {
+        return;
+    }
+    let memories: Vec<retrieval_feedback::RetrievedMemory> = prompt
+        .candidates
+        .iter()
+        .enumerate()
+        .map(|(i, (episode, similarity))| {
+            let sent = prompt.memories.iter().any(|memory| memory.id == episode.id);
+            retrieval_feedback::RetrievedMemory {
+                episode_id: episode.id.clone(),
+                rank: i + 1,
+                similarity: *similarity,
+                sent,
+                used: sent && retrieval_feedback::was_used(response, &episode.user_message, &episode.ai_response),
+            }
+        })
+        .collect();
+    let Ok(db) = db.lock() else {
+        return;
+    };
+    if let Err(e) = retrieval_feedback::record_turn(db.conn(), conversation_id, user_message, &memories) {
+        log::warn!("Failed to record retrieval usage: {}", e);
+    }
+}
+
+/// Reply of the fast model when a message needs the full model (v3.9.0)
+pub const ESCALATE_MARKER: &str = "ESCALATE";
+
+/// Response of the small triage model (v3.9.0)
+#[derive(Debug, Clone)]
+pub struct FastResponse {
+    pub response: String,
+    /// Stopped at the token limit
+    pub truncated: bool,
+}
+
+/// Answer a simple message with a small model (v3.9.0)
+///
+/// Uses the conversation's persona without RAG; the model replies with
+/// [`ESCALATE_MARKER`] when the message needs the full model.
+pub async fn generate_fast_response(
+    user_message: &str,
+    model: &str,
+    max_tokens: i32,
+    conversation_id: Option<&str>,
+    db: Option<&std::sync::Mutex<Database>>,
+) -> Result<FastResponse, String> {
+    let mut system_prompt = persona_system_prompt(conversation_id, db);
+    system_prompt.push_str(&language_detection::reply_instruction(user_message));
+    system_prompt.push_str("\n\n# Triage\n");
+    system_prompt.push_str(&format!(
+        "Answer directly only if this is a simple message (greeting, thanks, small talk, a short factual question). \
+         If it needs reasoning, code, tools, current information or you are not sure, reply with exactly: {}\n",
+        ESCALATE_MARKER
+    ));
+    let full_prompt = format!("{}\n\nUser: {}\nAssistant:", system_prompt, user_message);
+
+    // Wait for the supervisor if Ollama is restarting (v3.9.0)
+    super::ollama_supervisor::wait_for_ollama().await?;
+
+    let request = OllamaRequest {
+        model: model.to_string(),
+        prompt: full_prompt,
+        stream: false,
+        options: OllamaOptions {
+            temperature: 0.8,
+            top_p: 0.92,
+            top_k: 45,
+            repeat_penalty: 1.15,
+            num_ctx: Some(model_context::context_window_for(model)),
+            num_predict: Some(max_tokens),
+        },
+        format: None,
+    };
+
+    let inference_start = std::time::Instant::now();
+    let call_span = super::structured_logging::llm_call_span("ollama", &request.model, "generate_fast");
+    let response = Client::new()
+        .post(OLLAMA_API_URL)
+        .json(&request)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to Ollama: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(format!("Ollama API error ({}): {}", status, error_text));
+    }
+
+    let ollama_response: OllamaResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Ollama response: {}", e))?;
+
+    super::analytics::record_llm_call(
+        &request.model,
+        ollama_response.prompt_eval_count.unwrap_or(0),
+        ollama_response.eval_count.unwrap_or(0),
+        inference_start.elapsed().as_millis() as u64,
+    );
+    record_call_usage(&call_span, ollama_response.prompt_eval_count, ollama_response.eval_count);
+    log::info!("⏱️  [PERF] Fast model ({}) inference: {:?}", model, inference_start.elapsed());
+
+    Ok(FastResponse {
+        response: ollama_response.response.trim().to_string(),
+        truncated: ollama_response.done_reason.as_deref() == Some("length"),
+    })
+}
+
+/// Summarize a passage of a fetched page with the chat model (v3.9.0)
+///
+/// No persona: the output is a tool observation, not a reply to the user.
+pub async fn summarize_passage(passage: &str, max_tokens: i32) -> Result<String, String> {
+    let prompt = format!(
+        "Summarize the following part of a web page in a few sentences. \
+         Keep names, numbers, dates, code identifiers and conclusions. \
+         Reply with the summary only.\n\n---\n{}\n---\n\nSummary:",
+        passage
+    );
+    complete_without_persona(prompt, max_tokens, "summarize_passage").await
+}
+
+/// Translate a passage with the chat model (v3.9.0)
+///
+/// `instructions` names the language pair and glossary; no persona, so the
+/// reply is the translation only.
+pub async fn translate_passage(passage: &str, instructions: &str, max_tokens: i32) -> Result<String, String> {
+    let prompt = format!(
+        "{}\nReply with the translation only, keeping Markdown formatting, code, URLs and numbers unchanged.\n\n---\n{}\n---\n\nTranslation:",
+        instructions, passage
+    );
+    complete_without_persona(prompt, max_tokens, "translate_passage").await
+}
+
+/// Title and topic labels for a conversation excerpt (v3.9.0)
+///
+/// `known_topics` is the learned taxonomy the model should prefer. Returns the
+/// model's raw JSON reply (`{"title": ..., "topics": [...]}`).
+pub async fn label_conversation(transcript: &str, known_topics: &[String], max_tokens: i32) -> Result<String, String> {
+    let known = if known_topics.is_empty() {
+        "(none yet)".to_string()
+    } else {
+        known_topics.join(", ")
+    };
+    let prompt = format!(
+        "Give the conversation below a short title (at most 8 words, in the language of the conversation) \
+         and 1-3 broad topic labels (1-3 words each, in English). \
+         Reuse these existing topics whenever one fits: {}\n\
+         Reply with JSON only: {{\"title\": \"...\", \"topics\": [\"...\"]}}\n\n---\n{}\n---\n\nJSON:",
+        known, transcript
+    );
+    complete_without_persona(prompt, max_tokens, "label_conversation").await
+}
+
+/// JSON reply constrained by `schema` via Ollama's `format` parameter (v3.9.0)
+///
+/// `model` defaults to the chat model. Older Ollama versions ignore schemas
+/// they can't compile, so callers still validate the reply (see structured_output).
+pub async fn complete_with_schema(
+    prompt: String,
+    schema: &serde_json::Value,
+    model: Option<&str>,
+    max_tokens: i32,
+) -> Result<String, String> {
+    let model = model.map(str::to_string).unwrap_or_else(chat_model);
+    complete_raw(model, prompt, Some(schema.clone()), 0.1, max_tokens, "structured_output").await
+}
+
+/// Low-temperature completion of a raw prompt (v3.9.0)
+async fn complete_without_persona(prompt: String, max_tokens: i32, operation: &str) -> Result<String, String> {
+    complete_raw(chat_model(), prompt, None, 0.2, max_tokens, operation).await
+}
+
+async fn complete_raw(
+    model: String,
+    prompt: String,
+    format: Option<serde_json::Value>,
+    temperature: f32,
+    max_tokens: i32,
+    operation: &str,
+) -> Result<String, String> {
+    super::ollama_supervisor::wait_for_ollama().await?;
+
+    let request = OllamaRequest {
+        model: model.clone(),
+        prompt,
+        stream: false,
+        options: OllamaOptions {
+            temperature,
+            top_p: 0.9,
+            top_k: 40,
+            repeat_penalty: 1.1,
+            num_ctx: Some(model_context::context_window_for(&model)),
+            num_predict: Some(max_tokens),
+        },
+        format,
+    };
+
+    let inference_start = std::time::Instant::now();
+    let call_span = super::structured_logging::llm_call_span("ollama", &request.model, operation);
+    let response = Client::new()
+        .post(OLLAMA_API_URL)
+        .json(&request)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to Ollama: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(format!("Ollama API error ({}): {}", status, error_text));
+    }
+
+    let ollama_response: OllamaResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Ollama response: {}", e))?;
+
+    super::analytics::record_llm_call(
+        &request.model,
+        ollama_response.prompt_eval_count.unwrap_or(0),
+        ollama_response.eval_count.unwrap_or(0),
+        inference_start.elapsed().as_millis() as u64,
+    );
+    record_call_usage(&call_span, ollama_response.prompt_eval_count, ollama_response.eval_count);
+
     Ok(ollama_response.response.trim().to_string())
 }
 
@@ -194,12 +781,8 @@ where
 {
     log::info!("Generating streaming AI response for message: {}", user_message);
 
-    // Build system prompt (Korean-first AI assistant)
+    // Build system prompt (v3.9.0: reply language from the detected message language)
     let mut system_prompt = "Your name is Adam. You are a friendly and helpful AI assistant living in the Garden of Eden environment.\n\n\
-                         ⚠️ IMPORTANT: If the user asks in Korean, you MUST respond only in Korean!\n\
-                         - If the user message contains Hangul (Korean characters) → Respond 100% in Korean\n\
-                         - Only respond in English when the question is in English\n\
-                         - Never respond in English to Korean questions\n\n\
                          Response format:\n\
                          - Keep responses concise (보통 5줄 이내, 일반적으로 2-3줄)\n\
                          - Only provide detailed explanations when user explicitly asks (\"자세히\", \"more details\", etc.)\n\
@@ -208,14 +791,21 @@ where
                          - Use - or 1. for lists\n\
                          - Wrap code with ```\n\
                          - Use emojis appropriately for a friendly tone".to_string();
+    system_prompt.push_str(&language_detection::reply_instruction(user_message));
+
+    // v3.9.0: Budget the prompt against the active model's context window
+    let model = chat_model();
+    model_context::ensure_model_info(&model).await;
 
     // RAG: Retrieve relevant past conversations
     if let Some(rag) = &rag_service {
         match rag.retrieve_relevant(user_message, RAG_TOP_K).await {
             Ok(episodes) => {
+                super::analytics::record_rag_lookup(!episodes.is_empty());
+                let episodes = episodes_within_budget(&episodes, estimate_tokens(&system_prompt) + estimate_tokens(user_message));
                 if !episodes.is_empty() {
                     log::info!("Retrieved {} relevant memories from RAG for streaming", episodes.len());
-                    let memory_context = format_episodes_for_context(&episodes);
+                    let memory_context = format_episodes_for_context(episodes);
                     system_prompt.push_str(&memory_context);
                     system_prompt.push_str("\n💡 Use the above memories to provide more contextual and personalized responses. Reference past conversations when relevant.\n");
                 } else {
@@ -230,12 +820,15 @@ where
 
     let full_prompt = format!("{}\n\nUser: {}\nAssistant:", system_prompt, user_message);
 
+    // Wait for the supervisor if Ollama is restarting (v3.9.0)
+    super::ollama_supervisor::wait_for_ollama().await?;
+
     // Create HTTP client
     let client = Client::new();
 
     // Prepare streaming request with overfitting prevention parameters
     let request = OllamaRequest {
-        model: MODEL_NAME.to_string(),
+        model,
         prompt: full_prompt,
         stream: true, // Enable streaming
         options: OllamaOptions {
@@ -243,12 +836,16 @@ where
             top_p: 0.92,          // Nucleus sampling (diverse token selection)
             top_k: 45,            // Expanded token pool (avoid repetition)
             repeat_penalty: 1.15, // Penalize repetitive phrases (key overfitting prevention)
+            num_ctx: Some(model_context::context_window()),
+            num_predict: None,
         },
+        format: None,
     };
 
     log::debug!("Sending streaming request to Ollama");
 
     // Send request and get streaming response
+    let call_span = super::structured_logging::llm_call_span("ollama", &request.model, "generate_stream");  // v3.9.0
     let response = client
         .post(OLLAMA_API_URL)
         .json(&request)
@@ -348,6 +945,13 @@ where
                                 }
                                 log::info!("Streaming response complete ({:.2}s)",
                                     stream_start.elapsed().as_secs_f32());
+                                super::analytics::record_llm_call(
+                                    &request.model,
+                                    ollama_chunk.prompt_eval_count.unwrap_or(0),
+                                    ollama_chunk.eval_count.unwrap_or(0),
+                                    stream_start.elapsed().as_millis() as u64,
+                                );
+                                record_call_usage(&call_span, ollama_chunk.prompt_eval_count, ollama_chunk.eval_count);
                                 return Ok(full_response.trim().to_string());
                             }
                         }
@@ -413,16 +1017,19 @@ pub async fn test_connection() -> Result<bool, String> {
 
 /// Store a conversation episode in RAG memory
 /// Should be called after a successful response generation
+/// `conversation_id` / `message_id` are kept as provenance for citations (v3.9.0)
 pub async fn store_conversation_in_rag(
     rag_service: Arc<RagServiceV2>,  // v3.4.0: LanceDB
     user_message: &str,
     ai_response: &str,
     satisfaction: f32,
+    conversation_id: Option<&str>,
+    message_id: Option<&str>,
 ) -> Result<String, String> {
     log::info!("Storing conversation in RAG (satisfaction: {})", satisfaction);
 
     rag_service
-        .store_episode(user_message, ai_response, satisfaction)
+        .store_episode_with_source(user_message, ai_response, satisfaction, conversation_id, message_id)
         .await
         .map_err(|e| {
             log::error!("Failed to store episode in RAG: {}", e);
@@ -486,6 +1093,10 @@ struct OllamaChatResponse {
     message: ChatMessage,
     #[allow(dead_code)]
     done: bool,
+    #[serde(default)]
+    prompt_eval_count: Option<u32>,  // v3.9.0: Token usage
+    #[serde(default)]
+    eval_count: Option<u32>,
 }
 
 /// Convert ToolDefinition to Ollama format
@@ -543,30 +1154,28 @@ pub async fn generate_response_with_tools(
     max_iterations: usize,
     app_handle: Option<tauri::AppHandle>,  // v3.7.0: For emitting tool events
     _message_id: Option<String>,  // v3.7.0: Reserved for future event tracking
+    language: ConversationLanguage,  // v3.9.0: Prompt and tool description language
+    allowed_tools: Option<HashSet<String>>,  // v3.9.0: Conversation tool set (None = every tool)
 ) -> Result<String, String> {
     log::info!("Generating AI response with tool calling for: {}", user_message);
 
-    // Build system prompt
-    let mut system_prompt = "Your name is Adam. You are a friendly and helpful AI assistant living in the Garden of Eden environment.\n\n\
-                         ⚠️ IMPORTANT: If the user asks in Korean, you MUST respond only in Korean!\n\
-                         - If the user message contains Hangul (Korean characters) → Respond 100% in Korean\n\
-                         - Only respond in English when the question is in English\n\
-                         - Never respond in English to Korean questions\n\n\
-                         You have access to various tools to help answer user questions. Use tools when appropriate.\n\n\
-                         Response format:\n\
-                         - Emphasize important parts with **bold**\n\
-                         - Use *italics* for parts that need emphasis\n\
-                         - Use - or 1. for lists\n\
-                         - Wrap code with ```\n\
-                         - Use emojis appropriately for a friendly tone".to_string();
+    // Build system prompt (v3.9.0: in the conversation's language)
+    let mut system_prompt = localization::tools_system_prompt(language).to_string();
+    system_prompt.push_str(&language_detection::reply_instruction(user_message));
+
+    // v3.9.0: Budget the prompt against the active model's context window
+    let model = chat_model();
+    model_context::ensure_model_info(&model).await;
 
     // RAG: Retrieve relevant past conversations
     if let Some(rag) = &rag_service {
         match rag.retrieve_relevant(user_message, RAG_TOP_K).await {
             Ok(episodes) => {
+                super::analytics::record_rag_lookup(!episodes.is_empty());
+                let episodes = episodes_within_budget(&episodes, estimate_tokens(&system_prompt) + estimate_tokens(user_message));
                 if !episodes.is_empty() {
                     log::info!("Retrieved {} relevant memories from RAG", episodes.len());
-                    let memory_context = format_episodes_for_context(&episodes);
+                    let memory_context = format_episodes_for_context(episodes);
                     system_prompt.push_str(&memory_context);
                     system_prompt.push_str("\n💡 Use the above memories to provide more contextual and personalized responses.\n");
                 }
@@ -578,7 +1187,7 @@ pub async fn generate_response_with_tools(
     }
 
     // Get tool definitions
-    let tool_definitions = tool_service.get_tool_definitions();
+    let tool_definitions = localization::localize_tools(tool_service.get_tool_definitions_in(allowed_tools.as_ref()), language);
     let ollama_tools: Vec<OllamaTool> = tool_definitions
         .iter()
         .map(convert_tool_definition)
@@ -600,6 +1209,9 @@ pub async fn generate_response_with_tools(
         },
     ];
 
+    // Wait for the supervisor if Ollama is restarting (v3.9.0)
+    super::ollama_supervisor::wait_for_ollama().await?;
+
     let client = Client::new();
     let mut final_response = String::new();
 
@@ -608,7 +1220,7 @@ pub async fn generate_response_with_tools(
         log::debug!("Tool calling iteration {}/{}", iteration + 1, max_iterations);
 
         let request = OllamaChatRequest {
-            model: MODEL_NAME.to_string(),
+            model: model.clone(),
             messages: messages.clone(),
             stream: false,
             tools: Some(ollama_tools.clone()),
@@ -617,10 +1229,14 @@ pub async fn generate_response_with_tools(
                 top_p: 0.92,
                 top_k: 45,
                 repeat_penalty: 1.15,
+                num_ctx: Some(model_context::context_window()),
+                num_predict: None,
             },
         };
 
         // Send request
+        let iteration_start = std::time::Instant::now();
+        let call_span = super::structured_logging::llm_call_span("ollama", &request.model, "chat_tools");  // v3.9.0
         let response = client
             .post(OLLAMA_CHAT_API_URL)
             .json(&request)
@@ -639,6 +1255,14 @@ pub async fn generate_response_with_tools(
         let chat_response: OllamaChatResponse = response.json().await.map_err(|e| {
             format!("Failed to parse Ollama chat response: {}", e)
         })?;
+        super::analytics::record_llm_call(
+            &request.model,
+            chat_response.prompt_eval_count.unwrap_or(0),
+            chat_response.eval_count.unwrap_or(0),
+            iteration_start.elapsed().as_millis() as u64,
+        );
+        record_call_usage(&call_span, chat_response.prompt_eval_count, chat_response.eval_count);
+        drop(call_span);  // Tool execution below is not part of the model call
 
         // Check if LLM wants to call a tool
         if let Some(tool_calls) = &chat_response.message.tool_calls {
@@ -647,49 +1271,60 @@ pub async fn generate_response_with_tools(
             // Add assistant message with tool calls
             messages.push(chat_response.message.clone());
 
-            // Execute each tool call
-            for tool_call in tool_calls {
-                let function_name = &tool_call.function.name;
-                let arguments = &tool_call.function.arguments;
+            // v3.9.0: Independent calls run in parallel; each gets an id the UI can cancel it by
+            let calls: Vec<(String, ToolCall)> = tool_calls
+                .iter()
+                .map(|tool_call| {
+                    let call = ToolCall {
+                        tool_name: tool_call.function.name.clone(),
+                        arguments: tool_call.function.arguments.clone(),
+                    };
+                    (uuid::Uuid::new_v4().to_string(), call)
+                })
+                .collect();
 
-                log::info!("Executing tool: {} with args: {:?}", function_name, arguments);
+            for (call_id, call) in &calls {
+                log::info!("Executing tool: {} with args: {:?}", call.tool_name, call.arguments);
 
                 // v3.3.0: Emit tool execution start event
                 if let Some(app) = &app_handle {
                     let event_payload = serde_json::json!({
-                        "tool_name": function_name,
-                        "arguments": arguments,
+                        "call_id": call_id,
+                        "tool_name": call.tool_name,
+                        "arguments": call.arguments,
                         "timestamp": chrono::Utc::now().timestamp_millis(),
                     });
                     if let Err(e) = app.emit("ai:tool-execution-start", event_payload) {
                         log::warn!("Failed to emit tool-execution-start event: {}", e);
                     }
                 }
+            }
 
-                let tool_call_request = ToolCall {
-                    tool_name: function_name.clone(),
-                    arguments: arguments.clone(),
-                };
+            let results = tool_service.execute_tools_in(&calls, allowed_tools.as_ref()).await;
 
-                let start_time = std::time::Instant::now();
-                let tool_result = tool_service.execute_tool(&tool_call_request).await;
-                let execution_time_ms = start_time.elapsed().as_millis() as u64;
+            for ((call_id, call), tool_result) in calls.iter().zip(results) {
+                let function_name = &call.tool_name;
+                let execution_time_ms = tool_result.execution_ms;
 
                 // v3.3.0: Emit tool execution complete/error event
                 if let Some(app) = &app_handle {
                     let event_payload = if tool_result.success {
                         serde_json::json!({
+                            "call_id": call_id,
                             "tool_name": function_name,
                             "status": "success",
                             "result": tool_result.result,
+                            "cache": tool_result.cache,
                             "execution_time_ms": execution_time_ms,
                             "timestamp": chrono::Utc::now().timestamp_millis(),
                         })
                     } else {
                         serde_json::json!({
+                            "call_id": call_id,
                             "tool_name": function_name,
                             "status": "error",
                             "error": tool_result.error.clone().unwrap_or_default(),
+                            "interrupted": tool_result.interrupted,
                             "execution_time_ms": execution_time_ms,
                             "timestamp": chrono::Utc::now().timestamp_millis(),
                         })
@@ -709,7 +1344,7 @@ pub async fn generate_response_with_tools(
 
                 // Add tool result as a message
                 let result_content = if tool_result.success {
-                    serde_json::to_string(&tool_result.result).unwrap_or_else(|_| "{}".to_string())
+                    tool_result.observation()
                 } else {
                     format!("{{\"error\": \"{}\"}}", tool_result.error.unwrap_or_default())
                 };
@@ -753,7 +1388,10 @@ mod tests {
                 top_p: 0.92,
                 top_k: 45,
                 repeat_penalty: 1.15,
+                num_ctx: None,
+                num_predict: None,
             },
+            format: None,
         };
 
         assert_eq!(request.model, "qwen2.5:7b");
@@ -769,6 +1407,8 @@ mod tests {
             top_p: 0.92,
             top_k: 45,
             repeat_penalty: 1.15,
+            num_ctx: None,
+            num_predict: None,
         };
 
         // Verify anti-overfitting parameters are set correctly
@@ -867,10 +1507,6 @@ mod tests {
 /// Get default system prompt (fallback when persona loading fails)
 fn get_default_system_prompt() -> String {
     "Your name is Adam. You are a friendly and helpful AI assistant living in the Garden of Eden environment.\n\n\
-     ⚠️ IMPORTANT: If the user asks in Korean, you MUST respond only in Korean!\n\
-     - If the user message contains Hangul (Korean characters) → Respond 100% in Korean\n\
-     - Only respond in English when the question is in English\n\
-     - Never respond in English to Korean questions\n\n\
      Response format:\n\
      - Keep responses concise (보통 5줄 이내, 일반적으로 2-3줄)\n\
      - Only provide detailed explanations when user explicitly asks (\"자세히\", \"more details\", etc.)\n\
@@ -0,0 +1,449 @@
+//! Ollama Process Supervisor (v3.9.0)
+//!
+//! Keeps the local Ollama server alive for the whole session, not just at install time.
+//!
+//! Features:
+//! - Auto-start of `ollama serve` when the server is not reachable at launch
+//! - Periodic health watchdog (GET /api/version)
+//! - Restart with exponential backoff when the server dies mid-session
+//! - Readiness gate so in-flight LLM requests wait during an outage instead of failing
+//! - `ollama-status` events to the frontend on every status transition
+
+#![allow(dead_code)]  // Some accessors are only used by commands
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::process::Command as TokioCommand;
+use tokio::sync::{watch, Mutex as TokioMutex};
+
+const OLLAMA_HEALTH_URL: &str = "http://localhost:11434/api/version";
+const HEALTH_CHECK_TIMEOUT_SECS: u64 = 3;
+
+/// Event name emitted to the frontend on status changes
+pub const OLLAMA_STATUS_EVENT: &str = "ollama-status";
+
+/// Process-wide supervisor used by the free functions in `ollama.rs`
+static GLOBAL_SUPERVISOR: OnceLock<Arc<OllamaSupervisor>> = OnceLock::new();
+
+/// Ollama server status as seen by the supervisor
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum OllamaStatus {
+    /// Supervisor has not completed its first health check
+    Starting,
+    /// Server is responding to health checks
+    Healthy,
+    /// Health checks are failing, restart not yet attempted
+    Unreachable,
+    /// Restart in progress
+    Restarting { attempt: u32 },
+    /// Restart attempts exhausted; user action required
+    Failed,
+}
+
+/// Supervisor configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisorConfig {
+    /// Start `ollama serve` automatically if the server is down
+    pub auto_restart: bool,
+    /// Seconds between health checks
+    pub health_check_interval_secs: u64,
+    /// Consecutive failed checks before the server is considered down
+    pub failure_threshold: u32,
+    /// Maximum restart attempts before giving up (reset on recovery)
+    pub max_restart_attempts: u32,
+    /// First backoff delay in seconds (doubles per attempt)
+    pub initial_backoff_secs: u64,
+    /// Upper bound on backoff delay in seconds
+    pub max_backoff_secs: u64,
+    /// How long a queued request waits for recovery before failing
+    pub request_wait_timeout_secs: u64,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            auto_restart: true,
+            health_check_interval_secs: 10,
+            failure_threshold: 2,
+            max_restart_attempts: 5,
+            initial_backoff_secs: 2,
+            max_backoff_secs: 60,
+            request_wait_timeout_secs: 30,
+        }
+    }
+}
+
+/// Snapshot of supervisor state (returned to the frontend)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisorState {
+    pub status: OllamaStatus,
+    pub consecutive_failures: u32,
+    pub restart_attempts: u32,
+    pub total_restarts: u32,
+    pub last_healthy_at: Option<i64>, // Unix millis
+    pub last_error: Option<String>,
+    pub queued_requests: usize,
+}
+
+impl Default for SupervisorState {
+    fn default() -> Self {
+        Self {
+            status: OllamaStatus::Starting,
+            consecutive_failures: 0,
+            restart_attempts: 0,
+            total_restarts: 0,
+            last_healthy_at: None,
+            last_error: None,
+            queued_requests: 0,
+        }
+    }
+}
+
+/// Ollama process supervisor
+pub struct OllamaSupervisor {
+    state: Arc<Mutex<SupervisorState>>,
+    config: Arc<Mutex<SupervisorConfig>>,
+    ready_tx: watch::Sender<bool>,
+    queued: AtomicUsize,
+    running: AtomicBool,
+    app_handle: Arc<TokioMutex<Option<AppHandle>>>,
+}
+
+impl OllamaSupervisor {
+    /// Create a new supervisor (does not start the watchdog)
+    pub fn new(config: SupervisorConfig) -> Self {
+        let (ready_tx, _) = watch::channel(false);
+        Self {
+            state: Arc::new(Mutex::new(SupervisorState::default())),
+            config: Arc::new(Mutex::new(config)),
+            ready_tx,
+            queued: AtomicUsize::new(0),
+            running: AtomicBool::new(false),
+            app_handle: Arc::new(TokioMutex::new(None)),
+        }
+    }
+
+    /// Register this supervisor as the process-wide instance used by `ollama.rs`
+    pub fn install_global(self: &Arc<Self>) {
+        if GLOBAL_SUPERVISOR.set(Arc::clone(self)).is_err() {
+            warn!("Ollama supervisor already installed, ignoring");
+        }
+    }
+
+    /// Set the Tauri app handle for event emission
+    pub async fn set_app_handle(&self, handle: AppHandle) {
+        *self.app_handle.lock().await = Some(handle);
+    }
+
+    /// Start the health watchdog loop
+    pub fn start(self: &Arc<Self>) {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return; // Already running
+        }
+
+        let supervisor = Arc::clone(self);
+        tauri::async_runtime::spawn(async move {
+            info!("Ollama supervisor started");
+            let client = match reqwest::Client::builder()
+                .timeout(Duration::from_secs(HEALTH_CHECK_TIMEOUT_SECS))
+                .build()
+            {
+                Ok(client) => client,
+                Err(e) => {
+                    warn!("Ollama supervisor could not build HTTP client: {}", e);
+                    supervisor.running.store(false, Ordering::SeqCst);
+                    return;
+                }
+            };
+
+            while supervisor.running.load(Ordering::SeqCst) {
+                supervisor.tick(&client).await;
+                let interval = supervisor.config().health_check_interval_secs.max(1);
+                tokio::time::sleep(Duration::from_secs(interval)).await;
+            }
+
+            info!("Ollama supervisor stopped");
+        });
+    }
+
+    /// Stop the health watchdog loop (takes effect after the current tick)
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    /// Current state snapshot
+    pub fn get_state(&self) -> SupervisorState {
+        let mut state = self.state.lock().map(|s| s.clone()).unwrap_or_default();
+        state.queued_requests = self.queued.load(Ordering::SeqCst);
+        state
+    }
+
+    /// Current configuration
+    pub fn config(&self) -> SupervisorConfig {
+        self.config.lock().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    /// Replace configuration
+    pub fn update_config(&self, config: SupervisorConfig) {
+        if let Ok(mut current) = self.config.lock() {
+            *current = config;
+        }
+    }
+
+    /// Whether the last health check succeeded
+    pub fn is_ready(&self) -> bool {
+        *self.ready_tx.borrow()
+    }
+
+    /// Wait until Ollama is healthy, queueing the caller during an outage
+    pub async fn wait_until_ready(&self) -> Result<(), String> {
+        if self.is_ready() {
+            return Ok(());
+        }
+
+        if matches!(self.get_state().status, OllamaStatus::Failed) {
+            return Err(unavailable_message(&self.get_state()));
+        }
+
+        let timeout = Duration::from_secs(self.config().request_wait_timeout_secs);
+        let mut rx = self.ready_tx.subscribe();
+
+        self.queued.fetch_add(1, Ordering::SeqCst);
+        log::debug!("Ollama not ready, queueing request ({} waiting)", self.queued.load(Ordering::SeqCst));
+        let result = tokio::time::timeout(timeout, rx.wait_for(|ready| *ready)).await;
+        self.queued.fetch_sub(1, Ordering::SeqCst);
+
+        match result {
+            Ok(Ok(_)) => Ok(()),
+            _ => Err(unavailable_message(&self.get_state())),
+        }
+    }
+
+    /// Force an immediate restart (user-initiated)
+    pub async fn restart_now(&self) -> Result<(), String> {
+        self.set_status(OllamaStatus::Restarting { attempt: 1 }).await;
+        spawn_ollama_serve().await?;
+        if let Ok(mut state) = self.state.lock() {
+            state.total_restarts += 1;
+            state.restart_attempts = 0;
+        }
+        Ok(())
+    }
+
+    /// One watchdog iteration: health check, then restart if needed
+    async fn tick(&self, client: &reqwest::Client) {
+        match check_health(client).await {
+            Ok(()) => self.mark_healthy().await,
+            Err(error) => self.handle_failure(error).await,
+        }
+    }
+
+    async fn mark_healthy(&self) {
+        let recovered = {
+            let mut state = match self.state.lock() {
+                Ok(state) => state,
+                Err(_) => return,
+            };
+            let recovered = state.status != OllamaStatus::Healthy;
+            state.consecutive_failures = 0;
+            state.restart_attempts = 0;
+            state.last_error = None;
+            state.last_healthy_at = Some(chrono::Utc::now().timestamp_millis());
+            recovered
+        };
+
+        self.ready_tx.send_replace(true);
+        if recovered {
+            info!("✓ Ollama is healthy");
+            self.set_status(OllamaStatus::Healthy).await;
+        }
+    }
+
+    async fn handle_failure(&self, error: String) {
+        self.ready_tx.send_replace(false);
+        let config = self.config();
+
+        let (failures, attempts, status) = {
+            let mut state = match self.state.lock() {
+                Ok(state) => state,
+                Err(_) => return,
+            };
+            state.consecutive_failures += 1;
+            state.last_error = Some(error.clone());
+            (state.consecutive_failures, state.restart_attempts, state.status.clone())
+        };
+
+        if failures < config.failure_threshold {
+            log::debug!("Ollama health check failed ({}/{}): {}", failures, config.failure_threshold, error);
+            return;
+        }
+
+        if status == OllamaStatus::Failed {
+            return; // Give up until the user restarts manually or the server comes back
+        }
+
+        if !config.auto_restart {
+            if status != OllamaStatus::Unreachable {
+                warn!("Ollama is unreachable: {}", error);
+                self.set_status(OllamaStatus::Unreachable).await;
+            }
+            return;
+        }
+
+        if attempts >= config.max_restart_attempts {
+            warn!("Ollama restart attempts exhausted ({}), giving up", attempts);
+            self.set_status(OllamaStatus::Failed).await;
+            return;
+        }
+
+        let attempt = attempts + 1;
+        let delay = backoff_delay(attempt, config.initial_backoff_secs, config.max_backoff_secs);
+        warn!("Ollama is down ({}), restart attempt {} in {:?}", error, attempt, delay);
+        self.set_status(OllamaStatus::Restarting { attempt }).await;
+        tokio::time::sleep(delay).await;
+
+        if let Ok(mut state) = self.state.lock() {
+            state.restart_attempts = attempt;
+            state.total_restarts += 1;
+        }
+
+        if let Err(e) = spawn_ollama_serve().await {
+            warn!("Failed to restart Ollama: {}", e);
+            if let Ok(mut state) = self.state.lock() {
+                state.last_error = Some(e);
+            }
+        }
+    }
+
+    /// Update status and notify the frontend
+    async fn set_status(&self, status: OllamaStatus) {
+        let snapshot = {
+            let mut state = match self.state.lock() {
+                Ok(state) => state,
+                Err(_) => return,
+            };
+            state.status = status;
+            state.clone()
+        };
+
+        let handle = self.app_handle.lock().await;
+        if let Some(ref h) = *handle {
+            if let Err(e) = h.emit(OLLAMA_STATUS_EVENT, &snapshot) {
+                warn!("Failed to emit Ollama status: {}", e);
+            }
+        }
+    }
+}
+
+/// Wait for the global supervisor (no-op when none is installed)
+///
+/// Called by the LLM entry points in `ollama.rs` before sending a request so that
+/// requests made during an outage wait for recovery instead of failing with
+/// "connection refused".
+pub async fn wait_for_ollama() -> Result<(), String> {
+    match GLOBAL_SUPERVISOR.get() {
+        Some(supervisor) => supervisor.wait_until_ready().await,
+        None => Ok(()),
+    }
+}
+
+/// Exponential backoff: initial * 2^(attempt-1), capped at max
+pub fn backoff_delay(attempt: u32, initial_secs: u64, max_secs: u64) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(16);
+    let secs = initial_secs.saturating_mul(1u64 << exponent).min(max_secs);
+    Duration::from_secs(secs)
+}
+
+/// User-facing message for requests that could not be served
+fn unavailable_message(state: &SupervisorState) -> String {
+    match state.status {
+        OllamaStatus::Restarting { attempt } => format!(
+            "Ollama is restarting (attempt {}). Please try again in a moment.",
+            attempt
+        ),
+        OllamaStatus::Failed => {
+            "Ollama could not be restarted automatically. Please run 'ollama serve' or restart the app.".to_string()
+        }
+        _ => "Ollama is not responding. Make sure Ollama is running.".to_string(),
+    }
+}
+
+async fn check_health(client: &reqwest::Client) -> Result<(), String> {
+    match client.get(OLLAMA_HEALTH_URL).send().await {
+        Ok(response) if response.status().is_success() => Ok(()),
+        Ok(response) => Err(format!("Ollama responded with status {}", response.status())),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Spawn `ollama serve` in the background
+async fn spawn_ollama_serve() -> Result<(), String> {
+    info!("Spawning 'ollama serve'...");
+    TokioCommand::new("ollama")
+        .arg("serve")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to spawn 'ollama serve': {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delay_doubles_and_caps() {
+        assert_eq!(backoff_delay(1, 2, 60), Duration::from_secs(2));
+        assert_eq!(backoff_delay(2, 2, 60), Duration::from_secs(4));
+        assert_eq!(backoff_delay(3, 2, 60), Duration::from_secs(8));
+        assert_eq!(backoff_delay(10, 2, 60), Duration::from_secs(60));
+        assert_eq!(backoff_delay(100, 2, 60), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_initial_state() {
+        let supervisor = OllamaSupervisor::new(SupervisorConfig::default());
+        let state = supervisor.get_state();
+        assert_eq!(state.status, OllamaStatus::Starting);
+        assert_eq!(state.queued_requests, 0);
+        assert!(!supervisor.is_ready());
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_ready_times_out() {
+        let config = SupervisorConfig {
+            request_wait_timeout_secs: 0,
+            ..Default::default()
+        };
+        let supervisor = OllamaSupervisor::new(config);
+        assert!(supervisor.wait_until_ready().await.is_err());
+        assert_eq!(supervisor.get_state().queued_requests, 0);
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_ready_resumes_on_recovery() {
+        let supervisor = Arc::new(OllamaSupervisor::new(SupervisorConfig::default()));
+        let waiter = Arc::clone(&supervisor);
+        let handle = tokio::spawn(async move { waiter.wait_until_ready().await });
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        supervisor.mark_healthy().await;
+
+        assert!(handle.await.unwrap().is_ok());
+        assert_eq!(supervisor.get_state().status, OllamaStatus::Healthy);
+    }
+
+    #[test]
+    fn test_status_serialization() {
+        let json = serde_json::to_string(&OllamaStatus::Restarting { attempt: 2 }).unwrap();
+        assert!(json.contains("\"state\":\"restarting\""));
+        assert!(json.contains("\"attempt\":2"));
+    }
+}
@@ -0,0 +1,808 @@
+//! OpenAPI Tools (v3.9.0)
+//!
+//! Turns the operations of an OpenAPI / Swagger document into tools the agent
+//! can call, so users can wire their own HTTP APIs in without writing Rust.
+//!
+//! Features:
+//! - JSON documents, OpenAPI 3.x and Swagger 2.0 (YAML has to be converted first)
+//! - One tool per operation, taking its path, query and header parameters and
+//!   the top-level properties of a JSON request body
+//! - Auth from the document's security scheme (bearer, basic, or an API key
+//!   in a header or the query), with the credential kept in the secrets vault
+//! - Imported APIs are saved and registered again at startup; importing an
+//!   API again replaces its tools
+//! - GET and HEAD operations may run in parallel with other calls
+//!
+//! Requests go through the network policy like the other web tools.
+
+#![allow(dead_code)]  // Phase 5: User-defined API tools
+
+use crate::database::Database;
+use crate::services::network_policy;
+use crate::services::secrets::{self, SecretsService};
+use crate::services::tool_calling::{
+    ParameterType, ToolCategory, ToolDefinition, ToolExecutor, ToolParameter, ToolService,
+};
+use anyhow::{anyhow, Result};
+use reqwest::header::USER_AGENT;
+use reqwest::{Client, Method, Url};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Operations imported per API; more would crowd the model's tool list
+const MAX_OPERATIONS: usize = 64;
+
+/// Length limits of generated names and descriptions
+const MAX_API_ID_CHARS: usize = 24;
+const MAX_TOOL_NAME_CHARS: usize = 64;
+const MAX_DESCRIPTION_CHARS: usize = 300;
+
+/// Response text kept for the model
+const MAX_RESPONSE_CHARS: usize = 20_000;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// `$ref` hops followed before giving up (guards against cycles)
+const MAX_REF_DEPTH: usize = 8;
+
+const METHODS: &[&str] = &["get", "post", "put", "patch", "delete", "head"];
+
+/// Where an operation parameter goes in the request
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamLocation {
+    Path,
+    Query,
+    Header,
+    /// Property of the JSON request body
+    Body,
+    /// The whole request body (bodies that aren't objects)
+    RawBody,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiParam {
+    pub name: String,
+    pub location: ParamLocation,
+    pub param_type: ParameterType,
+    pub required: bool,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enum_values: Option<Vec<String>>,
+}
+
+/// One operation, registered as one tool
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiOperation {
+    pub tool_name: String,
+    /// Uppercase HTTP method
+    pub method: String,
+    /// Path template relative to the base URL ("/pets/{petId}")
+    pub path: String,
+    pub description: String,
+    pub params: Vec<ApiParam>,
+}
+
+/// How requests authenticate; the credential itself lives in the vault
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApiAuth {
+    None,
+    /// `Authorization: Bearer <credential>`
+    Bearer,
+    /// `Authorization: Basic`, credential given as "user:password"
+    Basic,
+    /// API key in a header
+    Header { name: String },
+    /// API key as a query parameter
+    Query { name: String },
+}
+
+/// An imported API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiSpec {
+    pub id: String,
+    pub name: String,
+    pub base_url: String,
+    pub auth: ApiAuth,
+    pub operations: Vec<ApiOperation>,
+    pub imported_at: i64,
+}
+
+/// A request built from a tool call, before auth is applied
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedRequest {
+    pub method: String,
+    pub url: Url,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Value>,
+}
+
+/// Parse a JSON OpenAPI 3.x / Swagger 2.0 document
+///
+/// `base_url` overrides the document's servers (needed when they are relative).
+pub fn parse_spec(name: &str, document: &str, base_url: Option<&str>) -> Result<ApiSpec> {
+    let doc: Value = serde_json::from_str(document).map_err(|e| {
+        anyhow!("Not a JSON OpenAPI document ({}); YAML documents have to be converted to JSON first", e)
+    })?;
+    let swagger = match (doc["openapi"].as_str(), doc["swagger"].as_str()) {
+        (Some(version), _) if version.starts_with('3') => false,
+        (_, Some("2.0")) => true,
+        _ => return Err(anyhow!("Unsupported document: expected OpenAPI 3.x or Swagger 2.0")),
+    };
+
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    let name = if name.is_empty() {
+        doc["info"]["title"].as_str().unwrap_or_default().trim().to_string()
+    } else {
+        name
+    };
+    let id: String = slug(&name).chars().take(MAX_API_ID_CHARS).collect::<String>().trim_matches('_').to_string();
+    if id.is_empty() {
+        return Err(anyhow!("API name is empty"));
+    }
+
+    let base_url = match base_url.map(str::trim).filter(|url| !url.is_empty()) {
+        Some(url) => url.to_string(),
+        None if swagger => swagger_base_url(&doc)?,
+        None => openapi_base_url(&doc)?,
+    };
+    let parsed = Url::parse(&base_url).map_err(|e| anyhow!("Invalid base URL {}: {}", base_url, e))?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return Err(anyhow!("Only HTTP/HTTPS APIs are supported"));
+    }
+
+    let operations = parse_operations(&doc, &id, &name, swagger)?;
+    if operations.is_empty() {
+        return Err(anyhow!("The document defines no operations"));
+    }
+
+    Ok(ApiSpec {
+        id,
+        name,
+        base_url: base_url.trim_end_matches('/').to_string(),
+        auth: parse_auth(&doc, swagger),
+        operations,
+        imported_at: chrono::Utc::now().timestamp_millis(),
+    })
+}
+
+fn openapi_base_url(doc: &Value) -> Result<String> {
+    let server = &doc["servers"][0];
+    let mut url = server["url"]
+        .as_str()
+        .ok_or_else(|| anyhow!("The document lists no servers; give a base URL"))?
+        .to_string();
+    if let Some(variables) = server["variables"].as_object() {
+        for (variable, spec) in variables {
+            if let Some(default) = spec["default"].as_str() {
+                url = url.replace(&format!("{{{}}}", variable), default);
+            }
+        }
+    }
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(anyhow!("The document's server URL {} is relative; give a base URL", url));
+    }
+    Ok(url)
+}
+
+fn swagger_base_url(doc: &Value) -> Result<String> {
+    let host = doc["host"]
+        .as_str()
+        .ok_or_else(|| anyhow!("The document has no host; give a base URL"))?;
+    let schemes: Vec<&str> = doc["schemes"]
+        .as_array()
+        .map(|schemes| schemes.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let scheme = if schemes.is_empty() || schemes.contains(&"https") { "https" } else { schemes[0] };
+    Ok(format!("{}://{}{}", scheme, host, doc["basePath"].as_str().unwrap_or_default()))
+}
+
+fn parse_operations(doc: &Value, api_id: &str, api_name: &str, swagger: bool) -> Result<Vec<ApiOperation>> {
+    let paths = doc["paths"].as_object().ok_or_else(|| anyhow!("The document has no paths"))?;
+    let mut operations = Vec::new();
+    let mut tool_names: HashSet<String> = HashSet::new();
+
+    for (path, item) in paths {
+        let item = resolve(doc, item);
+        let shared_params = item["parameters"].as_array().cloned().unwrap_or_default();
+        for method in METHODS {
+            let Some(operation) = item.get(*method).filter(|op| op.is_object()) else {
+                continue;
+            };
+            if operations.len() == MAX_OPERATIONS {
+                log::warn!("{} defines more than {} operations; the rest are skipped", api_name, MAX_OPERATIONS);
+                return Ok(operations);
+            }
+
+            // Operation parameters override path-level ones with the same name and location
+            let mut raw_params: Vec<&Value> = Vec::new();
+            let op_params = operation["parameters"].as_array().cloned().unwrap_or_default();
+            for param in op_params.iter().chain(shared_params.iter()).map(|param| resolve(doc, param)) {
+                let duplicate = raw_params.iter().any(|seen| seen["name"] == param["name"] && seen["in"] == param["in"]);
+                if !duplicate {
+                    raw_params.push(param);
+                }
+            }
+
+            let mut params: Vec<ApiParam> = Vec::new();
+            for param in raw_params {
+                let location = match param["in"].as_str() {
+                    Some("path") => ParamLocation::Path,
+                    Some("query") => ParamLocation::Query,
+                    Some("header") => ParamLocation::Header,
+                    Some("body") if swagger => {
+                        let required = param["required"].as_bool().unwrap_or(false);
+                        body_params(doc, &param["schema"], required, &mut params);
+                        continue;
+                    }
+                    _ => continue,  // cookie and form parameters aren't supported
+                };
+                let Some(name) = param["name"].as_str() else { continue };
+                // Swagger 2 puts the type on the parameter, OpenAPI 3 in its schema
+                let schema = if swagger { param } else { resolve(doc, &param["schema"]) };
+                params.push(ApiParam {
+                    name: name.to_string(),
+                    location,
+                    param_type: parameter_type(schema),
+                    required: location == ParamLocation::Path || param["required"].as_bool().unwrap_or(false),
+                    description: text(param["description"].as_str().unwrap_or_default()),
+                    enum_values: enum_values(schema),
+                });
+            }
+            if !swagger {
+                let body = resolve(doc, &operation["requestBody"]);
+                let schema = &body["content"]["application/json"]["schema"];
+                if !schema.is_null() {
+                    body_params(doc, schema, body["required"].as_bool().unwrap_or(false), &mut params);
+                }
+            }
+
+            let base_name = match operation["operationId"].as_str() {
+                Some(operation_id) => snake_case(operation_id),
+                None => slug(&format!("{} {}", method, path)),
+            };
+            let tool_name = unique_tool_name(&format!("api_{}_{}", api_id, base_name), &mut tool_names);
+
+            let summary = operation["summary"]
+                .as_str()
+                .or_else(|| operation["description"].as_str())
+                .map(text)
+                .filter(|summary| !summary.is_empty())
+                .unwrap_or_else(|| format!("{} {}", method.to_uppercase(), path));
+            let description: String = format!("{} API: {}", api_name, summary).chars().take(MAX_DESCRIPTION_CHARS).collect();
+
+            operations.push(ApiOperation {
+                tool_name,
+                method: method.to_uppercase(),
+                path: path.clone(),
+                description,
+                params,
+            });
+        }
+    }
+    Ok(operations)
+}
+
+/// Request body parameters: one per property of an object body, else one raw body
+fn body_params(doc: &Value, schema: &Value, required: bool, params: &mut Vec<ApiParam>) {
+    let schema = resolve(doc, schema);
+    match schema["properties"].as_object() {
+        Some(properties) => {
+            let required_props: Vec<&str> = schema["required"]
+                .as_array()
+                .map(|names| names.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            for (name, property) in properties {
+                if params.iter().any(|param| &param.name == name) {
+                    log::debug!("Body property {} shadows a parameter, skipping", name);
+                    continue;
+                }
+                let property = resolve(doc, property);
+                params.push(ApiParam {
+                    name: name.clone(),
+                    location: ParamLocation::Body,
+                    param_type: parameter_type(property),
+                    required: required && required_props.contains(&name.as_str()),
+                    description: text(property["description"].as_str().unwrap_or_default()),
+                    enum_values: enum_values(property),
+                });
+            }
+        }
+        None => params.push(ApiParam {
+            name: "body".to_string(),
+            location: ParamLocation::RawBody,
+            param_type: parameter_type(schema),
+            required,
+            description: "Request body".to_string(),
+            enum_values: None,
+        }),
+    }
+}
+
+/// Auth of the document's first required (or else first declared) security scheme
+fn parse_auth(doc: &Value, swagger: bool) -> ApiAuth {
+    let schemes = if swagger { &doc["securityDefinitions"] } else { &doc["components"]["securitySchemes"] };
+    let Some(schemes) = schemes.as_object() else {
+        return ApiAuth::None;
+    };
+    let required = doc["security"][0].as_object().and_then(|requirement| requirement.keys().next());
+    let scheme = match required.and_then(|name| schemes.get(name)).or_else(|| schemes.values().next()) {
+        Some(scheme) => resolve(doc, scheme),
+        None => return ApiAuth::None,
+    };
+
+    let key_name = || scheme["name"].as_str().unwrap_or_default().to_string();
+    match (scheme["type"].as_str(), scheme["scheme"].as_str().map(str::to_lowercase).as_deref()) {
+        (Some("http"), Some("basic")) | (Some("basic"), _) => ApiAuth::Basic,
+        (Some("http"), _) | (Some("oauth2"), _) | (Some("openIdConnect"), _) => ApiAuth::Bearer,
+        (Some("apiKey"), _) => match scheme["in"].as_str() {
+            Some("header") => ApiAuth::Header { name: key_name() },
+            Some("query") => ApiAuth::Query { name: key_name() },
+            _ => ApiAuth::None,
+        },
+        _ => ApiAuth::None,
+    }
+}
+
+/// Follow local `$ref`s ("#/components/schemas/Pet")
+fn resolve<'a>(doc: &'a Value, value: &'a Value) -> &'a Value {
+    let mut current = value;
+    for _ in 0..MAX_REF_DEPTH {
+        match current["$ref"].as_str().and_then(|reference| reference.strip_prefix('#')) {
+            Some(pointer) => match doc.pointer(pointer) {
+                Some(target) => current = target,
+                None => break,
+            },
+            None => break,
+        }
+    }
+    current
+}
+
+fn parameter_type(schema: &Value) -> ParameterType {
+    match schema["type"].as_str() {
+        Some("integer") | Some("number") => ParameterType::Number,
+        Some("boolean") => ParameterType::Boolean,
+        Some("array") => ParameterType::Array,
+        Some("object") => ParameterType::Object,
+        _ if schema["properties"].is_object() => ParameterType::Object,
+        _ => ParameterType::String,
+    }
+}
+
+fn enum_values(schema: &Value) -> Option<Vec<String>> {
+    let values: Vec<String> = schema["enum"]
+        .as_array()?
+        .iter()
+        .map(|value| value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string()))
+        .collect();
+    (!values.is_empty()).then_some(values)
+}
+
+/// Single-line, trimmed description text
+fn text(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Lowercase ASCII, everything else collapsed to '_'
+fn slug(value: &str) -> String {
+    let mut slug = String::new();
+    for c in value.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    slug.trim_matches('_').to_string()
+}
+
+/// "listPets" / "list-pets" → "list_pets"
+fn snake_case(value: &str) -> String {
+    let mut spaced = String::new();
+    let mut previous_lower = false;
+    for c in value.chars() {
+        if c.is_ascii_uppercase() && previous_lower {
+            spaced.push('_');
+        }
+        previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        spaced.push(c);
+    }
+    slug(&spaced)
+}
+
+fn unique_tool_name(name: &str, taken: &mut HashSet<String>) -> String {
+    let base: String = name.chars().take(MAX_TOOL_NAME_CHARS - 3).collect::<String>().trim_end_matches('_').to_string();
+    let mut candidate = base.clone();
+    let mut n = 2;
+    while taken.contains(&candidate) {
+        candidate = format!("{}_{}", base, n);
+        n += 1;
+    }
+    taken.insert(candidate.clone());
+    candidate
+}
+
+/// Build the request for a call (auth is added by `ApiTool`)
+pub fn build_request(base_url: &str, operation: &ApiOperation, arguments: &Value) -> Result<PreparedRequest> {
+    let mut path = operation.path.clone();
+    let mut query: Vec<(String, String)> = Vec::new();
+    let mut headers: Vec<(String, String)> = Vec::new();
+    let mut body_fields = Map::new();
+    let mut raw_body = None;
+
+    for param in &operation.params {
+        let value = match arguments.get(&param.name).filter(|value| !value.is_null()) {
+            Some(value) => value,
+            None if param.required => return Err(anyhow!("Missing required parameter: {}", param.name)),
+            None => continue,
+        };
+        match param.location {
+            ParamLocation::Path => {
+                path = path.replace(
+                    &format!("{{{}}}", param.name),
+                    &urlencoding::encode(&plain_value(value)),
+                );
+            }
+            ParamLocation::Query => match value.as_array() {
+                Some(items) => query.extend(items.iter().map(|item| (param.name.clone(), plain_value(item)))),
+                None => query.push((param.name.clone(), plain_value(value))),
+            },
+            ParamLocation::Header => headers.push((param.name.clone(), plain_value(value))),
+            ParamLocation::Body => {
+                body_fields.insert(param.name.clone(), value.clone());
+            }
+            ParamLocation::RawBody => raw_body = Some(value.clone()),
+        }
+    }
+
+    let mut url = Url::parse(&format!("{}{}", base_url.trim_end_matches('/'), path))
+        .map_err(|e| anyhow!("Invalid request URL: {}", e))?;
+    if !query.is_empty() {
+        url.query_pairs_mut().extend_pairs(query);
+    }
+    let body = raw_body.or_else(|| (!body_fields.is_empty()).then_some(Value::Object(body_fields)));
+
+    Ok(PreparedRequest {
+        method: operation.method.clone(),
+        url,
+        headers,
+        body,
+    })
+}
+
+/// Strings as-is, everything else as JSON text
+fn plain_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Tool calling one operation of an imported API
+pub struct ApiTool {
+    api_id: String,
+    api_name: String,
+    base_url: String,
+    auth: ApiAuth,
+    operation: ApiOperation,
+    secrets: Arc<SecretsService>,
+    client: Client,
+}
+
+#[async_trait::async_trait]
+impl ToolExecutor for ApiTool {
+    async fn execute(&self, arguments: Value) -> Result<Value> {
+        let mut prepared = build_request(&self.base_url, &self.operation, &arguments)?;
+
+        if self.auth != ApiAuth::None {
+            let credential = self
+                .secrets
+                .get(&secrets::api_credential_key(&self.api_id))?
+                .ok_or_else(|| anyhow!("No credential stored for the {} API", self.api_name))?;
+            match &self.auth {
+                ApiAuth::None => {}
+                ApiAuth::Bearer => prepared.headers.push(("Authorization".to_string(), format!("Bearer {}", credential))),
+                ApiAuth::Basic => {
+                    use base64::Engine;
+                    let encoded = base64::engine::general_purpose::STANDARD.encode(credential);
+                    prepared.headers.push(("Authorization".to_string(), format!("Basic {}", encoded)));
+                }
+                ApiAuth::Header { name } => prepared.headers.push((name.clone(), credential)),
+                ApiAuth::Query { name } => {
+                    prepared.url.query_pairs_mut().append_pair(name, &credential);
+                }
+            }
+        }
+
+        let policy = network_policy::global();
+        let _permit = policy.acquire(&prepared.url).await?;
+        let method = Method::from_bytes(prepared.method.as_bytes()).map_err(|e| anyhow!("Invalid method: {}", e))?;
+        let mut request = self
+            .client
+            .request(method, prepared.url.clone())
+            .header(USER_AGENT, policy.user_agent());
+        for (name, value) in &prepared.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        if let Some(body) = &prepared.body {
+            request = request.json(body);
+        }
+
+        log::info!("Calling {} {} ({})", prepared.method, self.operation.path, self.operation.tool_name);
+        let response = request.send().await?;
+        let status = response.status();
+        let text: String = response.text().await?.chars().take(MAX_RESPONSE_CHARS).collect();
+        if !status.is_success() {
+            return Err(anyhow!(
+                "{} {} returned HTTP {}: {}",
+                prepared.method,
+                self.operation.path,
+                status,
+                text.chars().take(500).collect::<String>()
+            ));
+        }
+
+        let body = serde_json::from_str::<Value>(&text).unwrap_or(Value::String(text));
+        Ok(serde_json::json!({
+            "status": status.as_u16(),
+            "body": body,
+        }))
+    }
+
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: self.operation.tool_name.clone(),
+            description: self.operation.description.clone(),
+            parameters: self
+                .operation
+                .params
+                .iter()
+                .map(|param| ToolParameter {
+                    name: param.name.clone(),
+                    description: param.description.clone(),
+                    param_type: param.param_type.clone(),
+                    required: param.required,
+                    enum_values: param.enum_values.clone(),
+                })
+                .collect(),
+            category: ToolCategory::Api,
+        }
+    }
+
+    fn parallel_safe(&self) -> bool {
+        matches!(self.operation.method.as_str(), "GET" | "HEAD")
+    }
+}
+
+/// Imported APIs and their registration in the ToolService
+pub struct OpenApiToolService {
+    db: Arc<Mutex<Database>>,
+    secrets: Arc<SecretsService>,
+    tool_service: Arc<ToolService>,
+    client: Client,
+}
+
+impl OpenApiToolService {
+    pub fn new(db: Arc<Mutex<Database>>, secrets: Arc<SecretsService>, tool_service: Arc<ToolService>) -> Result<Self> {
+        init_tables(&*db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?)?;
+        let client = Client::builder().timeout(REQUEST_TIMEOUT).build()?;
+        log::info!("✓ OpenAPI Tool Service initialized");
+        Ok(Self { db, secrets, tool_service, client })
+    }
+
+    /// Register the tools of every saved API, returning how many were registered
+    pub fn register_saved(&self) -> Result<usize> {
+        let mut registered = 0;
+        for spec in self.list()? {
+            match self.register(&spec) {
+                Ok(count) => registered += count,
+                Err(e) => log::warn!("Failed to register the {} API: {}", spec.name, e),
+            }
+        }
+        Ok(registered)
+    }
+
+    /// Import (or re-import) an API from its JSON document
+    ///
+    /// `credential` is stored in the vault; without one a previously stored
+    /// credential is kept.
+    pub fn import(
+        &self,
+        name: &str,
+        document: &str,
+        base_url: Option<&str>,
+        credential: Option<&str>,
+    ) -> Result<ApiSpec> {
+        let spec = parse_spec(name, document, base_url)?;
+        let previous = self.get(&spec.id)?;
+
+        if let Some(previous) = &previous {
+            self.unregister(previous);
+        }
+        if let Err(e) = self.register(&spec) {
+            if let Some(previous) = &previous {
+                self.register(previous).ok();
+            }
+            return Err(e);
+        }
+        if let Some(credential) = credential.map(str::trim).filter(|credential| !credential.is_empty()) {
+            self.secrets.set(&secrets::api_credential_key(&spec.id), credential)?;
+        }
+
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        save_spec(db.conn(), &spec)?;
+        log::info!("Imported the {} API ({} operations)", spec.name, spec.operations.len());
+        Ok(spec)
+    }
+
+    pub fn list(&self) -> Result<Vec<ApiSpec>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut stmt = db.conn().prepare("SELECT spec FROM api_tool_specs ORDER BY name COLLATE NOCASE")?;
+        let specs = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .filter_map(|r| r.ok())
+            .filter_map(|json| serde_json::from_str(&json).ok())
+            .collect();
+        Ok(specs)
+    }
+
+    pub fn get(&self, api_id: &str) -> Result<Option<ApiSpec>> {
+        Ok(self.list()?.into_iter().find(|spec| spec.id == api_id))
+    }
+
+    /// Remove an API, its tools and its stored credential
+    pub fn remove(&self, api_id: &str) -> Result<()> {
+        let spec = self.get(api_id)?.ok_or_else(|| anyhow!("API not found: {}", api_id))?;
+        self.unregister(&spec);
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            db.conn().execute("DELETE FROM api_tool_specs WHERE id = ?1", [api_id])?;
+        }
+        self.secrets.delete(&secrets::api_credential_key(api_id))?;
+        log::info!("Removed the {} API", spec.name);
+        Ok(())
+    }
+
+    /// Store, or with None delete, an API's credential
+    pub fn set_credential(&self, api_id: &str, credential: Option<&str>) -> Result<()> {
+        if self.get(api_id)?.is_none() {
+            return Err(anyhow!("API not found: {}", api_id));
+        }
+        let key = secrets::api_credential_key(api_id);
+        match credential.map(str::trim).filter(|credential| !credential.is_empty()) {
+            Some(credential) => self.secrets.set(&key, credential),
+            None => self.secrets.delete(&key),
+        }
+    }
+
+    /// Register every operation, rolling back if a name is already taken
+    fn register(&self, spec: &ApiSpec) -> Result<usize> {
+        for (i, operation) in spec.operations.iter().enumerate() {
+            let tool = ApiTool {
+                api_id: spec.id.clone(),
+                api_name: spec.name.clone(),
+                base_url: spec.base_url.clone(),
+                auth: spec.auth.clone(),
+                operation: operation.clone(),
+                secrets: Arc::clone(&self.secrets),
+                client: self.client.clone(),
+            };
+            if let Err(e) = self.tool_service.add_tool(Arc::new(tool)) {
+                for registered in &spec.operations[..i] {
+                    self.tool_service.remove_tool(&registered.tool_name);
+                }
+                return Err(e);
+            }
+        }
+        Ok(spec.operations.len())
+    }
+
+    fn unregister(&self, spec: &ApiSpec) {
+        for operation in &spec.operations {
+            self.tool_service.remove_tool(&operation.tool_name);
+        }
+    }
+}
+
+fn init_tables(db: &Database) -> Result<()> {
+    db.conn().execute(
+        "CREATE TABLE IF NOT EXISTS api_tool_specs (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            spec TEXT NOT NULL,
+            imported_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn save_spec(conn: &Connection, spec: &ApiSpec) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO api_tool_specs (id, name, spec, imported_at) VALUES (?1, ?2, ?3, ?4)",
+        params![spec.id, spec.name, serde_json::to_string(spec)?, spec.imported_at],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PETSTORE: &str = r##"{
+        "openapi": "3.0.0",
+        "info": {"title": "Petstore"},
+        "servers": [{"url": "https://{region}.pets.example.com/v1", "variables": {"region": {"default": "eu"}}}],
+        "components": {
+            "securitySchemes": {"key": {"type": "apiKey", "in": "header", "name": "X-Api-Key"}},
+            "parameters": {"limit": {"name": "limit", "in": "query", "schema": {"type": "integer"}}},
+            "schemas": {"NewPet": {"type": "object", "required": ["name"],
+                "properties": {"name": {"type": "string"}, "kind": {"type": "string", "enum": ["cat", "dog"]}}}}
+        },
+        "paths": {
+            "/pets": {
+                "get": {"operationId": "listPets", "summary": "List  all pets", "parameters": [{"$ref": "#/components/parameters/limit"}]},
+                "post": {"operationId": "createPet", "requestBody": {"required": true,
+                    "content": {"application/json": {"schema": {"$ref": "#/components/schemas/NewPet"}}}}}
+            },
+            "/pets/{petId}": {
+                "parameters": [{"name": "petId", "in": "path", "schema": {"type": "string"}}],
+                "delete": {}
+            }
+        }
+    }"##;
+
+    #[test]
+    fn test_parse_openapi_and_swagger() {
+        let spec = parse_spec("", PETSTORE, None).unwrap();
+        assert_eq!(spec.id, "petstore");
+        assert_eq!(spec.base_url, "https://eu.pets.example.com/v1");
+        assert_eq!(spec.auth, ApiAuth::Header { name: "X-Api-Key".into() });
+
+        let names: Vec<&str> = spec.operations.iter().map(|op| op.tool_name.as_str()).collect();
+        assert_eq!(names, vec!["api_petstore_list_pets", "api_petstore_create_pet", "api_petstore_delete_pets_petid"]);
+        assert_eq!(spec.operations[0].description, "Petstore API: List all pets");
+        assert_eq!(spec.operations[0].params[0].param_type, ParameterType::Number);
+
+        let create = &spec.operations[1].params;
+        assert_eq!(create.len(), 2);
+        assert!(create.iter().any(|p| p.name == "name" && p.required && p.location == ParamLocation::Body));
+        assert_eq!(create.iter().find(|p| p.name == "kind").unwrap().enum_values, Some(vec!["cat".into(), "dog".into()]));
+        assert!(spec.operations[2].params[0].required);
+
+        let swagger = r#"{"swagger": "2.0", "host": "api.example.com", "basePath": "/v2", "schemes": ["http"],
+            "securityDefinitions": {"basic": {"type": "basic"}},
+            "paths": {"/notes": {"post": {"parameters": [{"in": "body", "name": "note", "required": true,
+                "schema": {"type": "array"}}]}}}}"#;
+        let spec = parse_spec("My Notes", swagger, None).unwrap();
+        assert_eq!((spec.id.as_str(), spec.base_url.as_str()), ("my_notes", "http://api.example.com/v2"));
+        assert_eq!(spec.auth, ApiAuth::Basic);
+        assert_eq!(spec.operations[0].params[0].location, ParamLocation::RawBody);
+
+        assert!(parse_spec("x", "openapi: 3.0.0", None).is_err());
+        assert!(parse_spec("x", r#"{"openapi": "3.0.0", "servers": [{"url": "/v1"}], "paths": {}}"#, None).is_err());
+    }
+
+    #[test]
+    fn test_build_request() {
+        let spec = parse_spec("Petstore", PETSTORE, Some("http://localhost:8080/")).unwrap();
+
+        let list = build_request(&spec.base_url, &spec.operations[0], &serde_json::json!({ "limit": 5 })).unwrap();
+        assert_eq!(list.url.as_str(), "http://localhost:8080/pets?limit=5");
+        assert!(list.body.is_none());
+
+        let create = build_request(&spec.base_url, &spec.operations[1], &serde_json::json!({ "name": "Rex" })).unwrap();
+        assert_eq!(create.method, "POST");
+        assert_eq!(create.body, Some(serde_json::json!({ "name": "Rex" })));
+        assert!(build_request(&spec.base_url, &spec.operations[1], &serde_json::json!({})).is_err());
+
+        let delete = build_request(&spec.base_url, &spec.operations[2], &serde_json::json!({ "petId": "a b/c" })).unwrap();
+        assert_eq!(delete.url.path(), "/pets/a%20b%2Fc");
+    }
+}
@@ -0,0 +1,446 @@
+//! OS Automation (v3.9.0)
+//!
+//! Deep OS automation through the user's own building blocks instead of raw
+//! AppleScript: named macOS Shortcuts and PowerShell scripts the user approved.
+//!
+//! Features:
+//! - Run a Shortcut by name with text input; its output is captured as text
+//! - Optional allow-list of Shortcuts the agent may run
+//! - PowerShell scripts must be approved first (name, path, parameters);
+//!   the script's SHA-256 is recorded and a changed script needs re-approval
+//! - Only declared parameters are passed, as separate arguments (no shell)
+//! - Exit code, stdout and stderr captured with a timeout and output limit
+
+#![allow(dead_code)]  // Phase 5: OS automation
+
+use crate::database::Database;
+use anyhow::{anyhow, Result};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::process::Command as TokioCommand;
+
+const CONFIG_KEY: &str = "os_automation_config";
+
+/// Timeout users can choose, in seconds
+const TIMEOUT_RANGE: (u64, u64) = (5, 600);
+
+const MAX_INPUT_BYTES: usize = 1024 * 1024;
+
+#[cfg(target_os = "windows")]
+const POWERSHELL: &str = "powershell.exe";
+#[cfg(not(target_os = "windows"))]
+const POWERSHELL: &str = "pwsh";
+
+/// A PowerShell script the user allowed the agent to run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovedScript {
+    /// Name the agent calls it by
+    pub name: String,
+    pub path: String,
+    pub description: String,
+    /// Parameter names the script accepts (without the leading `-`)
+    pub parameters: Vec<String>,
+    /// SHA-256 of the script when it was approved
+    pub sha256: String,
+    pub approved_at: i64,
+}
+
+/// Automation settings, stored in user_preferences
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OsAutomationConfig {
+    pub shortcuts_enabled: bool,
+    /// Shortcuts the agent may run; empty = none
+    pub allowed_shortcuts: Vec<String>,
+    /// Managed by `approve_script` / `remove_script`
+    pub scripts: Vec<ApprovedScript>,
+    pub timeout_secs: u64,
+    /// Characters of stdout / stderr returned; the rest is dropped
+    pub max_output_chars: usize,
+}
+
+impl Default for OsAutomationConfig {
+    fn default() -> Self {
+        Self {
+            shortcuts_enabled: false,
+            allowed_shortcuts: Vec::new(),
+            scripts: Vec::new(),
+            timeout_secs: 60,
+            max_output_chars: 20_000,
+        }
+    }
+}
+
+/// Outcome of a Shortcut or script run; failures are reported here, not as `Err`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomationRunResult {
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    pub output: String,
+    pub stderr: String,
+    pub output_truncated: bool,
+    pub timed_out: bool,
+    pub duration_ms: u64,
+}
+
+pub struct OsAutomationService {
+    db: Arc<Mutex<Database>>,
+    config: RwLock<OsAutomationConfig>,
+}
+
+impl OsAutomationService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        let config = {
+            let db = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            load_config(db.conn())?
+        };
+        log::info!("✓ OS Automation initialized ({} approved scripts)", config.scripts.len());
+        Ok(Self { db, config: RwLock::new(config) })
+    }
+
+    pub fn get_config(&self) -> OsAutomationConfig {
+        self.config.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Save settings; approved scripts are kept as they are (use `approve_script`)
+    pub fn update_config(&self, mut config: OsAutomationConfig) -> Result<OsAutomationConfig> {
+        config.timeout_secs = config.timeout_secs.clamp(TIMEOUT_RANGE.0, TIMEOUT_RANGE.1);
+        config.max_output_chars = config.max_output_chars.clamp(1_000, 1_000_000);
+        config.allowed_shortcuts = config
+            .allowed_shortcuts
+            .iter()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect();
+        config.allowed_shortcuts.dedup();
+        config.scripts = self.get_config().scripts;
+
+        self.save_config(&config)?;
+        log::info!("OS automation config updated");
+        Ok(config)
+    }
+
+    /// Approve (or re-approve) a PowerShell script under `name`
+    pub fn approve_script(
+        &self,
+        name: &str,
+        path: &str,
+        description: &str,
+        parameters: Vec<String>,
+    ) -> Result<ApprovedScript> {
+        let name = name.trim();
+        if !is_identifier(name) {
+            return Err(anyhow!("Script names may only contain letters, digits, '-' and '_'"));
+        }
+        if let Some(invalid) = parameters.iter().find(|p| !is_identifier(p)) {
+            return Err(anyhow!("Invalid parameter name '{}'", invalid));
+        }
+        let path = Path::new(path.trim())
+            .canonicalize()
+            .map_err(|e| anyhow!("Can't approve '{}': {}", path, e))?;
+        if !path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("ps1")) {
+            return Err(anyhow!("Only .ps1 scripts can be approved"));
+        }
+
+        let script = ApprovedScript {
+            name: name.to_string(),
+            path: path.to_string_lossy().into_owned(),
+            description: description.trim().to_string(),
+            parameters,
+            sha256: file_sha256(&path)?,
+            approved_at: chrono::Utc::now().timestamp(),
+        };
+        let mut config = self.get_config();
+        config.scripts.retain(|s| !s.name.eq_ignore_ascii_case(name));
+        config.scripts.push(script.clone());
+        self.save_config(&config)?;
+        log::info!("Approved PowerShell script '{}' ({})", script.name, script.path);
+        Ok(script)
+    }
+
+    /// Returns false if no script had that name
+    pub fn remove_script(&self, name: &str) -> Result<bool> {
+        let mut config = self.get_config();
+        let before = config.scripts.len();
+        config.scripts.retain(|s| !s.name.eq_ignore_ascii_case(name));
+        if config.scripts.len() == before {
+            return Ok(false);
+        }
+        self.save_config(&config)?;
+        Ok(true)
+    }
+
+    /// Names of the user's Shortcuts the agent may run
+    pub async fn list_shortcuts(&self) -> Result<Vec<String>> {
+        let config = self.get_config();
+        if !config.shortcuts_enabled {
+            return Err(anyhow!("Running Shortcuts is turned off in settings"));
+        }
+        if config.allowed_shortcuts.is_empty() {
+            return Err(anyhow!("No Shortcuts are allowed yet; add them to the allowlist in settings"));
+        }
+        let output = TokioCommand::new("shortcuts")
+            .arg("list")
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|e| anyhow!("The Shortcuts command line tool isn't available (macOS 12+): {}", e))?;
+        if !output.status.success() {
+            return Err(anyhow!("shortcuts list failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|name| !name.is_empty() && is_shortcut_allowed(&config, name))
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Run a Shortcut by name; `input` is passed as a text file
+    pub async fn run_shortcut(&self, name: &str, input: Option<&str>) -> Result<AutomationRunResult> {
+        let config = self.get_config();
+        let name = self
+            .list_shortcuts()
+            .await?
+            .into_iter()
+            .find(|s| s.eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| anyhow!("No Shortcut named '{}' is available", name))?;
+        if input.is_some_and(|text| text.len() > MAX_INPUT_BYTES) {
+            return Err(anyhow!("Shortcut input is over {} bytes", MAX_INPUT_BYTES));
+        }
+
+        let dir = tempfile::tempdir()?;
+        let output_path = dir.path().join("output.txt");
+        let mut command = TokioCommand::new("shortcuts");
+        command
+            .arg("run")
+            .arg(&name)
+            .arg("--output-path")
+            .arg(&output_path)
+            .args(["--output-type", "public.plain-text"]);
+        if let Some(text) = input {
+            let input_path = dir.path().join("input.txt");
+            std::fs::write(&input_path, text)?;
+            command.arg("--input-path").arg(input_path);
+        }
+
+        log::info!("Running Shortcut '{}'", name);
+        let mut result = run_process(command, &config).await?;
+        // The Shortcut's result goes to the output file; stdout is usually empty
+        if let Ok(bytes) = std::fs::read(&output_path) {
+            let text = String::from_utf8_lossy(&bytes);
+            let (text, truncated) = truncate_chars(text.trim_end(), config.max_output_chars);
+            result.output = text;
+            result.output_truncated = truncated;
+        }
+        Ok(result)
+    }
+
+    /// Run an approved script; `arguments` maps declared parameter names to values
+    pub async fn run_script(
+        &self,
+        name: &str,
+        arguments: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<AutomationRunResult> {
+        let config = self.get_config();
+        let script = config
+            .scripts
+            .iter()
+            .find(|s| s.name.eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| anyhow!("'{}' isn't an approved PowerShell script", name))?;
+        let args = script_arguments(script, arguments)?;
+        if file_sha256(Path::new(&script.path))? != script.sha256 {
+            return Err(anyhow!(
+                "'{}' changed since it was approved; approve it again to run it",
+                script.name
+            ));
+        }
+
+        let mut command = TokioCommand::new(POWERSHELL);
+        command
+            .args(["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-File"])
+            .arg(&script.path)
+            .args(args);
+
+        log::info!("Running PowerShell script '{}'", script.name);
+        run_process(command, &config).await
+    }
+
+    fn save_config(&self, config: &OsAutomationConfig) -> Result<()> {
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            db.conn().execute(
+                "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+                rusqlite::params![CONFIG_KEY, serde_json::to_string(config)?, chrono::Utc::now().timestamp()],
+            )?;
+        }
+        *self.config.write().unwrap_or_else(|e| e.into_inner()) = config.clone();
+        Ok(())
+    }
+}
+
+fn load_config(conn: &Connection) -> Result<OsAutomationConfig> {
+    let json: Option<String> = conn
+        .query_row("SELECT value FROM user_preferences WHERE key = ?1", [CONFIG_KEY], |row| row.get(0))
+        .optional()?;
+    match json {
+        Some(json) => Ok(serde_json::from_str(&json)?),
+        None => Ok(OsAutomationConfig::default()),
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty() && name.len() <= 64 && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn is_shortcut_allowed(config: &OsAutomationConfig, name: &str) -> bool {
+    config.allowed_shortcuts.iter().any(|s| s.eq_ignore_ascii_case(name))
+}
+
+fn file_sha256(path: &Path) -> Result<String> {
+    let bytes = std::fs::read(path).map_err(|e| anyhow!("Can't read '{}': {}", path.display(), e))?;
+    Ok(format!("{:x}", Sha256::digest(&bytes)))
+}
+
+/// `-Name:value` per argument; the colon form keeps values starting with `-`
+/// from being read as parameter names
+fn script_arguments(
+    script: &ApprovedScript,
+    arguments: &serde_json::Map<String, serde_json::Value>,
+) -> Result<Vec<String>> {
+    arguments
+        .iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(key, value)| {
+            let name = script
+                .parameters
+                .iter()
+                .find(|p| p.eq_ignore_ascii_case(key))
+                .ok_or_else(|| anyhow!(
+                    "'{}' has no parameter '{}' (accepted: {})",
+                    script.name,
+                    key,
+                    script.parameters.join(", ")
+                ))?;
+            let value = match value {
+                serde_json::Value::String(text) => text.clone(),
+                serde_json::Value::Bool(flag) => format!("${}", flag),
+                serde_json::Value::Number(number) => number.to_string(),
+                _ => return Err(anyhow!("Parameter '{}' must be a string, number or boolean", name)),
+            };
+            Ok(format!("-{}:{}", name, value))
+        })
+        .collect()
+}
+
+fn truncate_chars(text: &str, limit: usize) -> (String, bool) {
+    match text.char_indices().nth(limit) {
+        Some((end, _)) => (text[..end].to_string(), true),
+        None => (text.to_string(), false),
+    }
+}
+
+async fn run_process(mut command: TokioCommand, config: &OsAutomationConfig) -> Result<AutomationRunResult> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let started = Instant::now();
+    let child = command.spawn().map_err(|e| anyhow!("Failed to start: {}", e))?;
+
+    match tokio::time::timeout(Duration::from_secs(config.timeout_secs), child.wait_with_output()).await {
+        Ok(output) => {
+            let output = output?;
+            let (stdout, stdout_truncated) =
+                truncate_chars(String::from_utf8_lossy(&output.stdout).trim_end(), config.max_output_chars);
+            let (stderr, stderr_truncated) =
+                truncate_chars(String::from_utf8_lossy(&output.stderr).trim_end(), config.max_output_chars);
+            Ok(AutomationRunResult {
+                success: output.status.success(),
+                exit_code: output.status.code(),
+                output: stdout,
+                stderr,
+                output_truncated: stdout_truncated || stderr_truncated,
+                timed_out: false,
+                duration_ms: started.elapsed().as_millis() as u64,
+            })
+        }
+        // Dropping the future kills the process
+        Err(_) => Ok(AutomationRunResult {
+            success: false,
+            exit_code: None,
+            output: String::new(),
+            stderr: format!("Stopped after {} s", config.timeout_secs),
+            output_truncated: false,
+            timed_out: true,
+            duration_ms: started.elapsed().as_millis() as u64,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shortcuts_need_an_allowlist() {
+        let mut config = OsAutomationConfig::default();
+        assert!(!config.shortcuts_enabled);
+        assert!(!is_shortcut_allowed(&config, "Send Message"));
+
+        config.allowed_shortcuts = vec!["Log Water".to_string()];
+        assert!(is_shortcut_allowed(&config, "log water"));
+        assert!(!is_shortcut_allowed(&config, "Send Message"));
+
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let service = OsAutomationService::new(db).unwrap();
+        assert!(service.run_shortcut("Log Water", None).await.is_err());
+
+        let mut enabled = service.get_config();
+        enabled.shortcuts_enabled = true;
+        service.update_config(enabled).unwrap();
+        let err = service.list_shortcuts().await.unwrap_err();
+        assert!(err.to_string().contains("No Shortcuts are allowed"));
+    }
+
+    #[tokio::test]
+    async fn test_script_approval_and_arguments() {
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let service = OsAutomationService::new(db).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("backup.ps1");
+        std::fs::write(&path, "param([string]$Target, [switch]$Force)\nWrite-Output $Target").unwrap();
+
+        assert!(service.approve_script("bad name", path.to_str().unwrap(), "", vec![]).is_err());
+        let script = service
+            .approve_script("backup", path.to_str().unwrap(), "Back up a folder", vec!["Target".into(), "Force".into()])
+            .unwrap();
+        assert_eq!(service.get_config().scripts.len(), 1);
+
+        // Settings changes keep approvals
+        let mut config = service.get_config();
+        config.scripts.clear();
+        assert_eq!(service.update_config(config).unwrap().scripts, vec![script.clone()]);
+
+        let arguments = serde_json::json!({ "target": "-C:/Users", "Force": true });
+        let args = script_arguments(&script, arguments.as_object().unwrap()).unwrap();
+        assert_eq!(args, vec!["-Force:$true".to_string(), "-Target:-C:/Users".to_string()]);
+        let unknown = serde_json::json!({ "Command": "rm" });
+        assert!(script_arguments(&script, unknown.as_object().unwrap()).is_err());
+
+        // Edited after approval
+        std::fs::write(&path, "Remove-Item -Recurse C:/").unwrap();
+        let error = service.run_script("backup", &serde_json::Map::new()).await.unwrap_err();
+        assert!(error.to_string().contains("approve it again"));
+
+        assert!(service.remove_script("BACKUP").unwrap());
+        assert!(service.run_script("backup", &serde_json::Map::new()).await.is_err());
+    }
+}
@@ -7,7 +7,7 @@
  * Replaces heuristic keyword matching with structured LLM analysis.
  */
 
-use crate::services::ollama;
+use crate::services::structured_output::{self, StructuredOptions};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
@@ -62,6 +62,23 @@ impl Default for TraitAnalysis {
     }
 }
 
+/// JSON schema of `TraitAnalysis` (v3.9.0)
+fn trait_schema() -> serde_json::Value {
+    let names = [
+        "formality", "verbosity", "technical_depth", "emoji_usage", "humor",
+        "creativity", "empathy", "assertiveness", "proactivity", "cultural_awareness",
+    ];
+    let properties: serde_json::Map<String, serde_json::Value> = names
+        .iter()
+        .map(|name| (name.to_string(), serde_json::json!({"type": "number", "minimum": 0.0, "maximum": 1.0})))
+        .collect();
+    serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "required": names,
+    })
+}
+
 /// LLM-based pattern detector service
 pub struct LlmPatternDetector;
 
@@ -82,13 +99,13 @@ impl LlmPatternDetector {
 
         let prompt = self.create_analysis_prompt(truncated_response);
 
-        // Generate analysis using Ollama
-        let response = ollama::generate_response(&prompt)
+        // v3.9.0: Schema-constrained generation, validated and retried on bad JSON
+        let output = structured_output::generate(&prompt, &trait_schema(), &StructuredOptions::default())
             .await
-            .map_err(|e| anyhow::anyhow!("Failed to generate trait analysis: {}", e))?;
+            .context("Failed to generate trait analysis")?;
 
         // Parse JSON response
-        self.parse_analysis_response(&response)
+        self.parse_analysis_response(&output.raw)
     }
 
     /// Batch analyze multiple texts
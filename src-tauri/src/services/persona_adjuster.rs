@@ -141,7 +141,13 @@ impl PersonaAdjusterService {
 
         // Auto-apply if configured
         let auto_applied = if self.config.auto_apply {
-            match self.apply_adjustment(&suggested) {
+            let context = serde_json::json!({
+                "conversation_id": conversation_id,
+                "sample_size": insights.sample_size,
+                "confidence": insights.confidence,
+                "explanation": explanation,
+            });
+            match self.apply_adjustment_with_context(&suggested, Some(&context)) {
                 Ok(_) => true,
                 Err(e) => {
                     log::error!("Failed to auto-apply persona adjustment: {}", e);
@@ -163,8 +169,13 @@ impl PersonaAdjusterService {
 
     /// Apply persona adjustment
     pub fn apply_adjustment(&self, suggested: &PersonaParameters) -> Result<()> {
+        self.apply_adjustment_with_context(suggested, None)
+    }
+
+    /// Apply persona adjustment, recording the insights behind it (v3.9.0)
+    fn apply_adjustment_with_context(&self, suggested: &PersonaParameters, context: Option<&serde_json::Value>) -> Result<()> {
         let db = self.db.lock().unwrap();
-        db.update_persona(suggested, "optimization")
+        db.update_persona_with_context(suggested, "optimization", context)
             .context("Failed to apply persona adjustment")?;
 
         log::info!("Applied automatic persona adjustment based on personality insights");
@@ -0,0 +1,265 @@
+//! Persona Change Log (v3.9.0)
+//!
+//! Explainable history of persona adjustments.
+//!
+//! Features:
+//! - Per-parameter deltas for every `persona_changes` entry
+//! - Human-readable explanation built from the recorded evidence
+//!   (preset, A/B experiment feedback, personality insights)
+//! - Rollback to any snapshot, recorded as a new "rollback" change
+
+#![allow(dead_code)]  // Phase 5: Persona change log
+
+use crate::database::models::PersonaParameters;
+use crate::database::Database;
+use anyhow::{anyhow, Result};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One parameter that moved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParameterDelta {
+    pub name: String,
+    pub from: i32,
+    pub to: i32,
+}
+
+/// A `persona_changes` entry with its explanation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonaChangeEntry {
+    pub id: String,
+    pub timestamp: i64, // Unix millis
+    /// "manual", "preset", "optimization", "reset", "ab_test" or "rollback"
+    pub reason: String,
+    pub changes: Vec<ParameterDelta>,
+    pub magnitude: f32,
+    pub explanation: String,
+    /// Evidence recorded with the change, if any
+    pub evidence: Option<Value>,
+    pub previous: PersonaParameters,
+    pub new: PersonaParameters,
+}
+
+/// Parameters as (name, value) pairs
+fn named_values(p: &PersonaParameters) -> [(&'static str, i32); 10] {
+    [
+        ("formality", p.formality),
+        ("verbosity", p.verbosity),
+        ("humor", p.humor),
+        ("emoji_usage", p.emoji_usage),
+        ("empathy", p.empathy),
+        ("creativity", p.creativity),
+        ("proactiveness", p.proactiveness),
+        ("technical_depth", p.technical_depth),
+        ("code_examples", p.code_examples),
+        ("questioning", p.questioning),
+    ]
+}
+
+/// Parameters that differ between two snapshots
+pub fn diff(previous: &PersonaParameters, new: &PersonaParameters) -> Vec<ParameterDelta> {
+    named_values(previous)
+        .into_iter()
+        .zip(named_values(new))
+        .filter(|((_, from), (_, to))| from != to)
+        .map(|((name, from), (_, to))| ParameterDelta { name: name.to_string(), from, to })
+        .collect()
+}
+
+/// "humor 20 → 45, verbosity 60 → 40"
+fn describe_deltas(changes: &[ParameterDelta]) -> String {
+    if changes.is_empty() {
+        return "no parameters changed".to_string();
+    }
+    changes
+        .iter()
+        .map(|d| format!("{} {} → {}", d.name.replace('_', " "), d.from, d.to))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn percent(value: Option<&Value>) -> Option<String> {
+    value.and_then(Value::as_f64).map(|v| format!("{:.0}%", v * 100.0))
+}
+
+/// Why the change happened, in one or two sentences
+pub fn explain(reason: &str, changes: &[ParameterDelta], evidence: Option<&Value>) -> String {
+    let field = |key: &str| evidence.and_then(|e| e.get(key));
+    let text = |key: &str| field(key).and_then(Value::as_str);
+
+    let cause = match reason {
+        "manual" => "Changed manually in settings.".to_string(),
+        "reset" => "Reset to the default persona.".to_string(),
+        "preset" => match text("preset_name") {
+            Some(name) => format!("Applied the \"{}\" preset.", name),
+            None => "Applied a persona preset.".to_string(),
+        },
+        "ab_test" => {
+            let experiment = text("experiment_name").unwrap_or("an A/B experiment");
+            let mut cause = match text("variant") {
+                Some(variant) => format!("Adopted variant {} of \"{}\"", variant.to_uppercase(), experiment),
+                None => format!("Adopted the winning variant of \"{}\"", experiment),
+            };
+            if let Some(confidence) = percent(field("confidence")) {
+                cause.push_str(&format!(" at {} confidence", confidence));
+            }
+            let stats = |key: &str| {
+                field(key).map(|s| {
+                    let count = |k: &str| s.get(k).and_then(Value::as_u64).unwrap_or(0);
+                    (count("positive"), count("negative"))
+                })
+            };
+            if let (Some((a_up, a_down)), Some((b_up, b_down))) = (stats("stats_a"), stats("stats_b")) {
+                cause.push_str(&format!(
+                    " (A: {} 👍 / {} 👎, B: {} 👍 / {} 👎)",
+                    a_up, a_down, b_up, b_down
+                ));
+            }
+            cause.push('.');
+            cause
+        }
+        "optimization" => {
+            let mut cause = "Adjusted automatically from personality insights".to_string();
+            if let Some(conversation) = text("conversation_id") {
+                cause.push_str(&format!(" of conversation {}", conversation));
+            }
+            let sample_size = field("sample_size").and_then(Value::as_u64);
+            match (sample_size, percent(field("confidence"))) {
+                (Some(n), Some(c)) => cause.push_str(&format!(" ({} messages, {} confidence)", n, c)),
+                (Some(n), None) => cause.push_str(&format!(" ({} messages)", n)),
+                (None, Some(c)) => cause.push_str(&format!(" ({} confidence)", c)),
+                (None, None) => {}
+            }
+            cause.push('.');
+            let notes: Vec<&str> = field("explanation")
+                .and_then(Value::as_array)
+                .map(|lines| lines.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            if !notes.is_empty() {
+                cause.push(' ');
+                cause.push_str(&notes.join(" "));
+            }
+            cause
+        }
+        "rollback" => match text("rolled_back_to") {
+            Some(id) if text("restored") == Some("before") => {
+                format!("Rolled back to the persona before change {}.", id)
+            }
+            Some(id) => format!("Rolled back to the persona after change {}.", id),
+            None => "Rolled back to an earlier persona.".to_string(),
+        },
+        other => format!("Changed ({}).", other),
+    };
+
+    format!("{} Changed: {}.", cause, describe_deltas(changes))
+}
+
+/// (id, previous_params, new_params, change_magnitude, timestamp, reason, context)
+type ChangeRow = (String, String, String, f32, i64, String, Option<String>);
+
+fn entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<ChangeRow> {
+    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?, row.get(6)?))
+}
+
+fn build_entry((id, previous, new, magnitude, timestamp, reason, context): ChangeRow) -> Result<PersonaChangeEntry> {
+    let previous: PersonaParameters = serde_json::from_str(&previous)?;
+    let new: PersonaParameters = serde_json::from_str(&new)?;
+    let evidence = context.as_deref().and_then(|json| serde_json::from_str(json).ok());
+    let changes = diff(&previous, &new);
+    let explanation = explain(&reason, &changes, evidence.as_ref());
+
+    Ok(PersonaChangeEntry {
+        id,
+        timestamp,
+        reason,
+        changes,
+        magnitude,
+        explanation,
+        evidence,
+        previous,
+        new,
+    })
+}
+
+/// Persona changes, newest first
+pub fn change_log(db: &Database, limit: usize) -> Result<Vec<PersonaChangeEntry>> {
+    let mut stmt = db.conn().prepare(
+        "SELECT id, previous_params, new_params, change_magnitude, timestamp, reason, context
+         FROM persona_changes
+         ORDER BY timestamp DESC, rowid DESC
+         LIMIT ?1",
+    )?;
+    let rows = stmt
+        .query_map(params![limit as i64], entry_from_row)?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    rows.into_iter().map(build_entry).collect()
+}
+
+/// Restore the persona snapshot of `change_id`
+///
+/// Restores the persona as it was after that change, or before it when
+/// `before` is set. The rollback itself is logged as a new change.
+pub fn rollback_to(db: &Database, change_id: &str, before: bool) -> Result<PersonaChangeEntry> {
+    let row = db.conn().query_row(
+        "SELECT id, previous_params, new_params, change_magnitude, timestamp, reason, context
+         FROM persona_changes WHERE id = ?1",
+        params![change_id],
+        entry_from_row,
+    ).optional()?;
+    let target = build_entry(row.ok_or_else(|| anyhow!("Persona change '{}' not found", change_id))?)?;
+
+    let restored = if before { &target.previous } else { &target.new };
+    let context = serde_json::json!({
+        "rolled_back_to": target.id,
+        "restored": if before { "before" } else { "after" },
+        "original_reason": target.reason,
+    });
+
+    let new_id = db
+        .update_persona_with_context(restored, "rollback", Some(&context))?
+        .ok_or_else(|| anyhow!("Persona already matches change '{}'", change_id))?;
+
+    let row = db.conn().query_row(
+        "SELECT id, previous_params, new_params, change_magnitude, timestamp, reason, context
+         FROM persona_changes WHERE id = ?1",
+        params![new_id],
+        entry_from_row,
+    )?;
+    log::info!("Rolled persona back to change {}", change_id);
+    build_entry(row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_changes() {
+        let previous = PersonaParameters {
+            formality: 50, verbosity: 50, humor: 20, emoji_usage: 10, empathy: 60,
+            creativity: 50, proactiveness: 40, technical_depth: 60, code_examples: 50, questioning: 40,
+        };
+        let new = PersonaParameters { humor: 40, ..previous.clone() };
+        let changes = diff(&previous, &new);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].name, "humor");
+
+        let evidence = serde_json::json!({
+            "experiment_name": "Funnier",
+            "variant": "b",
+            "confidence": 0.97,
+            "stats_a": { "positive": 10, "negative": 12 },
+            "stats_b": { "positive": 19, "negative": 3 },
+        });
+        let text = explain("ab_test", &changes, Some(&evidence));
+        assert!(text.contains("variant B of \"Funnier\""));
+        assert!(text.contains("97% confidence"));
+        assert!(text.contains("B: 19 👍 / 3 👎"));
+        assert!(text.contains(&format!("humor {} → {}", previous.humor, new.humor)));
+
+        let text = explain("optimization", &changes, None);
+        assert!(text.starts_with("Adjusted automatically from personality insights."));
+    }
+}
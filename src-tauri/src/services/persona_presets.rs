@@ -0,0 +1,372 @@
+//! Persona Presets (v3.9.0)
+//!
+//! Named, one-click persona profiles.
+//!
+//! Features:
+//! - Full snapshot of the 10 persona parameters plus custom instructions
+//! - Built-in presets: Mentor, Rubber duck, Code reviewer, Korean tutor
+//! - Applying a preset updates `persona_settings` (recorded as a "preset"
+//!   change) and marks it active, so the chat pipeline appends its instructions
+
+#![allow(dead_code)]  // Phase 5: Persona presets
+
+use crate::database::models::PersonaParameters;
+use crate::database::Database;
+use anyhow::{anyhow, Result};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// `user_preferences` key holding the active preset id
+const ACTIVE_PRESET_KEY: &str = "active_persona_preset";
+
+/// A named persona snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonaPreset {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub parameters: PersonaParameters,
+    pub custom_instructions: String,
+    pub builtin: bool,
+    pub active: bool,
+    pub created_at: i64, // Unix millis
+    pub updated_at: i64,
+}
+
+/// Parameters in `persona_settings` column order
+fn snapshot(values: [i32; 10]) -> PersonaParameters {
+    let [formality, verbosity, humor, emoji_usage, empathy, creativity, proactiveness, technical_depth, code_examples, questioning] =
+        values;
+    PersonaParameters {
+        formality,
+        verbosity,
+        humor,
+        emoji_usage,
+        empathy,
+        creativity,
+        proactiveness,
+        technical_depth,
+        code_examples,
+        questioning,
+    }
+}
+
+fn parameter_values(p: &PersonaParameters) -> [i32; 10] {
+    [
+        p.formality, p.verbosity, p.humor, p.emoji_usage, p.empathy,
+        p.creativity, p.proactiveness, p.technical_depth, p.code_examples, p.questioning,
+    ]
+}
+
+/// Built-in presets: (id, name, description, parameters, instructions)
+fn builtin_presets() -> Vec<(&'static str, &'static str, &'static str, PersonaParameters, &'static str)> {
+    vec![
+        (
+            "builtin-mentor",
+            "Mentor",
+            "Patient guide who explains the why and checks understanding",
+            snapshot([40, 65, 25, 10, 75, 55, 60, 60, 60, 65]),
+            "Act as a patient mentor. Explain the reasoning behind each suggestion, \
+             build on what the user already knows, and end with one question that \
+             checks their understanding or points to the next step.",
+        ),
+        (
+            "builtin-rubber-duck",
+            "Rubber duck",
+            "Asks questions so you find the answer yourself",
+            snapshot([20, 20, 30, 10, 60, 40, 20, 50, 10, 95]),
+            "Act as a rubber duck. Do not hand over solutions. Ask short, specific \
+             questions that make the user explain their code, assumptions and \
+             expected behaviour until they spot the problem themselves.",
+        ),
+        (
+            "builtin-code-reviewer",
+            "Code reviewer",
+            "Direct, thorough review focused on correctness and maintainability",
+            snapshot([65, 55, 5, 0, 30, 35, 70, 90, 85, 40]),
+            "Act as a senior code reviewer. Point out bugs, edge cases, naming and \
+             design issues in order of severity, quote the relevant lines, and show \
+             the corrected code. Be direct; skip praise that carries no information.",
+        ),
+        (
+            "builtin-korean-tutor",
+            "Korean tutor",
+            "Friendly Korean language tutor with corrections and examples",
+            snapshot([45, 60, 40, 30, 75, 60, 65, 30, 5, 60]),
+            "Act as a friendly Korean tutor. Reply in simple Korean followed by an \
+             English gloss, gently correct the user's Korean mistakes with a short \
+             explanation, and suggest one natural alternative phrasing per reply.",
+        ),
+    ]
+}
+
+/// Instructions of the active preset, for the chat system prompt
+///
+/// Returns `None` when no preset is active or the preset has no instructions.
+pub fn active_instructions(db: &Database) -> Option<String> {
+    db.conn()
+        .query_row(
+            "SELECT p.custom_instructions
+             FROM user_preferences u
+             JOIN persona_presets p ON p.id = u.value
+             WHERE u.key = ?1",
+            [ACTIVE_PRESET_KEY],
+            |row| row.get::<_, String>(0),
+        )
+        .ok()
+        .filter(|instructions| !instructions.trim().is_empty())
+}
+
+/// Persona preset service
+pub struct PersonaPresetService {
+    db: Arc<Mutex<Database>>,
+}
+
+impl PersonaPresetService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        let service = Self { db };
+        service.init_database()?;
+        log::info!("✓ Persona Preset Service initialized");
+        Ok(service)
+    }
+
+    fn init_database(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let conn = db.conn();
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS persona_presets (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                description TEXT NOT NULL DEFAULT '',
+                parameters TEXT NOT NULL,
+                custom_instructions TEXT NOT NULL DEFAULT '',
+                builtin INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        let now = chrono::Utc::now().timestamp_millis();
+        for (id, name, description, parameters, instructions) in builtin_presets() {
+            conn.execute(
+                "INSERT OR IGNORE INTO persona_presets
+                 (id, name, description, parameters, custom_instructions, builtin, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 1, ?6, ?6)",
+                params![id, name, description, serde_json::to_string(&parameters)?, instructions, now],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// All presets, built-ins first
+    pub fn list(&self) -> Result<Vec<PersonaPreset>> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let conn = db.conn();
+        let active = active_preset_id(conn)?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, name, description, parameters, custom_instructions, builtin, created_at, updated_at
+             FROM persona_presets
+             ORDER BY builtin DESC, name COLLATE NOCASE ASC",
+        )?;
+        let presets = stmt
+            .query_map([], row_to_preset)?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .map(|mut preset| {
+                preset.active = active.as_deref() == Some(preset.id.as_str());
+                preset
+            })
+            .collect();
+        Ok(presets)
+    }
+
+    /// Save a preset under `name`, overwriting a custom preset of the same name
+    ///
+    /// Without `parameters` the current persona settings are snapshotted.
+    pub fn save(
+        &self,
+        name: &str,
+        description: Option<&str>,
+        parameters: Option<PersonaParameters>,
+        custom_instructions: &str,
+    ) -> Result<PersonaPreset> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow!("Preset name is empty"));
+        }
+
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let parameters = match parameters {
+            Some(parameters) => parameters,
+            None => db.load_persona()?,
+        };
+        if !parameter_values(&parameters).iter().all(|v| (0..=100).contains(v)) {
+            return Err(anyhow!("Persona parameters must be between 0 and 100"));
+        }
+        let conn = db.conn();
+
+        let existing: Option<(String, bool)> = conn
+            .query_row(
+                "SELECT id, builtin FROM persona_presets WHERE name = ?1",
+                [name],
+                |row| Ok((row.get(0)?, row.get::<_, i64>(1)? != 0)),
+            )
+            .optional()?;
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let parameters_json = serde_json::to_string(&parameters)?;
+        let id = match existing {
+            Some((_, true)) => {
+                return Err(anyhow!("Built-in preset '{}' cannot be overwritten", name));
+            }
+            Some((id, false)) => {
+                conn.execute(
+                    "UPDATE persona_presets
+                     SET description = ?1, parameters = ?2, custom_instructions = ?3, updated_at = ?4
+                     WHERE id = ?5",
+                    params![description.unwrap_or(""), parameters_json, custom_instructions, now, id],
+                )?;
+                id
+            }
+            None => {
+                let id = uuid::Uuid::new_v4().to_string();
+                conn.execute(
+                    "INSERT INTO persona_presets
+                     (id, name, description, parameters, custom_instructions, builtin, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, 0, ?6, ?6)",
+                    params![id, name, description.unwrap_or(""), parameters_json, custom_instructions, now],
+                )?;
+                id
+            }
+        };
+
+        log::info!("Saved persona preset '{}'", name);
+        load_preset(conn, &id)?.ok_or_else(|| anyhow!("Preset '{}' not found after save", name))
+    }
+
+    /// Make a preset (by id or name) the current persona
+    pub fn apply(&self, preset: &str) -> Result<PersonaPreset> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let conn = db.conn();
+
+        let mut preset = find_preset(conn, preset)?
+            .ok_or_else(|| anyhow!("Persona preset '{}' not found", preset))?;
+
+        let context = serde_json::json!({ "preset_id": preset.id, "preset_name": preset.name });
+        db.update_persona_with_context(&preset.parameters, "preset", Some(&context))?;
+        conn.execute(
+            "INSERT INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+            params![ACTIVE_PRESET_KEY, preset.id, chrono::Utc::now().timestamp_millis()],
+        )?;
+
+        log::info!("Applied persona preset '{}'", preset.name);
+        preset.active = true;
+        Ok(preset)
+    }
+
+    /// Delete a custom preset (built-ins are kept)
+    pub fn delete(&self, preset: &str) -> Result<bool> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let conn = db.conn();
+
+        let Some(found) = find_preset(conn, preset)? else {
+            return Ok(false);
+        };
+        if found.builtin {
+            return Err(anyhow!("Built-in preset '{}' cannot be deleted", found.name));
+        }
+
+        conn.execute("DELETE FROM persona_presets WHERE id = ?1", [&found.id])?;
+        if found.active {
+            conn.execute("DELETE FROM user_preferences WHERE key = ?1", [ACTIVE_PRESET_KEY])?;
+        }
+        Ok(true)
+    }
+
+    /// Stop appending preset instructions (persona parameters stay as they are)
+    pub fn clear_active(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        db.conn().execute("DELETE FROM user_preferences WHERE key = ?1", [ACTIVE_PRESET_KEY])?;
+        Ok(())
+    }
+}
+
+fn active_preset_id(conn: &rusqlite::Connection) -> Result<Option<String>> {
+    Ok(conn
+        .query_row(
+            "SELECT value FROM user_preferences WHERE key = ?1",
+            [ACTIVE_PRESET_KEY],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+fn find_preset(conn: &rusqlite::Connection, id_or_name: &str) -> Result<Option<PersonaPreset>> {
+    let id: Option<String> = conn
+        .query_row(
+            "SELECT id FROM persona_presets WHERE id = ?1 OR name = ?1",
+            [id_or_name.trim()],
+            |row| row.get(0),
+        )
+        .optional()?;
+    match id {
+        Some(id) => load_preset(conn, &id),
+        None => Ok(None),
+    }
+}
+
+fn load_preset(conn: &rusqlite::Connection, id: &str) -> Result<Option<PersonaPreset>> {
+    let preset = conn
+        .query_row(
+            "SELECT id, name, description, parameters, custom_instructions, builtin, created_at, updated_at
+             FROM persona_presets WHERE id = ?1",
+            [id],
+            row_to_preset,
+        )
+        .optional()?;
+    let active = active_preset_id(conn)?;
+    Ok(preset.map(|mut preset| {
+        preset.active = active.as_deref() == Some(preset.id.as_str());
+        preset
+    }))
+}
+
+fn row_to_preset(row: &rusqlite::Row) -> rusqlite::Result<PersonaPreset> {
+    let parameters: String = row.get(3)?;
+    Ok(PersonaPreset {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        parameters: serde_json::from_str(&parameters).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e))
+        })?,
+        custom_instructions: row.get(4)?,
+        builtin: row.get::<_, i64>(5)? != 0,
+        active: false,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_presets_are_valid() {
+        let presets = builtin_presets();
+        let names: Vec<&str> = presets.iter().map(|(_, name, ..)| *name).collect();
+        assert_eq!(names, vec!["Mentor", "Rubber duck", "Code reviewer", "Korean tutor"]);
+
+        for (_, _, _, p, instructions) in presets {
+            assert!(parameter_values(&p).iter().all(|v| (0..=100).contains(v)));
+            assert!(!instructions.is_empty());
+        }
+    }
+}
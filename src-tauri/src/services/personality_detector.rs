@@ -4,11 +4,16 @@
 #![allow(dead_code)]
 
 use crate::database::{Database, models::PersonaParameters};
+use crate::services::language_detection;
 use anyhow::Result;
 use regex::Regex;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
+/// Whitespace-separated Korean words (eojeol) carry a word plus its
+/// particles, so one counts as this many English words (v3.9.0)
+const KOREAN_WORD_WEIGHT: f32 = 1.5;
+
 /// Conversation pattern analysis results
 #[derive(Debug, Clone)]
 pub struct ConversationPatterns {
@@ -220,24 +225,31 @@ impl PersonalityDetectorService {
         let mut informal_count = 0;
 
         for msg in messages {
-            // Korean honorifics increase formality
-            if let Some(pattern) = self.formality_patterns.get("honorifics_korean") {
-                formal_count += pattern.find_iter(msg).count();
-            }
-
-            // Korean informal speech decreases formality
-            if let Some(pattern) = self.formality_patterns.get("informal_korean") {
-                informal_count += pattern.find_iter(msg).count();
+            // v3.9.0: Only the patterns of the languages the message is written in
+            let (korean, english) = message_languages(msg);
+
+            if korean {
+                // Korean honorifics increase formality
+                if let Some(pattern) = self.formality_patterns.get("honorifics_korean") {
+                    formal_count += pattern.find_iter(msg).count();
+                }
+
+                // Korean informal speech decreases formality
+                if let Some(pattern) = self.formality_patterns.get("informal_korean") {
+                    informal_count += pattern.find_iter(msg).count();
+                }
             }
 
-            // English formal phrases
-            if let Some(pattern) = self.formality_patterns.get("formal_english") {
-                formal_count += pattern.find_iter(msg).count();
-            }
+            if english {
+                // English formal phrases
+                if let Some(pattern) = self.formality_patterns.get("formal_english") {
+                    formal_count += pattern.find_iter(msg).count();
+                }
 
-            // Contractions decrease formality
-            if let Some(pattern) = self.formality_patterns.get("contractions") {
-                informal_count += pattern.find_iter(msg).count();
+                // Contractions decrease formality
+                if let Some(pattern) = self.formality_patterns.get("contractions") {
+                    informal_count += pattern.find_iter(msg).count();
+                }
             }
         }
 
@@ -251,8 +263,9 @@ impl PersonalityDetectorService {
 
     /// Calculate verbosity score based on message length and detail
     pub fn calculate_verbosity(&self, messages: &[String]) -> f32 {
+        // v3.9.0: English-equivalent words, so Korean and English messages score alike
         let avg_length = messages.iter()
-            .map(|msg| msg.split_whitespace().count() as f32)
+            .map(|msg| word_equivalents(msg))
             .sum::<f32>() / messages.len() as f32;
 
         // Map average word count to verbosity score
@@ -536,6 +549,27 @@ impl PersonalityDetectorService {
     }
 }
 
+/// Whether a message is (partly) Korean / English (v3.9.0)
+///
+/// Mixed messages count as both; undecidable ones (too short) too.
+fn message_languages(msg: &str) -> (bool, bool) {
+    match language_detection::detect(msg) {
+        Some(detected) if detected.code == "ko" => (true, detected.mixed),
+        Some(detected) => (detected.mixed, true),
+        None => (true, true),
+    }
+}
+
+/// Message length in English-equivalent words (v3.9.0)
+fn word_equivalents(msg: &str) -> f32 {
+    let words = msg.split_whitespace().count() as f32;
+    if language_detection::is_korean(msg) {
+        words * KOREAN_WORD_WEIGHT
+    } else {
+        words
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -614,6 +648,18 @@ mod tests {
         assert!(technical_score > non_technical_score, "Technical messages should have higher technical depth score");
     }
 
+    #[test]
+    fn test_language_aware_patterns() {
+        assert_eq!(message_languages("안녕하세요. 도움을 주시겠습니까?"), (true, false));
+        assert_eq!(message_languages("I'm stuck, can't get the build to pass"), (false, true));
+        assert_eq!(message_languages("이거 async fn에서 lifetime error 나요"), (true, true));
+
+        // Same content, same verbosity regardless of language
+        let korean = word_equivalents("어제 배포한 서버가 메모리를 너무 많이 써서 재시작했어요");
+        let english = word_equivalents("The server we deployed yesterday used too much memory so I restarted it");
+        assert!((korean - english).abs() < 3.0, "korean={} english={}", korean, english);
+    }
+
     #[test]
     fn test_big_five_detection() {
         let db = Arc::new(Mutex::new(crate::database::Database::new().unwrap()));
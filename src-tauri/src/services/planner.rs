@@ -19,6 +19,7 @@
  * 5. Track progress and provide status updates
  */
 
+use crate::services::agent_actions;
 use crate::services::react_agent::ReActAgent;
 use log::{debug, info, warn};
 use serde::{Deserialize, Serialize};
@@ -393,6 +394,8 @@ Return ONLY the JSON, no additional text.
 
         info!("Starting plan execution: {}", plan.id);
         plan.execution_started = true;
+        // v3.9.0: File/git changes made by the steps are grouped under the plan id for rollback
+        agent_actions::begin_group(&plan.id);
 
         let mut execution_log = Vec::new();
         let mut completed_steps = 0;
@@ -476,6 +479,8 @@ Return ONLY the JSON, no additional text.
             }
         }
 
+        agent_actions::end_group();
+
         // Mark plan as completed
         plan.completed = plan.is_complete();
 
@@ -47,6 +47,7 @@ pub enum Permission {
     Notification,  // Show notifications
     Clipboard,     // Access clipboard
     Shell,         // Execute shell commands
+    Llm,           // Structured generation with the local model (v3.9.0)
 }
 
 /// Loaded plugin instance
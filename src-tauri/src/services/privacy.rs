@@ -0,0 +1,472 @@
+//! Privacy Service (v3.9.0)
+//!
+//! "Forget me" for a topic: finds everything stored about it and deletes it
+//! from every memory store in one go.
+//!
+//! Features:
+//! - Export of all matching data before anything is deleted
+//! - Episodic memories (with their vectors, BM25 postings and enhancement copies)
+//! - Semantic wiki facts and fact embeddings
+//! - Knowledge graph entities, relationships, and document links
+//! - Clipboard history entries and screen history frames (with their RAG episodes)
+//! - Vectors are removed first; SQLite changes roll back if the graph deletion fails
+
+#![allow(dead_code)]  // Phase 5: Privacy controls
+
+use crate::database::Database;
+use crate::services::graph_builder::{GraphEdge, GraphNode};
+use crate::services::graph_storage::GraphStorage;
+use crate::services::rag_v2::RagServiceV2;
+use crate::services::screen_history::select_episode_ids;
+use anyhow::{anyhow, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Shortest topic accepted, so a stray keystroke can't match everything
+const MIN_TOPIC_CHARS: usize = 2;
+
+/// Episodic memory matching a topic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpisodeRecord {
+    pub id: String,
+    pub user_message: String,
+    pub ai_response: String,
+    pub created_at: i64,
+}
+
+/// Semantic wiki fact matching a topic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactRecord {
+    pub id: String,
+    pub statement: String,
+    pub entity: String,
+    pub category: String,
+    pub confidence: f32,
+    pub source_conversation_id: String,
+    pub learned_at: i64,
+}
+
+/// Clipboard history entry matching a topic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardRecord {
+    pub id: String,
+    pub content: String,
+    pub source_app: Option<String>,
+    pub created_at: i64,
+}
+
+/// Screen history frame matching a topic (thumbnail left out of the export)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenFrameRecord {
+    pub id: String,
+    pub description: String,
+    pub app_name: Option<String>,
+    pub window_title: Option<String>,
+    pub captured_at: i64,
+}
+
+/// Everything stored about a topic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicExport {
+    pub query: String,
+    pub exported_at: i64,
+    pub episodes: Vec<EpisodeRecord>,
+    pub facts: Vec<FactRecord>,
+    pub entities: Vec<GraphNode>,
+    pub relationships: Vec<GraphEdge>,
+    pub clipboard_entries: Vec<ClipboardRecord>,
+    pub screen_frames: Vec<ScreenFrameRecord>,
+}
+
+/// Result of forgetting a topic (includes the export taken before deletion)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgetReport {
+    pub export: TopicExport,
+    pub episodes_deleted: usize,
+    pub facts_deleted: usize,
+    pub entities_deleted: usize,
+    pub relationships_deleted: usize,
+    pub clipboard_deleted: usize,
+    pub screen_frames_deleted: usize,
+}
+
+/// Trim the topic and reject ones too short to be a deliberate request
+pub fn normalize_topic(query: &str) -> Result<String> {
+    let topic = query.trim();
+    if topic.chars().count() < MIN_TOPIC_CHARS {
+        return Err(anyhow!("Topic must be at least {} characters", MIN_TOPIC_CHARS));
+    }
+    Ok(topic.to_string())
+}
+
+/// Substring LIKE pattern with `\` escaping the topic's own wildcards
+pub fn like_pattern(topic: &str) -> String {
+    let mut pattern = String::with_capacity(topic.len() + 2);
+    pattern.push('%');
+    for c in topic.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+/// Privacy service
+pub struct PrivacyService {
+    db: Arc<Mutex<Database>>,
+    graph: Arc<GraphStorage>,
+    rag: Arc<RagServiceV2>,
+}
+
+impl PrivacyService {
+    pub fn new(db: Arc<Mutex<Database>>, graph: Arc<GraphStorage>, rag: Arc<RagServiceV2>) -> Result<Self> {
+        log::info!("✓ Privacy Service initialized");
+        Ok(Self { db, graph, rag })
+    }
+
+    /// Export everything stored about a topic without deleting it
+    pub fn export_topic(&self, query: &str) -> Result<TopicExport> {
+        let topic = normalize_topic(query)?;
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        self.collect(db.conn(), &topic)
+    }
+
+    /// Delete everything stored about a topic across all memory stores
+    ///
+    /// Vectors go first: if the vector store fails nothing is deleted, and if a
+    /// later step fails the memories are already unretrievable and a retry
+    /// finds the rest.
+    pub async fn forget_topic(self: &Arc<Self>, query: &str) -> Result<ForgetReport> {
+        let topic = normalize_topic(query)?;
+        log::info!("Forgetting topic: {}", topic);
+
+        let service = Arc::clone(self);
+        let matched = tokio::task::spawn_blocking(move || service.export_topic(&topic)).await??;
+
+        let episode_ids: Vec<String> = matched.episodes.iter().map(|e| e.id.clone()).collect();
+        self.rag.delete_vectors(&episode_ids).await?;
+
+        let service = Arc::clone(self);
+        let report = tokio::task::spawn_blocking(move || service.delete_matches(&matched.query)).await??;
+
+        // Episodes stored while the vectors were being removed
+        let late_ids: Vec<String> = report.export.episodes
+            .iter()
+            .filter(|e| !episode_ids.contains(&e.id))
+            .map(|e| e.id.clone())
+            .collect();
+        self.rag.delete_vectors(&late_ids).await?;
+
+        Ok(report)
+    }
+
+    /// Delete the SQLite rows and graph entities matching a normalized topic
+    fn delete_matches(&self, topic: &str) -> Result<ForgetReport> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let tx = db.conn().unchecked_transaction()?;
+
+        // Export inside the transaction so it matches exactly what gets deleted
+        let export = self.collect(&tx, topic)?;
+
+        let mut episodes_deleted = 0;
+        for episode in &export.episodes {
+            episodes_deleted += tx.execute("DELETE FROM episodic_memory WHERE id = ?1", params![episode.id])?;
+            if table_exists(&tx, "memory_enhancements")? {
+                tx.execute("DELETE FROM memory_enhancements WHERE memory_id = ?1", params![episode.id])?;
+            }
+            if table_exists(&tx, "bm25_postings")? {
+                tx.execute("DELETE FROM bm25_postings WHERE id = ?1", params![episode.id])?;
+            }
+        }
+
+        let mut facts_deleted = 0;
+        for fact in &export.facts {
+            tx.execute("DELETE FROM wiki_fact_embeddings WHERE fact_id = ?1", params![fact.id])?;
+            if table_exists(&tx, "wiki_conflicts")? {
+                tx.execute(
+                    "DELETE FROM wiki_conflicts WHERE existing_fact_id = ?1 OR new_fact_id = ?1",
+                    params![fact.id],
+                )?;
+            }
+            facts_deleted += tx.execute("DELETE FROM wiki_facts WHERE id = ?1", params![fact.id])?;
+        }
+
+        let mut clipboard_deleted = 0;
+        for entry in &export.clipboard_entries {
+            clipboard_deleted += tx.execute("DELETE FROM clipboard_history WHERE id = ?1", params![entry.id])?;
+        }
+
+        let mut screen_frames_deleted = 0;
+        for frame in &export.screen_frames {
+            screen_frames_deleted += tx.execute("DELETE FROM screen_history_frames WHERE id = ?1", params![frame.id])?;
+        }
+
+        // Graph lives in its own database: commit it first, and only then the
+        // SQLite deletes, so a graph failure leaves both stores untouched
+        let entity_ids: Vec<String> = export.entities.iter().map(|e| e.entity_id.clone()).collect();
+        let entities_deleted = entity_ids.len();
+        let relationships_deleted = if entity_ids.is_empty() {
+            0
+        } else {
+            self.graph.forget_entities(&entity_ids).map_err(|e| anyhow!(e))?
+        };
+
+        tx.commit()?;
+
+        log::info!(
+            "✓ Forgot '{}': {} episodes, {} facts, {} entities, {} relationships, {} clipboard entries, {} screen frames",
+            topic,
+            episodes_deleted,
+            facts_deleted,
+            entities_deleted,
+            relationships_deleted,
+            clipboard_deleted,
+            screen_frames_deleted
+        );
+
+        Ok(ForgetReport {
+            export,
+            episodes_deleted,
+            facts_deleted,
+            entities_deleted,
+            relationships_deleted,
+            clipboard_deleted,
+            screen_frames_deleted,
+        })
+    }
+
+    /// Gather matching rows from every store
+    fn collect(&self, conn: &Connection, topic: &str) -> Result<TopicExport> {
+        let pattern = like_pattern(topic);
+
+        let mut stmt = conn.prepare(
+            "SELECT id, user_message, ai_response, created_at
+             FROM episodic_memory
+             WHERE user_message LIKE ?1 ESCAPE '\\' OR ai_response LIKE ?1 ESCAPE '\\'
+             ORDER BY created_at ASC",
+        )?;
+        let mut episodes = stmt
+            .query_map(params![pattern], episode_record)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let facts = if table_exists(conn, "wiki_facts")? {
+            let mut stmt = conn.prepare(
+                "SELECT id, statement, entity, category, confidence, source_conversation_id, learned_at
+                 FROM wiki_facts
+                 WHERE statement LIKE ?1 ESCAPE '\\' OR entity LIKE ?1 ESCAPE '\\'
+                 ORDER BY learned_at ASC",
+            )?;
+            let facts = stmt
+                .query_map(params![pattern], |row| {
+                    Ok(FactRecord {
+                        id: row.get(0)?,
+                        statement: row.get(1)?,
+                        entity: row.get(2)?,
+                        category: row.get(3)?,
+                        confidence: row.get(4)?,
+                        source_conversation_id: row.get(5)?,
+                        learned_at: row.get(6)?,
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            facts
+        } else {
+            Vec::new()
+        };
+
+        let clipboard_entries = if table_exists(conn, "clipboard_history")? {
+            let mut stmt = conn.prepare(
+                "SELECT id, content, source_app, created_at
+                 FROM clipboard_history
+                 WHERE content LIKE ?1 ESCAPE '\\'
+                 ORDER BY created_at ASC",
+            )?;
+            let entries = stmt
+                .query_map(params![pattern], |row| {
+                    Ok(ClipboardRecord {
+                        id: row.get(0)?,
+                        content: row.get(1)?,
+                        source_app: row.get(2)?,
+                        created_at: row.get(3)?,
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let linked = select_episode_ids(
+                conn,
+                "SELECT episode_ids FROM clipboard_history WHERE content LIKE ?1 ESCAPE '\\'",
+                params![pattern],
+            )?;
+            add_linked_episodes(conn, &linked, &mut episodes)?;
+            entries
+        } else {
+            Vec::new()
+        };
+
+        let screen_frames = if table_exists(conn, "screen_history_frames")? {
+            let mut stmt = conn.prepare(
+                "SELECT id, description, app_name, window_title, captured_at
+                 FROM screen_history_frames
+                 WHERE description LIKE ?1 ESCAPE '\\' OR window_title LIKE ?1 ESCAPE '\\'
+                 ORDER BY captured_at ASC",
+            )?;
+            let frames = stmt
+                .query_map(params![pattern], |row| {
+                    Ok(ScreenFrameRecord {
+                        id: row.get(0)?,
+                        description: row.get(1)?,
+                        app_name: row.get(2)?,
+                        window_title: row.get(3)?,
+                        captured_at: row.get(4)?,
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // Frames are indexed in RAG too
+            let linked = select_episode_ids(
+                conn,
+                "SELECT episode_ids FROM screen_history_frames
+                 WHERE description LIKE ?1 ESCAPE '\\' OR window_title LIKE ?1 ESCAPE '\\'",
+                params![pattern],
+            )?;
+            add_linked_episodes(conn, &linked, &mut episodes)?;
+            frames
+        } else {
+            Vec::new()
+        };
+
+        let (entities, relationships) = self.graph
+            .find_by_pattern(&pattern)
+            .map_err(|e| anyhow!(e))?;
+
+        Ok(TopicExport {
+            query: topic.to_string(),
+            exported_at: chrono::Utc::now().timestamp(),
+            episodes,
+            facts,
+            entities,
+            relationships,
+            clipboard_entries,
+            screen_frames,
+        })
+    }
+}
+
+/// Map an `id, user_message, ai_response, created_at` row
+fn episode_record(row: &rusqlite::Row) -> rusqlite::Result<EpisodeRecord> {
+    Ok(EpisodeRecord {
+        id: row.get(0)?,
+        user_message: row.get(1)?,
+        ai_response: row.get(2)?,
+        created_at: row.get(3)?,
+    })
+}
+
+/// Add episodes linked from another store, skipping ones already matched
+/// (a chunk of a long entry may not mention the topic itself)
+fn add_linked_episodes(conn: &Connection, ids: &[String], episodes: &mut Vec<EpisodeRecord>) -> Result<()> {
+    let mut stmt = conn.prepare(
+        "SELECT id, user_message, ai_response, created_at FROM episodic_memory WHERE id = ?1",
+    )?;
+    for id in ids {
+        if episodes.iter().any(|e| &e.id == id) {
+            continue;
+        }
+        if let Some(episode) = stmt.query_row(params![id], episode_record).optional()? {
+            episodes.push(episode);
+        }
+    }
+    Ok(())
+}
+
+/// Optional tables are created lazily by their services
+fn table_exists(conn: &Connection, table: &str) -> Result<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        params![table],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::clipboard_history::ClipboardHistoryService;
+    use crate::services::embedding::UnifiedEmbeddingService;
+    use crate::services::screen_history::{FrameInput, ScreenHistoryService};
+    use crate::services::vector_backend::{self, VectorBackendConfig, VectorBackendKind};
+
+    #[test]
+    fn test_normalize_topic() {
+        assert_eq!(normalize_topic("  my ex  ").unwrap(), "my ex");
+        assert!(normalize_topic(" ").is_err());
+        assert!(normalize_topic("a").is_err());
+        assert!(normalize_topic("서울").is_ok());
+    }
+
+    #[test]
+    fn test_like_pattern_escapes_wildcards() {
+        assert_eq!(like_pattern("Alice"), "%Alice%");
+        assert_eq!(like_pattern("100%_done"), "%100\\%\\_done%");
+
+        let conn = Connection::open_in_memory().unwrap();
+        let matches = |text: &str, topic: &str| -> bool {
+            conn.query_row(
+                "SELECT ?1 LIKE ?2 ESCAPE '\\'",
+                params![text, like_pattern(topic)],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        assert!(matches("I'm 100% sure", "100%"));
+        assert!(!matches("I'm 1000 sure", "100%"));
+        assert!(matches("Talked about ALICE today", "alice"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_forgotten_topic_is_not_retrieved() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let config = VectorBackendConfig { backend: VectorBackendKind::Sqlite, ..Default::default() };
+        vector_backend::save_config(&db, &config).unwrap();
+
+        let embedding = Arc::new(UnifiedEmbeddingService::new());
+        let rag = Arc::new(RagServiceV2::new_lazy(Arc::clone(&db), Arc::clone(&embedding), dir.path().join("lance_db")));
+        let kept = rag.store_episode("What should I cook tonight?", "Try a tomato pasta.", 0.8).await.unwrap();
+        let forgotten = rag.store_episode("Jordan called me again", "How did that make you feel?", 0.8).await.unwrap();
+
+        let clipboard = ClipboardHistoryService::new(Arc::clone(&db), Arc::clone(&rag)).unwrap();
+        clipboard.record_text("Jordan's new address").await.unwrap().unwrap();
+        let screen_history = Arc::new(ScreenHistoryService::new(Arc::clone(&db), Arc::clone(&rag)).unwrap());
+        screen_history
+            .record_frame(FrameInput {
+                thumbnail_base64: String::new(),
+                description: "Chat window".to_string(),
+                app_name: Some("Messages".to_string()),
+                window_title: Some("Messages - Jordan".to_string()),
+                captured_at: 1,
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(rag.get_vector_count().await.unwrap(), 4);
+
+        let graph = Arc::new(GraphStorage::new(":memory:").unwrap());
+        let privacy = Arc::new(PrivacyService::new(Arc::clone(&db), graph, Arc::clone(&rag)).unwrap());
+        let report = privacy.forget_topic("jordan").await.unwrap();
+        assert_eq!(report.episodes_deleted, 3);
+        assert_eq!(report.clipboard_deleted, 1);
+        assert_eq!(report.screen_frames_deleted, 1);
+        assert_eq!(report.export.clipboard_entries[0].content, "Jordan's new address");
+
+        let hits = rag.search_with_scores("Jordan called me again", 10).await.unwrap();
+        assert!(hits.iter().all(|(episode, _)| episode.id != forgotten));
+        assert!(hits.iter().any(|(episode, _)| episode.id == kept));
+        assert_eq!(rag.get_vector_count().await.unwrap(), 1);
+        assert!(privacy.export_topic("jordan").unwrap().screen_frames.is_empty());
+    }
+}
@@ -0,0 +1,781 @@
+//! Proactive Suggestion Engine (v3.9.0)
+//!
+//! Context-driven proactive suggestions that don't need screen analysis:
+//! combines the active window, upcoming calendar events, goals, and stale
+//! tasks into a few low-interruption suggestions per hour.
+//!
+//! Features:
+//! - Candidate suggestions from calendar, goal tracker, and task planner
+//! - Ambient context from the context enricher (phase5) or the active window
+//! - Low-interruption gating: hourly budget, quiet hours, focus apps (meetings, slides)
+//! - Accept/dismiss feedback stored as learning feedback to tune proactiveness
+
+#![allow(dead_code)]  // Phase 5: Proactive suggestions (some helpers used by future UI)
+
+use crate::database::Database;
+use crate::services::active_window::{ActiveWindow, ActiveWindowService};
+use crate::services::calendar::{CalendarEvent, CalendarService};
+#[cfg(feature = "phase5")]
+use crate::services::context_enricher::ContextEnricherService;
+use crate::services::conversation_language::ConversationLanguage;
+use crate::services::goal_tracker::{GoalReminder, GoalTrackerService};
+use crate::services::learning::{Feedback, LearningService};
+use crate::services::localization;
+use crate::services::notification::{
+    AppNotification, NotificationAction, NotificationService, NotificationSource, DEFAULT_SNOOZE_MINUTES,
+};
+use crate::services::proactive_manager::ProactiveSuggestion;
+use crate::services::task_planner::{Task, TaskPlannerService, TaskStatus};
+use anyhow::{anyhow, Result};
+use chrono::{Local, Timelike};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "phase5")]
+use std::sync::OnceLock;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Mutex as TokioMutex;
+
+/// The same candidate (event, goal, task) is suggested at most once per day
+const REPEAT_WINDOW_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Feedback samples needed before the acceptance rate affects the budget
+const MIN_FEEDBACK_SAMPLES: usize = 5;
+
+/// Proactive engine configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProactiveEngineConfig {
+    /// How often candidates are evaluated (in minutes)
+    pub check_interval_minutes: u64,
+
+    /// Upper bound of suggestions per hour (before learned scaling)
+    pub max_suggestions_per_hour: usize,
+
+    /// Suggest preparing for events starting within this many minutes
+    pub calendar_lookahead_minutes: i64,
+
+    /// Goals without a check-in for this many days are suggested
+    pub stale_goal_days: i64,
+
+    /// Pending / in-progress tasks untouched for this many days are suggested
+    pub stale_task_days: i64,
+
+    /// Minimum priority for a candidate to be delivered (0.0-1.0)
+    pub min_priority: f32,
+
+    /// No suggestions between these local hours (start > end wraps midnight)
+    pub quiet_hours: Option<(u32, u32)>,
+
+    /// App / title keywords where the user should not be interrupted
+    pub focus_apps: Vec<String>,
+}
+
+impl Default for ProactiveEngineConfig {
+    fn default() -> Self {
+        Self {
+            check_interval_minutes: 10,
+            max_suggestions_per_hour: 2,
+            calendar_lookahead_minutes: 15,
+            stale_goal_days: 7,
+            stale_task_days: 3,
+            min_priority: 0.4,
+            quiet_hours: Some((22, 8)),
+            focus_apps: vec![
+                "zoom".to_string(),
+                "teams".to_string(),
+                "facetime".to_string(),
+                "google meet".to_string(),
+                "keynote".to_string(),
+                "powerpoint".to_string(),
+                "presenting".to_string(),
+            ],
+        }
+    }
+}
+
+/// User reaction to a suggestion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SuggestionFeedback {
+    Accepted,
+    Dismissed,
+}
+
+impl SuggestionFeedback {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "accepted",
+            Self::Dismissed => "dismissed",
+        }
+    }
+}
+
+/// Engine statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProactiveEngineStats {
+    pub is_running: bool,
+    pub delivered_last_hour: usize,
+    pub hourly_budget: usize,
+    pub total_delivered: usize,
+    pub accepted: usize,
+    pub dismissed: usize,
+    pub acceptance_rate: Option<f32>,
+}
+
+/// Everything the engine looks at in one cycle
+#[derive(Debug, Clone, Default)]
+pub struct SuggestionSnapshot {
+    pub active_window: Option<ActiveWindow>,
+    /// Ambient context lines (context enricher)
+    pub context_notes: Vec<String>,
+    pub upcoming_events: Vec<CalendarEvent>,
+    pub stale_goals: Vec<GoalReminder>,
+    pub stale_tasks: Vec<Task>,
+    /// Language of the suggestion texts (v3.9.0)
+    pub language: ConversationLanguage,
+}
+
+/// A suggestion before gating
+#[derive(Debug, Clone)]
+pub struct SuggestionCandidate {
+    /// Identifies what the suggestion is about (e.g. "task:<id>") for repeat suppression
+    pub key: String,
+    pub trigger_type: String,
+    pub description: String,
+    pub suggestion: String,
+    pub priority: f32,
+    pub actions: Vec<NotificationAction>,
+}
+
+/// Hourly budget scaled by persona proactiveness and learned acceptance rate,
+/// never above the configured maximum and at least one (so feedback keeps flowing)
+pub fn hourly_budget(max_per_hour: usize, proactiveness: f32, acceptance_rate: Option<f32>) -> usize {
+    if max_per_hour == 0 {
+        return 0;
+    }
+
+    let mut factor = 0.5 + proactiveness.clamp(0.0, 1.0);
+    if let Some(rate) = acceptance_rate {
+        factor *= 0.5 + rate.clamp(0.0, 1.0);
+    }
+
+    ((max_per_hour as f32 * factor).round() as usize).clamp(1, max_per_hour)
+}
+
+/// Whether a local hour falls inside quiet hours (start > end wraps midnight)
+pub fn in_quiet_hours(hour: u32, quiet_hours: Option<(u32, u32)>) -> bool {
+    match quiet_hours {
+        Some((start, end)) if start > end => hour >= start || hour < end,
+        Some((start, end)) => hour >= start && hour < end,
+        None => false,
+    }
+}
+
+/// Whether the user is in a meeting / presentation / other focus app
+pub fn is_focus_context(window: Option<&ActiveWindow>, focus_apps: &[String]) -> bool {
+    let window = match window {
+        Some(window) => window,
+        None => return false,
+    };
+
+    let haystack = format!("{} {}", window.app_name, window.title).to_lowercase();
+    focus_apps
+        .iter()
+        .any(|keyword| !keyword.is_empty() && haystack.contains(&keyword.to_lowercase()))
+}
+
+/// Minutes until an event's start (None for all-day or unparsable events)
+fn minutes_until(event: &CalendarEvent, now: chrono::DateTime<chrono::Utc>) -> Option<i64> {
+    let start = event.start.date_time.as_deref()?;
+    let start = chrono::DateTime::parse_from_rfc3339(start).ok()?;
+    Some((start.with_timezone(&chrono::Utc) - now).num_minutes())
+}
+
+/// Whether the current window / context mentions a task (shared words of 4+ chars)
+fn relates_to_context(task: &Task, context_text: &str) -> bool {
+    task.title
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|w| w.chars().count() >= 4)
+        .any(|w| context_text.contains(&w))
+}
+
+/// Turn a snapshot into ranked candidates (highest priority first)
+pub fn generate_candidates(
+    snapshot: &SuggestionSnapshot,
+    config: &ProactiveEngineConfig,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Vec<SuggestionCandidate> {
+    let mut candidates = Vec::new();
+
+    // 1. Upcoming events: prepare shortly before they start
+    for event in &snapshot.upcoming_events {
+        let minutes = match minutes_until(event, now) {
+            Some(m) if (0..=config.calendar_lookahead_minutes).contains(&m) => m,
+            _ => continue,
+        };
+
+        candidates.push(SuggestionCandidate {
+            key: format!("event:{}", event.id.clone().unwrap_or_else(|| event.summary.clone())),
+            trigger_type: "calendar".to_string(),
+            description: event.summary.clone(),
+            suggestion: localization::event_suggestion(snapshot.language, &event.summary, minutes),
+            priority: 0.8,
+            actions: vec![
+                NotificationAction::OpenChat {
+                    prompt: Some(localization::event_prompt(snapshot.language, &event.summary)),
+                },
+                NotificationAction::Snooze { minutes: DEFAULT_SNOOZE_MINUTES },
+            ],
+        });
+    }
+
+    // 2. Stale tasks: boosted when related to what's on screen
+    let context_text = {
+        let mut text = snapshot.context_notes.join(" ");
+        if let Some(window) = &snapshot.active_window {
+            text.push(' ');
+            text.push_str(&window.app_name);
+            text.push(' ');
+            text.push_str(&window.title);
+        }
+        text.to_lowercase()
+    };
+
+    for task in &snapshot.stale_tasks {
+        let mut priority = 0.25 + task.priority.score() as f32 * 0.1;
+        if relates_to_context(task, &context_text) {
+            priority += 0.2;
+        }
+
+        candidates.push(SuggestionCandidate {
+            key: format!("task:{}", task.id),
+            trigger_type: "stale_task".to_string(),
+            description: task.title.clone(),
+            suggestion: localization::stale_task_suggestion(snapshot.language, &task.title, task.progress_percentage),
+            priority: priority.min(1.0),
+            actions: vec![
+                NotificationAction::RunPlan { task_id: task.id.clone() },
+                NotificationAction::Snooze { minutes: DEFAULT_SNOOZE_MINUTES },
+            ],
+        });
+    }
+
+    // 3. Stale goals: gentle check-ins
+    for goal in &snapshot.stale_goals {
+        candidates.push(SuggestionCandidate {
+            key: format!("goal:{}", goal.goal_id),
+            trigger_type: "goal".to_string(),
+            description: goal.goal_title.clone(),
+            suggestion: format!("{} ({}: {:.0}%)", goal.message, goal.goal_title, goal.progress),
+            priority: 0.45,
+            actions: vec![
+                NotificationAction::OpenChat {
+                    prompt: Some(localization::goal_prompt(snapshot.language, &goal.goal_title)),
+                },
+                NotificationAction::Snooze { minutes: DEFAULT_SNOOZE_MINUTES },
+            ],
+        });
+    }
+
+    candidates.retain(|c| c.priority >= config.min_priority);
+    candidates.sort_by(|a, b| b.priority.partial_cmp(&a.priority).unwrap_or(std::cmp::Ordering::Equal));
+    candidates
+}
+
+/// Proactive suggestion engine
+pub struct ProactiveSuggestionEngine {
+    db: Arc<Mutex<Database>>,
+    goal_tracker: Arc<GoalTrackerService>,
+    task_planner: Arc<TaskPlannerService>,
+    calendar: Arc<Mutex<Option<CalendarService>>>,
+    notifications: Arc<NotificationService>,
+    learning: LearningService,
+    active_window: ActiveWindowService,
+    #[cfg(feature = "phase5")]
+    context_enricher: OnceLock<Arc<ContextEnricherService>>,
+    config: Arc<Mutex<ProactiveEngineConfig>>,
+    is_running: Arc<AtomicBool>,
+    app_handle: Arc<TokioMutex<Option<AppHandle>>>,
+}
+
+impl ProactiveSuggestionEngine {
+    pub fn new(
+        db: Arc<Mutex<Database>>,
+        goal_tracker: Arc<GoalTrackerService>,
+        task_planner: Arc<TaskPlannerService>,
+        calendar: Arc<Mutex<Option<CalendarService>>>,
+        notifications: Arc<NotificationService>,
+    ) -> Result<Self> {
+        let learning = LearningService::new(Arc::clone(&db))?;
+        let active_window = ActiveWindowService::new()?;
+
+        let engine = Self {
+            db,
+            goal_tracker,
+            task_planner,
+            calendar,
+            notifications,
+            learning,
+            active_window,
+            #[cfg(feature = "phase5")]
+            context_enricher: OnceLock::new(),
+            config: Arc::new(Mutex::new(ProactiveEngineConfig::default())),
+            is_running: Arc::new(AtomicBool::new(false)),
+            app_handle: Arc::new(TokioMutex::new(None)),
+        };
+        engine.init_database()?;
+
+        log::info!("✓ Proactive Suggestion Engine initialized");
+        Ok(engine)
+    }
+
+    fn init_database(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let conn = db.conn();
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS proactive_suggestions (
+                id TEXT PRIMARY KEY,
+                candidate_key TEXT NOT NULL,
+                trigger_type TEXT NOT NULL,
+                suggestion TEXT NOT NULL,
+                priority REAL NOT NULL,
+                created_at INTEGER NOT NULL,
+                feedback TEXT,
+                feedback_at INTEGER
+            )",
+            [],
+        )?;
+
+        let _ = conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_proactive_suggestions_created ON proactive_suggestions(created_at)",
+            [],
+        );
+
+        Ok(())
+    }
+
+    /// Use the context enricher for ambient context (phase5)
+    #[cfg(feature = "phase5")]
+    pub fn attach_context_enricher(&self, enricher: Arc<ContextEnricherService>) {
+        let _ = self.context_enricher.set(enricher);
+    }
+
+    /// Set app handle for `proactive-suggestion` events
+    pub async fn set_app_handle(&self, handle: AppHandle) {
+        let mut app_handle = self.app_handle.lock().await;
+        *app_handle = Some(handle);
+    }
+
+    pub fn get_config(&self) -> ProactiveEngineConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    pub fn update_config(&self, config: ProactiveEngineConfig) {
+        *self.config.lock().unwrap() = config;
+        log::info!("Proactive engine config updated");
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.is_running.load(Ordering::SeqCst)
+    }
+
+    /// Start the periodic suggestion loop
+    pub fn start(self: &Arc<Self>) {
+        if self.is_running.swap(true, Ordering::SeqCst) {
+            return; // Already running
+        }
+
+        let engine = Arc::clone(self);
+        tauri::async_runtime::spawn(async move {
+            log::info!("Proactive suggestion engine started");
+
+            while engine.is_running() {
+                if let Err(e) = engine.run_cycle().await {
+                    log::warn!("Proactive suggestion cycle failed: {}", e);
+                }
+
+                let minutes = engine.get_config().check_interval_minutes.max(1);
+                tokio::time::sleep(std::time::Duration::from_secs(minutes * 60)).await;
+            }
+
+            log::info!("Proactive suggestion engine stopped");
+        });
+    }
+
+    pub fn stop(&self) {
+        self.is_running.store(false, Ordering::SeqCst);
+    }
+
+    /// Collect the current active window, calendar, goals, and tasks
+    async fn snapshot(&self, config: &ProactiveEngineConfig) -> SuggestionSnapshot {
+        let active_window = self.active_window.get_active_window().ok();
+
+        #[cfg(feature = "phase5")]
+        let context_notes = self
+            .context_enricher
+            .get()
+            .map(|enricher| enricher.ambient_context().into_iter().map(|p| p.content).collect())
+            .unwrap_or_default();
+        #[cfg(not(feature = "phase5"))]
+        let context_notes = Vec::new();
+
+        // Clone out of the lock: the calendar API is async
+        let calendar = self.calendar.lock().ok().and_then(|guard| guard.clone());
+        let upcoming_events = match calendar {
+            Some(calendar) if calendar.is_authenticated() => calendar
+                .get_upcoming_events("primary", 1)
+                .await
+                .unwrap_or_else(|e| {
+                    log::debug!("Calendar unavailable for proactive suggestions: {}", e);
+                    Vec::new()
+                }),
+            _ => Vec::new(),
+        };
+
+        let stale_goals = self
+            .goal_tracker
+            .get_stale_goals(config.stale_goal_days)
+            .unwrap_or_default();
+
+        let stale_before = chrono::Utc::now().timestamp() - config.stale_task_days * 24 * 60 * 60;
+        let stale_tasks = self
+            .task_planner
+            .get_all_tasks(None)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|t| t.parent_id.is_none())
+            .filter(|t| matches!(t.status, TaskStatus::Pending | TaskStatus::InProgress))
+            .filter(|t| t.started_at.unwrap_or(t.created_at) < stale_before)
+            .collect();
+
+        let language = self
+            .db
+            .lock()
+            .map(|db| localization::primary_language(db.conn()))
+            .unwrap_or(ConversationLanguage::English);
+
+        SuggestionSnapshot {
+            active_window,
+            context_notes,
+            upcoming_events,
+            stale_goals,
+            stale_tasks,
+            language,
+        }
+    }
+
+    /// Evaluate candidates once and deliver what fits the budget
+    pub async fn run_cycle(&self) -> Result<Vec<ProactiveSuggestion>> {
+        let config = self.get_config();
+
+        if in_quiet_hours(Local::now().hour(), config.quiet_hours) {
+            return Ok(Vec::new());
+        }
+
+        let snapshot = self.snapshot(&config).await;
+        if is_focus_context(snapshot.active_window.as_ref(), &config.focus_apps) {
+            log::debug!("Proactive suggestions held back: user is in a focus app");
+            return Ok(Vec::new());
+        }
+
+        let remaining = self
+            .current_budget(&config)?
+            .saturating_sub(self.delivered_since(chrono::Utc::now().timestamp_millis() - 60 * 60 * 1000)?);
+        if remaining == 0 {
+            return Ok(Vec::new());
+        }
+
+        let now = chrono::Utc::now();
+        let mut delivered = Vec::new();
+        for candidate in generate_candidates(&snapshot, &config, now) {
+            if delivered.len() >= remaining {
+                break;
+            }
+            if self.recently_suggested(&candidate.key, now.timestamp_millis())? {
+                continue;
+            }
+
+            let suggestion = self.deliver(candidate, snapshot.active_window.clone(), snapshot.language).await?;
+            delivered.push(suggestion);
+        }
+
+        Ok(delivered)
+    }
+
+    async fn deliver(
+        &self,
+        candidate: SuggestionCandidate,
+        active_window: Option<ActiveWindow>,
+        language: ConversationLanguage,
+    ) -> Result<ProactiveSuggestion> {
+        let suggestion = ProactiveSuggestion {
+            id: uuid::Uuid::new_v4().to_string(),
+            trigger_type: candidate.trigger_type.clone(),
+            description: candidate.description.clone(),
+            priority: candidate.priority,
+            suggestion: candidate.suggestion.clone(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            active_window,
+        };
+
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            db.conn().execute(
+                "INSERT INTO proactive_suggestions (id, candidate_key, trigger_type, suggestion, priority, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                rusqlite::params![
+                    &suggestion.id,
+                    &candidate.key,
+                    &suggestion.trigger_type,
+                    &suggestion.suggestion,
+                    suggestion.priority,
+                    suggestion.timestamp,
+                ],
+            )?;
+        }
+
+        if let Some(app) = self.app_handle.lock().await.as_ref() {
+            if let Err(e) = app.emit("proactive-suggestion", &suggestion) {
+                log::warn!("Failed to emit proactive suggestion: {}", e);
+            }
+        }
+
+        let mut notification = AppNotification::new(
+            NotificationSource::Proactive,
+            localization::proactive_title(language),
+            suggestion.suggestion.clone(),
+        );
+        for action in candidate.actions {
+            notification = notification.with_action(action);
+        }
+        self.notifications.notify(notification).await?;
+
+        log::info!("Delivered proactive suggestion ({}): {}", suggestion.trigger_type, candidate.key);
+        Ok(suggestion)
+    }
+
+    fn delivered_since(&self, since: i64) -> Result<usize> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let count: i64 = db.conn().query_row(
+            "SELECT COUNT(*) FROM proactive_suggestions WHERE created_at >= ?1",
+            [since],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    fn recently_suggested(&self, key: &str, now: i64) -> Result<bool> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let exists: bool = db.conn().query_row(
+            "SELECT EXISTS(SELECT 1 FROM proactive_suggestions WHERE candidate_key = ?1 AND created_at > ?2)",
+            rusqlite::params![key, now - REPEAT_WINDOW_MS],
+            |row| row.get(0),
+        )?;
+        Ok(exists)
+    }
+
+    /// (accepted, dismissed) over the last 100 rated suggestions
+    fn feedback_counts(&self) -> Result<(usize, usize)> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let (accepted, dismissed): (i64, i64) = db.conn().query_row(
+            "SELECT COALESCE(SUM(feedback = 'accepted'), 0), COALESCE(SUM(feedback = 'dismissed'), 0)
+             FROM (SELECT feedback FROM proactive_suggestions
+                   WHERE feedback IS NOT NULL ORDER BY feedback_at DESC LIMIT 100)",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok((accepted as usize, dismissed as usize))
+    }
+
+    fn acceptance_rate(&self) -> Result<Option<f32>> {
+        let (accepted, dismissed) = self.feedback_counts()?;
+        let total = accepted + dismissed;
+        Ok((total >= MIN_FEEDBACK_SAMPLES).then(|| accepted as f32 / total as f32))
+    }
+
+    fn current_persona(&self) -> crate::services::learning::PersonaParameters {
+        self.db
+            .lock()
+            .ok()
+            .and_then(|db| db.load_persona().ok())
+            .map(|persona| persona.to_learning_params())
+            .unwrap_or_default()
+    }
+
+    fn current_budget(&self, config: &ProactiveEngineConfig) -> Result<usize> {
+        Ok(hourly_budget(
+            config.max_suggestions_per_hour,
+            self.current_persona().proactiveness,
+            self.acceptance_rate()?,
+        ))
+    }
+
+    /// Record accept/dismiss for a suggestion (engine or screen-triggered) as learning feedback
+    pub fn record_feedback(&self, suggestion_id: &str, feedback: SuggestionFeedback) -> Result<()> {
+        let now = chrono::Utc::now().timestamp_millis();
+
+        let updated = {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            db.conn().execute(
+                "UPDATE proactive_suggestions SET feedback = ?1, feedback_at = ?2 WHERE id = ?3",
+                rusqlite::params![feedback.as_str(), now, suggestion_id],
+            )?
+        };
+
+        // Feeds LearningService::optimize_persona, which tunes `proactiveness`
+        self.learning.record_feedback(Feedback {
+            conversation_id: format!("proactive:{}", suggestion_id),
+            satisfaction: match feedback {
+                SuggestionFeedback::Accepted => 1.0,
+                SuggestionFeedback::Dismissed => 0.0,
+            },
+            timestamp: now / 1000,
+            persona_snapshot: self.current_persona(),
+        })?;
+
+        log::info!(
+            "Proactive suggestion {} {} (tracked: {})",
+            suggestion_id,
+            feedback.as_str(),
+            updated > 0
+        );
+        Ok(())
+    }
+
+    pub fn get_stats(&self) -> Result<ProactiveEngineStats> {
+        let config = self.get_config();
+        let (accepted, dismissed) = self.feedback_counts()?;
+
+        Ok(ProactiveEngineStats {
+            is_running: self.is_running(),
+            delivered_last_hour: self.delivered_since(chrono::Utc::now().timestamp_millis() - 60 * 60 * 1000)?,
+            hourly_budget: self.current_budget(&config)?,
+            total_delivered: self.delivered_since(0)?,
+            accepted,
+            dismissed,
+            acceptance_rate: self.acceptance_rate()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::calendar::EventDateTime;
+    use crate::services::task_planner::TaskPriority;
+
+    fn task(id: &str, title: &str, priority: TaskPriority) -> Task {
+        Task {
+            id: id.to_string(),
+            parent_id: None,
+            title: title.to_string(),
+            description: String::new(),
+            status: TaskStatus::Pending,
+            priority,
+            dependencies: Vec::new(),
+            estimated_duration_minutes: None,
+            actual_duration_minutes: None,
+            progress_percentage: 20.0,
+            created_at: 0,
+            started_at: None,
+            completed_at: None,
+            tags: Vec::new(),
+            due_at: None,
+            recurrence_id: None,
+        }
+    }
+
+    fn event(summary: &str, start: chrono::DateTime<chrono::Utc>) -> CalendarEvent {
+        let at = |t: chrono::DateTime<chrono::Utc>| EventDateTime {
+            date_time: Some(t.to_rfc3339()),
+            date: None,
+            time_zone: None,
+        };
+        CalendarEvent {
+            id: Some(summary.to_string()),
+            summary: summary.to_string(),
+            description: None,
+            location: None,
+            start: at(start),
+            end: at(start + chrono::Duration::hours(1)),
+            attendees: Vec::new(),
+            reminders: None,
+            color_id: None,
+            recurrence: None,
+            status: None,
+            visibility: None,
+        }
+    }
+
+    #[test]
+    fn test_hourly_budget() {
+        assert_eq!(hourly_budget(0, 1.0, Some(1.0)), 0);
+        assert_eq!(hourly_budget(4, 0.5, None), 4);
+        assert_eq!(hourly_budget(4, 0.0, None), 2);
+        // Mostly dismissed: throttled but never silenced
+        assert_eq!(hourly_budget(4, 0.0, Some(0.0)), 1);
+        // Never above the configured maximum
+        assert_eq!(hourly_budget(2, 1.0, Some(1.0)), 2);
+    }
+
+    #[test]
+    fn test_interruption_gating() {
+        assert!(in_quiet_hours(23, Some((22, 8))));
+        assert!(in_quiet_hours(3, Some((22, 8))));
+        assert!(!in_quiet_hours(12, Some((22, 8))));
+        assert!(in_quiet_hours(13, Some((12, 14))));
+        assert!(!in_quiet_hours(3, None));
+
+        let zoom = ActiveWindow {
+            title: "Weekly sync".to_string(),
+            app_name: "zoom.us".to_string(),
+            bundle_id: None,
+            process_id: None,
+        };
+        let config = ProactiveEngineConfig::default();
+        assert!(is_focus_context(Some(&zoom), &config.focus_apps));
+        assert!(!is_focus_context(None, &config.focus_apps));
+    }
+
+    #[test]
+    fn test_generate_candidates() {
+        let now = chrono::Utc::now();
+        let snapshot = SuggestionSnapshot {
+            active_window: Some(ActiveWindow {
+                title: "invoice_export.rs - garden".to_string(),
+                app_name: "Code".to_string(),
+                bundle_id: None,
+                process_id: None,
+            }),
+            context_notes: Vec::new(),
+            upcoming_events: vec![
+                event("Design review", now + chrono::Duration::minutes(10)),
+                event("Tomorrow's offsite", now + chrono::Duration::hours(20)),
+            ],
+            stale_goals: Vec::new(),
+            stale_tasks: vec![
+                task("t1", "Fix invoice export", TaskPriority::Low),
+                task("t2", "Write blog post", TaskPriority::Low),
+            ],
+            language: ConversationLanguage::English,
+        };
+
+        let candidates = generate_candidates(&snapshot, &ProactiveEngineConfig::default(), now);
+        let keys: Vec<&str> = candidates.iter().map(|c| c.key.as_str()).collect();
+
+        // Far-away events and unrelated low-priority tasks are filtered out
+        assert_eq!(keys, vec!["event:Design review", "task:t1"]);
+        assert_eq!(
+            candidates[1].actions[0],
+            NotificationAction::RunPlan { task_id: "t1".to_string() }
+        );
+
+        // Suggestion texts follow the snapshot language
+        let korean = SuggestionSnapshot { language: ConversationLanguage::Korean, ..snapshot };
+        let candidates = generate_candidates(&korean, &ProactiveEngineConfig::default(), now);
+        assert!(candidates[0].suggestion.contains("분 후에 시작해요"));
+    }
+}
@@ -5,7 +5,7 @@
 
 use anyhow::Result;
 use log::{info, warn};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::Mutex as TokioMutex;
 use tokio::time::interval;
@@ -13,6 +13,7 @@ use tauri::{AppHandle, Emitter};
 use super::llava::{LlavaService, ProactiveTrigger};
 use super::screen::ScreenCaptureService;
 use super::active_window::{ActiveWindowService, ActiveWindow};
+use super::notification::{AppNotification, NotificationAction, NotificationService, NotificationSource, DEFAULT_SNOOZE_MINUTES};
 
 /// Proactive AI manager for background monitoring and intelligent interruptions
 /// This implements the "AI-Led" mode from the spec
@@ -25,6 +26,8 @@ pub struct ProactiveManager {
     last_trigger_time: Arc<Mutex<SystemTime>>,
     /// Tauri app handle for emitting events to frontend
     app_handle: Arc<TokioMutex<Option<AppHandle>>>,
+    /// Native notifications for suggestions (v3.9.0)
+    notifications: OnceLock<Arc<NotificationService>>,
 }
 
 /// Proactive mode configuration
@@ -102,9 +105,15 @@ impl ProactiveManager {
             active_window_service,
             last_trigger_time: Arc::new(Mutex::new(UNIX_EPOCH)),
             app_handle: Arc::new(TokioMutex::new(None)),
+            notifications: OnceLock::new(),
         })
     }
 
+    /// Attach the notification service used for suggestions (v3.9.0)
+    pub fn attach_notifications(&self, notifications: Arc<NotificationService>) {
+        let _ = self.notifications.set(notifications);
+    }
+
     /// Set the Tauri app handle for event emission
     pub async fn set_app_handle(&self, handle: AppHandle) {
         let mut app_handle = self.app_handle.lock().await;
@@ -125,6 +134,23 @@ impl ProactiveManager {
         }
     }
 
+    /// Show a suggestion as a native notification (v3.9.0)
+    async fn notify_suggestion(notifications: &Option<Arc<NotificationService>>, suggestion: &ProactiveSuggestion) {
+        if let Some(notifications) = notifications {
+            let notification = AppNotification::new(
+                NotificationSource::Proactive,
+                "Adam noticed something",
+                suggestion.suggestion.clone(),
+            )
+            .with_action(NotificationAction::OpenChat { prompt: Some(suggestion.suggestion.clone()) })
+            .with_action(NotificationAction::Snooze { minutes: DEFAULT_SNOOZE_MINUTES });
+
+            if let Err(e) = notifications.notify(notification).await {
+                warn!("Failed to send proactive notification: {}", e);
+            }
+        }
+    }
+
     /// Start proactive monitoring
     pub async fn start(&self) -> Result<()> {
         let mut is_active = self.is_active.lock().unwrap();
@@ -152,6 +178,7 @@ impl ProactiveManager {
         let active_window_clone = self.active_window_service.clone();
         let last_trigger_clone = Arc::clone(&self.last_trigger_time);
         let app_handle_clone = Arc::clone(&self.app_handle);
+        let notifications_clone = self.notifications.get().cloned();
 
         tokio::spawn(async move {
             Self::monitoring_loop(
@@ -162,6 +189,7 @@ impl ProactiveManager {
                 active_window_clone,
                 last_trigger_clone,
                 app_handle_clone,
+                notifications_clone,
             )
             .await;
         });
@@ -214,6 +242,7 @@ impl ProactiveManager {
     }
 
     /// Main monitoring loop
+    #[allow(clippy::too_many_arguments)]
     async fn monitoring_loop(
         is_active: Arc<Mutex<bool>>,
         config: Arc<Mutex<ProactiveConfig>>,
@@ -222,6 +251,7 @@ impl ProactiveManager {
         active_window: ActiveWindowService,
         last_trigger: Arc<Mutex<SystemTime>>,
         app_handle: Arc<TokioMutex<Option<AppHandle>>>,
+        notifications: Option<Arc<NotificationService>>,
     ) {
         let mut interval_timer = interval(Duration::from_secs(30)); // Will be updated from config
 
@@ -265,6 +295,7 @@ impl ProactiveManager {
 
                         // Emit suggestion to frontend via Tauri events
                         Self::emit_suggestion(&app_handle, &suggestion).await;
+                        Self::notify_suggestion(&notifications, &suggestion).await;
                     }
                 }
                 Ok(None) => {
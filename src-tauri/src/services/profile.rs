@@ -0,0 +1,438 @@
+//! Profile Service (v3.9.0)
+//!
+//! Isolated workspaces ("Work", "Personal") that can be switched without restarting.
+//!
+//! Features:
+//! - Per-profile data.db (persona settings, conversations, service tables)
+//! - Per-profile LanceDB store and knowledge graph database
+//! - Registry kept in `profiles.json` next to the default profile's data
+//! - Live switching: shared database handles are reopened in place, so every
+//!   service holding them follows the active profile
+
+#![allow(dead_code)]  // Phase 5: Profiles (rename/delete used by future settings UI)
+
+use crate::database::Database;
+use anyhow::{anyhow, Context, Result};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// The profile backed by the original (pre-profiles) data directory
+pub const DEFAULT_PROFILE_ID: &str = "default";
+
+const REGISTRY_FILE: &str = "profiles.json";
+
+/// A workspace with its own persona, memory, and conversations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+    pub created_at: i64, // Unix millis
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ProfileRegistry {
+    active: String,
+    profiles: Vec<Profile>,
+}
+
+impl Default for ProfileRegistry {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_PROFILE_ID.to_string(),
+            profiles: vec![Profile {
+                id: DEFAULT_PROFILE_ID.to_string(),
+                name: "Personal".to_string(),
+                created_at: chrono::Utc::now().timestamp_millis(),
+            }],
+        }
+    }
+}
+
+impl ProfileRegistry {
+    fn load(data_dir: &Path) -> Result<Self> {
+        let path = data_dir.join(REGISTRY_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let json = std::fs::read_to_string(&path).context("Failed to read profile registry")?;
+        serde_json::from_str(&json).context("Failed to parse profile registry")
+    }
+
+    fn save(&self, data_dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(data_dir)?;
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(data_dir.join(REGISTRY_FILE), json).context("Failed to write profile registry")
+    }
+
+    fn get(&self, profile_id: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|p| p.id == profile_id)
+    }
+}
+
+/// Storage locations of a profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfilePaths {
+    pub db: PathBuf,
+    pub lance_db: PathBuf,
+    pub knowledge_graph: PathBuf,
+}
+
+impl ProfilePaths {
+    pub fn for_profile(data_dir: &Path, profile_id: &str) -> Self {
+        let root = if profile_id == DEFAULT_PROFILE_ID {
+            data_dir.to_path_buf()
+        } else {
+            data_dir.join("profiles").join(profile_id)
+        };
+        Self {
+            db: root.join("data.db"),
+            lance_db: root.join("lance_db"),
+            knowledge_graph: root.join("knowledge_graph.db"),
+        }
+    }
+}
+
+/// Turn a display name into a filesystem-safe profile id
+pub fn slugify_profile_id(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.trim().to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_matches('-').to_string();
+    if slug.is_empty() {
+        "profile".to_string()
+    } else {
+        slug
+    }
+}
+
+/// Pick an id that isn't taken yet ("work", "work-2", ...)
+fn unique_profile_id(base: &str, taken: &[Profile]) -> String {
+    let is_taken = |id: &str| id == DEFAULT_PROFILE_ID || taken.iter().any(|p| p.id == id);
+    if !is_taken(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|id| !is_taken(id))
+        .unwrap_or_else(|| base.to_string())
+}
+
+/// Make a `CREATE ...` statement from sqlite_master idempotent
+fn to_if_not_exists(sql: &str) -> String {
+    if sql.to_uppercase().contains("IF NOT EXISTS") {
+        return sql.to_string();
+    }
+    for prefix in [
+        "CREATE VIRTUAL TABLE ",
+        "CREATE UNIQUE INDEX ",
+        "CREATE TABLE ",
+        "CREATE INDEX ",
+        "CREATE TRIGGER ",
+    ] {
+        if let Some(head) = sql.get(..prefix.len()) {
+            if head.eq_ignore_ascii_case(prefix) {
+                return format!("{}IF NOT EXISTS {}", head, &sql[prefix.len()..]);
+            }
+        }
+    }
+    sql.to_string()
+}
+
+/// Replay every table/index/trigger definition from `from` onto `to`
+///
+/// Services create their tables once at startup against the active database, so a
+/// freshly created (or switched-to) profile database borrows their schema here.
+/// Virtual tables go first so their shadow tables already exist when replayed.
+pub fn clone_schema(from: &Connection, to: &Connection) -> Result<usize> {
+    let mut stmt = from.prepare(
+        "SELECT sql FROM sqlite_master
+         WHERE sql IS NOT NULL AND name NOT LIKE 'sqlite_%'
+         ORDER BY CASE
+             WHEN type = 'table' AND sql LIKE 'CREATE VIRTUAL TABLE%' THEN 0
+             WHEN type = 'table' THEN 1
+             WHEN type = 'index' THEN 2
+             ELSE 3
+         END",
+    )?;
+    let statements: Vec<String> = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut applied = 0;
+    for sql in statements {
+        match to.execute_batch(&to_if_not_exists(&sql)) {
+            Ok(()) => applied += 1,
+            Err(e) => log::debug!("Skipped schema statement while cloning: {}", e),
+        }
+    }
+    Ok(applied)
+}
+
+/// Profile service
+pub struct ProfileService {
+    data_dir: PathBuf,
+    db: Arc<Mutex<Database>>,
+    connections: Mutex<Vec<Arc<Mutex<Connection>>>>,
+    registry: Mutex<ProfileRegistry>,
+}
+
+impl ProfileService {
+    pub fn new(data_dir: PathBuf, db: Arc<Mutex<Database>>) -> Result<Self> {
+        let registry = ProfileRegistry::load(&data_dir)?;
+        registry.save(&data_dir)?;
+
+        log::info!("✓ Profile Service initialized (active: {})", registry.active);
+        Ok(Self {
+            data_dir,
+            db,
+            connections: Mutex::new(Vec::new()),
+            registry: Mutex::new(registry),
+        })
+    }
+
+    /// Storage locations of the profile that was active when the app last ran
+    pub fn active_paths(data_dir: &Path) -> Result<ProfilePaths> {
+        let registry = ProfileRegistry::load(data_dir)?;
+        Ok(ProfilePaths::for_profile(data_dir, &registry.active))
+    }
+
+    /// Storage locations of a registered profile, without switching to it (v3.9.0, garden-cli)
+    pub fn registered_paths(data_dir: &Path, profile_id: &str) -> Result<ProfilePaths> {
+        let registry = ProfileRegistry::load(data_dir)?;
+        if registry.get(profile_id).is_none() {
+            return Err(anyhow!("Profile not found: {}", profile_id));
+        }
+        Ok(ProfilePaths::for_profile(data_dir, profile_id))
+    }
+
+    /// Register a raw connection to data.db that must follow profile switches
+    pub fn bind_connection(&self, conn: Arc<Mutex<Connection>>) {
+        if let Ok(mut connections) = self.connections.lock() {
+            connections.push(conn);
+        }
+    }
+
+    pub fn list_profiles(&self) -> Result<Vec<Profile>> {
+        let registry = self.registry.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        Ok(registry.profiles.clone())
+    }
+
+    pub fn active_profile(&self) -> Result<Profile> {
+        let registry = self.registry.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        registry
+            .get(&registry.active)
+            .cloned()
+            .ok_or_else(|| anyhow!("Active profile not found: {}", registry.active))
+    }
+
+    pub fn paths(&self, profile_id: &str) -> ProfilePaths {
+        ProfilePaths::for_profile(&self.data_dir, profile_id)
+    }
+
+    /// Create a new, empty profile (does not switch to it)
+    pub fn create_profile(&self, name: &str) -> Result<Profile> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow!("Profile name cannot be empty"));
+        }
+
+        let mut registry = self.registry.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let profile = Profile {
+            id: unique_profile_id(&slugify_profile_id(name), &registry.profiles),
+            name: name.to_string(),
+            created_at: chrono::Utc::now().timestamp_millis(),
+        };
+
+        // Clone the active schema into the blank file first (keeps migrated columns),
+        // then Database::open fills in anything missing plus the default persona
+        let paths = self.paths(&profile.id);
+        if let Some(parent) = paths.db.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create profile directory")?;
+        }
+        {
+            let new_conn = crate::services::encryption::open_connection(&paths.db)?;
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            clone_schema(db.conn(), &new_conn)?;
+        }
+        Database::open(&paths.db)?;
+
+        registry.profiles.push(profile.clone());
+        registry.save(&self.data_dir)?;
+
+        log::info!("Created profile '{}' ({})", profile.name, profile.id);
+        Ok(profile)
+    }
+
+    pub fn rename_profile(&self, profile_id: &str, name: &str) -> Result<Profile> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow!("Profile name cannot be empty"));
+        }
+
+        let mut registry = self.registry.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let profile = registry
+            .profiles
+            .iter_mut()
+            .find(|p| p.id == profile_id)
+            .ok_or_else(|| anyhow!("Profile not found: {}", profile_id))?;
+        profile.name = name.to_string();
+        let profile = profile.clone();
+        registry.save(&self.data_dir)?;
+        Ok(profile)
+    }
+
+    /// Delete a profile and all of its data
+    pub fn delete_profile(&self, profile_id: &str) -> Result<()> {
+        if profile_id == DEFAULT_PROFILE_ID {
+            return Err(anyhow!("The default profile cannot be deleted"));
+        }
+
+        let mut registry = self.registry.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        if registry.active == profile_id {
+            return Err(anyhow!("Switch to another profile before deleting this one"));
+        }
+        if registry.get(profile_id).is_none() {
+            return Err(anyhow!("Profile not found: {}", profile_id));
+        }
+
+        let root = self.data_dir.join("profiles").join(profile_id);
+        if root.exists() {
+            std::fs::remove_dir_all(&root).context("Failed to remove profile data")?;
+        }
+
+        registry.profiles.retain(|p| p.id != profile_id);
+        registry.save(&self.data_dir)?;
+
+        log::info!("Deleted profile {}", profile_id);
+        Ok(())
+    }
+
+    /// Reopen the shared database handles on another profile
+    ///
+    /// Returns the new profile's paths so the caller can rebind the stores it owns
+    /// (AppState database, RAG vector store, knowledge graph).
+    pub fn switch_profile(&self, profile_id: &str) -> Result<ProfilePaths> {
+        let mut registry = self.registry.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        if registry.get(profile_id).is_none() {
+            return Err(anyhow!("Profile not found: {}", profile_id));
+        }
+
+        let paths = self.paths(profile_id);
+        if registry.active == profile_id {
+            return Ok(paths);
+        }
+
+        self.rebind_handles(&paths)?;
+
+        registry.active = profile_id.to_string();
+        registry.save(&self.data_dir)?;
+
+        log::info!("Switched to profile {}", profile_id);
+        Ok(paths)
+    }
+
+    /// Reopen the shared handles on the active profile (v3.9.0: after unlocking encryption)
+    pub fn reload_active(&self) -> Result<ProfilePaths> {
+        let registry = self.registry.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let paths = self.paths(&registry.active);
+        self.rebind_handles(&paths)?;
+        Ok(paths)
+    }
+
+    fn rebind_handles(&self, paths: &ProfilePaths) -> Result<()> {
+        let new_db = Database::open(&paths.db)?;
+        {
+            let mut db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            clone_schema(db.conn(), new_db.conn())?;
+            *db = new_db;
+        }
+
+        let connections = self.connections.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        for conn in connections.iter() {
+            let new_conn = crate::services::encryption::open_connection(&paths.db)
+                .context("Failed to reopen connection")?;
+            *conn.lock().map_err(|e| anyhow!("Connection lock error: {}", e))? = new_conn;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify_and_unique_ids() {
+        assert_eq!(slugify_profile_id("  Work Stuff! "), "work-stuff");
+        assert_eq!(slugify_profile_id("업무"), "profile");
+
+        let taken = vec![Profile { id: "work".to_string(), name: "Work".to_string(), created_at: 0 }];
+        assert_eq!(unique_profile_id("work", &taken), "work-2");
+        assert_eq!(unique_profile_id("default", &taken), "default-2");
+        assert_eq!(unique_profile_id("home", &taken), "home");
+    }
+
+    #[test]
+    fn test_profile_paths() {
+        let data_dir = Path::new("/data");
+        let default = ProfilePaths::for_profile(data_dir, DEFAULT_PROFILE_ID);
+        assert_eq!(default.db, PathBuf::from("/data/data.db"));
+
+        let work = ProfilePaths::for_profile(data_dir, "work");
+        assert_eq!(work.lance_db, PathBuf::from("/data/profiles/work/lance_db"));
+        assert_eq!(work.knowledge_graph, PathBuf::from("/data/profiles/work/knowledge_graph.db"));
+    }
+
+    #[test]
+    fn test_create_profile_keeps_migrated_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Mutex::new(Database::open(&dir.path().join("data.db")).unwrap()));
+        crate::services::temporal_memory::TemporalMemoryService::new(Arc::clone(&db)).unwrap();
+
+        let service = ProfileService::new(dir.path().to_path_buf(), db).unwrap();
+        let profile = service.create_profile("Work").unwrap();
+        assert_eq!(profile.id, "work");
+
+        let new_db = Database::open(&service.paths("work").db).unwrap();
+        assert!(new_db.load_persona().is_ok());
+        let has_retention: i64 = new_db
+            .conn()
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('episodic_memory') WHERE name = 'retention_score'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(has_retention, 1);
+        assert_eq!(service.list_profiles().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_clone_schema() {
+        let from = Connection::open_in_memory().unwrap();
+        from.execute_batch(
+            "CREATE TABLE goals (id TEXT PRIMARY KEY, title TEXT);
+             CREATE INDEX idx_goals_title ON goals(title);
+             INSERT INTO goals VALUES ('g1', 'secret');",
+        )
+        .unwrap();
+
+        let to = Connection::open_in_memory().unwrap();
+        to.execute_batch("CREATE TABLE goals (id TEXT PRIMARY KEY, title TEXT);").unwrap();
+
+        assert_eq!(clone_schema(&from, &to).unwrap(), 2);
+        let rows: i64 = to.query_row("SELECT COUNT(*) FROM goals", [], |r| r.get(0)).unwrap();
+        assert_eq!(rows, 0);
+        assert_eq!(
+            to_if_not_exists("create index idx ON t(a)"),
+            "create index IF NOT EXISTS idx ON t(a)"
+        );
+    }
+}
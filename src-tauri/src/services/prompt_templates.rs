@@ -0,0 +1,383 @@
+//! Prompt Template Service (v3.9.0)
+//!
+//! Reusable prompts with `{{variables}}`, inserted into chat or bound to hotkeys.
+//!
+//! Features:
+//! - Templates organized in categories, persisted in `prompt_templates`
+//! - Built-in variables resolved in the backend: `{{selection}}` (simulated
+//!   copy), `{{clipboard}}` and `{{file}}` (contents of a chosen file)
+//! - Any other `{{name}}` is filled from caller-supplied values; unresolved
+//!   placeholders are left in place and reported so the UI can ask for them
+//! - Optional global hotkey per template
+
+#![allow(dead_code)]  // Phase 5: Prompt templates (category management used by future UI)
+
+use crate::database::Database;
+use crate::services::quick_ask::{normalize_accelerator, validate_accelerator, QuickAskService, QuickAskSource};
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+
+pub const DEFAULT_CATEGORY: &str = "General";
+
+pub const VAR_SELECTION: &str = "selection";
+pub const VAR_CLIPBOARD: &str = "clipboard";
+pub const VAR_FILE: &str = "file";
+
+/// `{{file}}` reads at most this much of the file
+const MAX_FILE_BYTES: u64 = 200 * 1024;
+
+const MAX_NAME_CHARS: usize = 100;
+const MAX_BODY_CHARS: usize = 20_000;
+
+fn variable_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").unwrap())
+}
+
+/// Variable names used in a template body, in order of first use
+pub fn extract_variables(body: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for capture in variable_regex().captures_iter(body) {
+        let name = capture[1].to_lowercase();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Substitute `values` (keys lowercase); returns the text and the variables left unresolved
+pub fn render(body: &str, values: &HashMap<String, String>) -> (String, Vec<String>) {
+    let mut missing: Vec<String> = Vec::new();
+    let text = variable_regex()
+        .replace_all(body, |capture: &regex::Captures| {
+            let name = capture[1].to_lowercase();
+            match values.get(&name) {
+                Some(value) => value.clone(),
+                None => {
+                    if !missing.contains(&name) {
+                        missing.push(name);
+                    }
+                    capture[0].to_string()
+                }
+            }
+        })
+        .into_owned();
+    (text, missing)
+}
+
+/// A saved template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub id: String,
+    pub name: String,
+    pub category: String,
+    pub body: String,
+    /// Global shortcut that inserts the template into chat
+    pub hotkey: Option<String>,
+    /// Variables used in `body`
+    pub variables: Vec<String>,
+    pub created_at: i64, // Unix millis
+    pub updated_at: i64,
+}
+
+/// Template as sent by the editor (`id: None` creates a new one)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptTemplateInput {
+    pub id: Option<String>,
+    pub name: String,
+    pub category: Option<String>,
+    pub body: String,
+    pub hotkey: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateCategory {
+    pub name: String,
+    pub count: usize,
+}
+
+/// Values for a render beyond what the backend captures itself
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RenderRequest {
+    /// File read for `{{file}}`
+    pub file_path: Option<String>,
+    /// Custom variables (and overrides for built-ins)
+    pub values: HashMap<String, String>,
+}
+
+/// A template with its variables filled in (also the `prompt-template-insert` event payload)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderedPrompt {
+    pub template_id: String,
+    pub text: String,
+    /// Placeholders still in `text`
+    pub missing: Vec<String>,
+}
+
+pub fn init_tables(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS prompt_templates (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            category TEXT NOT NULL,
+            body TEXT NOT NULL,
+            hotkey TEXT,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_prompt_templates_category ON prompt_templates(category);",
+    )?;
+    Ok(())
+}
+
+fn row_to_template(row: &rusqlite::Row) -> rusqlite::Result<PromptTemplate> {
+    let body: String = row.get(3)?;
+    Ok(PromptTemplate {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        category: row.get(2)?,
+        variables: extract_variables(&body),
+        body,
+        hotkey: row.get(4)?,
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+    })
+}
+
+const TEMPLATE_COLUMNS: &str = "id, name, category, body, hotkey, created_at, updated_at";
+
+/// Text of a file for `{{file}}`, truncated to `MAX_FILE_BYTES`
+fn read_file_text(path: &Path) -> Result<String> {
+    let file = std::fs::File::open(path).map_err(|e| anyhow!("Failed to open {}: {}", path.display(), e))?;
+    let mut bytes = Vec::new();
+    file.take(MAX_FILE_BYTES).read_to_end(&mut bytes)?;
+    if bytes.contains(&0) {
+        return Err(anyhow!("{} is not a text file", path.display()));
+    }
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Prompt template service
+pub struct PromptTemplateService {
+    db: Arc<Mutex<Database>>,
+}
+
+impl PromptTemplateService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        {
+            let db_guard = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            init_tables(db_guard.conn())?;
+        }
+
+        log::info!("✓ Prompt Template Service initialized");
+        Ok(Self { db })
+    }
+
+    /// Templates sorted by category and name, optionally of one category
+    pub fn list(&self, category: Option<&str>) -> Result<Vec<PromptTemplate>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(&format!(
+            "SELECT {} FROM prompt_templates
+             WHERE ?1 IS NULL OR category = ?1
+             ORDER BY category COLLATE NOCASE, name COLLATE NOCASE",
+            TEMPLATE_COLUMNS
+        ))?;
+        let templates = stmt
+            .query_map([category], row_to_template)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(templates)
+    }
+
+    pub fn get(&self, id: &str) -> Result<PromptTemplate> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn()
+            .query_row(
+                &format!("SELECT {} FROM prompt_templates WHERE id = ?1", TEMPLATE_COLUMNS),
+                [id],
+                row_to_template,
+            )
+            .optional()?
+            .ok_or_else(|| anyhow!("Template not found: {}", id))
+    }
+
+    pub fn categories(&self) -> Result<Vec<TemplateCategory>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT category, COUNT(*) FROM prompt_templates GROUP BY category ORDER BY category COLLATE NOCASE",
+        )?;
+        let categories = stmt
+            .query_map([], |row| {
+                Ok(TemplateCategory {
+                    name: row.get(0)?,
+                    count: row.get::<_, i64>(1)? as usize,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(categories)
+    }
+
+    /// Create or update a template. Hotkeys must be valid and not used by another template.
+    pub fn save(&self, input: PromptTemplateInput) -> Result<PromptTemplate> {
+        let name = input.name.trim();
+        if name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+            return Err(anyhow!("Template name must be 1-{} characters", MAX_NAME_CHARS));
+        }
+        if input.body.trim().is_empty() || input.body.chars().count() > MAX_BODY_CHARS {
+            return Err(anyhow!("Template text must be 1-{} characters", MAX_BODY_CHARS));
+        }
+        let category = input
+            .category
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .unwrap_or(DEFAULT_CATEGORY)
+            .to_string();
+        let hotkey = input.hotkey.as_deref().map(str::trim).filter(|h| !h.is_empty());
+        if let Some(hotkey) = hotkey {
+            validate_accelerator(hotkey)?;
+            let wanted = normalize_accelerator(hotkey);
+            let taken = self.list(None)?.into_iter().find(|other| {
+                Some(&other.id) != input.id.as_ref()
+                    && other.hotkey.as_deref().map(normalize_accelerator).as_ref() == Some(&wanted)
+            });
+            if let Some(other) = taken {
+                return Err(anyhow!("Hotkey {} is already used by '{}'", hotkey, other.name));
+            }
+        }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let id = {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            let conn = db.conn();
+            match &input.id {
+                Some(id) => {
+                    let updated = conn.execute(
+                        "UPDATE prompt_templates SET name = ?1, category = ?2, body = ?3, hotkey = ?4, updated_at = ?5
+                         WHERE id = ?6",
+                        params![name, category, input.body, hotkey, now, id],
+                    )?;
+                    if updated == 0 {
+                        return Err(anyhow!("Template not found: {}", id));
+                    }
+                    id.clone()
+                }
+                None => {
+                    let id = format!("tmpl_{}", uuid::Uuid::new_v4());
+                    conn.execute(
+                        "INSERT INTO prompt_templates (id, name, category, body, hotkey, created_at, updated_at)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)",
+                        params![id, name, category, input.body, hotkey, now],
+                    )?;
+                    id
+                }
+            }
+        };
+
+        self.get(&id)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let deleted = db.conn().execute("DELETE FROM prompt_templates WHERE id = ?1", [id])?;
+        if deleted == 0 {
+            return Err(anyhow!("Template not found: {}", id));
+        }
+        Ok(())
+    }
+
+    /// (template id, accelerator) of templates bound to a hotkey
+    pub fn hotkeys(&self) -> Result<Vec<(String, String)>> {
+        Ok(self
+            .list(None)?
+            .into_iter()
+            .filter_map(|template| template.hotkey.map(|hotkey| (template.id, hotkey)))
+            .collect())
+    }
+
+    /// Fill in a template. Selection and clipboard are only captured when the template uses them.
+    pub async fn resolve(&self, id: &str, request: RenderRequest, quick_ask: &QuickAskService) -> Result<RenderedPrompt> {
+        let template = self.get(id)?;
+        let mut values: HashMap<String, String> = request
+            .values
+            .into_iter()
+            .map(|(name, value)| (name.to_lowercase(), value))
+            .collect();
+
+        for variable in &template.variables {
+            if values.contains_key(variable) {
+                continue;
+            }
+            let value = match variable.as_str() {
+                VAR_SELECTION => quick_ask.gather_context(QuickAskSource::Selection).await?,
+                VAR_CLIPBOARD => quick_ask.gather_context(QuickAskSource::Clipboard).await?,
+                VAR_FILE => match &request.file_path {
+                    Some(path) => {
+                        let path = std::path::PathBuf::from(path);
+                        Some(tokio::task::spawn_blocking(move || read_file_text(&path)).await??)
+                    }
+                    None => None,
+                },
+                _ => None,
+            };
+            if let Some(value) = value {
+                values.insert(variable.clone(), value);
+            }
+        }
+
+        let (text, missing) = render(&template.body, &values);
+        Ok(RenderedPrompt { template_id: template.id, text, missing })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_variables() {
+        let body = "Translate {{ selection }} to {{language}}. Again: {{Selection}} {{unknown}}";
+        assert_eq!(extract_variables(body), vec!["selection", "language", "unknown"]);
+
+        let values = HashMap::from([
+            ("selection".to_string(), "안녕".to_string()),
+            ("language".to_string(), "English".to_string()),
+        ]);
+        let (text, missing) = render(body, &values);
+        assert_eq!(text, "Translate 안녕 to English. Again: 안녕 {{unknown}}");
+        assert_eq!(missing, vec!["unknown"]);
+    }
+
+    #[test]
+    fn test_save_and_categories() {
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let service = PromptTemplateService::new(db).unwrap();
+
+        let input = |name: &str, category: Option<&str>, hotkey: Option<&str>| PromptTemplateInput {
+            id: None,
+            name: name.to_string(),
+            category: category.map(str::to_string),
+            body: "Review this:\n{{file}}".to_string(),
+            hotkey: hotkey.map(str::to_string),
+        };
+        let review = service.save(input("Code review", Some("Coding"), Some("Alt+Shift+R"))).unwrap();
+        assert_eq!(review.variables, vec!["file"]);
+        service.save(input("Summary", None, None)).unwrap();
+        assert!(service.save(input("Other", None, Some("alt + shift + r"))).is_err());
+        assert!(service.save(input("Bad key", None, Some("R"))).is_err());
+
+        let categories = service.categories().unwrap();
+        assert_eq!(categories.len(), 2);
+        assert_eq!(categories[0].name, "Coding");
+        assert_eq!(categories[1].name, DEFAULT_CATEGORY);
+        assert_eq!(service.list(Some("Coding")).unwrap().len(), 1);
+        assert_eq!(service.hotkeys().unwrap(), vec![(review.id, "Alt+Shift+R".to_string())]);
+    }
+}
@@ -0,0 +1,241 @@
+//! Memory Provenance (v3.9.0)
+//!
+//! Where a retrieved context snippet came from, and the citations built from
+//! those records for chat responses.
+//!
+//! Features:
+//! - One provenance record shape for RAG episodes, chat messages, wiki facts,
+//!   graph entities and documents
+//! - Human labels for the UI ("your conversation from March 3rd")
+//! - Citation snippets trimmed for display
+
+#![allow(dead_code)]  // Phase 5: Provenance & citations
+
+use chrono::{Datelike, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+
+/// Citation snippets are cut to this many characters
+const SNIPPET_MAX_CHARS: usize = 160;
+
+/// Kind of store a snippet was retrieved from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProvenanceSource {
+    /// RAG episode (user message + reply pair)
+    Episode,
+    /// Raw chat message
+    Message,
+    /// Semantic wiki fact
+    WikiFact,
+    /// Knowledge graph entity
+    GraphEntity,
+    /// File or other document
+    Document,
+}
+
+/// Where a context snippet came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    pub source: ProvenanceSource,
+    /// Id in the source store (episode id, fact id, entity id, ...)
+    pub source_id: String,
+    pub conversation_id: Option<String>,
+    pub message_id: Option<String>,
+    /// Document name or episode the snippet was extracted from
+    pub document: Option<String>,
+    /// When the source was recorded (Unix millis)
+    pub timestamp: Option<i64>,
+}
+
+impl Provenance {
+    pub fn new(source: ProvenanceSource, source_id: impl Into<String>) -> Self {
+        Self {
+            source,
+            source_id: source_id.into(),
+            conversation_id: None,
+            message_id: None,
+            document: None,
+            timestamp: None,
+        }
+    }
+
+    pub fn with_conversation(mut self, conversation_id: Option<String>, message_id: Option<String>) -> Self {
+        self.conversation_id = conversation_id;
+        self.message_id = message_id;
+        self
+    }
+
+    pub fn with_document(mut self, document: impl Into<String>) -> Self {
+        self.document = Some(document.into());
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp_millis: i64) -> Self {
+        self.timestamp = Some(timestamp_millis);
+        self
+    }
+
+    /// Same, for stores that keep Unix seconds
+    pub fn with_timestamp_secs(self, timestamp_secs: i64) -> Self {
+        self.with_timestamp(timestamp_secs * 1000)
+    }
+
+    /// UI label, e.g. "your conversation from March 3rd"
+    pub fn describe(&self) -> String {
+        let day = self.timestamp.and_then(|ts| {
+            let local = chrono::Local.timestamp_millis_opt(ts).single()?;
+            Some(format_day(local.date_naive(), chrono::Local::now().date_naive()))
+        });
+        describe_with_day(self.source, self.document.as_deref(), day.as_deref())
+    }
+}
+
+/// Source reference returned alongside a response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Citation {
+    /// 1-based, in the order the snippets were given to the model
+    pub index: usize,
+    pub label: String,
+    pub snippet: String,
+    pub provenance: Provenance,
+}
+
+impl Citation {
+    pub fn new(index: usize, provenance: Provenance, content: &str) -> Self {
+        Self {
+            index,
+            label: provenance.describe(),
+            snippet: snippet(content),
+            provenance,
+        }
+    }
+}
+
+fn describe_with_day(source: ProvenanceSource, document: Option<&str>, day: Option<&str>) -> String {
+    match (source, day) {
+        (ProvenanceSource::Document, _) => match document {
+            Some(document) => format!("your document \"{}\"", document),
+            None => "one of your documents".to_string(),
+        },
+        (ProvenanceSource::WikiFact, Some(day)) => format!("something you told me {}", on_day(day)),
+        (ProvenanceSource::WikiFact, None) => "something you told me".to_string(),
+        (ProvenanceSource::GraphEntity, Some(day)) => format!("your conversation {}", from_day(day)),
+        (ProvenanceSource::GraphEntity, None) => "what I know about your world".to_string(),
+        (_, Some(day)) => format!("your conversation {}", from_day(day)),
+        (_, None) => "an earlier conversation".to_string(),
+    }
+}
+
+/// "on March 3rd", but "today" / "yesterday" without the preposition
+fn on_day(day: &str) -> String {
+    if day == "today" || day == "yesterday" {
+        day.to_string()
+    } else {
+        format!("on {}", day)
+    }
+}
+
+/// "from March 3rd", but "earlier today" / "yesterday"
+fn from_day(day: &str) -> String {
+    match day {
+        "today" => "earlier today".to_string(),
+        "yesterday" => day.to_string(),
+        _ => format!("from {}", day),
+    }
+}
+
+/// "today", "yesterday", "March 3rd", or "March 3rd, 2024" for other years
+pub fn format_day(date: NaiveDate, today: NaiveDate) -> String {
+    if date == today {
+        return "today".to_string();
+    }
+    if today.pred_opt() == Some(date) {
+        return "yesterday".to_string();
+    }
+
+    let day = date.day();
+    let suffix = match (day % 10, day % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    let month_day = format!("{} {}{}", date.format("%B"), day, suffix);
+
+    if date.year() == today.year() {
+        month_day
+    } else {
+        format!("{}, {}", month_day, date.year())
+    }
+}
+
+/// Trim text for display in a citation
+pub fn snippet(content: &str) -> String {
+    let content = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    if content.chars().count() <= SNIPPET_MAX_CHARS {
+        return content;
+    }
+    let cut: String = content.chars().take(SNIPPET_MAX_CHARS - 1).collect();
+    format!("{}…", cut.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_format_day() {
+        let today = date(2025, 6, 15);
+        assert_eq!(format_day(today, today), "today");
+        assert_eq!(format_day(date(2025, 6, 14), today), "yesterday");
+        assert_eq!(format_day(date(2025, 3, 3), today), "March 3rd");
+        assert_eq!(format_day(date(2025, 3, 1), today), "March 1st");
+        assert_eq!(format_day(date(2025, 3, 12), today), "March 12th");
+        assert_eq!(format_day(date(2025, 3, 22), today), "March 22nd");
+        assert_eq!(format_day(date(2024, 12, 31), today), "December 31st, 2024");
+    }
+
+    #[test]
+    fn test_describe_labels() {
+        assert_eq!(
+            describe_with_day(ProvenanceSource::Episode, None, Some("March 3rd")),
+            "your conversation from March 3rd"
+        );
+        assert_eq!(
+            describe_with_day(ProvenanceSource::WikiFact, None, Some("yesterday")),
+            "something you told me yesterday"
+        );
+        assert_eq!(
+            describe_with_day(ProvenanceSource::WikiFact, None, Some("March 3rd")),
+            "something you told me on March 3rd"
+        );
+        assert_eq!(
+            describe_with_day(ProvenanceSource::Document, Some("notes.md"), Some("today")),
+            "your document \"notes.md\""
+        );
+        assert_eq!(
+            describe_with_day(ProvenanceSource::Message, None, Some("today")),
+            "your conversation earlier today"
+        );
+        assert_eq!(describe_with_day(ProvenanceSource::Message, None, None), "an earlier conversation");
+    }
+
+    #[test]
+    fn test_citation_snippet() {
+        let provenance = Provenance::new(ProvenanceSource::Episode, "ep_1")
+            .with_conversation(Some("conv_1".to_string()), Some("msg_1".to_string()));
+        let citation = Citation::new(1, provenance, "I  prefer\n tabs");
+        assert_eq!(citation.snippet, "I prefer tabs");
+        assert_eq!(citation.label, "an earlier conversation");
+
+        let long = "word ".repeat(100);
+        let cut = snippet(&long);
+        assert_eq!(cut.chars().count(), SNIPPET_MAX_CHARS);
+        assert!(cut.ends_with('…'));
+    }
+}
@@ -0,0 +1,282 @@
+//! Query Expansion & HyDE (v3.9.0)
+//!
+//! Optional query-transformation stage run before embedding for RAG and
+//! hybrid search, to improve recall on short or ambiguous queries.
+//!
+//! Features:
+//! - HyDE: the LLM writes a hypothetical answer, which is embedded alongside
+//!   the original query
+//! - Multi-query: the LLM rewrites the query into several alternative phrasings
+//! - Auto: HyDE for short queries only, long queries pass through unchanged
+//! - Reciprocal rank merging of the per-variant result lists
+
+#![allow(dead_code)]  // Phase 5: Query expansion for retrieval
+
+use crate::services::ollama;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Queries with at most this many words count as short for `Auto`
+const AUTO_SHORT_QUERY_WORDS: usize = 5;
+
+/// Upper bound on multi-query rewrites, whatever the request asks for
+const MAX_EXPANSIONS: usize = 5;
+
+/// RRF constant used when merging per-variant results
+const MERGE_RRF_K: f32 = 60.0;
+
+/// Query transformation applied before retrieval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryTransform {
+    /// Search with the query as typed
+    #[default]
+    None,
+    /// Hypothetical Document Embeddings
+    Hyde,
+    /// Alternative phrasings of the query
+    MultiQuery,
+    /// HyDE for short queries, nothing otherwise
+    Auto,
+}
+
+/// Per-request expansion options
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryExpansionOptions {
+    #[serde(default)]
+    pub transform: QueryTransform,
+    /// Number of rewrites for multi-query
+    #[serde(default = "default_num_queries")]
+    pub num_queries: usize,
+    /// Also search with the original query
+    #[serde(default = "default_include_original")]
+    pub include_original: bool,
+}
+
+fn default_num_queries() -> usize {
+    3
+}
+
+fn default_include_original() -> bool {
+    true
+}
+
+impl Default for QueryExpansionOptions {
+    fn default() -> Self {
+        Self {
+            transform: QueryTransform::None,
+            num_queries: default_num_queries(),
+            include_original: default_include_original(),
+        }
+    }
+}
+
+impl QueryExpansionOptions {
+    pub fn hyde() -> Self {
+        Self {
+            transform: QueryTransform::Hyde,
+            ..Self::default()
+        }
+    }
+
+    pub fn multi_query(num_queries: usize) -> Self {
+        Self {
+            transform: QueryTransform::MultiQuery,
+            num_queries,
+            ..Self::default()
+        }
+    }
+
+    /// Transform actually applied to this query (resolves `Auto`)
+    pub fn effective_transform(&self, query: &str) -> QueryTransform {
+        match self.transform {
+            QueryTransform::Auto if query.split_whitespace().count() <= AUTO_SHORT_QUERY_WORDS => {
+                QueryTransform::Hyde
+            }
+            QueryTransform::Auto => QueryTransform::None,
+            transform => transform,
+        }
+    }
+}
+
+/// Queries to retrieve with, original first when included
+///
+/// Falls back to the original query alone if the LLM call fails.
+pub async fn expand_query(query: &str, options: &QueryExpansionOptions) -> Vec<String> {
+    let transform = options.effective_transform(query);
+    if transform == QueryTransform::None {
+        return vec![query.to_string()];
+    }
+
+    let expansions = match generate_expansions(query, transform, options.num_queries).await {
+        Ok(expansions) => expansions,
+        Err(e) => {
+            log::warn!("Query expansion failed, searching with the original query: {}", e);
+            Vec::new()
+        }
+    };
+
+    let mut queries = Vec::with_capacity(expansions.len() + 1);
+    if options.include_original || expansions.is_empty() {
+        queries.push(query.to_string());
+    }
+    for expansion in expansions {
+        if !queries.iter().any(|q| q.eq_ignore_ascii_case(&expansion)) {
+            queries.push(expansion);
+        }
+    }
+
+    log::debug!("Expanded query '{}' into {} variants", query, queries.len());
+    queries
+}
+
+async fn generate_expansions(query: &str, transform: QueryTransform, num_queries: usize) -> Result<Vec<String>> {
+    let prompt = match transform {
+        QueryTransform::Hyde => hyde_prompt(query),
+        QueryTransform::MultiQuery => multi_query_prompt(query, num_queries.clamp(1, MAX_EXPANSIONS)),
+        QueryTransform::None | QueryTransform::Auto => return Ok(Vec::new()),
+    };
+
+    let response = ollama::generate_response(&prompt)
+        .await
+        .map_err(|e| anyhow::anyhow!("LLM call failed: {}", e))?;
+
+    Ok(match transform {
+        QueryTransform::Hyde => parse_hypothetical_answer(&response).into_iter().collect(),
+        _ => parse_expansions(&response, num_queries.clamp(1, MAX_EXPANSIONS)),
+    })
+}
+
+fn hyde_prompt(query: &str) -> String {
+    format!(
+        r#"Write a short passage (2-3 sentences) that would answer the question below, as if it were taken from the user's notes or past conversations. Write in the same language as the question. Do not say that you don't know; a plausible answer is fine.
+
+Question: {query}
+
+Passage:"#
+    )
+}
+
+fn multi_query_prompt(query: &str, num_queries: usize) -> String {
+    format!(
+        r#"Rewrite the search query below into {num_queries} different search queries that could find the same information. Use synonyms, spell out abbreviations and make vague references explicit. Keep the language of the original query.
+
+Query: {query}
+
+Respond with one query per line and nothing else."#
+    )
+}
+
+/// Trim the HyDE passage (drops a leading "Passage:" label)
+pub fn parse_hypothetical_answer(response: &str) -> Option<String> {
+    let text = response.trim();
+    let text = text
+        .strip_prefix("Passage:")
+        .or_else(|| text.strip_prefix("passage:"))
+        .unwrap_or(text)
+        .trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Parse one query per line, stripping list markers and quotes
+pub fn parse_expansions(response: &str, max: usize) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
+    for line in response.lines() {
+        let line = line
+            .trim()
+            .trim_start_matches(|c: char| c.is_ascii_digit() || matches!(c, '.' | ')' | '-' | '*' | '•'))
+            .trim()
+            .trim_matches('"')
+            .trim();
+        if line.is_empty() || line.ends_with(':') {
+            continue;
+        }
+        if !queries.iter().any(|q| q.eq_ignore_ascii_case(line)) {
+            queries.push(line.to_string());
+        }
+        if queries.len() >= max {
+            break;
+        }
+    }
+    queries
+}
+
+/// Merge ranked result lists (one per query variant) by reciprocal rank
+///
+/// Items are deduplicated by `key`; the first occurrence is kept.
+pub fn merge_ranked<T, F>(lists: Vec<Vec<T>>, key: F, limit: usize) -> Vec<T>
+where
+    F: Fn(&T) -> String,
+{
+    let mut scores: HashMap<String, f32> = HashMap::new();
+    let mut items: Vec<(String, T)> = Vec::new();
+
+    for list in lists {
+        for (rank, item) in list.into_iter().enumerate() {
+            let id = key(&item);
+            *scores.entry(id.clone()).or_insert(0.0) += 1.0 / (MERGE_RRF_K + rank as f32 + 1.0);
+            if !items.iter().any(|(existing, _)| *existing == id) {
+                items.push((id, item));
+            }
+        }
+    }
+
+    // Stable sort keeps first-seen order on ties
+    items.sort_by(|(a, _), (b, _)| {
+        scores[b]
+            .partial_cmp(&scores[a])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    items.into_iter().take(limit).map(|(_, item)| item).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effective_transform() {
+        let auto = QueryExpansionOptions {
+            transform: QueryTransform::Auto,
+            ..QueryExpansionOptions::default()
+        };
+        assert_eq!(auto.effective_transform("rust lifetimes"), QueryTransform::Hyde);
+        assert_eq!(
+            auto.effective_transform("how did we decide to structure the database migration for the sync service"),
+            QueryTransform::None
+        );
+        assert_eq!(
+            QueryExpansionOptions::multi_query(3).effective_transform("rust"),
+            QueryTransform::MultiQuery
+        );
+    }
+
+    #[test]
+    fn test_parse_expansions() {
+        let response = "Here are the queries:\n1. \"rust borrow checker errors\"\n2) lifetime annotations in Rust\n- Rust borrow checker errors\n* fixing E0502";
+        let queries = parse_expansions(response, 5);
+        assert_eq!(
+            queries,
+            vec!["rust borrow checker errors", "lifetime annotations in Rust", "fixing E0502"]
+        );
+        assert_eq!(parse_expansions(response, 1).len(), 1);
+
+        assert_eq!(
+            parse_hypothetical_answer("Passage: The meeting moved to Friday."),
+            Some("The meeting moved to Friday.".to_string())
+        );
+        assert_eq!(parse_hypothetical_answer("  "), None);
+    }
+
+    #[test]
+    fn test_merge_ranked() {
+        let lists = vec![
+            vec!["a", "b", "c"],
+            vec!["b", "d"],
+            vec!["b", "a"],
+        ];
+        let merged = merge_ranked(lists, |s| s.to_string(), 3);
+        assert_eq!(merged, vec!["b", "a", "d"]);
+    }
+}
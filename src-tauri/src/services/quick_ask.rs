@@ -0,0 +1,509 @@
+//! Quick Ask Service (v3.9.0)
+//!
+//! Lightweight "quick ask" flow triggered by global hotkeys: grab the current
+//! selection, clipboard, or screen, run a single chat turn, and show the answer
+//! in a small popup window.
+//!
+//! Features:
+//! - Configurable hotkey bindings (persisted in user_preferences)
+//! - Context capture: selection (simulated copy), clipboard, screen (vision)
+//!   and the frontmost app, all taken before the popup steals focus
+//! - Single-turn answer without creating a conversation
+//! - Popup window + `quick-ask-*` events for the overlay UI
+
+#![allow(dead_code)]  // Phase 5: Quick ask overlay (some helpers used by future UI)
+
+use crate::database::Database;
+use crate::services::ollama;
+use crate::services::active_window::ActiveWindow;
+use crate::services::screen::{PrivacyDecision, ScreenCaptureService};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Mutex as TokioMutex;
+
+/// user_preferences key holding the serialized quick ask configuration
+const QUICK_ASK_CONFIG_KEY: &str = "quick_ask_config";
+
+/// Label of the popup window
+pub const QUICK_ASK_WINDOW: &str = "quick-ask";
+
+/// Time the focused app gets to answer a simulated copy
+const SELECTION_COPY_DELAY_MS: u64 = 150;
+
+/// Where the quick ask context comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuickAskSource {
+    /// Currently selected text (copied via a simulated Cmd/Ctrl+C)
+    Selection,
+    Clipboard,
+    /// Vision description of the current screen
+    Screen,
+    /// Open the popup without context
+    None,
+}
+
+/// A global shortcut bound to a quick ask source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HotkeyBinding {
+    /// Accelerator string, e.g. "CmdOrCtrl+Shift+Space"
+    pub accelerator: String,
+    pub source: QuickAskSource,
+    pub enabled: bool,
+}
+
+/// Quick ask configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuickAskConfig {
+    pub bindings: Vec<HotkeyBinding>,
+    /// Context longer than this is truncated before prompting
+    pub max_context_chars: usize,
+    /// Restore the previous clipboard after capturing a selection
+    pub restore_clipboard: bool,
+}
+
+impl Default for QuickAskConfig {
+    fn default() -> Self {
+        Self {
+            bindings: vec![
+                HotkeyBinding {
+                    accelerator: "CmdOrCtrl+Shift+Space".to_string(),
+                    source: QuickAskSource::Selection,
+                    enabled: true,
+                },
+                HotkeyBinding {
+                    accelerator: "CmdOrCtrl+Shift+V".to_string(),
+                    source: QuickAskSource::Clipboard,
+                    enabled: true,
+                },
+                HotkeyBinding {
+                    accelerator: "CmdOrCtrl+Shift+S".to_string(),
+                    source: QuickAskSource::Screen,
+                    enabled: false,
+                },
+            ],
+            max_context_chars: 8000,
+            restore_clipboard: true,
+        }
+    }
+}
+
+impl QuickAskConfig {
+    /// Check accelerators are well-formed and not bound twice
+    pub fn validate(&self) -> Result<()> {
+        let mut seen: Vec<String> = Vec::new();
+        for binding in &self.bindings {
+            validate_accelerator(&binding.accelerator)?;
+            let normalized = normalize_accelerator(&binding.accelerator);
+            if seen.contains(&normalized) {
+                return Err(anyhow!("Hotkey bound twice: {}", binding.accelerator));
+            }
+            seen.push(normalized);
+        }
+        Ok(())
+    }
+}
+
+/// Form used to compare hotkeys ("CmdOrCtrl + K" == "cmdorctrl+k")
+pub fn normalize_accelerator(accelerator: &str) -> String {
+    accelerator.to_lowercase().replace(' ', "")
+}
+
+/// Accept "Modifier+...+Key" with at least one modifier and exactly one key
+pub fn validate_accelerator(accelerator: &str) -> Result<()> {
+    const MODIFIERS: [&str; 9] = [
+        "cmdorctrl", "commandorcontrol", "cmd", "command", "super", "ctrl", "control", "alt", "shift",
+    ];
+
+    let parts: Vec<String> = accelerator
+        .split('+')
+        .map(|p| p.trim().to_lowercase())
+        .collect();
+
+    if parts.iter().any(|p| p.is_empty()) {
+        return Err(anyhow!("Invalid hotkey: '{}'", accelerator));
+    }
+
+    let modifiers = parts.iter().filter(|p| MODIFIERS.contains(&p.as_str())).count();
+    let keys = parts.len() - modifiers;
+
+    if modifiers == 0 || keys != 1 {
+        return Err(anyhow!(
+            "Invalid hotkey: '{}' (expected modifiers plus one key, e.g. CmdOrCtrl+Shift+Space)",
+            accelerator
+        ));
+    }
+    Ok(())
+}
+
+/// Result of a quick ask turn (also the `quick-ask-result` event payload)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuickAskResult {
+    pub id: String,
+    pub source: QuickAskSource,
+    /// App the hotkey was pressed in, e.g. "Visual Studio Code — main.rs"
+    #[serde(default)]
+    pub app: Option<String>,
+    pub context: Option<String>,
+    pub question: Option<String>,
+    pub answer: String,
+    pub created_at: i64, // Unix millis
+}
+
+/// `quick-ask-started` event payload
+#[derive(Debug, Clone, Serialize)]
+struct QuickAskStarted {
+    id: String,
+    source: QuickAskSource,
+    app: Option<String>,
+    context: Option<String>,
+}
+
+/// `quick-ask-error` event payload
+#[derive(Debug, Clone, Serialize)]
+struct QuickAskError {
+    id: String,
+    error: String,
+}
+
+/// Describe the frontmost app for the prompt, leaving out titles the screen privacy zones hide
+pub fn describe_app(window: &ActiveWindow, decision: &PrivacyDecision) -> String {
+    match decision {
+        PrivacyDecision::Allow if !window.title.trim().is_empty() => {
+            format!("{} — {}", window.app_name, window.title.trim())
+        }
+        _ => window.app_name.clone(),
+    }
+}
+
+/// Build the single-turn prompt
+pub fn build_prompt(app: Option<&str>, context: Option<&str>, question: Option<&str>) -> String {
+    let mut prompt = String::from(
+        "You are Adam, answering a quick question from a popup. Be brief (2-4 sentences unless code is needed). \
+         Answer in the same language as the user's text.\n\n",
+    );
+
+    if let Some(app) = app {
+        prompt.push_str("The user is working in: ");
+        prompt.push_str(app);
+        prompt.push_str("\n\n");
+    }
+
+    if let Some(context) = context {
+        prompt.push_str("Context:\n\"\"\"\n");
+        prompt.push_str(context);
+        prompt.push_str("\n\"\"\"\n\n");
+    }
+
+    match question {
+        Some(question) => {
+            prompt.push_str("Question: ");
+            prompt.push_str(question);
+        }
+        None => prompt.push_str("Explain the context above, or answer it if it is a question."),
+    }
+
+    prompt.push_str("\nAnswer:");
+    prompt
+}
+
+/// Quick ask service
+pub struct QuickAskService {
+    db: Arc<Mutex<Database>>,
+    screen_service: Arc<ScreenCaptureService>,
+    config: Arc<Mutex<QuickAskConfig>>,
+    app_handle: Arc<TokioMutex<Option<AppHandle>>>,
+}
+
+impl QuickAskService {
+    pub fn new(
+        db: Arc<Mutex<Database>>,
+        screen_service: Arc<ScreenCaptureService>,
+    ) -> Result<Self> {
+        let config = Self::load_config(&db).unwrap_or_else(|e| {
+            log::warn!("Failed to load quick ask config, using defaults: {}", e);
+            QuickAskConfig::default()
+        });
+
+        log::info!("✓ Quick Ask Service initialized ({} hotkeys)", config.bindings.len());
+        Ok(Self {
+            db,
+            screen_service,
+            config: Arc::new(Mutex::new(config)),
+            app_handle: Arc::new(TokioMutex::new(None)),
+        })
+    }
+
+    /// Set app handle for events and the popup window
+    pub async fn set_app_handle(&self, handle: AppHandle) {
+        let mut app_handle = self.app_handle.lock().await;
+        *app_handle = Some(handle);
+    }
+
+    fn load_config(db: &Arc<Mutex<Database>>) -> Result<QuickAskConfig> {
+        let db_guard = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let json: Option<String> = db_guard
+            .conn()
+            .query_row(
+                "SELECT value FROM user_preferences WHERE key = ?1",
+                [QUICK_ASK_CONFIG_KEY],
+                |row| row.get(0),
+            )
+            .ok();
+
+        match json {
+            Some(json) => Ok(serde_json::from_str(&json)?),
+            None => Ok(QuickAskConfig::default()),
+        }
+    }
+
+    /// Get current configuration
+    pub fn get_config(&self) -> QuickAskConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    /// Validate, persist and apply a new configuration
+    pub fn update_config(&self, new_config: QuickAskConfig) -> Result<()> {
+        new_config.validate()?;
+
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            db.conn().execute(
+                "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+                rusqlite::params![
+                    QUICK_ASK_CONFIG_KEY,
+                    serde_json::to_string(&new_config)?,
+                    chrono::Utc::now().timestamp_millis()
+                ],
+            )?;
+        }
+
+        *self.config.lock().unwrap() = new_config;
+        log::info!("Quick ask config updated");
+        Ok(())
+    }
+
+    /// Capture context from the given source
+    pub async fn gather_context(&self, source: QuickAskSource) -> Result<Option<String>> {
+        let max_chars = self.get_config().max_context_chars;
+
+        let context = match source {
+            QuickAskSource::None => None,
+            QuickAskSource::Clipboard => {
+                tokio::task::spawn_blocking(|| arboard::Clipboard::new()?.get_text().map_err(|e| anyhow!(e)))
+                    .await?
+                    .ok()
+            }
+            QuickAskSource::Selection => {
+                let restore = self.get_config().restore_clipboard;
+                tokio::task::spawn_blocking(move || Self::copy_selection(restore)).await??
+            }
+            QuickAskSource::Screen => {
+                let capture = self
+                    .screen_service
+                    .capture_with_context(1)
+                    .await
+                    .map_err(|e| anyhow!(e))?;
+                capture.vision_analysis.map(|a| a.description)
+            }
+        };
+
+        Ok(context
+            .map(|text| text.trim().chars().take(max_chars).collect::<String>())
+            .filter(|text| !text.is_empty()))
+    }
+
+    /// Copy the current selection by simulating Cmd/Ctrl+C
+    fn copy_selection(restore_clipboard: bool) -> Result<Option<String>> {
+        use enigo::{Direction, Enigo, Key, Keyboard};
+
+        let mut clipboard = arboard::Clipboard::new()?;
+        let previous = clipboard.get_text().ok();
+        let _ = clipboard.clear();
+
+        let modifier = if cfg!(target_os = "macos") { Key::Meta } else { Key::Control };
+        let mut enigo = Enigo::new(&enigo::Settings::default())
+            .map_err(|e| anyhow!("Failed to create Enigo instance: {}", e))?;
+        enigo.key(modifier, Direction::Press).map_err(|e| anyhow!(e))?;
+        enigo.key(Key::Unicode('c'), Direction::Click).map_err(|e| anyhow!(e))?;
+        enigo.key(modifier, Direction::Release).map_err(|e| anyhow!(e))?;
+
+        std::thread::sleep(Duration::from_millis(SELECTION_COPY_DELAY_MS));
+        let selection = clipboard.get_text().ok();
+
+        if restore_clipboard {
+            if let Some(previous) = previous {
+                let _ = clipboard.set_text(previous);
+            }
+        }
+
+        Ok(selection)
+    }
+
+    /// The app in focus, described with the screen privacy rules applied
+    fn frontmost_app(&self) -> Option<String> {
+        let window = self.screen_service.get_active_window().ok()?;
+        let decision = self.screen_service.get_privacy_config().evaluate(Some(&window));
+        Some(describe_app(&window, &decision))
+    }
+
+    /// Run a quick ask turn: capture context, answer, and emit to the popup
+    pub async fn ask(&self, source: QuickAskSource, question: Option<String>) -> Result<QuickAskResult> {
+        let id = format!("quick_{}", uuid::Uuid::new_v4());
+
+        // Capture before showing the popup: it takes focus, so a simulated copy
+        // or the frontmost app lookup would otherwise hit the popup itself
+        let app = self.frontmost_app();
+        let context = match self.gather_context(source).await {
+            Ok(context) => context,
+            Err(e) => {
+                log::warn!("Quick ask context capture failed: {}", e);
+                None
+            }
+        };
+
+        self.show_popup().await;
+        self.emit("quick-ask-started", QuickAskStarted {
+            id: id.clone(),
+            source,
+            app: app.clone(),
+            context: context.clone(),
+        }).await;
+
+        // Nothing to answer yet: the popup asks the user for a question
+        if context.is_none() && question.is_none() {
+            let result = QuickAskResult {
+                id,
+                source,
+                app,
+                context: None,
+                question: None,
+                answer: String::new(),
+                created_at: chrono::Utc::now().timestamp_millis(),
+            };
+            return Ok(result);
+        }
+
+        let prompt = build_prompt(app.as_deref(), context.as_deref(), question.as_deref());
+        let answer = match ollama::generate_response(&prompt).await {
+            Ok(answer) => answer,
+            Err(e) => {
+                self.emit("quick-ask-error", QuickAskError { id: id.clone(), error: e.clone() }).await;
+                return Err(anyhow!(e));
+            }
+        };
+
+        let result = QuickAskResult {
+            id,
+            source,
+            app,
+            context,
+            question,
+            answer,
+            created_at: chrono::Utc::now().timestamp_millis(),
+        };
+
+        self.emit("quick-ask-result", result.clone()).await;
+        Ok(result)
+    }
+
+    async fn emit<S: Serialize + Clone>(&self, event: &str, payload: S) {
+        if let Some(app) = self.app_handle.lock().await.as_ref() {
+            if let Err(e) = app.emit_to(QUICK_ASK_WINDOW, event, payload) {
+                log::warn!("Failed to emit {}: {}", event, e);
+            }
+        }
+    }
+
+    /// Show (creating if needed) the quick ask popup window
+    async fn show_popup(&self) {
+        let app_guard = self.app_handle.lock().await;
+        let app = match app_guard.as_ref() {
+            Some(app) => app,
+            None => return,
+        };
+
+        let window = match app.get_webview_window(QUICK_ASK_WINDOW) {
+            Some(window) => window,
+            None => {
+                let built = tauri::WebviewWindowBuilder::new(
+                    app,
+                    QUICK_ASK_WINDOW,
+                    tauri::WebviewUrl::App("index.html?window=quick-ask".into()),
+                )
+                .title("Quick Ask")
+                .inner_size(520.0, 360.0)
+                .decorations(false)
+                .always_on_top(true)
+                .skip_taskbar(true)
+                .center()
+                .build();
+
+                match built {
+                    Ok(window) => window,
+                    Err(e) => {
+                        log::warn!("Failed to create quick ask window: {}", e);
+                        return;
+                    }
+                }
+            }
+        };
+
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_accelerator() {
+        assert!(validate_accelerator("CmdOrCtrl+Shift+Space").is_ok());
+        assert!(validate_accelerator("Alt+Q").is_ok());
+        assert!(validate_accelerator("Q").is_err());
+        assert!(validate_accelerator("Ctrl+Shift").is_err());
+        assert!(validate_accelerator("Ctrl+A+B").is_err());
+        assert!(validate_accelerator("Ctrl++").is_err());
+    }
+
+    #[test]
+    fn test_config_rejects_duplicates() {
+        assert!(QuickAskConfig::default().validate().is_ok());
+
+        let mut config = QuickAskConfig::default();
+        config.bindings[1].accelerator = "cmdorctrl+shift+space".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_build_prompt() {
+        let prompt = build_prompt(None, Some("fn main() {}"), Some("이게 뭐야?"));
+        assert!(prompt.contains("fn main() {}"));
+        assert!(prompt.contains("Question: 이게 뭐야?"));
+        assert!(!prompt.contains("working in"));
+
+        let prompt = build_prompt(Some("Terminal"), Some("error[E0382]: borrow of moved value"), None);
+        assert!(prompt.contains("Explain the context above"));
+        assert!(prompt.contains("The user is working in: Terminal"));
+    }
+
+    #[test]
+    fn test_describe_app_hides_private_titles() {
+        let window = ActiveWindow {
+            title: "Chase Bank - Accounts".to_string(),
+            app_name: "Safari".to_string(),
+            bundle_id: None,
+            process_id: None,
+        };
+
+        assert_eq!(describe_app(&window, &PrivacyDecision::Allow), "Safari — Chase Bank - Accounts");
+        assert_eq!(describe_app(&window, &PrivacyDecision::Blur("bank".to_string())), "Safari");
+        assert_eq!(describe_app(&window, &PrivacyDecision::Skip("bank".to_string())), "Safari");
+    }
+}
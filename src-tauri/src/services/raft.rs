@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use super::language_detection;
 
 #[cfg(feature = "lancedb-support")]
 use super::rag_v2::Episode;  // v3.4.0 Phase 7: Updated to use LanceDB-based RAG v2
@@ -23,6 +24,15 @@ pub struct RaftConfig {
     /// Enable chain-of-thought prompting
     /// Makes model explain its reasoning before answering
     pub use_chain_of_thought: bool,
+
+    /// Minimum groundedness of answers to memory questions (0.0-1.0, v3.9.0)
+    /// Less supported answers are replaced by an abstention
+    #[serde(default = "default_min_groundedness")]
+    pub min_groundedness: f32,
+}
+
+fn default_min_groundedness() -> f32 {
+    0.5
 }
 
 impl Default for RaftConfig {
@@ -32,8 +42,110 @@ impl Default for RaftConfig {
             num_distractors: 2,         // Add 2 random episodes as distractors
             confidence_threshold: 0.6,  // Admit uncertainty if similarity <60%
             use_chain_of_thought: true, // Enable CoT by default
+            min_groundedness: default_min_groundedness(), // Half the claims must be in memory
+        }
+    }
+}
+
+/// Reply when memory has nothing on a question (v3.9.0)
+pub const ABSTAIN_RESPONSE_EN: &str = "I don't have that in memory.";
+pub const ABSTAIN_RESPONSE_KO: &str = "그 내용은 기억에 없어요.";
+
+/// Share of a claim's terms that must appear in the context for it to count as supported
+const CLAIM_SUPPORT_RATIO: f32 = 0.5;
+
+/// Claims with fewer terms are not scored (greetings, filler)
+const MIN_CLAIM_TERMS: usize = 3;
+
+/// Phrases that ask about the user's own history rather than general knowledge
+const MEMORY_CUES: &[&str] = &[
+    "remember", "recall", "did i", "did we", "have i", "what did i", "i told you", "i mentioned",
+    "last time", "we talked", "we discussed", "you said", "기억", "내가 말", "저번에", "지난번",
+    "전에 말", "얘기했", "말했었",
+];
+
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "you", "your", "are", "was", "were", "this", "that", "with", "have",
+    "has", "had", "can", "will", "but", "not", "they", "them", "what", "which", "there", "here",
+    "from", "about", "just", "also", "its", "yes", "sure", "our", "all", "any", "some", "more",
+    "would", "could", "should", "been", "into", "than", "then", "when", "how", "why", "who",
+    "out", "get", "like", "one", "very", "did", "said", "told",
+];
+
+/// How much of a response is supported by the retrieved context (v3.9.0)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Groundedness {
+    /// Supported share of scored claims (1.0 when nothing was scored)
+    pub score: f32,
+    pub supported_claims: usize,
+    pub total_claims: usize,
+    /// First few unsupported sentences
+    pub unsupported: Vec<String>,
+}
+
+/// Whether a message asks about past conversations (v3.9.0)
+pub fn is_memory_query(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    MEMORY_CUES.iter().any(|cue| lower.contains(cue))
+}
+
+/// Abstention in the language of the message (v3.9.0)
+pub fn abstain_response(message: &str) -> &'static str {
+    if language_detection::is_korean(message) {
+        ABSTAIN_RESPONSE_KO
+    } else {
+        ABSTAIN_RESPONSE_EN
+    }
+}
+
+/// Comparable terms: content words for Latin text, character bigrams for Hangul
+/// (Korean particles attach to words, so whole words rarely match)
+pub(crate) fn terms(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for word in text.to_lowercase().split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+        if word.is_ascii() {
+            if word.len() >= 3 && !STOPWORDS.contains(&word) {
+                terms.push(word.to_string());
+            }
+        } else {
+            let chars: Vec<char> = word.chars().collect();
+            if chars.len() == 1 {
+                terms.push(word.to_string());
+            }
+            terms.extend(chars.windows(2).map(|pair| pair.iter().collect::<String>()));
+        }
+    }
+    terms
+}
+
+/// Score each sentence of a response by how many of its terms the context contains (v3.9.0)
+pub fn groundedness(response: &str, context: &[Episode]) -> Groundedness {
+    let known: std::collections::HashSet<String> = context
+        .iter()
+        .flat_map(|ep| terms(&ep.user_message).into_iter().chain(terms(&ep.ai_response)))
+        .collect();
+
+    let mut report = Groundedness::default();
+    for sentence in response.split(['.', '!', '?', '。', '\n']).map(str::trim) {
+        let claim_terms = terms(sentence);
+        if claim_terms.len() < MIN_CLAIM_TERMS {
+            continue;
+        }
+        report.total_claims += 1;
+        let found = claim_terms.iter().filter(|term| known.contains(*term)).count();
+        if found as f32 / claim_terms.len() as f32 >= CLAIM_SUPPORT_RATIO {
+            report.supported_claims += 1;
+        } else if report.unsupported.len() < 3 {
+            report.unsupported.push(sentence.to_string());
         }
     }
+
+    report.score = if report.total_claims == 0 {
+        1.0
+    } else {
+        report.supported_claims as f32 / report.total_claims as f32
+    };
+    report
 }
 
 /// Episode with relevance scoring
@@ -56,6 +168,11 @@ impl RaftService {
         Self { config }
     }
 
+    /// Create without logging, for per-request use (v3.9.0)
+    pub fn new_quiet(config: RaftConfig) -> Self {
+        Self { config }
+    }
+
     /// Create with default configuration
     pub fn with_defaults() -> Self {
         Self::new(RaftConfig::default())
@@ -253,6 +370,24 @@ impl RaftService {
         false // No hallucination detected
     }
 
+    /// Grounding rules appended after the retrieved memories (v3.9.0)
+    ///
+    /// Memory questions must be answered from the memories alone; other
+    /// questions may fall back to general knowledge.
+    pub fn grounding_instructions(&self, memory_query: bool, abstain: &str) -> String {
+        let mut rules = String::from("\n# Grounding\n");
+        if memory_query {
+            rules.push_str("The user is asking about past conversations. Answer ONLY with facts stated in the memories above.\n");
+            rules.push_str(&format!("If the memories do not contain the answer, reply exactly: \"{}\"\n", abstain));
+        } else {
+            rules.push_str("Use the memories only if they are relevant. Never attribute to the user anything the memories do not say.\n");
+        }
+        if self.config.use_chain_of_thought {
+            rules.push_str("Check silently whether each statement is supported before answering.\n");
+        }
+        rules
+    }
+
     /// Get current configuration
     pub fn get_config(&self) -> &RaftConfig {
         &self.config
@@ -279,6 +414,12 @@ mod tests {
             access_count: 0,
             importance: satisfaction,
             embedding_id: None,
+            conversation_id: None,
+            message_id: None,
+            document: None,
+            language: None,
+            user_asserted: false,
+            user_note: None,
         }
     }
 
@@ -366,6 +507,36 @@ mod tests {
         assert!(prompt.contains("general knowledge"));
     }
 
+    #[test]
+    fn test_groundedness() {
+        let context = vec![
+            create_test_episode("1", "My dog is called Bori", "Bori sounds like a lovely golden retriever", 0.9),
+            create_test_episode("2", "내 생일은 3월 5일이야", "3월 5일 생일 기억할게요", 0.8),
+        ];
+
+        let supported = groundedness("Your dog Bori is a golden retriever.", &context);
+        assert_eq!(supported.total_claims, 1);
+        assert_eq!(supported.score, 1.0);
+
+        let unsupported = groundedness("Your dog Bori is a golden retriever. You live in Berlin with three roommates.", &context);
+        assert_eq!(unsupported.total_claims, 2);
+        assert_eq!(unsupported.score, 0.5);
+        assert_eq!(unsupported.unsupported, vec!["You live in Berlin with three roommates".to_string()]);
+
+        assert_eq!(groundedness("생일은 3월 5일이에요.", &context).score, 1.0);
+        assert_eq!(groundedness("Hi!", &[]).total_claims, 0);
+    }
+
+    #[test]
+    fn test_memory_query_and_abstain() {
+        assert!(is_memory_query("Do you remember my dog's name?"));
+        assert!(is_memory_query("저번에 내가 말한 책 제목이 뭐였지?"));
+        assert!(!is_memory_query("What is the capital of France?"));
+
+        assert_eq!(abstain_response("What did I say?"), ABSTAIN_RESPONSE_EN);
+        assert_eq!(abstain_response("내가 뭐라고 했지?"), ABSTAIN_RESPONSE_KO);
+    }
+
     #[test]
     fn test_cot_prompt() {
         let mut raft = RaftService::with_defaults();
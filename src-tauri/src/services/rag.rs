@@ -7,11 +7,25 @@
 
 use anyhow::{anyhow, Result};
 use crate::database::Database;
+use rusqlite::OptionalExtension;
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
 use tracing::{info, debug, instrument};
 
 use super::embedding::UnifiedEmbeddingService;
+use super::chunker::{self, chunk_text_with_embedder, Chunk, ChunkingSettings, SourceKind};
+use super::provenance::{Provenance, ProvenanceSource};
+use super::language_detection;  // v3.9.0: Language-tagged memories
+use super::memory_scope::{self, MemoryScope, RetrievalScope};  // v3.9.0: Scoped memories
+use super::vector_backend::{self, VectorBackendConfig, VectorBackendKind};
+use super::index_tuning::{self, IndexStatus, IndexTuning, RecallPreference};  // v3.9.0: ANN index tuning
+use super::query_expansion::{self, QueryExpansionOptions};
+
+/// Importance given to ingested document chunks (v3.9.0)
+const DOCUMENT_IMPORTANCE: f32 = 0.5;
+
+/// Score bonus for memories the user wrote or corrected by hand (v3.9.0)
+const USER_ASSERTED_BOOST: f32 = 0.15;
 
 /// Episodic memory entry
 #[derive(Debug, Clone)]
@@ -24,6 +38,36 @@ pub struct Episode {
     pub access_count: i32,
     pub importance: f32,
     pub embedding_id: Option<String>,
+    /// Chat the episode was stored from (v3.9.0, `None` for older episodes)
+    pub conversation_id: Option<String>,
+    pub message_id: Option<String>,
+    /// Document the episode was chunked from (v3.9.0, `None` for conversations)
+    pub document: Option<String>,
+    /// Language of the user message (v3.9.0, ISO 639-1, `None` if undetected)
+    pub language: Option<String>,
+    /// Written or corrected by the user by hand (v3.9.0), ranked above inferred memories
+    pub user_asserted: bool,
+    /// User's annotation (v3.9.0)
+    pub user_note: Option<String>,
+}
+
+impl Episode {
+    /// Provenance record for citations (v3.9.0)
+    pub fn provenance(&self) -> Provenance {
+        match &self.document {
+            Some(document) => Provenance::new(ProvenanceSource::Document, &self.id)
+                .with_document(document.clone())
+                .with_timestamp_secs(self.created_at),
+            None => Provenance::new(ProvenanceSource::Episode, &self.id)
+                .with_conversation(self.conversation_id.clone(), self.message_id.clone())
+                .with_timestamp_secs(self.created_at),
+        }
+    }
+
+    /// Ranking bonus that puts user-asserted memories ahead of inferred ones (v3.9.0)
+    pub fn assertion_boost(&self) -> f32 {
+        if self.user_asserted { USER_ASSERTED_BOOST } else { 0.0 }
+    }
 }
 
 /// RAG Service for episodic memory retrieval
@@ -32,6 +76,7 @@ pub struct RagService {
     db: Arc<Mutex<Database>>,
     embedding_service: Arc<UnifiedEmbeddingService>,
     _lance_db_path: PathBuf,
+    chunking: RwLock<ChunkingSettings>,  // v3.9.0: Per-source chunking
 }
 
 impl RagService {
@@ -51,16 +96,181 @@ impl RagService {
             db,
             embedding_service,
             _lance_db_path: lance_db_path,
+            chunking: RwLock::new(ChunkingSettings::default()),
+        })
+    }
+
+    /// Same as `new`; nothing is slow to open here (v3.9.0, parity with RAG v2)
+    pub fn new_lazy(
+        db: Arc<Mutex<Database>>,
+        embedding_service: Arc<UnifiedEmbeddingService>,
+        lance_db_path: PathBuf,
+    ) -> Self {
+        if let Err(e) = std::fs::create_dir_all(&lance_db_path) {
+            log::warn!("Failed to create embeddings directory {:?}: {}", lance_db_path, e);
+        }
+        Self {
+            db,
+            embedding_service,
+            _lance_db_path: lance_db_path,
+            chunking: RwLock::new(ChunkingSettings::default()),
+        }
+    }
+
+    /// Warm-up hook (v3.9.0)
+    pub async fn warm_up(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Always ready: episodes and embeddings live in SQLite (v3.9.0)
+    pub fn is_ready(&self) -> bool {
+        true
+    }
+
+    pub fn init_error(&self) -> Option<String> {
+        None
+    }
+
+    /// Profile switch hook (v3.9.0)
+    ///
+    /// Episodes live in the shared SQLite database, so there is nothing to reopen here.
+    pub async fn rebind(&self, _lance_db_path: PathBuf) -> Result<()> {
+        Ok(())
+    }
+
+    /// Encryption hook (v3.9.0)
+    ///
+    /// Episodes live in SQLite, which SQLCipher already encrypts as a whole.
+    pub async fn seal_payloads(&self) -> Result<usize> {
+        Ok(0)
+    }
+
+    /// Vector store backend selection (v3.9.0)
+    ///
+    /// This build searches episodic_memory in SQLite directly; only the SQLite
+    /// backend can be selected.
+    pub async fn switch_vector_backend(&self, config: VectorBackendConfig, _reindex: bool) -> Result<usize> {
+        if config.backend != VectorBackendKind::Sqlite {
+            return Err(anyhow!("This build only searches memories in SQLite ({:?} needs lancedb-support)", config.backend));
+        }
+        vector_backend::save_config(&self.db, &config)?;
+        Ok(0)
+    }
+
+    /// Backend memories are searched with (v3.9.0)
+    pub fn vector_backend_config(&self) -> Result<VectorBackendConfig> {
+        Ok(VectorBackendConfig {
+            backend: VectorBackendKind::Sqlite,
+            ..vector_backend::load_config(&self.db)?
+        })
+    }
+
+    /// Tuning in effect (v3.9.0)
+    ///
+    /// Only the SQLite candidate limit applies in this build.
+    pub async fn index_status(&self) -> Result<IndexStatus> {
+        let vectors: i64 = {
+            let db_guard = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            db_guard.conn().query_row(
+                "SELECT COUNT(*) FROM episodic_memory WHERE embedding_id IS NOT NULL",
+                [],
+                |row| row.get(0),
+            )?
+        };
+        Ok(IndexStatus {
+            backend: VectorBackendKind::Sqlite,
+            vectors: vectors as usize,
+            indexed: false,
+            tuning: index_tuning::load(&self.db)?,
         })
     }
 
+    /// Tuning derived from the current corpus size (v3.9.0)
+    pub async fn auto_tune_index(&self, preference: RecallPreference) -> Result<IndexTuning> {
+        let vectors = self.index_status().await?.vectors;
+        Ok(index_tuning::auto_tune(vectors, preference))
+    }
+
+    /// Save ANN index tuning (v3.9.0); there is no index to rebuild in this build
+    pub async fn configure_index(&self, tuning: IndexTuning, _rebuild: bool) -> Result<IndexStatus> {
+        index_tuning::save(&self.db, &tuning)?;
+        self.index_status().await
+    }
+
     /// Store a conversation episode with embedding
-    #[instrument(skip(self, user_message, ai_response), fields(msg_len = user_message.len()))]
     pub async fn store_episode(
         &self,
         user_message: &str,
         ai_response: &str,
         satisfaction: f32,
+    ) -> Result<String> {
+        self.store_episode_with_source(user_message, ai_response, satisfaction, None, None).await
+    }
+
+    /// Store an episode along with the chat it came from (v3.9.0 - provenance)
+    ///
+    /// Replies longer than one conversation chunk are stored as one episode
+    /// per chunk (v3.9.0); the id of the first chunk is returned.
+    pub async fn store_episode_with_source(
+        &self,
+        user_message: &str,
+        ai_response: &str,
+        satisfaction: f32,
+        conversation_id: Option<&str>,
+        message_id: Option<&str>,
+    ) -> Result<String> {
+        self.store_episode_in_scope(user_message, ai_response, satisfaction, conversation_id, message_id, &MemoryScope::Global)
+            .await
+    }
+
+    /// Store an episode only retrievable within `scope` (v3.9.0)
+    pub async fn store_episode_in_scope(
+        &self,
+        user_message: &str,
+        ai_response: &str,
+        satisfaction: f32,
+        conversation_id: Option<&str>,
+        message_id: Option<&str>,
+        scope: &MemoryScope,
+    ) -> Result<String> {
+        let max_tokens = self.get_chunking_settings().conversation.chunk_size;
+        let ids = if chunker::estimate_tokens(ai_response) <= max_tokens {
+            vec![self
+                .insert_episode(user_message, ai_response, satisfaction, conversation_id, message_id, None)
+                .await?]
+        } else {
+            let chunks = self.chunk(ai_response, SourceKind::Conversation);
+            let mut ids = Vec::with_capacity(chunks.len());
+            for chunk in &chunks {
+                ids.push(
+                    self.insert_episode(user_message, &chunk.text, satisfaction, conversation_id, message_id, None)
+                        .await?,
+                );
+            }
+            log::info!("Stored long episode as {} chunks", chunks.len());
+            ids
+        };
+        self.assign_scope(&ids, scope)?;
+        ids.into_iter().next().ok_or_else(|| anyhow!("Episode has no content to store"))
+    }
+
+    /// Scope freshly stored episodes (v3.9.0)
+    fn assign_scope(&self, ids: &[String], scope: &MemoryScope) -> Result<()> {
+        let db_guard = self.db.lock()
+            .map_err(|e| anyhow!("Database lock failed: {}", e))?;
+        memory_scope::assign(db_guard.conn(), ids, scope)
+    }
+
+    /// Insert one episode row (and its embedding)
+    #[instrument(skip(self, user_message, ai_response), fields(msg_len = user_message.len()))]
+    async fn insert_episode(
+        &self,
+        user_message: &str,
+        ai_response: &str,
+        satisfaction: f32,
+        conversation_id: Option<&str>,
+        message_id: Option<&str>,
+        document: Option<&str>,
     ) -> Result<String> {
         info!(satisfaction = satisfaction, "Storing episode");
 
@@ -82,8 +292,8 @@ impl RagService {
         db.execute(
             "INSERT INTO episodic_memory (
                 id, user_message, ai_response, satisfaction, created_at,
-                access_count, importance, embedding_id
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                access_count, importance, embedding_id, conversation_id, message_id, document, language
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
             rusqlite::params![
                 id,
                 user_message,
@@ -93,6 +303,10 @@ impl RagService {
                 0, // initial access_count
                 satisfaction, // initial importance = satisfaction
                 embedding_json,  // Store embedding as JSON
+                conversation_id,
+                message_id,
+                document,
+                language_detection::language_code(user_message),
             ],
         )?;
 
@@ -100,6 +314,158 @@ impl RagService {
         Ok(id)
     }
 
+    /// Per-source chunking settings (v3.9.0)
+    pub fn get_chunking_settings(&self) -> ChunkingSettings {
+        self.chunking.read().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    /// Update per-source chunking settings (v3.9.0)
+    pub fn update_chunking_settings(&self, settings: ChunkingSettings) -> Result<()> {
+        for source in SourceKind::ALL {
+            let config = settings.for_source(source);
+            if config.chunk_size == 0 || config.overlap >= config.chunk_size {
+                return Err(anyhow!("Invalid {:?} chunking: overlap must be smaller than a non-zero chunk size", source));
+            }
+        }
+        *self.chunking.write().map_err(|e| anyhow!("Chunking settings lock error: {}", e))? = settings;
+        log::info!("RAG chunking settings updated");
+        Ok(())
+    }
+
+    /// Split text with the settings for its source kind
+    pub fn chunk(&self, text: &str, source: SourceKind) -> Vec<Chunk> {
+        let config = self.get_chunking_settings().for_source(source).clone();
+        chunk_text_with_embedder(text, source, &config, |t| self.embedding_service.embed(t))
+    }
+
+    /// Chunk a document and store each chunk as an episode (v3.9.0)
+    ///
+    /// Returns the ids of the stored chunks in document order.
+    pub async fn ingest_document(
+        &self,
+        name: &str,
+        content: &str,
+        source: Option<SourceKind>,
+    ) -> Result<Vec<String>> {
+        self.ingest_document_in_scope(name, content, source, &MemoryScope::Global).await
+    }
+
+    /// Ingest a document only retrievable within `scope` (v3.9.0)
+    pub async fn ingest_document_in_scope(
+        &self,
+        name: &str,
+        content: &str,
+        source: Option<SourceKind>,
+        scope: &MemoryScope,
+    ) -> Result<Vec<String>> {
+        let source = source.unwrap_or_else(|| SourceKind::detect(name, content));
+        let chunks = self.chunk(content, source);
+        log::info!("Ingesting document '{}' as {} {:?} chunks", name, chunks.len(), source);
+
+        let mut ids = Vec::with_capacity(chunks.len());
+        for chunk in &chunks {
+            let label = format!("{} (part {}/{})", name, chunk.index + 1, chunks.len());
+            let id = self
+                .insert_episode(&label, &chunk.text, DOCUMENT_IMPORTANCE, None, None, Some(name))
+                .await?;
+            ids.push(id);
+        }
+        self.assign_scope(&ids, scope)?;
+        Ok(ids)
+    }
+
+    /// Store a memory the user wrote by hand (v3.9.0)
+    ///
+    /// Embedded right away and marked user-asserted, with full satisfaction
+    /// and importance.
+    pub async fn store_user_memory(&self, content: &str, note: Option<&str>, scope: &MemoryScope) -> Result<String> {
+        let id = self.insert_episode(content, "", 1.0, None, None, None).await?;
+        self.assign_scope(std::slice::from_ref(&id), scope)?;
+
+        let db_guard = self.db.lock()
+            .map_err(|e| anyhow!("Database lock failed: {}", e))?;
+        db_guard.conn().execute(
+            "UPDATE episodic_memory SET user_asserted = 1, user_note = ?1 WHERE id = ?2",
+            rusqlite::params![note, id],
+        )?;
+
+        log::info!("Stored user-asserted memory {}", id);
+        Ok(id)
+    }
+
+    /// Replace a memory's text with the user's correction (v3.9.0)
+    ///
+    /// The memory is re-embedded and becomes user-asserted.
+    pub async fn edit_memory(&self, id: &str, user_message: &str, ai_response: &str) -> Result<()> {
+        let embedding = self.embedding_service.embed(&format!("{}\n{}", user_message, ai_response))?;
+        let embedding_json = serde_json::to_string(&embedding)?;
+
+        let db_guard = self.db.lock()
+            .map_err(|e| anyhow!("Database lock failed: {}", e))?;
+        let updated = db_guard.conn().execute(
+            "UPDATE episodic_memory
+             SET user_message = ?1, ai_response = ?2, embedding_id = ?3, language = ?4, user_asserted = 1
+             WHERE id = ?5",
+            rusqlite::params![
+                user_message,
+                ai_response,
+                embedding_json,
+                language_detection::language_code(user_message),
+                id,
+            ],
+        )?;
+        if updated == 0 {
+            return Err(anyhow!("Memory not found: {}", id));
+        }
+
+        log::info!("Memory {} corrected by the user", id);
+        Ok(())
+    }
+
+    /// Delete memories by id (v3.9.0)
+    pub async fn delete_memories(&self, ids: &[String]) -> Result<usize> {
+        let db_guard = self.db.lock()
+            .map_err(|e| anyhow!("Database lock failed: {}", e))?;
+        let mut deleted = 0;
+        for id in ids {
+            deleted += db_guard.conn().execute("DELETE FROM episodic_memory WHERE id = ?1", [id])?;
+        }
+
+        log::info!("Deleted {} memories", deleted);
+        Ok(deleted)
+    }
+
+    /// Re-embed memories written by another device (v3.9.0 device sync)
+    ///
+    /// Ids that no longer exist were deleted and are skipped.
+    pub async fn reindex_memories(&self, ids: &[String]) -> Result<usize> {
+        let mut reindexed = 0;
+        for id in ids {
+            let texts: Option<(String, String)> = {
+                let db_guard = self.db.lock()
+                    .map_err(|e| anyhow!("Database lock failed: {}", e))?;
+                db_guard.conn().query_row(
+                    "SELECT user_message, ai_response FROM episodic_memory WHERE id = ?1",
+                    [id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                ).optional()?
+            };
+            let Some((user_message, ai_response)) = texts else {
+                continue;
+            };
+
+            let embedding = self.embedding_service.embed(&format!("{}\n{}", user_message, ai_response))?;
+            let db_guard = self.db.lock()
+                .map_err(|e| anyhow!("Database lock failed: {}", e))?;
+            db_guard.conn().execute(
+                "UPDATE episodic_memory SET embedding_id = ?1 WHERE id = ?2",
+                rusqlite::params![serde_json::to_string(&embedding)?, id],
+            )?;
+            reindexed += 1;
+        }
+        Ok(reindexed)
+    }
+
     /// Retrieve relevant episodes for a query
     #[instrument(skip(self, query), fields(query_len = query.len()))]
     pub async fn retrieve_relevant(&self, query: &str, top_k: usize) -> Result<Vec<Episode>> {
@@ -109,7 +475,7 @@ impl RagService {
         let query_embedding = self.embedding_service.embed(query)?;
 
         // Get all episodes with embeddings from SQLite
-        let episodes = self.get_all_episodes_with_embeddings()?;
+        let episodes = self.get_all_episodes_with_embeddings(&RetrievalScope::global())?;
 
         // Compute cosine similarity for each episode
         let mut scored_episodes: Vec<(Episode, f32)> = episodes
@@ -117,7 +483,8 @@ impl RagService {
             .filter_map(|(episode, embedding_json)| {
                 // Parse embedding
                 if let Ok(embedding) = serde_json::from_str::<Vec<f32>>(&embedding_json) {
-                    let similarity = UnifiedEmbeddingService::cosine_similarity(&query_embedding, &embedding);
+                    let similarity = UnifiedEmbeddingService::cosine_similarity(&query_embedding, &embedding)
+                        + episode.assertion_boost();
                     Some((episode, similarity))
                 } else {
                     None
@@ -156,7 +523,7 @@ impl RagService {
         let query_embedding = self.embedding_service.embed(query)?;
 
         // Get all episodes with embeddings and retention scores from SQLite
-        let episodes = self.get_all_episodes_with_temporal()?;
+        let episodes = self.get_all_episodes_with_temporal(&RetrievalScope::global())?;
 
         // Compute combined score: semantic similarity + temporal retention
         let mut scored_episodes: Vec<(Episode, f32)> = episodes
@@ -168,7 +535,7 @@ impl RagService {
 
                     // Weighted combination: 70% semantic, 30% temporal
                     // This balances relevance with recency/importance
-                    let combined_score = (semantic_similarity * 0.7) + (retention_score * 0.3);
+                    let combined_score = (semantic_similarity * 0.7) + (retention_score * 0.3) + episode.assertion_boost();
 
                     Some((episode, combined_score))
                 } else {
@@ -195,6 +562,39 @@ impl RagService {
         Ok(top_episodes)
     }
 
+    /// Retrieve relevant episodes after query expansion / HyDE (v3.9.0)
+    ///
+    /// Each query variant is searched separately and the result lists are
+    /// merged by reciprocal rank.
+    pub async fn retrieve_relevant_expanded(
+        &self,
+        query: &str,
+        top_k: usize,
+        options: &QueryExpansionOptions,
+    ) -> Result<Vec<Episode>> {
+        let queries = query_expansion::expand_query(query, options).await;
+        if queries.len() <= 1 {
+            return self.retrieve_relevant(query, top_k).await;
+        }
+
+        let mut lists = Vec::with_capacity(queries.len());
+        for variant in &queries {
+            let scored = self.search_with_scores(variant, top_k).await?;
+            lists.push(scored.into_iter().map(|(episode, _)| episode).collect::<Vec<_>>());
+        }
+        let top_episodes = query_expansion::merge_ranked(lists, |episode| episode.id.clone(), top_k);
+
+        let ids: Vec<String> = top_episodes.iter().map(|e| e.id.clone()).collect();
+        self.increment_access_counts(&ids)?;
+
+        log::info!(
+            "Retrieved {} episodes with {} query variants",
+            top_episodes.len(),
+            queries.len()
+        );
+        Ok(top_episodes)
+    }
+
     /// Search memory semantically
     pub async fn search_memory(&self, query: &str, limit: usize) -> Result<Vec<Episode>> {
         self.retrieve_relevant(query, limit).await
@@ -208,21 +608,35 @@ impl RagService {
     /// Search episodes with similarity scores (v3.8.0 Phase 4 - for contextual retrieval)
     /// Returns episodes paired with their similarity scores
     pub async fn search_with_scores(&self, query: &str, top_k: usize) -> Result<Vec<(Episode, f32)>> {
+        self.search_with_scores_in(query, top_k, &RetrievalScope::global()).await
+    }
+
+    /// Search with scores among global memories and those of `scope` (v3.9.0)
+    pub async fn search_with_scores_in(
+        &self,
+        query: &str,
+        top_k: usize,
+        scope: &RetrievalScope,
+    ) -> Result<Vec<(Episode, f32)>> {
         log::info!("Searching {} episodes with similarity scores", top_k);
 
         // Generate query embedding
         let query_embedding = self.embedding_service.embed(query)?;
 
         // Get all episodes with embeddings from SQLite
-        let episodes = self.get_all_episodes_with_embeddings()?;
+        let episodes = self.get_all_episodes_with_embeddings(scope)?;
 
         // Compute cosine similarity for each episode
+        // v3.9.0: Memories in the query's language win near ties
+        let query_language = language_detection::language_code(query);
         let mut scored_episodes: Vec<(Episode, f32)> = episodes
             .into_iter()
             .filter_map(|(episode, embedding_json)| {
                 // Parse embedding
                 if let Ok(embedding) = serde_json::from_str::<Vec<f32>>(&embedding_json) {
-                    let similarity = UnifiedEmbeddingService::cosine_similarity(&query_embedding, &embedding);
+                    let similarity = UnifiedEmbeddingService::cosine_similarity(&query_embedding, &embedding)
+                        + language_detection::same_language_boost(query_language.as_deref(), episode.language.as_deref())
+                        + episode.assertion_boost();
                     Some((episode, similarity))
                 } else {
                     None
@@ -250,7 +664,8 @@ impl RagService {
 
         let mut stmt = db.prepare(
             "SELECT id, user_message, ai_response, satisfaction, created_at,
-                    access_count, importance, embedding_id
+                    access_count, importance, embedding_id, conversation_id, message_id, document, language,
+                    COALESCE(user_asserted, 0), user_note
              FROM episodic_memory
              ORDER BY created_at DESC
              LIMIT ?1"
@@ -267,6 +682,12 @@ impl RagService {
                     access_count: row.get(5)?,
                     importance: row.get(6)?,
                     embedding_id: row.get(7)?,
+                    conversation_id: row.get(8)?,
+                    message_id: row.get(9)?,
+                    document: row.get(10)?,
+                    language: row.get(11)?,
+                    user_asserted: row.get(12)?,
+                    user_note: row.get(13)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -346,37 +767,48 @@ impl RagService {
 
     // === Private helper methods ===
 
-    /// Maximum number of episodes to load for similarity search
+    /// Number of episodes to load for similarity search (v3.9.0: `sqlite_max_candidates`)
     /// This prevents loading 10,000+ episodes into memory
     /// Higher importance and more recent episodes are prioritized
-    const MAX_EPISODES_FOR_SEARCH: usize = 500;
+    fn max_candidates(&self) -> usize {
+        index_tuning::load(&self.db)
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to load index tuning, using defaults: {}", e);
+                IndexTuning::default()
+            })
+            .sqlite_max_candidates
+    }
 
     /// Get candidate episodes for similarity search
     ///
     /// Performance optimized:
-    /// - Limits to MAX_EPISODES_FOR_SEARCH (default: 500) instead of loading all
+    /// - Limits to the configured candidate count (default: 500) instead of loading all
     /// - Prioritizes by: (importance DESC, created_at DESC)
     /// - Only loads episodes with valid embeddings
     ///
     /// This reduces memory usage and computation time from O(n) to O(k) where k=500
-    fn get_all_episodes_with_embeddings(&self) -> Result<Vec<(Episode, String)>> {
+    fn get_all_episodes_with_embeddings(&self, scope: &RetrievalScope) -> Result<Vec<(Episode, String)>> {
+        let max_candidates = self.max_candidates();
         let db_guard = self.db.lock()
             .map_err(|e| anyhow::anyhow!("Database lock failed: {}", e))?;
         let db = db_guard.conn();
 
         // Optimized query: Load only top candidates by importance + recency
         // This prevents loading 10,000+ episodes for similarity computation
-        let mut stmt = db.prepare(
+        let mut stmt = db.prepare(&format!(
             "SELECT id, user_message, ai_response, satisfaction, created_at,
-                    access_count, importance, embedding_id
+                    access_count, importance, embedding_id, conversation_id, message_id, document, language,
+                    COALESCE(user_asserted, 0), user_note
              FROM episodic_memory
-             WHERE embedding_id IS NOT NULL
-             ORDER BY importance DESC, created_at DESC
-             LIMIT ?1"
-        )?;
+             WHERE embedding_id IS NOT NULL AND {}
+             ORDER BY COALESCE(user_asserted, 0) DESC, importance DESC, created_at DESC
+             LIMIT ?",
+            scope.sql_filter()
+        ))?;
 
+        let [conversation_id, project_id] = scope.params();
         let episodes = stmt
-            .query_map([Self::MAX_EPISODES_FOR_SEARCH], |row| {
+            .query_map(rusqlite::params![conversation_id, project_id, max_candidates as i64], |row| {
                 let episode = Episode {
                     id: row.get(0)?,
                     user_message: row.get(1)?,
@@ -386,6 +818,12 @@ impl RagService {
                     access_count: row.get(5)?,
                     importance: row.get(6)?,
                     embedding_id: row.get::<_, Option<String>>(7)?,
+                    conversation_id: row.get(8)?,
+                    message_id: row.get(9)?,
+                    document: row.get(10)?,
+                    language: row.get(11)?,
+                    user_asserted: row.get(12)?,
+                    user_note: row.get(13)?,
                 };
                 let embedding_json: String = row.get(7)?;
                 Ok((episode, embedding_json))
@@ -393,33 +831,37 @@ impl RagService {
             .collect::<Result<Vec<_>, _>>()?;
 
         log::debug!("Loaded {} candidate episodes for similarity search (max: {})",
-                   episodes.len(), Self::MAX_EPISODES_FOR_SEARCH);
+                   episodes.len(), max_candidates);
 
         Ok(episodes)
     }
 
     /// Get candidate episodes with temporal scores for similarity search (v3.8.0 Phase 3)
     ///
-    /// Performance optimized: Limits to MAX_EPISODES_FOR_SEARCH candidates
+    /// Performance optimized: Limits to the configured candidate count
     /// Prioritized by: retention_score (temporal importance) + importance + recency
-    fn get_all_episodes_with_temporal(&self) -> Result<Vec<(Episode, String, f32)>> {
+    fn get_all_episodes_with_temporal(&self, scope: &RetrievalScope) -> Result<Vec<(Episode, String, f32)>> {
+        let max_candidates = self.max_candidates();
         let db_guard = self.db.lock()
             .map_err(|e| anyhow::anyhow!("Database lock failed: {}", e))?;
         let db = db_guard.conn();
 
         // Optimized query: Prioritize by retention score + importance + recency
-        let mut stmt = db.prepare(
+        let mut stmt = db.prepare(&format!(
             "SELECT id, user_message, ai_response, satisfaction, created_at,
-                    access_count, importance, embedding_id,
-                    COALESCE(retention_score, 1.0) as retention_score
+                    access_count, importance, embedding_id, conversation_id, message_id,
+                    COALESCE(retention_score, 1.0) as retention_score, document, language,
+                    COALESCE(user_asserted, 0), user_note
              FROM episodic_memory
-             WHERE embedding_id IS NOT NULL
-             ORDER BY retention_score DESC, importance DESC, created_at DESC
-             LIMIT ?1"
-        )?;
+             WHERE embedding_id IS NOT NULL AND {}
+             ORDER BY COALESCE(user_asserted, 0) DESC, retention_score DESC, importance DESC, created_at DESC
+             LIMIT ?",
+            scope.sql_filter()
+        ))?;
 
+        let [conversation_id, project_id] = scope.params();
         let episodes = stmt
-            .query_map([Self::MAX_EPISODES_FOR_SEARCH], |row| {
+            .query_map(rusqlite::params![conversation_id, project_id, max_candidates as i64], |row| {
                 let episode = Episode {
                     id: row.get(0)?,
                     user_message: row.get(1)?,
@@ -429,21 +871,27 @@ impl RagService {
                     access_count: row.get(5)?,
                     importance: row.get(6)?,
                     embedding_id: row.get::<_, Option<String>>(7)?,
+                    conversation_id: row.get(8)?,
+                    message_id: row.get(9)?,
+                    document: row.get(11)?,
+                    language: row.get(12)?,
+                    user_asserted: row.get(13)?,
+                    user_note: row.get(14)?,
                 };
                 let embedding_json: String = row.get(7)?;
-                let retention_score: f32 = row.get::<_, f64>(8)? as f32;  // SQLite stores as REAL (f64)
+                let retention_score: f32 = row.get::<_, f64>(10)? as f32;  // SQLite stores as REAL (f64)
                 Ok((episode, embedding_json, retention_score))
             })?
             .collect::<Result<Vec<_>, _>>()?;
 
         log::debug!("Loaded {} candidate episodes with temporal scores (max: {})",
-                   episodes.len(), Self::MAX_EPISODES_FOR_SEARCH);
+                   episodes.len(), max_candidates);
 
         Ok(episodes)
     }
 
     /// Increment access counts for episodes
-    fn increment_access_counts(&self, ids: &[String]) -> Result<()> {
+    pub(crate) fn increment_access_counts(&self, ids: &[String]) -> Result<()> {
         if ids.is_empty() {
             return Ok(());
         }
@@ -480,13 +928,23 @@ pub fn format_episodes_for_context(episodes: &[Episode]) -> String {
     let mut context = String::from("Relevant past conversations:\n\n");
 
     for (i, episode) in episodes.iter().enumerate() {
-        context.push_str(&format!(
-            "{}. User: {}\n   AI: {}\n   (Satisfaction: {:.2})\n\n",
-            i + 1,
-            episode.user_message,
-            episode.ai_response,
-            episode.satisfaction
-        ));
+        if episode.user_asserted && episode.ai_response.is_empty() {
+            // v3.9.0: Written by the user, not a conversation
+            context.push_str(&format!("{}. Stated by the user: {}\n", i + 1, episode.user_message));
+        } else {
+            context.push_str(&format!(
+                "{}. User: {}\n   AI: {}\n   (Satisfaction: {:.2}{})\n",
+                i + 1,
+                episode.user_message,
+                episode.ai_response,
+                episode.satisfaction,
+                if episode.user_asserted { ", confirmed by the user" } else { "" }
+            ));
+        }
+        if let Some(note) = &episode.user_note {
+            context.push_str(&format!("   Note from the user: {}\n", note));
+        }
+        context.push('\n');
     }
 
     context
@@ -511,7 +969,13 @@ mod tests {
                 created_at INTEGER NOT NULL,
                 access_count INTEGER NOT NULL DEFAULT 0,
                 importance REAL NOT NULL,
-                embedding_id TEXT
+                embedding_id TEXT,
+                conversation_id TEXT,
+                message_id TEXT,
+                document TEXT,
+                language TEXT,
+                user_asserted INTEGER NOT NULL DEFAULT 0,
+                user_note TEXT
             )",
             [],
         ).unwrap();
@@ -546,6 +1010,12 @@ mod tests {
             access_count: 0,
             importance: 0.8,
             embedding_id: None,
+            conversation_id: None,
+            message_id: None,
+            document: None,
+            language: None,
+            user_asserted: false,
+            user_note: None,
         };
 
         let context = format_episodes_for_context(&[episode]);
@@ -567,6 +1037,14 @@ mod tests {
                 access_count: 2,
                 importance: 0.9,
                 embedding_id: None,
+                conversation_id: None,
+                message_id: None,
+                document: None,
+                language: None,
+                user_asserted: false,
+                user_note: None,
+            user_asserted: false,
+            user_note: None,
             },
             Episode {
                 id: "test2".to_string(),
@@ -577,6 +1055,14 @@ mod tests {
                 access_count: 1,
                 importance: 0.7,
                 embedding_id: None,
+                conversation_id: None,
+                message_id: None,
+                document: None,
+                language: None,
+                user_asserted: false,
+                user_note: None,
+            user_asserted: false,
+            user_note: None,
             },
         ];
 
@@ -0,0 +1,577 @@
+//! Retrieval Evaluation Harness (v3.9.0)
+//!
+//! Offline evaluation of memory retrieval against golden datasets of
+//! query → expected-memory pairs.
+//!
+//! Features:
+//! - Named datasets of eval cases in `rag_eval_cases`
+//! - recall@k, MRR and latency percentiles per retrieval configuration
+//!   (BM25-only, dense-only, hybrid, hybrid + rerank)
+//! - Runs persisted in `rag_eval_runs` / `rag_eval_results`, with deltas
+//!   against the previous run of the same dataset for regression tracking
+
+#![allow(dead_code)]  // Phase 5: Retrieval evaluation
+
+use crate::database::Database;
+use crate::services::analytics::{latency_percentiles, LatencyPercentiles};
+use anyhow::{anyhow, Result};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Drops larger than this (absolute, 0.0-1.0) are flagged as regressions
+const REGRESSION_TOLERANCE: f64 = 0.02;
+
+/// Retrieval configuration under evaluation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetrievalMode {
+    Bm25,
+    Dense,
+    Hybrid,
+    HybridRerank,
+}
+
+impl RetrievalMode {
+    pub const ALL: [RetrievalMode; 4] = [
+        RetrievalMode::Bm25,
+        RetrievalMode::Dense,
+        RetrievalMode::Hybrid,
+        RetrievalMode::HybridRerank,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RetrievalMode::Bm25 => "bm25",
+            RetrievalMode::Dense => "dense",
+            RetrievalMode::Hybrid => "hybrid",
+            RetrievalMode::HybridRerank => "hybrid_rerank",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.as_str() == value)
+    }
+}
+
+/// Golden query with the memories it should retrieve
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalCase {
+    pub id: String,
+    pub dataset: String,
+    pub query: String,
+    /// Episode ids expected in the results
+    pub expected_ids: Vec<String>,
+    pub note: Option<String>,
+    pub created_at: i64, // Unix millis
+}
+
+/// Case as submitted by the user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewEvalCase {
+    pub query: String,
+    pub expected_ids: Vec<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// Outcome of one case under one configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseResult {
+    pub case_id: String,
+    pub query: String,
+    pub retrieved_ids: Vec<String>,
+    /// 1-based rank of the first expected memory
+    pub first_hit_rank: Option<usize>,
+    pub recall: f64,
+    pub reciprocal_rank: f64,
+    pub latency_ms: u64,
+    /// Retrieval error, counted as a miss
+    pub error: Option<String>,
+}
+
+/// Aggregate metrics of one configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModeReport {
+    pub mode: RetrievalMode,
+    pub recall_at_k: f64,
+    pub mrr: f64,
+    pub avg_latency_ms: f64,
+    pub latency: LatencyPercentiles,
+    pub errors: usize,
+    pub cases: Vec<CaseResult>,
+}
+
+/// Change against the previous run of the dataset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegressionDelta {
+    pub mode: RetrievalMode,
+    pub previous_run_id: String,
+    pub recall_delta: f64,
+    pub mrr_delta: f64,
+    pub latency_p50_delta_ms: i64,
+    /// recall or MRR dropped by more than the tolerance
+    pub regressed: bool,
+}
+
+/// A full evaluation run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalRun {
+    pub id: String,
+    pub dataset: String,
+    pub k: usize,
+    pub started_at: i64, // Unix millis
+    pub case_count: usize,
+    pub reports: Vec<ModeReport>,
+    pub deltas: Vec<RegressionDelta>,
+}
+
+/// One configuration of a past run (without per-case details)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalHistoryEntry {
+    pub run_id: String,
+    pub dataset: String,
+    pub k: usize,
+    pub started_at: i64,
+    pub mode: RetrievalMode,
+    pub recall_at_k: f64,
+    pub mrr: f64,
+    pub avg_latency_ms: f64,
+    pub latency_p50_ms: u64,
+    pub latency_p90_ms: u64,
+}
+
+/// Share of expected ids found in the first `k` retrieved ids
+pub fn recall_at_k(retrieved: &[String], expected: &[String], k: usize) -> f64 {
+    if expected.is_empty() {
+        return 0.0;
+    }
+    let top = &retrieved[..retrieved.len().min(k)];
+    let found = expected.iter().filter(|id| top.contains(id)).count();
+    found as f64 / expected.len() as f64
+}
+
+/// 1-based rank of the first expected id
+pub fn first_hit_rank(retrieved: &[String], expected: &[String]) -> Option<usize> {
+    retrieved
+        .iter()
+        .position(|id| expected.contains(id))
+        .map(|index| index + 1)
+}
+
+/// Score one case from its retrieved ids
+pub fn score_case(
+    case: &EvalCase,
+    retrieved: std::result::Result<Vec<String>, String>,
+    k: usize,
+    latency_ms: u64,
+) -> CaseResult {
+    let (retrieved_ids, error) = match retrieved {
+        Ok(mut ids) => {
+            ids.truncate(k);
+            (ids, None)
+        }
+        Err(e) => (Vec::new(), Some(e)),
+    };
+    let rank = first_hit_rank(&retrieved_ids, &case.expected_ids);
+
+    CaseResult {
+        case_id: case.id.clone(),
+        query: case.query.clone(),
+        first_hit_rank: rank,
+        recall: recall_at_k(&retrieved_ids, &case.expected_ids, k),
+        reciprocal_rank: rank.map(|r| 1.0 / r as f64).unwrap_or(0.0),
+        retrieved_ids,
+        latency_ms,
+        error,
+    }
+}
+
+/// Aggregate case results into a report
+pub fn build_report(mode: RetrievalMode, cases: Vec<CaseResult>) -> ModeReport {
+    let n = cases.len().max(1) as f64;
+    let mut latencies: Vec<u64> = cases.iter().map(|c| c.latency_ms).collect();
+    latencies.sort_unstable();
+
+    ModeReport {
+        mode,
+        recall_at_k: cases.iter().map(|c| c.recall).sum::<f64>() / n,
+        mrr: cases.iter().map(|c| c.reciprocal_rank).sum::<f64>() / n,
+        avg_latency_ms: latencies.iter().sum::<u64>() as f64 / n,
+        latency: latency_percentiles(&latencies),
+        errors: cases.iter().filter(|c| c.error.is_some()).count(),
+        cases,
+    }
+}
+
+/// Compare a report with the same configuration from an earlier run
+pub fn regression_delta(report: &ModeReport, previous: &EvalHistoryEntry) -> RegressionDelta {
+    let recall_delta = report.recall_at_k - previous.recall_at_k;
+    let mrr_delta = report.mrr - previous.mrr;
+    RegressionDelta {
+        mode: report.mode,
+        previous_run_id: previous.run_id.clone(),
+        recall_delta,
+        mrr_delta,
+        latency_p50_delta_ms: report.latency.p50 as i64 - previous.latency_p50_ms as i64,
+        regressed: recall_delta < -REGRESSION_TOLERANCE || mrr_delta < -REGRESSION_TOLERANCE,
+    }
+}
+
+/// Retrieval evaluation service
+pub struct RagEvalService {
+    db: Arc<Mutex<Database>>,
+}
+
+impl RagEvalService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        let service = Self { db };
+        service.init_database()?;
+        log::info!("✓ RAG Eval Service initialized");
+        Ok(service)
+    }
+
+    fn init_database(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let conn = db.conn();
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS rag_eval_cases (
+                id TEXT PRIMARY KEY,
+                dataset TEXT NOT NULL,
+                query TEXT NOT NULL,
+                expected_ids TEXT NOT NULL,
+                note TEXT,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_rag_eval_cases_dataset ON rag_eval_cases(dataset)",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS rag_eval_runs (
+                id TEXT PRIMARY KEY,
+                dataset TEXT NOT NULL,
+                k INTEGER NOT NULL,
+                case_count INTEGER NOT NULL,
+                started_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS rag_eval_results (
+                run_id TEXT NOT NULL,
+                mode TEXT NOT NULL,
+                recall_at_k REAL NOT NULL,
+                mrr REAL NOT NULL,
+                avg_latency_ms REAL NOT NULL,
+                latency_p50_ms INTEGER NOT NULL,
+                latency_p90_ms INTEGER NOT NULL,
+                latency_p99_ms INTEGER NOT NULL,
+                errors INTEGER NOT NULL DEFAULT 0,
+                details TEXT NOT NULL,
+                PRIMARY KEY (run_id, mode),
+                FOREIGN KEY (run_id) REFERENCES rag_eval_runs(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Add golden cases to a dataset
+    pub fn add_cases(&self, dataset: &str, cases: Vec<NewEvalCase>) -> Result<Vec<EvalCase>> {
+        let dataset = dataset.trim();
+        if dataset.is_empty() {
+            return Err(anyhow!("Dataset name is required"));
+        }
+
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let conn = db.conn();
+        let now = chrono::Utc::now().timestamp_millis();
+
+        let mut added = Vec::with_capacity(cases.len());
+        for case in cases {
+            if case.query.trim().is_empty() || case.expected_ids.is_empty() {
+                return Err(anyhow!("Each case needs a query and at least one expected memory"));
+            }
+            let case = EvalCase {
+                id: uuid::Uuid::new_v4().to_string(),
+                dataset: dataset.to_string(),
+                query: case.query.trim().to_string(),
+                expected_ids: case.expected_ids,
+                note: case.note,
+                created_at: now,
+            };
+            conn.execute(
+                "INSERT INTO rag_eval_cases (id, dataset, query, expected_ids, note, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    case.id,
+                    case.dataset,
+                    case.query,
+                    serde_json::to_string(&case.expected_ids)?,
+                    case.note,
+                    case.created_at,
+                ],
+            )?;
+            added.push(case);
+        }
+
+        Ok(added)
+    }
+
+    /// Cases of a dataset, oldest first
+    pub fn list_cases(&self, dataset: &str) -> Result<Vec<EvalCase>> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT id, dataset, query, expected_ids, note, created_at
+             FROM rag_eval_cases WHERE dataset = ?1 ORDER BY created_at, id",
+        )?;
+
+        let cases = stmt
+            .query_map(params![dataset], |row| {
+                let expected: String = row.get(3)?;
+                Ok(EvalCase {
+                    id: row.get(0)?,
+                    dataset: row.get(1)?,
+                    query: row.get(2)?,
+                    expected_ids: serde_json::from_str(&expected).unwrap_or_default(),
+                    note: row.get(4)?,
+                    created_at: row.get(5)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(cases)
+    }
+
+    /// Dataset names with their case counts
+    pub fn list_datasets(&self) -> Result<Vec<(String, usize)>> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT dataset, COUNT(*) FROM rag_eval_cases GROUP BY dataset ORDER BY dataset",
+        )?;
+        let datasets = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as usize)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(datasets)
+    }
+
+    pub fn delete_case(&self, case_id: &str) -> Result<bool> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let deleted = db.conn().execute("DELETE FROM rag_eval_cases WHERE id = ?1", params![case_id])?;
+        Ok(deleted > 0)
+    }
+
+    /// Persist a finished run, filling in deltas against the previous run
+    pub fn record_run(&self, dataset: &str, k: usize, started_at: i64, case_count: usize, reports: Vec<ModeReport>) -> Result<EvalRun> {
+        let previous = self.latest_results(dataset)?;
+        let deltas: Vec<RegressionDelta> = reports
+            .iter()
+            .filter_map(|report| {
+                previous
+                    .iter()
+                    .find(|entry| entry.mode == report.mode && entry.k == k)
+                    .map(|entry| regression_delta(report, entry))
+            })
+            .collect();
+
+        let run = EvalRun {
+            id: uuid::Uuid::new_v4().to_string(),
+            dataset: dataset.to_string(),
+            k,
+            started_at,
+            case_count,
+            reports,
+            deltas,
+        };
+
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let conn = db.conn();
+        conn.execute(
+            "INSERT INTO rag_eval_runs (id, dataset, k, case_count, started_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![run.id, run.dataset, run.k as i64, run.case_count as i64, run.started_at],
+        )?;
+        for report in &run.reports {
+            conn.execute(
+                "INSERT INTO rag_eval_results
+                 (run_id, mode, recall_at_k, mrr, avg_latency_ms, latency_p50_ms, latency_p90_ms, latency_p99_ms, errors, details)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    run.id,
+                    report.mode.as_str(),
+                    report.recall_at_k,
+                    report.mrr,
+                    report.avg_latency_ms,
+                    report.latency.p50 as i64,
+                    report.latency.p90 as i64,
+                    report.latency.p99 as i64,
+                    report.errors as i64,
+                    serde_json::to_string(&report.cases)?,
+                ],
+            )?;
+        }
+
+        for delta in run.deltas.iter().filter(|d| d.regressed) {
+            log::warn!(
+                "Retrieval regression on '{}' ({}): recall {:+.3}, MRR {:+.3}",
+                dataset,
+                delta.mode.as_str(),
+                delta.recall_delta,
+                delta.mrr_delta
+            );
+        }
+
+        Ok(run)
+    }
+
+    /// Results of the most recent run of a dataset
+    fn latest_results(&self, dataset: &str) -> Result<Vec<EvalHistoryEntry>> {
+        let run_id: Option<String> = {
+            let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            db.conn()
+                .query_row(
+                    "SELECT id FROM rag_eval_runs WHERE dataset = ?1 ORDER BY started_at DESC LIMIT 1",
+                    params![dataset],
+                    |row| row.get(0),
+                )
+                .optional()?
+        };
+        match run_id {
+            Some(run_id) => Ok(self
+                .history(Some(dataset), usize::MAX)?
+                .into_iter()
+                .filter(|entry| entry.run_id == run_id)
+                .collect()),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Past results, newest run first
+    pub fn history(&self, dataset: Option<&str>, limit: usize) -> Result<Vec<EvalHistoryEntry>> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT r.id, r.dataset, r.k, r.started_at, e.mode, e.recall_at_k, e.mrr,
+                    e.avg_latency_ms, e.latency_p50_ms, e.latency_p90_ms
+             FROM rag_eval_runs r JOIN rag_eval_results e ON e.run_id = r.id
+             WHERE ?1 IS NULL OR r.dataset = ?1
+             ORDER BY r.started_at DESC, e.mode
+             LIMIT ?2",
+        )?;
+
+        let entries = stmt
+            .query_map(params![dataset, limit.min(i64::MAX as usize) as i64], |row| {
+                let mode: String = row.get(4)?;
+                let Some(mode) = RetrievalMode::parse(&mode) else {
+                    return Ok(None);
+                };
+                Ok(Some(EvalHistoryEntry {
+                    run_id: row.get(0)?,
+                    dataset: row.get(1)?,
+                    k: row.get::<_, i64>(2)? as usize,
+                    started_at: row.get(3)?,
+                    mode,
+                    recall_at_k: row.get(5)?,
+                    mrr: row.get(6)?,
+                    avg_latency_ms: row.get(7)?,
+                    latency_p50_ms: row.get::<_, i64>(8)? as u64,
+                    latency_p90_ms: row.get::<_, i64>(9)? as u64,
+                }))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        Ok(entries)
+    }
+
+    /// Per-case details of one configuration of a run
+    pub fn run_details(&self, run_id: &str, mode: RetrievalMode) -> Result<Vec<CaseResult>> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let details: Option<String> = db
+            .conn()
+            .query_row(
+                "SELECT details FROM rag_eval_results WHERE run_id = ?1 AND mode = ?2",
+                params![run_id, mode.as_str()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let details = details.ok_or_else(|| anyhow!("No {} results for run {}", mode.as_str(), run_id))?;
+        Ok(serde_json::from_str(&details)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn case(expected: &[&str]) -> EvalCase {
+        EvalCase {
+            id: "case_1".to_string(),
+            dataset: "golden".to_string(),
+            query: "where do I deploy".to_string(),
+            expected_ids: ids(expected),
+            note: None,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_retrieval_metrics() {
+        let retrieved = ids(&["a", "b", "c", "d"]);
+        assert_eq!(recall_at_k(&retrieved, &ids(&["b", "d"]), 2), 0.5);
+        assert_eq!(recall_at_k(&retrieved, &ids(&["b", "d"]), 4), 1.0);
+        assert_eq!(recall_at_k(&retrieved, &[], 4), 0.0);
+        assert_eq!(first_hit_rank(&retrieved, &ids(&["c", "d"])), Some(3));
+        assert_eq!(first_hit_rank(&retrieved, &ids(&["z"])), None);
+
+        let result = score_case(&case(&["c"]), Ok(retrieved), 3, 12);
+        assert_eq!(result.first_hit_rank, Some(3));
+        assert!((result.reciprocal_rank - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(result.retrieved_ids.len(), 3);
+
+        let failed = score_case(&case(&["c"]), Err("timeout".to_string()), 3, 40);
+        assert_eq!(failed.recall, 0.0);
+        assert!(failed.error.is_some());
+    }
+
+    #[test]
+    fn test_build_report_and_regression() {
+        let results = vec![
+            score_case(&case(&["a"]), Ok(ids(&["a", "b"])), 5, 10),
+            score_case(&case(&["b"]), Ok(ids(&["a", "b"])), 5, 30),
+            score_case(&case(&["z"]), Ok(ids(&["a", "b"])), 5, 20),
+        ];
+        let report = build_report(RetrievalMode::Hybrid, results);
+        assert!((report.recall_at_k - 2.0 / 3.0).abs() < 1e-9);
+        assert!((report.mrr - 0.5).abs() < 1e-9);
+        assert_eq!(report.latency.p50, 20);
+        assert_eq!(report.avg_latency_ms, 20.0);
+
+        let previous = EvalHistoryEntry {
+            run_id: "run_0".to_string(),
+            dataset: "golden".to_string(),
+            k: 5,
+            started_at: 0,
+            mode: RetrievalMode::Hybrid,
+            recall_at_k: 0.9,
+            mrr: 0.5,
+            avg_latency_ms: 15.0,
+            latency_p50_ms: 15,
+            latency_p90_ms: 20,
+        };
+        let delta = regression_delta(&report, &previous);
+        assert!(delta.regressed);
+        assert_eq!(delta.latency_p50_delta_ms, 5);
+    }
+}
@@ -5,20 +5,39 @@
 //! - Stores embeddings in LanceDB vector store
 //! - Maintains backward compatibility with existing API
 //! - v3.4.0 Phase 7: RAFT integration for hallucination reduction
+//! - v3.9.0: LanceDB opened lazily (warm-up in the background), retrieval awaits readiness
+//! - v3.9.0: Vector store backend selectable per profile (LanceDB, SQLite or Qdrant)
 //!
 //! NOTE: This module is only compiled when the `lancedb-support` feature is enabled.
 //! To enable: cargo build --features lancedb-support
 
 #![cfg(feature = "lancedb-support")]
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use crate::database::Database;
+use rusqlite::OptionalExtension;
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::sync::OnceCell;
 
 use super::embedding::UnifiedEmbeddingService;
-use super::vector_store::{VectorStoreService, VectorRecord};
+use super::chunker::{self, chunk_text_with_embedder, Chunk, ChunkingSettings, SourceKind};
+use super::provenance::{Provenance, ProvenanceSource};
+use super::language_detection;  // v3.9.0: Language-tagged memories
+use super::memory_scope::{self, MemoryScope, RetrievalScope};  // v3.9.0: Scoped memories
+use super::vector_backend::{self, VectorBackendConfig, VectorRecord, VectorStore};  // v3.9.0: LanceDB / SQLite / Qdrant
+use super::index_tuning::{self, IndexStatus, IndexTuning, RecallPreference};  // v3.9.0: ANN index tuning
 use super::raft::{RaftService, RaftConfig};
+use super::query_expansion::{self, QueryExpansionOptions};
+
+/// Importance given to ingested document chunks (v3.9.0)
+const DOCUMENT_IMPORTANCE: f32 = 0.5;
+
+/// Memories re-embedded per batch when switching vector backends
+const REINDEX_BATCH: usize = 64;
+
+/// Score bonus for memories the user wrote or corrected by hand (v3.9.0)
+const USER_ASSERTED_BOOST: f32 = 0.15;
 
 /// Episodic memory entry
 #[derive(Debug, Clone)]
@@ -31,14 +50,47 @@ pub struct Episode {
     pub access_count: i32,
     pub importance: f32,
     pub embedding_id: Option<String>,
+    /// Chat the episode was stored from (v3.9.0, `None` for older episodes)
+    pub conversation_id: Option<String>,
+    pub message_id: Option<String>,
+    /// Document the episode was chunked from (v3.9.0, `None` for conversations)
+    pub document: Option<String>,
+    /// Language of the user message (v3.9.0, ISO 639-1, `None` if undetected)
+    pub language: Option<String>,
+    /// Written or corrected by the user by hand (v3.9.0), ranked above inferred memories
+    pub user_asserted: bool,
+    /// User's annotation (v3.9.0)
+    pub user_note: Option<String>,
+}
+
+impl Episode {
+    /// Provenance record for citations (v3.9.0)
+    pub fn provenance(&self) -> Provenance {
+        match &self.document {
+            Some(document) => Provenance::new(ProvenanceSource::Document, &self.id)
+                .with_document(document.clone())
+                .with_timestamp_secs(self.created_at),
+            None => Provenance::new(ProvenanceSource::Episode, &self.id)
+                .with_conversation(self.conversation_id.clone(), self.message_id.clone())
+                .with_timestamp_secs(self.created_at),
+        }
+    }
+
+    /// Ranking bonus that puts user-asserted memories ahead of inferred ones (v3.9.0)
+    pub fn assertion_boost(&self) -> f32 {
+        if self.user_asserted { USER_ASSERTED_BOOST } else { 0.0 }
+    }
 }
 
 /// RAG Service v2 for episodic memory retrieval using LanceDB
 pub struct RagServiceV2 {
     db: Arc<Mutex<Database>>,
     embedding_service: Arc<UnifiedEmbeddingService>,
-    vector_store: Arc<VectorStoreService>,
+    vector_store: OnceCell<RwLock<Arc<dyn VectorStore>>>,  // v3.9.0: Opened on first use, swappable per profile / backend
+    lance_db_path: RwLock<PathBuf>,
+    init_error: RwLock<Option<String>>,  // v3.9.0: Last failed open, cleared on success
     raft_service: Arc<Mutex<RaftService>>,
+    chunking: RwLock<ChunkingSettings>,  // v3.9.0: Per-source chunking
 }
 
 impl RagServiceV2 {
@@ -48,23 +100,143 @@ impl RagServiceV2 {
         embedding_service: Arc<UnifiedEmbeddingService>,
         lance_db_path: PathBuf,
     ) -> Result<Self> {
-        log::info!("Initializing RAG v2 service with LanceDB at {:?}", lance_db_path);
+        let service = Self::new_lazy(db, embedding_service, lance_db_path);
+        service.warm_up().await?;
+        Ok(service)
+    }
 
-        // Initialize LanceDB vector store
-        let vector_store = Arc::new(
-            VectorStoreService::new(lance_db_path, "episodic_memory").await?
-        );
+    /// Create the service without opening LanceDB (v3.9.0)
+    ///
+    /// The vector store is opened by `warm_up` or by the first call that needs it.
+    pub fn new_lazy(
+        db: Arc<Mutex<Database>>,
+        embedding_service: Arc<UnifiedEmbeddingService>,
+        lance_db_path: PathBuf,
+    ) -> Self {
+        log::info!("Initializing RAG v2 service with LanceDB at {:?} (lazy)", lance_db_path);
 
         // Initialize RAFT service with default configuration
         let raft_service = Arc::new(Mutex::new(RaftService::with_defaults()));
         log::info!("✓ RAFT hallucination reduction initialized (relevance: 0.5, confidence: 0.6)");
 
-        Ok(Self {
+        Self {
             db,
             embedding_service,
-            vector_store,
+            vector_store: OnceCell::new(),
+            lance_db_path: RwLock::new(lance_db_path),
+            init_error: RwLock::new(None),
             raft_service,
-        })
+            chunking: RwLock::new(ChunkingSettings::default()),
+        }
+    }
+
+    /// Open LanceDB now instead of on first use (v3.9.0)
+    pub async fn warm_up(&self) -> Result<()> {
+        self.vector_store().await.map(|_| ())
+    }
+
+    /// Whether LanceDB is open and retrieval won't wait (v3.9.0)
+    pub fn is_ready(&self) -> bool {
+        self.vector_store.initialized()
+    }
+
+    /// Why the last attempt to open LanceDB failed (v3.9.0)
+    pub fn init_error(&self) -> Option<String> {
+        self.init_error.read().ok().and_then(|e| e.clone())
+    }
+
+    /// Point the vector store at another LanceDB directory (v3.9.0: profile switch)
+    ///
+    /// SQLite metadata follows the shared database handle, which the caller rebinds;
+    /// the new profile's backend selection applies.
+    /// A store that was never opened is opened at the new path on first use.
+    pub async fn rebind(&self, lance_db_path: PathBuf) -> Result<()> {
+        log::info!("Rebinding RAG v2 vector store to {:?}", lance_db_path);
+        *self.lance_db_path.write().map_err(|e| anyhow!("Vector store lock error: {}", e))? = lance_db_path.clone();
+        if let Some(slot) = self.vector_store.get() {
+            let config = vector_backend::load_config(&self.db)?;
+            let vector_store = vector_backend::open(&config, &self.db, lance_db_path, "episodic_memory").await?;
+            *slot.write().map_err(|e| anyhow!("Vector store lock error: {}", e))? = vector_store;
+        }
+        Ok(())
+    }
+
+    /// Move episodic memory to another vector store backend (v3.9.0)
+    ///
+    /// The new store is opened before the selection is saved, so an unreachable
+    /// server leaves the current one in place. With `reindex`, every memory is
+    /// re-embedded into the new store; returns the number of vectors written.
+    pub async fn switch_vector_backend(&self, config: VectorBackendConfig, reindex: bool) -> Result<usize> {
+        let lance_db_path = self.lance_db_path.read().map_err(|e| anyhow!("Vector store lock error: {}", e))?.clone();
+        let vector_store = vector_backend::open(&config, &self.db, lance_db_path, "episodic_memory").await?;
+        vector_backend::save_config(&self.db, &config)?;
+        match self.vector_store.get() {
+            Some(slot) => *slot.write().map_err(|e| anyhow!("Vector store lock error: {}", e))? = vector_store,
+            None => {
+                let _ = self.vector_store.set(RwLock::new(vector_store));
+            }
+        }
+        log::info!("Episodic memory now uses the {:?} vector store", config.backend);
+
+        if !reindex {
+            return Ok(0);
+        }
+        let ids: Vec<String> = {
+            let db_guard = self.db.lock().unwrap();
+            let mut stmt = db_guard.conn().prepare("SELECT id FROM episodic_memory")?;
+            let rows = stmt.query_map([], |row| row.get(0))?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+        let mut reindexed = 0;
+        for batch in ids.chunks(REINDEX_BATCH) {
+            reindexed += self.reindex_memories(batch).await?;
+        }
+        log::info!("Re-embedded {} memories into the new vector store", reindexed);
+        Ok(reindexed)
+    }
+
+    /// Backend the vector store is (or will be) opened with (v3.9.0)
+    pub fn vector_backend_config(&self) -> Result<VectorBackendConfig> {
+        vector_backend::load_config(&self.db)
+    }
+
+    /// Encrypt vector store payloads written before encryption was enabled (v3.9.0)
+    pub async fn seal_payloads(&self) -> Result<usize> {
+        self.vector_store().await?.seal_payloads().await
+    }
+
+    /// Current vector store (cloned out so no lock is held across awaits)
+    ///
+    /// Opens the configured backend on the first call; concurrent callers wait for the same open,
+    /// and a failed open is retried by the next call.
+    async fn vector_store(&self) -> Result<Arc<dyn VectorStore>> {
+        let slot = self
+            .vector_store
+            .get_or_try_init(|| async {
+                let lance_db_path = self.lance_db_path.read().map_err(|e| anyhow!("Vector store lock error: {}", e))?.clone();
+                let opened = match vector_backend::load_config(&self.db) {
+                    Ok(config) => vector_backend::open(&config, &self.db, lance_db_path, "episodic_memory").await,
+                    Err(e) => Err(e),
+                };
+                match opened {
+                    Ok(vector_store) => {
+                        if let Ok(mut error) = self.init_error.write() {
+                            *error = None;
+                        }
+                        log::info!("✓ Vector store ready");
+                        Ok(RwLock::new(vector_store))
+                    }
+                    Err(e) => {
+                        if let Ok(mut error) = self.init_error.write() {
+                            *error = Some(e.to_string());
+                        }
+                        Err(e)
+                    }
+                }
+            })
+            .await?;
+        let vector_store = slot.read().map_err(|e| anyhow!("Vector store lock error: {}", e))?;
+        Ok(Arc::clone(&vector_store))
     }
 
     /// Store a conversation episode with embedding in LanceDB
@@ -73,12 +245,78 @@ impl RagServiceV2 {
         user_message: &str,
         ai_response: &str,
         satisfaction: f32,
+    ) -> Result<String> {
+        self.store_episode_with_source(user_message, ai_response, satisfaction, None, None).await
+    }
+
+    /// Store an episode along with the chat it came from (v3.9.0 - provenance)
+    ///
+    /// Replies longer than one conversation chunk are stored as one episode
+    /// per chunk (v3.9.0); the id of the first chunk is returned.
+    pub async fn store_episode_with_source(
+        &self,
+        user_message: &str,
+        ai_response: &str,
+        satisfaction: f32,
+        conversation_id: Option<&str>,
+        message_id: Option<&str>,
+    ) -> Result<String> {
+        self.store_episode_in_scope(user_message, ai_response, satisfaction, conversation_id, message_id, &MemoryScope::Global)
+            .await
+    }
+
+    /// Store an episode only retrievable within `scope` (v3.9.0)
+    pub async fn store_episode_in_scope(
+        &self,
+        user_message: &str,
+        ai_response: &str,
+        satisfaction: f32,
+        conversation_id: Option<&str>,
+        message_id: Option<&str>,
+        scope: &MemoryScope,
+    ) -> Result<String> {
+        let max_tokens = self.get_chunking_settings().conversation.chunk_size;
+        let ids = if chunker::estimate_tokens(ai_response) <= max_tokens {
+            vec![self
+                .insert_episode(user_message, ai_response, satisfaction, conversation_id, message_id, None)
+                .await?]
+        } else {
+            let chunks = self.chunk(ai_response, SourceKind::Conversation);
+            let mut ids = Vec::with_capacity(chunks.len());
+            for chunk in &chunks {
+                ids.push(
+                    self.insert_episode(user_message, &chunk.text, satisfaction, conversation_id, message_id, None)
+                        .await?,
+                );
+            }
+            log::info!("Stored long episode as {} chunks", chunks.len());
+            ids
+        };
+        self.assign_scope(&ids, scope)?;
+        ids.into_iter().next().ok_or_else(|| anyhow!("Episode has no content to store"))
+    }
+
+    /// Scope freshly stored episodes (v3.9.0)
+    fn assign_scope(&self, ids: &[String], scope: &MemoryScope) -> Result<()> {
+        let db_guard = self.db.lock().unwrap();
+        memory_scope::assign(db_guard.conn(), ids, scope)
+    }
+
+    /// Insert one episode row (and its embedding)
+    async fn insert_episode(
+        &self,
+        user_message: &str,
+        ai_response: &str,
+        satisfaction: f32,
+        conversation_id: Option<&str>,
+        message_id: Option<&str>,
+        document: Option<&str>,
     ) -> Result<String> {
         log::info!("Storing episode: user_message length = {}", user_message.len());
 
         // Generate embedding for the conversation
         let combined_text = format!("{}\n{}", user_message, ai_response);
-        let embedding = self.embedding_service.embed(&combined_text)?;
+        let embedding = self.embed_blocking(&combined_text).await?;
 
         // Generate unique ID
         let id = uuid::Uuid::new_v4().to_string();
@@ -91,8 +329,8 @@ impl RagServiceV2 {
             db.execute(
                 "INSERT INTO episodic_memory (
                     id, user_message, ai_response, satisfaction, created_at,
-                    access_count, importance, embedding_id
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    access_count, importance, embedding_id, conversation_id, message_id, document, language
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
                 rusqlite::params![
                     id,
                     user_message,
@@ -102,6 +340,10 @@ impl RagServiceV2 {
                     0, // initial access_count
                     satisfaction, // initial importance = satisfaction
                     id, // embedding_id references the LanceDB record
+                    conversation_id,
+                    message_id,
+                    document,
+                    language_detection::language_code(user_message),
                 ],
             )?;
         }
@@ -120,12 +362,224 @@ impl RagServiceV2 {
             metadata,
         };
 
-        self.vector_store.insert(vec![vector_record]).await?;
+        self.vector_store().await?.insert(vec![vector_record]).await?;
 
         log::info!("Stored episode with ID: {} in LanceDB", id);
         Ok(id)
     }
 
+    /// Per-source chunking settings (v3.9.0)
+    pub fn get_chunking_settings(&self) -> ChunkingSettings {
+        self.chunking.read().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    /// Update per-source chunking settings (v3.9.0)
+    pub fn update_chunking_settings(&self, settings: ChunkingSettings) -> Result<()> {
+        for source in SourceKind::ALL {
+            let config = settings.for_source(source);
+            if config.chunk_size == 0 || config.overlap >= config.chunk_size {
+                return Err(anyhow!("Invalid {:?} chunking: overlap must be smaller than a non-zero chunk size", source));
+            }
+        }
+        *self.chunking.write().map_err(|e| anyhow!("Chunking settings lock error: {}", e))? = settings;
+        log::info!("RAG chunking settings updated");
+        Ok(())
+    }
+
+    /// Split text with the settings for its source kind
+    pub fn chunk(&self, text: &str, source: SourceKind) -> Vec<Chunk> {
+        let config = self.get_chunking_settings().for_source(source).clone();
+        chunk_text_with_embedder(text, source, &config, |t| self.embedding_service.embed(t))
+    }
+
+    /// `chunk` on the blocking pool (semantic chunking embeds sentences)
+    async fn chunk_blocking(&self, text: &str, source: SourceKind) -> Result<Vec<Chunk>> {
+        let config = self.get_chunking_settings().for_source(source).clone();
+        let embedding_service = Arc::clone(&self.embedding_service);
+        let text = text.to_string();
+        Ok(tokio::task::spawn_blocking(move || {
+            chunk_text_with_embedder(&text, source, &config, |t| embedding_service.embed(t))
+        })
+        .await?)
+    }
+
+    /// Embed on the blocking pool so callers on the async runtime are not stalled
+    async fn embed_blocking(&self, text: &str) -> Result<Vec<f32>> {
+        let embedding_service = Arc::clone(&self.embedding_service);
+        let text = text.to_string();
+        tokio::task::spawn_blocking(move || embedding_service.embed(&text)).await?
+    }
+
+    /// Chunk a document and store each chunk as an episode (v3.9.0)
+    ///
+    /// Returns the ids of the stored chunks in document order.
+    pub async fn ingest_document(
+        &self,
+        name: &str,
+        content: &str,
+        source: Option<SourceKind>,
+    ) -> Result<Vec<String>> {
+        self.ingest_document_in_scope(name, content, source, &MemoryScope::Global).await
+    }
+
+    /// Ingest a document only retrievable within `scope` (v3.9.0)
+    pub async fn ingest_document_in_scope(
+        &self,
+        name: &str,
+        content: &str,
+        source: Option<SourceKind>,
+        scope: &MemoryScope,
+    ) -> Result<Vec<String>> {
+        let source = source.unwrap_or_else(|| SourceKind::detect(name, content));
+        let chunks = self.chunk_blocking(content, source).await?;
+        log::info!("Ingesting document '{}' as {} {:?} chunks", name, chunks.len(), source);
+
+        let mut ids = Vec::with_capacity(chunks.len());
+        for chunk in &chunks {
+            let label = format!("{} (part {}/{})", name, chunk.index + 1, chunks.len());
+            let id = self
+                .insert_episode(&label, &chunk.text, DOCUMENT_IMPORTANCE, None, None, Some(name))
+                .await?;
+            ids.push(id);
+        }
+        self.assign_scope(&ids, scope)?;
+        Ok(ids)
+    }
+
+    /// Store a memory the user wrote by hand (v3.9.0)
+    ///
+    /// Embedded right away and marked user-asserted, with full satisfaction
+    /// and importance.
+    pub async fn store_user_memory(&self, content: &str, note: Option<&str>, scope: &MemoryScope) -> Result<String> {
+        let id = self.insert_episode(content, "", 1.0, None, None, None).await?;
+        self.assign_scope(std::slice::from_ref(&id), scope)?;
+
+        {
+            let db_guard = self.db.lock().unwrap();
+            db_guard.conn().execute(
+                "UPDATE episodic_memory SET user_asserted = 1, user_note = ?1 WHERE id = ?2",
+                rusqlite::params![note, id],
+            )?;
+        }
+
+        log::info!("Stored user-asserted memory {}", id);
+        Ok(id)
+    }
+
+    /// Replace a memory's text with the user's correction (v3.9.0)
+    ///
+    /// The memory is re-embedded and becomes user-asserted.
+    pub async fn edit_memory(&self, id: &str, user_message: &str, ai_response: &str) -> Result<()> {
+        let combined_text = format!("{}\n{}", user_message, ai_response);
+        let embedding = self.embedding_service.embed(&combined_text)?;
+
+        let (satisfaction, created_at, importance): (f32, i64, f32) = {
+            let db_guard = self.db.lock().unwrap();
+            let db = db_guard.conn();
+            let updated = db.execute(
+                "UPDATE episodic_memory
+                 SET user_message = ?1, ai_response = ?2, language = ?3, user_asserted = 1
+                 WHERE id = ?4",
+                rusqlite::params![user_message, ai_response, language_detection::language_code(user_message), id],
+            )?;
+            if updated == 0 {
+                return Err(anyhow!("Memory not found: {}", id));
+            }
+            db.query_row(
+                "SELECT satisfaction, created_at, importance FROM episodic_memory WHERE id = ?1",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )?
+        };
+
+        // Replace the LanceDB record under the same ID
+        let metadata = serde_json::json!({
+            "satisfaction": satisfaction,
+            "created_at": created_at,
+            "importance": importance,
+        }).to_string();
+        let vector_store = self.vector_store().await?;
+        vector_store.delete(&[id.to_string()]).await?;
+        vector_store.insert(vec![VectorRecord {
+            id: id.to_string(),
+            text: combined_text,
+            embedding,
+            metadata,
+        }]).await?;
+
+        log::info!("Memory {} corrected by the user", id);
+        Ok(())
+    }
+
+    /// Delete memories by id, vectors included (v3.9.0)
+    pub async fn delete_memories(&self, ids: &[String]) -> Result<usize> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        self.vector_store().await?.delete(ids).await?;
+
+        let db_guard = self.db.lock().unwrap();
+        let mut deleted = 0;
+        for id in ids {
+            deleted += db_guard.conn().execute("DELETE FROM episodic_memory WHERE id = ?1", [id])?;
+        }
+
+        log::info!("Deleted {} memories", deleted);
+        Ok(deleted)
+    }
+
+    /// Remove vectors only, leaving the SQLite rows to the caller (v3.9.0 privacy forget)
+    pub async fn delete_vectors(&self, ids: &[String]) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.vector_store().await?.delete(ids).await
+    }
+
+    /// Re-embed memories written by another device (v3.9.0 device sync)
+    ///
+    /// Vectors of memories that no longer exist are removed.
+    pub async fn reindex_memories(&self, ids: &[String]) -> Result<usize> {
+        let vector_store = self.vector_store().await?;
+        vector_store.delete(ids).await?;
+
+        let mut records = Vec::new();
+        for id in ids {
+            let row: Option<(String, String, f32, i64, f32)> = {
+                let db_guard = self.db.lock().unwrap();
+                db_guard.conn().query_row(
+                    "SELECT user_message, ai_response, satisfaction, created_at, importance
+                     FROM episodic_memory WHERE id = ?1",
+                    [id],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+                ).optional()?
+            };
+            let Some((user_message, ai_response, satisfaction, created_at, importance)) = row else {
+                continue;
+            };
+
+            let combined_text = format!("{}\n{}", user_message, ai_response);
+            let embedding = self.embedding_service.embed(&combined_text)?;
+            let metadata = serde_json::json!({
+                "satisfaction": satisfaction,
+                "created_at": created_at,
+                "importance": importance,
+            }).to_string();
+            records.push(VectorRecord {
+                id: id.clone(),
+                text: combined_text,
+                embedding,
+                metadata,
+            });
+        }
+
+        let reindexed = records.len();
+        if !records.is_empty() {
+            vector_store.insert(records).await?;
+        }
+        Ok(reindexed)
+    }
+
     /// Retrieve relevant episodes for a query using LanceDB vector search
     pub async fn retrieve_relevant(&self, query: &str, top_k: usize) -> Result<Vec<Episode>> {
         log::info!("Retrieving {} relevant episodes for query using LanceDB", top_k);
@@ -133,14 +587,26 @@ impl RagServiceV2 {
         // Generate query embedding
         let query_embedding = self.embedding_service.embed(query)?;
 
-        // Search LanceDB for similar vectors
-        let search_results = self.vector_store.search(&query_embedding, top_k).await?;
+        // Search LanceDB for similar vectors (v3.9.0: 2x candidates so user-asserted memories can move up)
+        let search_results = self.vector_store().await?.search(&query_embedding, top_k * 2).await?;
 
         // Fetch metadata from SQLite for the found IDs
         let ids: Vec<String> = search_results.iter().map(|r| r.id.clone()).collect();
-        let episodes = self.get_episodes_by_ids(&ids)?;
+        let candidates = self.get_episodes_by_ids(&ids, &RetrievalScope::global())?;
+
+        let mut scored: Vec<(Episode, f32)> = search_results
+            .iter()
+            .filter_map(|result| {
+                candidates.iter()
+                    .find(|ep| ep.id == result.id)
+                    .map(|episode| (episode.clone(), result.score + episode.assertion_boost()))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        let episodes: Vec<Episode> = scored.into_iter().take(top_k).map(|(episode, _)| episode).collect();
 
         // Update access counts
+        let ids: Vec<String> = episodes.iter().map(|e| e.id.clone()).collect();
         self.increment_access_counts(&ids)?;
 
         log::info!("Retrieved {} relevant episodes using LanceDB", episodes.len());
@@ -161,11 +627,11 @@ impl RagServiceV2 {
 
         // Search LanceDB (get more results for re-ranking)
         let candidate_count = top_k * 3; // Get 3x candidates for temporal re-ranking
-        let search_results = self.vector_store.search(&query_embedding, candidate_count).await?;
+        let search_results = self.vector_store().await?.search(&query_embedding, candidate_count).await?;
 
         // Fetch episodes with retention scores from SQLite
         let ids: Vec<String> = search_results.iter().map(|r| r.id.clone()).collect();
-        let episodes_with_retention = self.get_episodes_with_retention(&ids)?;
+        let episodes_with_retention = self.get_episodes_with_retention(&ids, &RetrievalScope::global())?;
 
         // Combine semantic similarity from LanceDB with temporal retention
         let mut scored_episodes: Vec<(Episode, f32)> = search_results
@@ -175,7 +641,7 @@ impl RagServiceV2 {
                     .find(|(ep, _)| ep.id == result.id)
                     .map(|(episode, retention_score)| {
                         // Weighted combination: 70% semantic, 30% temporal
-                        let combined_score = (result.score * 0.7) + (retention_score * 0.3);
+                        let combined_score = (result.score * 0.7) + (retention_score * 0.3) + episode.assertion_boost();
                         (episode.clone(), combined_score)
                     })
             })
@@ -199,6 +665,39 @@ impl RagServiceV2 {
         Ok(top_episodes)
     }
 
+    /// Retrieve relevant episodes after query expansion / HyDE (v3.9.0)
+    ///
+    /// Each query variant is searched separately and the result lists are
+    /// merged by reciprocal rank.
+    pub async fn retrieve_relevant_expanded(
+        &self,
+        query: &str,
+        top_k: usize,
+        options: &QueryExpansionOptions,
+    ) -> Result<Vec<Episode>> {
+        let queries = query_expansion::expand_query(query, options).await;
+        if queries.len() <= 1 {
+            return self.retrieve_relevant(query, top_k).await;
+        }
+
+        let mut lists = Vec::with_capacity(queries.len());
+        for variant in &queries {
+            let scored = self.search_with_scores(variant, top_k).await?;
+            lists.push(scored.into_iter().map(|(episode, _)| episode).collect::<Vec<_>>());
+        }
+        let top_episodes = query_expansion::merge_ranked(lists, |episode| episode.id.clone(), top_k);
+
+        let ids: Vec<String> = top_episodes.iter().map(|e| e.id.clone()).collect();
+        self.increment_access_counts(&ids)?;
+
+        log::info!(
+            "Retrieved {} episodes with {} query variants",
+            top_episodes.len(),
+            queries.len()
+        );
+        Ok(top_episodes)
+    }
+
     /// Search memory semantically using LanceDB
     pub async fn search_memory(&self, query: &str, limit: usize) -> Result<Vec<Episode>> {
         self.retrieve_relevant(query, limit).await
@@ -212,27 +711,45 @@ impl RagServiceV2 {
     /// Search episodes with similarity scores (v3.8.0 Phase 4 - for contextual retrieval)
     /// Returns episodes paired with their LanceDB similarity scores
     pub async fn search_with_scores(&self, query: &str, top_k: usize) -> Result<Vec<(Episode, f32)>> {
+        self.search_with_scores_in(query, top_k, &RetrievalScope::global()).await
+    }
+
+    /// Search with scores among global memories and those of `scope` (v3.9.0)
+    pub async fn search_with_scores_in(
+        &self,
+        query: &str,
+        top_k: usize,
+        scope: &RetrievalScope,
+    ) -> Result<Vec<(Episode, f32)>> {
         log::info!("Searching {} episodes with similarity scores", top_k);
 
         // Generate query embedding
         let query_embedding = self.embedding_service.embed(query)?;
 
-        // Search LanceDB
-        let search_results = self.vector_store.search(&query_embedding, top_k).await?;
+        // Search LanceDB (v3.9.0: 2x candidates so user-asserted memories can move up)
+        let search_results = self.vector_store().await?.search(&query_embedding, top_k * 2).await?;
 
         // Fetch episodes from SQLite
         let ids: Vec<String> = search_results.iter().map(|r| r.id.clone()).collect();
-        let episodes = self.get_episodes_by_ids(&ids)?;
+        let episodes = self.get_episodes_by_ids(&ids, scope)?;
 
         // Pair episodes with their similarity scores
-        let scored_episodes: Vec<(Episode, f32)> = search_results
+        // v3.9.0: Memories in the query's language win near ties
+        let query_language = language_detection::language_code(query);
+        let mut scored_episodes: Vec<(Episode, f32)> = search_results
             .iter()
             .filter_map(|result| {
                 episodes.iter()
                     .find(|ep| ep.id == result.id)
-                    .map(|episode| (episode.clone(), result.score))
+                    .map(|episode| {
+                        let boost = language_detection::same_language_boost(query_language.as_deref(), episode.language.as_deref())
+                            + episode.assertion_boost();
+                        (episode.clone(), result.score + boost)
+                    })
             })
             .collect();
+        scored_episodes.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored_episodes.truncate(top_k);
 
         // Note: Intentionally NOT updating access counts here
         log::info!("Found {} scored episodes", scored_episodes.len());
@@ -253,11 +770,11 @@ impl RagServiceV2 {
 
         // Search LanceDB for candidates (get 3x for RAFT filtering)
         let candidate_count = top_k * 3;
-        let search_results = self.vector_store.search(&query_embedding, candidate_count).await?;
+        let search_results = self.vector_store().await?.search(&query_embedding, candidate_count).await?;
 
         // Fetch episodes from SQLite
         let ids: Vec<String> = search_results.iter().map(|r| r.id.clone()).collect();
-        let episodes = self.get_episodes_by_ids(&ids)?;
+        let episodes = self.get_episodes_by_ids(&ids, &RetrievalScope::global())?;
 
         // Pair episodes with similarity scores for RAFT
         let scored_episodes: Vec<(Episode, f32)> = search_results
@@ -311,7 +828,8 @@ impl RagServiceV2 {
 
         let mut stmt = db.prepare(
             "SELECT id, user_message, ai_response, satisfaction, created_at,
-                    access_count, importance, embedding_id
+                    access_count, importance, embedding_id, conversation_id, message_id, document, language,
+                    COALESCE(user_asserted, 0), user_note
              FROM episodic_memory
              ORDER BY created_at DESC
              LIMIT ?1"
@@ -328,6 +846,12 @@ impl RagServiceV2 {
                     access_count: row.get(5)?,
                     importance: row.get(6)?,
                     embedding_id: row.get(7)?,
+                    conversation_id: row.get(8)?,
+                    message_id: row.get(9)?,
+                    document: row.get(10)?,
+                    language: row.get(11)?,
+                    user_asserted: row.get(12)?,
+                    user_note: row.get(13)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -361,7 +885,7 @@ impl RagServiceV2 {
 
         if count > 0 {
             // Delete from LanceDB
-            self.vector_store.delete(&ids_to_delete).await?;
+            self.vector_store().await?.delete(&ids_to_delete).await?;
 
             // Delete from SQLite
             let db_guard = self.db.lock().unwrap();
@@ -414,13 +938,13 @@ impl RagServiceV2 {
 
     /// Get vector count from LanceDB
     pub async fn get_vector_count(&self) -> Result<usize> {
-        self.vector_store.count().await
+        self.vector_store().await?.count().await
     }
 
     /// Optimize LanceDB storage (compact and rebuild indexes)
     pub async fn optimize_vector_store(&self) -> Result<()> {
         log::info!("Optimizing LanceDB vector store");
-        self.vector_store.compact().await?;
+        self.vector_store().await?.compact().await?;
         log::info!("Vector store optimization complete");
         Ok(())
     }
@@ -428,25 +952,54 @@ impl RagServiceV2 {
     /// Create optimized index for large datasets (>10,000 vectors)
     /// Call this after storing a large number of episodes
     pub async fn create_search_index(&self) -> Result<()> {
-        let count = self.vector_store.count().await?;
-        if count < 10_000 {
-            log::info!("Skipping index creation: only {} vectors (need 10,000+)", count);
+        let vector_store = self.vector_store().await?;
+        let count = vector_store.count().await?;
+        if !index_tuning::should_index(count) {
+            log::info!("Skipping index creation: only {} vectors (need {}+)", count, index_tuning::MIN_INDEXED_VECTORS);
             return Ok(());
         }
 
-        let num_partitions = (count as f64).sqrt() as usize;
-        let num_sub_vectors = 1024 / 16; // BGE-M3 dimension / 16
-
         log::info!("Creating IVF-PQ index for {} vectors", count);
-        self.vector_store.create_index(num_partitions, num_sub_vectors).await?;
+        vector_store.create_index(&index_tuning::load(&self.db)?).await?;
         log::info!("Search index created successfully");
         Ok(())
     }
 
+    /// Tuning in effect, with the store it applies to (v3.9.0)
+    pub async fn index_status(&self) -> Result<IndexStatus> {
+        let vector_store = self.vector_store().await?;
+        Ok(IndexStatus {
+            backend: vector_store.backend(),
+            vectors: vector_store.count().await?,
+            indexed: vector_store.has_index().await?,
+            tuning: index_tuning::load(&self.db)?,
+        })
+    }
+
+    /// Tuning derived from the current corpus size (v3.9.0)
+    pub async fn auto_tune_index(&self, preference: RecallPreference) -> Result<IndexTuning> {
+        let count = self.vector_store().await?.count().await?;
+        Ok(index_tuning::auto_tune(count, preference))
+    }
+
+    /// Save and apply ANN index tuning (v3.9.0)
+    ///
+    /// Query parameters apply to the next search; with `rebuild`, the index is
+    /// rebuilt with the new build parameters (skipped below 10,000 vectors).
+    pub async fn configure_index(&self, tuning: IndexTuning, rebuild: bool) -> Result<IndexStatus> {
+        index_tuning::save(&self.db, &tuning)?;
+        self.vector_store().await?.set_tuning(&tuning);
+        log::info!("Index tuning updated: {:?}", tuning);
+        if rebuild {
+            self.create_search_index().await?;
+        }
+        self.index_status().await
+    }
+
     // === Private helper methods ===
 
-    /// Get episodes by their IDs from SQLite
-    fn get_episodes_by_ids(&self, ids: &[String]) -> Result<Vec<Episode>> {
+    /// Get episodes by their IDs from SQLite, leaving out those outside `scope`
+    fn get_episodes_by_ids(&self, ids: &[String], scope: &RetrievalScope) -> Result<Vec<Episode>> {
         if ids.is_empty() {
             return Ok(vec![]);
         }
@@ -458,15 +1011,17 @@ impl RagServiceV2 {
         let placeholders = ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
         let query = format!(
             "SELECT id, user_message, ai_response, satisfaction, created_at,
-                    access_count, importance, embedding_id
+                    access_count, importance, embedding_id, conversation_id, message_id, document, language,
+                    COALESCE(user_asserted, 0), user_note
              FROM episodic_memory
-             WHERE id IN ({})",
-            placeholders
+             WHERE id IN ({}) AND {}",
+            placeholders,
+            scope.sql_filter()
         );
 
         let mut stmt = db.prepare(&query)?;
         let episodes = stmt
-            .query_map(rusqlite::params_from_iter(ids), |row| {
+            .query_map(rusqlite::params_from_iter(ids.iter().map(String::as_str).chain(scope.params())), |row| {
                 Ok(Episode {
                     id: row.get(0)?,
                     user_message: row.get(1)?,
@@ -476,6 +1031,12 @@ impl RagServiceV2 {
                     access_count: row.get(5)?,
                     importance: row.get(6)?,
                     embedding_id: row.get(7)?,
+                    conversation_id: row.get(8)?,
+                    message_id: row.get(9)?,
+                    document: row.get(10)?,
+                    language: row.get(11)?,
+                    user_asserted: row.get(12)?,
+                    user_note: row.get(13)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -484,7 +1045,7 @@ impl RagServiceV2 {
     }
 
     /// Get episodes with retention scores by IDs (v3.8.0 Phase 3)
-    fn get_episodes_with_retention(&self, ids: &[String]) -> Result<Vec<(Episode, f32)>> {
+    fn get_episodes_with_retention(&self, ids: &[String], scope: &RetrievalScope) -> Result<Vec<(Episode, f32)>> {
         if ids.is_empty() {
             return Ok(vec![]);
         }
@@ -495,16 +1056,18 @@ impl RagServiceV2 {
         let placeholders = ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
         let query = format!(
             "SELECT id, user_message, ai_response, satisfaction, created_at,
-                    access_count, importance, embedding_id,
-                    COALESCE(retention_score, 1.0) as retention_score
+                    access_count, importance, embedding_id, conversation_id, message_id,
+                    COALESCE(retention_score, 1.0) as retention_score, document, language,
+                    COALESCE(user_asserted, 0), user_note
              FROM episodic_memory
-             WHERE id IN ({})",
-            placeholders
+             WHERE id IN ({}) AND {}",
+            placeholders,
+            scope.sql_filter()
         );
 
         let mut stmt = db.prepare(&query)?;
         let episodes = stmt
-            .query_map(rusqlite::params_from_iter(ids), |row| {
+            .query_map(rusqlite::params_from_iter(ids.iter().map(String::as_str).chain(scope.params())), |row| {
                 let episode = Episode {
                     id: row.get(0)?,
                     user_message: row.get(1)?,
@@ -514,8 +1077,14 @@ impl RagServiceV2 {
                     access_count: row.get(5)?,
                     importance: row.get(6)?,
                     embedding_id: row.get(7)?,
+                    conversation_id: row.get(8)?,
+                    message_id: row.get(9)?,
+                    document: row.get(11)?,
+                    language: row.get(12)?,
+                    user_asserted: row.get(13)?,
+                    user_note: row.get(14)?,
                 };
-                let retention_score: f32 = row.get::<_, f64>(8)? as f32;
+                let retention_score: f32 = row.get::<_, f64>(10)? as f32;
                 Ok((episode, retention_score))
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -524,7 +1093,7 @@ impl RagServiceV2 {
     }
 
     /// Increment access counts for episodes
-    fn increment_access_counts(&self, ids: &[String]) -> Result<()> {
+    pub(crate) fn increment_access_counts(&self, ids: &[String]) -> Result<()> {
         if ids.is_empty() {
             return Ok(());
         }
@@ -582,13 +1151,23 @@ pub fn format_episodes_for_context(episodes: &[Episode]) -> String {
     let mut context = String::from("Relevant past conversations:\n\n");
 
     for (i, episode) in episodes.iter().enumerate() {
-        context.push_str(&format!(
-            "{}. User: {}\n   AI: {}\n   (Satisfaction: {:.2})\n\n",
-            i + 1,
-            episode.user_message,
-            episode.ai_response,
-            episode.satisfaction
-        ));
+        if episode.user_asserted && episode.ai_response.is_empty() {
+            // v3.9.0: Written by the user, not a conversation
+            context.push_str(&format!("{}. Stated by the user: {}\n", i + 1, episode.user_message));
+        } else {
+            context.push_str(&format!(
+                "{}. User: {}\n   AI: {}\n   (Satisfaction: {:.2}{})\n",
+                i + 1,
+                episode.user_message,
+                episode.ai_response,
+                episode.satisfaction,
+                if episode.user_asserted { ", confirmed by the user" } else { "" }
+            ));
+        }
+        if let Some(note) = &episode.user_note {
+            context.push_str(&format!("   Note from the user: {}\n", note));
+        }
+        context.push('\n');
     }
 
     context
@@ -14,6 +14,7 @@
  * - Tool integration for actions
  * - Structured thought-action-observation cycles
  * - Graceful error handling and recovery
+ * - Runs limited to a tool set (v3.9.0)
  *
  * Integration: Works with tool_calling.rs and ollama.rs
  */
@@ -21,6 +22,7 @@
 use crate::services::tool_calling::{ToolCall, ToolResult, ToolService};
 use log::{debug, info, warn};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::sync::Arc;
 
 /// ReAct step types
@@ -47,7 +49,7 @@ impl ReActStep {
         match self {
             ReActStep::Thought(s) => s.clone(),
             ReActStep::Action(call) => format!("{}: {}", call.tool_name, call.arguments),
-            ReActStep::Observation(result) => result.result.to_string(),
+            ReActStep::Observation(result) => result.observation(),
             ReActStep::Answer(s) => s.clone(),
         }
     }
@@ -120,6 +122,15 @@ impl ReActAgent {
 
     /// Execute ReAct loop
     pub async fn execute(&self, user_query: &str) -> Result<ReActExecution, String> {
+        self.execute_with_tools(user_query, None).await
+    }
+
+    /// Execute ReAct loop with only the tools of a tool set (v3.9.0)
+    pub async fn execute_with_tools(
+        &self,
+        user_query: &str,
+        allowed_tools: Option<&HashSet<String>>,
+    ) -> Result<ReActExecution, String> {
         info!("Starting ReAct execution for: {}", user_query);
 
         let mut steps: Vec<ReActStep> = Vec::new();
@@ -130,7 +141,7 @@ impl ReActAgent {
             debug!("ReAct iteration {}/{}", iterations, self.config.max_iterations);
 
             // Generate next step
-            let next_step = self.generate_next_step(user_query, &steps).await?;
+            let next_step = self.generate_next_step(user_query, &steps, allowed_tools).await?;
 
             debug!("Generated step: {}", next_step.step_type());
 
@@ -150,7 +161,7 @@ impl ReActAgent {
                     steps.push(ReActStep::Action(action.clone()));
 
                     // Execute action
-                    match self.execute_action(&action).await {
+                    match self.execute_action(&action, allowed_tools).await {
                         Ok(result) => {
                             steps.push(ReActStep::Observation(result));
                         }
@@ -160,6 +171,9 @@ impl ReActAgent {
                                 success: false,
                                 result: serde_json::json!(format!("Error: {}", e)),
                                 error: Some(e),
+                                cache: None,
+                                interrupted: None,
+                                execution_ms: 0,
                             };
                             steps.push(ReActStep::Observation(error_result));
                         }
@@ -194,8 +208,9 @@ impl ReActAgent {
         &self,
         query: &str,
         history: &[ReActStep],
+        allowed_tools: Option<&HashSet<String>>,
     ) -> Result<ReActStep, String> {
-        let prompt = self.build_react_prompt(query, history);
+        let prompt = self.build_react_prompt(query, history, allowed_tools);
 
         debug!("Generating next ReAct step");
 
@@ -231,7 +246,7 @@ impl ReActAgent {
     }
 
     /// Build ReAct prompt
-    fn build_react_prompt(&self, query: &str, history: &[ReActStep]) -> String {
+    fn build_react_prompt(&self, query: &str, history: &[ReActStep], allowed_tools: Option<&HashSet<String>>) -> String {
         let mut prompt = format!(
             "You are solving the following task using ReAct (Reasoning + Acting) framework.\n\n\
              Task: {}\n\n",
@@ -240,7 +255,7 @@ impl ReActAgent {
 
         // Add available tools
         prompt.push_str("Available Tools:\n");
-        for tool_name in self.tool_service.list_tools() {
+        for tool_name in self.tool_service.list_tools_in(allowed_tools) {
             prompt.push_str(&format!("- {}\n", tool_name));
         }
         prompt.push_str("\n");
@@ -327,10 +342,15 @@ impl ReActAgent {
     }
 
     /// Execute tool action
-    async fn execute_action(&self, action: &ToolCall) -> Result<ToolResult, String> {
+    async fn execute_action(
+        &self,
+        action: &ToolCall,
+        allowed_tools: Option<&HashSet<String>>,
+    ) -> Result<ToolResult, String> {
         debug!("Executing action: {}", action.tool_name);
 
-        let result = self.tool_service.execute_tool(action).await;
+        let call_id = uuid::Uuid::new_v4().to_string();
+        let result = self.tool_service.execute_tool_in(action, &call_id, allowed_tools).await;
         Ok(result)
     }
 
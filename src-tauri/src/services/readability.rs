@@ -0,0 +1,538 @@
+//! Readability Extraction (v3.9.0)
+//!
+//! Finds the main content of a fetched page and renders it as Markdown, so
+//! agents see the article instead of menus, cookie banners and footers.
+//!
+//! Features:
+//! - Readability-style candidate scoring (paragraph text, commas, link density,
+//!   class/id hints) with related siblings of the winner kept
+//! - HTML → Markdown: headings, emphasis, links, code, lists, quotes, tables
+//! - Links and images resolved against the page URL
+//! - Image alt-text captured as `![alt](src)` and returned separately
+
+#![allow(dead_code)]  // Phase 9: Internet Access (opt-in feature)
+
+use scraper::node::Node;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use url::Url;
+
+/// Paragraphs shorter than this do not vote for a container
+const MIN_PARAGRAPH_CHARS: usize = 25;
+
+/// Images kept per page (decorative icons are usually near the end)
+const MAX_IMAGES: usize = 20;
+
+/// Subtrees that never hold main content
+const SKIPPED_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "iframe", "svg", "canvas", "nav", "footer",
+    "aside", "form", "button", "input", "select", "textarea", "dialog",
+];
+
+const NEGATIVE_HINTS: &[&str] = &[
+    "sidebar", "comment", "advert", "sponsor", "promo", "share", "social", "related",
+    "cookie", "consent", "newsletter", "subscribe", "banner", "popup", "modal", "breadcrumb",
+    "menu", "footer", "masthead", "widget",
+];
+
+const POSITIVE_HINTS: &[&str] = &[
+    "article", "content", "entry", "main", "post", "story", "body", "text", "blog",
+];
+
+/// Image found in the main content
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageRef {
+    pub src: String,
+    /// Empty when the page gives no alt text
+    pub alt: String,
+}
+
+/// Main content of a page
+#[derive(Debug, Clone, Default)]
+pub struct Readable {
+    pub markdown: String,
+    pub images: Vec<ImageRef>,
+}
+
+/// Extract the main content of `document` as Markdown
+///
+/// `base_url` resolves relative links and image sources; without it they are
+/// kept as written.
+pub fn extract(document: &Html, base_url: Option<&Url>) -> Readable {
+    let roots = main_content(document);
+    let mut renderer = Renderer {
+        base_url,
+        images: Vec::new(),
+        lists: Vec::new(),
+    };
+
+    let mut markdown = String::new();
+    for root in roots {
+        renderer.render_children(root, &mut markdown);
+        markdown.push_str("\n\n");
+    }
+
+    Readable {
+        markdown: tidy(&markdown),
+        images: renderer.images,
+    }
+}
+
+/// Highest-scoring container plus siblings that look like part of the same article
+fn main_content(document: &Html) -> Vec<ElementRef<'_>> {
+    let paragraphs = Selector::parse("p, pre, td, blockquote").expect("valid selector");
+    let mut candidates = HashMap::new();
+
+    for paragraph in document.select(&paragraphs) {
+        if is_skipped(paragraph) {
+            continue;
+        }
+        let text = paragraph.text().collect::<String>();
+        let length = text.trim().chars().count();
+        if length < MIN_PARAGRAPH_CHARS {
+            continue;
+        }
+        let score = 1.0 + text.matches(',').count() as f64 + (length as f64 / 100.0).min(3.0);
+
+        // The parent gets the full score, the grandparent half
+        let ancestors = paragraph.ancestors().filter_map(ElementRef::wrap).take(2);
+        for (level, ancestor) in ancestors.enumerate() {
+            let entry = candidates
+                .entry(ancestor.id())
+                .or_insert_with(|| (ancestor, initial_score(ancestor)));
+            entry.1 += if level == 0 { score } else { score / 2.0 };
+        }
+    }
+
+    let scored: HashMap<_, _> = candidates
+        .into_iter()
+        .map(|(id, (element, score))| (id, (element, score * (1.0 - link_density(element)))))
+        .collect();
+
+    let best = scored
+        .values()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .copied();
+
+    let Some((best, best_score)) = best else {
+        let body = Selector::parse("body").expect("valid selector");
+        return document.select(&body).next().into_iter().collect();
+    };
+
+    let Some(parent) = best.parent().and_then(ElementRef::wrap) else {
+        return vec![best];
+    };
+
+    // Articles split across sibling <div>s or loose paragraphs
+    let threshold = (best_score * 0.2).max(10.0);
+    parent
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter(|sibling| {
+            if sibling.id() == best.id() {
+                return true;
+            }
+            if is_skipped(*sibling) {
+                return false;
+            }
+            if let Some((_, score)) = scored.get(&sibling.id()) {
+                return *score >= threshold;
+            }
+            sibling.value().name() == "p"
+                && sibling.text().collect::<String>().trim().chars().count() > 80
+                && link_density(*sibling) < 0.25
+        })
+        .collect()
+}
+
+fn initial_score(element: ElementRef) -> f64 {
+    let tag_score = match element.value().name() {
+        "article" | "main" => 10.0,
+        "div" => 5.0,
+        "section" | "pre" | "td" | "blockquote" => 3.0,
+        "address" | "ol" | "ul" | "dl" | "dd" | "dt" | "li" | "form" => -3.0,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+        _ => 0.0,
+    };
+    tag_score + class_weight(element)
+}
+
+/// +25 for content-like class/id names, -25 for boilerplate ones
+fn class_weight(element: ElementRef) -> f64 {
+    let hints = hint_text(element);
+    let mut weight = 0.0;
+    if NEGATIVE_HINTS.iter().any(|hint| hints.contains(hint)) {
+        weight -= 25.0;
+    }
+    if POSITIVE_HINTS.iter().any(|hint| hints.contains(hint)) {
+        weight += 25.0;
+    }
+    weight
+}
+
+fn hint_text(element: ElementRef) -> String {
+    let value = element.value();
+    format!(
+        "{} {}",
+        value.attr("class").unwrap_or_default(),
+        value.attr("id").unwrap_or_default()
+    )
+    .to_lowercase()
+}
+
+/// Share of the element's text that sits inside links
+fn link_density(element: ElementRef) -> f64 {
+    let total = element.text().map(|t| t.trim().chars().count()).sum::<usize>();
+    if total == 0 {
+        return 0.0;
+    }
+    let links = Selector::parse("a").expect("valid selector");
+    let linked = element
+        .select(&links)
+        .flat_map(|link| link.text())
+        .map(|t| t.trim().chars().count())
+        .sum::<usize>();
+    linked as f64 / total as f64
+}
+
+/// Boilerplate itself or inside boilerplate; `<html>`/`<body>` classes are ignored
+fn is_skipped(element: ElementRef) -> bool {
+    element
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .filter(|ancestor| !matches!(ancestor.value().name(), "html" | "body"))
+        .chain(Some(element))
+        .any(is_boilerplate)
+}
+
+fn is_boilerplate(element: ElementRef) -> bool {
+    let value = element.value();
+    if SKIPPED_TAGS.contains(&value.name()) || value.attr("hidden").is_some() {
+        return true;
+    }
+    if value.attr("aria-hidden") == Some("true") || value.attr("role") == Some("navigation") {
+        return true;
+    }
+    let style = value.attr("style").unwrap_or_default().replace(' ', "");
+    if style.contains("display:none") || style.contains("visibility:hidden") {
+        return true;
+    }
+    // Content-like hints win, so `<div class="post-content share-enabled">` is kept
+    let hints = hint_text(element);
+    NEGATIVE_HINTS.iter().any(|hint| hints.contains(hint))
+        && !POSITIVE_HINTS.iter().any(|hint| hints.contains(hint))
+}
+
+struct Renderer<'u> {
+    base_url: Option<&'u Url>,
+    images: Vec<ImageRef>,
+    /// Open lists: ordered flag and next item number
+    lists: Vec<(bool, usize)>,
+}
+
+impl Renderer<'_> {
+    fn render_children(&mut self, element: ElementRef, out: &mut String) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => push_text(out, text),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.render_element(child, out);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Children rendered on their own, whitespace collapsed to one line
+    fn render_inline(&mut self, element: ElementRef) -> String {
+        let mut inner = String::new();
+        self.render_children(element, &mut inner);
+        inner.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn render_element(&mut self, element: ElementRef, out: &mut String) {
+        if is_boilerplate(element) {
+            return;
+        }
+        let name = element.value().name();
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let text = self.render_inline(element);
+                if !text.is_empty() {
+                    let level = name[1..].parse::<usize>().unwrap_or(1);
+                    block(out, &format!("{} {}", "#".repeat(level), text));
+                }
+            }
+            "p" | "div" | "section" | "article" | "main" | "header" | "figure" | "dl" => {
+                out.push_str("\n\n");
+                self.render_children(element, out);
+                out.push_str("\n\n");
+            }
+            "figcaption" | "dt" | "dd" => {
+                out.push('\n');
+                self.render_children(element, out);
+                out.push('\n');
+            }
+            "br" => out.push('\n'),
+            "hr" => block(out, "---"),
+            "strong" | "b" => wrap_inline(out, "**", &self.render_inline(element)),
+            "em" | "i" => wrap_inline(out, "*", &self.render_inline(element)),
+            "code" => wrap_inline(out, "`", &element.text().collect::<String>()),
+            "pre" => {
+                let code = element.text().collect::<String>();
+                block(out, &format!("```\n{}\n```", code.trim_matches('\n')));
+            }
+            "a" => {
+                let text = self.render_inline(element);
+                match element.value().attr("href").and_then(|href| self.resolve(href)) {
+                    Some(href) if !text.is_empty() => {
+                        push_text(out, " ");
+                        out.push_str(&format!("[{}]({})", text, href));
+                    }
+                    _ => push_text(out, &text),
+                }
+            }
+            "img" => self.render_image(element, out),
+            "ul" | "ol" => {
+                self.lists.push((name == "ol", 1));
+                out.push('\n');
+                self.render_children(element, out);
+                self.lists.pop();
+                out.push_str(if self.lists.is_empty() { "\n\n" } else { "\n" });
+            }
+            "li" => {
+                let depth = self.lists.len().saturating_sub(1);
+                let marker = match self.lists.last_mut() {
+                    Some((true, number)) => {
+                        *number += 1;
+                        format!("{}.", *number - 1)
+                    }
+                    _ => "-".to_string(),
+                };
+                out.push_str(&format!("\n{}{} ", "  ".repeat(depth), marker));
+                self.render_children(element, out);
+            }
+            "blockquote" => {
+                let mut inner = String::new();
+                self.render_children(element, &mut inner);
+                let quoted = tidy(&inner)
+                    .lines()
+                    .map(|line| format!("> {}", line).trim_end().to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                block(out, &quoted);
+            }
+            "table" => self.render_table(element, out),
+            _ => self.render_children(element, out),
+        }
+    }
+
+    fn render_image(&mut self, element: ElementRef, out: &mut String) {
+        let value = element.value();
+        // Tracking pixels and spacers
+        if value.attr("width") == Some("1") || value.attr("height") == Some("1") {
+            return;
+        }
+        let src = value
+            .attr("src")
+            .or_else(|| value.attr("data-src"))
+            .filter(|src| !src.starts_with("data:"))
+            .and_then(|src| self.resolve(src));
+        let Some(src) = src else {
+            return;
+        };
+        let alt = value
+            .attr("alt")
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        push_text(out, " ");
+        out.push_str(&format!("![{}]({})", alt, src));
+        if self.images.len() < MAX_IMAGES && !self.images.iter().any(|image| image.src == src) {
+            self.images.push(ImageRef { src, alt });
+        }
+    }
+
+    fn render_table(&mut self, element: ElementRef, out: &mut String) {
+        let rows_selector = Selector::parse("tr").expect("valid selector");
+        let cells_selector = Selector::parse("th, td").expect("valid selector");
+
+        let rows: Vec<Vec<String>> = element
+            .select(&rows_selector)
+            .map(|row| {
+                row.select(&cells_selector)
+                    .map(|cell| self.render_inline(cell).replace('|', "\\|"))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|cells| !cells.is_empty())
+            .collect();
+
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if columns == 0 {
+            return;
+        }
+
+        let mut table = String::new();
+        for (index, row) in rows.iter().enumerate() {
+            let mut cells = row.clone();
+            cells.resize(columns, String::new());
+            table.push_str(&format!("| {} |\n", cells.join(" | ")));
+            if index == 0 {
+                table.push_str(&format!("|{}\n", " --- |".repeat(columns)));
+            }
+        }
+        block(out, table.trim_end());
+    }
+
+    /// Absolute URL for links and images; `javascript:` and `#` links are dropped
+    fn resolve(&self, href: &str) -> Option<String> {
+        let href = href.trim();
+        if href.is_empty() || href.starts_with('#') || href.starts_with("javascript:") {
+            return None;
+        }
+        match self.base_url {
+            Some(base) => base.join(href).ok().map(|url| url.to_string()),
+            None => Some(href.to_string()),
+        }
+    }
+}
+
+/// Append text with whitespace collapsed, without leading space at line starts
+fn push_text(out: &mut String, text: &str) {
+    let starts_with_space = text.starts_with(char::is_whitespace);
+    let ends_with_space = text.ends_with(char::is_whitespace);
+    let words = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let at_line_start = out.is_empty() || out.ends_with(['\n', ' ']);
+    if (starts_with_space || words.is_empty()) && !at_line_start {
+        out.push(' ');
+    }
+    out.push_str(&words);
+    if ends_with_space && !words.is_empty() {
+        out.push(' ');
+    }
+}
+
+fn wrap_inline(out: &mut String, marker: &str, text: &str) {
+    let text = text.trim();
+    if !text.is_empty() {
+        if !(out.is_empty() || out.ends_with(['\n', ' ', '(', '['])) {
+            out.push(' ');
+        }
+        out.push_str(&format!("{}{}{}", marker, text, marker));
+    }
+}
+
+fn block(out: &mut String, content: &str) {
+    out.push_str("\n\n");
+    out.push_str(content);
+    out.push_str("\n\n");
+}
+
+/// Trim trailing spaces and collapse runs of blank lines; fenced code is kept verbatim
+fn tidy(markdown: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    let mut in_code = false;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            lines.push(line.trim());
+            continue;
+        }
+        if in_code {
+            lines.push(line);
+            continue;
+        }
+        let line = line.trim_end();
+        let line = if line.trim_start().starts_with(['-', '>', '|']) || starts_with_number(line) {
+            line
+        } else {
+            line.trim_start()
+        };
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    lines.join("\n").trim().to_string()
+}
+
+/// Ordered list items keep their indentation
+fn starts_with_number(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    digits > 0 && trimmed[digits..].starts_with(". ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARTICLE: &str = r#"
+        <html><body>
+            <nav><a href="/">Home</a> <a href="/about">About</a></nav>
+            <div class="sidebar"><p>Subscribe to our newsletter for weekly updates, deals and news.</p></div>
+            <div class="post-content">
+                <h1>Rust Ownership</h1>
+                <p>Ownership is a set of rules, checked at compile time, that govern memory.</p>
+                <p>See <a href="/book/ch04">the book</a> for <strong>details</strong>, examples and exercises.</p>
+                <img src="/img/borrow.png" alt="Borrow checker diagram">
+                <img src="/pixel.gif" width="1" height="1">
+                <ul><li>Each value has an owner</li><li>One owner at a time</li></ul>
+                <pre><code>let s = String::from("hi");
+let t = s;</code></pre>
+                <table><tr><th>Type</th><th>Copy</th></tr><tr><td>i32</td><td>yes</td></tr></table>
+            </div>
+            <footer><p>Copyright 2026, Example Corp, all rights reserved worldwide.</p></footer>
+        </body></html>
+    "#;
+
+    #[test]
+    fn test_extracts_main_content_as_markdown() {
+        let document = Html::parse_document(ARTICLE);
+        let base = Url::parse("https://example.com/posts/ownership").unwrap();
+        let readable = extract(&document, Some(&base));
+        let md = &readable.markdown;
+
+        assert!(md.starts_with("# Rust Ownership"));
+        assert!(md.contains("[the book](https://example.com/book/ch04)"));
+        assert!(md.contains("**details**"));
+        assert!(md.contains("- Each value has an owner\n- One owner at a time"));
+        assert!(md.contains("```\nlet s = String::from(\"hi\");\nlet t = s;\n```"));
+        assert!(md.contains("| Type | Copy |\n| --- | --- |\n| i32 | yes |"));
+
+        assert!(!md.contains("newsletter"));
+        assert!(!md.contains("Copyright"));
+        assert!(!md.contains("About"));
+    }
+
+    #[test]
+    fn test_captures_image_alt_text() {
+        let document = Html::parse_document(ARTICLE);
+        let base = Url::parse("https://example.com/posts/ownership").unwrap();
+        let readable = extract(&document, Some(&base));
+
+        assert_eq!(
+            readable.images,
+            vec![ImageRef {
+                src: "https://example.com/img/borrow.png".to_string(),
+                alt: "Borrow checker diagram".to_string(),
+            }]
+        );
+        assert!(readable
+            .markdown
+            .contains("![Borrow checker diagram](https://example.com/img/borrow.png)"));
+    }
+
+    #[test]
+    fn test_falls_back_to_body() {
+        let document = Html::parse_document("<html><body><h2>Short</h2><p>Tiny.</p></body></html>");
+        let readable = extract(&document, None);
+        assert_eq!(readable.markdown, "## Short\n\nTiny.");
+    }
+}
@@ -0,0 +1,920 @@
+//! Report Service (v3.9.0)
+//!
+//! Turns agent output into documents that can be shared outside the app.
+//!
+//! Features:
+//! - Conversations (with their charts), weekly reviews, executed plans and
+//!   wiki pages as one common document of Markdown sections
+//! - Standalone HTML from a minijinja template: inline styles and inline SVG
+//!   charts, raw HTML in messages is escaped
+//! - PDF through genpdf, using a system TrueType font (Hangul-capable fonts
+//!   are tried first)
+//!
+//! PDFs can't embed the SVG charts yet; they list the chart titles instead.
+
+#![allow(dead_code)]  // Phase 5: Reports
+
+use crate::services::charts::{self, ChartFormat, StoredChart};
+use crate::services::knowledge_export::NOTE_SECTIONS;
+use crate::services::planner::{Plan, StepStatus};
+use crate::services::semantic_wiki::EntityPage;
+use crate::services::weekly_review::WeeklyReport;
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use genpdf::elements::{
+    Break, FrameCellDecorator, LinearLayout, OrderedList, Paragraph, TableLayout, UnorderedList,
+};
+use genpdf::style::{Color, Style, StyledString};
+use genpdf::{Alignment, Element};
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// TrueType fonts tried for PDFs (regular, bold), Hangul-capable first
+const PDF_FONTS: &[(&str, Option<&str>)] = &[
+    ("C:\\Windows\\Fonts\\malgun.ttf", Some("C:\\Windows\\Fonts\\malgunbd.ttf")),
+    ("/System/Library/Fonts/Supplemental/AppleGothic.ttf", None),
+    ("/Library/Fonts/Arial Unicode.ttf", None),
+    ("/System/Library/Fonts/Supplemental/Arial Unicode.ttf", None),
+    ("/usr/share/fonts/truetype/nanum/NanumGothic.ttf", Some("/usr/share/fonts/truetype/nanum/NanumGothicBold.ttf")),
+    ("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf", Some("/usr/share/fonts/truetype/dejavu/DejaVuSans-Bold.ttf")),
+    ("/usr/share/fonts/TTF/DejaVuSans.ttf", Some("/usr/share/fonts/TTF/DejaVuSans-Bold.ttf")),
+    ("C:\\Windows\\Fonts\\arial.ttf", Some("C:\\Windows\\Fonts\\arialbd.ttf")),
+    ("/System/Library/Fonts/Supplemental/Arial.ttf", Some("/System/Library/Fonts/Supplemental/Arial Bold.ttf")),
+];
+
+/// Messages included in a conversation report (the most recent ones)
+const MAX_CONVERSATION_MESSAGES: usize = 2_000;
+
+/// PDF body font size (points)
+const PDF_FONT_SIZE: u8 = 11;
+
+/// Standalone HTML page; `.html` so minijinja escapes everything not marked `safe`
+const HTML_TEMPLATE_NAME: &str = "report.html";
+const HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="{{ lang }}">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<meta name="generator" content="Garden of Eden">
+<title>{{ title }}</title>
+<style>
+  :root { color-scheme: light; }
+  body { margin: 0; background: #f4f5f7; color: #1f2328; font: 15px/1.6 -apple-system, "Segoe UI", "Apple SD Gothic Neo", "Malgun Gothic", "Noto Sans KR", sans-serif; }
+  main { max-width: 820px; margin: 32px auto; padding: 40px 48px; background: #fff; border-radius: 12px; box-shadow: 0 1px 4px rgba(0,0,0,.08); }
+  header { border-bottom: 1px solid #e5e7eb; margin-bottom: 24px; padding-bottom: 16px; }
+  h1 { font-size: 26px; margin: 0 0 4px; }
+  h2 { font-size: 19px; margin: 28px 0 4px; }
+  .subtitle { color: #57606a; margin: 0; }
+  .generated, .meta { color: #8c959f; font-size: 12px; margin: 2px 0 8px; }
+  section.user { background: #f0f6ff; border-radius: 10px; padding: 4px 16px 8px; }
+  section.user h2, section.assistant h2 { font-size: 14px; text-transform: uppercase; letter-spacing: .04em; color: #57606a; }
+  pre { background: #f6f8fa; border-radius: 8px; padding: 12px 14px; overflow-x: auto; font-size: 13px; }
+  code { font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; background: #f6f8fa; padding: 1px 4px; border-radius: 4px; }
+  pre code { padding: 0; background: none; }
+  blockquote { margin: 0; padding: 0 14px; border-left: 3px solid #d0d7de; color: #57606a; }
+  table { border-collapse: collapse; margin: 12px 0; }
+  th, td { border: 1px solid #d0d7de; padding: 5px 10px; text-align: left; }
+  th { background: #f6f8fa; }
+  figure.chart { margin: 16px 0; }
+  figure.chart svg { max-width: 100%; height: auto; border: 1px solid #e5e7eb; border-radius: 8px; }
+  figcaption { color: #57606a; font-size: 13px; text-align: center; }
+  footer { margin-top: 40px; color: #8c959f; font-size: 12px; text-align: center; }
+  @media print { body { background: #fff; } main { box-shadow: none; margin: 0; max-width: none; } section { break-inside: avoid-page; } }
+</style>
+</head>
+<body>
+<main>
+<header>
+  <h1>{{ title }}</h1>
+  {% if subtitle %}<p class="subtitle">{{ subtitle }}</p>{% endif %}
+  <p class="generated">Generated {{ generated_at }}</p>
+</header>
+{% for section in sections %}
+<section{% if section.role %} class="{{ section.role }}"{% endif %}>
+  {% if section.heading %}<h2>{{ section.heading }}</h2>{% endif %}
+  {% if section.meta %}<p class="meta">{{ section.meta }}</p>{% endif %}
+  {{ section.html|safe }}
+  {% for chart in section.charts %}
+  <figure class="chart">{{ chart.svg|safe }}{% if chart.title %}<figcaption>{{ chart.title }}</figcaption>{% endif %}</figure>
+  {% endfor %}
+</section>
+{% endfor %}
+<footer>Exported from Garden of Eden</footer>
+</main>
+</body>
+</html>
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[default]
+    Html,
+    Pdf,
+}
+
+impl ReportFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Html => "html",
+            ReportFormat::Pdf => "pdf",
+        }
+    }
+}
+
+/// What a report is made from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReportSource {
+    Conversation { conversation_id: String },
+    /// Week starting on this Monday (YYYY-MM-DD), the latest review when omitted
+    WeeklyReview { week_start: Option<String> },
+    Plan { plan_id: String },
+    WikiPage { entity: String },
+}
+
+/// A titled block of Markdown
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportSection {
+    pub heading: Option<String>,
+    /// Small print under the heading (time, status)
+    pub meta: Option<String>,
+    /// "user" / "assistant" for conversation messages, styled differently
+    pub role: Option<String>,
+    pub markdown: String,
+    #[serde(default)]
+    pub charts: Vec<StoredChart>,
+}
+
+/// Everything a report shows, independent of the output format
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportDocument {
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub sections: Vec<ReportSection>,
+}
+
+/// A generated report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedReport {
+    pub title: String,
+    pub format: ReportFormat,
+    /// Suggested file name, e.g. "weekly-review-2026-10-05.pdf"
+    pub file_name: String,
+    /// Where the report was written, if an output path was given
+    pub path: Option<String>,
+    /// HTML text or base64 PDF; empty when the report was written to `path`
+    pub data: String,
+    pub size_bytes: usize,
+}
+
+/// Render a document and optionally write it to `output`
+pub fn generate(document: &ReportDocument, format: ReportFormat, output: Option<&Path>) -> Result<GeneratedReport> {
+    let bytes = match format {
+        ReportFormat::Html => render_html(document)?.into_bytes(),
+        ReportFormat::Pdf => render_pdf(document)?,
+    };
+    let size_bytes = bytes.len();
+    let file_name = file_name(&document.title, format);
+
+    let (path, data) = match output {
+        Some(path) => {
+            std::fs::write(path, &bytes).map_err(|e| anyhow!("Failed to write {}: {}", path.display(), e))?;
+            log::info!("Wrote {} report to {}", format.extension(), path.display());
+            (Some(path.to_string_lossy().into_owned()), String::new())
+        }
+        None => match format {
+            ReportFormat::Html => (None, String::from_utf8(bytes)?),
+            ReportFormat::Pdf => (None, base64::engine::general_purpose::STANDARD.encode(bytes)),
+        },
+    };
+
+    Ok(GeneratedReport { title: document.title.clone(), format, file_name, path, data, size_bytes })
+}
+
+/// "Weekly Review: 2026-10-05" → "Weekly-Review-2026-10-05.html"
+fn file_name(title: &str, format: ReportFormat) -> String {
+    let stem: String = title
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .take(12)
+        .collect::<Vec<_>>()
+        .join("-");
+    let stem = if stem.is_empty() { "report".to_string() } else { stem };
+    format!("{}.{}", stem, format.extension())
+}
+
+fn format_time(timestamp_ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(timestamp_ms)
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+/// The markdown without a leading "# Title" line that repeats the document title
+fn strip_title(markdown: &str) -> (Option<String>, &str) {
+    let trimmed = markdown.trim_start();
+    match trimmed.strip_prefix("# ") {
+        Some(rest) => {
+            let (line, body) = rest.split_once('\n').unwrap_or((rest, ""));
+            (Some(line.trim().to_string()), body)
+        }
+        None => (None, trimmed),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Sources
+// ---------------------------------------------------------------------------
+
+/// A conversation's messages, each followed by the charts drawn for it
+pub fn conversation_document(conn: &Connection, conversation_id: &str, charts: &[StoredChart]) -> Result<ReportDocument> {
+    let (title, created_at): (String, i64) = conn
+        .query_row(
+            "SELECT title, created_at FROM conversations WHERE id = ?1",
+            [conversation_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?
+        .ok_or_else(|| anyhow!("Conversation not found: {}", conversation_id))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, role, content, timestamp FROM (
+             SELECT id, role, content, timestamp FROM messages
+             WHERE conversation_id = ?1 ORDER BY timestamp DESC LIMIT ?2
+         ) ORDER BY timestamp",
+    )?;
+    let messages: Vec<(String, String, String, i64)> = stmt
+        .query_map(rusqlite::params![conversation_id, MAX_CONVERSATION_MESSAGES as i64], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut charts_by_message: HashMap<&str, Vec<StoredChart>> = HashMap::new();
+    for chart in charts {
+        if let Some(message_id) = chart.message_id.as_deref() {
+            charts_by_message.entry(message_id).or_default().push(chart.clone());
+        }
+    }
+
+    let sections = messages
+        .iter()
+        .map(|(id, role, content, timestamp)| ReportSection {
+            heading: Some(if role == "user" { "You" } else { "Assistant" }.to_string()),
+            meta: Some(format_time(*timestamp)),
+            role: Some(if role == "user" { "user" } else { "assistant" }.to_string()),
+            markdown: content.clone(),
+            charts: charts_by_message.remove(id.as_str()).unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(ReportDocument {
+        title,
+        subtitle: Some(format!("Conversation started {} · {} messages", format_time(created_at), messages.len())),
+        sections,
+    })
+}
+
+pub fn weekly_review_document(report: &WeeklyReport) -> ReportDocument {
+    let (heading, body) = strip_title(&report.markdown);
+    ReportDocument {
+        title: heading.unwrap_or_else(|| format!("Weekly Review: {} – {}", report.week_start, report.week_end)),
+        subtitle: None,
+        sections: vec![ReportSection { markdown: body.to_string(), ..Default::default() }],
+    }
+}
+
+pub fn plan_document(plan: &Plan) -> ReportDocument {
+    let status = if plan.completed {
+        "completed".to_string()
+    } else if plan.execution_started {
+        format!("{:.0}% done", plan.progress())
+    } else {
+        "not started".to_string()
+    };
+
+    let mut overview = String::new();
+    if !plan.estimated_time.trim().is_empty() {
+        overview.push_str(&format!("**Estimated time:** {}\n\n", plan.estimated_time.trim()));
+    }
+    if !plan.required_tools.is_empty() {
+        overview.push_str(&format!("**Tools:** {}\n\n", plan.required_tools.join(", ")));
+    }
+    if !plan.risks.is_empty() {
+        overview.push_str("**Risks:**\n\n");
+        for risk in &plan.risks {
+            overview.push_str(&format!("- {}\n", risk));
+        }
+    }
+
+    let mut sections = Vec::new();
+    if !overview.is_empty() {
+        sections.push(ReportSection { heading: Some("Overview".to_string()), markdown: overview, ..Default::default() });
+    }
+    for step in &plan.steps {
+        let mut meta = match step.status {
+            StepStatus::Pending => "Pending",
+            StepStatus::InProgress => "In progress",
+            StepStatus::Completed => "Completed",
+            StepStatus::Failed => "Failed",
+            StepStatus::Skipped => "Skipped",
+        }
+        .to_string();
+        if !step.depends_on.is_empty() {
+            let steps: Vec<String> = step.depends_on.iter().map(|n| n.to_string()).collect();
+            meta.push_str(&format!(" · after step {}", steps.join(", ")));
+        }
+
+        let mut markdown = format!("**Action:** {}\n\n**Expected:** {}\n\n", step.action, step.expected_output);
+        if let Some(result) = step.result.as_deref().filter(|r| !r.trim().is_empty()) {
+            markdown.push_str(&format!("{}\n\n", result.trim()));
+        }
+        if let Some(error) = &step.error {
+            markdown.push_str(&format!("**Error:** {}\n", error));
+        }
+        sections.push(ReportSection {
+            heading: Some(format!("Step {}: {}", step.step_number, step.description)),
+            meta: Some(meta),
+            markdown,
+            ..Default::default()
+        });
+    }
+
+    let created = i64::try_from(plan.created_at).unwrap_or_default();
+    ReportDocument {
+        title: plan.goal.clone(),
+        subtitle: Some(format!(
+            "Plan · {} · {} steps · created {}",
+            status,
+            plan.steps.len(),
+            // Plans record seconds
+            format_time(created * 1000)
+        )),
+        sections,
+    }
+}
+
+pub fn wiki_document(page: &EntityPage) -> ReportDocument {
+    let mut sections = Vec::new();
+    if let Some(summary) = page.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+        sections.push(ReportSection { markdown: summary.trim().to_string(), ..Default::default() });
+    }
+
+    for (category, heading) in &NOTE_SECTIONS {
+        let Some(section) = page.sections.iter().find(|s| s.category == *category) else { continue };
+        let mut markdown = String::new();
+        for fact in &section.facts {
+            markdown.push_str(&format!("- {}\n", fact.statement.trim()));
+            if let Some(note) = fact.note.as_deref().filter(|n| !n.trim().is_empty()) {
+                markdown.push_str(&format!("  - *{}*\n", note.trim()));
+            }
+        }
+        sections.push(ReportSection { heading: Some(heading.to_string()), markdown, ..Default::default() });
+    }
+
+    if !page.related.is_empty() {
+        let markdown: String = page
+            .related
+            .iter()
+            .map(|related| {
+                let arrow = if related.outgoing { "→" } else { "←" };
+                format!("- {} {} **{}** ({})\n", related.relationship, arrow, related.name, related.entity_type)
+            })
+            .collect();
+        sections.push(ReportSection { heading: Some("Related".to_string()), markdown, ..Default::default() });
+    }
+    if !page.mentions.is_empty() {
+        let markdown: String = page.mentions.iter().map(|m| format!("> {}\n\n", m.snippet.trim())).collect();
+        sections.push(ReportSection { heading: Some("Mentioned in".to_string()), markdown, ..Default::default() });
+    }
+
+    ReportDocument {
+        title: page.entity.clone(),
+        subtitle: (!page.aliases.is_empty()).then(|| format!("Also known as {}", page.aliases.join(", "))),
+        sections,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+struct HtmlChart {
+    title: Option<String>,
+    svg: String,
+}
+
+#[derive(Serialize)]
+struct HtmlSection {
+    heading: Option<String>,
+    meta: Option<String>,
+    role: Option<String>,
+    html: String,
+    charts: Vec<HtmlChart>,
+}
+
+#[derive(Serialize)]
+struct HtmlContext {
+    lang: &'static str,
+    title: String,
+    subtitle: Option<String>,
+    generated_at: String,
+    sections: Vec<HtmlSection>,
+}
+
+fn markdown_options() -> Options {
+    Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS
+}
+
+/// Markdown as HTML; embedded HTML is shown as text, not rendered
+pub fn markdown_to_html(markdown: &str) -> String {
+    let events = Parser::new_ext(markdown, markdown_options()).map(|event| match event {
+        Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        event => event,
+    });
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, events);
+    html
+}
+
+fn is_hangul(c: char) -> bool {
+    matches!(c, '\u{AC00}'..='\u{D7A3}' | '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}')
+}
+
+pub fn render_html(document: &ReportDocument) -> Result<String> {
+    let korean = document.title.chars().any(is_hangul)
+        || document.sections.iter().any(|s| s.markdown.chars().any(is_hangul));
+
+    let sections = document
+        .sections
+        .iter()
+        .map(|section| {
+            let charts = section
+                .charts
+                .iter()
+                .map(|chart| {
+                    Ok(HtmlChart {
+                        title: chart.spec.title.clone(),
+                        svg: charts::render(&chart.spec, ChartFormat::Svg, None, None)?.data,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(HtmlSection {
+                heading: section.heading.clone(),
+                meta: section.meta.clone(),
+                role: section.role.clone(),
+                html: markdown_to_html(&section.markdown),
+                charts,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let context = HtmlContext {
+        lang: if korean { "ko" } else { "en" },
+        title: document.title.clone(),
+        subtitle: document.subtitle.clone(),
+        generated_at: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        sections,
+    };
+
+    let mut env = minijinja::Environment::new();
+    env.add_template(HTML_TEMPLATE_NAME, HTML_TEMPLATE)?;
+    Ok(env.get_template(HTML_TEMPLATE_NAME)?.render(context)?)
+}
+
+// ---------------------------------------------------------------------------
+// PDF
+// ---------------------------------------------------------------------------
+
+/// The first available font from `PDF_FONTS`
+fn load_pdf_font() -> Result<genpdf::fonts::FontFamily<genpdf::fonts::FontData>> {
+    for (regular, bold) in PDF_FONTS {
+        if !Path::new(regular).is_file() {
+            continue;
+        }
+        let regular = match genpdf::fonts::FontData::load(regular, None) {
+            Ok(font) => font,
+            Err(e) => {
+                log::debug!("Skipping PDF font {}: {}", regular, e);
+                continue;
+            }
+        };
+        let bold = bold
+            .filter(|path| Path::new(path).is_file())
+            .and_then(|path| genpdf::fonts::FontData::load(path, None).ok())
+            .unwrap_or_else(|| regular.clone());
+        return Ok(genpdf::fonts::FontFamily {
+            italic: regular.clone(),
+            bold_italic: bold.clone(),
+            regular,
+            bold,
+        });
+    }
+    Err(anyhow!("No TrueType font found for PDF reports; export as HTML instead"))
+}
+
+fn pdf_error(e: genpdf::error::Error) -> anyhow::Error {
+    anyhow!("PDF rendering failed: {}", e)
+}
+
+pub fn render_pdf(document: &ReportDocument) -> Result<Vec<u8>> {
+    let mut pdf = genpdf::Document::new(load_pdf_font()?);
+    pdf.set_title(document.title.clone());
+    pdf.set_font_size(PDF_FONT_SIZE);
+    pdf.set_line_spacing(1.25);
+
+    let mut decorator = genpdf::SimplePageDecorator::new();
+    decorator.set_margins(18);
+    decorator.set_header(|page| {
+        Paragraph::new(page.to_string())
+            .aligned(Alignment::Right)
+            .styled(Style::new().with_font_size(8).with_color(Color::Greyscale(140)))
+    });
+    pdf.set_page_decorator(decorator);
+
+    pdf.push(Paragraph::new(StyledString::new(document.title.clone(), Style::new().bold().with_font_size(20))));
+    if let Some(subtitle) = &document.subtitle {
+        pdf.push(Paragraph::new(StyledString::new(subtitle.clone(), Style::new().with_color(Color::Greyscale(90)))));
+    }
+    pdf.push(Paragraph::new(StyledString::new(
+        format!("Generated {} · Garden of Eden", chrono::Local::now().format("%Y-%m-%d %H:%M")),
+        Style::new().with_font_size(8).with_color(Color::Greyscale(140)),
+    )));
+    pdf.push(Break::new(1));
+
+    for section in &document.sections {
+        if let Some(heading) = &section.heading {
+            pdf.push(
+                Paragraph::new(StyledString::new(heading.clone(), Style::new().bold().with_font_size(14)))
+                    .padded((3, 0, 0, 0)),
+            );
+        }
+        if let Some(meta) = &section.meta {
+            pdf.push(Paragraph::new(StyledString::new(
+                meta.clone(),
+                Style::new().with_font_size(8).with_color(Color::Greyscale(120)),
+            )));
+        }
+        pdf.push(markdown_to_pdf(&section.markdown)?);
+        for chart in &section.charts {
+            let title = chart.spec.title.clone().unwrap_or_else(|| "untitled".to_string());
+            pdf.push(Paragraph::new(StyledString::new(
+                format!("[Chart: {} — included in the HTML report]", title),
+                Style::new().italic().with_color(Color::Greyscale(110)),
+            )));
+        }
+        pdf.push(Break::new(0.5));
+    }
+
+    let mut bytes = Vec::new();
+    pdf.render(&mut bytes).map_err(pdf_error)?;
+    Ok(bytes)
+}
+
+/// Block being built while walking Markdown events
+enum PdfBlock {
+    Items(LinearLayout),
+    List { start: Option<u64>, items: Vec<LinearLayout> },
+    Table { rows: Vec<Vec<String>>, in_head: bool },
+}
+
+/// Lays out Markdown with genpdf elements
+struct PdfMarkdown {
+    stack: Vec<PdfBlock>,
+    spans: Vec<StyledString>,
+    bold: usize,
+    italic: usize,
+    heading: Option<HeadingLevel>,
+    code_block: Option<String>,
+    quote_depth: usize,
+    /// Destination of the link being read, appended after its text
+    link: Option<String>,
+    cell: Option<String>,
+}
+
+impl PdfMarkdown {
+    fn new() -> Self {
+        Self {
+            stack: vec![PdfBlock::Items(LinearLayout::vertical())],
+            spans: Vec::new(),
+            bold: 0,
+            italic: 0,
+            heading: None,
+            code_block: None,
+            quote_depth: 0,
+            link: None,
+            cell: None,
+        }
+    }
+
+    fn style(&self) -> Style {
+        let mut style = Style::new();
+        if self.bold > 0 || self.heading.is_some() {
+            style.set_bold();
+        }
+        if self.italic > 0 {
+            style.set_italic();
+        }
+        if self.quote_depth > 0 {
+            style.set_color(Color::Greyscale(90));
+        }
+        if let Some(level) = self.heading {
+            style.set_font_size(match level {
+                HeadingLevel::H1 => 16,
+                HeadingLevel::H2 => 14,
+                HeadingLevel::H3 => 12,
+                _ => PDF_FONT_SIZE,
+            });
+        }
+        style
+    }
+
+    fn text(&mut self, text: &str, style: Style) {
+        if let Some(cell) = &mut self.cell {
+            cell.push_str(text);
+        } else if let Some(code) = &mut self.code_block {
+            code.push_str(text);
+        } else {
+            self.spans.push(StyledString::new(text.to_string(), style));
+        }
+    }
+
+    fn push<E: Element + 'static>(&mut self, element: E) {
+        match self.stack.last_mut() {
+            Some(PdfBlock::Items(layout)) => layout.push(element),
+            Some(PdfBlock::List { items, .. }) => {
+                // Text directly inside a list (tight lists have no item paragraphs)
+                if items.is_empty() {
+                    items.push(LinearLayout::vertical());
+                }
+                if let Some(item) = items.last_mut() {
+                    item.push(element);
+                }
+            }
+            Some(PdfBlock::Table { .. }) | None => {}
+        }
+    }
+
+    /// Turn the collected spans into a paragraph
+    fn flush(&mut self) {
+        if self.spans.is_empty() {
+            return;
+        }
+        let indent = if self.quote_depth > 0 { 6 } else { 0 };
+        let paragraph: Paragraph = std::mem::take(&mut self.spans).into_iter().collect();
+        self.push(paragraph.padded((0, 0, 1, indent)));
+    }
+
+    fn event(&mut self, event: Event) -> Result<()> {
+        match event {
+            Event::Start(tag) => match tag {
+                Tag::Paragraph => {}
+                Tag::Heading { level, .. } => {
+                    self.flush();
+                    self.heading = Some(level);
+                }
+                Tag::BlockQuote(_) => {
+                    self.flush();
+                    self.quote_depth += 1;
+                }
+                Tag::CodeBlock(kind) => {
+                    self.flush();
+                    if let CodeBlockKind::Fenced(language) = kind {
+                        if !language.is_empty() {
+                            self.push(Paragraph::new(StyledString::new(
+                                language.to_string(),
+                                Style::new().with_font_size(7).with_color(Color::Greyscale(140)),
+                            )));
+                        }
+                    }
+                    self.code_block = Some(String::new());
+                }
+                Tag::List(start) => {
+                    self.flush();
+                    self.stack.push(PdfBlock::List { start, items: Vec::new() });
+                }
+                Tag::Item => {
+                    self.flush();
+                    if let Some(PdfBlock::List { items, .. }) = self.stack.last_mut() {
+                        items.push(LinearLayout::vertical());
+                    }
+                    self.stack.push(PdfBlock::Items(LinearLayout::vertical()));
+                }
+                Tag::Table(_) => {
+                    self.flush();
+                    self.stack.push(PdfBlock::Table { rows: Vec::new(), in_head: false });
+                }
+                Tag::TableHead => {
+                    if let Some(PdfBlock::Table { rows, in_head }) = self.stack.last_mut() {
+                        rows.push(Vec::new());
+                        *in_head = true;
+                    }
+                }
+                Tag::TableRow => {
+                    if let Some(PdfBlock::Table { rows, in_head }) = self.stack.last_mut() {
+                        rows.push(Vec::new());
+                        *in_head = false;
+                    }
+                }
+                Tag::TableCell => self.cell = Some(String::new()),
+                Tag::Emphasis => self.italic += 1,
+                Tag::Strong => self.bold += 1,
+                Tag::Link { dest_url, .. } => self.link = Some(dest_url.to_string()),
+                Tag::Image { dest_url, .. } => self.text(&format!("[image: {}]", dest_url), Style::new().italic()),
+                _ => {}
+            },
+            Event::End(tag) => match tag {
+                TagEnd::Paragraph => self.flush(),
+                TagEnd::Heading(_) => {
+                    let paragraph: Paragraph = std::mem::take(&mut self.spans).into_iter().collect();
+                    self.push(paragraph.padded((2, 0, 1, 0)));
+                    self.heading = None;
+                }
+                TagEnd::BlockQuote(_) => {
+                    self.flush();
+                    self.quote_depth = self.quote_depth.saturating_sub(1);
+                }
+                TagEnd::CodeBlock => {
+                    let code = self.code_block.take().unwrap_or_default();
+                    let mut layout = LinearLayout::vertical();
+                    for line in code.trim_end_matches('\n').lines() {
+                        // Leading spaces would be dropped by the line wrapper
+                        let line = line.replace(' ', "\u{00A0}");
+                        layout.push(Paragraph::new(StyledString::new(
+                            if line.is_empty() { "\u{00A0}".to_string() } else { line },
+                            Style::new().with_font_size(9).with_color(Color::Greyscale(50)),
+                        )));
+                    }
+                    self.push(layout.padded(2).framed().padded((1, 0, 2, 0)));
+                }
+                TagEnd::Item => {
+                    self.flush();
+                    if let Some(PdfBlock::Items(layout)) = self.stack.pop() {
+                        if let Some(PdfBlock::List { items, .. }) = self.stack.last_mut() {
+                            match items.last_mut() {
+                                Some(item) => item.push(layout),
+                                None => items.push(layout),
+                            }
+                        }
+                    }
+                }
+                TagEnd::List(_) => {
+                    self.flush();
+                    if let Some(PdfBlock::List { start, items }) = self.stack.pop() {
+                        match start {
+                            Some(start) => {
+                                let mut list = OrderedList::with_start(start as usize);
+                                items.into_iter().for_each(|item| list.push(item));
+                                self.push(list.padded((0, 0, 1, 0)));
+                            }
+                            None => {
+                                let mut list = UnorderedList::new();
+                                items.into_iter().for_each(|item| list.push(item));
+                                self.push(list.padded((0, 0, 1, 0)));
+                            }
+                        }
+                    }
+                }
+                TagEnd::TableCell => {
+                    let cell = self.cell.take().unwrap_or_default();
+                    if let Some(PdfBlock::Table { rows, .. }) = self.stack.last_mut() {
+                        if let Some(row) = rows.last_mut() {
+                            row.push(cell);
+                        }
+                    }
+                }
+                TagEnd::Table => {
+                    if let Some(PdfBlock::Table { rows, .. }) = self.stack.pop() {
+                        self.push_table(rows)?;
+                    }
+                }
+                TagEnd::Emphasis => self.italic = self.italic.saturating_sub(1),
+                TagEnd::Strong => self.bold = self.bold.saturating_sub(1),
+                TagEnd::Link => {
+                    if let Some(url) = self.link.take() {
+                        self.text(&format!(" ({})", url), Style::new().with_font_size(8).with_color(Color::Greyscale(120)));
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => {
+                let style = self.style();
+                self.text(&text, style);
+            }
+            Event::Code(code) | Event::InlineMath(code) | Event::DisplayMath(code) => {
+                let style = self.style().with_color(Color::Rgb(150, 40, 60));
+                self.text(&code, style);
+            }
+            Event::SoftBreak => self.text(" ", Style::new()),
+            Event::HardBreak => self.flush(),
+            Event::Rule => {
+                self.flush();
+                self.push(Break::new(1));
+            }
+            Event::TaskListMarker(done) => self.text(if done { "☑ " } else { "☐ " }, Style::new()),
+            Event::FootnoteReference(name) => self.text(&format!("[{}]", name), Style::new()),
+        }
+        Ok(())
+    }
+
+    /// The first row is the header
+    fn push_table(&mut self, rows: Vec<Vec<String>>) -> Result<()> {
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if columns == 0 {
+            return Ok(());
+        }
+        let mut table = TableLayout::new(vec![1; columns]);
+        table.set_cell_decorator(FrameCellDecorator::new(true, true, false));
+        for (index, row) in rows.into_iter().enumerate() {
+            let style = if index == 0 { Style::new().bold() } else { Style::new() }.with_font_size(9);
+            let mut table_row = table.row();
+            for column in 0..columns {
+                let text = row.get(column).cloned().unwrap_or_default();
+                table_row.push_element(Paragraph::new(StyledString::new(text, style)).padded(1));
+            }
+            table_row.push().map_err(pdf_error)?;
+        }
+        self.push(table.padded((1, 0, 2, 0)));
+        Ok(())
+    }
+
+    fn finish(mut self) -> LinearLayout {
+        self.flush();
+        while self.stack.len() > 1 {
+            self.stack.pop();
+        }
+        match self.stack.pop() {
+            Some(PdfBlock::Items(layout)) => layout,
+            _ => LinearLayout::vertical(),
+        }
+    }
+}
+
+fn markdown_to_pdf(markdown: &str) -> Result<LinearLayout> {
+    let mut builder = PdfMarkdown::new();
+    for event in Parser::new_ext(markdown, markdown_options()) {
+        builder.event(event)?;
+    }
+    Ok(builder.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::services::charts::{ChartKind, ChartSeries, ChartSpec};
+
+    #[test]
+    fn test_conversation_report() {
+        let db = Database::new_test_db().unwrap();
+        let conn = db.conn();
+        conn.execute_batch(
+            "INSERT INTO conversations (id, title, mode, created_at, updated_at, message_count)
+             VALUES ('c1', '분기 매출 정리', 'user-led', 1760000000000, 1760000000000, 2);
+             INSERT INTO messages (id, conversation_id, role, content, timestamp)
+             VALUES ('m1', 'c1', 'user', 'Chart Q3 sales <script>alert(1)</script>', 1760000000000),
+                    ('m2', 'c1', 'assistant', '## Summary\n\n| month | sales |\n|---|---|\n| Jul | 12 |\n\n- **Aug** grew\n- Sep `flat`', 1760000001000);",
+        )
+        .unwrap();
+        let chart = StoredChart {
+            id: "chart_1".to_string(),
+            conversation_id: Some("c1".to_string()),
+            message_id: Some("m2".to_string()),
+            spec: ChartSpec {
+                kind: ChartKind::Line,
+                title: Some("Q3 sales".to_string()),
+                x_label: None,
+                y_label: None,
+                labels: vec!["Jul".into(), "Aug".into(), "Sep".into()],
+                series: vec![ChartSeries { name: String::new(), values: vec![12.0, 15.0, 15.0], x: None }],
+            },
+            created_at: 0,
+        };
+
+        let document = conversation_document(conn, "c1", &[chart]).unwrap();
+        assert_eq!(document.title, "분기 매출 정리");
+        assert_eq!(document.sections.len(), 2);
+        assert_eq!(document.sections[1].charts.len(), 1);
+
+        let report = generate(&document, ReportFormat::Html, None).unwrap();
+        assert_eq!(report.file_name, "분기-매출-정리.html");
+        assert!(report.data.contains("<html lang=\"ko\">"));
+        assert!(report.data.contains("<table>") && report.data.contains("<strong>Aug</strong>"));
+        assert!(report.data.contains("&lt;script&gt;") && !report.data.contains("<script>"));
+        assert!(report.data.contains("<svg") && report.data.contains("<figcaption>Q3 sales</figcaption>"));
+
+        // Only where a system font is available
+        if load_pdf_font().is_ok() {
+            let pdf = render_pdf(&document).unwrap();
+            assert!(pdf.starts_with(b"%PDF"));
+        }
+        assert!(conversation_document(conn, "missing", &[]).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 //! Cross-Encoder Re-ranking Service (v3.6.0)
 //!
-//! Uses ms-marco-MiniLM-L-12-v2 cross-encoder for relevance scoring
+//! Uses bge-reranker-base/large cross-encoders (ONNX, optionally quantized)
+//! for relevance scoring, with a heuristic fallback when no model is loaded.
 //!
 //! Re-ranking Pipeline:
 //! 1. Hybrid search returns top-20 candidates
@@ -8,16 +9,25 @@
 //! 3. Re-sort by cross-encoder scores
 //! 4. Return top-K most relevant
 //!
+//! Models are downloaded on first use into the models directory next to
+//! BGE-M3 and loaded lazily on the first query after selection.
+//!
 //! Expected improvement: 15-20% better precision@5
 
 #![allow(dead_code)]  // Phase 13: Re-ranking (LanceDB feature)
 
-use log::{debug, info};
-// TODO: Uncomment when implementing actual cross-encoder model
-// use ort::{Session, Value};
-// use ndarray::{Array1, Array2};
-// use std::sync::Arc;
-// use tokenizers::Tokenizer;
+use anyhow::{anyhow, Result};
+use log::{debug, info, warn};
+use ort::session::Session;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokenizers::{Tokenizer, TruncationParams, TruncationStrategy};
+
+/// Maximum query + document length fed to the cross-encoder
+const MODEL_MAX_LENGTH: usize = 512;
+
+/// Candidates re-ranked per query unless a preset says otherwise
+const DEFAULT_CANDIDATES: usize = 20;
 
 /// Re-ranked search result with cross-encoder score
 #[derive(Clone, Debug)]
@@ -29,34 +39,450 @@ pub struct RerankedResult {
     pub original_rank: usize,
 }
 
-// TODO: Implement actual cross-encoder re-ranker when ONNX model is ready
-// /// Cross-Encoder Re-ranker Service
-// pub struct RerankerService {
-//     session: Arc<Session>,
-//     tokenizer: Arc<Tokenizer>,
-//     max_length: usize,
-// }
-//
-// impl RerankerService {
-//     /// Create new re-ranker service
-//     pub fn new() -> Result<Self, String> {
-//         info!("Initializing Cross-Encoder Re-ranker (ms-marco-MiniLM-L-12-v2)");
-//         // Download and load the ONNX model
-//         // Model: https://huggingface.co/cross-encoder/ms-marco-MiniLM-L-12-v2
-//         Err("Cross-encoder model not yet implemented - using heuristic re-ranking".to_string())
-//     }
-//
-//     /// Re-rank search results using cross-encoder
-//     pub fn rerank(
-//         &self,
-//         query: &str,
-//         results: Vec<(String, String, f32)>,
-//         top_k: usize,
-//     ) -> Result<Vec<RerankedResult>, String> {
-//         // Implementation will go here
-//         Ok(vec![])
-//     }
-// }
+/// Re-ranking model selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RerankerModel {
+    /// Heuristic scoring only (no download)
+    #[default]
+    Heuristic,
+    /// bge-reranker-base, INT8 quantized (~280MB)
+    BgeRerankerBaseQuantized,
+    /// bge-reranker-base, FP32 (~1.1GB)
+    BgeRerankerBase,
+    /// bge-reranker-large, INT8 quantized (~560MB)
+    BgeRerankerLargeQuantized,
+    /// bge-reranker-large, FP32 (~2.2GB)
+    BgeRerankerLarge,
+}
+
+impl RerankerModel {
+    pub const ALL: [RerankerModel; 5] = [
+        RerankerModel::Heuristic,
+        RerankerModel::BgeRerankerBaseQuantized,
+        RerankerModel::BgeRerankerBase,
+        RerankerModel::BgeRerankerLargeQuantized,
+        RerankerModel::BgeRerankerLarge,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RerankerModel::Heuristic => "heuristic",
+            RerankerModel::BgeRerankerBaseQuantized => "bge-reranker-base-quantized",
+            RerankerModel::BgeRerankerBase => "bge-reranker-base",
+            RerankerModel::BgeRerankerLargeQuantized => "bge-reranker-large-quantized",
+            RerankerModel::BgeRerankerLarge => "bge-reranker-large",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|model| model.as_str().eq_ignore_ascii_case(value.trim()))
+    }
+
+    /// Hugging Face repository with the ONNX export (None for heuristic)
+    fn repo(&self) -> Option<&'static str> {
+        match self {
+            RerankerModel::Heuristic => None,
+            RerankerModel::BgeRerankerBaseQuantized | RerankerModel::BgeRerankerBase => {
+                Some("Xenova/bge-reranker-base")
+            }
+            RerankerModel::BgeRerankerLargeQuantized | RerankerModel::BgeRerankerLarge => {
+                Some("Xenova/bge-reranker-large")
+            }
+        }
+    }
+
+    pub fn is_quantized(&self) -> bool {
+        matches!(
+            self,
+            RerankerModel::BgeRerankerBaseQuantized | RerankerModel::BgeRerankerLargeQuantized
+        )
+    }
+
+    fn model_file(&self) -> &'static str {
+        if self.is_quantized() {
+            "model_quantized.onnx"
+        } else {
+            "model.onnx"
+        }
+    }
+
+    /// Approximate download size
+    pub fn size_mb(&self) -> u32 {
+        match self {
+            RerankerModel::Heuristic => 0,
+            RerankerModel::BgeRerankerBaseQuantized => 280,
+            RerankerModel::BgeRerankerBase => 1110,
+            RerankerModel::BgeRerankerLargeQuantized => 560,
+            RerankerModel::BgeRerankerLarge => 2240,
+        }
+    }
+
+    /// Model directory (shared by the quantized and FP32 variants)
+    fn model_dir(&self) -> Result<PathBuf> {
+        let repo = self.repo().ok_or_else(|| anyhow!("Heuristic re-ranker has no model files"))?;
+        let name = repo.rsplit('/').next().unwrap_or(repo);
+        let data_dir = dirs::data_dir()
+            .ok_or_else(|| anyhow!("Failed to get data directory"))?;
+        Ok(data_dir.join("garden-of-eden-v3").join("models").join(name))
+    }
+
+    /// Whether the model files are already on disk
+    pub fn is_downloaded(&self) -> bool {
+        match self.model_dir() {
+            Ok(dir) => dir.join(self.model_file()).exists() && dir.join("tokenizer.json").exists(),
+            Err(_) => *self == RerankerModel::Heuristic,
+        }
+    }
+}
+
+/// Latency/quality trade-off presets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RerankerPreset {
+    /// Small quantized model over a short candidate list
+    Latency,
+    Balanced,
+    /// Full-precision large model over a long candidate list
+    Quality,
+}
+
+impl RerankerPreset {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "latency" | "fast" => Some(RerankerPreset::Latency),
+            "balanced" => Some(RerankerPreset::Balanced),
+            "quality" | "accurate" => Some(RerankerPreset::Quality),
+            _ => None,
+        }
+    }
+
+    pub fn model(&self) -> RerankerModel {
+        match self {
+            RerankerPreset::Latency => RerankerModel::BgeRerankerBaseQuantized,
+            RerankerPreset::Balanced => RerankerModel::BgeRerankerLargeQuantized,
+            RerankerPreset::Quality => RerankerModel::BgeRerankerLarge,
+        }
+    }
+
+    /// Number of fused candidates passed to the cross-encoder
+    pub fn candidates(&self) -> usize {
+        match self {
+            RerankerPreset::Latency => 10,
+            RerankerPreset::Balanced => DEFAULT_CANDIDATES,
+            RerankerPreset::Quality => 30,
+        }
+    }
+}
+
+/// ONNX cross-encoder scoring (query, document) pairs
+pub struct CrossEncoder {
+    model: RerankerModel,
+    session: Mutex<Session>,
+    tokenizer: Tokenizer,
+}
+
+impl CrossEncoder {
+    /// Load a cross-encoder, downloading it on first use
+    pub fn load(model: RerankerModel) -> Result<Self> {
+        let model_dir = model.model_dir()?;
+        let model_path = model_dir.join(model.model_file());
+        let tokenizer_path = model_dir.join("tokenizer.json");
+
+        if !model_path.exists() || !tokenizer_path.exists() {
+            std::fs::create_dir_all(&model_dir)?;
+            info!("{} not found, downloading (~{}MB)...", model.as_str(), model.size_mb());
+            Self::download_model(model, &model_dir)?;
+        }
+
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_path)
+            .map_err(|e| anyhow!("Failed to load tokenizer: {}", e))?;
+        // Long documents are cut, never the query
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: MODEL_MAX_LENGTH,
+                strategy: TruncationStrategy::OnlySecond,
+                ..Default::default()
+            }))
+            .map_err(|e| anyhow!("Failed to configure truncation: {}", e))?;
+
+        info!("Loading {} ONNX model...", model.as_str());
+        let session = Session::builder()
+            .map_err(|e| anyhow!("Failed to create SessionBuilder: {:?}", e))?
+            .with_intra_threads(4)
+            .map_err(|e| anyhow!("Failed to set intra_threads: {:?}", e))?
+            .commit_from_file(&model_path)
+            .map_err(|e| anyhow!("Failed to load {}: {:?}", model.as_str(), e))?;
+
+        info!("Cross-encoder {} loaded", model.as_str());
+        Ok(Self {
+            model,
+            session: Mutex::new(session),
+            tokenizer,
+        })
+    }
+
+    /// Download model + tokenizer from Hugging Face
+    fn download_model(model: RerankerModel, model_dir: &std::path::Path) -> Result<()> {
+        let repo = model.repo().ok_or_else(|| anyhow!("Heuristic re-ranker has no model files"))?;
+        let files = [
+            (format!("onnx/{}", model.model_file()), model.model_file()),
+            ("tokenizer.json".to_string(), "tokenizer.json"),
+        ];
+
+        for (remote, local) in files {
+            let target = model_dir.join(local);
+            if target.exists() {
+                continue;
+            }
+            let url = format!("https://huggingface.co/{}/resolve/main/{}", repo, remote);
+            info!("Downloading {}...", url);
+            let bytes = reqwest::blocking::get(&url)?
+                .error_for_status()?
+                .bytes()?;
+            info!("{} downloaded ({:.1}MB)", local, bytes.len() as f64 / 1024.0 / 1024.0);
+
+            // Write to a temp file first so an interrupted download is retried
+            let partial = model_dir.join(format!("{}.part", local));
+            std::fs::write(&partial, bytes)?;
+            std::fs::rename(&partial, &target)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn model(&self) -> RerankerModel {
+        self.model
+    }
+
+    /// Relevance of each document to the query (sigmoid of the logit, 0-1)
+    pub fn score(&self, query: &str, documents: &[&str]) -> Result<Vec<f32>> {
+        if documents.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let encodings = documents
+            .iter()
+            .map(|doc| {
+                self.tokenizer
+                    .encode((query, *doc), true)
+                    .map_err(|e| anyhow!("Tokenization failed: {}", e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let batch_size = encodings.len();
+        let max_seq_len = encodings
+            .iter()
+            .map(|enc| enc.get_ids().len())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let pad_id = self.tokenizer.token_to_id("<pad>").unwrap_or(1) as i64;
+
+        let mut input_ids: Vec<i64> = vec![pad_id; batch_size * max_seq_len];
+        let mut attention_mask: Vec<i64> = vec![0; batch_size * max_seq_len];
+        for (i, encoding) in encodings.iter().enumerate() {
+            for (j, (&id, &mask)) in encoding
+                .get_ids()
+                .iter()
+                .zip(encoding.get_attention_mask())
+                .enumerate()
+            {
+                input_ids[i * max_seq_len + j] = id as i64;
+                attention_mask[i * max_seq_len + j] = mask as i64;
+            }
+        }
+
+        let input_ids_tensor = ort::value::Tensor::from_array((vec![batch_size, max_seq_len], input_ids))
+            .map_err(|e| anyhow!("Failed to create input_ids tensor: {:?}", e))?;
+        let attention_mask_tensor = ort::value::Tensor::from_array((vec![batch_size, max_seq_len], attention_mask))
+            .map_err(|e| anyhow!("Failed to create attention_mask tensor: {:?}", e))?;
+
+        let mut session = self.session.lock().map_err(|e| anyhow!("Lock failed: {}", e))?;
+        let outputs = session
+            .run(ort::inputs![input_ids_tensor, attention_mask_tensor])
+            .map_err(|e| anyhow!("Cross-encoder inference failed: {:?}", e))?;
+
+        // Logits have shape [batch_size, 1]
+        let logits = outputs[0]
+            .try_extract_array::<f32>()
+            .map_err(|e| anyhow!("Failed to extract logits: {:?}", e))?;
+        let scores: Vec<f32> = logits.iter().map(|&logit| sigmoid(logit)).collect();
+        if scores.len() != batch_size {
+            return Err(anyhow!("Expected {} scores, got {}", batch_size, scores.len()));
+        }
+        Ok(scores)
+    }
+}
+
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+struct RerankerState {
+    model: RerankerModel,
+    candidates: usize,
+    encoder: Option<Arc<CrossEncoder>>,
+    /// Set when loading the selected model failed; cleared on the next selection
+    load_error: Option<String>,
+}
+
+/// Re-ranker with a selectable cross-encoder, loaded lazily on first query
+///
+/// Falls back to heuristic scoring when no model is selected or the model
+/// can't be downloaded/loaded.
+pub struct RerankerService {
+    heuristic: HeuristicReranker,
+    state: Mutex<RerankerState>,
+}
+
+/// Snapshot of the re-ranker selection for the UI
+#[derive(Debug, Clone)]
+pub struct RerankerStatus {
+    pub model: RerankerModel,
+    pub candidates: usize,
+    pub loaded: bool,
+    pub downloaded: bool,
+    pub last_error: Option<String>,
+}
+
+impl RerankerService {
+    pub fn new() -> Self {
+        RerankerService {
+            heuristic: HeuristicReranker::new(),
+            state: Mutex::new(RerankerState {
+                model: RerankerModel::Heuristic,
+                candidates: DEFAULT_CANDIDATES,
+                encoder: None,
+                load_error: None,
+            }),
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, RerankerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Select a model; the previous one is unloaded, the new one loads on next use
+    pub fn set_model(&self, model: RerankerModel, candidates: Option<usize>) {
+        let mut state = self.lock_state();
+        info!("Re-ranker model: {} -> {}", state.model.as_str(), model.as_str());
+        if state.model != model {
+            state.encoder = None;
+        }
+        state.model = model;
+        state.load_error = None;
+        if let Some(candidates) = candidates {
+            state.candidates = candidates.max(1);
+        }
+    }
+
+    pub fn apply_preset(&self, preset: RerankerPreset) {
+        self.set_model(preset.model(), Some(preset.candidates()));
+    }
+
+    pub fn model(&self) -> RerankerModel {
+        self.lock_state().model
+    }
+
+    /// Number of fused candidates to pass to `rerank`
+    pub fn candidates(&self) -> usize {
+        self.lock_state().candidates
+    }
+
+    pub fn status(&self) -> RerankerStatus {
+        let state = self.lock_state();
+        RerankerStatus {
+            model: state.model,
+            candidates: state.candidates,
+            loaded: state.encoder.is_some(),
+            downloaded: state.model.is_downloaded(),
+            last_error: state.load_error.clone(),
+        }
+    }
+
+    /// Selected cross-encoder, loading (and downloading) it if needed
+    ///
+    /// Blocking: call from a blocking task.
+    fn encoder(&self) -> Option<Arc<CrossEncoder>> {
+        let mut state = self.lock_state();
+        if state.model == RerankerModel::Heuristic || state.load_error.is_some() {
+            return None;
+        }
+        if let Some(encoder) = &state.encoder {
+            return Some(Arc::clone(encoder));
+        }
+
+        match CrossEncoder::load(state.model) {
+            Ok(encoder) => {
+                let encoder = Arc::new(encoder);
+                state.encoder = Some(Arc::clone(&encoder));
+                Some(encoder)
+            }
+            Err(e) => {
+                warn!("Failed to load {}, using heuristic re-ranking: {}", state.model.as_str(), e);
+                state.load_error = Some(e.to_string());
+                None
+            }
+        }
+    }
+
+    /// Re-rank with the selected model, or heuristics as a fallback
+    ///
+    /// Blocking: the first call after a model change loads the model.
+    pub fn rerank(
+        &self,
+        query: &str,
+        results: Vec<(String, String, f32)>, // (id, content, score)
+        top_k: usize,
+    ) -> Vec<RerankedResult> {
+        let Some(encoder) = self.encoder() else {
+            return self.heuristic.rerank(query, results, top_k);
+        };
+
+        let documents: Vec<&str> = results.iter().map(|(_, content, _)| content.as_str()).collect();
+        let scores = match encoder.score(query, &documents) {
+            Ok(scores) => scores,
+            Err(e) => {
+                warn!("Cross-encoder scoring failed, using heuristic re-ranking: {}", e);
+                return self.heuristic.rerank(query, results, top_k);
+            }
+        };
+
+        let mut reranked: Vec<RerankedResult> = results
+            .into_iter()
+            .zip(scores)
+            .enumerate()
+            .map(|(rank, ((id, content, original_score), score))| RerankedResult {
+                document_id: id,
+                content,
+                cross_encoder_score: score,
+                original_score,
+                original_rank: rank + 1,
+            })
+            .collect();
+
+        reranked.sort_by(|a, b| {
+            b.cross_encoder_score
+                .partial_cmp(&a.cross_encoder_score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        reranked.truncate(top_k);
+
+        debug!(
+            "Cross-encoder ({}) re-ranking complete: {} results",
+            encoder.model().as_str(),
+            reranked.len()
+        );
+        reranked
+    }
+}
+
+impl Default for RerankerService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 /// Heuristic-based re-ranker (fallback when cross-encoder is not available)
 pub struct HeuristicReranker;
@@ -207,4 +633,43 @@ mod tests {
         // Document with all query terms should rank higher
         assert_eq!(reranked[0].document_id, "doc3");
     }
+
+    #[test]
+    fn test_reranker_model_ids() {
+        for model in RerankerModel::ALL {
+            assert_eq!(RerankerModel::parse(model.as_str()), Some(model));
+        }
+        assert_eq!(RerankerModel::parse("BGE-Reranker-Large"), Some(RerankerModel::BgeRerankerLarge));
+        assert_eq!(RerankerModel::parse("ms-marco"), None);
+        assert!(RerankerModel::BgeRerankerBaseQuantized.is_quantized());
+        assert_eq!(RerankerModel::BgeRerankerLarge.model_file(), "model.onnx");
+    }
+
+    #[test]
+    fn test_reranker_presets() {
+        let service = RerankerService::new();
+        assert_eq!(service.model(), RerankerModel::Heuristic);
+
+        service.apply_preset(RerankerPreset::parse("latency").unwrap());
+        assert_eq!(service.model(), RerankerModel::BgeRerankerBaseQuantized);
+        assert_eq!(service.candidates(), 10);
+
+        service.apply_preset(RerankerPreset::Quality);
+        assert_eq!(service.model(), RerankerModel::BgeRerankerLarge);
+        assert_eq!(service.candidates(), 30);
+    }
+
+    #[test]
+    fn test_heuristic_model_skips_loading() {
+        let service = RerankerService::new();
+        let results = vec![
+            ("doc1".to_string(), "python".to_string(), 0.5),
+            ("doc2".to_string(), "python programming language".to_string(), 0.5),
+        ];
+
+        let reranked = service.rerank("python programming", results, 1);
+        assert_eq!(reranked.len(), 1);
+        assert_eq!(reranked[0].document_id, "doc2");
+        assert!(!service.status().loaded);
+    }
 }
@@ -0,0 +1,520 @@
+//! Response Formatter (v3.9.0)
+//!
+//! Splits model output into structured segments so the frontend can render rich
+//! content without regex-parsing markdown itself.
+//!
+//! Segment types:
+//! - Text: plain markdown prose
+//! - Code: fenced code blocks (with optional language)
+//! - Math: LaTeX math, inline (`$...$`, `\(...\)`) or display (`$$...$$`, `\[...\]`)
+//! - Table: GitHub-style markdown tables, normalized to a rectangular grid
+//!
+//! Math is validated (balanced braces, matching \begin/\end) and tables are padded
+//! so every row has the same number of cells as the header.
+
+use serde::{Deserialize, Serialize};
+
+/// Column alignment for table segments
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnAlignment {
+    Left,
+    Center,
+    Right,
+    None,
+}
+
+/// A structured piece of a model response
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ResponseSegment {
+    Text {
+        content: String,
+    },
+    Code {
+        language: Option<String>,
+        content: String,
+    },
+    Math {
+        latex: String,
+        display: bool,
+        valid: bool,
+    },
+    Table {
+        headers: Vec<String>,
+        alignments: Vec<ColumnAlignment>,
+        rows: Vec<Vec<String>>,
+    },
+}
+
+/// Formatted response: raw markdown plus structured segments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormattedResponse {
+    pub raw: String,
+    pub segments: Vec<ResponseSegment>,
+    pub has_math: bool,
+    pub has_table: bool,
+    pub has_code: bool,
+}
+
+/// Format a model response into structured segments
+pub fn format_response(raw: &str) -> FormattedResponse {
+    let segments = parse_segments(raw);
+
+    let has_math = segments.iter().any(|s| matches!(s, ResponseSegment::Math { .. }));
+    let has_table = segments.iter().any(|s| matches!(s, ResponseSegment::Table { .. }));
+    let has_code = segments.iter().any(|s| matches!(s, ResponseSegment::Code { .. }));
+
+    FormattedResponse {
+        raw: raw.to_string(),
+        segments,
+        has_math,
+        has_table,
+        has_code,
+    }
+}
+
+/// Parse block-level structure (code fences, display math, tables), then inline math
+pub fn parse_segments(raw: &str) -> Vec<ResponseSegment> {
+    let lines: Vec<&str> = raw.lines().collect();
+    let mut segments = Vec::new();
+    let mut text_buffer: Vec<&str> = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+
+        // Fenced code block
+        if let Some(fence_lang) = trimmed.strip_prefix("```") {
+            flush_text(&mut text_buffer, &mut segments);
+            let language = Some(fence_lang.trim().to_string()).filter(|l| !l.is_empty());
+            let mut body = Vec::new();
+            i += 1;
+            while i < lines.len() && !lines[i].trim().starts_with("```") {
+                body.push(lines[i]);
+                i += 1;
+            }
+            segments.push(ResponseSegment::Code {
+                language,
+                content: body.join("\n"),
+            });
+            i += 1; // Skip closing fence (or end of input)
+            continue;
+        }
+
+        // Display math: $$...$$ or \[...\]
+        if let Some((open, close)) = display_math_delimiters(trimmed) {
+            flush_text(&mut text_buffer, &mut segments);
+            let after_open = &trimmed[open.len()..];
+
+            let latex = if let Some(end) = after_open.find(close) {
+                // Single-line display math
+                let trailing = after_open[end + close.len()..].trim();
+                let latex = after_open[..end].to_string();
+                if !trailing.is_empty() {
+                    text_buffer.push(trailing);
+                }
+                i += 1;
+                latex
+            } else {
+                let mut body = vec![after_open];
+                i += 1;
+                while i < lines.len() {
+                    if let Some(end) = lines[i].find(close) {
+                        body.push(&lines[i][..end]);
+                        // Text after the closing delimiter stays in the response
+                        let trailing = lines[i][end + close.len()..].trim();
+                        if !trailing.is_empty() {
+                            text_buffer.push(trailing);
+                        }
+                        break;
+                    }
+                    body.push(lines[i]);
+                    i += 1;
+                }
+                i += 1;
+                body.join("\n")
+            };
+
+            segments.push(math_segment(&latex, true));
+            flush_text(&mut text_buffer, &mut segments);
+            continue;
+        }
+
+        // Markdown table: header row followed by a separator row
+        if is_table_row(trimmed) && i + 1 < lines.len() && is_table_separator(lines[i + 1].trim()) {
+            flush_text(&mut text_buffer, &mut segments);
+            let headers = split_table_row(trimmed);
+            let alignments = parse_alignments(lines[i + 1].trim(), headers.len());
+            let mut rows = Vec::new();
+            i += 2;
+            while i < lines.len() && is_table_row(lines[i].trim()) {
+                rows.push(normalize_row(split_table_row(lines[i].trim()), headers.len()));
+                i += 1;
+            }
+            segments.push(ResponseSegment::Table {
+                headers,
+                alignments,
+                rows,
+            });
+            continue;
+        }
+
+        text_buffer.push(line);
+        i += 1;
+    }
+
+    flush_text(&mut text_buffer, &mut segments);
+    segments
+}
+
+/// Emit buffered text lines, splitting out inline math
+fn flush_text(buffer: &mut Vec<&str>, segments: &mut Vec<ResponseSegment>) {
+    if buffer.is_empty() {
+        return;
+    }
+    let text = buffer.join("\n");
+    buffer.clear();
+
+    if text.trim().is_empty() {
+        return;
+    }
+
+    segments.extend(split_inline_math(&text));
+}
+
+/// Split a text block into Text and inline Math segments
+pub fn split_inline_math(text: &str) -> Vec<ResponseSegment> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        // `code` spans are copied as-is, so `$HOME` stays text
+        if chars[i] == '`' {
+            let ticks = chars[i..].iter().take_while(|&&c| c == '`').count();
+            let fence = vec!['`'; ticks];
+            if let Some(end) = find_sequence(&chars, i + ticks, &fence) {
+                current.extend(&chars[i..end + ticks]);
+                i = end + ticks;
+                continue;
+            }
+        }
+
+        // \( ... \)
+        if chars[i] == '\\' && chars.get(i + 1) == Some(&'(') {
+            if let Some(end) = find_sequence(&chars, i + 2, &['\\', ')']) {
+                push_text(&mut current, &mut segments);
+                let latex: String = chars[i + 2..end].iter().collect();
+                segments.push(math_segment(&latex, false));
+                i = end + 2;
+                continue;
+            }
+        }
+
+        // $ ... $ (not $$, not currency)
+        if chars[i] == '$' && chars.get(i + 1) != Some(&'$') && !is_escaped(&chars, i) {
+            if let Some(end) = find_inline_dollar_end(&chars, i + 1) {
+                push_text(&mut current, &mut segments);
+                let latex: String = chars[i + 1..end].iter().collect();
+                segments.push(math_segment(&latex, false));
+                i = end + 1;
+                continue;
+            }
+        }
+
+        current.push(chars[i]);
+        i += 1;
+    }
+
+    push_text(&mut current, &mut segments);
+    segments
+}
+
+fn push_text(current: &mut String, segments: &mut Vec<ResponseSegment>) {
+    if !current.is_empty() {
+        segments.push(ResponseSegment::Text {
+            content: std::mem::take(current),
+        });
+    }
+}
+
+fn is_escaped(chars: &[char], i: usize) -> bool {
+    i > 0 && chars[i - 1] == '\\'
+}
+
+/// Find the closing `$` of inline math starting at `start`
+///
+/// Rejects currency-like usage: the content must not start or end with whitespace,
+/// and the closing `$` must not be followed by a digit ("$5 and $10").
+fn find_inline_dollar_end(chars: &[char], start: usize) -> Option<usize> {
+    if start >= chars.len() || chars[start].is_whitespace() {
+        return None;
+    }
+
+    let mut j = start;
+    while j < chars.len() {
+        match chars[j] {
+            // Math never spans a line or runs into a code span
+            '\n' | '`' => return None,
+            '$' if !is_escaped(chars, j) => {
+                if j == start || chars[j - 1].is_whitespace() {
+                    return None;
+                }
+                if chars.get(j + 1).is_some_and(|c| c.is_ascii_digit()) {
+                    return None;
+                }
+                return Some(j);
+            }
+            _ => {}
+        }
+        j += 1;
+    }
+    None
+}
+
+fn find_sequence(chars: &[char], start: usize, seq: &[char]) -> Option<usize> {
+    (start..chars.len().saturating_sub(seq.len() - 1)).find(|&j| chars[j..j + seq.len()] == *seq)
+}
+
+fn display_math_delimiters(trimmed: &str) -> Option<(&'static str, &'static str)> {
+    if trimmed.starts_with("$$") {
+        Some(("$$", "$$"))
+    } else if trimmed.starts_with("\\[") {
+        Some(("\\[", "\\]"))
+    } else {
+        None
+    }
+}
+
+/// Build a math segment with normalized LaTeX and validation result
+fn math_segment(latex: &str, display: bool) -> ResponseSegment {
+    let latex = normalize_latex(latex);
+    let valid = validate_latex(&latex);
+    ResponseSegment::Math { latex, display, valid }
+}
+
+/// Trim and collapse runs of whitespace in LaTeX source
+pub fn normalize_latex(latex: &str) -> String {
+    latex.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Basic LaTeX validation: non-empty, balanced braces, matching environments
+pub fn validate_latex(latex: &str) -> bool {
+    if latex.trim().is_empty() {
+        return false;
+    }
+
+    let mut depth: i32 = 0;
+    let mut prev = '\0';
+    for c in latex.chars() {
+        if prev != '\\' {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth < 0 {
+                        return false;
+                    }
+                }
+                _ => {}
+            }
+        }
+        prev = if prev == '\\' && c == '\\' { '\0' } else { c };
+    }
+    if depth != 0 {
+        return false;
+    }
+
+    let mut environments: Vec<&str> = Vec::new();
+    let mut rest = latex;
+    loop {
+        let (pos, is_begin) = match (rest.find("\\begin{"), rest.find("\\end{")) {
+            (Some(b), Some(e)) if b < e => (b, true),
+            (_, Some(e)) => (e, false),
+            (Some(b), None) => (b, true),
+            (None, None) => break,
+        };
+
+        let name_start = pos + if is_begin { "\\begin{".len() } else { "\\end{".len() };
+        let name_end = match rest[name_start..].find('}') {
+            Some(offset) => name_start + offset,
+            None => return false,
+        };
+        let name = &rest[name_start..name_end];
+
+        if is_begin {
+            environments.push(name);
+        } else if environments.pop() != Some(name) {
+            return false;
+        }
+        rest = &rest[name_end + 1..];
+    }
+
+    environments.is_empty()
+}
+
+fn is_table_row(trimmed: &str) -> bool {
+    trimmed.starts_with('|') && trimmed.len() > 1 && trimmed[1..].contains('|')
+}
+
+fn is_table_separator(trimmed: &str) -> bool {
+    if !trimmed.contains('-') || !trimmed.contains('|') {
+        return false;
+    }
+    split_table_row(trimmed).iter().all(|cell| {
+        let cell = cell.trim();
+        !cell.is_empty() && cell.chars().all(|c| c == '-' || c == ':') && cell.contains('-')
+    })
+}
+
+fn split_table_row(trimmed: &str) -> Vec<String> {
+    let inner = trimmed.strip_prefix('|').unwrap_or(trimmed);
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+    inner.split('|').map(|cell| cell.trim().to_string()).collect()
+}
+
+fn parse_alignments(separator: &str, columns: usize) -> Vec<ColumnAlignment> {
+    let mut alignments: Vec<ColumnAlignment> = split_table_row(separator)
+        .iter()
+        .map(|cell| match (cell.starts_with(':'), cell.ends_with(':')) {
+            (true, true) => ColumnAlignment::Center,
+            (true, false) => ColumnAlignment::Left,
+            (false, true) => ColumnAlignment::Right,
+            (false, false) => ColumnAlignment::None,
+        })
+        .collect();
+    alignments.resize(columns, ColumnAlignment::None);
+    alignments
+}
+
+/// Pad or truncate a row to the header width
+fn normalize_row(mut row: Vec<String>, columns: usize) -> Vec<String> {
+    row.resize(columns, String::new());
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text() {
+        let segments = parse_segments("Hello **world**");
+        assert_eq!(
+            segments,
+            vec![ResponseSegment::Text { content: "Hello **world**".to_string() }]
+        );
+    }
+
+    #[test]
+    fn test_code_block() {
+        let segments = parse_segments("Example:\n```rust\nfn main() {}\n```\nDone");
+        assert_eq!(segments.len(), 3);
+        assert_eq!(
+            segments[1],
+            ResponseSegment::Code {
+                language: Some("rust".to_string()),
+                content: "fn main() {}".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_dollar_signs_inside_code_are_not_math() {
+        let formatted = format_response("```bash\necho $HOME $PATH\n```");
+        assert!(formatted.has_code);
+        assert!(!formatted.has_math);
+    }
+
+    #[test]
+    fn test_inline_math() {
+        let segments = split_inline_math("The area is $\\pi r^2$ units");
+        assert_eq!(segments.len(), 3);
+        assert_eq!(
+            segments[1],
+            ResponseSegment::Math {
+                latex: "\\pi r^2".to_string(),
+                display: false,
+                valid: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_currency_is_not_math() {
+        let segments = split_inline_math("It costs $5 and $10 total");
+        assert_eq!(segments.len(), 1);
+        assert!(matches!(segments[0], ResponseSegment::Text { .. }));
+    }
+
+    #[test]
+    fn test_dollar_signs_inside_inline_code_are_not_math() {
+        let segments = split_inline_math("Run `echo $HOME` then `$PATH`");
+        assert_eq!(
+            segments,
+            vec![ResponseSegment::Text { content: "Run `echo $HOME` then `$PATH`".to_string() }]
+        );
+
+        let segments = split_inline_math("Set $PATH and `$HOME`");
+        assert_eq!(segments.len(), 1);
+
+        let segments = split_inline_math("Both $x$ and ``a `$y$` b``");
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[1], math_segment("x", false));
+        assert_eq!(segments[2], ResponseSegment::Text { content: " and ``a `$y$` b``".to_string() });
+    }
+
+    #[test]
+    fn test_text_after_display_math_is_kept() {
+        let segments = parse_segments("$$\nE = mc^2\n$$ where m is the mass");
+        assert_eq!(
+            segments,
+            vec![
+                math_segment("E = mc^2", true),
+                ResponseSegment::Text { content: "where m is the mass".to_string() },
+            ]
+        );
+
+        let segments = parse_segments("$$a + b$$ is the sum");
+        assert_eq!(segments[1], ResponseSegment::Text { content: "is the sum".to_string() });
+    }
+
+    #[test]
+    fn test_display_math_multiline() {
+        let formatted = format_response("Formula:\n$$\n\\frac{a}{b}\n$$\nAfter");
+        assert!(formatted.has_math);
+        assert!(formatted.segments.contains(&ResponseSegment::Math {
+            latex: "\\frac{a}{b}".to_string(),
+            display: true,
+            valid: true,
+        }));
+    }
+
+    #[test]
+    fn test_validate_latex() {
+        assert!(validate_latex("\\frac{1}{2}"));
+        assert!(!validate_latex("\\frac{1}{2"));
+        assert!(validate_latex("\\begin{matrix} a & b \\end{matrix}"));
+        assert!(!validate_latex("\\begin{matrix} a \\end{cases}"));
+        assert!(!validate_latex("   "));
+    }
+
+    #[test]
+    fn test_table_parsing_and_normalization() {
+        let md = "| Name | Score |\n|:-----|------:|\n| Kim | 90 |\n| Lee |\n";
+        let segments = parse_segments(md);
+        assert_eq!(segments.len(), 1);
+        match &segments[0] {
+            ResponseSegment::Table { headers, alignments, rows } => {
+                assert_eq!(headers, &vec!["Name".to_string(), "Score".to_string()]);
+                assert_eq!(alignments, &vec![ColumnAlignment::Left, ColumnAlignment::Right]);
+                assert_eq!(rows.len(), 2);
+                assert_eq!(rows[1], vec!["Lee".to_string(), String::new()]);
+            }
+            other => panic!("Expected table, got {:?}", other),
+        }
+    }
+}
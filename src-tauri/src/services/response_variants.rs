@@ -0,0 +1,318 @@
+//! Response Variants (v3.9.0)
+//!
+//! Regenerated replies kept next to the original.
+//!
+//! Features:
+//! - Every regeneration of an assistant message is stored with the overrides
+//!   it was steered by (temperature, persona, retrieval, model)
+//! - The original reply is archived as the first variant on the first regeneration
+//! - The selected variant is the message's content; any variant can be re-selected
+//! - Per-variant satisfaction so feedback can compare settings
+
+#![allow(dead_code)]  // Phase 5: Steerable regeneration
+
+use crate::database::Database;
+use crate::services::ollama::GenerationOverrides;
+use anyhow::{anyhow, Result};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponseVariant {
+    pub id: String,
+    /// Assistant message the variant is a reply for
+    pub message_id: String,
+    pub conversation_id: String,
+    pub content: String,
+    /// Empty for the original reply
+    pub overrides: GenerationOverrides,
+    /// None for the original reply (model not recorded)
+    pub model: Option<String>,
+    pub original: bool,
+    /// Currently shown as the message's content
+    pub selected: bool,
+    /// 0.0-1.0 user rating
+    pub satisfaction: Option<f32>,
+    pub created_at: i64, // Unix millis
+}
+
+/// The user turn an assistant message answered
+#[derive(Debug, Clone)]
+pub struct RegenerationSource {
+    pub conversation_id: String,
+    pub user_message: String,
+}
+
+const VARIANT_COLUMNS: &str =
+    "id, message_id, conversation_id, content, overrides, model, is_original, selected, satisfaction, created_at";
+
+fn init_tables(conn: &rusqlite::Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS response_variants (
+            id TEXT PRIMARY KEY,
+            message_id TEXT NOT NULL,
+            conversation_id TEXT NOT NULL,
+            content TEXT NOT NULL,
+            overrides TEXT NOT NULL DEFAULT '{}',
+            model TEXT,
+            is_original INTEGER NOT NULL DEFAULT 0,
+            selected INTEGER NOT NULL DEFAULT 0,
+            satisfaction REAL,
+            created_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_response_variants_message ON response_variants(message_id, created_at)",
+        [],
+    )?;
+    Ok(())
+}
+
+fn row_to_variant(row: &rusqlite::Row) -> rusqlite::Result<ResponseVariant> {
+    let overrides: String = row.get(4)?;
+    Ok(ResponseVariant {
+        id: row.get(0)?,
+        message_id: row.get(1)?,
+        conversation_id: row.get(2)?,
+        content: row.get(3)?,
+        overrides: serde_json::from_str(&overrides).unwrap_or_default(),
+        model: row.get(5)?,
+        original: row.get::<_, i64>(6)? != 0,
+        selected: row.get::<_, i64>(7)? != 0,
+        satisfaction: row.get(8)?,
+        created_at: row.get(9)?,
+    })
+}
+
+pub struct ResponseVariantService {
+    db: Arc<Mutex<Database>>,
+}
+
+impl ResponseVariantService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        {
+            let db_guard = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            init_tables(db_guard.conn())?;
+        }
+
+        log::info!("✓ Response Variant Service initialized");
+        Ok(Self { db })
+    }
+
+    /// Conversation and user message an assistant message replied to
+    pub fn source(&self, message_id: &str) -> Result<RegenerationSource> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let (conversation_id, role, timestamp): (String, String, i64) = db
+            .conn()
+            .query_row(
+                "SELECT conversation_id, role, timestamp FROM messages WHERE id = ?1 AND is_stale = 0",
+                [message_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?
+            .ok_or_else(|| anyhow!("Message not found: {}", message_id))?;
+        if role != "assistant" {
+            return Err(anyhow!("Only assistant replies can be regenerated"));
+        }
+
+        let user_message: String = db
+            .conn()
+            .query_row(
+                "SELECT content FROM messages
+                 WHERE conversation_id = ?1 AND role = 'user' AND is_stale = 0 AND timestamp <= ?2
+                 ORDER BY timestamp DESC LIMIT 1",
+                params![conversation_id, timestamp],
+                |row| row.get(0),
+            )
+            .optional()?
+            .ok_or_else(|| anyhow!("No user message precedes {}", message_id))?;
+
+        Ok(RegenerationSource { conversation_id, user_message })
+    }
+
+    /// Store a regenerated reply and show it in place of the current one
+    ///
+    /// On the first regeneration of a message its current content is archived
+    /// as the original variant.
+    pub fn record(
+        &self,
+        message_id: &str,
+        content: &str,
+        overrides: &GenerationOverrides,
+        model: &str,
+    ) -> Result<ResponseVariant> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let tx = db.conn().unchecked_transaction()?;
+
+        let (conversation_id, current, timestamp): (String, String, i64) = tx
+            .query_row(
+                "SELECT conversation_id, content, timestamp FROM messages WHERE id = ?1",
+                [message_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?
+            .ok_or_else(|| anyhow!("Message not found: {}", message_id))?;
+
+        let now = chrono::Utc::now().timestamp_millis();
+        tx.execute(
+            "INSERT INTO response_variants (id, message_id, conversation_id, content, model, is_original, selected, created_at)
+             SELECT ?1, ?2, ?3, ?4, NULL, 1, 0, ?5
+             WHERE NOT EXISTS (SELECT 1 FROM response_variants WHERE message_id = ?2)",
+            params![uuid::Uuid::new_v4().to_string(), message_id, conversation_id, current, timestamp],
+        )?;
+        tx.execute("UPDATE response_variants SET selected = 0 WHERE message_id = ?1", [message_id])?;
+
+        let id = uuid::Uuid::new_v4().to_string();
+        tx.execute(
+            "INSERT INTO response_variants (id, message_id, conversation_id, content, overrides, model, is_original, selected, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0, 1, ?7)",
+            params![id, message_id, conversation_id, content, serde_json::to_string(overrides)?, model, now],
+        )?;
+        tx.execute("UPDATE messages SET content = ?1 WHERE id = ?2", params![content, message_id])?;
+        tx.commit()?;
+
+        log::info!("Recorded response variant {} for message {}", id, message_id);
+        Ok(ResponseVariant {
+            id,
+            message_id: message_id.to_string(),
+            conversation_id,
+            content: content.to_string(),
+            overrides: overrides.clone(),
+            model: Some(model.to_string()),
+            original: false,
+            selected: true,
+            satisfaction: None,
+            created_at: now,
+        })
+    }
+
+    /// Variants of a message, original first
+    pub fn list(&self, message_id: &str) -> Result<Vec<ResponseVariant>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(&format!(
+            "SELECT {} FROM response_variants WHERE message_id = ?1 ORDER BY is_original DESC, created_at ASC",
+            VARIANT_COLUMNS
+        ))?;
+        let variants = stmt
+            .query_map([message_id], row_to_variant)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(variants)
+    }
+
+    pub fn get(&self, id: &str) -> Result<ResponseVariant> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn()
+            .query_row(
+                &format!("SELECT {} FROM response_variants WHERE id = ?1", VARIANT_COLUMNS),
+                [id],
+                row_to_variant,
+            )
+            .optional()?
+            .ok_or_else(|| anyhow!("Variant not found: {}", id))
+    }
+
+    /// Show a variant as the message's content
+    pub fn select(&self, id: &str) -> Result<ResponseVariant> {
+        let variant = self.get(id)?;
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            let tx = db.conn().unchecked_transaction()?;
+            tx.execute(
+                "UPDATE response_variants SET selected = (id = ?1) WHERE message_id = ?2",
+                params![id, variant.message_id],
+            )?;
+            tx.execute(
+                "UPDATE messages SET content = ?1 WHERE id = ?2",
+                params![variant.content, variant.message_id],
+            )?;
+            tx.commit()?;
+        }
+        Ok(ResponseVariant { selected: true, ..variant })
+    }
+
+    /// Rate a variant (0.0 = thumbs down, 1.0 = thumbs up)
+    pub fn rate(&self, id: &str, satisfaction: f32) -> Result<()> {
+        if !(0.0..=1.0).contains(&satisfaction) {
+            return Err(anyhow!("Satisfaction must be between 0.0 and 1.0"));
+        }
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let updated = db.conn().execute(
+            "UPDATE response_variants SET satisfaction = ?1 WHERE id = ?2",
+            params![satisfaction, id],
+        )?;
+        if updated == 0 {
+            return Err(anyhow!("Variant not found: {}", id));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service_with_reply() -> (ResponseVariantService, Arc<Mutex<Database>>) {
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        {
+            let db_guard = db.lock().unwrap();
+            let conn = db_guard.conn();
+            conn.execute(
+                "INSERT INTO conversations (id, title, mode, created_at, updated_at, message_count)
+                 VALUES ('c1', 'Test', 'user-led', 0, 0, 2)",
+                [],
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO messages (id, conversation_id, role, content, timestamp)
+                 VALUES ('u1', 'c1', 'user', 'Tell me a joke', 1), ('a1', 'c1', 'assistant', 'Original joke', 2)",
+                [],
+            )
+            .unwrap();
+        }
+        (ResponseVariantService::new(Arc::clone(&db)).unwrap(), db)
+    }
+
+    fn message_content(db: &Arc<Mutex<Database>>) -> String {
+        db.lock()
+            .unwrap()
+            .conn()
+            .query_row("SELECT content FROM messages WHERE id = 'a1'", [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_record_keeps_original() {
+        let (service, db) = service_with_reply();
+        assert_eq!(service.source("a1").unwrap().user_message, "Tell me a joke");
+        assert!(service.source("u1").is_err());
+
+        let overrides = GenerationOverrides { temperature: Some(1.2), retrieval: Some(false), ..Default::default() };
+        service.record("a1", "Wilder joke", &overrides, "qwen2.5:7b").unwrap();
+        service.record("a1", "Another joke", &GenerationOverrides::default(), "llama3").unwrap();
+        assert_eq!(message_content(&db), "Another joke");
+
+        let variants = service.list("a1").unwrap();
+        assert_eq!(variants.len(), 3);
+        assert!(variants[0].original && variants[0].content == "Original joke" && variants[0].overrides.is_empty());
+        assert_eq!(variants[1].overrides.temperature, Some(1.2));
+        assert_eq!(variants.iter().filter(|v| v.selected).count(), 1);
+        assert!(variants[2].selected);
+    }
+
+    #[test]
+    fn test_select_and_rate() {
+        let (service, db) = service_with_reply();
+        service.record("a1", "Wilder joke", &GenerationOverrides::default(), "qwen2.5:7b").unwrap();
+        let original = service.list("a1").unwrap().remove(0);
+
+        service.select(&original.id).unwrap();
+        assert_eq!(message_content(&db), "Original joke");
+        assert!(service.get(&original.id).unwrap().selected);
+
+        service.rate(&original.id, 1.0).unwrap();
+        assert_eq!(service.get(&original.id).unwrap().satisfaction, Some(1.0));
+        assert!(service.rate(&original.id, 1.5).is_err());
+    }
+}
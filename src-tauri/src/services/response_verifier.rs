@@ -0,0 +1,474 @@
+//! Response Verifier Service (v3.9.0)
+//!
+//! Heuristic self-check of every generated reply before it is returned.
+//!
+//! Features:
+//! - Truncation check (unclosed code fences, replies cut off mid-sentence)
+//! - Language check against the conversation language or an explicit request
+//! - Ignored-instruction checks (one sentence, N bullet points, brevity, JSON)
+//! - Empty and repetitive replies
+//! - One corrective regeneration, kept only when it has fewer issues
+//! - Retry statistics per issue kind
+
+#![allow(dead_code)]  // Phase 5: Response self-check
+
+use crate::database::Database;
+use crate::services::conversation_language::{detect_language, ConversationLanguage};
+use crate::services::ollama;
+use anyhow::{anyhow, Result};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// `user_preferences` key holding the verifier config
+const CONFIG_KEY: &str = "response_verifier_config";
+
+/// Replies shorter than this are not checked for truncation
+const MIN_TRUNCATION_CHARS: usize = 80;
+
+/// Word limit of replies to "briefly" / "짧게" requests
+const BRIEF_MAX_WORDS: usize = 120;
+
+/// Hangul endings that close a sentence without punctuation
+const KOREAN_SENTENCE_ENDINGS: &[char] = &['다', '요', '죠', '까', '네', '음', '함', '임'];
+
+/// Verifier configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifierConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Regenerate once when issues are found
+    #[serde(default = "default_true")]
+    pub auto_retry: bool,
+}
+
+fn default_true() -> bool { true }
+
+impl Default for VerifierConfig {
+    fn default() -> Self {
+        Self { enabled: true, auto_retry: true }
+    }
+}
+
+/// Kind of problem found in a reply
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    Empty,
+    Truncated,
+    LanguageMismatch,
+    IgnoredInstruction,
+    Repetition,
+}
+
+impl IssueKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Empty => "empty",
+            Self::Truncated => "truncated",
+            Self::LanguageMismatch => "language_mismatch",
+            Self::IgnoredInstruction => "ignored_instruction",
+            Self::Repetition => "repetition",
+        }
+    }
+}
+
+/// One problem with corrective guidance for the retry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationIssue {
+    pub kind: IssueKind,
+    pub guidance: String,
+}
+
+/// Reply after verification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifiedResponse {
+    pub response: String,
+    /// Issues of the original reply
+    pub issues: Vec<VerificationIssue>,
+    pub retried: bool,
+    /// The retry was kept
+    pub improved: bool,
+}
+
+/// Count of one issue kind
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueCount {
+    pub kind: IssueKind,
+    pub count: usize,
+}
+
+/// Verifier statistics over a period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifierStats {
+    pub days: u32,
+    pub checked: usize,
+    pub flagged: usize,
+    pub retried: usize,
+    pub improved: usize,
+    /// Share of checked replies that were regenerated
+    pub retry_rate: f32,
+    pub issues: Vec<IssueCount>,
+}
+
+fn explicit_language(message: &str) -> Option<ConversationLanguage> {
+    let lower = message.to_lowercase();
+    if lower.contains("in english") || message.contains("영어로") {
+        Some(ConversationLanguage::English)
+    } else if lower.contains("in korean") || message.contains("한국어로") || message.contains("한글로") {
+        Some(ConversationLanguage::Korean)
+    } else {
+        None
+    }
+}
+
+fn requested_count(lower: &str) -> Option<(usize, bool)> {
+    const UNITS: &[(&str, bool)] = &[
+        ("bullet", true), ("point", true), ("item", false), ("tip", false),
+        ("way", false), ("example", false), ("reason", false), ("step", false),
+        ("가지", false), ("개", false),
+    ];
+    let words: Vec<&str> = lower.split_whitespace().collect();
+    for (i, word) in words.iter().enumerate() {
+        let digits: String = word.chars().take_while(|c| c.is_ascii_digit()).collect();
+        let Ok(count) = digits.parse::<usize>() else { continue };
+        if count == 0 || count > 20 {
+            continue;
+        }
+        let rest = &word[digits.len()..];
+        let next = words.get(i + 1).copied().unwrap_or("");
+        for (unit, strict) in UNITS {
+            if rest.starts_with(unit) || next.starts_with(unit) {
+                return Some((count, *strict));
+            }
+        }
+    }
+    None
+}
+
+fn list_items(response: &str) -> usize {
+    response
+        .lines()
+        .map(str::trim_start)
+        .filter(|line| {
+            line.starts_with("- ")
+                || line.starts_with("* ")
+                || line.starts_with("• ")
+                || line
+                    .split_once(['.', ')'])
+                    .is_some_and(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        })
+        .count()
+}
+
+fn sentence_count(response: &str) -> usize {
+    response
+        .split(['.', '!', '?', '。', '\n'])
+        .filter(|s| s.chars().filter(|c| c.is_alphanumeric()).count() >= 3)
+        .count()
+}
+
+fn looks_truncated(response: &str) -> bool {
+    if response.matches("```").count() % 2 == 1 {
+        return true;
+    }
+    let trimmed = response.trim_end();
+    if trimmed.chars().count() < MIN_TRUNCATION_CHARS {
+        return false;
+    }
+    match trimmed.chars().last() {
+        Some(c) if KOREAN_SENTENCE_ENDINGS.contains(&c) => false,
+        Some(c) => c.is_alphanumeric() || c == ',' || c == '-',
+        None => false,
+    }
+}
+
+fn is_repetitive(response: &str) -> bool {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    response
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.chars().count() > 10)
+        .any(|line| {
+            let count = seen.entry(line).or_insert(0);
+            *count += 1;
+            *count >= 3
+        })
+}
+
+/// Heuristic checks of a reply against the user's message
+pub fn check_response(
+    user_message: &str,
+    response: &str,
+    expected_language: Option<ConversationLanguage>,
+) -> Vec<VerificationIssue> {
+    let mut issues = Vec::new();
+    let issue = |kind: IssueKind, guidance: &str| VerificationIssue { kind, guidance: guidance.to_string() };
+
+    if response.trim().is_empty() {
+        issues.push(issue(IssueKind::Empty, "The previous answer was empty. Answer the message."));
+        return issues;
+    }
+
+    if looks_truncated(response) {
+        issues.push(issue(
+            IssueKind::Truncated,
+            "The previous answer was cut off. Give a complete answer and close every code block.",
+        ));
+    }
+
+    if let Some(language) = explicit_language(user_message).or(expected_language) {
+        if detect_language(response).is_some_and(|detected| detected != language) {
+            let guidance = match language {
+                ConversationLanguage::Korean => "The previous answer was in the wrong language. 반드시 한국어로 답변하세요.",
+                ConversationLanguage::English => "The previous answer was in the wrong language. Answer in English only.",
+            };
+            issues.push(issue(IssueKind::LanguageMismatch, guidance));
+        }
+    }
+
+    let lower = user_message.to_lowercase();
+    if (lower.contains("one sentence") || user_message.contains("한 문장")) && sentence_count(response) > 2 {
+        issues.push(issue(IssueKind::IgnoredInstruction, "The user asked for one sentence. Answer in a single sentence."));
+    }
+    if let Some((count, strict)) = requested_count(&lower) {
+        let items = list_items(response);
+        if items != count && (strict || items > 0) {
+            issues.push(VerificationIssue {
+                kind: IssueKind::IgnoredInstruction,
+                guidance: format!("The user asked for exactly {} items. Give exactly {} list items.", count, count),
+            });
+        }
+    }
+    let wants_brief = ["briefly", "brief", "short answer", "concise", "짧게", "간단히", "간단하게"]
+        .iter()
+        .any(|cue| lower.contains(cue));
+    if wants_brief && response.split_whitespace().count() > BRIEF_MAX_WORDS {
+        issues.push(issue(IssueKind::IgnoredInstruction, "The user asked for a brief answer. Keep it short."));
+    }
+    if lower.contains("json") && !response.contains('{') && !response.contains('[') {
+        issues.push(issue(IssueKind::IgnoredInstruction, "The user asked for JSON. Answer with valid JSON."));
+    }
+
+    if is_repetitive(response) {
+        issues.push(issue(IssueKind::Repetition, "The previous answer repeated itself. Do not repeat lines."));
+    }
+
+    issues
+}
+
+/// Response verifier with one corrective retry
+pub struct ResponseVerifierService {
+    db: Arc<Mutex<Database>>,
+    config: RwLock<VerifierConfig>,
+}
+
+impl ResponseVerifierService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        let service = Self { db, config: RwLock::new(VerifierConfig::default()) };
+        service.init_database()?;
+        log::info!("✓ Response Verifier initialized");
+        Ok(service)
+    }
+
+    fn init_database(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let conn = db.conn();
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS response_verification_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                conversation_id TEXT,
+                issues TEXT NOT NULL,
+                retried INTEGER NOT NULL DEFAULT 0,
+                improved INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_response_verification_created ON response_verification_log(created_at)",
+            [],
+        )?;
+
+        let stored: Option<String> = conn
+            .query_row("SELECT value FROM user_preferences WHERE key = ?1", [CONFIG_KEY], |row| row.get(0))
+            .optional()?;
+        if let Some(config) = stored.and_then(|json| serde_json::from_str(&json).ok()) {
+            *self.config.write().map_err(|e| anyhow!("Lock error: {}", e))? = config;
+        }
+
+        Ok(())
+    }
+
+    pub fn config(&self) -> VerifierConfig {
+        self.config.read().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    pub fn set_config(&self, config: VerifierConfig) -> Result<VerifierConfig> {
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            db.conn().execute(
+                "INSERT INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+                params![CONFIG_KEY, serde_json::to_string(&config)?, chrono::Utc::now().timestamp_millis()],
+            )?;
+        }
+        *self.config.write().map_err(|e| anyhow!("Lock error: {}", e))? = config.clone();
+        Ok(config)
+    }
+
+    /// Check a reply and regenerate it once when issues were found.
+    /// The original reply is kept unless the retry has fewer issues.
+    pub async fn verify(
+        &self,
+        conversation_id: Option<&str>,
+        user_message: &str,
+        response: String,
+        expected_language: Option<ConversationLanguage>,
+    ) -> VerifiedResponse {
+        let config = self.config();
+        if !config.enabled {
+            return VerifiedResponse { response, issues: Vec::new(), retried: false, improved: false };
+        }
+
+        let issues = check_response(user_message, &response, expected_language);
+        let mut verified = VerifiedResponse { response, issues, retried: false, improved: false };
+
+        if !verified.issues.is_empty() && config.auto_retry {
+            log::warn!(
+                "Reply failed self-check ({}), regenerating",
+                verified.issues.iter().map(|i| i.kind.as_str()).collect::<Vec<_>>().join(", ")
+            );
+            verified.retried = true;
+
+            let prompt = Self::corrective_prompt(user_message, &verified.response, &verified.issues);
+            match ollama::generate_response(&prompt).await {
+                Ok(retry) => {
+                    let remaining = check_response(user_message, &retry, expected_language);
+                    if remaining.len() < verified.issues.len() {
+                        log::info!("✓ Regenerated reply passed more checks ({} issues left)", remaining.len());
+                        verified.response = retry;
+                        verified.improved = true;
+                    } else {
+                        log::warn!("Regenerated reply was not better, keeping the original");
+                    }
+                }
+                Err(e) => log::warn!("Corrective regeneration failed: {}", e),
+            }
+        }
+
+        if let Err(e) = self.record(conversation_id, &verified) {
+            log::warn!("Failed to record response verification: {}", e);
+        }
+        verified
+    }
+
+    fn corrective_prompt(user_message: &str, previous: &str, issues: &[VerificationIssue]) -> String {
+        let guidance = issues
+            .iter()
+            .map(|issue| format!("- {}", issue.guidance))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "Your previous answer had these problems:\n{}\n\n\
+             Answer the user's message again, fixing them.\n\n\
+             User message:\n{}\n\nPrevious answer:\n{}\n\nAnswer:",
+            guidance, user_message, previous
+        )
+    }
+
+    fn record(&self, conversation_id: Option<&str>, verified: &VerifiedResponse) -> Result<()> {
+        let kinds: Vec<IssueKind> = verified.issues.iter().map(|i| i.kind).collect();
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        db.conn().execute(
+            "INSERT INTO response_verification_log (conversation_id, issues, retried, improved, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                conversation_id,
+                serde_json::to_string(&kinds)?,
+                verified.retried,
+                verified.improved,
+                chrono::Utc::now().timestamp_millis(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// How often replies were flagged and retried over the last `days` days
+    pub fn stats(&self, days: u32) -> Result<VerifierStats> {
+        let days = days.max(1);
+        let since = chrono::Utc::now().timestamp_millis() - days as i64 * 24 * 60 * 60 * 1000;
+
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT issues, retried, improved FROM response_verification_log WHERE created_at >= ?1",
+        )?;
+        let rows = stmt
+            .query_map([since], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?, row.get::<_, bool>(2)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut counts: HashMap<IssueKind, usize> = HashMap::new();
+        let mut flagged = 0;
+        for (issues, _, _) in &rows {
+            let kinds: Vec<IssueKind> = serde_json::from_str(issues).unwrap_or_default();
+            if !kinds.is_empty() {
+                flagged += 1;
+            }
+            for kind in kinds {
+                *counts.entry(kind).or_insert(0) += 1;
+            }
+        }
+        let mut issues: Vec<IssueCount> = counts.into_iter().map(|(kind, count)| IssueCount { kind, count }).collect();
+        issues.sort_by_key(|issue| std::cmp::Reverse(issue.count));
+
+        let checked = rows.len();
+        let retried = rows.iter().filter(|(_, retried, _)| *retried).count();
+        Ok(VerifierStats {
+            days,
+            checked,
+            flagged,
+            retried,
+            improved: rows.iter().filter(|(_, _, improved)| *improved).count(),
+            retry_rate: if checked == 0 { 0.0 } else { retried as f32 / checked as f32 },
+            issues,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(message: &str, response: &str, language: Option<ConversationLanguage>) -> Vec<IssueKind> {
+        check_response(message, response, language).into_iter().map(|i| i.kind).collect()
+    }
+
+    #[test]
+    fn test_clean_response_passes() {
+        assert!(kinds("What is Rust?", "Rust is a systems programming language focused on safety and speed.", None).is_empty());
+        assert!(kinds("Rust가 뭐야?", "Rust는 안전성과 속도에 초점을 둔 시스템 프로그래밍 언어입니다", Some(ConversationLanguage::Korean)).is_empty());
+    }
+
+    #[test]
+    fn test_detects_issues() {
+        assert_eq!(kinds("hi", "  ", None), vec![IssueKind::Empty]);
+
+        let cut = "Here is how you sort a vector in Rust. First you create the vector, then you call the sort method and";
+        assert_eq!(kinds("How do I sort?", cut, None), vec![IssueKind::Truncated]);
+        assert_eq!(kinds("Show code", "```rust\nfn main() {}", None), vec![IssueKind::Truncated]);
+
+        assert_eq!(kinds("오늘 날씨 어때?", "It is sunny today.", Some(ConversationLanguage::Korean)), vec![IssueKind::LanguageMismatch]);
+        assert_eq!(kinds("Answer in English: 날씨 어때?", "오늘은 맑습니다.", Some(ConversationLanguage::Korean)), vec![IssueKind::LanguageMismatch]);
+
+        assert_eq!(kinds("Give me 3 bullet points about Rust", "- Safe\n- Fast", None), vec![IssueKind::IgnoredInstruction]);
+        assert!(kinds("Give me 3 bullet points about Rust", "- Safe\n- Fast\n- Fun", None).is_empty());
+        assert_eq!(kinds("Return the result as JSON", "The result is ok.", None), vec![IssueKind::IgnoredInstruction]);
+
+        let repeated = "I can help with that request.\n".repeat(3);
+        assert_eq!(kinds("help", &repeated, None), vec![IssueKind::Repetition]);
+    }
+}
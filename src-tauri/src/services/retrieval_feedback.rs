@@ -0,0 +1,590 @@
+//! Retrieval Feedback (v3.9.0)
+//!
+//! Learning-to-rank lite: which retrieved memories actually helped, and
+//! retrieval settings retrained from it.
+//!
+//! Features:
+//! - Every chat turn logs its retrieved memories: rank, similarity, whether
+//!   they reached the prompt and whether the response used them
+//! - Conversation ratings label those memories (useful = used in a
+//!   good-rated response)
+//! - Retraining picks the hybrid-search fusion weights and the RAFT relevance
+//!   threshold that best separate useful from useless memories
+//! - Weekly retrieval quality trend (precision, MRR, satisfaction) and
+//!   history of retraining runs
+
+use super::fusion::FusionWeights;
+use super::raft;
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Ratings at or above this count as a good response (thumbs up)
+pub const GOOD_SATISFACTION: f32 = 0.6;
+
+/// Ratings at or below this count as a bad response; in between is ignored
+pub const BAD_SATISFACTION: f32 = 0.4;
+
+/// Labeled memories needed before the relevance threshold is retuned
+pub const MIN_THRESHOLD_SAMPLES: usize = 30;
+
+/// Rated turns with a useful memory needed before fusion weights are retuned
+pub const MIN_WEIGHT_TURNS: usize = 10;
+
+/// Memory terms a response must repeat for the memory to count as used
+const MIN_USED_TERMS: usize = 3;
+
+/// Share of a memory's terms a response must repeat for it to count as used
+const USED_TERM_SHARE: f32 = 0.15;
+
+/// Relevance thresholds considered by retraining
+const THRESHOLD_RANGE: (f32, f32) = (0.3, 0.8);
+
+/// Oldest usage rows are dropped beyond this many
+const MAX_USAGE_ROWS: usize = 20_000;
+
+const WEEK_MS: i64 = 7 * 24 * 60 * 60 * 1000;
+
+/// One retrieved memory of a chat turn
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetrievedMemory {
+    pub episode_id: String,
+    /// 1-based rank in the retrieval results
+    pub rank: usize,
+    pub similarity: f32,
+    /// Passed the relevance filter and the prompt budget
+    pub sent: bool,
+    /// The response repeats enough of it to count as cited
+    pub used: bool,
+}
+
+/// A rated chat turn with its retrieved memories, best first
+#[derive(Debug, Clone)]
+pub struct RatedTurn {
+    pub turn_id: String,
+    pub query: String,
+    pub satisfaction: f32,
+    pub created_at: i64,
+    pub memories: Vec<RetrievedMemory>,
+}
+
+impl RatedTurn {
+    pub fn is_good(&self) -> bool {
+        self.satisfaction >= GOOD_SATISFACTION
+    }
+
+    /// Used in a good-rated response
+    pub fn is_useful(&self, memory: &RetrievedMemory) -> bool {
+        memory.sent && memory.used && self.is_good()
+    }
+
+    /// 1 / rank of the first useful sent memory (0 without one)
+    fn reciprocal_rank(&self) -> f32 {
+        self.memories
+            .iter()
+            .filter(|m| m.sent)
+            .position(|m| self.is_useful(m))
+            .map(|i| 1.0 / (i + 1) as f32)
+            .unwrap_or(0.0)
+    }
+}
+
+/// Retrieval quality over one week
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityPoint {
+    /// Start of the week (Unix millis)
+    pub week_start: i64,
+    /// Turns that retrieved memories
+    pub turns: usize,
+    pub rated_turns: usize,
+    /// Share of sent memories that were useful, over rated turns
+    pub precision: f32,
+    /// Mean reciprocal rank of the first useful memory, over rated turns
+    pub mrr: f32,
+    pub avg_satisfaction: f32,
+}
+
+/// One retraining pass; `None` settings were left unchanged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TuningRun {
+    pub id: String,
+    pub created_at: i64,
+    pub rated_turns: usize,
+    pub labeled_memories: usize,
+    pub relevance_threshold: Option<f32>,
+    pub weights: Option<FusionWeights>,
+    pub precision: f32,
+    pub mrr: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetrievalQualityReport {
+    pub trend: Vec<QualityPoint>,
+    pub runs: Vec<TuningRun>,
+}
+
+pub fn init_tables(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS retrieval_usage (
+            turn_id TEXT NOT NULL,
+            conversation_id TEXT NOT NULL,
+            query TEXT NOT NULL,
+            episode_id TEXT NOT NULL,
+            rank INTEGER NOT NULL,
+            similarity REAL NOT NULL,
+            sent INTEGER NOT NULL,
+            used INTEGER NOT NULL,
+            satisfaction REAL,
+            created_at INTEGER NOT NULL,
+            PRIMARY KEY (turn_id, episode_id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_retrieval_usage_conversation
+            ON retrieval_usage(conversation_id, satisfaction);
+        CREATE TABLE IF NOT EXISTS retrieval_tuning_runs (
+            id TEXT PRIMARY KEY,
+            created_at INTEGER NOT NULL,
+            rated_turns INTEGER NOT NULL,
+            labeled_memories INTEGER NOT NULL,
+            relevance_threshold REAL,
+            bm25_weight REAL,
+            semantic_weight REAL,
+            precision REAL NOT NULL,
+            mrr REAL NOT NULL
+        );",
+    )?;
+    Ok(())
+}
+
+/// Whether `response` repeats enough of a memory to count as citing it
+pub fn was_used(response: &str, user_message: &str, ai_response: &str) -> bool {
+    let memory: HashSet<String> = raft::terms(user_message).into_iter().chain(raft::terms(ai_response)).collect();
+    if memory.is_empty() {
+        return false;
+    }
+    let response: HashSet<String> = raft::terms(response).into_iter().collect();
+    let shared = memory.iter().filter(|term| response.contains(*term)).count();
+    shared >= MIN_USED_TERMS && shared as f32 / memory.len() as f32 >= USED_TERM_SHARE
+}
+
+/// Log the memories retrieved for one turn; returns the turn id
+pub fn record_turn(conn: &Connection, conversation_id: &str, query: &str, memories: &[RetrievedMemory]) -> Result<String> {
+    init_tables(conn)?;
+    let turn_id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().timestamp_millis();
+    let tx = conn.unchecked_transaction()?;
+    for memory in memories {
+        tx.execute(
+            "INSERT OR IGNORE INTO retrieval_usage
+                (turn_id, conversation_id, query, episode_id, rank, similarity, sent, used, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                turn_id,
+                conversation_id,
+                query,
+                memory.episode_id,
+                memory.rank as i64,
+                memory.similarity,
+                memory.sent,
+                memory.used,
+                now
+            ],
+        )?;
+    }
+    tx.execute(
+        "DELETE FROM retrieval_usage WHERE rowid IN (
+            SELECT rowid FROM retrieval_usage ORDER BY created_at DESC LIMIT -1 OFFSET ?1
+        )",
+        [MAX_USAGE_ROWS],
+    )?;
+    tx.commit()?;
+    Ok(turn_id)
+}
+
+/// Label the not-yet-rated turns of a conversation with its rating
+///
+/// Returns the number of memories labeled.
+pub fn apply_rating(conn: &Connection, conversation_id: &str, satisfaction: f32) -> Result<usize> {
+    init_tables(conn)?;
+    Ok(conn.execute(
+        "UPDATE retrieval_usage SET satisfaction = ?1 WHERE conversation_id = ?2 AND satisfaction IS NULL",
+        params![satisfaction.clamp(0.0, 1.0), conversation_id],
+    )?)
+}
+
+/// Turns rated clearly good or bad since `since` (Unix millis), oldest first
+pub fn rated_turns(conn: &Connection, since: Option<i64>) -> Result<Vec<RatedTurn>> {
+    init_tables(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT turn_id, query, satisfaction, created_at, episode_id, rank, similarity, sent, used
+         FROM retrieval_usage
+         WHERE satisfaction IS NOT NULL AND (satisfaction >= ?1 OR satisfaction <= ?2) AND created_at >= ?3
+         ORDER BY created_at, turn_id, rank",
+    )?;
+    let rows = stmt.query_map(params![GOOD_SATISFACTION, BAD_SATISFACTION, since.unwrap_or(0)], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, f32>(2)?,
+            row.get::<_, i64>(3)?,
+            RetrievedMemory {
+                episode_id: row.get(4)?,
+                rank: row.get::<_, i64>(5)? as usize,
+                similarity: row.get(6)?,
+                sent: row.get(7)?,
+                used: row.get(8)?,
+            },
+        ))
+    })?;
+
+    let mut turns: Vec<RatedTurn> = Vec::new();
+    for row in rows {
+        let (turn_id, query, satisfaction, created_at, memory) = row?;
+        match turns.last_mut() {
+            Some(turn) if turn.turn_id == turn_id => turn.memories.push(memory),
+            _ => turns.push(RatedTurn { turn_id, query, satisfaction, created_at, memories: vec![memory] }),
+        }
+    }
+    Ok(turns)
+}
+
+/// (precision, MRR) of sent memories over rated turns
+pub fn quality(turns: &[RatedTurn]) -> (f32, f32) {
+    let sent: Vec<bool> = turns
+        .iter()
+        .flat_map(|turn| turn.memories.iter().filter(|m| m.sent).map(move |m| turn.is_useful(m)))
+        .collect();
+    if sent.is_empty() {
+        return (0.0, 0.0);
+    }
+    let precision = sent.iter().filter(|useful| **useful).count() as f32 / sent.len() as f32;
+    let mrr = turns.iter().map(RatedTurn::reciprocal_rank).sum::<f32>() / turns.len() as f32;
+    (precision, mrr)
+}
+
+/// Relevance threshold with the best F1 at keeping useful memories out of useless ones
+///
+/// Only memories that reached the prompt are labeled, so the threshold is only
+/// raised past memories that didn't help; ties keep the value closest to `current`.
+pub fn tune_relevance_threshold(turns: &[RatedTurn], current: f32) -> Option<f32> {
+    let labeled: Vec<(f32, bool)> = turns
+        .iter()
+        .flat_map(|turn| turn.memories.iter().filter(|m| m.sent).map(move |m| (m.similarity, turn.is_useful(m))))
+        .collect();
+    let useful = labeled.iter().filter(|(_, useful)| *useful).count();
+    if labeled.len() < MIN_THRESHOLD_SAMPLES || useful == 0 || useful == labeled.len() {
+        return None;
+    }
+
+    let f1 = |threshold: f32| {
+        let kept_useful = labeled.iter().filter(|(sim, useful)| *useful && *sim >= threshold).count() as f32;
+        let kept = labeled.iter().filter(|(sim, _)| *sim >= threshold).count() as f32;
+        if kept_useful == 0.0 {
+            return 0.0;
+        }
+        let precision = kept_useful / kept;
+        let recall = kept_useful / useful as f32;
+        2.0 * precision * recall / (precision + recall)
+    };
+
+    let (min, max) = THRESHOLD_RANGE;
+    let mut best = (f1(current), current);
+    for step in 0..=((max - min) / 0.05).round() as usize {
+        let threshold = min + step as f32 * 0.05;
+        let score = f1(threshold);
+        if score > best.0 + 1e-6 || ((score - best.0).abs() <= 1e-6 && (threshold - current).abs() < (best.1 - current).abs()) {
+            best = (score, threshold);
+        }
+    }
+    ((best.1 - current).abs() > 1e-6).then_some(best.1)
+}
+
+/// BM25 / semantic weights whose RRF ordering ranks useful memories highest
+///
+/// Each rated turn's sent memories are re-ranked with `bm25_ranking(query)`
+/// (episode ids, best first) as the lexical list and the logged rank as the
+/// semantic list. Ties keep the weight closest to `current`.
+pub fn tune_weights(
+    turns: &[RatedTurn],
+    mut bm25_ranking: impl FnMut(&str) -> Vec<String>,
+    rrf_k: f32,
+    current: &FusionWeights,
+) -> Option<FusionWeights> {
+    let candidates: Vec<(&RatedTurn, HashMap<String, usize>)> = turns
+        .iter()
+        .filter(|turn| turn.memories.iter().filter(|m| m.sent).count() >= 2)
+        .filter(|turn| turn.memories.iter().any(|m| turn.is_useful(m)))
+        .map(|turn| {
+            let ranks = bm25_ranking(&turn.query)
+                .into_iter()
+                .enumerate()
+                .map(|(i, id)| (id, i + 1))
+                .collect();
+            (turn, ranks)
+        })
+        .collect();
+    if candidates.len() < MIN_WEIGHT_TURNS {
+        return None;
+    }
+
+    let mrr = |bm25_weight: f32| {
+        let total: f32 = candidates
+            .iter()
+            .map(|(turn, bm25_ranks)| {
+                let mut scored: Vec<(f32, bool)> = turn
+                    .memories
+                    .iter()
+                    .filter(|m| m.sent)
+                    .map(|m| {
+                        let lexical = bm25_ranks.get(&m.episode_id).map(|r| 1.0 / (rrf_k + *r as f32)).unwrap_or(0.0);
+                        let semantic = 1.0 / (rrf_k + m.rank as f32);
+                        (bm25_weight * lexical + (1.0 - bm25_weight) * semantic, turn.is_useful(m))
+                    })
+                    .collect();
+                scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+                scored.iter().position(|(_, useful)| *useful).map(|i| 1.0 / (i + 1) as f32).unwrap_or(0.0)
+            })
+            .sum();
+        total / candidates.len() as f32
+    };
+
+    let current_weight = current.bm25_weight / (current.bm25_weight + current.semantic_weight).max(f32::EPSILON);
+    let mut best = (mrr(current_weight), current_weight);
+    for step in 0..=10 {
+        let weight = step as f32 / 10.0;
+        let score = mrr(weight);
+        if score > best.0 + 1e-6 || ((score - best.0).abs() <= 1e-6 && (weight - current_weight).abs() < (best.1 - current_weight).abs()) {
+            best = (score, weight);
+        }
+    }
+    ((best.1 - current_weight).abs() > 1e-6).then_some(FusionWeights {
+        bm25_weight: best.1,
+        semantic_weight: 1.0 - best.1,
+    })
+}
+
+/// Persist a retraining pass
+pub fn record_run(
+    conn: &Connection,
+    turns: &[RatedTurn],
+    relevance_threshold: Option<f32>,
+    weights: Option<FusionWeights>,
+) -> Result<TuningRun> {
+    init_tables(conn)?;
+    let (precision, mrr) = quality(turns);
+    let run = TuningRun {
+        id: uuid::Uuid::new_v4().to_string(),
+        created_at: chrono::Utc::now().timestamp_millis(),
+        rated_turns: turns.len(),
+        labeled_memories: turns.iter().map(|t| t.memories.iter().filter(|m| m.sent).count()).sum(),
+        relevance_threshold,
+        weights,
+        precision,
+        mrr,
+    };
+    conn.execute(
+        "INSERT INTO retrieval_tuning_runs
+            (id, created_at, rated_turns, labeled_memories, relevance_threshold, bm25_weight, semantic_weight, precision, mrr)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            run.id,
+            run.created_at,
+            run.rated_turns as i64,
+            run.labeled_memories as i64,
+            run.relevance_threshold,
+            run.weights.as_ref().map(|w| w.bm25_weight),
+            run.weights.as_ref().map(|w| w.semantic_weight),
+            run.precision,
+            run.mrr
+        ],
+    )?;
+    Ok(run)
+}
+
+/// Most recent retraining runs, newest first
+pub fn tuning_runs(conn: &Connection, limit: usize) -> Result<Vec<TuningRun>> {
+    init_tables(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, created_at, rated_turns, labeled_memories, relevance_threshold, bm25_weight, semantic_weight, precision, mrr
+         FROM retrieval_tuning_runs ORDER BY created_at DESC LIMIT ?1",
+    )?;
+    let runs = stmt
+        .query_map([limit as i64], |row| {
+            let bm25_weight: Option<f32> = row.get(5)?;
+            let semantic_weight: Option<f32> = row.get(6)?;
+            Ok(TuningRun {
+                id: row.get(0)?,
+                created_at: row.get(1)?,
+                rated_turns: row.get::<_, i64>(2)? as usize,
+                labeled_memories: row.get::<_, i64>(3)? as usize,
+                relevance_threshold: row.get(4)?,
+                weights: bm25_weight.zip(semantic_weight).map(|(bm25_weight, semantic_weight)| FusionWeights {
+                    bm25_weight,
+                    semantic_weight,
+                }),
+                precision: row.get(7)?,
+                mrr: row.get(8)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(runs)
+}
+
+/// Relevance threshold of the latest run that changed it (re-applied at startup)
+pub fn latest_threshold(conn: &Connection) -> Result<Option<f32>> {
+    init_tables(conn)?;
+    Ok(conn
+        .query_row(
+            "SELECT relevance_threshold FROM retrieval_tuning_runs
+             WHERE relevance_threshold IS NOT NULL ORDER BY created_at DESC LIMIT 1",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+/// Weekly retrieval quality over the last `weeks` weeks, oldest first
+pub fn quality_trend(conn: &Connection, weeks: usize) -> Result<Vec<QualityPoint>> {
+    init_tables(conn)?;
+    let since = chrono::Utc::now().timestamp_millis() - weeks as i64 * WEEK_MS;
+
+    let mut stmt = conn.prepare(
+        "SELECT created_at / ?1, COUNT(DISTINCT turn_id) FROM retrieval_usage WHERE created_at >= ?2 GROUP BY 1",
+    )?;
+    let totals: HashMap<i64, usize> = stmt
+        .query_map(params![WEEK_MS, since], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)? as usize)))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    let mut by_week: HashMap<i64, Vec<RatedTurn>> = HashMap::new();
+    for turn in rated_turns(conn, Some(since))? {
+        by_week.entry(turn.created_at / WEEK_MS).or_default().push(turn);
+    }
+
+    let mut trend: Vec<QualityPoint> = totals
+        .into_iter()
+        .map(|(week, turns)| {
+            let rated = by_week.remove(&week).unwrap_or_default();
+            let (precision, mrr) = quality(&rated);
+            let avg_satisfaction = if rated.is_empty() {
+                0.0
+            } else {
+                rated.iter().map(|t| t.satisfaction).sum::<f32>() / rated.len() as f32
+            };
+            QualityPoint {
+                week_start: week * WEEK_MS,
+                turns,
+                rated_turns: rated.len(),
+                precision,
+                mrr,
+                avg_satisfaction,
+            }
+        })
+        .collect();
+    trend.sort_by_key(|point| point.week_start);
+    Ok(trend)
+}
+
+/// Retrain hybrid-search weights and the RAFT relevance threshold from
+/// rated turns, apply the changes and record the run
+#[cfg(feature = "lancedb-support")]
+pub async fn retrain(
+    db: &std::sync::Mutex<crate::database::Database>,
+    rag: &super::rag_v2::RagServiceV2,
+    hybrid: &tokio::sync::Mutex<super::hybrid_search::HybridSearchEngine>,
+) -> Result<TuningRun> {
+    use anyhow::anyhow;
+
+    let turns = {
+        let db = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        rated_turns(db.conn(), None)?
+    };
+
+    let raft_config = rag.get_raft_config()?;
+    let threshold = tune_relevance_threshold(&turns, raft_config.relevance_threshold);
+    if let Some(relevance_threshold) = threshold {
+        log::info!("Relevance threshold retuned: {:.2} → {:.2}", raft_config.relevance_threshold, relevance_threshold);
+        rag.update_raft_config(raft::RaftConfig { relevance_threshold, ..raft_config })?;
+    }
+
+    let mut engine = hybrid.lock().await;
+    let weights = tune_weights(&turns, |query| engine.bm25_ranking(query, 100), engine.rrf_k(), &engine.fusion_config().weights);
+
+    let db = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+    if let Some(weights) = &weights {
+        log::info!("Fusion weights retuned: BM25 {:.1} / semantic {:.1}", weights.bm25_weight, weights.semantic_weight);
+        engine.set_fusion_weights(weights.clone());
+        engine.fusion_config().save(db.conn())?;
+    }
+    record_run(db.conn(), &turns, threshold, weights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+
+    fn memory(id: &str, rank: usize, similarity: f32, used: bool) -> RetrievedMemory {
+        RetrievedMemory { episode_id: id.into(), rank, similarity, sent: true, used }
+    }
+
+    #[test]
+    fn test_usage_logging() {
+        assert!(was_used(
+            "Your sourdough starter needs feeding with rye flour every morning",
+            "How often should I feed my sourdough starter?",
+            "Feed the starter every morning with rye flour",
+        ));
+        assert!(!was_used("The weather is sunny today", "How often should I feed my sourdough starter?", "Every morning"));
+
+        let db = Database::new_test_db().unwrap();
+        let conn = db.conn();
+        record_turn(conn, "c1", "sourdough", &[memory("a", 1, 0.8, true), memory("b", 2, 0.6, false)]).unwrap();
+        record_turn(conn, "c2", "weather", &[memory("c", 1, 0.7, true)]).unwrap();
+        assert!(rated_turns(conn, None).unwrap().is_empty());
+
+        assert_eq!(apply_rating(conn, "c1", 1.0).unwrap(), 2);
+        assert_eq!(apply_rating(conn, "c2", 0.5).unwrap(), 1);
+        assert_eq!(apply_rating(conn, "c1", 0.0).unwrap(), 0);  // Already rated
+
+        let turns = rated_turns(conn, None).unwrap();
+        assert_eq!(turns.len(), 1);  // Neutral rating is ignored
+        assert_eq!(turns[0].memories.len(), 2);
+        assert_eq!(quality(&turns), (0.5, 1.0));
+
+        let trend = quality_trend(conn, 4).unwrap();
+        assert_eq!(trend.len(), 1);
+        assert_eq!((trend[0].turns, trend[0].rated_turns), (2, 1));
+
+        let run = record_run(conn, &turns, Some(0.65), None).unwrap();
+        assert_eq!(tuning_runs(conn, 5).unwrap()[0].id, run.id);
+        assert_eq!(latest_threshold(conn).unwrap(), Some(0.65));
+    }
+
+    #[test]
+    fn test_retraining() {
+        // Useful memories score 0.75 and lead BM25; the rest score below 0.55 but lead semantically
+        let turns: Vec<RatedTurn> = (0..12)
+            .map(|i| RatedTurn {
+                turn_id: format!("t{}", i),
+                query: format!("q{}", i),
+                satisfaction: 1.0,
+                created_at: i,
+                memories: vec![
+                    memory(&format!("noise{}", i), 1, 0.54, false),
+                    memory(&format!("noise2{}", i), 2, 0.52, false),
+                    memory(&format!("hit{}", i), 3, 0.75, true),
+                ],
+            })
+            .collect();
+
+        let threshold = tune_relevance_threshold(&turns, 0.5).unwrap();
+        assert!((threshold - 0.55).abs() < 1e-4);
+        assert_eq!(tune_relevance_threshold(&turns[..5], 0.5), None);  // Too few samples
+
+        let bm25 = |query: &str| vec![format!("hit{}", &query[1..]), format!("noise{}", &query[1..])];
+        let weights = tune_weights(&turns, bm25, 60.0, &FusionWeights::default()).unwrap();
+        assert!((weights.bm25_weight - 0.7).abs() < 1e-4);
+        assert!((weights.bm25_weight + weights.semantic_weight - 1.0).abs() < 1e-6);
+        assert!(tune_weights(&turns[..5], bm25, 60.0, &FusionWeights::default()).is_none());
+    }
+}
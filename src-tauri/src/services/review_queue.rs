@@ -0,0 +1,423 @@
+//! Review Queue Service (v3.9.0)
+//!
+//! Spaced-repetition review of what the assistant remembers about the user.
+//!
+//! Features:
+//! - Surfaces high-value memories (facts, procedures, decisions) that
+//!   temporal_memory forecasts will drop below critical retention soon
+//! - Confirm: stability grows and retention is restored (Anki-style)
+//! - Correct: the remembered reply is replaced with the user's correction, then confirmed
+//! - Dismiss: never surfaced again, the memory keeps decaying normally
+//! - Review history in `memory_reviews`
+
+#![allow(dead_code)]  // Phase 5: Review queue
+
+use crate::database::Database;
+use crate::services::embedding::UnifiedEmbeddingService;
+use crate::services::temporal_memory::{MemoryType, TemporalMemoryService};
+use anyhow::{anyhow, Result};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Retention below which a memory counts as critical (matches temporal forecasts)
+pub const CRITICAL_RETENTION: f64 = 0.3;
+
+/// Phrases marking a conversation turn as a decision
+const DECISION_MARKERS: &[&str] = &[
+    "decided",
+    "decision",
+    "i'll go with",
+    "let's go with",
+    "we'll go with",
+    "we chose",
+    "i chose",
+    "agreed",
+    "from now on",
+    "going forward",
+    "결정",
+    "하기로",
+    "정했",
+];
+
+/// Review queue configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewQueueConfig {
+    /// Memories going critical within this many days are due
+    pub horizon_days: f64,
+    /// Minimum value score (0.0-1.0) for a memory to be worth reviewing
+    pub min_value: f64,
+    /// Stability multiplier per confirmed review
+    pub ease: f64,
+    /// Retention a memory is restored to when confirmed
+    pub target_retention: f64,
+    /// Items returned when no limit is given
+    pub max_items: usize,
+}
+
+impl Default for ReviewQueueConfig {
+    fn default() -> Self {
+        Self {
+            horizon_days: 7.0,
+            min_value: 0.5,
+            ease: 2.0,
+            target_retention: 0.9,
+            max_items: 20,
+        }
+    }
+}
+
+/// A memory waiting for review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewItem {
+    pub memory_id: String,
+    pub user_message: String,
+    pub ai_response: String,
+    pub memory_type: MemoryType,
+    pub importance: f64,
+    pub is_decision: bool,
+    pub current_retention: f64,
+    pub days_until_critical: Option<f64>,
+    pub review_count: u32,
+    pub last_reviewed_at: Option<i64>, // Unix millis
+    /// How much this memory is worth keeping (0.0-1.0)
+    pub value: f64,
+    /// Queue order: value weighted by urgency
+    pub priority: f64,
+}
+
+/// What the user said about a memory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReviewOutcome {
+    /// Still correct
+    Confirm,
+    /// Outdated; `text` replaces what the assistant remembered
+    Correct { text: String },
+    /// Not worth remembering; stop asking
+    Dismiss,
+}
+
+impl ReviewOutcome {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Confirm => "confirm",
+            Self::Correct { .. } => "correct",
+            Self::Dismiss => "dismiss",
+        }
+    }
+}
+
+/// Memory state after a review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewResult {
+    pub memory_id: String,
+    pub outcome: ReviewOutcome,
+    /// New decay strength (`None` when dismissed)
+    pub decay_strength: Option<f64>,
+    pub retention: f64,
+    pub days_until_critical: Option<f64>,
+}
+
+/// Review queue statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewStats {
+    pub due: usize,
+    pub reviewed_last_7_days: usize,
+    pub total_reviews: u64,
+    pub dismissed: usize,
+}
+
+/// Whether a conversation turn records a decision
+pub fn looks_like_decision(user_message: &str, ai_response: &str) -> bool {
+    let text = format!("{}\n{}", user_message, ai_response).to_lowercase();
+    DECISION_MARKERS.iter().any(|marker| text.contains(marker))
+}
+
+/// How much a memory is worth keeping (0.0-1.0)
+///
+/// Facts and procedures count most, casual chat little, ephemeral Q&A not at
+/// all; decisions and stored importance add on top.
+pub fn memory_value(memory_type: MemoryType, importance: f64, is_decision: bool) -> f64 {
+    let type_weight = match memory_type {
+        MemoryType::Factual => 1.0,
+        MemoryType::Procedural => 0.8,
+        MemoryType::Conversational => 0.3,
+        MemoryType::Ephemeral => 0.0,
+    };
+    let decision_bonus = if is_decision { 0.3 } else { 0.0 };
+
+    (type_weight * 0.5 + importance.clamp(0.0, 1.0) * 0.3 + decision_bonus).min(1.0)
+}
+
+/// Queue priority: valuable memories about to go critical come first
+pub fn review_priority(value: f64, days_until_critical: Option<f64>) -> f64 {
+    match days_until_critical {
+        Some(days) => value / (1.0 + days.max(0.0)),
+        None => 0.0, // Never goes critical
+    }
+}
+
+/// Review queue service
+pub struct ReviewQueueService {
+    db: Arc<Mutex<Database>>,
+    temporal: Arc<TemporalMemoryService>,
+    embedding_service: Arc<UnifiedEmbeddingService>,
+    config: Mutex<ReviewQueueConfig>,
+}
+
+impl ReviewQueueService {
+    pub fn new(
+        db: Arc<Mutex<Database>>,
+        temporal: Arc<TemporalMemoryService>,
+        embedding_service: Arc<UnifiedEmbeddingService>,
+    ) -> Result<Self> {
+        {
+            let db = db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            db.conn().execute(
+                "CREATE TABLE IF NOT EXISTS memory_reviews (
+                    memory_id TEXT PRIMARY KEY,
+                    review_count INTEGER NOT NULL DEFAULT 0,
+                    last_reviewed_at INTEGER,
+                    last_outcome TEXT,
+                    dismissed INTEGER NOT NULL DEFAULT 0
+                )",
+                [],
+            )?;
+        }
+
+        log::info!("✓ Review Queue Service initialized");
+        Ok(Self {
+            db,
+            temporal,
+            embedding_service,
+            config: Mutex::new(ReviewQueueConfig::default()),
+        })
+    }
+
+    pub fn get_config(&self) -> Result<ReviewQueueConfig> {
+        Ok(self.config.lock().map_err(|e| anyhow!("Lock error: {}", e))?.clone())
+    }
+
+    pub fn update_config(&self, config: ReviewQueueConfig) -> Result<()> {
+        if !(0.0..1.0).contains(&config.target_retention) || config.target_retention <= CRITICAL_RETENTION {
+            return Err(anyhow!(
+                "Target retention must be between {} and 1.0",
+                CRITICAL_RETENTION
+            ));
+        }
+        *self.config.lock().map_err(|e| anyhow!("Lock error: {}", e))? = config;
+        Ok(())
+    }
+
+    /// Memories due for review, highest priority first
+    pub fn get_queue(&self, limit: Option<usize>) -> Result<Vec<ReviewItem>> {
+        let config = self.get_config()?;
+        let forecasts = self
+            .temporal
+            .find_at_risk_memories(config.horizon_days, CRITICAL_RETENTION)?;
+
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT m.user_message, m.ai_response,
+                    COALESCE(m.memory_type, 'conversational'), m.importance,
+                    COALESCE(r.review_count, 0), r.last_reviewed_at, COALESCE(r.dismissed, 0)
+             FROM episodic_memory m
+             LEFT JOIN memory_reviews r ON r.memory_id = m.id
+             WHERE m.id = ?1",
+        )?;
+
+        let mut queue = Vec::new();
+        for forecast in forecasts {
+            let row = stmt
+                .query_row(params![forecast.memory_id], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, f64>(3)?,
+                        row.get::<_, u32>(4)?,
+                        row.get::<_, Option<i64>>(5)?,
+                        row.get::<_, bool>(6)?,
+                    ))
+                })
+                .optional()?;
+
+            let (user_message, ai_response, memory_type, importance, review_count, last_reviewed_at, dismissed) =
+                match row {
+                    Some(row) => row,
+                    None => continue, // Deleted since the forecast
+                };
+            if dismissed {
+                continue;
+            }
+
+            let memory_type = MemoryType::from_str(&memory_type);
+            let is_decision = looks_like_decision(&user_message, &ai_response);
+            let value = memory_value(memory_type, importance, is_decision);
+            if value < config.min_value {
+                continue;
+            }
+
+            queue.push(ReviewItem {
+                memory_id: forecast.memory_id,
+                user_message,
+                ai_response,
+                memory_type,
+                importance,
+                is_decision,
+                current_retention: forecast.current_retention,
+                days_until_critical: forecast.days_until_critical,
+                review_count,
+                last_reviewed_at,
+                value,
+                priority: review_priority(value, forecast.days_until_critical),
+            });
+        }
+
+        queue.sort_by(|a, b| b.priority.partial_cmp(&a.priority).unwrap_or(std::cmp::Ordering::Equal));
+        queue.truncate(limit.unwrap_or(config.max_items));
+        Ok(queue)
+    }
+
+    /// Apply the user's review of a memory
+    pub fn review(&self, memory_id: &str, outcome: ReviewOutcome) -> Result<ReviewResult> {
+        let config = self.get_config()?;
+
+        if let ReviewOutcome::Correct { text } = &outcome {
+            self.correct_memory(memory_id, text)?;
+        }
+
+        let decay_strength = match outcome {
+            ReviewOutcome::Dismiss => None,
+            _ => Some(self.temporal.reinforce_memory(memory_id, config.ease, config.target_retention)?),
+        };
+
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            db.conn().execute(
+                "INSERT INTO memory_reviews (memory_id, review_count, last_reviewed_at, last_outcome, dismissed)
+                 VALUES (?1, 1, ?2, ?3, ?4)
+                 ON CONFLICT(memory_id) DO UPDATE SET
+                    review_count = review_count + 1,
+                    last_reviewed_at = excluded.last_reviewed_at,
+                    last_outcome = excluded.last_outcome,
+                    dismissed = excluded.dismissed",
+                params![
+                    memory_id,
+                    chrono::Utc::now().timestamp_millis(),
+                    outcome.as_str(),
+                    outcome == ReviewOutcome::Dismiss,
+                ],
+            )?;
+        }
+
+        let forecast = self.temporal.forecast_retention(memory_id, 0.0)?;
+        log::info!("Reviewed memory {} ({})", memory_id, outcome.as_str());
+
+        Ok(ReviewResult {
+            memory_id: memory_id.to_string(),
+            outcome,
+            decay_strength,
+            retention: forecast.current_retention,
+            days_until_critical: forecast.days_until_critical,
+        })
+    }
+
+    /// Replace the remembered reply and refresh its embedding
+    fn correct_memory(&self, memory_id: &str, text: &str) -> Result<()> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(anyhow!("Correction cannot be empty"));
+        }
+
+        let user_message: String = {
+            let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            db.conn()
+                .query_row(
+                    "SELECT user_message FROM episodic_memory WHERE id = ?1",
+                    params![memory_id],
+                    |row| row.get(0),
+                )
+                .optional()?
+                .ok_or_else(|| anyhow!("Memory not found: {}", memory_id))?
+        };
+
+        // Same text layout as RagService::store_episode
+        let embedding = self
+            .embedding_service
+            .embed(&format!("{}\n{}", user_message, text))?;
+        let embedding_json = serde_json::to_string(&embedding)?;
+
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        db.conn().execute(
+            "UPDATE episodic_memory SET ai_response = ?1, embedding_id = ?2 WHERE id = ?3",
+            params![text, embedding_json, memory_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_stats(&self) -> Result<ReviewStats> {
+        let due = self.get_queue(Some(usize::MAX))?.len();
+
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let week_ago = chrono::Utc::now().timestamp_millis() - 7 * 24 * 60 * 60 * 1000;
+        let (reviewed_last_7_days, total_reviews, dismissed): (i64, i64, i64) = db.conn().query_row(
+            "SELECT COALESCE(SUM(last_reviewed_at >= ?1), 0),
+                    COALESCE(SUM(review_count), 0),
+                    COALESCE(SUM(dismissed), 0)
+             FROM memory_reviews",
+            params![week_ago],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+
+        Ok(ReviewStats {
+            due,
+            reviewed_last_7_days: reviewed_last_7_days as usize,
+            total_reviews: total_reviews as u64,
+            dismissed: dismissed as usize,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::temporal_memory::reinforced_strength;
+
+    #[test]
+    fn test_memory_value_prefers_facts_and_decisions() {
+        let fact = memory_value(MemoryType::Factual, 0.5, false);
+        let chat = memory_value(MemoryType::Conversational, 0.5, false);
+        let decision = memory_value(MemoryType::Conversational, 0.5, true);
+
+        assert!(fact >= ReviewQueueConfig::default().min_value);
+        assert!(chat < ReviewQueueConfig::default().min_value);
+        assert!(decision >= ReviewQueueConfig::default().min_value);
+        assert!(memory_value(MemoryType::Factual, 1.0, true) <= 1.0);
+
+        assert!(looks_like_decision("Which database?", "Agreed, we'll go with Postgres."));
+        assert!(looks_like_decision("앞으로 러스트로 하기로 했어", "좋아요!"));
+        assert!(!looks_like_decision("What's the weather?", "Sunny."));
+    }
+
+    #[test]
+    fn test_review_priority_orders_by_urgency() {
+        assert!(review_priority(0.8, Some(0.0)) > review_priority(0.8, Some(3.0)));
+        assert!(review_priority(0.9, Some(2.0)) > review_priority(0.5, Some(2.0)));
+        assert_eq!(review_priority(0.8, None), 0.0);
+    }
+
+    #[test]
+    fn test_reinforced_strength_restores_target_retention() {
+        // 30 days old at S=20 (~22% retention): must be back at 90%
+        let strength = reinforced_strength(20.0, 30.0, 2.0, 0.9);
+        assert!(((-30.0 / strength).exp() - 0.9).abs() < 1e-9);
+
+        // Young memory: stability still grows by ease
+        assert_eq!(reinforced_strength(20.0, 1.0, 2.0, 0.9), 40.0);
+
+        // Capped
+        assert_eq!(reinforced_strength(3000.0, 1.0, 2.0, 0.9), crate::services::temporal_memory::MAX_DECAY_STRENGTH);
+    }
+}
@@ -1,4 +1,5 @@
 use screenshots::Screen;
+use screenshots::image::{imageops, RgbaImage};
 use base64::{Engine as _, engine::general_purpose};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -6,6 +7,239 @@ use tokio::time::interval;
 use crate::database::Database;
 use super::active_window::{ActiveWindowService, ActiveWindow};
 use super::llava::{LlavaService, ScreenAnalysis};
+use super::screen_history::{FrameInput, ScreenHistoryService};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// user_preferences key holding the serialized privacy configuration
+const PRIVACY_CONFIG_KEY: &str = "screen_privacy_config";
+
+/// Pixel block size used when blurring a capture
+const PIXELATE_BLOCK: u32 = 24;
+
+/// Longest side of frames kept in screenshot history
+const HISTORY_THUMBNAIL_SIZE: u32 = 480;
+
+/// What to do with a capture taken while a privacy zone is focused
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrivacyAction {
+    /// Do not capture or store anything
+    Skip,
+    /// Store a pixelated capture only
+    Blur,
+}
+
+/// Privacy zones and sensitive-content redaction settings (v3.9.0)
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct PrivacyConfig {
+    pub enabled: bool,
+    /// App names (case-insensitive substring match) that are never captured in the clear
+    pub blocked_apps: Vec<String>,
+    /// Window title keywords (case-insensitive) such as banking sites
+    pub blocked_title_keywords: Vec<String>,
+    pub action: PrivacyAction,
+    /// Mask credit cards, passwords and keys found in extracted text
+    pub redact_sensitive_text: bool,
+}
+
+impl Default for PrivacyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            blocked_apps: vec![
+                "1Password".to_string(),
+                "Bitwarden".to_string(),
+                "KeePassXC".to_string(),
+                "LastPass".to_string(),
+                "Dashlane".to_string(),
+                "Keychain Access".to_string(),
+            ],
+            blocked_title_keywords: vec![
+                "bank".to_string(),
+                "은행".to_string(),
+                "password".to_string(),
+                "비밀번호".to_string(),
+                "paypal".to_string(),
+                "toss".to_string(),
+            ],
+            action: PrivacyAction::Skip,
+            redact_sensitive_text: true,
+        }
+    }
+}
+
+/// Result of checking the focused window against the privacy zones
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrivacyDecision {
+    Allow,
+    Skip(String),
+    Blur(String),
+}
+
+impl PrivacyDecision {
+    /// The focused window as it may be stored or passed on: a blurred app keeps
+    /// its name, but its title (chat partner, document, account) is dropped
+    pub fn redact_window(&self, window: Option<ActiveWindow>) -> Option<ActiveWindow> {
+        match self {
+            Self::Blur(_) => window.map(|w| ActiveWindow { title: String::new(), ..w }),
+            _ => window,
+        }
+    }
+}
+
+impl PrivacyConfig {
+    /// Decide how a capture should be handled for the given focused window
+    pub fn evaluate(&self, window: Option<&ActiveWindow>) -> PrivacyDecision {
+        let window = match window {
+            Some(w) if self.enabled => w,
+            _ => return PrivacyDecision::Allow,
+        };
+
+        let app = window.app_name.to_lowercase();
+        let title = window.title.to_lowercase();
+
+        let reason = self
+            .blocked_apps
+            .iter()
+            .find(|a| !a.is_empty() && app.contains(&a.to_lowercase()))
+            .map(|a| format!("blocked app '{}'", a))
+            .or_else(|| {
+                self.blocked_title_keywords
+                    .iter()
+                    .find(|k| !k.is_empty() && title.contains(&k.to_lowercase()))
+                    .map(|k| format!("blocked title keyword '{}'", k))
+            });
+
+        match (reason, self.action) {
+            (None, _) => PrivacyDecision::Allow,
+            (Some(r), PrivacyAction::Skip) => PrivacyDecision::Skip(r),
+            (Some(r), PrivacyAction::Blur) => PrivacyDecision::Blur(r),
+        }
+    }
+
+    /// Mask secrets in the focused window's title (a card number in a tab name,
+    /// "password: ..." in an editor) when sensitive text redaction is on
+    pub fn redact_window_title(&self, window: Option<ActiveWindow>) -> Option<ActiveWindow> {
+        match window {
+            Some(w) if self.enabled && self.redact_sensitive_text => {
+                let (title, count) = redact_sensitive_text(&w.title);
+                if count > 0 {
+                    log::info!("Redacted {} sensitive item(s) from window title", count);
+                }
+                Some(ActiveWindow { title, ..w })
+            }
+            other => other,
+        }
+    }
+}
+
+fn card_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\b(?:\d[ -]?){12,18}\d\b").unwrap())
+}
+
+fn secret_regexes() -> &'static [Regex] {
+    static RES: OnceLock<Vec<Regex>> = OnceLock::new();
+    RES.get_or_init(|| {
+        [
+            // password: hunter2 / 비밀번호=1234
+            r"(?i)(password|passwd|pwd|비밀번호|암호)(\s*[:=]\s*)\S+",
+            // API keys and tokens
+            r"\b(sk-[A-Za-z0-9_-]{16,}|AKIA[0-9A-Z]{16}|ghp_[A-Za-z0-9]{36})\b",
+            // Korean resident registration number
+            r"\b\d{6}-[1-4]\d{6}\b",
+        ]
+        .iter()
+        .map(|p| Regex::new(p).unwrap())
+        .collect()
+    })
+}
+
+/// Luhn checksum, used to avoid masking arbitrary long numbers
+fn luhn_valid(digits: &str) -> bool {
+    let mut sum = 0;
+    for (i, c) in digits.chars().rev().enumerate() {
+        let mut d = match c.to_digit(10) {
+            Some(d) => d,
+            None => return false,
+        };
+        if i % 2 == 1 {
+            d *= 2;
+            if d > 9 {
+                d -= 9;
+            }
+        }
+        sum += d;
+    }
+    sum % 10 == 0
+}
+
+/// Mask credit-card numbers, passwords, API keys and ID numbers in text.
+/// Returns the redacted text and number of masked matches.
+pub fn redact_sensitive_text(text: &str) -> (String, usize) {
+    let mut count = 0;
+
+    let mut redacted = card_regex()
+        .replace_all(text, |caps: &regex::Captures| {
+            let matched = &caps[0];
+            let digits: String = matched.chars().filter(|c| c.is_ascii_digit()).collect();
+            if (13..=19).contains(&digits.len()) && luhn_valid(&digits) {
+                count += 1;
+                "[REDACTED CARD]".to_string()
+            } else {
+                matched.to_string()
+            }
+        })
+        .into_owned();
+
+    for (i, re) in secret_regexes().iter().enumerate() {
+        redacted = re
+            .replace_all(&redacted, |caps: &regex::Captures| {
+                count += 1;
+                if i == 0 {
+                    format!("{}{}[REDACTED]", &caps[1], &caps[2])
+                } else {
+                    "[REDACTED]".to_string()
+                }
+            })
+            .into_owned();
+    }
+
+    (redacted, count)
+}
+
+/// Pixelate an image so no text remains legible
+fn pixelate(image: &RgbaImage, block: u32) -> RgbaImage {
+    let (w, h) = image.dimensions();
+    let small = imageops::resize(
+        image,
+        (w / block).max(1),
+        (h / block).max(1),
+        imageops::FilterType::Triangle,
+    );
+    imageops::resize(&small, w, h, imageops::FilterType::Nearest)
+}
+
+/// Load the privacy configuration, falling back to defaults
+pub fn load_privacy_config(db: &Arc<Mutex<Database>>) -> PrivacyConfig {
+    let db_guard = match db.lock() {
+        Ok(guard) => guard,
+        Err(_) => return PrivacyConfig::default(),
+    };
+
+    db_guard
+        .conn()
+        .query_row(
+            "SELECT value FROM user_preferences WHERE key = ?1",
+            [PRIVACY_CONFIG_KEY],
+            |row| row.get::<_, String>(0),
+        )
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
 
 /// Screen capture tracking state
 #[derive(Debug, Clone)]
@@ -33,6 +267,7 @@ pub struct ScreenCaptureService {
     db: Arc<Mutex<Database>>,
     active_window_service: ActiveWindowService,
     llava_service: Option<Arc<LlavaService>>,
+    history: OnceLock<Arc<ScreenHistoryService>>,  // v3.9.0: Screenshot history
 }
 
 impl ScreenCaptureService {
@@ -56,6 +291,14 @@ impl ScreenCaptureService {
             db,
             active_window_service,
             llava_service,
+            history: OnceLock::new(),
+        }
+    }
+
+    /// Record captured frames into screenshot history (v3.9.0)
+    pub fn attach_history(&self, history: Arc<ScreenHistoryService>) {
+        if self.history.set(history).is_err() {
+            log::warn!("Screenshot history already attached");
         }
     }
 
@@ -83,6 +326,7 @@ impl ScreenCaptureService {
         let state_clone = Arc::clone(&self.state);
         let db_clone = Arc::clone(&self.db);
         let active_window_service = self.active_window_service.clone();
+        let history = self.history.get().cloned();
 
         tokio::spawn(async move {
             let mut interval_timer = interval(Duration::from_secs(interval_seconds));
@@ -100,7 +344,7 @@ impl ScreenCaptureService {
                 }
 
                 // Capture screen with active window info
-                if let Err(e) = Self::capture_and_save(&state_clone, &db_clone, &active_window_service).await {
+                if let Err(e) = Self::capture_and_save(&state_clone, &db_clone, &active_window_service, history.as_ref()).await {
                     log::error!("Screen capture failed: {}", e);
                 }
             }
@@ -146,7 +390,29 @@ impl ScreenCaptureService {
         state: &Arc<Mutex<ScreenTrackingState>>,
         db: &Arc<Mutex<Database>>,
         active_window_service: &ActiveWindowService,
+        history: Option<&Arc<ScreenHistoryService>>,
     ) -> Result<(), String> {
+        // Get active window information (v3.6.0)
+        let active_window: Option<ActiveWindow> = match active_window_service.get_active_window() {
+            Ok(window) => {
+                log::debug!("Active window: {} ({})", window.title, window.app_name);
+                Some(window)
+            },
+            Err(e) => {
+                log::warn!("Failed to get active window: {}", e);
+                None
+            }
+        };
+
+        // Privacy zones (v3.9.0): skip or blur when a sensitive app is focused
+        let privacy = load_privacy_config(db);
+        let decision = privacy.evaluate(active_window.as_ref());
+        if let PrivacyDecision::Skip(reason) = &decision {
+            log::info!("Screen capture skipped ({})", reason);
+            return Ok(());
+        }
+        let active_window = privacy.redact_window_title(decision.redact_window(active_window));
+
         // Capture all screens
         let screens = Screen::all().map_err(|e| format!("Failed to get screens: {}", e))?;
 
@@ -156,10 +422,15 @@ impl ScreenCaptureService {
 
         // Capture primary screen (index 0)
         let screen = &screens[0];
-        let image = screen
+        let mut image = screen
             .capture()
             .map_err(|e| format!("Failed to capture screen: {}", e))?;
 
+        if let PrivacyDecision::Blur(reason) = &decision {
+            log::info!("Screen capture blurred ({})", reason);
+            image = pixelate(&image, PIXELATE_BLOCK);
+        }
+
         // Convert image buffer to PNG bytes
         // The screenshots crate returns a screenshots::image::RgbaImage
         // We need to save it as PNG
@@ -179,40 +450,41 @@ impl ScreenCaptureService {
             .map_err(|e| format!("Failed to get timestamp: {}", e))?
             .as_millis() as i64;
 
-        // Get active window information (v3.6.0)
-        let active_window: Option<ActiveWindow> = match active_window_service.get_active_window() {
-            Ok(window) => {
-                log::debug!("Active window: {} ({})", window.title, window.app_name);
-                Some(window)
-            },
-            Err(e) => {
-                log::warn!("Failed to get active window: {}", e);
-                None
-            }
-        };
-
-        let window_title = active_window.as_ref().map(|w| w.title.clone());
+        let window_title = active_window.as_ref().map(|w| w.title.clone()).filter(|t| !t.is_empty());
         let app_name = active_window.as_ref().map(|w| w.app_name.clone());
 
         // Save to database
-        let db_guard = db.lock().map_err(|e| format!("Failed to lock database: {}", e))?;
-        let conn = db_guard.conn();
-
-        // Store base64 image in image_path for now (will be refactored to save as file later)
-        conn.execute(
-            "INSERT INTO screen_context (id, level, image_path, extracted_text, window_title, application_name, timestamp)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            rusqlite::params![
-                format!("screen_{}", timestamp),
-                1, // Level 1: Current screen only
-                base64_image, // Storing base64 in image_path temporarily
-                Option::<String>::None, // OCR: Use LLaVA vision for text extraction instead
-                window_title,
-                app_name,
-                timestamp,
-            ],
-        )
-        .map_err(|e| format!("Failed to save screen capture: {}", e))?;
+        {
+            let db_guard = db.lock().map_err(|e| format!("Failed to lock database: {}", e))?;
+            let conn = db_guard.conn();
+
+            // Store base64 image in image_path for now (will be refactored to save as file later)
+            conn.execute(
+                "INSERT INTO screen_context (id, level, image_path, extracted_text, window_title, application_name, timestamp)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                rusqlite::params![
+                    format!("screen_{}", timestamp),
+                    1, // Level 1: Current screen only
+                    base64_image, // Storing base64 in image_path temporarily
+                    Option::<String>::None, // OCR: Use LLaVA vision for text extraction instead
+                    window_title,
+                    app_name,
+                    timestamp,
+                ],
+            )
+            .map_err(|e| format!("Failed to save screen capture: {}", e))?;
+        }
+
+        // Screenshot history (v3.9.0): periodic frames are described by their window
+        if let Some(history) = history {
+            let description = match (&app_name, &window_title) {
+                (Some(app), Some(title)) => format!("{} window: {}", app, title),
+                (Some(app), None) => format!("{} window", app),
+                (None, Some(title)) => title.clone(),
+                (None, None) => String::new(),
+            };
+            Self::record_history(history, &image, description, app_name.clone(), window_title.clone(), timestamp).await;
+        }
 
         // Update state
         let mut state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
@@ -276,6 +548,17 @@ impl ScreenCaptureService {
 
     /// Capture screen with active window detection and vision analysis
     pub async fn capture_with_context(&self, context_level: u8) -> Result<EnhancedScreenCapture, String> {
+        // Get active window
+        let active_window = self.active_window_service.get_active_window().ok();
+
+        // Privacy zones (v3.9.0)
+        let privacy = load_privacy_config(&self.db);
+        let decision = privacy.evaluate(active_window.as_ref());
+        if let PrivacyDecision::Skip(reason) = &decision {
+            return Err(format!("Screen capture blocked by privacy settings ({})", reason));
+        }
+        let active_window = privacy.redact_window_title(decision.redact_window(active_window));
+
         // Capture screen
         let screens = Screen::all().map_err(|e| format!("Failed to get screens: {}", e))?;
         if screens.is_empty() {
@@ -283,41 +566,65 @@ impl ScreenCaptureService {
         }
 
         let screen = &screens[0];
-        let image = screen
+        let mut image = screen
             .capture()
             .map_err(|e| format!("Failed to capture screen: {}", e))?;
 
-        // Convert to PNG and base64
-        let mut png_data: Vec<u8> = Vec::new();
-        let mut cursor = std::io::Cursor::new(&mut png_data);
-        use screenshots::image::ImageFormat;
-        image
-            .write_to(&mut cursor, ImageFormat::Png)
-            .map_err(|e| format!("Failed to encode PNG: {}", e))?;
-
-        let base64_image = general_purpose::STANDARD.encode(&png_data);
-
-        // Get active window
-        let active_window = self.active_window_service.get_active_window().ok();
+        let blurred = matches!(decision, PrivacyDecision::Blur(_));
+        if blurred {
+            image = pixelate(&image, PIXELATE_BLOCK);
+        }
 
-        // Analyze with LLaVA if available
-        let vision_analysis = if let Some(llava) = &self.llava_service {
-            match llava.analyze_screen_context(base64_image.clone(), context_level).await {
-                Ok(analysis) => Some(analysis),
-                Err(e) => {
-                    log::warn!("Vision analysis failed: {}", e);
-                    None
+        let mut base64_image = encode_png_base64(&image)?;
+        let mut base64_image_redacted = false;
+
+        // Analyze with LLaVA if available (skipped for blurred captures, nothing is legible)
+        let mut vision_analysis = match &self.llava_service {
+            Some(llava) if !blurred => {
+                match llava.analyze_screen_context(base64_image.clone(), context_level).await {
+                    Ok(analysis) => Some(analysis),
+                    Err(e) => {
+                        log::warn!("Vision analysis failed: {}", e);
+                        None
+                    }
                 }
             }
-        } else {
-            None
+            _ => None,
         };
 
+        // Mask sensitive text the vision model read off the screen; the model gives no
+        // bounding boxes, so the whole screenshot is pixelated when anything is found
+        if privacy.enabled && privacy.redact_sensitive_text {
+            if let Some(analysis) = vision_analysis.as_mut() {
+                let (redacted, count) = redact_sensitive_text(&analysis.description);
+                if count > 0 {
+                    log::info!("Redacted {} sensitive item(s) from screen analysis", count);
+                    analysis.description = redacted;
+                    base64_image = encode_png_base64(&pixelate(&image, PIXELATE_BLOCK))?;
+                    base64_image_redacted = true;
+                }
+            }
+        }
+
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .map_err(|e| format!("Failed to get timestamp: {}", e))?
             .as_millis() as i64;
 
+        // Screenshot history (v3.9.0): keep the described frame for later recall
+        if let (Some(history), Some(analysis)) = (self.history.get(), vision_analysis.as_ref()) {
+            let stored_image = if base64_image_redacted { pixelate(&image, PIXELATE_BLOCK) } else { image.clone() };
+            Self::record_history(
+                history,
+                &stored_image,
+                analysis.description.clone(),
+                active_window.as_ref().map(|w| w.app_name.clone()),
+                active_window.as_ref().map(|w| w.title.clone()),
+                timestamp,
+            )
+            .await;
+        }
+
         Ok(EnhancedScreenCapture {
             screenshot_base64: base64_image,
             active_window,
@@ -327,6 +634,69 @@ impl ScreenCaptureService {
         })
     }
 
+    /// Store a downscaled frame in screenshot history (failures are logged only)
+    async fn record_history(
+        history: &Arc<ScreenHistoryService>,
+        image: &RgbaImage,
+        description: String,
+        app_name: Option<String>,
+        window_title: Option<String>,
+        captured_at: i64,
+    ) {
+        let thumbnail = imageops::thumbnail(
+            image,
+            HISTORY_THUMBNAIL_SIZE,
+            (HISTORY_THUMBNAIL_SIZE * image.height() / image.width().max(1)).max(1),
+        );
+        let thumbnail_base64 = match encode_png_base64(&thumbnail) {
+            Ok(encoded) => encoded,
+            Err(e) => {
+                log::warn!("Failed to encode history thumbnail: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = history.record_frame(FrameInput {
+            thumbnail_base64,
+            description,
+            app_name,
+            window_title,
+            captured_at,
+        })
+        .await
+        {
+            log::warn!("Failed to record screen history frame: {}", e);
+        }
+    }
+
+    /// Get current privacy configuration
+    pub fn get_privacy_config(&self) -> PrivacyConfig {
+        load_privacy_config(&self.db)
+    }
+
+    /// Update privacy configuration (shared by every capture service instance)
+    pub fn configure_privacy(&self, config: PrivacyConfig) -> Result<PrivacyConfig, String> {
+        let json = serde_json::to_string(&config)
+            .map_err(|e| format!("Failed to serialize privacy config: {}", e))?;
+
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        db.conn()
+            .execute(
+                "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+                rusqlite::params![PRIVACY_CONFIG_KEY, json, chrono::Utc::now().timestamp()],
+            )
+            .map_err(|e| format!("Failed to save privacy config: {}", e))?;
+
+        log::info!(
+            "Screen privacy updated (enabled: {}, {} apps, {} keywords, action: {:?})",
+            config.enabled,
+            config.blocked_apps.len(),
+            config.blocked_title_keywords.len(),
+            config.action
+        );
+        Ok(config)
+    }
+
     /// Get active window information
     pub fn get_active_window(&self) -> Result<ActiveWindow, String> {
         self.active_window_service.get_active_window()
@@ -343,6 +713,19 @@ impl ScreenCaptureService {
     }
 }
 
+/// Encode an image as base64 PNG
+fn encode_png_base64(image: &RgbaImage) -> Result<String, String> {
+    use screenshots::image::ImageFormat;
+
+    let mut png_data: Vec<u8> = Vec::new();
+    let mut cursor = std::io::Cursor::new(&mut png_data);
+    image
+        .write_to(&mut cursor, ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+
+    Ok(general_purpose::STANDARD.encode(&png_data))
+}
+
 /// Screen capture data model
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ScreenCapture {
@@ -363,3 +746,90 @@ pub struct EnhancedScreenCapture {
     pub timestamp: i64,
     pub context_level: u8,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(app: &str, title: &str) -> ActiveWindow {
+        ActiveWindow {
+            title: title.to_string(),
+            app_name: app.to_string(),
+            bundle_id: None,
+            process_id: None,
+        }
+    }
+
+    #[test]
+    fn test_privacy_zone_matching() {
+        let config = PrivacyConfig::default();
+
+        assert_eq!(config.evaluate(Some(&window("Visual Studio Code", "main.rs"))), PrivacyDecision::Allow);
+        assert!(matches!(config.evaluate(Some(&window("1Password 8", "Vault"))), PrivacyDecision::Skip(_)));
+        assert!(matches!(config.evaluate(Some(&window("Chrome", "KB국민은행"))), PrivacyDecision::Skip(_)));
+        assert_eq!(config.evaluate(None), PrivacyDecision::Allow);
+
+        let blur = PrivacyConfig { action: PrivacyAction::Blur, ..PrivacyConfig::default() };
+        assert!(matches!(blur.evaluate(Some(&window("Bitwarden", ""))), PrivacyDecision::Blur(_)));
+
+        let disabled = PrivacyConfig { enabled: false, ..PrivacyConfig::default() };
+        assert_eq!(disabled.evaluate(Some(&window("Bitwarden", ""))), PrivacyDecision::Allow);
+    }
+
+    #[test]
+    fn test_blurred_window_title_is_redacted() {
+        let blur = PrivacyConfig { action: PrivacyAction::Blur, ..PrivacyConfig::default() };
+        let focused = window("Chrome", "KB국민은행 - 계좌 조회");
+        let decision = blur.evaluate(Some(&focused));
+
+        let redacted = decision.redact_window(Some(focused.clone())).unwrap();
+        assert_eq!(redacted.app_name, "Chrome");
+        assert!(redacted.title.is_empty());
+
+        let allowed = PrivacyDecision::Allow.redact_window(Some(focused)).unwrap();
+        assert_eq!(allowed.title, "KB국민은행 - 계좌 조회");
+    }
+
+    #[test]
+    fn test_allowed_window_title_is_redacted() {
+        let config = PrivacyConfig::default();
+        let focused = window("Notes", "Card 4111 1111 1111 1111 - Notes");
+        assert_eq!(config.evaluate(Some(&focused)), PrivacyDecision::Allow);
+
+        let redacted = config.redact_window_title(Some(focused.clone())).unwrap();
+        assert_eq!(redacted.app_name, "Notes");
+        assert_eq!(redacted.title, "Card [REDACTED CARD] - Notes");
+
+        let off = PrivacyConfig { redact_sensitive_text: false, ..PrivacyConfig::default() };
+        assert_eq!(off.redact_window_title(Some(focused)).unwrap().title, "Card 4111 1111 1111 1111 - Notes");
+    }
+
+    #[test]
+    fn test_redact_credit_card() {
+        let (text, count) = redact_sensitive_text("Card: 4111 1111 1111 1111, order 1234567890123");
+        assert_eq!(count, 1);
+        assert!(text.contains("[REDACTED CARD]"));
+        assert!(text.contains("1234567890123")); // fails Luhn, left alone
+    }
+
+    #[test]
+    fn test_redact_secrets() {
+        let (text, count) = redact_sensitive_text(
+            "password: hunter2\n비밀번호=1234\nkey sk-abcdefghijklmnop1234 id 900101-1234567",
+        );
+        assert_eq!(count, 4);
+        assert!(text.contains("password: [REDACTED]"));
+        assert!(text.contains("비밀번호=[REDACTED]"));
+        assert!(!text.contains("hunter2"));
+        assert!(!text.contains("sk-abc"));
+        assert!(!text.contains("900101-1234567"));
+    }
+
+    #[test]
+    fn test_privacy_config_partial_deserialize() {
+        let config: PrivacyConfig = serde_json::from_str(r#"{"action":"blur"}"#).unwrap();
+        assert_eq!(config.action, PrivacyAction::Blur);
+        assert!(config.enabled);
+        assert!(!config.blocked_apps.is_empty());
+    }
+}
@@ -0,0 +1,474 @@
+//! Screenshot History Service (v3.9.0)
+//!
+//! Keeps downscaled screen frames together with their descriptions so users
+//! can recall things they saw on screen ("the error dialog from yesterday").
+//!
+//! Features:
+//! - Downscaled frame storage with LLaVA / window descriptions
+//! - Descriptions indexed in the RAG store, so chat recall finds them too
+//! - Semantic search with time hints (today, yesterday, last week, 어제, ...);
+//!   thumbnails are only loaded for the frames returned
+//! - Duplicate suppression and retention limit (pruned frames leave RAG too)
+
+#![allow(dead_code)]  // Phase 5: Screenshot history (some helpers used by future UI)
+
+use crate::database::Database;
+use crate::services::chunker::SourceKind;
+use crate::services::rag_v2::RagServiceV2;
+use anyhow::{anyhow, Result};
+use chrono::{Duration, Local, TimeZone};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Identical descriptions within this window are stored once (5 minutes)
+const DEDUP_WINDOW_MS: i64 = 5 * 60 * 1000;
+
+/// Maximum number of frames kept before the oldest are pruned
+const MAX_FRAMES: usize = 5000;
+
+/// Minimum similarity for a frame to count as a match
+const MIN_SIMILARITY: f32 = 0.25;
+
+/// RAG hits considered per requested frame; other memories share the index
+const SEARCH_CANDIDATES_PER_FRAME: usize = 10;
+
+/// Prefix of the RAG document names of frames
+const RAG_DOCUMENT_PREFIX: &str = "Screen";
+
+/// Frame to be recorded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameInput {
+    pub thumbnail_base64: String,
+    pub description: String,
+    pub app_name: Option<String>,
+    pub window_title: Option<String>,
+    pub captured_at: i64, // Unix millis
+}
+
+/// Stored screen frame
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenFrame {
+    pub id: String,
+    pub thumbnail_base64: String,
+    pub description: String,
+    pub app_name: Option<String>,
+    pub window_title: Option<String>,
+    pub captured_at: i64, // Unix millis
+}
+
+/// Search hit with similarity score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenHistoryMatch {
+    pub frame: ScreenFrame,
+    pub score: f32,
+}
+
+/// Time range (Unix millis, end exclusive) implied by words in a query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeRange {
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Extract a time range from phrases such as "yesterday" or "지난주"
+pub fn parse_time_hint(query: &str, now: chrono::DateTime<Local>) -> Option<TimeRange> {
+    let lower = query.to_lowercase();
+    let today = now.date_naive();
+
+    let day_range = |days_ago: i64, span_days: i64| -> Option<TimeRange> {
+        let start_date = today - Duration::days(days_ago);
+        let end_date = start_date + Duration::days(span_days);
+        let start = Local
+            .from_local_datetime(&start_date.and_hms_opt(0, 0, 0)?)
+            .earliest()?
+            .timestamp_millis();
+        let end = Local
+            .from_local_datetime(&end_date.and_hms_opt(0, 0, 0)?)
+            .earliest()?
+            .timestamp_millis();
+        Some(TimeRange { start, end })
+    };
+
+    if lower.contains("day before yesterday") || lower.contains("그저께") || lower.contains("그제") {
+        day_range(2, 1)
+    } else if lower.contains("yesterday") || lower.contains("어제") {
+        day_range(1, 1)
+    } else if lower.contains("today") || lower.contains("오늘") {
+        day_range(0, 1)
+    } else if lower.contains("last week") || lower.contains("지난주") || lower.contains("지난 주") {
+        day_range(7, 8)
+    } else if lower.contains("this week") || lower.contains("이번주") || lower.contains("이번 주") {
+        day_range(6, 7)
+    } else {
+        None
+    }
+}
+
+/// Screenshot history service
+pub struct ScreenHistoryService {
+    db: Arc<Mutex<Database>>,
+    rag: Arc<RagServiceV2>,
+}
+
+impl ScreenHistoryService {
+    pub fn new(
+        db: Arc<Mutex<Database>>,
+        rag: Arc<RagServiceV2>,
+    ) -> Result<Self> {
+        let service = Self { db, rag };
+        service.init_database()?;
+        log::info!("✓ Screen History Service initialized");
+        Ok(service)
+    }
+
+    fn init_database(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let conn = db.conn();
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS screen_history_frames (
+                id TEXT PRIMARY KEY,
+                thumbnail_base64 TEXT NOT NULL,
+                description TEXT NOT NULL,
+                app_name TEXT,
+                window_title TEXT,
+                captured_at INTEGER NOT NULL,
+                episode_ids TEXT NOT NULL DEFAULT '[]'
+            )",
+            [],
+        )?;
+
+        // Frames used to carry their own embedding; vectors now live in the RAG store
+        let has_column = |name: &str| -> Result<bool> {
+            Ok(conn.query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('screen_history_frames') WHERE name = ?1",
+                [name],
+                |row| row.get::<_, i64>(0),
+            )? > 0)
+        };
+        if has_column("embedding")? {
+            conn.execute("ALTER TABLE screen_history_frames DROP COLUMN embedding", [])?;
+        }
+        if !has_column("episode_ids")? {
+            conn.execute(
+                "ALTER TABLE screen_history_frames ADD COLUMN episode_ids TEXT NOT NULL DEFAULT '[]'",
+                [],
+            )?;
+        }
+
+        let _ = conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_screen_history_captured ON screen_history_frames(captured_at)",
+            [],
+        );
+
+        Ok(())
+    }
+
+    /// Text that gets embedded for a frame
+    fn searchable_text(frame: &FrameInput) -> String {
+        let mut parts = Vec::new();
+        if let Some(app) = &frame.app_name {
+            parts.push(app.as_str());
+        }
+        if let Some(title) = &frame.window_title {
+            parts.push(title.as_str());
+        }
+        parts.push(frame.description.as_str());
+        parts.join(" - ")
+    }
+
+    /// RAG document name, e.g. "Screen: Xcode"
+    fn document_name(frame: &FrameInput) -> String {
+        match frame.app_name.as_deref().or(frame.window_title.as_deref()) {
+            Some(label) => format!("{}: {}", RAG_DOCUMENT_PREFIX, label),
+            None => RAG_DOCUMENT_PREFIX.to_string(),
+        }
+    }
+
+    /// Store a frame and index its description in RAG.
+    /// Returns None when an identical recent frame was already stored.
+    ///
+    /// SQLite work runs on the blocking pool (the capture loop is async);
+    /// RAG embeds on the blocking pool as well.
+    pub async fn record_frame(self: &Arc<Self>, frame: FrameInput) -> Result<Option<String>> {
+        let text = Self::searchable_text(&frame);
+        if text.trim().is_empty() {
+            return Ok(None);
+        }
+        let name = Self::document_name(&frame);
+
+        let service = Arc::clone(self);
+        let Some((id, pruned)) = tokio::task::spawn_blocking(move || service.insert_frame(&frame)).await?? else {
+            return Ok(None);
+        };
+
+        let episode_ids = self.rag.ingest_document(&name, &text, Some(SourceKind::Prose)).await?;
+        let service = Arc::clone(self);
+        let frame_id = id.clone();
+        let ids = episode_ids.clone();
+        let linked = tokio::task::spawn_blocking(move || service.link_episodes(&frame_id, &ids)).await??;
+        if !linked {
+            // Pruned or forgotten while it was being indexed
+            self.rag.delete_memories(&episode_ids).await?;
+        }
+
+        if !pruned.is_empty() {
+            self.rag.delete_memories(&pruned).await?;
+        }
+
+        log::debug!("Recorded screen history frame {}", id);
+        Ok(Some(id))
+    }
+
+    /// Insert the frame row unless it repeats a recent one, pruning past `MAX_FRAMES`.
+    /// Returns the new id and the RAG episodes of pruned frames.
+    fn insert_frame(&self, frame: &FrameInput) -> Result<Option<(String, Vec<String>)>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let conn = db.conn();
+
+        // Skip exact repeats (same window, same description) within the dedup window
+        let duplicate: bool = conn.query_row(
+            "SELECT EXISTS(
+                SELECT 1 FROM screen_history_frames
+                WHERE description = ?1
+                  AND COALESCE(window_title, '') = COALESCE(?2, '')
+                  AND captured_at > ?3
+            )",
+            params![&frame.description, &frame.window_title, frame.captured_at - DEDUP_WINDOW_MS],
+            |row| row.get(0),
+        )?;
+        if duplicate {
+            return Ok(None);
+        }
+
+        let id = format!("frame_{}", uuid::Uuid::new_v4());
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT INTO screen_history_frames
+                (id, thumbnail_base64, description, app_name, window_title, captured_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                &id,
+                &frame.thumbnail_base64,
+                &frame.description,
+                &frame.app_name,
+                &frame.window_title,
+                frame.captured_at,
+            ],
+        )?;
+
+        // Retention: keep the newest MAX_FRAMES frames
+        let pruned = select_episode_ids(
+            &tx,
+            "SELECT episode_ids FROM screen_history_frames WHERE id NOT IN (
+                SELECT id FROM screen_history_frames ORDER BY captured_at DESC LIMIT ?1
+            )",
+            params![MAX_FRAMES as i64],
+        )?;
+        tx.execute(
+            "DELETE FROM screen_history_frames WHERE id NOT IN (
+                SELECT id FROM screen_history_frames ORDER BY captured_at DESC LIMIT ?1
+            )",
+            [MAX_FRAMES as i64],
+        )?;
+        tx.commit()?;
+
+        Ok(Some((id, pruned)))
+    }
+
+    /// Remember which RAG episodes index a frame; false if the frame is gone
+    fn link_episodes(&self, frame_id: &str, episode_ids: &[String]) -> Result<bool> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let updated = db.conn().execute(
+            "UPDATE screen_history_frames SET episode_ids = ?1 WHERE id = ?2",
+            params![serde_json::to_string(episode_ids)?, frame_id],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Search frames by meaning, honouring time hints in the query
+    pub async fn search(self: &Arc<Self>, query: &str, limit: usize) -> Result<Vec<ScreenHistoryMatch>> {
+        let range = parse_time_hint(query, Local::now());
+        let candidates = limit.max(1) * SEARCH_CANDIDATES_PER_FRAME;
+
+        let scores: HashMap<String, f32> = self
+            .rag
+            .search_with_scores(query, candidates)
+            .await?
+            .into_iter()
+            .filter(|(_, score)| *score >= MIN_SIMILARITY)
+            .map(|(episode, score)| (episode.id, score))
+            .collect();
+        if scores.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let service = Arc::clone(self);
+        let matches = tokio::task::spawn_blocking(move || service.top_frames(&scores, range, limit)).await??;
+
+        log::info!("Screen history search found {} frames", matches.len());
+        Ok(matches)
+    }
+
+    /// Best frame per RAG hit within `range`; thumbnails are read for the top `limit` only
+    fn top_frames(
+        &self,
+        scores: &HashMap<String, f32>,
+        range: Option<TimeRange>,
+        limit: usize,
+    ) -> Result<Vec<ScreenHistoryMatch>> {
+        let (start, end) = range
+            .map(|r| (r.start, r.end))
+            .unwrap_or((0, i64::MAX));
+
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let conn = db.conn();
+
+        // Score ids only: frame -> best score of its episodes
+        let mut best: HashMap<String, f32> = HashMap::new();
+        let mut stmt = conn.prepare(
+            "SELECT f.id, e.value
+             FROM screen_history_frames f, json_each(f.episode_ids) e
+             WHERE f.captured_at >= ?1 AND f.captured_at < ?2",
+        )?;
+        let rows = stmt.query_map(params![start, end], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        for (frame_id, episode_id) in rows.filter_map(|row| row.ok()) {
+            if let Some(&score) = scores.get(&episode_id) {
+                let entry = best.entry(frame_id).or_insert(score);
+                *entry = entry.max(score);
+            }
+        }
+
+        let mut ranked: Vec<(String, f32)> = best.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranked.truncate(limit);
+
+        let mut stmt = conn.prepare(
+            "SELECT id, thumbnail_base64, description, app_name, window_title, captured_at
+             FROM screen_history_frames WHERE id = ?1",
+        )?;
+        let mut matches = Vec::with_capacity(ranked.len());
+        for (id, score) in ranked {
+            let frame = stmt.query_row([&id], |row| {
+                Ok(ScreenFrame {
+                    id: row.get(0)?,
+                    thumbnail_base64: row.get(1)?,
+                    description: row.get(2)?,
+                    app_name: row.get(3)?,
+                    window_title: row.get(4)?,
+                    captured_at: row.get(5)?,
+                })
+            })?;
+            matches.push(ScreenHistoryMatch { frame, score });
+        }
+        Ok(matches)
+    }
+
+    /// Delete all stored frames and their RAG episodes (for privacy)
+    pub async fn clear(self: &Arc<Self>) -> Result<usize> {
+        let service = Arc::clone(self);
+        let (deleted, episode_ids) = tokio::task::spawn_blocking(move || {
+            let db = service.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            let conn = db.conn();
+            let episode_ids = select_episode_ids(conn, "SELECT episode_ids FROM screen_history_frames", [])?;
+            let deleted = conn.execute("DELETE FROM screen_history_frames", [])?;
+            Ok::<_, anyhow::Error>((deleted, episode_ids))
+        })
+        .await??;
+
+        self.rag.delete_memories(&episode_ids).await?;
+        log::info!("Cleared {} screen history frames", deleted);
+        Ok(deleted)
+    }
+}
+
+/// RAG episode ids of the frames a query selects (its only column is `episode_ids`)
+pub fn select_episode_ids<P: rusqlite::Params>(conn: &Connection, sql: &str, params: P) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(sql)?;
+    let lists = stmt
+        .query_map(params, |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(lists
+        .iter()
+        .flat_map(|json| serde_json::from_str::<Vec<String>>(json).unwrap_or_default())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noon() -> chrono::DateTime<Local> {
+        Local.with_ymd_and_hms(2025, 3, 12, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_time_hint_yesterday() {
+        let range = parse_time_hint("the error dialog from yesterday", noon()).unwrap();
+        let start = Local.with_ymd_and_hms(2025, 3, 11, 0, 0, 0).unwrap().timestamp_millis();
+        let end = Local.with_ymd_and_hms(2025, 3, 12, 0, 0, 0).unwrap().timestamp_millis();
+        assert_eq!(range, TimeRange { start, end });
+        assert_eq!(parse_time_hint("어제 본 에러 창", noon()), Some(range));
+    }
+
+    #[test]
+    fn test_parse_time_hint_other() {
+        let today = parse_time_hint("오늘 본 문서", noon()).unwrap();
+        assert!(today.start <= noon().timestamp_millis() && noon().timestamp_millis() < today.end);
+
+        let week = parse_time_hint("slides from last week", noon()).unwrap();
+        assert!(week.end - week.start > 7 * 24 * 3600 * 1000 - 2 * 3600 * 1000);
+
+        assert_eq!(parse_time_hint("the error dialog", noon()), None);
+    }
+
+    #[test]
+    fn test_searchable_text() {
+        let frame = FrameInput {
+            thumbnail_base64: String::new(),
+            description: "Build failed dialog".to_string(),
+            app_name: Some("Xcode".to_string()),
+            window_title: None,
+            captured_at: 0,
+        };
+        assert_eq!(ScreenHistoryService::searchable_text(&frame), "Xcode - Build failed dialog");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_frames_are_searchable_and_cleared_from_rag() {
+        use crate::services::embedding::UnifiedEmbeddingService;
+        use crate::services::vector_backend::{self, VectorBackendConfig, VectorBackendKind};
+
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let config = VectorBackendConfig { backend: VectorBackendKind::Sqlite, ..Default::default() };
+        vector_backend::save_config(&db, &config).unwrap();
+        let embedding = Arc::new(UnifiedEmbeddingService::new());
+        let rag = Arc::new(RagServiceV2::new_lazy(Arc::clone(&db), embedding, dir.path().join("lance_db")));
+        let history = Arc::new(ScreenHistoryService::new(Arc::clone(&db), Arc::clone(&rag)).unwrap());
+
+        let frame = FrameInput {
+            thumbnail_base64: "thumb".to_string(),
+            description: "Build failed dialog".to_string(),
+            app_name: Some("Xcode".to_string()),
+            window_title: None,
+            captured_at: Local::now().timestamp_millis(),
+        };
+        let id = history.record_frame(frame.clone()).await.unwrap().unwrap();
+        assert!(history.record_frame(frame).await.unwrap().is_none());
+        assert_eq!(rag.get_vector_count().await.unwrap(), 1);
+
+        let matches = history.search("Xcode - Build failed dialog", 5).await.unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].frame.id, id);
+        assert_eq!(matches[0].frame.thumbnail_base64, "thumb");
+
+        assert_eq!(history.clear().await.unwrap(), 1);
+        assert_eq!(rag.get_vector_count().await.unwrap(), 0);
+        assert!(history.search("Xcode - Build failed dialog", 5).await.unwrap().is_empty());
+    }
+}
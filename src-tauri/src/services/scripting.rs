@@ -0,0 +1,743 @@
+//! Scripting Service (v3.9.0)
+//!
+//! Small user automation scripts written in Rhai - a lighter-weight
+//! alternative to WASM plugins.
+//!
+//! Features:
+//! - Sandboxed engine: no file or module access, operation/depth/size limits
+//!   and a wall-clock timeout
+//! - Safe API: `chat(message)`, `search_memory(query[, top_k])`,
+//!   `call_tool(name[, args])` (read-only tools) and `notify(title, body)`;
+//!   `print()` output is kept with the run
+//! - Triggers: manual, global hotkey, schedule (every N minutes) or a webhook
+//!   event (`message_received`, `weekly_review`, ...) whose data is passed
+//!   in as `event`
+//! - Last run result / error persisted per script
+
+#![allow(dead_code)]  // Phase 5: Scripting (editor UI uses a subset)
+
+use crate::database::Database;
+use crate::services::notification::{AppNotification, NotificationService, NotificationSource};
+use crate::services::ollama;
+use crate::services::quick_ask::{normalize_accelerator, validate_accelerator};
+use crate::services::rag_v2::RagServiceV2;
+use crate::services::tool_calling::{ToolCall, ToolService};
+use crate::services::webhook::WebhookPayload;
+use crate::services::webhook_triggers::EVENT_NAMES;
+use anyhow::{anyhow, Result};
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Dynamic, Engine, EvalAltResult, Scope};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Engine operations per run (a tight loop hits this in well under a second)
+const MAX_OPERATIONS: u64 = 5_000_000;
+
+/// Wall-clock limit per run, including time spent in API calls
+const MAX_RUN_SECS: u64 = 300;
+
+const MAX_SOURCE_BYTES: usize = 64 * 1024;
+const MAX_NAME_CHARS: usize = 100;
+
+/// `print()` lines kept per run
+const MAX_LOG_LINES: usize = 200;
+
+/// Schedules run at most this often
+const MIN_INTERVAL_MINUTES: u64 = 1;
+
+const MAX_TOP_K: i64 = 20;
+
+/// The only tools unattended scripts may call: read-only, no files written,
+/// no apps or processes driven, no arbitrary network requests.
+/// Anything not listed (including plugin and OpenAPI tools) is rejected.
+const ALLOWED_TOOLS: &[&str] = &[
+    "web_search",
+    "read_file",
+    "terminal_history",
+    "get_system_info",
+    "calculate",
+    "translate",
+];
+
+fn check_tool_allowed(name: &str) -> Result<(), Box<EvalAltResult>> {
+    if ALLOWED_TOOLS.contains(&name) {
+        Ok(())
+    } else {
+        Err(format!("Tool '{}' is not available to scripts (allowed: {})", name, ALLOWED_TOOLS.join(", ")).into())
+    }
+}
+
+/// What starts a script (besides running it by hand)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScriptTrigger {
+    Manual,
+    Hotkey { accelerator: String },
+    Schedule { interval_minutes: u64 },
+    /// A webhook event name from `webhook_triggers::EVENT_NAMES`
+    Webhook { event: String },
+}
+
+impl ScriptTrigger {
+    fn validate(&self) -> Result<()> {
+        match self {
+            ScriptTrigger::Manual => Ok(()),
+            ScriptTrigger::Hotkey { accelerator } => validate_accelerator(accelerator),
+            ScriptTrigger::Schedule { interval_minutes } => {
+                if *interval_minutes < MIN_INTERVAL_MINUTES {
+                    return Err(anyhow!("Schedule must be at least {} minute", MIN_INTERVAL_MINUTES));
+                }
+                Ok(())
+            }
+            ScriptTrigger::Webhook { event } => {
+                if !EVENT_NAMES.contains(&event.as_str()) {
+                    return Err(anyhow!("Unknown event '{}' (one of: {})", event, EVENT_NAMES.join(", ")));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A saved script
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Script {
+    pub id: String,
+    pub name: String,
+    pub source: String,
+    pub trigger: ScriptTrigger,
+    pub enabled: bool,
+    pub created_at: i64, // Unix millis
+    pub updated_at: i64,
+    pub last_run_at: Option<i64>,
+    pub last_result: Option<String>,
+    pub last_error: Option<String>,
+}
+
+/// Script as sent by the editor (`id: None` creates a new one)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptInput {
+    pub id: Option<String>,
+    pub name: String,
+    pub source: String,
+    pub trigger: ScriptTrigger,
+    pub enabled: bool,
+}
+
+/// Outcome of one run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptRun {
+    pub script_id: Option<String>,
+    pub success: bool,
+    /// Value of the script's last expression (strings as-is, anything else as JSON)
+    pub result: Option<String>,
+    pub error: Option<String>,
+    pub logs: Vec<String>,
+    pub started_at: i64,
+    pub duration_ms: u64,
+}
+
+/// What scripts can reach. Called on a blocking thread.
+pub trait ScriptHost: Send + Sync {
+    /// One chat turn through the persona + RAG pipeline (not saved as a conversation)
+    fn chat(&self, message: &str) -> Result<String>;
+
+    /// Episodic memories most relevant to the query
+    fn search_memory(&self, query: &str, top_k: usize) -> Result<serde_json::Value>;
+
+    fn call_tool(&self, name: &str, arguments: serde_json::Value) -> Result<serde_json::Value>;
+
+    fn notify(&self, title: &str, body: &str) -> Result<()>;
+}
+
+/// The app's services, bridged from the blocking script thread onto the async runtime
+pub struct AppScriptHost {
+    db: Arc<Mutex<Database>>,
+    rag: Arc<RagServiceV2>,
+    tools: Arc<ToolService>,
+    notifications: Arc<NotificationService>,
+}
+
+impl AppScriptHost {
+    pub fn new(
+        db: Arc<Mutex<Database>>,
+        rag: Arc<RagServiceV2>,
+        tools: Arc<ToolService>,
+        notifications: Arc<NotificationService>,
+    ) -> Self {
+        Self { db, rag, tools, notifications }
+    }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Handle::current().block_on(future)
+    }
+}
+
+impl ScriptHost for AppScriptHost {
+    fn chat(&self, message: &str) -> Result<String> {
+        let cited = Self::block_on(ollama::generate_cited_response_for_conversation(
+            message,
+            None,
+            Some(Arc::clone(&self.rag)),
+            Some(&*self.db),
+        ))
+        .map_err(|e| anyhow!(e))?;
+        Ok(cited.response)
+    }
+
+    fn search_memory(&self, query: &str, top_k: usize) -> Result<serde_json::Value> {
+        let results = Self::block_on(self.rag.search_with_scores(query, top_k))?;
+        Ok(results
+            .into_iter()
+            .map(|(episode, score)| {
+                serde_json::json!({
+                    "id": episode.id,
+                    "score": score,
+                    "user_message": episode.user_message,
+                    "ai_response": episode.ai_response,
+                    "created_at": episode.created_at,
+                })
+            })
+            .collect())
+    }
+
+    fn call_tool(&self, name: &str, arguments: serde_json::Value) -> Result<serde_json::Value> {
+        let call = ToolCall { tool_name: name.to_string(), arguments };
+        let result = Self::block_on(self.tools.execute_tool(&call));
+        if result.success {
+            Ok(result.result)
+        } else {
+            Err(anyhow!(result.error.unwrap_or_else(|| format!("Tool '{}' failed", name))))
+        }
+    }
+
+    fn notify(&self, title: &str, body: &str) -> Result<()> {
+        let notification = AppNotification::new(NotificationSource::Script, title, body);
+        Self::block_on(self.notifications.notify(notification))?;
+        Ok(())
+    }
+}
+
+pub fn init_tables(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS scripts (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            source TEXT NOT NULL,
+            trigger_spec TEXT NOT NULL,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            last_run_at INTEGER,
+            last_result TEXT,
+            last_error TEXT
+        );",
+    )?;
+    Ok(())
+}
+
+const SCRIPT_COLUMNS: &str =
+    "id, name, source, trigger_spec, enabled, created_at, updated_at, last_run_at, last_result, last_error";
+
+fn row_to_script(row: &rusqlite::Row) -> rusqlite::Result<Script> {
+    let trigger: String = row.get(3)?;
+    Ok(Script {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        source: row.get(2)?,
+        trigger: serde_json::from_str(&trigger).unwrap_or(ScriptTrigger::Manual),
+        enabled: row.get::<_, i64>(4)? != 0,
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+        last_run_at: row.get(7)?,
+        last_result: row.get(8)?,
+        last_error: row.get(9)?,
+    })
+}
+
+fn list_scripts(conn: &Connection) -> Result<Vec<Script>> {
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM scripts ORDER BY name COLLATE NOCASE", SCRIPT_COLUMNS))?;
+    let scripts = stmt.query_map([], row_to_script)?.collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(scripts)
+}
+
+fn get_script(conn: &Connection, id: &str) -> Result<Option<Script>> {
+    Ok(conn
+        .query_row(&format!("SELECT {} FROM scripts WHERE id = ?1", SCRIPT_COLUMNS), [id], row_to_script)
+        .optional()?)
+}
+
+/// Enabled scripts on a schedule whose interval has passed since their last run (or last edit)
+pub fn due_scheduled(scripts: &[Script], now: i64) -> Vec<String> {
+    scripts
+        .iter()
+        .filter(|script| script.enabled)
+        .filter_map(|script| match script.trigger {
+            ScriptTrigger::Schedule { interval_minutes } => {
+                let since = script.last_run_at.unwrap_or(script.updated_at);
+                let due = since + (interval_minutes as i64) * 60_000 <= now;
+                due.then(|| script.id.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn script_error(e: impl std::fmt::Display) -> Box<EvalAltResult> {
+    e.to_string().into()
+}
+
+/// Sandboxed engine exposing the host API; `print`/`debug` go to `logs`
+fn build_engine(host: Arc<dyn ScriptHost>, logs: Arc<Mutex<Vec<String>>>) -> Engine {
+    let mut engine = Engine::new();
+
+    // No `import` of files; bounded work and memory
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(256 * 1024);
+    engine.set_max_array_size(10_000);
+    engine.set_max_map_size(10_000);
+
+    let deadline = Instant::now() + Duration::from_secs(MAX_RUN_SECS);
+    engine.on_progress(move |_| (Instant::now() > deadline).then(|| Dynamic::from("timeout")));
+
+    let print_logs = Arc::clone(&logs);
+    engine.on_print(move |text| {
+        if let Ok(mut logs) = print_logs.lock() {
+            if logs.len() < MAX_LOG_LINES {
+                logs.push(text.to_string());
+            }
+        }
+    });
+    engine.on_debug(move |text, _source, _position| {
+        if let Ok(mut logs) = logs.lock() {
+            if logs.len() < MAX_LOG_LINES {
+                logs.push(text.to_string());
+            }
+        }
+    });
+
+    let api = Arc::clone(&host);
+    engine.register_fn("chat", move |message: &str| -> Result<String, Box<EvalAltResult>> {
+        api.chat(message).map_err(script_error)
+    });
+
+    let api = Arc::clone(&host);
+    engine.register_fn("search_memory", move |query: &str, top_k: i64| -> Result<Dynamic, Box<EvalAltResult>> {
+        let hits = api.search_memory(query, top_k.clamp(1, MAX_TOP_K) as usize).map_err(script_error)?;
+        rhai::serde::to_dynamic(hits)
+    });
+    let api = Arc::clone(&host);
+    engine.register_fn("search_memory", move |query: &str| -> Result<Dynamic, Box<EvalAltResult>> {
+        let hits = api.search_memory(query, 5).map_err(script_error)?;
+        rhai::serde::to_dynamic(hits)
+    });
+
+    let api = Arc::clone(&host);
+    engine.register_fn("call_tool", move |name: &str, args: rhai::Map| -> Result<Dynamic, Box<EvalAltResult>> {
+        check_tool_allowed(name)?;
+        let arguments: serde_json::Value = rhai::serde::from_dynamic(&Dynamic::from_map(args))?;
+        let result = api.call_tool(name, arguments).map_err(script_error)?;
+        rhai::serde::to_dynamic(result)
+    });
+    let api = Arc::clone(&host);
+    engine.register_fn("call_tool", move |name: &str| -> Result<Dynamic, Box<EvalAltResult>> {
+        check_tool_allowed(name)?;
+        let result = api.call_tool(name, serde_json::json!({})).map_err(script_error)?;
+        rhai::serde::to_dynamic(result)
+    });
+
+    engine.register_fn("notify", move |title: &str, body: &str| -> Result<(), Box<EvalAltResult>> {
+        host.notify(title, body).map_err(script_error)
+    });
+
+    engine
+}
+
+/// Script result as shown in the run log
+fn describe_result(value: &Dynamic) -> Option<String> {
+    if value.is_unit() {
+        return None;
+    }
+    if value.is_string() {
+        return Some(value.to_string());
+    }
+    match rhai::serde::from_dynamic::<serde_json::Value>(value) {
+        Ok(json) => Some(json.to_string()),
+        Err(_) => Some(value.to_string()),
+    }
+}
+
+/// Compile without running, for the editor
+pub fn check_syntax(source: &str) -> Result<()> {
+    let mut engine = Engine::new();
+    engine.set_max_expr_depths(64, 32);
+    engine.compile(source).map_err(|e| anyhow!("{}", e))?;
+    Ok(())
+}
+
+/// Run `source` with a host. `event` is available to the script as `event`.
+pub fn execute(host: Arc<dyn ScriptHost>, source: &str, event: Option<serde_json::Value>) -> ScriptRun {
+    let started_at = chrono::Utc::now().timestamp_millis();
+    let start = Instant::now();
+    let logs = Arc::new(Mutex::new(Vec::new()));
+    let engine = build_engine(host, Arc::clone(&logs));
+
+    let outcome = (|| -> Result<Dynamic, Box<EvalAltResult>> {
+        let ast = engine.compile(source)?;
+        let mut scope = Scope::new();
+        let event = match event {
+            Some(event) => rhai::serde::to_dynamic(event)?,
+            None => Dynamic::UNIT,
+        };
+        scope.push_constant("event", event);
+        engine.eval_ast_with_scope::<Dynamic>(&mut scope, &ast)
+    })();
+
+    let logs = logs.lock().map(|l| l.clone()).unwrap_or_default();
+    let duration_ms = start.elapsed().as_millis() as u64;
+    match outcome {
+        Ok(value) => ScriptRun {
+            script_id: None,
+            success: true,
+            result: describe_result(&value),
+            error: None,
+            logs,
+            started_at,
+            duration_ms,
+        },
+        Err(e) => {
+            let error = match *e {
+                EvalAltResult::ErrorTerminated(..) => format!("Script stopped after {}s", MAX_RUN_SECS),
+                EvalAltResult::ErrorTooManyOperations(..) => "Script exceeded its operation limit".to_string(),
+                other => other.to_string(),
+            };
+            ScriptRun {
+                script_id: None,
+                success: false,
+                result: None,
+                error: Some(error),
+                logs,
+                started_at,
+                duration_ms,
+            }
+        }
+    }
+}
+
+/// Scripting service
+pub struct ScriptingService {
+    db: Arc<Mutex<Database>>,
+    host: Mutex<Option<Arc<dyn ScriptHost>>>,
+    running: Mutex<HashSet<String>>,
+}
+
+impl ScriptingService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        {
+            let db_guard = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            init_tables(db_guard.conn())?;
+        }
+
+        log::info!("✓ Scripting Service initialized");
+        Ok(Self {
+            db,
+            host: Mutex::new(None),
+            running: Mutex::new(HashSet::new()),
+        })
+    }
+
+    /// Attach the API scripts call into
+    pub fn set_host(&self, host: Arc<dyn ScriptHost>) {
+        if let Ok(mut slot) = self.host.lock() {
+            *slot = Some(host);
+        }
+    }
+
+    fn host(&self) -> Result<Arc<dyn ScriptHost>> {
+        self.host
+            .lock()
+            .map_err(|e| anyhow!("Lock error: {}", e))?
+            .clone()
+            .ok_or_else(|| anyhow!("Scripting API is not ready yet"))
+    }
+
+    pub fn list(&self) -> Result<Vec<Script>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        list_scripts(db.conn())
+    }
+
+    pub fn get(&self, id: &str) -> Result<Script> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        get_script(db.conn(), id)?.ok_or_else(|| anyhow!("Script not found: {}", id))
+    }
+
+    /// Create or update a script. It must compile, and hotkeys must not be taken by another script.
+    pub fn save(&self, input: ScriptInput) -> Result<Script> {
+        let name = input.name.trim();
+        if name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+            return Err(anyhow!("Script name must be 1-{} characters", MAX_NAME_CHARS));
+        }
+        if input.source.len() > MAX_SOURCE_BYTES {
+            return Err(anyhow!("Script is too long (max {} KB)", MAX_SOURCE_BYTES / 1024));
+        }
+        check_syntax(&input.source)?;
+        input.trigger.validate()?;
+
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let conn = db.conn();
+
+        if let (ScriptTrigger::Hotkey { accelerator }, true) = (&input.trigger, input.enabled) {
+            let wanted = normalize_accelerator(accelerator);
+            let taken = list_scripts(conn)?.into_iter().find(|other| {
+                other.enabled
+                    && Some(&other.id) != input.id.as_ref()
+                    && matches!(&other.trigger, ScriptTrigger::Hotkey { accelerator } if normalize_accelerator(accelerator) == wanted)
+            });
+            if let Some(other) = taken {
+                return Err(anyhow!("Hotkey {} is already used by '{}'", accelerator, other.name));
+            }
+        }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let trigger = serde_json::to_string(&input.trigger)?;
+        let id = match &input.id {
+            Some(id) => {
+                let updated = conn.execute(
+                    "UPDATE scripts SET name = ?1, source = ?2, trigger_spec = ?3, enabled = ?4, updated_at = ?5
+                     WHERE id = ?6",
+                    params![name, input.source, trigger, input.enabled as i64, now, id],
+                )?;
+                if updated == 0 {
+                    return Err(anyhow!("Script not found: {}", id));
+                }
+                id.clone()
+            }
+            None => {
+                let id = format!("script_{}", uuid::Uuid::new_v4());
+                conn.execute(
+                    "INSERT INTO scripts (id, name, source, trigger_spec, enabled, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)",
+                    params![id, name, input.source, trigger, input.enabled as i64, now],
+                )?;
+                id
+            }
+        };
+
+        get_script(conn, &id)?.ok_or_else(|| anyhow!("Script not found: {}", id))
+    }
+
+    pub fn delete(&self, id: &str) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let deleted = db.conn().execute("DELETE FROM scripts WHERE id = ?1", [id])?;
+        if deleted == 0 {
+            return Err(anyhow!("Script not found: {}", id));
+        }
+        Ok(())
+    }
+
+    /// (script id, accelerator) of enabled hotkey scripts
+    pub fn hotkeys(&self) -> Result<Vec<(String, String)>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .filter(|script| script.enabled)
+            .filter_map(|script| match script.trigger {
+                ScriptTrigger::Hotkey { accelerator } => Some((script.id, accelerator)),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Enabled scripts bound to a webhook event
+    pub fn scripts_for_event(&self, event: &str) -> Result<Vec<String>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .filter(|script| script.enabled && matches!(&script.trigger, ScriptTrigger::Webhook { event: e } if e == event))
+            .map(|script| script.id)
+            .collect())
+    }
+
+    pub fn due_scheduled(&self, now: i64) -> Result<Vec<String>> {
+        Ok(due_scheduled(&self.list()?, now))
+    }
+
+    /// Run a saved script and record the outcome (blocking). A script never runs twice at once.
+    pub fn run(&self, id: &str, event: Option<serde_json::Value>) -> Result<ScriptRun> {
+        let script = self.get(id)?;
+        let host = self.host()?;
+
+        {
+            let mut running = self.running.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            if !running.insert(script.id.clone()) {
+                return Err(anyhow!("'{}' is already running", script.name));
+            }
+        }
+
+        let mut run = execute(host, &script.source, event);
+        run.script_id = Some(script.id.clone());
+
+        if let Ok(mut running) = self.running.lock() {
+            running.remove(&script.id);
+        }
+
+        match &run.error {
+            Some(e) => log::warn!("Script '{}' failed: {}", script.name, e),
+            None => log::info!("Script '{}' finished in {}ms", script.name, run.duration_ms),
+        }
+
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn().execute(
+            "UPDATE scripts SET last_run_at = ?1, last_result = ?2, last_error = ?3 WHERE id = ?4",
+            params![run.started_at, run.result, run.error, script.id],
+        )?;
+
+        Ok(run)
+    }
+
+    /// `run` on a blocking thread
+    pub async fn run_async(self: &Arc<Self>, id: String, event: Option<serde_json::Value>) -> Result<ScriptRun> {
+        let service = Arc::clone(self);
+        tokio::task::spawn_blocking(move || service.run(&id, event)).await?
+    }
+
+    /// Start every script bound to a webhook event, without waiting for them
+    pub fn dispatch_event(self: &Arc<Self>, payload: &WebhookPayload) {
+        let ids = match self.scripts_for_event(&payload.event) {
+            Ok(ids) => ids,
+            Err(e) => {
+                log::warn!("Failed to load scripts for {}: {}", payload.event, e);
+                return;
+            }
+        };
+
+        let event = serde_json::json!({
+            "name": payload.event,
+            "data": payload.data,
+            "timestamp": payload.timestamp,
+        });
+        for id in ids {
+            let service = Arc::clone(self);
+            let event = event.clone();
+            tokio::spawn(async move {
+                if let Err(e) = service.run_async(id, Some(event)).await {
+                    log::warn!("Failed to run event script: {}", e);
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Echoes calls back so scripts can be checked without Ollama or tools
+    struct FakeHost {
+        notified: Mutex<Vec<String>>,
+    }
+
+    impl ScriptHost for FakeHost {
+        fn chat(&self, message: &str) -> Result<String> {
+            Ok(format!("echo: {}", message))
+        }
+
+        fn search_memory(&self, query: &str, top_k: usize) -> Result<serde_json::Value> {
+            Ok(serde_json::json!([{ "user_message": query, "score": 0.9, "top_k": top_k }]))
+        }
+
+        fn call_tool(&self, name: &str, arguments: serde_json::Value) -> Result<serde_json::Value> {
+            Ok(serde_json::json!({ "tool": name, "arguments": arguments }))
+        }
+
+        fn notify(&self, title: &str, body: &str) -> Result<()> {
+            self.notified.lock().unwrap().push(format!("{}: {}", title, body));
+            Ok(())
+        }
+    }
+
+    fn fake_host() -> Arc<FakeHost> {
+        Arc::new(FakeHost { notified: Mutex::new(Vec::new()) })
+    }
+
+    #[test]
+    fn test_script_uses_api_and_event() {
+        let host = fake_host();
+        let source = r#"
+            let hits = search_memory(event.data.message, 3);
+            print(`found ${hits.len()}`);
+            let tool = call_tool("calculate", #{ expression: "1+1" });
+            notify("Done", chat(hits[0].user_message));
+            tool.arguments.expression
+        "#;
+        let event = serde_json::json!({ "name": "message_sent", "data": { "message": "hello" } });
+
+        let run = execute(host.clone(), source, Some(event));
+        assert!(run.success, "{:?}", run.error);
+        assert_eq!(run.result.as_deref(), Some("1+1"));
+        assert_eq!(run.logs, vec!["found 1"]);
+        assert_eq!(*host.notified.lock().unwrap(), vec!["Done: echo: hello"]);
+    }
+
+    #[test]
+    fn test_sandbox_limits() {
+        let run = execute(fake_host(), "loop { }", None);
+        assert!(!run.success);
+        assert!(run.error.unwrap().contains("operation limit"));
+
+        let run = execute(fake_host(), r#"import "secrets" as s;"#, None);
+        assert!(!run.success);
+
+        let run = execute(fake_host(), r#"call_tool("delete_file", #{ path: "/tmp/x" })"#, None);
+        assert!(run.error.unwrap().contains("not available to scripts"));
+    }
+
+    #[test]
+    fn test_only_read_only_tools_are_callable() {
+        for tool in ["run_shortcut", "run_powershell_script", "applescript", "manage_windows", "sql_query", "fetch_url", "mouse_click"] {
+            let run = execute(fake_host(), &format!(r#"call_tool("{}")"#, tool), None);
+            assert!(!run.success, "{} should be rejected", tool);
+            assert!(run.error.unwrap().contains("not available to scripts"));
+        }
+
+        let run = execute(fake_host(), r#"call_tool("get_system_info").tool"#, None);
+        assert!(run.success, "{:?}", run.error);
+        assert_eq!(run.result.as_deref(), Some("get_system_info"));
+    }
+
+    #[test]
+    fn test_save_validates_and_schedules() {
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let service = ScriptingService::new(db).unwrap();
+
+        let input = |name: &str, source: &str, trigger: ScriptTrigger| ScriptInput {
+            id: None,
+            name: name.to_string(),
+            source: source.to_string(),
+            trigger,
+            enabled: true,
+        };
+        assert!(service.save(input("Broken", "let x = ;", ScriptTrigger::Manual)).is_err());
+        assert!(service
+            .save(input("Bad event", "1", ScriptTrigger::Webhook { event: "nope".to_string() }))
+            .is_err());
+
+        let hotkey = ScriptTrigger::Hotkey { accelerator: "Alt+Shift+J".to_string() };
+        service.save(input("Journal", "1", hotkey)).unwrap();
+        let taken = ScriptTrigger::Hotkey { accelerator: "alt+shift+j".to_string() };
+        assert!(service.save(input("Other", "2", taken)).is_err());
+
+        let scheduled = service
+            .save(input("Digest", "3", ScriptTrigger::Schedule { interval_minutes: 60 }))
+            .unwrap();
+        let scripts = service.list().unwrap();
+        assert!(due_scheduled(&scripts, scheduled.updated_at + 59 * 60_000).is_empty());
+        assert_eq!(due_scheduled(&scripts, scheduled.updated_at + 60 * 60_000), vec![scheduled.id]);
+    }
+}
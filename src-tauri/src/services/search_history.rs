@@ -0,0 +1,330 @@
+//! Search History (v3.9.0)
+//!
+//! Persists web searches so earlier findings can be looked up again instead of
+//! re-searching, by the user in the history list or by the assistant through RAG.
+//!
+//! Features:
+//! - `search_history` table: query, engines that answered, results (JSON)
+//! - Recent list and keyword search over queries, titles and snippets
+//! - Each search ingested as a RAG document ("Web search: <query>") once RAG is attached
+//! - Delete / clear, removing the RAG copies too
+
+#![allow(dead_code)]  // Phase 9: Internet Access (opt-in feature)
+
+use crate::database::Database;
+use crate::services::chunker::SourceKind;
+use crate::services::rag_v2::RagServiceV2;
+use crate::services::screen_history::select_episode_ids;
+use crate::services::web_search::SearchResult;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Stored web search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHistoryEntry {
+    pub id: String,
+    pub query: String,
+    /// Engines that contributed results, in fallback order
+    pub engines: Vec<String>,
+    pub results: Vec<SearchResult>,
+    pub created_at: i64,
+}
+
+/// Persisted web search history
+pub struct SearchHistoryService {
+    db: Arc<Mutex<Database>>,
+    /// Attached once RAG is up (it starts after the web tools)
+    rag: OnceLock<Arc<RagServiceV2>>,
+}
+
+impl SearchHistoryService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        {
+            let db = db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            db.conn().execute(
+                "CREATE TABLE IF NOT EXISTS search_history (
+                    id TEXT PRIMARY KEY,
+                    query TEXT NOT NULL,
+                    engines TEXT NOT NULL,
+                    results TEXT NOT NULL,
+                    created_at INTEGER NOT NULL,
+                    episode_ids TEXT NOT NULL DEFAULT '[]'
+                )",
+                [],
+            )?;
+            let has_episode_ids: bool = db.conn().query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('search_history') WHERE name = 'episode_ids'",
+                [],
+                |row| row.get(0),
+            )?;
+            if !has_episode_ids {
+                db.conn().execute(
+                    "ALTER TABLE search_history ADD COLUMN episode_ids TEXT NOT NULL DEFAULT '[]'",
+                    [],
+                )?;
+            }
+            db.conn().execute(
+                "CREATE INDEX IF NOT EXISTS idx_search_history_created ON search_history(created_at DESC)",
+                [],
+            )?;
+        }
+
+        log::info!("✓ Search History Service initialized");
+        Ok(Self {
+            db,
+            rag: OnceLock::new(),
+        })
+    }
+
+    /// Index future searches into RAG
+    pub fn attach_rag(&self, rag: Arc<RagServiceV2>) {
+        if self.rag.set(rag).is_err() {
+            log::warn!("Search history already has a RAG service attached");
+        }
+    }
+
+    /// Store a search; searches without results are not kept
+    pub fn record(&self, query: &str, results: &[SearchResult]) -> Result<Option<String>> {
+        if results.is_empty() {
+            return Ok(None);
+        }
+
+        let mut engines: Vec<String> = Vec::new();
+        for result in results {
+            if !engines.contains(&result.source) {
+                engines.push(result.source.clone());
+            }
+        }
+
+        let id = uuid::Uuid::new_v4().to_string();
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            db.conn().execute(
+                "INSERT INTO search_history (id, query, engines, results, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                rusqlite::params![
+                    id,
+                    query,
+                    serde_json::to_string(&engines)?,
+                    serde_json::to_string(results)?,
+                    chrono::Utc::now().timestamp(),
+                ],
+            )?;
+        }
+
+        if let Some(rag) = self.rag.get() {
+            // Embedding can take a moment; the search result shouldn't wait for it
+            let rag = Arc::clone(rag);
+            let db = Arc::clone(&self.db);
+            let entry_id = id.clone();
+            let name = format!("Web search: {}", query);
+            let document = rag_document(query, results);
+            tokio::spawn(async move {
+                if let Err(e) = index_entry(rag, db, entry_id, &name, &document).await {
+                    log::warn!("Failed to index '{}' into RAG: {}", name, e);
+                }
+            });
+        }
+
+        Ok(Some(id))
+    }
+
+    /// Most recent searches first
+    pub fn get_recent(&self, limit: usize) -> Result<Vec<SearchHistoryEntry>> {
+        self.query_entries(
+            "SELECT id, query, engines, results, created_at FROM search_history
+             ORDER BY created_at DESC LIMIT ?1",
+            rusqlite::params![limit as i64],
+        )
+    }
+
+    /// Searches whose query, result titles or snippets contain `text`
+    pub fn search(&self, text: &str, limit: usize) -> Result<Vec<SearchHistoryEntry>> {
+        let escaped = text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        let pattern = format!("%{}%", escaped);
+        self.query_entries(
+            "SELECT id, query, engines, results, created_at FROM search_history
+             WHERE query LIKE ?1 ESCAPE '\\' OR results LIKE ?1 ESCAPE '\\'
+             ORDER BY created_at DESC LIMIT ?2",
+            rusqlite::params![pattern, limit as i64],
+        )
+    }
+
+    fn query_entries(&self, sql: &str, params: &[&dyn rusqlite::ToSql]) -> Result<Vec<SearchHistoryEntry>> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(sql)?;
+        let rows = stmt.query_map(params, |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, i64>(4)?,
+            ))
+        })?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            let (id, query, engines, results, created_at) = row?;
+            entries.push(SearchHistoryEntry {
+                id,
+                query,
+                engines: serde_json::from_str(&engines).unwrap_or_default(),
+                results: serde_json::from_str(&results).unwrap_or_default(),
+                created_at,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// Delete a search and its RAG copy
+    pub async fn delete(self: &Arc<Self>, id: &str) -> Result<bool> {
+        let service = Arc::clone(self);
+        let id = id.to_string();
+        let (removed, episode_ids) = tokio::task::spawn_blocking(move || {
+            let db = service.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            let episode_ids = select_episode_ids(db.conn(), "SELECT episode_ids FROM search_history WHERE id = ?1", [&id])?;
+            let removed = db.conn().execute("DELETE FROM search_history WHERE id = ?1", [&id])?;
+            Ok::<_, anyhow::Error>((removed, episode_ids))
+        })
+        .await??;
+
+        self.forget_episodes(&episode_ids).await?;
+        Ok(removed > 0)
+    }
+
+    /// Delete every search and their RAG copies
+    pub async fn clear(self: &Arc<Self>) -> Result<usize> {
+        let service = Arc::clone(self);
+        let (removed, episode_ids) = tokio::task::spawn_blocking(move || {
+            let db = service.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            let episode_ids = select_episode_ids(db.conn(), "SELECT episode_ids FROM search_history", [])?;
+            let removed = db.conn().execute("DELETE FROM search_history", [])?;
+            Ok::<_, anyhow::Error>((removed, episode_ids))
+        })
+        .await??;
+
+        self.forget_episodes(&episode_ids).await?;
+        Ok(removed)
+    }
+
+    /// Episodes only exist once RAG is attached
+    async fn forget_episodes(&self, episode_ids: &[String]) -> Result<()> {
+        if let Some(rag) = self.rag.get() {
+            rag.delete_memories(episode_ids).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Ingest a search into RAG and link the episodes to its row
+///
+/// If the row was deleted while indexing, the episodes are deleted again.
+async fn index_entry(
+    rag: Arc<RagServiceV2>,
+    db: Arc<Mutex<Database>>,
+    entry_id: String,
+    name: &str,
+    document: &str,
+) -> Result<()> {
+    let episode_ids = rag.ingest_document(name, document, Some(SourceKind::Prose)).await?;
+    let episode_json = serde_json::to_string(&episode_ids)?;
+    let linked = tokio::task::spawn_blocking(move || {
+        let db = db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        Ok::<_, anyhow::Error>(db.conn().execute(
+            "UPDATE search_history SET episode_ids = ?1 WHERE id = ?2",
+            rusqlite::params![episode_json, entry_id],
+        )?)
+    })
+    .await??;
+
+    if linked == 0 {
+        rag.delete_memories(&episode_ids).await?;
+    }
+    Ok(())
+}
+
+/// RAG text for a search: the query and each result as a short paragraph
+fn rag_document(query: &str, results: &[SearchResult]) -> String {
+    let mut document = format!("Web search for \"{}\":\n\n", query);
+    for result in results {
+        document.push_str(&format!("{}\n{}\n{}\n\n", result.title, result.url, result.snippet));
+    }
+    document.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(title: &str, source: &str) -> SearchResult {
+        SearchResult {
+            title: title.to_string(),
+            url: format!("https://example.com/{}", title.to_lowercase().replace(' ', "-")),
+            snippet: format!("About {}", title),
+            source: source.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_and_search() {
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let history = Arc::new(SearchHistoryService::new(db).unwrap());
+
+        assert!(history.record("nothing", &[]).unwrap().is_none());
+        let id = history
+            .record("rust async", &[result("Tokio Tutorial", "searx"), result("Async Book", "brave")])
+            .unwrap()
+            .unwrap();
+        history.record("sourdough", &[result("Starter Guide", "duckduckgo")]).unwrap();
+
+        let recent = history.get_recent(10).unwrap();
+        assert_eq!(recent.len(), 2);
+
+        let found = history.search("tokio", 10).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, id);
+        assert_eq!(found[0].engines, vec!["searx", "brave"]);
+        assert_eq!(found[0].results.len(), 2);
+        assert!(history.search("100%", 10).unwrap().is_empty());
+
+        assert!(history.delete(&id).await.unwrap());
+        assert_eq!(history.clear().await.unwrap(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_delete_removes_rag_copies() {
+        use crate::services::embedding::UnifiedEmbeddingService;
+        use crate::services::vector_backend::{self, VectorBackendConfig, VectorBackendKind};
+
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let config = VectorBackendConfig { backend: VectorBackendKind::Sqlite, ..Default::default() };
+        vector_backend::save_config(&db, &config).unwrap();
+        let embedding = Arc::new(UnifiedEmbeddingService::new());
+        let rag = Arc::new(RagServiceV2::new_lazy(Arc::clone(&db), embedding, dir.path().join("lance_db")));
+        let history = Arc::new(SearchHistoryService::new(Arc::clone(&db)).unwrap());
+        history.attach_rag(Arc::clone(&rag));
+
+        let id = history.record("rust async", &[result("Tokio Tutorial", "searx")]).unwrap().unwrap();
+        history.record("sourdough", &[result("Starter Guide", "duckduckgo")]).unwrap();
+
+        // Indexing runs in the background
+        for _ in 0..100 {
+            let linked: i64 = db.lock().unwrap().conn()
+                .query_row("SELECT COUNT(*) FROM search_history WHERE episode_ids != '[]'", [], |row| row.get(0))
+                .unwrap();
+            if linked == 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert_eq!(rag.get_vector_count().await.unwrap(), 2);
+
+        assert!(history.delete(&id).await.unwrap());
+        assert_eq!(rag.get_vector_count().await.unwrap(), 1);
+        assert_eq!(history.clear().await.unwrap(), 1);
+        assert_eq!(rag.get_vector_count().await.unwrap(), 0);
+    }
+}
@@ -0,0 +1,370 @@
+//! Search Providers (v3.9.0)
+//!
+//! Backends behind `WebSearchService`, tried in order with automatic fallback.
+//!
+//! Features:
+//! - `SearchProvider` trait: one implementation per engine
+//! - SearxNG / SearX (self-hosted or public instance, optional bearer key)
+//! - Brave Search API and Tavily (API keys from the secrets vault)
+//! - DuckDuckGo Instant Answer API (no key, no tracking)
+//! - Rate limiting (HTTP 429) reported separately so the caller can cool the engine down
+//!
+//! Every request goes through `network_policy` (offline mode, per-domain limits, user agent).
+
+#![allow(dead_code)]  // Phase 9: Internet Access (opt-in feature)
+
+use reqwest::header::USER_AGENT;
+use reqwest::{Client, RequestBuilder, Response, StatusCode, Url};
+use serde::Deserialize;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::network_policy;
+use super::secrets::{self, SecretsService};
+use super::web_search::{SearchEngine, SearchResult};
+
+const BRAVE_ENDPOINT: &str = "https://api.search.brave.com/res/v1/web/search";
+const TAVILY_ENDPOINT: &str = "https://api.tavily.com/search";
+
+/// Why a provider returned no results
+#[derive(Debug)]
+pub enum ProviderError {
+    /// HTTP 429; `retry_after` from the response when given
+    RateLimited { retry_after: Option<Duration> },
+    /// Missing API key or instance URL
+    NotConfigured(String),
+    Failed(anyhow::Error),
+}
+
+impl fmt::Display for ProviderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RateLimited { retry_after: Some(after) } => {
+                write!(f, "rate limited (retry after {}s)", after.as_secs())
+            }
+            Self::RateLimited { retry_after: None } => write!(f, "rate limited"),
+            Self::NotConfigured(reason) => write!(f, "not configured: {}", reason),
+            Self::Failed(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<anyhow::Error> for ProviderError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::Failed(e)
+    }
+}
+
+impl From<reqwest::Error> for ProviderError {
+    fn from(e: reqwest::Error) -> Self {
+        Self::Failed(e.into())
+    }
+}
+
+impl From<url::ParseError> for ProviderError {
+    fn from(e: url::ParseError) -> Self {
+        Self::Failed(e.into())
+    }
+}
+
+/// A web search backend
+#[async_trait::async_trait]
+pub trait SearchProvider: Send + Sync {
+    fn engine(&self) -> SearchEngine;
+
+    /// Up to `limit` results for `query`
+    async fn search(&self, client: &Client, query: &str, limit: usize) -> Result<Vec<SearchResult>, ProviderError>;
+}
+
+/// Provider for `engine`, reading keys from `secrets` when present
+pub fn provider_for(
+    engine: SearchEngine,
+    searx_instance: &str,
+    secrets: Option<Arc<SecretsService>>,
+) -> Box<dyn SearchProvider> {
+    match engine {
+        SearchEngine::DuckDuckGo => Box::new(DuckDuckGoProvider),
+        SearchEngine::SearX => Box::new(SearxProvider {
+            instance: searx_instance.trim_end_matches('/').to_string(),
+            secrets,
+        }),
+        SearchEngine::Brave => Box::new(BraveProvider { secrets }),
+        SearchEngine::Tavily => Box::new(TavilyProvider { secrets }),
+    }
+}
+
+/// Send through the network policy, mapping 429 and other failures
+async fn send(request: RequestBuilder, url: &Url) -> Result<Response, ProviderError> {
+    let policy = network_policy::global();
+    let _permit = policy.acquire(url).await?;
+    let response = request.header(USER_AGENT, policy.user_agent()).send().await?;
+
+    let status = response.status();
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(Duration::from_secs);
+        return Err(ProviderError::RateLimited { retry_after });
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ProviderError::Failed(anyhow::anyhow!(
+            "HTTP {}: {}",
+            status,
+            body.chars().take(200).collect::<String>()
+        )));
+    }
+    Ok(response)
+}
+
+fn api_key(secrets: &Option<Arc<SecretsService>>, name: &str) -> Result<Option<String>, ProviderError> {
+    match secrets {
+        Some(secrets) => secrets.get(name).map_err(ProviderError::Failed),
+        None => Ok(None),
+    }
+}
+
+/// DuckDuckGo Instant Answer API
+pub struct DuckDuckGoProvider;
+
+#[derive(Debug, Deserialize)]
+struct DuckDuckGoResponse {
+    #[serde(rename = "RelatedTopics")]
+    related_topics: Vec<DuckDuckGoTopic>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DuckDuckGoTopic {
+    #[serde(rename = "Text")]
+    text: Option<String>,
+    #[serde(rename = "FirstURL")]
+    first_url: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for DuckDuckGoProvider {
+    fn engine(&self) -> SearchEngine {
+        SearchEngine::DuckDuckGo
+    }
+
+    async fn search(&self, client: &Client, query: &str, limit: usize) -> Result<Vec<SearchResult>, ProviderError> {
+        let url = Url::parse(&format!(
+            "https://api.duckduckgo.com/?q={}&format=json&no_html=1&skip_disambig=1",
+            urlencoding::encode(query)
+        ))?;
+        let response = send(client.get(url.clone()), &url).await?;
+        let ddg_response: DuckDuckGoResponse = response.json().await?;
+
+        Ok(ddg_response
+            .related_topics
+            .into_iter()
+            .filter_map(|topic| {
+                let (text, url) = (topic.text?, topic.first_url?);
+                // Extract title from text (first sentence usually)
+                let (title, snippet) = text.split_once(" - ").unwrap_or((&text, &text));
+                Some(SearchResult {
+                    title: title.to_string(),
+                    url,
+                    snippet: snippet.to_string(),
+                    source: "duckduckgo".to_string(),
+                })
+            })
+            .take(limit)
+            .collect())
+    }
+}
+
+/// SearxNG / SearX JSON API
+pub struct SearxProvider {
+    instance: String,
+    /// Bearer key for authenticated instances
+    secrets: Option<Arc<SecretsService>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearXResponse {
+    results: Vec<SearXResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearXResult {
+    title: String,
+    url: String,
+    #[serde(default)]
+    content: String,
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for SearxProvider {
+    fn engine(&self) -> SearchEngine {
+        SearchEngine::SearX
+    }
+
+    async fn search(&self, client: &Client, query: &str, limit: usize) -> Result<Vec<SearchResult>, ProviderError> {
+        if self.instance.is_empty() {
+            return Err(ProviderError::NotConfigured("no SearX instance URL".to_string()));
+        }
+        let url = Url::parse(&format!(
+            "{}/search?q={}&format=json&categories=general",
+            self.instance,
+            urlencoding::encode(query)
+        ))?;
+
+        let mut request = client.get(url.clone());
+        match api_key(&self.secrets, secrets::WEB_SEARCH_API_KEY) {
+            Ok(Some(key)) => request = request.bearer_auth(key),
+            Ok(None) => {}
+            Err(e) => log::warn!("Failed to read web search API key: {}", e),
+        }
+
+        let response = send(request, &url).await?;
+        let searx_response: SearXResponse = response.json().await?;
+
+        Ok(searx_response
+            .results
+            .into_iter()
+            .take(limit)
+            .map(|result| SearchResult {
+                title: result.title,
+                url: result.url,
+                snippet: result.content,
+                source: "searx".to_string(),
+            })
+            .collect())
+    }
+}
+
+/// Brave Search API
+pub struct BraveProvider {
+    secrets: Option<Arc<SecretsService>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BraveResponse {
+    #[serde(default)]
+    web: Option<BraveWeb>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BraveWeb {
+    results: Vec<BraveResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BraveResult {
+    title: String,
+    url: String,
+    #[serde(default)]
+    description: String,
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for BraveProvider {
+    fn engine(&self) -> SearchEngine {
+        SearchEngine::Brave
+    }
+
+    async fn search(&self, client: &Client, query: &str, limit: usize) -> Result<Vec<SearchResult>, ProviderError> {
+        let key = api_key(&self.secrets, secrets::BRAVE_SEARCH_API_KEY)?
+            .ok_or_else(|| ProviderError::NotConfigured("no Brave Search API key".to_string()))?;
+        let url = Url::parse_with_params(BRAVE_ENDPOINT, &[("q", query), ("count", &limit.to_string())])?;
+
+        let request = client
+            .get(url.clone())
+            .header("Accept", "application/json")
+            .header("X-Subscription-Token", key);
+        let response = send(request, &url).await?;
+        let brave_response: BraveResponse = response.json().await?;
+
+        Ok(brave_response
+            .web
+            .map(|web| web.results)
+            .unwrap_or_default()
+            .into_iter()
+            .take(limit)
+            .map(|result| SearchResult {
+                title: result.title,
+                url: result.url,
+                // Brave highlights matches with <strong>
+                snippet: result.description.replace("<strong>", "").replace("</strong>", ""),
+                source: "brave".to_string(),
+            })
+            .collect())
+    }
+}
+
+/// Tavily search API
+pub struct TavilyProvider {
+    secrets: Option<Arc<SecretsService>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TavilyResponse {
+    results: Vec<TavilyResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TavilyResult {
+    title: String,
+    url: String,
+    #[serde(default)]
+    content: String,
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for TavilyProvider {
+    fn engine(&self) -> SearchEngine {
+        SearchEngine::Tavily
+    }
+
+    async fn search(&self, client: &Client, query: &str, limit: usize) -> Result<Vec<SearchResult>, ProviderError> {
+        let key = api_key(&self.secrets, secrets::TAVILY_API_KEY)?
+            .ok_or_else(|| ProviderError::NotConfigured("no Tavily API key".to_string()))?;
+        let url = Url::parse(TAVILY_ENDPOINT)?;
+
+        let request = client.post(url.clone()).json(&serde_json::json!({
+            "api_key": key,
+            "query": query,
+            "max_results": limit,
+            "search_depth": "basic",
+        }));
+        let response = send(request, &url).await?;
+        let tavily_response: TavilyResponse = response.json().await?;
+
+        Ok(tavily_response
+            .results
+            .into_iter()
+            .take(limit)
+            .map(|result| SearchResult {
+                title: result.title,
+                url: result.url,
+                snippet: result.content,
+                source: "tavily".to_string(),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_keyed_providers_need_keys() {
+        let client = Client::new();
+        for engine in [SearchEngine::Brave, SearchEngine::Tavily] {
+            let provider = provider_for(engine, "", None);
+            assert_eq!(provider.engine(), engine);
+            let err = provider.search(&client, "rust", 5).await.unwrap_err();
+            assert!(matches!(err, ProviderError::NotConfigured(_)));
+        }
+
+        let searx = provider_for(SearchEngine::SearX, "", None);
+        let err = searx.search(&client, "rust", 5).await.unwrap_err();
+        assert!(matches!(err, ProviderError::NotConfigured(_)));
+    }
+}
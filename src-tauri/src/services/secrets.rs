@@ -0,0 +1,197 @@
+//! Secrets Service (v3.9.0)
+//!
+//! Vault for API keys and OAuth tokens backed by the OS keychain
+//! (macOS Keychain, Windows Credential Manager, Secret Service on Linux).
+//!
+//! Features:
+//! - `set` / `get` / `delete` by namespaced name ("google_calendar.token")
+//! - In-process cache so the keychain is only hit once per secret
+//! - Helpers for splitting sensitive webhook headers out of plain settings
+
+#![allow(dead_code)]  // Phase 5: Secrets (email integration names reserved)
+
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+const KEYCHAIN_SERVICE: &str = "garden-of-eden-v3.secrets";
+
+/// Google Calendar OAuth token (JSON-serialized `CalendarToken`)
+pub const CALENDAR_TOKEN: &str = "google_calendar.token";
+/// API key for authenticated SearX instances
+pub const WEB_SEARCH_API_KEY: &str = "web_search.api_key";
+/// Brave Search API subscription token (v3.9.0)
+pub const BRAVE_SEARCH_API_KEY: &str = "web_search.brave_api_key";
+/// Tavily search API key (v3.9.0)
+pub const TAVILY_API_KEY: &str = "web_search.tavily_api_key";
+/// Reserved for the email integration
+pub const EMAIL_TOKEN: &str = "email.token";
+
+/// Vault name for a webhook's sensitive headers
+///
+/// Webhook names are free-form, so they're hex-encoded to stay a valid secret name.
+pub fn webhook_headers_key(webhook_name: &str) -> String {
+    let encoded: String = webhook_name.bytes().map(|b| format!("{:02x}", b)).collect();
+    format!("webhook.{}.headers", encoded)
+}
+
+/// Vault name for the credential of an imported HTTP API (v3.9.0)
+///
+/// API ids are already lowercase slugs (see `openapi_tools`).
+pub fn api_credential_key(api_id: &str) -> String {
+    format!("openapi.{}.credential", api_id)
+}
+
+/// Vault name for the connection URL of a Postgres / MySQL database (v3.9.0)
+pub fn sql_connection_key(connection_id: &str) -> String {
+    format!("sql.{}.url", connection_id)
+}
+
+/// Names look like "service.field" using lowercase letters, digits, '_', '-' and '.'
+pub fn validate_secret_name(name: &str) -> Result<()> {
+    let valid_chars = name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '-' | '.'));
+    if name.is_empty() || name.len() > 128 || !valid_chars || !name.contains('.') {
+        return Err(anyhow!("Invalid secret name: {}", name));
+    }
+    if name.starts_with('.') || name.ends_with('.') || name.contains("..") {
+        return Err(anyhow!("Invalid secret name: {}", name));
+    }
+    Ok(())
+}
+
+/// Headers whose values are credentials and belong in the vault
+fn is_sensitive_header(header: &str) -> bool {
+    let header = header.to_lowercase();
+    header == "authorization"
+        || header == "cookie"
+        || ["token", "secret", "api-key", "apikey", "api_key", "password"]
+            .iter()
+            .any(|marker| header.contains(marker))
+}
+
+/// Split webhook headers into (plain, sensitive)
+pub fn split_sensitive_headers(
+    headers: HashMap<String, String>,
+) -> (HashMap<String, String>, HashMap<String, String>) {
+    headers.into_iter().partition(|(name, _)| !is_sensitive_header(name))
+}
+
+/// Secrets service
+pub struct SecretsService {
+    cache: Mutex<HashMap<String, Option<String>>>,
+}
+
+impl SecretsService {
+    pub fn new() -> Result<Self> {
+        log::info!("✓ Secrets Service initialized");
+        Ok(Self {
+            cache: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn entry(name: &str) -> Result<keyring::Entry> {
+        validate_secret_name(name)?;
+        keyring::Entry::new(KEYCHAIN_SERVICE, name).map_err(|e| anyhow!("Keychain error: {}", e))
+    }
+
+    /// Store (or replace) a secret
+    pub fn set(&self, name: &str, value: &str) -> Result<()> {
+        Self::entry(name)?
+            .set_password(value)
+            .map_err(|e| anyhow!("Failed to store secret {}: {}", name, e))?;
+
+        self.cache
+            .lock()
+            .map_err(|e| anyhow!("Lock error: {}", e))?
+            .insert(name.to_string(), Some(value.to_string()));
+        log::info!("Stored secret {}", name);
+        Ok(())
+    }
+
+    /// Read a secret, `None` if it was never set
+    pub fn get(&self, name: &str) -> Result<Option<String>> {
+        if let Some(cached) = self.cache.lock().map_err(|e| anyhow!("Lock error: {}", e))?.get(name) {
+            return Ok(cached.clone());
+        }
+
+        let value = match Self::entry(name)?.get_password() {
+            Ok(value) => Some(value),
+            Err(keyring::Error::NoEntry) => None,
+            Err(e) => return Err(anyhow!("Failed to read secret {}: {}", name, e)),
+        };
+
+        self.cache
+            .lock()
+            .map_err(|e| anyhow!("Lock error: {}", e))?
+            .insert(name.to_string(), value.clone());
+        Ok(value)
+    }
+
+    /// Remove a secret (no-op if it doesn't exist)
+    pub fn delete(&self, name: &str) -> Result<()> {
+        match Self::entry(name)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => {}
+            Err(e) => return Err(anyhow!("Failed to delete secret {}: {}", name, e)),
+        }
+
+        self.cache
+            .lock()
+            .map_err(|e| anyhow!("Lock error: {}", e))?
+            .insert(name.to_string(), None);
+        log::info!("Deleted secret {}", name);
+        Ok(())
+    }
+
+    /// Read a JSON-encoded map secret (e.g. webhook headers), empty if unset
+    pub fn get_map(&self, name: &str) -> Result<HashMap<String, String>> {
+        match self.get(name)? {
+            Some(json) => Ok(serde_json::from_str(&json)?),
+            None => Ok(HashMap::new()),
+        }
+    }
+
+    /// Store a map secret, deleting it when empty
+    pub fn set_map(&self, name: &str, values: &HashMap<String, String>) -> Result<()> {
+        if values.is_empty() {
+            return self.delete(name);
+        }
+        self.set(name, &serde_json::to_string(values)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_secret_name() {
+        assert!(validate_secret_name(CALENDAR_TOKEN).is_ok());
+        assert!(validate_secret_name(&webhook_headers_key("Slack Alerts")).is_ok());
+        assert_ne!(webhook_headers_key("a b"), webhook_headers_key("a-b"));
+        assert!(validate_secret_name("no_namespace").is_err());
+        assert!(validate_secret_name("Upper.case").is_err());
+        assert!(validate_secret_name("a..b").is_err());
+        assert!(validate_secret_name(".leading").is_err());
+    }
+
+    #[test]
+    fn test_split_sensitive_headers() {
+        let headers: HashMap<String, String> = [
+            ("Content-Type", "application/json"),
+            ("Authorization", "Bearer abc"),
+            ("X-Api-Key", "k"),
+            ("Notion-Version", "2022-06-28"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let (plain, sensitive) = split_sensitive_headers(headers);
+        assert_eq!(plain.len(), 2);
+        assert!(plain.contains_key("Notion-Version"));
+        assert_eq!(sensitive.get("Authorization").map(String::as_str), Some("Bearer abc"));
+        assert!(sensitive.contains_key("X-Api-Key"));
+    }
+}
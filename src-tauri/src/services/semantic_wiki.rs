@@ -12,19 +12,73 @@
 //! Features:
 //! - Automatic fact extraction from conversations
 //! - Entity and relationship extraction
-//! - Fact confidence scoring
-//! - Conflict detection (contradicting facts)
+//! - Fact confidence scoring, weighted by source and decaying unless re-observed
+//! - Conflict detection (contradicting facts) with user or LLM adjudication
 //! - Temporal tracking (when facts were learned)
 //! - Source attribution (conversation provenance)
+//! - Facts written, corrected and annotated by hand, ranked above inferred ones (v3.9.0)
+//! - Entity pages: facts, graph neighbors, recent mentions and an LLM summary
+//!   that is extended as new facts arrive (v3.9.0)
 
 #![allow(dead_code)]  // Phase 5: Knowledge base (scheduled)
 
 use crate::database::Database;
 use crate::services::embedding::UnifiedEmbeddingService;
+use crate::services::graph_storage::{normalize_entity_name, GraphStorage};
 use crate::services::ollama;
+use crate::services::provenance::{self, Provenance, ProvenanceSource};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Minimum embedding similarity for two facts about the same entity to be
+/// checked for a contradiction (duplicates at >= 0.95 are skipped earlier)
+const CONFLICT_MIN_SIMILARITY: f32 = 0.6;
+
+/// Context score bonus for facts the user wrote or corrected by hand
+const USER_ASSERTED_BOOST: f32 = 0.15;
+
+/// Words that flip the meaning of a statement
+const NEGATION_WORDS: &[&str] = &[
+    "not", "no", "never", "doesn", "don", "isn", "aren", "wasn", "didn", "won",
+    "dislike", "dislikes", "hate", "hates", "않", "안", "싫어",
+];
+
+/// Words that mark a statement as a preference
+const PREFERENCE_WORDS: &[&str] = &[
+    "prefer", "prefers", "favorite", "favourite", "like", "likes", "love", "loves",
+    "use", "uses", "좋아",
+];
+
+const STOP_WORDS: &[&str] = &[
+    "the", "a", "an", "is", "are", "was", "were", "be", "of", "to", "in", "on",
+    "at", "for", "and", "or", "with", "their", "his", "her", "my",
+];
+
+/// Recent conversations shown on an entity page
+const PAGE_MENTIONS: usize = 10;
+
+/// Graph neighbors shown on an entity page
+const PAGE_NEIGHBORS: usize = 20;
+
+/// Facts given to the model when a page summary is written from scratch
+const SUMMARY_MAX_FACTS: usize = 40;
+
+/// Order of the fact sections on an entity page
+const PAGE_SECTIONS: [FactCategory; 6] = [
+    FactCategory::Definition,
+    FactCategory::Knowledge,
+    FactCategory::Preference,
+    FactCategory::Instruction,
+    FactCategory::Task,
+    FactCategory::Other,
+];
+
+const MONTHS: &[&str] = &[
+    "january", "february", "march", "april", "may", "june", "july", "august",
+    "september", "october", "november", "december",
+];
 
 /// A fact stored in the semantic wiki
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,6 +112,53 @@ pub struct Fact {
 
     /// Related fact IDs (supports/extends this fact)
     pub related_facts: Vec<String>,
+
+    /// How the fact was learned (v3.9.0)
+    #[serde(default)]
+    pub source: FactSource,
+
+    /// Last time the fact was stated again (v3.9.0, 0 = never since learned_at)
+    #[serde(default)]
+    pub last_observed_at: i64,
+
+    /// User's annotation (v3.9.0)
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+impl Fact {
+    /// Provenance record for citations (v3.9.0)
+    pub fn provenance(&self) -> Provenance {
+        // Facts written by hand have no source conversation
+        let conversation_id = Some(self.source_conversation_id.clone()).filter(|id| !id.is_empty());
+        Provenance::new(ProvenanceSource::WikiFact, &self.id)
+            .with_conversation(conversation_id, self.source_message_id.clone())
+            .with_timestamp_secs(self.learned_at)
+    }
+
+    /// Line given to the model in assembled context (v3.9.0)
+    pub fn context_text(&self) -> String {
+        let mut text = match self.source {
+            FactSource::UserAsserted => format!("Known fact (stated by the user): {}", self.statement),
+            _ => format!("Known fact: {}", self.statement),
+        };
+        if let Some(note) = &self.note {
+            text.push_str(&format!(" (note: {})", note));
+        }
+        text
+    }
+
+    /// Confidence after source weighting and time decay (v3.9.0)
+    pub fn effective_confidence(&self, half_life_days: f32, now: i64) -> f32 {
+        effective_confidence(
+            self.confidence,
+            self.source,
+            self.last_observed_at.max(self.learned_at),
+            self.reinforcement_count,
+            half_life_days,
+            now,
+        )
+    }
 }
 
 /// Fact category
@@ -78,6 +179,45 @@ pub enum FactCategory {
     Other,
 }
 
+/// How a fact was learned
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FactSource {
+    /// The user said it directly ("my birthday is March 3rd")
+    UserStatement,
+    /// Inferred from the conversation or the assistant's reply
+    #[default]
+    Inferred,
+    /// Written or corrected by the user by hand (v3.9.0); never decays
+    UserAsserted,
+}
+
+impl FactSource {
+    /// Reliability weight applied to the stored confidence
+    pub fn weight(&self) -> f32 {
+        match self {
+            FactSource::UserStatement | FactSource::UserAsserted => 1.0,
+            FactSource::Inferred => 0.7,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            FactSource::UserStatement => "user_statement",
+            FactSource::Inferred => "inferred",
+            FactSource::UserAsserted => "user_asserted",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "user_statement" => FactSource::UserStatement,
+            "user_asserted" => FactSource::UserAsserted,
+            _ => FactSource::Inferred,
+        }
+    }
+}
+
 /// Relationship between two facts
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -92,6 +232,56 @@ pub enum FactRelation {
     Related,
 }
 
+/// Conflict lifecycle
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStatus {
+    /// Waiting for adjudication
+    Open,
+    /// One fact won, the other is superseded
+    Resolved,
+    /// Both facts were kept (not actually a contradiction)
+    Dismissed,
+}
+
+impl ConflictStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ConflictStatus::Open => "open",
+            ConflictStatus::Resolved => "resolved",
+            ConflictStatus::Dismissed => "dismissed",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "resolved" => ConflictStatus::Resolved,
+            "dismissed" => ConflictStatus::Dismissed,
+            _ => ConflictStatus::Open,
+        }
+    }
+}
+
+/// Two facts about the same entity that contradict each other
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WikiConflict {
+    pub id: String,
+    pub entity: String,
+    /// Fact that was already in the wiki
+    pub existing_fact: Fact,
+    /// Fact whose storage triggered the conflict
+    pub new_fact: Fact,
+    /// Why the facts were flagged
+    pub reason: String,
+    pub detected_at: i64,
+    pub status: ConflictStatus,
+    pub winner_fact_id: Option<String>,
+    /// "user" or "llm"
+    pub resolved_by: Option<String>,
+    pub resolution_note: Option<String>,
+    pub resolved_at: Option<i64>,
+}
+
 /// Configuration for semantic wiki
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SemanticWikiConfig {
@@ -106,6 +296,22 @@ pub struct SemanticWikiConfig {
 
     /// Number of facts to retrieve in search
     pub search_limit: usize,
+
+    /// Days for an unreinforced fact's confidence to halve (v3.9.0)
+    #[serde(default = "default_confidence_half_life_days")]
+    pub confidence_half_life_days: f32,
+
+    /// Facts below this effective confidence are left out of context (v3.9.0)
+    #[serde(default = "default_min_context_confidence")]
+    pub min_context_confidence: f32,
+}
+
+fn default_confidence_half_life_days() -> f32 {
+    90.0
+}
+
+fn default_min_context_confidence() -> f32 {
+    0.3
 }
 
 impl Default for SemanticWikiConfig {
@@ -115,15 +321,89 @@ impl Default for SemanticWikiConfig {
             max_facts_per_turn: 5,
             auto_extract: true,
             search_limit: 10,
+            confidence_half_life_days: default_confidence_half_life_days(),
+            min_context_confidence: default_min_context_confidence(),
         }
     }
 }
 
+/// Wiki-style page about one entity (v3.9.0)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityPage {
+    pub entity: String,
+    /// LLM-written overview of the facts, `None` until one was generated
+    pub summary: Option<String>,
+    /// When the summary was last written (Unix seconds)
+    pub summary_updated_at: Option<i64>,
+    /// The summary doesn't cover every current fact (regeneration failed)
+    pub summary_stale: bool,
+    /// Current facts grouped by category, most confident first
+    pub sections: Vec<PageSection>,
+    pub related: Vec<RelatedEntity>,
+    pub mentions: Vec<EntityMention>,
+    /// Other names the knowledge graph merged into this entity
+    pub aliases: Vec<String>,
+    pub open_conflicts: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageSection {
+    pub category: FactCategory,
+    pub facts: Vec<Fact>,
+}
+
+/// Knowledge graph neighbor of a page's entity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedEntity {
+    pub name: String,
+    pub entity_type: String,
+    pub relationship: String,
+    /// The page's entity is the source of the relationship
+    pub outgoing: bool,
+    /// Facts about the neighbor (0 = no page of its own)
+    pub fact_count: usize,
+}
+
+/// Recent conversation that mentions a page's entity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityMention {
+    pub snippet: String,
+    pub provenance: Provenance,
+}
+
+/// What a page summary needs to match its current facts
+#[derive(Debug, Clone)]
+pub enum SummaryUpdate {
+    Current,
+    /// Only new facts arrived: extend the existing summary with them
+    Extend(Vec<Fact>),
+    /// No summary yet or facts were removed: write it from all facts
+    Rewrite,
+}
+
+/// Plan the summary update from the fact ids the summary was written from
+pub fn plan_summary_update(summarized: &HashSet<String>, has_summary: bool, facts: &[Fact]) -> SummaryUpdate {
+    if facts.is_empty() {
+        return SummaryUpdate::Current;
+    }
+    let current: HashSet<&str> = facts.iter().map(|f| f.id.as_str()).collect();
+    if !has_summary || summarized.iter().any(|id| !current.contains(id.as_str())) {
+        return SummaryUpdate::Rewrite;
+    }
+    let new: Vec<Fact> = facts.iter().filter(|f| !summarized.contains(&f.id)).cloned().collect();
+    if new.is_empty() {
+        SummaryUpdate::Current
+    } else {
+        SummaryUpdate::Extend(new)
+    }
+}
+
 /// Semantic Wiki Service
 pub struct SemanticWikiService {
     db: Arc<Mutex<Database>>,
     embedding: Arc<UnifiedEmbeddingService>,
     config: Arc<Mutex<SemanticWikiConfig>>,
+    graph: OnceLock<Arc<GraphStorage>>,  // v3.9.0: Neighbors on entity pages, attached after construction
 }
 
 impl SemanticWikiService {
@@ -136,6 +416,7 @@ impl SemanticWikiService {
             db,
             embedding,
             config: Arc::new(Mutex::new(SemanticWikiConfig::default())),
+            graph: OnceLock::new(),
         };
 
         service.init_database()?;
@@ -175,7 +456,54 @@ impl SemanticWikiService {
             [],
         )?;
 
+        // Migration: losing side of a resolved conflict (v3.9.0)
+        let _ = conn.execute("ALTER TABLE wiki_facts ADD COLUMN superseded_by TEXT", []);
+
+        // Migration: source weighting and re-observation for confidence decay (v3.9.0)
+        let _ = conn.execute(
+            "ALTER TABLE wiki_facts ADD COLUMN source TEXT NOT NULL DEFAULT 'inferred'",
+            [],
+        );
+        let _ = conn.execute("ALTER TABLE wiki_facts ADD COLUMN last_observed_at INTEGER", []);
+
+        // Migration: user annotations (v3.9.0)
+        let _ = conn.execute("ALTER TABLE wiki_facts ADD COLUMN note TEXT", []);
+
+        // Create conflicts table
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS wiki_conflicts (
+                id TEXT PRIMARY KEY,
+                entity TEXT NOT NULL,
+                existing_fact_id TEXT NOT NULL,
+                new_fact_id TEXT NOT NULL,
+                reason TEXT NOT NULL,
+                detected_at INTEGER NOT NULL,
+                status TEXT NOT NULL DEFAULT 'open',
+                winner_fact_id TEXT,
+                resolved_by TEXT,
+                resolution_note TEXT,
+                resolved_at INTEGER,
+                UNIQUE(existing_fact_id, new_fact_id)
+            )",
+            [],
+        )?;
+
+        // Create entity page summaries table (v3.9.0)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS wiki_entity_pages (
+                entity TEXT PRIMARY KEY,
+                summary TEXT NOT NULL,
+                fact_ids TEXT NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
         // Create indexes
+        let _ = conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_wiki_conflict_status ON wiki_conflicts(status)",
+            [],
+        );
         let _ = conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_wiki_entity ON wiki_facts(entity)",
             [],
@@ -231,6 +559,7 @@ Extract facts as JSON array. Each fact should have:
 - "entity": Main entity/subject (e.g., "Rust", "User", "GraphRAG")
 - "category": One of: preference, knowledge, task, definition, instruction, other
 - "confidence": Confidence 0.0-1.0
+- "source": "user_statement" if the user said it directly, otherwise "inferred"
 
 Respond with JSON only (no other text):
 [
@@ -238,7 +567,8 @@ Respond with JSON only (no other text):
     "statement": "fact statement here",
     "entity": "entity name",
     "category": "knowledge",
-    "confidence": 0.9
+    "confidence": 0.9,
+    "source": "inferred"
   }}
 ]"#
         );
@@ -256,6 +586,8 @@ Respond with JSON only (no other text):
             entity: String,
             category: String,
             confidence: f32,
+            #[serde(default)]
+            source: String,
         }
 
         let extracted: Vec<ExtractedFact> = serde_json::from_str(&json)
@@ -287,6 +619,13 @@ Respond with JSON only (no other text):
                     learned_at: chrono::Utc::now().timestamp(),
                     reinforcement_count: 1,
                     related_facts: Vec::new(),
+                    source: match FactSource::parse(&f.source.to_lowercase()) {
+                        // Only facts entered by hand are user-asserted
+                        FactSource::UserAsserted => FactSource::UserStatement,
+                        source => source,
+                    },
+                    last_observed_at: 0,
+                    note: None,
                 }
             })
             .collect();
@@ -299,60 +638,284 @@ Respond with JSON only (no other text):
     /// Store facts in the wiki
     pub async fn store_facts(&self, facts: Vec<Fact>) -> Result<usize> {
         let mut stored_count = 0;
+        let mut conflict_count = 0;
 
         for fact in facts {
-            // Check for existing similar facts BEFORE generating embedding
-            if self.find_similar_fact(&fact.statement, 0.95).await?.is_some() {
-                log::debug!("Skipping duplicate fact: {}", &fact.statement[..fact.statement.len().min(50)]);
+            // Check for existing similar facts BEFORE generating embedding;
+            // a re-observed fact is reinforced instead of stored twice
+            if let Some(existing) = self.find_similar_fact(&fact.statement, 0.95).await? {
+                log::debug!("Reinforcing duplicate fact: {}", &fact.statement[..fact.statement.len().min(50)]);
+                self.reinforce_fact(&existing.id, &fact)?;
                 continue;
             }
 
-            // Generate embedding for the fact
-            let embedding = self.embedding.embed(&fact.statement)?;
+            conflict_count += self.insert_fact(&fact)?;
+            stored_count += 1;
+        }
 
-            // Prepare data before locking
-            let category_str = format!("{:?}", fact.category).to_lowercase();
-            let related_facts_json = serde_json::to_string(&fact.related_facts)?;
-            let embedding_json = serde_json::to_string(&embedding)?;
+        log::info!("Stored {} facts in wiki", stored_count);
+        if conflict_count > 0 {
+            log::info!("Flagged {} fact conflicts for review", conflict_count);
+        }
 
-            // Store in database (short-lived lock)
-            {
-                let db = self.db.lock().unwrap();
-                let conn = db.conn();
+        Ok(stored_count)
+    }
 
-                conn.execute(
-                    "INSERT INTO wiki_facts (
-                        id, statement, entity, category, confidence,
-                        source_conversation_id, source_message_id, learned_at,
-                        reinforcement_count, related_facts
-                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
-                    rusqlite::params![
-                        fact.id,
-                        fact.statement,
-                        fact.entity,
-                        category_str,
-                        fact.confidence,
-                        fact.source_conversation_id,
-                        fact.source_message_id,
-                        fact.learned_at,
-                        fact.reinforcement_count,
-                        related_facts_json,
-                    ],
+    /// Embed and insert one fact; returns the number of conflicts it raised
+    fn insert_fact(&self, fact: &Fact) -> Result<usize> {
+        // Generate embedding for the fact
+        let embedding = self.embedding.embed(&fact.statement)?;
+
+        // Prepare data before locking
+        let category_str = format!("{:?}", fact.category).to_lowercase();
+        let related_facts_json = serde_json::to_string(&fact.related_facts)?;
+        let embedding_json = serde_json::to_string(&embedding)?;
+
+        // Store in database (short-lived lock)
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
+
+        conn.execute(
+            "INSERT INTO wiki_facts (
+                id, statement, entity, category, confidence,
+                source_conversation_id, source_message_id, learned_at,
+                reinforcement_count, related_facts, source, last_observed_at, note
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            rusqlite::params![
+                fact.id,
+                fact.statement,
+                fact.entity,
+                category_str,
+                fact.confidence,
+                fact.source_conversation_id,
+                fact.source_message_id,
+                fact.learned_at,
+                fact.reinforcement_count,
+                related_facts_json,
+                fact.source.as_str(),
+                fact.last_observed_at.max(fact.learned_at),
+                fact.note,
+            ],
+        )?;
+
+        // Store embedding
+        conn.execute(
+            "INSERT INTO wiki_fact_embeddings (fact_id, embedding) VALUES (?1, ?2)",
+            rusqlite::params![fact.id, embedding_json],
+        )?;
+
+        Self::detect_conflicts(conn, fact, &embedding)
+    }
+
+    /// Add a fact written by the user (v3.9.0)
+    ///
+    /// The fact is embedded immediately, stored as user-asserted with full
+    /// confidence and wins every conflict it raises. A near-identical
+    /// existing fact is promoted instead of duplicated. `replaces` names a
+    /// fact this one corrects ("correction: I moved to Busan"), which is
+    /// marked as superseded.
+    pub async fn assert_fact(
+        &self,
+        statement: &str,
+        entity: &str,
+        category: FactCategory,
+        replaces: Option<&str>,
+    ) -> Result<Fact> {
+        let statement = statement.trim();
+        if statement.is_empty() {
+            anyhow::bail!("Fact statement is empty");
+        }
+        let now = chrono::Utc::now().timestamp();
+
+        let id = match self.find_similar_fact(statement, 0.95).await? {
+            Some(existing) if replaces != Some(existing.id.as_str()) => {
+                let db = self.db.lock().unwrap();
+                db.conn().execute(
+                    "UPDATE wiki_facts
+                     SET source = 'user_asserted', confidence = 1.0, last_observed_at = ?1,
+                         reinforcement_count = reinforcement_count + 1
+                     WHERE id = ?2",
+                    rusqlite::params![now, existing.id],
                 )?;
+                existing.id
+            }
+            _ => {
+                let fact = Fact {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    statement: statement.to_string(),
+                    entity: entity.trim().to_string(),
+                    category,
+                    confidence: 1.0,
+                    source_conversation_id: String::new(),
+                    source_message_id: None,
+                    learned_at: now,
+                    reinforcement_count: 1,
+                    related_facts: Vec::new(),
+                    source: FactSource::UserAsserted,
+                    last_observed_at: now,
+                    note: None,
+                };
+                self.insert_fact(&fact)?;
+                fact.id
+            }
+        };
 
-                // Store embedding
+        if let Some(replaced) = replaces {
+            let db = self.db.lock().unwrap();
+            let updated = db.conn().execute(
+                "UPDATE wiki_facts SET superseded_by = ?1 WHERE id = ?2 AND id != ?1",
+                rusqlite::params![id, replaced],
+            )?;
+            if updated == 0 {
+                log::warn!("Corrected fact {} not found", replaced);
+            }
+        }
+
+        self.settle_conflicts_for(&id, "Stated by the user")?;
+        log::info!("Stored user-asserted fact {}", id);
+
+        let db = self.db.lock().unwrap();
+        load_fact(db.conn(), &id)?.ok_or_else(|| anyhow::anyhow!("Fact not found: {}", id))
+    }
+
+    /// Edit a fact by hand (v3.9.0)
+    ///
+    /// The edited fact becomes user-asserted; a changed statement is
+    /// re-embedded and checked for new conflicts, which it wins.
+    pub async fn update_fact(
+        &self,
+        fact_id: &str,
+        statement: Option<&str>,
+        entity: Option<&str>,
+        category: Option<FactCategory>,
+    ) -> Result<Fact> {
+        let mut fact = {
+            let db = self.db.lock().unwrap();
+            load_fact(db.conn(), fact_id)?.ok_or_else(|| anyhow::anyhow!("Fact not found: {}", fact_id))?
+        };
+
+        let statement = statement.map(str::trim).filter(|s| !s.is_empty() && *s != fact.statement);
+        if let Some(statement) = statement {
+            fact.statement = statement.to_string();
+        }
+        if let Some(entity) = entity.map(str::trim).filter(|e| !e.is_empty()) {
+            fact.entity = entity.to_string();
+        }
+        if let Some(category) = category {
+            fact.category = category;
+        }
+        fact.source = FactSource::UserAsserted;
+        fact.confidence = 1.0;
+        fact.last_observed_at = chrono::Utc::now().timestamp();
+
+        // Embed before locking
+        let embedding = match statement {
+            Some(_) => Some(self.embedding.embed(&fact.statement)?),
+            None => None,
+        };
+
+        {
+            let db = self.db.lock().unwrap();
+            let conn = db.conn();
+            conn.execute(
+                "UPDATE wiki_facts
+                 SET statement = ?1, entity = ?2, category = ?3, confidence = ?4,
+                     source = ?5, last_observed_at = ?6
+                 WHERE id = ?7",
+                rusqlite::params![
+                    fact.statement,
+                    fact.entity,
+                    format!("{:?}", fact.category).to_lowercase(),
+                    fact.confidence,
+                    fact.source.as_str(),
+                    fact.last_observed_at,
+                    fact.id,
+                ],
+            )?;
+            if let Some(embedding) = &embedding {
                 conn.execute(
-                    "INSERT INTO wiki_fact_embeddings (fact_id, embedding) VALUES (?1, ?2)",
-                    rusqlite::params![fact.id, embedding_json],
+                    "INSERT OR REPLACE INTO wiki_fact_embeddings (fact_id, embedding) VALUES (?1, ?2)",
+                    rusqlite::params![fact.id, serde_json::to_string(embedding)?],
                 )?;
-            } // Lock released here
+                Self::detect_conflicts(conn, &fact, embedding)?;
+            }
+        }
 
-            stored_count += 1;
+        self.settle_conflicts_for(&fact.id, "Corrected by the user")?;
+        log::info!("Wiki fact {} edited by the user", fact.id);
+
+        let db = self.db.lock().unwrap();
+        load_fact(db.conn(), &fact.id)?.ok_or_else(|| anyhow::anyhow!("Fact not found: {}", fact.id))
+    }
+
+    /// Set or clear the user's note on a fact (v3.9.0)
+    pub fn annotate_fact(&self, fact_id: &str, note: Option<&str>) -> Result<Fact> {
+        let note = note.map(str::trim).filter(|n| !n.is_empty());
+
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
+        let updated = conn.execute(
+            "UPDATE wiki_facts SET note = ?1 WHERE id = ?2",
+            rusqlite::params![note, fact_id],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("Fact not found: {}", fact_id);
         }
 
-        log::info!("Stored {} facts in wiki", stored_count);
+        load_fact(conn, fact_id)?.ok_or_else(|| anyhow::anyhow!("Fact not found: {}", fact_id))
+    }
 
-        Ok(stored_count)
+    /// Re-embed facts written by another device (v3.9.0 device sync)
+    ///
+    /// Embeddings of facts that no longer exist are removed.
+    pub fn reindex_facts(&self, fact_ids: &[String]) -> Result<usize> {
+        let mut reindexed = 0;
+        for fact_id in fact_ids {
+            let fact = {
+                let db = self.db.lock().unwrap();
+                load_fact(db.conn(), fact_id)?
+            };
+            let Some(fact) = fact else {
+                let db = self.db.lock().unwrap();
+                db.conn().execute("DELETE FROM wiki_fact_embeddings WHERE fact_id = ?1", [fact_id])?;
+                continue;
+            };
+
+            // Embed before locking
+            let embedding = self.embedding.embed(&fact.statement)?;
+            let db = self.db.lock().unwrap();
+            db.conn().execute(
+                "INSERT OR REPLACE INTO wiki_fact_embeddings (fact_id, embedding) VALUES (?1, ?2)",
+                rusqlite::params![fact.id, serde_json::to_string(&embedding)?],
+            )?;
+            reindexed += 1;
+        }
+        Ok(reindexed)
+    }
+
+    /// Resolve open conflicts involving a user-asserted fact in its favor
+    fn settle_conflicts_for(&self, fact_id: &str, note: &str) -> Result<usize> {
+        let conflict_ids: Vec<String> = {
+            let db = self.db.lock().unwrap();
+            let mut stmt = db.conn().prepare(
+                "SELECT id FROM wiki_conflicts
+                 WHERE status = 'open' AND (existing_fact_id = ?1 OR new_fact_id = ?1)",
+            )?;
+            let ids = stmt
+                .query_map([fact_id], |row| row.get(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            ids
+        };
+
+        let mut settled = 0;
+        for conflict_id in &conflict_ids {
+            // An earlier resolution may already have settled it
+            match self.apply_resolution(conflict_id, Some(fact_id), "user", Some(note)) {
+                Ok(_) => settled += 1,
+                Err(e) => log::debug!("Skipped conflict {}: {}", conflict_id, e),
+            }
+        }
+        Ok(settled)
     }
 
     /// Search for facts semantically
@@ -384,19 +947,22 @@ Respond with JSON only (no other text):
             (
                 "SELECT f.id, f.statement, f.entity, f.category, f.confidence,
                         f.source_conversation_id, f.source_message_id, f.learned_at,
-                        f.reinforcement_count, f.related_facts, e.embedding
+                        f.reinforcement_count, f.related_facts, f.source, f.last_observed_at, f.note,
+                        e.embedding
                  FROM wiki_facts f
                  JOIN wiki_fact_embeddings e ON f.id = e.fact_id
-                 WHERE f.category = ?1".to_string(),
+                 WHERE f.category = ?1 AND f.superseded_by IS NULL".to_string(),
                 vec![category_str],
             )
         } else {
             (
                 "SELECT f.id, f.statement, f.entity, f.category, f.confidence,
                         f.source_conversation_id, f.source_message_id, f.learned_at,
-                        f.reinforcement_count, f.related_facts, e.embedding
+                        f.reinforcement_count, f.related_facts, f.source, f.last_observed_at, f.note,
+                        e.embedding
                  FROM wiki_facts f
-                 JOIN wiki_fact_embeddings e ON f.id = e.fact_id".to_string(),
+                 JOIN wiki_fact_embeddings e ON f.id = e.fact_id
+                 WHERE f.superseded_by IS NULL".to_string(),
                 vec![],
             )
         };
@@ -405,38 +971,13 @@ Respond with JSON only (no other text):
 
         let facts_with_scores: Vec<(Fact, f32)> = stmt
             .query_map(rusqlite::params_from_iter(params.iter()), |row| {
-                let category_str: String = row.get(3)?;
-                let category = match category_str.as_str() {
-                    "preference" => FactCategory::Preference,
-                    "knowledge" => FactCategory::Knowledge,
-                    "task" => FactCategory::Task,
-                    "definition" => FactCategory::Definition,
-                    "instruction" => FactCategory::Instruction,
-                    _ => FactCategory::Other,
-                };
-
-                let related_facts_json: String = row.get(9)?;
-                let related_facts: Vec<String> = serde_json::from_str(&related_facts_json)
-                    .unwrap_or_default();
-
-                let embedding_json: String = row.get(10)?;
+                let embedding_json: String = row.get(13)?;
                 let embedding: Vec<f32> = serde_json::from_str(&embedding_json)
                     .map_err(|e| rusqlite::Error::FromSqlConversionFailure(
-                        10, rusqlite::types::Type::Text, Box::new(e)
+                        13, rusqlite::types::Type::Text, Box::new(e)
                     ))?;
 
-                let fact = Fact {
-                    id: row.get(0)?,
-                    statement: row.get(1)?,
-                    entity: row.get(2)?,
-                    category,
-                    confidence: row.get(4)?,
-                    source_conversation_id: row.get(5)?,
-                    source_message_id: row.get(6)?,
-                    learned_at: row.get(7)?,
-                    reinforcement_count: row.get(8)?,
-                    related_facts,
-                };
+                let fact = fact_from_row(row)?;
 
                 Ok((fact, embedding))
             })?
@@ -471,6 +1012,55 @@ Respond with JSON only (no other text):
         Ok(None)
     }
 
+    /// Count a re-observation of an existing fact
+    ///
+    /// Resets its decay clock, keeps the higher confidence, and upgrades the
+    /// source when the user now states it directly.
+    fn reinforce_fact(&self, fact_id: &str, observed: &Fact) -> Result<()> {
+        let db = self.db.lock().unwrap();
+        db.conn().execute(
+            "UPDATE wiki_facts
+             SET reinforcement_count = reinforcement_count + 1,
+                 last_observed_at = ?1,
+                 confidence = MAX(confidence, ?2),
+                 source = CASE WHEN ?3 = 'user_statement' AND source = 'inferred' THEN ?3 ELSE source END
+             WHERE id = ?4",
+            rusqlite::params![
+                chrono::Utc::now().timestamp(),
+                observed.confidence,
+                observed.source.as_str(),
+                fact_id,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Facts for context assembly, ranked by similarity x effective confidence
+    ///
+    /// Facts whose effective confidence fell below `min_context_confidence`
+    /// are dropped, so stale inferences give way to fresh user statements.
+    /// User-asserted facts get a fixed bonus on top (v3.9.0).
+    pub async fn search_for_context(&self, query: &str, limit: usize) -> Result<Vec<(Fact, f32)>> {
+        let config = self.get_config();
+        let now = chrono::Utc::now().timestamp();
+
+        let mut ranked: Vec<(Fact, f32)> = self
+            .search(query, limit * 3, None)
+            .await?
+            .into_iter()
+            .filter_map(|(fact, similarity)| {
+                let confidence = fact.effective_confidence(config.confidence_half_life_days, now);
+                let boost = if fact.source == FactSource::UserAsserted { USER_ASSERTED_BOOST } else { 0.0 };
+                (confidence >= config.min_context_confidence).then_some((fact, similarity * confidence + boost))
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranked.truncate(limit);
+
+        Ok(ranked)
+    }
+
     /// Get facts about a specific entity
     pub fn get_facts_by_entity(&self, entity: &str, limit: usize) -> Result<Vec<Fact>> {
         let db = self.db.lock().unwrap();
@@ -479,47 +1069,529 @@ Respond with JSON only (no other text):
         let mut stmt = conn.prepare(
             "SELECT id, statement, entity, category, confidence,
                     source_conversation_id, source_message_id, learned_at,
-                    reinforcement_count, related_facts
+                    reinforcement_count, related_facts, source, last_observed_at, note
              FROM wiki_facts
-             WHERE entity = ?1
+             WHERE entity = ?1 AND superseded_by IS NULL
              ORDER BY confidence DESC, learned_at DESC
              LIMIT ?2"
         )?;
 
         let facts = stmt
-            .query_map([entity, &limit.to_string()], |row| {
-                let category_str: String = row.get(3)?;
-                let category = match category_str.as_str() {
-                    "preference" => FactCategory::Preference,
-                    "knowledge" => FactCategory::Knowledge,
-                    "task" => FactCategory::Task,
-                    "definition" => FactCategory::Definition,
-                    "instruction" => FactCategory::Instruction,
-                    _ => FactCategory::Other,
-                };
+            .query_map([entity, &limit.to_string()], fact_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
 
-                let related_facts_json: String = row.get(9)?;
-                let related_facts: Vec<String> = serde_json::from_str(&related_facts_json)
-                    .unwrap_or_default();
+        Ok(facts)
+    }
 
-                Ok(Fact {
-                    id: row.get(0)?,
-                    statement: row.get(1)?,
-                    entity: row.get(2)?,
-                    category,
-                    confidence: row.get(4)?,
-                    source_conversation_id: row.get(5)?,
-                    source_message_id: row.get(6)?,
-                    learned_at: row.get(7)?,
-                    reinforcement_count: row.get(8)?,
-                    related_facts,
-                })
-            })?
+    /// All current (not superseded) facts, grouped by entity (v3.9.0: vault export)
+    pub fn current_facts(&self) -> Result<Vec<Fact>> {
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, statement, entity, category, confidence,
+                    source_conversation_id, source_message_id, learned_at,
+                    reinforcement_count, related_facts, source, last_observed_at, note
+             FROM wiki_facts
+             WHERE superseded_by IS NULL
+             ORDER BY lower(entity), confidence DESC, learned_at DESC"
+        )?;
+
+        let facts = stmt
+            .query_map([], fact_from_row)?
             .collect::<rusqlite::Result<Vec<_>>>()?;
 
         Ok(facts)
     }
 
+    /// Delete facts with their embeddings and conflicts (v3.9.0: note re-import)
+    ///
+    /// Ids that don't exist are skipped. Returns the number of facts deleted.
+    pub fn delete_facts(&self, fact_ids: &[String]) -> Result<usize> {
+        let db = self.db.lock().unwrap();
+        let tx = db.conn().unchecked_transaction()?;
+        let mut deleted = 0;
+        for id in fact_ids {
+            tx.execute("DELETE FROM wiki_fact_embeddings WHERE fact_id = ?1", [id])?;
+            tx.execute("DELETE FROM wiki_conflicts WHERE existing_fact_id = ?1 OR new_fact_id = ?1", [id])?;
+            deleted += tx.execute("DELETE FROM wiki_facts WHERE id = ?1", [id])?;
+        }
+        tx.commit()?;
+        Ok(deleted)
+    }
+
+    /// Generated entity page summaries keyed by lowercased entity (v3.9.0)
+    pub fn page_summaries(&self) -> Result<HashMap<String, String>> {
+        let db = self.db.lock().unwrap();
+        let mut stmt = db.conn().prepare("SELECT entity, summary FROM wiki_entity_pages")?;
+        let summaries = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+        Ok(summaries)
+    }
+
+    /// Flag existing facts about the same entity that the new fact contradicts
+    fn detect_conflicts(conn: &rusqlite::Connection, fact: &Fact, embedding: &[f32]) -> Result<usize> {
+        let mut stmt = conn.prepare(
+            "SELECT f.id, f.statement, f.entity, f.category, f.confidence,
+                    f.source_conversation_id, f.source_message_id, f.learned_at,
+                    f.reinforcement_count, f.related_facts, f.source, f.last_observed_at, f.note,
+                    e.embedding
+             FROM wiki_facts f
+             JOIN wiki_fact_embeddings e ON f.id = e.fact_id
+             WHERE lower(f.entity) = lower(?1) AND f.id != ?2 AND f.superseded_by IS NULL"
+        )?;
+
+        let candidates: Vec<(Fact, Vec<f32>)> = stmt
+            .query_map(rusqlite::params![fact.entity, fact.id], |row| {
+                let embedding_json: String = row.get(13)?;
+                Ok((fact_from_row(row)?, serde_json::from_str(&embedding_json).unwrap_or_default()))
+            })?
+            .filter_map(|result| result.ok())
+            .collect();
+
+        let now = chrono::Utc::now().timestamp();
+        let mut flagged = 0;
+
+        for (existing, existing_embedding) in candidates {
+            if UnifiedEmbeddingService::cosine_similarity(embedding, &existing_embedding) < CONFLICT_MIN_SIMILARITY {
+                continue;
+            }
+            let Some(reason) = conflict_reason(&existing, fact) else {
+                continue;
+            };
+
+            flagged += conn.execute(
+                "INSERT OR IGNORE INTO wiki_conflicts (
+                    id, entity, existing_fact_id, new_fact_id, reason, detected_at, status
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'open')",
+                rusqlite::params![
+                    uuid::Uuid::new_v4().to_string(),
+                    fact.entity,
+                    existing.id,
+                    fact.id,
+                    reason,
+                    now,
+                ],
+            )?;
+        }
+
+        Ok(flagged)
+    }
+
+    /// List detected conflicts, newest first
+    pub fn list_conflicts(&self, include_resolved: bool) -> Result<Vec<WikiConflict>> {
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
+
+        let sql = if include_resolved {
+            "SELECT id FROM wiki_conflicts ORDER BY detected_at DESC"
+        } else {
+            "SELECT id FROM wiki_conflicts WHERE status = 'open' ORDER BY detected_at DESC"
+        };
+
+        let ids: Vec<String> = conn
+            .prepare(sql)?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut conflicts = Vec::with_capacity(ids.len());
+        for id in ids {
+            // Facts may have been removed since (e.g. by a privacy forget)
+            if let Some(conflict) = load_conflict(conn, &id)? {
+                conflicts.push(conflict);
+            }
+        }
+
+        Ok(conflicts)
+    }
+
+    /// Resolve a conflict by hand
+    ///
+    /// `winner_fact_id` must be one of the two facts; `None` keeps both.
+    pub fn resolve_conflict(
+        &self,
+        conflict_id: &str,
+        winner_fact_id: Option<&str>,
+        note: Option<&str>,
+    ) -> Result<WikiConflict> {
+        self.apply_resolution(conflict_id, winner_fact_id, "user", note)
+    }
+
+    /// Let the LLM decide which fact wins
+    pub async fn adjudicate_conflict(&self, conflict_id: &str) -> Result<WikiConflict> {
+        let conflict = {
+            let db = self.db.lock().unwrap();
+            load_conflict(db.conn(), conflict_id)?
+                .ok_or_else(|| anyhow::anyhow!("Conflict not found: {}", conflict_id))?
+        };
+
+        let learned = |fact: &Fact| {
+            chrono::DateTime::from_timestamp(fact.learned_at, 0)
+                .map(|dt| dt.format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|| "unknown".to_string())
+        };
+
+        let prompt = format!(
+            r#"Two facts about "{entity}" in the user's knowledge base contradict each other.
+
+Fact A (learned {a_date}): {a}
+Fact B (learned {b_date}): {b}
+
+Decide which fact should be kept. Prefer the more recent fact when the user's
+situation or preference has likely changed. Answer "both" if they do not
+actually contradict.
+
+Respond with JSON only (no other text):
+{{"winner": "A" | "B" | "both", "reason": "short explanation"}}"#,
+            entity = conflict.entity,
+            a = conflict.existing_fact.statement,
+            a_date = learned(&conflict.existing_fact),
+            b = conflict.new_fact.statement,
+            b_date = learned(&conflict.new_fact),
+        );
+
+        let response = ollama::generate_response(&prompt)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to adjudicate conflict: {}", e))?;
+
+        let (verdict, reason) = parse_adjudication(&response)
+            .context("Failed to parse conflict adjudication JSON")?;
+
+        let winner = match verdict {
+            Verdict::Existing => Some(conflict.existing_fact.id.as_str()),
+            Verdict::New => Some(conflict.new_fact.id.as_str()),
+            Verdict::Both => None,
+        };
+
+        self.apply_resolution(conflict_id, winner, "llm", Some(&reason))
+    }
+
+    /// Mark the losing fact as superseded and close the conflict
+    fn apply_resolution(
+        &self,
+        conflict_id: &str,
+        winner_fact_id: Option<&str>,
+        resolved_by: &str,
+        note: Option<&str>,
+    ) -> Result<WikiConflict> {
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
+
+        let (existing_id, new_id, status): (String, String, String) = conn
+            .query_row(
+                "SELECT existing_fact_id, new_fact_id, status FROM wiki_conflicts WHERE id = ?1",
+                [conflict_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .with_context(|| format!("Conflict not found: {}", conflict_id))?;
+
+        if ConflictStatus::parse(&status) != ConflictStatus::Open {
+            anyhow::bail!("Conflict {} is already {}", conflict_id, status);
+        }
+
+        let now = chrono::Utc::now().timestamp();
+
+        let status = match winner_fact_id {
+            Some(winner) => {
+                let loser = if winner == existing_id {
+                    &new_id
+                } else if winner == new_id {
+                    &existing_id
+                } else {
+                    anyhow::bail!("Fact {} is not part of conflict {}", winner, conflict_id);
+                };
+
+                conn.execute(
+                    "UPDATE wiki_facts SET superseded_by = ?1 WHERE id = ?2",
+                    rusqlite::params![winner, loser],
+                )?;
+
+                // Other open conflicts with the superseded fact are settled too
+                conn.execute(
+                    "UPDATE wiki_conflicts
+                     SET status = 'resolved',
+                         winner_fact_id = CASE WHEN existing_fact_id = ?1 THEN new_fact_id ELSE existing_fact_id END,
+                         resolved_by = ?2,
+                         resolution_note = 'Other fact was superseded in another conflict',
+                         resolved_at = ?3
+                     WHERE status = 'open' AND id != ?4 AND (existing_fact_id = ?1 OR new_fact_id = ?1)",
+                    rusqlite::params![loser, resolved_by, now, conflict_id],
+                )?;
+
+                ConflictStatus::Resolved
+            }
+            None => ConflictStatus::Dismissed,
+        };
+
+        conn.execute(
+            "UPDATE wiki_conflicts
+             SET status = ?1, winner_fact_id = ?2, resolved_by = ?3, resolution_note = ?4, resolved_at = ?5
+             WHERE id = ?6",
+            rusqlite::params![status.as_str(), winner_fact_id, resolved_by, note, now, conflict_id],
+        )?;
+
+        log::info!("Wiki conflict {} {} by {}", conflict_id, status.as_str(), resolved_by);
+
+        load_conflict(conn, conflict_id)?
+            .ok_or_else(|| anyhow::anyhow!("Conflict not found: {}", conflict_id))
+    }
+
+    /// Point facts about merged graph entities at the surviving entity name
+    /// (v3.9.0: graph alias merging)
+    pub fn rename_entity(&self, old_names: &[String], new_name: &str) -> Result<usize> {
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
+
+        let mut updated = 0;
+        for old_name in old_names {
+            updated += conn.execute(
+                "UPDATE wiki_facts SET entity = ?1 WHERE lower(entity) = lower(?2)",
+                rusqlite::params![new_name, old_name],
+            )?;
+            conn.execute(
+                "UPDATE wiki_conflicts SET entity = ?1 WHERE lower(entity) = lower(?2)",
+                rusqlite::params![new_name, old_name],
+            )?;
+            // The surviving page is rewritten from the combined facts
+            conn.execute("DELETE FROM wiki_entity_pages WHERE entity = lower(?1)", [old_name])?;
+        }
+
+        if updated > 0 {
+            log::info!("Moved {} wiki facts to entity {}", updated, new_name);
+        }
+        Ok(updated)
+    }
+
+    /// Attach the knowledge graph for entity page neighbors (v3.9.0)
+    pub fn attach_graph(&self, graph: Arc<GraphStorage>) {
+        let _ = self.graph.set(graph);
+    }
+
+    /// Wiki page of an entity (v3.9.0)
+    ///
+    /// The summary is extended with facts learned since it was written, or
+    /// rewritten when facts were removed or `regenerate` is set. If the model
+    /// is unavailable the previous summary is returned, marked stale.
+    pub async fn get_entity_page(&self, entity: &str, regenerate: bool) -> Result<EntityPage> {
+        let entity = entity.trim();
+        if entity.is_empty() {
+            anyhow::bail!("Entity name is empty");
+        }
+
+        let (mut page, update) = self.assemble_page(entity)?;
+        let has_facts = page.sections.iter().any(|section| !section.facts.is_empty());
+        let update = if regenerate && has_facts { SummaryUpdate::Rewrite } else { update };
+
+        if !matches!(update, SummaryUpdate::Current) {
+            let facts: Vec<Fact> = page.sections.iter().flat_map(|section| section.facts.iter().cloned()).collect();
+            match self.write_summary(entity, page.summary.as_deref(), &facts, &update).await {
+                Ok(summary) => {
+                    page.summary_updated_at = Some(chrono::Utc::now().timestamp());
+                    page.summary = Some(summary);
+                    page.summary_stale = false;
+                }
+                Err(e) => {
+                    log::warn!("Failed to summarize wiki entity {}: {}", entity, e);
+                    page.summary_stale = true;
+                }
+            }
+        }
+
+        Ok(page)
+    }
+
+    /// Bring the summaries of already generated pages up to date with new facts
+    ///
+    /// Entities without a page are skipped; their summary is written the first
+    /// time the page is opened. Returns the number of pages updated.
+    pub async fn refresh_entity_pages(&self, entities: &[String]) -> Result<usize> {
+        let mut seen = HashSet::new();
+        let mut refreshed = 0;
+        for entity in entities {
+            if !seen.insert(entity.to_lowercase()) {
+                continue;
+            }
+            let has_page = {
+                let db = self.db.lock().unwrap();
+                load_page_summary(db.conn(), entity)?.is_some()
+            };
+            if !has_page {
+                continue;
+            }
+            let page = self.get_entity_page(entity, false).await?;
+            if !page.summary_stale {
+                refreshed += 1;
+            }
+        }
+        Ok(refreshed)
+    }
+
+    /// Everything on an entity page except the summary update
+    fn assemble_page(&self, entity: &str) -> Result<(EntityPage, SummaryUpdate)> {
+        let (related, aliases) = self.graph_neighbors(entity);
+
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, statement, entity, category, confidence,
+                    source_conversation_id, source_message_id, learned_at,
+                    reinforcement_count, related_facts, source, last_observed_at, note
+             FROM wiki_facts
+             WHERE lower(entity) = lower(?1) AND superseded_by IS NULL
+             ORDER BY confidence DESC, learned_at DESC"
+        )?;
+        let facts = stmt
+            .query_map([entity], fact_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let stored = load_page_summary(conn, entity)?;
+        let update = match &stored {
+            Some((_, fact_ids, _)) => plan_summary_update(fact_ids, true, &facts),
+            None => plan_summary_update(&HashSet::new(), false, &facts),
+        };
+
+        let sections = PAGE_SECTIONS
+            .iter()
+            .map(|category| PageSection {
+                category: category.clone(),
+                facts: facts.iter().filter(|f| f.category == *category).cloned().collect(),
+            })
+            .filter(|section| !section.facts.is_empty())
+            .collect();
+
+        let related = related
+            .into_iter()
+            .map(|mut neighbor| {
+                neighbor.fact_count = count_entity_facts(conn, &neighbor.name).unwrap_or(0);
+                neighbor
+            })
+            .collect();
+
+        let open_conflicts: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM wiki_conflicts WHERE lower(entity) = lower(?1) AND status = 'open'",
+            [entity],
+            |row| row.get(0),
+        )?;
+
+        let (summary, summary_updated_at) = match stored {
+            Some((summary, _, updated_at)) => (Some(summary), Some(updated_at)),
+            None => (None, None),
+        };
+        let page = EntityPage {
+            entity: facts.first().map(|f| f.entity.clone()).unwrap_or_else(|| entity.to_string()),
+            summary,
+            summary_updated_at,
+            summary_stale: false,
+            sections,
+            related,
+            mentions: recent_mentions(conn, entity, PAGE_MENTIONS)?,
+            aliases,
+            open_conflicts: open_conflicts as usize,
+        };
+        Ok((page, update))
+    }
+
+    /// Graph neighbors and aliases of the entity (empty without a graph node)
+    fn graph_neighbors(&self, entity: &str) -> (Vec<RelatedEntity>, Vec<String>) {
+        let Some(graph) = self.graph.get() else {
+            return (Vec::new(), Vec::new());
+        };
+        let lookup = || -> std::result::Result<(Vec<RelatedEntity>, Vec<String>), String> {
+            let name = normalize_entity_name(entity);
+            let Some(node) = graph
+                .search_entities(entity, 10)?
+                .into_iter()
+                .find(|node| normalize_entity_name(&node.name) == name)
+            else {
+                return Ok((Vec::new(), Vec::new()));
+            };
+            let mut related: Vec<RelatedEntity> = graph
+                .get_relationships_during(&node.entity_id, i64::MIN, i64::MAX, None)?
+                .into_iter()
+                .map(|(edge, neighbor)| RelatedEntity {
+                    name: neighbor.name,
+                    entity_type: neighbor.entity_type,
+                    relationship: edge.relationship_type,
+                    outgoing: edge.source_id == node.entity_id,
+                    fact_count: 0,
+                })
+                .collect();
+            related.truncate(PAGE_NEIGHBORS);
+            Ok((related, graph.get_aliases(&node.entity_id)?))
+        };
+        lookup().unwrap_or_else(|e| {
+            log::warn!("Failed to load graph neighbors of {}: {}", entity, e);
+            (Vec::new(), Vec::new())
+        })
+    }
+
+    /// Ask the model for the page summary and store it
+    async fn write_summary(
+        &self,
+        entity: &str,
+        previous: Option<&str>,
+        facts: &[Fact],
+        update: &SummaryUpdate,
+    ) -> Result<String> {
+        let bullets = |facts: &[Fact]| {
+            facts.iter().map(|f| format!("- {}", f.statement)).collect::<Vec<_>>().join("\n")
+        };
+        let prompt = match (update, previous) {
+            (SummaryUpdate::Extend(new_facts), Some(previous)) => format!(
+                r#"This is the summary of "{entity}" in the user's personal wiki:
+
+{previous}
+
+These facts were learned since it was written:
+{new_facts}
+
+Rewrite the summary so it also covers the new facts. Where a new fact
+contradicts the summary, the new fact is correct. Keep it to 2-4 sentences
+in the third person, in the language of the facts, without adding anything
+the summary and facts don't say.
+
+Respond with the summary only (no other text)."#,
+                new_facts = bullets(new_facts),
+            ),
+            _ => {
+                let mut ranked: Vec<&Fact> = facts.iter().collect();
+                ranked.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal));
+                let top: Vec<Fact> = ranked.into_iter().take(SUMMARY_MAX_FACTS).cloned().collect();
+                format!(
+                    r#"Write the summary of "{entity}" for the user's personal wiki from these facts:
+{facts}
+
+Write 2-4 sentences in the third person, like the opening of an encyclopedia
+article, in the language of the facts. Use only what the facts say.
+
+Respond with the summary only (no other text)."#,
+                    facts = bullets(&top),
+                )
+            }
+        };
+
+        let response = ollama::generate_response(&prompt)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to generate summary: {}", e))?;
+        let summary = response.trim().trim_matches('"').trim().to_string();
+        if summary.is_empty() {
+            anyhow::bail!("Model returned an empty summary");
+        }
+
+        let fact_ids: Vec<&str> = facts.iter().map(|f| f.id.as_str()).collect();
+        let db = self.db.lock().unwrap();
+        db.conn().execute(
+            "INSERT OR REPLACE INTO wiki_entity_pages (entity, summary, fact_ids, updated_at)
+             VALUES (lower(?1), ?2, ?3, ?4)",
+            rusqlite::params![entity, summary, serde_json::to_string(&fact_ids)?, chrono::Utc::now().timestamp()],
+        )?;
+        log::info!("Wiki page summary of {} updated ({} facts)", entity, facts.len());
+
+        Ok(summary)
+    }
+
     /// Get statistics about the wiki
     pub fn get_stats(&self) -> Result<WikiStats> {
         let db = self.db.lock().unwrap();
@@ -587,6 +1659,310 @@ Respond with JSON only (no other text):
     }
 }
 
+/// Stored confidence weighted by source reliability and decayed since the
+/// fact was last observed
+///
+/// Each reinforcement stretches the half-life by 50%, up to 3x. User-asserted
+/// facts are pinned and do not decay.
+pub fn effective_confidence(
+    confidence: f32,
+    source: FactSource,
+    observed_at: i64,
+    reinforcement_count: i32,
+    half_life_days: f32,
+    now: i64,
+) -> f32 {
+    let age_days = (now - observed_at).max(0) as f32 / 86_400.0;
+    let half_life = half_life_days * (1.0 + 0.5 * (reinforcement_count - 1).clamp(0, 4) as f32);
+    let decay = if half_life > 0.0 && source != FactSource::UserAsserted {
+        0.5f32.powf(age_days / half_life)
+    } else {
+        1.0
+    };
+
+    (confidence * source.weight() * decay).clamp(0.0, 1.0)
+}
+
+/// Stored page summary: (summary, fact ids it was written from, updated at)
+fn load_page_summary(conn: &rusqlite::Connection, entity: &str) -> Result<Option<(String, HashSet<String>, i64)>> {
+    use rusqlite::OptionalExtension;
+
+    let row: Option<(String, String, i64)> = conn
+        .query_row(
+            "SELECT summary, fact_ids, updated_at FROM wiki_entity_pages WHERE entity = lower(?1)",
+            [entity],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()?;
+    Ok(row.map(|(summary, fact_ids, updated_at)| {
+        (summary, serde_json::from_str(&fact_ids).unwrap_or_default(), updated_at)
+    }))
+}
+
+fn count_entity_facts(conn: &rusqlite::Connection, entity: &str) -> Result<usize> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM wiki_facts WHERE lower(entity) = lower(?1) AND superseded_by IS NULL",
+        [entity],
+        |row| row.get(0),
+    )?;
+    Ok(count as usize)
+}
+
+/// Most recent episodes mentioning the entity, newest first
+fn recent_mentions(conn: &rusqlite::Connection, entity: &str, limit: usize) -> Result<Vec<EntityMention>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, user_message, ai_response, created_at, conversation_id, message_id
+         FROM episodic_memory
+         WHERE user_message LIKE ?1 OR ai_response LIKE ?1
+         ORDER BY created_at DESC
+         LIMIT ?2"
+    )?;
+    let needle = entity.to_lowercase();
+    let mentions = stmt
+        .query_map(rusqlite::params![format!("%{}%", entity), limit as i64], |row| {
+            let (id, user_message, ai_response): (String, String, String) = (row.get(0)?, row.get(1)?, row.get(2)?);
+            // Quote the side of the turn that names the entity
+            let text = if user_message.to_lowercase().contains(&needle) { user_message } else { ai_response };
+            Ok(EntityMention {
+                snippet: provenance::snippet(&text),
+                provenance: Provenance::new(ProvenanceSource::Episode, id)
+                    .with_conversation(row.get(4)?, row.get(5)?)
+                    .with_timestamp_secs(row.get(3)?),
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(mentions)
+}
+
+fn parse_category(value: &str) -> FactCategory {
+    match value {
+        "preference" => FactCategory::Preference,
+        "knowledge" => FactCategory::Knowledge,
+        "task" => FactCategory::Task,
+        "definition" => FactCategory::Definition,
+        "instruction" => FactCategory::Instruction,
+        _ => FactCategory::Other,
+    }
+}
+
+/// Map the first thirteen `wiki_facts` columns to a fact
+fn fact_from_row(row: &rusqlite::Row) -> rusqlite::Result<Fact> {
+    let category_str: String = row.get(3)?;
+    let related_facts_json: Option<String> = row.get(9)?;
+
+    Ok(Fact {
+        id: row.get(0)?,
+        statement: row.get(1)?,
+        entity: row.get(2)?,
+        category: parse_category(&category_str),
+        confidence: row.get(4)?,
+        source_conversation_id: row.get(5)?,
+        source_message_id: row.get(6)?,
+        learned_at: row.get(7)?,
+        reinforcement_count: row.get(8)?,
+        related_facts: related_facts_json
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+        source: FactSource::parse(&row.get::<_, Option<String>>(10)?.unwrap_or_default()),
+        last_observed_at: row.get::<_, Option<i64>>(11)?.unwrap_or(0),
+        note: row.get(12)?,
+    })
+}
+
+fn load_fact(conn: &rusqlite::Connection, id: &str) -> Result<Option<Fact>> {
+    use rusqlite::OptionalExtension;
+
+    Ok(conn
+        .query_row(
+            "SELECT id, statement, entity, category, confidence,
+                    source_conversation_id, source_message_id, learned_at,
+                    reinforcement_count, related_facts, source, last_observed_at, note
+             FROM wiki_facts WHERE id = ?1",
+            [id],
+            fact_from_row,
+        )
+        .optional()?)
+}
+
+fn load_conflict(conn: &rusqlite::Connection, id: &str) -> Result<Option<WikiConflict>> {
+    use rusqlite::OptionalExtension;
+
+    let fact_ids: Option<(String, String)> = conn
+        .query_row(
+            "SELECT existing_fact_id, new_fact_id FROM wiki_conflicts WHERE id = ?1",
+            [id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+
+    let Some((existing_id, new_id)) = fact_ids else {
+        return Ok(None);
+    };
+    let (Some(existing_fact), Some(new_fact)) = (load_fact(conn, &existing_id)?, load_fact(conn, &new_id)?) else {
+        return Ok(None);
+    };
+
+    let conflict = conn.query_row(
+        "SELECT id, entity, reason, detected_at, status,
+                winner_fact_id, resolved_by, resolution_note, resolved_at
+         FROM wiki_conflicts WHERE id = ?1",
+        [id],
+        |row| {
+            let status: String = row.get(4)?;
+            Ok(WikiConflict {
+                id: row.get(0)?,
+                entity: row.get(1)?,
+                existing_fact,
+                new_fact,
+                reason: row.get(2)?,
+                detected_at: row.get(3)?,
+                status: ConflictStatus::parse(&status),
+                winner_fact_id: row.get(5)?,
+                resolved_by: row.get(6)?,
+                resolution_note: row.get(7)?,
+                resolved_at: row.get(8)?,
+            })
+        },
+    )?;
+
+    Ok(Some(conflict))
+}
+
+/// Lowercased content words, with a trailing plural/third-person "s" dropped
+fn content_tokens(statement: &str) -> Vec<String> {
+    statement
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty() && !STOP_WORDS.contains(t))
+        .filter(|t| !(t.len() == 1 && t.chars().all(|c| c.is_ascii_alphabetic())))
+        .map(|t| {
+            if t.is_ascii() && t.len() > 3 && t.ends_with('s') && !t.ends_with("ss") {
+                t[..t.len() - 1].to_string()
+            } else {
+                t.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Exact match for English markers, substring match for Korean ones
+fn is_marker(token: &str, markers: &[&str]) -> bool {
+    markers
+        .iter()
+        .any(|m| token == *m || (!m.is_ascii() && token.contains(m)))
+}
+
+/// Numbers and month names (dates, ages, counts)
+fn is_value(token: &str) -> bool {
+    token.chars().any(|c| c.is_ascii_digit()) || MONTHS.contains(&token)
+}
+
+fn jaccard(a: &HashSet<&str>, b: &HashSet<&str>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f32 / union as f32
+}
+
+/// Why two facts about the same entity contradict each other, if they do
+///
+/// Cheap lexical checks run on every store; the embedding gate in
+/// `detect_conflicts` keeps them to statements on the same topic.
+fn conflict_reason(existing: &Fact, new: &Fact) -> Option<String> {
+    let a = content_tokens(&existing.statement);
+    let b = content_tokens(&new.statement);
+
+    let core = |tokens: &[String]| -> HashSet<String> {
+        tokens
+            .iter()
+            .filter(|t| !is_marker(t, NEGATION_WORDS) && !is_value(t))
+            .cloned()
+            .collect()
+    };
+    let (core_a, core_b) = (core(&a), core(&b));
+    let (core_a, core_b): (HashSet<&str>, HashSet<&str>) = (
+        core_a.iter().map(String::as_str).collect(),
+        core_b.iter().map(String::as_str).collect(),
+    );
+    let overlap = jaccard(&core_a, &core_b);
+
+    // Different birthdays, ages, dates
+    let values_a: HashSet<&str> = a.iter().map(String::as_str).filter(|t| is_value(t)).collect();
+    let values_b: HashSet<&str> = b.iter().map(String::as_str).filter(|t| is_value(t)).collect();
+    if !values_a.is_empty() && !values_b.is_empty() && values_a != values_b && overlap >= 0.5 {
+        let mut old_values: Vec<&str> = values_a.into_iter().collect();
+        let mut new_values: Vec<&str> = values_b.into_iter().collect();
+        old_values.sort_unstable();
+        new_values.sort_unstable();
+        return Some(format!(
+            "Different values: {} vs {}",
+            old_values.join(" "),
+            new_values.join(" ")
+        ));
+    }
+
+    // "likes coffee" vs "doesn't like coffee"
+    let negated_a = a.iter().any(|t| is_marker(t, NEGATION_WORDS));
+    let negated_b = b.iter().any(|t| is_marker(t, NEGATION_WORDS));
+    if negated_a != negated_b && overlap >= 0.6 {
+        return Some("One statement negates the other".to_string());
+    }
+
+    // "prefers tabs" vs "prefers spaces"
+    if existing.category == FactCategory::Preference
+        && new.category == FactCategory::Preference
+        && negated_a == negated_b
+        && a.iter().any(|t| is_marker(t, PREFERENCE_WORDS))
+        && b.iter().any(|t| is_marker(t, PREFERENCE_WORDS))
+        && overlap >= 0.5
+    {
+        let mut only_a: Vec<&str> = core_a.difference(&core_b).copied().collect();
+        let mut only_b: Vec<&str> = core_b.difference(&core_a).copied().collect();
+        if (1..=2).contains(&only_a.len()) && (1..=2).contains(&only_b.len()) {
+            only_a.sort_unstable();
+            only_b.sort_unstable();
+            return Some(format!(
+                "Preference may have changed: {} → {}",
+                only_a.join(" "),
+                only_b.join(" ")
+            ));
+        }
+    }
+
+    None
+}
+
+/// LLM adjudication outcome
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verdict {
+    Existing,
+    New,
+    Both,
+}
+
+fn parse_adjudication(response: &str) -> Option<(Verdict, String)> {
+    #[derive(Deserialize)]
+    struct Adjudication {
+        winner: String,
+        #[serde(default)]
+        reason: String,
+    }
+
+    let start = response.find('{')?;
+    let end = response.rfind('}')?;
+    let parsed: Adjudication = serde_json::from_str(response.get(start..=end)?).ok()?;
+
+    let verdict = match parsed.winner.trim().to_lowercase().as_str() {
+        "a" | "existing" => Verdict::Existing,
+        "b" | "new" => Verdict::New,
+        "both" => Verdict::Both,
+        _ => return None,
+    };
+
+    Some((verdict, parsed.reason))
+}
+
 /// Wiki statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WikiStats {
@@ -606,6 +1982,144 @@ mod tests {
         assert_eq!(config.max_facts_per_turn, 5);
         assert!(config.auto_extract);
         assert_eq!(config.search_limit, 10);
+        assert_eq!(config.confidence_half_life_days, 90.0);
+        assert_eq!(config.min_context_confidence, 0.3);
+    }
+
+    #[test]
+    fn test_effective_confidence() {
+        let day = 86_400;
+
+        // Fresh user statement keeps its confidence, inference is discounted
+        assert_eq!(effective_confidence(0.9, FactSource::UserStatement, 0, 1, 90.0, 0), 0.9);
+        assert!((effective_confidence(0.9, FactSource::Inferred, 0, 1, 90.0, 0) - 0.63).abs() < 1e-6);
+
+        // Halves after one half-life
+        let aged = effective_confidence(0.8, FactSource::UserStatement, 0, 1, 90.0, 90 * day);
+        assert!((aged - 0.4).abs() < 1e-6);
+
+        // Reinforcement slows the decay
+        let reinforced = effective_confidence(0.8, FactSource::UserStatement, 0, 3, 90.0, 90 * day);
+        assert!(reinforced > aged);
+
+        // Old config without the new fields still deserializes
+        let config: SemanticWikiConfig = serde_json::from_str(
+            r#"{"min_confidence":0.6,"max_facts_per_turn":5,"auto_extract":true,"search_limit":10}"#,
+        )
+        .unwrap();
+        assert_eq!(config.confidence_half_life_days, 90.0);
+    }
+
+    #[test]
+    fn test_user_asserted_facts() {
+        let day = 86_400;
+
+        // Facts entered by hand keep full confidence indefinitely
+        assert_eq!(effective_confidence(1.0, FactSource::UserAsserted, 0, 1, 90.0, 365 * day), 1.0);
+        assert_eq!(FactSource::parse(FactSource::UserAsserted.as_str()), FactSource::UserAsserted);
+
+        let mut moved = fact("User lives in Busan", FactCategory::Knowledge);
+        moved.source = FactSource::UserAsserted;
+        moved.note = Some("moved in 2025".to_string());
+        assert_eq!(
+            moved.context_text(),
+            "Known fact (stated by the user): User lives in Busan (note: moved in 2025)"
+        );
+
+        // No source conversation to cite
+        moved.source_conversation_id = String::new();
+        assert!(moved.provenance().conversation_id.is_none());
+    }
+
+    fn fact(statement: &str, category: FactCategory) -> Fact {
+        Fact {
+            id: uuid::Uuid::new_v4().to_string(),
+            statement: statement.to_string(),
+            entity: "User".to_string(),
+            category,
+            confidence: 0.9,
+            source_conversation_id: "conv_1".to_string(),
+            source_message_id: None,
+            learned_at: 0,
+            reinforcement_count: 1,
+            related_facts: Vec::new(),
+            source: FactSource::Inferred,
+            last_observed_at: 0,
+            note: None,
+        }
+    }
+
+    #[test]
+    fn test_entity_pages() {
+        let first = fact("Rust uses ownership for memory safety", FactCategory::Knowledge);
+        let second = fact("User prefers Rust for CLI tools", FactCategory::Preference);
+        let summarized: HashSet<String> = [first.id.clone()].into_iter().collect();
+
+        assert!(matches!(plan_summary_update(&HashSet::new(), false, &[]), SummaryUpdate::Current));
+        assert!(matches!(plan_summary_update(&HashSet::new(), false, std::slice::from_ref(&first)), SummaryUpdate::Rewrite));
+        assert!(matches!(plan_summary_update(&summarized, true, std::slice::from_ref(&first)), SummaryUpdate::Current));
+        match plan_summary_update(&summarized, true, &[first.clone(), second.clone()]) {
+            SummaryUpdate::Extend(new) => assert_eq!(new[0].statement, "User prefers Rust for CLI tools"),
+            other => panic!("expected extend, got {:?}", other),
+        }
+        // The summarized fact was superseded
+        assert!(matches!(plan_summary_update(&summarized, true, &[second]), SummaryUpdate::Rewrite));
+
+        let db = Database::new_test_db().unwrap();
+        let conn = db.conn();
+        conn.execute(
+            "INSERT INTO episodic_memory (id, user_message, ai_response, satisfaction, created_at, conversation_id)
+             VALUES ('e1', 'I started learning rust', 'Great choice!', 0.5, 100, 'c1'),
+                    ('e2', 'What is tokio?', 'An async runtime for Rust', 0.5, 200, 'c2'),
+                    ('e3', 'Weather?', 'Sunny', 0.5, 300, 'c3')",
+            [],
+        )
+        .unwrap();
+        let mentions = recent_mentions(conn, "Rust", 10).unwrap();
+        assert_eq!(mentions.len(), 2);
+        assert_eq!(mentions[0].snippet, "An async runtime for Rust");
+        assert_eq!(mentions[1].snippet, "I started learning rust");
+        assert_eq!(mentions[1].provenance.conversation_id.as_deref(), Some("c1"));
+    }
+
+    #[test]
+    fn test_conflict_reason() {
+        let birthday = fact("User's birthday is March 3", FactCategory::Knowledge);
+        let other_birthday = fact("User's birthday is April 5", FactCategory::Knowledge);
+        assert!(conflict_reason(&birthday, &other_birthday).unwrap().starts_with("Different values"));
+
+        let likes = fact("User likes coffee", FactCategory::Preference);
+        let dislikes = fact("User doesn't like coffee", FactCategory::Preference);
+        assert_eq!(
+            conflict_reason(&likes, &dislikes).as_deref(),
+            Some("One statement negates the other")
+        );
+
+        let tabs = fact("User prefers tabs", FactCategory::Preference);
+        let spaces = fact("User prefers spaces", FactCategory::Preference);
+        assert_eq!(
+            conflict_reason(&tabs, &spaces).as_deref(),
+            Some("Preference may have changed: tab → space")
+        );
+
+        // Refinements and unrelated knowledge are not conflicts
+        let tabs_python = fact("User prefers tabs for Python", FactCategory::Preference);
+        assert!(conflict_reason(&tabs, &tabs_python).is_none());
+        let ownership = fact("Rust uses ownership", FactCategory::Knowledge);
+        let borrow = fact("Rust uses a borrow checker", FactCategory::Knowledge);
+        assert!(conflict_reason(&ownership, &borrow).is_none());
+    }
+
+    #[test]
+    fn test_parse_adjudication() {
+        let (verdict, reason) =
+            parse_adjudication("```json\n{\"winner\": \"B\", \"reason\": \"newer\"}\n```").unwrap();
+        assert_eq!(verdict, Verdict::New);
+        assert_eq!(reason, "newer");
+
+        assert_eq!(parse_adjudication(r#"{"winner": "both"}"#).unwrap().0, Verdict::Both);
+        assert!(parse_adjudication(r#"{"winner": "C"}"#).is_none());
+        assert!(parse_adjudication("no json").is_none());
     }
 
     #[test]
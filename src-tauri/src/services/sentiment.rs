@@ -0,0 +1,374 @@
+//! Sentiment Tracking Service (v3.9.0)
+//!
+//! Scores every user message and keeps a mood timeline per conversation.
+//!
+//! Features:
+//! - Lexicon-based valence (-1.0 ~ 1.0) and arousal (0.0 ~ 1.0), English + Korean
+//! - Frustration detection (repeated failures, "still not working", 짜증, ㅡㅡ...)
+//! - Per-conversation mood timeline for the UI
+//! - Session empathy boost: recent distress raises empathy for the next replies
+//!   without touching the stored persona
+
+#![allow(dead_code)]  // Phase 5: Sentiment tracking
+
+use crate::database::Database;
+use anyhow::{anyhow, Result};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Frustration at or above which a message counts as frustrated
+const FRUSTRATION_THRESHOLD: f32 = 0.5;
+
+/// Largest empathy increase (0-100 scale) applied within a session
+const MAX_EMPATHY_BOOST: i32 = 30;
+
+/// Messages considered for the session mood
+const SESSION_WINDOW: usize = 5;
+
+/// Weight of each older message relative to the next newer one
+const SESSION_DECAY: f32 = 0.6;
+
+/// English words: (word, valence, arousal)
+const ENGLISH_LEXICON: &[(&str, f32, f32)] = &[
+    ("thanks", 0.6, 0.3), ("thank", 0.6, 0.3), ("great", 0.7, 0.5), ("awesome", 0.8, 0.7),
+    ("amazing", 0.8, 0.7), ("love", 0.8, 0.6), ("perfect", 0.8, 0.5), ("nice", 0.5, 0.3),
+    ("good", 0.4, 0.3), ("works", 0.4, 0.3), ("worked", 0.5, 0.4), ("happy", 0.7, 0.5),
+    ("excited", 0.7, 0.8), ("cool", 0.5, 0.4), ("glad", 0.6, 0.4), ("helpful", 0.6, 0.3),
+    ("sad", -0.6, 0.3), ("angry", -0.7, 0.8), ("frustrated", -0.7, 0.7), ("frustrating", -0.7, 0.7),
+    ("annoyed", -0.6, 0.6), ("annoying", -0.6, 0.6), ("hate", -0.8, 0.8), ("terrible", -0.7, 0.6),
+    ("awful", -0.7, 0.6), ("worried", -0.5, 0.6), ("anxious", -0.5, 0.7), ("stressed", -0.6, 0.7),
+    ("upset", -0.6, 0.6), ("broken", -0.5, 0.5), ("confused", -0.3, 0.4), ("tired", -0.4, 0.2),
+    ("stuck", -0.4, 0.4), ("useless", -0.7, 0.6), ("wrong", -0.3, 0.4), ("ugh", -0.5, 0.6),
+];
+
+/// Korean stems matched as substrings: (stem, valence, arousal)
+const KOREAN_LEXICON: &[(&str, f32, f32)] = &[
+    ("고마", 0.6, 0.3), ("감사", 0.6, 0.3), ("좋아", 0.6, 0.4), ("최고", 0.8, 0.7),
+    ("행복", 0.7, 0.5), ("기쁘", 0.7, 0.5), ("됐다", 0.5, 0.4), ("완벽", 0.8, 0.5),
+    ("짜증", -0.7, 0.7), ("화나", -0.7, 0.8), ("슬프", -0.6, 0.3), ("힘들", -0.5, 0.4),
+    ("걱정", -0.5, 0.5), ("불안", -0.5, 0.6), ("싫어", -0.6, 0.5), ("망했", -0.7, 0.7),
+    ("답답", -0.6, 0.6), ("피곤", -0.4, 0.2), ("모르겠", -0.3, 0.4),
+];
+
+/// Phrases that signal frustration beyond plain negative words
+const FRUSTRATION_MARKERS: &[&str] = &[
+    "still not", "still doesn't", "still does not", "still broken", "still getting",
+    "doesn't work", "does not work", "not working", "didn't work", "same error",
+    "why won't", "why doesn't", "why does it", "again", "give up", "for the third time",
+    "wtf", "ugh", "seriously",
+    "안 돼", "안돼", "안되", "왜 안", "아직도", "또 ", "ㅡㅡ", ";;", "미치겠",
+];
+
+const NEGATIONS: &[&str] = &["not", "no", "never", "don't", "doesn't", "isn't", "wasn't", "can't", "won't"];
+
+/// Sentiment of one message
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SentimentScore {
+    /// -1.0 (negative) ~ 1.0 (positive)
+    pub valence: f32,
+    /// 0.0 (calm) ~ 1.0 (agitated)
+    pub arousal: f32,
+    /// 0.0 ~ 1.0
+    pub frustration: f32,
+    pub frustrated: bool,
+}
+
+/// One scored user message on the timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoodPoint {
+    pub message_id: Option<String>,
+    pub valence: f32,
+    pub arousal: f32,
+    pub frustration: f32,
+    pub frustrated: bool,
+    pub timestamp: i64, // Unix millis
+}
+
+/// Mood across a conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoodTimeline {
+    pub conversation_id: String,
+    /// Oldest first
+    pub points: Vec<MoodPoint>,
+    pub average_valence: f32,
+    pub average_arousal: f32,
+    pub frustrated_messages: usize,
+    /// "improving", "worsening" or "steady"
+    pub trend: String,
+    /// Empathy added to replies right now (0-100 scale)
+    pub empathy_boost: i32,
+}
+
+/// Mood of the current session, used when generating a reply
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionMood {
+    /// Empathy to add (0-100 scale)
+    pub empathy_boost: i32,
+    /// Whether the latest message was frustrated
+    pub frustrated: bool,
+}
+
+fn tokens(text: &str) -> Vec<String> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_string())
+        .collect()
+}
+
+/// Score a single message
+pub fn score_message(text: &str) -> SentimentScore {
+    let lower = text.to_lowercase();
+    let words = tokens(&lower);
+
+    // Lexicon hits; a negation shortly before a positive word flips (and damps) it
+    let mut hits: Vec<(f32, f32)> = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        if let Some(&(_, valence, arousal)) = ENGLISH_LEXICON.iter().find(|(w, _, _)| w == word) {
+            let negated = valence > 0.0
+                && words[i.saturating_sub(2)..i].iter().any(|w| NEGATIONS.contains(&w.as_str()));
+            hits.push((if negated { -valence * 0.5 } else { valence }, arousal));
+        }
+    }
+    for &(stem, valence, arousal) in KOREAN_LEXICON {
+        hits.extend(std::iter::repeat_n((valence, arousal), lower.matches(stem).count()));
+    }
+
+    let markers = FRUSTRATION_MARKERS.iter().filter(|m| lower.contains(*m)).count();
+
+    // Intensity: exclamation marks, repeated question marks, shouting
+    let exclamations = text.matches('!').count().min(3) as f32 * 0.1;
+    let questions = if text.contains("??") { 0.15 } else { 0.0 };
+    let shouting = text
+        .split_whitespace()
+        .filter(|w| w.chars().filter(|c| c.is_ascii_alphabetic()).count() >= 3)
+        .filter(|w| w.chars().all(|c| !c.is_ascii_lowercase()))
+        .count()
+        .min(2) as f32
+        * 0.15;
+    let intensity = exclamations + questions + shouting;
+
+    let (mut valence, base_arousal) = if hits.is_empty() {
+        (0.0, 0.2)
+    } else {
+        let n = hits.len() as f32;
+        (
+            hits.iter().map(|(v, _)| v).sum::<f32>() / n,
+            hits.iter().map(|(_, a)| a).sum::<f32>() / n,
+        )
+    };
+    valence -= markers as f32 * 0.2;
+    let valence = valence.clamp(-1.0, 1.0);
+    let arousal = (base_arousal + intensity).clamp(0.0, 1.0);
+
+    let frustration = (markers as f32 * 0.35
+        + (-valence).max(0.0) * arousal * 0.5
+        + if valence < 0.0 { intensity * 0.5 } else { 0.0 })
+    .clamp(0.0, 1.0);
+
+    SentimentScore {
+        valence,
+        arousal,
+        frustration,
+        frustrated: frustration >= FRUSTRATION_THRESHOLD,
+    }
+}
+
+/// Distress of a message: negative valence plus frustration (0.0 ~ 1.0)
+fn distress(point: &MoodPoint) -> f32 {
+    ((-point.valence).max(0.0) * 0.6 + point.frustration * 0.4).clamp(0.0, 1.0)
+}
+
+/// Empathy boost from recent points (newest last)
+pub fn empathy_boost(points: &[MoodPoint]) -> i32 {
+    let recent = &points[points.len().saturating_sub(SESSION_WINDOW)..];
+    let (mut weighted, mut total) = (0.0, 0.0);
+    let mut weight = 1.0;
+    for point in recent.iter().rev() {
+        weighted += distress(point) * weight;
+        total += weight;
+        weight *= SESSION_DECAY;
+    }
+    if total == 0.0 {
+        return 0;
+    }
+    ((weighted / total) * MAX_EMPATHY_BOOST as f32).round() as i32
+}
+
+fn trend(points: &[MoodPoint]) -> &'static str {
+    if points.len() < 4 {
+        return "steady";
+    }
+    let half = points.len() / 2;
+    let mean = |ps: &[MoodPoint]| ps.iter().map(|p| p.valence).sum::<f32>() / ps.len() as f32;
+    let delta = mean(&points[half..]) - mean(&points[..half]);
+    if delta > 0.15 {
+        "improving"
+    } else if delta < -0.15 {
+        "worsening"
+    } else {
+        "steady"
+    }
+}
+
+fn load_points(db: &Database, conversation_id: &str, limit: Option<usize>) -> Result<Vec<MoodPoint>> {
+    let mut stmt = db.conn().prepare(
+        "SELECT message_id, valence, arousal, frustration, frustrated, timestamp
+         FROM mood_timeline
+         WHERE conversation_id = ?1
+         ORDER BY timestamp DESC, id DESC
+         LIMIT ?2",
+    )?;
+    let mut points = stmt
+        .query_map(params![conversation_id, limit.map(|l| l as i64).unwrap_or(-1)], |row| {
+            Ok(MoodPoint {
+                message_id: row.get(0)?,
+                valence: row.get(1)?,
+                arousal: row.get(2)?,
+                frustration: row.get(3)?,
+                frustrated: row.get(4)?,
+                timestamp: row.get(5)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    points.reverse();
+    Ok(points)
+}
+
+/// Current session mood of a conversation, `None` without scored messages
+pub fn session_mood(db: &Database, conversation_id: &str) -> Option<SessionMood> {
+    let points = load_points(db, conversation_id, Some(SESSION_WINDOW)).ok()?;
+    let latest = points.last()?;
+    Some(SessionMood {
+        empathy_boost: empathy_boost(&points),
+        frustrated: latest.frustrated,
+    })
+}
+
+/// Sentiment tracking service
+pub struct SentimentService {
+    db: Arc<Mutex<Database>>,
+}
+
+impl SentimentService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        let service = Self { db };
+        service.init_database()?;
+        log::info!("✓ Sentiment Service initialized");
+        Ok(service)
+    }
+
+    fn init_database(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let conn = db.conn();
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS mood_timeline (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                conversation_id TEXT NOT NULL,
+                message_id TEXT,
+                valence REAL NOT NULL,
+                arousal REAL NOT NULL,
+                frustration REAL NOT NULL,
+                frustrated INTEGER NOT NULL DEFAULT 0,
+                timestamp INTEGER NOT NULL,
+                FOREIGN KEY (conversation_id) REFERENCES conversations(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_mood_timeline_conversation
+             ON mood_timeline(conversation_id, timestamp)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Score a user message and append it to the conversation's timeline
+    pub fn record_message(&self, conversation_id: &str, message_id: &str, text: &str) -> Result<SentimentScore> {
+        let score = score_message(text);
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+
+        db.conn().execute(
+            "INSERT INTO mood_timeline (conversation_id, message_id, valence, arousal, frustration, frustrated, timestamp)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                conversation_id,
+                message_id,
+                score.valence,
+                score.arousal,
+                score.frustration,
+                score.frustrated,
+                chrono::Utc::now().timestamp_millis(),
+            ],
+        )?;
+
+        if score.frustrated {
+            log::info!("Frustration detected in conversation {} ({:.2})", conversation_id, score.frustration);
+        }
+        Ok(score)
+    }
+
+    /// Mood timeline of a conversation
+    pub fn timeline(&self, conversation_id: &str) -> Result<MoodTimeline> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let points = load_points(&db, conversation_id, None)?;
+
+        let n = points.len().max(1) as f32;
+        Ok(MoodTimeline {
+            conversation_id: conversation_id.to_string(),
+            average_valence: points.iter().map(|p| p.valence).sum::<f32>() / n,
+            average_arousal: points.iter().map(|p| p.arousal).sum::<f32>() / n,
+            frustrated_messages: points.iter().filter(|p| p.frustrated).count(),
+            trend: trend(&points).to_string(),
+            empathy_boost: empathy_boost(&points),
+            points,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_message() {
+        let happy = score_message("Thanks, that worked perfectly! Awesome");
+        assert!(happy.valence > 0.5);
+        assert!(!happy.frustrated);
+
+        let neutral = score_message("How do I read a file in Rust?");
+        assert_eq!(neutral.valence, 0.0);
+        assert!(!neutral.frustrated);
+
+        let frustrated = score_message("It's STILL not working, same error again!!");
+        assert!(frustrated.valence < 0.0);
+        assert!(frustrated.frustrated);
+
+        let korean = score_message("아직도 안돼요 ㅡㅡ 짜증나");
+        assert!(korean.frustrated);
+
+        assert!(score_message("this is not good").valence < 0.0);
+    }
+
+    #[test]
+    fn test_empathy_boost() {
+        let point = |valence: f32, frustration: f32| MoodPoint {
+            message_id: None,
+            valence,
+            arousal: 0.5,
+            frustration,
+            frustrated: frustration >= FRUSTRATION_THRESHOLD,
+            timestamp: 0,
+        };
+
+        assert_eq!(empathy_boost(&[]), 0);
+        assert_eq!(empathy_boost(&[point(0.6, 0.0)]), 0);
+
+        let calming = empathy_boost(&[point(-0.8, 0.9), point(-0.8, 0.9), point(0.5, 0.0)]);
+        let upset = empathy_boost(&[point(0.5, 0.0), point(-0.8, 0.9), point(-0.8, 0.9)]);
+        assert!(upset > calming);
+        assert!(upset <= MAX_EMPATHY_BOOST);
+    }
+}
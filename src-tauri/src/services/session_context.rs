@@ -0,0 +1,449 @@
+//! Session Context Bridge (v3.9.0)
+//!
+//! Lets a chat hand retrieved memories to computer-control actions, e.g.
+//! "fill this form with my details from memory".
+//!
+//! Computer control runs on its own DB connection and knows nothing about
+//! the chat, so the chat opens a short-lived context session instead:
+//!
+//! Features:
+//! - Context sessions: facts retrieved from the semantic wiki for a chat request,
+//!   tied to the conversation and expiring after a few minutes
+//! - Prompt section listing the shared facts by id, so the model can cite them
+//! - Form filling (click field → type value) where every value names the facts it came from;
+//!   facts not shared with the session are rejected
+//! - Audit log of which memories were shared and used by which action
+//! - Memory-derived text is kept out of the computer action log
+
+#![allow(dead_code)]  // Phase 5: Chat ↔ computer control context
+
+use crate::database::Database;
+use crate::services::computer_control::ComputerControlService;
+use crate::services::provenance::Provenance;
+use crate::services::semantic_wiki::SemanticWikiService;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Context sessions expire this long after they were opened (10 minutes)
+const SESSION_TTL_MS: i64 = 10 * 60 * 1000;
+
+/// Facts retrieved per session
+const DEFAULT_FACT_LIMIT: usize = 8;
+const MAX_FACT_LIMIT: usize = 20;
+
+/// Fields filled per request
+const MAX_FORM_FIELDS: usize = 30;
+
+/// Pause between clicking a field and typing into it
+const FOCUS_DELAY_MS: u64 = 200;
+
+/// A memory shared with computer control
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextFact {
+    /// Wiki fact id
+    pub id: String,
+    pub statement: String,
+    pub entity: String,
+    /// Retrieval score (similarity x confidence)
+    pub score: f32,
+    pub provenance: Provenance,
+}
+
+/// Chat context handed to computer control
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedContext {
+    pub session_id: String,
+    pub conversation_id: String,
+    /// The chat request the facts were retrieved for
+    pub request: String,
+    pub facts: Vec<ContextFact>,
+    pub created_at: i64, // Unix millis
+    pub expires_at: i64, // Unix millis
+}
+
+impl SharedContext {
+    /// System prompt section listing the shared facts by id
+    pub fn prompt_section(&self) -> String {
+        if self.facts.is_empty() {
+            return "\n\n# Shared Memory\nNo stored memories match this request. Ask the user for the values.\n".to_string();
+        }
+        let mut section = String::from(
+            "\n\n# Shared Memory\nUse only these facts to fill in the user's details, and list the ids of the facts each value comes from:\n",
+        );
+        for fact in &self.facts {
+            section.push_str(&format!("- [{}] {}\n", fact.id, fact.statement));
+        }
+        section
+    }
+
+    fn fact(&self, fact_id: &str) -> Option<&ContextFact> {
+        self.facts.iter().find(|fact| fact.id == fact_id)
+    }
+}
+
+/// One form field to fill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormField {
+    /// Description of the input to click ("email field"); None types at the cursor
+    pub element: Option<String>,
+    pub value: String,
+    /// Shared facts the value was taken from
+    #[serde(default)]
+    pub fact_ids: Vec<String>,
+}
+
+/// Outcome of one field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldResult {
+    pub element: Option<String>,
+    pub success: bool,
+    pub error: Option<String>,
+    pub fact_ids: Vec<String>,
+}
+
+/// Outcome of a form fill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormFillResult {
+    pub session_id: String,
+    pub fields: Vec<FieldResult>,
+    /// Pressed Enter after the last field
+    pub submitted: bool,
+}
+
+/// Memory referenced by an audit entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsedMemory {
+    pub fact_id: String,
+    pub entity: String,
+}
+
+/// Audit record of memories shared with or used by computer control
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextAuditEntry {
+    pub id: String,
+    pub session_id: String,
+    pub conversation_id: String,
+    /// "share" | "type" | "submit"
+    pub action: String,
+    /// Element description, if any
+    pub target: Option<String>,
+    pub memories: Vec<UsedMemory>,
+    pub success: bool,
+    pub error: Option<String>,
+    pub created_at: i64, // Unix millis
+}
+
+/// Chat ↔ computer control context bridge
+pub struct SessionContextBridge {
+    db: Arc<Mutex<Database>>,
+    wiki: OnceLock<Arc<SemanticWikiService>>,
+    sessions: Mutex<HashMap<String, SharedContext>>,
+}
+
+impl SessionContextBridge {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        let bridge = Self {
+            db,
+            wiki: OnceLock::new(),
+            sessions: Mutex::new(HashMap::new()),
+        };
+        bridge.init_database()?;
+        log::info!("✓ Session Context Bridge initialized");
+        Ok(bridge)
+    }
+
+    fn init_database(&self) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn().execute_batch(
+            "CREATE TABLE IF NOT EXISTS computer_context_audit (
+                id TEXT PRIMARY KEY,
+                session_id TEXT NOT NULL,
+                conversation_id TEXT NOT NULL,
+                action TEXT NOT NULL,
+                target TEXT,
+                memories TEXT NOT NULL,
+                success INTEGER NOT NULL,
+                error TEXT,
+                created_at INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_computer_context_audit_conversation
+                ON computer_context_audit(conversation_id, created_at DESC);",
+        )?;
+        Ok(())
+    }
+
+    /// Attach the semantic wiki as the fact source
+    pub fn attach_wiki(&self, wiki: Arc<SemanticWikiService>) {
+        let _ = self.wiki.set(wiki);
+    }
+
+    /// Retrieve facts relevant to a chat request and open a context session with them
+    pub async fn share_from_chat(
+        &self,
+        conversation_id: &str,
+        request: &str,
+        limit: Option<usize>,
+    ) -> Result<SharedContext> {
+        let wiki = self.wiki.get().ok_or_else(|| anyhow!("Semantic wiki is not available"))?;
+        let limit = limit.unwrap_or(DEFAULT_FACT_LIMIT).clamp(1, MAX_FACT_LIMIT);
+        let facts = wiki
+            .search_for_context(request, limit)
+            .await?
+            .into_iter()
+            .map(|(fact, score)| ContextFact {
+                provenance: fact.provenance(),
+                id: fact.id,
+                statement: fact.statement,
+                entity: fact.entity,
+                score,
+            })
+            .collect();
+        self.open_session(conversation_id, request, facts)
+    }
+
+    /// Open a context session with the given facts (audited as "share")
+    pub fn open_session(&self, conversation_id: &str, request: &str, facts: Vec<ContextFact>) -> Result<SharedContext> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let context = SharedContext {
+            session_id: format!("ctx_{}", uuid::Uuid::new_v4()),
+            conversation_id: conversation_id.to_string(),
+            request: request.to_string(),
+            facts,
+            created_at: now,
+            expires_at: now + SESSION_TTL_MS,
+        };
+
+        let memories = context.facts.iter().map(used_memory).collect::<Vec<_>>();
+        self.record_audit(&context, "share", None, memories, None)?;
+
+        let mut sessions = self.sessions.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        sessions.retain(|_, session| session.expires_at > now);
+        sessions.insert(context.session_id.clone(), context.clone());
+        log::info!(
+            "Shared {} memories with computer control (session {})",
+            context.facts.len(),
+            context.session_id
+        );
+        Ok(context)
+    }
+
+    /// An open, unexpired session
+    pub fn get_session(&self, session_id: &str) -> Result<SharedContext> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut sessions = self.sessions.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        sessions.retain(|_, session| session.expires_at > now);
+        sessions
+            .get(session_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Context session not found or expired: {}", session_id))
+    }
+
+    /// End a session early; its facts can no longer be used
+    pub fn close_session(&self, session_id: &str) -> Result<bool> {
+        let mut sessions = self.sessions.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        Ok(sessions.remove(session_id).is_some())
+    }
+
+    /// Fill form fields with values taken from the session's facts
+    ///
+    /// All fields are validated before anything is typed; filling stops at the
+    /// first failing field.
+    pub async fn fill_form(
+        &self,
+        computer: &ComputerControlService,
+        session_id: &str,
+        fields: Vec<FormField>,
+        submit: bool,
+    ) -> Result<FormFillResult> {
+        let context = self.get_session(session_id)?;
+        let fields_memories = validate_fields(&context, &fields)?;
+
+        let mut results = Vec::with_capacity(fields.len());
+        let mut failed = false;
+        for (field, memories) in fields.into_iter().zip(fields_memories) {
+            let outcome = fill_field(computer, &field).await;
+            let error = outcome.err().map(|e| e.to_string());
+            self.record_audit(&context, "type", field.element.as_deref(), memories, error.as_deref())?;
+            failed = error.is_some();
+            results.push(FieldResult {
+                element: field.element,
+                success: error.is_none(),
+                error,
+                fact_ids: field.fact_ids,
+            });
+            if failed {
+                break;
+            }
+        }
+
+        let submitted = submit && !failed;
+        if submitted {
+            let error = computer.press_key("enter").await.err().map(|e| e.to_string());
+            self.record_audit(&context, "submit", None, Vec::new(), error.as_deref())?;
+            if let Some(error) = error {
+                return Err(anyhow!("Failed to submit form: {}", error));
+            }
+        }
+
+        Ok(FormFillResult {
+            session_id: context.session_id,
+            fields: results,
+            submitted,
+        })
+    }
+
+    /// Audit entries, newest first, optionally for one conversation
+    pub fn list_audit(&self, conversation_id: Option<&str>, limit: usize) -> Result<Vec<ContextAuditEntry>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT id, session_id, conversation_id, action, target, memories, success, error, created_at
+             FROM computer_context_audit
+             WHERE ?1 IS NULL OR conversation_id = ?1
+             ORDER BY created_at DESC, rowid DESC
+             LIMIT ?2",
+        )?;
+        let entries = stmt
+            .query_map(rusqlite::params![conversation_id, limit as i64], |row| {
+                let memories: String = row.get(5)?;
+                Ok(ContextAuditEntry {
+                    id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    conversation_id: row.get(2)?,
+                    action: row.get(3)?,
+                    target: row.get(4)?,
+                    memories: serde_json::from_str(&memories).unwrap_or_default(),
+                    success: row.get::<_, i64>(6)? != 0,
+                    error: row.get(7)?,
+                    created_at: row.get(8)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(entries)
+    }
+
+    fn record_audit(
+        &self,
+        context: &SharedContext,
+        action: &str,
+        target: Option<&str>,
+        memories: Vec<UsedMemory>,
+        error: Option<&str>,
+    ) -> Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn().execute(
+            "INSERT INTO computer_context_audit
+             (id, session_id, conversation_id, action, target, memories, success, error, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            rusqlite::params![
+                uuid::Uuid::new_v4().to_string(),
+                context.session_id,
+                context.conversation_id,
+                action,
+                target,
+                serde_json::to_string(&memories)?,
+                error.is_none(),
+                error,
+                chrono::Utc::now().timestamp_millis(),
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+fn used_memory(fact: &ContextFact) -> UsedMemory {
+    UsedMemory {
+        fact_id: fact.id.clone(),
+        entity: fact.entity.clone(),
+    }
+}
+
+/// Memories used by each field; errors if a field cites a fact the session doesn't hold
+fn validate_fields(context: &SharedContext, fields: &[FormField]) -> Result<Vec<Vec<UsedMemory>>> {
+    if fields.is_empty() {
+        return Err(anyhow!("No form fields given"));
+    }
+    if fields.len() > MAX_FORM_FIELDS {
+        return Err(anyhow!("At most {} fields can be filled at once", MAX_FORM_FIELDS));
+    }
+    fields
+        .iter()
+        .map(|field| {
+            if field.value.is_empty() {
+                return Err(anyhow!("Empty value for field {}", field.element.as_deref().unwrap_or("(cursor)")));
+            }
+            field
+                .fact_ids
+                .iter()
+                .map(|fact_id| {
+                    context
+                        .fact(fact_id)
+                        .map(used_memory)
+                        .ok_or_else(|| anyhow!("Fact {} was not shared with session {}", fact_id, context.session_id))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+async fn fill_field(computer: &ComputerControlService, field: &FormField) -> Result<()> {
+    if let Some(element) = field.element.as_deref() {
+        computer.click_element(element).await?;
+        computer.wait(FOCUS_DELAY_MS).await?;
+    }
+    let description = match field.element.as_deref() {
+        Some(element) => format!("Type into {} (from memory)", element),
+        None => "Type (from memory)".to_string(),
+    };
+    computer.type_text_described(&field.value, &description).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::provenance::ProvenanceSource;
+
+    fn fact(id: &str, statement: &str) -> ContextFact {
+        ContextFact {
+            id: id.to_string(),
+            statement: statement.to_string(),
+            entity: "User".to_string(),
+            score: 0.9,
+            provenance: Provenance::new(ProvenanceSource::WikiFact, id),
+        }
+    }
+
+    #[test]
+    fn test_sessions_and_audit() {
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let bridge = SessionContextBridge::new(db).unwrap();
+        let context = bridge
+            .open_session("conv_1", "fill this form with my details", vec![fact("f1", "User's email is kim@example.com")])
+            .unwrap();
+        assert!(context.prompt_section().contains("[f1] User's email is kim@example.com"));
+        assert_eq!(bridge.get_session(&context.session_id).unwrap().facts.len(), 1);
+
+        let fields = vec![
+            FormField { element: Some("email field".into()), value: "kim@example.com".into(), fact_ids: vec!["f1".into()] },
+            FormField { element: None, value: "typed by hand".into(), fact_ids: Vec::new() },
+        ];
+        let memories = validate_fields(&context, &fields).unwrap();
+        assert_eq!(memories[0], vec![UsedMemory { fact_id: "f1".into(), entity: "User".into() }]);
+        assert!(memories[1].is_empty());
+
+        let unshared = vec![FormField { element: None, value: "x".into(), fact_ids: vec!["f2".into()] }];
+        assert!(validate_fields(&context, &unshared).is_err());
+
+        let audit = bridge.list_audit(Some("conv_1"), 10).unwrap();
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].action, "share");
+        assert_eq!(audit[0].memories[0].fact_id, "f1");
+        assert!(bridge.list_audit(Some("conv_2"), 10).unwrap().is_empty());
+
+        assert!(bridge.close_session(&context.session_id).unwrap());
+        assert!(bridge.get_session(&context.session_id).is_err());
+    }
+}
@@ -0,0 +1,272 @@
+//! Startup Profiler (v3.9.0)
+//!
+//! Dependency-aware service initialization with per-service timings.
+//!
+//! Features:
+//! - `checkpoint` timings for services initialized in order on the main thread
+//! - `spawn` for independent groups (embedding, graph, vision) initialized
+//!   on their own threads while the main thread continues; `join` records how
+//!   long the main thread had to wait
+//! - `LazyService` for rarely-used services, initialized on first use
+//! - `defer` / `record_lazy` for services warmed up in the background (RAG)
+//! - `report` backing the `startup_report` command
+
+#![allow(dead_code)]  // Phase 5: Startup profiling
+
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::ops::Deref;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Start of initialization (set by `begin`, or by the first recorded stage)
+static STARTED: OnceLock<Instant> = OnceLock::new();
+
+/// Wall-clock time until Tauri was started
+static TOTAL_MS: OnceLock<u64> = OnceLock::new();
+
+static STAGES: Mutex<Vec<StageTiming>> = Mutex::new(Vec::new());
+
+/// Lazy services that have not been used yet
+static DEFERRED: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+thread_local! {
+    /// End of the previous checkpoint on this thread
+    static LAST_CHECKPOINT: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// How a stage was initialized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StageKind {
+    /// In order on the main thread
+    Sequential,
+    /// On an init thread, concurrently with the main thread
+    Parallel,
+    /// Main thread blocked on a parallel group
+    Wait,
+    /// On first use, after startup
+    Lazy,
+}
+
+/// Timing of one initialization stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StageTiming {
+    pub name: String,
+    pub kind: StageKind,
+    pub thread: String,
+    /// Offset from the start of initialization
+    pub started_ms: u64,
+    pub duration_ms: u64,
+}
+
+/// Per-service startup timings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartupReport {
+    /// Wall-clock initialization time (None while still starting)
+    pub total_ms: Option<u64>,
+    /// Sum of all eager stages, i.e. what a fully sequential start would cost
+    pub sequential_ms: u64,
+    /// Stages in start order
+    pub stages: Vec<StageTiming>,
+    /// Lazy services not initialized yet
+    pub deferred: Vec<String>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn started() -> Instant {
+    *STARTED.get_or_init(Instant::now)
+}
+
+fn record(name: &str, kind: StageKind, start: Instant, duration: Duration) {
+    let thread = std::thread::current().name().unwrap_or("unnamed").to_string();
+    lock(&STAGES).push(StageTiming {
+        name: name.to_string(),
+        kind,
+        thread,
+        started_ms: start.saturating_duration_since(started()).as_millis() as u64,
+        duration_ms: duration.as_millis() as u64,
+    });
+}
+
+/// Mark the start of initialization (called first thing in main)
+pub fn begin() {
+    let now = started();
+    LAST_CHECKPOINT.with(|last| last.set(Some(now)));
+}
+
+/// Record everything since the previous checkpoint on this thread as `name`
+pub fn checkpoint(name: &str) {
+    let now = Instant::now();
+    let since = LAST_CHECKPOINT.with(|last| last.replace(Some(now))).unwrap_or_else(started);
+    record(name, StageKind::Sequential, since, now - since);
+}
+
+/// Time `init` as a stage of the current thread
+pub fn stage<T>(name: &str, init: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let value = init();
+    let kind = if std::thread::current().name() == Some("main") {
+        StageKind::Sequential
+    } else {
+        StageKind::Parallel
+    };
+    record(name, kind, start, start.elapsed());
+    LAST_CHECKPOINT.with(|last| last.set(Some(Instant::now())));
+    value
+}
+
+/// Register a service initialized after startup (listed as deferred until `record_lazy`)
+pub fn defer(name: &'static str) {
+    lock(&DEFERRED).push(name);
+}
+
+/// Record the late initialization of a deferred service that just finished
+pub fn record_lazy(name: &str, duration: Duration) {
+    let now = Instant::now();
+    record(name, StageKind::Lazy, now.checked_sub(duration).unwrap_or(now), duration);
+    lock(&DEFERRED).retain(|deferred| *deferred != name);
+}
+
+/// Parallel initialization group running on its own thread
+pub struct InitTask<T> {
+    name: &'static str,
+    handle: JoinHandle<T>,
+}
+
+/// Initialize `init` on a new thread; use `stage` inside it to time sub-stages
+pub fn spawn<T: Send + 'static>(name: &'static str, init: impl FnOnce() -> T + Send + 'static) -> InitTask<T> {
+    started();
+    let handle = std::thread::Builder::new()
+        .name(format!("init-{}", name))
+        .spawn(init)
+        .expect("Failed to spawn init thread");
+    InitTask { name, handle }
+}
+
+impl<T> InitTask<T> {
+    /// Wait for the group, re-raising a panic from its thread
+    ///
+    /// Call `checkpoint` first, the wait itself is recorded separately.
+    pub fn join(self) -> T {
+        let start = Instant::now();
+        let value = self.handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+        let waited = start.elapsed();
+        if waited >= Duration::from_millis(1) {
+            record(self.name, StageKind::Wait, start, waited);
+        }
+        LAST_CHECKPOINT.with(|last| last.set(Some(Instant::now())));
+        value
+    }
+}
+
+/// Service initialized on first use
+///
+/// Derefs to the service, so commands can take `State<'_, LazyService<T>>`
+/// and call it like the service itself.
+pub struct LazyService<T> {
+    name: &'static str,
+    init: Mutex<Option<Box<dyn FnOnce() -> T + Send>>>,
+    cell: OnceLock<T>,
+}
+
+impl<T> LazyService<T> {
+    pub fn new(name: &'static str, init: impl FnOnce() -> T + Send + 'static) -> Self {
+        defer(name);
+        Self {
+            name,
+            init: Mutex::new(Some(Box::new(init))),
+            cell: OnceLock::new(),
+        }
+    }
+
+    /// The service, initializing it on the first call
+    pub fn get(&self) -> &T {
+        self.cell.get_or_init(|| {
+            let init = lock(&self.init)
+                .take()
+                .unwrap_or_else(|| panic!("{} failed to initialize earlier", self.name));
+            log::info!("Initializing {} on first use...", self.name);
+            let start = Instant::now();
+            let value = init();
+            record_lazy(self.name, start.elapsed());
+            value
+        })
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.cell.get().is_some()
+    }
+}
+
+impl<T> Deref for LazyService<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.get()
+    }
+}
+
+/// Mark initialization as finished (called right before Tauri starts)
+pub fn finish(total: Duration) {
+    let _ = TOTAL_MS.set(total.as_millis() as u64);
+}
+
+/// Timings recorded so far
+pub fn report() -> StartupReport {
+    let mut stages = lock(&STAGES).clone();
+    stages.sort_by_key(|s| s.started_ms);
+    let sequential_ms = stages
+        .iter()
+        .filter(|s| matches!(s.kind, StageKind::Sequential | StageKind::Parallel))
+        .map(|s| s.duration_ms)
+        .sum();
+    StartupReport {
+        total_ms: TOTAL_MS.get().copied(),
+        sequential_ms,
+        stages,
+        deferred: lock(&DEFERRED).iter().map(|name| name.to_string()).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_spawn_records_parallel_stages() {
+        let task = spawn("test_group", || stage("test_group_inner", || 41) + 1);
+        assert_eq!(task.join(), 42);
+
+        let inner = report().stages.into_iter().find(|s| s.name == "test_group_inner").unwrap();
+        assert_eq!(inner.kind, StageKind::Parallel);
+        assert_eq!(inner.thread, "init-test_group");
+    }
+
+    #[test]
+    fn test_lazy_service_initializes_once_on_first_use() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let lazy = LazyService::new("test_lazy", move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            String::from("ready")
+        });
+
+        assert!(!lazy.is_initialized());
+        assert!(report().deferred.contains(&"test_lazy".to_string()));
+
+        assert_eq!(lazy.len(), 5);
+        assert_eq!(lazy.get(), "ready");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let report = report();
+        assert!(!report.deferred.contains(&"test_lazy".to_string()));
+        assert!(report.stages.iter().any(|s| s.name == "test_lazy" && s.kind == StageKind::Lazy));
+    }
+}
@@ -9,12 +9,15 @@
 #![allow(dead_code)]  // Phase 18: Streaming vision (proactive mode)
 
 use crate::services::{screen::ScreenCaptureService, llava::LlavaService};
+use crate::services::degradation::ServiceSlot;
+use crate::services::vision_backend::VisionTask;
+use crate::services::notification::{AppNotification, NotificationAction, NotificationService, NotificationSource, DEFAULT_SNOOZE_MINUTES};
 use crate::database::Database;
 use anyhow::{Context, Result};
 use screenshots::Screen;
 use base64::{Engine as _, engine::general_purpose};
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::time::interval;
 use sha2::{Sha256, Digest};
@@ -89,15 +92,17 @@ pub struct StreamingVisionService {
     config: Arc<Mutex<StreamingVisionConfig>>,
     state: Arc<Mutex<StreamingVisionState>>,
     screen_service: Arc<ScreenCaptureService>,
-    llava_service: Arc<LlavaService>,
+    llava_service: Arc<ServiceSlot<LlavaService>>,  // v3.9.0: Frames are captured but not analyzed while unavailable
     db: Arc<Mutex<Database>>,
+    /// Native notifications for "notification" alerts (v3.9.0)
+    notifications: OnceLock<Arc<NotificationService>>,
 }
 
 impl StreamingVisionService {
     /// Create new streaming vision service
     pub fn new(
         screen_service: Arc<ScreenCaptureService>,
-        llava_service: Arc<LlavaService>,
+        llava_service: Arc<ServiceSlot<LlavaService>>,
         db: Arc<Mutex<Database>>,
     ) -> Result<Self> {
         let service = Self {
@@ -106,6 +111,7 @@ impl StreamingVisionService {
             screen_service,
             llava_service,
             db,
+            notifications: OnceLock::new(),
         };
 
         service.init_database()?;
@@ -113,6 +119,11 @@ impl StreamingVisionService {
         Ok(service)
     }
 
+    /// Attach the notification service used for "notification" alerts (v3.9.0)
+    pub fn attach_notifications(&self, notifications: Arc<NotificationService>) {
+        let _ = self.notifications.set(notifications);
+    }
+
     /// Initialize database table for vision analysis
     fn init_database(&self) -> Result<()> {
         let db = self.db.lock().unwrap();
@@ -160,6 +171,7 @@ impl StreamingVisionService {
         let config_clone = Arc::clone(&self.config);
         let llava_clone = Arc::clone(&self.llava_service);
         let db_clone = Arc::clone(&self.db);
+        let notifications_clone = self.notifications.get().cloned();
 
         tokio::spawn(async move {
             let mut interval_timer = interval(Duration::from_secs(interval_secs));
@@ -183,6 +195,7 @@ impl StreamingVisionService {
                     &config_clone,
                     &llava_clone,
                     &db_clone,
+                    notifications_clone.as_ref(),
                 ).await {
                     log::error!("Streaming vision error: {}", e);
                 }
@@ -210,8 +223,9 @@ impl StreamingVisionService {
     async fn capture_and_analyze_static(
         state: &Arc<Mutex<StreamingVisionState>>,
         config: &Arc<Mutex<StreamingVisionConfig>>,
-        llava: &Arc<LlavaService>,
+        llava: &Arc<ServiceSlot<LlavaService>>,
         db: &Arc<Mutex<Database>>,
+        notifications: Option<&Arc<NotificationService>>,
     ) -> Result<()> {
         // 1. Capture screen
         let screenshot = Self::capture_screen().await?;
@@ -253,7 +267,14 @@ impl StreamingVisionService {
         let analysis = if is_significant_change {
             let prompt = config.lock().unwrap().analysis_prompt.clone();
 
-            match llava.analyze_image(screenshot.clone(), Some(prompt)).await {
+            let result = match llava.get() {
+                Ok(llava) => llava
+                    .analyze_image_for_task(screenshot.clone(), Some(prompt), VisionTask::Fast)
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            match result {
                 Ok(result) => {
                     state.lock().unwrap().analysis_count += 1;
                     Some(result)
@@ -271,7 +292,7 @@ impl StreamingVisionService {
         let (alert_sent, alert_method) = if is_significant_change {
             if let Some(ref analysis_text) = analysis {
                 if !analysis_text.contains("No significant changes") {
-                    Self::send_alert_static(analysis_text, config, state).await?
+                    Self::send_alert_static(analysis_text, config, state, notifications).await?
                 } else {
                     (false, None)
                 }
@@ -333,20 +354,22 @@ impl StreamingVisionService {
         }
     }
 
-    /// Send proactive alert (log-based - TTS removed to reduce latency)
+    /// Send proactive alert (log or native notification - TTS removed to reduce latency)
     async fn send_alert_static(
         message: &str,
         config: &Arc<Mutex<StreamingVisionConfig>>,
         state: &Arc<Mutex<StreamingVisionState>>,
+        notifications: Option<&Arc<NotificationService>>,
     ) -> Result<(bool, Option<String>)> {
-        let config_guard = config.lock().unwrap();
+        let alert_methods = {
+            let config_guard = config.lock().unwrap();
 
-        if !config_guard.enable_alerts {
-            return Ok((false, None));
-        }
+            if !config_guard.enable_alerts {
+                return Ok((false, None));
+            }
 
-        let alert_methods = config_guard.alert_methods.clone();
-        drop(config_guard);
+            config_guard.alert_methods.clone()
+        };
 
         for method in &alert_methods {
             match method.as_str() {
@@ -358,8 +381,22 @@ impl StreamingVisionService {
                     return Ok((true, Some("log".to_string())));
                 }
                 "notification" => {
-                    // Desktop notification (future implementation)
-                    log::warn!("Desktop notifications not yet implemented");
+                    if let Some(notifications) = notifications {
+                        let notification = AppNotification::new(
+                            NotificationSource::StreamingVision,
+                            "Something changed on your screen",
+                            message.to_string(),
+                        )
+                        .with_action(NotificationAction::OpenChat { prompt: Some(message.to_string()) })
+                        .with_action(NotificationAction::Snooze { minutes: DEFAULT_SNOOZE_MINUTES });
+
+                        if notifications.notify(notification).await? {
+                            state.lock().unwrap().alert_count += 1;
+                            return Ok((true, Some("notification".to_string())));
+                        }
+                    } else {
+                        log::warn!("Notification service not attached");
+                    }
                 }
                 "chat" => {
                     // Send to chat interface (future implementation)
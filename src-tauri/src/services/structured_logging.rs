@@ -9,6 +9,8 @@
  * - File rotation with tracing-appender
  * - Environment-based log level filtering
  * - Compatibility with existing log facade
+ * - LLM call spans persisted to the `llm_calls` table (v3.9.0)
+ * - Recent events kept as crash report breadcrumbs (v3.9.0)
  *
  * Log Levels:
  * - ERROR: Critical failures requiring immediate attention
@@ -29,12 +31,22 @@
  * ```
  */
 
+use crate::database::Database;
+use crate::services::crash_reporter::BreadcrumbLayer;
+use anyhow::anyhow;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use tracing::Level;
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Level, Subscriber};
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::{
     fmt::{self, format::FmtSpan},
-    layer::SubscriberExt,
+    layer::{Context, Layer, SubscriberExt},
+    registry::LookupSpan,
     util::SubscriberInitExt,
     EnvFilter,
 };
@@ -123,6 +135,9 @@ pub fn init_logging(config: LoggingConfig) -> anyhow::Result<()> {
             ))
         });
 
+    // LLM call spans are captured regardless of output format (v3.9.0)
+    let llm_call_layer = LlmCallLayer::install();
+
     // Determine span events to capture
     let span_events = if config.span_events {
         FmtSpan::NEW | FmtSpan::CLOSE
@@ -134,6 +149,8 @@ pub fn init_logging(config: LoggingConfig) -> anyhow::Result<()> {
         // JSON formatted logging for production/log aggregation
         let subscriber = tracing_subscriber::registry()
             .with(env_filter)
+            .with(llm_call_layer)
+            .with(BreadcrumbLayer)
             .with(
                 fmt::layer()
                     .json()
@@ -175,6 +192,8 @@ pub fn init_logging(config: LoggingConfig) -> anyhow::Result<()> {
         // Human-readable format for development
         let subscriber = tracing_subscriber::registry()
             .with(env_filter)
+            .with(llm_call_layer)
+            .with(BreadcrumbLayer)
             .with(
                 fmt::layer()
                     .with_span_events(span_events)
@@ -301,6 +320,329 @@ pub fn embedding_span(text_len: usize) -> tracing::Span {
     tracing::span!(Level::DEBUG, "embedding", text_len = text_len)
 }
 
+// ============================================================================
+// LLM CALL TRACING (v3.9.0)
+// ============================================================================
+
+/// Span name picked up by `LlmCallLayer`
+pub const LLM_CALL_SPAN: &str = "llm_call";
+
+/// Records buffered before the database is attached (further ones are dropped)
+const LLM_CALL_BUFFER: usize = 1024;
+
+/// Rows older than this are pruned at startup
+const LLM_CALL_RETENTION_DAYS: i64 = 30;
+
+/// Receiving end of the layer's channel, claimed by `LlmCallLog::start`
+static LLM_CALL_RECEIVER: Mutex<Option<Receiver<LlmCallRecord>>> = Mutex::new(None);
+
+/// Create a span for one model call (Ollama, embedding, vision)
+///
+/// Record `prompt_tokens` / `completion_tokens` when known and `success = true`
+/// once the call returns; the span is persisted when it is dropped.
+///
+/// ```ignore
+/// let span = llm_call_span("ollama", "qwen2.5:7b", "generate");
+/// // ... call the model ...
+/// span.record("prompt_tokens", 120u32);
+/// span.record("completion_tokens", 48u32);
+/// span.record("success", true);
+/// ```
+pub fn llm_call_span(kind: &str, model: &str, operation: &str) -> tracing::Span {
+    tracing::info_span!(
+        "llm_call",
+        kind = kind,
+        model = model,
+        operation = operation,
+        prompt_tokens = tracing::field::Empty,
+        completion_tokens = tracing::field::Empty,
+        success = tracing::field::Empty,
+    )
+}
+
+/// One persisted model call
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LlmCallRecord {
+    /// "ollama", "embedding", or "vision"
+    pub kind: String,
+    pub model: String,
+    pub operation: String,
+    pub prompt_tokens: Option<u32>,
+    pub completion_tokens: Option<u32>,
+    pub duration_ms: u64,
+    pub success: bool,
+    /// Unix timestamp (milliseconds) when the call started
+    pub started_at: i64,
+}
+
+/// Filter for `LlmCallLog::query`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LlmCallQuery {
+    pub kind: Option<String>,
+    pub model: Option<String>,
+    /// Only calls started at or after this Unix timestamp (milliseconds)
+    pub since: Option<i64>,
+    /// Only calls at least this slow
+    pub min_duration_ms: Option<u64>,
+    pub limit: Option<usize>,
+}
+
+/// Span fields collected while an `llm_call` span is open
+#[derive(Debug, Default)]
+struct LlmCallFields {
+    kind: String,
+    model: String,
+    operation: String,
+    prompt_tokens: Option<u32>,
+    completion_tokens: Option<u32>,
+    success: bool,
+}
+
+impl Visit for LlmCallFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "kind" => self.kind = value.to_string(),
+            "model" => self.model = value.to_string(),
+            "operation" => self.operation = value.to_string(),
+            _ => {}
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        let value = u32::try_from(value).unwrap_or(u32::MAX);
+        match field.name() {
+            "prompt_tokens" => self.prompt_tokens = Some(value),
+            "completion_tokens" => self.completion_tokens = Some(value),
+            _ => {}
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record_u64(field, value.max(0) as u64);
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        if field.name() == "success" {
+            self.success = value;
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.record_str(field, format!("{:?}", value).trim_matches('"'));
+    }
+}
+
+/// Per-span state stored in the registry's span extensions
+struct LlmCallTiming {
+    fields: LlmCallFields,
+    started: std::time::Instant,
+    started_at: i64,
+}
+
+/// Tracing layer that turns closed `llm_call` spans into `LlmCallRecord`s
+pub struct LlmCallLayer {
+    sender: SyncSender<LlmCallRecord>,
+}
+
+impl LlmCallLayer {
+    /// Create the layer and park its receiver for `LlmCallLog::start`
+    fn install() -> Self {
+        let (sender, receiver) = mpsc::sync_channel(LLM_CALL_BUFFER);
+        if let Ok(mut slot) = LLM_CALL_RECEIVER.lock() {
+            *slot = Some(receiver);
+        }
+        Self { sender }
+    }
+}
+
+impl<S> Layer<S> for LlmCallLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if attrs.metadata().name() != LLM_CALL_SPAN {
+            return;
+        }
+
+        let mut fields = LlmCallFields::default();
+        attrs.record(&mut fields);
+
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(LlmCallTiming {
+                fields,
+                started: std::time::Instant::now(),
+                started_at: chrono::Utc::now().timestamp_millis(),
+            });
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(timing) = span.extensions_mut().get_mut::<LlmCallTiming>() {
+                values.record(&mut timing.fields);
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let timing = match ctx.span(&id) {
+            Some(span) => span.extensions_mut().remove::<LlmCallTiming>(),
+            None => None,
+        };
+
+        if let Some(timing) = timing {
+            let record = LlmCallRecord {
+                kind: timing.fields.kind,
+                model: timing.fields.model,
+                operation: timing.fields.operation,
+                prompt_tokens: timing.fields.prompt_tokens,
+                completion_tokens: timing.fields.completion_tokens,
+                duration_ms: timing.started.elapsed().as_millis() as u64,
+                success: timing.fields.success,
+                started_at: timing.started_at,
+            };
+            // Never block the caller: drop the record if the writer has fallen behind
+            let _ = self.sender.try_send(record);
+        }
+    }
+}
+
+/// Persists LLM call spans into SQLite and serves them back for debugging
+pub struct LlmCallLog {
+    db: Arc<Mutex<Database>>,
+}
+
+impl LlmCallLog {
+    pub fn new(db: Arc<Mutex<Database>>) -> anyhow::Result<Self> {
+        {
+            let db = db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+            let conn = db.conn();
+
+            conn.execute(
+                "CREATE TABLE IF NOT EXISTS llm_calls (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    kind TEXT NOT NULL,
+                    model TEXT NOT NULL,
+                    operation TEXT NOT NULL,
+                    prompt_tokens INTEGER,
+                    completion_tokens INTEGER,
+                    duration_ms INTEGER NOT NULL,
+                    success INTEGER NOT NULL,
+                    started_at INTEGER NOT NULL
+                )",
+                [],
+            )?;
+            conn.execute(
+                "CREATE INDEX IF NOT EXISTS idx_llm_calls_started_at ON llm_calls(started_at DESC)",
+                [],
+            )?;
+
+            let cutoff = chrono::Utc::now().timestamp_millis() - LLM_CALL_RETENTION_DAYS * 24 * 60 * 60 * 1000;
+            conn.execute("DELETE FROM llm_calls WHERE started_at < ?1", params![cutoff])?;
+        }
+
+        tracing::info!("✓ LLM call log initialized");
+        Ok(Self { db })
+    }
+
+    /// Start the background writer draining the tracing layer's channel
+    ///
+    /// No-op when structured logging was not initialized (e.g. in tests).
+    pub fn start(self: &Arc<Self>) {
+        let receiver = match LLM_CALL_RECEIVER.lock().ok().and_then(|mut slot| slot.take()) {
+            Some(receiver) => receiver,
+            None => {
+                tracing::warn!("LLM call tracing layer not installed, calls will not be persisted");
+                return;
+            }
+        };
+
+        let log = Arc::clone(self);
+        let spawned = std::thread::Builder::new()
+            .name("llm-call-log".to_string())
+            .spawn(move || {
+                // Blocks until the next record, then writes everything already queued
+                while let Ok(first) = receiver.recv() {
+                    let mut batch = vec![first];
+                    batch.extend(receiver.try_iter());
+                    if let Err(e) = log.insert_batch(&batch) {
+                        tracing::warn!(error = %e, count = batch.len(), "Failed to persist LLM calls");
+                    }
+                }
+            });
+        if let Err(e) = spawned {
+            tracing::error!(error = %e, "Failed to start LLM call writer");
+        }
+    }
+
+    fn insert_batch(&self, records: &[LlmCallRecord]) -> anyhow::Result<()> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let tx = db.conn().unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO llm_calls
+                 (kind, model, operation, prompt_tokens, completion_tokens, duration_ms, success, started_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?;
+            for record in records {
+                stmt.execute(params![
+                    record.kind,
+                    record.model,
+                    record.operation,
+                    record.prompt_tokens,
+                    record.completion_tokens,
+                    record.duration_ms as i64,
+                    record.success,
+                    record.started_at,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Most recent calls matching the filter (newest first)
+    pub fn query(&self, filter: &LlmCallQuery) -> anyhow::Result<Vec<LlmCallRecord>> {
+        let db = self.db.lock().map_err(|e| anyhow!("Lock error: {}", e))?;
+        let mut stmt = db.conn().prepare(
+            "SELECT kind, model, operation, prompt_tokens, completion_tokens, duration_ms, success, started_at
+             FROM llm_calls
+             WHERE (?1 IS NULL OR kind = ?1)
+               AND (?2 IS NULL OR model = ?2)
+               AND (?3 IS NULL OR started_at >= ?3)
+               AND (?4 IS NULL OR duration_ms >= ?4)
+             ORDER BY started_at DESC
+             LIMIT ?5",
+        )?;
+
+        let records = stmt
+            .query_map(
+                params![
+                    filter.kind,
+                    filter.model,
+                    filter.since,
+                    filter.min_duration_ms.map(|ms| ms as i64),
+                    filter.limit.unwrap_or(100).min(1000) as i64,
+                ],
+                |row| {
+                    Ok(LlmCallRecord {
+                        kind: row.get(0)?,
+                        model: row.get(1)?,
+                        operation: row.get(2)?,
+                        prompt_tokens: row.get(3)?,
+                        completion_tokens: row.get(4)?,
+                        duration_ms: row.get::<_, i64>(5)? as u64,
+                        success: row.get(6)?,
+                        started_at: row.get(7)?,
+                    })
+                },
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(records)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -329,6 +671,30 @@ mod tests {
         assert!(config.ansi_colors);
     }
 
+    #[test]
+    fn test_llm_call_layer_records_span() {
+        let (sender, receiver) = mpsc::sync_channel(8);
+        let subscriber = tracing_subscriber::registry().with(LlmCallLayer { sender });
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = llm_call_span("ollama", "qwen2.5:7b", "generate");
+            span.record("prompt_tokens", 120u32);
+            span.record("completion_tokens", 48u32);
+            span.record("success", true);
+
+            // Unrelated spans are ignored
+            let _other = ai_span("qwen2.5:7b", "chat");
+        });
+
+        let records: Vec<LlmCallRecord> = receiver.try_iter().collect();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].kind, "ollama");
+        assert_eq!(records[0].model, "qwen2.5:7b");
+        assert_eq!(records[0].prompt_tokens, Some(120));
+        assert_eq!(records[0].completion_tokens, Some(48));
+        assert!(records[0].success);
+    }
+
     #[test]
     fn test_perf_timer() {
         let timer = PerfTimer::new("test_operation");
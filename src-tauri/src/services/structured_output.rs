@@ -0,0 +1,376 @@
+//! Structured Output (v3.9.0)
+//!
+//! JSON-schema constrained generation with the local model.
+//!
+//! Features:
+//! - The schema is passed to Ollama's `format` parameter (grammar-constrained decoding)
+//!   and included in the prompt for models / versions that ignore it
+//! - Every reply is parsed and validated against the schema; invalid replies are
+//!   retried with the validation errors fed back to the model
+//! - `generate_typed` deserializes the validated data into a Rust type
+//!
+//! Used by entity_extractor and pattern_detector, the `chat_structured` command and
+//! plugins with the `llm` permission.
+//!
+//! The validator covers the schema subset models are asked for in practice: type,
+//! enum, const, properties, required, additionalProperties, items, anyOf / oneOf,
+//! minimum / maximum, minLength / maxLength and minItems / maxItems. Other keywords are ignored.
+
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::ollama;
+
+/// Validation errors shown to the model in a retry prompt
+const MAX_ERRORS_IN_PROMPT: usize = 8;
+
+/// Generation settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructuredOptions {
+    /// Model override; None uses the chat model
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Instructions placed before the prompt
+    #[serde(default)]
+    pub system: Option<String>,
+    /// Attempts after the first one when the reply doesn't match the schema
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: i32,
+}
+
+fn default_max_retries() -> u32 {
+    2
+}
+
+fn default_max_tokens() -> i32 {
+    1024
+}
+
+impl Default for StructuredOptions {
+    fn default() -> Self {
+        Self {
+            model: None,
+            system: None,
+            max_retries: default_max_retries(),
+            max_tokens: default_max_tokens(),
+        }
+    }
+}
+
+/// Validated reply
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructuredOutput {
+    pub data: Value,
+    /// Model calls it took (1 = valid on the first try)
+    pub attempts: u32,
+    /// Raw text of the accepted reply
+    pub raw: String,
+}
+
+/// Generate JSON matching `schema` for `prompt`
+pub async fn generate(prompt: &str, schema: &Value, options: &StructuredOptions) -> Result<StructuredOutput> {
+    check_schema(schema)?;
+    let schema_text = serde_json::to_string_pretty(schema)?;
+
+    let mut feedback: Option<String> = None;
+    let mut last_errors = Vec::new();
+    for attempt in 1..=options.max_retries + 1 {
+        let full_prompt = build_prompt(prompt, &schema_text, options.system.as_deref(), feedback.as_deref());
+        let raw = ollama::complete_with_schema(full_prompt, schema, options.model.as_deref(), options.max_tokens)
+            .await
+            .map_err(|e| anyhow!(e))?;
+
+        let errors = match extract_json(&raw) {
+            Some(data) => {
+                let errors = validate(&data, schema);
+                if errors.is_empty() {
+                    if attempt > 1 {
+                        log::info!("Structured output valid after {} attempts", attempt);
+                    }
+                    return Ok(StructuredOutput { data, attempts: attempt, raw });
+                }
+                errors
+            }
+            None => vec!["reply is not valid JSON".to_string()],
+        };
+
+        log::warn!("Structured output attempt {} invalid: {}", attempt, errors.join("; "));
+        feedback = Some(retry_feedback(&raw, &errors));
+        last_errors = errors;
+    }
+
+    Err(anyhow!(
+        "Model reply did not match the schema after {} attempts: {}",
+        options.max_retries + 1,
+        last_errors.join("; ")
+    ))
+}
+
+/// Generate JSON matching `schema` and deserialize it into `T`
+pub async fn generate_typed<T: DeserializeOwned>(prompt: &str, schema: &Value, options: &StructuredOptions) -> Result<T> {
+    let output = generate(prompt, schema, options).await?;
+    serde_json::from_value(output.data).map_err(|e| anyhow!("Structured output doesn't fit the expected type: {}", e))
+}
+
+/// Reject schemas the model can't be constrained with
+pub fn check_schema(schema: &Value) -> Result<()> {
+    match schema {
+        Value::Object(map) if !map.is_empty() => Ok(()),
+        Value::Bool(true) => Ok(()),
+        _ => Err(anyhow!("Schema must be a non-empty JSON schema object")),
+    }
+}
+
+fn build_prompt(prompt: &str, schema_text: &str, system: Option<&str>, feedback: Option<&str>) -> String {
+    let mut full_prompt = String::new();
+    if let Some(system) = system.filter(|s| !s.trim().is_empty()) {
+        full_prompt.push_str(system.trim());
+        full_prompt.push_str("\n\n");
+    }
+    full_prompt.push_str(prompt.trim());
+    full_prompt.push_str("\n\nReply with a single JSON value matching this JSON schema, and nothing else:\n");
+    full_prompt.push_str(schema_text);
+    if let Some(feedback) = feedback {
+        full_prompt.push_str("\n\n");
+        full_prompt.push_str(feedback);
+    }
+    full_prompt.push_str("\n\nJSON:");
+    full_prompt
+}
+
+fn retry_feedback(raw: &str, errors: &[String]) -> String {
+    let previous: String = raw.chars().take(1500).collect();
+    let listed: Vec<String> = errors
+        .iter()
+        .take(MAX_ERRORS_IN_PROMPT)
+        .map(|e| format!("- {}", e))
+        .collect();
+    format!(
+        "Your previous reply was rejected:\n{}\n\nProblems:\n{}\n\nFix these problems and reply again.",
+        previous,
+        listed.join("\n")
+    )
+}
+
+/// Parse the JSON in a model reply (tolerates code fences and surrounding text)
+pub fn extract_json(text: &str) -> Option<Value> {
+    let trimmed = text.trim();
+    if let Ok(value) = serde_json::from_str(trimmed) {
+        return Some(value);
+    }
+
+    let unfenced = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.trim_end().strip_suffix("```"));
+    if let Some(inner) = unfenced {
+        if let Ok(value) = serde_json::from_str(inner.trim()) {
+            return Some(value);
+        }
+    }
+
+    for (open, close) in [('{', '}'), ('[', ']')] {
+        if let (Some(start), Some(end)) = (trimmed.find(open), trimmed.rfind(close)) {
+            if start < end {
+                if let Ok(value) = serde_json::from_str(&trimmed[start..=end]) {
+                    return Some(value);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Validate `value` against `schema`; returns one message per problem
+pub fn validate(value: &Value, schema: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    validate_at(value, schema, "$", &mut errors);
+    errors
+}
+
+fn validate_at(value: &Value, schema: &Value, path: &str, errors: &mut Vec<String>) {
+    let schema = match schema {
+        Value::Object(schema) => schema,
+        Value::Bool(false) => {
+            errors.push(format!("{}: no value allowed here", path));
+            return;
+        }
+        _ => return,
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(|t| t.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| has_type(value, t)) {
+            errors.push(format!("{}: expected {}, got {}", path, types.join(" or "), type_name(value)));
+            return;
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            errors.push(format!("{}: must be one of {}", path, Value::Array(allowed.clone())));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if value != expected {
+            errors.push(format!("{}: must be {}", path, expected));
+        }
+    }
+
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(Value::Array(options)) = schema.get(keyword) {
+            let matches = options.iter().filter(|option| validate(value, option).is_empty()).count();
+            let ok = if keyword == "oneOf" { matches == 1 } else { matches > 0 };
+            if !ok {
+                errors.push(format!("{}: doesn't match {} of the allowed schemas", path, if keyword == "oneOf" { "exactly one" } else { "any" }));
+            }
+        }
+    }
+
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for key in required.iter().filter_map(|k| k.as_str()) {
+                    if !object.contains_key(key) {
+                        errors.push(format!("{}: missing required property \"{}\"", path, key));
+                    }
+                }
+            }
+            let properties = schema.get("properties").and_then(|p| p.as_object());
+            for (key, child) in object {
+                let child_path = format!("{}.{}", path, key);
+                match properties.and_then(|p| p.get(key)) {
+                    Some(child_schema) => validate_at(child, child_schema, &child_path, errors),
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => errors.push(format!("{}: unexpected property", child_path)),
+                        Some(extra @ Value::Object(_)) => validate_at(child, extra, &child_path, errors),
+                        _ => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(min) = schema.get("minItems").and_then(|m| m.as_u64()) {
+                if (items.len() as u64) < min {
+                    errors.push(format!("{}: needs at least {} items", path, min));
+                }
+            }
+            if let Some(max) = schema.get("maxItems").and_then(|m| m.as_u64()) {
+                if (items.len() as u64) > max {
+                    errors.push(format!("{}: allows at most {} items", path, max));
+                }
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    validate_at(item, item_schema, &format!("{}[{}]", path, i), errors);
+                }
+            }
+        }
+        Value::String(text) => {
+            let length = text.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(|m| m.as_u64()) {
+                if length < min {
+                    errors.push(format!("{}: must be at least {} characters", path, min));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(|m| m.as_u64()) {
+                if length > max {
+                    errors.push(format!("{}: must be at most {} characters", path, max));
+                }
+            }
+        }
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or_default();
+            if let Some(min) = schema.get("minimum").and_then(|m| m.as_f64()) {
+                if number < min {
+                    errors.push(format!("{}: must be >= {}", path, min));
+                }
+            }
+            if let Some(max) = schema.get("maximum").and_then(|m| m.as_f64()) {
+                if number > max {
+                    errors.push(format!("{}: must be <= {}", path, max));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64() || value.as_f64().map(|n| n.fract() == 0.0).unwrap_or(false),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn person_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string", "minLength": 1},
+                "age": {"type": "integer", "minimum": 0},
+                "role": {"enum": ["admin", "user"]},
+                "tags": {"type": "array", "items": {"type": "string"}, "maxItems": 2}
+            },
+            "required": ["name", "age"],
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn test_validate() {
+        let schema = person_schema();
+        assert!(validate(&json!({"name": "Ada", "age": 36, "role": "admin", "tags": ["math"]}), &schema).is_empty());
+
+        let errors = validate(&json!({"name": "", "age": -1.5, "role": "root", "tags": [1, "a", "b"], "extra": true}), &schema);
+        assert!(errors.iter().any(|e| e.starts_with("$.name: must be at least")));
+        assert!(errors.iter().any(|e| e.starts_with("$.age: expected integer")));
+        assert!(errors.iter().any(|e| e.starts_with("$.role: must be one of")));
+        assert!(errors.iter().any(|e| e.starts_with("$.tags: allows at most 2")));
+        assert!(errors.iter().any(|e| e.starts_with("$.tags[0]: expected string")));
+        assert!(errors.iter().any(|e| e == "$.extra: unexpected property"));
+
+        let errors = validate(&json!({"name": "Ada"}), &schema);
+        assert_eq!(errors, vec!["$: missing required property \"age\"".to_string()]);
+    }
+
+    #[test]
+    fn test_extract_json() {
+        assert_eq!(extract_json(r#"{"a": 1}"#), Some(json!({"a": 1})));
+        assert_eq!(extract_json("```json\n{\"a\": 1}\n```"), Some(json!({"a": 1})));
+        assert_eq!(extract_json("Here you go: [1, 2] done"), Some(json!([1, 2])));
+        assert_eq!(extract_json("no json here"), None);
+        assert!(check_schema(&json!({})).is_err());
+        assert!(check_schema(&person_schema()).is_ok());
+    }
+}
@@ -8,6 +8,9 @@
 //! - Execution plan generation
 //! - Progress tracking
 //! - Subtask reordering based on dependencies
+//! - Dependency blocking: tasks wait as `blocked` until their dependencies complete
+//! - Recurring task definitions (daily / weekly) that generate one open instance at a time
+//! - Schedule view: due dates placed into free calendar time
 
 #![allow(dead_code)]  // Phase 5: Task planning (Stage 4)
 
@@ -15,9 +18,29 @@ use crate::database::Database;
 use crate::services::ollama;
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use chrono::{Datelike, Local, NaiveDate, NaiveTime, TimeZone};
+use rusqlite::{Connection, OptionalExtension};
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
+/// Columns read by `row_to_task`, in order
+const TASK_COLUMNS: &str = "id, parent_id, title, description, status, priority,
+     dependencies, estimated_duration_minutes, actual_duration_minutes,
+     progress_percentage, created_at, started_at, completed_at, tags, due_at, recurrence_id";
+
+/// Recurring instances are created this long before they are due
+const RECURRENCE_LEAD_SECS: i64 = 24 * 3600;
+
+/// Working hours used to find free time in the schedule view (local time)
+const WORKDAY_START_HOUR: u32 = 9;
+const WORKDAY_END_HOUR: u32 = 18;
+
+/// Planned block length for tasks without an estimate
+const DEFAULT_BLOCK_MINUTES: i64 = 30;
+
+/// Free slots shorter than this are not offered
+const MIN_FREE_SLOT_MINUTES: i64 = 15;
+
 /// Task status
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -67,6 +90,19 @@ pub struct Task {
     pub started_at: Option<i64>,
     pub completed_at: Option<i64>,
     pub tags: Vec<String>,
+    /// Unix seconds
+    #[serde(default)]
+    pub due_at: Option<i64>,
+    /// Recurring definition this task was generated from
+    #[serde(default)]
+    pub recurrence_id: Option<String>,
+}
+
+impl Task {
+    /// Pending, in progress or blocked
+    pub fn is_open(&self) -> bool {
+        matches!(self.status, TaskStatus::Pending | TaskStatus::InProgress | TaskStatus::Blocked)
+    }
 }
 
 /// Execution plan
@@ -86,6 +122,173 @@ pub struct TaskBreakdown {
     pub dependencies: HashMap<String, Vec<String>>, // task_id -> [dependency_ids]
 }
 
+/// How often a recurring task repeats
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RecurrenceFrequency {
+    Daily,
+    Weekly,
+}
+
+/// When a recurring task is due
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecurrenceRule {
+    pub frequency: RecurrenceFrequency,
+    /// Every N days / weeks
+    #[serde(default = "default_interval")]
+    pub interval: u32,
+    /// Weekly only: 0 = Monday … 6 = Sunday. Empty repeats on the weekday it was created.
+    #[serde(default)]
+    pub weekdays: Vec<u32>,
+    /// Local due time, minutes after midnight
+    #[serde(default = "default_due_minute")]
+    pub due_minute_of_day: u32,
+}
+
+fn default_interval() -> u32 {
+    1
+}
+
+fn default_due_minute() -> u32 {
+    WORKDAY_END_HOUR * 60
+}
+
+impl RecurrenceRule {
+    /// Due times are counted from the local date of `anchor` (the creation time)
+    fn matches(&self, date: NaiveDate, anchor: NaiveDate) -> bool {
+        let interval = self.interval.max(1) as i64;
+        match self.frequency {
+            RecurrenceFrequency::Daily => (date - anchor).num_days() % interval == 0,
+            RecurrenceFrequency::Weekly => {
+                let weekday = date.weekday().num_days_from_monday();
+                let on_weekday = if self.weekdays.is_empty() {
+                    weekday == anchor.weekday().num_days_from_monday()
+                } else {
+                    self.weekdays.contains(&weekday)
+                };
+                let week_start = |d: NaiveDate| d - chrono::Duration::days(d.weekday().num_days_from_monday() as i64);
+                let weeks = (week_start(date) - week_start(anchor)).num_days() / 7;
+                on_weekday && weeks % interval == 0
+            }
+        }
+    }
+
+    /// First due time strictly after `after` (Unix seconds)
+    pub fn next_after(&self, after: i64, anchor: i64) -> Option<i64> {
+        let anchor = local_date(anchor)?;
+        let due_time = NaiveTime::from_hms_opt(self.due_minute_of_day.min(24 * 60 - 1) / 60, self.due_minute_of_day % 60, 0)?;
+        let mut date = local_date(after)?.max(anchor);
+        // Long enough for any weekly interval the UI offers
+        for _ in 0..(7 * 53) {
+            if self.matches(date, anchor) {
+                if let Some(due) = Local.from_local_datetime(&date.and_time(due_time)).earliest() {
+                    if due.timestamp() > after {
+                        return Some(due.timestamp());
+                    }
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+}
+
+/// Template for generated task instances
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurringTask {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub priority: TaskPriority,
+    pub rule: RecurrenceRule,
+    pub estimated_duration_minutes: Option<i32>,
+    pub tags: Vec<String>,
+    /// Paused definitions generate nothing
+    pub active: bool,
+    /// Due time of the next instance to generate
+    pub next_due_at: Option<i64>,
+    pub created_at: i64,
+}
+
+/// New recurring task from the frontend
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewRecurringTask {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub priority: TaskPriority,
+    pub rule: RecurrenceRule,
+    #[serde(default)]
+    pub estimated_duration_minutes: Option<i32>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Task dependency graph for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyGraph {
+    pub nodes: Vec<DependencyNode>,
+    /// (dependency id, dependent id)
+    pub edges: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyNode {
+    pub id: String,
+    pub title: String,
+    pub status: TaskStatus,
+    /// Incomplete dependencies (missing ones included)
+    pub waiting_on: Vec<String>,
+}
+
+/// Calendar event as the schedule sees it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleEvent {
+    pub title: String,
+    pub start: i64,
+    pub end: i64,
+    /// All-day events are listed but don't take up working hours
+    pub all_day: bool,
+}
+
+/// Free time within working hours
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FreeSlot {
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Suggested time to work on a task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedBlock {
+    pub task_id: String,
+    pub title: String,
+    pub start: i64,
+    pub end: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleDay {
+    /// Local date, YYYY-MM-DD
+    pub date: String,
+    pub tasks_due: Vec<Task>,
+    pub events: Vec<ScheduleEvent>,
+    /// Free time left after the planned blocks
+    pub free_slots: Vec<FreeSlot>,
+    pub planned: Vec<PlannedBlock>,
+}
+
+/// Due dates combined with calendar availability
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSchedule {
+    pub days: Vec<ScheduleDay>,
+    /// Open tasks whose due time has passed
+    pub overdue: Vec<Task>,
+    /// Due tasks that didn't fit into free time before their due date
+    pub unplanned: Vec<String>,
+    pub calendar_connected: bool,
+}
+
 /// Task Planner Service
 pub struct TaskPlannerService {
     db: Arc<Mutex<Database>>,
@@ -135,6 +338,31 @@ impl TaskPlannerService {
             [],
         )?;
 
+        // Columns added after the first release; errors mean they already exist
+        let _ = conn.execute("ALTER TABLE tasks ADD COLUMN due_at INTEGER", []);
+        let _ = conn.execute("ALTER TABLE tasks ADD COLUMN recurrence_id TEXT", []);
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_tasks_due ON tasks(due_at)",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS task_recurrences (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                description TEXT NOT NULL,
+                priority TEXT NOT NULL,
+                rule TEXT NOT NULL, -- JSON RecurrenceRule
+                estimated_duration_minutes INTEGER,
+                tags TEXT, -- JSON array
+                active INTEGER NOT NULL DEFAULT 1,
+                next_due_at INTEGER,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
         log::info!("✓ Task Planner database initialized");
         Ok(())
     }
@@ -262,6 +490,8 @@ Rules:
                 started_at: None,
                 completed_at: None,
                 tags,
+                due_at: None,
+                recurrence_id: None,
             };
 
             subtasks.push(task);
@@ -288,36 +518,19 @@ Rules:
         })
     }
 
-    /// Create a new task
-    pub fn create_task(&self, task: Task) -> Result<String> {
+    /// Create a new task. A pending task with incomplete dependencies starts out blocked.
+    pub fn create_task(&self, mut task: Task) -> Result<String> {
+        if task.dependencies.contains(&task.id) {
+            return Err(anyhow!("Task cannot depend on itself"));
+        }
+
         let db = self.db.lock().unwrap();
         let conn = db.conn();
 
-        let dependencies_json = serde_json::to_string(&task.dependencies)?;
-        let tags_json = serde_json::to_string(&task.tags)?;
-
-        conn.execute(
-            "INSERT INTO tasks (id, parent_id, title, description, status, priority,
-             dependencies, estimated_duration_minutes, actual_duration_minutes,
-             progress_percentage, created_at, started_at, completed_at, tags)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
-            rusqlite::params![
-                task.id,
-                task.parent_id,
-                task.title,
-                task.description,
-                serde_json::to_string(&task.status)?.trim_matches('"'),
-                serde_json::to_string(&task.priority)?.trim_matches('"'),
-                dependencies_json,
-                task.estimated_duration_minutes,
-                task.actual_duration_minutes,
-                task.progress_percentage,
-                task.created_at,
-                task.started_at,
-                task.completed_at,
-                tags_json,
-            ],
-        )?;
+        if task.status == TaskStatus::Pending && !waiting_on(conn, &task.dependencies)?.is_empty() {
+            task.status = TaskStatus::Blocked;
+        }
+        insert_task(conn, &task)?;
 
         log::info!("✓ Task created: {}", task.title);
         Ok(task.id)
@@ -329,53 +542,21 @@ Rules:
         let conn = db.conn();
 
         let task = conn.query_row(
-            "SELECT id, parent_id, title, description, status, priority,
-             dependencies, estimated_duration_minutes, actual_duration_minutes,
-             progress_percentage, created_at, started_at, completed_at, tags
-             FROM tasks WHERE id = ?1",
+            &format!("SELECT {} FROM tasks WHERE id = ?1", TASK_COLUMNS),
             [task_id],
-            |row| {
-                let dependencies_json: String = row.get(6)?;
-                let dependencies: Vec<String> = serde_json::from_str(&dependencies_json).unwrap_or_default();
-
-                let tags_json: String = row.get(13)?;
-                let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
-
-                let status_str: String = row.get(4)?;
-                let status = serde_json::from_str(&format!("\"{}\"", status_str)).unwrap_or(TaskStatus::Pending);
-
-                let priority_str: String = row.get(5)?;
-                let priority = serde_json::from_str(&format!("\"{}\"", priority_str)).unwrap_or(TaskPriority::Medium);
-
-                Ok(Task {
-                    id: row.get(0)?,
-                    parent_id: row.get(1)?,
-                    title: row.get(2)?,
-                    description: row.get(3)?,
-                    status,
-                    priority,
-                    dependencies,
-                    estimated_duration_minutes: row.get(7)?,
-                    actual_duration_minutes: row.get(8)?,
-                    progress_percentage: row.get(9)?,
-                    created_at: row.get(10)?,
-                    started_at: row.get(11)?,
-                    completed_at: row.get(12)?,
-                    tags,
-                })
-            },
+            row_to_task,
         )?;
 
         Ok(task)
     }
 
-    /// Update task status
+    /// Update task status. Dependents are unblocked (or blocked again) to match.
     pub fn update_task_status(&self, task_id: &str, status: TaskStatus) -> Result<()> {
         let db = self.db.lock().unwrap();
         let conn = db.conn();
 
         let now = chrono::Utc::now().timestamp();
-        let status_str = serde_json::to_string(&status)?.trim_matches('"').to_string();
+        let status_str = status_to_str(&status)?;
 
         match status {
             TaskStatus::InProgress => {
@@ -398,6 +579,8 @@ Rules:
             }
         }
 
+        refresh_dependents(conn, task_id)?;
+
         log::info!("✓ Task {} status updated to {:?}", task_id, status);
         Ok(())
     }
@@ -421,11 +604,106 @@ Rules:
                 "UPDATE tasks SET status = 'completed', completed_at = ?1 WHERE id = ?2",
                 rusqlite::params![now, task_id],
             )?;
+            refresh_dependents(conn, task_id)?;
         }
 
         Ok(())
     }
 
+    /// Make `task_id` wait for `depends_on`. Rejects links that would create a cycle.
+    pub fn add_dependency(&self, task_id: &str, depends_on: &str) -> Result<Task> {
+        if task_id == depends_on {
+            return Err(anyhow!("Task cannot depend on itself"));
+        }
+
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
+
+        let mut task = conn
+            .query_row(&format!("SELECT {} FROM tasks WHERE id = ?1", TASK_COLUMNS), [task_id], row_to_task)
+            .optional()?
+            .ok_or_else(|| anyhow!("Task not found: {}", task_id))?;
+        let exists: bool = conn
+            .query_row("SELECT 1 FROM tasks WHERE id = ?1", [depends_on], |_| Ok(true))
+            .optional()?
+            .unwrap_or(false);
+        if !exists {
+            return Err(anyhow!("Task not found: {}", depends_on));
+        }
+        if task.dependencies.iter().any(|id| id == depends_on) {
+            return Ok(task);
+        }
+
+        let all_tasks = load_tasks(conn, "1 = 1", [])?;
+        let graph: HashMap<&str, &[String]> = all_tasks
+            .iter()
+            .map(|t| (t.id.as_str(), t.dependencies.as_slice()))
+            .collect();
+        if depends_transitively(&graph, depends_on, task_id) {
+            return Err(anyhow!("Dependency would create a cycle"));
+        }
+
+        task.dependencies.push(depends_on.to_string());
+        if task.status == TaskStatus::Pending && !waiting_on(conn, &task.dependencies)?.is_empty() {
+            task.status = TaskStatus::Blocked;
+        }
+        conn.execute(
+            "UPDATE tasks SET dependencies = ?1, status = ?2 WHERE id = ?3",
+            rusqlite::params![serde_json::to_string(&task.dependencies)?, status_to_str(&task.status)?, task_id],
+        )?;
+
+        Ok(task)
+    }
+
+    /// Drop a dependency; the task is unblocked when nothing else holds it back
+    pub fn remove_dependency(&self, task_id: &str, depends_on: &str) -> Result<Task> {
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
+
+        let mut task = conn
+            .query_row(&format!("SELECT {} FROM tasks WHERE id = ?1", TASK_COLUMNS), [task_id], row_to_task)
+            .optional()?
+            .ok_or_else(|| anyhow!("Task not found: {}", task_id))?;
+
+        task.dependencies.retain(|id| id != depends_on);
+        if task.status == TaskStatus::Blocked && waiting_on(conn, &task.dependencies)?.is_empty() {
+            task.status = TaskStatus::Pending;
+        }
+        conn.execute(
+            "UPDATE tasks SET dependencies = ?1, status = ?2 WHERE id = ?3",
+            rusqlite::params![serde_json::to_string(&task.dependencies)?, status_to_str(&task.status)?, task_id],
+        )?;
+
+        Ok(task)
+    }
+
+    /// Dependency graph of a task's subtasks, or of every task
+    pub fn get_dependency_graph(&self, root_task_id: Option<&str>) -> Result<DependencyGraph> {
+        let tasks = match root_task_id {
+            Some(root) => self.get_subtasks(root)?,
+            None => self.get_all_tasks(None)?,
+        };
+
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
+
+        let mut nodes = Vec::with_capacity(tasks.len());
+        let mut edges = Vec::new();
+        for task in &tasks {
+            for dep_id in &task.dependencies {
+                edges.push((dep_id.clone(), task.id.clone()));
+            }
+            nodes.push(DependencyNode {
+                id: task.id.clone(),
+                title: task.title.clone(),
+                status: task.status.clone(),
+                waiting_on: waiting_on(conn, &task.dependencies)?,
+            });
+        }
+
+        Ok(DependencyGraph { nodes, edges })
+    }
+
     /// Generate execution plan with dependency ordering
     pub fn generate_execution_plan(&self, root_task_id: &str) -> Result<ExecutionPlan> {
         let _root_task = self.get_task(root_task_id)?;
@@ -495,48 +773,7 @@ Rules:
     /// Get all subtasks for a parent task
     pub fn get_subtasks(&self, parent_id: &str) -> Result<Vec<Task>> {
         let db = self.db.lock().unwrap();
-        let conn = db.conn();
-
-        let mut stmt = conn.prepare(
-            "SELECT id, parent_id, title, description, status, priority,
-             dependencies, estimated_duration_minutes, actual_duration_minutes,
-             progress_percentage, created_at, started_at, completed_at, tags
-             FROM tasks WHERE parent_id = ?1 ORDER BY created_at ASC"
-        )?;
-
-        let tasks = stmt.query_map([parent_id], |row| {
-            let dependencies_json: String = row.get(6)?;
-            let dependencies: Vec<String> = serde_json::from_str(&dependencies_json).unwrap_or_default();
-
-            let tags_json: String = row.get(13)?;
-            let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
-
-            let status_str: String = row.get(4)?;
-            let status = serde_json::from_str(&format!("\"{}\"", status_str)).unwrap_or(TaskStatus::Pending);
-
-            let priority_str: String = row.get(5)?;
-            let priority = serde_json::from_str(&format!("\"{}\"", priority_str)).unwrap_or(TaskPriority::Medium);
-
-            Ok(Task {
-                id: row.get(0)?,
-                parent_id: row.get(1)?,
-                title: row.get(2)?,
-                description: row.get(3)?,
-                status,
-                priority,
-                dependencies,
-                estimated_duration_minutes: row.get(7)?,
-                actual_duration_minutes: row.get(8)?,
-                progress_percentage: row.get(9)?,
-                created_at: row.get(10)?,
-                started_at: row.get(11)?,
-                completed_at: row.get(12)?,
-                tags,
-            })
-        })?
-        .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(tasks)
+        load_tasks(db.conn(), "parent_id = ?1 ORDER BY created_at ASC", [parent_id])
     }
 
     /// Find critical path (longest path in dependency graph)
@@ -594,66 +831,642 @@ Rules:
         let db = self.db.lock().unwrap();
         let conn = db.conn();
 
-        let query = if let Some(status) = status_filter {
-            let status_str = serde_json::to_string(&status)?.trim_matches('"').to_string();
-            format!(
-                "SELECT id, parent_id, title, description, status, priority,
-                 dependencies, estimated_duration_minutes, actual_duration_minutes,
-                 progress_percentage, created_at, started_at, completed_at, tags
-                 FROM tasks WHERE status = '{}' ORDER BY priority DESC, created_at ASC",
-                status_str
-            )
-        } else {
-            "SELECT id, parent_id, title, description, status, priority,
-             dependencies, estimated_duration_minutes, actual_duration_minutes,
-             progress_percentage, created_at, started_at, completed_at, tags
-             FROM tasks ORDER BY priority DESC, created_at ASC".to_string()
-        };
+        match status_filter {
+            Some(status) => load_tasks(
+                conn,
+                "status = ?1 ORDER BY priority DESC, created_at ASC",
+                [status_to_str(&status)?],
+            ),
+            None => load_tasks(conn, "1 = 1 ORDER BY priority DESC, created_at ASC", []),
+        }
+    }
 
-        let mut stmt = conn.prepare(&query)?;
+    /// Delete a task. Tasks that depended on it no longer wait for it.
+    pub fn delete_task(&self, task_id: &str) -> Result<()> {
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
 
-        let tasks = stmt.query_map([], |row| {
-            let dependencies_json: String = row.get(6)?;
-            let dependencies: Vec<String> = serde_json::from_str(&dependencies_json).unwrap_or_default();
+        conn.execute("DELETE FROM tasks WHERE id = ?1", [task_id])?;
+        for mut dependent in dependents_of(conn, task_id)? {
+            dependent.dependencies.retain(|id| id != task_id);
+            if dependent.status == TaskStatus::Blocked && waiting_on(conn, &dependent.dependencies)?.is_empty() {
+                dependent.status = TaskStatus::Pending;
+            }
+            conn.execute(
+                "UPDATE tasks SET dependencies = ?1, status = ?2 WHERE id = ?3",
+                rusqlite::params![
+                    serde_json::to_string(&dependent.dependencies)?,
+                    status_to_str(&dependent.status)?,
+                    dependent.id
+                ],
+            )?;
+        }
 
-            let tags_json: String = row.get(13)?;
-            let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
+        log::info!("✓ Task deleted: {}", task_id);
+        Ok(())
+    }
 
-            let status_str: String = row.get(4)?;
-            let status = serde_json::from_str(&format!("\"{}\"", status_str)).unwrap_or(TaskStatus::Pending);
+    /// Add a recurring task; its first instance is generated by `generate_recurring_tasks`
+    pub fn create_recurring_task(&self, new: NewRecurringTask) -> Result<RecurringTask> {
+        if new.title.trim().is_empty() {
+            return Err(anyhow!("Recurring task needs a title"));
+        }
+        if new.rule.weekdays.iter().any(|day| *day > 6) {
+            return Err(anyhow!("Weekdays run from 0 (Monday) to 6 (Sunday)"));
+        }
+        if new.rule.due_minute_of_day >= 24 * 60 {
+            return Err(anyhow!("Due time must be before midnight"));
+        }
 
-            let priority_str: String = row.get(5)?;
-            let priority = serde_json::from_str(&format!("\"{}\"", priority_str)).unwrap_or(TaskPriority::Medium);
+        let now = chrono::Utc::now().timestamp();
+        let recurring = RecurringTask {
+            id: uuid::Uuid::new_v4().to_string(),
+            title: new.title.trim().to_string(),
+            description: new.description,
+            priority: new.priority,
+            next_due_at: new.rule.next_after(now, now),
+            rule: new.rule,
+            estimated_duration_minutes: new.estimated_duration_minutes,
+            tags: new.tags,
+            active: true,
+            created_at: now,
+        };
 
-            Ok(Task {
-                id: row.get(0)?,
-                parent_id: row.get(1)?,
-                title: row.get(2)?,
-                description: row.get(3)?,
-                status,
-                priority,
-                dependencies,
-                estimated_duration_minutes: row.get(7)?,
-                actual_duration_minutes: row.get(8)?,
-                progress_percentage: row.get(9)?,
-                created_at: row.get(10)?,
-                started_at: row.get(11)?,
-                completed_at: row.get(12)?,
-                tags,
-            })
-        })?
-        .collect::<Result<Vec<_>, _>>()?;
+        let db = self.db.lock().unwrap();
+        db.conn().execute(
+            "INSERT INTO task_recurrences (id, title, description, priority, rule,
+             estimated_duration_minutes, tags, active, next_due_at, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            rusqlite::params![
+                recurring.id,
+                recurring.title,
+                recurring.description,
+                serde_json::to_string(&recurring.priority)?.trim_matches('"'),
+                serde_json::to_string(&recurring.rule)?,
+                recurring.estimated_duration_minutes,
+                serde_json::to_string(&recurring.tags)?,
+                recurring.active,
+                recurring.next_due_at,
+                recurring.created_at,
+            ],
+        )?;
 
-        Ok(tasks)
+        log::info!("✓ Recurring task created: {}", recurring.title);
+        Ok(recurring)
     }
 
-    /// Delete a task
-    pub fn delete_task(&self, task_id: &str) -> Result<()> {
+    pub fn list_recurring_tasks(&self) -> Result<Vec<RecurringTask>> {
+        let db = self.db.lock().unwrap();
+        load_recurrences(db.conn())
+    }
+
+    /// Pause or resume. Resuming schedules from now; missed occurrences are skipped.
+    pub fn set_recurring_task_active(&self, recurrence_id: &str, active: bool) -> Result<bool> {
         let db = self.db.lock().unwrap();
         let conn = db.conn();
 
-        conn.execute("DELETE FROM tasks WHERE id = ?1", [task_id])?;
-        log::info!("✓ Task deleted: {}", task_id);
-        Ok(())
+        let Some(recurring) = load_recurrences(conn)?.into_iter().find(|r| r.id == recurrence_id) else {
+            return Ok(false);
+        };
+        let next_due_at = if active {
+            recurring.rule.next_after(chrono::Utc::now().timestamp(), recurring.created_at)
+        } else {
+            recurring.next_due_at
+        };
+        conn.execute(
+            "UPDATE task_recurrences SET active = ?1, next_due_at = ?2 WHERE id = ?3",
+            rusqlite::params![active, next_due_at, recurrence_id],
+        )?;
+        Ok(true)
+    }
+
+    /// Delete a recurring definition. Instances already generated are kept.
+    pub fn delete_recurring_task(&self, recurrence_id: &str) -> Result<bool> {
+        let db = self.db.lock().unwrap();
+        let removed = db.conn().execute("DELETE FROM task_recurrences WHERE id = ?1", [recurrence_id])?;
+        Ok(removed > 0)
+    }
+
+    /// Create instances that are due within a day. Each definition has at most one open
+    /// instance; occurrences missed while one was open are skipped rather than backfilled.
+    pub fn generate_recurring_tasks(&self, now: i64) -> Result<Vec<Task>> {
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
+
+        let mut created = Vec::new();
+        for recurring in load_recurrences(conn)?.into_iter().filter(|r| r.active) {
+            let has_open_instance: bool = conn
+                .query_row(
+                    "SELECT 1 FROM tasks WHERE recurrence_id = ?1
+                     AND status IN ('pending', 'inprogress', 'blocked') LIMIT 1",
+                    [&recurring.id],
+                    |_| Ok(true),
+                )
+                .optional()?
+                .unwrap_or(false);
+            if has_open_instance {
+                continue;
+            }
+
+            let due = match recurring.next_due_at.filter(|due| *due > now) {
+                Some(due) => Some(due),
+                None => recurring.rule.next_after(now, recurring.created_at),
+            };
+            let Some(due) = due else { continue };
+            if due - RECURRENCE_LEAD_SECS > now {
+                if recurring.next_due_at != Some(due) {
+                    conn.execute(
+                        "UPDATE task_recurrences SET next_due_at = ?1 WHERE id = ?2",
+                        rusqlite::params![due, recurring.id],
+                    )?;
+                }
+                continue;
+            }
+
+            let task = Task {
+                id: uuid::Uuid::new_v4().to_string(),
+                parent_id: None,
+                title: recurring.title.clone(),
+                description: recurring.description.clone(),
+                status: TaskStatus::Pending,
+                priority: recurring.priority.clone(),
+                dependencies: Vec::new(),
+                estimated_duration_minutes: recurring.estimated_duration_minutes,
+                actual_duration_minutes: None,
+                progress_percentage: 0.0,
+                created_at: now,
+                started_at: None,
+                completed_at: None,
+                tags: recurring.tags.clone(),
+                due_at: Some(due),
+                recurrence_id: Some(recurring.id.clone()),
+            };
+            insert_task(conn, &task)?;
+            conn.execute(
+                "UPDATE task_recurrences SET next_due_at = ?1 WHERE id = ?2",
+                rusqlite::params![recurring.rule.next_after(due, recurring.created_at), recurring.id],
+            )?;
+            created.push(task);
+        }
+
+        if !created.is_empty() {
+            log::info!("✓ Generated {} recurring task instances", created.len());
+        }
+        Ok(created)
+    }
+
+    /// Open tasks due in the next `days` days (and overdue ones) placed into free calendar time
+    pub fn build_schedule(
+        &self,
+        events: Vec<ScheduleEvent>,
+        calendar_connected: bool,
+        now: i64,
+        days: u32,
+    ) -> Result<TaskSchedule> {
+        let tasks = {
+            let db = self.db.lock().unwrap();
+            load_tasks(
+                db.conn(),
+                "due_at IS NOT NULL AND status IN ('pending', 'inprogress', 'blocked') ORDER BY due_at ASC",
+                [],
+            )?
+        };
+        plan_schedule(tasks, events, calendar_connected, now, days)
+    }
+}
+
+fn status_to_str(status: &TaskStatus) -> Result<String> {
+    Ok(serde_json::to_string(status)?.trim_matches('"').to_string())
+}
+
+fn row_to_task(row: &rusqlite::Row) -> rusqlite::Result<Task> {
+    let dependencies_json: String = row.get(6)?;
+    let dependencies: Vec<String> = serde_json::from_str(&dependencies_json).unwrap_or_default();
+
+    let tags_json: String = row.get(13)?;
+    let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
+
+    let status_str: String = row.get(4)?;
+    let status = serde_json::from_str(&format!("\"{}\"", status_str)).unwrap_or(TaskStatus::Pending);
+
+    let priority_str: String = row.get(5)?;
+    let priority = serde_json::from_str(&format!("\"{}\"", priority_str)).unwrap_or(TaskPriority::Medium);
+
+    Ok(Task {
+        id: row.get(0)?,
+        parent_id: row.get(1)?,
+        title: row.get(2)?,
+        description: row.get(3)?,
+        status,
+        priority,
+        dependencies,
+        estimated_duration_minutes: row.get(7)?,
+        actual_duration_minutes: row.get(8)?,
+        progress_percentage: row.get(9)?,
+        created_at: row.get(10)?,
+        started_at: row.get(11)?,
+        completed_at: row.get(12)?,
+        tags,
+        due_at: row.get(14)?,
+        recurrence_id: row.get(15)?,
+    })
+}
+
+fn load_tasks<P: rusqlite::Params>(conn: &Connection, filter: &str, params: P) -> Result<Vec<Task>> {
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM tasks WHERE {}", TASK_COLUMNS, filter))?;
+    let tasks = stmt.query_map(params, row_to_task)?.collect::<Result<Vec<_>, _>>()?;
+    Ok(tasks)
+}
+
+fn insert_task(conn: &Connection, task: &Task) -> Result<()> {
+    conn.execute(
+        "INSERT INTO tasks (id, parent_id, title, description, status, priority,
+         dependencies, estimated_duration_minutes, actual_duration_minutes,
+         progress_percentage, created_at, started_at, completed_at, tags, due_at, recurrence_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+        rusqlite::params![
+            task.id,
+            task.parent_id,
+            task.title,
+            task.description,
+            status_to_str(&task.status)?,
+            serde_json::to_string(&task.priority)?.trim_matches('"'),
+            serde_json::to_string(&task.dependencies)?,
+            task.estimated_duration_minutes,
+            task.actual_duration_minutes,
+            task.progress_percentage,
+            task.created_at,
+            task.started_at,
+            task.completed_at,
+            serde_json::to_string(&task.tags)?,
+            task.due_at,
+            task.recurrence_id,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Dependencies that aren't completed yet; ids of tasks that don't exist (yet) count too,
+/// since decomposed subtasks are created one by one
+fn waiting_on(conn: &Connection, dependencies: &[String]) -> Result<Vec<String>> {
+    let mut waiting = Vec::new();
+    for dep_id in dependencies {
+        let status: Option<String> = conn
+            .query_row("SELECT status FROM tasks WHERE id = ?1", [dep_id], |row| row.get(0))
+            .optional()?;
+        if status.as_deref() != Some("completed") {
+            waiting.push(dep_id.clone());
+        }
+    }
+    Ok(waiting)
+}
+
+fn dependents_of(conn: &Connection, task_id: &str) -> Result<Vec<Task>> {
+    // LIKE narrows the scan; the JSON array is checked exactly afterwards
+    let escaped = task_id.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    let pattern = format!("%\"{}\"%", escaped);
+    Ok(load_tasks(conn, "dependencies LIKE ?1 ESCAPE '\\'", [pattern])?
+        .into_iter()
+        .filter(|t| t.dependencies.iter().any(|id| id == task_id))
+        .collect())
+}
+
+/// Block or unblock the pending / blocked tasks that depend on `task_id`
+fn refresh_dependents(conn: &Connection, task_id: &str) -> Result<()> {
+    for dependent in dependents_of(conn, task_id)? {
+        let waiting = !waiting_on(conn, &dependent.dependencies)?.is_empty();
+        let status = match (&dependent.status, waiting) {
+            (TaskStatus::Pending, true) => TaskStatus::Blocked,
+            (TaskStatus::Blocked, false) => TaskStatus::Pending,
+            _ => continue,
+        };
+        conn.execute(
+            "UPDATE tasks SET status = ?1 WHERE id = ?2",
+            rusqlite::params![status_to_str(&status)?, dependent.id],
+        )?;
+        log::info!("Task {} is now {:?}", dependent.id, status);
+    }
+    Ok(())
+}
+
+/// Whether `from` (eventually) depends on `target`
+fn depends_transitively(graph: &HashMap<&str, &[String]>, from: &str, target: &str) -> bool {
+    let mut stack = vec![from];
+    let mut seen = HashSet::new();
+    while let Some(id) = stack.pop() {
+        if id == target {
+            return true;
+        }
+        if !seen.insert(id) {
+            continue;
+        }
+        if let Some(deps) = graph.get(id) {
+            stack.extend(deps.iter().map(String::as_str));
+        }
+    }
+    false
+}
+
+fn load_recurrences(conn: &Connection) -> Result<Vec<RecurringTask>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, title, description, priority, rule, estimated_duration_minutes,
+         tags, active, next_due_at, created_at
+         FROM task_recurrences ORDER BY created_at ASC",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?,
+            row.get::<_, Option<i32>>(5)?,
+            row.get::<_, Option<String>>(6)?,
+            row.get::<_, bool>(7)?,
+            row.get::<_, Option<i64>>(8)?,
+            row.get::<_, i64>(9)?,
+        ))
+    })?;
+
+    let mut recurrences = Vec::new();
+    for row in rows {
+        let (id, title, description, priority, rule, estimated, tags, active, next_due_at, created_at) = row?;
+        let Ok(rule) = serde_json::from_str(&rule) else {
+            log::warn!("Skipping recurring task {} with an unreadable rule", id);
+            continue;
+        };
+        recurrences.push(RecurringTask {
+            id,
+            title,
+            description,
+            priority: serde_json::from_str(&format!("\"{}\"", priority)).unwrap_or(TaskPriority::Medium),
+            rule,
+            estimated_duration_minutes: estimated,
+            tags: tags.and_then(|t| serde_json::from_str(&t).ok()).unwrap_or_default(),
+            active,
+            next_due_at,
+            created_at,
+        });
+    }
+    Ok(recurrences)
+}
+
+fn local_date(timestamp: i64) -> Option<NaiveDate> {
+    Local.timestamp_opt(timestamp, 0).earliest().map(|t| t.date_naive())
+}
+
+fn local_timestamp(date: NaiveDate, hour: u32) -> Option<i64> {
+    let time = NaiveTime::from_hms_opt(hour, 0, 0)?;
+    Local.from_local_datetime(&date.and_time(time)).earliest().map(|t| t.timestamp())
+}
+
+/// Lay out `days` days from `now`: events, free working time, and a block for every open
+/// task in the earliest free slot before its due date (overdue tasks go first)
+pub fn plan_schedule(
+    mut tasks: Vec<Task>,
+    events: Vec<ScheduleEvent>,
+    calendar_connected: bool,
+    now: i64,
+    days: u32,
+) -> Result<TaskSchedule> {
+    let today = local_date(now).ok_or_else(|| anyhow!("Invalid timestamp: {}", now))?;
+
+    let mut schedule_days = Vec::new();
+    // (day start, day end) for matching due dates to days
+    let mut bounds = Vec::new();
+    for offset in 0..days.max(1) {
+        let date = today + chrono::Duration::days(offset as i64);
+        let (Some(day_start), Some(day_end), Some(work_start), Some(work_end)) = (
+            local_timestamp(date, 0),
+            date.succ_opt().and_then(|next| local_timestamp(next, 0)),
+            local_timestamp(date, WORKDAY_START_HOUR),
+            local_timestamp(date, WORKDAY_END_HOUR),
+        ) else {
+            continue;
+        };
+
+        let mut day_events: Vec<ScheduleEvent> = events
+            .iter()
+            .filter(|e| e.start < day_end && e.end > day_start)
+            .cloned()
+            .collect();
+        day_events.sort_by_key(|e| e.start);
+
+        let mut free_slots = Vec::new();
+        let mut cursor = work_start.max(now);
+        for event in day_events.iter().filter(|e| !e.all_day) {
+            if event.start > cursor {
+                free_slots.push(FreeSlot { start: cursor, end: event.start.min(work_end) });
+            }
+            cursor = cursor.max(event.end);
+        }
+        if cursor < work_end {
+            free_slots.push(FreeSlot { start: cursor, end: work_end });
+        }
+        free_slots.retain(|slot| slot.end - slot.start >= MIN_FREE_SLOT_MINUTES * 60);
+
+        bounds.push((day_start, day_end));
+        schedule_days.push(ScheduleDay {
+            date: date.format("%Y-%m-%d").to_string(),
+            tasks_due: Vec::new(),
+            events: day_events,
+            free_slots,
+            planned: Vec::new(),
+        });
+    }
+
+    // Earliest due first, then the most important
+    tasks.sort_by(|a, b| {
+        a.due_at
+            .cmp(&b.due_at)
+            .then_with(|| b.priority.score().cmp(&a.priority.score()))
+    });
+
+    let mut overdue = Vec::new();
+    let mut unplanned = Vec::new();
+    for task in tasks {
+        let Some(due_at) = task.due_at else { continue };
+
+        // Blocked tasks can't be worked on yet, so they get no time
+        if task.status != TaskStatus::Blocked {
+            let minutes = task
+                .estimated_duration_minutes
+                .map(|m| m.max(1) as i64)
+                .unwrap_or(DEFAULT_BLOCK_MINUTES);
+            let deadline = if due_at < now { i64::MAX } else { due_at };
+            let slot = schedule_days.iter_mut().find_map(|day| {
+                let index = day
+                    .free_slots
+                    .iter()
+                    .position(|s| s.end - s.start >= minutes * 60 && s.start + minutes * 60 <= deadline)?;
+                Some((day, index))
+            });
+            match slot {
+                Some((day, index)) => {
+                    let start = day.free_slots[index].start;
+                    let end = start + minutes * 60;
+                    day.planned.push(PlannedBlock {
+                        task_id: task.id.clone(),
+                        title: task.title.clone(),
+                        start,
+                        end,
+                    });
+                    day.free_slots[index].start = end;
+                    day.free_slots.retain(|s| s.end - s.start >= MIN_FREE_SLOT_MINUTES * 60);
+                }
+                None => unplanned.push(task.id.clone()),
+            }
+        }
+
+        if due_at < now {
+            overdue.push(task);
+        } else if let Some(index) = bounds.iter().position(|(start, end)| due_at >= *start && due_at < *end) {
+            schedule_days[index].tasks_due.push(task);
+        }
+    }
+
+    Ok(TaskSchedule {
+        days: schedule_days,
+        overdue,
+        unplanned,
+        calendar_connected,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service() -> TaskPlannerService {
+        TaskPlannerService::new(Arc::new(Mutex::new(Database::new_test_db().unwrap()))).unwrap()
+    }
+
+    fn task(id: &str, dependencies: &[&str]) -> Task {
+        Task {
+            id: id.to_string(),
+            parent_id: None,
+            title: id.to_string(),
+            description: String::new(),
+            status: TaskStatus::Pending,
+            priority: TaskPriority::Medium,
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            estimated_duration_minutes: Some(60),
+            actual_duration_minutes: None,
+            progress_percentage: 0.0,
+            created_at: 0,
+            started_at: None,
+            completed_at: None,
+            tags: Vec::new(),
+            due_at: None,
+            recurrence_id: None,
+        }
+    }
+
+    #[test]
+    fn test_dependencies_block_and_unblock() {
+        let planner = service();
+        planner.create_task(task("design", &[])).unwrap();
+        planner.create_task(task("build", &["design"])).unwrap();
+        planner.create_task(task("ship", &["build"])).unwrap();
+        assert_eq!(planner.get_task("build").unwrap().status, TaskStatus::Blocked);
+
+        assert!(planner.add_dependency("design", "ship").is_err());
+
+        planner.update_task_status("design", TaskStatus::Completed).unwrap();
+        assert_eq!(planner.get_task("build").unwrap().status, TaskStatus::Pending);
+        assert_eq!(planner.get_task("ship").unwrap().status, TaskStatus::Blocked);
+
+        // Reopening blocks dependents again
+        planner.update_task_status("design", TaskStatus::InProgress).unwrap();
+        assert_eq!(planner.get_task("build").unwrap().status, TaskStatus::Blocked);
+
+        planner.delete_task("build").unwrap();
+        let ship = planner.get_task("ship").unwrap();
+        assert!(ship.dependencies.is_empty());
+        assert_eq!(ship.status, TaskStatus::Pending);
+    }
+
+    #[test]
+    fn test_recurring_instances() {
+        let planner = service();
+        let recurring = planner
+            .create_recurring_task(NewRecurringTask {
+                title: "Water plants".to_string(),
+                description: String::new(),
+                priority: TaskPriority::Low,
+                rule: RecurrenceRule {
+                    frequency: RecurrenceFrequency::Daily,
+                    interval: 1,
+                    weekdays: Vec::new(),
+                    due_minute_of_day: 9 * 60,
+                },
+                estimated_duration_minutes: Some(10),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        let first_due = recurring.next_due_at.unwrap();
+        let now = chrono::Utc::now().timestamp();
+        assert!(first_due > now && first_due <= now + 24 * 3600);
+
+        let created = planner.generate_recurring_tasks(now).unwrap();
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].due_at, Some(first_due));
+        // One open instance at a time
+        assert!(planner.generate_recurring_tasks(now).unwrap().is_empty());
+
+        planner.update_task_status(&created[0].id, TaskStatus::Completed).unwrap();
+        let next = planner.generate_recurring_tasks(first_due).unwrap();
+        assert_eq!(next.len(), 1);
+        assert_eq!(next[0].due_at, recurring.rule.next_after(first_due, recurring.created_at));
+
+        let weekly = RecurrenceRule {
+            frequency: RecurrenceFrequency::Weekly,
+            interval: 2,
+            weekdays: vec![0],
+            due_minute_of_day: 10 * 60,
+        };
+        let due = weekly.next_after(now, now).unwrap();
+        let due_date = local_date(due).unwrap();
+        assert_eq!(due_date.weekday(), chrono::Weekday::Mon);
+        let following = local_date(weekly.next_after(due, now).unwrap()).unwrap();
+        assert_eq!((following - due_date).num_days(), 14);
+    }
+
+    #[test]
+    fn test_plan_schedule_around_events() {
+        let day = local_date(chrono::Utc::now().timestamp()).unwrap() + chrono::Duration::days(1);
+        let at = |hour| local_timestamp(day, hour).unwrap();
+        let now = at(8);
+
+        let mut report = task("report", &[]);
+        report.due_at = Some(at(17));
+        report.estimated_duration_minutes = Some(90);
+        let mut too_big = task("too_big", &[]);
+        too_big.due_at = Some(at(12));
+        too_big.estimated_duration_minutes = Some(240);
+        let mut late = task("late", &[]);
+        late.due_at = Some(now - 3600);
+        late.estimated_duration_minutes = None;
+
+        let events = vec![ScheduleEvent {
+            title: "Standup".to_string(),
+            start: at(9),
+            end: at(10),
+            all_day: false,
+        }];
+        let schedule = plan_schedule(vec![report, too_big, late], events, true, now, 2).unwrap();
+
+        assert_eq!(schedule.days.len(), 2);
+        assert_eq!(schedule.overdue.len(), 1);
+        assert_eq!(schedule.unplanned, vec!["too_big"]);
+
+        let today = &schedule.days[0];
+        assert_eq!(today.tasks_due.len(), 2);
+        // Overdue first, right after the standup; the report follows
+        assert_eq!(today.planned[0].task_id, "late");
+        assert_eq!(today.planned[0].start, at(10));
+        assert_eq!(today.planned[1].task_id, "report");
+        assert_eq!(today.planned[1].start, at(10) + 30 * 60);
+        assert_eq!(today.free_slots, vec![FreeSlot { start: at(12), end: at(18) }]);
     }
 }
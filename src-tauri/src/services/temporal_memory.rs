@@ -12,6 +12,7 @@
 //! - Access-based retention boost
 //! - Automated decay updates every 24 hours
 //! - Configurable decay strength per memory
+//! - Review reinforcement (spaced repetition, v3.9.0)
 
 #![allow(dead_code)]  // Phase 18: Temporal memory (Phase 3)
 
@@ -21,6 +22,22 @@ use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Upper bound for decay strength after repeated reviews (~10 years)
+pub const MAX_DECAY_STRENGTH: f64 = 3650.0;
+
+/// Decay strength after a successful review (spaced repetition)
+///
+/// Stability grows by `ease` per review, and is raised at least far enough
+/// that a memory of this age is back at `target_retention`.
+pub fn reinforced_strength(current_strength: f64, age_days: f64, ease: f64, target_retention: f64) -> f64 {
+    let target = target_retention.clamp(0.01, 0.99);
+    let strength_for_target = -age_days.max(0.0) / target.ln();
+
+    (current_strength * ease.max(1.0))
+        .max(strength_for_target)
+        .min(MAX_DECAY_STRENGTH)
+}
+
 /// Memory type classification for adaptive decay
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -230,6 +247,12 @@ impl TemporalMemoryService {
              ON episodic_memory(retention_score)",
             [],
         );
+        // Temporal retrieval (retention score + importance); needs the column added above
+        let _ = conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_episodic_memory_retention
+             ON episodic_memory(retention_score DESC, importance DESC)",
+            [],
+        );
         let _ = conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_episodic_decay_update
              ON episodic_memory(last_decay_update)",
@@ -423,6 +446,54 @@ impl TemporalMemoryService {
         Ok(())
     }
 
+    /// Reinforce a memory the user just reviewed
+    ///
+    /// Counts as an access and raises its decay strength (see
+    /// `reinforced_strength`), so the boost survives later decay runs.
+    /// Returns the new decay strength.
+    pub fn reinforce_memory(&self, memory_id: &str, ease: f64, target_retention: f64) -> Result<f64> {
+        let db = self.db.lock().unwrap();
+        let conn = db.conn();
+
+        let (created_at, access_count, is_pinned, decay_strength): (i64, i32, bool, f64) = conn
+            .query_row(
+                "SELECT created_at,
+                        COALESCE(access_count, 0),
+                        COALESCE(is_pinned, 0),
+                        COALESCE(decay_strength, 20.0)
+                 FROM episodic_memory
+                 WHERE id = ?1",
+                rusqlite::params![memory_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .context("Memory not found")?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)?
+            .as_secs() as i64;
+
+        let days_elapsed = (now - created_at) as f64 / 86400.0;
+        let new_strength = reinforced_strength(decay_strength, days_elapsed, ease, target_retention);
+        let retention = self.calculate_retention(days_elapsed, new_strength, access_count + 1, is_pinned);
+
+        conn.execute(
+            "UPDATE episodic_memory
+             SET decay_strength = ?1, access_count = ?2, retention_score = ?3, last_decay_update = ?4
+             WHERE id = ?5",
+            rusqlite::params![new_strength, access_count + 1, retention, now, memory_id],
+        )?;
+
+        log::info!(
+            "Reinforced memory {} (S: {:.1} → {:.1}, retention {:.2})",
+            memory_id,
+            decay_strength,
+            new_strength,
+            retention
+        );
+
+        Ok(new_strength)
+    }
+
     /// Prune memories below retention threshold
     pub fn prune_low_retention_memories(&self, threshold: f64) -> Result<usize> {
         let db = self.db.lock().unwrap();
@@ -0,0 +1,212 @@
+//! Tool Result Cache (v3.9.0)
+//!
+//! Plans and ReAct loops often repeat the same web search or page fetch a few
+//! steps apart. Results of deterministic tools are reused until their TTL runs out.
+//!
+//! Features:
+//! - Keys built from the tool name and normalized arguments
+//! - Per-tool TTL declared by the executor (`ToolExecutor::cache_ttl`)
+//! - Hit metadata (`ToolCacheInfo`) attached to cached results
+//! - Hit/miss counters and `clear` for `tool_cache_clear`
+//!
+//! Only successful results are cached; failures always re-run.
+
+#![allow(dead_code)]  // Phase 5: Tool result caching
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Upper bound on cached results; the oldest entry is evicted first
+const MAX_ENTRIES: usize = 256;
+
+/// Cache metadata attached to a tool result served from the cache
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolCacheInfo {
+    pub hit: bool,
+    /// Seconds since the result was produced
+    pub age_secs: u64,
+    pub ttl_secs: u64,
+}
+
+/// Cache counters for the settings UI
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolCacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+struct Entry {
+    result: Value,
+    stored_at: Instant,
+    ttl: Duration,
+}
+
+impl Entry {
+    fn is_fresh(&self) -> bool {
+        self.stored_at.elapsed() < self.ttl
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: HashMap<String, Entry>,
+    hits: u64,
+    misses: u64,
+}
+
+/// In-memory TTL cache of tool results, shared by every `ToolService` caller
+#[derive(Default)]
+pub struct ToolCache {
+    inner: Mutex<Inner>,
+}
+
+impl ToolCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        // Entries are plain values, a panic mid-update cannot leave them inconsistent
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Fresh cached result for `key`, with hit metadata
+    pub fn get(&self, key: &str) -> Option<(Value, ToolCacheInfo)> {
+        let mut inner = self.lock();
+        let cached = match inner.entries.get(key) {
+            Some(entry) if entry.is_fresh() => Some((
+                entry.result.clone(),
+                ToolCacheInfo {
+                    hit: true,
+                    age_secs: entry.stored_at.elapsed().as_secs(),
+                    ttl_secs: entry.ttl.as_secs(),
+                },
+            )),
+            Some(_) => {
+                inner.entries.remove(key);
+                None
+            }
+            None => None,
+        };
+        if cached.is_some() {
+            inner.hits += 1;
+        } else {
+            inner.misses += 1;
+        }
+        cached
+    }
+
+    /// Store a successful result for `ttl`
+    pub fn insert(&self, key: String, result: Value, ttl: Duration) {
+        let mut inner = self.lock();
+        inner.entries.retain(|_, entry| entry.is_fresh());
+        if inner.entries.len() >= MAX_ENTRIES && !inner.entries.contains_key(&key) {
+            let oldest = inner
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.stored_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                inner.entries.remove(&oldest);
+            }
+        }
+        inner.entries.insert(
+            key,
+            Entry {
+                result,
+                stored_at: Instant::now(),
+                ttl,
+            },
+        );
+    }
+
+    /// Drop every cached result, returning how many were removed
+    pub fn clear(&self) -> usize {
+        let mut inner = self.lock();
+        let removed = inner.entries.len();
+        inner.entries.clear();
+        removed
+    }
+
+    pub fn stats(&self) -> ToolCacheStats {
+        let inner = self.lock();
+        ToolCacheStats {
+            entries: inner.entries.values().filter(|entry| entry.is_fresh()).count(),
+            hits: inner.hits,
+            misses: inner.misses,
+        }
+    }
+}
+
+/// Cache key for a call to `tool_name` with already-normalized arguments
+pub fn cache_key(tool_name: &str, normalized_arguments: &str) -> String {
+    format!("{}:{}", tool_name, normalized_arguments)
+}
+
+/// Canonical form of tool arguments
+///
+/// Object keys are sorted, `null` fields dropped and string whitespace
+/// trimmed/collapsed, so `{"query": " rust  async "}` and
+/// `{"query": "rust async", "limit": null}` share a key.
+pub fn normalize_arguments(arguments: &Value) -> String {
+    normalize_value(arguments).to_string()
+}
+
+fn normalize_value(value: &Value) -> Value {
+    match value {
+        Value::String(s) => Value::String(s.split_whitespace().collect::<Vec<_>>().join(" ")),
+        Value::Array(items) => Value::Array(items.iter().map(normalize_value).collect()),
+        Value::Object(map) => {
+            let sorted: BTreeMap<&String, Value> = map
+                .iter()
+                .filter(|(_, v)| !v.is_null())
+                .map(|(k, v)| (k, normalize_value(v)))
+                .collect();
+            Value::Object(sorted.into_iter().map(|(k, v)| (k.clone(), v)).collect())
+        }
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_normalized_arguments_share_a_key() {
+        let a = normalize_arguments(&json!({ "query": "  rust   async ", "limit": null }));
+        let b = normalize_arguments(&json!({ "query": "rust async" }));
+        assert_eq!(a, b);
+
+        let c = normalize_arguments(&json!({ "b": 1, "a": [" x "] }));
+        let d = normalize_arguments(&json!({ "a": ["x"], "b": 1 }));
+        assert_eq!(c, d);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_hits_expiry_and_clear() {
+        let cache = ToolCache::new();
+        let key = cache_key("web_search", &normalize_arguments(&json!({ "query": "rust" })));
+
+        assert!(cache.get(&key).is_none());
+        cache.insert(key.clone(), json!({ "count": 3 }), Duration::from_secs(60));
+        let (result, info) = cache.get(&key).unwrap();
+        assert_eq!(result["count"], 3);
+        assert!(info.hit);
+        assert_eq!(info.ttl_secs, 60);
+
+        cache.insert("expired".to_string(), json!(1), Duration::ZERO);
+        assert!(cache.get("expired").is_none());
+
+        let stats = cache.stats();
+        assert_eq!((stats.entries, stats.hits, stats.misses), (1, 1, 2));
+        assert_eq!(cache.clear(), 1);
+        assert!(cache.get(&key).is_none());
+    }
+}
@@ -7,13 +7,26 @@
  * - Tool execution with parameter validation
  * - Integration with Ollama/Qwen for function calling
  * - Support for plugins, web search, file ops, etc.
+ * - Result caching for deterministic tools (v3.9.0)
+ * - Per-tool timeouts, cancellation by call id and parallel execution of
+ *   independent calls (v3.9.0)
+ * - Tool sets limiting the tools a conversation or agent run sees (v3.9.0)
+ * - Tools added and removed at runtime, e.g. imported HTTP APIs (v3.9.0)
  */
 
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+use std::time::Duration;
 use tracing::{info, debug, instrument};
 
+use super::tool_sets;
+use super::tool_cache::{self, ToolCache, ToolCacheInfo, ToolCacheStats};
+use super::tool_runtime::{
+    self, RunningCalls, RunningToolCall, TimeoutOverrides, ToolInterruption, ToolTimeout, DEFAULT_TOOL_TIMEOUT,
+};
+
 /// Tool parameter definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolParameter {
@@ -58,6 +71,9 @@ pub enum ToolCategory {
     Calculation,  // Math and calculations
     Memory,       // RAG memory operations
     Git,          // Git operations
+    Language,     // Translation (v3.9.0)
+    Api,          // User-defined HTTP APIs (v3.9.0)
+    Database,     // Read-only SQL on user databases (v3.9.0)
 }
 
 /// Tool execution request
@@ -74,6 +90,41 @@ pub struct ToolResult {
     pub result: serde_json::Value,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Set when the result was served from the tool cache (v3.9.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<ToolCacheInfo>,
+    /// Set when the call timed out or was cancelled (v3.9.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interrupted: Option<ToolInterruption>,
+    /// Wall-clock execution time (v3.9.0)
+    #[serde(default)]
+    pub execution_ms: u64,
+}
+
+impl ToolResult {
+    /// Failed result with an error message
+    pub fn failure(error: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            result: serde_json::Value::Null,
+            error: Some(error.into()),
+            cache: None,
+            interrupted: None,
+            execution_ms: 0,
+        }
+    }
+
+    /// Result as shown to the model, noting when it came from the cache
+    pub fn observation(&self) -> String {
+        let content = self.result.to_string();
+        match &self.cache {
+            Some(cache) if cache.hit => format!(
+                "{}\n(cached result from {}s ago; identical call, not re-run)",
+                content, cache.age_secs
+            ),
+            _ => content,
+        }
+    }
 }
 
 /// Tool executor trait (async support for v3.5.1)
@@ -84,18 +135,52 @@ pub trait ToolExecutor: Send + Sync {
 
     /// Get tool definition
     fn definition(&self) -> ToolDefinition;
+
+    /// How long a successful result can be reused (v3.9.0)
+    ///
+    /// `None` (the default) never caches; only tools whose output depends
+    /// solely on their arguments should return a TTL.
+    fn cache_ttl(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Normalized arguments identifying equivalent calls (v3.9.0)
+    fn cache_key(&self, arguments: &serde_json::Value) -> String {
+        tool_cache::normalize_arguments(arguments)
+    }
+
+    /// How long a call may run before it is abandoned (v3.9.0)
+    ///
+    /// Users can override this per tool (`ToolService::set_timeout`).
+    fn timeout(&self) -> Duration {
+        DEFAULT_TOOL_TIMEOUT
+    }
+
+    /// Whether calls can run concurrently with other calls of the same turn (v3.9.0)
+    ///
+    /// `false` (the default) runs the call alone, in order; only tools that
+    /// don't change files, the screen or other state should return `true`.
+    fn parallel_safe(&self) -> bool {
+        false
+    }
 }
 
 /// Tool registry and execution service
 pub struct ToolService {
-    tools: HashMap<String, Box<dyn ToolExecutor>>,
+    tools: RwLock<HashMap<String, Arc<dyn ToolExecutor>>>,  // v3.9.0: shared so tools can be added at runtime
+    cache: ToolCache,  // v3.9.0: results of deterministic tools
+    running: RunningCalls,  // v3.9.0: cancel switches of calls in progress
+    timeouts: TimeoutOverrides,  // v3.9.0: user timeout overrides
 }
 
 impl ToolService {
     /// Create new tool service
     pub fn new() -> Self {
         Self {
-            tools: HashMap::new(),
+            tools: RwLock::new(HashMap::new()),
+            cache: ToolCache::new(),
+            running: RunningCalls::default(),
+            timeouts: TimeoutOverrides::default(),
         }
     }
 
@@ -103,54 +188,264 @@ impl ToolService {
     pub fn register_tool(&mut self, executor: Box<dyn ToolExecutor>) {
         let name = executor.definition().name.clone();
         info!(tool = %name, "Registering tool");
-        self.tools.insert(name, executor);
+        self.tools.get_mut().unwrap_or_else(|e| e.into_inner()).insert(name, Arc::from(executor));
+    }
+
+    /// Register a tool after startup; fails if the name is taken (v3.9.0)
+    pub fn add_tool(&self, executor: Arc<dyn ToolExecutor>) -> Result<()> {
+        let name = executor.definition().name.clone();
+        let mut tools = self.tools.write().unwrap_or_else(|e| e.into_inner());
+        if tools.contains_key(&name) {
+            return Err(anyhow!("A tool named {} is already registered", name));
+        }
+        info!(tool = %name, "Adding tool");
+        tools.insert(name, executor);
+        Ok(())
+    }
+
+    /// Unregister a tool added with `add_tool`; `false` if it isn't registered (v3.9.0)
+    pub fn remove_tool(&self, name: &str) -> bool {
+        let removed = self.tools.write().unwrap_or_else(|e| e.into_inner()).remove(name).is_some();
+        if removed {
+            info!(tool = %name, "Removed tool");
+        }
+        removed
+    }
+
+    fn registry(&self) -> RwLockReadGuard<'_, HashMap<String, Arc<dyn ToolExecutor>>> {
+        self.tools.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn executor(&self, name: &str) -> Option<Arc<dyn ToolExecutor>> {
+        self.registry().get(name).cloned()
     }
 
     /// Get all available tool definitions (for LLM prompt)
     pub fn get_tool_definitions(&self) -> Vec<ToolDefinition> {
-        self.tools
-            .values()
-            .map(|executor| executor.definition())
+        self.get_tool_definitions_in(None)
+    }
+
+    /// Definitions of the tools a tool set allows, or all with None (v3.9.0)
+    pub fn get_tool_definitions_in(&self, allowed: Option<&HashSet<String>>) -> Vec<ToolDefinition> {
+        self.registry()
+            .iter()
+            .filter(|(name, _)| tool_sets::allows(allowed, name))
+            .map(|(_, executor)| executor.definition())
             .collect()
     }
 
     /// Execute a tool call (now async)
-    #[instrument(skip(self), fields(tool = %tool_call.tool_name))]
     pub async fn execute_tool(&self, tool_call: &ToolCall) -> ToolResult {
+        self.execute_tool_as(tool_call, &uuid::Uuid::new_v4().to_string()).await
+    }
+
+    /// Execute a tool call the UI can cancel by `call_id` (v3.9.0)
+    #[instrument(skip(self), fields(tool = %tool_call.tool_name))]
+    pub async fn execute_tool_as(&self, tool_call: &ToolCall, call_id: &str) -> ToolResult {
         info!(tool = %tool_call.tool_name, "Executing tool");
         debug!(arguments = ?tool_call.arguments, "Tool arguments");
 
-        match self.tools.get(&tool_call.tool_name) {
+        let started = std::time::Instant::now();
+        let mut result = match self.executor(&tool_call.tool_name) {
             Some(executor) => {
-                match executor.execute(tool_call.arguments.clone()).await {
-                    Ok(result) => ToolResult {
-                        success: true,
-                        result,
-                        error: None,
-                    },
-                    Err(e) => ToolResult {
-                        success: false,
-                        result: serde_json::Value::Null,
-                        error: Some(e.to_string()),
+                let ttl = executor.cache_ttl();
+                let key = ttl.map(|_| {
+                    tool_cache::cache_key(&tool_call.tool_name, &executor.cache_key(&tool_call.arguments))
+                });
+                let cached = key.as_deref().and_then(|key| self.cache.get(key));
+
+                match cached {
+                    Some((result, cache)) => {
+                        debug!(age_secs = cache.age_secs, "Tool result served from cache");
+                        ToolResult {
+                            success: true,
+                            result,
+                            error: None,
+                            cache: Some(cache),
+                            interrupted: None,
+                            execution_ms: 0,
+                        }
+                    }
+                    None => match self.run_with_limits(executor.as_ref(), tool_call, call_id).await {
+                        Ok(Ok(result)) => {
+                            if let (Some(key), Some(ttl)) = (key, ttl) {
+                                self.cache.insert(key, result.clone(), ttl);
+                            }
+                            ToolResult {
+                                success: true,
+                                result,
+                                error: None,
+                                cache: None,
+                                interrupted: None,
+                                execution_ms: 0,
+                            }
+                        }
+                        Ok(Err(e)) => ToolResult::failure(e.to_string()),
+                        Err(interruption) => {
+                            info!(tool = %tool_call.tool_name, ?interruption, "Tool call interrupted");
+                            ToolResult {
+                                interrupted: Some(interruption.clone()),
+                                ..ToolResult::failure(interruption.message(&tool_call.tool_name))
+                            }
+                        }
                     },
                 }
             }
-            None => ToolResult {
-                success: false,
-                result: serde_json::Value::Null,
-                error: Some(format!("Tool not found: {}", tool_call.tool_name)),
-            },
+            None => ToolResult::failure(format!("Tool not found: {}", tool_call.tool_name)),
+        };
+        result.execution_ms = started.elapsed().as_millis() as u64;
+
+        // v3.9.0: Usage analytics
+        super::analytics::record_tool_call(&tool_call.tool_name, result.success);
+        result
+    }
+
+    /// Execute the calls of one model turn, results in call order (v3.9.0)
+    ///
+    /// Consecutive parallel-safe calls run concurrently; every other call runs
+    /// alone once the calls before it have finished.
+    pub async fn execute_tools(&self, calls: &[(String, ToolCall)]) -> Vec<ToolResult> {
+        self.execute_tools_in(calls, None).await
+    }
+
+    /// `execute_tools`, refusing calls to tools outside the tool set (v3.9.0)
+    pub async fn execute_tools_in(
+        &self,
+        calls: &[(String, ToolCall)],
+        allowed: Option<&HashSet<String>>,
+    ) -> Vec<ToolResult> {
+        let parallel_safe: Vec<bool> = calls
+            .iter()
+            .map(|(_, call)| self.executor(&call.tool_name).is_some_and(|executor| executor.parallel_safe()))
+            .collect();
+
+        let mut results = Vec::with_capacity(calls.len());
+        for batch in tool_runtime::batches(&parallel_safe, tool_runtime::MAX_PARALLEL_CALLS) {
+            if batch.len() > 1 {
+                debug!(calls = batch.len(), "Running tool calls in parallel");
+            }
+            let batch_results = futures::future::join_all(
+                calls[batch].iter().map(|(call_id, call)| self.execute_tool_in(call, call_id, allowed)),
+            )
+            .await;
+            results.extend(batch_results);
+        }
+        results
+    }
+
+    /// Execute a call unless the tool set excludes its tool (v3.9.0)
+    pub async fn execute_tool_in(
+        &self,
+        tool_call: &ToolCall,
+        call_id: &str,
+        allowed: Option<&HashSet<String>>,
+    ) -> ToolResult {
+        if !tool_sets::allows(allowed, &tool_call.tool_name) {
+            info!(tool = %tool_call.tool_name, "Tool call refused by the active tool set");
+            return ToolResult::failure(format!("Tool {} is not enabled in the active tool set", tool_call.tool_name));
+        }
+        self.execute_tool_as(tool_call, call_id).await
+    }
+
+    /// Run the executor until it finishes, times out or is cancelled
+    async fn run_with_limits(
+        &self,
+        executor: &dyn ToolExecutor,
+        tool_call: &ToolCall,
+        call_id: &str,
+    ) -> std::result::Result<Result<serde_json::Value>, ToolInterruption> {
+        let timeout = self.timeouts.get(&tool_call.tool_name).unwrap_or_else(|| executor.timeout());
+        let cancelled = self.running.start(call_id, &tool_call.tool_name);
+
+        let outcome = tokio::select! {
+            result = tokio::time::timeout(timeout, executor.execute(tool_call.arguments.clone())) => {
+                result.map_err(|_| ToolInterruption::TimedOut { after_secs: timeout.as_secs() })
+            }
+            Ok(()) = cancelled => Err(ToolInterruption::Cancelled),
+        };
+
+        self.running.finish(call_id);
+        outcome
+    }
+
+    /// Cancel a running call; `false` if it already finished (v3.9.0)
+    pub fn cancel_call(&self, call_id: &str) -> bool {
+        self.running.cancel(call_id)
+    }
+
+    /// Cancel every running call (v3.9.0)
+    pub fn cancel_all_calls(&self) -> usize {
+        self.running.cancel_all()
+    }
+
+    /// Calls in progress, oldest first (v3.9.0)
+    pub fn running_calls(&self) -> Vec<RunningToolCall> {
+        self.running.list()
+    }
+
+    /// Effective timeout of every registered tool, sorted by name (v3.9.0)
+    pub fn timeouts(&self) -> Vec<ToolTimeout> {
+        let mut timeouts: Vec<ToolTimeout> = self
+            .registry()
+            .iter()
+            .map(|(name, executor)| {
+                let default_secs = executor.timeout().as_secs();
+                let overridden = self.timeouts.get(name);
+                ToolTimeout {
+                    tool_name: name.clone(),
+                    timeout_secs: overridden.map_or(default_secs, |timeout| timeout.as_secs()),
+                    default_secs,
+                    overridden: overridden.is_some(),
+                }
+            })
+            .collect();
+        timeouts.sort_by(|a, b| a.tool_name.cmp(&b.tool_name));
+        timeouts
+    }
+
+    /// Override a tool's timeout, or with `None` restore its default (v3.9.0)
+    pub fn set_timeout(&self, tool_name: &str, timeout_secs: Option<u64>) -> Result<()> {
+        if !self.registry().contains_key(tool_name) {
+            return Err(anyhow!("Tool not found: {}", tool_name));
         }
+        self.timeouts.set(tool_name, timeout_secs)
+    }
+
+    /// Timeout overrides to persist (v3.9.0)
+    pub fn timeout_overrides(&self) -> HashMap<String, u64> {
+        self.timeouts.snapshot()
+    }
+
+    /// Apply timeout overrides loaded at startup (v3.9.0)
+    pub fn load_timeout_overrides(&self, overrides: HashMap<String, u64>) {
+        self.timeouts.replace(overrides);
     }
 
     /// Get tool definition by name
     pub fn get_tool(&self, name: &str) -> Option<ToolDefinition> {
-        self.tools.get(name).map(|executor| executor.definition())
+        self.registry().get(name).map(|executor| executor.definition())
     }
 
     /// List all available tools
     pub fn list_tools(&self) -> Vec<String> {
-        self.tools.keys().cloned().collect()
+        self.registry().keys().cloned().collect()
+    }
+
+    /// Names of the tools a tool set allows, or all with None (v3.9.0)
+    pub fn list_tools_in(&self, allowed: Option<&HashSet<String>>) -> Vec<String> {
+        self.registry().keys().filter(|name| tool_sets::allows(allowed, name)).cloned().collect()
+    }
+
+    /// Drop all cached tool results, returning how many were removed
+    pub fn clear_cache(&self) -> usize {
+        let removed = self.cache.clear();
+        info!(removed, "Tool cache cleared");
+        removed
+    }
+
+    /// Tool cache counters
+    pub fn cache_stats(&self) -> ToolCacheStats {
+        self.cache.stats()
     }
 
     /// Format tools for LLM system prompt
@@ -282,6 +577,10 @@ impl ToolExecutor for WebSearchTool {
             ],
         }
     }
+
+    fn parallel_safe(&self) -> bool {
+        true
+    }
 }
 
 /// File read tool with real FileService integration
@@ -338,6 +637,10 @@ impl ToolExecutor for FileReadTool {
             ],
         }
     }
+
+    fn parallel_safe(&self) -> bool {
+        true
+    }
 }
 
 /// Calculator tool with actual expression evaluation
@@ -505,6 +808,10 @@ impl ToolExecutor for CalculatorTool {
             ],
         }
     }
+
+    fn parallel_safe(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -580,6 +887,171 @@ mod tests {
         assert_eq!(CalculatorTool::evaluate_expression("2.5*4").unwrap(), 10.0); // decimals
     }
 
+    /// Counts executions so tests can tell cache hits from re-runs
+    struct CountingTool {
+        calls: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl ToolExecutor for CountingTool {
+        async fn execute(&self, arguments: serde_json::Value) -> Result<serde_json::Value> {
+            let n = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            Ok(serde_json::json!({ "query": arguments["query"], "run": n }))
+        }
+
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition {
+                name: "counting".to_string(),
+                description: "Counts calls".to_string(),
+                category: ToolCategory::WebSearch,
+                parameters: vec![],
+            }
+        }
+
+        fn cache_ttl(&self) -> Option<Duration> {
+            Some(Duration::from_secs(60))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_identical_calls_are_served_from_cache() {
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut service = ToolService::new();
+        service.register_tool(Box::new(CountingTool { calls: calls.clone() }));
+
+        let call = |query: &str| ToolCall {
+            tool_name: "counting".to_string(),
+            arguments: serde_json::json!({ "query": query }),
+        };
+
+        let first = service.execute_tool(&call("rust async")).await;
+        assert!(first.success && first.cache.is_none());
+
+        let second = service.execute_tool(&call("  rust   async ")).await;
+        assert!(second.cache.as_ref().unwrap().hit);
+        assert_eq!(second.result["run"], 1);
+        assert!(second.observation().contains("cached result"));
+
+        service.execute_tool(&call("other")).await;
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        assert_eq!(service.clear_cache(), 2);
+        let third = service.execute_tool(&call("rust async")).await;
+        assert!(third.cache.is_none());
+        assert_eq!(third.result["run"], 3);
+    }
+
+    /// Sleeps for `ms` milliseconds and records how many calls overlap
+    struct SleepTool {
+        active: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+        peak: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl ToolExecutor for SleepTool {
+        async fn execute(&self, arguments: serde_json::Value) -> Result<serde_json::Value> {
+            use std::sync::atomic::Ordering;
+            let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(active, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(arguments["ms"].as_u64().unwrap_or(0))).await;
+            self.active.fetch_sub(1, Ordering::SeqCst);
+            Ok(serde_json::json!({ "slept_ms": arguments["ms"] }))
+        }
+
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition {
+                name: "sleep".to_string(),
+                description: "Sleeps".to_string(),
+                category: ToolCategory::System,
+                parameters: vec![],
+            }
+        }
+
+        fn timeout(&self) -> Duration {
+            Duration::from_millis(500)
+        }
+
+        fn parallel_safe(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeouts_cancellation_and_parallel_calls() {
+        let active = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let peak = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut service = ToolService::new();
+        service.register_tool(Box::new(SleepTool { active: active.clone(), peak: peak.clone() }));
+        service.register_tool(Box::new(CalculatorTool));
+        let service = std::sync::Arc::new(service);
+
+        let sleep = |ms: u64| ToolCall {
+            tool_name: "sleep".to_string(),
+            arguments: serde_json::json!({ "ms": ms }),
+        };
+
+        let timed_out = service.execute_tool(&sleep(5_000)).await;
+        assert!(!timed_out.success);
+        assert_eq!(timed_out.interrupted, Some(ToolInterruption::TimedOut { after_secs: 0 }));
+
+        let running = {
+            let service = service.clone();
+            tokio::spawn(async move { service.execute_tool_as(&sleep(5_000), "call-1").await })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(service.running_calls().len(), 1);
+        assert!(service.cancel_call("call-1"));
+        let cancelled = running.await.unwrap();
+        assert_eq!(cancelled.interrupted, Some(ToolInterruption::Cancelled));
+        assert!(service.running_calls().is_empty());
+
+        // Interrupted calls were dropped mid-sleep and never left `active`
+        active.store(0, std::sync::atomic::Ordering::SeqCst);
+        peak.store(0, std::sync::atomic::Ordering::SeqCst);
+
+        // Both sleeps overlap; the calculator isn't parallel-safe and runs after them
+        let calculate = ToolCall {
+            tool_name: "calculate".to_string(),
+            arguments: serde_json::json!({ "expression": "1+1" }),
+        };
+        let calls = vec![
+            ("a".to_string(), sleep(100)),
+            ("b".to_string(), sleep(100)),
+            ("c".to_string(), calculate),
+        ];
+        let results = service.execute_tools(&calls).await;
+        assert!(results.iter().all(|result| result.success));
+        assert_eq!(results[1].result["slept_ms"], 100);
+        assert_eq!(peak.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        assert!(service.set_timeout("missing", Some(5)).is_err());
+        service.set_timeout("sleep", Some(5)).unwrap();
+        let sleep_timeout = service.timeouts().into_iter().find(|t| t.tool_name == "sleep").unwrap();
+        assert_eq!((sleep_timeout.timeout_secs, sleep_timeout.default_secs), (5, 0));
+        assert!(sleep_timeout.overridden);
+    }
+
+    #[tokio::test]
+    async fn test_tool_set_filtering() {
+        let mut service = ToolService::new();
+        service.register_tool(Box::new(CalculatorTool));
+        service.register_tool(Box::new(FileReadTool));
+
+        let allowed: HashSet<String> = ["read_file".to_string()].into_iter().collect();
+        let definitions = service.get_tool_definitions_in(Some(&allowed));
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions[0].name, "read_file");
+        assert_eq!(service.list_tools_in(None).len(), 2);
+
+        let calculate = ToolCall {
+            tool_name: "calculate".to_string(),
+            arguments: serde_json::json!({ "expression": "1+1" }),
+        };
+        let refused = service.execute_tools_in(&[("a".to_string(), calculate.clone())], Some(&allowed)).await;
+        assert!(!refused[0].success);
+        assert!(service.execute_tool_in(&calculate, "b", None).await.success);
+    }
+
     #[test]
     fn test_format_tools_for_prompt() {
         let mut service = ToolService::new();
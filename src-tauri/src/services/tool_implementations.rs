@@ -1,25 +1,66 @@
 //! Tool Implementations (v3.5.2)
 //!
 //! Production tool implementations for the tool calling system:
-//! - WebSearchTool: Fully integrated with WebSearchService (DuckDuckGo/SearX)
-//! - UrlFetchTool: Fully integrated with UrlFetchService (HTML parsing)
+//! - WebSearchTool: Fully integrated with WebSearchService (DuckDuckGo/SearX/Brave/Tavily with fallback)
+//! - UrlFetchTool: Fully integrated with UrlFetchService (Markdown, long pages summarized, optional translation)
 //! - FileReadTool: Integrated with FileService
 //! - FileWriteTool: Integrated with FileService
+//! - EditFileTool: Unified diffs / search-replace via FileEditService (v3.9.0)
+//! - DeleteFileTool: Deletes a file, restorable via the agent action log (v3.9.0)
+//! - GitCommitTool: Stages files and commits them (v3.9.0)
+//! - TerminalHistoryTool: Commands and output from captured terminal sessions (v3.9.0)
 //! - SystemInfoTool: Integrated with SystemInfoService
-//! - CalculatorTool: Simple math expression evaluator
+//! - CalculatorTool: Expressions, units, dates and currency via CalculatorService (v3.9.0)
+//! - TranslateTool: Local-model translation with the user's glossary (v3.9.0)
+//! - SqlQueryTool: Read-only queries on the user's configured databases (v3.9.0)
+//! - CreateChartTool: Charts shown with the reply, rendered by ChartService (v3.9.0)
+//! - RunCodeTool: JavaScript in the limited QuickJS sandbox of CodeSandboxService (v3.9.0)
+//! - WindowManagementTool: Lists, focuses, opens and arranges app windows via WindowManagerService (v3.9.0)
+//! - RunShortcutTool / RunPowerShellScriptTool: macOS Shortcuts and approved PowerShell scripts via OsAutomationService (v3.9.0)
+//!
+//! Web search, URL fetch and system info declare cache TTLs (v3.9.0).
+//! File and git changes are logged in the agent action log for undo (v3.9.0).
 
 #![allow(dead_code)]  // Phase 11: Tool implementations (on-demand loading)
 
 use anyhow::{anyhow, Result};
 use serde_json;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 
+use super::agent_actions::{self, ActionKind};
+use super::calculator::CalculatorService;
+use super::charts::{ChartService, ChartSpec};
+use super::code_sandbox::CodeSandboxService;
+use super::file_edit::{self, EditRequest, FileEditService, SearchReplaceBlock};
+use super::os_automation::OsAutomationService;
+use super::tool_cache;
 use super::tool_calling::{
     ToolCategory, ToolDefinition, ToolExecutor, ToolParameter, ParameterType,
 };
+use super::search_history::SearchHistoryService;
+use super::secrets::SecretsService;
+use super::sql_query::SqlQueryService;
+use super::terminal_capture::{TerminalCaptureService, TerminalQuery};
+use super::translation::TranslationService;
 use super::web_search::{WebSearchService, WebSearchSettings};
+use super::window_manager::{WindowManagerService, WindowPlacement};
+use super::workspace;
 use super::url_fetch::{UrlFetchService, UrlFetchSettings};
+use super::ollama;
+
+/// How long identical calls reuse a result (v3.9.0)
+const WEB_SEARCH_CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+const URL_FETCH_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+const SYSTEM_INFO_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Output characters returned per command by terminal_history; errors are at the end (v3.9.0)
+const TERMINAL_OUTPUT_TAIL_CHARS: usize = 3_000;
+
+/// Token budget for each summarized chunk of a long page (v3.9.0)
+const PAGE_SUMMARY_TOKENS: i32 = 160;
 
 /// Web search tool (fully integrated with WebSearchService)
 pub struct WebSearchTool {
@@ -46,6 +87,35 @@ impl WebSearchTool {
             service: Arc::new(Mutex::new(service)),
         })
     }
+
+    /// Create with API keys read from the secrets vault (v3.9.0)
+    pub fn with_secrets(secrets: Arc<SecretsService>) -> Result<Self> {
+        let settings = WebSearchSettings {
+            enabled: true,
+            ..Default::default()
+        };
+        let service = WebSearchService::new(settings)?.with_secrets(secrets);
+        Ok(Self {
+            service: Arc::new(Mutex::new(service)),
+        })
+    }
+
+    /// Create with vault API keys, saving every search to `history` (v3.9.0)
+    pub fn with_secrets_and_history(
+        secrets: Arc<SecretsService>,
+        history: Arc<SearchHistoryService>,
+    ) -> Result<Self> {
+        let settings = WebSearchSettings {
+            enabled: true,
+            ..Default::default()
+        };
+        let service = WebSearchService::new(settings)?
+            .with_secrets(secrets)
+            .with_history(history);
+        Ok(Self {
+            service: Arc::new(Mutex::new(service)),
+        })
+    }
 }
 
 #[async_trait::async_trait]
@@ -88,7 +158,7 @@ impl ToolExecutor for WebSearchTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "web_search".to_string(),
-            description: "Search the web for information using privacy-preserving DuckDuckGo".to_string(),
+            description: "Search the web for information (privacy-preserving engines with automatic fallback)".to_string(),
             category: ToolCategory::WebSearch,
             parameters: vec![
                 ToolParameter {
@@ -101,11 +171,25 @@ impl ToolExecutor for WebSearchTool {
             ],
         }
     }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(WEB_SEARCH_CACHE_TTL)
+    }
+
+    /// Search engines ignore case, so "Rust async" and "rust async" share a result
+    fn cache_key(&self, arguments: &serde_json::Value) -> String {
+        tool_cache::normalize_arguments(arguments).to_lowercase()
+    }
+
+    fn parallel_safe(&self) -> bool {
+        true
+    }
 }
 
 /// URL fetch tool (fully integrated with UrlFetchService)
 pub struct UrlFetchTool {
     service: Arc<UrlFetchService>,
+    translation: Option<Arc<TranslationService>>,  // v3.9.0: `translate_to` support
 }
 
 impl UrlFetchTool {
@@ -118,6 +202,7 @@ impl UrlFetchTool {
         let service = UrlFetchService::new(settings)?;
         Ok(Self {
             service: Arc::new(service),
+            translation: None,
         })
     }
 
@@ -126,6 +211,7 @@ impl UrlFetchTool {
         let service = UrlFetchService::new(settings)?;
         Ok(Self {
             service: Arc::new(service),
+            translation: None,
         })
     }
 }
@@ -141,13 +227,43 @@ impl ToolExecutor for UrlFetchTool {
 
         match self.service.fetch(url).await {
             Ok(content) => {
-                Ok(serde_json::json!({
+                // v3.9.0: Markdown main content, long pages condensed chunk by chunk
+                let condensed = UrlFetchService::condense(&content.markdown, |chunk| async move {
+                    ollama::summarize_passage(&chunk, PAGE_SUMMARY_TOKENS).await
+                })
+                .await;
+                if condensed.is_condensed() {
+                    log::info!(
+                        "Condensed {} ({} words) into {} parts ({} extractive)",
+                        content.url,
+                        content.word_count,
+                        condensed.chunks,
+                        condensed.fallback_chunks
+                    );
+                }
+
+                let mut output = serde_json::json!({
                     "url": content.url,
                     "title": content.title,
-                    "text": content.text,
+                    "content": condensed.content,
+                    "condensed": condensed.is_condensed(),
+                    "omitted_parts": condensed.omitted_chunks,
+                    "images": content.images,
                     "summary": content.summary,
                     "word_count": content.word_count
-                }))
+                });
+
+                // v3.9.0: Foreign-language pages translated for the user
+                let translate_to = arguments.get("translate_to").and_then(|v| v.as_str());
+                if let (Some(target), Some(translation)) = (translate_to, &self.translation) {
+                    let translated = translation.translate(&condensed.content, target, None).await?;
+                    if translated.translated {
+                        output["content"] = serde_json::Value::String(translated.text);
+                        output["translated_from"] = serde_json::json!(translated.source_language);
+                        output["translated_to"] = serde_json::Value::String(translated.target_language);
+                    }
+                }
+                Ok(output)
             }
             Err(e) => {
                 log::error!("URL fetch failed: {}", e);
@@ -159,7 +275,7 @@ impl ToolExecutor for UrlFetchTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "fetch_url".to_string(),
-            description: "Fetch and extract text content from a web page URL".to_string(),
+            description: "Fetch a web page and return its main content as Markdown (long pages are summarized)".to_string(),
             category: ToolCategory::WebFetch,
             parameters: vec![
                 ToolParameter {
@@ -169,9 +285,41 @@ impl ToolExecutor for UrlFetchTool {
                     required: true,
                     enum_values: None,
                 },
+                ToolParameter {
+                    name: "translate_to".to_string(),
+                    description: "Language code to translate the page into if it is written in another language (e.g. 'ko')".to_string(),
+                    param_type: ParameterType::String,
+                    required: false,
+                    enum_values: None,
+                },
             ],
         }
     }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(URL_FETCH_CACHE_TTL)
+    }
+
+    /// Canonical URL (lowercase scheme/host, no fragment), since paths are case-sensitive
+    fn cache_key(&self, arguments: &serde_json::Value) -> String {
+        let mut arguments = arguments.clone();
+        let canonical = arguments
+            .get("url")
+            .and_then(|v| v.as_str())
+            .and_then(|url| url::Url::parse(url.trim()).ok())
+            .map(|mut url| {
+                url.set_fragment(None);
+                url.to_string()
+            });
+        if let Some(url) = canonical {
+            arguments["url"] = serde_json::Value::String(url);
+        }
+        tool_cache::normalize_arguments(&arguments)
+    }
+
+    fn parallel_safe(&self) -> bool {
+        true
+    }
 }
 
 /// File read tool (demonstration)
@@ -183,6 +331,7 @@ impl ToolExecutor for FileReadTool {
         let path = arguments.get("path")
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow!("Missing 'path' parameter"))?;
+        let path = &workspace::resolve_tool_path(path)?;  // v3.9.0: Relative to the active project
 
         log::info!("File read tool executing: {}", path);
 
@@ -190,8 +339,9 @@ impl ToolExecutor for FileReadTool {
         match super::file::FileService::read_file(path) {
             Ok(content) => Ok(serde_json::json!({
                 "path": path,
-                "content": content,
-                "size": content.len()
+                "sha256": file_edit::content_hash(&content),  // v3.9.0: Passed back to edit_file
+                "size": content.len(),
+                "content": content
             })),
             Err(e) => Err(anyhow!("Failed to read file: {}", e))
         }
@@ -205,7 +355,7 @@ impl ToolExecutor for FileReadTool {
             parameters: vec![
                 ToolParameter {
                     name: "path".to_string(),
-                    description: "Path to the file to read".to_string(),
+                    description: "Path to the file to read (relative paths resolve in the active project)".to_string(),
                     param_type: ParameterType::String,
                     required: true,
                     enum_values: None,
@@ -213,6 +363,10 @@ impl ToolExecutor for FileReadTool {
             ],
         }
     }
+
+    fn parallel_safe(&self) -> bool {
+        true
+    }
 }
 
 /// File write tool (demonstration)
@@ -228,16 +382,21 @@ impl ToolExecutor for FileWriteTool {
         let content = arguments.get("content")
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow!("Missing 'content' parameter"))?;
+        let path = &workspace::resolve_tool_path(path)?;  // v3.9.0: Relative to the active project
 
         log::info!("File write tool executing: {}", path);
 
         // Use FileService::write_file directly (it's a static method)
-        super::file::FileService::write_file(path, content)?;
+        let description = format!("Write {} bytes", content.len());
+        let (_, action_id) = agent_actions::track_file_change(ActionKind::WriteFile, path, &description, || {
+            super::file::FileService::write_file(path, content)
+        })?;
 
         Ok(serde_json::json!({
             "path": path,
             "bytes_written": content.len(),
-            "success": true
+            "success": true,
+            "action_id": action_id  // v3.9.0: For agent_undo_action
         }))
     }
 
@@ -249,7 +408,7 @@ impl ToolExecutor for FileWriteTool {
             parameters: vec![
                 ToolParameter {
                     name: "path".to_string(),
-                    description: "Path where the file should be written".to_string(),
+                    description: "Path where the file should be written (relative paths resolve in the active project)".to_string(),
                     param_type: ParameterType::String,
                     required: true,
                     enum_values: None,
@@ -266,6 +425,364 @@ impl ToolExecutor for FileWriteTool {
     }
 }
 
+/// Diff-based file edit tool (v3.9.0)
+pub struct EditFileTool {
+    service: Arc<FileEditService>,
+}
+
+impl EditFileTool {
+    /// Pre-edit copies go to `backup_dir`
+    pub fn new(backup_dir: std::path::PathBuf) -> Self {
+        Self {
+            service: Arc::new(FileEditService::new(backup_dir)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ToolExecutor for EditFileTool {
+    async fn execute(&self, arguments: serde_json::Value) -> Result<serde_json::Value> {
+        let path = arguments.get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Missing 'path' parameter"))?;
+        let path = workspace::resolve_tool_path(path)?;
+
+        // `edits` array, or `patch` holding a unified diff or SEARCH/REPLACE blocks
+        let request = if let Some(edits) = arguments.get("edits").filter(|v| !v.is_null()) {
+            let blocks: Vec<SearchReplaceBlock> = serde_json::from_value(edits.clone())
+                .map_err(|e| anyhow!("Invalid 'edits' parameter (expected [{{search, replace}}]): {}", e))?;
+            EditRequest::SearchReplace(blocks)
+        } else {
+            let patch = arguments.get("patch")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("Missing 'patch' or 'edits' parameter"))?;
+            if patch.contains("<<<<<<<") {
+                EditRequest::SearchReplace(file_edit::parse_search_replace_blocks(patch)?)
+            } else {
+                EditRequest::UnifiedDiff(patch.to_string())
+            }
+        };
+        let expected_sha256 = arguments.get("expected_sha256")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        let preview = arguments.get("preview")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        log::info!("Edit file tool executing: {} (preview: {})", path, preview);
+
+        let service = Arc::clone(&self.service);
+        tokio::task::spawn_blocking(move || {
+            let plan = service.plan(&path, &request, expected_sha256.as_deref())?;
+            let mut output = serde_json::json!({
+                "path": plan.path,
+                "diff": plan.diff,
+                "lines_added": plan.lines_added,
+                "lines_removed": plan.lines_removed,
+                "applied": false
+            });
+            if !preview {
+                let description = format!("Edit (+{} -{})", plan.lines_added, plan.lines_removed);
+                let (outcome, action_id) = agent_actions::track_file_change(ActionKind::EditFile, &plan.path, &description, || {
+                    service.apply(&plan)
+                })?;
+                output["applied"] = serde_json::Value::Bool(true);
+                output["backup_path"] = serde_json::Value::String(outcome.backup_path);
+                output["sha256"] = serde_json::Value::String(outcome.sha256);
+                output["action_id"] = serde_json::json!(action_id);
+            }
+            Ok(output)
+        })
+        .await
+        .map_err(|e| anyhow!("Edit task failed: {}", e))?
+    }
+
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "edit_file".to_string(),
+            description: "Edit part of a text file with a unified diff or search/replace blocks (backs up the old version)".to_string(),
+            category: ToolCategory::FileSystem,
+            parameters: vec![
+                ToolParameter {
+                    name: "path".to_string(),
+                    description: "Path of the file to edit".to_string(),
+                    param_type: ParameterType::String,
+                    required: true,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "patch".to_string(),
+                    description: "Unified diff, or <<<<<<< SEARCH / ======= / >>>>>>> REPLACE blocks".to_string(),
+                    param_type: ParameterType::String,
+                    required: false,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "edits".to_string(),
+                    description: "Alternative to patch: list of {\"search\", \"replace\"} objects; each search must match once".to_string(),
+                    param_type: ParameterType::Array,
+                    required: false,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "expected_sha256".to_string(),
+                    description: "sha256 returned by read_file; the edit is refused if the file changed since".to_string(),
+                    param_type: ParameterType::String,
+                    required: false,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "preview".to_string(),
+                    description: "Return the resulting diff without writing (default false)".to_string(),
+                    param_type: ParameterType::Boolean,
+                    required: false,
+                    enum_values: None,
+                },
+            ],
+        }
+    }
+}
+
+/// File delete tool (v3.9.0)
+///
+/// The file is snapshotted in the agent action log first, so the deletion can be undone.
+pub struct DeleteFileTool;
+
+#[async_trait::async_trait]
+impl ToolExecutor for DeleteFileTool {
+    async fn execute(&self, arguments: serde_json::Value) -> Result<serde_json::Value> {
+        let path = arguments.get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Missing 'path' parameter"))?;
+        let path = workspace::resolve_tool_path(path)?;
+
+        log::info!("Delete file tool executing: {}", path);
+
+        tokio::task::spawn_blocking(move || {
+            let (_, action_id) = agent_actions::track_file_change(ActionKind::DeleteFile, &path, "Delete file", || {
+                super::file::FileService::delete_file(&path)
+            })?;
+            Ok(serde_json::json!({
+                "path": path,
+                "deleted": true,
+                "action_id": action_id
+            }))
+        })
+        .await
+        .map_err(|e| anyhow!("Delete task failed: {}", e))?
+    }
+
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "delete_file".to_string(),
+            description: "Delete a file (the user can undo this)".to_string(),
+            category: ToolCategory::FileSystem,
+            parameters: vec![
+                ToolParameter {
+                    name: "path".to_string(),
+                    description: "Path of the file to delete (relative paths resolve in the active project)".to_string(),
+                    param_type: ParameterType::String,
+                    required: true,
+                    enum_values: None,
+                },
+            ],
+        }
+    }
+}
+
+/// Git commit tool (v3.9.0)
+///
+/// Stages the given files and commits them; the commit is logged so it can be undone
+/// (soft reset, changes stay staged).
+pub struct GitCommitTool;
+
+#[async_trait::async_trait]
+impl ToolExecutor for GitCommitTool {
+    async fn execute(&self, arguments: serde_json::Value) -> Result<serde_json::Value> {
+        let message = arguments.get("message")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|m| !m.is_empty())
+            .ok_or_else(|| anyhow!("Missing 'message' parameter"))?
+            .to_string();
+        let paths: Vec<String> = arguments.get("paths")
+            .and_then(|v| v.as_array())
+            .map(|paths| paths.iter().filter_map(|p| p.as_str().map(str::to_string)).collect())
+            .unwrap_or_default();
+        if paths.is_empty() {
+            return Err(anyhow!("Missing 'paths' parameter (files to commit)"));
+        }
+        let repo_path = workspace::resolve_tool_path(
+            arguments.get("repo_path").and_then(|v| v.as_str()).unwrap_or("."),
+        )?;
+
+        log::info!("Git commit tool executing in {} ({} files)", repo_path, paths.len());
+
+        tokio::task::spawn_blocking(move || {
+            use super::git::GitService;
+
+            let repo = git2::Repository::open(&repo_path)
+                .map_err(|e| anyhow!("Not a git repository: {}", e))?;
+            let (name, email) = match repo.signature() {
+                Ok(signature) => (
+                    signature.name().unwrap_or("Garden of Eden").to_string(),
+                    signature.email().unwrap_or("agent@localhost").to_string(),
+                ),
+                Err(_) => ("Garden of Eden".to_string(), "agent@localhost".to_string()),
+            };
+
+            let parent = GitService::head_commit_id(&repo_path)?;
+            GitService::stage_files(&repo_path, paths.clone())?;
+            let commit_id = GitService::commit(&repo_path, &message, &name, &email)?;
+            let action_id = agent_actions::record_git_commit(&repo_path, &commit_id, parent.as_deref(), &message);
+
+            Ok(serde_json::json!({
+                "repo_path": repo_path,
+                "commit_id": commit_id,
+                "files": paths,
+                "action_id": action_id
+            }))
+        })
+        .await
+        .map_err(|e| anyhow!("Git commit task failed: {}", e))?
+    }
+
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "git_commit".to_string(),
+            description: "Stage files and create a git commit in the active project (the user can undo this)".to_string(),
+            category: ToolCategory::Git,
+            parameters: vec![
+                ToolParameter {
+                    name: "message".to_string(),
+                    description: "Commit message".to_string(),
+                    param_type: ParameterType::String,
+                    required: true,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "paths".to_string(),
+                    description: "Files to stage, relative to the repository root".to_string(),
+                    param_type: ParameterType::Array,
+                    required: true,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "repo_path".to_string(),
+                    description: "Repository path (defaults to the active project)".to_string(),
+                    param_type: ParameterType::String,
+                    required: false,
+                    enum_values: None,
+                },
+            ],
+        }
+    }
+}
+
+/// Terminal history tool (v3.9.0)
+pub struct TerminalHistoryTool {
+    service: Arc<TerminalCaptureService>,
+}
+
+impl TerminalHistoryTool {
+    pub fn new(service: Arc<TerminalCaptureService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait::async_trait]
+impl ToolExecutor for TerminalHistoryTool {
+    async fn execute(&self, arguments: serde_json::Value) -> Result<serde_json::Value> {
+        let query = TerminalQuery {
+            text: arguments.get("query").and_then(|v| v.as_str()).map(str::to_string),
+            failed_only: arguments.get("failed_only").and_then(|v| v.as_bool()).unwrap_or(false),
+            builds_only: arguments.get("builds_only").and_then(|v| v.as_bool()).unwrap_or(false),
+            session_id: None,
+            limit: Some(arguments.get("limit").and_then(|v| v.as_u64()).unwrap_or(3).clamp(1, 10) as usize),
+        };
+
+        log::info!("Terminal history tool executing: {:?}", query);
+
+        let service = Arc::clone(&self.service);
+        let (commands, capturing) = tokio::task::spawn_blocking(move || {
+            service.find(&query).map(|commands| (commands, service.has_active_session()))
+        })
+        .await
+        .map_err(|e| anyhow!("Terminal history task failed: {}", e))??;
+
+        let results: Vec<serde_json::Value> = commands
+            .iter()
+            .map(|command| {
+                let total = command.output.chars().count();
+                let output: String = command.output.chars().skip(total.saturating_sub(TERMINAL_OUTPUT_TAIL_CHARS)).collect();
+                serde_json::json!({
+                    "command": command.command,
+                    "cwd": command.cwd,
+                    "exit_code": command.exit_code,
+                    "failed": command.failed(),
+                    "finished_at": chrono::DateTime::from_timestamp(command.finished_at, 0)
+                        .map(|t| t.with_timezone(&chrono::Local).to_rfc3339()),
+                    "output": output,
+                    "output_truncated": total > TERMINAL_OUTPUT_TAIL_CHARS,
+                })
+            })
+            .collect();
+
+        let mut output = serde_json::json!({
+            "commands": results,
+            "capture_active": capturing,
+        });
+        if commands.is_empty() {
+            output["note"] = serde_json::Value::String(
+                "No matching commands recorded. Terminal history only covers sessions started with `garden_capture` after installing the shell hook in Settings.".to_string(),
+            );
+        }
+        Ok(output)
+    }
+
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "terminal_history".to_string(),
+            description: "Look up commands the user ran in captured terminal sessions with their exit codes and output, most recent first (e.g. the error from the last failed build)".to_string(),
+            category: ToolCategory::System,
+            parameters: vec![
+                ToolParameter {
+                    name: "query".to_string(),
+                    description: "Text to find in the command line or output".to_string(),
+                    param_type: ParameterType::String,
+                    required: false,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "failed_only".to_string(),
+                    description: "Only commands that exited with an error".to_string(),
+                    param_type: ParameterType::Boolean,
+                    required: false,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "builds_only".to_string(),
+                    description: "Only build and test commands (cargo, npm run build, make, pytest, ...)".to_string(),
+                    param_type: ParameterType::Boolean,
+                    required: false,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "limit".to_string(),
+                    description: "Number of commands to return (default 3, max 10)".to_string(),
+                    param_type: ParameterType::Number,
+                    required: false,
+                    enum_values: None,
+                },
+            ],
+        }
+    }
+
+    fn parallel_safe(&self) -> bool {
+        true
+    }
+}
+
 /// System information tool (demonstration)
 pub struct SystemInfoTool;
 
@@ -295,10 +812,27 @@ impl ToolExecutor for SystemInfoTool {
             parameters: vec![],
         }
     }
+
+    /// Short TTL: available RAM changes, but not within one plan step
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(SYSTEM_INFO_CACHE_TTL)
+    }
+
+    fn parallel_safe(&self) -> bool {
+        true
+    }
 }
 
-/// Calculator tool (demonstration)
-pub struct CalculatorTool;
+/// Calculator tool (v3.9.0: expressions, units, dates and currency via CalculatorService)
+pub struct CalculatorTool {
+    service: Arc<CalculatorService>,
+}
+
+impl CalculatorTool {
+    pub fn new(service: Arc<CalculatorService>) -> Self {
+        Self { service }
+    }
+}
 
 #[async_trait::async_trait]
 impl ToolExecutor for CalculatorTool {
@@ -306,46 +840,676 @@ impl ToolExecutor for CalculatorTool {
         let expression = arguments.get("expression")
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow!("Missing 'expression' parameter"))?;
+        // Some models send the variables as a JSON string
+        let variables: HashMap<String, serde_json::Value> = match arguments.get("variables") {
+            Some(serde_json::Value::String(text)) => serde_json::from_str(text)
+                .map_err(|e| anyhow!("'variables' must be an object: {}", e))?,
+            Some(serde_json::Value::Null) | None => HashMap::new(),
+            Some(value) => serde_json::from_value(value.clone())
+                .map_err(|e| anyhow!("'variables' must be an object: {}", e))?,
+        };
 
         log::info!("Calculator tool executing: {}", expression);
 
-        // Simple placeholder evaluation
-        // In production, use a proper math expression parser
-        let result = if expression.contains("+") {
-            let parts: Vec<&str> = expression.split('+').collect();
-            if parts.len() == 2 {
-                let a = parts[0].trim().parse::<f64>().unwrap_or(0.0);
-                let b = parts[1].trim().parse::<f64>().unwrap_or(0.0);
-                a + b
-            } else {
-                0.0
+        match self.service.calculate(expression, &variables).await {
+            Ok(calculation) => Ok(serde_json::to_value(calculation)?),
+            Err(e) => {
+                log::warn!("Calculation failed: {}", e);
+                Ok(serde_json::json!({
+                    "expression": expression,
+                    "result": null,
+                    "error": e.to_string(),
+                }))
             }
+        }
+    }
+
+    fn definition(&self) -> ToolDefinition {
+        let mut description = "Evaluate math exactly: arithmetic and functions, unit conversions ('5 ft to m', '3 h + 45 min to min'), \
+             percentages ('20% of 150', '150 + 10%'), variables ('a = 5; a^2') and dates \
+             ('today + 30 days', '2026-12-25 - today', 'days between 2026-01-01 and 2026-03-01')".to_string();
+        if self.service.get_config().exchange_rates_enabled {
+            description.push_str(", plus currency conversion ('100 USD to KRW')");
+        }
+
+        ToolDefinition {
+            name: "calculate".to_string(),
+            description,
+            category: ToolCategory::Calculation,
+            parameters: vec![
+                ToolParameter {
+                    name: "expression".to_string(),
+                    description: "Expression to evaluate (e.g., '2 + 3 * 4', '70 kg to lb')".to_string(),
+                    param_type: ParameterType::String,
+                    required: true,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "variables".to_string(),
+                    description: "Values to define first, e.g. {\"price\": 1200, \"qty\": 3}".to_string(),
+                    param_type: ParameterType::Object,
+                    required: false,
+                    enum_values: None,
+                },
+            ],
+        }
+    }
+
+    fn parallel_safe(&self) -> bool {
+        true
+    }
+}
+
+/// Translation tool (v3.9.0)
+pub struct TranslateTool {
+    service: Arc<TranslationService>,
+}
+
+impl TranslateTool {
+    pub fn new(service: Arc<TranslationService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait::async_trait]
+impl ToolExecutor for TranslateTool {
+    async fn execute(&self, arguments: serde_json::Value) -> Result<serde_json::Value> {
+        let text = arguments.get("text")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Missing 'text' parameter"))?;
+        let target = arguments.get("target_language")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Missing 'target_language' parameter"))?;
+        let source = arguments.get("source_language").and_then(|v| v.as_str());
+
+        log::info!("Translate tool executing: {} chars → {}", text.chars().count(), target);
+
+        let translation = self.service.translate(text, target, source).await?;
+        Ok(serde_json::to_value(translation)?)
+    }
+
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "translate".to_string(),
+            description: "Translate text between languages (Korean↔English and others) with the local model, using the user's glossary".to_string(),
+            category: ToolCategory::Language,
+            parameters: vec![
+                ToolParameter {
+                    name: "text".to_string(),
+                    description: "Text to translate".to_string(),
+                    param_type: ParameterType::String,
+                    required: true,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "target_language".to_string(),
+                    description: "Language code or name to translate into (e.g. 'ko', 'en', 'ja')".to_string(),
+                    param_type: ParameterType::String,
+                    required: true,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "source_language".to_string(),
+                    description: "Language of the text (detected when omitted)".to_string(),
+                    param_type: ParameterType::String,
+                    required: false,
+                    enum_values: None,
+                },
+            ],
+        }
+    }
+
+    fn parallel_safe(&self) -> bool {
+        true
+    }
+}
+
+/// SQL query tool (v3.9.0)
+///
+/// The description lists the configured databases and their schemas, so the
+/// model can write queries without exploring first.
+pub struct SqlQueryTool {
+    service: Arc<SqlQueryService>,
+}
+
+impl SqlQueryTool {
+    pub fn new(service: Arc<SqlQueryService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait::async_trait]
+impl ToolExecutor for SqlQueryTool {
+    async fn execute(&self, arguments: serde_json::Value) -> Result<serde_json::Value> {
+        let database = arguments.get("database")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Missing 'database' parameter"))?;
+        let query = arguments.get("query")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Missing 'query' parameter"))?;
+        let limit = arguments.get("limit").and_then(|v| v.as_u64()).map(|n| n as usize);
+
+        log::info!("SQL query tool executing on {}: {}", database, query);
+
+        let result = self.service.run_query(database, query, limit).await?;
+        Ok(serde_json::json!({
+            "columns": result.columns,
+            "row_count": result.rows.len(),
+            "rows": result.rows,
+            "truncated": result.truncated,
+        }))
+    }
+
+    fn definition(&self) -> ToolDefinition {
+        let overview = self.service.schema_overview();
+        let names: Vec<String> = self
+            .service
+            .list_connections()
+            .map(|connections| connections.into_iter().map(|c| c.name).collect())
+            .unwrap_or_default();
+        let description = if overview.is_empty() {
+            "Run a read-only SQL query on one of the user's databases (none are configured yet)".to_string()
         } else {
-            expression.parse::<f64>().unwrap_or(0.0)
+            format!(
+                "Run a read-only SQL query (SELECT / WITH / EXPLAIN) on one of the user's databases. Databases and tables:\n{}",
+                overview
+            )
         };
 
+        ToolDefinition {
+            name: "sql_query".to_string(),
+            description,
+            category: ToolCategory::Database,
+            parameters: vec![
+                ToolParameter {
+                    name: "database".to_string(),
+                    description: "Name of the database to query".to_string(),
+                    param_type: ParameterType::String,
+                    required: true,
+                    enum_values: if names.is_empty() { None } else { Some(names) },
+                },
+                ToolParameter {
+                    name: "query".to_string(),
+                    description: "One read-only SQL statement in the database's dialect".to_string(),
+                    param_type: ParameterType::String,
+                    required: true,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "limit".to_string(),
+                    description: format!(
+                        "Maximum rows to return (default {}, max {})",
+                        super::sql_query::DEFAULT_ROW_LIMIT,
+                        super::sql_query::MAX_ROW_LIMIT
+                    ),
+                    param_type: ParameterType::Number,
+                    required: false,
+                    enum_values: None,
+                },
+            ],
+        }
+    }
+
+    fn parallel_safe(&self) -> bool {
+        true
+    }
+}
+
+/// Chart tool (v3.9.0)
+///
+/// Stores the chart; the chat command attaches it to the reply being generated.
+pub struct CreateChartTool {
+    service: Arc<ChartService>,
+}
+
+impl CreateChartTool {
+    pub fn new(service: Arc<ChartService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait::async_trait]
+impl ToolExecutor for CreateChartTool {
+    async fn execute(&self, mut arguments: serde_json::Value) -> Result<serde_json::Value> {
+        // Some models send the arrays as JSON strings
+        for key in ["labels", "series"] {
+            if let Some(text) = arguments.get(key).and_then(|v| v.as_str()) {
+                arguments[key] = serde_json::from_str(text)
+                    .map_err(|e| anyhow!("'{}' must be a JSON array: {}", key, e))?;
+            }
+        }
+        let spec: ChartSpec = serde_json::from_value(arguments)
+            .map_err(|e| anyhow!("Invalid chart: {}", e))?;
+
+        log::info!("Create chart tool executing: {:?} chart with {} series", spec.kind, spec.series.len());
+
+        let service = Arc::clone(&self.service);
+        let chart = tokio::task::spawn_blocking(move || service.create(spec, None, None))
+            .await
+            .map_err(|e| anyhow!("Chart task failed: {}", e))??;
+
         Ok(serde_json::json!({
-            "expression": expression,
-            "result": result
+            "chart_id": chart.id,
+            "note": "The chart is shown to the user with your reply; describe what it shows instead of repeating the numbers.",
         }))
     }
 
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
-            name: "calculate".to_string(),
-            description: "Perform mathematical calculations on simple expressions".to_string(),
+            name: "create_chart".to_string(),
+            description: "Draw a line, bar, area or scatter chart from data (e.g. query results) to show with your reply".to_string(),
             category: ToolCategory::Calculation,
             parameters: vec![
                 ToolParameter {
-                    name: "expression".to_string(),
-                    description: "Mathematical expression to evaluate (e.g., '2 + 3')".to_string(),
+                    name: "kind".to_string(),
+                    description: "Chart type".to_string(),
+                    param_type: ParameterType::String,
+                    required: true,
+                    enum_values: Some(vec!["line".to_string(), "bar".to_string(), "area".to_string(), "scatter".to_string()]),
+                },
+                ToolParameter {
+                    name: "title".to_string(),
+                    description: "Chart title".to_string(),
                     param_type: ParameterType::String,
+                    required: false,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "labels".to_string(),
+                    description: "Category names along the x axis, one per value (e.g. [\"Jan\", \"Feb\"])".to_string(),
+                    param_type: ParameterType::Array,
+                    required: false,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "series".to_string(),
+                    description: "Data series: [{\"name\": \"2026\", \"values\": [1, 2]}]; line, area and scatter series may add numeric \"x\" values instead of labels".to_string(),
+                    param_type: ParameterType::Array,
                     required: true,
                     enum_values: None,
                 },
+                ToolParameter {
+                    name: "x_label".to_string(),
+                    description: "X axis title".to_string(),
+                    param_type: ParameterType::String,
+                    required: false,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "y_label".to_string(),
+                    description: "Y axis title".to_string(),
+                    param_type: ParameterType::String,
+                    required: false,
+                    enum_values: None,
+                },
             ],
         }
     }
+
+    fn parallel_safe(&self) -> bool {
+        true
+    }
+}
+
+/// Code execution tool (v3.9.0)
+///
+/// JavaScript in the QuickJS sandbox; script errors are returned as results
+/// so the model can fix its code and try again.
+pub struct RunCodeTool {
+    service: Arc<CodeSandboxService>,
+}
+
+impl RunCodeTool {
+    pub fn new(service: Arc<CodeSandboxService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait::async_trait]
+impl ToolExecutor for RunCodeTool {
+    async fn execute(&self, arguments: serde_json::Value) -> Result<serde_json::Value> {
+        let code = arguments.get("code")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Missing 'code' parameter"))?;
+        if let Some(language) = arguments.get("language").and_then(|v| v.as_str()) {
+            if !language.eq_ignore_ascii_case("javascript") && !language.eq_ignore_ascii_case("js") {
+                return Err(anyhow!("Only JavaScript can be run, not '{}'", language));
+            }
+        }
+        // Some models send the input as a JSON string
+        let input = match arguments.get("input") {
+            Some(serde_json::Value::String(text)) => Some(
+                serde_json::from_str(text).unwrap_or_else(|_| serde_json::Value::String(text.clone())),
+            ),
+            Some(serde_json::Value::Null) | None => None,
+            Some(value) => Some(value.clone()),
+        };
+
+        log::info!("Run code tool executing: {} bytes of JavaScript", code.len());
+
+        let result = self.service.run(code, input, None).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+
+    fn definition(&self) -> ToolDefinition {
+        let config = self.service.get_config();
+        let mut description = format!(
+            "Run JavaScript in a sandbox for exact calculations, data processing or checking an answer. \
+             Returns the value of the last expression and console.log output. \
+             No network, timers or modules; {} s and {} MB limits.",
+            config.timeout_secs, config.memory_limit_mb
+        );
+        if config.readable_paths.is_empty() {
+            description.push_str(" No file access.");
+        } else {
+            description.push_str(&format!(
+                " readFile(path) returns the text of files in: {}.",
+                config.readable_paths.join(", ")
+            ));
+        }
+
+        ToolDefinition {
+            name: "run_code".to_string(),
+            description,
+            category: ToolCategory::Calculation,
+            parameters: vec![
+                ToolParameter {
+                    name: "code".to_string(),
+                    description: "JavaScript to run; end with the expression whose value you need".to_string(),
+                    param_type: ParameterType::String,
+                    required: true,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "input".to_string(),
+                    description: "JSON data available to the code as `input`".to_string(),
+                    param_type: ParameterType::Object,
+                    required: false,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "language".to_string(),
+                    description: "Language of the code".to_string(),
+                    param_type: ParameterType::String,
+                    required: false,
+                    enum_values: Some(vec!["javascript".to_string()]),
+                },
+            ],
+        }
+    }
+
+    fn timeout(&self) -> Duration {
+        // The sandbox enforces its own limit; leave room for it to report
+        Duration::from_secs(self.service.get_config().timeout_secs + 5)
+    }
+
+    fn parallel_safe(&self) -> bool {
+        true
+    }
+}
+
+/// Window management tool (v3.9.0)
+///
+/// Lists, focuses, opens, moves and resizes app windows through the
+/// platform window APIs instead of pixel-level clicks.
+pub struct WindowManagementTool {
+    service: Arc<WindowManagerService>,
+}
+
+impl WindowManagementTool {
+    pub fn new(service: Arc<WindowManagerService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait::async_trait]
+impl ToolExecutor for WindowManagementTool {
+    async fn execute(&self, arguments: serde_json::Value) -> Result<serde_json::Value> {
+        let action = arguments.get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Missing 'action' parameter"))?
+            .to_string();
+        let target = arguments.get("target")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        // A single path is sometimes sent as a string
+        let paths: Vec<String> = match arguments.get("paths") {
+            Some(serde_json::Value::Array(items)) => items.iter().filter_map(|v| v.as_str().map(str::to_string)).collect(),
+            Some(serde_json::Value::String(path)) if !path.trim().is_empty() => vec![path.clone()],
+            _ => Vec::new(),
+        };
+        let number = |name: &str| arguments.get(name).and_then(|v| v.as_f64()).map(|n| n.round() as i32);
+        let placement = WindowPlacement {
+            x: number("x"),
+            y: number("y"),
+            width: number("width"),
+            height: number("height"),
+        };
+
+        log::info!("Window management tool executing: {} {:?}", action, target);
+
+        let service = Arc::clone(&self.service);
+        tokio::task::spawn_blocking(move || {
+            let require_target = || target.clone().ok_or_else(|| anyhow!("'{}' needs a 'target'", action));
+            match action.as_str() {
+                "list_windows" => {
+                    let windows = service.list_windows(target.as_deref())?;
+                    Ok(serde_json::json!({ "count": windows.len(), "windows": windows }))
+                }
+                "focus" => Ok(serde_json::json!({ "focused": service.focus(&require_target()?)? })),
+                "open_app" => {
+                    let app = require_target()?;
+                    let opened = service.open_app(&app, &paths)?;
+                    Ok(serde_json::json!({ "opened": app, "paths": opened }))
+                }
+                "open_url" => Ok(serde_json::json!({ "opened": service.open_url(&require_target()?)? })),
+                "move_resize" => Ok(serde_json::json!({ "window": service.move_resize(&require_target()?, placement)? })),
+                other => Err(anyhow!("Unknown action '{}'", other)),
+            }
+        })
+        .await?
+    }
+
+    fn definition(&self) -> ToolDefinition {
+        let number = |name: &str, description: &str| ToolParameter {
+            name: name.to_string(),
+            description: description.to_string(),
+            param_type: ParameterType::Number,
+            required: false,
+            enum_values: None,
+        };
+
+        ToolDefinition {
+            name: "manage_windows".to_string(),
+            description: "Manage apps and windows on the user's computer: list open windows, focus a window or app, \
+                          open an app (optionally with files or folders, e.g. a project in VS Code), \
+                          open a web URL, or move / resize a window. Prefer this over clicking on the screen."
+                .to_string(),
+            category: ToolCategory::System,
+            parameters: vec![
+                ToolParameter {
+                    name: "action".to_string(),
+                    description: "What to do".to_string(),
+                    param_type: ParameterType::String,
+                    required: true,
+                    enum_values: Some(vec![
+                        "list_windows".to_string(),
+                        "focus".to_string(),
+                        "open_app".to_string(),
+                        "open_url".to_string(),
+                        "move_resize".to_string(),
+                    ]),
+                },
+                ToolParameter {
+                    name: "target".to_string(),
+                    description: "Window id, app name or part of a window title (focus, move_resize); \
+                                  app name (open_app); URL (open_url); optional filter (list_windows)"
+                        .to_string(),
+                    param_type: ParameterType::String,
+                    required: false,
+                    enum_values: None,
+                },
+                ToolParameter {
+                    name: "paths".to_string(),
+                    description: "Existing files or folders to open with the app (open_app)".to_string(),
+                    param_type: ParameterType::Array,
+                    required: false,
+                    enum_values: None,
+                },
+                number("x", "New left edge in screen pixels (move_resize)"),
+                number("y", "New top edge in screen pixels (move_resize)"),
+                number("width", "New width in pixels (move_resize)"),
+                number("height", "New height in pixels (move_resize)"),
+            ],
+        }
+    }
+}
+
+/// macOS Shortcuts tool (v3.9.0)
+///
+/// Runs the user's Shortcuts by name; without a name it lists them.
+pub struct RunShortcutTool {
+    service: Arc<OsAutomationService>,
+}
+
+impl RunShortcutTool {
+    pub fn new(service: Arc<OsAutomationService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait::async_trait]
+impl ToolExecutor for RunShortcutTool {
+    async fn execute(&self, arguments: serde_json::Value) -> Result<serde_json::Value> {
+        let name = arguments.get("name")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|name| !name.is_empty());
+        let Some(name) = name else {
+            let shortcuts = self.service.list_shortcuts().await?;
+            return Ok(serde_json::json!({ "count": shortcuts.len(), "shortcuts": shortcuts }));
+        };
+        let input = arguments.get("input").and_then(|v| v.as_str());
+
+        log::info!("Run shortcut tool executing: {}", name);
+
+        let result = self.service.run_shortcut(name, input).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+
+    fn definition(&self) -> ToolDefinition {
+        let config = self.service.get_config();
+        let mut description = "Run one of the user's macOS Shortcuts by name, optionally with text input, \
+                               and return its text output. Call without a name to list the Shortcuts."
+            .to_string();
+        if config.allowed_shortcuts.is_empty() {
+            description.push_str(" No Shortcuts are allowed yet; the user adds them in settings.");
+        } else {
+            description.push_str(&format!(" Allowed: {}.", config.allowed_shortcuts.join(", ")));
+        }
+
+        ToolDefinition {
+            name: "run_shortcut".to_string(),
+            description,
+            category: ToolCategory::System,
+            parameters: vec![
+                ToolParameter {
+                    name: "name".to_string(),
+                    description: "Shortcut name exactly as in the Shortcuts app; empty to list them".to_string(),
+                    param_type: ParameterType::String,
+                    required: false,
+                    enum_values: (!config.allowed_shortcuts.is_empty()).then(|| config.allowed_shortcuts.clone()),
+                },
+                ToolParameter {
+                    name: "input".to_string(),
+                    description: "Text passed to the Shortcut as its input".to_string(),
+                    param_type: ParameterType::String,
+                    required: false,
+                    enum_values: None,
+                },
+            ],
+        }
+    }
+
+    fn timeout(&self) -> Duration {
+        // The service enforces its own limit; leave room for it to report
+        Duration::from_secs(self.service.get_config().timeout_secs + 5)
+    }
+}
+
+/// PowerShell tool (v3.9.0)
+///
+/// Runs only scripts the user approved, with their declared parameters.
+pub struct RunPowerShellScriptTool {
+    service: Arc<OsAutomationService>,
+}
+
+impl RunPowerShellScriptTool {
+    pub fn new(service: Arc<OsAutomationService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait::async_trait]
+impl ToolExecutor for RunPowerShellScriptTool {
+    async fn execute(&self, arguments: serde_json::Value) -> Result<serde_json::Value> {
+        let name = arguments.get("name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Missing 'name' parameter"))?;
+        let parameters = match arguments.get("parameters") {
+            Some(serde_json::Value::Object(map)) => map.clone(),
+            // Some models send the parameters as a JSON string
+            Some(serde_json::Value::String(text)) if !text.trim().is_empty() => serde_json::from_str(text)
+                .map_err(|_| anyhow!("'parameters' must be an object of parameter names to values"))?,
+            _ => serde_json::Map::new(),
+        };
+
+        log::info!("PowerShell tool executing: {}", name);
+
+        let result = self.service.run_script(name, &parameters).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+
+    fn definition(&self) -> ToolDefinition {
+        let scripts = self.service.get_config().scripts;
+        let mut description = "Run a PowerShell script the user approved and return its output.".to_string();
+        if scripts.is_empty() {
+            description.push_str(" No scripts are approved yet.");
+        } else {
+            for script in &scripts {
+                description.push_str(&format!("\n- {}: {}", script.name, script.description));
+                if !script.parameters.is_empty() {
+                    description.push_str(&format!(" (parameters: {})", script.parameters.join(", ")));
+                }
+            }
+        }
+
+        ToolDefinition {
+            name: "run_powershell_script".to_string(),
+            description,
+            category: ToolCategory::System,
+            parameters: vec![
+                ToolParameter {
+                    name: "name".to_string(),
+                    description: "Name of the approved script".to_string(),
+                    param_type: ParameterType::String,
+                    required: true,
+                    enum_values: (!scripts.is_empty()).then(|| scripts.iter().map(|s| s.name.clone()).collect()),
+                },
+                ToolParameter {
+                    name: "parameters".to_string(),
+                    description: "Script parameters as {\"Name\": value}; booleans set switches".to_string(),
+                    param_type: ParameterType::Object,
+                    required: false,
+                    enum_values: None,
+                },
+            ],
+        }
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.service.get_config().timeout_secs + 5)
+    }
 }
 
 #[cfg(test)]
@@ -372,6 +1536,39 @@ mod tests {
         assert_eq!(def.parameters.len(), 1);
     }
 
+    #[test]
+    fn test_url_fetch_cache_key_is_canonical() {
+        let tool = UrlFetchTool::new().unwrap();
+        let key = |url: &str| tool.cache_key(&serde_json::json!({ "url": url }));
+
+        assert_eq!(key("https://Example.com/Docs#intro"), key(" https://example.com/Docs "));
+        assert_ne!(key("https://example.com/Docs"), key("https://example.com/docs"));
+    }
+
+    #[tokio::test]
+    async fn test_edit_file_tool_previews_then_applies() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, "alpha\nbeta\ngamma\n").unwrap();
+        let path = path.to_string_lossy().to_string();
+        let tool = EditFileTool::new(dir.path().join("backups"));
+
+        let read = FileReadTool.execute(serde_json::json!({ "path": path })).await.unwrap();
+        let patch = "<<<<<<< SEARCH\nbeta\n=======\nBETA\n>>>>>>> REPLACE\n";
+        let args = serde_json::json!({ "path": path, "patch": patch, "expected_sha256": read["sha256"], "preview": true });
+
+        let preview = tool.execute(args.clone()).await.unwrap();
+        assert_eq!(preview["applied"], false);
+        assert!(preview["diff"].as_str().unwrap().contains("-beta\n+BETA\n"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "alpha\nbeta\ngamma\n");
+
+        let mut apply = args;
+        apply["preview"] = serde_json::Value::Bool(false);
+        let applied = tool.execute(apply).await.unwrap();
+        assert_eq!(applied["applied"], true);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "alpha\nBETA\ngamma\n");
+    }
+
     // TODO: Fix this async test (execute returns Future)
     // #[test]
     // fn test_calculator_tool() {
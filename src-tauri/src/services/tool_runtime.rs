@@ -0,0 +1,268 @@
+//! Tool Execution Runtime (v3.9.0)
+//!
+//! Timeouts, cancellation and parallel execution for tool calls.
+//!
+//! Features:
+//! - Per-tool timeouts: the executor's default (`ToolExecutor::timeout`) or a
+//!   user override persisted in `user_preferences`
+//! - Every running call has an id the UI can cancel it by (a hung fetch or a
+//!   search that takes too long)
+//! - Independent calls the model issues in one turn run concurrently; tools
+//!   that aren't `ToolExecutor::parallel_safe` run alone, in order
+//!
+//! A timed-out or cancelled call fails with `ToolResult::interrupted` set. Its
+//! future is dropped, so anything the tool hasn't started yet never happens.
+
+#![allow(dead_code)]  // Phase 5: Tool runtime
+
+use anyhow::{anyhow, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+/// Timeout of tools that don't declare their own
+pub const DEFAULT_TOOL_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Range accepted for user timeout overrides
+pub const MIN_TIMEOUT_SECS: u64 = 1;
+pub const MAX_TIMEOUT_SECS: u64 = 30 * 60;
+
+/// Calls run at once within one parallel batch
+pub const MAX_PARALLEL_CALLS: usize = 4;
+
+/// `user_preferences` key of the timeout overrides (tool name → seconds)
+const TIMEOUTS_KEY: &str = "tool_timeouts";
+
+/// Why a tool call stopped before finishing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum ToolInterruption {
+    TimedOut { after_secs: u64 },
+    Cancelled,
+}
+
+impl ToolInterruption {
+    /// Error text shown to the model and the UI
+    pub fn message(&self, tool_name: &str) -> String {
+        match self {
+            ToolInterruption::TimedOut { after_secs } => {
+                format!("Tool {} timed out after {}s", tool_name, after_secs)
+            }
+            ToolInterruption::Cancelled => format!("Tool {} was cancelled by the user", tool_name),
+        }
+    }
+}
+
+/// A tool call in progress
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunningToolCall {
+    pub call_id: String,
+    pub tool_name: String,
+    /// Unix milliseconds
+    pub started_at: i64,
+}
+
+/// Effective timeout of a tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolTimeout {
+    pub tool_name: String,
+    pub timeout_secs: u64,
+    /// The tool's own timeout
+    pub default_secs: u64,
+    pub overridden: bool,
+}
+
+/// Running calls with the switch that cancels each
+#[derive(Default)]
+pub struct RunningCalls {
+    calls: Mutex<HashMap<String, (RunningToolCall, oneshot::Sender<()>)>>,
+}
+
+impl RunningCalls {
+    /// Register a call; the receiver fires when it is cancelled
+    pub fn start(&self, call_id: &str, tool_name: &str) -> oneshot::Receiver<()> {
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+        let call = RunningToolCall {
+            call_id: call_id.to_string(),
+            tool_name: tool_name.to_string(),
+            started_at: chrono::Utc::now().timestamp_millis(),
+        };
+        if let Ok(mut calls) = self.calls.lock() {
+            calls.insert(call_id.to_string(), (call, cancel_tx));
+        }
+        cancel_rx
+    }
+
+    pub fn finish(&self, call_id: &str) {
+        if let Ok(mut calls) = self.calls.lock() {
+            calls.remove(call_id);
+        }
+    }
+
+    /// Cancel one call; `false` if it isn't running (any more)
+    pub fn cancel(&self, call_id: &str) -> bool {
+        let entry = self.calls.lock().ok().and_then(|mut calls| calls.remove(call_id));
+        match entry {
+            Some((call, cancel_tx)) => {
+                log::info!("Cancelling tool call {} ({})", call_id, call.tool_name);
+                cancel_tx.send(()).is_ok()
+            }
+            None => false,
+        }
+    }
+
+    /// Cancel every running call, returning how many were cancelled
+    pub fn cancel_all(&self) -> usize {
+        let switches: Vec<oneshot::Sender<()>> = match self.calls.lock() {
+            Ok(mut calls) => calls.drain().map(|(_, (_, cancel_tx))| cancel_tx).collect(),
+            Err(_) => return 0,
+        };
+        let cancelled = switches.into_iter().filter_map(|cancel_tx| cancel_tx.send(()).ok()).count();
+        log::info!("Cancelled {} running tool calls", cancelled);
+        cancelled
+    }
+
+    /// Calls in progress, oldest first
+    pub fn list(&self) -> Vec<RunningToolCall> {
+        let mut calls: Vec<RunningToolCall> = self
+            .calls
+            .lock()
+            .map(|calls| calls.values().map(|(call, _)| call.clone()).collect())
+            .unwrap_or_default();
+        calls.sort_by_key(|call| call.started_at);
+        calls
+    }
+}
+
+/// User timeout overrides, in seconds per tool
+#[derive(Default)]
+pub struct TimeoutOverrides {
+    overrides: RwLock<HashMap<String, u64>>,
+}
+
+impl TimeoutOverrides {
+    pub fn get(&self, tool_name: &str) -> Option<Duration> {
+        self.overrides
+            .read()
+            .ok()
+            .and_then(|overrides| overrides.get(tool_name).copied())
+            .map(Duration::from_secs)
+    }
+
+    /// Set (or with `None` remove) a tool's override
+    pub fn set(&self, tool_name: &str, timeout_secs: Option<u64>) -> Result<()> {
+        if let Some(secs) = timeout_secs {
+            if !(MIN_TIMEOUT_SECS..=MAX_TIMEOUT_SECS).contains(&secs) {
+                return Err(anyhow!(
+                    "Timeout must be between {} and {} seconds",
+                    MIN_TIMEOUT_SECS,
+                    MAX_TIMEOUT_SECS
+                ));
+            }
+        }
+        let mut overrides = self.overrides.write().map_err(|e| anyhow!("Timeout lock error: {}", e))?;
+        match timeout_secs {
+            Some(secs) => overrides.insert(tool_name.to_string(), secs),
+            None => overrides.remove(tool_name),
+        };
+        Ok(())
+    }
+
+    /// Replace all overrides (values outside the valid range are dropped)
+    pub fn replace(&self, loaded: HashMap<String, u64>) {
+        if let Ok(mut overrides) = self.overrides.write() {
+            *overrides = loaded
+                .into_iter()
+                .filter(|(_, secs)| (MIN_TIMEOUT_SECS..=MAX_TIMEOUT_SECS).contains(secs))
+                .collect();
+        }
+    }
+
+    pub fn snapshot(&self) -> HashMap<String, u64> {
+        self.overrides.read().map(|overrides| overrides.clone()).unwrap_or_default()
+    }
+}
+
+/// Saved timeout overrides
+pub fn load_overrides(conn: &Connection) -> Result<HashMap<String, u64>> {
+    let value: Option<String> = conn
+        .query_row("SELECT value FROM user_preferences WHERE key = ?1", [TIMEOUTS_KEY], |row| row.get(0))
+        .optional()?;
+    match value {
+        Some(json) => Ok(serde_json::from_str(&json)?),
+        None => Ok(HashMap::new()),
+    }
+}
+
+pub fn save_overrides(conn: &Connection, overrides: &HashMap<String, u64>) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+        params![TIMEOUTS_KEY, serde_json::to_string(overrides)?, chrono::Utc::now().timestamp_millis()],
+    )?;
+    Ok(())
+}
+
+/// Consecutive calls that can run together, in call order
+///
+/// A call that isn't parallel-safe gets a batch of its own, so state-changing
+/// tools see the effects of every call the model made before them.
+pub fn batches(parallel_safe: &[bool], max_parallel: usize) -> Vec<Range<usize>> {
+    let max_parallel = max_parallel.max(1);
+    let mut batches = Vec::new();
+    let mut start = 0;
+    for (i, safe) in parallel_safe.iter().enumerate() {
+        if !safe {
+            if start < i {
+                batches.push(start..i);
+            }
+            batches.push(i..i + 1);
+            start = i + 1;
+        } else if i + 1 - start == max_parallel {
+            batches.push(start..i + 1);
+            start = i + 1;
+        }
+    }
+    if start < parallel_safe.len() {
+        batches.push(start..parallel_safe.len());
+    }
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batches() {
+        assert_eq!(batches(&[true, true, false, true], 4), vec![0..2, 2..3, 3..4]);
+        assert_eq!(batches(&[true; 5], 2), vec![0..2, 2..4, 4..5]);
+        assert_eq!(batches(&[false, false], 4), vec![0..1, 1..2]);
+        assert!(batches(&[], 4).is_empty());
+    }
+
+    #[test]
+    fn test_running_calls() {
+        let running = RunningCalls::default();
+        let mut cancelled = running.start("call-1", "fetch_url");
+        let _other = running.start("call-2", "web_search");
+        assert_eq!(running.list().len(), 2);
+
+        assert!(running.cancel("call-1"));
+        assert!(cancelled.try_recv().is_ok());
+        assert!(!running.cancel("call-1"));
+
+        running.finish("call-2");
+        assert!(running.list().is_empty());
+
+        let overrides = TimeoutOverrides::default();
+        assert!(overrides.set("fetch_url", Some(0)).is_err());
+        overrides.set("fetch_url", Some(5)).unwrap();
+        assert_eq!(overrides.get("fetch_url"), Some(Duration::from_secs(5)));
+        overrides.set("fetch_url", None).unwrap();
+        assert_eq!(overrides.get("fetch_url"), None);
+    }
+}
@@ -0,0 +1,338 @@
+//! Tool Sets (v3.9.0)
+//!
+//! Named groups of tools a conversation or agent run is limited to, so a
+//! research chat can't write files and "Safe mode" never leaves the machine.
+//!
+//! Features:
+//! - Built-in sets: Research, Coding and Safe mode (read-only)
+//! - User-defined sets of any registered tools
+//! - One set per conversation; conversations without one get every tool
+//! - The ToolService presents only the set's tools to the model and refuses
+//!   calls to anything outside it
+
+#![allow(dead_code)]  // Phase 5: Tool sets
+
+use anyhow::{anyhow, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Maximum length of a set name (characters)
+const MAX_NAME_CHARS: usize = 40;
+
+/// Built-in sets: (id, name, description, tools)
+const BUILT_IN_SETS: &[(&str, &str, &str, &[&str])] = &[
+    (
+        "research",
+        "Research",
+        "Search the web, read pages, local files and databases",
+        &["web_search", "fetch_url", "read_file", "sql_query", "create_chart", "run_code", "translate", "calculate"],
+    ),
+    (
+        "coding",
+        "Coding",
+        "Read, edit and commit files in a project",
+        &[
+            "read_file",
+            "write_file",
+            "edit_file",
+            "delete_file",
+            "git_commit",
+            "terminal_history",
+            "web_search",
+            "fetch_url",
+            "calculate",
+            "run_code",
+            "get_system_info",
+            "manage_windows",
+        ],
+    ),
+    (
+        "safe_mode",
+        "Safe mode",
+        "Only tools that neither change files nor use the network",
+        &["read_file", "terminal_history", "get_system_info", "calculate", "run_code", "create_chart", "translate"],
+    ),
+];
+
+/// A named set of tools
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolSet {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub tools: Vec<String>,
+    /// Built-in sets can't be changed or deleted
+    pub built_in: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl ToolSet {
+    /// Tool names the set allows
+    pub fn allowed(&self) -> HashSet<String> {
+        self.tools.iter().cloned().collect()
+    }
+}
+
+/// Whether `allowed` (None meaning every tool) includes a tool
+pub fn allows(allowed: Option<&HashSet<String>>, tool_name: &str) -> bool {
+    allowed.is_none_or(|tools| tools.contains(tool_name))
+}
+
+pub fn built_in_sets() -> Vec<ToolSet> {
+    BUILT_IN_SETS
+        .iter()
+        .map(|(id, name, description, tools)| ToolSet {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            tools: tools.iter().map(|tool| tool.to_string()).collect(),
+            built_in: true,
+            created_at: 0,
+            updated_at: 0,
+        })
+        .collect()
+}
+
+/// Built-in sets first, then the user's by name
+pub fn list_tool_sets(conn: &Connection) -> Result<Vec<ToolSet>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, description, tools, created_at, updated_at
+         FROM tool_sets ORDER BY name COLLATE NOCASE",
+    )?;
+    let custom: Vec<ToolSet> = stmt
+        .query_map([], row_to_set)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut sets = built_in_sets();
+    sets.extend(custom);
+    Ok(sets)
+}
+
+pub fn get_tool_set(conn: &Connection, set_id: &str) -> Result<Option<ToolSet>> {
+    if let Some(set) = built_in_sets().into_iter().find(|set| set.id == set_id) {
+        return Ok(Some(set));
+    }
+    Ok(conn
+        .query_row(
+            "SELECT id, name, description, tools, created_at, updated_at FROM tool_sets WHERE id = ?1",
+            [set_id],
+            row_to_set,
+        )
+        .optional()?)
+}
+
+/// Create a set from registered tools (`known_tools`)
+pub fn create_tool_set(
+    conn: &Connection,
+    name: &str,
+    description: &str,
+    tools: &[String],
+    known_tools: &[String],
+) -> Result<ToolSet> {
+    let name = normalize_name(name).ok_or_else(|| anyhow!("Tool set name is empty"))?;
+    ensure_name_free(conn, &name, None)?;
+    let tools = validate_tools(tools, known_tools)?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let set = ToolSet {
+        id: format!("toolset_{}", uuid::Uuid::new_v4()),
+        name,
+        description: description.trim().to_string(),
+        tools,
+        built_in: false,
+        created_at: now,
+        updated_at: now,
+    };
+    conn.execute(
+        "INSERT INTO tool_sets (id, name, description, tools, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![set.id, set.name, set.description, serde_json::to_string(&set.tools)?, set.created_at, set.updated_at],
+    )?;
+    log::info!("Created tool set '{}' ({} tools)", set.name, set.tools.len());
+    Ok(set)
+}
+
+pub fn update_tool_set(
+    conn: &Connection,
+    set_id: &str,
+    name: &str,
+    description: &str,
+    tools: &[String],
+    known_tools: &[String],
+) -> Result<ToolSet> {
+    ensure_custom(set_id)?;
+    let name = normalize_name(name).ok_or_else(|| anyhow!("Tool set name is empty"))?;
+    ensure_name_free(conn, &name, Some(set_id))?;
+    let tools = validate_tools(tools, known_tools)?;
+
+    let changed = conn.execute(
+        "UPDATE tool_sets SET name = ?1, description = ?2, tools = ?3, updated_at = ?4 WHERE id = ?5",
+        params![
+            name,
+            description.trim(),
+            serde_json::to_string(&tools)?,
+            chrono::Utc::now().timestamp_millis(),
+            set_id
+        ],
+    )?;
+    if changed == 0 {
+        return Err(anyhow!("Tool set not found: {}", set_id));
+    }
+    get_tool_set(conn, set_id)?.ok_or_else(|| anyhow!("Tool set not found: {}", set_id))
+}
+
+/// Delete a set; conversations using it get every tool again
+pub fn delete_tool_set(conn: &Connection, set_id: &str) -> Result<()> {
+    ensure_custom(set_id)?;
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("UPDATE conversations SET tool_set_id = NULL WHERE tool_set_id = ?1", [set_id])?;
+    let deleted = tx.execute("DELETE FROM tool_sets WHERE id = ?1", [set_id])?;
+    if deleted == 0 {
+        return Err(anyhow!("Tool set not found: {}", set_id));
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Bind a conversation to a set, or back to every tool with None
+pub fn set_conversation_tool_set(conn: &Connection, conversation_id: &str, set_id: Option<&str>) -> Result<()> {
+    if let Some(set_id) = set_id {
+        if get_tool_set(conn, set_id)?.is_none() {
+            return Err(anyhow!("Tool set not found: {}", set_id));
+        }
+    }
+    let changed = conn.execute(
+        "UPDATE conversations SET tool_set_id = ?1 WHERE id = ?2",
+        params![set_id, conversation_id],
+    )?;
+    if changed == 0 {
+        return Err(anyhow!("Conversation not found: {}", conversation_id));
+    }
+    Ok(())
+}
+
+pub fn conversation_tool_set(conn: &Connection, conversation_id: &str) -> Result<Option<ToolSet>> {
+    let set_id: Option<String> = conn
+        .query_row(
+            "SELECT tool_set_id FROM conversations WHERE id = ?1",
+            [conversation_id],
+            |row| row.get(0),
+        )
+        .optional()?
+        .flatten();
+    match set_id {
+        Some(set_id) => get_tool_set(conn, &set_id),
+        None => Ok(None),
+    }
+}
+
+/// Tools a conversation may use; None means every tool
+pub fn allowed_tools(conn: &Connection, conversation_id: &str) -> Result<Option<HashSet<String>>> {
+    Ok(conversation_tool_set(conn, conversation_id)?.map(|set| set.allowed()))
+}
+
+fn row_to_set(row: &rusqlite::Row) -> rusqlite::Result<ToolSet> {
+    let tools: String = row.get(3)?;
+    Ok(ToolSet {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        tools: serde_json::from_str(&tools).unwrap_or_default(),
+        built_in: false,
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+    })
+}
+
+fn ensure_custom(set_id: &str) -> Result<()> {
+    if BUILT_IN_SETS.iter().any(|(id, ..)| *id == set_id) {
+        return Err(anyhow!("Built-in tool sets can't be changed"));
+    }
+    Ok(())
+}
+
+fn ensure_name_free(conn: &Connection, name: &str, except_id: Option<&str>) -> Result<()> {
+    if BUILT_IN_SETS.iter().any(|(_, built_in, ..)| built_in.eq_ignore_ascii_case(name)) {
+        return Err(anyhow!("'{}' is a built-in tool set", name));
+    }
+    let existing: Option<String> = conn
+        .query_row(
+            "SELECT id FROM tool_sets WHERE name = ?1 COLLATE NOCASE",
+            [name],
+            |row| row.get(0),
+        )
+        .optional()?;
+    match existing {
+        Some(id) if Some(id.as_str()) != except_id => Err(anyhow!("A tool set named '{}' already exists", name)),
+        _ => Ok(()),
+    }
+}
+
+/// De-duplicated tool names, all of them registered
+fn validate_tools(tools: &[String], known_tools: &[String]) -> Result<Vec<String>> {
+    let mut validated: Vec<String> = Vec::new();
+    for tool in tools.iter().map(|tool| tool.trim()) {
+        if !known_tools.iter().any(|known| known == tool) {
+            return Err(anyhow!("Unknown tool: {}", tool));
+        }
+        if !validated.iter().any(|existing| existing == tool) {
+            validated.push(tool.to_string());
+        }
+    }
+    Ok(validated)
+}
+
+fn normalize_name(name: &str) -> Option<String> {
+    let name: String = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() {
+        return None;
+    }
+    Some(name.chars().take(MAX_NAME_CHARS).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+
+    #[test]
+    fn test_tool_sets_and_bindings() {
+        let db = Database::new_test_db().unwrap();
+        let conn = db.conn();
+        conn.execute(
+            "INSERT INTO conversations (id, title, mode, created_at, updated_at, message_count)
+             VALUES ('a', 'Refactor', 'user-led', 1000, 1000, 0)",
+            [],
+        )
+        .unwrap();
+        let known: Vec<String> = ["read_file", "write_file", "web_search"].iter().map(|t| t.to_string()).collect();
+
+        assert!(create_tool_set(conn, "Writer", "", &["rm_rf".into()], &known).is_err());
+        assert!(create_tool_set(conn, "safe MODE", "", &[], &known).is_err());
+        let set = create_tool_set(conn, " Writer ", "", &["write_file".into(), "read_file".into(), "write_file".into()], &known)
+            .unwrap();
+        assert_eq!(set.name, "Writer");
+        assert_eq!(set.tools, vec!["write_file", "read_file"]);
+        assert_eq!(list_tool_sets(conn).unwrap().len(), BUILT_IN_SETS.len() + 1);
+        assert!(update_tool_set(conn, "coding", "Coding", "", &[], &known).is_err());
+
+        // Unbound conversations get every tool
+        assert_eq!(allowed_tools(conn, "a").unwrap(), None);
+        set_conversation_tool_set(conn, "a", Some(&set.id)).unwrap();
+        let allowed = allowed_tools(conn, "a").unwrap();
+        assert!(allows(allowed.as_ref(), "read_file"));
+        assert!(!allows(allowed.as_ref(), "web_search"));
+
+        set_conversation_tool_set(conn, "a", Some("safe_mode")).unwrap();
+        assert_eq!(conversation_tool_set(conn, "a").unwrap().unwrap().name, "Safe mode");
+        assert!(set_conversation_tool_set(conn, "a", Some("missing")).is_err());
+
+        set_conversation_tool_set(conn, "a", Some(&set.id)).unwrap();
+        delete_tool_set(conn, &set.id).unwrap();
+        assert_eq!(allowed_tools(conn, "a").unwrap(), None);
+        assert!(delete_tool_set(conn, "research").is_err());
+    }
+}
@@ -0,0 +1,324 @@
+//! Translation Service (v3.9.0)
+//!
+//! Translates text with the local chat model, so nothing leaves the machine.
+//!
+//! Features:
+//! - Korean↔English and any other pair the model handles
+//! - Source language detected when not given; same-language text is returned as is
+//! - User glossary (term → translation, optionally per target language)
+//!   passed to the model and enforced on the output
+//! - Long text translated passage by passage; fenced code blocks are kept verbatim
+//!
+//! The glossary persists in `user_preferences`.
+
+#![allow(dead_code)]  // Phase 5: Translation
+
+use crate::database::Database;
+use crate::services::chunker::estimate_tokens;
+use crate::services::{language_detection, ollama};
+use anyhow::{anyhow, Result};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+const GLOSSARY_KEY: &str = "translation_glossary";
+
+/// Characters per passage sent to the model
+const MAX_PASSAGE_CHARS: usize = 2_000;
+
+/// Glossary entries kept
+const MAX_GLOSSARY_ENTRIES: usize = 200;
+
+/// User-defined term mapping
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GlossaryEntry {
+    /// Term as it appears in the source text
+    pub term: String,
+    /// Required translation of the term
+    pub translation: String,
+    /// Target language the mapping applies to (ISO 639-1); None for all
+    #[serde(default)]
+    pub target_language: Option<String>,
+}
+
+/// Translated text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Translation {
+    pub text: String,
+    /// Given or detected source language (ISO 639-1), None if undetectable
+    pub source_language: Option<String>,
+    pub target_language: String,
+    /// False when the text already was in the target language
+    pub translated: bool,
+    /// Glossary terms found in the source text
+    pub glossary_terms: Vec<String>,
+}
+
+fn load_glossary(conn: &Connection) -> Vec<GlossaryEntry> {
+    conn.query_row("SELECT value FROM user_preferences WHERE key = ?1", [GLOSSARY_KEY], |row| {
+        row.get::<_, String>(0)
+    })
+    .optional()
+    .ok()
+    .flatten()
+    .and_then(|json| serde_json::from_str(&json).ok())
+    .unwrap_or_default()
+}
+
+/// Local-model translation service
+pub struct TranslationService {
+    db: Arc<Mutex<Database>>,
+}
+
+impl TranslationService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Self {
+        log::info!("✓ Translation Service initialized");
+        Self { db }
+    }
+
+    pub fn get_glossary(&self) -> Result<Vec<GlossaryEntry>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        Ok(load_glossary(db.conn()))
+    }
+
+    /// Replace the glossary (blank entries dropped, later duplicates win)
+    pub fn update_glossary(&self, entries: Vec<GlossaryEntry>) -> Result<Vec<GlossaryEntry>> {
+        let mut glossary: Vec<GlossaryEntry> = Vec::new();
+        for entry in entries {
+            let target_language = match entry.target_language.as_deref().map(str::trim).filter(|code| !code.is_empty()) {
+                Some(code) => Some(
+                    language_detection::resolve_language(code)
+                        .map(|(code, _)| code)
+                        .ok_or_else(|| anyhow!("Unknown glossary language: {}", code))?,
+                ),
+                None => None,
+            };
+            let entry = GlossaryEntry {
+                term: entry.term.trim().to_string(),
+                translation: entry.translation.trim().to_string(),
+                target_language,
+            };
+            if entry.term.is_empty() || entry.translation.is_empty() {
+                continue;
+            }
+            glossary.retain(|existing| {
+                !(existing.term.eq_ignore_ascii_case(&entry.term) && existing.target_language == entry.target_language)
+            });
+            glossary.push(entry);
+        }
+        if glossary.len() > MAX_GLOSSARY_ENTRIES {
+            return Err(anyhow!("Glossary is limited to {} entries", MAX_GLOSSARY_ENTRIES));
+        }
+
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn().execute(
+            "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+            rusqlite::params![GLOSSARY_KEY, serde_json::to_string(&glossary)?, chrono::Utc::now().timestamp()],
+        )?;
+        log::info!("Translation glossary updated ({} entries)", glossary.len());
+        Ok(glossary)
+    }
+
+    /// Translate `text` into `target_language` (code or name, e.g. "ko", "English")
+    pub async fn translate(
+        &self,
+        text: &str,
+        target_language: &str,
+        source_language: Option<&str>,
+    ) -> Result<Translation> {
+        let (target_code, target_name) = language_detection::resolve_language(target_language)
+            .ok_or_else(|| anyhow!("Unknown target language: {}", target_language))?;
+        let source = match source_language.map(str::trim).filter(|code| !code.is_empty()) {
+            Some(code) => Some(
+                language_detection::resolve_language(code)
+                    .ok_or_else(|| anyhow!("Unknown source language: {}", code))?,
+            ),
+            None => language_detection::detect(text).map(|detected| (detected.code, detected.name)),
+        };
+
+        if text.trim().is_empty() || source.as_ref().is_some_and(|(code, _)| *code == target_code) {
+            return Ok(Translation {
+                text: text.to_string(),
+                source_language: source.map(|(code, _)| code),
+                target_language: target_code,
+                translated: false,
+                glossary_terms: Vec::new(),
+            });
+        }
+
+        let glossary = glossary_for(&self.get_glossary()?, text, &target_code);
+        let instructions = instructions(source.as_ref().map(|(_, name)| name.as_str()), &target_name, &glossary);
+
+        let mut translated = Vec::new();
+        for passage in split_passages(text, MAX_PASSAGE_CHARS) {
+            if is_code_block(&passage) {
+                translated.push(passage);
+                continue;
+            }
+            let max_tokens = (estimate_tokens(&passage) * 2 + 64) as i32;
+            let output = ollama::translate_passage(&passage, &instructions, max_tokens)
+                .await
+                .map_err(|e| anyhow!("Translation failed: {}", e))?;
+            translated.push(output);
+        }
+
+        let text = apply_glossary(&translated.join("\n\n"), &glossary);
+        log::info!(
+            "Translated {} chars {} → {}",
+            text.chars().count(),
+            source.as_ref().map(|(code, _)| code.as_str()).unwrap_or("?"),
+            target_code
+        );
+        Ok(Translation {
+            text,
+            source_language: source.map(|(code, _)| code),
+            target_language: target_code,
+            translated: true,
+            glossary_terms: glossary.into_iter().map(|entry| entry.term).collect(),
+        })
+    }
+}
+
+/// Glossary entries for `target` whose term occurs in `text`
+fn glossary_for(glossary: &[GlossaryEntry], text: &str, target: &str) -> Vec<GlossaryEntry> {
+    let lower = text.to_lowercase();
+    glossary
+        .iter()
+        .filter(|entry| entry.target_language.as_deref().is_none_or(|code| code == target))
+        .filter(|entry| lower.contains(&entry.term.to_lowercase()))
+        .cloned()
+        .collect()
+}
+
+fn instructions(source_name: Option<&str>, target_name: &str, glossary: &[GlossaryEntry]) -> String {
+    let mut instructions = match source_name {
+        Some(source) => format!("Translate the following text from {} to {}.", source, target_name),
+        None => format!("Translate the following text to {}.", target_name),
+    };
+    if !glossary.is_empty() {
+        instructions.push_str("\nAlways translate these terms exactly as given:\n");
+        for entry in glossary {
+            instructions.push_str(&format!("- \"{}\" → \"{}\"\n", entry.term, entry.translation));
+        }
+    }
+    instructions
+}
+
+/// Replace glossary terms the model left untranslated
+fn apply_glossary(text: &str, glossary: &[GlossaryEntry]) -> String {
+    let mut text = text.to_string();
+    for entry in glossary {
+        if !text.contains(&entry.translation) {
+            text = text.replace(&entry.term, &entry.translation);
+        }
+    }
+    text
+}
+
+/// Split text at blank lines into passages of at most `max_chars`
+/// (a single longer paragraph stays whole; fenced code blocks are never split)
+fn split_passages(text: &str, max_chars: usize) -> Vec<String> {
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut in_fence = false;
+    for line in text.lines() {
+        let fence = line.trim_start().starts_with("```");
+        if fence && !in_fence && !current.trim().is_empty() {
+            // Code blocks are their own passages
+            paragraphs.push(std::mem::take(&mut current));
+        }
+        if line.trim().is_empty() && !in_fence {
+            if !current.trim().is_empty() {
+                paragraphs.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+        if fence {
+            in_fence = !in_fence;
+            if !in_fence {
+                paragraphs.push(std::mem::take(&mut current));
+            }
+        }
+    }
+    if !current.trim().is_empty() {
+        paragraphs.push(current);
+    }
+
+    let mut passages: Vec<String> = Vec::new();
+    for paragraph in paragraphs {
+        match passages.last_mut() {
+            Some(last)
+                if !is_code_block(last)
+                    && !is_code_block(&paragraph)
+                    && last.chars().count() + paragraph.chars().count() + 2 <= max_chars =>
+            {
+                last.push_str("\n\n");
+                last.push_str(&paragraph);
+            }
+            _ => passages.push(paragraph),
+        }
+    }
+    passages
+}
+
+fn is_code_block(passage: &str) -> bool {
+    passage.trim_start().starts_with("```")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_passages_keeps_code_blocks() {
+        let text = "첫 번째 문단입니다.\n\n두 번째 문단입니다.\n```rust\nfn main() {\n\n    println!(\"hi\");\n}\n```\n마지막 문단.";
+        let passages = split_passages(text, 2_000);
+        assert_eq!(passages.len(), 3);
+        assert_eq!(passages[0], "첫 번째 문단입니다.\n\n두 번째 문단입니다.");
+        assert!(is_code_block(&passages[1]));
+        assert!(passages[1].contains("\n\n    println!"));
+        assert_eq!(passages[2], "마지막 문단.");
+
+        let long = vec!["a".repeat(900); 3].join("\n\n");
+        assert_eq!(split_passages(&long, 2_000).len(), 2);
+    }
+
+    #[test]
+    fn test_glossary() {
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let service = TranslationService::new(db);
+        let glossary = service
+            .update_glossary(vec![
+                GlossaryEntry { term: "Garden of Eden".into(), translation: "에덴 정원".into(), target_language: Some("Korean".into()) },
+                GlossaryEntry { term: "  ".into(), translation: "x".into(), target_language: None },
+                GlossaryEntry { term: "garden of eden".into(), translation: "에덴의 정원".into(), target_language: Some("ko".into()) },
+                GlossaryEntry { term: "Adam".into(), translation: "아담".into(), target_language: None },
+            ])
+            .unwrap();
+        assert_eq!(glossary.len(), 2);
+        assert_eq!(service.get_glossary().unwrap(), glossary);
+
+        let matched = glossary_for(&glossary, "Welcome to the Garden of Eden", "ko");
+        assert_eq!(matched.len(), 1);
+        assert!(glossary_for(&glossary, "Welcome to the Garden of Eden", "ja").is_empty());
+        assert!(instructions(Some("English"), "Korean", &matched).contains("\"garden of eden\" → \"에덴의 정원\""));
+
+        let entries = [GlossaryEntry { term: "Adam".into(), translation: "아담".into(), target_language: None }];
+        assert_eq!(apply_glossary("Adam이 도와드릴게요", &entries), "아담이 도와드릴게요");
+    }
+
+    #[tokio::test]
+    async fn test_same_language_is_not_translated() {
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let service = TranslationService::new(db);
+        let result = service.translate("오늘 회의는 세 시에 시작합니다", "korean", None).await.unwrap();
+        assert!(!result.translated);
+        assert_eq!(result.source_language.as_deref(), Some("ko"));
+        assert_eq!(result.target_language, "ko");
+        assert!(service.translate("hello", "klingon", None).await.is_err());
+    }
+}
@@ -3,17 +3,35 @@
 //! Privacy-preserving web content fetching:
 //! - Fetch and parse HTML content
 //! - Extract main text content (remove ads, navigation, etc.)
-//! - Respect robots.txt and rate limits
+//! - Respect robots.txt and rate limits (via `network_policy`, v3.9.0)
+//! - Offline mode and configurable user agent (v3.9.0)
+//! - Readability extraction to Markdown with image alt-text (v3.9.0)
+//! - Chunk-and-summarize for pages too long for the context (v3.9.0)
 //! - User opt-in required
 //! - No tracking or cookies
 
 #![allow(dead_code)]  // Phase 9: Internet Access (opt-in feature)
 
 use anyhow::{anyhow, Result};
+use reqwest::header::USER_AGENT;
 use reqwest::Client;
 use scraper::{Html, Selector};
+use std::future::Future;
 use std::time::Duration;
 
+use super::chunker::{self, ChunkStrategy, ChunkingConfig, SourceKind};
+use super::network_policy;
+use super::readability::{self, ImageRef};
+
+/// Pages above this many (estimated) tokens are condensed for the agent (v3.9.0)
+pub const CONDENSE_THRESHOLD_TOKENS: usize = 3_000;
+/// Chunk size when condensing a long page
+const CONDENSE_CHUNK_TOKENS: usize = 800;
+/// Chunks summarized per page; the rest is reported as omitted
+const MAX_CONDENSED_CHUNKS: usize = 12;
+/// Words kept from a chunk when its summary fails
+const LEAD_WORDS: usize = 80;
+
 /// Fetched web content
 #[derive(Debug, Clone)]
 pub struct WebContent {
@@ -22,6 +40,26 @@ pub struct WebContent {
     pub text: String,
     pub summary: String,  // First ~500 chars
     pub word_count: usize,
+    pub markdown: String,  // v3.9.0: Main content as Markdown
+    pub images: Vec<ImageRef>,  // v3.9.0: Images in the main content, with alt text
+}
+
+/// Page content sized for the agent's context (v3.9.0)
+#[derive(Debug, Clone)]
+pub struct CondensedContent {
+    pub content: String,
+    /// Chunks the page was split into (0 when it fit as-is)
+    pub chunks: usize,
+    /// Chunks that fell back to their opening words
+    pub fallback_chunks: usize,
+    /// Chunks beyond `MAX_CONDENSED_CHUNKS`, left out
+    pub omitted_chunks: usize,
+}
+
+impl CondensedContent {
+    pub fn is_condensed(&self) -> bool {
+        self.chunks > 0
+    }
 }
 
 /// URL fetching settings
@@ -30,6 +68,7 @@ pub struct UrlFetchSettings {
     pub enabled: bool,
     pub max_content_length: usize,  // Maximum bytes to download
     pub timeout_seconds: u64,
+    /// Checked only while the network policy also respects robots.txt
     pub respect_robots_txt: bool,
 }
 
@@ -82,9 +121,18 @@ impl UrlFetchService {
             return Err(anyhow!("Only HTTP/HTTPS URLs are supported"));
         }
 
+        // v3.9.0: Offline mode, robots.txt and per-domain rate limits
+        let policy = network_policy::global();
+        policy.ensure_online()?;
+        if self.settings.respect_robots_txt && !policy.allowed_by_robots(&self.client, &parsed_url).await? {
+            return Err(anyhow!("Fetching {} is disallowed by the site's robots.txt", url));
+        }
+        let _permit = policy.acquire(&parsed_url).await?;
+
         // Fetch HTML
         let response = self.client
             .get(url)
+            .header(USER_AGENT, policy.user_agent())
             .send()
             .await?;
 
@@ -127,12 +175,23 @@ impl UrlFetchService {
 
         let word_count = text.split_whitespace().count();
 
+        // v3.9.0: Readability main content as Markdown, falling back to the plain text
+        let base_url = reqwest::Url::parse(url).ok();
+        let readable = readability::extract(&document, base_url.as_ref());
+        let markdown = if readable.markdown.is_empty() {
+            text.clone()
+        } else {
+            readable.markdown
+        };
+
         Ok(WebContent {
             url: url.to_string(),
             title,
             text,
             summary,
             word_count,
+            markdown,
+            images: readable.images,
         })
     }
 
@@ -245,11 +304,75 @@ impl UrlFetchService {
         context.push_str(&format!("URL: {}\n", content.url));
         context.push_str(&format!("Word Count: {}\n\n", content.word_count));
         context.push_str("Content:\n");
-        context.push_str(&content.text);
+        if content.markdown.is_empty() {
+            context.push_str(&content.text);
+        } else {
+            context.push_str(&content.markdown);
+        }
         context.push_str("\n\n=== End of Web Content ===\n");
         context
     }
 
+    /// Fit `markdown` into the agent's context (v3.9.0)
+    ///
+    /// Short pages are returned unchanged. Long pages are split into chunks and
+    /// each chunk is replaced by `summarize`'s output, or by its opening words
+    /// if summarizing fails.
+    pub async fn condense<F, Fut>(markdown: &str, summarize: F) -> CondensedContent
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<String, String>>,
+    {
+        if chunker::estimate_tokens(markdown) <= CONDENSE_THRESHOLD_TOKENS {
+            return CondensedContent {
+                content: markdown.to_string(),
+                chunks: 0,
+                fallback_chunks: 0,
+                omitted_chunks: 0,
+            };
+        }
+
+        let config = ChunkingConfig::new(ChunkStrategy::Recursive, CONDENSE_CHUNK_TOKENS, 0);
+        let chunks = chunker::chunk_text(markdown, SourceKind::Markdown, &config);
+        let total = chunks.len();
+        let kept = total.min(MAX_CONDENSED_CHUNKS);
+
+        let mut sections = Vec::with_capacity(kept);
+        let mut fallback_chunks = 0;
+        for (index, chunk) in chunks.into_iter().take(kept).enumerate() {
+            let summary = match summarize(chunk.text.clone()).await {
+                Ok(summary) if !summary.trim().is_empty() => summary.trim().to_string(),
+                Ok(_) => {
+                    fallback_chunks += 1;
+                    lead(&chunk.text)
+                }
+                Err(e) => {
+                    log::warn!("Chunk {} summary failed, using its opening: {}", index + 1, e);
+                    fallback_chunks += 1;
+                    lead(&chunk.text)
+                }
+            };
+            sections.push(format!("## Part {}/{}\n\n{}", index + 1, total, summary));
+        }
+
+        let mut content = format!(
+            "(Long page condensed: {} words summarized in {} parts)\n\n{}",
+            markdown.split_whitespace().count(),
+            total,
+            sections.join("\n\n")
+        );
+        if total > kept {
+            content.push_str(&format!("\n\n({} later parts omitted)", total - kept));
+        }
+
+        CondensedContent {
+            content,
+            chunks: total,
+            fallback_chunks,
+            omitted_chunks: total - kept,
+        }
+    }
+
     /// Update settings
     pub fn update_settings(&mut self, settings: UrlFetchSettings) {
         log::info!("Updating URL fetch settings");
@@ -262,6 +385,16 @@ impl UrlFetchService {
     }
 }
 
+/// Opening words of a chunk, used when it cannot be summarized
+fn lead(text: &str) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.len() > LEAD_WORDS {
+        format!("{} ...", words[..LEAD_WORDS].join(" "))
+    } else {
+        words.join(" ")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -293,6 +426,8 @@ mod tests {
             text: "This is example content.".to_string(),
             summary: "This is example...".to_string(),
             word_count: 4,
+            markdown: String::new(),
+            images: Vec::new(),
         };
 
         let formatted = UrlFetchService::format_for_context(&content);
@@ -361,5 +496,28 @@ mod tests {
         assert!(content.text.contains("This is the main content"));
         assert!(content.text.contains("Another paragraph"));
         assert!(content.word_count > 0);
+        assert!(content.markdown.contains("This is the main content."));
+    }
+
+    #[tokio::test]
+    async fn test_condense_long_page() {
+        let short = "A short page.";
+        let condensed = UrlFetchService::condense(short, |_| async { Ok("unused".to_string()) }).await;
+        assert!(!condensed.is_condensed());
+        assert_eq!(condensed.content, short);
+
+        let paragraph = "word ".repeat(500);
+        let long = [paragraph.trim(); 8].join("\n\n");
+        let condensed = UrlFetchService::condense(&long, |_| async { Ok("Summary of this part.".to_string()) }).await;
+        assert!(condensed.is_condensed());
+        assert!(condensed.chunks > 1);
+        assert_eq!(condensed.fallback_chunks, 0);
+        assert!(condensed.content.contains("## Part 1/"));
+        assert!(chunker::estimate_tokens(&condensed.content) < CONDENSE_THRESHOLD_TOKENS);
+
+        // Summaries failing still shrink the page to each chunk's opening
+        let fallback = UrlFetchService::condense(&long, |_| async { Err("model unavailable".to_string()) }).await;
+        assert_eq!(fallback.fallback_chunks, fallback.chunks);
+        assert!(chunker::estimate_tokens(&fallback.content) < CONDENSE_THRESHOLD_TOKENS);
     }
 }
@@ -0,0 +1,622 @@
+//! Vector Store Backends (v3.9.0)
+//!
+//! Decouples episodic memory from LanceDB so large corpora can live in a
+//! dedicated vector database.
+//!
+//! Features:
+//! - `VectorStore` trait implemented by LanceDB (`lancedb-support` builds),
+//!   SQLite brute force and an external Qdrant instance
+//! - Backend selection persisted per profile in `user_preferences`
+//! - Qdrant payloads are sealed like LanceDB ones when encryption at rest is on
+//!
+//! Switching backends starts from an empty store; `RagServiceV2::switch_vector_backend`
+//! can re-embed existing memories into it.
+
+use crate::database::Database;
+use crate::services::embedding::UnifiedEmbeddingService;
+use crate::services::encryption;
+use crate::services::index_tuning::{self, IndexTuning};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// user_preferences key holding the serialized backend configuration
+pub const VECTOR_BACKEND_CONFIG_KEY: &str = "vector_backend_config";
+
+/// Dimension of BGE-M3 embeddings
+pub const EMBEDDING_DIM: usize = 1024;
+
+/// Vector record for storage
+#[derive(Debug, Clone)]
+pub struct VectorRecord {
+    pub id: String,
+    pub text: String,
+    pub embedding: Vec<f32>,
+    pub metadata: String, // JSON metadata
+}
+
+/// Search result with similarity score
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub id: String,
+    pub text: String,
+    pub metadata: String,
+    pub score: f32, // Cosine similarity score (0.0 - 1.0)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorBackendKind {
+    /// Embedded LanceDB in the profile directory (default)
+    #[default]
+    LanceDb,
+    /// Embeddings in the app database, searched by brute force
+    Sqlite,
+    /// An external Qdrant server
+    Qdrant,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QdrantConfig {
+    /// e.g. "http://localhost:6333"
+    pub url: String,
+    pub api_key: Option<String>,
+    /// Collections are named `<prefix>_<table>`, so profiles can share a server
+    pub collection_prefix: String,
+}
+
+impl Default for QdrantConfig {
+    fn default() -> Self {
+        Self {
+            url: "http://localhost:6333".to_string(),
+            api_key: None,
+            collection_prefix: "eden".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VectorBackendConfig {
+    pub backend: VectorBackendKind,
+    pub qdrant: QdrantConfig,
+}
+
+impl VectorBackendConfig {
+    pub fn validate(&self) -> Result<()> {
+        match self.backend {
+            VectorBackendKind::LanceDb if !cfg!(feature = "lancedb-support") => {
+                Err(anyhow!("This build does not include LanceDB"))
+            }
+            VectorBackendKind::Qdrant => {
+                let url = reqwest::Url::parse(&self.qdrant.url)
+                    .map_err(|e| anyhow!("Invalid Qdrant URL '{}': {}", self.qdrant.url, e))?;
+                if !matches!(url.scheme(), "http" | "https") {
+                    return Err(anyhow!("Qdrant URL must be http(s)"));
+                }
+                let prefix = &self.qdrant.collection_prefix;
+                if prefix.is_empty() || !prefix.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+                    return Err(anyhow!("Collection prefix must be letters, digits, '_' or '-'"));
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Persisted configuration of the profile, or the default
+pub fn load_config(db: &Mutex<Database>) -> Result<VectorBackendConfig> {
+    let db_guard = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+    let json: Option<String> = db_guard
+        .conn()
+        .query_row(
+            "SELECT value FROM user_preferences WHERE key = ?1",
+            [VECTOR_BACKEND_CONFIG_KEY],
+            |row| row.get(0),
+        )
+        .ok();
+    match json {
+        Some(json) => Ok(serde_json::from_str(&json)?),
+        None => Ok(VectorBackendConfig::default()),
+    }
+}
+
+pub fn save_config(db: &Mutex<Database>, config: &VectorBackendConfig) -> Result<()> {
+    let db_guard = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+    db_guard.conn().execute(
+        "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+        rusqlite::params![
+            VECTOR_BACKEND_CONFIG_KEY,
+            serde_json::to_string(config)?,
+            chrono::Utc::now().timestamp_millis()
+        ],
+    )?;
+    Ok(())
+}
+
+/// Storage and nearest-neighbour search of embedding vectors
+#[async_trait]
+pub trait VectorStore: Send + Sync {
+    fn backend(&self) -> VectorBackendKind;
+
+    async fn insert(&self, records: Vec<VectorRecord>) -> Result<()>;
+
+    /// `top_k` most similar records, best first
+    async fn search(&self, query_embedding: &[f32], top_k: usize) -> Result<Vec<SearchResult>>;
+
+    async fn delete(&self, ids: &[String]) -> Result<()>;
+
+    async fn count(&self) -> Result<usize>;
+
+    /// Reclaim space after large deletions
+    async fn compact(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Build an ANN index where the backend doesn't maintain one itself
+    async fn create_index(&self, _tuning: &IndexTuning) -> Result<()> {
+        Ok(())
+    }
+
+    /// Whether searches go through an ANN index built by `create_index`
+    async fn has_index(&self) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Apply the query-time parameters of `tuning` to later searches
+    fn set_tuning(&self, _tuning: &IndexTuning) {}
+
+    /// Encrypt payloads stored before encryption at rest was enabled; returns rows rewritten
+    async fn seal_payloads(&self) -> Result<usize> {
+        Ok(0)
+    }
+}
+
+/// Open the configured backend for `table`
+pub async fn open(
+    config: &VectorBackendConfig,
+    db: &Arc<Mutex<Database>>,
+    lance_db_path: PathBuf,
+    table: &str,
+) -> Result<Arc<dyn VectorStore>> {
+    config.validate()?;
+    let store: Arc<dyn VectorStore> = match config.backend {
+        #[cfg(feature = "lancedb-support")]
+        VectorBackendKind::LanceDb => {
+            Arc::new(super::vector_store::VectorStoreService::new(lance_db_path, table).await?)
+        }
+        #[cfg(not(feature = "lancedb-support"))]
+        VectorBackendKind::LanceDb => {
+            let _ = lance_db_path;
+            return Err(anyhow!("This build does not include LanceDB"));
+        }
+        VectorBackendKind::Sqlite => Arc::new(SqliteVectorStore::new(Arc::clone(db), table)?),
+        VectorBackendKind::Qdrant => Arc::new(QdrantVectorStore::connect(&config.qdrant, table).await?),
+    };
+    store.set_tuning(&index_tuning::load(db)?);
+    log::info!("✓ {:?} vector store opened for '{}'", config.backend, table);
+    Ok(store)
+}
+
+// ============================================================================
+// SQLite (brute force)
+// ============================================================================
+
+/// Embeddings as JSON in the app database; fine up to a few thousand vectors
+///
+/// The database is encrypted as a whole by SQLCipher, so payloads are stored as-is.
+pub struct SqliteVectorStore {
+    db: Arc<Mutex<Database>>,
+    table_name: String,
+    batch_size: AtomicUsize,
+}
+
+impl SqliteVectorStore {
+    pub fn new(db: Arc<Mutex<Database>>, table_name: &str) -> Result<Self> {
+        {
+            let db_guard = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            db_guard.conn().execute(
+                "CREATE TABLE IF NOT EXISTS vector_records (
+                    table_name TEXT NOT NULL,
+                    id TEXT NOT NULL,
+                    text TEXT NOT NULL,
+                    embedding TEXT NOT NULL,
+                    metadata TEXT NOT NULL DEFAULT '',
+                    PRIMARY KEY (table_name, id)
+                )",
+                [],
+            )?;
+        }
+        Ok(Self {
+            db,
+            table_name: table_name.to_string(),
+            batch_size: AtomicUsize::new(IndexTuning::default().sqlite_batch_size),
+        })
+    }
+}
+
+#[async_trait]
+impl VectorStore for SqliteVectorStore {
+    fn backend(&self) -> VectorBackendKind {
+        VectorBackendKind::Sqlite
+    }
+
+    async fn insert(&self, records: Vec<VectorRecord>) -> Result<()> {
+        let db_guard = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let tx = db_guard.conn().unchecked_transaction()?;
+        for record in &records {
+            tx.execute(
+                "INSERT OR REPLACE INTO vector_records (table_name, id, text, embedding, metadata)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                rusqlite::params![
+                    self.table_name,
+                    record.id,
+                    record.text,
+                    serde_json::to_string(&record.embedding)?,
+                    record.metadata
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Scans in `sqlite_batch_size` pages, keeping only the best `top_k` between pages
+    async fn search(&self, query_embedding: &[f32], top_k: usize) -> Result<Vec<SearchResult>> {
+        let batch_size = self.batch_size.load(Ordering::Relaxed).max(1);
+        let db_guard = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut stmt = db_guard.conn().prepare(
+            "SELECT id, text, embedding, metadata FROM vector_records
+             WHERE table_name = ?1 ORDER BY id LIMIT ?2 OFFSET ?3",
+        )?;
+        let mut results: Vec<SearchResult> = Vec::new();
+        let mut offset = 0;
+        loop {
+            let rows = stmt
+                .query_map(rusqlite::params![self.table_name, batch_size as i64, offset as i64], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, String>(3)?))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            let fetched = rows.len();
+            results.extend(rows.into_iter().filter_map(|(id, text, embedding_json, metadata)| {
+                let embedding: Vec<f32> = serde_json::from_str(&embedding_json).ok()?;
+                let score = UnifiedEmbeddingService::cosine_similarity(query_embedding, &embedding);
+                Some(SearchResult { id, text, metadata, score: score.clamp(0.0, 1.0) })
+            }));
+            results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+            results.truncate(top_k);
+            if fetched < batch_size {
+                break;
+            }
+            offset += batch_size;
+        }
+        Ok(results)
+    }
+
+    async fn delete(&self, ids: &[String]) -> Result<()> {
+        let db_guard = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        for id in ids {
+            db_guard.conn().execute(
+                "DELETE FROM vector_records WHERE table_name = ?1 AND id = ?2",
+                rusqlite::params![self.table_name, id],
+            )?;
+        }
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<usize> {
+        let db_guard = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let count: i64 = db_guard.conn().query_row(
+            "SELECT COUNT(*) FROM vector_records WHERE table_name = ?1",
+            [&self.table_name],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    fn set_tuning(&self, tuning: &IndexTuning) {
+        self.batch_size.store(tuning.sqlite_batch_size, Ordering::Relaxed);
+    }
+}
+
+// ============================================================================
+// Qdrant (REST API)
+// ============================================================================
+
+/// Points kept in a Qdrant collection with cosine distance
+///
+/// Point ids must be UUIDs, which every episode id is.
+pub struct QdrantVectorStore {
+    client: Client,
+    base_url: String,
+    api_key: Option<String>,
+    collection: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct QdrantResponse<T> {
+    result: T,
+}
+
+#[derive(Debug, Deserialize)]
+struct QdrantPoint {
+    id: serde_json::Value,
+    #[serde(default)]
+    score: f32,
+    #[serde(default)]
+    payload: Option<QdrantPayload>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct QdrantPayload {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    metadata: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct QdrantScroll {
+    points: Vec<QdrantPoint>,
+    next_page_offset: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct QdrantCount {
+    count: usize,
+}
+
+impl QdrantPoint {
+    fn id(&self) -> String {
+        match &self.id {
+            serde_json::Value::String(id) => id.clone(),
+            other => other.to_string(),
+        }
+    }
+}
+
+/// Collection holding `table`'s vectors
+fn collection_name(config: &QdrantConfig, table: &str) -> String {
+    format!("{}_{}", config.collection_prefix, table)
+}
+
+impl QdrantVectorStore {
+    /// Connect and create the collection if it doesn't exist
+    pub async fn connect(config: &QdrantConfig, table: &str) -> Result<Self> {
+        let store = Self {
+            client: Client::builder().timeout(std::time::Duration::from_secs(30)).build()?,
+            base_url: config.url.trim_end_matches('/').to_string(),
+            api_key: config.api_key.clone().filter(|key| !key.is_empty()),
+            collection: collection_name(config, table),
+        };
+
+        let response = store.request(reqwest::Method::GET, "").send().await
+            .map_err(|e| anyhow!("Qdrant at {} is unreachable: {}", store.base_url, e))?;
+        match response.status() {
+            status if status.is_success() => {}
+            StatusCode::NOT_FOUND => {
+                log::info!("Creating Qdrant collection '{}'", store.collection);
+                store
+                    .call::<serde_json::Value>(
+                        store.request(reqwest::Method::PUT, "").json(&json!({
+                            "vectors": { "size": EMBEDDING_DIM, "distance": "Cosine" }
+                        })),
+                    )
+                    .await?;
+            }
+            status => {
+                let body = response.text().await.unwrap_or_default();
+                return Err(anyhow!("Qdrant error ({}): {}", status, body));
+            }
+        }
+        Ok(store)
+    }
+
+    /// Request to `path` under the collection
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let url = format!("{}/collections/{}{}", self.base_url, self.collection, path);
+        let builder = self.client.request(method, url);
+        match &self.api_key {
+            Some(key) => builder.header("api-key", key),
+            None => builder,
+        }
+    }
+
+    async fn call<T: serde::de::DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> Result<T> {
+        let response = request.send().await.map_err(|e| anyhow!("Qdrant request failed: {}", e))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Qdrant error ({}): {}", status, body));
+        }
+        let parsed: QdrantResponse<T> = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Qdrant response parsing failed: {}", e))?;
+        Ok(parsed.result)
+    }
+
+    async fn set_payload(&self, id: &str, text: &str, metadata: &str) -> Result<()> {
+        self.call::<serde_json::Value>(
+            self.request(reqwest::Method::POST, "/points/payload?wait=true").json(&json!({
+                "payload": { "text": text, "metadata": metadata },
+                "points": [id],
+            })),
+        )
+        .await
+        .map(|_| ())
+    }
+}
+
+#[async_trait]
+impl VectorStore for QdrantVectorStore {
+    fn backend(&self) -> VectorBackendKind {
+        VectorBackendKind::Qdrant
+    }
+
+    async fn insert(&self, records: Vec<VectorRecord>) -> Result<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        let points = records
+            .iter()
+            .map(|record| {
+                Ok(json!({
+                    "id": record.id,
+                    "vector": record.embedding,
+                    "payload": {
+                        "text": encryption::seal_field(&record.text)?,
+                        "metadata": encryption::seal_field(&record.metadata)?,
+                    },
+                }))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.call::<serde_json::Value>(
+            self.request(reqwest::Method::PUT, "/points?wait=true").json(&json!({ "points": points })),
+        )
+        .await?;
+        log::debug!("Upserted {} points into '{}'", records.len(), self.collection);
+        Ok(())
+    }
+
+    async fn search(&self, query_embedding: &[f32], top_k: usize) -> Result<Vec<SearchResult>> {
+        if query_embedding.len() != EMBEDDING_DIM {
+            return Err(anyhow!(
+                "Query embedding dimension {} does not match expected {}",
+                query_embedding.len(),
+                EMBEDDING_DIM
+            ));
+        }
+        let points: Vec<QdrantPoint> = self
+            .call(self.request(reqwest::Method::POST, "/points/search").json(&json!({
+                "vector": query_embedding,
+                "limit": top_k,
+                "with_payload": true,
+            })))
+            .await?;
+        points
+            .into_iter()
+            .map(|point| {
+                let payload = point.payload.as_ref();
+                Ok(SearchResult {
+                    id: point.id(),
+                    text: encryption::open_field(payload.map(|p| p.text.as_str()).unwrap_or_default())?,
+                    metadata: encryption::open_field(payload.map(|p| p.metadata.as_str()).unwrap_or_default())?,
+                    score: point.score.clamp(0.0, 1.0),
+                })
+            })
+            .collect()
+    }
+
+    async fn delete(&self, ids: &[String]) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.call::<serde_json::Value>(
+            self.request(reqwest::Method::POST, "/points/delete?wait=true").json(&json!({ "points": ids })),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<usize> {
+        let count: QdrantCount = self
+            .call(self.request(reqwest::Method::POST, "/points/count").json(&json!({ "exact": true })))
+            .await?;
+        Ok(count.count)
+    }
+
+    /// Qdrant builds its HNSW index itself
+    async fn create_index(&self, _tuning: &IndexTuning) -> Result<()> {
+        log::info!("Qdrant maintains the index of '{}' automatically", self.collection);
+        Ok(())
+    }
+
+    async fn seal_payloads(&self) -> Result<usize> {
+        let mut offset: Option<serde_json::Value> = None;
+        let mut sealed = 0;
+        loop {
+            let page: QdrantScroll = self
+                .call(self.request(reqwest::Method::POST, "/points/scroll").json(&json!({
+                    "limit": 256,
+                    "offset": offset,
+                    "with_payload": true,
+                    "with_vector": false,
+                })))
+                .await?;
+            for point in &page.points {
+                let payload = point.payload.as_ref();
+                let text = payload.map(|p| p.text.as_str()).unwrap_or_default();
+                if encryption::is_sealed(text) {
+                    continue;
+                }
+                let metadata = payload.map(|p| p.metadata.as_str()).unwrap_or_default();
+                self.set_payload(&point.id(), &encryption::seal_field(text)?, &encryption::seal_field(metadata)?)
+                    .await?;
+                sealed += 1;
+            }
+            match page.next_page_offset {
+                Some(next) if !next.is_null() => offset = Some(next),
+                _ => break,
+            }
+        }
+        if sealed > 0 {
+            log::info!("Encrypted {} payloads in Qdrant collection '{}'", sealed, self.collection);
+        }
+        Ok(sealed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, embedding: Vec<f32>) -> VectorRecord {
+        VectorRecord { id: id.to_string(), text: format!("text {}", id), embedding, metadata: "{}".to_string() }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_store() {
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let store = SqliteVectorStore::new(Arc::clone(&db), "episodic_memory").unwrap();
+        let other = SqliteVectorStore::new(db, "wiki_facts").unwrap();
+
+        store.insert(vec![record("a", vec![1.0, 0.0]), record("b", vec![0.6, 0.8])]).await.unwrap();
+        other.insert(vec![record("c", vec![1.0, 0.0])]).await.unwrap();
+        assert_eq!(store.count().await.unwrap(), 2);
+
+        store.set_tuning(&IndexTuning { sqlite_batch_size: 1, ..IndexTuning::default() });
+        let results = store.search(&[0.0, 1.0], 5).await.unwrap();
+        assert_eq!(results.iter().map(|r| r.id.as_str()).collect::<Vec<_>>(), vec!["b", "a"]);
+        assert!((results[0].score - 0.8).abs() < 1e-5);
+        assert_eq!(results[0].text, "text b");
+
+        store.insert(vec![record("a", vec![0.0, 1.0])]).await.unwrap();
+        store.delete(&["b".to_string()]).await.unwrap();
+        let results = store.search(&[0.0, 1.0], 5).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert!((results[0].score - 1.0).abs() < 1e-5);
+        assert_eq!(other.count().await.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_config_validation() {
+        let config: VectorBackendConfig = serde_json::from_str(r#"{"backend":"qdrant"}"#).unwrap();
+        assert_eq!(config.qdrant, QdrantConfig::default());
+        assert!(config.validate().is_ok());
+        assert_eq!(collection_name(&config.qdrant, "episodic_memory"), "eden_episodic_memory");
+
+        let mut invalid = config.clone();
+        invalid.qdrant.url = "localhost:6333".to_string();
+        assert!(invalid.validate().is_err());
+        invalid.qdrant = QdrantConfig { collection_prefix: "my collection".to_string(), ..QdrantConfig::default() };
+        assert!(invalid.validate().is_err());
+    }
+}
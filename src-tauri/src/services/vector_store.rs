@@ -11,41 +11,29 @@
 
 #![cfg(feature = "lancedb-support")]
 
+use crate::services::encryption;
+use crate::services::index_tuning::IndexTuning;
+use crate::services::vector_backend::{VectorBackendKind, VectorStore};
 use anyhow::{anyhow, Result};
+use async_trait::async_trait;
 use arrow_array::{Array, Float32Array, RecordBatch, RecordBatchIterator, StringArray};
 use arrow_schema::{DataType, Field, Schema, SchemaRef};
 use futures::StreamExt;
 use lancedb::connection::Connection;
+use lancedb::index::vector::IvfPqIndexBuilder;
+use lancedb::index::Index;
 use lancedb::query::{ExecutableQuery, QueryBase};
 use lancedb::table::OptimizeAction;
 use std::path::PathBuf;
-use std::sync::Arc;
-
-/// Dimension of BGE-M3 embeddings
-const EMBEDDING_DIM: usize = 1024;
-
-/// Vector record for storage
-#[derive(Debug, Clone)]
-pub struct VectorRecord {
-    pub id: String,
-    pub text: String,
-    pub embedding: Vec<f32>,
-    pub metadata: String, // JSON metadata
-}
+use std::sync::{Arc, RwLock};
 
-/// Search result with similarity score
-#[derive(Debug, Clone)]
-pub struct SearchResult {
-    pub id: String,
-    pub text: String,
-    pub metadata: String,
-    pub score: f32, // Cosine similarity score (0.0 - 1.0)
-}
+pub use super::vector_backend::{SearchResult, VectorRecord, EMBEDDING_DIM};
 
 /// LanceDB Vector Store Service
 pub struct VectorStoreService {
     connection: Arc<Connection>,
     table_name: String,
+    tuning: RwLock<IndexTuning>,  // v3.9.0: Query-time probes / refine factor
 }
 
 impl VectorStoreService {
@@ -71,6 +59,7 @@ impl VectorStoreService {
         let service = Self {
             connection,
             table_name: table_name.to_string(),
+            tuning: RwLock::new(IndexTuning::default()),
         };
 
         // Initialize table if not exists
@@ -142,12 +131,19 @@ impl VectorStoreService {
 
         // Prepare data for insertion
         let ids: Vec<String> = records.iter().map(|r| r.id.clone()).collect();
-        let texts: Vec<String> = records.iter().map(|r| r.text.clone()).collect();
+        // Payloads are sealed when encryption at rest is on (v3.9.0)
+        let texts: Vec<String> = records
+            .iter()
+            .map(|r| encryption::seal_field(&r.text))
+            .collect::<Result<_>>()?;
         let embeddings: Vec<f32> = records
             .iter()
             .flat_map(|r| r.embedding.clone())
             .collect();
-        let metadatas: Vec<String> = records.iter().map(|r| r.metadata.clone()).collect();
+        let metadatas: Vec<String> = records
+            .iter()
+            .map(|r| encryption::seal_field(&r.metadata))
+            .collect::<Result<_>>()?;
 
         // Create Arrow arrays
         let id_array = Arc::new(StringArray::from(ids)) as Arc<dyn arrow_array::Array>;
@@ -236,12 +232,20 @@ impl VectorStoreService {
             .await
             .map_err(|e| anyhow!("Failed to open table: {:?}", e))?;
 
-        // Perform vector search
-        let query = table
+        // Perform vector search (probes / refine only matter once an index exists)
+        let (nprobes, refine_factor) = {
+            let tuning = self.tuning.read().map_err(|e| anyhow!("Tuning lock error: {}", e))?;
+            (tuning.nprobes, tuning.refine_factor)
+        };
+        let mut query = table
             .query()
             .nearest_to(query_embedding)
             .map_err(|e| anyhow!("Failed to create query: {:?}", e))?
+            .nprobes(nprobes)
             .limit(top_k);
+        if let Some(refine_factor) = refine_factor {
+            query = query.refine_factor(refine_factor);
+        }
 
         let results = query
             .execute()
@@ -287,11 +291,11 @@ impl VectorStoreService {
 
             for i in 0..batch.num_rows() {
                 let id = ids.value(i).to_string();
-                let text = texts.value(i).to_string();
+                let text = encryption::open_field(texts.value(i))?;
                 let metadata = if metadata_col.is_null(i) {
                     String::new()
                 } else {
-                    metadatas.value(i).to_string()
+                    encryption::open_field(metadatas.value(i))?
                 };
 
                 // LanceDB returns L2 distance, convert to cosine similarity
@@ -314,31 +318,58 @@ impl VectorStoreService {
 
     /// Create IVF-PQ index for faster search on large datasets
     ///
-    /// This creates an optimized index for Approximate Nearest Neighbor (ANN) search.
-    /// Only call this when you have a large number of vectors (>10,000).
-    ///
-    /// # Arguments
-    /// * `_num_partitions` - Number of IVF partitions (not used in LanceDB 0.22, auto-configured)
-    /// * `_num_sub_vectors` - Number of PQ sub-vectors (not used in LanceDB 0.22, auto-configured)
-    pub async fn create_index(&self, _num_partitions: usize, _num_sub_vectors: usize) -> Result<()> {
-        log::info!("Creating vector index (LanceDB 0.22 auto-configures parameters)");
-
-        let _table = self
+    /// This creates an optimized index for Approximate Nearest Neighbor (ANN) search,
+    /// replacing any previous one. Only call this when you have a large number of
+    /// vectors (>10,000); partitions default to sqrt(vector count).
+    pub async fn create_index(&self, tuning: &IndexTuning) -> Result<()> {
+        let table = self
             .connection
             .open_table(&self.table_name)
             .execute()
             .await
             .map_err(|e| anyhow!("Failed to open table: {:?}", e))?;
 
-        // LanceDB 0.22 API: Index::IvfPq is a tuple variant that needs to be constructed
-        // For now, we'll skip index creation as it requires complex builder configuration
-        // The vector search will still work, just without the IVF-PQ optimization
-        log::warn!("Index creation skipped - LanceDB 0.22 requires manual index configuration");
-        log::info!("Vector search will use brute-force (still fast for <100k vectors)");
+        let count = table
+            .count_rows(None)
+            .await
+            .map_err(|e| anyhow!("Failed to count rows: {:?}", e))?;
+        let num_partitions = tuning.partitions_for(count);
+        log::info!(
+            "Creating IVF-PQ index on '{}' ({} partitions, {} sub-vectors, {} bits)",
+            self.table_name, num_partitions, tuning.num_sub_vectors, tuning.num_bits
+        );
+
+        let builder = IvfPqIndexBuilder::default()
+            .num_partitions(num_partitions)
+            .num_sub_vectors(tuning.num_sub_vectors)
+            .num_bits(tuning.num_bits);
+        table
+            .create_index(&["embedding"], Index::IvfPq(builder))
+            .replace(true)
+            .execute()
+            .await
+            .map_err(|e| anyhow!("Failed to create index: {:?}", e))?;
 
+        log::info!("Vector index created for {} vectors", count);
         Ok(())
     }
 
+    /// Whether the embedding column has a vector index
+    pub async fn has_index(&self) -> Result<bool> {
+        let table = self
+            .connection
+            .open_table(&self.table_name)
+            .execute()
+            .await
+            .map_err(|e| anyhow!("Failed to open table: {:?}", e))?;
+
+        let indices = table
+            .list_indices()
+            .await
+            .map_err(|e| anyhow!("Failed to list indices: {:?}", e))?;
+        Ok(indices.iter().any(|index| index.columns.iter().any(|column| column == "embedding")))
+    }
+
     /// Delete vectors by IDs
     ///
     /// # Arguments
@@ -370,6 +401,72 @@ impl VectorStoreService {
         Ok(())
     }
 
+    /// Encrypt payloads stored before encryption at rest was enabled (v3.9.0)
+    ///
+    /// Returns the number of rows rewritten. Embeddings are left untouched.
+    pub async fn seal_payloads(&self) -> Result<usize> {
+        let table = self
+            .connection
+            .open_table(&self.table_name)
+            .execute()
+            .await
+            .map_err(|e| anyhow!("Failed to open table: {:?}", e))?;
+
+        let mut results = table
+            .query()
+            .execute()
+            .await
+            .map_err(|e| anyhow!("Failed to scan table: {:?}", e))?;
+
+        // Collect plaintext rows first, then rewrite them
+        let mut plaintext_rows = Vec::new();
+        while let Some(batch_result) = results.next().await {
+            let batch = batch_result.map_err(|e| anyhow!("Failed to read batch: {:?}", e))?;
+
+            let ids = batch
+                .column_by_name("id")
+                .and_then(|c| c.as_any().downcast_ref::<StringArray>())
+                .ok_or_else(|| anyhow!("Missing 'id' column"))?;
+            let texts = batch
+                .column_by_name("text")
+                .and_then(|c| c.as_any().downcast_ref::<StringArray>())
+                .ok_or_else(|| anyhow!("Missing 'text' column"))?;
+            let metadatas = batch
+                .column_by_name("metadata")
+                .and_then(|c| c.as_any().downcast_ref::<StringArray>())
+                .ok_or_else(|| anyhow!("Missing 'metadata' column"))?;
+
+            for i in 0..batch.num_rows() {
+                if encryption::is_sealed(texts.value(i)) {
+                    continue;
+                }
+                let metadata = if metadatas.is_null(i) { "" } else { metadatas.value(i) };
+                plaintext_rows.push((
+                    ids.value(i).to_string(),
+                    encryption::seal_field(texts.value(i))?,
+                    encryption::seal_field(metadata)?,
+                ));
+            }
+        }
+
+        // Sealed values are base64 behind a fixed prefix, so they never contain quotes
+        for (id, text, metadata) in &plaintext_rows {
+            table
+                .update()
+                .only_if(format!("id = '{}'", id.replace('\'', "''")))
+                .column("text", format!("'{}'", text))
+                .column("metadata", format!("'{}'", metadata))
+                .execute()
+                .await
+                .map_err(|e| anyhow!("Failed to encrypt record {}: {:?}", id, e))?;
+        }
+
+        if !plaintext_rows.is_empty() {
+            log::info!("Encrypted {} payloads in table '{}'", plaintext_rows.len(), self.table_name);
+        }
+        Ok(plaintext_rows.len())
+    }
+
     /// Count total vectors in the store
     pub async fn count(&self) -> Result<usize> {
         let table = self
@@ -411,6 +508,52 @@ impl VectorStoreService {
     }
 }
 
+#[async_trait]
+impl VectorStore for VectorStoreService {
+    fn backend(&self) -> VectorBackendKind {
+        VectorBackendKind::LanceDb
+    }
+
+    async fn insert(&self, records: Vec<VectorRecord>) -> Result<()> {
+        VectorStoreService::insert(self, records).await
+    }
+
+    async fn search(&self, query_embedding: &[f32], top_k: usize) -> Result<Vec<SearchResult>> {
+        VectorStoreService::search(self, query_embedding, top_k).await
+    }
+
+    async fn delete(&self, ids: &[String]) -> Result<()> {
+        VectorStoreService::delete(self, ids).await
+    }
+
+    async fn count(&self) -> Result<usize> {
+        VectorStoreService::count(self).await
+    }
+
+    async fn compact(&self) -> Result<()> {
+        VectorStoreService::compact(self).await
+    }
+
+    async fn create_index(&self, tuning: &IndexTuning) -> Result<()> {
+        VectorStoreService::create_index(self, tuning).await
+    }
+
+    async fn has_index(&self) -> Result<bool> {
+        VectorStoreService::has_index(self).await
+    }
+
+    fn set_tuning(&self, tuning: &IndexTuning) {
+        match self.tuning.write() {
+            Ok(mut current) => *current = tuning.clone(),
+            Err(e) => log::warn!("Tuning lock error: {}", e),
+        }
+    }
+
+    async fn seal_payloads(&self) -> Result<usize> {
+        VectorStoreService::seal_payloads(self).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,324 @@
+//! Vision Backend Abstraction (v3.9.0)
+//!
+//! Decouples image understanding from a single hardcoded model so LLaVA
+//! variants, Qwen2-VL and Moondream can all be served through Ollama.
+//!
+//! Features:
+//! - `VisionBackend` trait implemented by the Ollama backend
+//! - Model family detection (LLaVA / Qwen2-VL / Moondream) with per-family options
+//! - Per-task model selection: fast (OCR-ish, UI reading) vs detailed description
+//! - Process-wide model configuration shared by every LlavaService instance
+
+#![allow(dead_code)]  // Phase 5: Vision backends (some helpers used by future UI)
+
+use crate::database::Database;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+
+const OLLAMA_API_URL: &str = "http://localhost:11434/api/generate";
+
+/// user_preferences key holding the serialized vision model configuration
+pub const VISION_CONFIG_KEY: &str = "vision_model_config";
+
+/// Default model used for every task (previous hardcoded behaviour)
+pub const DEFAULT_VISION_MODEL: &str = "llava:7b";
+
+/// Kind of vision work being requested
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VisionTask {
+    /// Quick reads: UI text, element locating, periodic screen checks
+    Fast,
+    /// Thorough descriptions: visual analysis, deep screen context
+    Detailed,
+}
+
+/// Vision model family, used to tune request options
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VisionModelFamily {
+    Llava,
+    Qwen2Vl,
+    Moondream,
+}
+
+impl VisionModelFamily {
+    /// Detect the family from an Ollama model name
+    pub fn from_model(model: &str) -> Option<Self> {
+        let name = model.to_lowercase();
+        if name.starts_with("llava") || name.starts_with("bakllava") {
+            Some(Self::Llava)
+        } else if name.starts_with("qwen2-vl") || name.starts_with("qwen2.5vl") || name.starts_with("qwen2.5-vl") {
+            Some(Self::Qwen2Vl)
+        } else if name.starts_with("moondream") {
+            Some(Self::Moondream)
+        } else {
+            None
+        }
+    }
+
+    fn options(&self, task: VisionTask) -> VisionOptions {
+        match (self, task) {
+            // Moondream is small and rambles with long outputs
+            (Self::Moondream, _) => VisionOptions { temperature: 0.1, top_p: 0.9, num_predict: Some(256) },
+            // Qwen2-VL reads text well; keep it deterministic for OCR-like tasks
+            (Self::Qwen2Vl, VisionTask::Fast) => VisionOptions { temperature: 0.0, top_p: 0.8, num_predict: Some(512) },
+            (Self::Qwen2Vl, VisionTask::Detailed) => VisionOptions { temperature: 0.2, top_p: 0.9, num_predict: None },
+            (Self::Llava, VisionTask::Fast) => VisionOptions { temperature: 0.2, top_p: 0.9, num_predict: Some(384) },
+            (Self::Llava, VisionTask::Detailed) => VisionOptions { temperature: 0.3, top_p: 0.9, num_predict: None },
+        }
+    }
+}
+
+/// Per-task model selection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VisionModelConfig {
+    pub fast_model: String,
+    pub detailed_model: String,
+}
+
+impl Default for VisionModelConfig {
+    fn default() -> Self {
+        Self {
+            fast_model: DEFAULT_VISION_MODEL.to_string(),
+            detailed_model: DEFAULT_VISION_MODEL.to_string(),
+        }
+    }
+}
+
+impl VisionModelConfig {
+    pub fn model_for(&self, task: VisionTask) -> &str {
+        match task {
+            VisionTask::Fast => &self.fast_model,
+            VisionTask::Detailed => &self.detailed_model,
+        }
+    }
+
+    /// Reject models that are not a supported vision family
+    pub fn validate(&self) -> Result<()> {
+        for model in [&self.fast_model, &self.detailed_model] {
+            if VisionModelFamily::from_model(model).is_none() {
+                return Err(anyhow!(
+                    "Unsupported vision model '{}' (expected llava*, qwen2-vl / qwen2.5vl, or moondream*)",
+                    model
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Request options sent to the model
+#[derive(Debug, Clone, Serialize)]
+pub struct VisionOptions {
+    pub temperature: f32,
+    pub top_p: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_predict: Option<i32>,
+}
+
+/// A vision model that can describe an image
+#[async_trait]
+pub trait VisionBackend: Send + Sync {
+    /// Model identifier (e.g. "llava:7b")
+    fn model(&self) -> &str;
+
+    fn family(&self) -> VisionModelFamily;
+
+    /// Describe a base64 (PNG/JPEG, no data: prefix) image following the prompt
+    async fn describe(&self, image_base64: String, prompt: &str, task: VisionTask) -> Result<String>;
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaVisionRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+    images: Vec<String>,
+    stream: bool,
+    options: VisionOptions,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaVisionResponse {
+    response: String,
+    #[serde(default)]
+    prompt_eval_count: Option<u32>,  // v3.9.0: Token usage for call tracing
+    #[serde(default)]
+    eval_count: Option<u32>,
+}
+
+/// Vision backend served by the local Ollama instance
+pub struct OllamaVisionBackend {
+    client: Client,
+    model: String,
+    family: VisionModelFamily,
+}
+
+impl OllamaVisionBackend {
+    pub fn new(model: &str) -> Result<Self> {
+        let family = VisionModelFamily::from_model(model)
+            .ok_or_else(|| anyhow!("Unsupported vision model: {}", model))?;
+
+        Ok(Self {
+            client: Client::new(),
+            model: model.to_string(),
+            family,
+        })
+    }
+}
+
+#[async_trait]
+impl VisionBackend for OllamaVisionBackend {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn family(&self) -> VisionModelFamily {
+        self.family
+    }
+
+    async fn describe(&self, image_base64: String, prompt: &str, task: VisionTask) -> Result<String> {
+        // Wait for the supervisor if Ollama is restarting (v3.9.0)
+        super::ollama_supervisor::wait_for_ollama()
+            .await
+            .map_err(|e| anyhow!(e))?;
+
+        let request = OllamaVisionRequest {
+            model: &self.model,
+            prompt,
+            images: vec![image_base64],
+            stream: false,
+            options: self.family.options(task),
+        };
+
+        let operation = match task {
+            VisionTask::Fast => "describe_fast",
+            VisionTask::Detailed => "describe_detailed",
+        };
+        let call_span = super::structured_logging::llm_call_span("vision", &self.model, operation);  // v3.9.0
+        let response = self.client
+            .post(OLLAMA_API_URL)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| anyhow!("Ollama connection failed: {}", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Vision analysis failed ({}): {} - {}", self.model, status, error_text));
+        }
+
+        let parsed: OllamaVisionResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Response parsing failed: {}", e))?;
+
+        if let Some(tokens) = parsed.prompt_eval_count {
+            call_span.record("prompt_tokens", tokens);
+        }
+        if let Some(tokens) = parsed.eval_count {
+            call_span.record("completion_tokens", tokens);
+        }
+        call_span.record("success", true);
+
+        Ok(parsed.response.trim().to_string())
+    }
+}
+
+fn global_config() -> &'static RwLock<VisionModelConfig> {
+    static CONFIG: OnceLock<RwLock<VisionModelConfig>> = OnceLock::new();
+    CONFIG.get_or_init(|| RwLock::new(VisionModelConfig::default()))
+}
+
+/// Current process-wide vision model configuration
+pub fn current_config() -> VisionModelConfig {
+    global_config()
+        .read()
+        .map(|c| c.clone())
+        .unwrap_or_default()
+}
+
+/// Replace the process-wide vision model configuration
+pub fn set_config(config: VisionModelConfig) -> Result<()> {
+    config.validate()?;
+    let mut guard = global_config()
+        .write()
+        .map_err(|e| anyhow!("Vision config lock error: {}", e))?;
+    log::info!(
+        "Vision models: fast={}, detailed={}",
+        config.fast_model,
+        config.detailed_model
+    );
+    *guard = config;
+    Ok(())
+}
+
+/// Load the persisted configuration into the process-wide config (called at startup)
+pub fn load_persisted_config(db: &Arc<Mutex<Database>>) -> Result<()> {
+    let json: Option<String> = {
+        let db_guard = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db_guard
+            .conn()
+            .query_row(
+                "SELECT value FROM user_preferences WHERE key = ?1",
+                [VISION_CONFIG_KEY],
+                |row| row.get(0),
+            )
+            .ok()
+    };
+
+    match json {
+        Some(json) => set_config(serde_json::from_str(&json)?),
+        None => Ok(()),
+    }
+}
+
+/// Backend selected for a task under the current configuration
+pub fn backend_for_task(task: VisionTask) -> Result<Arc<dyn VisionBackend>> {
+    let config = current_config();
+    let backend = OllamaVisionBackend::new(config.model_for(task))?;
+    Ok(Arc::new(backend))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_family_detection() {
+        assert_eq!(VisionModelFamily::from_model("llava:7b"), Some(VisionModelFamily::Llava));
+        assert_eq!(VisionModelFamily::from_model("llava-llama3:8b"), Some(VisionModelFamily::Llava));
+        assert_eq!(VisionModelFamily::from_model("Qwen2.5VL:7b"), Some(VisionModelFamily::Qwen2Vl));
+        assert_eq!(VisionModelFamily::from_model("moondream:1.8b"), Some(VisionModelFamily::Moondream));
+        assert_eq!(VisionModelFamily::from_model("qwen2.5:7b"), None);
+    }
+
+    #[test]
+    fn test_config_model_for_task() {
+        let config = VisionModelConfig {
+            fast_model: "moondream".to_string(),
+            detailed_model: "llava:13b".to_string(),
+        };
+        assert_eq!(config.model_for(VisionTask::Fast), "moondream");
+        assert_eq!(config.model_for(VisionTask::Detailed), "llava:13b");
+        assert!(config.validate().is_ok());
+
+        let invalid = VisionModelConfig {
+            fast_model: "qwen2.5:7b".to_string(),
+            ..VisionModelConfig::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_family_options() {
+        let fast = VisionModelFamily::Qwen2Vl.options(VisionTask::Fast);
+        assert_eq!(fast.temperature, 0.0);
+        assert!(VisionModelFamily::Llava.options(VisionTask::Detailed).num_predict.is_none());
+    }
+}
@@ -19,6 +19,7 @@
 
 use crate::database::Database;
 use crate::services::llava::LlavaService;
+use crate::services::vision_backend::VisionTask;
 use crate::services::screen::ScreenCaptureService;
 use anyhow::{Context, Result};
 use base64::Engine;
@@ -285,7 +286,7 @@ impl VisualAnalyzerService {
             .context("LLaVA not loaded")?;
 
         let response = llava
-            .analyze_image(base64_image.to_string(), Some(prompt))
+            .analyze_image_for_task(base64_image.to_string(), Some(prompt), VisionTask::Detailed)
             .await
             .context("Failed to analyze image with LLaVA")?;
 
@@ -3,16 +3,34 @@
 //! Privacy-first web search integration:
 //! - DuckDuckGo API (no tracking)
 //! - SearX instances (privacy-preserving meta-search)
+//! - Brave and Tavily APIs behind the `SearchProvider` trait (v3.9.0)
+//! - Automatic fallback across engines on failure or rate limiting (v3.9.0)
+//! - Result deduplication by canonical URL (v3.9.0)
+//! - Persisted search history, indexed into RAG (v3.9.0)
 //! - User opt-in required
-//! - Rate limiting to prevent abuse
+//! - Rate limiting to prevent abuse (per-domain, via `network_policy`, v3.9.0)
+//! - Offline mode and configurable user agent (v3.9.0)
 //! - No tracking or analytics
 
 #![allow(dead_code)]  // Phase 9: Internet Access (opt-in feature)
 
 use anyhow::{anyhow, Result};
-use reqwest::Client;
+use reqwest::{Client, Url};
 use serde::{Deserialize, Serialize};
-use std::time::{Duration, SystemTime};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::network_policy;
+use super::search_history::SearchHistoryService;
+use super::search_providers::{self, ProviderError};
+use super::secrets::SecretsService;
+
+/// How long a rate-limited engine is skipped when it gives no Retry-After
+const RATE_LIMIT_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Query parameters that only track the click
+const TRACKING_PARAMS: &[&str] = &["fbclid", "gclid", "msclkid", "ref", "ref_src", "igshid"];
 
 /// Search result from web search
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,7 +38,7 @@ pub struct SearchResult {
     pub title: String,
     pub url: String,
     pub snippet: String,
-    pub source: String,  // "duckduckgo" or "searx"
+    pub source: String,  // "duckduckgo", "searx", "brave" or "tavily"
 }
 
 /// Web search settings
@@ -30,6 +48,13 @@ pub struct WebSearchSettings {
     pub default_engine: SearchEngine,
     pub max_results: usize,
     pub searx_instance: String,  // Custom SearX instance URL
+    /// Tried in order after `default_engine` fails, is rate limited or returns too few results (v3.9.0)
+    #[serde(default = "default_fallback_engines")]
+    pub fallback_engines: Vec<SearchEngine>,
+}
+
+fn default_fallback_engines() -> Vec<SearchEngine> {
+    vec![SearchEngine::SearX, SearchEngine::Brave, SearchEngine::Tavily]
 }
 
 impl Default for WebSearchSettings {
@@ -39,51 +64,27 @@ impl Default for WebSearchSettings {
             default_engine: SearchEngine::DuckDuckGo,
             max_results: 5,
             searx_instance: "https://searx.be".to_string(),
+            fallback_engines: default_fallback_engines(),
         }
     }
 }
 
 /// Available search engines
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum SearchEngine {
     DuckDuckGo,
-    SearX,
-}
-
-/// DuckDuckGo API response
-#[derive(Debug, Deserialize)]
-struct DuckDuckGoResponse {
-    #[serde(rename = "RelatedTopics")]
-    related_topics: Vec<DuckDuckGoTopic>,
-}
-
-#[derive(Debug, Deserialize)]
-struct DuckDuckGoTopic {
-    #[serde(rename = "Text")]
-    text: Option<String>,
-    #[serde(rename = "FirstURL")]
-    first_url: Option<String>,
-}
-
-/// SearX API response
-#[derive(Debug, Deserialize)]
-struct SearXResponse {
-    results: Vec<SearXResult>,
-}
-
-#[derive(Debug, Deserialize)]
-struct SearXResult {
-    title: String,
-    url: String,
-    content: String,
+    SearX,  // Also SearxNG
+    Brave,  // v3.9.0: Needs an API key in the secrets vault
+    Tavily,  // v3.9.0: Needs an API key in the secrets vault
 }
 
 /// Web search service
 pub struct WebSearchService {
     client: Client,
     settings: WebSearchSettings,
-    last_search_time: Option<SystemTime>,
-    rate_limit_seconds: u64,
+    secrets: Option<Arc<SecretsService>>,  // v3.9.0: API keys for SearX, Brave and Tavily
+    history: Option<Arc<SearchHistoryService>>,  // v3.9.0: Persisted search history
+    cooldowns: HashMap<SearchEngine, Instant>,  // v3.9.0: Rate-limited engines skipped until then
 }
 
 impl WebSearchService {
@@ -97,125 +98,107 @@ impl WebSearchService {
         Ok(Self {
             client,
             settings,
-            last_search_time: None,
-            rate_limit_seconds: 2, // Minimum 2 seconds between searches
+            secrets: None,
+            history: None,
+            cooldowns: HashMap::new(),
         })
     }
 
+    /// Read search API keys from the secrets vault (v3.9.0)
+    pub fn with_secrets(mut self, secrets: Arc<SecretsService>) -> Self {
+        self.secrets = Some(secrets);
+        self
+    }
+
+    /// Record every search in `history` (v3.9.0)
+    pub fn with_history(mut self, history: Arc<SearchHistoryService>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
     /// Check if internet access is enabled
     pub fn is_enabled(&self) -> bool {
         self.settings.enabled
     }
 
     /// Perform web search
+    ///
+    /// Engines are tried in order (default first, then the fallbacks) until
+    /// `max_results` distinct results are collected. Failing, unconfigured and
+    /// rate-limited engines are skipped; an error is returned only if none answered.
     pub async fn search(&mut self, query: &str) -> Result<Vec<SearchResult>> {
         if !self.settings.enabled {
             return Err(anyhow!("Web search is disabled. Enable it in settings."));
         }
 
-        // Rate limiting
-        self.check_rate_limit()?;
+        // v3.9.0: Offline mode; per-domain rate limits apply per request
+        network_policy::global().ensure_online()?;
 
         log::info!("Performing web search: {} (engine: {:?})", query, self.settings.default_engine);
 
-        let results = match self.settings.default_engine {
-            SearchEngine::DuckDuckGo => self.search_duckduckgo(query).await?,
-            SearchEngine::SearX => self.search_searx(query).await?,
-        };
-
-        self.last_search_time = Some(SystemTime::now());
-
-        log::info!("Found {} search results", results.len());
-        Ok(results)
-    }
-
-    /// Search using DuckDuckGo Instant Answer API
-    async fn search_duckduckgo(&self, query: &str) -> Result<Vec<SearchResult>> {
-        let url = format!(
-            "https://api.duckduckgo.com/?q={}&format=json&no_html=1&skip_disambig=1",
-            urlencoding::encode(query)
-        );
+        let limit = self.settings.max_results;
+        let mut results = Vec::new();
+        let mut seen = HashSet::new();
+        let mut errors = Vec::new();
 
-        let response = self.client
-            .get(&url)
-            .send()
-            .await?;
-
-        let ddg_response: DuckDuckGoResponse = response.json().await?;
-
-        let results = ddg_response
-            .related_topics
-            .into_iter()
-            .filter_map(|topic| {
-                if let (Some(text), Some(url)) = (topic.text, topic.first_url) {
-                    // Extract title from text (first sentence usually)
-                    let parts: Vec<&str> = text.splitn(2, " - ").collect();
-                    let title = parts.get(0).unwrap_or(&text.as_str()).to_string();
-                    let snippet = parts.get(1).unwrap_or(&text.as_str()).to_string();
-
-                    Some(SearchResult {
-                        title,
-                        url,
-                        snippet,
-                        source: "duckduckgo".to_string(),
-                    })
-                } else {
-                    None
+        for engine in self.engine_order() {
+            if results.len() >= limit {
+                break;
+            }
+            if let Some(until) = self.cooldowns.get(&engine) {
+                if Instant::now() < *until {
+                    log::debug!("Skipping {:?}: rate limited", engine);
+                    continue;
                 }
-            })
-            .take(self.settings.max_results)
-            .collect();
+                self.cooldowns.remove(&engine);
+            }
 
-        Ok(results)
-    }
+            let provider = search_providers::provider_for(engine, &self.settings.searx_instance, self.secrets.clone());
+            match provider.search(&self.client, query, limit).await {
+                Ok(found) => {
+                    let before = results.len();
+                    merge_results(&mut results, &mut seen, found, limit);
+                    log::info!("{:?} added {} results", engine, results.len() - before);
+                }
+                Err(ProviderError::NotConfigured(reason)) => {
+                    log::debug!("Skipping {:?}: {}", engine, reason);
+                }
+                Err(ProviderError::RateLimited { retry_after }) => {
+                    let cooldown = retry_after.unwrap_or(RATE_LIMIT_COOLDOWN);
+                    log::warn!("{:?} rate limited, skipping it for {}s", engine, cooldown.as_secs());
+                    self.cooldowns.insert(engine, Instant::now() + cooldown);
+                    errors.push(format!("{:?}: rate limited", engine));
+                }
+                Err(e) => {
+                    log::warn!("{:?} search failed, trying the next engine: {}", engine, e);
+                    errors.push(format!("{:?}: {}", engine, e));
+                }
+            }
+        }
 
-    /// Search using SearX meta-search engine
-    async fn search_searx(&self, query: &str) -> Result<Vec<SearchResult>> {
-        let url = format!(
-            "{}/search?q={}&format=json&categories=general",
-            self.settings.searx_instance,
-            urlencoding::encode(query)
-        );
+        if results.is_empty() && !errors.is_empty() {
+            return Err(anyhow!("All search engines failed ({})", errors.join("; ")));
+        }
 
-        let response = self.client
-            .get(&url)
-            .send()
-            .await?;
-
-        let searx_response: SearXResponse = response.json().await?;
-
-        let results = searx_response
-            .results
-            .into_iter()
-            .take(self.settings.max_results)
-            .map(|result| SearchResult {
-                title: result.title,
-                url: result.url,
-                snippet: result.content,
-                source: "searx".to_string(),
-            })
-            .collect();
+        if let Some(history) = &self.history {
+            if let Err(e) = history.record(query, &results) {
+                log::warn!("Failed to save search history: {}", e);
+            }
+        }
 
+        log::info!("Found {} search results", results.len());
         Ok(results)
     }
 
-    /// Check rate limiting
-    fn check_rate_limit(&self) -> Result<()> {
-        if let Some(last_time) = self.last_search_time {
-            let elapsed = SystemTime::now()
-                .duration_since(last_time)
-                .unwrap_or(Duration::from_secs(0));
-
-            if elapsed.as_secs() < self.rate_limit_seconds {
-                let wait_time = self.rate_limit_seconds - elapsed.as_secs();
-                return Err(anyhow!(
-                    "Rate limit: Please wait {} seconds before next search",
-                    wait_time
-                ));
+    /// Default engine followed by the fallbacks, without repeats
+    fn engine_order(&self) -> Vec<SearchEngine> {
+        let mut order = vec![self.settings.default_engine];
+        for engine in &self.settings.fallback_engines {
+            if !order.contains(engine) {
+                order.push(*engine);
             }
         }
-
-        Ok(())
+        order
     }
 
     /// Update settings
@@ -252,6 +235,50 @@ impl WebSearchService {
     }
 }
 
+/// Append results whose canonical URL hasn't been seen, up to `limit`
+fn merge_results(
+    results: &mut Vec<SearchResult>,
+    seen: &mut HashSet<String>,
+    found: Vec<SearchResult>,
+    limit: usize,
+) {
+    for result in found {
+        if results.len() >= limit {
+            break;
+        }
+        if seen.insert(canonical_url(&result.url)) {
+            results.push(result);
+        }
+    }
+}
+
+/// URL identity for deduplication: no scheme, `www.`, fragment, tracking
+/// parameters or trailing slash
+pub fn canonical_url(url: &str) -> String {
+    let Ok(mut parsed) = Url::parse(url.trim()) else {
+        return url.trim().to_lowercase();
+    };
+    parsed.set_fragment(None);
+
+    let params: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(key, _)| !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_ref()))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    if params.is_empty() {
+        parsed.set_query(None);
+    } else {
+        parsed.query_pairs_mut().clear().extend_pairs(params);
+    }
+
+    let host = parsed.host_str().unwrap_or_default().trim_start_matches("www.").to_string();
+    let path = parsed.path().trim_end_matches('/');
+    match parsed.query() {
+        Some(query) => format!("{}{}?{}", host, path, query),
+        None => format!("{}{}", host, path),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -262,6 +289,53 @@ mod tests {
         assert!(!settings.enabled); // Disabled by default for privacy
         assert_eq!(settings.default_engine, SearchEngine::DuckDuckGo);
         assert_eq!(settings.max_results, 5);
+        assert_eq!(settings.fallback_engines, vec![SearchEngine::SearX, SearchEngine::Brave, SearchEngine::Tavily]);
+    }
+
+    #[test]
+    fn test_settings_without_fallbacks_deserialize() {
+        let json = r#"{"enabled":true,"default_engine":"SearX","max_results":3,"searx_instance":"http://localhost:8888"}"#;
+        let settings: WebSearchSettings = serde_json::from_str(json).unwrap();
+        assert_eq!(settings.fallback_engines, default_fallback_engines());
+
+        let service = WebSearchService::new(settings).unwrap();
+        assert_eq!(
+            service.engine_order(),
+            vec![SearchEngine::SearX, SearchEngine::Brave, SearchEngine::Tavily]
+        );
+    }
+
+    #[test]
+    fn test_results_deduplicated_by_canonical_url() {
+        assert_eq!(
+            canonical_url("https://www.example.com/docs/?utm_source=x&page=2#intro"),
+            canonical_url("http://example.com/docs?page=2")
+        );
+        assert_ne!(canonical_url("https://example.com/a"), canonical_url("https://example.com/b"));
+
+        let make = |url: &str, source: &str| SearchResult {
+            title: url.to_string(),
+            url: url.to_string(),
+            snippet: String::new(),
+            source: source.to_string(),
+        };
+        let mut results = Vec::new();
+        let mut seen = HashSet::new();
+        merge_results(&mut results, &mut seen, vec![make("https://example.com/a", "searx")], 3);
+        merge_results(
+            &mut results,
+            &mut seen,
+            vec![
+                make("https://www.example.com/a/", "brave"),
+                make("https://example.com/b", "brave"),
+                make("https://example.com/c", "brave"),
+                make("https://example.com/d", "brave"),
+            ],
+            3,
+        );
+
+        let urls: Vec<&str> = results.iter().map(|r| r.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://example.com/a", "https://example.com/b", "https://example.com/c"]);
     }
 
     #[test]
@@ -4,9 +4,23 @@
  */
 
 use crate::database::Database;
+use crate::services::scripting::ScriptingService;
+use crate::services::secrets::{self, SecretsService};
 use crate::services::webhook::{WebhookConfig, WebhookPayload, WebhookService};
 use log::{error, info};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Event names sent in `WebhookPayload::event` (v3.9.0)
+pub const EVENT_NAMES: &[&str] = &[
+    "conversation_started",
+    "message_sent",
+    "message_received",
+    "error_occurred",
+    "model_switched",
+    "feedback_received",
+    "screen_captured",
+    "weekly_review",
+];
 
 /// Events that can trigger webhooks
 #[derive(Debug, Clone)]
@@ -18,6 +32,7 @@ pub enum WebhookTriggerEvent {
     ModelSwitched { old_model: String, new_model: String },
     FeedbackReceived { message_id: String, satisfaction: String },
     ScreenCaptured { context_level: i32, analysis: Option<String> },
+    WeeklyReview { week_start: String, markdown: String }, // v3.9.0
 }
 
 impl WebhookTriggerEvent {
@@ -74,6 +89,13 @@ impl WebhookTriggerEvent {
                     "analysis": analysis,
                 }),
             ),
+            WebhookTriggerEvent::WeeklyReview { week_start, markdown } => (
+                "weekly_review".to_string(),
+                serde_json::json!({
+                    "week_start": week_start,
+                    "message": markdown,
+                }),
+            ),
         };
 
         WebhookPayload {
@@ -93,6 +115,7 @@ impl WebhookTriggerEvent {
             WebhookTriggerEvent::ModelSwitched { .. } => "system",
             WebhookTriggerEvent::FeedbackReceived { .. } => "feedback",
             WebhookTriggerEvent::ScreenCaptured { .. } => "screen",
+            WebhookTriggerEvent::WeeklyReview { .. } => "review",
         }
     }
 }
@@ -101,6 +124,8 @@ impl WebhookTriggerEvent {
 pub struct WebhookTriggerManager {
     db: Arc<Mutex<Database>>,
     webhook_service: WebhookService,
+    secrets: OnceLock<Arc<SecretsService>>,  // v3.9.0: sensitive headers
+    scripts: OnceLock<Arc<ScriptingService>>,  // v3.9.0: event-triggered scripts
 }
 
 impl WebhookTriggerManager {
@@ -109,13 +134,33 @@ impl WebhookTriggerManager {
         Self {
             db,
             webhook_service: WebhookService::new(),
+            secrets: OnceLock::new(),
+            scripts: OnceLock::new(),
         }
     }
 
+    /// Attach the secrets vault holding webhook credentials (v3.9.0)
+    pub fn attach_secrets(&self, secrets: Arc<SecretsService>) {
+        let _ = self.secrets.set(secrets);
+    }
+
+    /// Attach the scripting service so scripts can run on events (v3.9.0)
+    pub fn attach_scripts(&self, scripts: Arc<ScriptingService>) {
+        let _ = self.scripts.set(scripts);
+    }
+
     /// Trigger all enabled webhooks for an event
     pub async fn trigger_event(&self, event: WebhookTriggerEvent) {
         info!("Triggering webhooks for event: {:?}", event.category());
 
+        // Convert event to payload
+        let payload = event.to_payload();
+
+        // Start scripts bound to this event (v3.9.0)
+        if let Some(scripts) = self.scripts.get() {
+            scripts.dispatch_event(&payload);
+        }
+
         // Get all enabled webhooks from database
         let webhooks = match self.get_enabled_webhooks() {
             Ok(hooks) => hooks,
@@ -129,9 +174,6 @@ impl WebhookTriggerManager {
             return;
         }
 
-        // Convert event to payload
-        let payload = event.to_payload();
-
         // Trigger each webhook (in parallel for performance)
         let mut handles = vec![];
         for webhook in webhooks {
@@ -209,8 +251,20 @@ impl WebhookTriggerManager {
             })
             .map_err(|e| format!("Failed to query webhooks: {}", e))?;
 
-        webhooks.collect::<Result<Vec<_>, _>>()
-            .map_err(|e| format!("Failed to collect webhooks: {}", e))
+        let mut webhooks = webhooks.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect webhooks: {}", e))?;
+
+        // Merge credentials kept in the secrets vault
+        if let Some(vault) = self.secrets.get() {
+            for webhook in &mut webhooks {
+                match vault.get_map(&secrets::webhook_headers_key(&webhook.name)) {
+                    Ok(sensitive) => webhook.headers.extend(sensitive),
+                    Err(e) => error!("Failed to read secrets for webhook '{}': {}", webhook.name, e),
+                }
+            }
+        }
+
+        Ok(webhooks)
     }
 
     /// Update last_used_at timestamp for all webhooks
@@ -0,0 +1,662 @@
+//! Weekly Review (v3.9.0)
+//!
+//! A Markdown report of the week: what moved, what got done, where the time went.
+//!
+//! Features:
+//! - Goals that progressed (or were completed) and the progress gained
+//! - Tasks completed in the planner
+//! - Dominant work themes: top apps and window-title topics from the activity timeline
+//! - Notable conversations (most active first)
+//! - Memory highlights: important episodes and wiki facts learned during the week
+//! - Reports stored in `weekly_reviews`; optional delivery every Friday via notification
+//!   and/or the configured webhooks
+//!
+//! Configuration persists in `user_preferences`.
+
+#![allow(dead_code)]  // Phase 5: Weekly review
+
+use crate::database::Database;
+use crate::services::activity_timeline::{aggregate_app_usage, ActivitySession, ActivityTimelineService, AppUsage};
+use crate::services::notification::{AppNotification, NotificationAction, NotificationService, NotificationSource};
+use crate::services::webhook_triggers::{WebhookTriggerEvent, WebhookTriggerManager};
+use anyhow::{anyhow, Context, Result};
+use chrono::{Datelike, Local, NaiveDate, TimeZone, Timelike};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+
+const CONFIG_KEY: &str = "weekly_review_config";
+
+/// Entries per report section
+const SECTION_LIMIT: usize = 8;
+const THEME_LIMIT: usize = 6;
+
+/// Topics with less time than this across the week are noise (10 minutes)
+const MIN_THEME_SECS: i64 = 10 * 60;
+
+/// Memory text longer than this is cut in the report
+const HIGHLIGHT_MAX_CHARS: usize = 160;
+
+/// Window-title words that say nothing about the work
+const TITLE_STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "new", "tab", "window", "untitled", "home", "edit", "view",
+    "file", "files", "help", "settings", "preferences", "google", "chrome", "safari", "firefox",
+    "mozilla", "microsoft", "edge", "visual", "studio", "code", "terminal", "finder", "inbox",
+    "page", "search", "loading", "welcome", "http", "https", "www", "com", "html",
+];
+
+/// Weekly review settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeeklyReviewConfig {
+    /// Generate and deliver a report every week
+    pub scheduled: bool,
+    /// Delivery day, 0 = Monday … 6 = Sunday
+    pub weekday: u32,
+    /// Local hour from which the report is delivered
+    pub hour: u32,
+    pub notify: bool,
+    /// Send the Markdown to every enabled webhook
+    pub webhook: bool,
+}
+
+impl Default for WeeklyReviewConfig {
+    fn default() -> Self {
+        Self {
+            scheduled: true,
+            weekday: 4,
+            hour: 17,
+            notify: true,
+            webhook: false,
+        }
+    }
+}
+
+/// Goal that moved during the week
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalProgressSummary {
+    pub goal_id: String,
+    pub title: String,
+    pub progress_gained: f32,
+    pub progress: f32,
+    pub completed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletedTaskSummary {
+    pub task_id: String,
+    pub title: String,
+    pub completed_at: i64,
+}
+
+/// Topic from window titles, weighted by time spent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkTheme {
+    pub topic: String,
+    pub total_secs: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotableConversation {
+    pub conversation_id: String,
+    pub title: String,
+    pub message_count: u32,
+}
+
+/// The week's report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeeklyReport {
+    /// Monday of the week, YYYY-MM-DD (local time)
+    pub week_start: String,
+    /// Sunday of the week
+    pub week_end: String,
+    pub goals: Vec<GoalProgressSummary>,
+    pub tasks_completed: Vec<CompletedTaskSummary>,
+    pub top_apps: Vec<AppUsage>,
+    pub themes: Vec<WorkTheme>,
+    pub total_tracked_secs: i64,
+    pub conversations: Vec<NotableConversation>,
+    pub memory_highlights: Vec<String>,
+    pub markdown: String,
+    pub generated_at: i64,
+    /// Set once the report went out by notification / webhook
+    pub delivered_at: Option<i64>,
+}
+
+/// Weekly review generator
+pub struct WeeklyReviewService {
+    db: Arc<Mutex<Database>>,
+    timeline: Arc<ActivityTimelineService>,
+    notifications: Arc<NotificationService>,
+    webhooks: Arc<WebhookTriggerManager>,
+    config: RwLock<WeeklyReviewConfig>,
+}
+
+impl WeeklyReviewService {
+    pub fn new(
+        db: Arc<Mutex<Database>>,
+        timeline: Arc<ActivityTimelineService>,
+        notifications: Arc<NotificationService>,
+        webhooks: Arc<WebhookTriggerManager>,
+    ) -> Result<Self> {
+        let config = {
+            let db = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            db.conn().execute(
+                "CREATE TABLE IF NOT EXISTS weekly_reviews (
+                    week_start TEXT PRIMARY KEY,
+                    report TEXT NOT NULL, -- JSON WeeklyReport
+                    generated_at INTEGER NOT NULL,
+                    delivered_at INTEGER
+                )",
+                [],
+            )?;
+            load_config(db.conn())?
+        };
+
+        log::info!("✓ Weekly Review Service initialized");
+        Ok(Self {
+            db,
+            timeline,
+            notifications,
+            webhooks,
+            config: RwLock::new(config),
+        })
+    }
+
+    pub fn get_config(&self) -> WeeklyReviewConfig {
+        self.config.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn update_config(&self, mut config: WeeklyReviewConfig) -> Result<()> {
+        config.weekday = config.weekday.min(6);
+        config.hour = config.hour.min(23);
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            db.conn().execute(
+                "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+                rusqlite::params![CONFIG_KEY, serde_json::to_string(&config)?, chrono::Utc::now().timestamp()],
+            )?;
+        }
+        *self.config.write().unwrap_or_else(|e| e.into_inner()) = config;
+        log::info!("Weekly review config updated");
+        Ok(())
+    }
+
+    /// Build (and store) the report for the week containing `date` (YYYY-MM-DD, default today)
+    pub fn generate(&self, date: Option<&str>) -> Result<WeeklyReport> {
+        let day = match date {
+            Some(d) => NaiveDate::parse_from_str(d, "%Y-%m-%d")
+                .with_context(|| format!("Invalid date '{}', expected YYYY-MM-DD", d))?,
+            None => Local::now().date_naive(),
+        };
+        let week_start = day - chrono::Duration::days(day.weekday().num_days_from_monday() as i64);
+        let week_end = week_start + chrono::Duration::days(6);
+        let (start_secs, end_secs) = (local_midnight(week_start)?, local_midnight(week_end + chrono::Duration::days(1))?);
+
+        // The timeline rebuilds each day's sessions from screen captures
+        let mut sessions: Vec<ActivitySession> = Vec::new();
+        for offset in 0..7 {
+            let date = (week_start + chrono::Duration::days(offset)).format("%Y-%m-%d").to_string();
+            match self.timeline.get_day(Some(&date)) {
+                Ok(timeline) => sessions.extend(timeline.sessions),
+                Err(e) => log::warn!("Weekly review: no timeline for {}: {}", date, e),
+            }
+        }
+
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let conn = db.conn();
+
+        let previous_delivery: Option<i64> = conn
+            .query_row(
+                "SELECT delivered_at FROM weekly_reviews WHERE week_start = ?1",
+                [week_start.format("%Y-%m-%d").to_string()],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+
+        let mut top_apps = aggregate_app_usage(&sessions);
+        top_apps.truncate(THEME_LIMIT);
+
+        let mut report = WeeklyReport {
+            week_start: week_start.format("%Y-%m-%d").to_string(),
+            week_end: week_end.format("%Y-%m-%d").to_string(),
+            goals: section("goals", goals_progressed(conn, start_secs, end_secs)),
+            tasks_completed: section("tasks", tasks_completed(conn, start_secs, end_secs)),
+            top_apps,
+            themes: work_themes(&sessions),
+            total_tracked_secs: sessions.iter().map(|s| s.duration_secs).sum(),
+            conversations: section("conversations", notable_conversations(conn, start_secs * 1000, end_secs * 1000)),
+            memory_highlights: section("memories", memory_highlights(conn, start_secs, end_secs)),
+            markdown: String::new(),
+            generated_at: chrono::Utc::now().timestamp(),
+            delivered_at: previous_delivery,
+        };
+        report.markdown = render_markdown(&report);
+
+        conn.execute(
+            "INSERT OR REPLACE INTO weekly_reviews (week_start, report, generated_at, delivered_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![report.week_start, serde_json::to_string(&report)?, report.generated_at, report.delivered_at],
+        )?;
+
+        log::info!("✓ Weekly review generated for week of {}", report.week_start);
+        Ok(report)
+    }
+
+    /// Stored reports, newest week first
+    pub fn list(&self, limit: usize) -> Result<Vec<WeeklyReport>> {
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        let mut stmt = db
+            .conn()
+            .prepare("SELECT report FROM weekly_reviews ORDER BY week_start DESC LIMIT ?1")?;
+        let reports = stmt
+            .query_map([limit as i64], |row| row.get::<_, String>(0))?
+            .filter_map(|row| row.ok())
+            .filter_map(|json| serde_json::from_str(&json).ok())
+            .collect();
+        Ok(reports)
+    }
+
+    /// Send the report by notification and/or webhook, as configured
+    pub async fn deliver(&self, report: &mut WeeklyReport) -> Result<()> {
+        let config = self.get_config();
+
+        if config.notify {
+            let notification = AppNotification::new(
+                NotificationSource::WeeklyReview,
+                format!("Your week in review ({})", report.week_start),
+                short_summary(report),
+            )
+            .with_action(NotificationAction::OpenChat {
+                prompt: Some("Let's go over my weekly review".to_string()),
+            });
+            self.notifications.notify(notification).await?;
+        }
+
+        if config.webhook {
+            self.webhooks
+                .trigger_event(WebhookTriggerEvent::WeeklyReview {
+                    week_start: report.week_start.clone(),
+                    markdown: report.markdown.clone(),
+                })
+                .await;
+        }
+
+        report.delivered_at = Some(chrono::Utc::now().timestamp());
+        let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+        db.conn().execute(
+            "UPDATE weekly_reviews SET report = ?1, delivered_at = ?2 WHERE week_start = ?3",
+            rusqlite::params![serde_json::to_string(&*report)?, report.delivered_at, report.week_start],
+        )?;
+        Ok(())
+    }
+
+    /// Generate and deliver this week's report when the configured day and hour have come
+    /// and it hasn't gone out yet
+    pub async fn deliver_if_due(self: &Arc<Self>) -> Result<Option<WeeklyReport>> {
+        let config = self.get_config();
+        let now = Local::now();
+        if !config.scheduled
+            || now.weekday().num_days_from_monday() != config.weekday
+            || now.hour() < config.hour
+        {
+            return Ok(None);
+        }
+
+        let service = Arc::clone(self);
+        let mut report = tokio::task::spawn_blocking(move || service.generate(None))
+            .await
+            .map_err(|e| anyhow!("Task join error: {}", e))??;
+        if report.delivered_at.is_some() {
+            return Ok(None);
+        }
+        self.deliver(&mut report).await?;
+        Ok(Some(report))
+    }
+}
+
+fn load_config(conn: &Connection) -> Result<WeeklyReviewConfig> {
+    let json: Option<String> = conn
+        .query_row("SELECT value FROM user_preferences WHERE key = ?1", [CONFIG_KEY], |row| row.get(0))
+        .optional()?;
+    match json {
+        Some(json) => Ok(serde_json::from_str(&json)?),
+        None => Ok(WeeklyReviewConfig::default()),
+    }
+}
+
+fn local_midnight(date: NaiveDate) -> Result<i64> {
+    let naive = date.and_hms_opt(0, 0, 0).ok_or_else(|| anyhow!("Invalid day start"))?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.timestamp())
+        .ok_or_else(|| anyhow!("Ambiguous local time for {}", date))
+}
+
+/// A section whose source is missing (feature never used) is left empty
+fn section<T>(name: &str, result: Result<Vec<T>>) -> Vec<T> {
+    result.unwrap_or_else(|e| {
+        log::warn!("Weekly review: skipped {}: {}", name, e);
+        Vec::new()
+    })
+}
+
+fn goals_progressed(conn: &Connection, start: i64, end: i64) -> Result<Vec<GoalProgressSummary>> {
+    let mut stmt = conn.prepare(
+        "SELECT g.id, g.title, g.progress_percentage, g.status,
+                COALESCE((SELECT SUM(u.progress_delta) FROM progress_updates u
+                          WHERE u.goal_id = g.id AND u.created_at >= ?1 AND u.created_at < ?2), 0.0) AS gained
+         FROM goals g
+         WHERE gained > 0 OR (g.completed_at >= ?1 AND g.completed_at < ?2)
+         ORDER BY gained DESC
+         LIMIT ?3",
+    )?;
+    let goals = stmt
+        .query_map(rusqlite::params![start, end, SECTION_LIMIT as i64], |row| {
+            Ok(GoalProgressSummary {
+                goal_id: row.get(0)?,
+                title: row.get(1)?,
+                progress: row.get(2)?,
+                completed: row.get::<_, String>(3)? == "completed",
+                progress_gained: row.get::<_, f64>(4)? as f32,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(goals)
+}
+
+fn tasks_completed(conn: &Connection, start: i64, end: i64) -> Result<Vec<CompletedTaskSummary>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, title, completed_at FROM tasks
+         WHERE status = 'completed' AND completed_at >= ?1 AND completed_at < ?2
+         ORDER BY completed_at ASC",
+    )?;
+    let tasks = stmt
+        .query_map(rusqlite::params![start, end], |row| {
+            Ok(CompletedTaskSummary {
+                task_id: row.get(0)?,
+                title: row.get(1)?,
+                completed_at: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(tasks)
+}
+
+/// Most active conversations; bounds in Unix millis like `messages.timestamp`
+fn notable_conversations(conn: &Connection, start_ms: i64, end_ms: i64) -> Result<Vec<NotableConversation>> {
+    let mut stmt = conn.prepare(
+        "SELECT c.id, c.title, COUNT(m.id) AS message_count
+         FROM messages m
+         JOIN conversations c ON c.id = m.conversation_id
+         WHERE m.timestamp >= ?1 AND m.timestamp < ?2
+         GROUP BY c.id
+         ORDER BY message_count DESC, MAX(m.timestamp) DESC
+         LIMIT ?3",
+    )?;
+    let conversations = stmt
+        .query_map(rusqlite::params![start_ms, end_ms, SECTION_LIMIT as i64], |row| {
+            Ok(NotableConversation {
+                conversation_id: row.get(0)?,
+                title: row.get(1)?,
+                message_count: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(conversations)
+}
+
+/// Wiki facts learned during the week, then the most important new episodes
+fn memory_highlights(conn: &Connection, start: i64, end: i64) -> Result<Vec<String>> {
+    let mut highlights: Vec<String> = Vec::new();
+
+    let facts = conn
+        .prepare(
+            "SELECT statement FROM wiki_facts
+             WHERE learned_at >= ?1 AND learned_at < ?2
+             ORDER BY confidence DESC, reinforcement_count DESC
+             LIMIT ?3",
+        )
+        .and_then(|mut stmt| {
+            stmt.query_map(rusqlite::params![start, end, (SECTION_LIMIT / 2) as i64], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()
+        });
+    match facts {
+        Ok(facts) => highlights.extend(facts.into_iter().map(|fact| format!("Learned: {}", fact))),
+        Err(e) => log::debug!("Weekly review: no wiki facts: {}", e),
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT user_message FROM episodic_memory
+         WHERE created_at >= ?1 AND created_at < ?2
+         ORDER BY importance DESC, access_count DESC
+         LIMIT ?3",
+    )?;
+    let remaining = SECTION_LIMIT.saturating_sub(highlights.len());
+    let episodes = stmt
+        .query_map(rusqlite::params![start, end, remaining as i64], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    highlights.extend(episodes.into_iter().map(|episode| format!("Discussed: {}", truncate(&episode))));
+
+    Ok(highlights)
+}
+
+fn truncate(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= HIGHLIGHT_MAX_CHARS {
+        return text;
+    }
+    format!("{}…", text.chars().take(HIGHLIGHT_MAX_CHARS).collect::<String>().trim_end())
+}
+
+/// Window-title words weighted by session time. App names are left out: they're in `top_apps`.
+pub fn work_themes(sessions: &[ActivitySession]) -> Vec<WorkTheme> {
+    let mut totals: HashMap<String, i64> = HashMap::new();
+    for session in sessions {
+        if session.window_titles.is_empty() {
+            continue;
+        }
+        let app_words: Vec<String> = session
+            .app_name
+            .split(|c: char| !c.is_alphanumeric())
+            .map(|w| w.to_lowercase())
+            .collect();
+        let share = session.duration_secs / session.window_titles.len() as i64;
+
+        for title in &session.window_titles {
+            let mut seen: Vec<String> = Vec::new();
+            for word in title.split(|c: char| !c.is_alphanumeric() && c != '_' && c != '-') {
+                let word = word.trim_matches(|c| c == '_' || c == '-').to_lowercase();
+                if word.chars().count() < 3
+                    || word.chars().all(|c| c.is_ascii_digit())
+                    || TITLE_STOPWORDS.contains(&word.as_str())
+                    || app_words.contains(&word)
+                    || seen.contains(&word)
+                {
+                    continue;
+                }
+                *totals.entry(word.clone()).or_insert(0) += share;
+                seen.push(word);
+            }
+        }
+    }
+
+    let mut themes: Vec<WorkTheme> = totals
+        .into_iter()
+        .filter(|(_, secs)| *secs >= MIN_THEME_SECS)
+        .map(|(topic, total_secs)| WorkTheme { topic, total_secs })
+        .collect();
+    themes.sort_by(|a, b| b.total_secs.cmp(&a.total_secs).then_with(|| a.topic.cmp(&b.topic)));
+    themes.truncate(THEME_LIMIT);
+    themes
+}
+
+fn format_duration(secs: i64) -> String {
+    let (hours, minutes) = (secs / 3600, (secs % 3600) / 60);
+    match (hours, minutes) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+fn short_summary(report: &WeeklyReport) -> String {
+    format!(
+        "{} goals moved, {} tasks completed, {} tracked",
+        report.goals.len(),
+        report.tasks_completed.len(),
+        format_duration(report.total_tracked_secs)
+    )
+}
+
+/// The report as Markdown
+pub fn render_markdown(report: &WeeklyReport) -> String {
+    const NOTHING: &str = "_Nothing recorded this week._\n";
+    let mut md = format!("# Weekly Review: {} – {}\n\n", report.week_start, report.week_end);
+    md.push_str(&format!("{}.\n\n", short_summary(report)));
+
+    md.push_str("## Goals\n\n");
+    if report.goals.is_empty() {
+        md.push_str(NOTHING);
+    }
+    for goal in &report.goals {
+        if goal.completed {
+            md.push_str(&format!("- **{}** — completed\n", goal.title));
+        } else {
+            md.push_str(&format!(
+                "- **{}** — +{:.0}% (now {:.0}%)\n",
+                goal.title, goal.progress_gained, goal.progress
+            ));
+        }
+    }
+
+    md.push_str(&format!("\n## Tasks completed ({})\n\n", report.tasks_completed.len()));
+    if report.tasks_completed.is_empty() {
+        md.push_str(NOTHING);
+    }
+    for task in &report.tasks_completed {
+        md.push_str(&format!("- {}\n", task.title));
+    }
+
+    md.push_str("\n## Where the time went\n\n");
+    if report.top_apps.is_empty() {
+        md.push_str(NOTHING);
+    }
+    for app in &report.top_apps {
+        md.push_str(&format!("- {} — {}\n", app.app_name, format_duration(app.total_secs)));
+    }
+    if !report.themes.is_empty() {
+        let topics: Vec<String> = report
+            .themes
+            .iter()
+            .map(|theme| format!("{} ({})", theme.topic, format_duration(theme.total_secs)))
+            .collect();
+        md.push_str(&format!("\nMain topics: {}\n", topics.join(", ")));
+    }
+
+    md.push_str("\n## Notable conversations\n\n");
+    if report.conversations.is_empty() {
+        md.push_str(NOTHING);
+    }
+    for conversation in &report.conversations {
+        md.push_str(&format!("- {} ({} messages)\n", conversation.title, conversation.message_count));
+    }
+
+    md.push_str("\n## Memory highlights\n\n");
+    if report.memory_highlights.is_empty() {
+        md.push_str(NOTHING);
+    }
+    for highlight in &report.memory_highlights {
+        md.push_str(&format!("- {}\n", highlight));
+    }
+
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(app: &str, titles: &[&str], duration_secs: i64) -> ActivitySession {
+        ActivitySession {
+            id: uuid::Uuid::new_v4().to_string(),
+            app_name: app.to_string(),
+            window_titles: titles.iter().map(|t| t.to_string()).collect(),
+            started_at: 0,
+            ended_at: duration_secs * 1000,
+            duration_secs,
+            sample_count: 1,
+        }
+    }
+
+    #[test]
+    fn test_work_themes() {
+        let sessions = vec![
+            session("Code", &["sync_queue.rs — garden", "offline.rs — garden"], 7200),
+            session("Safari", &["Rust async book - Safari"], 1800),
+            session("Slack", &["general"], 60),
+        ];
+        let themes = work_themes(&sessions);
+        assert_eq!(themes[0].topic, "garden");
+        assert_eq!(themes[0].total_secs, 7200);
+        assert!(themes.iter().any(|t| t.topic == "sync_queue" && t.total_secs == 3600));
+        assert!(themes.iter().any(|t| t.topic == "rust"));
+        assert!(!themes.iter().any(|t| t.topic == "safari" || t.topic == "general"));
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let report = WeeklyReport {
+            week_start: "2026-10-12".to_string(),
+            week_end: "2026-10-18".to_string(),
+            goals: vec![
+                GoalProgressSummary {
+                    goal_id: "g1".to_string(),
+                    title: "Ship offline sync".to_string(),
+                    progress_gained: 12.0,
+                    progress: 57.0,
+                    completed: false,
+                },
+                GoalProgressSummary {
+                    goal_id: "g2".to_string(),
+                    title: "Run a 10k".to_string(),
+                    progress_gained: 20.0,
+                    progress: 100.0,
+                    completed: true,
+                },
+            ],
+            tasks_completed: vec![CompletedTaskSummary {
+                task_id: "t1".to_string(),
+                title: "Write sync tests".to_string(),
+                completed_at: 0,
+            }],
+            top_apps: vec![AppUsage {
+                app_name: "Code".to_string(),
+                total_secs: 5400,
+                session_count: 3,
+            }],
+            themes: vec![WorkTheme {
+                topic: "garden".to_string(),
+                total_secs: 3600,
+            }],
+            total_tracked_secs: 5400,
+            conversations: Vec::new(),
+            memory_highlights: vec!["Learned: User prefers dark mode".to_string()],
+            markdown: String::new(),
+            generated_at: 0,
+            delivered_at: None,
+        };
+
+        let md = render_markdown(&report);
+        assert!(md.starts_with("# Weekly Review: 2026-10-12 – 2026-10-18\n"));
+        assert!(md.contains("2 goals moved, 1 tasks completed, 1h 30m tracked."));
+        assert!(md.contains("- **Ship offline sync** — +12% (now 57%)"));
+        assert!(md.contains("- **Run a 10k** — completed"));
+        assert!(md.contains("- Code — 1h 30m"));
+        assert!(md.contains("Main topics: garden (1h)"));
+        assert!(md.contains("## Notable conversations\n\n_Nothing recorded this week._"));
+        assert!(md.contains("- Learned: User prefers dark mode"));
+    }
+}
@@ -0,0 +1,656 @@
+//! Window Manager (v3.9.0)
+//!
+//! App and window management for agents, so "open VS Code and my project"
+//! doesn't need pixel-level clicking.
+//!
+//! Features:
+//! - List visible windows with their app, title and bounds
+//! - Focus a window or app by id, app name or title
+//! - Open an app (optionally with existing files / folders) or a web URL
+//! - Move and resize windows
+//!
+//! Platform layers, as used by computer control:
+//! - macOS: System Events (JXA through `osascript`, needs the Accessibility
+//!   permission) and `open`
+//! - Windows: Win32 window APIs and ShellExecute
+//! - Linux: `wmctrl` / `xprop` (X11; Wayland compositors don't allow it) and `xdg-open`
+//!
+//! Apps are launched directly, never through a shell, and only http(s) and
+//! mailto URLs can be opened.
+
+#![allow(dead_code)]  // Phase 5: Window management
+
+use crate::services::computer_control::BoundingBox;
+use anyhow::{anyhow, Result};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Files / folders passed to one app launch
+const MAX_OPEN_PATHS: usize = 20;
+
+/// A visible top-level window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowInfo {
+    /// Platform window id (macOS: "pid:index", Windows: HWND, Linux: X11 id)
+    pub id: String,
+    pub app_name: String,
+    pub title: String,
+    pub process_id: Option<u32>,
+    pub bounds: Option<BoundingBox>,
+    pub focused: bool,
+}
+
+/// New position and / or size; missing values keep the current ones
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct WindowPlacement {
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+}
+
+impl WindowPlacement {
+    fn is_empty(&self) -> bool {
+        self.x.is_none() && self.y.is_none() && self.width.is_none() && self.height.is_none()
+    }
+
+    /// The placement merged into the window's current bounds
+    fn apply(&self, current: Option<&BoundingBox>) -> Result<BoundingBox> {
+        let pick = |value: Option<i32>, current: Option<i32>, name: &str| {
+            value.or(current).ok_or_else(|| anyhow!("The window's current {} is unknown; pass it too", name))
+        };
+        let bounds = BoundingBox {
+            x: pick(self.x, current.map(|b| b.x), "x")?,
+            y: pick(self.y, current.map(|b| b.y), "y")?,
+            width: pick(self.width, current.map(|b| b.width), "width")?,
+            height: pick(self.height, current.map(|b| b.height), "height")?,
+        };
+        if bounds.width < 50 || bounds.height < 50 {
+            return Err(anyhow!("Windows must be at least 50×50"));
+        }
+        Ok(bounds)
+    }
+}
+
+/// Window management on the current platform
+#[derive(Clone, Default)]
+pub struct WindowManagerService;
+
+impl WindowManagerService {
+    pub fn new() -> Self {
+        log::info!("✓ Window Manager initialized");
+        Self
+    }
+
+    /// Visible windows, front to back on platforms that report z-order;
+    /// `filter` matches app names and titles
+    pub fn list_windows(&self, filter: Option<&str>) -> Result<Vec<WindowInfo>> {
+        let windows = platform::list_windows()?;
+        Ok(match filter.map(str::trim).filter(|f| !f.is_empty()) {
+            Some(filter) => {
+                let filter = filter.to_lowercase();
+                windows
+                    .into_iter()
+                    .filter(|w| w.app_name.to_lowercase().contains(&filter) || w.title.to_lowercase().contains(&filter))
+                    .collect()
+            }
+            None => windows,
+        })
+    }
+
+    /// Bring a window (or, on macOS, a running app without windows) to the front
+    pub fn focus(&self, target: &str) -> Result<WindowInfo> {
+        let windows = platform::list_windows()?;
+        match find_window(&windows, target) {
+            Some(window) => {
+                platform::focus(window)?;
+                log::info!("Focused window '{}' ({})", window.title, window.app_name);
+                Ok(WindowInfo { focused: true, ..window.clone() })
+            }
+            None => Err(anyhow!(
+                "No open window matches '{}'; use open_app to start it",
+                target
+            )),
+        }
+    }
+
+    /// Launch an app, optionally opening existing files or folders with it
+    pub fn open_app(&self, app: &str, paths: &[String]) -> Result<Vec<String>> {
+        let app = app.trim();
+        if app.is_empty() {
+            return Err(anyhow!("Missing app name"));
+        }
+        if paths.len() > MAX_OPEN_PATHS {
+            return Err(anyhow!("At most {} files or folders can be opened at once", MAX_OPEN_PATHS));
+        }
+        let paths = paths.iter().map(|p| resolve_path(p)).collect::<Result<Vec<_>>>()?;
+
+        platform::open_app(&platform::app_alias(app), &paths)?;
+        log::info!("Opened {} with {} path(s)", app, paths.len());
+        Ok(paths.iter().map(|p| p.to_string_lossy().into_owned()).collect())
+    }
+
+    /// Open a web page or mail link in the default app
+    pub fn open_url(&self, url: &str) -> Result<String> {
+        let url = validate_url(url)?;
+        platform::open_target(url.as_str())?;
+        log::info!("Opened URL {}", url);
+        Ok(url.to_string())
+    }
+
+    pub fn move_resize(&self, target: &str, placement: WindowPlacement) -> Result<WindowInfo> {
+        if placement.is_empty() {
+            return Err(anyhow!("Pass a new position (x, y) and / or size (width, height)"));
+        }
+        let windows = platform::list_windows()?;
+        let window = find_window(&windows, target).ok_or_else(|| anyhow!("No open window matches '{}'", target))?;
+        let bounds = placement.apply(window.bounds.as_ref())?;
+
+        platform::set_bounds(window, &bounds)?;
+        log::info!(
+            "Moved window '{}' to {},{} {}×{}",
+            window.title, bounds.x, bounds.y, bounds.width, bounds.height
+        );
+        Ok(WindowInfo { bounds: Some(bounds), ..window.clone() })
+    }
+}
+
+/// The window a target names: its id, then app name, then part of app name or title
+fn find_window<'a>(windows: &'a [WindowInfo], target: &str) -> Option<&'a WindowInfo> {
+    let target = target.trim();
+    if target.is_empty() {
+        return None;
+    }
+    let lower = target.to_lowercase();
+    let alias = platform::app_alias(target).to_lowercase();
+    windows
+        .iter()
+        .find(|w| w.id == target)
+        .or_else(|| windows.iter().find(|w| w.app_name.eq_ignore_ascii_case(target) || w.app_name.to_lowercase() == alias))
+        .or_else(|| windows.iter().find(|w| w.app_name.to_lowercase().contains(&lower)))
+        .or_else(|| windows.iter().find(|w| w.title.to_lowercase().contains(&lower)))
+}
+
+/// An existing file or folder, `~` expanded
+fn resolve_path(path: &str) -> Result<PathBuf> {
+    let path = path.trim();
+    let expanded = match path.strip_prefix("~") {
+        Some(rest) => dirs::home_dir()
+            .ok_or_else(|| anyhow!("No home directory"))?
+            .join(rest.trim_start_matches(['/', '\\'])),
+        None => PathBuf::from(path),
+    };
+    Path::new(&expanded)
+        .canonicalize()
+        .map_err(|_| anyhow!("'{}' doesn't exist", path))
+}
+
+fn validate_url(url: &str) -> Result<Url> {
+    let url = Url::parse(url.trim()).map_err(|e| anyhow!("Invalid URL '{}': {}", url, e))?;
+    match url.scheme() {
+        "http" | "https" | "mailto" => Ok(url),
+        other => Err(anyhow!("Only http(s) and mailto links can be opened, not {}:", other)),
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::*;
+    use std::process::Command;
+
+    /// Windows of regular apps as JSON, frontmost app first
+    const LIST_SCRIPT: &str = r#"
+function run() {
+  var se = Application('System Events');
+  var out = [];
+  se.applicationProcesses.whose({ backgroundOnly: false })().forEach(function (p) {
+    try {
+      var name = p.name(), pid = p.unixId(), front = p.frontmost();
+      p.windows().forEach(function (w, i) {
+        var item = { app: name, pid: pid, index: i + 1, title: '', front: front && i === 0 };
+        try { item.title = w.name() || ''; } catch (e) {}
+        try { var pos = w.position(), size = w.size(); item.x = pos[0]; item.y = pos[1]; item.width = size[0]; item.height = size[1]; } catch (e) {}
+        out.push(item);
+      });
+    } catch (e) {}
+  });
+  out.sort(function (a, b) { return (b.front ? 1 : 0) - (a.front ? 1 : 0); });
+  return JSON.stringify(out);
+}
+"#;
+
+    const FOCUS_SCRIPT: &str = r#"
+function run(argv) {
+  var p = Application('System Events').processes.whose({ unixId: Number(argv[0]) })[0];
+  p.frontmost = true;
+  p.windows[Number(argv[1]) - 1].actions['AXRaise'].perform();
+  return 'ok';
+}
+"#;
+
+    const BOUNDS_SCRIPT: &str = r#"
+function run(argv) {
+  var p = Application('System Events').processes.whose({ unixId: Number(argv[0]) })[0];
+  var w = p.windows[Number(argv[1]) - 1];
+  w.position = [Number(argv[2]), Number(argv[3])];
+  w.size = [Number(argv[4]), Number(argv[5])];
+  return 'ok';
+}
+"#;
+
+    #[derive(Deserialize)]
+    struct JxaWindow {
+        app: String,
+        pid: u32,
+        index: u32,
+        title: String,
+        front: bool,
+        x: Option<i32>,
+        y: Option<i32>,
+        width: Option<i32>,
+        height: Option<i32>,
+    }
+
+    fn jxa(script: &str, args: &[String]) -> Result<String> {
+        let output = Command::new("osascript")
+            .args(["-l", "JavaScript", "-e", script])
+            .args(args)
+            .output()
+            .map_err(|e| anyhow!("Failed to run osascript: {}", e))?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            if error.contains("-1719") || error.contains("-25211") || error.contains("not allowed assistive") {
+                return Err(anyhow!("Allow Garden of Eden under System Settings → Privacy & Security → Accessibility"));
+            }
+            return Err(anyhow!("System Events failed: {}", error.trim()));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn parse_id(id: &str) -> Result<(u32, u32)> {
+        let (pid, index) = id.split_once(':').ok_or_else(|| anyhow!("Invalid window id '{}'", id))?;
+        Ok((pid.parse()?, index.parse()?))
+    }
+
+    pub fn list_windows() -> Result<Vec<WindowInfo>> {
+        let windows: Vec<JxaWindow> = serde_json::from_str(&jxa(LIST_SCRIPT, &[])?)?;
+        Ok(windows
+            .into_iter()
+            .map(|w| WindowInfo {
+                id: format!("{}:{}", w.pid, w.index),
+                bounds: match (w.x, w.y, w.width, w.height) {
+                    (Some(x), Some(y), Some(width), Some(height)) => Some(BoundingBox { x, y, width, height }),
+                    _ => None,
+                },
+                app_name: w.app,
+                title: w.title,
+                process_id: Some(w.pid),
+                focused: w.front,
+            })
+            .collect())
+    }
+
+    pub fn focus(window: &WindowInfo) -> Result<()> {
+        let (pid, index) = parse_id(&window.id)?;
+        jxa(FOCUS_SCRIPT, &[pid.to_string(), index.to_string()]).map(|_| ())
+    }
+
+    pub fn set_bounds(window: &WindowInfo, bounds: &BoundingBox) -> Result<()> {
+        let (pid, index) = parse_id(&window.id)?;
+        let args = [pid, index].iter().map(u32::to_string).chain(
+            [bounds.x, bounds.y, bounds.width, bounds.height].iter().map(i32::to_string),
+        );
+        jxa(BOUNDS_SCRIPT, &args.collect::<Vec<_>>()).map(|_| ())
+    }
+
+    pub fn app_alias(app: &str) -> String {
+        match app.to_lowercase().as_str() {
+            "code" | "vscode" | "vs code" => "Visual Studio Code".to_string(),
+            "chrome" => "Google Chrome".to_string(),
+            "terminal" => "Terminal".to_string(),
+            _ => app.to_string(),
+        }
+    }
+
+    pub fn open_app(app: &str, paths: &[PathBuf]) -> Result<()> {
+        // `open -a` also brings an already running app to the front
+        let output = Command::new("open").arg("-a").arg(app).args(paths).output()?;
+        if !output.status.success() {
+            return Err(anyhow!("Couldn't open {}: {}", app, String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        Ok(())
+    }
+
+    pub fn open_target(target: &str) -> Result<()> {
+        let status = Command::new("open").arg(target).status()?;
+        if !status.success() {
+            return Err(anyhow!("Couldn't open {}", target));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::*;
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::Foundation::{CloseHandle, BOOL, HWND, LPARAM, MAX_PATH, RECT};
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_FORMAT, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::UI::Shell::ShellExecuteW;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetForegroundWindow, GetWindowRect, GetWindowTextLengthW, GetWindowTextW,
+        GetWindowThreadProcessId, IsIconic, IsWindowVisible, SetForegroundWindow, SetWindowPos, ShowWindow,
+        SWP_NOACTIVATE, SWP_NOZORDER, SW_RESTORE, SW_SHOWNORMAL,
+    };
+
+    unsafe extern "system" fn collect(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let handles = &mut *(lparam.0 as *mut Vec<HWND>);
+        if IsWindowVisible(hwnd).as_bool() && GetWindowTextLengthW(hwnd) > 0 {
+            handles.push(hwnd);
+        }
+        BOOL(1)
+    }
+
+    fn process_name(pid: u32) -> Option<String> {
+        unsafe {
+            let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+            let mut buffer = vec![0u16; MAX_PATH as usize];
+            let mut len = buffer.len() as u32;
+            let result = QueryFullProcessImageNameW(
+                handle,
+                PROCESS_NAME_FORMAT(0),
+                windows::core::PWSTR(buffer.as_mut_ptr()),
+                &mut len,
+            );
+            let _ = CloseHandle(handle);
+            result.ok()?;
+            let path = String::from_utf16_lossy(&buffer[..len as usize]);
+            Path::new(&path).file_stem().map(|s| s.to_string_lossy().into_owned())
+        }
+    }
+
+    fn hwnd(id: &str) -> Result<HWND> {
+        let raw: isize = id.parse().map_err(|_| anyhow!("Invalid window id '{}'", id))?;
+        Ok(HWND(raw as *mut std::ffi::c_void))
+    }
+
+    pub fn list_windows() -> Result<Vec<WindowInfo>> {
+        let mut handles: Vec<HWND> = Vec::new();
+        unsafe { EnumWindows(Some(collect), LPARAM(&mut handles as *mut Vec<HWND> as isize))? };
+        let foreground = unsafe { GetForegroundWindow() };
+
+        Ok(handles
+            .into_iter()
+            .map(|handle| unsafe {
+                let mut title = vec![0u16; 512];
+                let len = GetWindowTextW(handle, &mut title).max(0) as usize;
+                let mut pid = 0u32;
+                GetWindowThreadProcessId(handle, Some(&mut pid));
+                let mut rect = RECT::default();
+                // Minimized windows report an off-screen placeholder rect
+                let bounds = (!IsIconic(handle).as_bool() && GetWindowRect(handle, &mut rect).is_ok()).then(|| BoundingBox {
+                    x: rect.left,
+                    y: rect.top,
+                    width: rect.right - rect.left,
+                    height: rect.bottom - rect.top,
+                });
+                WindowInfo {
+                    id: (handle.0 as isize).to_string(),
+                    app_name: process_name(pid).unwrap_or_default(),
+                    title: String::from_utf16_lossy(&title[..len]),
+                    process_id: (pid != 0).then_some(pid),
+                    bounds,
+                    focused: handle == foreground,
+                }
+            })
+            .collect())
+    }
+
+    pub fn focus(window: &WindowInfo) -> Result<()> {
+        let handle = hwnd(&window.id)?;
+        unsafe {
+            if IsIconic(handle).as_bool() {
+                let _ = ShowWindow(handle, SW_RESTORE);
+            }
+            if !SetForegroundWindow(handle).as_bool() {
+                return Err(anyhow!("Windows refused to bring '{}' to the front", window.title));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn set_bounds(window: &WindowInfo, bounds: &BoundingBox) -> Result<()> {
+        let handle = hwnd(&window.id)?;
+        unsafe {
+            if IsIconic(handle).as_bool() {
+                let _ = ShowWindow(handle, SW_RESTORE);
+            }
+            SetWindowPos(
+                handle,
+                HWND(std::ptr::null_mut()),
+                bounds.x,
+                bounds.y,
+                bounds.width,
+                bounds.height,
+                SWP_NOZORDER | SWP_NOACTIVATE,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn app_alias(app: &str) -> String {
+        match app.to_lowercase().as_str() {
+            "visual studio code" | "vs code" | "vscode" => "code".to_string(),
+            "google chrome" => "chrome".to_string(),
+            "microsoft edge" | "edge" => "msedge".to_string(),
+            "terminal" => "wt".to_string(),
+            _ => app.to_string(),
+        }
+    }
+
+    fn shell_execute(file: &str, parameters: Option<String>) -> Result<()> {
+        let file = HSTRING::from(file);
+        let parameters = parameters.map(HSTRING::from);
+        let result = unsafe {
+            ShellExecuteW(
+                HWND(std::ptr::null_mut()),
+                &HSTRING::from("open"),
+                &file,
+                parameters.as_ref().map(|p| PCWSTR(p.as_ptr())).unwrap_or(PCWSTR::null()),
+                PCWSTR::null(),
+                SW_SHOWNORMAL,
+            )
+        };
+        // Values up to 32 are error codes
+        if result.0 as isize <= 32 {
+            return Err(anyhow!("Couldn't open {} (error {})", file, result.0 as isize));
+        }
+        Ok(())
+    }
+
+    pub fn open_app(app: &str, paths: &[PathBuf]) -> Result<()> {
+        let parameters = (!paths.is_empty()).then(|| {
+            paths
+                .iter()
+                .map(|p| format!("\"{}\"", p.to_string_lossy().trim_start_matches(r"\\?\")))
+                .collect::<Vec<_>>()
+                .join(" ")
+        });
+        shell_execute(app, parameters)
+    }
+
+    pub fn open_target(target: &str) -> Result<()> {
+        shell_execute(target, None)
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    use super::*;
+    use std::process::{Command, Stdio};
+
+    fn run(program: &str, args: &[&str]) -> Result<String> {
+        let output = Command::new(program).args(args).output().map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                anyhow!("Window management needs '{}' (X11); install it with your package manager", program)
+            } else {
+                anyhow!("Failed to run {}: {}", program, e)
+            }
+        })?;
+        if !output.status.success() {
+            return Err(anyhow!("{} failed: {}", program, String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// X11 id of the active window, from `xprop -root _NET_ACTIVE_WINDOW`
+    fn active_window() -> Option<u64> {
+        let output = run("xprop", &["-root", "_NET_ACTIVE_WINDOW"]).ok()?;
+        let hex = output.rsplit("# ").next()?.trim().trim_start_matches("0x");
+        u64::from_str_radix(hex.split(',').next()?.trim(), 16).ok()
+    }
+
+    pub fn list_windows() -> Result<Vec<WindowInfo>> {
+        let active = active_window();
+        Ok(parse_wmctrl(&run("wmctrl", &["-lpG"])?, active))
+    }
+
+    pub fn focus(window: &WindowInfo) -> Result<()> {
+        run("wmctrl", &["-ia", &window.id]).map(|_| ())
+    }
+
+    pub fn set_bounds(window: &WindowInfo, bounds: &BoundingBox) -> Result<()> {
+        let geometry = format!("0,{},{},{},{}", bounds.x, bounds.y, bounds.width, bounds.height);
+        // Maximized windows ignore new geometry
+        let _ = run("wmctrl", &["-ir", &window.id, "-b", "remove,maximized_vert,maximized_horz"]);
+        run("wmctrl", &["-ir", &window.id, "-e", &geometry]).map(|_| ())
+    }
+
+    pub fn app_alias(app: &str) -> String {
+        match app.to_lowercase().as_str() {
+            "visual studio code" | "vs code" | "vscode" => "code".to_string(),
+            "google chrome" | "chrome" => "google-chrome".to_string(),
+            "terminal" => "x-terminal-emulator".to_string(),
+            _ => app.to_string(),
+        }
+    }
+
+    pub fn open_app(app: &str, paths: &[PathBuf]) -> Result<()> {
+        let spawned = Command::new(app)
+            .args(paths)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match spawned {
+            Ok(_) => Ok(()),
+            // Desktop entry names ("org.gnome.Nautilus", "firefox")
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let mut args = vec![app.to_string()];
+                args.extend(paths.iter().map(|p| p.to_string_lossy().into_owned()));
+                let args: Vec<&str> = args.iter().map(String::as_str).collect();
+                run("gtk-launch", &args).map(|_| ()).map_err(|_| anyhow!("No app named '{}' was found", app))
+            }
+            Err(e) => Err(anyhow!("Couldn't open {}: {}", app, e)),
+        }
+    }
+
+    pub fn open_target(target: &str) -> Result<()> {
+        Command::new("xdg-open")
+            .arg(target)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| anyhow!("Couldn't open {}: {}", target, e))?;
+        Ok(())
+    }
+
+    /// `wmctrl -lpG`: id, desktop, pid, x, y, width, height, host, title
+    pub(super) fn parse_wmctrl(output: &str, active: Option<u64>) -> Vec<WindowInfo> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let id = fields.next()?;
+                let desktop: i32 = fields.next()?.parse().ok()?;
+                let pid: u32 = fields.next()?.parse().ok()?;
+                let numbers: Vec<i32> = fields.by_ref().take(4).filter_map(|f| f.parse().ok()).collect();
+                let _host = fields.next()?;
+                let title = fields.collect::<Vec<_>>().join(" ");
+                // Desktop -1 holds panels and docks
+                if desktop < 0 || numbers.len() < 4 {
+                    return None;
+                }
+                let numeric_id = u64::from_str_radix(id.trim_start_matches("0x"), 16).ok();
+                let app_name = std::fs::read_to_string(format!("/proc/{}/comm", pid))
+                    .map(|name| name.trim().to_string())
+                    .unwrap_or_default();
+                Some(WindowInfo {
+                    id: id.to_string(),
+                    app_name,
+                    title,
+                    process_id: (pid != 0).then_some(pid),
+                    bounds: Some(BoundingBox { x: numbers[0], y: numbers[1], width: numbers[2], height: numbers[3] }),
+                    focused: numeric_id.is_some() && numeric_id == active,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(id: &str, app: &str, title: &str) -> WindowInfo {
+        WindowInfo {
+            id: id.to_string(),
+            app_name: app.to_string(),
+            title: title.to_string(),
+            process_id: None,
+            bounds: Some(BoundingBox { x: 0, y: 0, width: 800, height: 600 }),
+            focused: false,
+        }
+    }
+
+    #[test]
+    fn test_find_window_and_placement() {
+        let windows = vec![
+            window("1", "Finder", "Downloads"),
+            window("2", "Code", "main.rs — garden"),
+            window("3", "Safari", "Rust docs"),
+        ];
+        assert_eq!(find_window(&windows, "3").unwrap().app_name, "Safari");
+        assert_eq!(find_window(&windows, "code").unwrap().id, "2");
+        assert_eq!(find_window(&windows, "saf").unwrap().id, "3");
+        assert_eq!(find_window(&windows, "garden").unwrap().id, "2");
+        assert!(find_window(&windows, "spotify").is_none());
+
+        let placement = WindowPlacement { x: Some(100), width: Some(1200), ..Default::default() };
+        let bounds = placement.apply(windows[0].bounds.as_ref()).unwrap();
+        assert_eq!((bounds.x, bounds.y, bounds.width, bounds.height), (100, 0, 1200, 600));
+        assert!(placement.apply(None).is_err());
+        assert!(WindowPlacement { width: Some(10), ..Default::default() }.apply(windows[0].bounds.as_ref()).is_err());
+
+        assert!(validate_url("https://example.com/a?b=1").is_ok());
+        assert!(validate_url("mailto:me@example.com").is_ok());
+        assert!(validate_url("file:///etc/passwd").is_err());
+        assert!(validate_url("javascript:alert(1)").is_err());
+        assert!(resolve_path("/definitely/not/here").is_err());
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    #[test]
+    fn test_parse_wmctrl() {
+        let output = "0x01e00003 -1 1234   0    0    1920 32   host Top panel\n\
+                      0x03a00007  0 0      10   52   1280 720  host main.rs - garden - Visual Studio Code\n";
+        let windows = platform::parse_wmctrl(output, Some(0x03a00007));
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].title, "main.rs - garden - Visual Studio Code");
+        assert!(windows[0].focused && windows[0].process_id.is_none());
+        assert_eq!(windows[0].bounds.as_ref().map(|b| (b.x, b.width)), Some((10, 1280)));
+    }
+}
@@ -0,0 +1,680 @@
+//! Workspace Awareness (v3.9.0)
+//!
+//! Knows which project the user is working in, so answers and file tools use
+//! it without the user spelling out paths.
+//!
+//! Features:
+//! - Active project from the focused window title (IDE / editor / terminal),
+//!   matched against configured roots and common dev folders
+//! - Manual pin (`workspace_set_active`) that overrides detection
+//! - Project summary: languages, build systems, recently modified files
+//! - Context piece for the context enricher
+//! - File tools resolve relative paths in the workspace and, while scoping is
+//!   on, refuse paths outside it
+//!
+//! Configuration persists in `user_preferences`.
+
+#![allow(dead_code)]  // Phase 7: File system integration (on-demand)
+
+use crate::database::Database;
+use crate::services::active_window::ActiveWindowService;
+use crate::services::file::{FileService, WorkspaceType};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Process-wide workspace used by the file tools
+static GLOBAL_WORKSPACE: OnceLock<Arc<WorkspaceService>> = OnceLock::new();
+
+const CONFIG_KEY: &str = "workspace_config";
+
+/// Detected workspace is reused for this long before re-scanning
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Files looked at per scan (recent files and language stats)
+const MAX_SCAN_FILES: usize = 5_000;
+const MAX_SCAN_DEPTH: usize = 8;
+
+/// Directories never scanned
+const IGNORED_DIRS: &[&str] = &[
+    ".git", "node_modules", "target", "dist", "build", ".next", ".venv", "venv", "__pycache__",
+    ".idea", ".vscode", ".gradle", "Pods", "vendor", ".cache", "coverage",
+];
+
+/// Folders under home searched for a project named in the window title
+const DEV_FOLDERS: &[&str] = &[
+    "projects", "Projects", "dev", "Developer", "code", "Code", "src", "repos", "workspace",
+    "git", "GitHub", "Documents/GitHub",
+];
+
+/// Markers that make a directory a project root
+const ROOT_MARKERS: &[&str] = &[
+    ".git", "Cargo.toml", "package.json", "pyproject.toml", "go.mod", "pom.xml",
+    "build.gradle", "build.gradle.kts", "CMakeLists.txt", "Package.swift", "Gemfile",
+    "composer.json",
+];
+
+/// Window title separators used by editors and terminals
+const TITLE_SEPARATORS: &[&str] = &[" — ", " – ", " - ", " | ", ": "];
+
+/// Workspace settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceConfig {
+    /// Project roots (or folders containing projects) to match window titles against
+    #[serde(default)]
+    pub roots: Vec<String>,
+    /// Detect the project from the focused window
+    #[serde(default = "default_true")]
+    pub follow_active_window: bool,
+    /// Refuse file tool paths outside the active workspace
+    #[serde(default = "default_true")]
+    pub scope_file_tools: bool,
+    #[serde(default = "default_recent_file_limit")]
+    pub recent_file_limit: usize,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_recent_file_limit() -> usize {
+    8
+}
+
+impl Default for WorkspaceConfig {
+    fn default() -> Self {
+        Self {
+            roots: Vec::new(),
+            follow_active_window: true,
+            scope_file_tools: true,
+            recent_file_limit: default_recent_file_limit(),
+        }
+    }
+}
+
+/// How the active workspace was chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspaceSource {
+    Pinned,
+    ActiveWindow,
+    /// The only configured root
+    Configured,
+}
+
+/// Recently modified file, relative to the workspace root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentFile {
+    pub path: String,
+    pub modified: i64,
+}
+
+/// Active project summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    pub root: String,
+    pub name: String,
+    pub source: WorkspaceSource,
+    pub workspace_types: Vec<WorkspaceType>,
+    /// Most common source languages first
+    pub languages: Vec<String>,
+    pub build_systems: Vec<String>,
+    pub recent_files: Vec<RecentFile>,
+}
+
+impl Workspace {
+    /// One-line summary for prompts
+    pub fn summary(&self) -> String {
+        let mut summary = format!("Active project: {} ({})", self.name, self.root);
+        if !self.languages.is_empty() {
+            summary.push_str(&format!("; languages: {}", self.languages.join(", ")));
+        }
+        if !self.build_systems.is_empty() {
+            summary.push_str(&format!("; build: {}", self.build_systems.join(", ")));
+        }
+        if !self.recent_files.is_empty() {
+            let files: Vec<&str> = self.recent_files.iter().take(5).map(|f| f.path.as_str()).collect();
+            summary.push_str(&format!("; recently edited: {}", files.join(", ")));
+        }
+        summary
+    }
+}
+
+/// Active project detection and file tool scoping
+pub struct WorkspaceService {
+    db: Arc<Mutex<Database>>,
+    active_window: ActiveWindowService,
+    config: RwLock<WorkspaceConfig>,
+    pinned: RwLock<Option<PathBuf>>,
+    cached: Mutex<Option<(Workspace, Instant)>>,
+}
+
+impl WorkspaceService {
+    pub fn new(db: Arc<Mutex<Database>>) -> Result<Self> {
+        let active_window = ActiveWindowService::new()?;
+        let config = load_config(&db).unwrap_or_else(|e| {
+            log::warn!("Failed to load workspace config, using defaults: {}", e);
+            WorkspaceConfig::default()
+        });
+
+        log::info!("✓ Workspace Service initialized ({} configured roots)", config.roots.len());
+        Ok(Self {
+            db,
+            active_window,
+            config: RwLock::new(config),
+            pinned: RwLock::new(None),
+            cached: Mutex::new(None),
+        })
+    }
+
+    /// Register this service for the file tools
+    pub fn install_global(self: &Arc<Self>) {
+        if GLOBAL_WORKSPACE.set(Arc::clone(self)).is_err() {
+            log::warn!("Workspace service already installed, ignoring");
+        }
+    }
+
+    pub fn get_config(&self) -> WorkspaceConfig {
+        self.config.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Validate, persist and apply new settings
+    pub fn update_config(&self, config: WorkspaceConfig) -> Result<()> {
+        for root in &config.roots {
+            if !Path::new(root).is_dir() {
+                return Err(anyhow!("Workspace root is not a directory: {}", root));
+            }
+        }
+
+        let json = serde_json::to_string(&config)?;
+        {
+            let db = self.db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+            db.conn().execute(
+                "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+                rusqlite::params![CONFIG_KEY, json, chrono::Utc::now().timestamp()],
+            )?;
+        }
+
+        *self.config.write().unwrap_or_else(|e| e.into_inner()) = config;
+        self.invalidate();
+        log::info!("Workspace config updated");
+        Ok(())
+    }
+
+    /// Pin a project root (any path inside it works), or `None` to go back to detection
+    pub fn set_active(&self, path: Option<&str>) -> Result<Option<Workspace>> {
+        let root = match path {
+            Some(path) => {
+                let path = Path::new(path);
+                if !path.exists() {
+                    return Err(anyhow!("Path does not exist: {}", path.display()));
+                }
+                Some(project_root(path).unwrap_or_else(|| path.to_path_buf()))
+            }
+            None => None,
+        };
+        *self.pinned.write().unwrap_or_else(|e| e.into_inner()) = root;
+        self.invalidate();
+        self.current()
+    }
+
+    fn invalidate(&self) {
+        *self.cached.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    /// Active workspace, re-detected at most every `REFRESH_INTERVAL`
+    ///
+    /// Does blocking file system work; call from `spawn_blocking` in async code.
+    pub fn current(&self) -> Result<Option<Workspace>> {
+        let Some((root, source)) = self.detect_root() else {
+            self.invalidate();
+            return Ok(None);
+        };
+
+        if let Some((workspace, at)) = self.cached.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            if Path::new(&workspace.root) == root && at.elapsed() < REFRESH_INTERVAL {
+                return Ok(Some(workspace.clone()));
+            }
+        }
+
+        let workspace = self.describe(&root, source)?;
+        *self.cached.lock().unwrap_or_else(|e| e.into_inner()) = Some((workspace.clone(), Instant::now()));
+        Ok(Some(workspace))
+    }
+
+    /// Cached root only, without touching the file system or the window
+    fn cached_root(&self) -> Option<PathBuf> {
+        if let Some(pinned) = self.pinned.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            return Some(pinned.clone());
+        }
+        self.cached
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map(|(workspace, _)| PathBuf::from(&workspace.root))
+    }
+
+    fn detect_root(&self) -> Option<(PathBuf, WorkspaceSource)> {
+        if let Some(pinned) = self.pinned.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            return Some((pinned.clone(), WorkspaceSource::Pinned));
+        }
+
+        let config = self.get_config();
+        if config.follow_active_window {
+            match self.active_window.get_active_window() {
+                Ok(window) => {
+                    if let Some(root) = root_from_title(&window.title, &config.roots, &dev_folders()) {
+                        return Some((root, WorkspaceSource::ActiveWindow));
+                    }
+                }
+                Err(e) => log::debug!("No active window for workspace detection: {}", e),
+            }
+        }
+
+        match config.roots.as_slice() {
+            [only] => Some((PathBuf::from(only), WorkspaceSource::Configured)),
+            _ => None,
+        }
+    }
+
+    fn describe(&self, root: &Path, source: WorkspaceSource) -> Result<Workspace> {
+        let root_str = root.to_string_lossy().to_string();
+        let info = FileService::detect_workspace(&root_str)?;
+        let scan = scan_files(root, self.get_config().recent_file_limit);
+
+        Ok(Workspace {
+            root: root_str,
+            name: info.project_name,
+            source,
+            workspace_types: info.workspace_type,
+            languages: scan.languages,
+            build_systems: build_systems(root),
+            recent_files: scan.recent_files,
+        })
+    }
+
+    /// Resolve a file tool path against the active workspace
+    pub fn resolve_path(&self, path: &str) -> Result<String> {
+        let root = match self.cached_root() {
+            Some(root) => Some(root),
+            None => self.current().ok().flatten().map(|w| PathBuf::from(w.root)),
+        };
+        resolve_in_workspace(path, root.as_deref(), self.get_config().scope_file_tools)
+    }
+}
+
+/// Resolve `path` for a file tool; passthrough until a workspace service is installed
+pub fn resolve_tool_path(path: &str) -> Result<String> {
+    match GLOBAL_WORKSPACE.get() {
+        Some(workspace) => workspace.resolve_path(path),
+        None => Ok(path.to_string()),
+    }
+}
+
+fn resolve_in_workspace(path: &str, root: Option<&Path>, scoped: bool) -> Result<String> {
+    let Some(root) = root else {
+        return Ok(path.to_string());
+    };
+    let candidate = Path::new(path);
+    let resolved = if candidate.is_absolute() {
+        candidate.to_path_buf()
+    } else {
+        root.join(candidate)
+    };
+
+    if scoped && !normalize(&resolved).starts_with(normalize(root)) {
+        return Err(anyhow!(
+            "{} is outside the active workspace ({}); switch workspace or turn off workspace scoping",
+            path,
+            root.display()
+        ));
+    }
+    Ok(resolved.to_string_lossy().to_string())
+}
+
+/// Lexical normalization (`.` and `..`), so not-yet-existing paths can be checked
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+fn load_config(db: &Arc<Mutex<Database>>) -> Result<WorkspaceConfig> {
+    let db = db.lock().map_err(|e| anyhow!("DB lock error: {}", e))?;
+    let json: Option<String> = db
+        .conn()
+        .query_row("SELECT value FROM user_preferences WHERE key = ?1", [CONFIG_KEY], |row| row.get(0))
+        .ok();
+    match json {
+        Some(json) => Ok(serde_json::from_str(&json)?),
+        None => Ok(WorkspaceConfig::default()),
+    }
+}
+
+fn dev_folders() -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    DEV_FOLDERS.iter().map(|folder| home.join(folder)).filter(|dir| dir.is_dir()).collect()
+}
+
+/// Project containing `path`: the enclosing git repository, else the nearest
+/// directory with a project marker (so `repo/src-tauri/` resolves to `repo/`)
+fn project_root(path: &Path) -> Option<PathBuf> {
+    let start = if path.is_file() { path.parent()? } else { path };
+    let home = dirs::home_dir();
+    let mut nearest = None;
+    for dir in start.ancestors() {
+        if home.as_deref() == Some(dir) {
+            break;
+        }
+        if dir.join(".git").exists() {
+            return Some(dir.to_path_buf());
+        }
+        if nearest.is_none() && ROOT_MARKERS.iter().any(|marker| dir.join(marker).exists()) {
+            nearest = Some(dir.to_path_buf());
+        }
+    }
+    nearest
+}
+
+/// Project named in a window title such as `main.rs — Garden_of_Eden_V3 — Visual Studio Code`
+///
+/// Title parts are matched against configured roots, projects inside them and
+/// projects inside `search_dirs`; absolute paths in the title (terminals) are
+/// walked up to their project root.
+fn root_from_title(title: &str, roots: &[String], search_dirs: &[PathBuf]) -> Option<PathBuf> {
+    let mut parts = vec![title.trim().to_string()];
+    for separator in TITLE_SEPARATORS {
+        parts = parts
+            .iter()
+            .flat_map(|part| part.split(separator).map(|p| p.trim().to_string()).collect::<Vec<_>>())
+            .collect();
+    }
+    parts.retain(|part| !part.is_empty());
+
+    // Paths first: terminals show the working directory
+    for part in &parts {
+        let expanded = match (part.strip_prefix("~/"), dirs::home_dir()) {
+            (Some(rest), Some(home)) => home.join(rest),
+            _ => PathBuf::from(part),
+        };
+        if expanded.is_absolute() && expanded.exists() {
+            if let Some(root) = project_root(&expanded) {
+                return Some(root);
+            }
+        }
+    }
+
+    let roots: Vec<PathBuf> = roots.iter().map(PathBuf::from).collect();
+    for part in &parts {
+        let wanted = part.trim_matches(['[', ']']).to_lowercase();
+        for root in &roots {
+            if root.file_name().is_some_and(|name| name.to_string_lossy().to_lowercase() == wanted) {
+                return Some(root.clone());
+            }
+        }
+        for dir in roots.iter().chain(search_dirs) {
+            if let Some(found) = child_named(dir, &wanted) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+fn child_named(dir: &Path, wanted_lowercase: &str) -> Option<PathBuf> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.is_dir()
+                && path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().to_lowercase() == wanted_lowercase)
+        })
+}
+
+/// Build tools declared at the root or one level down (monorepos, `src-tauri/`)
+fn build_systems(root: &Path) -> Vec<String> {
+    let mut dirs = vec![root.to_path_buf()];
+    if let Ok(entries) = fs::read_dir(root) {
+        dirs.extend(
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir() && !is_ignored(path)),
+        );
+    }
+
+    let mut systems: Vec<String> = Vec::new();
+    let mut add = |name: &str| {
+        if !systems.iter().any(|s| s == name) {
+            systems.push(name.to_string());
+        }
+    };
+    for dir in &dirs {
+        let has = |file: &str| dir.join(file).exists();
+        if has("Cargo.toml") {
+            add("Cargo");
+        }
+        if has("package.json") {
+            if has("pnpm-lock.yaml") {
+                add("pnpm");
+            } else if has("yarn.lock") {
+                add("Yarn");
+            } else if has("bun.lockb") {
+                add("Bun");
+            } else {
+                add("npm");
+            }
+        }
+        if has("pyproject.toml") {
+            if has("poetry.lock") {
+                add("Poetry");
+            } else if has("uv.lock") {
+                add("uv");
+            } else {
+                add("pyproject");
+            }
+        } else if has("requirements.txt") || has("setup.py") {
+            add("pip");
+        }
+        if has("go.mod") {
+            add("Go modules");
+        }
+        if has("pom.xml") {
+            add("Maven");
+        }
+        if has("build.gradle") || has("build.gradle.kts") {
+            add("Gradle");
+        }
+        if has("CMakeLists.txt") {
+            add("CMake");
+        } else if has("Makefile") {
+            add("Make");
+        }
+        if has("Package.swift") {
+            add("SwiftPM");
+        }
+        if has("Gemfile") {
+            add("Bundler");
+        }
+        if has("composer.json") {
+            add("Composer");
+        }
+    }
+    systems
+}
+
+fn is_ignored(path: &Path) -> bool {
+    path.file_name()
+        .map(|name| name.to_string_lossy())
+        .is_some_and(|name| IGNORED_DIRS.contains(&name.as_ref()))
+}
+
+fn language_for(extension: &str) -> Option<&'static str> {
+    Some(match extension {
+        "rs" => "Rust",
+        "ts" | "tsx" | "mts" => "TypeScript",
+        "js" | "jsx" | "mjs" | "cjs" => "JavaScript",
+        "py" => "Python",
+        "go" => "Go",
+        "java" => "Java",
+        "kt" | "kts" => "Kotlin",
+        "swift" => "Swift",
+        "c" | "h" => "C",
+        "cpp" | "cc" | "cxx" | "hpp" => "C++",
+        "cs" => "C#",
+        "rb" => "Ruby",
+        "php" => "PHP",
+        "scala" => "Scala",
+        "dart" => "Dart",
+        "lua" => "Lua",
+        "sh" | "bash" | "zsh" => "Shell",
+        "vue" => "Vue",
+        "svelte" => "Svelte",
+        _ => return None,
+    })
+}
+
+struct Scan {
+    languages: Vec<String>,
+    recent_files: Vec<RecentFile>,
+}
+
+/// Language stats and most recently modified files, bounded by `MAX_SCAN_FILES`
+fn scan_files(root: &Path, recent_limit: usize) -> Scan {
+    let mut counts: HashMap<&'static str, usize> = HashMap::new();
+    let mut files: Vec<(SystemTime, PathBuf)> = Vec::new();
+    let mut stack = vec![(root.to_path_buf(), 0usize)];
+    let mut seen = 0;
+
+    while let Some((dir, depth)) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            if seen >= MAX_SCAN_FILES {
+                break;
+            }
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let hidden = entry.file_name().to_string_lossy().starts_with('.');
+                if depth < MAX_SCAN_DEPTH && !hidden && !is_ignored(&path) {
+                    stack.push((path, depth + 1));
+                }
+            } else if file_type.is_file() {
+                seen += 1;
+                let language = path
+                    .extension()
+                    .and_then(|ext| language_for(&ext.to_string_lossy().to_lowercase()));
+                if let Some(language) = language {
+                    *counts.entry(language).or_default() += 1;
+                }
+                if let Ok(modified) = entry.metadata().and_then(|m| m.modified()) {
+                    files.push((modified, path));
+                }
+            }
+        }
+    }
+
+    let mut languages: Vec<(&str, usize)> = counts.into_iter().collect();
+    languages.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    files.sort_by_key(|file| std::cmp::Reverse(file.0));
+    let recent_files = files
+        .into_iter()
+        .take(recent_limit)
+        .map(|(modified, path)| RecentFile {
+            path: path.strip_prefix(root).unwrap_or(&path).to_string_lossy().to_string(),
+            modified: chrono::DateTime::<chrono::Utc>::from(modified).timestamp(),
+        })
+        .collect();
+
+    Scan {
+        languages: languages.into_iter().take(3).map(|(name, _)| name.to_string()).collect(),
+        recent_files,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("garden");
+        fs::create_dir_all(project.join("src-tauri/src")).unwrap();
+        fs::create_dir_all(project.join("src")).unwrap();
+        fs::create_dir_all(project.join("node_modules/left-pad")).unwrap();
+        fs::create_dir(project.join(".git")).unwrap();
+        fs::write(project.join("package.json"), "{}").unwrap();
+        fs::write(project.join("src-tauri/Cargo.toml"), "[package]").unwrap();
+        fs::write(project.join("src-tauri/src/main.rs"), "fn main() {}").unwrap();
+        fs::write(project.join("src-tauri/src/lib.rs"), "").unwrap();
+        fs::write(project.join("src/App.tsx"), "").unwrap();
+        fs::write(project.join("node_modules/left-pad/index.js"), "").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_describes_project() {
+        let dir = sample_project();
+        let root = dir.path().join("garden");
+        let db = Arc::new(Mutex::new(Database::new_test_db().unwrap()));
+        let service = WorkspaceService::new(db).unwrap();
+
+        let workspace = service
+            .set_active(Some(&root.join("src-tauri/src/main.rs").to_string_lossy()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(workspace.name, "garden");
+        assert_eq!(workspace.source, WorkspaceSource::Pinned);
+        assert_eq!(workspace.languages, vec!["Rust", "TypeScript"]);
+        assert_eq!(workspace.build_systems, vec!["npm", "Cargo"]);
+        assert!(workspace.workspace_types.contains(&WorkspaceType::Git));
+        assert!(workspace.recent_files.iter().all(|f| !f.path.contains("node_modules")));
+        assert!(workspace.summary().contains("Active project: garden"));
+    }
+
+    #[test]
+    fn test_root_from_window_title() {
+        let dir = sample_project();
+        let roots = vec![dir.path().to_string_lossy().to_string()];
+
+        let vscode = root_from_title("main.rs — garden — Visual Studio Code", &roots, &[]);
+        assert_eq!(vscode, Some(dir.path().join("garden")));
+
+        let terminal = format!("zsh: {}", dir.path().join("garden/src").display());
+        assert_eq!(root_from_title(&terminal, &[], &[]), Some(dir.path().join("garden")));
+
+        assert_eq!(root_from_title("Inbox - Mail", &roots, &[]), None);
+    }
+
+    #[test]
+    fn test_file_tool_paths_are_scoped() {
+        let root = Path::new("/work/garden");
+        assert_eq!(resolve_in_workspace("src/main.rs", Some(root), true).unwrap(), "/work/garden/src/main.rs");
+        assert!(resolve_in_workspace("/etc/hosts", Some(root), true).is_err());
+        assert!(resolve_in_workspace("../other/secret.txt", Some(root), true).is_err());
+        assert_eq!(resolve_in_workspace("/etc/hosts", Some(root), false).unwrap(), "/etc/hosts");
+        assert_eq!(resolve_in_workspace("notes.txt", None, true).unwrap(), "notes.txt");
+    }
+}
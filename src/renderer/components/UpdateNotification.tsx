@@ -10,6 +10,7 @@ import { Button } from './ui/button';
 import { Progress } from './ui/progress';
 import { X, Download, RefreshCw, CheckCircle, AlertCircle } from 'lucide-react';
 import { cn } from '../lib/utils';
+import { getErrorMessage } from '@shared/types/error.types';
 
 interface UpdateCheckResult {
   available: boolean;
@@ -96,7 +97,7 @@ export function UpdateNotification({
       }
     } catch (error) {
       console.error('Failed to check for updates:', error);
-      setErrorMessage(error as string);
+      setErrorMessage(getErrorMessage(error));
       setStatus('error');
       setTimeout(() => setIsVisible(false), 5000);
     }
@@ -113,7 +114,7 @@ export function UpdateNotification({
       // The app will restart automatically after successful installation
     } catch (error) {
       console.error('Failed to install update:', error);
-      setErrorMessage(error as string);
+      setErrorMessage(getErrorMessage(error));
       setStatus('error');
     }
   };
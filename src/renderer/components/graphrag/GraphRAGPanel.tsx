@@ -6,6 +6,7 @@
 
 import React, { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { getErrorMessage } from '@shared/types/error.types';
 
 interface GraphEntity {
   entity_id: string;
@@ -66,7 +67,7 @@ export const GraphRAGPanel: React.FC = () => {
       );
       setResults(response.results);
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(getErrorMessage(err));
     } finally {
       setLoading(false);
     }
@@ -9,6 +9,7 @@ import { invoke } from '@tauri-apps/api/core';
 import { Button } from '../ui/button';
 import { Card } from '../ui/card';
 import { cn } from '../../lib/utils';
+import { getErrorMessage } from '@shared/types/error.types';
 
 export interface LearningStyleProfile {
   user_id: string;
@@ -71,7 +72,7 @@ export function LearningStylePanel() {
       setManualAttentionSpan(result.attention_span_minutes);
     } catch (err) {
       console.error('Failed to load profile:', err);
-      setError(err instanceof Error ? err.message : String(err));
+      setError(getErrorMessage(err));
     } finally {
       setIsLoading(false);
     }
@@ -95,7 +96,7 @@ export function LearningStylePanel() {
       await loadProfile();
       setShowManualConfig(false);
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(getErrorMessage(err));
     } finally {
       setIsLoading(false);
     }
@@ -111,7 +112,7 @@ export function LearningStylePanel() {
       });
       setProfile(updated);
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(getErrorMessage(err));
     } finally {
       setIsLoading(false);
     }
@@ -5,6 +5,7 @@
 
 import { useState, useEffect, useRef } from 'react';
 import type { RequiredModels, ModelDownloadState, DownloadProgress } from '../../lib/tauri-api';
+import { getErrorMessage } from '@shared/types/error.types';
 
 interface ModelDownloaderProps {
   requiredModels: RequiredModels;
@@ -74,7 +75,7 @@ export default function ModelDownloader({ requiredModels, onComplete, onBack }:
       startDownloads(llmExists, llavaExists);
     } catch (err) {
       console.error('[ModelDownloader] Error in checkOllamaAndModels:', err);
-      setError(err instanceof Error ? err.message : String(err));
+      setError(getErrorMessage(err));
       setCurrentPhase('error');
     }
   };
@@ -95,7 +96,7 @@ export default function ModelDownloader({ requiredModels, onComplete, onBack }:
       }, 2000);
     } catch (err) {
       console.error('Failed to install Ollama:', err);
-      setError(err instanceof Error ? err.message : String(err));
+      setError(getErrorMessage(err));
       setCurrentPhase('error');
       setIsInstallingOllama(false);
     }
@@ -175,7 +176,7 @@ export default function ModelDownloader({ requiredModels, onComplete, onBack }:
       }, 1000);
     } catch (err) {
       console.error('[ModelDownloader] Error in startDownloads:', err);
-      setError(err instanceof Error ? err.message : String(err));
+      setError(getErrorMessage(err));
       setCurrentPhase('error');
     }
   };
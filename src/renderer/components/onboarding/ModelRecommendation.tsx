@@ -5,6 +5,7 @@
 
 import { useState, useEffect } from 'react';
 import type { SystemSpecs, ModelOption, RequiredModels } from '../../lib/tauri-api';
+import { getErrorMessage } from '@shared/types/error.types';
 
 interface ModelRecommendationProps {
   specs: SystemSpecs;
@@ -58,7 +59,7 @@ export default function ModelRecommendation({
       setIsLoading(false);
     } catch (err) {
       console.error('Failed to load models:', err);
-      setError(err instanceof Error ? err.message : String(err));
+      setError(getErrorMessage(err));
       setIsLoading(false);
     }
   };
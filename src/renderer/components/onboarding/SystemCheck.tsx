@@ -5,6 +5,7 @@
 
 import { useState, useEffect } from 'react';
 import type { SystemSpecs } from '../../lib/tauri-api';
+import { getErrorMessage } from '@shared/types/error.types';
 
 interface SystemCheckProps {
   onComplete: (specs: SystemSpecs) => void;
@@ -34,7 +35,7 @@ export default function SystemCheck({ onComplete }: SystemCheckProps) {
       }, 1500);
     } catch (err) {
       console.error('Failed to detect system specs:', err);
-      setError(err instanceof Error ? err.message : String(err));
+      setError(getErrorMessage(err));
       setIsDetecting(false);
     }
   };
@@ -6,6 +6,7 @@
 
 import React, { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { getErrorMessage } from '@shared/types/error.types';
 
 interface PlanStep {
   step_number: number;
@@ -87,7 +88,7 @@ export const PlannerPanel: React.FC = () => {
       setCurrentPlan(plan);
       setView('review');
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(getErrorMessage(err));
     } finally {
       setLoading(false);
     }
@@ -112,7 +113,7 @@ export const PlannerPanel: React.FC = () => {
       setView('execution');
       await loadStats();
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(getErrorMessage(err));
     } finally {
       setLoading(false);
     }
@@ -126,7 +127,7 @@ export const PlannerPanel: React.FC = () => {
       setCurrentPlan(null);
       setView('input');
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(getErrorMessage(err));
     }
   };
 
@@ -6,6 +6,7 @@
 
 import React, { useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { getErrorMessage } from '@shared/types/error.types';
 
 interface ReActStep {
   type: string;
@@ -60,7 +61,7 @@ export const ReActPanel: React.FC = () => {
       const result = await invoke<ReActExecution>('react_execute', { query });
       setExecution(result);
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(getErrorMessage(err));
     } finally {
       setLoading(false);
     }
@@ -11,6 +11,7 @@ import { AlertTriangle, Download, Trash2, RefreshCw, FileText, BarChart3, Trendi
 import { CrashReportDialog, useCrashReportDialog, CrashReport } from '../CrashReportDialog';
 import { toast } from '../../stores/toast.store';
 import { Skeleton } from '../ui/skeleton';
+import { getErrorMessage } from '@shared/types/error.types';
 
 interface CrashReportingSettings {
   enabled: boolean;
@@ -114,7 +115,7 @@ export function CrashReportsPanel() {
       setTimeout(loadCrashReports, 500);
     } catch (error) {
       console.error('Failed to create test crash report:', error);
-      toast.error('Failed to create test crash report', error as string);
+      toast.error('Failed to create test crash report', getErrorMessage(error));
     }
   };
 
@@ -9,6 +9,7 @@ import { Button } from '../ui/button';
 import { Switch } from '../ui/switch';
 import { RefreshCw, Download, CheckCircle, Info, Clock, AlertCircle } from 'lucide-react';
 import { toast } from '../../stores/toast.store';
+import { getErrorMessage } from '@shared/types/error.types';
 
 interface UpdateCheckResult {
   available: boolean;
@@ -83,7 +84,7 @@ export function UpdateSettingsPanel() {
       toast.success('Update Channel Changed', `Now on ${newChannel} channel`);
     } catch (error) {
       console.error('Failed to set update channel:', error);
-      toast.error('Channel Change Failed', error as string);
+      toast.error('Channel Change Failed', getErrorMessage(error));
     }
   };
 
@@ -96,7 +97,7 @@ export function UpdateSettingsPanel() {
       toast.success('Beta Channel Enabled', 'You will now receive pre-release updates');
     } catch (error) {
       console.error('Failed to enable beta channel:', error);
-      toast.error('Channel Change Failed', error as string);
+      toast.error('Channel Change Failed', getErrorMessage(error));
       setShowBetaWarning(false);
     }
   };
@@ -132,7 +133,7 @@ export function UpdateSettingsPanel() {
       toast.success('Settings Updated', 'Update schedule settings saved');
     } catch (error) {
       console.error('Failed to update schedule settings:', error);
-      toast.error('Update Failed', error as string);
+      toast.error('Update Failed', getErrorMessage(error));
     }
   };
 
@@ -190,7 +191,7 @@ export function UpdateSettingsPanel() {
       }
     } catch (error) {
       console.error('Failed to check for updates:', error);
-      toast.error('Update Check Failed', error as string);
+      toast.error('Update Check Failed', getErrorMessage(error));
     } finally {
       setIsChecking(false);
     }
@@ -204,7 +205,7 @@ export function UpdateSettingsPanel() {
       toast.success('Update Installing', 'App will restart when complete');
     } catch (error) {
       console.error('Failed to install update:', error);
-      toast.error('Update Failed', error as string);
+      toast.error('Update Failed', getErrorMessage(error));
     }
   };
 
@@ -11,6 +11,7 @@ import { Card } from '../ui/card';
 import { Input } from '../ui/input';
 import { TaskCard } from './TaskCard';
 import { ExecutionTimeline } from './ExecutionTimeline';
+import { getErrorMessage } from '@shared/types/error.types';
 
 export interface Task {
   id: string;
@@ -27,6 +28,8 @@ export interface Task {
   started_at?: number | null;
   completed_at?: number | null;
   tags: string[];
+  due_at?: number | null;
+  recurrence_id?: string | null;
 }
 
 export interface TaskBreakdown {
@@ -73,7 +76,7 @@ export function TaskPlannerPanel() {
         await createTasksFromBreakdown(result);
       }
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(getErrorMessage(err));
     } finally {
       setIsLoading(false);
     }
@@ -11,6 +11,7 @@ import { listen } from '@tauri-apps/api/event';
 import type { Message } from './useChatState';
 import type { ChatInputHandle } from '../components/chat/ChatInput';
 import type { ConversationHistoryHandle } from '../components/sidebar/ConversationHistory';
+import { isAppError } from '../../shared/types/error.types';
 
 interface UseChatHandlersProps {
   messages: Message[];
@@ -148,6 +149,9 @@ export function useChatHandlers({
           } else {
             errorContent = `오류 발생:\n\n${error.message}\n\n문제가 계속되면 개발자에게 문의하세요.`;
           }
+        } else if (isAppError(error)) {
+          // Structured backend error with a recovery hint
+          errorContent = `${error.message}\n\n${error.recovery}`;
         } else {
           errorContent = `알 수 없는 오류:\n\n${String(error)}`;
         }
@@ -32,6 +32,7 @@ import { ToolCall } from '../../shared/types/tool.types';
 import { ToolCallIndicator, ToolResultCard, ToolHistory } from '../components/tool';
 import type { ToolCallRecord } from '../../shared/types/tool-history.types';
 import { getToolDisplayName } from '../../shared/types/tool-history.types';
+import { isAppError } from '../../shared/types/error.types';
 
 // Backend ToolCallRecord type (snake_case from Rust)
 interface BackendToolCallRecord {
@@ -465,6 +466,9 @@ export function Chat({ onOpenSettings }: ChatProps) {
           // Show the actual error for debugging in production
           errorContent = `❌ 오류 발생:\n\n${error.message}\n\n문제가 계속되면 개발자에게 문의하세요.`;
         }
+      } else if (isAppError(error)) {
+        // Structured backend error with a recovery hint
+        errorContent = `❌ ${error.message}\n\n${error.recovery}`;
       } else {
         errorContent = `❌ 알 수 없는 오류:\n\n${String(error)}`;
       }
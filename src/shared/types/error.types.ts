@@ -6,61 +6,66 @@
  * - ErrorCode enum matching Rust backend error codes
  * - ActionableError for user-friendly error display
  * - Utility functions for error conversion and parsing
+ *
+ * v3.9.0: every Tauri command rejects with an `AppError` object
+ * (not a string), so use `getErrorMessage` instead of `String(error)`.
  */
 
 // ============================================================================
 // ERROR CODES (matching Rust backend)
 // ============================================================================
 
-/** Error codes matching Rust ErrorCode enum */
+/** Error codes matching Rust ErrorCode enum (serialized by name) */
 export enum ErrorCode {
   // Connection errors (1xxx)
-  OLLAMA_NOT_RUNNING = 1001,
-  OLLAMA_CONNECTION_FAILED = 1002,
-  NETWORK_TIMEOUT = 1003,
-  NETWORK_UNAVAILABLE = 1004,
+  OLLAMA_NOT_RUNNING = 'OLLAMA_NOT_RUNNING',
+  OLLAMA_CONNECTION_FAILED = 'OLLAMA_CONNECTION_FAILED',
+  NETWORK_TIMEOUT = 'NETWORK_TIMEOUT',
+  NETWORK_UNAVAILABLE = 'NETWORK_UNAVAILABLE',
 
   // Model errors (2xxx)
-  MODEL_NOT_FOUND = 2001,
-  MODEL_DOWNLOAD_FAILED = 2002,
-  MODEL_INCOMPATIBLE = 2003,
-  MODEL_LOAD_FAILED = 2004,
-  INSUFFICIENT_VRAM = 2005,
+  MODEL_NOT_FOUND = 'MODEL_NOT_FOUND',
+  MODEL_DOWNLOAD_FAILED = 'MODEL_DOWNLOAD_FAILED',
+  MODEL_INCOMPATIBLE = 'MODEL_INCOMPATIBLE',
+  MODEL_LOAD_FAILED = 'MODEL_LOAD_FAILED',
+  INSUFFICIENT_VRAM = 'INSUFFICIENT_VRAM',
 
   // Database errors (3xxx)
-  DATABASE_CONNECTION_FAILED = 3001,
-  DATABASE_QUERY_FAILED = 3002,
-  DATABASE_CORRUPTED = 3003,
-  DATABASE_LOCKED = 3004,
-  MIGRATION_FAILED = 3005,
+  DATABASE_CONNECTION_FAILED = 'DATABASE_CONNECTION_FAILED',
+  DATABASE_QUERY_FAILED = 'DATABASE_QUERY_FAILED',
+  DATABASE_CORRUPTED = 'DATABASE_CORRUPTED',
+  DATABASE_LOCKED = 'DATABASE_LOCKED',
+  MIGRATION_FAILED = 'MIGRATION_FAILED',
 
   // File system errors (4xxx)
-  FILE_NOT_FOUND = 4001,
-  FILE_READ_FAILED = 4002,
-  FILE_WRITE_FAILED = 4003,
-  PERMISSION_DENIED = 4004,
-  DISK_FULL = 4005,
+  FILE_NOT_FOUND = 'FILE_NOT_FOUND',
+  FILE_READ_FAILED = 'FILE_READ_FAILED',
+  FILE_WRITE_FAILED = 'FILE_WRITE_FAILED',
+  PERMISSION_DENIED = 'PERMISSION_DENIED',
+  DISK_FULL = 'DISK_FULL',
 
   // AI/Inference errors (5xxx)
-  INFERENCE_FAILED = 5001,
-  CONTEXT_TOO_LONG = 5002,
-  TOKEN_LIMIT_EXCEEDED = 5003,
-  EMBEDDING_FAILED = 5004,
-  RAG_RETRIEVAL_FAILED = 5005,
+  INFERENCE_FAILED = 'INFERENCE_FAILED',
+  CONTEXT_TOO_LONG = 'CONTEXT_TOO_LONG',
+  TOKEN_LIMIT_EXCEEDED = 'TOKEN_LIMIT_EXCEEDED',
+  EMBEDDING_FAILED = 'EMBEDDING_FAILED',
+  RAG_RETRIEVAL_FAILED = 'RAG_RETRIEVAL_FAILED',
 
   // User input errors (6xxx)
-  INVALID_INPUT = 6001,
-  MISSING_REQUIRED_FIELD = 6002,
-  VALIDATION_FAILED = 6003,
+  INVALID_INPUT = 'INVALID_INPUT',
+  MISSING_REQUIRED_FIELD = 'MISSING_REQUIRED_FIELD',
+  VALIDATION_FAILED = 'VALIDATION_FAILED',
+  NOT_FOUND = 'NOT_FOUND',
 
   // Authentication errors (7xxx)
-  OAUTH_FAILED = 7001,
-  TOKEN_EXPIRED = 7002,
-  UNAUTHORIZED = 7003,
+  OAUTH_FAILED = 'OAUTH_FAILED',
+  TOKEN_EXPIRED = 'TOKEN_EXPIRED',
+  UNAUTHORIZED = 'UNAUTHORIZED',
 
   // System errors (9xxx)
-  INTERNAL_ERROR = 9001,
-  UNKNOWN = 9999,
+  INTERNAL_ERROR = 'INTERNAL_ERROR',
+  SERVICE_UNAVAILABLE = 'SERVICE_UNAVAILABLE',
+  UNKNOWN = 'UNKNOWN',
 }
 
 // ============================================================================
@@ -79,6 +84,8 @@ export interface AppError {
   details?: string;
   /** Error timestamp (Unix milliseconds) */
   timestamp: number;
+  /** Whether retrying the same call can succeed */
+  retryable: boolean;
 }
 
 /** Check if an error is an AppError from backend */
@@ -114,6 +121,15 @@ export function parseBackendError(error: unknown): AppError | null {
   return null;
 }
 
+/** Human-readable message for any error thrown by `invoke` or the UI */
+export function getErrorMessage(error: unknown): string {
+  const appError = parseBackendError(error);
+  if (appError) {
+    return appError.message;
+  }
+  return error instanceof Error ? error.message : String(error);
+}
+
 // ============================================================================
 // ACTIONABLE ERROR (UI-friendly format)
 // ============================================================================
@@ -139,6 +155,61 @@ export interface ActionableError {
   canDismiss?: boolean;
 }
 
+const CATEGORY_TITLES: Record<ErrorCategory, string> = {
+  network: '네트워크 연결 오류',
+  file: '파일 접근 오류',
+  permission: '권한 오류',
+  validation: '입력 오류',
+  'ai-model': 'AI 모델 오류',
+  database: '데이터베이스 오류',
+  unknown: '알 수 없는 오류',
+};
+
+/** Category of a backend error code */
+export function categoryForCode(code: ErrorCode): ErrorCategory {
+  switch (code) {
+    case ErrorCode.OLLAMA_NOT_RUNNING:
+    case ErrorCode.OLLAMA_CONNECTION_FAILED:
+    case ErrorCode.NETWORK_TIMEOUT:
+    case ErrorCode.NETWORK_UNAVAILABLE:
+    case ErrorCode.OAUTH_FAILED:
+      return 'network';
+    case ErrorCode.MODEL_NOT_FOUND:
+    case ErrorCode.MODEL_DOWNLOAD_FAILED:
+    case ErrorCode.MODEL_INCOMPATIBLE:
+    case ErrorCode.MODEL_LOAD_FAILED:
+    case ErrorCode.INSUFFICIENT_VRAM:
+    case ErrorCode.INFERENCE_FAILED:
+    case ErrorCode.CONTEXT_TOO_LONG:
+    case ErrorCode.TOKEN_LIMIT_EXCEEDED:
+    case ErrorCode.EMBEDDING_FAILED:
+    case ErrorCode.RAG_RETRIEVAL_FAILED:
+      return 'ai-model';
+    case ErrorCode.DATABASE_CONNECTION_FAILED:
+    case ErrorCode.DATABASE_QUERY_FAILED:
+    case ErrorCode.DATABASE_CORRUPTED:
+    case ErrorCode.DATABASE_LOCKED:
+    case ErrorCode.MIGRATION_FAILED:
+      return 'database';
+    case ErrorCode.FILE_NOT_FOUND:
+    case ErrorCode.FILE_READ_FAILED:
+    case ErrorCode.FILE_WRITE_FAILED:
+    case ErrorCode.DISK_FULL:
+      return 'file';
+    case ErrorCode.PERMISSION_DENIED:
+    case ErrorCode.TOKEN_EXPIRED:
+    case ErrorCode.UNAUTHORIZED:
+      return 'permission';
+    case ErrorCode.INVALID_INPUT:
+    case ErrorCode.MISSING_REQUIRED_FIELD:
+    case ErrorCode.VALIDATION_FAILED:
+    case ErrorCode.NOT_FOUND:
+      return 'validation';
+    default:
+      return 'unknown';
+  }
+}
+
 /**
  * Convert generic errors to actionable errors with user guidance
  */
@@ -146,6 +217,25 @@ export function createActionableError(
   error: unknown,
   context?: string
 ): ActionableError {
+  // Structured backend errors carry their own recovery hint
+  const appError = parseBackendError(error);
+  if (appError) {
+    const category = categoryForCode(appError.code);
+    return {
+      category,
+      title: CATEGORY_TITLES[category],
+      description: appError.message,
+      whatHappened: context
+        ? `${context} 작업 중 오류가 발생했습니다: ${appError.message}`
+        : appError.message,
+      whyItHappened: appError.details ?? appError.message,
+      howToFix: [appError.recovery],
+      technicalDetails: appError.details ? `${appError.code}: ${appError.details}` : appError.code,
+      canRetry: appError.retryable,
+      canDismiss: true,
+    };
+  }
+
   const errorMessage = error instanceof Error ? error.message : String(error);
 
   // Network errors
@@ -132,6 +132,18 @@ export const TOOL_METADATA: Record<string, ToolCallMetadata> = {
     category: 'file',
     description: 'Write content to a file',
   },
+  edit_file: {
+    icon: '📝',
+    color: 'amber',
+    category: 'file',
+    description: 'Apply a diff or search/replace edit to a file',
+  },
+  terminal_history: {
+    icon: '🖥️',
+    color: 'slate',
+    category: 'system',
+    description: 'Look up commands and output from captured terminals',
+  },
   get_system_info: {
     icon: '💻',
     color: 'purple',
@@ -155,6 +167,8 @@ export function getToolDisplayName(toolName: string): string {
     fetch_url: 'URL Fetch',
     read_file: 'Read File',
     write_file: 'Write File',
+    edit_file: 'Edit File',
+    terminal_history: 'Terminal History',
     get_system_info: 'System Info',
     calculate: 'Calculator',
   };
@@ -142,6 +142,28 @@ export const TOOL_PRIVACY_INFO: Record<string, ToolPrivacyInfo> = {
     privacyLevel: 'high',
     requiresPermission: false,
   },
+  edit_file: {
+    toolName: 'edit_file',
+    displayName: 'Edit File',
+    dataAccess: [
+      'File paths on your computer',
+      'Contents of edited files',
+      'Pre-edit backups in the app data folder',
+    ],
+    privacyLevel: 'high',
+    requiresPermission: false,
+  },
+  terminal_history: {
+    toolName: 'terminal_history',
+    displayName: 'Terminal History',
+    dataAccess: [
+      'Commands run in terminals you chose to capture',
+      'Command output and exit codes (secrets masked)',
+      'Working directories and timestamps',
+    ],
+    privacyLevel: 'high',
+    requiresPermission: false,
+  },
   get_system_info: {
     toolName: 'get_system_info',
     displayName: 'System Info',
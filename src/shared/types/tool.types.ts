@@ -56,6 +56,8 @@ export const TOOL_DISPLAY_NAMES: Record<string, string> = {
   fetch_url: 'Fetch URL',
   read_file: 'Read File',
   write_file: 'Write File',
+  edit_file: 'Edit File',
+  terminal_history: 'Terminal History',
   get_system_info: 'System Info',
   calculate: 'Calculator',
 };
@@ -66,6 +68,8 @@ export const TOOL_ICONS: Record<string, string> = {
   fetch_url: 'Globe',
   read_file: 'FileText',
   write_file: 'FilePlus',
+  edit_file: 'FileDiff',
+  terminal_history: 'Terminal',
   get_system_info: 'Cpu',
   calculate: 'Calculator',
 };
@@ -92,6 +96,16 @@ export const TOOL_COLORS: Record<string, { bg: string; text: string; border: str
     text: 'text-orange-600 dark:text-orange-400',
     border: 'border-orange-500/20',
   },
+  edit_file: {
+    bg: 'bg-amber-500/10',
+    text: 'text-amber-600 dark:text-amber-400',
+    border: 'border-amber-500/20',
+  },
+  terminal_history: {
+    bg: 'bg-slate-500/10',
+    text: 'text-slate-600 dark:text-slate-400',
+    border: 'border-slate-500/20',
+  },
   get_system_info: {
     bg: 'bg-cyan-500/10',
     text: 'text-cyan-600 dark:text-cyan-400',